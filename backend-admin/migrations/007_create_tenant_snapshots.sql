@@ -0,0 +1,50 @@
+-- Tenant snapshot catalog. Archive entries are written by backend-school to
+-- File Platform storage under snapshots/{source_tenant_id}/{id}/.
+-- Snapshots outlive their school so a deleted tenant can still be restored.
+CREATE TABLE IF NOT EXISTS tenant_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    school_id UUID REFERENCES schools(id) ON DELETE SET NULL,
+    source_tenant_id UUID NOT NULL,
+    source_subdomain VARCHAR(100) NOT NULL,
+    status VARCHAR(50) NOT NULL DEFAULT 'in_progress', -- 'in_progress', 'completed', 'failed'
+    format_version INTEGER,
+    schema_version BIGINT,
+    captured_at TIMESTAMPTZ,
+    manifest_checksum CHAR(64),
+    table_count INTEGER,
+    row_count BIGINT,
+    object_count INTEGER,
+    total_bytes BIGINT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_snapshot_status CHECK (status IN ('in_progress', 'completed', 'failed')),
+    CONSTRAINT completed_snapshot_has_manifest CHECK (
+        status <> 'completed' OR (manifest_checksum IS NOT NULL AND schema_version IS NOT NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenant_snapshots_school_id ON tenant_snapshots(school_id);
+CREATE INDEX IF NOT EXISTS idx_tenant_snapshots_created_at ON tenant_snapshots(created_at DESC);
+
+CREATE TABLE IF NOT EXISTS tenant_snapshot_restores (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    snapshot_id UUID NOT NULL REFERENCES tenant_snapshots(id) ON DELETE CASCADE,
+    target_school_id UUID REFERENCES schools(id) ON DELETE SET NULL,
+    replace_existing BOOLEAN NOT NULL DEFAULT FALSE,
+    status VARCHAR(50) NOT NULL DEFAULT 'in_progress', -- 'in_progress', 'completed', 'failed'
+    schema_version BIGINT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_restore_status CHECK (status IN ('in_progress', 'completed', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenant_snapshot_restores_snapshot_id ON tenant_snapshot_restores(snapshot_id);
+
+-- Only one restore may run against a tenant at a time.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tenant_snapshot_restores_running_target
+    ON tenant_snapshot_restores(target_school_id)
+    WHERE status = 'in_progress';
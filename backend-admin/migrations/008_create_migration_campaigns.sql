@@ -0,0 +1,58 @@
+-- Tenant schema migration campaigns. A campaign moves schools to one target
+-- migration version in waves: wave 0 holds the canary tenants, later waves
+-- hold everyone else. A wave failure pauses the whole campaign.
+CREATE TABLE IF NOT EXISTS migration_campaigns (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_version INTEGER NOT NULL,
+    wave_size INTEGER NOT NULL,
+    concurrency INTEGER NOT NULL,
+    status VARCHAR(50) NOT NULL DEFAULT 'pending', -- 'pending', 'running', 'paused', 'completed', 'cancelled'
+    current_wave INTEGER NOT NULL DEFAULT 0,
+    paused_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_campaign_status CHECK (
+        status IN ('pending', 'running', 'paused', 'completed', 'cancelled')
+    ),
+    CONSTRAINT positive_campaign_target CHECK (target_version > 0),
+    CONSTRAINT valid_campaign_wave_size CHECK (wave_size BETWEEN 1 AND 1000),
+    CONSTRAINT valid_campaign_concurrency CHECK (concurrency BETWEEN 1 AND 20)
+);
+
+-- Two open campaigns would race for the same tenants.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_migration_campaigns_single_open
+    ON migration_campaigns((TRUE))
+    WHERE status IN ('pending', 'running', 'paused');
+
+CREATE INDEX IF NOT EXISTS idx_migration_campaigns_created_at ON migration_campaigns(created_at DESC);
+
+-- One row per school and campaign; this is the per-tenant migration history.
+CREATE TABLE IF NOT EXISTS migration_campaign_tenants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    campaign_id UUID NOT NULL REFERENCES migration_campaigns(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    wave INTEGER NOT NULL,
+    status VARCHAR(50) NOT NULL DEFAULT 'pending', -- 'pending', 'migrating', 'migrated', 'failed', 'skipped'
+    from_version INTEGER,
+    applied_version INTEGER,
+    duration_ms BIGINT,
+    error TEXT,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_campaign_tenant_status CHECK (
+        status IN ('pending', 'migrating', 'migrated', 'failed', 'skipped')
+    ),
+    CONSTRAINT non_negative_campaign_wave CHECK (wave >= 0),
+    CONSTRAINT unique_campaign_school UNIQUE (campaign_id, school_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_migration_campaign_tenants_wave
+    ON migration_campaign_tenants(campaign_id, wave);
+CREATE INDEX IF NOT EXISTS idx_migration_campaign_tenants_school_id
+    ON migration_campaign_tenants(school_id, started_at DESC);
+
+COMMENT ON COLUMN schools.migration_status IS 'Migration status: pending, migrating, migrated, failed, outdated. backend-school refuses requests while migrating.';
@@ -0,0 +1,33 @@
+-- Periodic per-tenant usage samples collected from backend-school. Each row is
+-- one point of the time series; rows older than the retention window are pruned
+-- by the collector.
+CREATE TABLE IF NOT EXISTS tenant_usage_samples (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    collected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    active_users_7d BIGINT NOT NULL,
+    active_users_30d BIGINT NOT NULL,
+    student_count BIGINT NOT NULL,
+    staff_count BIGINT NOT NULL,
+    storage_bytes BIGINT NOT NULL,
+    storage_bytes_by_purpose JSONB NOT NULL DEFAULT '{}'::jsonb, -- FilePurpose code -> bytes
+    certificates_issued BIGINT NOT NULL,
+    last_login_at TIMESTAMPTZ,
+    migration_version INTEGER,
+    database_bytes BIGINT NOT NULL,
+
+    CONSTRAINT non_negative_usage CHECK (
+        active_users_7d >= 0
+        AND active_users_30d >= 0
+        AND student_count >= 0
+        AND staff_count >= 0
+        AND storage_bytes >= 0
+        AND certificates_issued >= 0
+        AND database_bytes >= 0
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_tenant_usage_samples_school_collected
+    ON tenant_usage_samples(school_id, collected_at DESC);
+CREATE INDEX IF NOT EXISTS idx_tenant_usage_samples_collected_at
+    ON tenant_usage_samples(collected_at);
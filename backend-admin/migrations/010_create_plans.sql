@@ -0,0 +1,28 @@
+-- Plan definitions limit what a school may use. NULL limits are unlimited and a
+-- NULL enabled_modules list enables every module. Schools without a plan are
+-- unrestricted.
+CREATE TABLE IF NOT EXISTS plans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(50) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    max_students BIGINT,
+    max_storage_bytes BIGINT,
+    max_certificates_per_year BIGINT,
+    enabled_modules TEXT[],
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT valid_plan_code CHECK (code ~ '^[a-z0-9][a-z0-9_-]*$'),
+    CONSTRAINT positive_plan_limits CHECK (
+        (max_students IS NULL OR max_students > 0)
+        AND (max_storage_bytes IS NULL OR max_storage_bytes > 0)
+        AND (max_certificates_per_year IS NULL OR max_certificates_per_year > 0)
+    )
+);
+
+ALTER TABLE schools
+ADD COLUMN IF NOT EXISTS plan_id UUID REFERENCES plans(id) ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS idx_schools_plan_id ON schools(plan_id);
+
+COMMENT ON COLUMN schools.plan_id IS 'Plan whose limits backend-school enforces; NULL means unrestricted';
@@ -0,0 +1,80 @@
+-- Control-plane accounts: viewer, operator and owner roles, TOTP MFA,
+-- server-side sessions, invitations and an append-only audit log.
+
+-- Existing accounts were full administrators.
+UPDATE admin_users SET role = 'owner' WHERE role IN ('super_admin', 'admin');
+UPDATE admin_users SET role = 'viewer' WHERE role NOT IN ('viewer', 'operator', 'owner');
+
+ALTER TABLE admin_users
+    ALTER COLUMN role SET DEFAULT 'viewer',
+    ADD COLUMN IF NOT EXISTS email VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS totp_secret TEXT,
+    ADD COLUMN IF NOT EXISTS totp_enabled_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS totp_last_used_step BIGINT,
+    ADD CONSTRAINT valid_admin_role CHECK (role IN ('viewer', 'operator', 'owner'));
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_admin_users_email ON admin_users(LOWER(email));
+
+COMMENT ON COLUMN admin_users.totp_secret IS 'Base32 TOTP secret; pending until totp_enabled_at is set';
+COMMENT ON COLUMN admin_users.totp_last_used_step IS 'Last accepted TOTP time step, rejects code replay';
+
+-- Every login creates a session; the JWT only carries its id, so revoking
+-- the row signs the browser out on its next request.
+CREATE TABLE IF NOT EXISTS admin_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admin_user_id UUID NOT NULL REFERENCES admin_users(id) ON DELETE CASCADE,
+    user_agent TEXT,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_sessions_user ON admin_sessions(admin_user_id, created_at DESC);
+
+-- Only the SHA-256 of an invitation token is stored.
+CREATE TABLE IF NOT EXISTS admin_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(50) NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    invited_by UUID REFERENCES admin_users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    accepted_admin_id UUID REFERENCES admin_users(id) ON DELETE SET NULL,
+    revoked_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_invitation_role CHECK (role IN ('viewer', 'operator', 'owner'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_invitations_created_at ON admin_invitations(created_at DESC);
+
+-- Actor name and target label are copied so entries stay readable after the
+-- admin or school is deleted.
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_admin_id UUID,
+    actor_name VARCHAR(255) NOT NULL,
+    action VARCHAR(100) NOT NULL,
+    target_type VARCHAR(50) NOT NULL,
+    target_id UUID,
+    target_label VARCHAR(255),
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created_at ON admin_audit_log(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_target ON admin_audit_log(target_type, target_id, created_at DESC);
+
+CREATE OR REPLACE FUNCTION reject_admin_audit_log_change() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'admin_audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS admin_audit_log_immutable ON admin_audit_log;
+CREATE TRIGGER admin_audit_log_immutable
+    BEFORE UPDATE OR DELETE OR TRUNCATE ON admin_audit_log
+    FOR EACH STATEMENT EXECUTE FUNCTION reject_admin_audit_log_change();
@@ -0,0 +1,36 @@
+-- Support sessions into a school. The school keeps the authoritative grant
+-- (approval, expiry, revocation) under the same id; this row mirrors its
+-- status and counts the requests backend-school reports for it.
+CREATE TABLE IF NOT EXISTS school_support_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    admin_user_id UUID NOT NULL,
+    admin_name VARCHAR(255) NOT NULL,
+    ticket_reference VARCHAR(100) NOT NULL,
+    reason TEXT,
+    scope VARCHAR(20) NOT NULL,
+    permissions TEXT[] NOT NULL DEFAULT '{}',
+    duration_minutes INTEGER NOT NULL,
+    requires_approval BOOLEAN NOT NULL,
+    target_username VARCHAR(255),
+    target_user_name VARCHAR(255),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    decision_note TEXT,
+    expires_at TIMESTAMPTZ,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    last_activity_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_support_scope CHECK (scope IN ('read_only', 'scoped')),
+    CONSTRAINT valid_support_status CHECK (
+        status IN ('pending', 'approved', 'rejected', 'active', 'ended')
+    ),
+    CONSTRAINT valid_support_duration CHECK (duration_minutes BETWEEN 5 AND 240)
+);
+
+CREATE INDEX IF NOT EXISTS idx_school_support_sessions_school
+    ON school_support_sessions(school_id, created_at DESC);
+
+COMMENT ON COLUMN school_support_sessions.admin_user_id IS 'Copied, not referenced, so history survives admin deletion';
@@ -0,0 +1,5 @@
+-- Restores that cloned a school into a sandbox with personal data replaced.
+-- The summary holds the counts backend-school reported, never any values.
+ALTER TABLE tenant_snapshot_restores
+    ADD COLUMN IF NOT EXISTS anonymized BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS anonymization_summary JSONB;
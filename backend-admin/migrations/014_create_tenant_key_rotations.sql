@@ -0,0 +1,33 @@
+-- Field-encryption key rotation runs. backend-school owns the keys and the
+-- per-tenant cursor; this row records who started a run and mirrors the
+-- progress backend-school reports after each slice of work.
+CREATE TABLE IF NOT EXISTS tenant_key_rotations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    admin_user_id UUID NOT NULL,
+    admin_name VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'running', -- 'running', 'completed', 'failed'
+    encryption_key_id VARCHAR(32),
+    blind_index_key_id VARCHAR(32),
+    rows_scanned BIGINT NOT NULL DEFAULT 0,
+    rows_rewritten BIGINT NOT NULL DEFAULT 0,
+    rows_failed BIGINT NOT NULL DEFAULT 0,
+    last_row_error TEXT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT valid_key_rotation_status CHECK (status IN ('running', 'completed', 'failed'))
+);
+
+-- One runner per school; a second would only wait on the tenant's advisory lock.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tenant_key_rotations_single_running
+    ON tenant_key_rotations(school_id)
+    WHERE status = 'running';
+
+CREATE INDEX IF NOT EXISTS idx_tenant_key_rotations_school
+    ON tenant_key_rotations(school_id, created_at DESC);
+
+COMMENT ON COLUMN tenant_key_rotations.admin_user_id IS 'Copied, not referenced, so history survives admin deletion';
+COMMENT ON COLUMN tenant_key_rotations.last_row_error IS 'Latest row backend-school could not rotate: table, column and row id only';
@@ -23,7 +23,17 @@ pub fn build_app(state: AppState) -> Router {
         .route("/ready", get(handlers::health::readiness_check))
         .route("/api/v1/auth/login", post(handlers::auth::login_handler))
         .route("/api/v1/auth/logout", post(handlers::auth::logout_handler))
-        .route("/api/v1/auth/me", get(handlers::auth::me_handler))
+        .route(
+            "/api/v1/auth/me",
+            get(handlers::auth::me_handler).layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::auth::require_auth,
+            )),
+        )
+        .route(
+            "/api/v1/auth/invitations/accept",
+            post(handlers::admin_account::accept_invitation),
+        )
         // Internal routes (protected by INTERNAL_API_SECRET header)
         .route(
             "/internal/schools",
@@ -37,6 +47,10 @@ pub fn build_app(state: AppState) -> Router {
             "/internal/schools/{subdomain}/migration-status",
             axum::routing::put(handlers::internal::update_migration_status_internal),
         )
+        .route(
+            "/internal/support-sessions/{id}/activity",
+            post(handlers::internal::record_support_activity_internal),
+        )
         // Protected routes (require authentication)
         .nest(
             "/api/v1/schools",
@@ -62,7 +76,182 @@ pub fn build_app(state: AppState) -> Router {
                     "/{id}/deployments",
                     get(handlers::school::get_deployment_history),
                 )
-                .layer(axum::middleware::from_fn(middleware::auth::require_auth)),
+                // Tenant snapshots
+                .route(
+                    "/{id}/snapshots",
+                    get(handlers::snapshot::list_school_snapshots),
+                )
+                .route(
+                    "/{id}/snapshots/stream",
+                    post(handlers::snapshot::create_snapshot_sse),
+                )
+                // Tenant migration history
+                .route(
+                    "/{id}/migrations",
+                    get(handlers::migration_campaign::list_school_migrations),
+                )
+                // Usage analytics
+                .route("/{id}/usage", get(handlers::usage::get_school_usage))
+                // Field-encryption key rotation
+                .route(
+                    "/{id}/key-rotation",
+                    get(handlers::key_rotation::get_school_key_rotation),
+                )
+                .route(
+                    "/{id}/key-rotation",
+                    post(handlers::key_rotation::start_key_rotation),
+                )
+                // Plan assignment
+                .route(
+                    "/{id}/plan",
+                    axum::routing::put(handlers::plan::assign_school_plan),
+                )
+                // Support sessions into the school
+                .route(
+                    "/{id}/support-sessions",
+                    get(handlers::support_session::list_support_sessions),
+                )
+                .route(
+                    "/{id}/support-sessions",
+                    post(handlers::support_session::create_support_session),
+                )
+                .route(
+                    "/{id}/support-sessions/{session_id}",
+                    get(handlers::support_session::get_support_session),
+                )
+                .route(
+                    "/{id}/support-sessions/{session_id}/handoff",
+                    post(handlers::support_session::issue_support_handoff),
+                )
+                .route(
+                    "/{id}/support-sessions/{session_id}/end",
+                    post(handlers::support_session::end_support_session),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/snapshots",
+            Router::new()
+                .route("/{id}", get(handlers::snapshot::get_snapshot))
+                .route(
+                    "/{id}/restores",
+                    get(handlers::snapshot::list_snapshot_restores),
+                )
+                .route(
+                    "/{id}/restore/stream",
+                    post(handlers::snapshot::restore_snapshot_sse),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/migration-campaigns",
+            Router::new()
+                .route("/", post(handlers::migration_campaign::create_campaign))
+                .route("/", get(handlers::migration_campaign::list_campaigns))
+                .route("/{id}", get(handlers::migration_campaign::get_campaign))
+                .route(
+                    "/{id}/tenants",
+                    get(handlers::migration_campaign::list_campaign_tenants),
+                )
+                .route(
+                    "/{id}/run/stream",
+                    post(handlers::migration_campaign::run_campaign_sse),
+                )
+                .route(
+                    "/{id}/cancel",
+                    post(handlers::migration_campaign::cancel_campaign),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/plans",
+            Router::new()
+                .route("/", post(handlers::plan::create_plan))
+                .route("/", get(handlers::plan::list_plans))
+                .route("/{id}", get(handlers::plan::get_plan))
+                .route("/{id}", axum::routing::put(handlers::plan::update_plan))
+                .route("/{id}", axum::routing::delete(handlers::plan::delete_plan))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/usage",
+            Router::new()
+                .route("/", get(handlers::usage::get_usage_overview))
+                .route("/inactive", get(handlers::usage::list_inactive_schools))
+                .route("/alerts", get(handlers::usage::list_quota_alerts))
+                .route("/collect", post(handlers::usage::collect_usage))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/account",
+            Router::new()
+                .route("/sessions", get(handlers::admin_account::list_my_sessions))
+                .route(
+                    "/sessions/{id}",
+                    axum::routing::delete(handlers::admin_account::revoke_my_session),
+                )
+                .route("/mfa/setup", post(handlers::admin_account::setup_mfa))
+                .route("/mfa/enable", post(handlers::admin_account::enable_mfa))
+                .route("/mfa/disable", post(handlers::admin_account::disable_mfa))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .nest(
+            "/api/v1/admin-users",
+            Router::new()
+                .route("/", get(handlers::admin_account::list_admins))
+                .route(
+                    "/{id}/role",
+                    axum::routing::put(handlers::admin_account::update_admin_role),
+                )
+                .route(
+                    "/{id}/sessions",
+                    get(handlers::admin_account::list_admin_sessions),
+                )
+                .route(
+                    "/{id}/sessions/{session_id}",
+                    axum::routing::delete(handlers::admin_account::revoke_admin_session),
+                )
+                .route(
+                    "/invitations",
+                    get(handlers::admin_account::list_invitations),
+                )
+                .route(
+                    "/invitations",
+                    post(handlers::admin_account::create_invitation),
+                )
+                .route(
+                    "/invitations/{id}",
+                    axum::routing::delete(handlers::admin_account::revoke_invitation),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::auth::require_auth,
+                )),
+        )
+        .route(
+            "/api/v1/audit-log",
+            get(handlers::audit::list_audit_log).layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                middleware::auth::require_auth,
+            )),
         )
         // Global layers
         .layer(CookieManagerLayer::new())
@@ -1,7 +1,8 @@
 pub mod jwt;
+pub mod totp;
 pub mod types;
 pub mod validation;
 
 pub use jwt::{generate_token, validate_token};
-pub use types::{AdminClaims, AdminRole};
+pub use types::{AdminClaims, AdminPermission, AdminRole, AuthenticatedAdmin};
 pub use validation::{hash_password, verify_password};
@@ -0,0 +1,133 @@
+//! RFC 6238 time-based one-time passwords (SHA-1, 6 digits, 30-second steps),
+//! the format every authenticator app understands.
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+const STEP_SECONDS: i64 = 30;
+const DIGITS: u32 = 6;
+/// Steps accepted on either side of the current one, for clock drift
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+fn base32_decode(value: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(value.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in value.chars().filter(|c| *c != '=' && !c.is_whitespace()) {
+        let index = BASE32_ALPHABET
+            .iter()
+            .position(|&letter| letter as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | index as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+/// A new random 160-bit secret, base32 encoded
+pub fn generate_secret() -> String {
+    let mut secret = [0u8; 20];
+    rand::thread_rng().fill_bytes(&mut secret);
+    base32_encode(&secret)
+}
+
+/// `otpauth://` URI for authenticator apps to scan as a QR code
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&digits={DIGITS}&period={STEP_SECONDS}",
+        issuer = issuer.replace(' ', "%20"),
+        account = account.replace(' ', "%20"),
+    )
+}
+
+fn code_at(secret: &[u8], step: i64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// Verify `code` at `unix_time`, returning the matched time step.
+///
+/// Callers must reject steps at or below the last accepted one, so a code
+/// cannot be replayed within its validity window.
+pub fn verify_code(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let expected: u32 = code.parse().ok()?;
+    let secret = base32_decode(secret)?;
+    let current = unix_time / STEP_SECONDS;
+
+    (current - ALLOWED_DRIFT_STEPS..=current + ALLOWED_DRIFT_STEPS)
+        .find(|&step| code_at(&secret, step) == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base32_decode, base32_encode, code_at, generate_secret, verify_code};
+
+    // RFC 6238 appendix B, SHA-1 seed, truncated to six digits
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn codes_match_rfc_6238_vectors() {
+        assert_eq!(code_at(RFC_SECRET, 59 / 30), 287_082);
+        assert_eq!(code_at(RFC_SECRET, 1_111_111_109 / 30), 81_804);
+        assert_eq!(code_at(RFC_SECRET, 2_000_000_000 / 30), 279_037);
+    }
+
+    #[test]
+    fn base32_round_trips() {
+        let encoded = base32_encode(RFC_SECRET);
+        assert_eq!(encoded, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(base32_decode(&encoded).unwrap(), RFC_SECRET);
+        assert_eq!(base32_decode(&generate_secret()).unwrap().len(), 20);
+    }
+
+    #[test]
+    fn verification_allows_one_step_of_drift() {
+        let secret = base32_encode(RFC_SECRET);
+
+        assert_eq!(verify_code(&secret, "287082", 59), Some(1));
+        assert_eq!(verify_code(&secret, "287082", 89), Some(1));
+        assert_eq!(verify_code(&secret, "287082", 150), None);
+        assert_eq!(verify_code(&secret, "28708", 59), None);
+    }
+}
@@ -1,27 +1,58 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminClaims {
     pub sub: String, // User ID
-    pub email: String,
+    pub sid: String, // Session ID, checked against admin_sessions on every request
     pub role: AdminRole,
     pub exp: usize, // Expiration timestamp
     pub iat: usize, // Issued at timestamp
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Control-plane roles, from least to most privileged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "snake_case")]
 pub enum AdminRole {
-    SuperAdmin,
-    Admin,
-    SchoolAdmin,
-    Teacher,
-    Student,
+    Viewer,
+    Operator,
+    Owner,
+}
+
+/// Actions a control-plane role may perform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminPermission {
+    /// Read schools, deployments, usage and campaigns
+    ViewSchools,
+    /// Create, update and deploy schools; run snapshots and campaigns
+    OperateSchools,
+    DeleteSchools,
+    /// Invite admins, change roles and revoke other admins' sessions
+    ManageAdmins,
+    ViewAuditLog,
 }
 
 impl AdminRole {
-    pub fn can_access_admin_backend(&self) -> bool {
-        matches!(self, AdminRole::SuperAdmin | AdminRole::Admin)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminRole::Viewer => "viewer",
+            AdminRole::Operator => "operator",
+            AdminRole::Owner => "owner",
+        }
+    }
+
+    pub fn has_permission(&self, permission: AdminPermission) -> bool {
+        let required = match permission {
+            AdminPermission::ViewSchools => AdminRole::Viewer,
+            AdminPermission::OperateSchools | AdminPermission::ViewAuditLog => AdminRole::Operator,
+            AdminPermission::DeleteSchools | AdminPermission::ManageAdmins => AdminRole::Owner,
+        };
+        *self >= required
     }
 }
 
@@ -30,44 +61,90 @@ impl TryFrom<&str> for AdminRole {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "super_admin" => Ok(AdminRole::SuperAdmin),
-            "admin" => Ok(AdminRole::Admin),
-            "school_admin" => Ok(AdminRole::SchoolAdmin),
-            "teacher" => Ok(AdminRole::Teacher),
-            "student" => Ok(AdminRole::Student),
+            "viewer" => Ok(AdminRole::Viewer),
+            "operator" => Ok(AdminRole::Operator),
+            "owner" => Ok(AdminRole::Owner),
             _ => Err(format!("Unsupported admin role: {}", value)),
         }
     }
 }
 
+/// The signed-in admin, resolved from a live session by the auth middleware
+/// and available to handlers as a request extension
+#[derive(Debug, Clone)]
+pub struct AuthenticatedAdmin {
+    pub admin_id: Uuid,
+    pub session_id: Uuid,
+    pub name: String,
+    pub role: AdminRole,
+}
+
+impl AuthenticatedAdmin {
+    pub fn require(&self, permission: AdminPermission) -> Result<(), PermissionDenied> {
+        if self.role.has_permission(permission) {
+            Ok(())
+        } else {
+            Err(PermissionDenied { role: self.role })
+        }
+    }
+}
+
+/// Rejection for an admin whose role lacks a permission; renders as 403
+#[derive(Debug)]
+pub struct PermissionDenied {
+    pub role: AdminRole,
+}
+
+impl IntoResponse for PermissionDenied {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": format!("Forbidden - {} role cannot perform this action", self.role.as_str())
+            })),
+        )
+            .into_response()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AdminClaims, AdminRole};
+    use super::{AdminClaims, AdminPermission, AdminRole};
     use serde_json::json;
 
     #[test]
     fn admin_claims_do_not_serialize_school_scoped_fields() {
         let claims = AdminClaims {
             sub: "admin-user-id".to_string(),
-            email: "1234567890123".to_string(),
-            role: AdminRole::Admin,
+            sid: "admin-session-id".to_string(),
+            role: AdminRole::Operator,
             exp: 1_900_000_000,
             iat: 1_800_000_000,
         };
 
         let value = serde_json::to_value(claims).expect("claims should serialize");
 
-        assert_eq!(value["role"], json!("admin"));
+        assert_eq!(value["role"], json!("operator"));
         assert!(value.get("school_id").is_none());
         assert!(value.get("subdomain").is_none());
+        assert!(value.get("email").is_none());
+    }
+
+    #[test]
+    fn school_roles_are_not_admin_roles() {
+        assert!(AdminRole::try_from("owner").is_ok());
+        assert!(AdminRole::try_from("school_admin").is_err());
+        assert!(AdminRole::try_from("teacher").is_err());
+        assert!(AdminRole::try_from("student").is_err());
     }
 
     #[test]
-    fn only_admin_roles_can_access_admin_backend() {
-        assert!(AdminRole::SuperAdmin.can_access_admin_backend());
-        assert!(AdminRole::Admin.can_access_admin_backend());
-        assert!(!AdminRole::SchoolAdmin.can_access_admin_backend());
-        assert!(!AdminRole::Teacher.can_access_admin_backend());
-        assert!(!AdminRole::Student.can_access_admin_backend());
+    fn roles_grant_increasing_permissions() {
+        assert!(AdminRole::Viewer.has_permission(AdminPermission::ViewSchools));
+        assert!(!AdminRole::Viewer.has_permission(AdminPermission::OperateSchools));
+        assert!(AdminRole::Operator.has_permission(AdminPermission::OperateSchools));
+        assert!(!AdminRole::Operator.has_permission(AdminPermission::DeleteSchools));
+        assert!(AdminRole::Owner.has_permission(AdminPermission::DeleteSchools));
+        assert!(AdminRole::Owner.has_permission(AdminPermission::ManageAdmins));
     }
 }
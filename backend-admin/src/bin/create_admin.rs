@@ -20,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     sqlx::query!(
         r#"
         INSERT INTO admin_users (national_id, password_hash, name, role)
-        VALUES ($1, $2, $3, 'super_admin')
+        VALUES ($1, $2, $3, 'owner')
         ON CONFLICT (national_id) DO UPDATE SET password_hash = $2
         "#,
         national_id,
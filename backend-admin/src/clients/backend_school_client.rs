@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
+use uuid::Uuid;
 
 const INTERNAL_CALLER: &str = "backend-admin";
 const INTERNAL_CALLER_HEADER: &str = "X-Internal-Caller";
@@ -26,6 +29,228 @@ pub struct ProvisionResponse {
     pub school_id: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotExportRequest {
+    pub snapshot_id: Uuid,
+    pub tenant_id: Uuid,
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotExportSummary {
+    pub snapshot_id: Uuid,
+    pub format_version: i32,
+    pub schema_version: i64,
+    pub captured_at: DateTime<Utc>,
+    pub manifest_checksum: String,
+    pub table_count: i32,
+    pub row_count: i64,
+    pub object_count: i32,
+    pub total_bytes: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRestoreRequest {
+    pub snapshot_id: Uuid,
+    pub source_tenant_id: Uuid,
+    pub manifest_checksum: String,
+    pub target_tenant_id: Uuid,
+    pub target_subdomain: String,
+    pub db_connection_string: String,
+    pub replace_existing: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anonymization: Option<SnapshotAnonymization>,
+}
+
+/// Asks backend-school to replace personal data while restoring a sandbox clone
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotAnonymization {
+    pub sandbox_password: String,
+}
+
+impl std::fmt::Debug for SnapshotAnonymization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotAnonymization")
+            .field("sandbox_password", &"[redacted]")
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRestoreSummary {
+    pub snapshot_id: Uuid,
+    pub snapshot_schema_version: i64,
+    pub schema_version: i64,
+    pub table_count: i32,
+    pub row_count: i64,
+    pub object_count: i32,
+    #[serde(default)]
+    pub anonymization: Option<AnonymizationSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationSummary {
+    pub replaced_values: i64,
+    pub cleared_values: i64,
+    pub scrubbed_values: i64,
+    pub placeholder_objects: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMigrationRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+    pub target_version: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMigrationSummary {
+    pub subdomain: String,
+    pub from_version: Option<i64>,
+    pub applied_version: Option<i64>,
+    pub latest_version: i64,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantUsageRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantUsageMetrics {
+    pub subdomain: String,
+    pub collected_at: DateTime<Utc>,
+    pub active_users_7d: i64,
+    pub active_users_30d: i64,
+    pub student_count: i64,
+    pub staff_count: i64,
+    pub storage_bytes_by_purpose: BTreeMap<String, i64>,
+    pub storage_bytes: i64,
+    pub certificates_issued: i64,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub migration_version: Option<i64>,
+    pub database_bytes: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+/// The school's progress towards its active encryption and blind-index keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationRun {
+    pub encryption_key_id: String,
+    pub blind_index_key_id: String,
+    /// `running` or `completed`
+    pub status: String,
+    pub cursor_table: Option<String>,
+    pub rows_scanned: i64,
+    pub rows_rewritten: i64,
+    pub rows_failed: i64,
+    pub last_error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedColumnStatus {
+    pub table: String,
+    pub column: String,
+    pub rows_by_key: BTreeMap<String, i64>,
+    pub rows_on_old_keys: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlindIndexStatus {
+    pub table: String,
+    pub column: String,
+    pub rows_pending: i64,
+}
+
+/// How many of a school's encrypted values and blind indexes still use older keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationStatus {
+    pub subdomain: String,
+    pub active_encryption_key_id: String,
+    pub active_blind_index_key_id: String,
+    pub rows_on_old_keys: i64,
+    pub blind_index_rows_pending: i64,
+    pub columns: Vec<EncryptedColumnStatus>,
+    pub blind_indexes: Vec<BlindIndexStatus>,
+    pub run: Option<KeyRotationRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessCreateRequest {
+    pub subdomain: String,
+    pub support_access_id: Uuid,
+    pub platform_admin_id: Uuid,
+    pub platform_admin_name: String,
+    pub ticket_reference: String,
+    pub reason: Option<String>,
+    pub scope: String,
+    pub permissions: Vec<String>,
+    pub duration_minutes: i32,
+    pub requires_approval: bool,
+    pub target_username: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessLookupRequest {
+    pub subdomain: String,
+    pub support_access_id: Uuid,
+}
+
+/// The school's view of a support grant
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessGrant {
+    pub id: Uuid,
+    pub target_user_name: String,
+    pub status: String,
+    pub decision_note: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessHandoff {
+    pub grant: SupportAccessGrant,
+    pub token: String,
+    pub token_expires_at: DateTime<Utc>,
+}
+
+/// Success envelope returned by backend-school (`ApiResponse<T>`).
+#[derive(Debug, Deserialize)]
+struct BackendSchoolResponse<T> {
+    data: T,
+}
+
 pub struct BackendSchoolClient {
     client: Client,
     base_url: String,
@@ -102,6 +327,154 @@ impl BackendSchoolClient {
         Ok(response_data)
     }
 
+    /// Ask backend-school to export a point-in-time snapshot of a tenant
+    pub async fn export_tenant_snapshot(
+        &self,
+        request: &SnapshotExportRequest,
+    ) -> Result<SnapshotExportSummary, String> {
+        self.post_internal("/internal/snapshots/export", request, "snapshot export")
+            .await
+    }
+
+    /// Ask backend-school to restore a snapshot into a tenant database
+    pub async fn restore_tenant_snapshot(
+        &self,
+        request: &SnapshotRestoreRequest,
+    ) -> Result<SnapshotRestoreSummary, String> {
+        self.post_internal("/internal/snapshots/restore", request, "snapshot restore")
+            .await
+    }
+
+    /// Ask backend-school to migrate one tenant up to a campaign's target version
+    pub async fn migrate_tenant(
+        &self,
+        request: &TenantMigrationRequest,
+    ) -> Result<TenantMigrationSummary, String> {
+        self.post_internal("/internal/migrations/tenant", request, "tenant migration")
+            .await
+    }
+
+    /// Ask backend-school for aggregate usage counters of one tenant
+    pub async fn collect_tenant_usage(
+        &self,
+        request: &TenantUsageRequest,
+    ) -> Result<TenantUsageMetrics, String> {
+        self.post_internal("/internal/usage", request, "usage collection")
+            .await
+    }
+
+    /// Advance a school's re-encryption towards its active keys by one slice of work
+    pub async fn run_key_rotation(
+        &self,
+        request: &KeyRotationRequest,
+    ) -> Result<KeyRotationRun, String> {
+        self.post_internal("/internal/key-rotation/run", request, "key rotation")
+            .await
+    }
+
+    /// Count the values a school still holds under older keys
+    pub async fn key_rotation_status(
+        &self,
+        request: &KeyRotationRequest,
+    ) -> Result<KeyRotationStatus, String> {
+        self.post_internal(
+            "/internal/key-rotation/status",
+            request,
+            "key rotation status",
+        )
+        .await
+    }
+
+    /// Ask a school to record a support access grant
+    pub async fn create_support_access(
+        &self,
+        request: &SupportAccessCreateRequest,
+    ) -> Result<SupportAccessGrant, String> {
+        self.post_internal(
+            "/internal/support-access",
+            request,
+            "support access request",
+        )
+        .await
+    }
+
+    /// Read the school's current state of a support access grant
+    pub async fn support_access_status(
+        &self,
+        request: &SupportAccessLookupRequest,
+    ) -> Result<SupportAccessGrant, String> {
+        self.post_internal(
+            "/internal/support-access/status",
+            request,
+            "support access lookup",
+        )
+        .await
+    }
+
+    /// Issue the single-use token that starts an approved support session
+    pub async fn issue_support_handoff(
+        &self,
+        request: &SupportAccessLookupRequest,
+    ) -> Result<SupportAccessHandoff, String> {
+        self.post_internal(
+            "/internal/support-access/handoff",
+            request,
+            "support handoff",
+        )
+        .await
+    }
+
+    /// End a support grant and sign its session out of the school
+    pub async fn end_support_access(
+        &self,
+        request: &SupportAccessLookupRequest,
+    ) -> Result<SupportAccessGrant, String> {
+        self.post_internal(
+            "/internal/support-access/end",
+            request,
+            "support access end",
+        )
+        .await
+    }
+
+    async fn post_internal<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+        action: &str,
+    ) -> Result<T, String> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let response = self
+            .client
+            .post(&url)
+            .header(INTERNAL_SECRET_HEADER, &self.internal_secret)
+            .header(INTERNAL_CALLER_HEADER, INTERNAL_CALLER)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call backend-school: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!(
+                "Backend-school {} failed ({}): {}",
+                action, status, error_text
+            ));
+        }
+
+        let envelope: BackendSchoolResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        Ok(envelope.data)
+    }
+
     /// Health check for backend-school
     pub async fn health_check(&self) -> bool {
         let url = format!("{}/health", self.base_url);
@@ -1,3 +1,5 @@
 pub mod backend_school_client;
 pub mod cloudflare_client;
+pub mod local_client;
 pub mod neon_client;
+pub mod provisioning_driver;
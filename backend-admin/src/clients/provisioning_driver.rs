@@ -0,0 +1,256 @@
+use crate::clients::cloudflare_client::CloudflareClient;
+use crate::clients::local_client::LocalClient;
+use crate::clients::neon_client::NeonClient;
+use crate::models::School;
+use chrono::{DateTime, Utc};
+use std::env;
+use tracing::info;
+
+/// Which infrastructure `SchoolService` provisions schools on, chosen by PROVISIONING_DRIVER
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvisioningDriverKind {
+    /// Neon databases, Cloudflare Workers deployed through GitHub Actions
+    Cloud,
+    /// Databases and roles on a configured PostgreSQL server, no managed DNS
+    Local,
+}
+
+impl ProvisioningDriverKind {
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("PROVISIONING_DRIVER") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::Cloud),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "cloud" => Ok(Self::Cloud),
+            "local" => Ok(Self::Local),
+            other => Err(format!(
+                "PROVISIONING_DRIVER must be 'cloud' or 'local', got '{}'",
+                other
+            )),
+        }
+    }
+}
+
+pub struct ProvisionedDatabase {
+    pub db_id: Option<i64>,
+    pub connection_string: String,
+}
+
+pub struct Deployment {
+    pub url: String,
+    pub dns_record_id: String,
+    /// Set when the deployment finishes asynchronously and can be polled
+    pub workflow_triggered_at: Option<DateTime<Utc>>,
+    pub run_url: Option<String>,
+}
+
+pub enum ProvisioningDriver {
+    Cloud(NeonClient),
+    Local(LocalClient),
+}
+
+impl ProvisioningDriver {
+    pub fn from_env() -> Result<Self, String> {
+        match ProvisioningDriverKind::from_env()? {
+            ProvisioningDriverKind::Cloud => NeonClient::new()
+                .map(Self::Cloud)
+                .map_err(|e| format!("Neon client error: {}", e)),
+            ProvisioningDriverKind::Local => LocalClient::new()
+                .map(Self::Local)
+                .map_err(|e| format!("Local provisioning error: {}", e)),
+        }
+    }
+
+    pub fn kind(&self) -> ProvisioningDriverKind {
+        match self {
+            Self::Cloud(_) => ProvisioningDriverKind::Cloud,
+            Self::Local(_) => ProvisioningDriverKind::Local,
+        }
+    }
+
+    /// Human-readable name of the database host for progress messages
+    pub fn database_provider(&self) -> &'static str {
+        match self {
+            Self::Cloud(_) => "Neon",
+            Self::Local(_) => "PostgreSQL",
+        }
+    }
+
+    /// Human-readable name of the frontend target for progress messages
+    pub fn deployment_target(&self) -> &'static str {
+        match self {
+            Self::Cloud(_) => "Cloudflare Worker",
+            Self::Local(_) => "local deployment",
+        }
+    }
+
+    pub async fn create_database(&self, db_name: &str) -> Result<ProvisionedDatabase, String> {
+        match self {
+            Self::Cloud(neon) => {
+                // This is the password for the neondb_owner role in the Neon project.
+                let db_password = env::var("NEON_DB_PASSWORD").map_err(|_| {
+                    "NEON_DB_PASSWORD not set. Get this from Neon console.".to_string()
+                })?;
+                let db_id = neon.create_database(db_name, "neondb_owner").await?;
+
+                Ok(ProvisionedDatabase {
+                    db_id: Some(db_id),
+                    connection_string: neon.get_connection_string(
+                        db_name,
+                        "neondb_owner",
+                        &db_password,
+                    ),
+                })
+            }
+            Self::Local(local) => Ok(ProvisionedDatabase {
+                db_id: None,
+                connection_string: local.create_database(db_name).await?,
+            }),
+        }
+    }
+
+    pub async fn wait_for_database_ready(
+        &self,
+        db_name: &str,
+        connection_string: &str,
+    ) -> Result<(), String> {
+        match self {
+            Self::Cloud(neon) => {
+                // Neon can list the database before its create operation finishes.
+                neon.wait_for_database_ready(db_name).await?;
+                neon.wait_for_database_connectable(connection_string).await
+            }
+            Self::Local(local) => local.wait_for_database_connectable(connection_string).await,
+        }
+    }
+
+    pub async fn delete_database(&self, db_name: &str) -> Result<(), String> {
+        match self {
+            Self::Cloud(neon) => neon.delete_database_by_name(db_name).await,
+            Self::Local(local) => local.delete_database(db_name).await,
+        }
+    }
+
+    /// Publish the school frontend and return where it will be served
+    pub async fn deploy(&self, subdomain: &str, school_id: &str) -> Result<Deployment, String> {
+        match self {
+            Self::Cloud(_) => {
+                let cloudflare = CloudflareClient::new()
+                    .map_err(|e| format!("Cloudflare client error: {}", e))?;
+                let api_url = env::var("API_URL")
+                    .unwrap_or_else(|_| "https://school-api.schoolorbit.app".to_string());
+                let github_repo = env::var("GITHUB_REPO")
+                    .unwrap_or_else(|_| "akephisit/schoolorbit-new".to_string());
+
+                let (url, trigger_time) = cloudflare
+                    .deploy_worker(subdomain, school_id, &api_url)
+                    .await?;
+
+                Ok(Deployment {
+                    url,
+                    // DNS is created and managed by Wrangler's custom_domain deployment.
+                    dns_record_id: String::new(),
+                    workflow_triggered_at: Some(trigger_time),
+                    run_url: Some(format!("https://github.com/{}/actions", github_repo)),
+                })
+            }
+            Self::Local(local) => {
+                local.write_hosts_entry(subdomain).await?;
+                local.wait_for_backend_ready().await?;
+
+                Ok(Deployment {
+                    url: local.school_url(subdomain),
+                    dns_record_id: String::new(),
+                    workflow_triggered_at: None,
+                    run_url: None,
+                })
+            }
+        }
+    }
+
+    /// Wait for an asynchronous deployment to finish; synchronous ones are already done
+    pub async fn wait_for_deployment(
+        &self,
+        subdomain: &str,
+        deployment: &Deployment,
+    ) -> Result<(), String> {
+        match (self, deployment.workflow_triggered_at) {
+            (Self::Cloud(_), Some(trigger_time)) => {
+                let cloudflare = CloudflareClient::new()
+                    .map_err(|e| format!("Cloudflare client error: {}", e))?;
+                cloudflare
+                    .wait_for_workflow_completion(subdomain, trigger_time, 10)
+                    .await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove whatever `deploy` published; individual failures are reported, not fatal
+    pub async fn undeploy(&self, school: &School) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        match self {
+            Self::Cloud(_) => {
+                let cloudflare = match CloudflareClient::new() {
+                    Ok(client) => client,
+                    Err(e) => {
+                        errors.push(format!("Cloudflare client not available: {}", e));
+                        return errors;
+                    }
+                };
+
+                let worker_name = format!("schoolorbit-school-{}", school.subdomain);
+                match cloudflare.delete_worker(&worker_name).await {
+                    Ok(_) => info!(worker_name, "worker deleted"),
+                    Err(e) => errors.push(format!("Worker deletion failed: {}", e)),
+                }
+
+                match school.config.dns_record_id.as_deref() {
+                    Some(dns_id) if !dns_id.is_empty() => {
+                        match cloudflare.delete_dns_record(dns_id).await {
+                            Ok(_) => info!(dns_id, "DNS record deleted"),
+                            Err(e) => errors.push(format!("DNS record deletion failed: {}", e)),
+                        }
+                    }
+                    _ => info!("no DNS record ID (managed by Wrangler)"),
+                }
+            }
+            Self::Local(local) => {
+                if let Err(e) = local.remove_hosts_entry(&school.subdomain).await {
+                    errors.push(format!("Hosts entry removal failed: {}", e));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProvisioningDriverKind;
+
+    #[test]
+    fn driver_kind_defaults_to_cloud() {
+        assert_eq!(
+            ProvisioningDriverKind::parse("").unwrap(),
+            ProvisioningDriverKind::Cloud
+        );
+        assert_eq!(
+            ProvisioningDriverKind::parse(" Local ").unwrap(),
+            ProvisioningDriverKind::Local
+        );
+    }
+
+    #[test]
+    fn unknown_driver_kind_is_rejected() {
+        let error = ProvisioningDriverKind::parse("neon").unwrap_err();
+
+        assert!(error.contains("PROVISIONING_DRIVER"));
+    }
+}
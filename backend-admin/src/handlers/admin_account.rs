@@ -0,0 +1,354 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::{
+    AcceptAdminInvitation, CreateAdminInvitation, MfaCodeRequest, UpdateAdminRole,
+};
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AdminAccountService, AuditService};
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+fn admin_target(id: Uuid, label: Option<&str>) -> AuditTarget<'_> {
+    AuditTarget {
+        target_type: "admin",
+        target_id: Some(id),
+        label,
+    }
+}
+
+// List the signed-in admin's active sessions
+pub async fn list_my_sessions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.list_sessions(admin.admin_id).await {
+        Ok(sessions) => (StatusCode::OK, Json(ApiResponse::success(sessions))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Sign out one of the signed-in admin's sessions
+pub async fn revoke_my_session(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(session_id): Path<Uuid>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.revoke_session(admin.admin_id, session_id).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(json!({"success": true, "message": "Session revoked"})),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Start MFA enrollment with a fresh TOTP secret
+pub async fn setup_mfa(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.setup_mfa(&admin).await {
+        Ok(setup) => (StatusCode::OK, Json(ApiResponse::success(setup))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Confirm MFA enrollment with a code from the authenticator app
+pub async fn enable_mfa(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Json(data): Json<MfaCodeRequest>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.enable_mfa(admin.admin_id, &data.code).await {
+        Ok(_) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.mfa_enable",
+                    admin_target(admin.admin_id, Some(&admin.name)),
+                    json!({}),
+                )
+                .await;
+            (
+                StatusCode::OK,
+                Json(json!({"success": true, "message": "MFA enabled"})),
+            )
+                .into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// Turn MFA off with a current code
+pub async fn disable_mfa(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Json(data): Json<MfaCodeRequest>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.disable_mfa(admin.admin_id, &data.code).await {
+        Ok(_) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.mfa_disable",
+                    admin_target(admin.admin_id, Some(&admin.name)),
+                    json!({}),
+                )
+                .await;
+            (
+                StatusCode::OK,
+                Json(json!({"success": true, "message": "MFA disabled"})),
+            )
+                .into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// List admin accounts
+pub async fn list_admins(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.list_admins().await {
+        Ok(admins) => (StatusCode::OK, Json(ApiResponse::success(admins))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Change an admin's role
+pub async fn update_admin_role(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+    Json(data): Json<UpdateAdminRole>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.update_role(id, &data.role).await {
+        Ok(account) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.role_update",
+                    admin_target(account.id, Some(&account.name)),
+                    json!({"role": account.role}),
+                )
+                .await;
+            (StatusCode::OK, Json(ApiResponse::success(account))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// List another admin's active sessions
+pub async fn list_admin_sessions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.list_sessions(id).await {
+        Ok(sessions) => (StatusCode::OK, Json(ApiResponse::success(sessions))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Sign out another admin's session
+pub async fn revoke_admin_session(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.revoke_session(id, session_id).await {
+        Ok(_) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.session_revoke",
+                    admin_target(id, None),
+                    json!({"sessionId": session_id}),
+                )
+                .await;
+            (
+                StatusCode::OK,
+                Json(json!({"success": true, "message": "Session revoked"})),
+            )
+                .into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// List recent invitations
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.list_invitations().await {
+        Ok(invitations) => {
+            (StatusCode::OK, Json(ApiResponse::success(invitations))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// Invite an admin by email
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Json(data): Json<CreateAdminInvitation>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.create_invitation(&admin, data).await {
+        Ok(created) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.invite",
+                    AuditTarget {
+                        target_type: "admin_invitation",
+                        target_id: Some(created.invitation.id),
+                        label: Some(&created.invitation.email),
+                    },
+                    json!({"role": created.invitation.role}),
+                )
+                .await;
+            (StatusCode::CREATED, Json(ApiResponse::success(created))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// Revoke a pending invitation
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ManageAdmins) {
+        return denied.into_response();
+    }
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.revoke_invitation(id).await {
+        Ok(_) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "admin.invitation_revoke",
+                    AuditTarget {
+                        target_type: "admin_invitation",
+                        target_id: Some(id),
+                        label: None,
+                    },
+                    json!({}),
+                )
+                .await;
+            (
+                StatusCode::OK,
+                Json(json!({"success": true, "message": "Invitation revoked"})),
+            )
+                .into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// Accept an invitation and create the account (public, token authenticated)
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    Json(data): Json<AcceptAdminInvitation>,
+) -> Response {
+    let service = AdminAccountService::new(state.pool.clone());
+
+    match service.accept_invitation(data).await {
+        Ok((account, invitation)) => {
+            AuditService::new(state.pool.clone())
+                .record_as(
+                    account.id,
+                    &account.name,
+                    "admin.invitation_accept",
+                    admin_target(account.id, Some(&account.name)),
+                    json!({"invitationId": invitation.id, "role": invitation.role}),
+                )
+                .await;
+            (
+                StatusCode::CREATED,
+                Json(ApiResponse::success_with_message(
+                    json!({
+                        "id": account.id,
+                        "name": account.name,
+                        "email": account.email,
+                        "role": account.role,
+                    }),
+                    "Invitation accepted".to_string(),
+                )),
+            )
+                .into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
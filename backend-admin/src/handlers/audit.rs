@@ -0,0 +1,32 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::models::AuditLogQuery;
+use crate::services::AuditService;
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+
+// List audit entries, newest first
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Query(query): Query<AuditLogQuery>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ViewAuditLog) {
+        return denied.into_response();
+    }
+    let service = AuditService::new(state.pool.clone());
+
+    match service.list_entries(query).await {
+        Ok(entries) => (StatusCode::OK, Json(ApiResponse::success(entries))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
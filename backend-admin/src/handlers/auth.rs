@@ -1,13 +1,15 @@
-use crate::auth::validate_token;
+use crate::auth::{validate_token, AuthenticatedAdmin};
+use crate::error::AppError;
 use crate::models::LoginRequest;
+use crate::services::auth_service::{SessionClient, MFA_REQUIRED};
 use crate::services::AuthService;
 use crate::types::ApiResponse;
 use crate::AppState;
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use tower_cookies::{Cookie, Cookies};
@@ -18,14 +20,40 @@ pub struct LoginResponse {
     pub user: serde_json::Value,
 }
 
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Client details for the session list; the first X-Forwarded-For hop is the
+/// browser when running behind the nginx proxy
+pub(crate) fn session_client(headers: &HeaderMap) -> SessionClient {
+    let ip_address = header_value(headers, "x-forwarded-for")
+        .and_then(|value| value.split(',').next().map(|ip| ip.trim().to_string()))
+        .or_else(|| header_value(headers, "x-real-ip"));
+
+    SessionClient {
+        user_agent: header_value(headers, "user-agent")
+            .map(|agent| agent.chars().take(512).collect()),
+        ip_address: ip_address.map(|ip| ip.chars().take(64).collect()),
+    }
+}
+
 pub async fn login_handler(
     State(state): State<AppState>,
     cookies: Cookies,
+    headers: HeaderMap,
     Json(credentials): Json<LoginRequest>,
 ) -> Response {
     let auth_service = AuthService::new(state.pool.clone());
 
-    match auth_service.login(credentials).await {
+    match auth_service
+        .login(credentials, session_client(&headers))
+        .await
+    {
         Ok((admin, token)) => {
             // Set cookie using tower-cookies with proper configuration
             let mut cookie = Cookie::new("auth_token", token.clone());
@@ -43,11 +71,20 @@ pub async fn login_handler(
                     "nationalId": admin.national_id,
                     "name": admin.name,
                     "role": admin.role,
+                    "mfaEnabled": admin.totp_enabled_at.is_some(),
                 }),
             });
 
             (StatusCode::OK, Json(response_data)).into_response()
         }
+        Err(AppError::Unauthorized(message)) if message == MFA_REQUIRED => (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "error": AppError::Unauthorized(message).to_string(),
+                "mfaRequired": true
+            })),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::UNAUTHORIZED,
             Json(serde_json::json!({
@@ -58,7 +95,20 @@ pub async fn login_handler(
     }
 }
 
-pub async fn logout_handler(cookies: Cookies) -> Response {
+pub async fn logout_handler(State(state): State<AppState>, cookies: Cookies) -> Response {
+    // Revoke the server-side session so the token stops working even if copied
+    if let Some(claims) = cookies
+        .get("auth_token")
+        .and_then(|cookie| validate_token(cookie.value()).ok())
+    {
+        if let Ok(session_id) = Uuid::parse_str(&claims.sid) {
+            let auth_service = AuthService::new(state.pool.clone());
+            if let Err(e) = auth_service.revoke_session(session_id).await {
+                tracing::warn!("Failed to revoke admin session on logout: {}", e);
+            }
+        }
+    }
+
     // Remove cookie by setting Max-Age to 0
     // Must match the same path/domain as when cookie was created
     let mut cookie = Cookie::new("auth_token", "");
@@ -77,68 +127,23 @@ pub async fn logout_handler(cookies: Cookies) -> Response {
         .into_response()
 }
 
-pub async fn me_handler(State(state): State<AppState>, cookies: Cookies) -> Response {
-    // Get auth_token from cookies
-    let token = match cookies.get("auth_token") {
-        Some(cookie) => cookie.value().to_string(),
-        None => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({
-                    "error": "No auth token in cookie"
-                })),
-            )
-                .into_response();
-        }
-    };
-
-    // Validate token
-    let claims = match validate_token(&token) {
-        Ok(c) => c,
-        Err(_) => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({
-                    "error": "Invalid token"
-                })),
-            )
-                .into_response();
-        }
-    };
-
-    if !claims.role.can_access_admin_backend() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({
-                "error": "Admin role required"
-            })),
-        )
-            .into_response();
-    }
-
-    // Get user from database
+pub async fn me_handler(
+    State(state): State<AppState>,
+    Extension(current): Extension<AuthenticatedAdmin>,
+) -> Response {
     let auth_service = AuthService::new(state.pool.clone());
-    let user_id = match Uuid::parse_str(&claims.sub) {
-        Ok(id) => id,
-        Err(_) => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({
-                    "error": "Invalid user ID in token"
-                })),
-            )
-                .into_response();
-        }
-    };
 
-    match auth_service.get_admin_by_id(user_id).await {
+    match auth_service.get_admin_by_id(current.admin_id).await {
         Ok(admin) => {
             let response = ApiResponse::success(LoginResponse {
                 user: serde_json::json!({
                     "id": admin.id,
                     "nationalId": admin.national_id,
                     "name": admin.name,
-                    "role": admin.role,
+                    "email": admin.email,
+                    "role": current.role,
+                    "mfaEnabled": admin.totp_enabled_at.is_some(),
+                    "sessionId": current.session_id,
                 }),
             });
             (StatusCode::OK, Json(response)).into_response()
@@ -152,3 +157,25 @@ pub async fn me_handler(State(state): State<AppState>, cookies: Cookies) -> Resp
             .into_response(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::session_client;
+    use axum::http::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn session_client_uses_first_forwarded_hop() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 10.0.0.2"),
+        );
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+        headers.insert("user-agent", HeaderValue::from_static("Firefox"));
+
+        let client = session_client(&headers);
+
+        assert_eq!(client.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(client.user_agent.as_deref(), Some("Firefox"));
+    }
+}
@@ -1,3 +1,6 @@
+use crate::models::{SupportActivity, TenantPlan};
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, SupportSessionService};
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -82,6 +85,8 @@ pub struct SchoolInfo {
     pub migration_status: Option<String>,
     pub last_migrated_at: Option<String>,
     pub migration_error: Option<String>,
+    /// Limits and modules of the school's plan; `None` means unrestricted
+    pub plan: Option<TenantPlan>,
 }
 
 #[derive(Debug, Serialize)]
@@ -108,6 +113,46 @@ struct SchoolRow {
     migration_status: Option<String>,
     last_migrated_at: Option<chrono::NaiveDateTime>,
     migration_error: Option<String>,
+    plan_code: Option<String>,
+    plan_max_students: Option<i64>,
+    plan_max_storage_bytes: Option<i64>,
+    plan_max_certificates_per_year: Option<i64>,
+    plan_enabled_modules: Option<Vec<String>>,
+}
+
+const SCHOOL_INFO_SELECT: &str =
+    "SELECT s.id, s.subdomain, s.name, s.status, s.db_connection_string,
+        s.migration_version, s.migration_status, s.last_migrated_at, s.migration_error,
+        p.code AS plan_code, p.max_students AS plan_max_students,
+        p.max_storage_bytes AS plan_max_storage_bytes,
+        p.max_certificates_per_year AS plan_max_certificates_per_year,
+        p.enabled_modules AS plan_enabled_modules
+ FROM schools s
+ LEFT JOIN plans p ON p.id = s.plan_id";
+
+impl From<SchoolRow> for SchoolInfo {
+    fn from(s: SchoolRow) -> Self {
+        let plan = s.plan_code.map(|code| TenantPlan {
+            code,
+            max_students: s.plan_max_students,
+            max_storage_bytes: s.plan_max_storage_bytes,
+            max_certificates_per_year: s.plan_max_certificates_per_year,
+            enabled_modules: s.plan_enabled_modules,
+        });
+
+        SchoolInfo {
+            id: s.id.to_string(),
+            subdomain: s.subdomain,
+            name: s.name,
+            status: s.status,
+            db_connection_string: s.db_connection_string,
+            migration_version: s.migration_version,
+            migration_status: s.migration_status,
+            last_migrated_at: s.last_migrated_at.map(|dt| dt.to_string()),
+            migration_error: s.migration_error,
+            plan,
+        }
+    }
 }
 
 /// Internal endpoint to list schools - protected by INTERNAL_API_SECRET
@@ -119,12 +164,10 @@ pub async fn list_schools_internal(
 ) -> Result<Json<ListSchoolsResponse>, (StatusCode, String)> {
     verify_internal_secret(&headers)?;
 
-    let schools = sqlx::query_as::<_, SchoolRow>(
-        "SELECT id, subdomain, name, status, db_connection_string,
-                migration_version, migration_status, last_migrated_at, migration_error
-         FROM schools
-         ORDER BY created_at DESC",
-    )
+    let schools = sqlx::query_as::<_, SchoolRow>(&format!(
+        "{} ORDER BY s.created_at DESC",
+        SCHOOL_INFO_SELECT
+    ))
     .fetch_all(&state.pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
@@ -138,17 +181,7 @@ pub async fn list_schools_internal(
                 true
             }
         })
-        .map(|s| SchoolInfo {
-            id: s.id.to_string(),
-            subdomain: s.subdomain,
-            name: s.name,
-            status: s.status,
-            db_connection_string: s.db_connection_string,
-            migration_version: s.migration_version,
-            migration_status: s.migration_status,
-            last_migrated_at: s.last_migrated_at.map(|dt| dt.to_string()),
-            migration_error: s.migration_error,
-        })
+        .map(SchoolInfo::from)
         .collect();
 
     let total = filtered.len() as i64;
@@ -168,12 +201,10 @@ pub async fn get_school_by_subdomain_internal(
 ) -> Result<Json<SchoolInfo>, (StatusCode, String)> {
     verify_internal_secret(&headers)?;
 
-    let school = sqlx::query_as::<_, SchoolRow>(
-        "SELECT id, subdomain, name, status, db_connection_string,
-                migration_version, migration_status, last_migrated_at, migration_error
-         FROM schools
-         WHERE subdomain = $1 AND status IN ('active', 'provisioning')",
-    )
+    let school = sqlx::query_as::<_, SchoolRow>(&format!(
+        "{} WHERE s.subdomain = $1 AND s.status IN ('active', 'provisioning')",
+        SCHOOL_INFO_SELECT
+    ))
     .bind(&subdomain)
     .fetch_optional(&state.pool)
     .await
@@ -183,17 +214,7 @@ pub async fn get_school_by_subdomain_internal(
         format!("School '{}' not found or inactive", subdomain),
     ))?;
 
-    Ok(Json(SchoolInfo {
-        id: school.id.to_string(),
-        subdomain: school.subdomain,
-        name: school.name,
-        status: school.status,
-        db_connection_string: school.db_connection_string,
-        migration_version: school.migration_version,
-        migration_status: school.migration_status,
-        last_migrated_at: school.last_migrated_at.map(|dt| dt.to_string()),
-        migration_error: school.migration_error,
-    }))
+    Ok(Json(SchoolInfo::from(school)))
 }
 
 /// Internal endpoint to update migration status - protected by INTERNAL_API_SECRET
@@ -231,6 +252,45 @@ pub async fn update_migration_status_internal(
     Ok(StatusCode::OK)
 }
 
+/// Internal endpoint for requests made during a support session - protected by
+/// INTERNAL_API_SECRET. Called by backend-school for every request, so the
+/// platform audit log shows what support did inside the school
+pub async fn record_support_activity_internal(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<uuid::Uuid>,
+    Json(activity): Json<SupportActivity>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    verify_internal_secret(&headers)?;
+
+    let (session, school_name) = SupportSessionService::new(state.pool.clone())
+        .record_activity(id, &activity)
+        .await
+        .map_err(|e| match e {
+            crate::error::AppError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+        })?;
+
+    AuditService::new(state.pool.clone())
+        .record_as(
+            session.admin_user_id,
+            &session.admin_name,
+            "support.request",
+            AuditTarget::school(session.school_id, &school_name),
+            serde_json::json!({
+                "supportSessionId": session.id,
+                "ticketReference": session.ticket_reference,
+                "method": activity.method,
+                "path": activity.path,
+                "status": activity.status,
+                "occurredAt": activity.occurred_at,
+            }),
+        )
+        .await;
+
+    Ok(StatusCode::OK)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,4 +345,35 @@ mod tests {
 
         assert!(verify_internal_secret(&headers("shared-secret", Some("backend-school"))).is_ok());
     }
+
+    fn school_row(plan_code: Option<&str>) -> SchoolRow {
+        SchoolRow {
+            id: uuid::Uuid::nil(),
+            subdomain: "demo".to_string(),
+            name: "Demo School".to_string(),
+            status: "active".to_string(),
+            db_connection_string: None,
+            migration_version: Some(12),
+            migration_status: Some("migrated".to_string()),
+            last_migrated_at: None,
+            migration_error: None,
+            plan_code: plan_code.map(String::from),
+            plan_max_students: Some(800),
+            plan_max_storage_bytes: None,
+            plan_max_certificates_per_year: Some(3000),
+            plan_enabled_modules: Some(vec!["student".to_string()]),
+        }
+    }
+
+    #[test]
+    fn school_info_carries_plan_only_when_assigned() {
+        let unassigned = SchoolInfo::from(school_row(None));
+        assert!(unassigned.plan.is_none());
+
+        let plan = SchoolInfo::from(school_row(Some("basic"))).plan.unwrap();
+        assert_eq!(plan.code, "basic");
+        assert_eq!(plan.max_students, Some(800));
+        assert_eq!(plan.max_storage_bytes, None);
+        assert_eq!(plan.enabled_modules, Some(vec!["student".to_string()]));
+    }
 }
@@ -0,0 +1,79 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, KeyRotationService, SchoolService};
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        AppError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// Recent key rotation runs of a school and how many values remain on older keys
+pub async fn get_school_key_rotation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = KeyRotationService::new(state.pool.clone());
+
+    match service.school_key_rotation(&school).await {
+        Ok(rotation) => (StatusCode::OK, Json(ApiResponse::success(rotation))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Start or resume re-encrypting a school under its active keys in the background
+pub async fn start_key_rotation(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = KeyRotationService::new(state.pool.clone());
+
+    match service.start_rotation(&admin, &school).await {
+        Ok(run) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "school.key_rotation",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({ "keyRotationId": run.id }),
+                )
+                .await;
+            (StatusCode::ACCEPTED, Json(ApiResponse::success(run))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,142 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::CreateMigrationCampaign;
+use crate::services::MigrationCampaignService;
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// Plan a migration campaign over all outdated schools
+pub async fn create_campaign(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Json(data): Json<CreateMigrationCampaign>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.create_campaign(data).await {
+        Ok(campaign) => (StatusCode::CREATED, Json(ApiResponse::success(campaign))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// List recent migration campaigns
+pub async fn list_campaigns(State(state): State<AppState>) -> Response {
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.list_campaigns().await {
+        Ok(campaigns) => (StatusCode::OK, Json(ApiResponse::success(campaigns))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Get migration campaign by ID
+pub async fn get_campaign(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.get_campaign(id).await {
+        Ok(campaign) => (StatusCode::OK, Json(ApiResponse::success(campaign))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// List the schools of a campaign with their per-tenant results
+pub async fn list_campaign_tenants(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.list_campaign_tenants(id).await {
+        Ok(tenants) => (StatusCode::OK, Json(ApiResponse::success(tenants))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Cancel a pending or paused campaign
+pub async fn cancel_campaign(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.cancel_campaign(id).await {
+        Ok(campaign) => (StatusCode::OK, Json(ApiResponse::success(campaign))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Migration history of a school across campaigns
+pub async fn list_school_migrations(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let service = MigrationCampaignService::new(state.pool.clone());
+
+    match service.list_school_history(id).await {
+        Ok(history) => (StatusCode::OK, Json(ApiResponse::success(history))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// SSE endpoint for running or resuming a campaign with real-time logs
+pub async fn run_campaign_sse(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    use crate::utils::sse::SseLogger;
+    use axum::response::sse::KeepAlive;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+
+    let (tx, rx) = mpsc::channel(100);
+    let logger = SseLogger::new(tx);
+    let pool = state.pool.clone();
+
+    tokio::spawn(async move {
+        let service = MigrationCampaignService::new(pool);
+
+        if let Err(e) = service.run_campaign_stream(id, logger.clone()).await {
+            let _ = logger.error_complete(e.to_string()).await;
+        }
+    });
+
+    axum::response::Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+        .into_response()
+}
@@ -1,5 +1,13 @@
+pub mod admin_account;
+pub mod audit;
 pub mod auth;
 pub mod health;
 pub mod internal;
+pub mod key_rotation;
+pub mod migration_campaign;
+pub mod plan;
 pub mod school;
 pub mod school_sse;
+pub mod snapshot;
+pub mod support_session;
+pub mod usage;
@@ -0,0 +1,127 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::{AssignPlan, SavePlan};
+use crate::services::PlanService;
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// Create a plan
+pub async fn create_plan(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Json(data): Json<SavePlan>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = PlanService::new(state.pool.clone());
+
+    match service.create_plan(data).await {
+        Ok(plan) => (StatusCode::CREATED, Json(ApiResponse::success(plan))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// List plans
+pub async fn list_plans(State(state): State<AppState>) -> Response {
+    let service = PlanService::new(state.pool.clone());
+
+    match service.list_plans().await {
+        Ok(plans) => (StatusCode::OK, Json(ApiResponse::success(plans))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Get plan by ID
+pub async fn get_plan(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+    let service = PlanService::new(state.pool.clone());
+
+    match service.get_plan(id).await {
+        Ok(plan) => (StatusCode::OK, Json(ApiResponse::success(plan))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Update plan limits and modules
+pub async fn update_plan(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+    Json(data): Json<SavePlan>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = PlanService::new(state.pool.clone());
+
+    match service.update_plan(id, data).await {
+        Ok(plan) => (StatusCode::OK, Json(ApiResponse::success(plan))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Delete a plan that no school uses
+pub async fn delete_plan(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = PlanService::new(state.pool.clone());
+
+    match service.delete_plan(id).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"success": true, "message": "Plan deleted"})),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Assign a plan to a school, or clear it for unlimited usage
+pub async fn assign_school_plan(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+    Json(data): Json<AssignPlan>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = PlanService::new(state.pool.clone());
+
+    match service.assign_plan(id, data.plan_id).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(serde_json::json!({"success": true, "message": "School plan updated"})),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -1,14 +1,17 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
 use crate::models::{CreateSchool, School, UpdateSchool};
-use crate::services::SchoolService;
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, SchoolService};
 use crate::types::ApiResponse;
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -39,12 +42,24 @@ pub struct SchoolListResponse {
 // Create school
 pub async fn create_school(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Json(data): Json<CreateSchool>,
 ) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.create_school(data).await {
         Ok(school) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "school.create",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({"subdomain": school.subdomain}),
+                )
+                .await;
             let response = ApiResponse::success(school);
             (StatusCode::CREATED, Json(response)).into_response()
         }
@@ -59,8 +74,12 @@ pub async fn create_school(
 // List schools with pagination
 pub async fn list_schools(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Query(params): Query<PaginationQuery>,
 ) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ViewSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.list_schools(params.page, params.limit).await {
@@ -84,7 +103,14 @@ pub async fn list_schools(
 }
 
 // Get school by ID
-pub async fn get_school(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+pub async fn get_school(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ViewSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.get_school(id).await {
@@ -103,13 +129,30 @@ pub async fn get_school(State(state): State<AppState>, Path(id): Path<Uuid>) ->
 // Update school
 pub async fn update_school(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Path(id): Path<Uuid>,
     Json(data): Json<UpdateSchool>,
 ) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
+    let details = json!({
+        "name": data.name,
+        "status": data.status,
+        "configChanged": data.config.is_some(),
+    });
 
     match service.update_school(id, data).await {
         Ok(school) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "school.update",
+                    AuditTarget::school(school.id, &school.name),
+                    details,
+                )
+                .await;
             let response = ApiResponse::success(school);
             (StatusCode::OK, Json(response)).into_response()
         }
@@ -122,15 +165,44 @@ pub async fn update_school(
 }
 
 // Delete school
-pub async fn delete_school(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+pub async fn delete_school(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::DeleteSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
+    // Fetched first so the audit entry keeps the school's name
+    let school = match service.get_school(id).await {
+        Ok(school) => school,
+        Err(_) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "School not found"})),
+            )
+                .into_response()
+        }
+    };
+
     match service.delete_school(id).await {
-        Ok(_) => (
-            StatusCode::OK,
-            Json(serde_json::json!({"success": true, "message": "School deleted"})),
-        )
-            .into_response(),
+        Ok(_) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "school.delete",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({"subdomain": school.subdomain}),
+                )
+                .await;
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({"success": true, "message": "School deleted"})),
+            )
+                .into_response()
+        }
         Err(_) => (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({"error": "School not found"})),
@@ -140,11 +212,31 @@ pub async fn delete_school(State(state): State<AppState>, Path(id): Path<Uuid>)
 }
 
 // Deploy/Redeploy school frontend
-pub async fn deploy_school(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+pub async fn deploy_school(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.deploy_school(id).await {
         Ok(result) => {
+            let label = service.get_school(id).await.ok().map(|school| school.name);
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "school.deploy",
+                    AuditTarget {
+                        target_type: "school",
+                        target_id: Some(id),
+                        label: label.as_deref(),
+                    },
+                    json!({"deploymentUrl": result.deployment_url}),
+                )
+                .await;
             let response = ApiResponse::success(result);
             (StatusCode::OK, Json(response)).into_response()
         }
@@ -164,12 +256,32 @@ pub struct BulkDeployRequest {
 // Bulk deploy multiple schools
 pub async fn bulk_deploy_schools(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Json(data): Json<BulkDeployRequest>,
 ) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.bulk_deploy_schools(data.school_ids).await {
         Ok(results) => {
+            // One entry per school so each school's history shows the deploy
+            let audit = AuditService::new(state.pool.clone());
+            for result in results.successful.iter().chain(results.failed.iter()) {
+                audit
+                    .record(
+                        &admin,
+                        "school.deploy",
+                        AuditTarget::school(result.school_id, &result.school_name),
+                        json!({
+                            "bulk": true,
+                            "success": result.success,
+                            "message": result.message,
+                        }),
+                    )
+                    .await;
+            }
             let response = ApiResponse::success(results);
             (StatusCode::OK, Json(response)).into_response()
         }
@@ -184,8 +296,12 @@ pub async fn bulk_deploy_schools(
 // Get deployment history for a school
 pub async fn get_deployment_history(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Path(id): Path<Uuid>,
 ) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::ViewSchools) {
+        return denied.into_response();
+    }
     let service = SchoolService::new(state.pool.clone());
 
     match service.get_deployment_history(id).await {
@@ -1,67 +1,109 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
 use crate::models::CreateSchool;
-use crate::services::SchoolService;
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, SchoolService};
 use crate::AppState;
 use axum::{
     extract::{Path, State},
-    response::Json,
+    response::{IntoResponse, Json, Response},
+    Extension,
 };
+use serde_json::json;
 use uuid::Uuid;
 
 // SSE endpoint for creating school with real-time logs
 pub async fn create_school_sse(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Json(data): Json<CreateSchool>,
-) -> axum::response::Sse<
-    impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
-> {
+) -> Response {
     use crate::utils::sse::SseLogger;
     use axum::response::sse::KeepAlive;
     use std::time::Duration;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
 
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+
     let (tx, rx) = mpsc::channel(100);
     let logger = SseLogger::new(tx);
     let pool = state.pool.clone();
 
     // Spawn background task
     tokio::spawn(async move {
-        let service = SchoolService::new(pool);
+        let service = SchoolService::new(pool.clone());
 
-        if let Err(e) = service.create_school_stream(data, logger.clone()).await {
-            let _ = logger.error_complete(e.to_string()).await;
+        match service.create_school_stream(data, logger.clone()).await {
+            Ok(school) => {
+                AuditService::new(pool)
+                    .record(
+                        &admin,
+                        "school.create",
+                        AuditTarget::school(school.id, &school.name),
+                        json!({"subdomain": school.subdomain}),
+                    )
+                    .await;
+            }
+            Err(e) => {
+                let _ = logger.error_complete(e.to_string()).await;
+            }
         }
     });
 
     axum::response::Sse::new(ReceiverStream::new(rx))
         .keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+        .into_response()
 }
 
 // SSE endpoint for deleting school with real-time logs
 pub async fn delete_school_sse(
     State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
     Path(id): Path<Uuid>,
-) -> axum::response::Sse<
-    impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
-> {
+) -> Response {
     use crate::utils::sse::SseLogger;
     use axum::response::sse::KeepAlive;
     use std::time::Duration;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
 
+    if let Err(denied) = admin.require(AdminPermission::DeleteSchools) {
+        return denied.into_response();
+    }
+
     let (tx, rx) = mpsc::channel(100);
     let logger = SseLogger::new(tx);
     let pool = state.pool.clone();
 
     tokio::spawn(async move {
-        let service = SchoolService::new(pool);
+        let service = SchoolService::new(pool.clone());
+        // Looked up before deletion so the audit entry keeps the school's name
+        let school = service.get_school(id).await.ok();
 
-        if let Err(e) = service.delete_school_stream(id, logger.clone()).await {
-            let _ = logger.error_complete(e.to_string()).await;
+        match service.delete_school_stream(id, logger.clone()).await {
+            Ok(()) => {
+                AuditService::new(pool)
+                    .record(
+                        &admin,
+                        "school.delete",
+                        AuditTarget {
+                            target_type: "school",
+                            target_id: Some(id),
+                            label: school.as_ref().map(|school| school.name.as_str()),
+                        },
+                        json!({"subdomain": school.as_ref().map(|school| &school.subdomain)}),
+                    )
+                    .await;
+            }
+            Err(e) => {
+                let _ = logger.error_complete(e.to_string()).await;
+            }
         }
     });
 
     axum::response::Sse::new(ReceiverStream::new(rx))
         .keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+        .into_response()
 }
@@ -0,0 +1,165 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::RestoreSnapshot;
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, SchoolService, SnapshotService};
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// List snapshots taken from a school
+pub async fn list_school_snapshots(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let service = SnapshotService::new(state.pool.clone());
+
+    match service.list_snapshots(id).await {
+        Ok(snapshots) => (StatusCode::OK, Json(ApiResponse::success(snapshots))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Get snapshot by ID
+pub async fn get_snapshot(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+    let service = SnapshotService::new(state.pool.clone());
+
+    match service.get_snapshot(id).await {
+        Ok(snapshot) => (StatusCode::OK, Json(ApiResponse::success(snapshot))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// List restore runs of a snapshot
+pub async fn list_snapshot_restores(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let service = SnapshotService::new(state.pool.clone());
+
+    match service.list_restores(id).await {
+        Ok(restores) => (StatusCode::OK, Json(ApiResponse::success(restores))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// SSE endpoint for taking a school snapshot with real-time logs
+pub async fn create_snapshot_sse(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    use crate::utils::sse::SseLogger;
+    use axum::response::sse::KeepAlive;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+
+    let (tx, rx) = mpsc::channel(100);
+    let logger = SseLogger::new(tx);
+    let pool = state.pool.clone();
+
+    tokio::spawn(async move {
+        let service = SnapshotService::new(pool);
+
+        if let Err(e) = service.create_snapshot_stream(id, logger.clone()).await {
+            let _ = logger.error_complete(e.to_string()).await;
+        }
+    });
+
+    axum::response::Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+        .into_response()
+}
+
+// SSE endpoint for restoring a snapshot with real-time logs
+pub async fn restore_snapshot_sse(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+    Json(data): Json<RestoreSnapshot>,
+) -> Response {
+    use crate::utils::sse::SseLogger;
+    use axum::response::sse::KeepAlive;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+
+    let (tx, rx) = mpsc::channel(100);
+    let logger = SseLogger::new(tx);
+    let pool = state.pool.clone();
+
+    tokio::spawn(async move {
+        let service = SnapshotService::new(pool.clone());
+
+        match service
+            .restore_snapshot_stream(id, data, logger.clone())
+            .await
+        {
+            Ok(restore) if restore.anonymized => {
+                let Some(school_id) = restore.target_school_id else {
+                    return;
+                };
+                let school = SchoolService::new(pool.clone())
+                    .get_school(school_id)
+                    .await
+                    .ok();
+                AuditService::new(pool)
+                    .record(
+                        &admin,
+                        "school.sandbox_clone",
+                        AuditTarget {
+                            target_type: "school",
+                            target_id: Some(school_id),
+                            label: school.as_ref().map(|school| school.name.as_str()),
+                        },
+                        json!({
+                            "snapshotId": restore.snapshot_id,
+                            "restoreId": restore.id,
+                            "anonymization": restore.anonymization_summary,
+                        }),
+                    )
+                    .await;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let _ = logger.error_complete(e.to_string()).await;
+            }
+        }
+    });
+
+    axum::response::Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(5)))
+        .into_response()
+}
@@ -0,0 +1,171 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::CreateSupportSession;
+use crate::services::audit_service::AuditTarget;
+use crate::services::{AuditService, SchoolService, SupportSessionService};
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        AppError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// List support sessions opened into a school
+pub async fn list_support_sessions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let service = SupportSessionService::new(state.pool.clone());
+
+    match service.list_sessions(id).await {
+        Ok(sessions) => (StatusCode::OK, Json(ApiResponse::success(sessions))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Ask a school for a support session; requires a ticket reference
+pub async fn create_support_session(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path(id): Path<Uuid>,
+    Json(data): Json<CreateSupportSession>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = SupportSessionService::new(state.pool.clone());
+
+    match service.create_session(&admin, &school, data).await {
+        Ok(session) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "support.create",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({
+                        "supportSessionId": session.id,
+                        "ticketReference": session.ticket_reference,
+                        "scope": session.scope,
+                        "permissions": session.permissions,
+                        "durationMinutes": session.duration_minutes,
+                        "requiresApproval": session.requires_approval,
+                        "targetUsername": session.target_username,
+                    }),
+                )
+                .await;
+            (StatusCode::CREATED, Json(ApiResponse::success(session))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// Get a support session, refreshed from the school
+pub async fn get_support_session(
+    State(state): State<AppState>,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = SupportSessionService::new(state.pool.clone());
+
+    match service.refresh_session(&school, session_id).await {
+        Ok(session) => (StatusCode::OK, Json(ApiResponse::success(session))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Issue the one-time link that signs the support agent into the school
+pub async fn issue_support_handoff(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = SupportSessionService::new(state.pool.clone());
+
+    match service.issue_handoff(&school, session_id).await {
+        Ok(handoff) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "support.handoff",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({
+                        "supportSessionId": handoff.session.id,
+                        "ticketReference": handoff.session.ticket_reference,
+                    }),
+                )
+                .await;
+            (StatusCode::OK, Json(ApiResponse::success(handoff))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// End a support session and sign the agent out of the school
+pub async fn end_support_session(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let school = match SchoolService::new(state.pool.clone()).get_school(id).await {
+        Ok(school) => school,
+        Err(e) => return error_response(e),
+    };
+    let service = SupportSessionService::new(state.pool.clone());
+
+    match service.end_session(&school, session_id).await {
+        Ok(session) => {
+            AuditService::new(state.pool.clone())
+                .record(
+                    &admin,
+                    "support.end",
+                    AuditTarget::school(school.id, &school.name),
+                    json!({
+                        "supportSessionId": session.id,
+                        "ticketReference": session.ticket_reference,
+                        "requestCount": session.request_count,
+                    }),
+                )
+                .await;
+            (StatusCode::OK, Json(ApiResponse::success(session))).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,94 @@
+use crate::auth::{AdminPermission, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::services::UsageService;
+use crate::types::ApiResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct TrendQuery {
+    pub days: Option<i64>,
+}
+
+fn error_response(error: AppError) -> Response {
+    let status = match error {
+        AppError::NotFound(_) => StatusCode::NOT_FOUND,
+        AppError::DatabaseError(_) | AppError::InternalServerError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+
+    (
+        status,
+        Json(serde_json::json!({"error": error.to_string()})),
+    )
+        .into_response()
+}
+
+// Latest usage and health flags of every active school
+pub async fn get_usage_overview(State(state): State<AppState>) -> Response {
+    let service = UsageService::new(state.pool.clone());
+
+    match service.overview().await {
+        Ok(overview) => (StatusCode::OK, Json(ApiResponse::success(overview))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Schools nobody has signed in to recently
+pub async fn list_inactive_schools(State(state): State<AppState>) -> Response {
+    let service = UsageService::new(state.pool.clone());
+
+    match service.inactive_schools().await {
+        Ok(schools) => (StatusCode::OK, Json(ApiResponse::success(schools))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Schools near or over the configured usage limits
+pub async fn list_quota_alerts(State(state): State<AppState>) -> Response {
+    let service = UsageService::new(state.pool.clone());
+
+    match service.quota_alerts().await {
+        Ok(alerts) => (StatusCode::OK, Json(ApiResponse::success(alerts))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Collect a usage sample from every active school now
+pub async fn collect_usage(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AuthenticatedAdmin>,
+) -> Response {
+    if let Err(denied) = admin.require(AdminPermission::OperateSchools) {
+        return denied.into_response();
+    }
+    let service = UsageService::new(state.pool.clone());
+
+    match service.collect_all().await {
+        Ok(summary) => (StatusCode::OK, Json(ApiResponse::success(summary))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+// Usage samples of one school over time
+pub async fn get_school_usage(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<TrendQuery>,
+) -> Response {
+    let service = UsageService::new(state.pool.clone());
+
+    match service.school_trend(id, query.days).await {
+        Ok(samples) => (StatusCode::OK, Json(ApiResponse::success(samples))).into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -1,4 +1,6 @@
-use backend_admin::{build_app, db::init_admin_pool, AppState};
+use backend_admin::{
+    build_app, db::init_admin_pool, services::usage_service::spawn_usage_collector, AppState,
+};
 use dotenv::dotenv;
 use std::env;
 use tracing::info;
@@ -31,6 +33,8 @@ async fn main() {
 
     info!("database migrations completed");
 
+    spawn_usage_collector(pool.clone());
+
     info!("services initialized");
     info!("CORS handling delegated to nginx reverse proxy");
 
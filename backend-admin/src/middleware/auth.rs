@@ -1,44 +1,71 @@
 use crate::auth::validate_token;
+use crate::error::AppError;
+use crate::services::AuthService;
+use crate::AppState;
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
 use tower_cookies::Cookies;
+use uuid::Uuid;
 
-pub async fn require_auth(cookies: Cookies, request: Request, next: Next) -> Response {
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({
+            "error": message
+        })),
+    )
+        .into_response()
+}
+
+/// Validate the auth cookie against a live admin session. The role is read
+/// from the database, so role changes and revocations apply immediately.
+pub async fn require_auth(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    mut request: Request,
+    next: Next,
+) -> Response {
     // Get auth_token from cookies
     let token = match cookies.get("auth_token") {
         Some(cookie) => cookie.value().to_string(),
-        None => {
-            return (
-                StatusCode::UNAUTHORIZED,
+        None => return unauthorized("Unauthorized - No auth token"),
+    };
+
+    let claims = match validate_token(&token) {
+        Ok(claims) => claims,
+        Err(_) => return unauthorized("Unauthorized - Invalid token"),
+    };
+
+    let (admin_id, session_id) = match (Uuid::parse_str(&claims.sub), Uuid::parse_str(&claims.sid))
+    {
+        (Ok(admin_id), Ok(session_id)) => (admin_id, session_id),
+        _ => return unauthorized("Unauthorized - Invalid token"),
+    };
+
+    let auth_service = AuthService::new(state.pool.clone());
+    match auth_service
+        .authenticate_session(admin_id, session_id)
+        .await
+    {
+        Ok(admin) => {
+            request.extensions_mut().insert(admin);
+            next.run(request).await
+        }
+        Err(AppError::DatabaseError(e)) => {
+            tracing::error!("Failed to load admin session: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({
-                    "error": "Unauthorized - No auth token"
+                    "error": "Failed to verify session"
                 })),
             )
-                .into_response();
+                .into_response()
         }
-    };
-
-    // Validate token
-    match validate_token(&token) {
-        Ok(claims) if claims.role.can_access_admin_backend() => next.run(request).await,
-        Ok(_) => (
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({
-                "error": "Forbidden - Admin role required"
-            })),
-        )
-            .into_response(),
-        Err(_) => (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({
-                "error": "Unauthorized - Invalid token"
-            })),
-        )
-            .into_response(),
+        Err(_) => unauthorized("Unauthorized - Session expired or revoked"),
     }
 }
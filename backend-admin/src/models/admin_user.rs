@@ -11,10 +11,28 @@ pub struct AdminUser {
     pub password_hash: String,
     pub name: String,
     pub role: String,
+    pub email: Option<String>,
+    #[serde(skip_serializing)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
+    pub totp_last_used_step: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Admin account as listed to owners; national IDs are not exposed
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AdminAccount {
+    pub id: Uuid,
+    pub name: String,
+    pub email: Option<String>,
+    pub role: String,
+    pub mfa_enabled: bool,
+    pub active_sessions: i64,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateAdminUser {
@@ -28,4 +46,95 @@ pub struct CreateAdminUser {
 pub struct LoginRequest {
     pub national_id: String,
     pub password: String,
+    /// Required once the account has enabled MFA
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AdminSession {
+    pub id: Uuid,
+    pub admin_user_id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AdminInvitation {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub accepted_admin_id: Option<Uuid>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminInvitationCreated {
+    pub invitation: AdminInvitation,
+    /// Single-use link; shown once so the owner can pass it on when no mail is sent
+    pub invite_url: String,
+    pub email_sent: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAdminInvitation {
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptAdminInvitation {
+    pub token: String,
+    pub national_id: String,
+    pub name: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAdminRole {
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaSetup {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AdminAuditEntry {
+    pub id: Uuid,
+    pub actor_admin_id: Option<Uuid>,
+    pub actor_name: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub target_label: Option<String>,
+    pub details: sqlx::types::Json<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    pub before: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
 }
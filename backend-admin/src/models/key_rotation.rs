@@ -0,0 +1,37 @@
+use crate::clients::backend_school_client::KeyRotationStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TenantKeyRotation {
+    pub id: Uuid,
+    pub school_id: Uuid,
+    pub admin_user_id: Uuid,
+    pub admin_name: String,
+    /// `running`, `completed` or `failed`
+    pub status: String,
+    /// Keys the school reported as active; unknown until its first reply
+    pub encryption_key_id: Option<String>,
+    pub blind_index_key_id: Option<String>,
+    pub rows_scanned: i64,
+    pub rows_rewritten: i64,
+    pub rows_failed: i64,
+    /// Latest row the school could not rotate: table, column and row id
+    pub last_row_error: Option<String>,
+    /// Why the run stopped before completing
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Recent runs of a school together with the school's live key status
+#[derive(Debug, Clone, Serialize)]
+pub struct SchoolKeyRotation {
+    pub runs: Vec<TenantKeyRotation>,
+    pub status: Option<KeyRotationStatus>,
+    /// Set when the school could not report its status
+    pub status_error: Option<String>,
+}
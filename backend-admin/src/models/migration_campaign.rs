@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MigrationCampaign {
+    pub id: Uuid,
+    pub target_version: i32,
+    pub wave_size: i32,
+    pub concurrency: i32,
+    pub status: String,
+    pub current_wave: i32,
+    pub paused_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MigrationCampaignTenant {
+    pub id: Uuid,
+    pub campaign_id: Uuid,
+    pub school_id: Uuid,
+    pub subdomain: String,
+    pub wave: i32,
+    pub status: String,
+    pub from_version: Option<i32>,
+    pub applied_version: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMigrationCampaign {
+    pub target_version: i32,
+    /// Schools migrated first, in wave 0; the campaign pauses after them.
+    #[serde(default)]
+    pub canary_school_ids: Vec<Uuid>,
+    #[serde(default = "default_wave_size")]
+    pub wave_size: i32,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: i32,
+}
+
+fn default_wave_size() -> i32 {
+    25
+}
+
+fn default_concurrency() -> i32 {
+    4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CreateMigrationCampaign;
+
+    #[test]
+    fn campaign_request_defaults_waves_and_concurrency() {
+        let request: CreateMigrationCampaign =
+            serde_json::from_str(r#"{"targetVersion":31}"#).unwrap();
+
+        assert_eq!(request.target_version, 31);
+        assert!(request.canary_school_ids.is_empty());
+        assert_eq!(request.wave_size, 25);
+        assert_eq!(request.concurrency, 4);
+    }
+}
@@ -1,7 +1,19 @@
 pub mod admin_user;
 pub mod deployment;
+pub mod key_rotation;
+pub mod migration_campaign;
+pub mod plan;
 pub mod school;
+pub mod snapshot;
+pub mod support_session;
+pub mod usage;
 
 pub use admin_user::*;
 pub use deployment::*;
+pub use key_rotation::*;
+pub use migration_campaign::*;
+pub use plan::*;
 pub use school::*;
+pub use snapshot::*;
+pub use support_session::*;
+pub use usage::*;
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Plan {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub max_students: Option<i64>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_certificates_per_year: Option<i64>,
+    /// Permission module codes; `None` enables every module
+    pub enabled_modules: Option<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Limits as delivered to backend-school with the school lookup
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TenantPlan {
+    pub code: String,
+    pub max_students: Option<i64>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_certificates_per_year: Option<i64>,
+    pub enabled_modules: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePlan {
+    pub code: String,
+    pub name: String,
+    pub max_students: Option<i64>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_certificates_per_year: Option<i64>,
+    pub enabled_modules: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignPlan {
+    /// `None` removes the plan and leaves the school unrestricted
+    pub plan_id: Option<Uuid>,
+}
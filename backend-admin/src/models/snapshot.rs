@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TenantSnapshot {
+    pub id: Uuid,
+    pub school_id: Option<Uuid>,
+    pub source_tenant_id: Uuid,
+    pub source_subdomain: String,
+    pub status: String,
+    pub format_version: Option<i32>,
+    pub schema_version: Option<i64>,
+    pub captured_at: Option<DateTime<Utc>>,
+    pub manifest_checksum: Option<String>,
+    pub table_count: Option<i32>,
+    pub row_count: Option<i64>,
+    pub object_count: Option<i32>,
+    pub total_bytes: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TenantSnapshotRestore {
+    pub id: Uuid,
+    pub snapshot_id: Uuid,
+    pub target_school_id: Option<Uuid>,
+    pub replace_existing: bool,
+    pub anonymized: bool,
+    pub anonymization_summary: Option<serde_json::Value>,
+    pub status: String,
+    pub schema_version: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSnapshot {
+    /// Defaults to the school the snapshot was taken from.
+    #[serde(default)]
+    pub target_school_id: Option<Uuid>,
+    /// Must be set when the target tenant already holds school data.
+    #[serde(default)]
+    pub replace_existing: bool,
+    /// Makes the restore a sandbox clone with personal data replaced.
+    #[serde(default)]
+    pub anonymization: Option<SandboxAnonymization>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxAnonymization {
+    /// Every account in the sandbox signs in with this password.
+    pub sandbox_password: String,
+}
+
+impl std::fmt::Debug for SandboxAnonymization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SandboxAnonymization")
+            .field("sandbox_password", &"[redacted]")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RestoreSnapshot;
+
+    #[test]
+    fn restore_request_defaults_to_source_school_without_overwrite() {
+        let request: RestoreSnapshot = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(request.target_school_id, None);
+        assert!(!request.replace_existing);
+        assert!(request.anonymization.is_none());
+    }
+
+    #[test]
+    fn sandbox_password_is_redacted_from_debug_output() {
+        let request: RestoreSnapshot =
+            serde_json::from_str(r#"{"anonymization":{"sandboxPassword":"training-2026!"}}"#)
+                .unwrap();
+
+        assert_eq!(
+            request
+                .anonymization
+                .as_ref()
+                .map(|anonymization| anonymization.sandbox_password.as_str()),
+            Some("training-2026!")
+        );
+        assert!(!format!("{:?}", request).contains("training-2026!"));
+    }
+
+    #[test]
+    fn restore_request_reads_camel_case_fields() {
+        let request: RestoreSnapshot = serde_json::from_str(
+            r#"{"targetSchoolId":"11111111-1111-1111-1111-111111111111","replaceExisting":true}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.target_school_id.map(|id| id.to_string()).as_deref(),
+            Some("11111111-1111-1111-1111-111111111111")
+        );
+        assert!(request.replace_existing);
+    }
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SupportSession {
+    pub id: Uuid,
+    pub school_id: Uuid,
+    pub admin_user_id: Uuid,
+    pub admin_name: String,
+    pub ticket_reference: String,
+    pub reason: Option<String>,
+    /// `read_only` or `scoped`
+    pub scope: String,
+    /// School permission codes a scoped session may use
+    pub permissions: Vec<String>,
+    pub duration_minutes: i32,
+    pub requires_approval: bool,
+    pub target_username: Option<String>,
+    /// Display name of the school user being assisted, as reported by the school
+    pub target_user_name: Option<String>,
+    /// Last status reported by the school: pending, approved, rejected, active or ended
+    pub status: String,
+    pub decision_note: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub request_count: i32,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSupportSession {
+    pub ticket_reference: String,
+    pub reason: Option<String>,
+    pub scope: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    pub duration_minutes: i32,
+    /// Wait for a school administrator to approve before the session can start
+    #[serde(default)]
+    pub requires_approval: bool,
+    /// School user to act as; defaults to the school's first administrator
+    pub target_username: Option<String>,
+}
+
+/// Link that signs the support agent into the school for this session
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportHandoff {
+    pub session: SupportSession,
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A request made during a support session, reported by backend-school
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportActivity {
+    pub subdomain: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub occurred_at: DateTime<Utc>,
+}
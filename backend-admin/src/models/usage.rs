@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TenantUsageSample {
+    pub id: Uuid,
+    pub school_id: Uuid,
+    pub collected_at: DateTime<Utc>,
+    pub active_users_7d: i64,
+    pub active_users_30d: i64,
+    pub student_count: i64,
+    pub staff_count: i64,
+    pub storage_bytes: i64,
+    /// Bytes per File Platform purpose code
+    pub storage_bytes_by_purpose: Json<BTreeMap<String, i64>>,
+    pub certificates_issued: i64,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub migration_version: Option<i32>,
+    pub database_bytes: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaAlertLevel {
+    Warning,
+    Exceeded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaAlert {
+    pub school_id: Uuid,
+    pub subdomain: String,
+    /// `storage_bytes`, `database_bytes` or `student_count`
+    pub metric: String,
+    pub current: i64,
+    pub limit: i64,
+    pub level: QuotaAlertLevel,
+}
+
+/// Latest sample of one school with its health flags
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantUsageOverview {
+    pub school_id: Uuid,
+    pub name: String,
+    pub subdomain: String,
+    pub latest: Option<TenantUsageSample>,
+    /// No sample was collected recently; the tenant may be unreachable
+    pub stale: bool,
+    pub inactive: bool,
+    pub alerts: Vec<QuotaAlert>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageCollectionFailure {
+    pub school_id: Uuid,
+    pub subdomain: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageCollectionSummary {
+    pub collected: usize,
+    pub failed: Vec<UsageCollectionFailure>,
+    pub pruned: u64,
+}
@@ -0,0 +1,517 @@
+use crate::auth::{hash_password, totp, AdminRole, AuthenticatedAdmin};
+use crate::error::AppError;
+use crate::models::{
+    AcceptAdminInvitation, AdminAccount, AdminInvitation, AdminInvitationCreated, AdminSession,
+    AdminUser, CreateAdminInvitation, MfaSetup,
+};
+use crate::services::AuthService;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+const TOTP_ISSUER: &str = "SchoolOrbit Admin";
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+fn invitation_ttl_hours() -> i64 {
+    env::var("ADMIN_INVITATION_TTL_HOURS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(72)
+}
+
+fn invite_url(token: &str) -> String {
+    let base = env::var("ADMIN_INVITE_URL_BASE")
+        .unwrap_or_else(|_| "https://admin.schoolorbit.app/invite".to_string());
+    format!("{}?token={}", base.trim_end_matches('/'), token)
+}
+
+/// Random single-use invitation token, hex encoded
+fn generate_invitation_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Only this digest is stored, so a database leak does not expose live invitations
+fn hash_invitation_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+fn normalize_email(email: &str) -> Result<String, AppError> {
+    let email = email.trim().to_lowercase();
+    let valid = email.len() <= 255
+        && email.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+        })
+        && !email.contains(char::is_whitespace);
+
+    if !valid {
+        return Err(AppError::ValidationError(
+            "Invalid email address".to_string(),
+        ));
+    }
+    Ok(email)
+}
+
+fn parse_role(role: &str) -> Result<AdminRole, AppError> {
+    AdminRole::try_from(role.trim()).map_err(AppError::ValidationError)
+}
+
+/// The platform must always keep at least one owner who can manage admins
+fn removes_last_owner(current: AdminRole, next: AdminRole, owner_count: i64) -> bool {
+    current == AdminRole::Owner && next != AdminRole::Owner && owner_count <= 1
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(e.to_string())
+}
+
+pub struct AdminAccountService {
+    pool: PgPool,
+}
+
+impl AdminAccountService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_admins(&self) -> Result<Vec<AdminAccount>, AppError> {
+        sqlx::query_as::<_, AdminAccount>(
+            "SELECT a.id, a.name, a.email, a.role,
+                    a.totp_enabled_at IS NOT NULL AS mfa_enabled,
+                    COUNT(s.id) AS active_sessions,
+                    a.created_at
+             FROM admin_users a
+             LEFT JOIN admin_sessions s
+               ON s.admin_user_id = a.id AND s.revoked_at IS NULL AND s.expires_at > NOW()
+             GROUP BY a.id
+             ORDER BY a.created_at",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Change an admin's role; takes effect on their next request
+    pub async fn update_role(&self, admin_id: Uuid, role: &str) -> Result<AdminAccount, AppError> {
+        let next = parse_role(role)?;
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // Lock every owner so two concurrent demotions cannot both pass the check
+        let owners: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM admin_users WHERE role = 'owner' FOR UPDATE")
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(db_error)?;
+
+        let current: String =
+            sqlx::query_scalar("SELECT role FROM admin_users WHERE id = $1 FOR UPDATE")
+                .bind(admin_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| AppError::NotFound("Admin user not found".to_string()))?;
+        let current = AdminRole::try_from(current.as_str()).unwrap_or(AdminRole::Viewer);
+
+        if removes_last_owner(current, next, owners.len() as i64) {
+            return Err(AppError::ValidationError(
+                "Cannot demote the last owner".to_string(),
+            ));
+        }
+
+        sqlx::query("UPDATE admin_users SET role = $2, updated_at = NOW() WHERE id = $1")
+            .bind(admin_id)
+            .bind(next.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        self.list_admins()
+            .await?
+            .into_iter()
+            .find(|admin| admin.id == admin_id)
+            .ok_or_else(|| AppError::NotFound("Admin user not found".to_string()))
+    }
+
+    /// Active sessions of an admin, newest first
+    pub async fn list_sessions(&self, admin_id: Uuid) -> Result<Vec<AdminSession>, AppError> {
+        sqlx::query_as::<_, AdminSession>(
+            "SELECT * FROM admin_sessions
+             WHERE admin_user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+             ORDER BY last_seen_at DESC",
+        )
+        .bind(admin_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    pub async fn revoke_session(&self, admin_id: Uuid, session_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "UPDATE admin_sessions SET revoked_at = NOW()
+             WHERE id = $1 AND admin_user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(session_id)
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Session not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Store a new pending secret; MFA stays off until a code from it is confirmed
+    pub async fn setup_mfa(&self, admin: &AuthenticatedAdmin) -> Result<MfaSetup, AppError> {
+        let account = AuthService::new(self.pool.clone())
+            .get_admin_by_id(admin.admin_id)
+            .await?;
+        if account.totp_enabled_at.is_some() {
+            return Err(AppError::ValidationError(
+                "MFA is already enabled".to_string(),
+            ));
+        }
+
+        let secret = totp::generate_secret();
+        sqlx::query(
+            "UPDATE admin_users
+             SET totp_secret = $2, totp_last_used_step = NULL, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(admin.admin_id)
+        .bind(&secret)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let account_label = account.email.unwrap_or(account.name);
+        Ok(MfaSetup {
+            provisioning_uri: totp::provisioning_uri(TOTP_ISSUER, &account_label, &secret),
+            secret,
+        })
+    }
+
+    pub async fn enable_mfa(&self, admin_id: Uuid, code: &str) -> Result<(), AppError> {
+        let auth_service = AuthService::new(self.pool.clone());
+        let account = auth_service.get_admin_by_id(admin_id).await?;
+        if account.totp_enabled_at.is_some() {
+            return Err(AppError::ValidationError(
+                "MFA is already enabled".to_string(),
+            ));
+        }
+        let secret = account.totp_secret.ok_or_else(|| {
+            AppError::ValidationError("Start MFA setup before enabling it".to_string())
+        })?;
+
+        auth_service
+            .consume_totp_code(admin_id, &secret, code)
+            .await?;
+
+        sqlx::query(
+            "UPDATE admin_users SET totp_enabled_at = NOW(), updated_at = NOW() WHERE id = $1",
+        )
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    /// Turning MFA off needs a current code so a stolen session alone cannot do it
+    pub async fn disable_mfa(&self, admin_id: Uuid, code: &str) -> Result<(), AppError> {
+        let auth_service = AuthService::new(self.pool.clone());
+        let account = auth_service.get_admin_by_id(admin_id).await?;
+        let secret = match (account.totp_enabled_at, account.totp_secret) {
+            (Some(_), Some(secret)) => secret,
+            _ => return Err(AppError::ValidationError("MFA is not enabled".to_string())),
+        };
+
+        auth_service
+            .consume_totp_code(admin_id, &secret, code)
+            .await?;
+
+        sqlx::query(
+            "UPDATE admin_users
+             SET totp_secret = NULL, totp_enabled_at = NULL, totp_last_used_step = NULL,
+                 updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(admin_id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    pub async fn list_invitations(&self) -> Result<Vec<AdminInvitation>, AppError> {
+        sqlx::query_as::<_, AdminInvitation>(
+            "SELECT id, email, role, invited_by, created_at, expires_at, accepted_at,
+                    accepted_admin_id, revoked_at
+             FROM admin_invitations
+             ORDER BY created_at DESC
+             LIMIT 200",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Create an invitation and send its link through the mail webhook when
+    /// one is configured; the link is also returned once to the inviting owner
+    pub async fn create_invitation(
+        &self,
+        invited_by: &AuthenticatedAdmin,
+        data: CreateAdminInvitation,
+    ) -> Result<AdminInvitationCreated, AppError> {
+        let email = normalize_email(&data.email)?;
+        let role = parse_role(&data.role)?;
+
+        let taken: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM admin_users WHERE LOWER(email) = $1)")
+                .bind(&email)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(db_error)?;
+        if taken {
+            return Err(AppError::ValidationError(
+                "An admin with this email already exists".to_string(),
+            ));
+        }
+
+        let token = generate_invitation_token();
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(invitation_ttl_hours());
+
+        let invitation = sqlx::query_as::<_, AdminInvitation>(
+            "INSERT INTO admin_invitations (email, role, token_hash, invited_by, expires_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, email, role, invited_by, created_at, expires_at, accepted_at,
+                       accepted_admin_id, revoked_at",
+        )
+        .bind(&email)
+        .bind(role.as_str())
+        .bind(hash_invitation_token(&token))
+        .bind(invited_by.admin_id)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let invite_url = invite_url(&token);
+        let email_sent = self
+            .send_invitation_email(&invitation, &invited_by.name, &invite_url)
+            .await;
+
+        Ok(AdminInvitationCreated {
+            invitation,
+            invite_url,
+            email_sent,
+        })
+    }
+
+    async fn send_invitation_email(
+        &self,
+        invitation: &AdminInvitation,
+        inviter: &str,
+        invite_url: &str,
+    ) -> bool {
+        let Ok(webhook) = env::var("ADMIN_MAIL_WEBHOOK_URL") else {
+            return false;
+        };
+
+        let body = serde_json::json!({
+            "to": invitation.email,
+            "subject": "You are invited to SchoolOrbit Admin",
+            "text": format!(
+                "{} invited you to SchoolOrbit Admin as {}.\n\nAccept the invitation before {}:\n{}",
+                inviter,
+                invitation.role,
+                invitation.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                invite_url
+            ),
+        });
+
+        match reqwest::Client::new()
+            .post(&webhook)
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => true,
+            Ok(response) => {
+                tracing::warn!(
+                    "Invitation mail webhook returned {} for invitation {}",
+                    response.status(),
+                    invitation.id
+                );
+                false
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Invitation mail webhook failed for {}: {}",
+                    invitation.id,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    pub async fn revoke_invitation(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "UPDATE admin_invitations SET revoked_at = NOW()
+             WHERE id = $1 AND accepted_at IS NULL AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(
+                "Pending invitation not found".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Redeem an invitation token into a new admin account. The invitation row
+    /// is locked so a token can only ever be used once.
+    pub async fn accept_invitation(
+        &self,
+        data: AcceptAdminInvitation,
+    ) -> Result<(AdminUser, AdminInvitation), AppError> {
+        if !AuthService::validate_national_id(&data.national_id) {
+            return Err(AppError::ValidationError(
+                "Invalid national ID format. Must be 13 digits.".to_string(),
+            ));
+        }
+        let name = data.name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError("Name is required".to_string()));
+        }
+        if data.password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            )));
+        }
+
+        let invalid = || AppError::ValidationError("Invitation is invalid or expired".to_string());
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        let invitation = sqlx::query_as::<_, AdminInvitation>(
+            "SELECT id, email, role, invited_by, created_at, expires_at, accepted_at,
+                    accepted_admin_id, revoked_at
+             FROM admin_invitations
+             WHERE token_hash = $1
+             FOR UPDATE",
+        )
+        .bind(hash_invitation_token(&data.token))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(invalid)?;
+
+        if invitation.accepted_at.is_some()
+            || invitation.revoked_at.is_some()
+            || invitation.expires_at <= chrono::Utc::now()
+        {
+            return Err(invalid());
+        }
+
+        let password_hash = hash_password(&data.password)?;
+        let admin = sqlx::query_as::<_, AdminUser>(
+            "INSERT INTO admin_users (national_id, password_hash, name, role, email)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING *",
+        )
+        .bind(&data.national_id)
+        .bind(&password_hash)
+        .bind(name)
+        .bind(&invitation.role)
+        .bind(&invitation.email)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::ValidationError(
+                "An admin with this national ID or email already exists".to_string(),
+            ),
+            e => db_error(e),
+        })?;
+
+        let invitation = sqlx::query_as::<_, AdminInvitation>(
+            "UPDATE admin_invitations
+             SET accepted_at = NOW(), accepted_admin_id = $2
+             WHERE id = $1
+             RETURNING id, email, role, invited_by, created_at, expires_at, accepted_at,
+                       accepted_admin_id, revoked_at",
+        )
+        .bind(invitation.id)
+        .bind(admin.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok((admin, invitation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_invitation_token, hash_invitation_token, normalize_email, removes_last_owner,
+    };
+    use crate::auth::AdminRole;
+
+    #[test]
+    fn invitation_tokens_are_random_and_stored_as_sha256() {
+        let token = generate_invitation_token();
+
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_invitation_token());
+        assert_eq!(
+            hash_invitation_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash_invitation_token(&token).len(), 64);
+    }
+
+    #[test]
+    fn last_owner_cannot_be_demoted() {
+        assert!(removes_last_owner(AdminRole::Owner, AdminRole::Operator, 1));
+        assert!(!removes_last_owner(
+            AdminRole::Owner,
+            AdminRole::Operator,
+            2
+        ));
+        assert!(!removes_last_owner(AdminRole::Owner, AdminRole::Owner, 1));
+        assert!(!removes_last_owner(
+            AdminRole::Viewer,
+            AdminRole::Operator,
+            1
+        ));
+    }
+
+    #[test]
+    fn invitation_emails_are_normalized() {
+        assert_eq!(
+            normalize_email(" Ops@School.Example ").unwrap(),
+            "ops@school.example"
+        );
+        assert!(normalize_email("not-an-email").is_err());
+        assert!(normalize_email("a@localhost").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::auth::AuthenticatedAdmin;
+use crate::error::AppError;
+use crate::models::{AdminAuditEntry, AuditLogQuery};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+/// Keys never written to the audit log, even when a caller passes the raw
+/// request body as details
+const REDACTED_KEYS: &[&str] = &[
+    "password",
+    "adminPassword",
+    "admin_password",
+    "nationalId",
+    "national_id",
+    "adminNationalId",
+    "admin_national_id",
+    "token",
+    "totpCode",
+    "code",
+];
+
+/// Target of an audited action, copied into the entry so it stays readable
+/// after the target is deleted
+pub struct AuditTarget<'a> {
+    pub target_type: &'a str,
+    pub target_id: Option<Uuid>,
+    pub label: Option<&'a str>,
+}
+
+impl<'a> AuditTarget<'a> {
+    pub fn school(id: Uuid, name: &'a str) -> Self {
+        Self {
+            target_type: "school",
+            target_id: Some(id),
+            label: Some(name),
+        }
+    }
+}
+
+fn sanitize_details(mut details: Value) -> Value {
+    match &mut details {
+        Value::Object(map) => {
+            map.retain(|key, _| !REDACTED_KEYS.contains(&key.as_str()));
+            for value in map.values_mut() {
+                *value = sanitize_details(value.take());
+            }
+        }
+        Value::Array(items) => {
+            for value in items.iter_mut() {
+                *value = sanitize_details(value.take());
+            }
+        }
+        _ => {}
+    }
+    details
+}
+
+pub struct AuditService {
+    pool: PgPool,
+}
+
+impl AuditService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append an entry for an action that already succeeded. A failed write is
+    /// logged rather than turning a completed change into an error response.
+    pub async fn record(
+        &self,
+        actor: &AuthenticatedAdmin,
+        action: &str,
+        target: AuditTarget<'_>,
+        details: Value,
+    ) {
+        self.record_as(actor.admin_id, &actor.name, action, target, details)
+            .await
+    }
+
+    /// Record an action by an admin who has no session yet, such as accepting
+    /// an invitation
+    pub async fn record_as(
+        &self,
+        actor_admin_id: Uuid,
+        actor_name: &str,
+        action: &str,
+        target: AuditTarget<'_>,
+        details: Value,
+    ) {
+        let result = sqlx::query(
+            "INSERT INTO admin_audit_log (
+                actor_admin_id, actor_name, action, target_type, target_id, target_label, details
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(actor_admin_id)
+        .bind(actor_name)
+        .bind(action)
+        .bind(target.target_type)
+        .bind(target.target_id)
+        .bind(target.label)
+        .bind(sqlx::types::Json(sanitize_details(details)))
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to write audit entry {} for {} {:?}: {}",
+                action,
+                target.target_type,
+                target.target_id,
+                e
+            );
+        }
+    }
+
+    pub async fn list_entries(
+        &self,
+        query: AuditLogQuery,
+    ) -> Result<Vec<AdminAuditEntry>, AppError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        sqlx::query_as::<_, AdminAuditEntry>(
+            "SELECT * FROM admin_audit_log
+             WHERE ($1::text IS NULL OR target_type = $1)
+               AND ($2::uuid IS NULL OR target_id = $2)
+               AND ($3::timestamptz IS NULL OR created_at < $3)
+             ORDER BY created_at DESC
+             LIMIT $4",
+        )
+        .bind(&query.target_type)
+        .bind(query.target_id)
+        .bind(query.before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize_details;
+    use serde_json::json;
+
+    #[test]
+    fn audit_details_drop_credentials_and_national_ids() {
+        let details = sanitize_details(json!({
+            "name": "Demo School",
+            "adminNationalId": "1234567890123",
+            "adminPassword": "secret",
+            "changes": [{"password": "x", "subdomain": "demo"}]
+        }));
+
+        assert_eq!(
+            details,
+            json!({
+                "name": "Demo School",
+                "changes": [{"subdomain": "demo"}]
+            })
+        );
+    }
+}
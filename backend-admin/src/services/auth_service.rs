@@ -1,9 +1,31 @@
-use crate::auth::{generate_token, hash_password, verify_password, AdminClaims, AdminRole};
+use crate::auth::{
+    generate_token, hash_password, totp, verify_password, AdminClaims, AdminRole,
+    AuthenticatedAdmin,
+};
 use crate::error::AppError;
 use crate::models::{AdminUser, CreateAdminUser, LoginRequest};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+/// Returned as the login error when the password is correct but the account
+/// needs a TOTP code; the login handler turns it into an `mfaRequired` flag.
+pub const MFA_REQUIRED: &str = "MFA code required";
+
+const SESSION_HOURS: i64 = 24;
+
+/// Browser details recorded on the session so admins can tell sessions apart
+#[derive(Debug, Clone, Default)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionAdminRow {
+    name: String,
+    role: String,
+}
+
 pub struct AuthService {
     pool: PgPool,
 }
@@ -13,6 +35,7 @@ impl AuthService {
         Self { pool }
     }
 
+    /// Create the first owner account; further admins join by invitation
     pub async fn create_admin(&self, data: CreateAdminUser) -> Result<AdminUser, AppError> {
         // Validate national ID (13 digits)
         if !Self::validate_national_id(&data.national_id) {
@@ -28,7 +51,7 @@ impl AuthService {
         let admin = sqlx::query_as::<_, AdminUser>(
             r#"
             INSERT INTO admin_users (national_id, password_hash, name, role)
-            VALUES ($1, $2, $3, 'super_admin')
+            VALUES ($1, $2, $3, 'owner')
             RETURNING *
             "#,
         )
@@ -42,7 +65,11 @@ impl AuthService {
         Ok(admin)
     }
 
-    pub async fn login(&self, data: LoginRequest) -> Result<(AdminUser, String), AppError> {
+    pub async fn login(
+        &self,
+        data: LoginRequest,
+        client: SessionClient,
+    ) -> Result<(AdminUser, String), AppError> {
         // Validate national ID format
         if !Self::validate_national_id(&data.national_id) {
             return Err(AppError::ValidationError(
@@ -71,18 +98,39 @@ impl AuthService {
 
         let role = AdminRole::try_from(admin.role.as_str())
             .map_err(|_| AppError::Unauthorized("Admin role is not allowed".to_string()))?;
-        if !role.can_access_admin_backend() {
-            return Err(AppError::Unauthorized(
-                "Admin role is not allowed".to_string(),
-            ));
+
+        if admin.totp_enabled_at.is_some() {
+            let code = data
+                .totp_code
+                .as_deref()
+                .map(str::trim)
+                .filter(|code| !code.is_empty())
+                .ok_or_else(|| AppError::Unauthorized(MFA_REQUIRED.to_string()))?;
+            let secret = admin.totp_secret.as_deref().unwrap_or_default();
+            self.consume_totp_code(admin.id, secret, code).await?;
         }
 
+        let now = chrono::Utc::now();
+        let expires_at = now + chrono::Duration::hours(SESSION_HOURS);
+        let session_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO admin_sessions (admin_user_id, user_agent, ip_address, expires_at)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+        )
+        .bind(admin.id)
+        .bind(&client.user_agent)
+        .bind(&client.ip_address)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
         let claims = AdminClaims {
             sub: admin.id.to_string(),
-            email: admin.national_id.clone(), // Use national_id in email field for compatibility
+            sid: session_id.to_string(),
             role,
-            exp: (chrono::Utc::now() + chrono::Duration::hours(24)).timestamp() as usize,
-            iat: chrono::Utc::now().timestamp() as usize,
+            exp: expires_at.timestamp() as usize,
+            iat: now.timestamp() as usize,
         };
 
         let token = generate_token(claims)?;
@@ -90,6 +138,90 @@ impl AuthService {
         Ok((admin, token))
     }
 
+    /// Check a TOTP code and record its time step so the same code cannot be
+    /// replayed within its validity window
+    pub async fn consume_totp_code(
+        &self,
+        admin_id: Uuid,
+        secret: &str,
+        code: &str,
+    ) -> Result<(), AppError> {
+        let step = totp::verify_code(secret, code, chrono::Utc::now().timestamp())
+            .ok_or_else(|| AppError::Unauthorized("Invalid MFA code".to_string()))?;
+
+        let result = sqlx::query(
+            "UPDATE admin_users
+             SET totp_last_used_step = $2
+             WHERE id = $1 AND (totp_last_used_step IS NULL OR totp_last_used_step < $2)",
+        )
+        .bind(admin_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::Unauthorized(
+                "MFA code was already used".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a token's session; fails once the session is revoked or expired
+    pub async fn authenticate_session(
+        &self,
+        admin_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<AuthenticatedAdmin, AppError> {
+        let row = sqlx::query_as::<_, SessionAdminRow>(
+            "SELECT a.name, a.role
+             FROM admin_sessions s
+             JOIN admin_users a ON a.id = s.admin_user_id
+             WHERE s.id = $1 AND s.admin_user_id = $2
+               AND s.revoked_at IS NULL AND s.expires_at > NOW()",
+        )
+        .bind(session_id)
+        .bind(admin_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::Unauthorized("Session expired or revoked".to_string()))?;
+
+        let role = AdminRole::try_from(row.role.as_str())
+            .map_err(|_| AppError::Unauthorized("Admin role is not allowed".to_string()))?;
+
+        // Keep last_seen_at roughly current without a write on every request
+        sqlx::query(
+            "UPDATE admin_sessions SET last_seen_at = NOW()
+             WHERE id = $1 AND last_seen_at < NOW() - INTERVAL '1 minute'",
+        )
+        .bind(session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(AuthenticatedAdmin {
+            admin_id,
+            session_id,
+            name: row.name,
+            role,
+        })
+    }
+
+    pub async fn revoke_session(&self, session_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE admin_sessions SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     pub async fn get_admin_by_id(&self, id: Uuid) -> Result<AdminUser, AppError> {
         let admin = sqlx::query_as::<_, AdminUser>("SELECT * FROM admin_users WHERE id = $1")
             .bind(id)
@@ -105,7 +237,7 @@ impl AuthService {
     // Supports:
     // 1. Thai National ID: 13 digits (e.g., 1234567890123)
     // 2. Foreign ID with G prefix: G + 12 digits (e.g., G123456789012)
-    fn validate_national_id(national_id: &str) -> bool {
+    pub(crate) fn validate_national_id(national_id: &str) -> bool {
         // Must be exactly 13 characters
         if national_id.len() != 13 {
             return false;
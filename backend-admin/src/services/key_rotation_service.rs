@@ -0,0 +1,268 @@
+use crate::auth::AuthenticatedAdmin;
+use crate::clients::backend_school_client::{
+    BackendSchoolClient, KeyRotationRequest, KeyRotationRun,
+};
+use crate::error::AppError;
+use crate::models::{School, SchoolKeyRotation, TenantKeyRotation};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A running row not updated for this long lost its runner, usually to a restart.
+/// One backend-school slice takes well under a minute.
+const STALE_RUN_MINUTES: i32 = 15;
+const RECENT_RUNS: i64 = 20;
+
+pub struct KeyRotationService {
+    pool: PgPool,
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(e.to_string())
+}
+
+fn require_rotation_target(school: &School) -> Result<&str, AppError> {
+    if school.status != "active" {
+        return Err(AppError::ValidationError(
+            "School must be active for key rotation".to_string(),
+        ));
+    }
+
+    school
+        .db_connection_string
+        .as_deref()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            AppError::ValidationError("School has no database connection string".to_string())
+        })
+}
+
+fn backend_school_client() -> Result<BackendSchoolClient, AppError> {
+    BackendSchoolClient::new()
+        .map_err(|e| AppError::ExternalServiceError(format!("Backend-school client error: {}", e)))
+}
+
+fn rotation_request(school: &School) -> Result<KeyRotationRequest, AppError> {
+    Ok(KeyRotationRequest {
+        subdomain: school.subdomain.clone(),
+        db_connection_string: require_rotation_target(school)?.to_string(),
+    })
+}
+
+impl KeyRotationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_runs(&self, school_id: Uuid) -> Result<Vec<TenantKeyRotation>, AppError> {
+        sqlx::query_as::<_, TenantKeyRotation>(
+            "SELECT * FROM tenant_key_rotations
+             WHERE school_id = $1
+             ORDER BY created_at DESC
+             LIMIT $2",
+        )
+        .bind(school_id)
+        .bind(RECENT_RUNS)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Recent runs plus the school's live count of values on older keys
+    pub async fn school_key_rotation(
+        &self,
+        school: &School,
+    ) -> Result<SchoolKeyRotation, AppError> {
+        let runs = self.list_runs(school.id).await?;
+        let request = rotation_request(school)?;
+        let client = backend_school_client()?;
+
+        let (status, status_error) = match client.key_rotation_status(&request).await {
+            Ok(status) => (Some(status), None),
+            Err(e) => {
+                warn!(school_id = %school.id, error = %e, "key rotation status unavailable");
+                (None, Some(e))
+            }
+        };
+
+        Ok(SchoolKeyRotation {
+            runs,
+            status,
+            status_error,
+        })
+    }
+
+    /// Start a run, or take over one whose runner stopped, and drive it in the
+    /// background. backend-school keeps the cursor, so a new run resumes where
+    /// the previous one stopped.
+    pub async fn start_rotation(
+        &self,
+        admin: &AuthenticatedAdmin,
+        school: &School,
+    ) -> Result<TenantKeyRotation, AppError> {
+        let request = rotation_request(school)?;
+        let client = backend_school_client()?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "UPDATE tenant_key_rotations
+             SET status = 'failed', error = 'Runner stopped reporting progress', updated_at = NOW()
+             WHERE school_id = $1 AND status = 'running'
+               AND updated_at < NOW() - make_interval(mins => $2)",
+        )
+        .bind(school.id)
+        .bind(STALE_RUN_MINUTES)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let run = sqlx::query_as::<_, TenantKeyRotation>(
+            "INSERT INTO tenant_key_rotations (school_id, admin_user_id, admin_name)
+             VALUES ($1, $2, $3)
+             RETURNING *",
+        )
+        .bind(school.id)
+        .bind(admin.admin_id)
+        .bind(&admin.name)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::ValidationError(
+                "A key rotation is already running for this school".to_string(),
+            ),
+            e => db_error(e),
+        })?;
+        tx.commit().await.map_err(db_error)?;
+
+        let pool = self.pool.clone();
+        let run_id = run.id;
+        tokio::spawn(async move {
+            KeyRotationService::new(pool)
+                .drive_rotation(run_id, client, request)
+                .await;
+        });
+
+        Ok(run)
+    }
+
+    /// Call backend-school until the school reports the run completed
+    async fn drive_rotation(
+        &self,
+        run_id: Uuid,
+        client: BackendSchoolClient,
+        request: KeyRotationRequest,
+    ) {
+        loop {
+            let outcome = match client.run_key_rotation(&request).await {
+                Ok(progress) => self.record_progress(run_id, &progress).await,
+                Err(e) => {
+                    warn!(%run_id, subdomain = %request.subdomain, error = %e, "key rotation failed");
+                    self.record_failure(run_id, &e).await.map(|_| false)
+                }
+            };
+
+            match outcome {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    warn!(%run_id, error = %e, "key rotation progress could not be recorded");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Mirror one slice of progress; returns whether the run should continue
+    async fn record_progress(
+        &self,
+        run_id: Uuid,
+        progress: &KeyRotationRun,
+    ) -> Result<bool, AppError> {
+        let completed = progress.status == "completed";
+        let updated = sqlx::query_scalar::<_, String>(
+            "UPDATE tenant_key_rotations
+             SET encryption_key_id = $2,
+                 blind_index_key_id = $3,
+                 rows_scanned = $4,
+                 rows_rewritten = $5,
+                 rows_failed = $6,
+                 last_row_error = $7,
+                 status = CASE WHEN $8 THEN 'completed' ELSE status END,
+                 completed_at = CASE WHEN $8 THEN NOW() ELSE completed_at END,
+                 updated_at = NOW()
+             WHERE id = $1 AND status = 'running'
+             RETURNING status",
+        )
+        .bind(run_id)
+        .bind(&progress.encryption_key_id)
+        .bind(&progress.blind_index_key_id)
+        .bind(progress.rows_scanned)
+        .bind(progress.rows_rewritten)
+        .bind(progress.rows_failed)
+        .bind(&progress.last_error)
+        .bind(completed)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        if completed {
+            info!(
+                %run_id,
+                encryption_key_id = %progress.encryption_key_id,
+                blind_index_key_id = %progress.blind_index_key_id,
+                rows_rewritten = progress.rows_rewritten,
+                rows_failed = progress.rows_failed,
+                "key rotation completed"
+            );
+        }
+
+        // A run taken over as stale is no longer ours to drive.
+        Ok(updated.as_deref() == Some("running"))
+    }
+
+    async fn record_failure(&self, run_id: Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE tenant_key_rotations
+             SET status = 'failed', error = $2, updated_at = NOW()
+             WHERE id = $1 AND status = 'running'",
+        )
+        .bind(run_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rotation_request;
+    use crate::models::{School, SchoolConfig};
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    fn school(status: &str, db_connection_string: Option<&str>) -> School {
+        School {
+            id: Uuid::new_v4(),
+            name: "Rotation".to_string(),
+            subdomain: "rotation".to_string(),
+            db_name: "schoolorbit_rotation".to_string(),
+            db_connection_string: db_connection_string.map(str::to_string),
+            status: status.to_string(),
+            config: Json(SchoolConfig::default()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rotation_needs_an_active_school_with_a_database() {
+        assert!(rotation_request(&school("suspended", Some("postgres://db"))).is_err());
+        assert!(rotation_request(&school("active", Some(""))).is_err());
+
+        let request = rotation_request(&school("active", Some("postgres://db"))).unwrap();
+        assert_eq!(request.subdomain, "rotation");
+        assert_eq!(request.db_connection_string, "postgres://db");
+    }
+}
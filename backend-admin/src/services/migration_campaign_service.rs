@@ -0,0 +1,767 @@
+use crate::clients::backend_school_client::{
+    BackendSchoolClient, TenantMigrationRequest, TenantMigrationSummary,
+};
+use crate::error::AppError;
+use crate::models::{CreateMigrationCampaign, MigrationCampaign, MigrationCampaignTenant};
+use crate::utils::sse::SseLogger;
+use futures::stream::{self, StreamExt};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// A running campaign that has not written progress for this long is assumed
+/// to have lost its runner (admin restart) and may be claimed again.
+const STALE_RUN_MINUTES: i32 = 15;
+
+const CAMPAIGN_TENANT_COLUMNS: &str =
+    "t.id, t.campaign_id, t.school_id, s.subdomain, t.wave, t.status,
+     t.from_version, t.applied_version, t.duration_ms, t.error, t.started_at, t.finished_at";
+
+pub struct MigrationCampaignService {
+    pool: PgPool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct WaveTenant {
+    id: Uuid,
+    school_id: Uuid,
+    subdomain: String,
+    school_status: String,
+    db_connection_string: Option<String>,
+}
+
+enum TenantOutcome {
+    Migrated(TenantMigrationSummary),
+    Failed {
+        applied_version: Option<i64>,
+        from_version: Option<i64>,
+        duration_ms: Option<i64>,
+        error: String,
+    },
+}
+
+fn validate_campaign_request(data: &CreateMigrationCampaign) -> Result<(), AppError> {
+    if data.target_version < 1 {
+        return Err(AppError::ValidationError(
+            "Target version must be at least 1".to_string(),
+        ));
+    }
+    if !(1..=1000).contains(&data.wave_size) {
+        return Err(AppError::ValidationError(
+            "Wave size must be between 1 and 1000".to_string(),
+        ));
+    }
+    if !(1..=20).contains(&data.concurrency) {
+        return Err(AppError::ValidationError(
+            "Concurrency must be between 1 and 20".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Assign every candidate school to a wave: canaries form wave 0, the rest are
+/// chunked into waves of `wave_size` starting at wave 1 in candidate order.
+fn plan_waves(
+    candidates: &[Uuid],
+    canary_ids: &[Uuid],
+    wave_size: usize,
+) -> Result<Vec<(Uuid, i32)>, AppError> {
+    if let Some(missing) = canary_ids.iter().find(|id| !candidates.contains(id)) {
+        return Err(AppError::ValidationError(format!(
+            "Canary school {} is not an active school below the target version",
+            missing
+        )));
+    }
+
+    let mut plan: Vec<(Uuid, i32)> = Vec::with_capacity(candidates.len());
+    for id in canary_ids {
+        if !plan.iter().any(|(planned, _)| planned == id) {
+            plan.push((*id, 0));
+        }
+    }
+
+    let rest: Vec<Uuid> = candidates
+        .iter()
+        .filter(|id| !canary_ids.contains(id))
+        .copied()
+        .collect();
+    for (index, chunk) in rest.chunks(wave_size.max(1)).enumerate() {
+        plan.extend(chunk.iter().map(|id| (*id, index as i32 + 1)));
+    }
+
+    Ok(plan)
+}
+
+/// A tenant only counts as migrated once it reports a version at or past the target.
+fn classify_outcome(
+    result: Result<TenantMigrationSummary, String>,
+    target_version: i64,
+) -> TenantOutcome {
+    match result {
+        Ok(summary) => match (&summary.error, summary.applied_version) {
+            (None, Some(applied)) if applied >= target_version => TenantOutcome::Migrated(summary),
+            (error, applied_version) => TenantOutcome::Failed {
+                applied_version,
+                from_version: summary.from_version,
+                duration_ms: Some(summary.duration_ms as i64),
+                error: error.clone().unwrap_or_else(|| {
+                    format!(
+                        "Tenant stopped at version {} below target {}",
+                        applied_version.unwrap_or(0),
+                        target_version
+                    )
+                }),
+            },
+        },
+        Err(error) => TenantOutcome::Failed {
+            applied_version: None,
+            from_version: None,
+            duration_ms: None,
+            error,
+        },
+    }
+}
+
+async fn migrate_one<'a>(
+    client: &BackendSchoolClient,
+    tenant: &'a WaveTenant,
+    target_version: i64,
+) -> (&'a WaveTenant, Result<TenantMigrationSummary, String>) {
+    let request = TenantMigrationRequest {
+        subdomain: tenant.subdomain.clone(),
+        db_connection_string: tenant.db_connection_string.clone().unwrap_or_default(),
+        target_version,
+    };
+    (tenant, client.migrate_tenant(&request).await)
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(e.to_string())
+}
+
+impl MigrationCampaignService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_campaigns(&self) -> Result<Vec<MigrationCampaign>, AppError> {
+        sqlx::query_as::<_, MigrationCampaign>(
+            "SELECT * FROM migration_campaigns ORDER BY created_at DESC LIMIT 50",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    pub async fn get_campaign(&self, id: Uuid) -> Result<MigrationCampaign, AppError> {
+        sqlx::query_as::<_, MigrationCampaign>("SELECT * FROM migration_campaigns WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| AppError::NotFound("Migration campaign not found".to_string()))
+    }
+
+    pub async fn list_campaign_tenants(
+        &self,
+        campaign_id: Uuid,
+    ) -> Result<Vec<MigrationCampaignTenant>, AppError> {
+        sqlx::query_as::<_, MigrationCampaignTenant>(&format!(
+            "SELECT {CAMPAIGN_TENANT_COLUMNS}
+             FROM migration_campaign_tenants t
+             JOIN schools s ON s.id = t.school_id
+             WHERE t.campaign_id = $1
+             ORDER BY t.wave, s.subdomain"
+        ))
+        .bind(campaign_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Migration history of one school across campaigns, newest first
+    pub async fn list_school_history(
+        &self,
+        school_id: Uuid,
+    ) -> Result<Vec<MigrationCampaignTenant>, AppError> {
+        sqlx::query_as::<_, MigrationCampaignTenant>(&format!(
+            "SELECT {CAMPAIGN_TENANT_COLUMNS}
+             FROM migration_campaign_tenants t
+             JOIN schools s ON s.id = t.school_id
+             WHERE t.school_id = $1
+             ORDER BY t.started_at DESC NULLS FIRST
+             LIMIT 100"
+        ))
+        .bind(school_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Plan a campaign over every active school below the target version
+    pub async fn create_campaign(
+        &self,
+        data: CreateMigrationCampaign,
+    ) -> Result<MigrationCampaign, AppError> {
+        validate_campaign_request(&data)?;
+
+        let candidates = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id
+             FROM schools
+             WHERE status = 'active'
+               AND db_connection_string IS NOT NULL
+               AND COALESCE(migration_version, 0) < $1
+             ORDER BY created_at",
+        )
+        .bind(data.target_version)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        if candidates.is_empty() {
+            return Err(AppError::ValidationError(
+                "No active schools are below the target version".to_string(),
+            ));
+        }
+        let plan = plan_waves(
+            &candidates,
+            &data.canary_school_ids,
+            data.wave_size as usize,
+        )?;
+        let first_wave = plan.iter().map(|(_, wave)| *wave).min().unwrap_or(0);
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let campaign = sqlx::query_as::<_, MigrationCampaign>(
+            "INSERT INTO migration_campaigns (target_version, wave_size, concurrency, current_wave)
+             VALUES ($1, $2, $3, $4)
+             RETURNING *",
+        )
+        .bind(data.target_version)
+        .bind(data.wave_size)
+        .bind(data.concurrency)
+        .bind(first_wave)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::ValidationError("Another migration campaign is still open".to_string())
+            }
+            e => db_error(e),
+        })?;
+
+        let (school_ids, waves): (Vec<Uuid>, Vec<i32>) = plan.into_iter().unzip();
+        sqlx::query(
+            "INSERT INTO migration_campaign_tenants (campaign_id, school_id, wave)
+             SELECT $1, school_id, wave FROM UNNEST($2::uuid[], $3::int[]) AS p(school_id, wave)",
+        )
+        .bind(campaign.id)
+        .bind(&school_ids)
+        .bind(&waves)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(campaign)
+    }
+
+    /// Cancel a campaign that is not actively being run
+    pub async fn cancel_campaign(&self, id: Uuid) -> Result<MigrationCampaign, AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let campaign = sqlx::query_as::<_, MigrationCampaign>(
+            "UPDATE migration_campaigns
+             SET status = 'cancelled', completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1
+               AND (status IN ('pending', 'paused')
+                    OR (status = 'running' AND updated_at < NOW() - make_interval(mins => $2)))
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(STALE_RUN_MINUTES)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let Some(campaign) = campaign else {
+            let current = self.get_campaign(id).await?;
+            return Err(AppError::ValidationError(format!(
+                "Campaign is {} and cannot be cancelled",
+                current.status
+            )));
+        };
+
+        // A lost runner can leave tenants out of service; give them back.
+        sqlx::query(
+            "UPDATE schools
+             SET migration_status = 'failed',
+                 migration_error = 'Migration campaign cancelled while migrating',
+                 updated_at = NOW()
+             WHERE id IN (
+                 SELECT school_id FROM migration_campaign_tenants
+                 WHERE campaign_id = $1 AND status = 'migrating'
+             )",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "UPDATE migration_campaign_tenants
+             SET status = 'failed', error = 'Campaign cancelled', finished_at = NOW()
+             WHERE campaign_id = $1 AND status = 'migrating'",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(campaign)
+    }
+
+    async fn claim_campaign(&self, id: Uuid) -> Result<MigrationCampaign, AppError> {
+        let claimed = sqlx::query_as::<_, MigrationCampaign>(
+            "UPDATE migration_campaigns
+             SET status = 'running',
+                 paused_reason = NULL,
+                 started_at = COALESCE(started_at, NOW()),
+                 updated_at = NOW()
+             WHERE id = $1
+               AND (status IN ('pending', 'paused')
+                    OR (status = 'running' AND updated_at < NOW() - make_interval(mins => $2)))
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(STALE_RUN_MINUTES)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        match claimed {
+            Some(campaign) => Ok(campaign),
+            None => {
+                let current = self.get_campaign(id).await?;
+                Err(AppError::ValidationError(format!(
+                    "Campaign is {} and cannot be run",
+                    current.status
+                )))
+            }
+        }
+    }
+
+    async fn remaining_waves(&self, campaign: &MigrationCampaign) -> Result<Vec<i32>, AppError> {
+        sqlx::query_scalar::<_, i32>(
+            "SELECT DISTINCT wave FROM migration_campaign_tenants
+             WHERE campaign_id = $1 AND wave >= $2
+             ORDER BY wave",
+        )
+        .bind(campaign.id)
+        .bind(campaign.current_wave)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Take the wave's unfinished tenants out of service and return them.
+    /// Failed and interrupted tenants are picked up again when a wave is resumed.
+    async fn start_wave(&self, campaign_id: Uuid, wave: i32) -> Result<Vec<WaveTenant>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            "UPDATE migration_campaigns SET current_wave = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(campaign_id)
+        .bind(wave)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let tenants = sqlx::query_as::<_, WaveTenant>(
+            "SELECT t.id, t.school_id, s.subdomain, s.status AS school_status, s.db_connection_string
+             FROM migration_campaign_tenants t
+             JOIN schools s ON s.id = t.school_id
+             WHERE t.campaign_id = $1 AND t.wave = $2
+               AND t.status IN ('pending', 'migrating', 'failed')
+             ORDER BY s.subdomain",
+        )
+        .bind(campaign_id)
+        .bind(wave)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        // Schools suspended or moved since planning are not migrated by this campaign.
+        let (ready, skipped): (Vec<WaveTenant>, Vec<WaveTenant>) =
+            tenants.into_iter().partition(|tenant| {
+                tenant.school_status == "active" && tenant.db_connection_string.is_some()
+            });
+        let skipped_ids: Vec<Uuid> = skipped.iter().map(|tenant| tenant.id).collect();
+        let ready_ids: Vec<Uuid> = ready.iter().map(|tenant| tenant.id).collect();
+        let ready_school_ids: Vec<Uuid> = ready.iter().map(|tenant| tenant.school_id).collect();
+
+        sqlx::query(
+            "UPDATE migration_campaign_tenants
+             SET status = 'skipped', error = 'School is no longer active', finished_at = NOW()
+             WHERE id = ANY($1)",
+        )
+        .bind(&skipped_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "UPDATE migration_campaign_tenants
+             SET status = 'migrating', error = NULL, started_at = NOW(), finished_at = NULL
+             WHERE id = ANY($1)",
+        )
+        .bind(&ready_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "UPDATE schools SET migration_status = 'migrating', updated_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(&ready_school_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(ready)
+    }
+
+    /// Record one tenant's result and return it to service
+    async fn record_outcome(
+        &self,
+        campaign_id: Uuid,
+        tenant: &WaveTenant,
+        outcome: &TenantOutcome,
+    ) -> Result<(), AppError> {
+        let (status, from_version, applied_version, duration_ms, error) = match outcome {
+            TenantOutcome::Migrated(summary) => (
+                "migrated",
+                summary.from_version,
+                summary.applied_version,
+                Some(summary.duration_ms as i64),
+                None,
+            ),
+            TenantOutcome::Failed {
+                applied_version,
+                from_version,
+                duration_ms,
+                error,
+            } => (
+                "failed",
+                *from_version,
+                *applied_version,
+                *duration_ms,
+                Some(error.as_str()),
+            ),
+        };
+        let from_version = from_version.map(|version| version as i32);
+        let applied_version = applied_version.map(|version| version as i32);
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "UPDATE migration_campaign_tenants
+             SET status = $2, from_version = $3, applied_version = $4, duration_ms = $5,
+                 error = $6, finished_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(tenant.id)
+        .bind(status)
+        .bind(from_version)
+        .bind(applied_version)
+        .bind(duration_ms)
+        .bind(error)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            "UPDATE schools
+             SET migration_status = $2,
+                 migration_version = COALESCE($3, migration_version),
+                 migration_error = $4,
+                 last_migrated_at = CASE WHEN $2 = 'migrated' THEN NOW() ELSE last_migrated_at END,
+                 updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(tenant.school_id)
+        .bind(status)
+        .bind(applied_version)
+        .bind(error)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        // Heartbeat: a campaign that keeps recording tenants is not stale.
+        sqlx::query("UPDATE migration_campaigns SET updated_at = NOW() WHERE id = $1")
+            .bind(campaign_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    async fn set_campaign_status(
+        &self,
+        campaign_id: Uuid,
+        status: &str,
+        current_wave: i32,
+        paused_reason: Option<&str>,
+    ) -> Result<MigrationCampaign, AppError> {
+        sqlx::query_as::<_, MigrationCampaign>(
+            "UPDATE migration_campaigns
+             SET status = $2,
+                 current_wave = $3,
+                 paused_reason = $4,
+                 completed_at = CASE WHEN $2 = 'completed' THEN NOW() ELSE completed_at END,
+                 updated_at = NOW()
+             WHERE id = $1 AND status = 'running'
+             RETURNING *",
+        )
+        .bind(campaign_id)
+        .bind(status)
+        .bind(current_wave)
+        .bind(paused_reason)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            AppError::ValidationError("Campaign was cancelled while running".to_string())
+        })
+    }
+
+    async fn pause(
+        &self,
+        campaign_id: Uuid,
+        wave: i32,
+        reason: &str,
+        logger: &SseLogger,
+    ) -> Result<MigrationCampaign, AppError> {
+        warn!(%campaign_id, wave, reason, "migration campaign paused");
+        logger
+            .warning(&format!("⏸️  Campaign paused: {}", reason))
+            .await;
+        let campaign = self
+            .set_campaign_status(campaign_id, "paused", wave, Some(reason))
+            .await?;
+        logger
+            .complete(serde_json::to_value(&campaign).unwrap_or_default())
+            .await;
+        Ok(campaign)
+    }
+
+    /// Run (or resume) a campaign wave by wave with SSE logging.
+    ///
+    /// The campaign pauses after the canary wave and whenever a tenant in a
+    /// wave fails; resuming retries the failed tenants of the current wave.
+    pub async fn run_campaign_stream(
+        &self,
+        campaign_id: Uuid,
+        logger: SseLogger,
+    ) -> Result<MigrationCampaign, AppError> {
+        let campaign = self.claim_campaign(campaign_id).await?;
+        let target_version = campaign.target_version as i64;
+        logger
+            .info(&format!(
+                "🚀 Migrating schools to version {} (wave {}, {} at a time)",
+                campaign.target_version, campaign.current_wave, campaign.concurrency
+            ))
+            .await;
+
+        let client = match BackendSchoolClient::new() {
+            Ok(client) => client,
+            Err(e) => {
+                let reason = format!("Backend-school client error: {}", e);
+                self.pause(campaign.id, campaign.current_wave, &reason, &logger)
+                    .await?;
+                return Err(AppError::ExternalServiceError(reason));
+            }
+        };
+
+        let waves = self.remaining_waves(&campaign).await?;
+        let last_wave = waves.last().copied().unwrap_or(campaign.current_wave);
+
+        for wave in waves {
+            let tenants = self.start_wave(campaign.id, wave).await?;
+            let label = if wave == 0 {
+                "canary wave".to_string()
+            } else {
+                format!("wave {}", wave)
+            };
+            logger
+                .info(&format!(
+                    "🌊 Starting {} ({} schools)",
+                    label,
+                    tenants.len()
+                ))
+                .await;
+
+            let migrations: Vec<_> = tenants
+                .iter()
+                .map(|tenant| migrate_one(&client, tenant, target_version))
+                .collect();
+            let mut results =
+                stream::iter(migrations).buffer_unordered(campaign.concurrency.max(1) as usize);
+
+            let mut failed = 0usize;
+            while let Some((tenant, result)) = results.next().await {
+                let outcome = classify_outcome(result, target_version);
+                match &outcome {
+                    TenantOutcome::Migrated(summary) => {
+                        logger
+                            .success(&format!(
+                                "✅ {} migrated to version {} in {} ms",
+                                tenant.subdomain,
+                                summary.applied_version.unwrap_or(target_version),
+                                summary.duration_ms
+                            ))
+                            .await;
+                    }
+                    TenantOutcome::Failed { error, .. } => {
+                        failed += 1;
+                        logger
+                            .error(&format!("❌ {} failed: {}", tenant.subdomain, error))
+                            .await;
+                    }
+                }
+                self.record_outcome(campaign.id, tenant, &outcome).await?;
+            }
+
+            if failed > 0 {
+                let reason = format!("{} school(s) failed in {}", failed, label);
+                return self.pause(campaign.id, wave, &reason, &logger).await;
+            }
+
+            logger.success(&format!("✅ Finished {}", label)).await;
+
+            if wave == 0 && wave < last_wave {
+                return self
+                    .pause(
+                        campaign.id,
+                        wave + 1,
+                        "Canary wave completed; verify the canary schools, then resume",
+                        &logger,
+                    )
+                    .await;
+            }
+        }
+
+        let campaign = self
+            .set_campaign_status(campaign.id, "completed", last_wave, None)
+            .await?;
+        logger.success("🎉 Migration campaign completed").await;
+        logger
+            .complete(serde_json::to_value(&campaign).unwrap_or_default())
+            .await;
+
+        Ok(campaign)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        classify_outcome, plan_waves, validate_campaign_request, TenantMigrationSummary,
+        TenantOutcome,
+    };
+    use crate::models::CreateMigrationCampaign;
+    use uuid::Uuid;
+
+    fn summary(applied_version: Option<i64>, error: Option<&str>) -> TenantMigrationSummary {
+        TenantMigrationSummary {
+            subdomain: "demo".to_string(),
+            from_version: Some(29),
+            applied_version,
+            latest_version: 31,
+            duration_ms: 120,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn canaries_form_wave_zero_and_the_rest_are_chunked() {
+        let schools: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+
+        let plan = plan_waves(&schools, &[schools[3]], 2).unwrap();
+
+        assert_eq!(plan[0], (schools[3], 0));
+        assert_eq!(
+            plan[1..],
+            [
+                (schools[0], 1),
+                (schools[1], 1),
+                (schools[2], 2),
+                (schools[4], 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn campaigns_without_canaries_start_at_wave_one() {
+        let schools: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        let plan = plan_waves(&schools, &[], 25).unwrap();
+
+        assert!(plan.iter().all(|(_, wave)| *wave == 1));
+        assert_eq!(plan.len(), 3);
+    }
+
+    #[test]
+    fn canaries_must_be_campaign_candidates() {
+        let schools: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+
+        assert!(plan_waves(&schools, &[Uuid::new_v4()], 10).is_err());
+    }
+
+    #[test]
+    fn campaign_limits_are_validated() {
+        let request = |wave_size, concurrency| CreateMigrationCampaign {
+            target_version: 31,
+            canary_school_ids: Vec::new(),
+            wave_size,
+            concurrency,
+        };
+
+        assert!(validate_campaign_request(&request(25, 4)).is_ok());
+        assert!(validate_campaign_request(&request(0, 4)).is_err());
+        assert!(validate_campaign_request(&request(25, 21)).is_err());
+    }
+
+    #[test]
+    fn tenants_short_of_the_target_are_failures() {
+        assert!(matches!(
+            classify_outcome(Ok(summary(Some(31), None)), 31),
+            TenantOutcome::Migrated(_)
+        ));
+        assert!(matches!(
+            classify_outcome(Ok(summary(Some(32), None)), 31),
+            TenantOutcome::Migrated(_)
+        ));
+
+        match classify_outcome(Ok(summary(Some(30), None)), 31) {
+            TenantOutcome::Failed {
+                applied_version,
+                error,
+                ..
+            } => {
+                assert_eq!(applied_version, Some(30));
+                assert!(error.contains("below target 31"));
+            }
+            TenantOutcome::Migrated(_) => panic!("tenant below target counted as migrated"),
+        }
+
+        assert!(matches!(
+            classify_outcome(Ok(summary(Some(31), Some("lock timeout"))), 31),
+            TenantOutcome::Failed { .. }
+        ));
+        assert!(matches!(
+            classify_outcome(Err("connection refused".to_string()), 31),
+            TenantOutcome::Failed { .. }
+        ));
+    }
+}
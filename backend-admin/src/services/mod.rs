@@ -1,5 +1,21 @@
+pub mod admin_account_service;
+pub mod audit_service;
 pub mod auth_service;
+pub mod key_rotation_service;
+pub mod migration_campaign_service;
+pub mod plan_service;
 pub mod school_service;
+pub mod snapshot_service;
+pub mod support_session_service;
+pub mod usage_service;
 
+pub use admin_account_service::AdminAccountService;
+pub use audit_service::AuditService;
 pub use auth_service::AuthService;
+pub use key_rotation_service::KeyRotationService;
+pub use migration_campaign_service::MigrationCampaignService;
+pub use plan_service::PlanService;
 pub use school_service::SchoolService;
+pub use snapshot_service::SnapshotService;
+pub use support_session_service::SupportSessionService;
+pub use usage_service::UsageService;
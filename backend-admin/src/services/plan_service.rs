@@ -0,0 +1,228 @@
+use crate::error::AppError;
+use crate::models::{Plan, SavePlan};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct PlanService {
+    pool: PgPool,
+}
+
+fn is_code(value: &str) -> bool {
+    value
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_lowercase() || first.is_ascii_digit())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Normalize a plan before saving: limits must be positive and module codes
+/// are permission module names, stored sorted and without duplicates.
+fn normalize_plan(mut data: SavePlan) -> Result<SavePlan, AppError> {
+    data.code = data.code.trim().to_string();
+    data.name = data.name.trim().to_string();
+
+    if data.code.is_empty() || data.code.len() > 50 || !is_code(&data.code) {
+        return Err(AppError::ValidationError(
+            "Plan code must use lowercase letters, digits, '-' or '_'".to_string(),
+        ));
+    }
+    if data.name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Plan name is required".to_string(),
+        ));
+    }
+    for (label, limit) in [
+        ("Max students", data.max_students),
+        ("Max storage bytes", data.max_storage_bytes),
+        ("Max certificates per year", data.max_certificates_per_year),
+    ] {
+        if limit.is_some_and(|limit| limit <= 0) {
+            return Err(AppError::ValidationError(format!(
+                "{} must be positive or empty for unlimited",
+                label
+            )));
+        }
+    }
+
+    if let Some(modules) = data.enabled_modules.as_mut() {
+        for module in modules.iter_mut() {
+            *module = module.trim().to_string();
+            if module.is_empty() || module.contains('-') || !is_code(module) {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid module code '{}'",
+                    module
+                )));
+            }
+        }
+        modules.sort();
+        modules.dedup();
+    }
+
+    Ok(data)
+}
+
+fn map_plan_write_error(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            AppError::ValidationError("Plan code already exists".to_string())
+        }
+        e => AppError::DatabaseError(e.to_string()),
+    }
+}
+
+impl PlanService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_plans(&self) -> Result<Vec<Plan>, AppError> {
+        sqlx::query_as::<_, Plan>("SELECT * FROM plans ORDER BY code")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub async fn get_plan(&self, id: Uuid) -> Result<Plan, AppError> {
+        sqlx::query_as::<_, Plan>("SELECT * FROM plans WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("Plan not found".to_string()))
+    }
+
+    pub async fn create_plan(&self, data: SavePlan) -> Result<Plan, AppError> {
+        let data = normalize_plan(data)?;
+
+        sqlx::query_as::<_, Plan>(
+            "INSERT INTO plans (
+                code, name, max_students, max_storage_bytes, max_certificates_per_year,
+                enabled_modules
+             )
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING *",
+        )
+        .bind(&data.code)
+        .bind(&data.name)
+        .bind(data.max_students)
+        .bind(data.max_storage_bytes)
+        .bind(data.max_certificates_per_year)
+        .bind(&data.enabled_modules)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_plan_write_error)
+    }
+
+    /// Changes reach backend-school on the next school lookup of each tenant
+    pub async fn update_plan(&self, id: Uuid, data: SavePlan) -> Result<Plan, AppError> {
+        let data = normalize_plan(data)?;
+
+        sqlx::query_as::<_, Plan>(
+            "UPDATE plans
+             SET code = $2, name = $3, max_students = $4, max_storage_bytes = $5,
+                 max_certificates_per_year = $6, enabled_modules = $7, updated_at = NOW()
+             WHERE id = $1
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(&data.code)
+        .bind(&data.name)
+        .bind(data.max_students)
+        .bind(data.max_storage_bytes)
+        .bind(data.max_certificates_per_year)
+        .bind(&data.enabled_modules)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_plan_write_error)?
+        .ok_or_else(|| AppError::NotFound("Plan not found".to_string()))
+    }
+
+    pub async fn delete_plan(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM plans WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                    AppError::ValidationError(
+                        "Plan is assigned to schools; move them to another plan first".to_string(),
+                    )
+                }
+                e => AppError::DatabaseError(e.to_string()),
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Plan not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn assign_plan(
+        &self,
+        school_id: Uuid,
+        plan_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        if let Some(plan_id) = plan_id {
+            self.get_plan(plan_id).await?;
+        }
+
+        let result =
+            sqlx::query("UPDATE schools SET plan_id = $2, updated_at = NOW() WHERE id = $1")
+                .bind(school_id)
+                .bind(plan_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("School not found".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_plan;
+    use crate::models::SavePlan;
+
+    fn plan(code: &str, modules: Option<Vec<&str>>) -> SavePlan {
+        SavePlan {
+            code: code.to_string(),
+            name: "Basic".to_string(),
+            max_students: Some(500),
+            max_storage_bytes: None,
+            max_certificates_per_year: Some(2_000),
+            enabled_modules: modules.map(|modules| modules.into_iter().map(String::from).collect()),
+        }
+    }
+
+    #[test]
+    fn plan_modules_are_sorted_and_deduplicated() {
+        let normalized = normalize_plan(plan(
+            " basic ",
+            Some(vec!["finance", " certificate", "finance"]),
+        ))
+        .unwrap();
+
+        assert_eq!(normalized.code, "basic");
+        assert_eq!(
+            normalized.enabled_modules.unwrap(),
+            vec!["certificate".to_string(), "finance".to_string()]
+        );
+    }
+
+    #[test]
+    fn plan_codes_and_limits_are_validated() {
+        assert!(normalize_plan(plan("Basic Plan", None)).is_err());
+        assert!(normalize_plan(plan("basic", Some(vec!["Finance"]))).is_err());
+
+        let mut negative = plan("basic", None);
+        negative.max_students = Some(0);
+        assert!(normalize_plan(negative).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::clients::provisioning_driver::ProvisioningDriver;
 use crate::error::AppError;
 use crate::models::{CreateSchool, School, SchoolConfig, UpdateSchool};
 use sqlx::{types::Json, PgPool};
@@ -38,12 +39,12 @@ fn build_school_database_name(subdomain: &str) -> String {
 }
 
 fn build_active_school_config(
-    db_id: i64,
+    db_id: Option<i64>,
     dns_record_id: &str,
     deployment_url: &str,
 ) -> SchoolConfig {
     SchoolConfig {
-        db_id: Some(db_id),
+        db_id,
         dns_record_id: Some(dns_record_id.to_string()),
         deployment_url: Some(deployment_url.to_string()),
     }
@@ -204,7 +205,7 @@ impl SchoolService {
     async fn rollback_failed_provisioning(
         &self,
         school_id: Option<Uuid>,
-        neon_client: &crate::clients::neon_client::NeonClient,
+        driver: &ProvisioningDriver,
         db_name: &str,
         reason: String,
     ) -> AppError {
@@ -222,12 +223,14 @@ impl SchoolService {
             }
         }
 
-        let database_deleted = match neon_client.delete_database_by_name(db_name).await {
+        let database_deleted = match driver.delete_database(db_name).await {
             Ok(_) => true,
             Err(e) => {
                 cleanup_errors.push(format!(
-                    "failed to delete Neon database '{}': {}",
-                    db_name, e
+                    "failed to delete {} database '{}': {}",
+                    driver.database_provider(),
+                    db_name,
+                    e
                 ));
                 false
             }
@@ -330,66 +333,45 @@ impl SchoolService {
         // Generate database name
         let db_name = build_school_database_name(&data.subdomain);
 
-        // Step 1: Create database in Neon
-        options
-            .progress(2, TOTAL_STEPS, "Creating database in Neon...")
-            .await;
-        use crate::clients::neon_client::NeonClient;
-        let neon_client = NeonClient::new()
-            .map_err(|e| AppError::ExternalServiceError(format!("Neon client error: {}", e)))?;
-
-        let db_id = neon_client
-            .create_database(&db_name, "neondb_owner")
-            .await
-            .map_err(|e| {
-                AppError::ExternalServiceError(format!("Failed to create database: {}", e))
-            })?;
+        let driver = ProvisioningDriver::from_env().map_err(AppError::ExternalServiceError)?;
 
+        // Step 1: Create the tenant database
         options
-            .success(&format!("✅ Database created with ID: {}", db_id))
+            .progress(
+                2,
+                TOTAL_STEPS,
+                &format!("Creating database in {}...", driver.database_provider()),
+            )
             .await;
+        let database = driver.create_database(&db_name).await.map_err(|e| {
+            AppError::ExternalServiceError(format!("Failed to create database: {}", e))
+        })?;
 
-        // Get Neon database password from environment
-        // This is the password for neondb_owner role in your Neon project
-        let db_password = match std::env::var("NEON_DB_PASSWORD") {
-            Ok(value) => value,
-            Err(_) => {
-                let reason = "NEON_DB_PASSWORD not set. Get this from Neon console.".to_string();
-                return Err(self
-                    .rollback_failed_provisioning(None, &neon_client, &db_name, reason)
-                    .await);
+        match database.db_id {
+            Some(db_id) => {
+                options
+                    .success(&format!("✅ Database created with ID: {}", db_id))
+                    .await
+            }
+            None => {
+                options
+                    .success(&format!("✅ Database created: {}", db_name))
+                    .await
             }
-        };
-
-        let db_connection_string =
-            neon_client.get_connection_string(&db_name, "neondb_owner", &db_password);
-
-        // Wait for Neon to finish creating the database and for Postgres to accept connections.
-        options.info("⏳ Waiting for database to be ready...").await;
-        if let Err(e) = neon_client.wait_for_database_ready(&db_name).await {
-            return Err(self
-                .rollback_failed_provisioning(
-                    None,
-                    &neon_client,
-                    &db_name,
-                    format!("Database not ready: {}", e),
-                )
-                .await);
         }
+        let db_connection_string = database.connection_string;
 
-        options
-            .info("⏳ Waiting for database connection to be ready...")
-            .await;
-        if let Err(e) = neon_client
-            .wait_for_database_connectable(&db_connection_string)
+        options.info("⏳ Waiting for database to be ready...").await;
+        if let Err(e) = driver
+            .wait_for_database_ready(&db_name, &db_connection_string)
             .await
         {
             return Err(self
                 .rollback_failed_provisioning(
                     None,
-                    &neon_client,
+                    &driver,
                     &db_name,
-                    format!("Database connection not ready: {}", e),
+                    format!("Database not ready: {}", e),
                 )
                 .await);
         }
@@ -418,7 +400,7 @@ impl SchoolService {
                 return Err(self
                     .rollback_failed_provisioning(
                         None,
-                        &neon_client,
+                        &driver,
                         &db_name,
                         format!("Failed to create school record: {}", e),
                     )
@@ -446,7 +428,7 @@ impl SchoolService {
                 return Err(self
                     .rollback_failed_provisioning(
                         Some(school_id),
-                        &neon_client,
+                        &driver,
                         &db_name,
                         format!("Backend-school client error: {}", e),
                     )
@@ -481,7 +463,7 @@ impl SchoolService {
                 return Err(self
                     .rollback_failed_provisioning(
                         Some(school_id),
-                        &neon_client,
+                        &driver,
                         &db_name,
                         format!("Tenant provisioning failed: {}", e),
                     )
@@ -489,58 +471,32 @@ impl SchoolService {
             }
         }
 
-        // Step 3: Create DNS record in Cloudflare
-        // NOTE: Skipped! Wrangler (GitHub Actions) will create and manage DNS automatically
-        // when deploying with custom_domain configuration
+        // Step 3: Publish the school frontend
         options
             .progress(
                 5,
                 TOTAL_STEPS,
-                "Deploying Cloudflare Worker and finalizing school setup...",
+                &format!(
+                    "Deploying {} and finalizing school setup...",
+                    driver.deployment_target()
+                ),
             )
             .await;
-        options
-            .info("Skipping DNS creation (Wrangler will handle this)")
-            .await;
-        let dns_record_id = "".to_string(); // Managed by Wrangler
-
-        // Step 4: Deploy Cloudflare Worker
-        options.info("Deploying Cloudflare Worker...").await;
-        let api_url = std::env::var("API_URL")
-            .unwrap_or_else(|_| "https://school-api.schoolorbit.app".to_string());
 
-        // Create Cloudflare client for deployment
-        use crate::clients::cloudflare_client::CloudflareClient;
-        let cloudflare_client = match CloudflareClient::new() {
-            Ok(client) => client,
-            Err(e) => {
-                return Err(self
-                    .mark_school_status_error(
-                        school_id,
-                        "deployment_failed",
-                        format!("Cloudflare client error: {}", e),
-                    )
-                    .await);
-            }
-        };
-
-        let subdomain_url = match cloudflare_client
-            .deploy_worker(&data.subdomain, &school_id.to_string(), &api_url)
-            .await
-        {
-            Ok((url, trigger_time)) => {
+        let deployment = match driver.deploy(&data.subdomain, &school_id.to_string()).await {
+            Ok(deployment) => {
                 options
-                    .success(&format!("✅ Worker deployment initiated: {}", url))
+                    .success(&format!("✅ Deployment initiated: {}", deployment.url))
                     .await;
 
-                if options.wait_for_deployment {
+                if options.wait_for_deployment && deployment.workflow_triggered_at.is_some() {
                     options.info("GitHub Actions workflow triggered").await;
                     options
                         .info("⏳ Waiting for deployment to complete (3-5 minutes)...")
                         .await;
 
-                    match cloudflare_client
-                        .wait_for_workflow_completion(&data.subdomain, trigger_time, 10)
+                    match driver
+                        .wait_for_deployment(&data.subdomain, &deployment)
                         .await
                     {
                         Ok(_) => {
@@ -555,31 +511,31 @@ impl SchoolService {
                             options
                                 .info("Workflow may still be processing in background")
                                 .await;
-                            options
-                                .info("Check: https://github.com/akephisit/schoolorbit-new/actions")
-                                .await;
+                            if let Some(run_url) = &deployment.run_url {
+                                options.info(&format!("Check: {}", run_url)).await;
+                            }
                         }
                     }
                 }
 
-                url
+                deployment
             }
             Err(e) => {
-                options
-                    .error(&format!("❌ Failed to deploy worker: {}", e))
-                    .await;
+                options.error(&format!("❌ Failed to deploy: {}", e)).await;
                 return Err(self
                     .mark_school_status_error(
                         school_id,
                         "deployment_failed",
-                        format!("Worker deployment failed: {}", e),
+                        format!("Deployment failed: {}", e),
                     )
                     .await);
             }
         };
+        let subdomain_url = deployment.url;
 
         // Update school record with deployment info
-        let config = build_active_school_config(db_id, &dns_record_id, &subdomain_url);
+        let config =
+            build_active_school_config(database.db_id, &deployment.dns_record_id, &subdomain_url);
 
         let updated_school = sqlx::query_as::<_, School>(
             r#"
@@ -705,66 +661,29 @@ impl SchoolService {
 
         info!(school = %school.name, subdomain = %school.subdomain, "loaded school for deletion");
 
-        let db_id = school.config.db_id;
-        let dns_record_id = school.config.dns_record_id.as_deref();
-
-        // Step 1: Delete Cloudflare Worker
-        info!("deleting Cloudflare Worker");
-        use crate::clients::cloudflare_client::CloudflareClient;
+        let db_name = build_school_database_name(&school.subdomain);
 
-        if let Ok(cf_client) = CloudflareClient::new() {
-            let worker_name = format!("schoolorbit-school-{}", school.subdomain);
-            match cf_client.delete_worker(&worker_name).await {
-                Ok(_) => info!(worker_name, "worker deleted"),
-                Err(e) => {
-                    warn!(error = %e, "failed to delete Worker; continuing with deletion");
+        match ProvisioningDriver::from_env() {
+            Ok(driver) => {
+                // Step 1: Remove the deployment (Worker and DNS, or the local hosts entry)
+                info!(target = driver.deployment_target(), "removing deployment");
+                for e in driver.undeploy(&school).await {
+                    warn!(error = %e, "failed to remove deployment; continuing with deletion");
                 }
-            }
-        } else {
-            warn!("Cloudflare client not available");
-        }
 
-        // Step 2: Delete DNS record (if exists)
-        info!("deleting DNS record");
-        if let Some(dns_id) = dns_record_id {
-            if !dns_id.is_empty() {
-                if let Ok(cf_client) = CloudflareClient::new() {
-                    match cf_client.delete_dns_record(dns_id).await {
-                        Ok(_) => info!(dns_id, "DNS record deleted"),
-                        Err(e) => {
-                            warn!(error = %e, "failed to delete DNS record");
-                        }
+                // Step 2: Delete tenant database
+                info!(db_name, db_id = ?school.config.db_id, "deleting tenant database");
+                match driver.delete_database(&db_name).await {
+                    Ok(_) => info!(db_name, "database deleted"),
+                    Err(e) => {
+                        warn!(error = %e, "failed to delete database; manual cleanup may be needed");
                     }
                 }
-            } else {
-                info!("no DNS record ID (managed by Wrangler)");
             }
-        } else {
-            info!("no DNS record to delete");
+            Err(e) => warn!(error = %e, "provisioning driver not available"),
         }
 
-        // Step 3: Delete Neon database
-        info!("deleting Neon database");
-
-        // Construct database name from subdomain (same as creation)
-        let db_name = format!("schoolorbit_{}", school.subdomain);
-
-        info!(db_name, db_id = ?db_id, "deleting tenant database");
-
-        use crate::clients::neon_client::NeonClient;
-
-        if let Ok(neon_client) = NeonClient::new() {
-            match neon_client.delete_database_by_name(&db_name).await {
-                Ok(_) => info!(db_name, "database deleted"),
-                Err(e) => {
-                    warn!(error = %e, "failed to delete database; manual cleanup may be needed");
-                }
-            }
-        } else {
-            warn!("Neon client not available");
-        }
-
-        // Step 4: Delete school record from database
+        // Step 3: Delete school record from database
         info!("deleting school record");
         let result = sqlx::query("DELETE FROM schools WHERE id = $1")
             .bind(id)
@@ -786,8 +705,6 @@ impl SchoolService {
         &self,
         school_id: Uuid,
     ) -> Result<crate::models::DeployResponse, AppError> {
-        use crate::clients::cloudflare_client::CloudflareClient;
-
         let school = self.get_school(school_id).await?;
 
         if school.status != "active" {
@@ -796,10 +713,7 @@ impl SchoolService {
             ));
         }
 
-        let api_url = std::env::var("API_URL")
-            .unwrap_or_else(|_| "https://school-api.schoolorbit.app".to_string());
-        let github_repo = std::env::var("GITHUB_REPO")
-            .unwrap_or_else(|_| "akephisit/schoolorbit-new".to_string());
+        let driver = ProvisioningDriver::from_env().map_err(AppError::ExternalServiceError)?;
 
         let history_id = sqlx::query_scalar::<_, Uuid>(
             "INSERT INTO deployment_history (school_id, status, message) 
@@ -811,23 +725,27 @@ impl SchoolService {
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let cloudflare_client =
-            CloudflareClient::new().map_err(|e| AppError::ExternalServiceError(e))?;
-
-        match cloudflare_client
-            .deploy_worker(&school.subdomain, &school_id.to_string(), &api_url)
+        match driver
+            .deploy(&school.subdomain, &school_id.to_string())
             .await
         {
-            Ok((deployment_url, _trigger_time)) => {
-                let github_actions_url = format!("https://github.com/{}/actions", github_repo);
+            Ok(deployment) => {
+                // Local deployments are ready once deploy returns; workflows finish later.
+                let status = if deployment.workflow_triggered_at.is_some() {
+                    "in_progress"
+                } else {
+                    "success"
+                };
 
                 sqlx::query(
                     "UPDATE deployment_history 
-                     SET status = 'in_progress', github_run_url = $2
+                     SET status = $2, github_run_url = $3,
+                         completed_at = CASE WHEN $2 = 'success' THEN NOW() END
                      WHERE id = $1",
                 )
                 .bind(history_id)
-                .bind(&github_actions_url)
+                .bind(status)
+                .bind(&deployment.run_url)
                 .execute(&self.pool)
                 .await
                 .ok();
@@ -835,8 +753,8 @@ impl SchoolService {
                 Ok(crate::models::DeployResponse {
                     success: true,
                     message: "Deployment triggered successfully".to_string(),
-                    deployment_url: Some(deployment_url),
-                    github_actions_url: Some(github_actions_url),
+                    deployment_url: Some(deployment.url),
+                    github_actions_url: deployment.run_url,
                 })
             }
             Err(e) => {
@@ -944,39 +862,42 @@ impl SchoolService {
             .info(&format!("Subdomain: {}", school.subdomain))
             .await;
 
-        // Step 1: Delete Cloudflare Worker
-        logger.progress(1, 4, "Deleting Cloudflare Worker...").await;
+        let driver = match ProvisioningDriver::from_env() {
+            Ok(driver) => Some(driver),
+            Err(e) => {
+                logger
+                    .warning(&format!("⚠️  Provisioning driver not available: {}", e))
+                    .await;
+                None
+            }
+        };
 
-        use crate::clients::cloudflare_client::CloudflareClient;
-        if let Ok(cf_client) = CloudflareClient::new() {
-            let worker_name = format!("schoolorbit-school-{}", school.subdomain);
-            match cf_client.delete_worker(&worker_name).await {
-                Ok(_) => {
-                    logger
-                        .success(&format!("✅ Worker deleted: {}", worker_name))
-                        .await
-                }
-                Err(e) => {
-                    logger
-                        .warning(&format!("⚠️  Worker deletion failed: {}", e))
-                        .await
-                }
+        // Step 1: Remove the deployment
+        logger.progress(1, 4, "Removing deployment...").await;
+        if let Some(driver) = &driver {
+            let errors = driver.undeploy(&school).await;
+            if errors.is_empty() {
+                logger
+                    .success(&format!("✅ {} removed", driver.deployment_target()))
+                    .await;
+            }
+            for e in errors {
+                logger.warning(&format!("⚠️  {}", e)).await;
             }
         }
 
-        // Step 2: Skip DNS (managed by Wrangler)
+        // Step 2: DNS is removed with the deployment (Wrangler or the hosts snippet)
         logger.progress(2, 4, "Skipping DNS cleanup...").await;
-        logger.info("⏭️  DNS managed by Wrangler").await;
+        logger.info("⏭️  DNS removed with the deployment").await;
 
-        // Step 3: Delete Neon database
-        logger.progress(3, 4, "Deleting Neon database...").await;
+        // Step 3: Delete tenant database
+        logger.progress(3, 4, "Deleting tenant database...").await;
 
-        let db_name = format!("schoolorbit_{}", school.subdomain);
+        let db_name = build_school_database_name(&school.subdomain);
         logger.info(&format!("Database name: {}", db_name)).await;
 
-        use crate::clients::neon_client::NeonClient;
-        if let Ok(neon_client) = NeonClient::new() {
-            match neon_client.delete_database_by_name(&db_name).await {
+        if let Some(driver) = &driver {
+            match driver.delete_database(&db_name).await {
                 Ok(_) => {
                     logger
                         .success(&format!("✅ Database deleted: {}", db_name))
@@ -1048,7 +969,7 @@ mod tests {
 
     #[test]
     fn active_school_config_records_database_and_deployment_url() {
-        let config = build_active_school_config(42, "", "https://sandbox.schoolorbit.app");
+        let config = build_active_school_config(Some(42), "", "https://sandbox.schoolorbit.app");
 
         assert_eq!(config.db_id, Some(42));
         assert_eq!(config.dns_record_id.as_deref(), Some(""));
@@ -1058,6 +979,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn active_school_config_allows_databases_without_provider_id() {
+        let config = build_active_school_config(None, "", "http://sandbox.school.test");
+
+        assert_eq!(config.db_id, None);
+        assert_eq!(
+            config.deployment_url.as_deref(),
+            Some("http://sandbox.school.test")
+        );
+    }
+
     #[test]
     fn subdomain_validation_rejects_symbols() {
         let error = validate_school_subdomain("bad_domain").unwrap_err();
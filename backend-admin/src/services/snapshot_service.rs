@@ -0,0 +1,577 @@
+use crate::clients::backend_school_client::{
+    BackendSchoolClient, SnapshotAnonymization, SnapshotExportRequest, SnapshotRestoreRequest,
+};
+use crate::error::AppError;
+use crate::models::{
+    RestoreSnapshot, SandboxAnonymization, School, TenantSnapshot, TenantSnapshotRestore,
+};
+use crate::services::SchoolService;
+use crate::utils::sse::SseLogger;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+const SANDBOX_PASSWORD_MIN_CHARS: usize = 12;
+
+pub struct SnapshotService {
+    pool: PgPool,
+}
+
+fn require_snapshot_source(school: &School) -> Result<&str, AppError> {
+    if school.status != "active" {
+        return Err(AppError::ValidationError(
+            "School must be active for snapshots and restores".to_string(),
+        ));
+    }
+
+    school
+        .db_connection_string
+        .as_deref()
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            AppError::ValidationError("School has no database connection string".to_string())
+        })
+}
+
+/// A snapshot can only be restored once its manifest checksum has been recorded.
+fn require_restorable(snapshot: &TenantSnapshot) -> Result<&str, AppError> {
+    match (
+        snapshot.status.as_str(),
+        snapshot.manifest_checksum.as_deref(),
+    ) {
+        ("completed", Some(checksum)) => Ok(checksum),
+        _ => Err(AppError::ValidationError(
+            "Only completed snapshots can be restored".to_string(),
+        )),
+    }
+}
+
+fn resolve_restore_target(
+    snapshot: &TenantSnapshot,
+    requested: Option<Uuid>,
+) -> Result<Uuid, AppError> {
+    requested.or(snapshot.school_id).ok_or_else(|| {
+        AppError::ValidationError(
+            "The source school no longer exists; choose a target school".to_string(),
+        )
+    })
+}
+
+/// A sandbox clone never overwrites the school it was taken from.
+fn validate_sandbox_clone(
+    snapshot: &TenantSnapshot,
+    target_id: Uuid,
+    anonymization: &SandboxAnonymization,
+) -> Result<(), AppError> {
+    if snapshot.school_id == Some(target_id) || snapshot.source_tenant_id == target_id {
+        return Err(AppError::ValidationError(
+            "A sandbox clone must target a different school than the snapshot source".to_string(),
+        ));
+    }
+    if anonymization.sandbox_password.chars().count() < SANDBOX_PASSWORD_MIN_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "Sandbox password must be at least {} characters",
+            SANDBOX_PASSWORD_MIN_CHARS
+        )));
+    }
+    Ok(())
+}
+
+impl SnapshotService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_snapshots(&self, school_id: Uuid) -> Result<Vec<TenantSnapshot>, AppError> {
+        sqlx::query_as::<_, TenantSnapshot>(
+            "SELECT * FROM tenant_snapshots
+             WHERE school_id = $1
+             ORDER BY created_at DESC
+             LIMIT 50",
+        )
+        .bind(school_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    pub async fn get_snapshot(&self, id: Uuid) -> Result<TenantSnapshot, AppError> {
+        sqlx::query_as::<_, TenantSnapshot>("SELECT * FROM tenant_snapshots WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("Snapshot not found".to_string()))
+    }
+
+    pub async fn list_restores(
+        &self,
+        snapshot_id: Uuid,
+    ) -> Result<Vec<TenantSnapshotRestore>, AppError> {
+        sqlx::query_as::<_, TenantSnapshotRestore>(
+            "SELECT * FROM tenant_snapshot_restores
+             WHERE snapshot_id = $1
+             ORDER BY created_at DESC",
+        )
+        .bind(snapshot_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn mark_snapshot_failed(&self, snapshot_id: Uuid, reason: &str) {
+        if let Err(e) = sqlx::query(
+            "UPDATE tenant_snapshots
+             SET status = 'failed', error = $2, completed_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(snapshot_id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        {
+            warn!(%snapshot_id, error = %e, "failed to mark snapshot as failed");
+        }
+    }
+
+    async fn finish_restore(
+        &self,
+        restore_id: Uuid,
+        school: &School,
+        outcome: Result<i64, &str>,
+    ) -> Result<(), AppError> {
+        let (status, schema_version, error) = match outcome {
+            Ok(version) => ("completed", Some(version), None),
+            Err(reason) => ("failed", None, Some(reason)),
+        };
+
+        sqlx::query(
+            "UPDATE tenant_snapshot_restores
+             SET status = $2, schema_version = $3, error = $4, completed_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(restore_id)
+        .bind(status)
+        .bind(schema_version)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // The tenant was taken out of service for the restore; return it to the
+        // status it had before and record the schema version it now runs.
+        sqlx::query(
+            "UPDATE schools
+             SET status = $2,
+                 migration_version = COALESCE($3, migration_version),
+                 migration_status = CASE WHEN $3 IS NULL THEN migration_status ELSE 'migrated' END,
+                 last_migrated_at = CASE WHEN $3 IS NULL THEN last_migrated_at ELSE NOW() END,
+                 updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(school.id)
+        .bind(&school.status)
+        .bind(schema_version.map(|version| version as i32))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Export a tenant snapshot with SSE logging for real-time progress
+    pub async fn create_snapshot_stream(
+        &self,
+        school_id: Uuid,
+        logger: SseLogger,
+    ) -> Result<TenantSnapshot, AppError> {
+        const TOTAL_STEPS: u8 = 3;
+
+        logger.progress(1, TOTAL_STEPS, "Checking school...").await;
+        let school = SchoolService::new(self.pool.clone())
+            .get_school(school_id)
+            .await?;
+        let db_connection_string = require_snapshot_source(&school)?.to_string();
+        logger
+            .info(&format!("📦 Creating snapshot for: {}", school.name))
+            .await;
+
+        let client = BackendSchoolClient::new().map_err(|e| {
+            AppError::ExternalServiceError(format!("Backend-school client error: {}", e))
+        })?;
+
+        let snapshot_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO tenant_snapshots (school_id, source_tenant_id, source_subdomain)
+             VALUES ($1, $1, $2)
+             RETURNING id",
+        )
+        .bind(school.id)
+        .bind(&school.subdomain)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        logger
+            .progress(
+                2,
+                TOTAL_STEPS,
+                "Exporting tables and files via backend-school...",
+            )
+            .await;
+        let summary = match client
+            .export_tenant_snapshot(&SnapshotExportRequest {
+                snapshot_id,
+                tenant_id: school.id,
+                subdomain: school.subdomain.clone(),
+                db_connection_string,
+            })
+            .await
+        {
+            Ok(summary) => summary,
+            Err(e) => {
+                logger
+                    .error(&format!("❌ Snapshot export failed: {}", e))
+                    .await;
+                self.mark_snapshot_failed(snapshot_id, &e).await;
+                return Err(AppError::ExternalServiceError(e));
+            }
+        };
+        logger
+            .success(&format!(
+                "✅ Exported {} tables ({} rows) and {} files at schema version {}",
+                summary.table_count,
+                summary.row_count,
+                summary.object_count,
+                summary.schema_version
+            ))
+            .await;
+
+        logger
+            .progress(3, TOTAL_STEPS, "Recording snapshot...")
+            .await;
+        let snapshot = sqlx::query_as::<_, TenantSnapshot>(
+            "UPDATE tenant_snapshots
+             SET status = 'completed',
+                 format_version = $2,
+                 schema_version = $3,
+                 captured_at = $4,
+                 manifest_checksum = $5,
+                 table_count = $6,
+                 row_count = $7,
+                 object_count = $8,
+                 total_bytes = $9,
+                 completed_at = NOW()
+             WHERE id = $1
+             RETURNING *",
+        )
+        .bind(snapshot_id)
+        .bind(summary.format_version)
+        .bind(summary.schema_version)
+        .bind(summary.captured_at)
+        .bind(&summary.manifest_checksum)
+        .bind(summary.table_count)
+        .bind(summary.row_count)
+        .bind(summary.object_count)
+        .bind(summary.total_bytes)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        logger
+            .success(&format!(
+                "🎉 Snapshot completed (checksum {})",
+                summary.manifest_checksum
+            ))
+            .await;
+        let payload = serde_json::to_value(&snapshot)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        logger.complete(payload).await;
+
+        Ok(snapshot)
+    }
+
+    /// Restore a snapshot into a school with SSE logging for real-time progress.
+    /// The target is taken out of tenant resolution while its data is replaced.
+    pub async fn restore_snapshot_stream(
+        &self,
+        snapshot_id: Uuid,
+        data: RestoreSnapshot,
+        logger: SseLogger,
+    ) -> Result<TenantSnapshotRestore, AppError> {
+        const TOTAL_STEPS: u8 = 4;
+
+        logger
+            .progress(1, TOTAL_STEPS, "Checking snapshot...")
+            .await;
+        let snapshot = self.get_snapshot(snapshot_id).await?;
+        let manifest_checksum = require_restorable(&snapshot)?.to_string();
+        let target_id = resolve_restore_target(&snapshot, data.target_school_id)?;
+        if let Some(anonymization) = &data.anonymization {
+            validate_sandbox_clone(&snapshot, target_id, anonymization)?;
+        }
+        let school = SchoolService::new(self.pool.clone())
+            .get_school(target_id)
+            .await?;
+        let db_connection_string = require_snapshot_source(&school)?.to_string();
+        logger
+            .info(&format!(
+                "Snapshot of {} at schema version {} → {}",
+                snapshot.source_subdomain,
+                snapshot.schema_version.unwrap_or_default(),
+                school.subdomain
+            ))
+            .await;
+        if data.replace_existing {
+            logger
+                .warning("⚠️  Existing school data will be replaced")
+                .await;
+        }
+        if data.anonymization.is_some() {
+            logger
+                .info("🕶️  Sandbox clone: personal data and files will be replaced")
+                .await;
+        }
+
+        let client = BackendSchoolClient::new().map_err(|e| {
+            AppError::ExternalServiceError(format!("Backend-school client error: {}", e))
+        })?;
+
+        logger
+            .progress(2, TOTAL_STEPS, "Taking school out of service...")
+            .await;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let restore_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO tenant_snapshot_restores (
+                snapshot_id, target_school_id, replace_existing, anonymized
+             )
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+        )
+        .bind(snapshot.id)
+        .bind(school.id)
+        .bind(data.replace_existing)
+        .bind(data.anonymization.is_some())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::ValidationError(
+                "A restore is already running for this school".to_string(),
+            ),
+            e => AppError::DatabaseError(e.to_string()),
+        })?;
+        sqlx::query("UPDATE schools SET status = 'restoring', updated_at = NOW() WHERE id = $1")
+            .bind(school.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        logger.success("✅ School is in restoring mode").await;
+
+        logger
+            .progress(
+                3,
+                TOTAL_STEPS,
+                "Restoring tables and files via backend-school...",
+            )
+            .await;
+        let summary = match client
+            .restore_tenant_snapshot(&SnapshotRestoreRequest {
+                snapshot_id: snapshot.id,
+                source_tenant_id: snapshot.source_tenant_id,
+                manifest_checksum,
+                target_tenant_id: school.id,
+                target_subdomain: school.subdomain.clone(),
+                db_connection_string,
+                replace_existing: data.replace_existing,
+                anonymization: data
+                    .anonymization
+                    .map(|anonymization| SnapshotAnonymization {
+                        sandbox_password: anonymization.sandbox_password,
+                    }),
+            })
+            .await
+        {
+            Ok(summary) => summary,
+            Err(e) => {
+                logger.error(&format!("❌ Restore failed: {}", e)).await;
+                if let Err(cleanup) = self.finish_restore(restore_id, &school, Err(&e)).await {
+                    warn!(%restore_id, error = %cleanup, "failed to record restore failure");
+                }
+                return Err(AppError::ExternalServiceError(e));
+            }
+        };
+        logger
+            .success(&format!(
+                "✅ Restored {} tables ({} rows) and {} files",
+                summary.table_count, summary.row_count, summary.object_count
+            ))
+            .await;
+        if let Some(anonymization) = &summary.anonymization {
+            logger
+                .success(&format!(
+                    "✅ Anonymized {} values, cleared {}, scrubbed {} free-text values and replaced {} files",
+                    anonymization.replaced_values,
+                    anonymization.cleared_values,
+                    anonymization.scrubbed_values,
+                    anonymization.placeholder_objects
+                ))
+                .await;
+            let recorded = serde_json::to_value(anonymization)
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            sqlx::query(
+                "UPDATE tenant_snapshot_restores SET anonymization_summary = $2 WHERE id = $1",
+            )
+            .bind(restore_id)
+            .bind(recorded)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+        if summary.schema_version > summary.snapshot_schema_version {
+            logger
+                .info(&format!(
+                    "Migrated from schema version {} to {}",
+                    summary.snapshot_schema_version, summary.schema_version
+                ))
+                .await;
+        }
+
+        logger
+            .progress(4, TOTAL_STEPS, "Returning school to service...")
+            .await;
+        self.finish_restore(restore_id, &school, Ok(summary.schema_version))
+            .await?;
+        let restore = sqlx::query_as::<_, TenantSnapshotRestore>(
+            "SELECT * FROM tenant_snapshot_restores WHERE id = $1",
+        )
+        .bind(restore_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        logger.success("🎉 Restore completed!").await;
+        let payload = serde_json::to_value(&restore)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        logger.complete(payload).await;
+
+        Ok(restore)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        require_restorable, require_snapshot_source, resolve_restore_target, validate_sandbox_clone,
+    };
+    use crate::models::{SandboxAnonymization, School, SchoolConfig, TenantSnapshot};
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    fn school(status: &str, db_connection_string: Option<&str>) -> School {
+        School {
+            id: Uuid::new_v4(),
+            name: "Sandbox".to_string(),
+            subdomain: "sandbox".to_string(),
+            db_name: "schoolorbit_sandbox".to_string(),
+            db_connection_string: db_connection_string.map(str::to_string),
+            status: status.to_string(),
+            config: Json(SchoolConfig::default()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn snapshot(status: &str, checksum: Option<&str>, school_id: Option<Uuid>) -> TenantSnapshot {
+        TenantSnapshot {
+            id: Uuid::new_v4(),
+            school_id,
+            source_tenant_id: Uuid::new_v4(),
+            source_subdomain: "sandbox".to_string(),
+            status: status.to_string(),
+            format_version: Some(1),
+            schema_version: Some(44),
+            captured_at: None,
+            manifest_checksum: checksum.map(str::to_string),
+            table_count: None,
+            row_count: None,
+            object_count: None,
+            total_bytes: None,
+            error: None,
+            created_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_source_must_be_active_with_a_database() {
+        assert_eq!(
+            require_snapshot_source(&school("active", Some("postgres://db"))).unwrap(),
+            "postgres://db"
+        );
+        assert!(require_snapshot_source(&school("provisioning", Some("postgres://db"))).is_err());
+        assert!(require_snapshot_source(&school("active", Some(""))).is_err());
+        assert!(require_snapshot_source(&school("active", None)).is_err());
+    }
+
+    #[test]
+    fn only_completed_snapshots_with_a_checksum_are_restorable() {
+        assert_eq!(
+            require_restorable(&snapshot("completed", Some("abc"), None)).unwrap(),
+            "abc"
+        );
+        assert!(require_restorable(&snapshot("in_progress", Some("abc"), None)).is_err());
+        assert!(require_restorable(&snapshot("failed", None, None)).is_err());
+    }
+
+    #[test]
+    fn restore_target_defaults_to_the_source_school() {
+        let source = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        assert_eq!(
+            resolve_restore_target(&snapshot("completed", Some("abc"), Some(source)), None)
+                .unwrap(),
+            source
+        );
+        assert_eq!(
+            resolve_restore_target(
+                &snapshot("completed", Some("abc"), Some(source)),
+                Some(other)
+            )
+            .unwrap(),
+            other
+        );
+        assert!(resolve_restore_target(&snapshot("completed", Some("abc"), None), None).is_err());
+    }
+
+    #[test]
+    fn sandbox_clones_need_another_school_and_a_long_password() {
+        let source = Uuid::new_v4();
+        let sandbox = Uuid::new_v4();
+        let snapshot = snapshot("completed", Some("abc"), Some(source));
+        let anonymization = |password: &str| SandboxAnonymization {
+            sandbox_password: password.to_string(),
+        };
+
+        assert!(
+            validate_sandbox_clone(&snapshot, sandbox, &anonymization("training-2026!")).is_ok()
+        );
+        assert!(
+            validate_sandbox_clone(&snapshot, source, &anonymization("training-2026!")).is_err()
+        );
+        assert!(validate_sandbox_clone(
+            &snapshot,
+            snapshot.source_tenant_id,
+            &anonymization("training-2026!")
+        )
+        .is_err());
+        assert!(validate_sandbox_clone(&snapshot, sandbox, &anonymization("short")).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::auth::AuthenticatedAdmin;
+use crate::clients::backend_school_client::{
+    BackendSchoolClient, SupportAccessCreateRequest, SupportAccessGrant, SupportAccessLookupRequest,
+};
+use crate::error::AppError;
+use crate::models::{
+    CreateSupportSession, School, SupportActivity, SupportHandoff, SupportSession,
+};
+use sqlx::PgPool;
+use std::env;
+use tracing::warn;
+use uuid::Uuid;
+
+const SUPPORT_SCOPES: &[&str] = &["read_only", "scoped"];
+const MIN_DURATION_MINUTES: i32 = 5;
+const MAX_DURATION_MINUTES: i32 = 240;
+const MAX_TICKET_REFERENCE_CHARS: usize = 100;
+
+pub struct SupportSessionService {
+    pool: PgPool,
+}
+
+fn validate_request(data: &CreateSupportSession) -> Result<(), AppError> {
+    let ticket = data.ticket_reference.trim();
+    if ticket.is_empty() {
+        return Err(AppError::ValidationError(
+            "A ticket reference is required".to_string(),
+        ));
+    }
+    if ticket.chars().count() > MAX_TICKET_REFERENCE_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "Ticket reference must be at most {} characters",
+            MAX_TICKET_REFERENCE_CHARS
+        )));
+    }
+    if !SUPPORT_SCOPES.contains(&data.scope.as_str()) {
+        return Err(AppError::ValidationError(
+            "Scope must be read_only or scoped".to_string(),
+        ));
+    }
+    match (data.scope.as_str(), data.permissions.is_empty()) {
+        ("read_only", false) => {
+            return Err(AppError::ValidationError(
+                "A read-only session cannot list permissions".to_string(),
+            ))
+        }
+        ("scoped", true) => {
+            return Err(AppError::ValidationError(
+                "A scoped session needs at least one permission".to_string(),
+            ))
+        }
+        _ => {}
+    }
+    if !(MIN_DURATION_MINUTES..=MAX_DURATION_MINUTES).contains(&data.duration_minutes) {
+        return Err(AppError::ValidationError(format!(
+            "Duration must be between {} and {} minutes",
+            MIN_DURATION_MINUTES, MAX_DURATION_MINUTES
+        )));
+    }
+    Ok(())
+}
+
+fn require_support_target(school: &School) -> Result<(), AppError> {
+    if school.status != "active" {
+        return Err(AppError::ValidationError(
+            "School must be active for support sessions".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Page of the school frontend that exchanges the token for a session cookie
+fn handoff_url(subdomain: &str, base_domain: &str, token: &str) -> String {
+    format!(
+        "https://{}.{}/support-handoff?token={}",
+        subdomain, base_domain, token
+    )
+}
+
+fn backend_school_client() -> Result<BackendSchoolClient, AppError> {
+    BackendSchoolClient::new()
+        .map_err(|e| AppError::ExternalServiceError(format!("Backend-school client error: {}", e)))
+}
+
+fn lookup_request(school: &School, id: Uuid) -> Result<SupportAccessLookupRequest, AppError> {
+    require_support_target(school)?;
+    Ok(SupportAccessLookupRequest {
+        subdomain: school.subdomain.clone(),
+        support_access_id: id,
+    })
+}
+
+impl SupportSessionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_sessions(&self, school_id: Uuid) -> Result<Vec<SupportSession>, AppError> {
+        sqlx::query_as::<_, SupportSession>(
+            "SELECT * FROM school_support_sessions
+             WHERE school_id = $1
+             ORDER BY created_at DESC
+             LIMIT 100",
+        )
+        .bind(school_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_session(&self, school_id: Uuid, id: Uuid) -> Result<SupportSession, AppError> {
+        sqlx::query_as::<_, SupportSession>(
+            "SELECT * FROM school_support_sessions WHERE id = $1 AND school_id = $2",
+        )
+        .bind(id)
+        .bind(school_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Support session not found".to_string()))
+    }
+
+    /// Mirror the school's view of the grant onto the local record
+    async fn apply_grant(&self, grant: &SupportAccessGrant) -> Result<SupportSession, AppError> {
+        sqlx::query_as::<_, SupportSession>(
+            "UPDATE school_support_sessions
+             SET status = $2, target_user_name = $3, decision_note = $4, expires_at = $5,
+                 ended_at = $6, updated_at = NOW()
+             WHERE id = $1
+             RETURNING *",
+        )
+        .bind(grant.id)
+        .bind(&grant.status)
+        .bind(&grant.target_user_name)
+        .bind(&grant.decision_note)
+        .bind(grant.expires_at)
+        .bind(grant.ended_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Ask the school for a support grant. With approval required the school's
+    /// administrators are notified and the session stays pending until they decide.
+    pub async fn create_session(
+        &self,
+        admin: &AuthenticatedAdmin,
+        school: &School,
+        data: CreateSupportSession,
+    ) -> Result<SupportSession, AppError> {
+        validate_request(&data)?;
+        require_support_target(school)?;
+        let client = backend_school_client()?;
+        let ticket_reference = data.ticket_reference.trim().to_string();
+        let target_username = data
+            .target_username
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+
+        let session = sqlx::query_as::<_, SupportSession>(
+            "INSERT INTO school_support_sessions (
+                id, school_id, admin_user_id, admin_name, ticket_reference, reason, scope,
+                permissions, duration_minutes, requires_approval, target_username
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4())
+        .bind(school.id)
+        .bind(admin.admin_id)
+        .bind(&admin.name)
+        .bind(&ticket_reference)
+        .bind(&data.reason)
+        .bind(&data.scope)
+        .bind(&data.permissions)
+        .bind(data.duration_minutes)
+        .bind(data.requires_approval)
+        .bind(&target_username)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let request = SupportAccessCreateRequest {
+            subdomain: school.subdomain.clone(),
+            support_access_id: session.id,
+            platform_admin_id: admin.admin_id,
+            platform_admin_name: admin.name.clone(),
+            ticket_reference,
+            reason: data.reason,
+            scope: data.scope,
+            permissions: data.permissions,
+            duration_minutes: data.duration_minutes,
+            requires_approval: data.requires_approval,
+            target_username,
+        };
+
+        match client.create_support_access(&request).await {
+            Ok(grant) => self.apply_grant(&grant).await,
+            Err(e) => {
+                // The school never recorded the grant, so nothing can start from it
+                if let Err(delete_error) =
+                    sqlx::query("DELETE FROM school_support_sessions WHERE id = $1")
+                        .bind(session.id)
+                        .execute(&self.pool)
+                        .await
+                {
+                    warn!(support_session_id = %session.id, error = %delete_error, "failed to discard support session");
+                }
+                Err(AppError::ExternalServiceError(e))
+            }
+        }
+    }
+
+    /// Current state of a session, refreshed from the school
+    pub async fn refresh_session(
+        &self,
+        school: &School,
+        id: Uuid,
+    ) -> Result<SupportSession, AppError> {
+        let session = self.find_session(school.id, id).await?;
+        if session.status == "ended" || session.status == "rejected" {
+            return Ok(session);
+        }
+
+        let grant = backend_school_client()?
+            .support_access_status(&lookup_request(school, id)?)
+            .await
+            .map_err(AppError::ExternalServiceError)?;
+        self.apply_grant(&grant).await
+    }
+
+    /// Link that signs the support agent into the school; valid for a few minutes
+    /// and only once
+    pub async fn issue_handoff(
+        &self,
+        school: &School,
+        id: Uuid,
+    ) -> Result<SupportHandoff, AppError> {
+        self.find_session(school.id, id).await?;
+
+        let handoff = backend_school_client()?
+            .issue_support_handoff(&lookup_request(school, id)?)
+            .await
+            .map_err(AppError::ExternalServiceError)?;
+        let session = self.apply_grant(&handoff.grant).await?;
+        let base_domain = env::var("BASE_DOMAIN").unwrap_or_else(|_| "schoolorbit.app".to_string());
+
+        Ok(SupportHandoff {
+            session,
+            url: handoff_url(&school.subdomain, &base_domain, &handoff.token),
+            expires_at: handoff.token_expires_at,
+        })
+    }
+
+    /// End the session at the school, which signs the support agent out
+    pub async fn end_session(&self, school: &School, id: Uuid) -> Result<SupportSession, AppError> {
+        self.find_session(school.id, id).await?;
+
+        let grant = backend_school_client()?
+            .end_support_access(&lookup_request(school, id)?)
+            .await
+            .map_err(AppError::ExternalServiceError)?;
+        self.apply_grant(&grant).await
+    }
+
+    /// Count a request reported by backend-school. Returns the session and the
+    /// school name for the audit entry; reports from another school are rejected.
+    pub async fn record_activity(
+        &self,
+        id: Uuid,
+        activity: &SupportActivity,
+    ) -> Result<(SupportSession, String), AppError> {
+        let school_name = sqlx::query_scalar::<_, String>(
+            "SELECT s.name FROM school_support_sessions ss
+             JOIN schools s ON s.id = ss.school_id
+             WHERE ss.id = $1 AND s.subdomain = $2",
+        )
+        .bind(id)
+        .bind(&activity.subdomain)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Support session not found".to_string()))?;
+
+        let session = sqlx::query_as::<_, SupportSession>(
+            "UPDATE school_support_sessions
+             SET request_count = request_count + 1,
+                 last_activity_at = GREATEST(COALESCE(last_activity_at, $2), $2),
+                 status = CASE WHEN status = 'approved' THEN 'active' ELSE status END,
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(activity.occurred_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok((session, school_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scope: &str, permissions: &[&str], duration_minutes: i32) -> CreateSupportSession {
+        CreateSupportSession {
+            ticket_reference: "SUP-1024".to_string(),
+            reason: None,
+            scope: scope.to_string(),
+            permissions: permissions.iter().map(|code| code.to_string()).collect(),
+            duration_minutes,
+            requires_approval: true,
+            target_username: None,
+        }
+    }
+
+    #[test]
+    fn validates_scope_permissions_and_duration() {
+        assert!(validate_request(&request("read_only", &[], 30)).is_ok());
+        assert!(validate_request(&request("scoped", &["settings.update.all"], 240)).is_ok());
+
+        assert!(validate_request(&request("read_only", &["settings.update.all"], 30)).is_err());
+        assert!(validate_request(&request("scoped", &[], 30)).is_err());
+        assert!(validate_request(&request("admin", &[], 30)).is_err());
+        assert!(validate_request(&request("read_only", &[], 4)).is_err());
+        assert!(validate_request(&request("read_only", &[], 241)).is_err());
+    }
+
+    #[test]
+    fn requires_a_ticket_reference() {
+        let mut blank = request("read_only", &[], 30);
+        blank.ticket_reference = "   ".to_string();
+        assert!(validate_request(&blank).is_err());
+    }
+
+    #[test]
+    fn handoff_url_points_at_the_school_subdomain() {
+        assert_eq!(
+            handoff_url("sandbox", "schoolorbit.app", "abc"),
+            "https://sandbox.schoolorbit.app/support-handoff?token=abc"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+use crate::clients::backend_school_client::{
+    BackendSchoolClient, TenantUsageMetrics, TenantUsageRequest,
+};
+use crate::error::AppError;
+use crate::models::{
+    QuotaAlert, QuotaAlertLevel, TenantUsageOverview, TenantUsageSample, UsageCollectionFailure,
+    UsageCollectionSummary,
+};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use sqlx::{types::Json, PgPool};
+use std::collections::BTreeMap;
+use std::env;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Tenants queried at the same time during a collection run
+const COLLECTION_CONCURRENCY: usize = 4;
+
+/// Collection and alerting settings, read from the environment
+#[derive(Debug, Clone)]
+pub struct UsageSettings {
+    /// Minutes between background collections; 0 disables the collector
+    pub interval_minutes: i64,
+    pub retention_days: i64,
+    /// Days without a login after which a school counts as inactive
+    pub inactive_days: i64,
+    pub limits: UsageLimits,
+}
+
+/// Platform-wide thresholds for quota alerts; unset limits raise no alert
+#[derive(Debug, Clone, Default)]
+pub struct UsageLimits {
+    pub max_storage_bytes: Option<i64>,
+    pub max_database_bytes: Option<i64>,
+    pub max_students: Option<i64>,
+    /// Share of a limit at which a warning is raised
+    pub warning_ratio: f64,
+}
+
+fn env_i64(name: &str, default: i64) -> i64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+fn env_limit(name: &str) -> Option<i64> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|limit: &i64| *limit > 0)
+}
+
+impl UsageSettings {
+    pub fn from_env() -> Self {
+        Self {
+            interval_minutes: env_i64("USAGE_COLLECTION_INTERVAL_MINUTES", 60).max(0),
+            retention_days: env_i64("USAGE_RETENTION_DAYS", 400).max(1),
+            inactive_days: env_i64("USAGE_INACTIVE_DAYS", 30).max(1),
+            limits: UsageLimits {
+                max_storage_bytes: env_limit("USAGE_ALERT_MAX_STORAGE_BYTES"),
+                max_database_bytes: env_limit("USAGE_ALERT_MAX_DATABASE_BYTES"),
+                max_students: env_limit("USAGE_ALERT_MAX_STUDENTS"),
+                warning_ratio: env::var("USAGE_ALERT_WARNING_RATIO")
+                    .ok()
+                    .and_then(|value| value.trim().parse().ok())
+                    .filter(|ratio: &f64| *ratio > 0.0 && *ratio <= 1.0)
+                    .unwrap_or(0.8),
+            },
+        }
+    }
+
+    /// A school without a sample for three collection intervals is stale
+    fn stale_after(&self) -> Duration {
+        Duration::minutes(self.interval_minutes.max(60) * 3)
+    }
+}
+
+impl UsageLimits {
+    /// Limits of a school's plan take precedence over the platform-wide ones
+    pub fn with_plan(&self, max_students: Option<i64>, max_storage_bytes: Option<i64>) -> Self {
+        Self {
+            max_students: max_students.or(self.max_students),
+            max_storage_bytes: max_storage_bytes.or(self.max_storage_bytes),
+            ..self.clone()
+        }
+    }
+
+    pub fn alerts_for(&self, subdomain: &str, sample: &TenantUsageSample) -> Vec<QuotaAlert> {
+        [
+            (
+                "storage_bytes",
+                sample.storage_bytes,
+                self.max_storage_bytes,
+            ),
+            (
+                "database_bytes",
+                sample.database_bytes,
+                self.max_database_bytes,
+            ),
+            ("student_count", sample.student_count, self.max_students),
+        ]
+        .into_iter()
+        .filter_map(|(metric, current, limit)| {
+            let limit = limit?;
+            let level = if current >= limit {
+                QuotaAlertLevel::Exceeded
+            } else if current as f64 >= limit as f64 * self.warning_ratio {
+                QuotaAlertLevel::Warning
+            } else {
+                return None;
+            };
+
+            Some(QuotaAlert {
+                school_id: sample.school_id,
+                subdomain: subdomain.to_string(),
+                metric: metric.to_string(),
+                current,
+                limit,
+                level,
+            })
+        })
+        .collect()
+    }
+}
+
+/// A school is inactive when nobody has signed in within `inactive_days`.
+/// Without a sample there is nothing to judge, so the school is not flagged.
+fn is_inactive(sample: Option<&TenantUsageSample>, now: DateTime<Utc>, inactive_days: i64) -> bool {
+    match sample {
+        Some(sample) => match sample.last_login_at {
+            Some(last_login_at) => last_login_at < now - Duration::days(inactive_days),
+            None => true,
+        },
+        None => false,
+    }
+}
+
+fn is_stale(sample: Option<&TenantUsageSample>, now: DateTime<Utc>, stale_after: Duration) -> bool {
+    sample.is_none_or(|sample| sample.collected_at < now - stale_after)
+}
+
+fn clamp_trend_days(days: Option<i64>) -> i64 {
+    days.unwrap_or(30).clamp(1, 365)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CollectionTarget {
+    id: Uuid,
+    subdomain: String,
+    db_connection_string: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct OverviewRow {
+    school_id: Uuid,
+    name: String,
+    subdomain: String,
+    plan_max_students: Option<i64>,
+    plan_max_storage_bytes: Option<i64>,
+    #[sqlx(flatten)]
+    sample: OptionalSample,
+}
+
+/// `LEFT JOIN` columns of the latest sample; all NULL for a school without samples
+#[derive(Debug, sqlx::FromRow)]
+struct OptionalSample {
+    sample_id: Option<Uuid>,
+    collected_at: Option<DateTime<Utc>>,
+    active_users_7d: Option<i64>,
+    active_users_30d: Option<i64>,
+    student_count: Option<i64>,
+    staff_count: Option<i64>,
+    storage_bytes: Option<i64>,
+    storage_bytes_by_purpose: Option<Json<BTreeMap<String, i64>>>,
+    certificates_issued: Option<i64>,
+    last_login_at: Option<DateTime<Utc>>,
+    migration_version: Option<i32>,
+    database_bytes: Option<i64>,
+}
+
+impl OptionalSample {
+    fn into_sample(self, school_id: Uuid) -> Option<TenantUsageSample> {
+        Some(TenantUsageSample {
+            id: self.sample_id?,
+            school_id,
+            collected_at: self.collected_at?,
+            active_users_7d: self.active_users_7d.unwrap_or(0),
+            active_users_30d: self.active_users_30d.unwrap_or(0),
+            student_count: self.student_count.unwrap_or(0),
+            staff_count: self.staff_count.unwrap_or(0),
+            storage_bytes: self.storage_bytes.unwrap_or(0),
+            storage_bytes_by_purpose: self.storage_bytes_by_purpose.unwrap_or_default(),
+            certificates_issued: self.certificates_issued.unwrap_or(0),
+            last_login_at: self.last_login_at,
+            migration_version: self.migration_version,
+            database_bytes: self.database_bytes.unwrap_or(0),
+        })
+    }
+}
+
+async fn collect_one(
+    client: &BackendSchoolClient,
+    target: CollectionTarget,
+) -> (CollectionTarget, Result<TenantUsageMetrics, String>) {
+    let request = TenantUsageRequest {
+        subdomain: target.subdomain.clone(),
+        db_connection_string: target.db_connection_string.clone(),
+    };
+    let result = client.collect_tenant_usage(&request).await;
+    (target, result)
+}
+
+pub struct UsageService {
+    pool: PgPool,
+    settings: UsageSettings,
+}
+
+impl UsageService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            settings: UsageSettings::from_env(),
+        }
+    }
+
+    async fn record_sample(
+        &self,
+        school_id: Uuid,
+        metrics: &TenantUsageMetrics,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO tenant_usage_samples (
+                school_id, collected_at, active_users_7d, active_users_30d, student_count,
+                staff_count, storage_bytes, storage_bytes_by_purpose, certificates_issued,
+                last_login_at, migration_version, database_bytes
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+        )
+        .bind(school_id)
+        .bind(metrics.collected_at)
+        .bind(metrics.active_users_7d)
+        .bind(metrics.active_users_30d)
+        .bind(metrics.student_count)
+        .bind(metrics.staff_count)
+        .bind(metrics.storage_bytes)
+        .bind(Json(&metrics.storage_bytes_by_purpose))
+        .bind(metrics.certificates_issued)
+        .bind(metrics.last_login_at)
+        .bind(metrics.migration_version.map(|version| version as i32))
+        .bind(metrics.database_bytes)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Collect one sample from every active school and prune expired samples
+    pub async fn collect_all(&self) -> Result<UsageCollectionSummary, AppError> {
+        let client = BackendSchoolClient::new().map_err(|e| {
+            AppError::ExternalServiceError(format!("Backend-school client error: {}", e))
+        })?;
+
+        let targets = sqlx::query_as::<_, CollectionTarget>(
+            "SELECT id, subdomain, db_connection_string
+             FROM schools
+             WHERE status = 'active' AND db_connection_string IS NOT NULL
+             ORDER BY subdomain",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let collections: Vec<_> = targets
+            .into_iter()
+            .map(|target| collect_one(&client, target))
+            .collect();
+        let mut results = stream::iter(collections).buffer_unordered(COLLECTION_CONCURRENCY);
+
+        let mut collected = 0;
+        let mut failed = Vec::new();
+        while let Some((target, result)) = results.next().await {
+            let outcome = match result {
+                Ok(metrics) => self.record_sample(target.id, &metrics).await,
+                Err(e) => Err(AppError::ExternalServiceError(e)),
+            };
+
+            match outcome {
+                Ok(()) => collected += 1,
+                Err(e) => {
+                    warn!(subdomain = %target.subdomain, error = %e, "usage collection failed");
+                    failed.push(UsageCollectionFailure {
+                        school_id: target.id,
+                        subdomain: target.subdomain,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let pruned = sqlx::query(
+            "DELETE FROM tenant_usage_samples WHERE collected_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(self.settings.retention_days as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .rows_affected();
+
+        info!(
+            collected,
+            failed = failed.len(),
+            pruned,
+            "usage collection finished"
+        );
+
+        Ok(UsageCollectionSummary {
+            collected,
+            failed,
+            pruned,
+        })
+    }
+
+    /// Samples of one school over the last `days` days, oldest first
+    pub async fn school_trend(
+        &self,
+        school_id: Uuid,
+        days: Option<i64>,
+    ) -> Result<Vec<TenantUsageSample>, AppError> {
+        sqlx::query_as::<_, TenantUsageSample>(
+            "SELECT * FROM tenant_usage_samples
+             WHERE school_id = $1 AND collected_at >= NOW() - make_interval(days => $2)
+             ORDER BY collected_at",
+        )
+        .bind(school_id)
+        .bind(clamp_trend_days(days) as i32)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Latest sample and health flags of every active school
+    pub async fn overview(&self) -> Result<Vec<TenantUsageOverview>, AppError> {
+        let rows = sqlx::query_as::<_, OverviewRow>(
+            "SELECT s.id AS school_id, s.name, s.subdomain,
+                    p.max_students AS plan_max_students,
+                    p.max_storage_bytes AS plan_max_storage_bytes,
+                    u.id AS sample_id, u.collected_at, u.active_users_7d, u.active_users_30d,
+                    u.student_count, u.staff_count, u.storage_bytes, u.storage_bytes_by_purpose,
+                    u.certificates_issued, u.last_login_at, u.migration_version, u.database_bytes
+             FROM schools s
+             LEFT JOIN plans p ON p.id = s.plan_id
+             LEFT JOIN LATERAL (
+                 SELECT * FROM tenant_usage_samples
+                 WHERE school_id = s.id
+                 ORDER BY collected_at DESC
+                 LIMIT 1
+             ) u ON TRUE
+             WHERE s.status = 'active'
+             ORDER BY s.subdomain",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let now = Utc::now();
+        let stale_after = self.settings.stale_after();
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let latest = row.sample.into_sample(row.school_id);
+                let limits = self
+                    .settings
+                    .limits
+                    .with_plan(row.plan_max_students, row.plan_max_storage_bytes);
+                let alerts = latest
+                    .as_ref()
+                    .map(|sample| limits.alerts_for(&row.subdomain, sample))
+                    .unwrap_or_default();
+
+                TenantUsageOverview {
+                    school_id: row.school_id,
+                    name: row.name,
+                    subdomain: row.subdomain,
+                    stale: is_stale(latest.as_ref(), now, stale_after),
+                    inactive: is_inactive(latest.as_ref(), now, self.settings.inactive_days),
+                    latest,
+                    alerts,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn inactive_schools(&self) -> Result<Vec<TenantUsageOverview>, AppError> {
+        Ok(self
+            .overview()
+            .await?
+            .into_iter()
+            .filter(|school| school.inactive)
+            .collect())
+    }
+
+    pub async fn quota_alerts(&self) -> Result<Vec<QuotaAlert>, AppError> {
+        Ok(self
+            .overview()
+            .await?
+            .into_iter()
+            .flat_map(|school| school.alerts)
+            .collect())
+    }
+}
+
+/// Collect usage on a fixed interval for the lifetime of the process
+pub fn spawn_usage_collector(pool: PgPool) {
+    let settings = UsageSettings::from_env();
+    if settings.interval_minutes == 0 {
+        info!("usage collection disabled (USAGE_COLLECTION_INTERVAL_MINUTES=0)");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let period = std::time::Duration::from_secs(settings.interval_minutes as u64 * 60);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = UsageService::new(pool.clone()).collect_all().await {
+                warn!(error = %e, "scheduled usage collection failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp_trend_days, is_inactive, is_stale, UsageLimits};
+    use crate::models::{QuotaAlertLevel, TenantUsageSample};
+    use chrono::{Duration, Utc};
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    fn sample(storage_bytes: i64, student_count: i64) -> TenantUsageSample {
+        TenantUsageSample {
+            id: Uuid::new_v4(),
+            school_id: Uuid::new_v4(),
+            collected_at: Utc::now(),
+            active_users_7d: 10,
+            active_users_30d: 40,
+            student_count,
+            staff_count: 12,
+            storage_bytes,
+            storage_bytes_by_purpose: Json(Default::default()),
+            certificates_issued: 3,
+            last_login_at: Some(Utc::now()),
+            migration_version: Some(44),
+            database_bytes: 8_000_000,
+        }
+    }
+
+    #[test]
+    fn alerts_warn_near_the_limit_and_flag_exceeded_limits() {
+        let limits = UsageLimits {
+            max_storage_bytes: Some(1_000),
+            max_database_bytes: None,
+            max_students: Some(500),
+            warning_ratio: 0.8,
+        };
+
+        let alerts = limits.alerts_for("demo", &sample(850, 500));
+
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].metric, "storage_bytes");
+        assert_eq!(alerts[0].level, QuotaAlertLevel::Warning);
+        assert_eq!(alerts[1].metric, "student_count");
+        assert_eq!(alerts[1].level, QuotaAlertLevel::Exceeded);
+        assert!(limits.alerts_for("demo", &sample(100, 10)).is_empty());
+    }
+
+    #[test]
+    fn unset_limits_raise_no_alerts() {
+        let limits = UsageLimits {
+            warning_ratio: 0.8,
+            ..UsageLimits::default()
+        };
+
+        assert!(limits
+            .alerts_for("demo", &sample(i64::MAX, i64::MAX))
+            .is_empty());
+    }
+
+    #[test]
+    fn plan_limits_override_platform_limits() {
+        let limits = UsageLimits {
+            max_storage_bytes: Some(1_000),
+            max_database_bytes: None,
+            max_students: Some(500),
+            warning_ratio: 0.8,
+        };
+
+        let planned = limits.with_plan(Some(2_000), None);
+        assert_eq!(planned.max_students, Some(2_000));
+        assert_eq!(planned.max_storage_bytes, Some(1_000));
+        assert!(planned.alerts_for("demo", &sample(100, 600)).is_empty());
+    }
+
+    #[test]
+    fn schools_without_recent_logins_are_inactive() {
+        let now = Utc::now();
+        let mut quiet = sample(0, 0);
+        quiet.last_login_at = Some(now - Duration::days(45));
+        let mut never = sample(0, 0);
+        never.last_login_at = None;
+
+        assert!(is_inactive(Some(&quiet), now, 30));
+        assert!(is_inactive(Some(&never), now, 30));
+        assert!(!is_inactive(Some(&sample(0, 0)), now, 30));
+        assert!(!is_inactive(None, now, 30));
+    }
+
+    #[test]
+    fn schools_without_recent_samples_are_stale() {
+        let now = Utc::now();
+        let mut old = sample(0, 0);
+        old.collected_at = now - Duration::hours(4);
+
+        assert!(is_stale(None, now, Duration::hours(3)));
+        assert!(is_stale(Some(&old), now, Duration::hours(3)));
+        assert!(!is_stale(Some(&sample(0, 0)), now, Duration::hours(3)));
+    }
+
+    #[test]
+    fn trend_window_is_bounded() {
+        assert_eq!(clamp_trend_days(None), 30);
+        assert_eq!(clamp_trend_days(Some(0)), 1);
+        assert_eq!(clamp_trend_days(Some(5_000)), 365);
+    }
+}
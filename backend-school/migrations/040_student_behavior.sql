@@ -0,0 +1,279 @@
+-- Student behavior V1: configurable incident/merit categories, incident records with
+-- evidence, semester point thresholds that open advisor follow-up work, and interventions.
+
+CREATE TABLE behavior_categories (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(50) NOT NULL,
+    name VARCHAR(120) NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    points INTEGER NOT NULL,
+    description TEXT,
+    order_index INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT behavior_categories_code_unique UNIQUE (code),
+    CONSTRAINT behavior_categories_kind_check CHECK (kind IN ('incident', 'merit')),
+    CONSTRAINT behavior_categories_points_sign_check CHECK (
+        (kind = 'incident' AND points < 0)
+        OR (kind = 'merit' AND points > 0)
+    )
+);
+
+CREATE INDEX idx_behavior_categories_order
+    ON behavior_categories (is_active, kind, order_index, name);
+
+CREATE TRIGGER update_behavior_categories_updated_at
+    BEFORE UPDATE ON behavior_categories
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE behavior_thresholds (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(120) NOT NULL,
+    points_at_or_below INTEGER NOT NULL,
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT behavior_thresholds_negative_check CHECK (points_at_or_below < 0)
+);
+
+CREATE UNIQUE INDEX idx_behavior_thresholds_active_points_unique
+    ON behavior_thresholds (points_at_or_below)
+    WHERE is_active = true;
+
+CREATE TRIGGER update_behavior_thresholds_updated_at
+    BEFORE UPDATE ON behavior_thresholds
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE behavior_incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES behavior_categories(id) ON DELETE RESTRICT,
+    academic_semester_id UUID NOT NULL REFERENCES academic_semesters(id) ON DELETE RESTRICT,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    room_id UUID REFERENCES rooms(id) ON DELETE SET NULL,
+    location_note VARCHAR(200),
+    description TEXT,
+    points INTEGER NOT NULL,
+    is_confidential BOOLEAN NOT NULL DEFAULT false,
+    status VARCHAR(20) NOT NULL DEFAULT 'recorded',
+    reported_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    voided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    voided_at TIMESTAMPTZ,
+    void_reason TEXT,
+    guardian_notified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT behavior_incidents_status_check CHECK (status IN ('recorded', 'voided')),
+    CONSTRAINT behavior_incidents_void_check CHECK (
+        (status = 'voided' AND voided_at IS NOT NULL)
+        OR (status = 'recorded' AND voided_at IS NULL)
+    )
+);
+
+CREATE INDEX idx_behavior_incidents_student_semester
+    ON behavior_incidents (student_id, academic_semester_id, occurred_at DESC);
+
+CREATE INDEX idx_behavior_incidents_semester_occurred
+    ON behavior_incidents (academic_semester_id, occurred_at DESC);
+
+CREATE INDEX idx_behavior_incidents_category
+    ON behavior_incidents (category_id);
+
+CREATE TRIGGER update_behavior_incidents_updated_at
+    BEFORE UPDATE ON behavior_incidents
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE behavior_incident_evidence (
+    incident_id UUID NOT NULL REFERENCES behavior_incidents(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'behavior_evidence',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (incident_id, file_id),
+    CONSTRAINT behavior_incident_evidence_file_unique UNIQUE (file_id),
+    CONSTRAINT behavior_incident_evidence_purpose_check CHECK (
+        purpose_code = 'behavior_evidence'
+    ),
+    CONSTRAINT behavior_incident_evidence_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE behavior_interventions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    academic_semester_id UUID NOT NULL REFERENCES academic_semesters(id) ON DELETE RESTRICT,
+    incident_id UUID REFERENCES behavior_incidents(id) ON DELETE SET NULL,
+    action_type VARCHAR(30) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    note TEXT,
+    due_date DATE,
+    status VARCHAR(20) NOT NULL DEFAULT 'planned',
+    outcome TEXT,
+    assigned_to UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT behavior_interventions_action_type_check CHECK (
+        action_type IN ('counseling', 'guardian_meeting', 'home_visit', 'referral', 'contract', 'other')
+    ),
+    CONSTRAINT behavior_interventions_status_check CHECK (
+        status IN ('planned', 'in_progress', 'completed', 'cancelled')
+    )
+);
+
+CREATE INDEX idx_behavior_interventions_student
+    ON behavior_interventions (student_id, academic_semester_id, created_at DESC);
+
+CREATE INDEX idx_behavior_interventions_open
+    ON behavior_interventions (assigned_to, due_date)
+    WHERE status IN ('planned', 'in_progress');
+
+CREATE TRIGGER update_behavior_interventions_updated_at
+    BEFORE UPDATE ON behavior_interventions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE behavior_followup_windows (
+    academic_semester_id UUID PRIMARY KEY
+        REFERENCES academic_semesters(id) ON DELETE CASCADE,
+    workflow_window_id UUID NOT NULL
+        REFERENCES workflow_windows(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE behavior_threshold_crossings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    threshold_id UUID NOT NULL REFERENCES behavior_thresholds(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    academic_semester_id UUID NOT NULL REFERENCES academic_semesters(id) ON DELETE CASCADE,
+    incident_id UUID REFERENCES behavior_incidents(id) ON DELETE SET NULL,
+    points_total INTEGER NOT NULL,
+    work_item_id UUID REFERENCES work_items(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT behavior_threshold_crossings_unique
+        UNIQUE (threshold_id, student_id, academic_semester_id)
+);
+
+CREATE INDEX idx_behavior_threshold_crossings_student
+    ON behavior_threshold_crossings (student_id, academic_semester_id);
+
+COMMENT ON TABLE behavior_categories IS
+    'Configurable incident (negative points) and merit (positive points) categories.';
+COMMENT ON COLUMN behavior_incidents.points IS
+    'Category points captured when the incident was recorded; later category edits do not rewrite history.';
+COMMENT ON COLUMN behavior_incidents.is_confidential IS
+    'Confidential incidents are hidden from guardians, students and advisors unless behavior_confidential.read.school is granted.';
+COMMENT ON TABLE behavior_threshold_crossings IS
+    'One advisor follow-up per threshold, student and semester even if the running total later recovers and drops again.';
+COMMENT ON TABLE behavior_followup_windows IS
+    'Workflow window that owns behavior follow-up work items for an academic semester.';
+
+WITH behavior_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'behavior.read.own',
+            'ดูพฤติกรรมของตนเอง',
+            'behavior',
+            'read',
+            'own',
+            'นักเรียนดูคะแนนและเหตุการณ์พฤติกรรมที่ไม่เป็นความลับของตนเอง'
+        ),
+        (
+            'behavior.read.assigned',
+            'ดูพฤติกรรมนักเรียนในที่ปรึกษา',
+            'behavior',
+            'read',
+            'assigned',
+            'ดูเหตุการณ์ คะแนน และการติดตามพฤติกรรมของนักเรียนในห้องที่เป็นครูที่ปรึกษา'
+        ),
+        (
+            'behavior.read.school',
+            'ดูพฤติกรรมนักเรียนทั้งโรงเรียน',
+            'behavior',
+            'read',
+            'school',
+            'ดูเหตุการณ์ คะแนน และการติดตามพฤติกรรมของนักเรียนทั้งโรงเรียน'
+        ),
+        (
+            'behavior.create.school',
+            'บันทึกพฤติกรรมนักเรียน',
+            'behavior',
+            'create',
+            'school',
+            'บันทึกเหตุการณ์ความผิดหรือความดีของนักเรียนทุกคนในโรงเรียน'
+        ),
+        (
+            'behavior.manage.assigned',
+            'ติดตามพฤติกรรมนักเรียนในที่ปรึกษา',
+            'behavior',
+            'manage',
+            'assigned',
+            'บันทึกและปรับสถานะการติดตามช่วยเหลือนักเรียนในห้องที่เป็นครูที่ปรึกษา'
+        ),
+        (
+            'behavior.manage.school',
+            'จัดการระบบพฤติกรรมนักเรียน',
+            'behavior',
+            'manage',
+            'school',
+            'จัดการหมวดคะแนน เกณฑ์เฝ้าระวัง ยกเลิกเหตุการณ์ และการติดตามช่วยเหลือทั้งโรงเรียน'
+        ),
+        (
+            'behavior_confidential.read.school',
+            'ดูเหตุการณ์พฤติกรรมที่เป็นความลับ',
+            'behavior_confidential',
+            'read',
+            'school',
+            'ดูรายละเอียดและหลักฐานของเหตุการณ์พฤติกรรมที่ถูกระบุว่าเป็นความลับ'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM behavior_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
+
+WITH base_staff_permissions AS (
+    SELECT id
+    FROM permissions
+    WHERE code IN (
+        'behavior.create.school',
+        'behavior.read.assigned',
+        'behavior.manage.assigned'
+    )
+),
+staff_roles AS (
+    SELECT id
+    FROM roles
+    WHERE user_type = 'staff'
+)
+INSERT INTO role_permissions (role_id, permission_id, created_at)
+SELECT staff_roles.id, base_staff_permissions.id, now()
+FROM staff_roles
+CROSS JOIN base_staff_permissions
+ON CONFLICT DO NOTHING;
+
+WITH base_student_permissions AS (
+    SELECT id
+    FROM permissions
+    WHERE code = 'behavior.read.own'
+),
+student_roles AS (
+    SELECT id
+    FROM roles
+    WHERE user_type = 'student'
+)
+INSERT INTO role_permissions (role_id, permission_id, created_at)
+SELECT student_roles.id, base_student_permissions.id, now()
+FROM student_roles
+CROSS JOIN base_student_permissions
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,163 @@
+-- Guardian-submitted student leave requests with homeroom advisor review and
+-- excused timetable occurrences pre-filled on approval.
+
+CREATE TABLE student_leave_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    academic_semester_id UUID NOT NULL REFERENCES academic_semesters(id) ON DELETE RESTRICT,
+    leave_type VARCHAR(20) NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    review_note TEXT,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    work_item_id UUID REFERENCES work_items(id) ON DELETE SET NULL,
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT student_leave_requests_type_check CHECK (
+        leave_type IN ('sick', 'personal', 'other')
+    ),
+    CONSTRAINT student_leave_requests_status_check CHECK (
+        status IN ('pending', 'returned', 'approved', 'rejected', 'cancelled')
+    ),
+    CONSTRAINT student_leave_requests_date_range_check CHECK (end_date >= start_date),
+    CONSTRAINT student_leave_requests_review_check CHECK (
+        status NOT IN ('returned', 'approved', 'rejected')
+        OR (reviewed_by IS NOT NULL AND reviewed_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_student_leave_requests_student
+    ON student_leave_requests (student_id, start_date DESC);
+
+CREATE INDEX idx_student_leave_requests_pending
+    ON student_leave_requests (status, submitted_at)
+    WHERE status = 'pending';
+
+CREATE TRIGGER update_student_leave_requests_updated_at
+    BEFORE UPDATE ON student_leave_requests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE student_leave_request_periods (
+    leave_request_id UUID NOT NULL REFERENCES student_leave_requests(id) ON DELETE CASCADE,
+    period_id UUID NOT NULL REFERENCES academic_periods(id) ON DELETE RESTRICT,
+    PRIMARY KEY (leave_request_id, period_id)
+);
+
+CREATE TABLE student_leave_request_documents (
+    leave_request_id UUID NOT NULL REFERENCES student_leave_requests(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'student_leave_document',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (leave_request_id, file_id),
+    CONSTRAINT student_leave_request_documents_file_unique UNIQUE (file_id),
+    CONSTRAINT student_leave_request_documents_purpose_check CHECK (
+        purpose_code = 'student_leave_document'
+    ),
+    CONSTRAINT student_leave_request_documents_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE student_leave_excused_occurrences (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    leave_request_id UUID NOT NULL REFERENCES student_leave_requests(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    leave_date DATE NOT NULL,
+    timetable_entry_id UUID NOT NULL
+        REFERENCES academic_timetable_entries(id) ON DELETE CASCADE,
+    period_id UUID NOT NULL REFERENCES academic_periods(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT student_leave_excused_occurrences_unique
+        UNIQUE (student_id, timetable_entry_id, leave_date)
+);
+
+CREATE INDEX idx_student_leave_excused_occurrences_request
+    ON student_leave_excused_occurrences (leave_request_id, leave_date);
+
+CREATE INDEX idx_student_leave_excused_occurrences_entry_date
+    ON student_leave_excused_occurrences (timetable_entry_id, leave_date);
+
+CREATE TABLE student_leave_review_windows (
+    academic_semester_id UUID PRIMARY KEY
+        REFERENCES academic_semesters(id) ON DELETE CASCADE,
+    workflow_window_id UUID NOT NULL
+        REFERENCES workflow_windows(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE student_leave_request_periods IS
+    'Periods covered by a partial-day request; no rows means the whole school day.';
+COMMENT ON TABLE student_leave_excused_occurrences IS
+    'Timetable occurrences pre-filled as excused when a leave request is approved; attendance taking reads these as the default status.';
+COMMENT ON TABLE student_leave_review_windows IS
+    'Workflow window that owns advisor leave review work items for an academic semester.';
+
+WITH student_leave_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'student_leave.read.assigned',
+            'ดูใบลานักเรียนในที่ปรึกษา',
+            'student_leave',
+            'read',
+            'assigned',
+            'ดูคำขอลาที่ผู้ปกครองส่งสำหรับนักเรียนในห้องที่เป็นครูที่ปรึกษา'
+        ),
+        (
+            'student_leave.read.school',
+            'ดูใบลานักเรียนทั้งโรงเรียน',
+            'student_leave',
+            'read',
+            'school',
+            'ดูคำขอลาของนักเรียนทุกคนในโรงเรียน'
+        ),
+        (
+            'student_leave.approve.assigned',
+            'พิจารณาใบลานักเรียนในที่ปรึกษา',
+            'student_leave',
+            'approve',
+            'assigned',
+            'อนุมัติ ส่งกลับแก้ไข หรือไม่อนุมัติคำขอลาของนักเรียนในห้องที่เป็นครูที่ปรึกษา'
+        ),
+        (
+            'student_leave.approve.school',
+            'พิจารณาใบลานักเรียนทั้งโรงเรียน',
+            'student_leave',
+            'approve',
+            'school',
+            'อนุมัติ ส่งกลับแก้ไข หรือไม่อนุมัติคำขอลาของนักเรียนทุกคนในโรงเรียน'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM student_leave_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
+
+WITH base_staff_permissions AS (
+    SELECT id
+    FROM permissions
+    WHERE code IN (
+        'student_leave.read.assigned',
+        'student_leave.approve.assigned'
+    )
+),
+staff_roles AS (
+    SELECT id
+    FROM roles
+    WHERE user_type = 'staff'
+)
+INSERT INTO role_permissions (role_id, permission_id, created_at)
+SELECT staff_roles.id, base_staff_permissions.id, now()
+FROM staff_roles
+CROSS JOIN base_staff_permissions
+ON CONFLICT DO NOTHING;
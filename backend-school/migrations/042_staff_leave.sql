@@ -0,0 +1,276 @@
+-- Staff leave types with yearly entitlements, fiscal-year balances and leave
+-- requests approved through organization unit leaders and the school.
+-- Leave amounts are stored in half-day units so half-day leave stays exact.
+
+CREATE TABLE staff_leave_types (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(50) NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    annual_entitlement_half_days INTEGER,
+    max_carry_over_half_days INTEGER NOT NULL DEFAULT 0,
+    max_accumulated_half_days INTEGER,
+    counts_weekends BOOLEAN NOT NULL DEFAULT false,
+    requires_school_approval BOOLEAN NOT NULL DEFAULT true,
+    document_required_from_days INTEGER,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT staff_leave_types_code_key UNIQUE (code),
+    CONSTRAINT staff_leave_types_code_format_check CHECK (code ~ '^[a-z][a-z0-9_]*$'),
+    CONSTRAINT staff_leave_types_name_not_blank CHECK (btrim(name) <> ''),
+    CONSTRAINT staff_leave_types_entitlement_check CHECK (
+        annual_entitlement_half_days IS NULL OR annual_entitlement_half_days >= 0
+    ),
+    CONSTRAINT staff_leave_types_carry_over_check CHECK (max_carry_over_half_days >= 0),
+    CONSTRAINT staff_leave_types_accumulated_check CHECK (
+        max_accumulated_half_days IS NULL
+        OR (
+            annual_entitlement_half_days IS NOT NULL
+            AND max_accumulated_half_days >= annual_entitlement_half_days
+        )
+    ),
+    CONSTRAINT staff_leave_types_document_threshold_check CHECK (
+        document_required_from_days IS NULL OR document_required_from_days > 0
+    )
+);
+
+CREATE TRIGGER update_staff_leave_types_updated_at
+    BEFORE UPDATE ON staff_leave_types
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE staff_leave_balances (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    leave_type_id UUID NOT NULL REFERENCES staff_leave_types(id) ON DELETE CASCADE,
+    fiscal_year INTEGER NOT NULL,
+    entitled_half_days INTEGER NOT NULL DEFAULT 0,
+    carried_over_half_days INTEGER NOT NULL DEFAULT 0,
+    adjustment_half_days INTEGER NOT NULL DEFAULT 0,
+    adjustment_note TEXT,
+    used_half_days INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, leave_type_id, fiscal_year),
+    CONSTRAINT staff_leave_balances_amounts_check CHECK (
+        entitled_half_days >= 0
+        AND carried_over_half_days >= 0
+        AND used_half_days >= 0
+    )
+);
+
+CREATE INDEX idx_staff_leave_balances_fiscal_year
+    ON staff_leave_balances (fiscal_year, leave_type_id);
+
+CREATE TRIGGER update_staff_leave_balances_updated_at
+    BEFORE UPDATE ON staff_leave_balances
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE staff_leave_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    leave_type_id UUID NOT NULL REFERENCES staff_leave_types(id) ON DELETE RESTRICT,
+    fiscal_year INTEGER NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    day_portion VARCHAR(20) NOT NULL DEFAULT 'full',
+    requested_half_days INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    contact_during_leave TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    decision_note TEXT,
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT staff_leave_requests_portion_check CHECK (
+        day_portion IN ('full', 'morning', 'afternoon')
+    ),
+    CONSTRAINT staff_leave_requests_status_check CHECK (
+        status IN ('pending', 'returned', 'approved', 'rejected', 'cancelled')
+    ),
+    CONSTRAINT staff_leave_requests_date_range_check CHECK (end_date >= start_date),
+    CONSTRAINT staff_leave_requests_half_day_check CHECK (
+        day_portion = 'full' OR start_date = end_date
+    ),
+    CONSTRAINT staff_leave_requests_amount_check CHECK (requested_half_days > 0),
+    CONSTRAINT staff_leave_requests_decision_check CHECK (
+        status NOT IN ('returned', 'approved', 'rejected')
+        OR (decided_by IS NOT NULL AND decided_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_staff_leave_requests_user
+    ON staff_leave_requests (user_id, start_date DESC);
+
+CREATE INDEX idx_staff_leave_requests_approved_dates
+    ON staff_leave_requests (start_date, end_date)
+    WHERE status = 'approved';
+
+CREATE TRIGGER update_staff_leave_requests_updated_at
+    BEFORE UPDATE ON staff_leave_requests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE staff_leave_approval_steps (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    leave_request_id UUID NOT NULL REFERENCES staff_leave_requests(id) ON DELETE CASCADE,
+    step_order INTEGER NOT NULL,
+    step_kind VARCHAR(20) NOT NULL,
+    organization_unit_id UUID REFERENCES organization_units(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'waiting',
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    note TEXT,
+    work_item_id UUID REFERENCES work_items(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT staff_leave_approval_steps_order_key UNIQUE (leave_request_id, step_order),
+    CONSTRAINT staff_leave_approval_steps_kind_check CHECK (
+        step_kind IN ('unit_leader', 'school')
+    ),
+    CONSTRAINT staff_leave_approval_steps_status_check CHECK (
+        status IN ('waiting', 'pending', 'approved', 'returned', 'rejected', 'cancelled')
+    )
+);
+
+CREATE INDEX idx_staff_leave_approval_steps_pending
+    ON staff_leave_approval_steps (step_kind, organization_unit_id)
+    WHERE status = 'pending';
+
+CREATE TRIGGER update_staff_leave_approval_steps_updated_at
+    BEFORE UPDATE ON staff_leave_approval_steps
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE staff_leave_request_documents (
+    leave_request_id UUID NOT NULL REFERENCES staff_leave_requests(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'staff_leave_document',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (leave_request_id, file_id),
+    CONSTRAINT staff_leave_request_documents_file_unique UNIQUE (file_id),
+    CONSTRAINT staff_leave_request_documents_purpose_check CHECK (
+        purpose_code = 'staff_leave_document'
+    ),
+    CONSTRAINT staff_leave_request_documents_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE staff_leave_request_actions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    leave_request_id UUID NOT NULL REFERENCES staff_leave_requests(id) ON DELETE CASCADE,
+    actor_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    action_kind VARCHAR(30) NOT NULL,
+    step_kind VARCHAR(20),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT staff_leave_request_actions_kind_check CHECK (
+        action_kind IN (
+            'submitted', 'resubmitted', 'step_approved', 'approved',
+            'returned', 'rejected', 'cancelled'
+        )
+    )
+);
+
+CREATE INDEX idx_staff_leave_request_actions_request
+    ON staff_leave_request_actions (leave_request_id, created_at);
+
+CREATE TABLE staff_leave_review_windows (
+    fiscal_year INTEGER PRIMARY KEY,
+    workflow_window_id UUID NOT NULL REFERENCES workflow_windows(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE staff_leave_types IS
+    'Leave types with yearly entitlement and carry-over rules; NULL entitlement means the type is not quota-limited.';
+COMMENT ON TABLE staff_leave_balances IS
+    'Per fiscal year (Thai government Oct-Sep, Buddhist Era of the closing year) leave balance in half-day units.';
+COMMENT ON TABLE staff_leave_approval_steps IS
+    'Current approval route: an organization unit leader step followed by the school approval step.';
+COMMENT ON TABLE staff_leave_review_windows IS
+    'Workflow window that owns staff leave approval work items for a fiscal year.';
+
+INSERT INTO staff_leave_types (
+    code, name, annual_entitlement_half_days, max_carry_over_half_days,
+    max_accumulated_half_days, counts_weekends, requires_school_approval,
+    document_required_from_days, sort_order
+)
+VALUES
+    ('sick', 'ลาป่วย', 120, 0, NULL, false, true, 30, 1),
+    ('personal', 'ลากิจส่วนตัว', 90, 0, NULL, false, true, NULL, 2),
+    ('vacation', 'ลาพักผ่อน', 20, 20, 40, false, true, NULL, 3)
+ON CONFLICT (code) DO NOTHING;
+
+WITH staff_leave_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'staff_leave.request.own',
+            'ยื่นใบลาของตนเอง',
+            'staff_leave',
+            'request',
+            'own',
+            'ยื่น แก้ไข และยกเลิกใบลาของตนเอง'
+        ),
+        (
+            'staff_leave.read.own',
+            'ดูใบลาและวันลาคงเหลือของตนเอง',
+            'staff_leave',
+            'read',
+            'own',
+            'ดูประวัติการลาและยอดวันลาคงเหลือของตนเอง'
+        ),
+        (
+            'staff_leave.read.school',
+            'ดูการลาของบุคลากรทั้งโรงเรียน',
+            'staff_leave',
+            'read',
+            'school',
+            'ดูใบลา ยอดวันลา และปฏิทินการลาของบุคลากรทุกคน'
+        ),
+        (
+            'staff_leave.approve.school',
+            'อนุมัติการลาของบุคลากร',
+            'staff_leave',
+            'approve',
+            'school',
+            'พิจารณาใบลาขั้นสุดท้ายในนามโรงเรียน'
+        ),
+        (
+            'staff_leave.manage.school',
+            'จัดการประเภทการลาและวันลา',
+            'staff_leave',
+            'manage',
+            'school',
+            'กำหนดประเภทการลา สิทธิ์วันลาประจำปี และปรับยอดวันลาของบุคลากร'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM staff_leave_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
+
+WITH base_staff_permissions AS (
+    SELECT id
+    FROM permissions
+    WHERE code IN (
+        'staff_leave.request.own',
+        'staff_leave.read.own'
+    )
+),
+staff_roles AS (
+    SELECT id
+    FROM roles
+    WHERE user_type = 'staff'
+)
+INSERT INTO role_permissions (role_id, permission_id, created_at)
+SELECT staff_roles.id, base_staff_permissions.id, now()
+FROM staff_roles
+CROSS JOIN base_staff_permissions
+ON CONFLICT DO NOTHING;
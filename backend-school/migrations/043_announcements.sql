@@ -0,0 +1,137 @@
+-- School announcements with scheduled publishing, targeted audiences, read
+-- receipts and acknowledgement reminders. Public announcements also appear on
+-- the school website.
+
+CREATE TABLE announcements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    summary TEXT,
+    content JSONB NOT NULL,
+    content_text TEXT NOT NULL DEFAULT '',
+    status VARCHAR(20) NOT NULL DEFAULT 'draft',
+    publish_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    is_pinned BOOLEAN NOT NULL DEFAULT false,
+    is_public BOOLEAN NOT NULL DEFAULT false,
+    requires_acknowledgement BOOLEAN NOT NULL DEFAULT false,
+    acknowledge_by DATE,
+    published_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    published_notified_at TIMESTAMPTZ,
+    last_reminded_on DATE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT announcements_title_not_blank CHECK (btrim(title) <> ''),
+    CONSTRAINT announcements_status_check CHECK (
+        status IN ('draft', 'published', 'archived')
+    ),
+    CONSTRAINT announcements_published_schedule_check CHECK (
+        status = 'draft' OR publish_at IS NOT NULL
+    ),
+    CONSTRAINT announcements_expiry_check CHECK (
+        expires_at IS NULL OR publish_at IS NULL OR expires_at > publish_at
+    ),
+    CONSTRAINT announcements_acknowledge_by_check CHECK (
+        acknowledge_by IS NULL OR requires_acknowledgement
+    )
+);
+
+CREATE INDEX idx_announcements_status_publish_at
+    ON announcements (status, publish_at DESC);
+
+CREATE INDEX idx_announcements_public
+    ON announcements (publish_at DESC)
+    WHERE is_public = true AND status = 'published';
+
+CREATE INDEX idx_announcements_pending_notification
+    ON announcements (publish_at)
+    WHERE status = 'published' AND published_notified_at IS NULL;
+
+CREATE TRIGGER update_announcements_updated_at
+    BEFORE UPDATE ON announcements
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE announcement_audiences (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    announcement_id UUID NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    audience_type VARCHAR(30) NOT NULL,
+    role_id UUID REFERENCES roles(id) ON DELETE CASCADE,
+    grade_level_id UUID REFERENCES grade_levels(id) ON DELETE CASCADE,
+    class_room_id UUID REFERENCES class_rooms(id) ON DELETE CASCADE,
+    organization_unit_id UUID REFERENCES organization_units(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT announcement_audiences_type_check CHECK (
+        audience_type IN (
+            'all_staff', 'all_students', 'all_guardians', 'role', 'grade_level',
+            'classroom', 'organization_unit', 'classroom_guardians'
+        )
+    ),
+    CONSTRAINT announcement_audiences_reference_check CHECK (
+        (role_id IS NOT NULL) = (audience_type = 'role')
+        AND (grade_level_id IS NOT NULL) = (audience_type = 'grade_level')
+        AND (class_room_id IS NOT NULL) = (audience_type IN ('classroom', 'classroom_guardians'))
+        AND (organization_unit_id IS NOT NULL) = (audience_type = 'organization_unit')
+    )
+);
+
+CREATE INDEX idx_announcement_audiences_announcement
+    ON announcement_audiences (announcement_id);
+
+CREATE TABLE announcement_files (
+    announcement_id UUID NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'announcement_file',
+    is_attachment BOOLEAN NOT NULL DEFAULT true,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (announcement_id, file_id),
+    CONSTRAINT announcement_files_file_unique UNIQUE (file_id),
+    CONSTRAINT announcement_files_purpose_check CHECK (
+        purpose_code = 'announcement_file'
+    ),
+    CONSTRAINT announcement_files_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE announcement_receipts (
+    announcement_id UUID NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    read_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    acknowledged_at TIMESTAMPTZ,
+    PRIMARY KEY (announcement_id, user_id)
+);
+
+CREATE INDEX idx_announcement_receipts_user
+    ON announcement_receipts (user_id, announcement_id);
+
+WITH announcement_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'announcement.read.school',
+            'ดูประกาศและการรับทราบทั้งโรงเรียน',
+            'announcement',
+            'read',
+            'school',
+            'ดูประกาศทุกฉบับรวมถึงฉบับร่าง และรายงานการอ่านและการรับทราบ'
+        ),
+        (
+            'announcement.manage.school',
+            'จัดการประกาศของโรงเรียน',
+            'announcement',
+            'manage',
+            'school',
+            'สร้าง แก้ไข เผยแพร่ และเก็บประกาศ พร้อมกำหนดกลุ่มผู้รับและส่งการแจ้งเตือน'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM announcement_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
@@ -0,0 +1,323 @@
+-- School fee collection: fee items priced per grade level and academic year,
+-- bulk-generated student invoices with discounts and scholarships, payments
+-- with bank-slip review, numbered receipts and PromptPay settings. Amounts are
+-- stored in satang.
+
+CREATE TABLE finance_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT true,
+    payee_name TEXT NOT NULL DEFAULT '',
+    promptpay_target_type VARCHAR(20),
+    promptpay_target VARCHAR(20),
+    receipt_prefix VARCHAR(10) NOT NULL DEFAULT 'RC',
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT finance_settings_singleton CHECK (id),
+    CONSTRAINT finance_settings_promptpay_pair_check CHECK (
+        (promptpay_target_type IS NULL) = (promptpay_target IS NULL)
+    ),
+    CONSTRAINT finance_settings_promptpay_target_check CHECK (
+        promptpay_target_type IS NULL
+        OR (promptpay_target_type = 'phone' AND promptpay_target ~ '^0[0-9]{9}$')
+        OR (promptpay_target_type = 'tax_id' AND promptpay_target ~ '^0[0-9]{12}$')
+        OR (promptpay_target_type = 'ewallet' AND promptpay_target ~ '^[0-9]{15}$')
+    ),
+    CONSTRAINT finance_settings_receipt_prefix_check CHECK (
+        receipt_prefix ~ '^[A-Z]{1,10}$'
+    )
+);
+
+CREATE TRIGGER update_finance_settings_updated_at
+    BEFORE UPDATE ON finance_settings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE fee_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(30) NOT NULL,
+    name TEXT NOT NULL,
+    category VARCHAR(20) NOT NULL,
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_items_code_unique UNIQUE (code),
+    CONSTRAINT fee_items_name_not_blank CHECK (btrim(name) <> ''),
+    CONSTRAINT fee_items_category_check CHECK (
+        category IN ('tuition', 'activity', 'exam', 'other')
+    )
+);
+
+CREATE TRIGGER update_fee_items_updated_at
+    BEFORE UPDATE ON fee_items
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE fee_item_rates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    fee_item_id UUID NOT NULL REFERENCES fee_items(id) ON DELETE CASCADE,
+    academic_year_id UUID NOT NULL REFERENCES academic_years(id) ON DELETE RESTRICT,
+    academic_semester_id UUID REFERENCES academic_semesters(id) ON DELETE RESTRICT,
+    grade_level_id UUID NOT NULL REFERENCES grade_levels(id) ON DELETE RESTRICT,
+    amount_satang BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_item_rates_amount_check CHECK (amount_satang > 0)
+);
+
+CREATE UNIQUE INDEX idx_fee_item_rates_unique
+    ON fee_item_rates (
+        fee_item_id,
+        academic_year_id,
+        grade_level_id,
+        COALESCE(academic_semester_id, '00000000-0000-0000-0000-000000000000'::uuid)
+    );
+
+CREATE INDEX idx_fee_item_rates_year_grade
+    ON fee_item_rates (academic_year_id, grade_level_id);
+
+CREATE TABLE student_fee_discounts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    academic_year_id UUID NOT NULL REFERENCES academic_years(id) ON DELETE RESTRICT,
+    fee_item_id UUID REFERENCES fee_items(id) ON DELETE CASCADE,
+    discount_kind VARCHAR(20) NOT NULL,
+    name TEXT NOT NULL,
+    percent_basis_points INTEGER,
+    amount_satang BIGINT,
+    note TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT student_fee_discounts_kind_check CHECK (
+        discount_kind IN ('discount', 'scholarship')
+    ),
+    CONSTRAINT student_fee_discounts_name_not_blank CHECK (btrim(name) <> ''),
+    CONSTRAINT student_fee_discounts_value_check CHECK (
+        (percent_basis_points IS NULL) <> (amount_satang IS NULL)
+        AND (percent_basis_points IS NULL OR percent_basis_points BETWEEN 1 AND 10000)
+        AND (amount_satang IS NULL OR amount_satang > 0)
+    )
+);
+
+CREATE INDEX idx_student_fee_discounts_student_year
+    ON student_fee_discounts (student_id, academic_year_id);
+
+CREATE TABLE fee_invoice_batches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    academic_year_id UUID NOT NULL REFERENCES academic_years(id) ON DELETE RESTRICT,
+    academic_semester_id UUID REFERENCES academic_semesters(id) ON DELETE RESTRICT,
+    title TEXT NOT NULL,
+    due_date DATE NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_invoice_batches_title_not_blank CHECK (btrim(title) <> '')
+);
+
+CREATE TRIGGER update_fee_invoice_batches_updated_at
+    BEFORE UPDATE ON fee_invoice_batches
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE fee_invoice_batch_grade_levels (
+    batch_id UUID NOT NULL REFERENCES fee_invoice_batches(id) ON DELETE CASCADE,
+    grade_level_id UUID NOT NULL REFERENCES grade_levels(id) ON DELETE RESTRICT,
+    PRIMARY KEY (batch_id, grade_level_id)
+);
+
+CREATE TABLE fee_invoices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invoice_number VARCHAR(30) NOT NULL,
+    batch_id UUID NOT NULL REFERENCES fee_invoice_batches(id) ON DELETE RESTRICT,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    class_room_id UUID REFERENCES class_rooms(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open',
+    subtotal_satang BIGINT NOT NULL,
+    discount_satang BIGINT NOT NULL DEFAULT 0,
+    total_satang BIGINT NOT NULL,
+    paid_satang BIGINT NOT NULL DEFAULT 0,
+    due_date DATE NOT NULL,
+    voided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    voided_at TIMESTAMPTZ,
+    void_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_invoices_number_unique UNIQUE (invoice_number),
+    CONSTRAINT fee_invoices_batch_student_unique UNIQUE (batch_id, student_id),
+    CONSTRAINT fee_invoices_status_check CHECK (
+        status IN ('open', 'partially_paid', 'paid', 'void')
+    ),
+    CONSTRAINT fee_invoices_amounts_check CHECK (
+        subtotal_satang >= 0
+        AND discount_satang BETWEEN 0 AND subtotal_satang
+        AND total_satang = subtotal_satang - discount_satang
+        AND paid_satang BETWEEN 0 AND total_satang
+    ),
+    CONSTRAINT fee_invoices_void_check CHECK (
+        (status = 'void') = (voided_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_fee_invoices_student
+    ON fee_invoices (student_id, due_date DESC);
+
+CREATE INDEX idx_fee_invoices_outstanding
+    ON fee_invoices (due_date)
+    WHERE status IN ('open', 'partially_paid');
+
+CREATE TRIGGER update_fee_invoices_updated_at
+    BEFORE UPDATE ON fee_invoices
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE fee_invoice_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invoice_id UUID NOT NULL REFERENCES fee_invoices(id) ON DELETE CASCADE,
+    line_kind VARCHAR(20) NOT NULL,
+    fee_item_id UUID REFERENCES fee_items(id) ON DELETE RESTRICT,
+    discount_id UUID REFERENCES student_fee_discounts(id) ON DELETE SET NULL,
+    description TEXT NOT NULL,
+    amount_satang BIGINT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT fee_invoice_lines_kind_check CHECK (line_kind IN ('charge', 'discount')),
+    CONSTRAINT fee_invoice_lines_amount_check CHECK (amount_satang > 0),
+    CONSTRAINT fee_invoice_lines_charge_item_check CHECK (
+        line_kind <> 'charge' OR fee_item_id IS NOT NULL
+    )
+);
+
+CREATE INDEX idx_fee_invoice_lines_invoice
+    ON fee_invoice_lines (invoice_id, sort_order);
+
+CREATE TABLE fee_payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invoice_id UUID NOT NULL REFERENCES fee_invoices(id) ON DELETE RESTRICT,
+    method VARCHAR(20) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    amount_satang BIGINT NOT NULL,
+    paid_at TIMESTAMPTZ NOT NULL,
+    reference TEXT,
+    note TEXT,
+    submitted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    rejection_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_payments_method_check CHECK (
+        method IN ('cash', 'bank_transfer', 'promptpay')
+    ),
+    CONSTRAINT fee_payments_status_check CHECK (
+        status IN ('pending_review', 'confirmed', 'rejected')
+    ),
+    CONSTRAINT fee_payments_amount_check CHECK (amount_satang > 0),
+    CONSTRAINT fee_payments_review_check CHECK (
+        status = 'pending_review'
+        OR (reviewed_by IS NOT NULL AND reviewed_at IS NOT NULL)
+    ),
+    CONSTRAINT fee_payments_rejection_check CHECK (
+        (status = 'rejected') = (rejection_reason IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_fee_payments_invoice
+    ON fee_payments (invoice_id, paid_at);
+
+CREATE INDEX idx_fee_payments_pending
+    ON fee_payments (created_at)
+    WHERE status = 'pending_review';
+
+CREATE TRIGGER update_fee_payments_updated_at
+    BEFORE UPDATE ON fee_payments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE fee_payment_slips (
+    payment_id UUID NOT NULL REFERENCES fee_payments(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'fee_payment_slip',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (payment_id, file_id),
+    CONSTRAINT fee_payment_slips_file_unique UNIQUE (file_id),
+    CONSTRAINT fee_payment_slips_purpose_check CHECK (
+        purpose_code = 'fee_payment_slip'
+    ),
+    CONSTRAINT fee_payment_slips_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE fee_receipts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    receipt_number VARCHAR(30) NOT NULL,
+    payment_id UUID NOT NULL REFERENCES fee_payments(id) ON DELETE RESTRICT,
+    invoice_id UUID NOT NULL REFERENCES fee_invoices(id) ON DELETE RESTRICT,
+    amount_satang BIGINT NOT NULL,
+    issued_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fee_receipts_number_unique UNIQUE (receipt_number),
+    CONSTRAINT fee_receipts_payment_unique UNIQUE (payment_id),
+    CONSTRAINT fee_receipts_amount_check CHECK (amount_satang > 0)
+);
+
+CREATE INDEX idx_fee_receipts_invoice
+    ON fee_receipts (invoice_id);
+
+CREATE INDEX idx_fee_receipts_issued_at
+    ON fee_receipts (issued_at);
+
+CREATE TABLE finance_document_counters (
+    document_type VARCHAR(20) NOT NULL,
+    buddhist_year INTEGER NOT NULL,
+    last_sequence INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (document_type, buddhist_year),
+    CONSTRAINT finance_document_counters_type_check CHECK (
+        document_type IN ('invoice', 'receipt')
+    ),
+    CONSTRAINT finance_document_counters_sequence_check CHECK (
+        last_sequence BETWEEN 1 AND 999999
+    )
+);
+
+COMMENT ON TABLE finance_settings IS
+    'Single-row PromptPay and receipt settings. A tax_id target must be a juristic-person ID (leading 0); personal national IDs are not accepted.';
+COMMENT ON COLUMN fee_item_rates.academic_semester_id IS
+    'NULL means an annual rate billed by year-level batches; otherwise the rate is billed by that semester''s batches.';
+COMMENT ON COLUMN fee_invoices.class_room_id IS
+    'Classroom the student was enrolled in when the invoice was generated.';
+COMMENT ON TABLE finance_document_counters IS
+    'Running invoice and receipt numbers per Buddhist calendar year.';
+
+WITH finance_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'finance.read.school',
+            'ดูข้อมูลการเงินค่าธรรมเนียม',
+            'finance',
+            'read',
+            'school',
+            'ดูรายการค่าธรรมเนียม ใบแจ้งหนี้ การชำระเงิน ใบเสร็จ และรายงานกระทบยอดรายวันของโรงเรียน'
+        ),
+        (
+            'finance.manage.school',
+            'จัดการค่าธรรมเนียมและใบแจ้งหนี้',
+            'finance',
+            'manage',
+            'school',
+            'กำหนดรายการค่าธรรมเนียม อัตราตามระดับชั้น ส่วนลดและทุนการศึกษา ออกใบแจ้งหนี้ และยกเลิกใบแจ้งหนี้'
+        ),
+        (
+            'finance.issue.school',
+            'รับชำระเงินและออกใบเสร็จ',
+            'finance',
+            'issue',
+            'school',
+            'บันทึกการรับชำระเงิน ตรวจสอบสลิปโอนเงินจากผู้ปกครอง และออกใบเสร็จรับเงิน'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM finance_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
@@ -0,0 +1,79 @@
+-- Time-limited support sessions opened by platform staff from backend-admin.
+-- A grant names the ticket, the school user being assisted and the permission
+-- scope; the session it starts is an ordinary auth_sessions row flagged with
+-- the grant so every request can be narrowed and audited.
+
+CREATE TABLE support_access_grants (
+    -- Same identifier as the backend-admin support session record.
+    id UUID PRIMARY KEY,
+    target_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform_admin_id UUID NOT NULL,
+    platform_admin_name TEXT NOT NULL,
+    ticket_reference TEXT NOT NULL,
+    reason TEXT,
+    scope VARCHAR(20) NOT NULL,
+    permissions TEXT[] NOT NULL DEFAULT '{}',
+    duration_minutes INTEGER NOT NULL,
+    requires_approval BOOLEAN NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    start_by TIMESTAMPTZ NOT NULL,
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decided_at TIMESTAMPTZ,
+    decision_note TEXT,
+    handoff_token_hash BYTEA,
+    handoff_expires_at TIMESTAMPTZ,
+    started_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    ended_at TIMESTAMPTZ,
+    ended_by VARCHAR(20),
+    CONSTRAINT support_access_grants_ticket_not_blank CHECK (btrim(ticket_reference) <> ''),
+    CONSTRAINT support_access_grants_admin_name_not_blank CHECK (
+        btrim(platform_admin_name) <> ''
+    ),
+    CONSTRAINT support_access_grants_scope_check CHECK (scope IN ('read_only', 'scoped')),
+    CONSTRAINT support_access_grants_scoped_permissions_check CHECK (
+        (scope = 'read_only' AND cardinality(permissions) = 0)
+        OR (scope = 'scoped' AND cardinality(permissions) > 0)
+    ),
+    CONSTRAINT support_access_grants_duration_check CHECK (
+        duration_minutes BETWEEN 5 AND 240
+    ),
+    CONSTRAINT support_access_grants_status_check CHECK (
+        status IN ('pending', 'approved', 'rejected', 'active', 'ended')
+    ),
+    CONSTRAINT support_access_grants_start_by_check CHECK (start_by > requested_at),
+    CONSTRAINT support_access_grants_decision_check CHECK (
+        status <> 'rejected' OR decided_at IS NOT NULL
+    ),
+    CONSTRAINT support_access_grants_handoff_check CHECK (
+        (handoff_token_hash IS NULL) = (handoff_expires_at IS NULL)
+        AND (handoff_token_hash IS NULL OR octet_length(handoff_token_hash) = 32)
+    ),
+    CONSTRAINT support_access_grants_started_check CHECK (
+        (started_at IS NULL) = (expires_at IS NULL)
+        AND (status <> 'active' OR started_at IS NOT NULL)
+    ),
+    CONSTRAINT support_access_grants_ended_check CHECK (
+        (status = 'ended') = (ended_at IS NOT NULL)
+        AND (ended_by IS NULL OR ended_by IN ('platform', 'school', 'expired'))
+    )
+);
+
+CREATE UNIQUE INDEX support_access_grants_handoff_token_hash_key
+    ON support_access_grants (handoff_token_hash)
+    WHERE handoff_token_hash IS NOT NULL;
+
+CREATE INDEX idx_support_access_grants_requested_at
+    ON support_access_grants (requested_at DESC);
+
+CREATE INDEX idx_support_access_grants_open
+    ON support_access_grants (status, start_by)
+    WHERE status IN ('pending', 'approved', 'active');
+
+ALTER TABLE auth_sessions
+    ADD COLUMN support_access_id UUID REFERENCES support_access_grants(id) ON DELETE CASCADE;
+
+CREATE INDEX auth_sessions_support_access_idx
+    ON auth_sessions (support_access_id)
+    WHERE support_access_id IS NOT NULL;
@@ -0,0 +1,148 @@
+-- Field-encryption key rotation. Ciphertexts written under a keyring name their
+-- key (`v2:{key_id}:...`) and blind indexes can be rebuilt under a new HMAC key.
+-- One row per target key pair records how far the background re-encryption job
+-- got, so an interrupted run resumes from its cursor.
+
+CREATE TABLE field_key_rotation_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    encryption_key_id VARCHAR(32) NOT NULL,
+    blind_index_key_id VARCHAR(32) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'running',
+    -- Last processed row; the job walks its column catalog table by table in id
+    -- order. A NULL cursor_id means cursor_table has not been started yet.
+    cursor_table TEXT,
+    cursor_id UUID,
+    rows_scanned BIGINT NOT NULL DEFAULT 0,
+    rows_rewritten BIGINT NOT NULL DEFAULT 0,
+    rows_failed BIGINT NOT NULL DEFAULT 0,
+    -- Table, column and row id of the latest failure. Never a value or a key.
+    last_error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    completed_at TIMESTAMPTZ,
+    CONSTRAINT field_key_rotation_runs_key_pair_key UNIQUE (encryption_key_id, blind_index_key_id),
+    CONSTRAINT field_key_rotation_runs_status_check CHECK (status IN ('running', 'completed')),
+    CONSTRAINT field_key_rotation_runs_completed_check CHECK (
+        (status = 'completed') = (completed_at IS NOT NULL)
+    ),
+    CONSTRAINT field_key_rotation_runs_cursor_check CHECK (
+        cursor_id IS NULL OR cursor_table IS NOT NULL
+    ),
+    CONSTRAINT field_key_rotation_runs_counts_check CHECK (
+        rows_scanned >= 0 AND rows_rewritten >= 0 AND rows_failed >= 0
+    )
+);
+
+-- Everything written before keyrings existed used the single ENCRYPTION_KEY and
+-- BLIND_INDEX_KEY, which the keyring reads as key id 'legacy'.
+INSERT INTO field_key_rotation_runs (
+    encryption_key_id, blind_index_key_id, status, completed_at
+) VALUES ('legacy', 'legacy', 'completed', now());
+
+COMMENT ON TABLE field_key_rotation_runs IS
+    'Progress of the field-encryption re-encryption job, one row per target key pair.';
+
+COMMENT ON COLUMN users.national_id_hash IS
+    'Blind index for national ID lookup and uniqueness (HMAC-SHA256 hex; keyed by the active blind-index key).';
+COMMENT ON COLUMN admission_applications.national_id IS
+    'Encrypted applicant national ID (AES-GCM; app-side; legacy Base64 or v2:{key_id}:Base64).';
+COMMENT ON COLUMN admission_applications.national_id_hash IS
+    'Blind index for applicant national ID lookup and uniqueness (HMAC-SHA256 hex; keyed by the active blind-index key).';
+
+-- Issued certificates stay immutable, except that the key-rotation job may
+-- re-encrypt the QR proof and rebuild its blind index. It opts in per
+-- transaction with SET LOCAL app.field_key_rotation = 'on'.
+CREATE OR REPLACE FUNCTION enforce_certificate_snapshot_immutability()
+RETURNS TRIGGER
+LANGUAGE plpgsql
+AS $$
+BEGIN
+    IF ROW(
+        NEW.id,
+        NEW.campaign_id,
+        NEW.template_id,
+        NEW.candidate_id,
+        NEW.issue_run_id,
+        NEW.academic_year_id,
+        NEW.academic_year_value,
+        NEW.activity_sequence,
+        NEW.certificate_sequence,
+        NEW.check_digit,
+        NEW.certificate_number,
+        NEW.recipient_type,
+        NEW.user_id,
+        NEW.title_snapshot,
+        NEW.first_name_snapshot,
+        NEW.last_name_snapshot,
+        NEW.template_name_snapshot,
+        NEW.activity_item_snapshot,
+        NEW.award_or_role_snapshot,
+        NEW.custom_values_snapshot,
+        NEW.school_name_snapshot,
+        NEW.owner_organization_unit_name_snapshot,
+        NEW.issue_date,
+        NEW.replacement_for_certificate_id,
+        NEW.created_at
+    ) IS DISTINCT FROM ROW(
+        OLD.id,
+        OLD.campaign_id,
+        OLD.template_id,
+        OLD.candidate_id,
+        OLD.issue_run_id,
+        OLD.academic_year_id,
+        OLD.academic_year_value,
+        OLD.activity_sequence,
+        OLD.certificate_sequence,
+        OLD.check_digit,
+        OLD.certificate_number,
+        OLD.recipient_type,
+        OLD.user_id,
+        OLD.title_snapshot,
+        OLD.first_name_snapshot,
+        OLD.last_name_snapshot,
+        OLD.template_name_snapshot,
+        OLD.activity_item_snapshot,
+        OLD.award_or_role_snapshot,
+        OLD.custom_values_snapshot,
+        OLD.school_name_snapshot,
+        OLD.owner_organization_unit_name_snapshot,
+        OLD.issue_date,
+        OLD.replacement_for_certificate_id,
+        OLD.created_at
+    ) THEN
+        RAISE EXCEPTION 'issued certificate snapshots are immutable'
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    IF ROW(NEW.qr_proof_encrypted, NEW.qr_proof_hash)
+           IS DISTINCT FROM ROW(OLD.qr_proof_encrypted, OLD.qr_proof_hash)
+       AND current_setting('app.field_key_rotation', true) IS DISTINCT FROM 'on' THEN
+        RAISE EXCEPTION 'issued certificate snapshots are immutable'
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    IF OLD.status = 'revoked'
+       AND ROW(
+           NEW.status,
+           NEW.revoked_by,
+           NEW.revoked_at,
+           NEW.revocation_reason
+       ) IS DISTINCT FROM ROW(
+           OLD.status,
+           OLD.revoked_by,
+           OLD.revoked_at,
+           OLD.revocation_reason
+       ) THEN
+        RAISE EXCEPTION 'certificate revocation is immutable'
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    IF OLD.replaced_by_certificate_id IS NOT NULL
+       AND NEW.replaced_by_certificate_id IS DISTINCT FROM OLD.replaced_by_certificate_id THEN
+        RAISE EXCEPTION 'certificate replacement link is immutable'
+            USING ERRCODE = 'integrity_constraint_violation';
+    END IF;
+
+    RETURN NEW;
+END;
+$$;
@@ -0,0 +1,12 @@
+-- Automatic invigilator assignment. Each room assignment states how many
+-- invigilators it needs, and invigilator rows record whether the auto-assign
+-- job chose them. Rows chosen by hand stay pinned: a re-run replaces only the
+-- automatic rows of the round.
+
+ALTER TABLE academic_exam_day_room_assignments
+  ADD COLUMN required_invigilators SMALLINT NOT NULL DEFAULT 1,
+  ADD CONSTRAINT academic_exam_day_room_assignments_required_invigilators_range
+    CHECK (required_invigilators BETWEEN 1 AND 5);
+
+ALTER TABLE academic_exam_day_invigilators
+  ADD COLUMN assigned_automatically BOOLEAN NOT NULL DEFAULT false;
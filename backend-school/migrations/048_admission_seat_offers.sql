@@ -0,0 +1,71 @@
+-- Admission seat offers with per-track confirmation deadlines and a ranked
+-- waitlist. A declined or expired offer releases the seat to the next
+-- waitlisted applicant of the same track; every offer is kept as history.
+
+ALTER TABLE admission_tracks
+    ADD COLUMN confirmation_window_hours INTEGER NOT NULL DEFAULT 72,
+    ADD CONSTRAINT admission_tracks_confirmation_window_check CHECK (
+        confirmation_window_hours BETWEEN 1 AND 720
+    );
+
+COMMENT ON COLUMN admission_tracks.confirmation_window_hours IS
+    'ชั่วโมงที่ผู้ได้รับสิทธิ์ต้องยืนยันเข้าเรียนนับจากเวลาที่เสนอสิทธิ์';
+
+CREATE TABLE admission_waitlist_entries (
+    application_id UUID PRIMARY KEY
+        REFERENCES admission_applications(id) ON DELETE CASCADE,
+    admission_track_id UUID NOT NULL REFERENCES admission_tracks(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT admission_waitlist_entries_position_check CHECK (position > 0),
+    CONSTRAINT admission_waitlist_entries_track_position_unique
+        UNIQUE (admission_track_id, position)
+);
+
+COMMENT ON TABLE admission_waitlist_entries IS
+    'ลำดับสำรองต่อสายการเรียน — ผู้สมัครออกจากรายการเมื่อได้รับการเสนอสิทธิ์';
+
+CREATE TABLE admission_seat_offers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    application_id UUID NOT NULL REFERENCES admission_applications(id) ON DELETE CASCADE,
+    admission_track_id UUID NOT NULL REFERENCES admission_tracks(id) ON DELETE CASCADE,
+    class_room_id UUID NOT NULL REFERENCES class_rooms(id) ON DELETE RESTRICT,
+    source VARCHAR(20) NOT NULL,
+    waitlist_position INTEGER,
+    replaces_offer_id UUID REFERENCES admission_seat_offers(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    offered_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    deadline_at TIMESTAMPTZ NOT NULL,
+    responded_at TIMESTAMPTZ,
+    offered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    CONSTRAINT admission_seat_offers_source_check CHECK (
+        source IN ('initial', 'waitlist')
+    ),
+    CONSTRAINT admission_seat_offers_status_check CHECK (
+        status IN ('pending', 'accepted', 'declined', 'expired')
+    ),
+    CONSTRAINT admission_seat_offers_waitlist_check CHECK (
+        (source = 'waitlist') = (waitlist_position IS NOT NULL)
+    ),
+    CONSTRAINT admission_seat_offers_response_check CHECK (
+        (status = 'pending') = (responded_at IS NULL)
+    ),
+    CONSTRAINT admission_seat_offers_deadline_check CHECK (deadline_at > offered_at)
+);
+
+COMMENT ON TABLE admission_seat_offers IS
+    'ประวัติการเสนอสิทธิ์เข้าเรียน (เสนอ/ยืนยัน/สละสิทธิ์/หมดเวลา) ต่อใบสมัคร';
+
+CREATE UNIQUE INDEX idx_admission_seat_offers_one_pending
+    ON admission_seat_offers (application_id)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_admission_seat_offers_due
+    ON admission_seat_offers (deadline_at)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_admission_seat_offers_track
+    ON admission_seat_offers (admission_track_id, offered_at);
+
+CREATE INDEX idx_admission_seat_offers_application
+    ON admission_seat_offers (application_id, offered_at);
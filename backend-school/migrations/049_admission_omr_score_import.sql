@@ -0,0 +1,40 @@
+-- Admission exam answer keys and OMR answer-sheet imports. Each import is
+-- auto-marked against the subject's key at import time; the key and the
+-- item-analysis statistics are kept with the import so later key edits do not
+-- rewrite history.
+
+CREATE TABLE admission_exam_answer_keys (
+    exam_subject_id UUID PRIMARY KEY
+        REFERENCES admission_exam_subjects(id) ON DELETE CASCADE,
+    answer_key VARCHAR(500) NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT admission_exam_answer_keys_not_empty_check CHECK (
+        char_length(answer_key) > 0
+    )
+);
+
+COMMENT ON TABLE admission_exam_answer_keys IS
+    'เฉลยข้อสอบปรนัยต่อวิชา — หนึ่งอักขระต่อข้อ, * = ยกเลิกข้อ (ให้คะแนนทุกคน)';
+
+CREATE TABLE admission_omr_imports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    admission_round_id UUID NOT NULL REFERENCES admission_rounds(id) ON DELETE CASCADE,
+    exam_subject_id UUID NOT NULL REFERENCES admission_exam_subjects(id) ON DELETE CASCADE,
+    file_name VARCHAR(255),
+    answer_key VARCHAR(500) NOT NULL,
+    sheet_count INTEGER NOT NULL,
+    scored_count INTEGER NOT NULL,
+    flagged_count INTEGER NOT NULL,
+    missing_count INTEGER NOT NULL,
+    written_count INTEGER NOT NULL,
+    item_analysis JSONB NOT NULL DEFAULT '[]'::jsonb,
+    imported_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    imported_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE admission_omr_imports IS
+    'ประวัติการนำเข้าผลสแกนกระดาษคำตอบ (OMR) พร้อมสถิติวิเคราะห์ข้อสอบ';
+
+CREATE INDEX idx_admission_omr_imports_round
+    ON admission_omr_imports (admission_round_id, imported_at DESC);
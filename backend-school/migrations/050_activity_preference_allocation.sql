@@ -0,0 +1,88 @@
+-- Preference-ranked activity registration. In a slot with
+-- registration_type = 'preference' students rank the slot's groups during the
+-- registration window; an allocation run then fills activity_group_members.
+-- Runs are kept as reviewable drafts until one is published.
+
+ALTER TABLE activity_slots
+    DROP CONSTRAINT activity_slots_registration_type_check,
+    ADD CONSTRAINT activity_slots_registration_type_check CHECK (
+        registration_type IN ('self', 'assigned', 'preference')
+    ),
+    ADD COLUMN max_preferences INTEGER NOT NULL DEFAULT 3,
+    ADD CONSTRAINT activity_slots_max_preferences_check CHECK (
+        max_preferences BETWEEN 1 AND 10
+    );
+
+COMMENT ON COLUMN activity_slots.max_preferences IS
+    'จำนวนลำดับความสนใจสูงสุดที่นักเรียนเลือกได้ เมื่อ registration_type = preference';
+
+CREATE TABLE activity_preferences (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slot_id UUID NOT NULL REFERENCES activity_slots(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    activity_group_id UUID NOT NULL REFERENCES activity_groups(id) ON DELETE CASCADE,
+    preference_rank INTEGER NOT NULL,
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT activity_preferences_rank_check CHECK (preference_rank > 0),
+    CONSTRAINT activity_preferences_student_rank_unique
+        UNIQUE (slot_id, student_id, preference_rank),
+    CONSTRAINT activity_preferences_student_group_unique
+        UNIQUE (slot_id, student_id, activity_group_id)
+);
+
+COMMENT ON TABLE activity_preferences IS
+    'ลำดับกลุ่มกิจกรรมที่นักเรียนต้องการ (1 = อันดับแรก) ต่อช่องกิจกรรมแบบจัดลำดับ';
+
+CREATE TABLE activity_allocation_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slot_id UUID NOT NULL REFERENCES activity_slots(id) ON DELETE CASCADE,
+    method VARCHAR(40) NOT NULL,
+    seed BIGINT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft',
+    student_count INTEGER NOT NULL,
+    matched_count INTEGER NOT NULL,
+    unmatched_count INTEGER NOT NULL,
+    first_choice_count INTEGER NOT NULL,
+    already_enrolled_count INTEGER NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    published_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    published_at TIMESTAMPTZ,
+    CONSTRAINT activity_allocation_runs_method_check CHECK (
+        method IN ('random_serial_dictatorship', 'grade_priority_stable')
+    ),
+    CONSTRAINT activity_allocation_runs_status_check CHECK (
+        status IN ('draft', 'published', 'discarded')
+    ),
+    CONSTRAINT activity_allocation_runs_published_check CHECK (
+        (status = 'published') = (published_at IS NOT NULL)
+    )
+);
+
+COMMENT ON TABLE activity_allocation_runs IS
+    'ผลการจัดสรรกลุ่มกิจกรรมตามลำดับความสนใจ — ร่างทบทวนได้และจับใหม่ได้ก่อนเผยแพร่';
+
+CREATE INDEX idx_activity_allocation_runs_slot
+    ON activity_allocation_runs (slot_id, created_at DESC);
+
+CREATE UNIQUE INDEX activity_allocation_runs_one_published
+    ON activity_allocation_runs (slot_id)
+    WHERE status = 'published';
+
+CREATE TABLE activity_allocation_results (
+    run_id UUID NOT NULL REFERENCES activity_allocation_runs(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    activity_group_id UUID REFERENCES activity_groups(id) ON DELETE CASCADE,
+    preference_rank INTEGER,
+    unmatched_reason VARCHAR(30),
+    PRIMARY KEY (run_id, student_id),
+    CONSTRAINT activity_allocation_results_outcome_check CHECK (
+        (activity_group_id IS NOT NULL AND preference_rank IS NOT NULL AND unmatched_reason IS NULL)
+        OR (activity_group_id IS NULL AND preference_rank IS NULL AND unmatched_reason IN (
+            'no_preferences', 'no_eligible_choice', 'choices_full'
+        ))
+    )
+);
+
+COMMENT ON TABLE activity_allocation_results IS
+    'ผลรายคนของแต่ละรอบการจัดสรร: กลุ่มที่ได้และอันดับที่เลือก หรือเหตุผลที่ไม่ได้กลุ่ม';
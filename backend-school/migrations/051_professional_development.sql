@@ -0,0 +1,192 @@
+-- Teacher professional development portfolio: PLC sessions with participants
+-- and evidence, external training with certificates, and the yearly hour
+-- requirements used for the วPA evaluation.
+-- Durations are stored in minutes; fiscal years follow staff leave
+-- (Thai government Oct-Sep, Buddhist Era of the closing year).
+
+CREATE TABLE pd_hour_requirements (
+    fiscal_year INTEGER PRIMARY KEY,
+    plc_minutes_required INTEGER NOT NULL DEFAULT 0,
+    training_minutes_required INTEGER NOT NULL DEFAULT 0,
+    note TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT pd_hour_requirements_amounts_check CHECK (
+        plc_minutes_required >= 0 AND training_minutes_required >= 0
+    )
+);
+
+CREATE TRIGGER update_pd_hour_requirements_updated_at
+    BEFORE UPDATE ON pd_hour_requirements
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE pd_plc_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    fiscal_year INTEGER NOT NULL,
+    session_date DATE NOT NULL,
+    topic TEXT NOT NULL,
+    details TEXT,
+    duration_minutes INTEGER NOT NULL,
+    supervision_observation_id UUID REFERENCES supervision_observations(id) ON DELETE SET NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT pd_plc_sessions_topic_not_blank CHECK (btrim(topic) <> ''),
+    CONSTRAINT pd_plc_sessions_duration_check CHECK (
+        duration_minutes > 0 AND duration_minutes <= 1440
+    )
+);
+
+CREATE INDEX idx_pd_plc_sessions_fiscal_year
+    ON pd_plc_sessions (fiscal_year, session_date DESC);
+
+CREATE INDEX idx_pd_plc_sessions_observation
+    ON pd_plc_sessions (supervision_observation_id)
+    WHERE supervision_observation_id IS NOT NULL;
+
+CREATE TRIGGER update_pd_plc_sessions_updated_at
+    BEFORE UPDATE ON pd_plc_sessions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE pd_plc_participants (
+    session_id UUID NOT NULL REFERENCES pd_plc_sessions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL DEFAULT 'member',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, user_id),
+    CONSTRAINT pd_plc_participants_role_check CHECK (role IN ('facilitator', 'member'))
+);
+
+CREATE INDEX idx_pd_plc_participants_user
+    ON pd_plc_participants (user_id);
+
+CREATE TABLE pd_plc_evidence (
+    session_id UUID NOT NULL REFERENCES pd_plc_sessions(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'professional_development_evidence',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, file_id),
+    CONSTRAINT pd_plc_evidence_file_unique UNIQUE (file_id),
+    CONSTRAINT pd_plc_evidence_purpose_check CHECK (
+        purpose_code = 'professional_development_evidence'
+    ),
+    CONSTRAINT pd_plc_evidence_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+CREATE TABLE pd_training_records (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fiscal_year INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    provider TEXT,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    duration_minutes INTEGER NOT NULL,
+    note TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT pd_training_records_title_not_blank CHECK (btrim(title) <> ''),
+    CONSTRAINT pd_training_records_date_range_check CHECK (end_date >= start_date),
+    CONSTRAINT pd_training_records_duration_check CHECK (duration_minutes > 0)
+);
+
+CREATE INDEX idx_pd_training_records_user
+    ON pd_training_records (user_id, fiscal_year, end_date DESC);
+
+CREATE TRIGGER update_pd_training_records_updated_at
+    BEFORE UPDATE ON pd_training_records
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE pd_training_certificates (
+    training_record_id UUID NOT NULL REFERENCES pd_training_records(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL,
+    purpose_code VARCHAR(100) NOT NULL DEFAULT 'professional_development_evidence',
+    attached_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (training_record_id, file_id),
+    CONSTRAINT pd_training_certificates_file_unique UNIQUE (file_id),
+    CONSTRAINT pd_training_certificates_purpose_check CHECK (
+        purpose_code = 'professional_development_evidence'
+    ),
+    CONSTRAINT pd_training_certificates_file_purpose_fkey
+        FOREIGN KEY (file_id, purpose_code)
+        REFERENCES files(id, purpose_code) ON DELETE CASCADE
+);
+
+COMMENT ON TABLE pd_hour_requirements IS
+    'Minimum PLC and training hours per fiscal year; a missing row means no requirement is configured.';
+COMMENT ON TABLE pd_plc_sessions IS
+    'Professional learning community sessions; every participant is credited the full session duration.';
+COMMENT ON COLUMN pd_plc_sessions.supervision_observation_id IS
+    'Optional classroom observation the session reflected on, e.g. a lesson study cycle.';
+COMMENT ON TABLE pd_training_records IS
+    'Training, seminars and courses a staff member attended; counted in the fiscal year of end_date.';
+
+WITH professional_development_permissions (code, name, module, action, scope, description) AS (
+    VALUES
+        (
+            'professional_development.submit.own',
+            'บันทึกการพัฒนาวิชาชีพของตนเอง',
+            'professional_development',
+            'submit',
+            'own',
+            'บันทึกกิจกรรม PLC การอบรม และแนบหลักฐานของตนเอง'
+        ),
+        (
+            'professional_development.read.own',
+            'ดูแฟ้มพัฒนาวิชาชีพของตนเอง',
+            'professional_development',
+            'read',
+            'own',
+            'ดูชั่วโมง PLC ชั่วโมงอบรม และพิมพ์แฟ้มสะสมผลงานของตนเอง'
+        ),
+        (
+            'professional_development.read.school',
+            'ดูการพัฒนาวิชาชีพของบุคลากรทั้งโรงเรียน',
+            'professional_development',
+            'read',
+            'school',
+            'ดูสรุปชั่วโมงและแฟ้มสะสมผลงานการพัฒนาวิชาชีพของบุคลากรทุกคน'
+        ),
+        (
+            'professional_development.manage.school',
+            'จัดการเกณฑ์และบันทึกการพัฒนาวิชาชีพ',
+            'professional_development',
+            'manage',
+            'school',
+            'กำหนดเกณฑ์ชั่วโมงประจำปีและแก้ไขบันทึก PLC และการอบรมของบุคลากรทุกคน'
+        )
+)
+INSERT INTO permissions (code, name, module, action, scope, description)
+SELECT code, name, module, action, scope, description
+FROM professional_development_permissions
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
+
+WITH base_staff_permissions AS (
+    SELECT id
+    FROM permissions
+    WHERE code IN (
+        'professional_development.submit.own',
+        'professional_development.read.own'
+    )
+),
+staff_roles AS (
+    SELECT id
+    FROM roles
+    WHERE user_type = 'staff'
+)
+INSERT INTO role_permissions (role_id, permission_id, created_at)
+SELECT staff_roles.id, base_staff_permissions.id, now()
+FROM staff_roles
+CROSS JOIN base_staff_permissions
+ON CONFLICT DO NOTHING;
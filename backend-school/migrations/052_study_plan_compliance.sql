@@ -0,0 +1,189 @@
+-- Study plan compliance against curriculum time structures. A rule set holds
+-- minimum/maximum hours per subject group, activity hours and credit ranges
+-- for a band of grade levels; each study_plan_versions row is validated
+-- against its pinned rule set, or the school default. Activating a version or
+-- generating classroom courses from it is blocked while checks fail, unless
+-- an override waiving exactly those failing checks is on record.
+
+CREATE TABLE curriculum_rule_sets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    weeks_per_term INTEGER NOT NULL DEFAULT 20,
+    terms_per_year INTEGER NOT NULL DEFAULT 2,
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT curriculum_rule_sets_weeks_check CHECK (weeks_per_term BETWEEN 1 AND 30),
+    CONSTRAINT curriculum_rule_sets_terms_check CHECK (terms_per_year BETWEEN 1 AND 4)
+);
+
+CREATE UNIQUE INDEX curriculum_rule_sets_single_default_idx
+    ON curriculum_rule_sets ((true))
+    WHERE is_default;
+
+CREATE TRIGGER update_curriculum_rule_sets_updated_at
+    BEFORE UPDATE ON curriculum_rule_sets
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE curriculum_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_set_id UUID NOT NULL REFERENCES curriculum_rule_sets(id) ON DELETE CASCADE,
+    rule_kind VARCHAR(30) NOT NULL,
+    label VARCHAR(200) NOT NULL,
+    level_type VARCHAR(20) NOT NULL,
+    grade_year_from INTEGER NOT NULL,
+    grade_year_to INTEGER NOT NULL,
+    scope VARCHAR(20) NOT NULL,
+    subject_group_id UUID REFERENCES subject_groups(id) ON DELETE CASCADE,
+    subject_type VARCHAR(50),
+    activity_type VARCHAR(20),
+    min_value DOUBLE PRECISION,
+    max_value DOUBLE PRECISION,
+    display_order INTEGER NOT NULL DEFAULT 0,
+    CONSTRAINT curriculum_rules_kind_check CHECK (
+        rule_kind IN ('subject_group_hours', 'activity_hours', 'credit_range')
+    ),
+    CONSTRAINT curriculum_rules_level_type_check CHECK (
+        level_type IN ('kindergarten', 'primary', 'secondary')
+    ),
+    CONSTRAINT curriculum_rules_grade_range_check CHECK (
+        grade_year_from BETWEEN 1 AND 6
+        AND grade_year_to BETWEEN grade_year_from AND 6
+    ),
+    CONSTRAINT curriculum_rules_scope_check CHECK (scope IN ('per_grade', 'band_total')),
+    CONSTRAINT curriculum_rules_subject_group_check CHECK (
+        (rule_kind = 'subject_group_hours') = (subject_group_id IS NOT NULL)
+    ),
+    CONSTRAINT curriculum_rules_activity_type_check CHECK (
+        activity_type IS NULL
+        OR (
+            rule_kind = 'activity_hours'
+            AND activity_type IN ('scout', 'club', 'guidance', 'social', 'other')
+        )
+    ),
+    CONSTRAINT curriculum_rules_bounds_check CHECK (
+        (min_value IS NOT NULL OR max_value IS NOT NULL)
+        AND (min_value IS NULL OR min_value >= 0)
+        AND (max_value IS NULL OR max_value >= 0)
+        AND (min_value IS NULL OR max_value IS NULL OR min_value <= max_value)
+    )
+);
+
+CREATE INDEX idx_curriculum_rules_rule_set
+    ON curriculum_rules (rule_set_id, display_order);
+
+ALTER TABLE study_plan_versions
+    ADD COLUMN compliance_rule_set_id UUID
+        REFERENCES curriculum_rule_sets(id) ON DELETE SET NULL;
+
+CREATE TABLE study_plan_compliance_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    study_plan_version_id UUID NOT NULL REFERENCES study_plan_versions(id) ON DELETE CASCADE,
+    rule_set_id UUID REFERENCES curriculum_rule_sets(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    waived_checks JSONB NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    revoked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    revoked_at TIMESTAMPTZ,
+    CONSTRAINT study_plan_compliance_overrides_reason_check CHECK (length(btrim(reason)) > 0)
+);
+
+CREATE UNIQUE INDEX study_plan_compliance_overrides_active_idx
+    ON study_plan_compliance_overrides (study_plan_version_id)
+    WHERE revoked_at IS NULL;
+
+COMMENT ON TABLE curriculum_rule_sets IS
+    'ชุดเกณฑ์โครงสร้างเวลาเรียนสำหรับตรวจสอบหลักสูตรสถานศึกษา';
+COMMENT ON COLUMN curriculum_rule_sets.weeks_per_term IS
+    'จำนวนสัปดาห์ต่อภาคเรียน ใช้แปลงคาบกิจกรรมต่อสัปดาห์เป็นชั่วโมง';
+COMMENT ON TABLE curriculum_rules IS
+    'เกณฑ์รายข้อ: ชั่วโมงกลุ่มสาระ ชั่วโมงกิจกรรมพัฒนาผู้เรียน หรือช่วงหน่วยกิตรวม ต่อระดับชั้นหรือรวมทั้งช่วงชั้น';
+COMMENT ON COLUMN study_plan_versions.compliance_rule_set_id IS
+    'ชุดเกณฑ์ที่ใช้ตรวจหลักสูตรฉบับนี้ — null = ใช้ชุดเกณฑ์ตั้งต้นของโรงเรียน';
+COMMENT ON TABLE study_plan_compliance_overrides IS
+    'การอนุมัติยกเว้นเกณฑ์หลักสูตร ครอบคลุมเฉพาะรายการที่ไม่ผ่าน ณ เวลาที่บันทึก (waived_checks)';
+
+-- Default rule set: time structure of the Basic Education Core Curriculum
+-- B.E. 2551 (revised B.E. 2560). Lower secondary hours are per year, upper
+-- secondary hours are totals over ม.4-ม.6.
+WITH default_rule_set AS (
+    INSERT INTO curriculum_rule_sets (name, description, is_default)
+    VALUES (
+        'โครงสร้างเวลาเรียนหลักสูตรแกนกลางฯ พ.ศ. 2551 (ฉบับปรับปรุง พ.ศ. 2560)',
+        'เวลาเรียนพื้นฐานขั้นต่ำรายกลุ่มสาระ กิจกรรมพัฒนาผู้เรียน และหน่วยกิตรวมขั้นต่ำสำหรับมัธยมศึกษา',
+        true
+    )
+    RETURNING id
+),
+rule_seed (
+    display_order, rule_kind, label, grade_year_from, grade_year_to, scope,
+    subject_group_code, subject_type, activity_type, min_value, max_value
+) AS (
+    VALUES
+        (1, 'subject_group_hours', 'ภาษาไทย (ม.ต้น)', 1, 3, 'per_grade', 'TH', 'BASIC', NULL::text, 120::double precision, NULL::double precision),
+        (2, 'subject_group_hours', 'คณิตศาสตร์ (ม.ต้น)', 1, 3, 'per_grade', 'MA', 'BASIC', NULL, 120, NULL),
+        (3, 'subject_group_hours', 'วิทยาศาสตร์และเทคโนโลยี (ม.ต้น)', 1, 3, 'per_grade', 'SC', 'BASIC', NULL, 160, NULL),
+        (4, 'subject_group_hours', 'สังคมศึกษา ศาสนา และวัฒนธรรม (ม.ต้น)', 1, 3, 'per_grade', 'SO', 'BASIC', NULL, 160, NULL),
+        (5, 'subject_group_hours', 'สุขศึกษาและพลศึกษา (ม.ต้น)', 1, 3, 'per_grade', 'HP', 'BASIC', NULL, 80, NULL),
+        (6, 'subject_group_hours', 'ศิลปะ (ม.ต้น)', 1, 3, 'per_grade', 'AR', 'BASIC', NULL, 80, NULL),
+        (7, 'subject_group_hours', 'การงานอาชีพ (ม.ต้น)', 1, 3, 'per_grade', 'OC', 'BASIC', NULL, 40, NULL),
+        (8, 'subject_group_hours', 'ภาษาต่างประเทศ (ม.ต้น)', 1, 3, 'per_grade', 'EN', 'BASIC', NULL, 120, NULL),
+        (9, 'activity_hours', 'กิจกรรมพัฒนาผู้เรียน (ม.ต้น)', 1, 3, 'per_grade', NULL, NULL, NULL, 120, NULL),
+        (10, 'activity_hours', 'กิจกรรมแนะแนว (ม.ต้น)', 1, 3, 'per_grade', NULL, NULL, 'guidance', 40, NULL),
+        (11, 'activity_hours', 'ลูกเสือ เนตรนารี (ม.ต้น)', 1, 3, 'per_grade', NULL, NULL, 'scout', 40, NULL),
+        (12, 'activity_hours', 'กิจกรรมเพื่อสังคมและสาธารณประโยชน์ (ม.ต้น)', 1, 3, 'per_grade', NULL, NULL, 'social', 15, NULL),
+        (13, 'credit_range', 'หน่วยกิตรวม ม.1-ม.3', 1, 3, 'band_total', NULL, NULL, NULL, 77, NULL),
+        (14, 'subject_group_hours', 'ภาษาไทย (ม.ปลาย)', 4, 6, 'band_total', 'TH', 'BASIC', NULL, 240, NULL),
+        (15, 'subject_group_hours', 'คณิตศาสตร์ (ม.ปลาย)', 4, 6, 'band_total', 'MA', 'BASIC', NULL, 240, NULL),
+        (16, 'subject_group_hours', 'วิทยาศาสตร์และเทคโนโลยี (ม.ปลาย)', 4, 6, 'band_total', 'SC', 'BASIC', NULL, 240, NULL),
+        (17, 'subject_group_hours', 'สังคมศึกษา ศาสนา และวัฒนธรรม (ม.ปลาย)', 4, 6, 'band_total', 'SO', 'BASIC', NULL, 320, NULL),
+        (18, 'subject_group_hours', 'สุขศึกษาและพลศึกษา (ม.ปลาย)', 4, 6, 'band_total', 'HP', 'BASIC', NULL, 120, NULL),
+        (19, 'subject_group_hours', 'ศิลปะ (ม.ปลาย)', 4, 6, 'band_total', 'AR', 'BASIC', NULL, 120, NULL),
+        (20, 'subject_group_hours', 'การงานอาชีพ (ม.ปลาย)', 4, 6, 'band_total', 'OC', 'BASIC', NULL, 120, NULL),
+        (21, 'subject_group_hours', 'ภาษาต่างประเทศ (ม.ปลาย)', 4, 6, 'band_total', 'EN', 'BASIC', NULL, 240, NULL),
+        (22, 'activity_hours', 'กิจกรรมพัฒนาผู้เรียน (ม.ปลาย)', 4, 6, 'band_total', NULL, NULL, NULL, 360, NULL),
+        (23, 'activity_hours', 'กิจกรรมแนะแนว (ม.ปลาย)', 4, 6, 'band_total', NULL, NULL, 'guidance', 120, NULL),
+        (24, 'activity_hours', 'กิจกรรมเพื่อสังคมและสาธารณประโยชน์ (ม.ปลาย)', 4, 6, 'band_total', NULL, NULL, 'social', 60, NULL),
+        (25, 'credit_range', 'หน่วยกิตรวม ม.4-ม.6', 4, 6, 'band_total', NULL, NULL, NULL, 77, NULL)
+)
+INSERT INTO curriculum_rules (
+    rule_set_id, rule_kind, label, level_type, grade_year_from, grade_year_to, scope,
+    subject_group_id, subject_type, activity_type, min_value, max_value, display_order
+)
+SELECT default_rule_set.id,
+       rule_seed.rule_kind,
+       rule_seed.label,
+       'secondary',
+       rule_seed.grade_year_from,
+       rule_seed.grade_year_to,
+       rule_seed.scope,
+       subject_groups.id,
+       rule_seed.subject_type,
+       rule_seed.activity_type,
+       rule_seed.min_value,
+       rule_seed.max_value,
+       rule_seed.display_order
+FROM default_rule_set
+CROSS JOIN rule_seed
+LEFT JOIN subject_groups ON subject_groups.code = rule_seed.subject_group_code
+WHERE rule_seed.subject_group_code IS NULL
+   OR subject_groups.id IS NOT NULL;
+
+INSERT INTO permissions (code, name, module, action, scope, description)
+VALUES (
+    'academic_curriculum.approve.all',
+    'อนุมัติยกเว้นเกณฑ์หลักสูตร',
+    'academic_curriculum',
+    'approve',
+    'all',
+    'บันทึกการอนุมัติให้เปิดใช้หรือสร้างรายวิชาจากหลักสูตรที่ไม่ผ่านเกณฑ์โครงสร้างเวลาเรียน'
+)
+ON CONFLICT (code) DO UPDATE
+SET name = EXCLUDED.name,
+    module = EXCLUDED.module,
+    action = EXCLUDED.action,
+    scope = EXCLUDED.scope,
+    description = EXCLUDED.description;
@@ -11,11 +11,14 @@ use crate::modules::academic::handlers::course_planning::CourseAssignedCountData
 use crate::modules::academic::handlers::study_plans::{CountData, GenerateCoursesData};
 use crate::modules::academic::handlers::timetable::{MyTimetableData, TimetableItemsData};
 use crate::modules::academic::models::activity::{
-    ActivityGroup, ActivityGroupFilter, ActivityGroupInstructorRole, ActivityGroupMember,
-    ActivityMemberResult, ActivityRegistrationType, ActivitySlot, ActivitySlotFilter,
-    ActivitySlotTimetableContextResponse, AddMembersRequest,
-    BatchUpsertSlotClassroomAssignmentsRequest, CreateActivityGroupRequest, InstructorInfo,
-    SlotClassroomAssignment, SlotInstructorInfo, UpdateActivityGroupRequest,
+    ActivityAllocationGroupFill, ActivityAllocationMethod, ActivityAllocationPlacement,
+    ActivityAllocationRun, ActivityAllocationRunDetail, ActivityGroup, ActivityGroupFilter,
+    ActivityGroupInstructorRole, ActivityGroupMember, ActivityMemberResult,
+    ActivityPreferenceChoice, ActivityRegistrationType, ActivitySlot, ActivitySlotFilter,
+    ActivitySlotTimetableContextResponse, ActivityUnmatchedReason, ActivityUnmatchedStudent,
+    AddMembersRequest, BatchUpsertSlotClassroomAssignmentsRequest, CreateActivityGroupRequest,
+    InstructorInfo, MyActivityPreferences, RunActivityAllocationRequest, SlotClassroomAssignment,
+    SlotInstructorInfo, SubmitActivityPreferencesRequest, UpdateActivityGroupRequest,
     UpdateActivitySlotRequest, UpdateMemberResultRequest, UpsertSlotClassroomAssignmentRequest,
 };
 use crate::modules::academic::models::course_planning::{
@@ -38,9 +41,12 @@ use crate::modules::academic::models::study_plans::{
     ActivityCatalog, ActivityCatalogType, ActivitySchedulingMode,
     AddCatalogDefaultInstructorRequest, AddSubjectsToVersionRequest, CatalogDefaultInstructor,
     CatalogDefaultInstructorInput, CreateCatalogRequest, CreatePlanActivityRequest,
-    CreateStudyPlanRequest, CreateStudyPlanVersionRequest, GenerateActivitiesFromPlanRequest,
-    GenerateCoursesFromPlanRequest, GenerateCoursesResponse, StudyPlan, StudyPlanSubject,
-    StudyPlanVersion, StudyPlanVersionActivity, SubjectInPlan,
+    CreateStudyPlanRequest, CreateStudyPlanVersionRequest, CurriculumRule, CurriculumRuleInput,
+    CurriculumRuleKind, CurriculumRuleScope, CurriculumRuleSet, CurriculumRuleUnit,
+    GenerateActivitiesFromPlanRequest, GenerateCoursesFromPlanRequest, GenerateCoursesResponse,
+    RecordStudyPlanComplianceOverrideRequest, SaveCurriculumRuleSetRequest, StudyPlan,
+    StudyPlanComplianceCheck, StudyPlanComplianceOverride, StudyPlanComplianceReport,
+    StudyPlanSubject, StudyPlanVersion, StudyPlanVersionActivity, SubjectInPlan,
     UpdateCatalogDefaultInstructorRoleRequest, UpdateCatalogRequest, UpdatePlanActivityRequest,
     UpdateStudyPlanRequest, UpdateStudyPlanVersionRequest,
 };
@@ -70,7 +76,7 @@ use crate::modules::admission::models::applications::PortalCredentials;
 use crate::modules::admission::services::application_service::DocumentUploadResponse;
 use crate::modules::auth::models::{
     ChangePasswordRequest, CurrentUserResponse, LoginData, LoginRequest, ProfileResponse,
-    SessionListData, SessionResponse, UpdateProfileRequest,
+    SessionListData, SessionResponse, SupportSessionBanner, UpdateProfileRequest,
 };
 use crate::modules::calendar::models::{
     CalendarCategory, CalendarEvent, CalendarEventReminder, CalendarEventTag, CalendarEventTarget,
@@ -119,6 +125,10 @@ use crate::modules::files::models::{
     PublicFileDeliveryResponse,
 };
 use crate::modules::files::platform_types::{FileLifecycleStatus, FilePurpose};
+use crate::modules::finance::models::{
+    ChildFeeInvoice, ChildFeePayment, ChildFeeStatement, FeeInvoiceLine, FeeInvoiceLineKind,
+    FeeInvoiceStatus, FeePaymentMethod, FeePaymentStatus,
+};
 use crate::modules::lookup::models::{
     AcademicYearLookupItem, ClassroomLookupItem, GradeLevelLookupItem, LookupItem,
     OrganizationUnitLookupItem, RoleLookupItem, StaffLookupItem, StudentLookupItem,
@@ -162,6 +172,7 @@ use crate::modules::students::models::{
     CreateParentRequest, CreateStudentRequest, CreateStudentResponse, ParentDto, StudentDbRow,
     StudentProfile, UpdateOwnProfileRequest, UpdateStudentRequest,
 };
+use crate::modules::support_access::models::SupportHandoffRequest;
 use crate::modules::system::handlers::feature_toggles::{
     FeatureListResponse, FeatureToggleResponse,
 };
@@ -174,6 +185,7 @@ use utoipa::OpenApi;
     paths(
         crate::modules::auth::session_handlers::login,
         crate::modules::auth::session_handlers::logout,
+        crate::modules::auth::session_handlers::support_handoff,
         crate::modules::auth::session_handlers::me,
         crate::modules::auth::handlers::get_profile,
         crate::modules::auth::handlers::update_profile,
@@ -294,6 +306,7 @@ use utoipa::OpenApi;
         crate::modules::parents::handlers::get_child_timetable,
         crate::modules::parents::handlers::get_child_exam_schedule,
         crate::modules::parents::handlers::get_child_calendar_events,
+        crate::modules::parents::handlers::get_child_fees,
         crate::modules::academic::handlers::timetable::get_my_timetable,
         crate::modules::academic::handlers::timetable::daily_teaching_overview,
         crate::modules::academic::handlers::exam_schedule::list_my_exam_schedule,
@@ -337,6 +350,13 @@ use utoipa::OpenApi;
         crate::modules::academic::handlers::study_plans::create_study_plan_version,
         crate::modules::academic::handlers::study_plans::update_study_plan_version,
         crate::modules::academic::handlers::study_plans::delete_study_plan_version,
+        crate::modules::academic::handlers::study_plans::list_curriculum_rule_sets,
+        crate::modules::academic::handlers::study_plans::create_curriculum_rule_set,
+        crate::modules::academic::handlers::study_plans::update_curriculum_rule_set,
+        crate::modules::academic::handlers::study_plans::delete_curriculum_rule_set,
+        crate::modules::academic::handlers::study_plans::get_study_plan_compliance_report,
+        crate::modules::academic::handlers::study_plans::record_study_plan_compliance_override,
+        crate::modules::academic::handlers::study_plans::revoke_study_plan_compliance_override,
         crate::modules::academic::handlers::study_plans::list_study_plan_subjects,
         crate::modules::academic::handlers::study_plans::add_subjects_to_version,
         crate::modules::academic::handlers::study_plans::delete_study_plan_subject,
@@ -383,6 +403,12 @@ use utoipa::OpenApi;
         crate::modules::academic::handlers::activity::my_enrollments,
         crate::modules::academic::handlers::activity::self_enroll,
         crate::modules::academic::handlers::activity::self_unenroll,
+        crate::modules::academic::handlers::activity::get_my_activity_preferences,
+        crate::modules::academic::handlers::activity::submit_my_activity_preferences,
+        crate::modules::academic::handlers::activity::list_activity_allocation_runs,
+        crate::modules::academic::handlers::activity::run_activity_allocation,
+        crate::modules::academic::handlers::activity::get_activity_allocation_run,
+        crate::modules::academic::handlers::activity::publish_activity_allocation_run,
         crate::modules::academic::handlers::course_planning::list_classroom_courses,
         crate::modules::academic::handlers::course_planning::assign_courses,
         crate::modules::academic::handlers::course_planning::update_course,
@@ -437,6 +463,8 @@ use utoipa::OpenApi;
         UpdateProfileRequest,
         ChangePasswordRequest,
         CurrentUserResponse,
+        SupportSessionBanner,
+        SupportHandoffRequest,
         SessionResponse,
         SessionListData,
         ApiResponse<CurrentUserResponse>,
@@ -748,6 +776,20 @@ use utoipa::OpenApi;
         ApiResponse<Vec<StudyPlanSubject>>,
         ApiResponse<CountData<usize>>,
         ApiResponse<GenerateCoursesData>,
+        CurriculumRuleKind,
+        CurriculumRuleScope,
+        CurriculumRuleUnit,
+        CurriculumRule,
+        CurriculumRuleSet,
+        CurriculumRuleInput,
+        SaveCurriculumRuleSetRequest,
+        StudyPlanComplianceCheck,
+        StudyPlanComplianceOverride,
+        StudyPlanComplianceReport,
+        RecordStudyPlanComplianceOverrideRequest,
+        ApiResponse<Vec<CurriculumRuleSet>>,
+        ApiResponse<CurriculumRuleSet>,
+        ApiResponse<StudyPlanComplianceReport>,
         CourseInstructorRole,
         ClassroomCourse,
         ClassroomCourseSettings,
@@ -806,6 +848,17 @@ use utoipa::OpenApi;
         ActivityAddedCountData,
         ActivityDeletedCountData,
         ActivityProcessedCountData,
+        ActivityPreferenceChoice,
+        MyActivityPreferences,
+        SubmitActivityPreferencesRequest,
+        ActivityAllocationMethod,
+        RunActivityAllocationRequest,
+        ActivityUnmatchedReason,
+        ActivityAllocationRun,
+        ActivityAllocationPlacement,
+        ActivityUnmatchedStudent,
+        ActivityAllocationGroupFill,
+        ActivityAllocationRunDetail,
         ApiResponse<Vec<ActivitySlot>>,
         ApiResponse<ActivitySlot>,
         ApiResponse<ActivitySlotTimetableContextResponse>,
@@ -819,6 +872,9 @@ use utoipa::OpenApi;
         ApiResponse<ActivityAddedCountData>,
         ApiResponse<ActivityDeletedCountData>,
         ApiResponse<ActivityProcessedCountData>,
+        ApiResponse<MyActivityPreferences>,
+        ApiResponse<Vec<ActivityAllocationRun>>,
+        ApiResponse<ActivityAllocationRunDetail>,
         GenerateActivitiesFromPlanOutcome,
         ApiResponse<Vec<StudyPlanVersionActivity>>,
         ApiResponse<StudyPlanVersionActivity>,
@@ -856,6 +912,15 @@ use utoipa::OpenApi;
         CalendarEventTag,
         CalendarViewerEvent,
         ApiResponse<Vec<CalendarViewerEvent>>,
+        FeeInvoiceStatus,
+        FeeInvoiceLineKind,
+        FeePaymentMethod,
+        FeePaymentStatus,
+        FeeInvoiceLine,
+        ChildFeePayment,
+        ChildFeeInvoice,
+        ChildFeeStatement,
+        ApiResponse<ChildFeeStatement>,
         CalendarCategory,
         CalendarTag,
         CalendarEventTarget,
@@ -1053,6 +1118,7 @@ mod tests {
             "ApiResponse_LoginData",
             "ApiResponse_CurrentUserResponse",
             "ApiResponse_SessionListData",
+            "SupportHandoffRequest",
         ] {
             assert!(!schemas[schema].is_null(), "missing schema {schema}");
         }
@@ -1149,6 +1215,7 @@ mod tests {
             &[
                 ("/api/auth/login", "post", "login"),
                 ("/api/auth/logout", "post", "logout"),
+                ("/api/auth/support-handoff", "post", "supportHandoff"),
                 ("/api/auth/me", "get", "getCurrentUser"),
                 ("/api/auth/me/profile", "get", "getCurrentUserProfile"),
                 ("/api/auth/me/profile", "put", "updateCurrentUserProfile"),
@@ -1794,6 +1861,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn academic_study_plan_compliance_contracts() {
+        let document = school_api_value().expect("document should serialize");
+        assert_operations(
+            &document,
+            &[
+                (
+                    "/api/academic/curriculum-rule-sets",
+                    "get",
+                    "listCurriculumRuleSets",
+                ),
+                (
+                    "/api/academic/curriculum-rule-sets",
+                    "post",
+                    "createCurriculumRuleSet",
+                ),
+                (
+                    "/api/academic/curriculum-rule-sets/{id}",
+                    "put",
+                    "updateCurriculumRuleSet",
+                ),
+                (
+                    "/api/academic/curriculum-rule-sets/{id}",
+                    "delete",
+                    "deleteCurriculumRuleSet",
+                ),
+                (
+                    "/api/academic/study-plan-versions/{id}/compliance",
+                    "get",
+                    "getStudyPlanComplianceReport",
+                ),
+                (
+                    "/api/academic/study-plan-versions/{id}/compliance-override",
+                    "post",
+                    "recordStudyPlanComplianceOverride",
+                ),
+                (
+                    "/api/academic/study-plan-versions/{id}/compliance-override",
+                    "delete",
+                    "revokeStudyPlanComplianceOverride",
+                ),
+            ],
+        );
+
+        let create_rule_set = &document["paths"]["/api/academic/curriculum-rule-sets"]["post"];
+        assert_eq!(
+            create_rule_set["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/SaveCurriculumRuleSetRequest"
+        );
+        assert_eq!(
+            create_rule_set["responses"]["201"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiResponse_CurriculumRuleSet"
+        );
+        let report = &document["paths"]["/api/academic/study-plan-versions/{id}/compliance"]["get"];
+        assert_eq!(
+            report["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiResponse_StudyPlanComplianceReport"
+        );
+        for (path, method) in [
+            ("/api/academic/study-plan-versions/{id}", "put"),
+            ("/api/academic/planning/generate-from-plan", "post"),
+            ("/api/academic/activities/generate-from-plan", "post"),
+        ] {
+            assert!(
+                document["paths"][path][method]["responses"]["409"].is_object(),
+                "compliance-gated operation {method} {path} should document 409"
+            );
+        }
+    }
+
     #[test]
     fn academic_activity_template_contracts() {
         let document = school_api_value().expect("document should serialize");
@@ -2106,6 +2243,36 @@ mod tests {
                     "delete",
                     "selfUnenrollActivityGroup",
                 ),
+                (
+                    "/api/academic/activity-slots/{id}/my-preferences",
+                    "get",
+                    "getMyActivityPreferences",
+                ),
+                (
+                    "/api/academic/activity-slots/{id}/my-preferences",
+                    "put",
+                    "submitMyActivityPreferences",
+                ),
+                (
+                    "/api/academic/activity-slots/{id}/allocation-runs",
+                    "get",
+                    "listActivityAllocationRuns",
+                ),
+                (
+                    "/api/academic/activity-slots/{id}/allocation-runs",
+                    "post",
+                    "runActivityAllocation",
+                ),
+                (
+                    "/api/academic/activity-allocation-runs/{id}",
+                    "get",
+                    "getActivityAllocationRun",
+                ),
+                (
+                    "/api/academic/activity-allocation-runs/{id}/publish",
+                    "post",
+                    "publishActivityAllocationRun",
+                ),
             ],
         );
 
@@ -2155,6 +2322,16 @@ mod tests {
                 "post",
                 "InstructorRoleRequest",
             ),
+            (
+                "/api/academic/activity-slots/{id}/my-preferences",
+                "put",
+                "SubmitActivityPreferencesRequest",
+            ),
+            (
+                "/api/academic/activity-slots/{id}/allocation-runs",
+                "post",
+                "RunActivityAllocationRequest",
+            ),
         ] {
             assert_eq!(
                 document["paths"][path][method]["requestBody"]["content"]["application/json"]
@@ -2209,6 +2386,26 @@ mod tests {
                 "post",
                 "ApiResponse_ActivityProcessedCountData",
             ),
+            (
+                "/api/academic/activity-slots/{id}/my-preferences",
+                "get",
+                "ApiResponse_MyActivityPreferences",
+            ),
+            (
+                "/api/academic/activity-slots/{id}/allocation-runs",
+                "get",
+                "ApiResponse_Vec_ActivityAllocationRun",
+            ),
+            (
+                "/api/academic/activity-slots/{id}/allocation-runs",
+                "post",
+                "ApiResponse_ActivityAllocationRunDetail",
+            ),
+            (
+                "/api/academic/activity-allocation-runs/{id}/publish",
+                "post",
+                "ApiResponse_ActivityAllocationRunDetail",
+            ),
         ] {
             assert_eq!(
                 document["paths"][path][method]["responses"]["200"]["content"]["application/json"]
@@ -2239,6 +2436,20 @@ mod tests {
         assert!(schemas["InstructorRoleRequest"]["properties"]["role"]
             .to_string()
             .contains("#/components/schemas/ActivityGroupInstructorRole"));
+
+        assert!(
+            schemas["RunActivityAllocationRequest"]["properties"]["method"]
+                .to_string()
+                .contains("#/components/schemas/ActivityAllocationMethod")
+        );
+        assert!(schemas["ActivityUnmatchedStudent"]["properties"]["reason"]
+            .to_string()
+            .contains("#/components/schemas/ActivityUnmatchedReason"));
+        assert_eq!(
+            document["paths"]["/api/academic/activity-allocation-runs/{id}/publish"]["post"]
+                ["responses"]["409"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiErrorResponse"
+        );
     }
 
     #[test]
@@ -2759,6 +2970,11 @@ mod tests {
                     "get",
                     "getParentChildCalendarEvents",
                 ),
+                (
+                    "/api/parent/students/{student_id}/fees",
+                    "get",
+                    "getParentChildFees",
+                ),
                 ("/api/me/timetable", "get", "getMyTimetable"),
                 ("/api/me/exam-schedules", "get", "listMyExamSchedules"),
                 ("/api/staff/exam-schedules", "get", "listStaffExamSchedules"),
@@ -2781,6 +2997,28 @@ mod tests {
                 ["application/json"]["schema"]["$ref"],
             "#/components/schemas/ApiResponse_Vec_StaffPublishedExamScheduleRound"
         );
+        assert_eq!(
+            document["paths"]["/api/parent/students/{student_id}/fees"]["get"]["responses"]["200"]
+                ["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiResponse_ChildFeeStatement"
+        );
+        assert_eq!(
+            required(&document["components"]["schemas"]["ChildFeeInvoice"]),
+            vec![
+                "dueDate",
+                "id",
+                "invoiceNumber",
+                "lines",
+                "outstandingSatang",
+                "paidSatang",
+                "payments",
+                "pendingSatang",
+                "promptpayPayload",
+                "status",
+                "title",
+                "totalSatang",
+            ]
+        );
 
         let parent_calendar_parameters = document["paths"]
             ["/api/parent/students/{student_id}/calendar/events"]["get"]["parameters"]
@@ -2989,6 +3227,7 @@ mod tests {
         let expected = [
             ("/api/auth/login", "post", "login"),
             ("/api/auth/logout", "post", "logout"),
+            ("/api/auth/support-handoff", "post", "supportHandoff"),
             ("/api/auth/me", "get", "getCurrentUser"),
             ("/api/auth/me/profile", "get", "getCurrentUserProfile"),
             ("/api/auth/me/profile", "put", "updateCurrentUserProfile"),
@@ -3080,7 +3319,7 @@ mod tests {
                 "removeOrganizationMember",
             ),
         ];
-        assert_eq!(expected.len(), 32);
+        assert_eq!(expected.len(), 33);
         assert_operations(&document, &expected);
 
         let mut operation_ids = HashSet::new();
@@ -46,6 +46,11 @@ fn public_routes() -> Router<AppState> {
             "/api/auth/logout",
             post(modules::auth::session_handlers::logout),
         )
+        .route(
+            "/api/auth/support-handoff",
+            post(modules::auth::session_handlers::support_handoff)
+                .layer(DefaultBodyLimit::max(AUTH_JSON_BODY_LIMIT)),
+        )
         .route(
             "/api/consent/types",
             get(modules::consent::handlers::get_consent_types),
@@ -59,6 +64,10 @@ fn public_routes() -> Router<AppState> {
             get(modules::files::handlers::get_public_file_delivery),
         )
         .merge(public_certificate_routes())
+        .nest(
+            "/api/public/announcements",
+            modules::announcement::announcement_public_routes(),
+        )
         .nest(
             "/api/admission",
             modules::admission::admission_public_routes(),
@@ -123,6 +132,50 @@ fn internal_routes() -> Router<AppState> {
             "/internal/migration-status",
             get(modules::system::handlers::migration::migration_status),
         )
+        .route(
+            "/internal/migrations/tenant",
+            post(modules::system::handlers::migration::migrate_tenant),
+        )
+        .route(
+            "/internal/snapshots/export",
+            post(modules::system::handlers::snapshot::export_tenant_snapshot),
+        )
+        .route(
+            "/internal/snapshots/restore",
+            post(modules::system::handlers::snapshot::restore_tenant_snapshot),
+        )
+        .route(
+            "/internal/key-rotation/run",
+            post(modules::system::handlers::key_rotation::run_key_rotation),
+        )
+        .route(
+            "/internal/key-rotation/status",
+            post(modules::system::handlers::key_rotation::key_rotation_status),
+        )
+        .route(
+            "/internal/usage",
+            post(modules::system::handlers::usage::collect_tenant_usage),
+        )
+        .route(
+            "/internal/metrics",
+            get(modules::system::handlers::metrics::tenant_pool_metrics),
+        )
+        .route(
+            "/internal/support-access",
+            post(modules::support_access::handlers::create_support_access),
+        )
+        .route(
+            "/internal/support-access/status",
+            post(modules::support_access::handlers::support_access_status),
+        )
+        .route(
+            "/internal/support-access/handoff",
+            post(modules::support_access::handlers::issue_support_handoff),
+        )
+        .route(
+            "/internal/support-access/end",
+            post(modules::support_access::handlers::end_support_access_internal),
+        )
         .route_layer(from_fn(middleware::internal_auth::validate_internal_secret))
 }
 
@@ -365,6 +418,10 @@ fn protected_routes() -> Router<AppState> {
             "/api/parent/students/{student_id}/calendar/events",
             get(modules::parents::handlers::get_child_calendar_events),
         )
+        .route(
+            "/api/parent/students/{student_id}/fees",
+            get(modules::parents::handlers::get_child_fees),
+        )
         .route(
             "/api/me/timetable",
             get(modules::academic::handlers::timetable::get_my_timetable),
@@ -567,12 +624,34 @@ fn protected_routes() -> Router<AppState> {
             post(modules::files::handlers::download_file),
         )
         .nest("/api/academic", modules::academic::academic_routes())
+        .nest("/api/behavior", modules::behavior::behavior_routes())
         .nest("/api/calendar", modules::calendar::calendar_routes())
         .nest(
             "/api/supervision",
             modules::supervision::supervision_routes(),
         )
         .nest("/api/facilities", modules::facility::facility_routes())
+        .nest(
+            "/api/support-access",
+            modules::support_access::support_access_routes(),
+        )
+        .nest(
+            "/api/student-leave",
+            modules::student_leave::student_leave_routes(),
+        )
+        .nest(
+            "/api/staff-leave",
+            modules::staff_leave::staff_leave_routes(),
+        )
+        .nest(
+            "/api/professional-development",
+            modules::professional_development::professional_development_routes(),
+        )
+        .nest(
+            "/api/announcements",
+            modules::announcement::announcement_routes(),
+        )
+        .nest("/api/finance", modules::finance::finance_routes())
         .nest("/api", modules::workflow::workflow_routes())
         .nest("/api", modules::work::work_routes())
         .nest(
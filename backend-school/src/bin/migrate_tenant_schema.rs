@@ -1,4 +1,4 @@
-use sqlx::postgres::PgPoolOptions;
+use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::{env, error::Error, time::Duration};
 
 #[path = "../permissions/registry.rs"]
@@ -32,26 +32,47 @@ async fn main() -> MigrationResult<()> {
 
     validate_schema_name(&schema, allow_public_schema())?;
 
-    let search_path_sql = format!(r#"SET search_path TO "{schema}", public"#);
+    // search_path is set per transaction rather than per connection, so the
+    // URL may point at a transaction-mode pooler as well as the server itself.
     let pool = PgPoolOptions::new()
-        .max_connections(5)
+        .max_connections(1)
         .acquire_timeout(Duration::from_secs(30))
-        .after_connect(move |connection, _metadata| {
-            let search_path_sql = search_path_sql.clone();
-            Box::pin(async move {
-                sqlx::query(&search_path_sql).execute(connection).await?;
-                Ok(())
-            })
-        })
         .connect(&database_url)
         .await?;
 
-    migration::run_tenant_migrations(&pool).await?;
+    run_tenant_migrations_in_schema(&pool, &schema).await?;
     pool.close().await;
 
     Ok(())
 }
 
+/// Applies every tenant migration and the permission contract in one
+/// transaction whose search_path starts at `schema`. `set_config(..., true)`
+/// is transaction-local, so no session state outlives the run.
+async fn run_tenant_migrations_in_schema(pool: &PgPool, schema: &str) -> MigrationResult<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT set_config('search_path', $1, true)")
+        .bind(schema_search_path(schema))
+        .execute(&mut *tx)
+        .await?;
+
+    migration::all_migrations_without_db_lock()
+        .run(&mut *tx)
+        .await
+        .map_err(|error| format!("Migration failed: {}", error))?;
+    permission_sync::sync_permissions_on(&mut tx)
+        .await
+        .map_err(|error| format!("Permission sync failed after migrations: {}", error))?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+fn schema_search_path(schema: &str) -> String {
+    format!(r#""{schema}", public"#)
+}
+
 fn allow_public_schema() -> bool {
     env::var("MIGRATION_SCHEMA_ALLOW_PUBLIC").is_ok_and(|value| value == "1")
 }
@@ -80,7 +101,15 @@ fn validate_schema_name(schema: &str, allow_public: bool) -> MigrationResult<()>
 
 #[cfg(test)]
 mod tests {
-    use super::validate_schema_name;
+    use super::{schema_search_path, validate_schema_name};
+
+    #[test]
+    fn search_path_targets_the_schema_then_public() {
+        assert_eq!(
+            schema_search_path("tenant_alpha"),
+            r#""tenant_alpha", public"#
+        );
+    }
 
     #[test]
     fn rejects_public_schema_without_explicit_guard() {
@@ -2,4 +2,5 @@ pub mod admin_client;
 pub mod migration;
 pub mod permission_cache;
 pub mod pool_manager;
+pub mod pool_metrics;
 pub mod school_mapping;
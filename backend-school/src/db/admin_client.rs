@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -10,6 +11,10 @@ const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 5_000;
 const DEFAULT_MAX_ATTEMPTS: usize = 3;
 const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 100;
 
+/// `migration_status` backend-admin sets while a migration campaign wave is
+/// migrating the tenant. Such tenants are not served until the wave records a result.
+pub const TENANT_MIGRATING_STATUS: &str = "migrating";
+
 #[derive(Clone, Debug)]
 pub struct AdminClientConfig {
     request_timeout: Duration,
@@ -76,7 +81,7 @@ impl AdminClientConfig {
     }
 }
 
-fn parse_bounded<T>(
+pub(super) fn parse_bounded<T>(
     name: &str,
     value: Option<&str>,
     default: T,
@@ -117,12 +122,53 @@ struct SchoolDbInfo {
     id: Option<String>,
     db_connection_string: Option<String>,
     name: Option<String>,
+    #[serde(default)]
+    migration_status: Option<String>,
+    #[serde(default)]
+    plan: Option<TenantPlan>,
+}
+
+/// Modules every school keeps regardless of plan, so an administrator can
+/// always sign in and manage the school.
+const CORE_MODULES: &[&str] = &[
+    "dashboard",
+    "features",
+    "menu",
+    "profile",
+    "roles",
+    "settings",
+    "system",
+];
+
+/// Limits and modules of the school's plan in backend-admin.
+/// A missing limit is unlimited; a school without a plan is unrestricted.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TenantPlan {
+    pub code: Option<String>,
+    pub max_students: Option<i64>,
+    pub max_storage_bytes: Option<i64>,
+    pub max_certificates_per_year: Option<i64>,
+    /// Permission module codes; `None` enables every module
+    pub enabled_modules: Option<Vec<String>>,
+}
+
+impl TenantPlan {
+    pub fn is_module_enabled(&self, module: &str) -> bool {
+        match &self.enabled_modules {
+            None => true,
+            Some(modules) => {
+                CORE_MODULES.contains(&module) || modules.iter().any(|enabled| enabled == module)
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct SchoolDatabaseInfo {
     pub tenant_id: Uuid,
     pub database_url: String,
+    pub migration_in_progress: bool,
+    pub plan: TenantPlan,
 }
 
 /// School info returned by the list endpoint, includes migration metadata
@@ -136,6 +182,13 @@ pub struct ActiveSchool {
     pub migration_error: Option<String>,
 }
 
+impl ActiveSchool {
+    /// Background jobs skip tenants whose schema a campaign wave is changing.
+    pub fn is_migrating(&self) -> bool {
+        self.migration_status.as_deref() == Some(TENANT_MIGRATING_STATUS)
+    }
+}
+
 #[derive(Deserialize)]
 struct ListSchoolsResponse {
     schools: Vec<ActiveSchool>,
@@ -148,6 +201,17 @@ struct UpdateMigrationStatusPayload {
     migration_error: Option<String>,
 }
 
+/// One request made during a support session, mirrored into the platform audit log.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportActivityReport {
+    pub subdomain: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub occurred_at: DateTime<Utc>,
+}
+
 impl AdminClient {
     pub fn new(base_url: String, secret: String, config: AdminClientConfig) -> Self {
         Self {
@@ -261,6 +325,9 @@ impl AdminClient {
         Ok(SchoolDatabaseInfo {
             tenant_id,
             database_url,
+            migration_in_progress: info.migration_status.as_deref()
+                == Some(TENANT_MIGRATING_STATUS),
+            plan: info.plan.unwrap_or_default(),
         })
     }
 
@@ -346,6 +413,41 @@ impl AdminClient {
         Ok(())
     }
 
+    /// Record a request made during a support session in backend-admin's audit log.
+    pub async fn report_support_activity(
+        &self,
+        support_access_id: Uuid,
+        activity: &SupportActivityReport,
+    ) -> Result<(), String> {
+        let url = format!(
+            "{}/internal/support-sessions/{}/activity",
+            self.base_url, support_access_id
+        );
+
+        let resp = self
+            .client
+            .post(&url)
+            .header(INTERNAL_SECRET_HEADER, &self.secret)
+            .header(INTERNAL_CALLER_HEADER, INTERNAL_CALLER)
+            .json(activity)
+            .timeout(self.config.request_timeout)
+            .send()
+            .await
+            .map_err(|error| {
+                if error.is_timeout() {
+                    "support activity report timed out".to_string()
+                } else {
+                    "support activity report could not reach backend-admin".to_string()
+                }
+            })?;
+
+        if !resp.status().is_success() {
+            return Err(format!("Admin service returned error: {}", resp.status()));
+        }
+
+        Ok(())
+    }
+
     pub async fn check_readiness(&self) -> Result<(), String> {
         let response = self
             .get_with_retry("/ready", "backend-admin readiness")
@@ -608,6 +710,53 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn tenant_database_info_reports_campaign_migrations() {
+        let router = Router::new()
+            .route(
+                "/internal/schools/migrating",
+                get(|| async {
+                    Json(json!({
+                        "id": "11111111-1111-1111-1111-111111111111",
+                        "db_connection_string": "postgres://tenant",
+                        "migration_status": "migrating"
+                    }))
+                }),
+            )
+            .route(
+                "/internal/schools/migrated",
+                get(|| async {
+                    Json(json!({
+                        "id": "11111111-1111-1111-1111-111111111111",
+                        "db_connection_string": "postgres://tenant",
+                        "migration_status": "migrated"
+                    }))
+                }),
+            );
+        let (base_url, server) = spawn_server(router).await;
+        let client = AdminClient::new(
+            base_url,
+            "test-secret".to_string(),
+            test_config(Duration::from_millis(100), 1),
+        );
+
+        assert!(
+            client
+                .get_school_database_info("migrating")
+                .await
+                .expect("school info must parse")
+                .migration_in_progress
+        );
+        assert!(
+            !client
+                .get_school_database_info("migrated")
+                .await
+                .expect("school info must parse")
+                .migration_in_progress
+        );
+        server.abort();
+    }
+
     #[tokio::test]
     async fn migration_status_put_is_never_retried() {
         let attempts = Arc::new(AtomicUsize::new(0));
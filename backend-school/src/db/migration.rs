@@ -7,7 +7,7 @@ use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 
-fn all_migrations_without_db_lock() -> Migrator {
+pub fn all_migrations_without_db_lock() -> Migrator {
     let base = sqlx::migrate!("./migrations");
     Migrator {
         migrations: Cow::Owned(base.iter().cloned().collect()),
@@ -30,21 +30,67 @@ pub async fn run_tenant_migrations(pool: &PgPool) -> Result<(), String> {
     Ok(())
 }
 
-/// Track which schools have been migrated and synced in this session
+/// Version and checksum of every tenant migration compiled into this build.
+/// Tenant snapshots record the same pairs so restores can refuse archives taken
+/// from a diverged migration history.
+pub fn tenant_migration_checksums() -> Vec<(i64, String)> {
+    all_migrations_without_db_lock()
+        .iter()
+        .map(|migration| (migration.version, hex::encode(&migration.checksum)))
+        .collect()
+}
+
+/// Applies tenant migrations up to and including `version`, without the
+/// permission sync that `run_tenant_migrations` performs afterwards.
+pub async fn run_tenant_migrations_through(pool: &PgPool, version: i64) -> Result<(), String> {
+    let base = all_migrations_without_db_lock();
+    let migrator = Migrator {
+        migrations: Cow::Owned(
+            base.iter()
+                .filter(|migration| migration.version <= version)
+                .cloned()
+                .collect(),
+        ),
+        ..base
+    };
+
+    migrator
+        .run(pool)
+        .await
+        .map_err(|error| format!("Migration to version {} failed: {}", version, error))
+}
+
+/// Newest tenant migration version compiled into this build.
+pub fn latest_tenant_migration_version() -> i64 {
+    all_migrations_without_db_lock()
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Version of the newest migration applied to a tenant database, 0 when none.
+pub async fn applied_tenant_migration_version(pool: &PgPool) -> Result<i64, String> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|error| format!("Failed to read applied migration version: {}", error))
+}
+
+/// Track which schools have had permissions synced in this session.
+/// Migrations are not tracked here: campaigns apply them explicitly.
 #[derive(Clone)]
 pub struct MigrationTracker {
-    migrated: Arc<RwLock<HashSet<String>>>,
     permissions_synced: Arc<RwLock<HashSet<String>>>,
-    migration_locks: Arc<DashMap<String, Arc<Mutex<()>>>>,
     permission_locks: Arc<DashMap<String, Arc<Mutex<()>>>>,
 }
 
 impl MigrationTracker {
     pub fn new() -> Self {
         Self {
-            migrated: Arc::new(RwLock::new(HashSet::new())),
             permissions_synced: Arc::new(RwLock::new(HashSet::new())),
-            migration_locks: Arc::new(DashMap::new()),
             permission_locks: Arc::new(DashMap::new()),
         }
     }
@@ -88,25 +134,6 @@ impl MigrationTracker {
         Ok(true)
     }
 
-    /// Run migrations for a school (once per session)
-    pub async fn run_migrations_once(
-        &self,
-        subdomain: &str,
-        pool: &PgPool,
-    ) -> Result<bool, String> {
-        self.run_once(subdomain, &self.migrated, &self.migration_locks, || async {
-            tracing::info!("🔄 Running migrations for school: {}", subdomain);
-
-            run_tenant_migrations(pool)
-                .await
-                .map_err(|e| format!("Migration failed for {}: {}", subdomain, e))?;
-
-            tracing::info!("✅ Migrations completed for: {}", subdomain);
-            Ok(())
-        })
-        .await
-    }
-
     /// Sync permissions for a school (once per session)
     pub async fn sync_permissions_once(
         &self,
@@ -130,18 +157,6 @@ impl MigrationTracker {
         )
         .await
     }
-
-    /// Get list of migrated schools
-    pub async fn get_migrated_schools(&self) -> Vec<String> {
-        let migrated = self.migrated.read().await;
-        migrated.iter().cloned().collect()
-    }
-
-    /// Get migration count
-    pub async fn migration_count(&self) -> usize {
-        let migrated = self.migrated.read().await;
-        migrated.len()
-    }
 }
 
 impl Default for MigrationTracker {
@@ -173,8 +188,8 @@ mod tests {
                 tracker
                     .run_once(
                         "sandbox",
-                        &tracker.migrated,
-                        &tracker.migration_locks,
+                        &tracker.permissions_synced,
+                        &tracker.permission_locks,
                         || {
                             let operation_count = operation_count.clone();
                             async move {
@@ -201,7 +216,7 @@ mod tests {
 
         assert_eq!(newly_run, 1);
         assert_eq!(operation_count.load(Ordering::SeqCst), 1);
-        assert_eq!(tracker.migration_count().await, 1);
+        assert_eq!(tracker.permissions_synced.read().await.len(), 1);
     }
 
     #[test]
@@ -217,6 +232,27 @@ mod tests {
         assert_eq!(versions, expected_versions);
     }
 
+    #[test]
+    fn latest_version_is_the_last_active_migration() {
+        assert_eq!(
+            latest_tenant_migration_version(),
+            all_migrations_without_db_lock().iter().count() as i64
+        );
+    }
+
+    #[test]
+    fn migration_checksums_follow_the_active_migrator() {
+        let checksums = tenant_migration_checksums();
+
+        assert_eq!(
+            checksums.len(),
+            all_migrations_without_db_lock().iter().count()
+        );
+        assert!(checksums
+            .iter()
+            .all(|(version, checksum)| *version > 0 && checksum.len() == 96));
+    }
+
     #[test]
     fn active_baseline_sql_is_clean_application_schema() {
         let baseline_sql = include_str!("../../migrations/001_baseline.sql");
@@ -1,4 +1,6 @@
+use super::admin_client::parse_bounded;
 use super::migration::MigrationTracker;
+use super::pool_metrics::{self, EvictionReason, PoolCounters, PoolSnapshot, TenantPoolSnapshot};
 use dashmap::DashMap;
 use sqlx::{
     postgres::{PgConnectOptions, PgPoolOptions},
@@ -11,9 +13,244 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock};
 
+const DEFAULT_MAX_CONNECTIONS_PER_SCHOOL: u32 = 5;
+const DEFAULT_CONNECTION_BUDGET: u32 = 100;
+const DEFAULT_POOL_TTL_SECS: u64 = 1800;
+
+/// How tenant connections reach PostgreSQL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantPoolMode {
+    /// Direct connections or a session-mode pooler; a connection keeps its
+    /// server session for its whole life.
+    Session,
+    /// A transaction-mode pooler such as PgBouncer. Each transaction may run on
+    /// a different server connection, so connections carry no session state.
+    /// A schema-per-tenant URL names its schema with `-c search_path=...`; the
+    /// pooler tracks that parameter and applies it to the server connection
+    /// that runs each transaction, and pool creation checks that it did.
+    Transaction,
+}
+
+impl TenantPoolMode {
+    fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(str::trim) {
+            None | Some("") | Some("session") => Ok(Self::Session),
+            Some("transaction") => Ok(Self::Transaction),
+            Some(_) => Err("TENANT_POOL_MODE must be session or transaction".to_string()),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Transaction => "transaction",
+        }
+    }
+}
+
+/// Sizing of the tenant pool cache for one replica
+#[derive(Debug, Clone)]
+pub struct TenantPoolConfig {
+    mode: TenantPoolMode,
+    max_connections_per_school: u32,
+    connection_budget: u32,
+    pool_ttl: Duration,
+}
+
+impl TenantPoolConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_values(
+            std::env::var("TENANT_POOL_MODE").ok().as_deref(),
+            std::env::var("TENANT_POOL_MAX_CONNECTIONS_PER_SCHOOL")
+                .ok()
+                .as_deref(),
+            std::env::var("TENANT_POOL_CONNECTION_BUDGET")
+                .ok()
+                .as_deref(),
+            std::env::var("TENANT_POOL_TTL_SECS").ok().as_deref(),
+        )
+    }
+
+    fn from_values(
+        mode: Option<&str>,
+        max_connections_per_school: Option<&str>,
+        connection_budget: Option<&str>,
+        pool_ttl_secs: Option<&str>,
+    ) -> Result<Self, String> {
+        let max_connections_per_school = parse_bounded(
+            "TENANT_POOL_MAX_CONNECTIONS_PER_SCHOOL",
+            max_connections_per_school,
+            DEFAULT_MAX_CONNECTIONS_PER_SCHOOL,
+            1,
+            50,
+        )?;
+        let connection_budget = parse_bounded(
+            "TENANT_POOL_CONNECTION_BUDGET",
+            connection_budget,
+            DEFAULT_CONNECTION_BUDGET,
+            1,
+            10_000,
+        )?;
+        if connection_budget < max_connections_per_school {
+            return Err(
+                "TENANT_POOL_CONNECTION_BUDGET must be at least TENANT_POOL_MAX_CONNECTIONS_PER_SCHOOL"
+                    .to_string(),
+            );
+        }
+
+        Ok(Self {
+            mode: TenantPoolMode::parse(mode)?,
+            max_connections_per_school,
+            connection_budget,
+            pool_ttl: Duration::from_secs(parse_bounded(
+                "TENANT_POOL_TTL_SECS",
+                pool_ttl_secs,
+                DEFAULT_POOL_TTL_SECS,
+                60,
+                86_400,
+            )?),
+        })
+    }
+
+    pub fn mode(&self) -> TenantPoolMode {
+        self.mode
+    }
+
+    /// Tenant pools that fit at the per-school maximum in what is left of the
+    /// budget after `still_open` connections of evicted pools
+    fn pool_capacity(&self, still_open: u32) -> usize {
+        (self.connection_budget.saturating_sub(still_open) / self.max_connections_per_school)
+            as usize
+    }
+
+    /// Schema a transaction-mode pool must find its transactions running in
+    fn pinned_schema(&self, options: &PgConnectOptions) -> Option<String> {
+        match self.mode {
+            TenantPoolMode::Session => None,
+            TenantPoolMode::Transaction => options
+                .get_options()
+                .and_then(|startup_options| startup_search_path_schema(startup_options).ok()),
+        }
+    }
+
+    fn connect_options(
+        &self,
+        database_url: &str,
+        subdomain: &str,
+    ) -> Result<PgConnectOptions, String> {
+        let options = PgConnectOptions::from_str(database_url)
+            .map_err(|error| format!("Invalid database configuration for {subdomain}: {error}"))?
+            .statement_cache_capacity(0);
+
+        match self.mode {
+            TenantPoolMode::Session => Ok(options),
+            TenantPoolMode::Transaction => {
+                // The pooler keeps only the startup parameters it tracks for each
+                // client and re-applies them per transaction; anything else in
+                // `options` would be rejected or silently lost.
+                if let Some(startup_options) = options.get_options() {
+                    startup_search_path_schema(startup_options).map_err(|error| {
+                        format!("Database configuration for {subdomain} {error}")
+                    })?;
+                }
+                Ok(options.extra_float_digits(None))
+            }
+        }
+    }
+
+    fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .min_connections(0)
+            .max_connections(self.max_connections_per_school)
+            .acquire_timeout(Duration::from_secs(20))
+            .idle_timeout(Duration::from_secs(300))
+            // Behind a transaction pooler the ping only reaches the pooler, so
+            // it costs a round trip without proving a server connection is healthy.
+            .test_before_acquire(self.mode == TenantPoolMode::Session)
+    }
+}
+
+impl Default for TenantPoolConfig {
+    fn default() -> Self {
+        Self {
+            mode: TenantPoolMode::Session,
+            max_connections_per_school: DEFAULT_MAX_CONNECTIONS_PER_SCHOOL,
+            connection_budget: DEFAULT_CONNECTION_BUDGET,
+            pool_ttl: Duration::from_secs(DEFAULT_POOL_TTL_SECS),
+        }
+    }
+}
+
+/// Tenant schema named by the startup `options` of a transaction-mode URL.
+/// Only `-c search_path=<schema>[,<schema>...]` is accepted: it is the one
+/// startup parameter the pooler tracks and re-applies to the server connection
+/// of every transaction. The first schema is the tenant's.
+fn startup_search_path_schema(options: &str) -> Result<String, String> {
+    let mut tokens = options.split_whitespace();
+    let mut search_path = None;
+    while let Some(token) = tokens.next() {
+        let setting = match token {
+            "-c" => tokens.next().unwrap_or_default(),
+            _ => token
+                .strip_prefix("--")
+                .or_else(|| token.strip_prefix("-c"))
+                .unwrap_or_default(),
+        };
+        match setting.split_once('=') {
+            Some((name, value))
+                if name.eq_ignore_ascii_case("search_path") && search_path.is_none() =>
+            {
+                search_path = Some(value);
+            }
+            _ => {
+                return Err(
+                    "sets startup options other than one search_path, which a transaction pooler does not keep"
+                        .to_string(),
+                )
+            }
+        }
+    }
+
+    let schemas: Vec<&str> = search_path
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .collect();
+    let valid = |schema: &&str| {
+        !schema.is_empty()
+            && schema
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+    };
+    if !schemas.iter().all(valid) {
+        return Err("sets a search_path that is not a list of plain schema names".to_string());
+    }
+    Ok(schemas[0].to_string())
+}
+
+/// Confirm the pooler applied the tenant's search_path: an autocommit
+/// statement, which the pooler runs as its own transaction, must resolve
+/// unqualified names in the tenant schema. A pooler that drops or does not
+/// track the parameter would otherwise send tenant queries to the wrong schema.
+async fn verify_pinned_schema(pool: &PgPool, schema: &str, subdomain: &str) -> Result<(), String> {
+    let current_schema: Option<String> = sqlx::query_scalar("SELECT current_schema()::text")
+        .fetch_one(pool)
+        .await
+        .map_err(|error| format!("Failed to check the search_path for {subdomain}: {error}"))?;
+
+    if current_schema.as_deref() == Some(schema) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Transaction pooler for {subdomain} did not apply the tenant search_path; it must track search_path, and the schema must exist"
+        ))
+    }
+}
+
 /// Pool cache entry
 struct PoolEntry {
     pool: PgPool,
+    subdomain: String,
     last_used: Instant,
 }
 
@@ -25,25 +262,47 @@ impl PoolEntry {
     fn touch(&mut self, now: Instant) {
         self.last_used = now;
     }
+
+    fn in_use(&self) -> usize {
+        (self.pool.size() as usize).saturating_sub(self.pool.num_idle())
+    }
+}
+
+/// Entry to give up when a new tenant needs room: the least recently used pool
+/// with no connection checked out. A busy pool is never evicted, because its
+/// connections would stay open next to the new tenant's.
+fn eviction_candidate(pools: &HashMap<String, PoolEntry>) -> Option<String> {
+    pools
+        .iter()
+        .filter(|(_, entry)| entry.in_use() == 0)
+        .min_by_key(|(_, entry)| entry.last_used)
+        .map(|(key, _)| key.clone())
 }
 
 /// Dynamic connection pool manager for multi-tenant
 pub struct PoolManager {
     pools: Arc<RwLock<HashMap<String, PoolEntry>>>,
     creation_locks: Arc<DashMap<String, Arc<Mutex<()>>>>,
+    /// Evicted pools until their last connection has closed
+    closing: Arc<Mutex<Vec<PgPool>>>,
     migration_tracker: MigrationTracker,
-    max_connections_per_school: u32,
-    pool_ttl: Duration,
+    config: TenantPoolConfig,
+    counters: PoolCounters,
 }
 
 impl PoolManager {
     pub fn new() -> Self {
+        Self::with_config(TenantPoolConfig::default())
+    }
+
+    pub fn with_config(config: TenantPoolConfig) -> Self {
         Self {
             pools: Arc::new(RwLock::new(HashMap::new())),
             creation_locks: Arc::new(DashMap::new()),
+            closing: Arc::new(Mutex::new(Vec::new())),
             migration_tracker: MigrationTracker::new(),
-            max_connections_per_school: 5,
-            pool_ttl: Duration::from_secs(1800), // 30 minutes TTL
+            config,
+            counters: PoolCounters::default(),
         }
     }
 
@@ -53,6 +312,7 @@ impl PoolManager {
             database_url.to_string(),
             PoolEntry {
                 pool,
+                subdomain: "test".to_string(),
                 last_used: Instant::now(),
             },
         );
@@ -61,18 +321,77 @@ impl PoolManager {
     async fn cached_pool_at(&self, key: &str, now: Instant) -> Option<PgPool> {
         let mut pools = self.pools.write().await;
         match pools.get_mut(key) {
-            Some(entry) if entry.is_fresh_at(now, self.pool_ttl) => {
+            Some(entry) if entry.is_fresh_at(now, self.config.pool_ttl) => {
                 entry.touch(now);
+                if entry.in_use() >= self.config.max_connections_per_school as usize {
+                    self.counters.record_saturated_checkout();
+                }
                 Some(entry.pool.clone())
             }
             Some(_) => {
                 pools.remove(key);
+                self.counters.record_eviction(EvictionReason::Expired);
                 None
             }
             None => None,
         }
     }
 
+    /// Evict least recently used idle tenants until one more pool fits in the
+    /// budget. Evicted pools are closed, and connections of earlier evictions
+    /// that are still open count against the budget until they close, so the
+    /// budget caps open connections rather than cached pools. Fails when only
+    /// busy pools are left to evict.
+    async fn make_room(
+        &self,
+        pools: &mut HashMap<String, PoolEntry>,
+        subdomain: &str,
+    ) -> Result<(), String> {
+        let mut closing = self.closing.lock().await;
+        closing.retain(|pool| pool.size() > 0);
+        let still_open = closing.iter().map(PgPool::size).sum();
+
+        while pools.len() >= self.config.pool_capacity(still_open) {
+            let Some(entry) = eviction_candidate(pools).and_then(|victim| pools.remove(&victim))
+            else {
+                return Err(format!(
+                    "Tenant connection budget is held by busy pools; cannot open a pool for {subdomain}"
+                ));
+            };
+            self.counters.record_eviction(EvictionReason::Budget);
+            tracing::info!(
+                subdomain = %entry.subdomain,
+                "Evicting least recently used tenant pool to stay within the connection budget"
+            );
+            closing.push(entry.pool.clone());
+            tokio::spawn(async move { entry.pool.close().await });
+        }
+
+        Ok(())
+    }
+
+    /// Cache a new pool once it fits in the budget.
+    async fn insert_within_budget(
+        &self,
+        key: &str,
+        subdomain: &str,
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let mut pools = self.pools.write().await;
+        pools.remove(key);
+        self.make_room(&mut pools, subdomain).await?;
+
+        pools.insert(
+            key.to_string(),
+            PoolEntry {
+                pool,
+                subdomain: subdomain.to_string(),
+                last_used: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
     async fn get_or_create_pool_with<F, Fut>(
         &self,
         database_url: &str,
@@ -88,6 +407,7 @@ impl PoolManager {
             return Ok(pool);
         }
 
+        let started = Instant::now();
         let creation_lock = self
             .creation_locks
             .entry(subdomain.to_string())
@@ -100,51 +420,72 @@ impl PoolManager {
                 subdomain,
                 "Using tenant pool created by a concurrent request"
             );
+            self.counters.record_creation_wait(started.elapsed());
             return Ok(pool);
         }
 
-        let pool = create_pool().await?;
-        self.pools.write().await.insert(
-            database_url.to_string(),
-            PoolEntry {
-                pool: pool.clone(),
-                last_used: Instant::now(),
-            },
-        );
+        // Free room before connecting, so a full budget fails fast instead of
+        // opening a connection it has to close again.
+        let room = self
+            .make_room(&mut *self.pools.write().await, subdomain)
+            .await;
+        if let Err(error) = room {
+            self.counters.record_creation_failure();
+            return Err(error);
+        }
+
+        let pool = match create_pool().await {
+            Ok(pool) => pool,
+            Err(error) => {
+                self.counters.record_creation_failure();
+                return Err(error);
+            }
+        };
+        // A concurrent creation for another tenant may have taken the room.
+        if let Err(error) = self
+            .insert_within_budget(database_url, subdomain, pool.clone())
+            .await
+        {
+            pool.close().await;
+            self.counters.record_creation_failure();
+            return Err(error);
+        }
+        self.counters.record_created();
+        self.counters.record_creation_wait(started.elapsed());
         Ok(pool)
     }
 
-    /// Get or create a connection pool for a specific school
-    /// Also runs migrations lazily on first connection
+    /// Get or create a connection pool for a specific school.
+    /// Schema changes are applied by migration campaigns, never on first use.
     pub async fn get_pool(&self, database_url: &str, subdomain: &str) -> Result<PgPool, String> {
         let pool = self
             .get_or_create_pool_with(database_url, subdomain, || async {
-                tracing::info!(subdomain, "Creating tenant database pool");
-                let connect_options = PgConnectOptions::from_str(database_url)
-                    .map_err(|error| {
-                        format!("Invalid database configuration for {subdomain}: {error}")
-                    })?
-                    .statement_cache_capacity(0);
-
-                PgPoolOptions::new()
-                    .min_connections(0)
-                    .max_connections(self.max_connections_per_school)
-                    .acquire_timeout(Duration::from_secs(20))
-                    .idle_timeout(Duration::from_secs(300))
-                    .test_before_acquire(true)
+                tracing::info!(
+                    subdomain,
+                    mode = self.config.mode.as_str(),
+                    "Creating tenant database pool"
+                );
+                let connect_options = self.config.connect_options(database_url, subdomain)?;
+                let pinned_schema = self.config.pinned_schema(&connect_options);
+
+                let pool = self
+                    .config
+                    .pool_options()
                     .connect_with(connect_options)
                     .await
                     .map_err(|error| {
                         format!("Failed to connect to database for {subdomain}: {error}")
-                    })
+                    })?;
+                if let Some(schema) = pinned_schema {
+                    if let Err(error) = verify_pinned_schema(&pool, &schema, subdomain).await {
+                        pool.close().await;
+                        return Err(error);
+                    }
+                }
+                Ok(pool)
             })
             .await?;
 
-        // Run migrations (lazy - only once per school per session)
-        self.migration_tracker
-            .run_migrations_once(subdomain, &pool)
-            .await?;
-
         // Sync permissions (lazy - only once per school per session)
         // This ensures existing schools get updated permissions after backend deploy
         self.migration_tracker
@@ -164,9 +505,10 @@ impl PoolManager {
         let now = Instant::now();
         let mut pools = self.pools.write().await;
         pools.retain(|_, entry| {
-            let expired = !entry.is_fresh_at(now, self.pool_ttl);
+            let expired = !entry.is_fresh_at(now, self.config.pool_ttl);
             if expired {
-                tracing::info!("🧹 Removing expired pool");
+                self.counters.record_eviction(EvictionReason::Expired);
+                tracing::info!(subdomain = %entry.subdomain, "🧹 Removing expired pool");
             }
             !expired
         });
@@ -176,6 +518,31 @@ impl PoolManager {
     pub async fn pool_count(&self) -> usize {
         self.pools.read().await.len()
     }
+
+    /// Pool sizes, waits and evictions in the Prometheus text format
+    pub async fn render_metrics(&self) -> String {
+        let pools = self
+            .pools
+            .read()
+            .await
+            .values()
+            .map(|entry| TenantPoolSnapshot {
+                subdomain: entry.subdomain.clone(),
+                size: entry.pool.size(),
+                idle: entry.pool.num_idle() as u32,
+                max_connections: self.config.max_connections_per_school,
+            })
+            .collect();
+
+        pool_metrics::render(
+            &self.counters,
+            &PoolSnapshot {
+                mode: self.config.mode.as_str(),
+                connection_budget: self.config.connection_budget,
+                pools,
+            },
+        )
+    }
 }
 
 impl Default for PoolManager {
@@ -186,7 +553,11 @@ impl Default for PoolManager {
 
 #[cfg(test)]
 mod tests {
-    use super::{PoolEntry, PoolManager};
+    use super::{
+        startup_search_path_schema, verify_pinned_schema, PoolEntry, PoolManager, TenantPoolConfig,
+        TenantPoolMode,
+    };
+    use crate::test_helpers::create_test_pool;
     use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
@@ -209,6 +580,7 @@ mod tests {
         let started = Instant::now();
         let mut entry = PoolEntry {
             pool: lazy_pool("touch_test"),
+            subdomain: "touch".to_string(),
             last_used: started,
         };
         let ttl = Duration::from_secs(30);
@@ -229,6 +601,7 @@ mod tests {
             database_url.to_string(),
             PoolEntry {
                 pool: lazy_pool("touch_cache"),
+                subdomain: "touch-cache".to_string(),
                 last_used: started,
             },
         );
@@ -257,7 +630,8 @@ mod tests {
             database_url.to_string(),
             PoolEntry {
                 pool: lazy_pool("expired_old"),
-                last_used: Instant::now() - manager.pool_ttl - Duration::from_secs(1),
+                subdomain: "expired".to_string(),
+                last_used: Instant::now() - manager.config.pool_ttl - Duration::from_secs(1),
             },
         );
         let creations = AtomicUsize::new(0);
@@ -354,4 +728,258 @@ mod tests {
         assert!(second.is_ok());
         assert_eq!(creations.load(Ordering::SeqCst), 2);
     }
+
+    #[test]
+    fn config_defaults_keep_session_pooling() {
+        let config = TenantPoolConfig::from_values(None, None, None, None).unwrap();
+
+        assert_eq!(config.mode(), TenantPoolMode::Session);
+        assert_eq!(config.max_connections_per_school, 5);
+        assert_eq!(config.connection_budget, 100);
+        assert_eq!(config.pool_ttl, Duration::from_secs(1800));
+        assert_eq!(config.pool_capacity(0), 20);
+    }
+
+    #[test]
+    fn config_rejects_unknown_modes_and_budgets_below_one_pool() {
+        assert!(TenantPoolConfig::from_values(Some("statement"), None, None, None).is_err());
+        assert!(TenantPoolConfig::from_values(None, Some("10"), Some("5"), None).is_err());
+        assert!(TenantPoolConfig::from_values(None, Some("0"), None, None).is_err());
+
+        let config =
+            TenantPoolConfig::from_values(Some("transaction"), Some("2"), Some("30"), Some("600"))
+                .unwrap();
+        assert_eq!(config.mode(), TenantPoolMode::Transaction);
+        assert_eq!(config.pool_capacity(0), 15);
+        assert_eq!(config.pool_capacity(5), 12);
+    }
+
+    #[test]
+    fn transaction_mode_keeps_only_a_tenant_search_path() {
+        let session = TenantPoolConfig::default();
+        let transaction =
+            TenantPoolConfig::from_values(Some("transaction"), None, None, None).unwrap();
+        let schema_url =
+            "postgres://app@pooler/schools?options=-c%20search_path%3Dtenant_a%2Cpublic";
+
+        let options = transaction.connect_options(schema_url, "alpha").unwrap();
+        assert!(options
+            .get_options()
+            .is_some_and(|options| options.contains("search_path=tenant_a,public")));
+        assert_eq!(
+            transaction.pinned_schema(&options).as_deref(),
+            Some("tenant_a")
+        );
+        assert_eq!(
+            session.pinned_schema(&session.connect_options(schema_url, "alpha").unwrap()),
+            None
+        );
+
+        let plain = transaction
+            .connect_options("postgres://app@pooler/tenant", "alpha")
+            .unwrap();
+        assert_eq!(transaction.pinned_schema(&plain), None);
+
+        for url in [
+            "postgres://app@pooler/tenant?options=-c%20statement_timeout%3D5s",
+            "postgres://app@pooler/tenant?options=-c%20search_path%3Da%20-c%20work_mem%3D4MB",
+            "postgres://app@pooler/tenant?options=-c%20search_path%3D%22a%22%3Bdrop",
+        ] {
+            assert!(session.connect_options(url, "alpha").is_ok());
+            let error = transaction
+                .connect_options(url, "alpha")
+                .expect_err("only a plain search_path may reach a transaction pooler");
+            assert!(error.contains("alpha"));
+            assert!(!error.contains("pooler/tenant"));
+        }
+    }
+
+    #[test]
+    fn startup_search_path_accepts_every_option_spelling() {
+        for options in [
+            "-c search_path=tenant_a",
+            "-csearch_path=tenant_a,public",
+            "--search_path=tenant_a",
+            "-c SEARCH_PATH=tenant_a",
+        ] {
+            assert_eq!(
+                startup_search_path_schema(options).as_deref(),
+                Ok("tenant_a"),
+                "{options}"
+            );
+        }
+        assert!(startup_search_path_schema("-c search_path=tenant_a, public").is_err());
+        assert!(startup_search_path_schema("-c search_path=").is_err());
+        assert!(startup_search_path_schema("-c").is_err());
+        assert!(startup_search_path_schema("search_path=tenant_a").is_err());
+    }
+
+    #[tokio::test]
+    async fn pinned_schema_check_fails_when_the_pooler_drops_search_path() {
+        let pool = create_test_pool().await;
+        let schema: String = sqlx::query_scalar("SELECT current_schema()::text")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        assert!(verify_pinned_schema(&pool, &schema, "alpha").await.is_ok());
+        let error = verify_pinned_schema(&pool, "tenant_missing", "alpha")
+            .await
+            .unwrap_err();
+        assert!(error.contains("alpha"));
+        assert!(error.contains("track search_path"));
+    }
+
+    #[tokio::test]
+    async fn new_tenant_evicts_the_least_recently_used_pool_over_budget() {
+        let manager = PoolManager::with_config(
+            TenantPoolConfig::from_values(None, Some("5"), Some("10"), None).unwrap(),
+        );
+        let now = Instant::now();
+        for (key, subdomain, age) in [
+            ("postgres://oldest", "oldest", 20),
+            ("postgres://recent", "recent", 5),
+        ] {
+            manager.pools.write().await.insert(
+                key.to_string(),
+                PoolEntry {
+                    pool: lazy_pool(subdomain),
+                    subdomain: subdomain.to_string(),
+                    last_used: now - Duration::from_secs(age),
+                },
+            );
+        }
+
+        let result = manager
+            .get_or_create_pool_with("postgres://newest", "newest", || async {
+                Ok(lazy_pool("newest"))
+            })
+            .await;
+
+        assert!(result.is_ok());
+        let pools = manager.pools.read().await;
+        assert_eq!(pools.len(), 2);
+        assert!(!pools.contains_key("postgres://oldest"));
+        assert!(pools.contains_key("postgres://recent"));
+        assert!(pools.contains_key("postgres://newest"));
+        drop(pools);
+        assert!(manager
+            .render_metrics()
+            .await
+            .contains("schoolorbit_tenant_pool_evictions_total{reason=\"budget\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn budget_eviction_skips_busy_pools_and_closes_the_evicted_one() {
+        let manager = PoolManager::with_config(
+            TenantPoolConfig::from_values(None, Some("5"), Some("10"), None).unwrap(),
+        );
+        let busy = create_test_pool().await;
+        let idle = create_test_pool().await;
+        let checked_out = busy.acquire().await.unwrap();
+        let now = Instant::now();
+        for (key, subdomain, pool, age) in [
+            ("postgres://busy", "busy", busy.clone(), 20),
+            ("postgres://idle", "idle", idle.clone(), 5),
+        ] {
+            manager.pools.write().await.insert(
+                key.to_string(),
+                PoolEntry {
+                    pool,
+                    subdomain: subdomain.to_string(),
+                    last_used: now - Duration::from_secs(age),
+                },
+            );
+        }
+
+        let result = manager
+            .get_or_create_pool_with("postgres://newest", "newest", || async {
+                Ok(lazy_pool("newest"))
+            })
+            .await;
+
+        assert!(result.is_ok());
+        let pools = manager.pools.read().await;
+        assert!(pools.contains_key("postgres://busy"));
+        assert!(!pools.contains_key("postgres://idle"));
+        drop(pools);
+        tokio::time::timeout(Duration::from_secs(5), idle.close_event())
+            .await
+            .expect("the evicted pool must be closed");
+        assert!(!busy.is_closed());
+        drop(checked_out);
+    }
+
+    #[tokio::test]
+    async fn full_budget_of_busy_pools_refuses_a_new_tenant() {
+        let manager = PoolManager::with_config(
+            TenantPoolConfig::from_values(None, Some("5"), Some("10"), None).unwrap(),
+        );
+        let first = create_test_pool().await;
+        let second = create_test_pool().await;
+        let _first_checkout = first.acquire().await.unwrap();
+        let _second_checkout = second.acquire().await.unwrap();
+        for (key, subdomain, pool) in [
+            ("postgres://first", "first", first.clone()),
+            ("postgres://second", "second", second.clone()),
+        ] {
+            manager.pools.write().await.insert(
+                key.to_string(),
+                PoolEntry {
+                    pool,
+                    subdomain: subdomain.to_string(),
+                    last_used: Instant::now(),
+                },
+            );
+        }
+        let creations = AtomicUsize::new(0);
+
+        let error = manager
+            .get_or_create_pool_with("postgres://newest", "newest", || async {
+                creations.fetch_add(1, Ordering::SeqCst);
+                Ok(lazy_pool("newest"))
+            })
+            .await
+            .expect_err("busy pools must not be evicted past the budget");
+
+        assert!(error.contains("newest"));
+        assert_eq!(creations.load(Ordering::SeqCst), 0);
+        assert_eq!(manager.pool_count().await, 2);
+        assert!(!first.is_closed() && !second.is_closed());
+        assert!(manager
+            .render_metrics()
+            .await
+            .contains("schoolorbit_tenant_pool_creation_failures_total 1\n"));
+    }
+
+    #[tokio::test]
+    async fn connections_of_a_closing_pool_count_against_the_budget() {
+        let manager = PoolManager::with_config(
+            TenantPoolConfig::from_values(None, Some("5"), Some("10"), None).unwrap(),
+        );
+        let closing = create_test_pool().await;
+        let still_held = closing.acquire().await.unwrap();
+        manager.closing.lock().await.push(closing.clone());
+        manager.pools.write().await.insert(
+            "postgres://cached".to_string(),
+            PoolEntry {
+                pool: lazy_pool("cached"),
+                subdomain: "cached".to_string(),
+                last_used: Instant::now(),
+            },
+        );
+
+        let result = manager
+            .get_or_create_pool_with("postgres://newest", "newest", || async {
+                Ok(lazy_pool("newest"))
+            })
+            .await;
+
+        assert!(result.is_ok());
+        let pools = manager.pools.read().await;
+        assert_eq!(pools.len(), 1);
+        assert!(pools.contains_key("postgres://newest"));
+        drop(pools);
+        drop(still_held);
+    }
 }
@@ -0,0 +1,295 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Prometheus text exposition content type served by `/internal/metrics`.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Why a tenant pool left the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// Unused for longer than the pool TTL
+    Expired,
+    /// Least recently used when a new tenant needed room in the connection budget
+    Budget,
+}
+
+/// Counters kept by `PoolManager` for the lifetime of the process
+#[derive(Default)]
+pub struct PoolCounters {
+    created: AtomicU64,
+    creation_failures: AtomicU64,
+    expired_evictions: AtomicU64,
+    budget_evictions: AtomicU64,
+    saturated_checkouts: AtomicU64,
+    creation_wait_micros: AtomicU64,
+    creation_waits: AtomicU64,
+}
+
+impl PoolCounters {
+    pub fn record_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_creation_failure(&self) {
+        self.creation_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_eviction(&self, reason: EvictionReason) {
+        match reason {
+            EvictionReason::Expired => &self.expired_evictions,
+            EvictionReason::Budget => &self.budget_evictions,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A cached pool was handed out with every connection checked out, so the
+    /// caller queues inside sqlx until one is released.
+    pub fn record_saturated_checkout(&self) {
+        self.saturated_checkouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Time a cache miss spent behind the tenant's creation lock and opening the pool
+    pub fn record_creation_wait(&self, waited: Duration) {
+        self.creation_wait_micros
+            .fetch_add(waited.as_micros() as u64, Ordering::Relaxed);
+        self.creation_waits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Point-in-time view of one cached tenant pool
+pub struct TenantPoolSnapshot {
+    pub subdomain: String,
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+}
+
+/// Point-in-time view of the whole cache
+pub struct PoolSnapshot {
+    pub mode: &'static str,
+    pub connection_budget: u32,
+    pub pools: Vec<TenantPoolSnapshot>,
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Render counters and a snapshot in the Prometheus text format.
+/// Labels carry the tenant subdomain only, never a connection string.
+pub fn render(counters: &PoolCounters, snapshot: &PoolSnapshot) -> String {
+    let mut out = String::new();
+    let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_info",
+        "gauge",
+        "Tenant pooling mode of this replica.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_info{{mode=\"{}\"}} 1",
+        snapshot.mode
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pools",
+        "gauge",
+        "Tenant pools currently cached.",
+    );
+    let _ = writeln!(out, "schoolorbit_tenant_pools {}", snapshot.pools.len());
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_connection_budget",
+        "gauge",
+        "Connections this replica may reserve across all tenant pools.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_connection_budget {}",
+        snapshot.connection_budget
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_reserved_connections",
+        "gauge",
+        "Connections reserved by cached tenant pools (sum of their maximum sizes).",
+    );
+    let reserved: u64 = snapshot
+        .pools
+        .iter()
+        .map(|pool| u64::from(pool.max_connections))
+        .sum();
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_reserved_connections {reserved}"
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_connections",
+        "gauge",
+        "Open connections per tenant pool by state.",
+    );
+    for pool in &snapshot.pools {
+        let tenant = escape_label(&pool.subdomain);
+        let in_use = pool.size.saturating_sub(pool.idle);
+        let _ = writeln!(
+            out,
+            "schoolorbit_tenant_pool_connections{{tenant=\"{tenant}\",state=\"idle\"}} {}",
+            pool.idle
+        );
+        let _ = writeln!(
+            out,
+            "schoolorbit_tenant_pool_connections{{tenant=\"{tenant}\",state=\"in_use\"}} {in_use}"
+        );
+    }
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_created_total",
+        "counter",
+        "Tenant pools opened.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_created_total {}",
+        load(&counters.created)
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_creation_failures_total",
+        "counter",
+        "Tenant pools that failed to open.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_creation_failures_total {}",
+        load(&counters.creation_failures)
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_evictions_total",
+        "counter",
+        "Tenant pools dropped from the cache by reason.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_evictions_total{{reason=\"expired\"}} {}",
+        load(&counters.expired_evictions)
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_evictions_total{{reason=\"budget\"}} {}",
+        load(&counters.budget_evictions)
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_waits_total",
+        "counter",
+        "Checkouts of a cached pool whose connections were all in use.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_waits_total {}",
+        load(&counters.saturated_checkouts)
+    );
+
+    header(
+        &mut out,
+        "schoolorbit_tenant_pool_creation_wait_seconds",
+        "summary",
+        "Time cache misses spent waiting for a tenant pool to open.",
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_creation_wait_seconds_sum {:.6}",
+        load(&counters.creation_wait_micros) as f64 / 1_000_000.0
+    );
+    let _ = writeln!(
+        out,
+        "schoolorbit_tenant_pool_creation_wait_seconds_count {}",
+        load(&counters.creation_waits)
+    );
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, EvictionReason, PoolCounters, PoolSnapshot, TenantPoolSnapshot};
+    use std::time::Duration;
+
+    #[test]
+    fn render_reports_per_tenant_connections_and_counters() {
+        let counters = PoolCounters::default();
+        counters.record_created();
+        counters.record_eviction(EvictionReason::Budget);
+        counters.record_eviction(EvictionReason::Budget);
+        counters.record_saturated_checkout();
+        counters.record_creation_wait(Duration::from_millis(1500));
+
+        let rendered = render(
+            &counters,
+            &PoolSnapshot {
+                mode: "transaction",
+                connection_budget: 20,
+                pools: vec![TenantPoolSnapshot {
+                    subdomain: "alpha".to_string(),
+                    size: 3,
+                    idle: 1,
+                    max_connections: 5,
+                }],
+            },
+        );
+
+        assert!(rendered.contains("schoolorbit_tenant_pool_info{mode=\"transaction\"} 1\n"));
+        assert!(rendered.contains("schoolorbit_tenant_pools 1\n"));
+        assert!(rendered.contains("schoolorbit_tenant_pool_reserved_connections 5\n"));
+        assert!(rendered.contains(
+            "schoolorbit_tenant_pool_connections{tenant=\"alpha\",state=\"in_use\"} 2\n"
+        ));
+        assert!(rendered.contains("schoolorbit_tenant_pool_evictions_total{reason=\"budget\"} 2\n"));
+        assert!(
+            rendered.contains("schoolorbit_tenant_pool_evictions_total{reason=\"expired\"} 0\n")
+        );
+        assert!(rendered.contains("schoolorbit_tenant_pool_waits_total 1\n"));
+        assert!(rendered.contains("schoolorbit_tenant_pool_creation_wait_seconds_sum 1.500000\n"));
+        assert!(rendered.contains("schoolorbit_tenant_pool_creation_wait_seconds_count 1\n"));
+    }
+
+    #[test]
+    fn tenant_labels_are_escaped() {
+        let rendered = render(
+            &PoolCounters::default(),
+            &PoolSnapshot {
+                mode: "session",
+                connection_budget: 5,
+                pools: vec![TenantPoolSnapshot {
+                    subdomain: "a\"b".to_string(),
+                    size: 0,
+                    idle: 0,
+                    max_connections: 5,
+                }],
+            },
+        );
+
+        assert!(rendered.contains("tenant=\"a\\\"b\""));
+    }
+}
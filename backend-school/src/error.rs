@@ -8,6 +8,7 @@ use crate::api_response::ApiErrorResponse;
 use crate::{
     api_response::ApiErrorResponseWithData,
     modules::certificates::models::{CertificateResourceLockCode, CertificateResourceLocked},
+    utils::quota::{QuotaCode, QuotaExceeded},
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -39,6 +40,9 @@ pub enum AppError {
     #[error("Certificate resource locked")]
     CertificateResourceLocked { request_id: Option<uuid::Uuid> },
 
+    #[error("Plan quota exceeded: {0:?}")]
+    QuotaExceeded(QuotaExceeded),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
@@ -78,6 +82,7 @@ impl AppError {
             AppError::Conflict(_) | AppError::CertificateResourceLocked { .. } => {
                 StatusCode::CONFLICT
             }
+            AppError::QuotaExceeded(_) => StatusCode::FORBIDDEN,
             AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
             AppError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
@@ -105,6 +110,13 @@ impl AppError {
             | AppError::BadRequest(message)
             | AppError::Conflict(message) => message,
             AppError::CertificateResourceLocked { .. } => "ทรัพยากรนี้อยู่ในคำขอออกเกียรติบัตรที่กำลังตรวจสอบ",
+            AppError::QuotaExceeded(quota) => match quota.code {
+                QuotaCode::StudentLimitReached => "จำนวนนักเรียนครบตามแพ็กเกจของโรงเรียนแล้ว",
+                QuotaCode::StorageLimitReached => "พื้นที่จัดเก็บไฟล์ครบตามแพ็กเกจของโรงเรียนแล้ว",
+                QuotaCode::CertificateLimitReached => {
+                    "จำนวนเกียรติบัตรในปีการศึกษานี้ครบตามแพ็กเกจของโรงเรียนแล้ว"
+                }
+            },
             AppError::InternalServerError(_) => "Internal server error",
             AppError::ConfigError(_) => "System configuration error",
             AppError::ServiceUnavailable(_) => "Service temporarily unavailable",
@@ -159,6 +171,14 @@ impl IntoResponse for AppError {
                 )),
             )
                 .into_response(),
+            AppError::QuotaExceeded(quota) => (
+                status,
+                Json(ApiErrorResponseWithData::new(
+                    self.public_message().to_string(),
+                    quota.clone(),
+                )),
+            )
+                .into_response(),
             _ => (
                 status,
                 Json(ApiErrorResponse::new(self.public_message().to_string())),
@@ -208,4 +228,21 @@ mod tests {
         assert_eq!(error.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
         assert_eq!(error.public_message(), "Request payload is too large");
     }
+
+    #[test]
+    fn quota_exceeded_uses_403_with_usage_data() {
+        let error = AppError::QuotaExceeded(QuotaExceeded {
+            code: QuotaCode::StudentLimitReached,
+            current: 500,
+            requested: 1,
+            limit: 500,
+        });
+
+        assert_eq!(error.status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            error.public_message(),
+            "จำนวนนักเรียนครบตามแพ็กเกจของโรงเรียนแล้ว"
+        );
+        assert_eq!(error.into_response().status(), StatusCode::FORBIDDEN);
+    }
 }
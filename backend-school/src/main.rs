@@ -19,7 +19,7 @@ use crate::modules::notification::events::{
 };
 use db::admin_client::{AdminClient, AdminClientConfig};
 use db::permission_cache::PermissionCache;
-use db::pool_manager::PoolManager;
+use db::pool_manager::{PoolManager, TenantPoolConfig};
 use dotenvy::dotenv;
 use std::{env, net::SocketAddr, sync::Arc};
 use tokio::sync::broadcast;
@@ -124,7 +124,15 @@ async fn main() {
     ));
     tracing::info!("✅ Admin client initialized (HTTP-based school mapping)");
 
-    let pool_manager = Arc::new(PoolManager::new());
+    let tenant_pool_config = match TenantPoolConfig::from_env() {
+        Ok(config) => config,
+        Err(error) => {
+            tracing::error!(error = %error, "Invalid tenant pool configuration");
+            std::process::exit(1);
+        }
+    };
+    let tenant_pool_mode = tenant_pool_config.mode();
+    let pool_manager = Arc::new(PoolManager::with_config(tenant_pool_config));
     let websocket_manager = Arc::new(modules::academic::websockets::WebSocketManager::new());
     websocket_manager.clone().spawn_cleanup_task();
 
@@ -141,7 +149,10 @@ async fn main() {
         }
     });
 
-    tracing::info!("✅ Pool manager initialized");
+    tracing::info!(
+        mode = tenant_pool_mode.as_str(),
+        "✅ Pool manager initialized"
+    );
     tracing::info!("ℹ️  Multi-tenant architecture ready");
     tracing::info!("ℹ️  Each school has its own database connection pool (cached)");
 
@@ -230,6 +241,11 @@ async fn main() {
     tracing::info!("  POST /internal/provision        - Provision tenant database");
     tracing::info!("  POST /internal/migrate-all      - Migrate all school databases");
     tracing::info!("  GET  /internal/migration-status - Get migration status");
+    tracing::info!("  POST /internal/migrations/tenant - Migrate one tenant for a campaign wave");
+    tracing::info!("  POST /internal/snapshots/export - Export tenant snapshot");
+    tracing::info!("  POST /internal/snapshots/restore - Restore tenant snapshot");
+    tracing::info!("  POST /internal/usage            - Collect tenant usage metrics");
+    tracing::info!("  GET  /internal/metrics          - Tenant pool metrics (Prometheus)");
     tracing::info!("  GET  /ws/timetable              - Real-time Timetable Collaboration");
 
     let listener = match tokio::net::TcpListener::bind(&addr).await {
@@ -266,6 +282,9 @@ async fn main() {
 
                 tracing::info!("Found {} active schools to reconcile.", schools.len());
                 for school in schools {
+                    if school.is_migrating() {
+                        continue;
+                    }
                     let db_url = match school.db_connection_string {
                         Some(ref url) if !url.is_empty() => url.clone(),
                         _ => {
@@ -321,8 +340,54 @@ async fn main() {
         })
         .expect("Failed to create calendar reminder job");
 
+    let admin_client_for_announcement_job = Arc::clone(&state.admin_client);
+    let pool_manager_for_announcement_job = Arc::clone(&state.pool_manager);
+    let notification_channel_for_announcement_job = state.notification_channel.clone();
+    let announcement_dispatch_job = scheduling::new_school_cron_job(
+        scheduling::ANNOUNCEMENT_DISPATCH_CRON,
+        move |_uuid, _l| {
+            let admin_client = Arc::clone(&admin_client_for_announcement_job);
+            let pool_manager = Arc::clone(&pool_manager_for_announcement_job);
+            let notification_channel = notification_channel_for_announcement_job.clone();
+
+            Box::pin(async move {
+                modules::announcement::services::process_due_announcements_for_all_tenants(
+                    admin_client,
+                    pool_manager,
+                    notification_channel,
+                )
+                .await;
+            })
+        },
+    )
+    .expect("Failed to create announcement dispatch job");
+
+    let admin_client_for_offer_job = Arc::clone(&state.admin_client);
+    let pool_manager_for_offer_job = Arc::clone(&state.pool_manager);
+    let notification_channel_for_offer_job = state.notification_channel.clone();
+    let admission_offer_expiry_job = scheduling::new_school_cron_job(
+        scheduling::ADMISSION_OFFER_EXPIRY_CRON,
+        move |_uuid, _l| {
+            let admin_client = Arc::clone(&admin_client_for_offer_job);
+            let pool_manager = Arc::clone(&pool_manager_for_offer_job);
+            let notification_channel = notification_channel_for_offer_job.clone();
+
+            Box::pin(async move {
+                modules::admission::services::offer_service::process_overdue_offers_for_all_tenants(
+                    admin_client,
+                    pool_manager,
+                    notification_channel,
+                )
+                .await;
+            })
+        },
+    )
+    .expect("Failed to create admission offer expiry job");
+
     let cleaner_job_id = cleaner_job.guid();
     let calendar_reminder_job_id = calendar_reminder_job.guid();
+    let announcement_dispatch_job_id = announcement_dispatch_job.guid();
+    let admission_offer_expiry_job_id = admission_offer_expiry_job.guid();
     sched
         .add(cleaner_job)
         .await
@@ -331,6 +396,14 @@ async fn main() {
         .add(calendar_reminder_job)
         .await
         .expect("Failed to add calendar reminder job");
+    sched
+        .add(announcement_dispatch_job)
+        .await
+        .expect("Failed to add announcement dispatch job");
+    sched
+        .add(admission_offer_expiry_job)
+        .await
+        .expect("Failed to add admission offer expiry job");
 
     let cleaner_next_run = scheduling::next_run_for_job(&mut sched, cleaner_job_id)
         .await
@@ -348,6 +421,24 @@ async fn main() {
         scheduling::CALENDAR_REMINDER_CRON,
         calendar_next_run,
     );
+    let announcement_next_run =
+        scheduling::next_run_for_job(&mut sched, announcement_dispatch_job_id)
+            .await
+            .expect("Failed to resolve next announcement dispatch");
+    scheduling::log_next_run(
+        "announcement_dispatch",
+        scheduling::ANNOUNCEMENT_DISPATCH_CRON,
+        announcement_next_run,
+    );
+    let admission_offer_next_run =
+        scheduling::next_run_for_job(&mut sched, admission_offer_expiry_job_id)
+            .await
+            .expect("Failed to resolve next admission offer expiry");
+    scheduling::log_next_run(
+        "admission_offer_expiry",
+        scheduling::ADMISSION_OFFER_EXPIRY_CRON,
+        admission_offer_next_run,
+    );
     sched.start().await.expect("Failed to start scheduler");
 
     axum::serve(
@@ -1,6 +1,8 @@
+use std::sync::Arc;
+
 use axum::{
     extract::{Request, State},
-    http::{HeaderName, Method},
+    http::{HeaderName, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -8,6 +10,7 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::{
+    db::admin_client::SupportActivityReport,
     error::AppError,
     modules::auth::{
         audit::{self, SessionFailureReason},
@@ -21,7 +24,11 @@ use crate::{
         session_repository::SessionMaintenanceMode,
         session_service,
     },
-    utils::{subdomain::parse_realtime_tenant_hint, tenant::resolve_auth_tenant_context},
+    modules::support_access::{models::SupportSessionScope, services as support_access},
+    utils::{
+        subdomain::parse_realtime_tenant_hint,
+        tenant::{resolve_auth_tenant_context, TenantContext},
+    },
 };
 
 pub async fn session_middleware(
@@ -74,10 +81,29 @@ async fn authenticate_request(
 
     let csrf_token = authentication.csrf_token;
     let replacement = authentication.replacement;
+    let user_id = authentication.authenticated.user_id;
+    let support = authentication.authenticated.support.clone();
     request
         .extensions_mut()
         .insert(authentication.authenticated);
-    let mut response = next.run(request).await;
+    let mut response = match &support {
+        Some(scope) if !support_access::request_allowed(scope, &method, &path) => {
+            support_request_forbidden().into_response()
+        }
+        _ => next.run(request).await,
+    };
+    if let Some(scope) = &support {
+        record_support_request(
+            runtime,
+            context.tenant(),
+            user_id,
+            scope,
+            &method,
+            &path,
+            response.status(),
+        )
+        .await;
+    }
 
     if let Some(replacement) = replacement {
         let encoded = replacement.encoded();
@@ -116,6 +142,57 @@ fn is_session_revoke_route(method: &Method, path: &str) -> bool {
             .is_some()
 }
 
+/// Every request of a support session lands in the school's audit log and,
+/// without holding up the response, in backend-admin's.
+async fn record_support_request(
+    runtime: &AuthRuntime,
+    tenant: &TenantContext,
+    user_id: Uuid,
+    scope: &SupportSessionScope,
+    method: &Method,
+    path: &str,
+    status: StatusCode,
+) {
+    if let Err(error) = support_access::record_support_request(
+        &tenant.pool,
+        user_id,
+        scope,
+        method,
+        path,
+        status.as_u16(),
+    )
+    .await
+    {
+        tracing::error!(
+            support_access_id = %scope.support_access_id,
+            error = %error,
+            "Failed to audit support session request"
+        );
+    }
+
+    let admin_client = Arc::clone(&runtime.admin_client);
+    let support_access_id = scope.support_access_id;
+    let activity = SupportActivityReport {
+        subdomain: tenant.subdomain.clone(),
+        method: method.to_string(),
+        path: path.to_string(),
+        status: status.as_u16(),
+        occurred_at: Utc::now(),
+    };
+    tokio::spawn(async move {
+        if let Err(error) = admin_client
+            .report_support_activity(support_access_id, &activity)
+            .await
+        {
+            tracing::warn!(
+                support_access_id = %support_access_id,
+                error = %error,
+                "Failed to report support session request to backend-admin"
+            );
+        }
+    });
+}
+
 fn is_unsafe_method(method: &Method) -> bool {
     matches!(
         *method,
@@ -140,6 +217,10 @@ fn audit_origin_rejection(error: AppError) -> AppError {
     error
 }
 
+fn support_request_forbidden() -> AppError {
+    AppError::Forbidden("เซสชันฝ่ายสนับสนุนไม่สามารถทำรายการนี้ได้".to_string())
+}
+
 fn authentication_required() -> AppError {
     AppError::AuthError("กรุณาเข้าสู่ระบบ".to_string())
 }
@@ -1,21 +1,28 @@
 pub mod academic;
 pub mod achievement;
 pub mod admission;
+pub mod announcement;
 pub mod auth;
+pub mod behavior;
 pub mod calendar;
 pub mod certificates;
 pub mod consent;
 pub mod facility;
 pub mod files;
+pub mod finance;
 pub mod lookup;
 pub mod menu;
 pub mod notification;
 pub mod parents;
+pub mod professional_development;
 pub mod question_bank;
 pub mod school;
 pub mod staff;
+pub mod staff_leave;
+pub mod student_leave;
 pub mod students;
 pub mod supervision;
+pub mod support_access;
 pub mod system;
 pub mod work;
 pub mod workflow;
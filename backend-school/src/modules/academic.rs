@@ -194,10 +194,22 @@ pub fn academic_routes() -> Router<AppState> {
             "/exam-schedules/sessions/{session_id}",
             delete(handlers::exam_schedule::delete_session),
         )
+        .route(
+            "/exam-schedules/{round_id}/auto-placement/preview",
+            post(handlers::exam_schedule::preview_auto_placement),
+        )
+        .route(
+            "/exam-schedules/{round_id}/auto-placement/apply",
+            post(handlers::exam_schedule::apply_auto_placement),
+        )
         .route(
             "/exam-schedules/{round_id}/invigilators",
             get(handlers::exam_schedule::get_invigilator_workspace),
         )
+        .route(
+            "/exam-schedules/{round_id}/invigilators/auto-assign",
+            post(handlers::exam_schedule::auto_assign_invigilators),
+        )
         .route(
             "/exam-schedules/{round_id}/invigilator-staff-options",
             get(handlers::exam_schedule::get_invigilator_staff_options),
@@ -211,6 +223,10 @@ pub fn academic_routes() -> Router<AppState> {
             "/exam-schedules/room-assignments/{assignment_id}/invigilators",
             put(handlers::exam_schedule::update_assignment_invigilators),
         )
+        .route(
+            "/exam-schedules/{round_id}/documents/{kind}",
+            get(handlers::exam_schedule::download_exam_day_document),
+        )
         .route(
             "/exam-schedules/{round_id}/publish",
             post(handlers::exam_schedule::publish_round),
@@ -313,6 +329,26 @@ pub fn academic_routes() -> Router<AppState> {
                 .put(handlers::study_plans::update_study_plan_version)
                 .delete(handlers::study_plans::delete_study_plan_version),
         )
+        // Study Plan Compliance
+        .route(
+            "/curriculum-rule-sets",
+            get(handlers::study_plans::list_curriculum_rule_sets)
+                .post(handlers::study_plans::create_curriculum_rule_set),
+        )
+        .route(
+            "/curriculum-rule-sets/{id}",
+            put(handlers::study_plans::update_curriculum_rule_set)
+                .delete(handlers::study_plans::delete_curriculum_rule_set),
+        )
+        .route(
+            "/study-plan-versions/{id}/compliance",
+            get(handlers::study_plans::get_study_plan_compliance_report),
+        )
+        .route(
+            "/study-plan-versions/{id}/compliance-override",
+            post(handlers::study_plans::record_study_plan_compliance_override)
+                .delete(handlers::study_plans::revoke_study_plan_compliance_override),
+        )
         // Study Plan Subjects
         .route(
             "/study-plan-versions/{id}/subjects",
@@ -444,6 +480,24 @@ pub fn academic_routes() -> Router<AppState> {
             "/activity-slots/{id}/classroom-assignments/{assignment_id}",
             axum::routing::delete(handlers::activity::delete_slot_classroom_assignment),
         )
+        .route(
+            "/activity-slots/{id}/my-preferences",
+            get(handlers::activity::get_my_activity_preferences)
+                .put(handlers::activity::submit_my_activity_preferences),
+        )
+        .route(
+            "/activity-slots/{id}/allocation-runs",
+            get(handlers::activity::list_activity_allocation_runs)
+                .post(handlers::activity::run_activity_allocation),
+        )
+        .route(
+            "/activity-allocation-runs/{id}",
+            get(handlers::activity::get_activity_allocation_run),
+        )
+        .route(
+            "/activity-allocation-runs/{id}/publish",
+            post(handlers::activity::publish_activity_allocation_run),
+        )
         // Activity Groups (กิจกรรมจริง ภายใต้ slot)
         .route(
             "/activities/my-enrollments",
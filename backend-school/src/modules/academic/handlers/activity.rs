@@ -10,7 +10,7 @@ use uuid::Uuid;
 use crate::api_response::{ApiErrorResponse, ApiResponse};
 use crate::error::AppError;
 use crate::modules::academic::models::activity::*;
-use crate::modules::academic::services::activity_service;
+use crate::modules::academic::services::{activity_allocation_service, activity_service};
 use crate::modules::auth::session_service::AuthenticatedSession;
 use crate::permissions::registry::codes;
 use crate::policies::activity_access_policy;
@@ -947,3 +947,185 @@ pub async fn delete_slot_classroom_assignment(
     activity_service::delete_slot_classroom_assignment(&pool, slot_id, assignment_id).await?;
     Ok(Json(ApiResponse::empty_with_message("ลบสำเร็จ")).into_response())
 }
+
+// ============================================
+// Preference Registration & Allocation
+// ============================================
+
+#[utoipa::path(
+    get,
+    path = "/api/academic/activity-slots/{id}/my-preferences",
+    operation_id = "getMyActivityPreferences",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Activity slot ID")),
+    responses(
+        (status = 200, description = "Current student's ranked choices for the slot", body = ApiResponse<MyActivityPreferences>),
+        (status = 400, description = "Activity slot is not in preference registration mode", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 404, description = "Activity slot not found", body = ApiErrorResponse),
+        (status = 500, description = "Preferences could not be loaded", body = ApiErrorResponse)
+    )
+)]
+pub async fn get_my_activity_preferences(
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(slot_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = current_user_tenant_context_from_session(&session);
+    let data = activity_allocation_service::get_my_preferences(
+        &context.tenant.pool,
+        slot_id,
+        context.user_id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/academic/activity-slots/{id}/my-preferences",
+    operation_id = "submitMyActivityPreferences",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Activity slot ID")),
+    request_body = SubmitActivityPreferencesRequest,
+    responses(
+        (status = 200, description = "Current student's ranked choices replaced", body = ApiResponse<MyActivityPreferences>),
+        (status = 400, description = "Registration is closed or a choice is invalid for the student", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 404, description = "Activity slot not found", body = ApiErrorResponse),
+        (status = 500, description = "Preferences could not be saved", body = ApiErrorResponse)
+    )
+)]
+pub async fn submit_my_activity_preferences(
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(slot_id): Path<Uuid>,
+    Json(body): Json<SubmitActivityPreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = current_user_tenant_context_from_session(&session);
+    let data = activity_allocation_service::submit_my_preferences(
+        &context.tenant.pool,
+        slot_id,
+        context.user_id,
+        body.group_ids,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/academic/activity-slots/{id}/allocation-runs",
+    operation_id = "listActivityAllocationRuns",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Activity slot ID")),
+    responses(
+        (status = 200, description = "Allocation runs for the slot, newest first", body = ApiResponse<Vec<ActivityAllocationRun>>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide activity management permission denied", body = ApiErrorResponse),
+        (status = 500, description = "Allocation runs could not be loaded", body = ApiErrorResponse)
+    )
+)]
+pub async fn list_activity_allocation_runs(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(slot_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACTIVITY_MANAGE_ALL)?;
+    let data = activity_allocation_service::list_runs(&pool, slot_id).await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/academic/activity-slots/{id}/allocation-runs",
+    operation_id = "runActivityAllocation",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Activity slot ID")),
+    request_body = RunActivityAllocationRequest,
+    responses(
+        (status = 200, description = "Draft allocation with placements and unmatched students", body = ApiResponse<ActivityAllocationRunDetail>),
+        (status = 400, description = "Activity slot is not in preference registration mode", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide activity management permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Activity slot not found", body = ApiErrorResponse),
+        (status = 409, description = "An allocation for the slot is already published", body = ApiErrorResponse),
+        (status = 500, description = "Allocation could not be run", body = ApiErrorResponse)
+    )
+)]
+pub async fn run_activity_allocation(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(slot_id): Path<Uuid>,
+    Json(body): Json<RunActivityAllocationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACTIVITY_MANAGE_ALL)?;
+    let data = activity_allocation_service::run_allocation(
+        &pool,
+        slot_id,
+        body.method,
+        body.seed,
+        actor.user_id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/academic/activity-allocation-runs/{id}",
+    operation_id = "getActivityAllocationRun",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Allocation run ID")),
+    responses(
+        (status = 200, description = "Allocation run with placements, unmatched students and group fill", body = ApiResponse<ActivityAllocationRunDetail>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide activity management permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Allocation run not found", body = ApiErrorResponse),
+        (status = 500, description = "Allocation run could not be loaded", body = ApiErrorResponse)
+    )
+)]
+pub async fn get_activity_allocation_run(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(run_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACTIVITY_MANAGE_ALL)?;
+    let data = activity_allocation_service::get_run(&pool, run_id).await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/academic/activity-allocation-runs/{id}/publish",
+    operation_id = "publishActivityAllocationRun",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Allocation run ID")),
+    responses(
+        (status = 200, description = "Placements enrolled and student registration closed", body = ApiResponse<ActivityAllocationRunDetail>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide activity management permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Allocation run not found", body = ApiErrorResponse),
+        (status = 409, description = "Run is not a draft, another run is published, or groups changed since the run", body = ApiErrorResponse),
+        (status = 500, description = "Allocation could not be published", body = ApiErrorResponse)
+    )
+)]
+pub async fn publish_activity_allocation_run(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(run_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACTIVITY_MANAGE_ALL)?;
+    let data = activity_allocation_service::publish_run(&pool, run_id, actor.user_id).await?;
+    Ok(Json(ApiResponse::ok(data)).into_response())
+}
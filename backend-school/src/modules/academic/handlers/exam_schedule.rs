@@ -1,9 +1,10 @@
 use crate::api_response::{ApiErrorResponse, ApiResponse};
 use crate::error::AppError;
 use crate::modules::academic::models::exam_schedule::{
-    CreateExamRoundRequest, GenerateSeatsRequest, ImportExamItemsRequest, PlaceExamSessionRequest,
-    UpdateExamInvigilatorsRequest, UpdateExamRoundRequest, UpsertDayRoomAssignmentRequest,
-    UpsertExamDayRequest,
+    ApplyExamAutoPlacementRequest, AutoAssignInvigilatorsRequest, CreateExamRoundRequest,
+    ExamDayDocumentKind, GenerateSeatsRequest, ImportExamItemsRequest, PlaceExamSessionRequest,
+    PreviewExamAutoPlacementRequest, UpdateExamInvigilatorsRequest, UpdateExamRoundRequest,
+    UpsertDayRoomAssignmentRequest, UpsertExamDayRequest,
 };
 use crate::modules::academic::services::exam_schedule_service;
 use crate::modules::auth::session_service::AuthenticatedSession;
@@ -14,7 +15,7 @@ use crate::utils::request_context::{
 use crate::AppState;
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -39,6 +40,14 @@ pub struct InvigilatorStaffOptionsQuery {
     pub limit: Option<i64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExamDayDocumentQuery {
+    /// Limits the document to one exam day; the whole round when omitted
+    pub exam_day_id: Option<Uuid>,
+    /// Columns in the seating-chart grid
+    pub seat_columns: Option<usize>,
+}
+
 /// GET /api/academic/exam-schedules
 pub async fn list_rounds(
     State(state): State<AppState>,
@@ -371,6 +380,91 @@ pub async fn delete_session(
     Ok(Json(ApiResponse::empty()).into_response())
 }
 
+/// POST /api/academic/exam-schedules/{round_id}/auto-placement/preview
+pub async fn preview_auto_placement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+    Json(payload): Json<PreviewExamAutoPlacementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACADEMIC_EXAM_SCHEDULE_MANAGE_SCHOOL)?;
+
+    let proposal = exam_schedule_service::preview_auto_placement(&pool, round_id, payload).await?;
+    Ok(Json(ApiResponse::ok(proposal)).into_response())
+}
+
+/// POST /api/academic/exam-schedules/{round_id}/auto-placement/apply
+pub async fn apply_auto_placement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+    Json(payload): Json<ApplyExamAutoPlacementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACADEMIC_EXAM_SCHEDULE_MANAGE_SCHOOL)?;
+
+    let result =
+        exam_schedule_service::apply_auto_placement(&pool, round_id, payload, actor.user_id)
+            .await?;
+    Ok(Json(ApiResponse::ok(result)).into_response())
+}
+
+/// POST /api/academic/exam-schedules/{round_id}/invigilators/auto-assign
+pub async fn auto_assign_invigilators(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+    Json(payload): Json<AutoAssignInvigilatorsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACADEMIC_EXAM_SCHEDULE_MANAGE_SCHOOL)?;
+
+    let result =
+        exam_schedule_service::auto_assign_invigilators(&pool, round_id, payload, actor.user_id)
+            .await?;
+    Ok(Json(ApiResponse::ok(result)).into_response())
+}
+
+/// GET /api/academic/exam-schedules/{round_id}/documents/{kind}
+pub async fn download_exam_day_document(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path((round_id, kind)): Path<(Uuid, ExamDayDocumentKind)>,
+    Query(query): Query<ExamDayDocumentQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ACADEMIC_EXAM_SCHEDULE_READ_SCHOOL)?;
+
+    let document = exam_schedule_service::render_exam_day_document(
+        &pool,
+        round_id,
+        kind,
+        query.exam_day_id,
+        query.seat_columns,
+    )
+    .await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", document.filename),
+            ),
+        ],
+        document.content,
+    )
+        .into_response())
+}
+
 /// POST /api/academic/exam-schedules/{round_id}/publish
 pub async fn publish_round(
     State(state): State<AppState>,
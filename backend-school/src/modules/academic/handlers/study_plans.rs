@@ -1,5 +1,6 @@
 use crate::api_response::{ApiErrorResponse, ApiResponse};
 use crate::error::AppError;
+use crate::modules::academic::services::study_plan_compliance_service;
 use crate::modules::academic::services::study_plan_service::{
     self, GenerateActivitiesFromPlanOutcome,
 };
@@ -280,7 +281,7 @@ pub async fn create_study_plan_version(
         (status = 401, description = "Authentication required", body = ApiErrorResponse),
         (status = 403, description = "Curriculum update permission denied", body = ApiErrorResponse),
         (status = 404, description = "Study-plan version not found", body = ApiErrorResponse),
-        (status = 409, description = "Study-plan version conflicts with an existing version", body = ApiErrorResponse),
+        (status = 409, description = "Study-plan version conflicts with an existing version, or activation is blocked by failing compliance checks", body = ApiErrorResponse),
         (status = 500, description = "Study-plan version could not be updated", body = ApiErrorResponse)
     )
 )]
@@ -325,6 +326,207 @@ pub async fn delete_study_plan_version(
     Ok((StatusCode::OK, Json(ApiResponse::empty())))
 }
 
+// ============================================
+// Curriculum Compliance
+// ============================================
+
+#[utoipa::path(
+    get,
+    path = "/api/academic/curriculum-rule-sets",
+    operation_id = "listCurriculumRuleSets",
+    tag = "academic",
+    responses(
+        (status = 200, description = "Curriculum rule sets with their rules", body = ApiResponse<Vec<CurriculumRuleSet>>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "Curriculum read permission denied", body = ApiErrorResponse),
+        (status = 500, description = "Curriculum rule sets could not be loaded", body = ApiErrorResponse)
+    )
+)]
+pub async fn list_curriculum_rule_sets(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_read(&actor)?;
+    let rule_sets = study_plan_compliance_service::list_rule_sets(&pool).await?;
+    Ok(Json(ApiResponse::ok(rule_sets)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/academic/curriculum-rule-sets",
+    operation_id = "createCurriculumRuleSet",
+    tag = "academic",
+    request_body = SaveCurriculumRuleSetRequest,
+    responses(
+        (status = 201, description = "Curriculum rule set created", body = ApiResponse<CurriculumRuleSet>),
+        (status = 400, description = "Invalid rule set or rule", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide curriculum update permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Referenced subject group not found", body = ApiErrorResponse),
+        (status = 500, description = "Curriculum rule set could not be created", body = ApiErrorResponse)
+    )
+)]
+pub async fn create_curriculum_rule_set(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(req): Json<SaveCurriculumRuleSetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_rule_set_manage(&actor)?;
+    let rule_set =
+        study_plan_compliance_service::create_rule_set(&pool, req, actor.user_id).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(rule_set))))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/academic/curriculum-rule-sets/{id}",
+    operation_id = "updateCurriculumRuleSet",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Curriculum rule set ID")),
+    request_body = SaveCurriculumRuleSetRequest,
+    responses(
+        (status = 200, description = "Curriculum rule set and its rules replaced", body = ApiResponse<CurriculumRuleSet>),
+        (status = 400, description = "Invalid rule set or rule", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide curriculum update permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Rule set or referenced subject group not found", body = ApiErrorResponse),
+        (status = 500, description = "Curriculum rule set could not be updated", body = ApiErrorResponse)
+    )
+)]
+pub async fn update_curriculum_rule_set(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(rule_set_id): Path<Uuid>,
+    Json(req): Json<SaveCurriculumRuleSetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_rule_set_manage(&actor)?;
+    let rule_set = study_plan_compliance_service::update_rule_set(&pool, rule_set_id, req).await?;
+    Ok(Json(ApiResponse::ok(rule_set)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/academic/curriculum-rule-sets/{id}",
+    operation_id = "deleteCurriculumRuleSet",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Curriculum rule set ID")),
+    responses(
+        (status = 200, description = "Curriculum rule set deleted", body = ApiResponse<crate::api_response::EmptyData>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "School-wide curriculum update permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Curriculum rule set not found", body = ApiErrorResponse),
+        (status = 500, description = "Curriculum rule set could not be deleted", body = ApiErrorResponse)
+    )
+)]
+pub async fn delete_curriculum_rule_set(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(rule_set_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_rule_set_manage(&actor)?;
+    study_plan_compliance_service::delete_rule_set(&pool, rule_set_id).await?;
+    Ok((StatusCode::OK, Json(ApiResponse::empty())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/academic/study-plan-versions/{id}/compliance",
+    operation_id = "getStudyPlanComplianceReport",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Study-plan version ID")),
+    responses(
+        (status = 200, description = "Compliance report against the pinned or default rule set", body = ApiResponse<StudyPlanComplianceReport>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "Curriculum read permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Study-plan version not found", body = ApiErrorResponse),
+        (status = 500, description = "Compliance report could not be built", body = ApiErrorResponse)
+    )
+)]
+pub async fn get_study_plan_compliance_report(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(version_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_read(&actor)?;
+    let report = study_plan_compliance_service::get_compliance_report(&pool, version_id).await?;
+    Ok(Json(ApiResponse::ok(report)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/academic/study-plan-versions/{id}/compliance-override",
+    operation_id = "recordStudyPlanComplianceOverride",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Study-plan version ID")),
+    request_body = RecordStudyPlanComplianceOverrideRequest,
+    responses(
+        (status = 201, description = "Override recorded for the currently failing checks", body = ApiResponse<StudyPlanComplianceReport>),
+        (status = 400, description = "Reason missing or nothing to override", body = ApiErrorResponse),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "Curriculum approve permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Study-plan version not found", body = ApiErrorResponse),
+        (status = 500, description = "Override could not be recorded", body = ApiErrorResponse)
+    )
+)]
+pub async fn record_study_plan_compliance_override(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(version_id): Path<Uuid>,
+    Json(req): Json<RecordStudyPlanComplianceOverrideRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_compliance_override(&actor)?;
+    let report =
+        study_plan_compliance_service::record_override(&pool, version_id, req, actor.user_id)
+            .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(report))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/academic/study-plan-versions/{id}/compliance-override",
+    operation_id = "revokeStudyPlanComplianceOverride",
+    tag = "academic",
+    params(("id" = Uuid, Path, description = "Study-plan version ID")),
+    responses(
+        (status = 200, description = "Override revoked; the refreshed report is returned", body = ApiResponse<StudyPlanComplianceReport>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "Curriculum approve permission denied", body = ApiErrorResponse),
+        (status = 404, description = "Version not found or no active override", body = ApiErrorResponse),
+        (status = 500, description = "Override could not be revoked", body = ApiErrorResponse)
+    )
+)]
+pub async fn revoke_study_plan_compliance_override(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(version_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    curriculum_access_policy::ensure_curriculum_compliance_override(&actor)?;
+    let report =
+        study_plan_compliance_service::revoke_override(&pool, version_id, actor.user_id).await?;
+    Ok(Json(ApiResponse::ok(report)))
+}
+
 // ============================================
 // Study Plan Subjects
 // ============================================
@@ -438,6 +640,7 @@ pub async fn delete_study_plan_subject(
         (status = 401, description = "Authentication required", body = ApiErrorResponse),
         (status = 403, description = "Course-plan management permission denied", body = ApiErrorResponse),
         (status = 404, description = "Classroom or semester not found", body = ApiErrorResponse),
+        (status = 409, description = "Study-plan version fails its compliance checks without an override", body = ApiErrorResponse),
         (status = 500, description = "Courses could not be generated", body = ApiErrorResponse)
     )
 )]
@@ -601,6 +804,7 @@ pub async fn delete_plan_activity(
         (status = 401, description = "Authentication required", body = ApiErrorResponse),
         (status = 403, description = "Curriculum read or school activity management permission denied", body = ApiErrorResponse),
         (status = 404, description = "Study-plan version or semester not found", body = ApiErrorResponse),
+        (status = 409, description = "Study-plan version fails its compliance checks without an override", body = ApiErrorResponse),
         (status = 500, description = "Activities could not be generated", body = ApiErrorResponse)
     )
 )]
@@ -16,6 +16,8 @@ pub enum ActivityRegistrationType {
     #[serde(rename = "self")]
     SelfRegistration,
     Assigned,
+    /// Students rank groups; an allocation run fills the groups
+    Preference,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
@@ -43,6 +45,8 @@ pub struct ActivitySlot {
     pub student_reg_open: bool,
     pub student_reg_start: Option<DateTime<Utc>>,
     pub student_reg_end: Option<DateTime<Utc>>,
+    /// Ranked choices allowed per student in preference registration
+    pub max_preferences: i32,
     pub created_by: Option<Uuid>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
@@ -88,6 +92,7 @@ pub struct UpdateActivitySlotRequest {
     pub student_reg_open: Option<bool>,
     pub student_reg_start: Option<String>,
     pub student_reg_end: Option<String>,
+    pub max_preferences: Option<i32>,
     pub is_active: Option<bool>,
 }
 
@@ -274,3 +279,156 @@ pub struct UpsertSlotClassroomAssignmentRequest {
 pub struct BatchUpsertSlotClassroomAssignmentsRequest {
     pub assignments: Vec<UpsertSlotClassroomAssignmentRequest>,
 }
+
+// ==========================================
+// Preference Registration & Allocation (ลงทะเบียนตามลำดับความสนใจ / จัดสรร)
+// ==========================================
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPreferenceChoice {
+    /// 1 = first choice
+    pub preference_rank: i32,
+    pub activity_group_id: Uuid,
+    pub group_name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MyActivityPreferences {
+    pub slot_id: Uuid,
+    /// Whether choices can be submitted or changed right now
+    pub registration_open: bool,
+    pub max_preferences: i32,
+    pub choices: Vec<ActivityPreferenceChoice>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub allocation_published: bool,
+    /// The group from the published allocation, if the student was placed
+    pub allocated_group_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitActivityPreferencesRequest {
+    /// Group IDs from first to last choice; an empty list withdraws
+    pub group_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAllocationMethod {
+    /// Students pick in a random lottery order
+    RandomSerialDictatorship,
+    /// Stable matching in which higher grades have priority; ties within a
+    /// grade are broken by lottery
+    GradePriorityStable,
+}
+
+impl ActivityAllocationMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RandomSerialDictatorship => "random_serial_dictatorship",
+            Self::GradePriorityStable => "grade_priority_stable",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunActivityAllocationRequest {
+    pub method: ActivityAllocationMethod,
+    /// Lottery seed; a random one is drawn when omitted. The same seed over
+    /// the same preferences reproduces the run
+    pub seed: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityUnmatchedReason {
+    /// The student submitted no choices
+    NoPreferences,
+    /// None of the chosen groups is open to the student's classroom
+    NoEligibleChoice,
+    /// Every eligible chosen group was full by the student's turn
+    ChoicesFull,
+}
+
+impl ActivityUnmatchedReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoPreferences => "no_preferences",
+            Self::NoEligibleChoice => "no_eligible_choice",
+            Self::ChoicesFull => "choices_full",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityAllocationRun {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    #[schema(value_type = ActivityAllocationMethod)]
+    pub method: String,
+    pub seed: i64,
+    /// draft | published | discarded
+    pub status: String,
+    /// Students taking part, excluding those already in a group of the slot
+    pub student_count: i32,
+    pub matched_count: i32,
+    pub unmatched_count: i32,
+    pub first_choice_count: i32,
+    pub already_enrolled_count: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub published_by: Option<Uuid>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityAllocationPlacement {
+    pub student_id: Uuid,
+    pub student_name: Option<String>,
+    pub student_code: Option<String>,
+    pub classroom_name: Option<String>,
+    pub activity_group_id: Uuid,
+    pub group_name: String,
+    pub preference_rank: i32,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityUnmatchedStudent {
+    pub student_id: Uuid,
+    pub student_name: Option<String>,
+    pub student_code: Option<String>,
+    pub classroom_name: Option<String>,
+    #[schema(value_type = ActivityUnmatchedReason)]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityAllocationGroupFill {
+    pub activity_group_id: Uuid,
+    pub group_name: String,
+    pub max_capacity: Option<i32>,
+    /// Members now; includes this run's placements once it is published
+    pub current_members: i64,
+    pub allocated: i64,
+    /// Students who ranked the group first
+    pub first_choice_demand: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityAllocationRunDetail {
+    #[serde(flatten)]
+    pub run: ActivityAllocationRun,
+    /// Matched students per choice rank; index 0 is the first choice
+    pub matched_by_rank: Vec<i64>,
+    pub groups: Vec<ActivityAllocationGroupFill>,
+    pub placements: Vec<ActivityAllocationPlacement>,
+    pub unmatched: Vec<ActivityUnmatchedStudent>,
+}
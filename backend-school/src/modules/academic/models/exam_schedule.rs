@@ -83,6 +83,9 @@ pub struct UpsertDayRoomAssignmentRequest {
     pub capacity_override: Option<i32>,
     #[serde(default)]
     pub invigilator_staff_ids: Option<Vec<Uuid>>,
+    /// Keeps the stored count when omitted
+    #[serde(default)]
+    pub required_invigilators: Option<i16>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,6 +126,7 @@ pub struct DayRoomAssignmentView {
     pub building_name: Option<String>,
     pub room_capacity: Option<i32>,
     pub capacity_override: Option<i32>,
+    pub required_invigilators: i16,
     #[sqlx(default)]
     pub invigilators: Vec<InvigilatorView>,
     pub seats_generated: bool,
@@ -133,6 +137,8 @@ pub struct DayRoomAssignmentView {
 pub struct InvigilatorView {
     pub staff_id: Uuid,
     pub display_name: String,
+    /// Chosen by auto-assign; hand-picked invigilators are pinned and kept on re-runs
+    pub assigned_automatically: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -145,6 +151,7 @@ pub struct ExamInvigilatorAssignmentSummary {
     pub room_id: Uuid,
     pub room_name: String,
     pub session_minutes: i32,
+    pub required_invigilators: i16,
     pub invigilators: Vec<InvigilatorView>,
 }
 
@@ -175,6 +182,90 @@ pub struct ExamInvigilatorWorkspace {
     pub staff_workloads: Vec<ExamInvigilatorStaffWorkload>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAssignInvigilatorsRequest {
+    /// Staff to draw from; every active staff user when omitted
+    #[serde(default)]
+    pub staff_ids: Option<Vec<Uuid>>,
+    /// Report the assignment without saving it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAssignInvigilatorsResult {
+    pub round_id: Uuid,
+    pub dry_run: bool,
+    pub assigned: Vec<AutoAssignedInvigilator>,
+    pub unfilled: Vec<UnfilledInvigilatorSlot>,
+    /// Total minutes per eligible staff member, pinned and automatic together
+    pub staff_totals: Vec<AutoAssignStaffTotal>,
+    pub min_total_minutes: i32,
+    pub max_total_minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAssignedInvigilator {
+    pub assignment_id: Uuid,
+    pub exam_day_id: Uuid,
+    pub classroom_name: String,
+    pub room_name: String,
+    pub staff_id: Uuid,
+    pub staff_name: String,
+    pub session_minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnfilledInvigilatorSlot {
+    pub assignment_id: Uuid,
+    pub exam_day_id: Uuid,
+    pub classroom_name: String,
+    pub room_name: String,
+    pub missing_count: i32,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAssignStaffTotal {
+    pub staff_id: Uuid,
+    pub staff_name: String,
+    pub total_minutes: i32,
+    pub assignment_count: i32,
+}
+
+/// Printable exam-day documents, named by their URL segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExamDayDocumentKind {
+    /// One grid-layout seating chart per room
+    SeatingCharts,
+    /// One student list per room, for the door
+    DoorLists,
+    /// One invigilator sign-in sheet per exam day
+    InvigilatorSignIn,
+    /// One attendance and signature sheet per room and session
+    AttendanceSheets,
+    /// One answer-envelope label per room and session
+    EnvelopeLabels,
+}
+
+impl ExamDayDocumentKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SeatingCharts => "seating-charts",
+            Self::DoorLists => "door-lists",
+            Self::InvigilatorSignIn => "invigilator-sign-in",
+            Self::AttendanceSheets => "attendance-sheets",
+            Self::EnvelopeLabels => "envelope-labels",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct ExamInvigilatorStaffOption {
@@ -206,6 +297,93 @@ pub struct PlaceExamSessionRequest {
     pub starts_at: NaiveTime,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewExamAutoPlacementRequest {
+    /// Days to fill; every day of the round when omitted
+    #[serde(default)]
+    pub exam_day_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamAutoPlacementProposal {
+    pub exam_round_id: Uuid,
+    pub placements: Vec<ProposedExamSession>,
+    pub unplaced_items: Vec<UnplacedExamItem>,
+    pub grade_day_loads: Vec<ExamGradeDayLoad>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposedExamSession {
+    pub exam_schedule_item_id: Uuid,
+    pub exam_day_id: Uuid,
+    pub exam_date: NaiveDate,
+    pub starts_at: NaiveTime,
+    pub ends_at: NaiveTime,
+    pub room_id: Uuid,
+    pub room_name: Option<String>,
+    /// The classroom has no room on this day yet; applying creates the assignment
+    pub creates_room_assignment: bool,
+    pub classroom_id: Uuid,
+    pub classroom_name: Option<String>,
+    pub grade_level_id: Uuid,
+    pub grade_level_name: Option<String>,
+    pub subject_code: Option<String>,
+    pub subject_name_th: Option<String>,
+    pub assessment_category_name: Option<String>,
+    pub duration_minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnplacedExamItem {
+    pub exam_schedule_item_id: Uuid,
+    pub classroom_id: Uuid,
+    pub classroom_name: Option<String>,
+    pub grade_level_name: Option<String>,
+    pub subject_code: Option<String>,
+    pub subject_name_th: Option<String>,
+    pub duration_minutes: i32,
+    pub reason: String,
+}
+
+/// Exam load of one grade level on one day, counting existing and proposed sessions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamGradeDayLoad {
+    pub grade_level_id: Uuid,
+    pub grade_level_name: Option<String>,
+    pub exam_day_id: Uuid,
+    pub exam_date: NaiveDate,
+    pub session_count: i32,
+    /// Longest total exam time any classroom of the grade sits that day
+    pub max_classroom_minutes: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyExamAutoPlacementRequest {
+    pub placements: Vec<ExamAutoPlacementInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamAutoPlacementInput {
+    pub exam_schedule_item_id: Uuid,
+    pub exam_day_id: Uuid,
+    pub starts_at: NaiveTime,
+    pub room_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamAutoPlacementResult {
+    pub placed_sessions: usize,
+    pub created_room_assignments: usize,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExamDayDetail {
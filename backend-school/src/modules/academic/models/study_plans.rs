@@ -3,7 +3,7 @@ use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::modules::academic::models::curriculum::CurriculumInstructorRole;
+use crate::modules::academic::models::curriculum::{CurriculumInstructorRole, SubjectType};
 use chrono::{DateTime, Utc};
 
 // ==========================================
@@ -55,6 +55,9 @@ pub struct StudyPlanVersion {
     pub end_academic_year_id: Option<Uuid>,
     pub description: Option<String>,
     pub is_active: bool,
+    /// ชุดเกณฑ์ที่ใช้ตรวจหลักสูตรฉบับนี้ — null = ใช้ชุดเกณฑ์ตั้งต้นของโรงเรียน
+    #[sqlx(default)]
+    pub compliance_rule_set_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
@@ -74,6 +77,7 @@ pub struct CreateStudyPlanVersionRequest {
     pub start_academic_year_id: Uuid,
     pub end_academic_year_id: Option<Uuid>,
     pub description: Option<String>,
+    pub compliance_rule_set_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -82,7 +86,10 @@ pub struct UpdateStudyPlanVersionRequest {
     pub start_academic_year_id: Option<Uuid>,
     pub end_academic_year_id: Option<Uuid>,
     pub description: Option<String>,
+    /// Activating a version requires it to pass its compliance checks or
+    /// carry an active override
     pub is_active: Option<bool>,
+    pub compliance_rule_set_id: Option<Uuid>,
 }
 
 // ==========================================
@@ -349,3 +356,195 @@ pub struct UpdateCatalogDefaultInstructorRoleRequest {
     #[schema(value_type = CurriculumInstructorRole)]
     pub role: String,
 }
+
+// ==========================================
+// Curriculum Compliance (ตรวจหลักสูตรตามโครงสร้างเวลาเรียน)
+// ==========================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CurriculumRuleKind {
+    /// Hours of subjects in one subject group (กลุ่มสาระ)
+    SubjectGroupHours,
+    /// Hours of plan activities (กิจกรรมพัฒนาผู้เรียน), optionally of one type
+    ActivityHours,
+    /// Total credits of plan subjects
+    CreditRange,
+}
+
+impl CurriculumRuleKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SubjectGroupHours => "subject_group_hours",
+            Self::ActivityHours => "activity_hours",
+            Self::CreditRange => "credit_range",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "subject_group_hours" => Some(Self::SubjectGroupHours),
+            "activity_hours" => Some(Self::ActivityHours),
+            "credit_range" => Some(Self::CreditRange),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CurriculumRuleScope {
+    /// Checked separately for every grade level in the range
+    PerGrade,
+    /// Checked once against the sum over all grade levels in the range
+    BandTotal,
+}
+
+impl CurriculumRuleScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PerGrade => "per_grade",
+            Self::BandTotal => "band_total",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "per_grade" => Some(Self::PerGrade),
+            "band_total" => Some(Self::BandTotal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CurriculumRuleUnit {
+    Hours,
+    Credits,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CurriculumRule {
+    pub id: Uuid,
+    #[schema(value_type = CurriculumRuleKind)]
+    pub rule_kind: String,
+    pub label: String,
+    /// kindergarten | primary | secondary
+    pub level_type: String,
+    pub grade_year_from: i32,
+    pub grade_year_to: i32,
+    #[schema(value_type = CurriculumRuleScope)]
+    pub scope: String,
+    pub subject_group_id: Option<Uuid>,
+    #[sqlx(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_group_name: Option<String>,
+    /// Restricts subject rules to one subject type, e.g. BASIC hours only
+    #[schema(value_type = Option<SubjectType>)]
+    pub subject_type: Option<String>,
+    /// Restricts activity rules to one catalog type; null = every activity
+    #[schema(value_type = Option<ActivityCatalogType>)]
+    pub activity_type: Option<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub display_order: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CurriculumRuleSet {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Converts activity periods per week into hours
+    pub weeks_per_term: i32,
+    /// Terms an activity pinned to "every term" runs in per grade level
+    pub terms_per_year: i32,
+    pub is_default: bool,
+    pub rules: Vec<CurriculumRule>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CurriculumRuleInput {
+    pub rule_kind: CurriculumRuleKind,
+    pub label: String,
+    /// kindergarten | primary | secondary
+    pub level_type: String,
+    pub grade_year_from: i32,
+    pub grade_year_to: i32,
+    pub scope: CurriculumRuleScope,
+    pub subject_group_id: Option<Uuid>,
+    #[schema(value_type = Option<SubjectType>)]
+    pub subject_type: Option<String>,
+    #[schema(value_type = Option<ActivityCatalogType>)]
+    pub activity_type: Option<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveCurriculumRuleSetRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub weeks_per_term: Option<i32>,
+    pub terms_per_year: Option<i32>,
+    /// Makes this the set used by versions without a pinned rule set
+    pub is_default: Option<bool>,
+    /// Replaces every rule of the set, in display order
+    pub rules: Vec<CurriculumRuleInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StudyPlanComplianceCheck {
+    /// Stable identity of the check: rule id, plus the grade level for
+    /// per-grade rules
+    pub key: String,
+    pub rule_id: Uuid,
+    pub rule_kind: CurriculumRuleKind,
+    pub label: String,
+    pub grade_level_id: Option<Uuid>,
+    /// e.g. "ม.1" or "ม.4-ม.6"
+    pub grade_label: String,
+    pub unit: CurriculumRuleUnit,
+    pub actual: f64,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub passed: bool,
+    /// Failed but covered by the active override
+    pub waived: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StudyPlanComplianceOverride {
+    pub id: Uuid,
+    pub rule_set_id: Option<Uuid>,
+    pub reason: String,
+    /// Failing checks as they stood when the override was recorded
+    pub waived_checks: Vec<StudyPlanComplianceCheck>,
+    pub created_by: Option<Uuid>,
+    pub created_by_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StudyPlanComplianceReport {
+    pub study_plan_version_id: Uuid,
+    /// null when the version has no pinned set and no default set exists
+    pub rule_set_id: Option<Uuid>,
+    pub rule_set_name: Option<String>,
+    /// Every check passed
+    pub compliant: bool,
+    /// Activation and course generation are refused: some failing check is
+    /// not covered by the active override
+    pub blocked: bool,
+    pub failed_count: i32,
+    pub checks: Vec<StudyPlanComplianceCheck>,
+    pub active_override: Option<StudyPlanComplianceOverride>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RecordStudyPlanComplianceOverrideRequest {
+    pub reason: String,
+}
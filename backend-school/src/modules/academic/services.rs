@@ -1,10 +1,12 @@
 pub mod academic_structure_service;
+pub mod activity_allocation_service;
 pub mod activity_service;
 pub mod assessment_service;
 pub mod course_planning_service;
 pub mod daily_teaching_service;
 pub mod exam_schedule_service;
 pub mod period_service;
+pub mod study_plan_compliance_service;
 pub mod study_plan_service;
 pub mod subject_service;
 pub mod timetable_realtime_service;
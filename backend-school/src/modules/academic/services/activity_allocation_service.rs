@@ -0,0 +1,1126 @@
+use crate::error::AppError;
+use crate::modules::academic::models::activity::{
+    ActivityAllocationGroupFill, ActivityAllocationMethod, ActivityAllocationPlacement,
+    ActivityAllocationRun, ActivityAllocationRunDetail, ActivityPreferenceChoice,
+    ActivityUnmatchedReason, ActivityUnmatchedStudent, MyActivityPreferences,
+};
+use chrono::{DateTime, Utc};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use sqlx::{types::Json, FromRow, PgPool, Postgres, Transaction};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+const PREFERENCE_REGISTRATION_TYPE: &str = "preference";
+
+fn db_error(context: &'static str) -> impl Fn(sqlx::Error) -> AppError {
+    move |e| {
+        tracing::error!("{context} error: {e}");
+        AppError::InternalServerError("เกิดข้อผิดพลาด".to_string())
+    }
+}
+
+// ============================================
+// Allocation (pure)
+// ============================================
+
+#[derive(Debug, Clone)]
+pub(crate) struct AllocationStudent {
+    pub student_id: Uuid,
+    pub classroom_id: Uuid,
+    /// Higher is a more senior grade
+    pub grade_order: i32,
+    /// Group IDs from first to last choice
+    pub choices: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AllocationGroup {
+    pub group_id: Uuid,
+    /// Seats left; `None` is unlimited
+    pub remaining: Option<usize>,
+    pub classroom_ids: HashSet<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AllocationPlacement {
+    pub student_id: Uuid,
+    pub group_id: Uuid,
+    /// 1-based rank of the group in the student's own list
+    pub preference_rank: i32,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct AllocationOutcome {
+    pub placements: Vec<AllocationPlacement>,
+    pub unmatched: Vec<(Uuid, ActivityUnmatchedReason)>,
+}
+
+/// Order in which students choose. Both methods start from a seeded lottery
+/// over the students sorted by ID, so a seed reproduces a run exactly.
+///
+/// With grade priority every group ranks students the same way (senior grade
+/// first, then lottery). When all groups share one priority list, the
+/// student-proposing deferred-acceptance matching is exactly serial
+/// dictatorship in that order, so both methods share the picking loop below.
+fn picking_order(
+    students: &[AllocationStudent],
+    method: ActivityAllocationMethod,
+    seed: i64,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..students.len()).collect();
+    order.sort_by_key(|&index| students[index].student_id);
+    order.shuffle(&mut StdRng::seed_from_u64(seed as u64));
+    if method == ActivityAllocationMethod::GradePriorityStable {
+        order.sort_by_key(|&index| std::cmp::Reverse(students[index].grade_order));
+    }
+    order
+}
+
+pub(crate) fn allocate(
+    students: &[AllocationStudent],
+    groups: &[AllocationGroup],
+    method: ActivityAllocationMethod,
+    seed: i64,
+) -> AllocationOutcome {
+    let mut remaining: HashMap<Uuid, (Option<usize>, &HashSet<Uuid>)> = groups
+        .iter()
+        .map(|group| (group.group_id, (group.remaining, &group.classroom_ids)))
+        .collect();
+    let mut outcome = AllocationOutcome::default();
+
+    for index in picking_order(students, method, seed) {
+        let student = &students[index];
+        if student.choices.is_empty() {
+            outcome
+                .unmatched
+                .push((student.student_id, ActivityUnmatchedReason::NoPreferences));
+            continue;
+        }
+
+        let mut any_eligible = false;
+        let mut placed = false;
+        for (position, group_id) in student.choices.iter().enumerate() {
+            let Some((seats, classroom_ids)) = remaining.get_mut(group_id) else {
+                continue;
+            };
+            if !classroom_ids.contains(&student.classroom_id) {
+                continue;
+            }
+            any_eligible = true;
+            if *seats == Some(0) {
+                continue;
+            }
+            if let Some(seats) = seats.as_mut() {
+                *seats -= 1;
+            }
+            outcome.placements.push(AllocationPlacement {
+                student_id: student.student_id,
+                group_id: *group_id,
+                preference_rank: position as i32 + 1,
+            });
+            placed = true;
+            break;
+        }
+
+        if !placed {
+            let reason = if any_eligible {
+                ActivityUnmatchedReason::ChoicesFull
+            } else {
+                ActivityUnmatchedReason::NoEligibleChoice
+            };
+            outcome.unmatched.push((student.student_id, reason));
+        }
+    }
+
+    outcome
+}
+
+/// Matched students per choice rank; index 0 counts first choices.
+fn matched_by_rank(ranks: &[i32]) -> Vec<i64> {
+    let longest = ranks.iter().copied().max().unwrap_or(0).max(0) as usize;
+    let mut counts = vec![0; longest];
+    for rank in ranks {
+        if *rank > 0 {
+            counts[*rank as usize - 1] += 1;
+        }
+    }
+    counts
+}
+
+// ============================================
+// Student preferences
+// ============================================
+
+fn registration_window_open(
+    student_reg_open: bool,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    student_reg_open && start.is_none_or(|start| now >= start) && end.is_none_or(|end| now <= end)
+}
+
+/// Rejects repeated groups and lists longer than the slot allows.
+fn validate_preference_choices(group_ids: &[Uuid], max_preferences: i32) -> Result<(), AppError> {
+    if group_ids.len() > max_preferences.max(0) as usize {
+        return Err(AppError::BadRequest(format!(
+            "เลือกได้ไม่เกิน {max_preferences} อันดับ"
+        )));
+    }
+    let mut seen = HashSet::with_capacity(group_ids.len());
+    if group_ids.iter().any(|group_id| !seen.insert(*group_id)) {
+        return Err(AppError::BadRequest("เลือกกลุ่มเดียวกันซ้ำไม่ได้".to_string()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, FromRow)]
+struct PreferenceSlotRow {
+    registration_type: String,
+    student_reg_open: bool,
+    student_reg_start: Option<DateTime<Utc>>,
+    student_reg_end: Option<DateTime<Utc>>,
+    max_preferences: i32,
+    published_run_id: Option<Uuid>,
+}
+
+async fn load_preference_slot<'e, E>(
+    executor: E,
+    slot_id: Uuid,
+    lock: &str,
+) -> Result<PreferenceSlotRow, AppError>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, PreferenceSlotRow>(&format!(
+        r#"SELECT s.registration_type, s.student_reg_open, s.student_reg_start,
+                  s.student_reg_end, s.max_preferences,
+                  (SELECT r.id FROM activity_allocation_runs r
+                   WHERE r.slot_id = s.id AND r.status = 'published') AS published_run_id
+           FROM activity_slots s
+           WHERE s.id = $1 AND s.is_active = true
+           {lock}"#
+    ))
+    .bind(slot_id)
+    .fetch_optional(executor)
+    .await
+    .map_err(db_error("load_preference_slot"))?
+    .ok_or_else(|| AppError::NotFound("ไม่พบช่องกิจกรรม".to_string()))
+}
+
+fn ensure_preference_slot(slot: &PreferenceSlotRow) -> Result<(), AppError> {
+    if slot.registration_type == PREFERENCE_REGISTRATION_TYPE {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(
+            "ช่องกิจกรรมนี้ไม่ได้ลงทะเบียนแบบจัดลำดับความสนใจ".to_string(),
+        ))
+    }
+}
+
+async fn active_classroom_id(pool: &PgPool, student_id: Uuid) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar(
+        r#"SELECT sce.class_room_id FROM student_class_enrollments sce
+           WHERE sce.student_id = $1 AND sce.status = 'active'
+           ORDER BY sce.enrollment_date DESC
+           LIMIT 1"#,
+    )
+    .bind(student_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error("active_classroom_id"))
+}
+
+pub async fn get_my_preferences(
+    pool: &PgPool,
+    slot_id: Uuid,
+    student_id: Uuid,
+) -> Result<MyActivityPreferences, AppError> {
+    let slot = load_preference_slot(pool, slot_id, "").await?;
+    ensure_preference_slot(&slot)?;
+
+    let choices: Vec<ActivityPreferenceChoice> = sqlx::query_as(
+        r#"SELECT ap.preference_rank, ap.activity_group_id, ag.name AS group_name
+           FROM activity_preferences ap
+           JOIN activity_groups ag ON ag.id = ap.activity_group_id
+           WHERE ap.slot_id = $1 AND ap.student_id = $2
+           ORDER BY ap.preference_rank"#,
+    )
+    .bind(slot_id)
+    .bind(student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error("get_my_activity_preferences"))?;
+
+    let submitted_at: Option<DateTime<Utc>> = sqlx::query_scalar(
+        "SELECT MAX(submitted_at) FROM activity_preferences WHERE slot_id = $1 AND student_id = $2",
+    )
+    .bind(slot_id)
+    .bind(student_id)
+    .fetch_one(pool)
+    .await
+    .map_err(db_error("get_my_activity_preferences"))?;
+
+    let allocated_group_id = match slot.published_run_id {
+        Some(run_id) => sqlx::query_scalar(
+            "SELECT activity_group_id FROM activity_allocation_results WHERE run_id = $1 AND student_id = $2",
+        )
+        .bind(run_id)
+        .bind(student_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(db_error("get_my_activity_preferences"))?
+        .flatten(),
+        None => None,
+    };
+
+    Ok(MyActivityPreferences {
+        slot_id,
+        registration_open: slot.published_run_id.is_none()
+            && registration_window_open(
+                slot.student_reg_open,
+                slot.student_reg_start,
+                slot.student_reg_end,
+                Utc::now(),
+            ),
+        max_preferences: slot.max_preferences,
+        choices,
+        submitted_at,
+        allocation_published: slot.published_run_id.is_some(),
+        allocated_group_id,
+    })
+}
+
+#[derive(Debug, FromRow)]
+struct PreferenceGroupRow {
+    name: String,
+    accepts_classroom: bool,
+}
+
+async fn bulk_insert_activity_preferences(
+    tx: &mut Transaction<'_, Postgres>,
+    slot_id: Uuid,
+    student_id: Uuid,
+    group_ids: &[Uuid],
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"INSERT INTO activity_preferences (slot_id, student_id, activity_group_id, preference_rank)
+           SELECT $1, $2, rows.group_id, rows.preference_rank::int
+           FROM UNNEST($3::uuid[]) WITH ORDINALITY AS rows(group_id, preference_rank)"#,
+    )
+    .bind(slot_id)
+    .bind(student_id)
+    .bind(group_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(db_error("bulk_insert_activity_preferences"))?;
+    Ok(())
+}
+
+/// Replaces the student's ranked list for the slot while registration is open.
+pub async fn submit_my_preferences(
+    pool: &PgPool,
+    slot_id: Uuid,
+    student_id: Uuid,
+    group_ids: Vec<Uuid>,
+) -> Result<MyActivityPreferences, AppError> {
+    let slot = load_preference_slot(pool, slot_id, "").await?;
+    ensure_preference_slot(&slot)?;
+    if slot.published_run_id.is_some() {
+        return Err(AppError::BadRequest(
+            "ประกาศผลการจัดสรรแล้ว ไม่สามารถแก้ไขลำดับได้".to_string(),
+        ));
+    }
+    if !registration_window_open(
+        slot.student_reg_open,
+        slot.student_reg_start,
+        slot.student_reg_end,
+        Utc::now(),
+    ) {
+        return Err(AppError::BadRequest("ยังไม่เปิดรับสมัคร".to_string()));
+    }
+    validate_preference_choices(&group_ids, slot.max_preferences)?;
+
+    if !group_ids.is_empty() {
+        let classroom_id = active_classroom_id(pool, student_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("ไม่พบการลงทะเบียนห้องเรียนที่ใช้งานอยู่".to_string()))?;
+        let groups: Vec<PreferenceGroupRow> = sqlx::query_as(
+            r#"SELECT ag.name,
+                      CASE
+                          WHEN ag.allowed_classroom_ids IS NOT NULL
+                            THEN ag.allowed_classroom_ids ? $3::text
+                          ELSE EXISTS(
+                            SELECT 1 FROM activity_slot_classrooms asc2
+                            WHERE asc2.slot_id = ag.slot_id AND asc2.classroom_id = $3
+                          )
+                      END AS accepts_classroom
+               FROM activity_groups ag
+               WHERE ag.id = ANY($1) AND ag.slot_id = $2
+                 AND ag.is_active = true AND ag.registration_open = true"#,
+        )
+        .bind(&group_ids)
+        .bind(slot_id)
+        .bind(classroom_id)
+        .fetch_all(pool)
+        .await
+        .map_err(db_error("submit_activity_preferences"))?;
+
+        if groups.len() != group_ids.len() {
+            return Err(AppError::BadRequest(
+                "มีกลุ่มที่ไม่อยู่ในช่องกิจกรรมนี้หรือไม่เปิดรับสมัคร".to_string(),
+            ));
+        }
+        if let Some(group) = groups.iter().find(|group| !group.accepts_classroom) {
+            return Err(AppError::BadRequest(format!(
+                "กลุ่ม {} ไม่รับห้องเรียนของคุณ",
+                group.name
+            )));
+        }
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(db_error("submit_activity_preferences"))?;
+    // Shares the slot lock with other students; publishing takes it exclusively.
+    let locked = load_preference_slot(&mut *tx, slot_id, "FOR SHARE OF s").await?;
+    if locked.published_run_id.is_some() {
+        return Err(AppError::BadRequest(
+            "ประกาศผลการจัดสรรแล้ว ไม่สามารถแก้ไขลำดับได้".to_string(),
+        ));
+    }
+    sqlx::query("DELETE FROM activity_preferences WHERE slot_id = $1 AND student_id = $2")
+        .bind(slot_id)
+        .bind(student_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error("submit_activity_preferences"))?;
+    bulk_insert_activity_preferences(&mut tx, slot_id, student_id, &group_ids).await?;
+    tx.commit()
+        .await
+        .map_err(db_error("submit_activity_preferences"))?;
+
+    get_my_preferences(pool, slot_id, student_id).await
+}
+
+// ============================================
+// Allocation runs
+// ============================================
+
+#[derive(Debug, FromRow)]
+struct AllocationGroupRow {
+    id: Uuid,
+    max_capacity: Option<i32>,
+    allowed_classroom_ids: Option<Json<Vec<Uuid>>>,
+    member_count: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct AllocationStudentRow {
+    student_id: Uuid,
+    classroom_id: Uuid,
+    grade_order: i32,
+}
+
+struct AllocationInput {
+    students: Vec<AllocationStudent>,
+    groups: Vec<AllocationGroup>,
+    already_enrolled_count: usize,
+}
+
+/// Students in the slot's classrooms (and anyone who ranked its groups), minus
+/// those already in one of its groups, with each group's remaining seats.
+async fn load_allocation_input(
+    tx: &mut Transaction<'_, Postgres>,
+    slot_id: Uuid,
+) -> Result<AllocationInput, AppError> {
+    let slot_classroom_ids: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+        "SELECT classroom_id FROM activity_slot_classrooms WHERE slot_id = $1",
+    )
+    .bind(slot_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error("load_allocation_input"))?
+    .into_iter()
+    .collect();
+
+    let group_rows: Vec<AllocationGroupRow> = sqlx::query_as(
+        r#"SELECT ag.id, ag.max_capacity, ag.allowed_classroom_ids,
+                  (SELECT COUNT(*) FROM activity_group_members agm
+                   WHERE agm.activity_group_id = ag.id) AS member_count
+           FROM activity_groups ag
+           WHERE ag.slot_id = $1 AND ag.is_active = true AND ag.registration_open = true"#,
+    )
+    .bind(slot_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error("load_allocation_input"))?;
+    let groups = group_rows
+        .into_iter()
+        .map(|row| AllocationGroup {
+            group_id: row.id,
+            remaining: row
+                .max_capacity
+                .map(|capacity| (i64::from(capacity) - row.member_count).max(0) as usize),
+            classroom_ids: match row.allowed_classroom_ids {
+                Some(Json(ids)) => ids.into_iter().collect(),
+                None => slot_classroom_ids.clone(),
+            },
+        })
+        .collect();
+
+    let student_rows: Vec<AllocationStudentRow> = sqlx::query_as(
+        r#"SELECT DISTINCT ON (sce.student_id)
+                  sce.student_id, sce.class_room_id AS classroom_id,
+                  (CASE gl.level_type
+                       WHEN 'kindergarten' THEN 1
+                       WHEN 'primary' THEN 2
+                       WHEN 'secondary' THEN 3
+                       ELSE 4
+                   END) * 100 + gl.year AS grade_order
+           FROM student_class_enrollments sce
+           JOIN class_rooms cr ON cr.id = sce.class_room_id
+           JOIN grade_levels gl ON gl.id = cr.grade_level_id
+           WHERE sce.status = 'active'
+             AND (
+                 sce.class_room_id IN (
+                     SELECT classroom_id FROM activity_slot_classrooms WHERE slot_id = $1
+                 )
+                 OR sce.student_id IN (
+                     SELECT student_id FROM activity_preferences WHERE slot_id = $1
+                 )
+             )
+           ORDER BY sce.student_id, sce.enrollment_date DESC"#,
+    )
+    .bind(slot_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error("load_allocation_input"))?;
+
+    let enrolled: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+        r#"SELECT DISTINCT agm.student_id
+           FROM activity_group_members agm
+           JOIN activity_groups ag ON ag.id = agm.activity_group_id
+           WHERE ag.slot_id = $1"#,
+    )
+    .bind(slot_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error("load_allocation_input"))?
+    .into_iter()
+    .collect();
+
+    let mut choices: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let preference_rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"SELECT student_id, activity_group_id FROM activity_preferences
+           WHERE slot_id = $1
+           ORDER BY student_id, preference_rank"#,
+    )
+    .bind(slot_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(db_error("load_allocation_input"))?;
+    for (student_id, group_id) in preference_rows {
+        choices.entry(student_id).or_default().push(group_id);
+    }
+
+    let already_enrolled_count = student_rows
+        .iter()
+        .filter(|row| enrolled.contains(&row.student_id))
+        .count();
+    let students = student_rows
+        .into_iter()
+        .filter(|row| !enrolled.contains(&row.student_id))
+        .map(|row| AllocationStudent {
+            student_id: row.student_id,
+            classroom_id: row.classroom_id,
+            grade_order: row.grade_order,
+            choices: choices.remove(&row.student_id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(AllocationInput {
+        students,
+        groups,
+        already_enrolled_count,
+    })
+}
+
+async fn bulk_insert_allocation_results(
+    tx: &mut Transaction<'_, Postgres>,
+    run_id: Uuid,
+    outcome: &AllocationOutcome,
+) -> Result<(), AppError> {
+    let placed_students: Vec<Uuid> = outcome.placements.iter().map(|p| p.student_id).collect();
+    let placed_groups: Vec<Uuid> = outcome.placements.iter().map(|p| p.group_id).collect();
+    let placed_ranks: Vec<i32> = outcome
+        .placements
+        .iter()
+        .map(|p| p.preference_rank)
+        .collect();
+    sqlx::query(
+        r#"INSERT INTO activity_allocation_results
+               (run_id, student_id, activity_group_id, preference_rank)
+           SELECT $1, rows.student_id, rows.group_id, rows.preference_rank
+           FROM UNNEST($2::uuid[], $3::uuid[], $4::int[])
+               AS rows(student_id, group_id, preference_rank)"#,
+    )
+    .bind(run_id)
+    .bind(&placed_students)
+    .bind(&placed_groups)
+    .bind(&placed_ranks)
+    .execute(&mut **tx)
+    .await
+    .map_err(db_error("bulk_insert_allocation_results"))?;
+
+    let unmatched_students: Vec<Uuid> = outcome.unmatched.iter().map(|(id, _)| *id).collect();
+    let unmatched_reasons: Vec<&str> = outcome
+        .unmatched
+        .iter()
+        .map(|(_, reason)| reason.as_str())
+        .collect();
+    sqlx::query(
+        r#"INSERT INTO activity_allocation_results (run_id, student_id, unmatched_reason)
+           SELECT $1, rows.student_id, rows.reason
+           FROM UNNEST($2::uuid[], $3::text[]) AS rows(student_id, reason)"#,
+    )
+    .bind(run_id)
+    .bind(&unmatched_students)
+    .bind(&unmatched_reasons)
+    .execute(&mut **tx)
+    .await
+    .map_err(db_error("bulk_insert_allocation_results"))?;
+
+    Ok(())
+}
+
+/// Runs the allocation and stores it as a draft; nothing is enrolled until a
+/// run is published, so admins can re-run with another seed or method.
+pub async fn run_allocation(
+    pool: &PgPool,
+    slot_id: Uuid,
+    method: ActivityAllocationMethod,
+    seed: Option<i64>,
+    actor_user_id: Uuid,
+) -> Result<ActivityAllocationRunDetail, AppError> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(db_error("run_activity_allocation"))?;
+    let slot = load_preference_slot(&mut *tx, slot_id, "FOR UPDATE OF s").await?;
+    ensure_preference_slot(&slot)?;
+    if slot.published_run_id.is_some() {
+        return Err(AppError::Conflict(
+            "ประกาศผลการจัดสรรของช่องกิจกรรมนี้แล้ว".to_string(),
+        ));
+    }
+
+    let input = load_allocation_input(&mut tx, slot_id).await?;
+    let outcome = allocate(&input.students, &input.groups, method, seed);
+    let first_choice_count = outcome
+        .placements
+        .iter()
+        .filter(|placement| placement.preference_rank == 1)
+        .count();
+
+    let run_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO activity_allocation_runs
+               (slot_id, method, seed, student_count, matched_count, unmatched_count,
+                first_choice_count, already_enrolled_count, created_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           RETURNING id"#,
+    )
+    .bind(slot_id)
+    .bind(method.as_str())
+    .bind(seed)
+    .bind(input.students.len() as i32)
+    .bind(outcome.placements.len() as i32)
+    .bind(outcome.unmatched.len() as i32)
+    .bind(first_choice_count as i32)
+    .bind(input.already_enrolled_count as i32)
+    .bind(actor_user_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error("run_activity_allocation"))?;
+
+    bulk_insert_allocation_results(&mut tx, run_id, &outcome).await?;
+
+    tx.commit()
+        .await
+        .map_err(db_error("run_activity_allocation"))?;
+    get_run(pool, run_id).await
+}
+
+const RUN_COLUMNS: &str = r#"id, slot_id, method, seed, status, student_count, matched_count,
+    unmatched_count, first_choice_count, already_enrolled_count, created_by, created_at,
+    published_by, published_at"#;
+
+pub async fn list_runs(
+    pool: &PgPool,
+    slot_id: Uuid,
+) -> Result<Vec<ActivityAllocationRun>, AppError> {
+    sqlx::query_as(&format!(
+        "SELECT {RUN_COLUMNS} FROM activity_allocation_runs WHERE slot_id = $1 ORDER BY created_at DESC"
+    ))
+    .bind(slot_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error("list_activity_allocation_runs"))
+}
+
+pub async fn get_run(pool: &PgPool, run_id: Uuid) -> Result<ActivityAllocationRunDetail, AppError> {
+    let run: ActivityAllocationRun = sqlx::query_as(&format!(
+        "SELECT {RUN_COLUMNS} FROM activity_allocation_runs WHERE id = $1"
+    ))
+    .bind(run_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(db_error("get_activity_allocation_run"))?
+    .ok_or_else(|| AppError::NotFound("ไม่พบผลการจัดสรร".to_string()))?;
+
+    let placements: Vec<ActivityAllocationPlacement> = sqlx::query_as(
+        r#"SELECT r.student_id,
+                  u.first_name || ' ' || u.last_name AS student_name,
+                  si.student_id AS student_code,
+                  cr.name AS classroom_name,
+                  r.activity_group_id,
+                  ag.name AS group_name,
+                  r.preference_rank
+           FROM activity_allocation_results r
+           JOIN activity_groups ag ON ag.id = r.activity_group_id
+           JOIN users u ON u.id = r.student_id
+           LEFT JOIN student_info si ON si.user_id = r.student_id
+           LEFT JOIN student_class_enrollments se ON se.student_id = r.student_id AND se.status = 'active'
+           LEFT JOIN class_rooms cr ON cr.id = se.class_room_id
+           WHERE r.run_id = $1
+           ORDER BY ag.name, cr.name, u.first_name"#,
+    )
+    .bind(run_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error("get_activity_allocation_run"))?;
+
+    let unmatched: Vec<ActivityUnmatchedStudent> = sqlx::query_as(
+        r#"SELECT r.student_id,
+                  u.first_name || ' ' || u.last_name AS student_name,
+                  si.student_id AS student_code,
+                  cr.name AS classroom_name,
+                  r.unmatched_reason AS reason
+           FROM activity_allocation_results r
+           JOIN users u ON u.id = r.student_id
+           LEFT JOIN student_info si ON si.user_id = r.student_id
+           LEFT JOIN student_class_enrollments se ON se.student_id = r.student_id AND se.status = 'active'
+           LEFT JOIN class_rooms cr ON cr.id = se.class_room_id
+           WHERE r.run_id = $1 AND r.activity_group_id IS NULL
+           ORDER BY r.unmatched_reason, cr.name, u.first_name"#,
+    )
+    .bind(run_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error("get_activity_allocation_run"))?;
+
+    let groups: Vec<ActivityAllocationGroupFill> = sqlx::query_as(
+        r#"SELECT ag.id AS activity_group_id,
+                  ag.name AS group_name,
+                  ag.max_capacity,
+                  (SELECT COUNT(*) FROM activity_group_members agm
+                   WHERE agm.activity_group_id = ag.id) AS current_members,
+                  (SELECT COUNT(*) FROM activity_allocation_results r
+                   WHERE r.run_id = $2 AND r.activity_group_id = ag.id) AS allocated,
+                  (SELECT COUNT(*) FROM activity_preferences ap
+                   WHERE ap.activity_group_id = ag.id AND ap.preference_rank = 1) AS first_choice_demand
+           FROM activity_groups ag
+           WHERE ag.slot_id = $1 AND ag.is_active = true
+           ORDER BY ag.name"#,
+    )
+    .bind(run.slot_id)
+    .bind(run_id)
+    .fetch_all(pool)
+    .await
+    .map_err(db_error("get_activity_allocation_run"))?;
+
+    let ranks: Vec<i32> = placements.iter().map(|p| p.preference_rank).collect();
+    Ok(ActivityAllocationRunDetail {
+        matched_by_rank: matched_by_rank(&ranks),
+        run,
+        groups,
+        placements,
+        unmatched,
+    })
+}
+
+/// Enrols a draft run's placements, discards the other drafts and closes
+/// student registration for the slot. Fails if a group no longer has room or a
+/// placed student joined a group of the slot since the run, so the admin can
+/// re-run against the current state.
+pub async fn publish_run(
+    pool: &PgPool,
+    run_id: Uuid,
+    actor_user_id: Uuid,
+) -> Result<ActivityAllocationRunDetail, AppError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(db_error("publish_activity_allocation"))?;
+    let (slot_id, status): (Uuid, String) =
+        sqlx::query_as("SELECT slot_id, status FROM activity_allocation_runs WHERE id = $1")
+            .bind(run_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(db_error("publish_activity_allocation"))?
+            .ok_or_else(|| AppError::NotFound("ไม่พบผลการจัดสรร".to_string()))?;
+    let slot = load_preference_slot(&mut *tx, slot_id, "FOR UPDATE OF s").await?;
+    if slot.published_run_id.is_some() || status != "draft" {
+        return Err(AppError::Conflict(
+            "ผลการจัดสรรนี้เผยแพร่ไม่ได้ เนื่องจากไม่ใช่ฉบับร่างหรือมีการเผยแพร่แล้ว".to_string(),
+        ));
+    }
+
+    let already_enrolled: i64 = sqlx::query_scalar(
+        r#"SELECT COUNT(*)
+           FROM activity_allocation_results r
+           JOIN activity_group_members agm ON agm.student_id = r.student_id
+           JOIN activity_groups ag ON ag.id = agm.activity_group_id
+           WHERE r.run_id = $1 AND r.activity_group_id IS NOT NULL AND ag.slot_id = $2"#,
+    )
+    .bind(run_id)
+    .bind(slot_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error("publish_activity_allocation"))?;
+    let over_capacity: Option<String> = sqlx::query_scalar(
+        r#"SELECT ag.name
+           FROM activity_groups ag
+           WHERE ag.slot_id = $2 AND ag.max_capacity IS NOT NULL
+             AND (SELECT COUNT(*) FROM activity_group_members agm
+                  WHERE agm.activity_group_id = ag.id)
+               + (SELECT COUNT(*) FROM activity_allocation_results r
+                  WHERE r.run_id = $1 AND r.activity_group_id = ag.id)
+               > ag.max_capacity
+           ORDER BY ag.name
+           LIMIT 1
+           FOR UPDATE OF ag"#,
+    )
+    .bind(run_id)
+    .bind(slot_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error("publish_activity_allocation"))?;
+    if already_enrolled > 0 || over_capacity.is_some() {
+        let detail = over_capacity
+            .map(|name| format!("กลุ่ม {name} ที่นั่งไม่พอแล้ว"))
+            .unwrap_or_else(|| "มีนักเรียนเข้ากลุ่มของช่องนี้ไปแล้ว".to_string());
+        return Err(AppError::Conflict(format!(
+            "ข้อมูลเปลี่ยนไปหลังจัดสรร ({detail}) กรุณาจัดสรรใหม่"
+        )));
+    }
+
+    sqlx::query(
+        r#"INSERT INTO activity_group_members (activity_group_id, student_id, enrolled_by)
+           SELECT r.activity_group_id, r.student_id, $2
+           FROM activity_allocation_results r
+           WHERE r.run_id = $1 AND r.activity_group_id IS NOT NULL
+           ON CONFLICT DO NOTHING"#,
+    )
+    .bind(run_id)
+    .bind(actor_user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error("publish_activity_allocation"))?;
+    sqlx::query(
+        r#"UPDATE activity_allocation_runs
+           SET status = CASE WHEN id = $1 THEN 'published' ELSE 'discarded' END,
+               published_by = CASE WHEN id = $1 THEN $3 END,
+               published_at = CASE WHEN id = $1 THEN NOW() END
+           WHERE slot_id = $2 AND status = 'draft'"#,
+    )
+    .bind(run_id)
+    .bind(slot_id)
+    .bind(actor_user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error("publish_activity_allocation"))?;
+    sqlx::query(
+        "UPDATE activity_slots SET student_reg_open = false, updated_at = NOW() WHERE id = $1",
+    )
+    .bind(slot_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error("publish_activity_allocation"))?;
+    tx.commit()
+        .await
+        .map_err(db_error("publish_activity_allocation"))?;
+
+    get_run(pool, run_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn student(grade_order: i32, classroom_id: Uuid, choices: &[Uuid]) -> AllocationStudent {
+        AllocationStudent {
+            student_id: Uuid::new_v4(),
+            classroom_id,
+            grade_order,
+            choices: choices.to_vec(),
+        }
+    }
+
+    fn group(remaining: Option<usize>, classroom_ids: &[Uuid]) -> AllocationGroup {
+        AllocationGroup {
+            group_id: Uuid::new_v4(),
+            remaining,
+            classroom_ids: classroom_ids.iter().copied().collect(),
+        }
+    }
+
+    fn placed_group(outcome: &AllocationOutcome, student_id: Uuid) -> Option<Uuid> {
+        outcome
+            .placements
+            .iter()
+            .find(|placement| placement.student_id == student_id)
+            .map(|placement| placement.group_id)
+    }
+
+    #[test]
+    fn serial_dictatorship_respects_capacity_and_reports_every_student() {
+        let room = Uuid::new_v4();
+        let popular = group(Some(2), &[room]);
+        let spare = group(None, &[room]);
+        let students: Vec<AllocationStudent> = (0..5)
+            .map(|_| student(401, room, &[popular.group_id, spare.group_id]))
+            .collect();
+
+        let outcome = allocate(
+            &students,
+            &[popular.clone(), spare.clone()],
+            ActivityAllocationMethod::RandomSerialDictatorship,
+            7,
+        );
+
+        assert!(outcome.unmatched.is_empty());
+        let in_popular: Vec<_> = outcome
+            .placements
+            .iter()
+            .filter(|placement| placement.group_id == popular.group_id)
+            .collect();
+        assert_eq!(in_popular.len(), 2);
+        assert!(in_popular
+            .iter()
+            .all(|placement| placement.preference_rank == 1));
+        assert_eq!(
+            outcome
+                .placements
+                .iter()
+                .filter(|placement| placement.preference_rank == 2)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn same_seed_reproduces_the_lottery_regardless_of_input_order() {
+        let room = Uuid::new_v4();
+        let only = group(Some(3), &[room]);
+        let students: Vec<AllocationStudent> = (0..12)
+            .map(|_| student(401, room, &[only.group_id]))
+            .collect();
+        let mut reversed = students.clone();
+        reversed.reverse();
+
+        let first = allocate(
+            &students,
+            std::slice::from_ref(&only),
+            ActivityAllocationMethod::RandomSerialDictatorship,
+            42,
+        );
+        let again = allocate(
+            &reversed,
+            std::slice::from_ref(&only),
+            ActivityAllocationMethod::RandomSerialDictatorship,
+            42,
+        );
+
+        assert_eq!(first, again);
+        assert_eq!(first.placements.len(), 3);
+        assert_eq!(first.unmatched.len(), 9);
+    }
+
+    #[test]
+    fn grade_priority_seats_senior_grades_first() {
+        let room_m4 = Uuid::new_v4();
+        let room_m6 = Uuid::new_v4();
+        let club = group(Some(2), &[room_m4, room_m6]);
+        let juniors: Vec<AllocationStudent> = (0..4)
+            .map(|_| student(304, room_m4, &[club.group_id]))
+            .collect();
+        let seniors: Vec<AllocationStudent> = (0..2)
+            .map(|_| student(306, room_m6, &[club.group_id]))
+            .collect();
+        let students: Vec<AllocationStudent> =
+            juniors.iter().chain(seniors.iter()).cloned().collect();
+
+        for seed in [1, 2, 3] {
+            let outcome = allocate(
+                &students,
+                std::slice::from_ref(&club),
+                ActivityAllocationMethod::GradePriorityStable,
+                seed,
+            );
+            for senior in &seniors {
+                assert_eq!(
+                    placed_group(&outcome, senior.student_id),
+                    Some(club.group_id)
+                );
+            }
+            assert!(juniors
+                .iter()
+                .all(|junior| placed_group(&outcome, junior.student_id).is_none()));
+        }
+    }
+
+    #[test]
+    fn grade_priority_matching_is_stable() {
+        let rooms: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let groups: Vec<AllocationGroup> =
+            (0..3).map(|index| group(Some(index + 1), &rooms)).collect();
+        let ids: Vec<Uuid> = groups.iter().map(|group| group.group_id).collect();
+        let students: Vec<AllocationStudent> = (0..10)
+            .map(|index| {
+                let mut choices = ids.clone();
+                choices.rotate_left(index % 3);
+                student(301 + (index % 3) as i32, rooms[index % 3], &choices)
+            })
+            .collect();
+
+        let outcome = allocate(
+            &students,
+            &groups,
+            ActivityAllocationMethod::GradePriorityStable,
+            99,
+        );
+
+        // No student prefers a group that holds someone of a lower grade or
+        // has a free seat.
+        for student in &students {
+            let assigned = placed_group(&outcome, student.student_id);
+            let better_choices = student
+                .choices
+                .iter()
+                .take_while(|choice| Some(**choice) != assigned);
+            for choice in better_choices {
+                let capacity = groups
+                    .iter()
+                    .find(|group| group.group_id == *choice)
+                    .and_then(|group| group.remaining)
+                    .unwrap();
+                let holders: Vec<&AllocationStudent> = outcome
+                    .placements
+                    .iter()
+                    .filter(|placement| placement.group_id == *choice)
+                    .filter_map(|placement| {
+                        students
+                            .iter()
+                            .find(|s| s.student_id == placement.student_id)
+                    })
+                    .collect();
+                assert_eq!(holders.len(), capacity);
+                assert!(holders
+                    .iter()
+                    .all(|holder| holder.grade_order >= student.grade_order));
+            }
+        }
+    }
+
+    #[test]
+    fn unmatched_students_carry_a_reason() {
+        let room = Uuid::new_v4();
+        let other_room = Uuid::new_v4();
+        let full = group(Some(0), &[room]);
+        let closed_to_room = group(None, &[other_room]);
+        let no_choice = student(401, room, &[]);
+        let ineligible = student(401, room, &[closed_to_room.group_id, Uuid::new_v4()]);
+        let crowded_out = student(401, room, &[full.group_id, closed_to_room.group_id]);
+
+        let outcome = allocate(
+            &[no_choice.clone(), ineligible.clone(), crowded_out.clone()],
+            &[full, closed_to_room],
+            ActivityAllocationMethod::RandomSerialDictatorship,
+            5,
+        );
+
+        assert!(outcome.placements.is_empty());
+        let reason = |id: Uuid| {
+            outcome
+                .unmatched
+                .iter()
+                .find(|(student_id, _)| *student_id == id)
+                .map(|(_, reason)| *reason)
+        };
+        assert_eq!(
+            reason(no_choice.student_id),
+            Some(ActivityUnmatchedReason::NoPreferences)
+        );
+        assert_eq!(
+            reason(ineligible.student_id),
+            Some(ActivityUnmatchedReason::NoEligibleChoice)
+        );
+        assert_eq!(
+            reason(crowded_out.student_id),
+            Some(ActivityUnmatchedReason::ChoicesFull)
+        );
+    }
+
+    #[test]
+    fn preference_choices_reject_duplicates_and_overlong_lists() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+
+        assert!(validate_preference_choices(&[a, b], 2).is_ok());
+        assert!(validate_preference_choices(&[], 2).is_ok());
+        assert!(matches!(
+            validate_preference_choices(&[a, b, Uuid::new_v4()], 2),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            validate_preference_choices(&[a, a], 3),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn registration_window_requires_the_flag_and_the_time_range() {
+        let at = |hour| Utc.with_ymd_and_hms(2026, 6, 1, hour, 0, 0).unwrap();
+
+        assert!(registration_window_open(true, None, None, at(8)));
+        assert!(!registration_window_open(false, None, None, at(8)));
+        assert!(registration_window_open(
+            true,
+            Some(at(8)),
+            Some(at(16)),
+            at(8)
+        ));
+        assert!(!registration_window_open(
+            true,
+            Some(at(8)),
+            Some(at(16)),
+            at(7)
+        ));
+        assert!(!registration_window_open(
+            true,
+            Some(at(8)),
+            Some(at(16)),
+            at(17)
+        ));
+    }
+
+    #[test]
+    fn matched_by_rank_counts_each_choice_position() {
+        assert_eq!(matched_by_rank(&[1, 1, 3, 2, 1]), vec![3, 1, 1]);
+        assert_eq!(matched_by_rank(&[]), Vec::<i64>::new());
+    }
+}
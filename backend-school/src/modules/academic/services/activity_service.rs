@@ -152,11 +152,21 @@ fn validate_group_instructor_role(role: &str) -> Result<(), AppError> {
 }
 
 fn validate_activity_registration_type(registration_type: &str) -> Result<(), AppError> {
-    if matches!(registration_type, "self" | "assigned") {
+    if matches!(registration_type, "self" | "assigned" | "preference") {
         Ok(())
     } else {
         Err(AppError::BadRequest(
-            "registration_type ต้องเป็น self หรือ assigned".to_string(),
+            "registration_type ต้องเป็น self, assigned หรือ preference".to_string(),
+        ))
+    }
+}
+
+fn validate_max_preferences(max_preferences: i32) -> Result<(), AppError> {
+    if (1..=10).contains(&max_preferences) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(
+            "max_preferences ต้องอยู่ระหว่าง 1 ถึง 10".to_string(),
         ))
     }
 }
@@ -199,6 +209,7 @@ struct ActivitySlotRow {
     student_reg_open: bool,
     student_reg_start: Option<DateTime<Utc>>,
     student_reg_end: Option<DateTime<Utc>>,
+    max_preferences: i32,
     created_by: Option<Uuid>,
     is_active: bool,
     created_at: DateTime<Utc>,
@@ -226,6 +237,7 @@ impl From<ActivitySlotRow> for ActivitySlot {
             student_reg_open: row.student_reg_open,
             student_reg_start: row.student_reg_start,
             student_reg_end: row.student_reg_end,
+            max_preferences: row.max_preferences,
             created_by: row.created_by,
             is_active: row.is_active,
             created_at: row.created_at,
@@ -544,6 +556,9 @@ pub async fn update_slot(
     if let Some(registration_type) = body.registration_type.as_deref() {
         validate_activity_registration_type(registration_type)?;
     }
+    if let Some(max_preferences) = body.max_preferences {
+        validate_max_preferences(max_preferences)?;
+    }
 
     sqlx::query_as::<_, ActivitySlotRow>(
         r#"WITH upd AS (
@@ -554,6 +569,7 @@ pub async fn update_slot(
                 student_reg_start = COALESCE($5, student_reg_start),
                 student_reg_end = COALESCE($6, student_reg_end),
                 is_active = COALESCE($7, is_active),
+                max_preferences = COALESCE($8, max_preferences),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -582,6 +598,7 @@ pub async fn update_slot(
     .bind(activity_datetime_from_rfc3339(body.student_reg_start.as_deref()))
     .bind(activity_datetime_from_rfc3339(body.student_reg_end.as_deref()))
     .bind(body.is_active)
+    .bind(body.max_preferences)
     .fetch_optional(pool)
     .await
     .map_err(|e| {
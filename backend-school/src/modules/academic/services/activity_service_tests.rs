@@ -44,6 +44,7 @@ fn empty_slot_update() -> UpdateActivitySlotRequest {
         student_reg_open: None,
         student_reg_start: None,
         student_reg_end: None,
+        max_preferences: None,
         is_active: Some(false),
     }
 }
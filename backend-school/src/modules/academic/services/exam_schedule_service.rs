@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
+mod auto_placement;
+mod exam_day_documents;
 mod invigilation;
+mod invigilator_auto_assignment;
 mod published_views;
 mod publishing;
 mod room_assignments;
@@ -9,10 +12,13 @@ mod sessions_and_conflicts;
 mod shared;
 mod workspace;
 
+pub use self::auto_placement::{apply_auto_placement, preview_auto_placement};
+pub use self::exam_day_documents::render_exam_day_document;
 pub use self::invigilation::{
     assign_invigilator_to_assignment, get_invigilator_workspace, list_invigilator_staff_options,
     remove_invigilator_from_assignment, update_assignment_invigilators,
 };
+pub use self::invigilator_auto_assignment::auto_assign_invigilators;
 pub use self::published_views::{
     list_child_published_exam_schedule, list_my_published_exam_schedule,
     list_staff_published_exam_schedule,
@@ -0,0 +1,744 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{NaiveTime, Timelike};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::academic::models::exam_schedule::{
+    ApplyExamAutoPlacementRequest, BlockedWindow, ExamAutoPlacementProposal,
+    ExamAutoPlacementResult, ExamGradeDayLoad, PlaceExamSessionRequest,
+    PreviewExamAutoPlacementRequest, ProposedExamSession, UnplacedExamItem,
+};
+
+use super::room_assignments::ensure_day_room_assignment_in_tx;
+use super::rounds_and_days::{
+    fetch_exam_day_details_for_round, fetch_round, mark_round_draft_after_mutation,
+};
+use super::sessions_and_conflicts::{grade_level_allowed_by_day_scope, place_exam_session_in_tx};
+use super::shared::{
+    add_minutes, minutes_between_times, time_ranges_overlap, unique_uuids, validate_session_window,
+    EXAM_SESSION_SLOT_MINUTES,
+};
+use super::workspace::{fetch_scheduled_sessions, fetch_unscheduled_items};
+
+/// Grade name, session count and minutes per classroom for one grade on one day
+type GradeDayTally = (Option<String>, i32, HashMap<Uuid, i32>);
+
+#[derive(Debug, Clone)]
+pub(super) struct PlannerDay {
+    pub(super) id: Uuid,
+    pub(super) start_time: NaiveTime,
+    pub(super) end_time: NaiveTime,
+    pub(super) grade_level_ids: Vec<Uuid>,
+    pub(super) blocked_windows: Vec<BlockedWindow>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PlannerItem {
+    pub(super) id: Uuid,
+    pub(super) classroom_id: Uuid,
+    pub(super) grade_level_id: Uuid,
+    pub(super) subject_id: Uuid,
+    pub(super) assessment_category_id: Uuid,
+    pub(super) duration_minutes: i32,
+    pub(super) active_student_count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PlannerRoom {
+    pub(super) id: Uuid,
+    pub(super) capacity: i32,
+}
+
+/// A session that already exists on one of the planned days
+#[derive(Debug, Clone)]
+pub(super) struct PlannerBooking {
+    pub(super) exam_day_id: Uuid,
+    pub(super) classroom_id: Uuid,
+    pub(super) grade_level_id: Uuid,
+    pub(super) starts_at: NaiveTime,
+    pub(super) ends_at: NaiveTime,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct PlannerRoomAssignment {
+    pub(super) exam_day_id: Uuid,
+    pub(super) classroom_id: Uuid,
+    pub(super) room_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PlannedSession {
+    pub(super) exam_schedule_item_id: Uuid,
+    pub(super) exam_day_id: Uuid,
+    pub(super) starts_at: NaiveTime,
+    pub(super) ends_at: NaiveTime,
+    pub(super) room_id: Uuid,
+    pub(super) creates_room_assignment: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum UnplacedReason {
+    GradeLevelNotOnAnyDay,
+    RoomTooSmall,
+    TimeSlotsFull,
+}
+
+impl UnplacedReason {
+    fn message(self) -> &'static str {
+        match self {
+            UnplacedReason::GradeLevelNotOnAnyDay => "No exam day allows this grade level",
+            UnplacedReason::RoomTooSmall => {
+                "No free active room is large enough for this classroom"
+            }
+            UnplacedReason::TimeSlotsFull => {
+                "No free time slot fits this exam around blocked windows and other sessions"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct PlacementPlan {
+    pub(super) placed: Vec<PlannedSession>,
+    pub(super) unplaced: Vec<(Uuid, UnplacedReason)>,
+}
+
+#[derive(Default)]
+struct PlannerState {
+    classroom_windows: HashMap<(Uuid, Uuid), Vec<(NaiveTime, NaiveTime)>>,
+    classroom_minutes: HashMap<(Uuid, Uuid), i32>,
+    grade_minutes: HashMap<(Uuid, Uuid), i32>,
+    classroom_rooms: HashMap<(Uuid, Uuid), Uuid>,
+    claimed_rooms: HashSet<(Uuid, Uuid)>,
+}
+
+impl PlannerState {
+    fn classroom_is_free(
+        &self,
+        exam_day_id: Uuid,
+        classroom_id: Uuid,
+        starts_at: NaiveTime,
+        ends_at: NaiveTime,
+    ) -> bool {
+        self.classroom_windows
+            .get(&(exam_day_id, classroom_id))
+            .is_none_or(|windows| {
+                windows.iter().all(|(existing_start, existing_end)| {
+                    !time_ranges_overlap(starts_at, ends_at, *existing_start, *existing_end)
+                })
+            })
+    }
+
+    fn book(
+        &mut self,
+        exam_day_id: Uuid,
+        classroom_id: Uuid,
+        grade_level_id: Uuid,
+        starts_at: NaiveTime,
+        ends_at: NaiveTime,
+        minutes: i32,
+    ) {
+        self.classroom_windows
+            .entry((exam_day_id, classroom_id))
+            .or_default()
+            .push((starts_at, ends_at));
+        *self
+            .classroom_minutes
+            .entry((exam_day_id, classroom_id))
+            .or_default() += minutes;
+        *self
+            .grade_minutes
+            .entry((exam_day_id, grade_level_id))
+            .or_default() += minutes;
+    }
+}
+
+/// Places unscheduled items onto exam days without classroom or room clashes.
+///
+/// Items that share grade level, subject, assessment category and duration are
+/// placed as one group at a common start time where possible, so a grade sits
+/// the same paper together; a group that cannot fit together falls back to
+/// placing its classrooms one by one. Days are tried least loaded first for the
+/// group's classrooms, then earliest start time. A classroom keeps its existing
+/// room for the day; otherwise the smallest free active room that seats it is
+/// proposed, since a room holds one classroom per exam day.
+pub(super) fn plan_exam_placements(
+    days: &[PlannerDay],
+    items: &[PlannerItem],
+    rooms: &[PlannerRoom],
+    bookings: &[PlannerBooking],
+    room_assignments: &[PlannerRoomAssignment],
+) -> PlacementPlan {
+    let mut state = PlannerState::default();
+    for booking in bookings {
+        let minutes = minutes_between_times(booking.starts_at, booking.ends_at);
+        state.book(
+            booking.exam_day_id,
+            booking.classroom_id,
+            booking.grade_level_id,
+            booking.starts_at,
+            booking.ends_at,
+            minutes,
+        );
+    }
+    for assignment in room_assignments {
+        state.classroom_rooms.insert(
+            (assignment.exam_day_id, assignment.classroom_id),
+            assignment.room_id,
+        );
+        state
+            .claimed_rooms
+            .insert((assignment.exam_day_id, assignment.room_id));
+    }
+
+    let mut rooms_by_size = rooms.to_vec();
+    rooms_by_size.sort_by_key(|room| (room.capacity, room.id));
+
+    let mut groups: BTreeMap<(Uuid, Uuid, Uuid, i32), Vec<&PlannerItem>> = BTreeMap::new();
+    for item in items {
+        groups
+            .entry((
+                item.grade_level_id,
+                item.subject_id,
+                item.assessment_category_id,
+                item.duration_minutes,
+            ))
+            .or_default()
+            .push(item);
+    }
+    let mut groups: Vec<Vec<&PlannerItem>> = groups.into_values().collect();
+    groups.sort_by_key(|group| (Reverse(group.len()), Reverse(group[0].duration_minutes)));
+
+    let mut plan = PlacementPlan::default();
+    for mut group in groups {
+        group.sort_by_key(|item| (Reverse(item.active_student_count), item.classroom_id));
+        if let Ok(placed) = place_group(days, &group, &rooms_by_size, &mut state) {
+            plan.placed.extend(placed);
+            continue;
+        }
+        for item in group {
+            match place_group(days, &[item], &rooms_by_size, &mut state) {
+                Ok(placed) => plan.placed.extend(placed),
+                Err(reason) => plan.unplaced.push((item.id, reason)),
+            }
+        }
+    }
+
+    plan
+}
+
+fn place_group(
+    days: &[PlannerDay],
+    group: &[&PlannerItem],
+    rooms_by_size: &[PlannerRoom],
+    state: &mut PlannerState,
+) -> Result<Vec<PlannedSession>, UnplacedReason> {
+    let grade_level_id = group[0].grade_level_id;
+    let duration_minutes = group[0].duration_minutes;
+
+    let mut candidate_days: Vec<(usize, &PlannerDay)> = days
+        .iter()
+        .enumerate()
+        .filter(|(_, day)| grade_level_allowed_by_day_scope(grade_level_id, &day.grade_level_ids))
+        .collect();
+    if candidate_days.is_empty() {
+        return Err(UnplacedReason::GradeLevelNotOnAnyDay);
+    }
+    candidate_days.sort_by_key(|(index, day)| {
+        let busiest_classroom = group
+            .iter()
+            .map(|item| {
+                state
+                    .classroom_minutes
+                    .get(&(day.id, item.classroom_id))
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0);
+        let grade_minutes = state
+            .grade_minutes
+            .get(&(day.id, grade_level_id))
+            .copied()
+            .unwrap_or(0);
+        (busiest_classroom, grade_minutes, *index)
+    });
+
+    let mut found_rooms = false;
+    for (_, day) in candidate_days {
+        let Some(rooms) = rooms_for_group(day.id, group, rooms_by_size, state) else {
+            continue;
+        };
+        found_rooms = true;
+
+        let mut starts_at = first_slot_at_or_after(day.start_time);
+        while let Some(start) = starts_at {
+            let Ok(ends_at) = add_minutes(start, duration_minutes) else {
+                break;
+            };
+            if ends_at > day.end_time {
+                break;
+            }
+            let fits = validate_session_window(
+                start,
+                duration_minutes,
+                day.start_time,
+                day.end_time,
+                &day.blocked_windows,
+            )
+            .is_ok()
+                && group
+                    .iter()
+                    .all(|item| state.classroom_is_free(day.id, item.classroom_id, start, ends_at));
+            if fits {
+                let mut placed = Vec::with_capacity(group.len());
+                for (item, (room_id, creates_room_assignment)) in group.iter().zip(rooms) {
+                    if creates_room_assignment {
+                        state
+                            .classroom_rooms
+                            .insert((day.id, item.classroom_id), room_id);
+                        state.claimed_rooms.insert((day.id, room_id));
+                    }
+                    state.book(
+                        day.id,
+                        item.classroom_id,
+                        item.grade_level_id,
+                        start,
+                        ends_at,
+                        duration_minutes,
+                    );
+                    placed.push(PlannedSession {
+                        exam_schedule_item_id: item.id,
+                        exam_day_id: day.id,
+                        starts_at: start,
+                        ends_at,
+                        room_id,
+                        creates_room_assignment,
+                    });
+                }
+                return Ok(placed);
+            }
+            starts_at = add_minutes(start, EXAM_SESSION_SLOT_MINUTES as i32).ok();
+        }
+    }
+
+    if found_rooms {
+        Err(UnplacedReason::TimeSlotsFull)
+    } else {
+        Err(UnplacedReason::RoomTooSmall)
+    }
+}
+
+/// Room per group member for the day: the classroom's existing room, or the
+/// smallest unclaimed room that seats it. `None` when any member has no room.
+fn rooms_for_group(
+    exam_day_id: Uuid,
+    group: &[&PlannerItem],
+    rooms_by_size: &[PlannerRoom],
+    state: &PlannerState,
+) -> Option<Vec<(Uuid, bool)>> {
+    let mut claimed_now: HashMap<Uuid, Uuid> = HashMap::new();
+    let mut rooms = Vec::with_capacity(group.len());
+    for item in group {
+        if let Some(room_id) = state.classroom_rooms.get(&(exam_day_id, item.classroom_id)) {
+            rooms.push((*room_id, false));
+            continue;
+        }
+        if let Some(room_id) = claimed_now.get(&item.classroom_id) {
+            rooms.push((*room_id, true));
+            continue;
+        }
+        let room = rooms_by_size.iter().find(|room| {
+            i64::from(room.capacity) >= item.active_student_count
+                && !state.claimed_rooms.contains(&(exam_day_id, room.id))
+                && !claimed_now.values().any(|claimed| *claimed == room.id)
+        })?;
+        claimed_now.insert(item.classroom_id, room.id);
+        rooms.push((room.id, true));
+    }
+    Some(rooms)
+}
+
+fn first_slot_at_or_after(time: NaiveTime) -> Option<NaiveTime> {
+    let slot_seconds = EXAM_SESSION_SLOT_MINUTES * 60;
+    let seconds = time.num_seconds_from_midnight().div_ceil(slot_seconds) * slot_seconds;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ActiveRoomRow {
+    id: Uuid,
+    name_th: String,
+    capacity: i32,
+}
+
+pub async fn preview_auto_placement(
+    pool: &PgPool,
+    round_id: Uuid,
+    request: PreviewExamAutoPlacementRequest,
+) -> Result<ExamAutoPlacementProposal, AppError> {
+    fetch_round(pool, round_id).await?;
+
+    let mut days = fetch_exam_day_details_for_round(pool, round_id).await?;
+    if let Some(exam_day_ids) = request.exam_day_ids {
+        let exam_day_ids = unique_uuids(exam_day_ids);
+        if exam_day_ids
+            .iter()
+            .any(|day_id| !days.iter().any(|day| day.id == *day_id))
+        {
+            return Err(AppError::BadRequest(
+                "Exam day belongs to a different exam round".to_string(),
+            ));
+        }
+        days.retain(|day| exam_day_ids.contains(&day.id));
+    }
+
+    let items = fetch_unscheduled_items(pool, round_id).await?;
+    let sessions = fetch_scheduled_sessions(pool, round_id).await?;
+    let classroom_ids = unique_uuids(items.iter().map(|item| item.classroom_id).collect());
+    let student_counts = fetch_active_student_counts(pool, &classroom_ids).await?;
+    let rooms = sqlx::query_as::<_, ActiveRoomRow>(
+        r#"
+        SELECT id,
+               name_th,
+               capacity
+        FROM rooms
+        WHERE status = 'ACTIVE'
+        ORDER BY capacity, name_th, id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let planner_days: Vec<PlannerDay> = days
+        .iter()
+        .map(|day| PlannerDay {
+            id: day.id,
+            start_time: day.start_time,
+            end_time: day.end_time,
+            grade_level_ids: day.grade_level_ids.clone(),
+            blocked_windows: day.blocked_windows.clone(),
+        })
+        .collect();
+    let planner_items: Vec<PlannerItem> = items
+        .iter()
+        .map(|item| PlannerItem {
+            id: item.id,
+            classroom_id: item.classroom_id,
+            grade_level_id: item.grade_level_id,
+            subject_id: item.subject_id,
+            assessment_category_id: item.assessment_category_id,
+            duration_minutes: item.duration_minutes,
+            active_student_count: student_counts.get(&item.classroom_id).copied().unwrap_or(0),
+        })
+        .collect();
+    let planner_rooms: Vec<PlannerRoom> = rooms
+        .iter()
+        .map(|room| PlannerRoom {
+            id: room.id,
+            capacity: room.capacity,
+        })
+        .collect();
+    let bookings: Vec<PlannerBooking> = sessions
+        .iter()
+        .map(|session| PlannerBooking {
+            exam_day_id: session.exam_day_id,
+            classroom_id: session.classroom_id,
+            grade_level_id: session.grade_level_id,
+            starts_at: session.starts_at,
+            ends_at: session.ends_at,
+        })
+        .collect();
+    let room_assignments: Vec<PlannerRoomAssignment> = days
+        .iter()
+        .flat_map(|day| day.room_assignments.iter())
+        .map(|assignment| PlannerRoomAssignment {
+            exam_day_id: assignment.exam_day_id,
+            classroom_id: assignment.classroom_id,
+            room_id: assignment.room_id,
+        })
+        .collect();
+
+    let plan = plan_exam_placements(
+        &planner_days,
+        &planner_items,
+        &planner_rooms,
+        &bookings,
+        &room_assignments,
+    );
+
+    let items_by_id: HashMap<Uuid, _> = items.iter().map(|item| (item.id, item)).collect();
+    let days_by_id: HashMap<Uuid, _> = days.iter().map(|day| (day.id, day)).collect();
+    let room_names: HashMap<Uuid, String> = rooms
+        .iter()
+        .map(|room| (room.id, room.name_th.clone()))
+        .chain(days.iter().flat_map(|day| {
+            day.room_assignments.iter().filter_map(|assignment| {
+                assignment
+                    .room_name
+                    .clone()
+                    .map(|name| (assignment.room_id, name))
+            })
+        }))
+        .collect();
+
+    let mut placements: Vec<ProposedExamSession> = plan
+        .placed
+        .iter()
+        .map(|placed| {
+            let item = items_by_id[&placed.exam_schedule_item_id];
+            ProposedExamSession {
+                exam_schedule_item_id: item.id,
+                exam_day_id: placed.exam_day_id,
+                exam_date: days_by_id[&placed.exam_day_id].exam_date,
+                starts_at: placed.starts_at,
+                ends_at: placed.ends_at,
+                room_id: placed.room_id,
+                room_name: room_names.get(&placed.room_id).cloned(),
+                creates_room_assignment: placed.creates_room_assignment,
+                classroom_id: item.classroom_id,
+                classroom_name: item.classroom_name.clone(),
+                grade_level_id: item.grade_level_id,
+                grade_level_name: item.grade_level_name.clone(),
+                subject_code: item.subject_code.clone(),
+                subject_name_th: item.subject_name_th.clone(),
+                assessment_category_name: item.assessment_category_name.clone(),
+                duration_minutes: item.duration_minutes,
+            }
+        })
+        .collect();
+    placements.sort_by(|left, right| {
+        (
+            left.exam_date,
+            left.starts_at,
+            &left.grade_level_name,
+            &left.classroom_name,
+        )
+            .cmp(&(
+                right.exam_date,
+                right.starts_at,
+                &right.grade_level_name,
+                &right.classroom_name,
+            ))
+    });
+
+    let unplaced_items = plan
+        .unplaced
+        .iter()
+        .map(|(item_id, reason)| {
+            let item = items_by_id[item_id];
+            UnplacedExamItem {
+                exam_schedule_item_id: item.id,
+                classroom_id: item.classroom_id,
+                classroom_name: item.classroom_name.clone(),
+                grade_level_name: item.grade_level_name.clone(),
+                subject_code: item.subject_code.clone(),
+                subject_name_th: item.subject_name_th.clone(),
+                duration_minutes: item.duration_minutes,
+                reason: reason.message().to_string(),
+            }
+        })
+        .collect();
+
+    // (day, grade) -> (grade name, sessions, minutes per classroom)
+    let mut loads: HashMap<(Uuid, Uuid), GradeDayTally> = HashMap::new();
+    let existing = sessions
+        .iter()
+        .filter(|session| days_by_id.contains_key(&session.exam_day_id))
+        .map(|session| {
+            (
+                session.exam_day_id,
+                session.grade_level_id,
+                session.grade_level_name.clone(),
+                session.classroom_id,
+                session.duration_minutes,
+            )
+        });
+    let proposed = placements.iter().map(|placement| {
+        (
+            placement.exam_day_id,
+            placement.grade_level_id,
+            placement.grade_level_name.clone(),
+            placement.classroom_id,
+            placement.duration_minutes,
+        )
+    });
+    for (exam_day_id, grade_level_id, grade_level_name, classroom_id, minutes) in
+        existing.chain(proposed)
+    {
+        let load = loads
+            .entry((exam_day_id, grade_level_id))
+            .or_insert_with(|| (grade_level_name, 0, HashMap::new()));
+        load.1 += 1;
+        *load.2.entry(classroom_id).or_default() += minutes;
+    }
+    let mut grade_day_loads: Vec<ExamGradeDayLoad> = loads
+        .into_iter()
+        .map(
+            |((exam_day_id, grade_level_id), (grade_level_name, session_count, minutes))| {
+                ExamGradeDayLoad {
+                    grade_level_id,
+                    grade_level_name,
+                    exam_day_id,
+                    exam_date: days_by_id[&exam_day_id].exam_date,
+                    session_count,
+                    max_classroom_minutes: minutes.into_values().max().unwrap_or(0),
+                }
+            },
+        )
+        .collect();
+    grade_day_loads.sort_by(|left, right| {
+        (&left.grade_level_name, left.exam_date, left.grade_level_id).cmp(&(
+            &right.grade_level_name,
+            right.exam_date,
+            right.grade_level_id,
+        ))
+    });
+
+    Ok(ExamAutoPlacementProposal {
+        exam_round_id: round_id,
+        placements,
+        unplaced_items,
+        grade_day_loads,
+    })
+}
+
+/// Writes a reviewed proposal in one transaction. Every placement goes through
+/// the same checks as a manual drop, so anything that changed since the preview
+/// rejects the whole batch instead of leaving a partial schedule.
+pub async fn apply_auto_placement(
+    pool: &PgPool,
+    round_id: Uuid,
+    request: ApplyExamAutoPlacementRequest,
+    actor_user_id: Uuid,
+) -> Result<ExamAutoPlacementResult, AppError> {
+    if request.placements.is_empty() {
+        return Err(AppError::BadRequest(
+            "Select at least one proposed session to apply".to_string(),
+        ));
+    }
+    let item_ids: Vec<Uuid> = request
+        .placements
+        .iter()
+        .map(|placement| placement.exam_schedule_item_id)
+        .collect();
+    if unique_uuids(item_ids.clone()).len() != item_ids.len() {
+        return Err(AppError::BadRequest(
+            "Each exam item can only be placed once".to_string(),
+        ));
+    }
+
+    let mut placements = request.placements;
+    placements.sort_by_key(|placement| {
+        (
+            placement.exam_day_id,
+            placement.starts_at,
+            placement.exam_schedule_item_id,
+        )
+    });
+
+    let mut tx = pool.begin().await?;
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT id
+        FROM academic_exam_rounds
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(round_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Exam round not found".to_string()))?;
+
+    let mut created_room_assignments = 0;
+    for placement in &placements {
+        let (exam_round_id, classroom_id, already_scheduled): (Uuid, Uuid, bool) = sqlx::query_as(
+            r#"
+                SELECT item.exam_round_id,
+                       item.classroom_id,
+                       EXISTS (
+                           SELECT 1
+                           FROM academic_exam_sessions session
+                           WHERE session.exam_schedule_item_id = item.id
+                       )
+                FROM academic_exam_schedule_items item
+                WHERE item.id = $1
+                "#,
+        )
+        .bind(placement.exam_schedule_item_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Exam schedule item not found".to_string()))?;
+        if exam_round_id != round_id {
+            return Err(AppError::BadRequest(
+                "Exam schedule item belongs to a different exam round".to_string(),
+            ));
+        }
+        if already_scheduled {
+            return Err(AppError::Conflict(
+                "An exam item was scheduled after the proposal was built; preview again"
+                    .to_string(),
+            ));
+        }
+
+        if ensure_day_room_assignment_in_tx(
+            &mut tx,
+            round_id,
+            placement.exam_day_id,
+            classroom_id,
+            placement.room_id,
+            actor_user_id,
+        )
+        .await?
+        {
+            created_room_assignments += 1;
+        }
+        place_exam_session_in_tx(
+            &mut tx,
+            &PlaceExamSessionRequest {
+                exam_schedule_item_id: placement.exam_schedule_item_id,
+                exam_day_id: placement.exam_day_id,
+                starts_at: placement.starts_at,
+            },
+            actor_user_id,
+        )
+        .await?;
+    }
+
+    mark_round_draft_after_mutation(&mut tx, round_id, Some(actor_user_id)).await?;
+    tx.commit().await?;
+
+    Ok(ExamAutoPlacementResult {
+        placed_sessions: placements.len(),
+        created_room_assignments,
+    })
+}
+
+async fn fetch_active_student_counts(
+    pool: &PgPool,
+    classroom_ids: &[Uuid],
+) -> Result<HashMap<Uuid, i64>, AppError> {
+    if classroom_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = sqlx::query_as::<_, (Uuid, i64)>(
+        r#"
+        SELECT enrollment.class_room_id,
+               COUNT(*)::BIGINT
+        FROM student_class_enrollments enrollment
+        WHERE enrollment.class_room_id = ANY($1)
+          AND enrollment.status = 'active'
+        GROUP BY enrollment.class_room_id
+        "#,
+    )
+    .bind(classroom_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().collect())
+}
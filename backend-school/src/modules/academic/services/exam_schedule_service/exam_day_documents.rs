@@ -0,0 +1,1070 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::academic::models::exam_schedule::ExamDayDocumentKind;
+use crate::utils::pdf::{
+    centered_line, draw_table, thai_date, PdfDocument, PdfFontWeight, PdfPageSize, PdfTextAlign,
+    TableColumn, A4_LANDSCAPE, A4_PORTRAIT, PAGE_MARGIN, TABLE_CELL_PADDING,
+};
+
+use super::room_assignments::{build_default_seat_assignments, SeatStudent};
+
+pub(super) const DEFAULT_SEAT_COLUMNS: usize = 6;
+pub(super) const MAX_SEAT_COLUMNS: usize = 10;
+pub(super) const LABEL_COLUMNS: usize = 2;
+pub(super) const LABEL_ROWS: usize = 4;
+
+pub struct ExamDayDocumentFile {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct DocumentSession {
+    pub(super) starts_at: NaiveTime,
+    pub(super) ends_at: NaiveTime,
+    pub(super) subject_code: String,
+    pub(super) subject_name: String,
+    pub(super) category_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct DocumentStudent {
+    pub(super) seat_number: String,
+    pub(super) student_code: Option<String>,
+    pub(super) student_name: String,
+}
+
+/// One classroom in one room on one exam day, with everything printed about it
+#[derive(Debug, Clone)]
+pub(super) struct DocumentRoom {
+    pub(super) exam_day_id: Uuid,
+    pub(super) exam_date: NaiveDate,
+    pub(super) day_label: Option<String>,
+    pub(super) classroom_name: String,
+    pub(super) room_name: String,
+    pub(super) building_name: Option<String>,
+    pub(super) required_invigilators: usize,
+    pub(super) sessions: Vec<DocumentSession>,
+    pub(super) students: Vec<DocumentStudent>,
+    pub(super) invigilators: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ExamDayDocumentData {
+    pub(super) round_name: String,
+    pub(super) rooms: Vec<DocumentRoom>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DocumentRoomRow {
+    assignment_id: Uuid,
+    exam_day_id: Uuid,
+    exam_date: NaiveDate,
+    day_label: Option<String>,
+    classroom_id: Uuid,
+    classroom_name: String,
+    room_name: String,
+    building_name: Option<String>,
+    required_invigilators: i16,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DocumentSessionRow {
+    assignment_id: Uuid,
+    starts_at: NaiveTime,
+    ends_at: NaiveTime,
+    subject_code: String,
+    subject_name: String,
+    category_name: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DocumentSeatRow {
+    assignment_id: Uuid,
+    seat_number: String,
+    student_code: Option<String>,
+    student_name: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DocumentEnrollmentRow {
+    classroom_id: Uuid,
+    student_id: Uuid,
+    student_code: Option<String>,
+    student_name: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DocumentInvigilatorRow {
+    assignment_id: Uuid,
+    display_name: String,
+}
+
+pub async fn render_exam_day_document(
+    pool: &PgPool,
+    round_id: Uuid,
+    kind: ExamDayDocumentKind,
+    exam_day_id: Option<Uuid>,
+    seat_columns: Option<usize>,
+) -> Result<ExamDayDocumentFile, AppError> {
+    let seat_columns = validate_seat_columns(seat_columns)?;
+    let data = fetch_exam_day_document_data(pool, round_id, exam_day_id).await?;
+    if data.rooms.is_empty() {
+        return Err(AppError::BadRequest(
+            "No exam rooms are assigned for this selection".to_string(),
+        ));
+    }
+    let needs_sessions = matches!(
+        kind,
+        ExamDayDocumentKind::AttendanceSheets | ExamDayDocumentKind::EnvelopeLabels
+    );
+    if needs_sessions && data.rooms.iter().all(|room| room.sessions.is_empty()) {
+        return Err(AppError::BadRequest(
+            "No exam sessions are placed in the selected rooms".to_string(),
+        ));
+    }
+
+    let document = match kind {
+        ExamDayDocumentKind::SeatingCharts => render_seating_charts(&data, seat_columns),
+        ExamDayDocumentKind::DoorLists => render_door_lists(&data),
+        ExamDayDocumentKind::InvigilatorSignIn => render_invigilator_sign_in(&data),
+        ExamDayDocumentKind::AttendanceSheets => render_attendance_sheets(&data),
+        ExamDayDocumentKind::EnvelopeLabels => render_envelope_labels(&data),
+    };
+    let filename = match exam_day_id.and_then(|_| data.rooms.first()) {
+        Some(room) => format!("exam-{}-{}.pdf", kind.as_str(), room.exam_date),
+        None => format!("exam-{}.pdf", kind.as_str()),
+    };
+
+    Ok(ExamDayDocumentFile {
+        filename,
+        content: document.finish(),
+    })
+}
+
+pub(super) fn validate_seat_columns(seat_columns: Option<usize>) -> Result<usize, AppError> {
+    match seat_columns {
+        None => Ok(DEFAULT_SEAT_COLUMNS),
+        Some(columns) if (1..=MAX_SEAT_COLUMNS).contains(&columns) => Ok(columns),
+        Some(_) => Err(AppError::BadRequest(format!(
+            "seat_columns must be between 1 and {MAX_SEAT_COLUMNS}"
+        ))),
+    }
+}
+
+async fn fetch_exam_day_document_data(
+    pool: &PgPool,
+    round_id: Uuid,
+    exam_day_id: Option<Uuid>,
+) -> Result<ExamDayDocumentData, AppError> {
+    let round_name: String =
+        sqlx::query_scalar("SELECT name FROM academic_exam_rounds WHERE id = $1")
+            .bind(round_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Exam round not found".to_string()))?;
+
+    if let Some(exam_day_id) = exam_day_id {
+        let day_in_round: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM academic_exam_days
+                WHERE id = $1
+                  AND exam_round_id = $2
+            )
+            "#,
+        )
+        .bind(exam_day_id)
+        .bind(round_id)
+        .fetch_one(pool)
+        .await?;
+        if !day_in_round {
+            return Err(AppError::NotFound("Exam day not found".to_string()));
+        }
+    }
+
+    let room_rows = sqlx::query_as::<_, DocumentRoomRow>(
+        r#"
+        SELECT assignment.id AS assignment_id,
+               assignment.exam_day_id,
+               day.exam_date,
+               day.label AS day_label,
+               assignment.classroom_id,
+               classroom.name AS classroom_name,
+               room.name_th AS room_name,
+               building.name_th AS building_name,
+               assignment.required_invigilators
+        FROM academic_exam_day_room_assignments assignment
+        JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+        JOIN class_rooms classroom ON classroom.id = assignment.classroom_id
+        JOIN rooms room ON room.id = assignment.room_id
+        LEFT JOIN buildings building ON building.id = room.building_id
+        WHERE day.exam_round_id = $1
+          AND ($2::uuid IS NULL OR day.id = $2)
+        ORDER BY day.exam_date, room.name_th, classroom.name, assignment.id
+        "#,
+    )
+    .bind(round_id)
+    .bind(exam_day_id)
+    .fetch_all(pool)
+    .await?;
+
+    let session_rows = sqlx::query_as::<_, DocumentSessionRow>(
+        r#"
+        SELECT assignment.id AS assignment_id,
+               session.starts_at,
+               session.ends_at,
+               subject.code AS subject_code,
+               COALESCE(NULLIF(subject.name_th, ''), NULLIF(subject.name_en, ''), subject.code)
+                   AS subject_name,
+               category.name AS category_name
+        FROM academic_exam_day_room_assignments assignment
+        JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+        JOIN academic_exam_sessions session
+          ON session.exam_day_id = assignment.exam_day_id
+         AND session.exam_round_id = day.exam_round_id
+        JOIN academic_exam_schedule_items item
+          ON item.id = session.exam_schedule_item_id
+         AND item.classroom_id = assignment.classroom_id
+        JOIN subjects subject ON subject.id = item.subject_id
+        JOIN academic_assessment_categories category
+          ON category.id = item.assessment_category_id
+        WHERE day.exam_round_id = $1
+          AND ($2::uuid IS NULL OR day.id = $2)
+        ORDER BY assignment.id, session.starts_at, subject.code, session.id
+        "#,
+    )
+    .bind(round_id)
+    .bind(exam_day_id)
+    .fetch_all(pool)
+    .await?;
+
+    let seat_rows = sqlx::query_as::<_, DocumentSeatRow>(
+        r#"
+        SELECT seat.day_room_assignment_id AS assignment_id,
+               seat.seat_number,
+               student_info.student_id AS student_code,
+               concat_ws(
+                   ' ',
+                   NULLIF(
+                       concat_ws('', NULLIF(TRIM(user_account.title), ''), NULLIF(TRIM(user_account.first_name), '')),
+                       ''
+                   ),
+                   NULLIF(TRIM(user_account.last_name), '')
+               ) AS student_name
+        FROM academic_exam_seat_assignments seat
+        JOIN academic_exam_day_room_assignments assignment
+          ON assignment.id = seat.day_room_assignment_id
+        JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+        JOIN users user_account ON user_account.id = seat.student_id
+        LEFT JOIN student_info ON student_info.user_id = user_account.id
+        WHERE day.exam_round_id = $1
+          AND ($2::uuid IS NULL OR day.id = $2)
+        ORDER BY seat.day_room_assignment_id,
+                 length(seat.seat_number),
+                 seat.seat_number,
+                 seat.id
+        "#,
+    )
+    .bind(round_id)
+    .bind(exam_day_id)
+    .fetch_all(pool)
+    .await?;
+
+    let invigilator_rows = sqlx::query_as::<_, DocumentInvigilatorRow>(
+        r#"
+        SELECT invigilator.day_room_assignment_id AS assignment_id,
+               COALESCE(
+                   NULLIF(
+                       concat_ws(
+                           ' ',
+                           NULLIF(
+                               concat_ws('', NULLIF(TRIM(user_account.title), ''), NULLIF(TRIM(user_account.first_name), '')),
+                               ''
+                           ),
+                           NULLIF(TRIM(user_account.last_name), '')
+                       ),
+                       ''
+                   ),
+                   user_account.id::TEXT
+               ) AS display_name
+        FROM academic_exam_day_invigilators invigilator
+        JOIN academic_exam_days day ON day.id = invigilator.exam_day_id
+        JOIN users user_account ON user_account.id = invigilator.staff_id
+        WHERE day.exam_round_id = $1
+          AND ($2::uuid IS NULL OR day.id = $2)
+        ORDER BY invigilator.day_room_assignment_id, display_name, invigilator.id
+        "#,
+    )
+    .bind(round_id)
+    .bind(exam_day_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sessions: HashMap<Uuid, Vec<DocumentSession>> = HashMap::new();
+    for row in session_rows {
+        sessions
+            .entry(row.assignment_id)
+            .or_default()
+            .push(DocumentSession {
+                starts_at: row.starts_at,
+                ends_at: row.ends_at,
+                subject_code: row.subject_code,
+                subject_name: row.subject_name,
+                category_name: row.category_name,
+            });
+    }
+    let mut students: HashMap<Uuid, Vec<DocumentStudent>> = HashMap::new();
+    for row in seat_rows {
+        students
+            .entry(row.assignment_id)
+            .or_default()
+            .push(DocumentStudent {
+                seat_number: row.seat_number,
+                student_code: row.student_code,
+                student_name: row.student_name,
+            });
+    }
+    let mut invigilators: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for row in invigilator_rows {
+        invigilators
+            .entry(row.assignment_id)
+            .or_default()
+            .push(row.display_name);
+    }
+
+    // Rooms whose seats were never generated print the order seat generation
+    // would use, so the sheets match the seats handed out later.
+    let unseated_classroom_ids: Vec<Uuid> = room_rows
+        .iter()
+        .filter(|row| !students.contains_key(&row.assignment_id))
+        .map(|row| row.classroom_id)
+        .collect();
+    let mut enrolled: HashMap<Uuid, Vec<DocumentStudent>> = HashMap::new();
+    if !unseated_classroom_ids.is_empty() {
+        let enrollment_rows = sqlx::query_as::<_, DocumentEnrollmentRow>(
+            r#"
+            SELECT enrollment.class_room_id AS classroom_id,
+                   user_account.id AS student_id,
+                   student_info.student_id AS student_code,
+                   concat_ws(
+                       ' ',
+                       NULLIF(
+                           concat_ws('', NULLIF(TRIM(user_account.title), ''), NULLIF(TRIM(user_account.first_name), '')),
+                           ''
+                       ),
+                       NULLIF(TRIM(user_account.last_name), '')
+                   ) AS student_name
+            FROM student_class_enrollments enrollment
+            JOIN users user_account
+              ON user_account.id = enrollment.student_id
+             AND user_account.user_type = 'student'
+             AND user_account.status = 'active'
+            LEFT JOIN student_info ON student_info.user_id = user_account.id
+            WHERE enrollment.class_room_id = ANY($1)
+              AND enrollment.status = 'active'
+            ORDER BY enrollment.class_room_id,
+                     enrollment.class_number ASC NULLS LAST,
+                     student_info.student_id ASC NULLS LAST,
+                     user_account.id ASC
+            "#,
+        )
+        .bind(&unseated_classroom_ids)
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_classroom: HashMap<Uuid, Vec<DocumentEnrollmentRow>> = HashMap::new();
+        for row in enrollment_rows {
+            by_classroom.entry(row.classroom_id).or_default().push(row);
+        }
+        for (classroom_id, rows) in by_classroom {
+            let seat_students: Vec<SeatStudent> = rows
+                .iter()
+                .map(|row| SeatStudent {
+                    student_id: row.student_id,
+                })
+                .collect();
+            let drafts = build_default_seat_assignments(&seat_students);
+            let classroom_students = rows
+                .into_iter()
+                .zip(drafts)
+                .map(|(row, draft)| DocumentStudent {
+                    seat_number: draft.seat_number,
+                    student_code: row.student_code,
+                    student_name: row.student_name,
+                })
+                .collect();
+            enrolled.insert(classroom_id, classroom_students);
+        }
+    }
+
+    let rooms = room_rows
+        .into_iter()
+        .map(|row| DocumentRoom {
+            exam_day_id: row.exam_day_id,
+            exam_date: row.exam_date,
+            day_label: row.day_label,
+            classroom_name: row.classroom_name,
+            room_name: row.room_name,
+            building_name: row.building_name,
+            required_invigilators: usize::try_from(row.required_invigilators).unwrap_or(1),
+            sessions: sessions.remove(&row.assignment_id).unwrap_or_default(),
+            students: students
+                .remove(&row.assignment_id)
+                .or_else(|| enrolled.get(&row.classroom_id).cloned())
+                .unwrap_or_default(),
+            invigilators: invigilators.remove(&row.assignment_id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(ExamDayDocumentData { round_name, rooms })
+}
+
+pub(super) fn render_seating_charts(data: &ExamDayDocumentData, columns: usize) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let mut document = PdfDocument::new(format!("ผังที่นั่งสอบ {}", data.round_name));
+    for room in &data.rooms {
+        document.add_page(page);
+        let mut y = draw_room_heading(&mut document, page, "ผังที่นั่งสอบ", data, room);
+
+        let front_width = 240.0;
+        document.rect((page.width - front_width) / 2.0, y, front_width, 24.0, 0.8);
+        document.aligned_text(
+            page.width / 2.0,
+            y + 16.5,
+            11.0,
+            PdfFontWeight::Regular,
+            PdfTextAlign::Center,
+            "หน้าห้องสอบ (โต๊ะกรรมการคุมสอบ)",
+        );
+        y += 38.0;
+
+        let gap = 6.0;
+        let rows = seat_rows(room.students.len(), columns);
+        let grid_bottom = page.height - PAGE_MARGIN - 20.0;
+        let cell_width =
+            (page.width - 2.0 * PAGE_MARGIN - gap * (columns - 1) as f32) / columns as f32;
+        let cell_height = ((grid_bottom - y - gap * (rows - 1) as f32) / rows as f32).min(64.0);
+        for (index, student) in room.students.iter().enumerate() {
+            let (column, row) = seat_grid_position(index, rows);
+            let x = PAGE_MARGIN + column as f32 * (cell_width + gap);
+            let top = y + row as f32 * (cell_height + gap);
+            draw_seat_cell(&mut document, x, top, cell_width, cell_height, student);
+        }
+
+        document.text(
+            PAGE_MARGIN,
+            page.height - PAGE_MARGIN,
+            11.0,
+            PdfFontWeight::Regular,
+            &format!(
+                "จำนวนนักเรียน {} คน · เลขที่นั่งเรียงจากหน้าห้องลงไปทีละแถวตั้ง เริ่มจากแถวซ้าย",
+                room.students.len()
+            ),
+        );
+    }
+    document
+}
+
+pub(super) fn seat_rows(seat_count: usize, columns: usize) -> usize {
+    seat_count.div_ceil(columns.max(1)).max(1)
+}
+
+/// Seats run front to back down each column, then on to the next column
+pub(super) fn seat_grid_position(index: usize, rows: usize) -> (usize, usize) {
+    (index / rows, index % rows)
+}
+
+fn draw_seat_cell(
+    document: &mut PdfDocument,
+    x: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    student: &DocumentStudent,
+) {
+    document.rect(x, top, width, height, 0.6);
+    let inner_width = width - 2.0 * TABLE_CELL_PADDING;
+    let font_size = (height * 0.3).clamp(6.0, 11.0);
+    if height >= 30.0 {
+        document.text(
+            x + TABLE_CELL_PADDING,
+            top + font_size + 3.0,
+            font_size + 2.0,
+            PdfFontWeight::Bold,
+            &student.seat_number,
+        );
+        if let Some(student_code) = &student.student_code {
+            document.aligned_text(
+                x + width - TABLE_CELL_PADDING,
+                top + font_size + 3.0,
+                font_size - 1.0,
+                PdfFontWeight::Regular,
+                PdfTextAlign::Right,
+                student_code,
+            );
+        }
+        let name = document.fit_text(
+            &student.student_name,
+            font_size,
+            PdfFontWeight::Regular,
+            inner_width,
+        );
+        document.text(
+            x + TABLE_CELL_PADDING,
+            top + 2.0 * font_size + 9.0,
+            font_size,
+            PdfFontWeight::Regular,
+            &name,
+        );
+    } else {
+        let line = document.fit_text(
+            &format!("{} {}", student.seat_number, student.student_name),
+            font_size,
+            PdfFontWeight::Regular,
+            inner_width,
+        );
+        document.text(
+            x + TABLE_CELL_PADDING,
+            top + (height + font_size) / 2.0 - 1.0,
+            font_size,
+            PdfFontWeight::Regular,
+            &line,
+        );
+    }
+}
+
+pub(super) fn render_door_lists(data: &ExamDayDocumentData) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let mut document = PdfDocument::new(format!("รายชื่อหน้าห้องสอบ {}", data.round_name));
+    for room in &data.rooms {
+        document.add_page(page);
+        let mut y = PAGE_MARGIN + 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            16.0,
+            PdfFontWeight::Bold,
+            "รายชื่อผู้เข้าสอบประจำห้อง",
+        );
+        y += 34.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            28.0,
+            PdfFontWeight::Bold,
+            &room_location(room),
+        );
+        y += 24.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            14.0,
+            PdfFontWeight::Regular,
+            &format!("ชั้น {} · {}", room.classroom_name, day_heading(room)),
+        );
+        y += 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            12.0,
+            PdfFontWeight::Regular,
+            &data.round_name,
+        );
+        y += 6.0;
+        for session in &room.sessions {
+            y += 16.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                11.0,
+                PdfFontWeight::Regular,
+                &format!(
+                    "{} {} ({})",
+                    time_range(session.starts_at, session.ends_at),
+                    session_title(session),
+                    session.category_name
+                ),
+            );
+        }
+        y += 14.0;
+
+        let columns = [
+            TableColumn::new("ที่นั่ง", 60.0, PdfTextAlign::Center),
+            TableColumn::new("เลขประจำตัว", 100.0, PdfTextAlign::Center),
+            TableColumn::new(
+                "ชื่อ–สกุล",
+                page.width - 2.0 * PAGE_MARGIN - 160.0,
+                PdfTextAlign::Left,
+            ),
+        ];
+        let rows: Vec<Vec<String>> = room
+            .students
+            .iter()
+            .map(|student| {
+                vec![
+                    student.seat_number.clone(),
+                    student.student_code.clone().unwrap_or_default(),
+                    student.student_name.clone(),
+                ]
+            })
+            .collect();
+        let continued = format!("{} · ชั้น {} (ต่อ)", room_location(room), room.classroom_name);
+        y = draw_table(&mut document, page, &columns, &rows, y, &continued);
+        document.text(
+            PAGE_MARGIN,
+            y + 18.0,
+            11.0,
+            PdfFontWeight::Regular,
+            &format!("รวม {} คน", room.students.len()),
+        );
+    }
+    document
+}
+
+pub(super) fn render_invigilator_sign_in(data: &ExamDayDocumentData) -> PdfDocument {
+    let page = A4_LANDSCAPE;
+    let mut document = PdfDocument::new(format!("ใบลงชื่อกรรมการคุมสอบ {}", data.round_name));
+    let columns = [
+        TableColumn::new("ห้องสอบ", 125.0, PdfTextAlign::Left),
+        TableColumn::new("ชั้น", 60.0, PdfTextAlign::Center),
+        TableColumn::new("เวลาสอบ", 95.0, PdfTextAlign::Center),
+        TableColumn::new("ชื่อกรรมการคุมสอบ", 170.0, PdfTextAlign::Left),
+        TableColumn::new("ลายมือชื่อ (มา)", 110.0, PdfTextAlign::Left),
+        TableColumn::new("เวลา", 45.0, PdfTextAlign::Center),
+        TableColumn::new("ลายมือชื่อ (กลับ)", 110.0, PdfTextAlign::Left),
+        TableColumn::new("เวลา", 45.0, PdfTextAlign::Center),
+    ];
+
+    for day_rooms in rooms_by_day(&data.rooms) {
+        let first = day_rooms[0];
+        document.add_page(page);
+        let mut y = PAGE_MARGIN + 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            18.0,
+            PdfFontWeight::Bold,
+            "ใบลงชื่อกรรมการคุมสอบ",
+        );
+        y += 20.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            13.0,
+            PdfFontWeight::Regular,
+            &data.round_name,
+        );
+        y += 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            12.0,
+            PdfFontWeight::Regular,
+            &day_heading(first),
+        );
+        y += 14.0;
+
+        let mut rows = Vec::new();
+        for room in &day_rooms {
+            let span = session_span(&room.sessions)
+                .map(|(starts_at, ends_at)| time_range(starts_at, ends_at))
+                .unwrap_or_else(|| "-".to_string());
+            let blank_rows = room
+                .required_invigilators
+                .saturating_sub(room.invigilators.len());
+            let names = room
+                .invigilators
+                .iter()
+                .cloned()
+                .chain(std::iter::repeat_n(String::new(), blank_rows));
+            for name in names {
+                rows.push(vec![
+                    room_location(room),
+                    room.classroom_name.clone(),
+                    span.clone(),
+                    name,
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ]);
+            }
+        }
+        let continued = format!("ใบลงชื่อกรรมการคุมสอบ {} (ต่อ)", day_heading(first));
+        draw_table(&mut document, page, &columns, &rows, y, &continued);
+    }
+    document
+}
+
+pub(super) fn render_attendance_sheets(data: &ExamDayDocumentData) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let mut document = PdfDocument::new(format!("บัญชีลงชื่อผู้เข้าสอบ {}", data.round_name));
+    let columns = [
+        TableColumn::new("ที่นั่ง", 50.0, PdfTextAlign::Center),
+        TableColumn::new("เลขประจำตัว", 85.0, PdfTextAlign::Center),
+        TableColumn::new("ชื่อ–สกุล", 190.0, PdfTextAlign::Left),
+        TableColumn::new("ลายมือชื่อ", 125.0, PdfTextAlign::Left),
+        TableColumn::new(
+            "หมายเหตุ",
+            page.width - 2.0 * PAGE_MARGIN - 450.0,
+            PdfTextAlign::Left,
+        ),
+    ];
+
+    for room in &data.rooms {
+        for session in &room.sessions {
+            document.add_page(page);
+            let mut y = PAGE_MARGIN + 18.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                18.0,
+                PdfFontWeight::Bold,
+                "บัญชีลงชื่อผู้เข้าสอบ",
+            );
+            y += 20.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                13.0,
+                PdfFontWeight::Regular,
+                &data.round_name,
+            );
+            y += 18.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                13.0,
+                PdfFontWeight::Bold,
+                &format!("{} · {}", session_title(session), session.category_name),
+            );
+            y += 17.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                12.0,
+                PdfFontWeight::Regular,
+                &format!(
+                    "{} เวลา {}",
+                    day_heading(room),
+                    time_range(session.starts_at, session.ends_at)
+                ),
+            );
+            y += 16.0;
+            centered_line(
+                &mut document,
+                page,
+                y,
+                12.0,
+                PdfFontWeight::Regular,
+                &format!("{} · ชั้น {}", room_location(room), room.classroom_name),
+            );
+            y += 12.0;
+
+            let rows: Vec<Vec<String>> = room
+                .students
+                .iter()
+                .map(|student| {
+                    vec![
+                        student.seat_number.clone(),
+                        student.student_code.clone().unwrap_or_default(),
+                        student.student_name.clone(),
+                        String::new(),
+                        String::new(),
+                    ]
+                })
+                .collect();
+            let continued = format!(
+                "{} · {} · {} (ต่อ)",
+                session_title(session),
+                room_location(room),
+                room.classroom_name
+            );
+            y = draw_table(&mut document, page, &columns, &rows, y, &continued);
+
+            let signers: Vec<Option<&String>> = room
+                .invigilators
+                .iter()
+                .map(Some)
+                .chain(std::iter::repeat_n(
+                    None,
+                    room.required_invigilators
+                        .saturating_sub(room.invigilators.len()),
+                ))
+                .collect();
+            let footer_height = 30.0 + 42.0 * signers.len() as f32;
+            if y + footer_height > page.height - PAGE_MARGIN {
+                document.add_page(page);
+                y = PAGE_MARGIN;
+            }
+            y += 22.0;
+            document.text(
+                PAGE_MARGIN,
+                y,
+                12.0,
+                PdfFontWeight::Regular,
+                &format!(
+                    "ผู้มีสิทธิ์สอบ {} คน    เข้าสอบ ............ คน    ขาดสอบ ............ คน",
+                    room.students.len()
+                ),
+            );
+            for signer in signers {
+                y += 28.0;
+                let centre = page.width * 0.68;
+                document.aligned_text(
+                    centre,
+                    y,
+                    12.0,
+                    PdfFontWeight::Regular,
+                    PdfTextAlign::Center,
+                    "ลงชื่อ ........................................ กรรมการคุมสอบ",
+                );
+                let name = signer.map_or_else(
+                    || "(........................................)".to_string(),
+                    |name| format!("({name})"),
+                );
+                document.aligned_text(
+                    centre - 20.0,
+                    y + 14.0,
+                    11.0,
+                    PdfFontWeight::Regular,
+                    PdfTextAlign::Center,
+                    &name,
+                );
+            }
+        }
+    }
+    document
+}
+
+pub(super) fn render_envelope_labels(data: &ExamDayDocumentData) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let mut document = PdfDocument::new(format!("ป้ายซองกระดาษคำตอบ {}", data.round_name));
+    let gap = 10.0;
+    let label_width =
+        (page.width - 2.0 * PAGE_MARGIN - gap * (LABEL_COLUMNS - 1) as f32) / LABEL_COLUMNS as f32;
+    let label_height =
+        (page.height - 2.0 * PAGE_MARGIN - gap * (LABEL_ROWS - 1) as f32) / LABEL_ROWS as f32;
+
+    let labels = data
+        .rooms
+        .iter()
+        .flat_map(|room| room.sessions.iter().map(move |session| (room, session)));
+    for (index, (room, session)) in labels.enumerate() {
+        let (starts_page, column, row) = label_position(index);
+        if starts_page {
+            document.add_page(page);
+        }
+        let x = PAGE_MARGIN + column as f32 * (label_width + gap);
+        let top = PAGE_MARGIN + row as f32 * (label_height + gap);
+        draw_envelope_label(
+            &mut document,
+            x,
+            top,
+            label_width,
+            label_height,
+            &data.round_name,
+            room,
+            session,
+        );
+    }
+    document
+}
+
+/// Where label `index` goes: whether it opens a new page, then its column and row
+pub(super) fn label_position(index: usize) -> (bool, usize, usize) {
+    let slot = index % (LABEL_COLUMNS * LABEL_ROWS);
+    (slot == 0, slot % LABEL_COLUMNS, slot / LABEL_COLUMNS)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_envelope_label(
+    document: &mut PdfDocument,
+    x: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    round_name: &str,
+    room: &DocumentRoom,
+    session: &DocumentSession,
+) {
+    let padding = 10.0;
+    let inner_width = width - 2.0 * padding;
+    let left = x + padding;
+    document.rect(x, top, width, height, 0.8);
+
+    let mut y = top + padding + 12.0;
+    document.text(left, y, 12.0, PdfFontWeight::Bold, "ซองกระดาษคำตอบ");
+    let round = document.fit_text(round_name, 10.0, PdfFontWeight::Regular, inner_width / 2.0);
+    document.aligned_text(
+        x + width - padding,
+        y,
+        10.0,
+        PdfFontWeight::Regular,
+        PdfTextAlign::Right,
+        &round,
+    );
+    y += 6.0;
+    document.line(left, y, x + width - padding, y, 0.5);
+
+    let lines = [
+        (18.0, PdfFontWeight::Bold, session.subject_code.clone()),
+        (13.0, PdfFontWeight::Regular, session.subject_name.clone()),
+        (11.0, PdfFontWeight::Regular, session.category_name.clone()),
+        (11.0, PdfFontWeight::Regular, day_heading(room)),
+        (
+            11.0,
+            PdfFontWeight::Regular,
+            format!("เวลา {}", time_range(session.starts_at, session.ends_at)),
+        ),
+        (
+            11.0,
+            PdfFontWeight::Regular,
+            format!("{} · ชั้น {}", room_location(room), room.classroom_name),
+        ),
+        (
+            14.0,
+            PdfFontWeight::Bold,
+            format!("จำนวนนักเรียน {} คน", room.students.len()),
+        ),
+    ];
+    for (size, weight, text) in lines {
+        y += size + 5.0;
+        let text = document.fit_text(&text, size, weight, inner_width);
+        document.text(left, y, size, weight, &text);
+    }
+    if let (Some(first), Some(last)) = (room.students.first(), room.students.last()) {
+        y += 16.0;
+        document.text(
+            left,
+            y,
+            11.0,
+            PdfFontWeight::Regular,
+            &format!("เลขที่นั่ง {}–{}", first.seat_number, last.seat_number),
+        );
+    }
+}
+
+fn draw_room_heading(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    title: &str,
+    data: &ExamDayDocumentData,
+    room: &DocumentRoom,
+) -> f32 {
+    let mut y = PAGE_MARGIN + 18.0;
+    centered_line(document, page, y, 18.0, PdfFontWeight::Bold, title);
+    y += 20.0;
+    centered_line(
+        document,
+        page,
+        y,
+        13.0,
+        PdfFontWeight::Regular,
+        &data.round_name,
+    );
+    y += 18.0;
+    centered_line(
+        document,
+        page,
+        y,
+        12.0,
+        PdfFontWeight::Regular,
+        &format!(
+            "{} · {} · ชั้น {}",
+            day_heading(room),
+            room_location(room),
+            room.classroom_name
+        ),
+    );
+    if !room.sessions.is_empty() {
+        y += 16.0;
+        let sessions = room
+            .sessions
+            .iter()
+            .map(|session| {
+                format!(
+                    "{} {}",
+                    time_range(session.starts_at, session.ends_at),
+                    session.subject_code
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" · ");
+        centered_line(document, page, y, 11.0, PdfFontWeight::Regular, &sessions);
+    }
+    y + 16.0
+}
+
+/// Rooms grouped by exam day, keeping the date order of `rooms`
+fn rooms_by_day(rooms: &[DocumentRoom]) -> Vec<Vec<&DocumentRoom>> {
+    let mut days: Vec<Vec<&DocumentRoom>> = Vec::new();
+    for room in rooms {
+        match days.last_mut() {
+            Some(day) if day[0].exam_day_id == room.exam_day_id => day.push(room),
+            _ => days.push(vec![room]),
+        }
+    }
+    days
+}
+
+fn session_span(sessions: &[DocumentSession]) -> Option<(NaiveTime, NaiveTime)> {
+    let starts_at = sessions.iter().map(|session| session.starts_at).min()?;
+    let ends_at = sessions.iter().map(|session| session.ends_at).max()?;
+    Some((starts_at, ends_at))
+}
+
+fn room_location(room: &DocumentRoom) -> String {
+    match &room.building_name {
+        Some(building_name) => format!("ห้อง {} อาคาร {}", room.room_name, building_name),
+        None => format!("ห้อง {}", room.room_name),
+    }
+}
+
+fn session_title(session: &DocumentSession) -> String {
+    format!("{} {}", session.subject_code, session.subject_name)
+}
+
+pub(super) fn day_heading(room: &DocumentRoom) -> String {
+    let date = thai_weekday_date(room.exam_date);
+    match room.day_label.as_deref().map(str::trim) {
+        Some(label) if !label.is_empty() => format!("{date} ({label})"),
+        _ => date,
+    }
+}
+
+pub(super) fn thai_weekday_date(date: NaiveDate) -> String {
+    const WEEKDAYS: [&str; 7] = ["จันทร์", "อังคาร", "พุธ", "พฤหัสบดี", "ศุกร์", "เสาร์", "อาทิตย์"];
+    format!(
+        "วัน{}ที่ {}",
+        WEEKDAYS[date.weekday().num_days_from_monday() as usize],
+        thai_date(date)
+    )
+}
+
+fn time_range(starts_at: NaiveTime, ends_at: NaiveTime) -> String {
+    format!(
+        "{}–{} น.",
+        starts_at.format("%H:%M"),
+        ends_at.format("%H:%M")
+    )
+}
@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use chrono::NaiveTime;
+use chrono::{NaiveDate, NaiveTime};
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
@@ -10,6 +10,7 @@ use crate::modules::academic::models::exam_schedule::{
     ExamInvigilatorStaffOption, ExamInvigilatorStaffWorkload, ExamInvigilatorView,
     ExamInvigilatorWorkspace, InvigilatorView, UpdateExamInvigilatorsRequest,
 };
+use crate::modules::staff_leave::services::{self as staff_leave_service, portion_covers_window};
 
 use super::room_assignments::{
     fetch_day_room_assignment_view, fetch_seat_assignment_context,
@@ -30,6 +31,7 @@ struct InvigilatorViewRow {
     day_room_assignment_id: Uuid,
     staff_id: Uuid,
     display_name: String,
+    assigned_automatically: bool,
 }
 #[derive(Debug, sqlx::FromRow)]
 struct InvigilatorAssignmentSummaryRow {
@@ -40,6 +42,7 @@ struct InvigilatorAssignmentSummaryRow {
     room_id: Uuid,
     room_name: String,
     session_minutes: i32,
+    required_invigilators: i16,
 }
 #[derive(Debug, sqlx::FromRow)]
 pub(super) struct InvigilatorSessionWindowRow {
@@ -62,6 +65,7 @@ impl InvigilatorViewRow {
         InvigilatorView {
             staff_id: self.staff_id,
             display_name: self.display_name,
+            assigned_automatically: self.assigned_automatically,
         }
     }
 }
@@ -105,6 +109,7 @@ pub async fn get_invigilator_workspace(
                 room_id: row.room_id,
                 room_name: row.room_name,
                 session_minutes: row.session_minutes,
+                required_invigilators: row.required_invigilators,
                 invigilators: invigilators_by_assignment
                     .remove(&row.assignment_id)
                     .unwrap_or_default(),
@@ -219,6 +224,15 @@ pub async fn assign_invigilator_to_assignment(
     let staff_ids = vec![staff_id];
     lock_exam_invigilator_staff_conflict_scope(&mut tx, context.exam_day_id, &staff_ids).await?;
     validate_active_staff_users(&mut tx, &staff_ids).await?;
+    let candidate_windows =
+        fetch_assignment_session_windows(&mut tx, context.assignment_id, &staff_ids).await?;
+    validate_invigilators_not_on_leave(
+        &mut tx,
+        context.assignment_id,
+        &candidate_windows,
+        &staff_ids,
+    )
+    .await?;
 
     let removed_count = delete_staff_invigilator_from_other_day_assignments_in_tx(
         &mut tx,
@@ -348,7 +362,9 @@ async fn insert_staff_invigilator_if_missing_in_tx(
             staff_id
         )
         VALUES ($1, $2, $3)
-        ON CONFLICT (day_room_assignment_id, staff_id) DO NOTHING
+        ON CONFLICT (day_room_assignment_id, staff_id)
+        DO UPDATE SET assigned_automatically = false
+        WHERE academic_exam_day_invigilators.assigned_automatically
         "#,
     )
     .bind(exam_day_id)
@@ -390,7 +406,7 @@ pub(super) fn validate_unique_invigilator_staff_ids(ids: Vec<Uuid>) -> Result<Ve
     }
     Ok(ids)
 }
-async fn validate_active_staff_users(
+pub(super) async fn validate_active_staff_users(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     staff_ids: &[Uuid],
 ) -> Result<(), AppError> {
@@ -525,6 +541,7 @@ pub(super) async fn validate_invigilator_time_conflicts(
     }
 
     let candidate_windows = fetch_assignment_session_windows(tx, assignment_id, staff_ids).await?;
+    validate_invigilators_not_on_leave(tx, assignment_id, &candidate_windows, staff_ids).await?;
     if candidate_windows.is_empty() {
         return Ok(());
     }
@@ -539,6 +556,55 @@ pub(super) async fn validate_invigilator_time_conflicts(
 
     Ok(())
 }
+/// Staff on approved leave cannot invigilate on that exam day. Half-day leave
+/// only blocks the staff member's sessions on its side of noon; before any
+/// session is placed the whole exam day window is checked.
+async fn validate_invigilators_not_on_leave(
+    tx: &mut Transaction<'_, Postgres>,
+    assignment_id: Uuid,
+    candidate_windows: &[InvigilatorSessionWindow],
+    staff_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if staff_ids.is_empty() {
+        return Ok(());
+    }
+
+    let (exam_date, day_starts_at, day_ends_at) =
+        sqlx::query_as::<_, (NaiveDate, NaiveTime, NaiveTime)>(
+            r#"
+            SELECT day.exam_date, day.start_time, day.end_time
+            FROM academic_exam_day_room_assignments assignment
+            JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+            WHERE assignment.id = $1
+            "#,
+        )
+        .bind(assignment_id)
+        .fetch_one(&mut **tx)
+        .await?;
+    let absences =
+        staff_leave_service::approved_absences_on(&mut **tx, staff_ids, exam_date).await?;
+
+    let blocking_absence = absences.iter().find(|absence| {
+        let mut staff_windows = candidate_windows
+            .iter()
+            .filter(|window| window.staff_id == absence.user_id)
+            .peekable();
+        if staff_windows.peek().is_none() {
+            return portion_covers_window(absence.day_portion, day_starts_at, day_ends_at);
+        }
+        staff_windows.any(|window| {
+            portion_covers_window(absence.day_portion, window.starts_at, window.ends_at)
+        })
+    });
+    if let Some(absence) = blocking_absence {
+        return Err(AppError::BadRequest(format!(
+            "Invigilator {} is on approved leave on this exam day",
+            absence.user_name
+        )));
+    }
+
+    Ok(())
+}
 pub(super) fn build_invigilator_candidate_session_windows(
     assignment_id: Uuid,
     exam_day_id: Uuid,
@@ -617,7 +683,8 @@ async fn fetch_invigilator_assignment_summaries(
                assignment.room_id,
                room.name_th AS room_name,
                COALESCE(SUM(EXTRACT(EPOCH FROM (session.ends_at - session.starts_at)) / 60), 0)::INT
-                   AS session_minutes
+                   AS session_minutes,
+               assignment.required_invigilators
         FROM academic_exam_day_room_assignments assignment
         JOIN academic_exam_days day ON day.id = assignment.exam_day_id
         JOIN class_rooms classroom ON classroom.id = assignment.classroom_id
@@ -630,7 +697,7 @@ async fn fetch_invigilator_assignment_summaries(
          AND session.exam_day_id = assignment.exam_day_id
          AND session.exam_round_id = day.exam_round_id
         WHERE day.exam_round_id = $1
-        GROUP BY assignment.id, day.id, assignment.classroom_id, classroom.name, assignment.room_id, room.name_th
+        GROUP BY assignment.id, day.id, assignment.classroom_id, classroom.name, assignment.room_id, room.name_th, assignment.required_invigilators
         ORDER BY day.exam_date, day.start_time, day.id, classroom.name, room.name_th, assignment.id
         "#,
     )
@@ -760,7 +827,8 @@ pub(super) async fn fetch_invigilator_views_by_assignment_ids(
                    ),
                    NULLIF(TRIM(user_account.last_name), '')
                )
-                   AS display_name
+                   AS display_name,
+               invigilator.assigned_automatically
         FROM academic_exam_day_invigilators invigilator
         JOIN users user_account ON user_account.id = invigilator.staff_id
         WHERE invigilator.day_room_assignment_id = ANY($1)
@@ -0,0 +1,660 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{NaiveDate, NaiveTime};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::academic::models::exam_schedule::{
+    AutoAssignInvigilatorsRequest, AutoAssignInvigilatorsResult, AutoAssignStaffTotal,
+    AutoAssignedInvigilator, UnfilledInvigilatorSlot,
+};
+use crate::modules::staff_leave::services::{self as staff_leave_service, portion_covers_window};
+
+use super::invigilation::{
+    lock_exam_invigilator_staff_conflict_scope, validate_active_staff_users,
+    validate_invigilator_time_conflicts,
+};
+use super::rounds_and_days::{ensure_exam_round_is_mutable, mark_round_draft_after_mutation};
+use super::shared::{minutes_between_times, time_ranges_overlap, unique_uuids};
+
+/// One room assignment that needs invigilators
+#[derive(Debug, Clone)]
+pub(super) struct InvigilationSlot {
+    pub(super) assignment_id: Uuid,
+    pub(super) exam_day_id: Uuid,
+    pub(super) required: i32,
+    pub(super) session_windows: Vec<(NaiveTime, NaiveTime)>,
+    pub(super) subject_ids: Vec<Uuid>,
+    /// Hand-picked invigilators; they count toward `required` and are never moved
+    pub(super) pinned_staff_ids: Vec<Uuid>,
+}
+
+impl InvigilationSlot {
+    fn session_minutes(&self) -> i32 {
+        self.session_windows
+            .iter()
+            .map(|(starts_at, ends_at)| minutes_between_times(*starts_at, *ends_at))
+            .sum()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct InvigilationConstraints {
+    /// Timetabled teaching per (exam day, staff) for classes that are not sitting exams
+    pub(super) teaching_windows: HashMap<(Uuid, Uuid), Vec<(NaiveTime, NaiveTime)>>,
+    /// (assignment, staff) pairs blocked by approved leave
+    pub(super) on_leave: HashSet<(Uuid, Uuid)>,
+    /// (staff, subject) pairs the staff member teaches this semester
+    pub(super) subject_teachers: HashSet<(Uuid, Uuid)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct UnfilledSlot {
+    pub(super) assignment_id: Uuid,
+    pub(super) missing: i32,
+    pub(super) busy_overlapping: usize,
+    pub(super) on_leave: usize,
+    pub(super) teaching: usize,
+    pub(super) teaches_subject: usize,
+}
+
+impl UnfilledSlot {
+    fn reason(&self) -> String {
+        let excluded = [
+            (self.busy_overlapping, "invigilating an overlapping session"),
+            (self.on_leave, "on approved leave"),
+            (self.teaching, "teaching during the session"),
+            (
+                self.teaches_subject,
+                "teach a subject examined in this room",
+            ),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>();
+        if excluded.is_empty() {
+            return "No staff are available to draw from".to_string();
+        }
+        format!("No eligible staff left: {}", excluded.join(", "))
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct InvigilatorPlan {
+    /// (assignment, staff) picks in the order they were made
+    pub(super) picks: Vec<(Uuid, Uuid)>,
+    pub(super) unfilled: Vec<UnfilledSlot>,
+    /// Minutes per staff member, pinned and picked together
+    pub(super) total_minutes: HashMap<Uuid, i32>,
+    pub(super) assignment_counts: HashMap<Uuid, i32>,
+}
+
+enum Exclusion {
+    BusyOverlapping,
+    OnLeave,
+    Teaching,
+    TeachesSubject,
+}
+
+/// Fills every slot's missing seats from `staff_ids`, longest slots first, each
+/// time picking the eligible staff member with the fewest minutes so far. This
+/// longest-first greedy keeps the spread between the busiest and idlest staff
+/// small. A staff member may cover several rooms on one exam day as long as
+/// their sessions do not overlap, matching `validate_invigilator_time_conflicts`.
+pub(super) fn plan_invigilator_assignment(
+    slots: &[InvigilationSlot],
+    staff_ids: &[Uuid],
+    constraints: &InvigilationConstraints,
+) -> InvigilatorPlan {
+    let mut plan = InvigilatorPlan::default();
+    let mut busy_windows: HashMap<(Uuid, Uuid), Vec<(NaiveTime, NaiveTime)>> = HashMap::new();
+    for staff_id in staff_ids {
+        plan.total_minutes.insert(*staff_id, 0);
+        plan.assignment_counts.insert(*staff_id, 0);
+    }
+    for slot in slots {
+        let minutes = slot.session_minutes();
+        for staff_id in &slot.pinned_staff_ids {
+            busy_windows
+                .entry((slot.exam_day_id, *staff_id))
+                .or_default()
+                .extend(slot.session_windows.iter().copied());
+            *plan.total_minutes.entry(*staff_id).or_default() += minutes;
+            *plan.assignment_counts.entry(*staff_id).or_default() += 1;
+        }
+    }
+
+    let mut ordered: Vec<&InvigilationSlot> = slots
+        .iter()
+        .filter(|slot| !slot.session_windows.is_empty())
+        .collect();
+    ordered.sort_by_key(|slot| {
+        (
+            std::cmp::Reverse(slot.session_minutes()),
+            slot.exam_day_id,
+            slot.assignment_id,
+        )
+    });
+
+    for slot in ordered {
+        let minutes = slot.session_minutes();
+        let missing = slot.required - slot.pinned_staff_ids.len() as i32;
+        for seat in 0..missing.max(0) {
+            let mut unfilled = UnfilledSlot {
+                assignment_id: slot.assignment_id,
+                missing: missing - seat,
+                busy_overlapping: 0,
+                on_leave: 0,
+                teaching: 0,
+                teaches_subject: 0,
+            };
+            let mut best: Option<Uuid> = None;
+            for staff_id in staff_ids {
+                match exclusion(slot, *staff_id, &busy_windows, constraints) {
+                    Some(Exclusion::BusyOverlapping) => unfilled.busy_overlapping += 1,
+                    Some(Exclusion::OnLeave) => unfilled.on_leave += 1,
+                    Some(Exclusion::Teaching) => unfilled.teaching += 1,
+                    Some(Exclusion::TeachesSubject) => unfilled.teaches_subject += 1,
+                    None => {
+                        let key = |id: &Uuid| {
+                            (
+                                plan.total_minutes.get(id).copied().unwrap_or(0),
+                                plan.assignment_counts.get(id).copied().unwrap_or(0),
+                                *id,
+                            )
+                        };
+                        if best.is_none_or(|current| key(staff_id) < key(&current)) {
+                            best = Some(*staff_id);
+                        }
+                    }
+                }
+            }
+
+            let Some(staff_id) = best else {
+                plan.unfilled.push(unfilled);
+                break;
+            };
+            busy_windows
+                .entry((slot.exam_day_id, staff_id))
+                .or_default()
+                .extend(slot.session_windows.iter().copied());
+            *plan.total_minutes.entry(staff_id).or_default() += minutes;
+            *plan.assignment_counts.entry(staff_id).or_default() += 1;
+            plan.picks.push((slot.assignment_id, staff_id));
+        }
+    }
+
+    plan
+}
+
+fn exclusion(
+    slot: &InvigilationSlot,
+    staff_id: Uuid,
+    busy_windows: &HashMap<(Uuid, Uuid), Vec<(NaiveTime, NaiveTime)>>,
+    constraints: &InvigilationConstraints,
+) -> Option<Exclusion> {
+    let overlaps = |windows: &Vec<(NaiveTime, NaiveTime)>| {
+        windows.iter().any(|(busy_start, busy_end)| {
+            slot.session_windows.iter().any(|(starts_at, ends_at)| {
+                time_ranges_overlap(*starts_at, *ends_at, *busy_start, *busy_end)
+            })
+        })
+    };
+    if busy_windows
+        .get(&(slot.exam_day_id, staff_id))
+        .is_some_and(overlaps)
+    {
+        return Some(Exclusion::BusyOverlapping);
+    }
+    if constraints
+        .on_leave
+        .contains(&(slot.assignment_id, staff_id))
+    {
+        return Some(Exclusion::OnLeave);
+    }
+    if constraints
+        .teaching_windows
+        .get(&(slot.exam_day_id, staff_id))
+        .is_some_and(overlaps)
+    {
+        return Some(Exclusion::Teaching);
+    }
+    if slot.subject_ids.iter().any(|subject_id| {
+        constraints
+            .subject_teachers
+            .contains(&(staff_id, *subject_id))
+    }) {
+        return Some(Exclusion::TeachesSubject);
+    }
+    None
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AutoAssignRoundRow {
+    status: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AutoAssignSlotRow {
+    assignment_id: Uuid,
+    exam_day_id: Uuid,
+    exam_date: NaiveDate,
+    classroom_name: String,
+    room_name: String,
+    required_invigilators: i16,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AutoAssignSessionRow {
+    assignment_id: Uuid,
+    starts_at: NaiveTime,
+    ends_at: NaiveTime,
+    subject_id: Uuid,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AutoAssignStaffRow {
+    staff_id: Uuid,
+    display_name: String,
+}
+
+pub async fn auto_assign_invigilators(
+    pool: &PgPool,
+    round_id: Uuid,
+    request: AutoAssignInvigilatorsRequest,
+    actor_user_id: Uuid,
+) -> Result<AutoAssignInvigilatorsResult, AppError> {
+    let requested_staff_ids = request.staff_ids.map(unique_uuids);
+    let mut tx = pool.begin().await?;
+
+    let round = sqlx::query_as::<_, AutoAssignRoundRow>(
+        r#"
+        SELECT status
+        FROM academic_exam_rounds
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(round_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Exam round not found".to_string()))?;
+    ensure_exam_round_is_mutable(&round.status)?;
+
+    if let Some(staff_ids) = &requested_staff_ids {
+        validate_active_staff_users(&mut tx, staff_ids).await?;
+    }
+    let staff = sqlx::query_as::<_, AutoAssignStaffRow>(
+        r#"
+        SELECT user_account.id AS staff_id,
+               COALESCE(
+                   NULLIF(
+                       concat_ws(
+                           ' ',
+                           NULLIF(
+                               concat_ws('', NULLIF(TRIM(user_account.title), ''), NULLIF(TRIM(user_account.first_name), '')),
+                               ''
+                           ),
+                           NULLIF(TRIM(user_account.last_name), '')
+                       ),
+                       ''
+                   ),
+                   user_account.id::TEXT
+               ) AS display_name
+        FROM users user_account
+        WHERE user_account.user_type = 'staff'
+          AND user_account.status = 'active'
+          AND ($1::uuid[] IS NULL OR user_account.id = ANY($1))
+        ORDER BY user_account.first_name, user_account.last_name, user_account.id
+        "#,
+    )
+    .bind(requested_staff_ids.as_deref())
+    .fetch_all(&mut *tx)
+    .await?;
+    let staff_ids: Vec<Uuid> = staff.iter().map(|row| row.staff_id).collect();
+
+    let slot_rows = sqlx::query_as::<_, AutoAssignSlotRow>(
+        r#"
+        SELECT assignment.id AS assignment_id,
+               assignment.exam_day_id,
+               day.exam_date,
+               classroom.name AS classroom_name,
+               room.name_th AS room_name,
+               assignment.required_invigilators
+        FROM academic_exam_day_room_assignments assignment
+        JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+        JOIN class_rooms classroom ON classroom.id = assignment.classroom_id
+        JOIN rooms room ON room.id = assignment.room_id
+        WHERE day.exam_round_id = $1
+        ORDER BY day.exam_date, day.start_time, classroom.name, assignment.id
+        FOR UPDATE OF assignment
+        "#,
+    )
+    .bind(round_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let session_rows = sqlx::query_as::<_, AutoAssignSessionRow>(
+        r#"
+        SELECT assignment.id AS assignment_id,
+               session.starts_at,
+               session.ends_at,
+               item.subject_id
+        FROM academic_exam_day_room_assignments assignment
+        JOIN academic_exam_days day ON day.id = assignment.exam_day_id
+        JOIN academic_exam_sessions session
+          ON session.exam_day_id = assignment.exam_day_id
+         AND session.exam_round_id = day.exam_round_id
+        JOIN academic_exam_schedule_items item
+          ON item.id = session.exam_schedule_item_id
+         AND item.classroom_id = assignment.classroom_id
+        WHERE day.exam_round_id = $1
+        ORDER BY assignment.id, session.starts_at
+        "#,
+    )
+    .bind(round_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let pinned_rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT invigilator.day_room_assignment_id,
+               invigilator.staff_id
+        FROM academic_exam_day_invigilators invigilator
+        JOIN academic_exam_days day ON day.id = invigilator.exam_day_id
+        WHERE day.exam_round_id = $1
+          AND NOT invigilator.assigned_automatically
+        "#,
+    )
+    .bind(round_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut slots: BTreeMap<Uuid, InvigilationSlot> = slot_rows
+        .iter()
+        .map(|row| {
+            (
+                row.assignment_id,
+                InvigilationSlot {
+                    assignment_id: row.assignment_id,
+                    exam_day_id: row.exam_day_id,
+                    required: i32::from(row.required_invigilators),
+                    session_windows: Vec::new(),
+                    subject_ids: Vec::new(),
+                    pinned_staff_ids: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    for row in session_rows {
+        if let Some(slot) = slots.get_mut(&row.assignment_id) {
+            slot.session_windows.push((row.starts_at, row.ends_at));
+            if !slot.subject_ids.contains(&row.subject_id) {
+                slot.subject_ids.push(row.subject_id);
+            }
+        }
+    }
+    for (assignment_id, staff_id) in pinned_rows {
+        if let Some(slot) = slots.get_mut(&assignment_id) {
+            slot.pinned_staff_ids.push(staff_id);
+        }
+    }
+    let slots: Vec<InvigilationSlot> = slots.into_values().collect();
+
+    let constraints = fetch_invigilation_constraints(
+        &mut tx,
+        round_id,
+        &staff_ids,
+        &slots,
+        &slot_rows
+            .iter()
+            .map(|row| (row.exam_day_id, row.exam_date))
+            .collect(),
+    )
+    .await?;
+    let plan = plan_invigilator_assignment(&slots, &staff_ids, &constraints);
+
+    if !request.dry_run {
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM academic_exam_day_invigilators invigilator
+            USING academic_exam_days day
+            WHERE day.id = invigilator.exam_day_id
+              AND day.exam_round_id = $1
+              AND invigilator.assigned_automatically
+            "#,
+        )
+        .bind(round_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let mut picks_by_assignment: BTreeMap<Uuid, Vec<Uuid>> = BTreeMap::new();
+        for (assignment_id, staff_id) in &plan.picks {
+            picks_by_assignment
+                .entry(*assignment_id)
+                .or_default()
+                .push(*staff_id);
+        }
+        let day_by_assignment: HashMap<Uuid, Uuid> = slots
+            .iter()
+            .map(|slot| (slot.assignment_id, slot.exam_day_id))
+            .collect();
+        for (assignment_id, picked_staff_ids) in &picks_by_assignment {
+            let exam_day_id = day_by_assignment[assignment_id];
+            lock_exam_invigilator_staff_conflict_scope(&mut tx, exam_day_id, picked_staff_ids)
+                .await?;
+            validate_invigilator_time_conflicts(
+                &mut tx,
+                round_id,
+                *assignment_id,
+                picked_staff_ids,
+            )
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO academic_exam_day_invigilators (
+                    exam_day_id,
+                    day_room_assignment_id,
+                    staff_id,
+                    assigned_automatically
+                )
+                SELECT $1, $2, staff_id, true
+                FROM unnest($3::uuid[]) AS staff_id
+                "#,
+            )
+            .bind(exam_day_id)
+            .bind(assignment_id)
+            .bind(picked_staff_ids)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if removed > 0 || !plan.picks.is_empty() {
+            mark_round_draft_after_mutation(&mut tx, round_id, Some(actor_user_id)).await?;
+        }
+        tx.commit().await?;
+    }
+
+    let slot_rows_by_id: HashMap<Uuid, &AutoAssignSlotRow> = slot_rows
+        .iter()
+        .map(|row| (row.assignment_id, row))
+        .collect();
+    let minutes_by_assignment: HashMap<Uuid, i32> = slots
+        .iter()
+        .map(|slot| (slot.assignment_id, slot.session_minutes()))
+        .collect();
+    let staff_names: HashMap<Uuid, &str> = staff
+        .iter()
+        .map(|row| (row.staff_id, row.display_name.as_str()))
+        .collect();
+
+    let assigned = plan
+        .picks
+        .iter()
+        .map(|(assignment_id, staff_id)| {
+            let slot_row = slot_rows_by_id[assignment_id];
+            AutoAssignedInvigilator {
+                assignment_id: *assignment_id,
+                exam_day_id: slot_row.exam_day_id,
+                classroom_name: slot_row.classroom_name.clone(),
+                room_name: slot_row.room_name.clone(),
+                staff_id: *staff_id,
+                staff_name: staff_names[staff_id].to_string(),
+                session_minutes: minutes_by_assignment[assignment_id],
+            }
+        })
+        .collect();
+    let unfilled = plan
+        .unfilled
+        .iter()
+        .map(|slot| {
+            let slot_row = slot_rows_by_id[&slot.assignment_id];
+            UnfilledInvigilatorSlot {
+                assignment_id: slot.assignment_id,
+                exam_day_id: slot_row.exam_day_id,
+                classroom_name: slot_row.classroom_name.clone(),
+                room_name: slot_row.room_name.clone(),
+                missing_count: slot.missing,
+                reason: slot.reason(),
+            }
+        })
+        .collect();
+    let mut staff_totals: Vec<AutoAssignStaffTotal> = staff
+        .iter()
+        .map(|row| AutoAssignStaffTotal {
+            staff_id: row.staff_id,
+            staff_name: row.display_name.clone(),
+            total_minutes: plan.total_minutes.get(&row.staff_id).copied().unwrap_or(0),
+            assignment_count: plan
+                .assignment_counts
+                .get(&row.staff_id)
+                .copied()
+                .unwrap_or(0),
+        })
+        .collect();
+    staff_totals.sort_by(|left, right| {
+        right
+            .total_minutes
+            .cmp(&left.total_minutes)
+            .then_with(|| left.staff_name.cmp(&right.staff_name))
+    });
+
+    Ok(AutoAssignInvigilatorsResult {
+        round_id,
+        dry_run: request.dry_run,
+        assigned,
+        unfilled,
+        min_total_minutes: staff_totals
+            .iter()
+            .map(|total| total.total_minutes)
+            .min()
+            .unwrap_or(0),
+        max_total_minutes: staff_totals
+            .iter()
+            .map(|total| total.total_minutes)
+            .max()
+            .unwrap_or(0),
+        staff_totals,
+    })
+}
+
+async fn fetch_invigilation_constraints(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    round_id: Uuid,
+    staff_ids: &[Uuid],
+    slots: &[InvigilationSlot],
+    exam_dates: &HashMap<Uuid, NaiveDate>,
+) -> Result<InvigilationConstraints, AppError> {
+    let mut constraints = InvigilationConstraints::default();
+    if staff_ids.is_empty() {
+        return Ok(constraints);
+    }
+
+    // Regular lessons still run for classes that have no exam room that day.
+    let teaching_rows: Vec<(Uuid, Uuid, NaiveTime, NaiveTime)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT day.id,
+               entry_instructor.instructor_id,
+               period.start_time,
+               period.end_time
+        FROM academic_exam_days day
+        JOIN academic_exam_rounds round ON round.id = day.exam_round_id
+        JOIN academic_timetable_entries entry
+          ON entry.academic_semester_id = round.academic_semester_id
+         AND entry.is_active
+         AND entry.day_of_week = to_char(day.exam_date, 'DY')
+        JOIN timetable_entry_instructors entry_instructor ON entry_instructor.entry_id = entry.id
+        JOIN academic_periods period ON period.id = entry.period_id
+        WHERE day.exam_round_id = $1
+          AND entry_instructor.instructor_id = ANY($2)
+          AND NOT EXISTS (
+              SELECT 1
+              FROM academic_exam_day_room_assignments assignment
+              WHERE assignment.exam_day_id = day.id
+                AND assignment.classroom_id = entry.classroom_id
+          )
+        "#,
+    )
+    .bind(round_id)
+    .bind(staff_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    for (exam_day_id, staff_id, starts_at, ends_at) in teaching_rows {
+        constraints
+            .teaching_windows
+            .entry((exam_day_id, staff_id))
+            .or_default()
+            .push((starts_at, ends_at));
+    }
+
+    let subject_rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT teacher.staff_id,
+               course.subject_id
+        FROM academic_exam_rounds round
+        JOIN classroom_courses course
+          ON course.academic_semester_id = round.academic_semester_id
+        CROSS JOIN LATERAL (
+            SELECT course.primary_instructor_id
+            UNION
+            SELECT instructor.instructor_id
+            FROM classroom_course_instructors instructor
+            WHERE instructor.classroom_course_id = course.id
+        ) AS teacher(staff_id)
+        WHERE round.id = $1
+          AND teacher.staff_id = ANY($2)
+        "#,
+    )
+    .bind(round_id)
+    .bind(staff_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+    constraints.subject_teachers = subject_rows.into_iter().collect();
+
+    let mut dates: Vec<(Uuid, NaiveDate)> = exam_dates
+        .iter()
+        .map(|(day_id, date)| (*day_id, *date))
+        .collect();
+    dates.sort_by_key(|(day_id, date)| (*date, *day_id));
+    for (exam_day_id, exam_date) in dates {
+        let absences =
+            staff_leave_service::approved_absences_on(&mut **tx, staff_ids, exam_date).await?;
+        for absence in absences {
+            for slot in slots.iter().filter(|slot| slot.exam_day_id == exam_day_id) {
+                let blocked = slot.session_windows.iter().any(|(starts_at, ends_at)| {
+                    portion_covers_window(absence.day_portion, *starts_at, *ends_at)
+                });
+                if blocked {
+                    constraints
+                        .on_leave
+                        .insert((slot.assignment_id, absence.user_id));
+                }
+            }
+        }
+    }
+
+    Ok(constraints)
+}
@@ -26,6 +26,7 @@ struct DayRoomAssignmentViewRow {
     building_name: Option<String>,
     room_capacity: Option<i32>,
     capacity_override: Option<i32>,
+    required_invigilators: i16,
     seats_generated: bool,
 }
 #[derive(Debug, sqlx::FromRow)]
@@ -61,6 +62,7 @@ impl DayRoomAssignmentViewRow {
             building_name: self.building_name,
             room_capacity: self.room_capacity,
             capacity_override: self.capacity_override,
+            required_invigilators: self.required_invigilators,
             invigilators,
             seats_generated: self.seats_generated,
         }
@@ -136,6 +138,7 @@ pub async fn upsert_day_room_assignment(
         .map(|ids| validate_unique_invigilator_staff_ids(ids.clone()))
         .transpose()?;
     let capacity_override = validate_capacity_override(request.capacity_override)?;
+    let required_invigilators = validate_required_invigilators(request.required_invigilators)?;
 
     let mut tx = pool.begin().await?;
     let day_context = fetch_exam_day_context_for_update(&mut tx, exam_day_id).await?;
@@ -170,14 +173,19 @@ pub async fn upsert_day_room_assignment(
             classroom_id,
             room_id,
             capacity_override,
+            required_invigilators,
             created_by,
             updated_by
         )
-        VALUES ($1, $2, $3, $4, $5, $5)
+        VALUES ($1, $2, $3, $4, COALESCE($6, 1), $5, $5)
         ON CONFLICT (exam_day_id, classroom_id)
         DO UPDATE SET
             room_id = EXCLUDED.room_id,
             capacity_override = EXCLUDED.capacity_override,
+            required_invigilators = COALESCE(
+                $6,
+                academic_exam_day_room_assignments.required_invigilators
+            ),
             updated_by = EXCLUDED.updated_by,
             updated_at = now()
         RETURNING id
@@ -188,6 +196,7 @@ pub async fn upsert_day_room_assignment(
     .bind(request.room_id)
     .bind(capacity_override)
     .bind(actor_user_id)
+    .bind(required_invigilators)
     .fetch_one(&mut *tx)
     .await
     .map_err(map_day_room_assignment_write_error)?;
@@ -298,6 +307,91 @@ pub async fn generate_seats_for_assignment(
 
     Ok(seats)
 }
+/// Makes sure `classroom_id` sits in `room_id` on the day, creating the assignment
+/// with the same checks as `upsert_day_room_assignment` when it is missing.
+/// Returns whether an assignment was created.
+pub(super) async fn ensure_day_room_assignment_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    exam_round_id: Uuid,
+    exam_day_id: Uuid,
+    classroom_id: Uuid,
+    room_id: Uuid,
+    actor_user_id: Uuid,
+) -> Result<bool, AppError> {
+    let day_context = fetch_exam_day_context_for_update(tx, exam_day_id).await?;
+    if day_context.exam_round_id != exam_round_id {
+        return Err(AppError::BadRequest(
+            "Exam day belongs to a different exam round".to_string(),
+        ));
+    }
+
+    let existing_room_id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT room_id
+        FROM academic_exam_day_room_assignments
+        WHERE exam_day_id = $1
+          AND classroom_id = $2
+        FOR UPDATE
+        "#,
+    )
+    .bind(exam_day_id)
+    .bind(classroom_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+    if let Some(existing_room_id) = existing_room_id {
+        if existing_room_id != room_id {
+            return Err(AppError::Conflict(
+                "Classroom was assigned a different exam room after the proposal was built"
+                    .to_string(),
+            ));
+        }
+        return Ok(false);
+    }
+
+    let classroom = fetch_classroom_assignment_context(tx, classroom_id).await?;
+    if classroom.is_active != Some(true) {
+        return Err(AppError::BadRequest(
+            "Classroom must be active before assigning an exam room".to_string(),
+        ));
+    }
+    validate_day_allows_grade_level(tx, exam_day_id, classroom.grade_level_id).await?;
+
+    let room = fetch_room_assignment_context(tx, room_id).await?;
+    if room.status != "ACTIVE" {
+        return Err(AppError::BadRequest(
+            "Room must be ACTIVE before assigning it to an exam day".to_string(),
+        ));
+    }
+    let active_student_count = count_active_classroom_students(tx, classroom_id).await?;
+    if active_student_count > i64::from(room.capacity) {
+        return Err(AppError::BadRequest(format!(
+            "Classroom has {active_student_count} active student(s), which exceeds the room capacity of {}",
+            room.capacity
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO academic_exam_day_room_assignments (
+            exam_day_id,
+            classroom_id,
+            room_id,
+            created_by,
+            updated_by
+        )
+        VALUES ($1, $2, $3, $4, $4)
+        "#,
+    )
+    .bind(exam_day_id)
+    .bind(classroom_id)
+    .bind(room_id)
+    .bind(actor_user_id)
+    .execute(&mut **tx)
+    .await
+    .map_err(map_day_room_assignment_write_error)?;
+
+    Ok(true)
+}
 fn validate_capacity_override(capacity_override: Option<i32>) -> Result<Option<i32>, AppError> {
     if matches!(capacity_override, Some(value) if value <= 0) {
         return Err(AppError::BadRequest(
@@ -306,6 +400,16 @@ fn validate_capacity_override(capacity_override: Option<i32>) -> Result<Option<i
     }
     Ok(capacity_override)
 }
+fn validate_required_invigilators(
+    required_invigilators: Option<i16>,
+) -> Result<Option<i16>, AppError> {
+    if matches!(required_invigilators, Some(value) if !(1..=5).contains(&value)) {
+        return Err(AppError::BadRequest(
+            "Required invigilators must be between 1 and 5".to_string(),
+        ));
+    }
+    Ok(required_invigilators)
+}
 async fn fetch_classroom_assignment_context(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     classroom_id: Uuid,
@@ -375,6 +479,7 @@ async fn fetch_day_room_assignment_views_for_day(
                building.name_th AS building_name,
                room.capacity AS room_capacity,
                assignment.capacity_override,
+               assignment.required_invigilators,
                EXISTS (
                    SELECT 1
                    FROM academic_exam_seat_assignments seat
@@ -409,6 +514,7 @@ pub(super) async fn fetch_day_room_assignment_view(
                building.name_th AS building_name,
                room.capacity AS room_capacity,
                assignment.capacity_override,
+               assignment.required_invigilators,
                EXISTS (
                    SELECT 1
                    FROM academic_exam_seat_assignments seat
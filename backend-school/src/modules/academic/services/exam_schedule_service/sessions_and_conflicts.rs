@@ -138,17 +138,30 @@ pub async fn place_exam_session(
 ) -> Result<ExamSessionView, AppError> {
     let mut tx = pool.begin().await?;
 
-    let item =
-        fetch_schedule_item_placement_context(&mut tx, request.exam_schedule_item_id).await?;
-    let day = fetch_exam_day_placement_context(&mut tx, request.exam_day_id).await?;
+    let (session_id, round_id) = place_exam_session_in_tx(&mut tx, &request, actor_user_id).await?;
+
+    mark_round_draft_after_mutation(&mut tx, round_id, Some(actor_user_id)).await?;
+    tx.commit().await?;
+
+    fetch_exam_session_view(pool, session_id).await
+}
+/// Validates and writes one session inside the caller's transaction, returning
+/// the session id and its round. The caller marks the round draft and commits.
+pub(super) async fn place_exam_session_in_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    request: &PlaceExamSessionRequest,
+    actor_user_id: Uuid,
+) -> Result<(Uuid, Uuid), AppError> {
+    let item = fetch_schedule_item_placement_context(tx, request.exam_schedule_item_id).await?;
+    let day = fetch_exam_day_placement_context(tx, request.exam_day_id).await?;
     if day.exam_round_id != item.exam_round_id {
         return Err(AppError::BadRequest(
             "Exam day belongs to a different exam round".to_string(),
         ));
     }
 
-    validate_day_allows_grade_level(&mut tx, day.id, item.grade_level_id).await?;
-    let blocked_windows = fetch_blocked_windows_for_day_for_placement(&mut tx, day.id).await?;
+    validate_day_allows_grade_level(tx, day.id, item.grade_level_id).await?;
+    let blocked_windows = fetch_blocked_windows_for_day_for_placement(tx, day.id).await?;
     let ends_at = validate_session_window(
         request.starts_at,
         item.duration_minutes,
@@ -159,16 +172,11 @@ pub async fn place_exam_session(
     .map_err(validation_error_to_app_error)?;
 
     let day_room_assignment =
-        fetch_day_room_assignment_placement_context(&mut tx, day.id, item.classroom_id).await?;
-    lock_exam_session_conflict_scope(
-        &mut tx,
-        day.id,
-        item.classroom_id,
-        day_room_assignment.room_id,
-    )
-    .await?;
+        fetch_day_room_assignment_placement_context(tx, day.id, item.classroom_id).await?;
+    lock_exam_session_conflict_scope(tx, day.id, item.classroom_id, day_room_assignment.room_id)
+        .await?;
     let existing_session_id =
-        fetch_existing_session_id_for_item(&mut tx, request.exam_schedule_item_id).await?;
+        fetch_existing_session_id_for_item(tx, request.exam_schedule_item_id).await?;
 
     let candidate = CandidateSession {
         session_id: existing_session_id,
@@ -177,7 +185,7 @@ pub async fn place_exam_session(
         starts_at: request.starts_at,
         ends_at,
     };
-    let existing_classroom_sessions = fetch_candidate_sessions_for_day(&mut tx, day.id).await?;
+    let existing_classroom_sessions = fetch_candidate_sessions_for_day(tx, day.id).await?;
     if has_same_classroom_conflict(&candidate, &existing_classroom_sessions) {
         return Err(AppError::BadRequest(
             "Classroom already has an exam session during this time".to_string(),
@@ -191,7 +199,7 @@ pub async fn place_exam_session(
         starts_at: request.starts_at,
         ends_at,
     };
-    let existing_room_sessions = fetch_candidate_room_sessions_for_day(&mut tx, day.id).await?;
+    let existing_room_sessions = fetch_candidate_room_sessions_for_day(tx, day.id).await?;
     if has_same_room_conflict(&room_candidate, &existing_room_sessions) {
         return Err(AppError::BadRequest(
             "Room already has an exam session during this time".to_string(),
@@ -199,10 +207,10 @@ pub async fn place_exam_session(
     }
 
     let invigilator_staff_ids =
-        fetch_invigilator_staff_ids_for_assignment(&mut tx, day_room_assignment.id).await?;
-    lock_exam_invigilator_staff_conflict_scope(&mut tx, day.id, &invigilator_staff_ids).await?;
+        fetch_invigilator_staff_ids_for_assignment(tx, day_room_assignment.id).await?;
+    lock_exam_invigilator_staff_conflict_scope(tx, day.id, &invigilator_staff_ids).await?;
     validate_invigilator_candidate_session_conflicts(
-        &mut tx,
+        tx,
         item.exam_round_id,
         day_room_assignment.id,
         day.id,
@@ -241,13 +249,10 @@ pub async fn place_exam_session(
     .bind(request.starts_at)
     .bind(ends_at)
     .bind(actor_user_id)
-    .fetch_one(&mut *tx)
+    .fetch_one(&mut **tx)
     .await?;
 
-    mark_round_draft_after_mutation(&mut tx, item.exam_round_id, Some(actor_user_id)).await?;
-    tx.commit().await?;
-
-    fetch_exam_session_view(pool, session_id).await
+    Ok((session_id, item.exam_round_id))
 }
 pub async fn delete_exam_session(
     pool: &PgPool,
@@ -6,7 +6,7 @@ use uuid::Uuid;
 use crate::error::AppError;
 use crate::modules::academic::models::exam_schedule::BlockedWindow;
 
-const EXAM_SESSION_SLOT_MINUTES: u32 = 5;
+pub(super) const EXAM_SESSION_SLOT_MINUTES: u32 = 5;
 const EXAM_SESSION_CLASSROOM_LOCK_NAMESPACE: i64 = 0x4558_5343_4C52_0000;
 const EXAM_SESSION_ROOM_LOCK_NAMESPACE: i64 = 0x4558_5352_4F4D_0000;
 const EXAM_INVIGILATOR_STAFF_LOCK_NAMESPACE: i64 = 0x4558_5349_4E56_0000;
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use chrono::NaiveTime;
+use chrono::{NaiveDate, NaiveTime};
 use uuid::Uuid;
 
 use crate::error::AppError;
@@ -9,11 +9,24 @@ use crate::modules::academic::models::exam_schedule::{
     UpsertDayRoomAssignmentRequest,
 };
 
+use super::auto_placement::{
+    plan_exam_placements, PlannerBooking, PlannerDay, PlannerItem, PlannerRoom,
+    PlannerRoomAssignment, UnplacedReason,
+};
+use super::exam_day_documents::{
+    label_position, render_attendance_sheets, render_door_lists, render_envelope_labels,
+    render_invigilator_sign_in, render_seating_charts, seat_grid_position, seat_rows,
+    thai_weekday_date, validate_seat_columns, DocumentRoom, DocumentSession, DocumentStudent,
+    ExamDayDocumentData,
+};
 use super::invigilation::{
     build_invigilator_candidate_session_windows, build_invigilator_staff_workloads,
     invigilator_staff_option_limit, invigilator_staff_option_search_pattern,
     invigilators_for_assignment, InvigilatorSessionWindowRow,
 };
+use super::invigilator_auto_assignment::{
+    plan_invigilator_assignment, InvigilationConstraints, InvigilationSlot,
+};
 use super::room_assignments::{
     build_default_seat_assignments, validate_seat_generation_capacity, SeatStudent,
 };
@@ -886,3 +899,354 @@ fn rejects_seat_generation_when_effective_capacity_is_not_positive() {
         Err(AppError::BadRequest(message)) if message.contains("greater than zero")
     ));
 }
+
+fn planner_day(id: u128, grade_level_ids: Vec<Uuid>, blocked: Vec<BlockedWindow>) -> PlannerDay {
+    PlannerDay {
+        id: Uuid::from_u128(id),
+        start_time: t("08:30"),
+        end_time: t("12:00"),
+        grade_level_ids,
+        blocked_windows: blocked,
+    }
+}
+
+fn planner_item(
+    id: u128,
+    classroom: u128,
+    grade: u128,
+    subject: u128,
+    minutes: i32,
+) -> PlannerItem {
+    PlannerItem {
+        id: Uuid::from_u128(id),
+        classroom_id: Uuid::from_u128(classroom),
+        grade_level_id: Uuid::from_u128(grade),
+        subject_id: Uuid::from_u128(subject),
+        assessment_category_id: Uuid::from_u128(900),
+        duration_minutes: minutes,
+        active_student_count: 30,
+    }
+}
+
+fn planner_room(id: u128, capacity: i32) -> PlannerRoom {
+    PlannerRoom {
+        id: Uuid::from_u128(id),
+        capacity,
+    }
+}
+
+#[test]
+fn auto_placement_seats_a_grade_together_in_separate_rooms() {
+    let days = vec![planner_day(1, Vec::new(), Vec::new())];
+    let items = vec![
+        planner_item(10, 20, 30, 40, 60),
+        planner_item(11, 21, 30, 40, 60),
+    ];
+    let rooms = vec![planner_room(50, 40), planner_room(51, 35)];
+
+    let plan = plan_exam_placements(&days, &items, &rooms, &[], &[]);
+
+    assert!(plan.unplaced.is_empty());
+    assert_eq!(plan.placed.len(), 2);
+    assert!(plan
+        .placed
+        .iter()
+        .all(|session| session.starts_at == t("08:30") && session.creates_room_assignment));
+    assert_ne!(plan.placed[0].room_id, plan.placed[1].room_id);
+}
+
+#[test]
+fn auto_placement_respects_blocked_windows_grade_scope_and_existing_sessions() {
+    let grade = Uuid::from_u128(30);
+    let blocked = BlockedWindow {
+        id: None,
+        label: "Assembly".to_string(),
+        start_time: t("08:30"),
+        end_time: t("09:00"),
+    };
+    let days = vec![
+        planner_day(1, vec![Uuid::from_u128(31)], Vec::new()),
+        planner_day(2, vec![grade], vec![blocked]),
+    ];
+    let items = vec![planner_item(10, 20, 30, 40, 60)];
+    let rooms = vec![planner_room(50, 40)];
+    let bookings = vec![PlannerBooking {
+        exam_day_id: Uuid::from_u128(2),
+        classroom_id: Uuid::from_u128(20),
+        grade_level_id: grade,
+        starts_at: t("09:00"),
+        ends_at: t("09:30"),
+    }];
+    let assignments = vec![PlannerRoomAssignment {
+        exam_day_id: Uuid::from_u128(2),
+        classroom_id: Uuid::from_u128(20),
+        room_id: Uuid::from_u128(50),
+    }];
+
+    let plan = plan_exam_placements(&days, &items, &rooms, &bookings, &assignments);
+
+    assert_eq!(plan.placed.len(), 1);
+    let session = &plan.placed[0];
+    assert_eq!(session.exam_day_id, Uuid::from_u128(2));
+    assert_eq!(session.starts_at, t("09:30"));
+    assert_eq!(session.ends_at, t("10:30"));
+    assert_eq!(session.room_id, Uuid::from_u128(50));
+    assert!(!session.creates_room_assignment);
+}
+
+#[test]
+fn auto_placement_spreads_a_grade_across_days() {
+    let days = vec![
+        planner_day(1, Vec::new(), Vec::new()),
+        planner_day(2, Vec::new(), Vec::new()),
+    ];
+    let items = vec![
+        planner_item(10, 20, 30, 40, 90),
+        planner_item(11, 20, 30, 41, 90),
+        planner_item(12, 20, 30, 42, 90),
+        planner_item(13, 20, 30, 43, 90),
+    ];
+    let rooms = vec![planner_room(50, 40)];
+
+    let plan = plan_exam_placements(&days, &items, &rooms, &[], &[]);
+
+    assert!(plan.unplaced.is_empty());
+    for day in [Uuid::from_u128(1), Uuid::from_u128(2)] {
+        assert_eq!(
+            plan.placed
+                .iter()
+                .filter(|session| session.exam_day_id == day)
+                .count(),
+            2
+        );
+    }
+}
+
+#[test]
+fn auto_placement_reports_items_it_cannot_place() {
+    let days = vec![planner_day(1, vec![Uuid::from_u128(31)], Vec::new())];
+    let items = vec![planner_item(10, 20, 30, 40, 60)];
+    let rooms = vec![planner_room(50, 40)];
+    let plan = plan_exam_placements(&days, &items, &rooms, &[], &[]);
+    assert_eq!(
+        plan.unplaced,
+        vec![(Uuid::from_u128(10), UnplacedReason::GradeLevelNotOnAnyDay)]
+    );
+
+    let days = vec![planner_day(1, Vec::new(), Vec::new())];
+    let plan = plan_exam_placements(&days, &items, &[planner_room(50, 20)], &[], &[]);
+    assert_eq!(
+        plan.unplaced,
+        vec![(Uuid::from_u128(10), UnplacedReason::RoomTooSmall)]
+    );
+
+    let long_exam = vec![planner_item(10, 20, 30, 40, 240)];
+    let plan = plan_exam_placements(&days, &long_exam, &rooms, &[], &[]);
+    assert_eq!(
+        plan.unplaced,
+        vec![(Uuid::from_u128(10), UnplacedReason::TimeSlotsFull)]
+    );
+}
+
+fn invigilation_slot(assignment: u128, day: u128, windows: &[(&str, &str)]) -> InvigilationSlot {
+    InvigilationSlot {
+        assignment_id: Uuid::from_u128(assignment),
+        exam_day_id: Uuid::from_u128(day),
+        required: 1,
+        session_windows: windows
+            .iter()
+            .map(|(starts_at, ends_at)| (t(starts_at), t(ends_at)))
+            .collect(),
+        subject_ids: vec![Uuid::from_u128(500)],
+        pinned_staff_ids: Vec::new(),
+    }
+}
+
+#[test]
+fn invigilator_auto_assignment_balances_minutes_and_keeps_pins() {
+    let staff = [Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3)];
+    let mut pinned = invigilation_slot(10, 100, &[("08:30", "10:30")]);
+    pinned.pinned_staff_ids = vec![staff[0]];
+    let slots = vec![
+        pinned,
+        invigilation_slot(11, 100, &[("08:30", "10:30")]),
+        invigilation_slot(12, 101, &[("08:30", "09:30")]),
+        invigilation_slot(13, 101, &[("08:30", "09:30")]),
+    ];
+
+    let plan = plan_invigilator_assignment(&slots, &staff, &InvigilationConstraints::default());
+
+    assert!(plan.unfilled.is_empty());
+    assert_eq!(plan.picks.len(), 3);
+    assert!(!plan
+        .picks
+        .iter()
+        .any(|(assignment_id, _)| *assignment_id == Uuid::from_u128(10)));
+    let mut totals: Vec<i32> = staff.iter().map(|id| plan.total_minutes[id]).collect();
+    totals.sort_unstable();
+    assert_eq!(totals, vec![60, 120, 180]);
+}
+
+#[test]
+fn invigilator_auto_assignment_reuses_staff_for_non_overlapping_sessions_on_one_day() {
+    let staff = [Uuid::from_u128(1)];
+    let slots = vec![
+        invigilation_slot(10, 100, &[("08:30", "10:30")]),
+        invigilation_slot(11, 100, &[("10:30", "12:00")]),
+        invigilation_slot(12, 100, &[("11:00", "12:00")]),
+    ];
+
+    let plan = plan_invigilator_assignment(&slots, &staff, &InvigilationConstraints::default());
+
+    assert_eq!(
+        plan.picks,
+        vec![
+            (Uuid::from_u128(10), staff[0]),
+            (Uuid::from_u128(11), staff[0]),
+        ]
+    );
+    assert_eq!(plan.total_minutes[&staff[0]], 210);
+    assert_eq!(plan.unfilled.len(), 1);
+    assert_eq!(plan.unfilled[0].assignment_id, Uuid::from_u128(12));
+    assert_eq!(plan.unfilled[0].busy_overlapping, 1);
+}
+
+#[test]
+fn invigilator_auto_assignment_explains_unfilled_slots() {
+    let staff = [
+        Uuid::from_u128(1),
+        Uuid::from_u128(2),
+        Uuid::from_u128(3),
+        Uuid::from_u128(4),
+    ];
+    let mut slot = invigilation_slot(10, 100, &[("08:30", "10:30")]);
+    slot.required = 2;
+    slot.pinned_staff_ids = vec![staff[0]];
+    let mut constraints = InvigilationConstraints::default();
+    constraints.on_leave.insert((Uuid::from_u128(10), staff[1]));
+    constraints.teaching_windows.insert(
+        (Uuid::from_u128(100), staff[2]),
+        vec![(t("10:00"), t("10:50"))],
+    );
+    constraints
+        .subject_teachers
+        .insert((staff[3], Uuid::from_u128(500)));
+
+    let plan = plan_invigilator_assignment(&[slot], &staff, &constraints);
+
+    assert!(plan.picks.is_empty());
+    assert_eq!(plan.unfilled.len(), 1);
+    let unfilled = &plan.unfilled[0];
+    assert_eq!(unfilled.missing, 1);
+    assert_eq!(
+        (
+            unfilled.busy_overlapping,
+            unfilled.on_leave,
+            unfilled.teaching,
+            unfilled.teaches_subject
+        ),
+        (1, 1, 1, 1)
+    );
+}
+
+fn document_room(
+    exam_day: u128,
+    room_name: &str,
+    student_count: usize,
+    session_starts: &[&str],
+    invigilators: &[&str],
+) -> DocumentRoom {
+    DocumentRoom {
+        exam_day_id: Uuid::from_u128(exam_day),
+        exam_date: NaiveDate::from_ymd_opt(2026, 3, 2).expect("fixture date should be valid"),
+        day_label: Some("วันแรก".to_string()),
+        classroom_name: "ม.1/1".to_string(),
+        room_name: room_name.to_string(),
+        building_name: Some("อาคาร 1".to_string()),
+        required_invigilators: 2,
+        sessions: session_starts
+            .iter()
+            .map(|starts_at| DocumentSession {
+                starts_at: t(starts_at),
+                ends_at: add_minutes(t(starts_at), 60).expect("fixture time should fit"),
+                subject_code: "ค21101".to_string(),
+                subject_name: "คณิตศาสตร์พื้นฐาน".to_string(),
+                category_name: "สอบกลางภาค".to_string(),
+            })
+            .collect(),
+        students: (1..=student_count)
+            .map(|number| DocumentStudent {
+                seat_number: format!("{number:02}"),
+                student_code: Some(format!("{}", 65000 + number)),
+                student_name: format!("เด็กชายทดสอบ นามสกุลที่{number}"),
+            })
+            .collect(),
+        invigilators: invigilators.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+#[test]
+fn exam_day_document_grids_and_labels_are_laid_out_predictably() {
+    assert_eq!(seat_rows(40, 6), 7);
+    assert_eq!(seat_rows(0, 6), 1);
+    assert_eq!(seat_grid_position(0, 7), (0, 0));
+    assert_eq!(seat_grid_position(6, 7), (0, 6));
+    assert_eq!(seat_grid_position(7, 7), (1, 0));
+
+    assert_eq!(label_position(0), (true, 0, 0));
+    assert_eq!(label_position(1), (false, 1, 0));
+    assert_eq!(label_position(7), (false, 1, 3));
+    assert_eq!(label_position(8), (true, 0, 0));
+
+    assert_eq!(validate_seat_columns(None).unwrap(), 6);
+    assert_eq!(validate_seat_columns(Some(4)).unwrap(), 4);
+    assert!(matches!(
+        validate_seat_columns(Some(0)),
+        Err(AppError::BadRequest(_))
+    ));
+    assert!(matches!(
+        validate_seat_columns(Some(11)),
+        Err(AppError::BadRequest(_))
+    ));
+    assert_eq!(
+        thai_weekday_date(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()),
+        "วันจันทร์ที่ 2 มีนาคม 2569"
+    );
+}
+
+#[test]
+fn exam_day_documents_paginate_rooms_sessions_and_labels() {
+    let data = ExamDayDocumentData {
+        round_name: "สอบกลางภาค 1/2569".to_string(),
+        rooms: vec![
+            document_room(1, "101", 45, &["08:30", "10:00"], &["ครูสมศรี ใจดี"]),
+            document_room(1, "102", 3, &["08:30"], &[]),
+        ],
+    };
+
+    assert_eq!(render_seating_charts(&data, 6).page_count(), 2);
+    // 45 students overflow the first door-list page of room 101
+    assert_eq!(render_door_lists(&data).page_count(), 3);
+    assert_eq!(render_invigilator_sign_in(&data).page_count(), 1);
+    // Room 101 needs two pages per session, room 102 one
+    assert_eq!(render_attendance_sheets(&data).page_count(), 5);
+    assert_eq!(render_envelope_labels(&data).page_count(), 1);
+
+    let many_sessions = ExamDayDocumentData {
+        round_name: data.round_name.clone(),
+        rooms: vec![document_room(
+            1,
+            "101",
+            10,
+            &[
+                "08:00", "09:00", "10:00", "11:00", "12:00", "13:00", "14:00", "15:00", "16:00",
+            ],
+            &[],
+        )],
+    };
+    assert_eq!(render_envelope_labels(&many_sessions).page_count(), 2);
+
+    let pdf = render_door_lists(&data).finish();
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+}
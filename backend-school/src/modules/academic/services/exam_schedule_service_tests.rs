@@ -9,7 +9,8 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::modules::academic::models::exam_schedule::{
-    CreateExamRoundRequest, GenerateSeatsRequest, ImportExamItemsRequest, PlaceExamSessionRequest,
+    AutoAssignInvigilatorsRequest, CreateExamRoundRequest, ExamDayDocumentKind,
+    GenerateSeatsRequest, ImportExamItemsRequest, PlaceExamSessionRequest,
     UpdateExamInvigilatorsRequest, UpdateExamRoundRequest, UpsertDayRoomAssignmentRequest,
     UpsertExamDayRequest,
 };
@@ -386,6 +387,7 @@ async fn assign_room_for(
             room_id,
             capacity_override: None,
             invigilator_staff_ids: None,
+            required_invigilators: None,
         },
         actor_user_id,
     )
@@ -636,6 +638,7 @@ async fn room_assignment_and_seat_generation_preserve_capacity_rules() {
             room_id: fixture.room_id,
             capacity_override: Some(1),
             invigilator_staff_ids: None,
+            required_invigilators: None,
         },
         fixture.staff_user_id,
     )
@@ -753,6 +756,203 @@ async fn invigilator_assignment_rejects_overlapping_live_sessions() {
     assert!(second_assignment.invigilators.is_empty());
 }
 
+#[tokio::test]
+async fn invigilator_auto_assignment_keeps_pins_and_skips_subject_teachers() {
+    let pool = migrated_pool().await;
+    let fixture = insert_fixture(&pool).await;
+    let (round_id, day_id) = create_round_with_day(&pool, &fixture).await;
+    let items = import_items(&pool, round_id, &fixture).await;
+    let first_assignment_id = assign_room(&pool, day_id, &fixture).await;
+    let second_assignment_id = assign_room_for(
+        &pool,
+        day_id,
+        fixture.second_classroom_id,
+        fixture.second_room_id,
+        fixture.staff_user_id,
+    )
+    .await;
+    place_session(
+        &pool,
+        imported_item_id(&items, fixture.classroom_id, fixture.subject_id),
+        day_id,
+        NaiveTime::from_hms_opt(8, 0, 0).expect("fixture time should be valid"),
+        fixture.staff_user_id,
+    )
+    .await;
+    place_session(
+        &pool,
+        imported_item_id(&items, fixture.second_classroom_id, fixture.subject_id),
+        day_id,
+        NaiveTime::from_hms_opt(8, 0, 0).expect("fixture time should be valid"),
+        fixture.staff_user_id,
+    )
+    .await;
+
+    let free_staff_id = insert_active_user(&pool, "staff", "Free Invigilator").await;
+    let subject_teacher_id = insert_active_user(&pool, "staff", "Subject Teacher").await;
+    sqlx::query(
+        "INSERT INTO classroom_course_instructors (classroom_course_id, instructor_id, role)
+         VALUES ($1, $2, 'secondary')",
+    )
+    .bind(fixture.course_id)
+    .bind(subject_teacher_id)
+    .execute(&pool)
+    .await
+    .expect("course instructor should insert");
+
+    exam_schedule_service::assign_invigilator_to_assignment(
+        &pool,
+        first_assignment_id,
+        fixture.staff_user_id,
+        fixture.staff_user_id,
+    )
+    .await
+    .expect("pinned invigilator should be assigned");
+
+    let request = |dry_run| AutoAssignInvigilatorsRequest {
+        staff_ids: Some(vec![
+            fixture.staff_user_id,
+            free_staff_id,
+            subject_teacher_id,
+        ]),
+        dry_run,
+    };
+    let preview = exam_schedule_service::auto_assign_invigilators(
+        &pool,
+        round_id,
+        request(true),
+        fixture.staff_user_id,
+    )
+    .await
+    .expect("dry run should succeed");
+    assert_eq!(preview.assigned.len(), 1);
+    assert_eq!(preview.assigned[0].assignment_id, second_assignment_id);
+    assert_eq!(preview.assigned[0].staff_id, free_staff_id);
+    assert!(preview.unfilled.is_empty());
+
+    let workspace = exam_schedule_service::get_invigilator_workspace(&pool, round_id)
+        .await
+        .expect("invigilator workspace should load");
+    assert!(workspace
+        .assignments
+        .iter()
+        .find(|assignment| assignment.assignment_id == second_assignment_id)
+        .expect("second assignment should exist")
+        .invigilators
+        .is_empty());
+
+    exam_schedule_service::auto_assign_invigilators(
+        &pool,
+        round_id,
+        request(false),
+        fixture.staff_user_id,
+    )
+    .await
+    .expect("auto-assignment should save");
+    let rerun = exam_schedule_service::auto_assign_invigilators(
+        &pool,
+        round_id,
+        request(false),
+        fixture.staff_user_id,
+    )
+    .await
+    .expect("re-running auto-assignment should replace only automatic rows");
+    assert_eq!(rerun.assigned.len(), 1);
+
+    let workspace = exam_schedule_service::get_invigilator_workspace(&pool, round_id)
+        .await
+        .expect("invigilator workspace should load");
+    let invigilators_of = |assignment_id| {
+        workspace
+            .assignments
+            .iter()
+            .find(|assignment| assignment.assignment_id == assignment_id)
+            .expect("assignment should exist")
+            .invigilators
+            .iter()
+            .map(|invigilator| (invigilator.staff_id, invigilator.assigned_automatically))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        invigilators_of(first_assignment_id),
+        vec![(fixture.staff_user_id, false)]
+    );
+    assert_eq!(
+        invigilators_of(second_assignment_id),
+        vec![(free_staff_id, true)]
+    );
+}
+
+#[tokio::test]
+async fn exam_day_documents_render_for_assigned_rooms_only() {
+    let pool = migrated_pool().await;
+    let fixture = insert_fixture(&pool).await;
+    let (round_id, day_id) = create_round_with_day(&pool, &fixture).await;
+    let items = import_items(&pool, round_id, &fixture).await;
+
+    let empty = exam_schedule_service::render_exam_day_document(
+        &pool,
+        round_id,
+        ExamDayDocumentKind::DoorLists,
+        Some(day_id),
+        None,
+    )
+    .await;
+    assert!(matches!(empty, Err(AppError::BadRequest(_))));
+
+    assign_room(&pool, day_id, &fixture).await;
+    let no_sessions = exam_schedule_service::render_exam_day_document(
+        &pool,
+        round_id,
+        ExamDayDocumentKind::EnvelopeLabels,
+        Some(day_id),
+        None,
+    )
+    .await;
+    assert!(matches!(no_sessions, Err(AppError::BadRequest(_))));
+
+    place_session(
+        &pool,
+        imported_item_id(&items, fixture.classroom_id, fixture.subject_id),
+        day_id,
+        NaiveTime::from_hms_opt(8, 0, 0).expect("fixture time should be valid"),
+        fixture.staff_user_id,
+    )
+    .await;
+    for kind in [
+        ExamDayDocumentKind::SeatingCharts,
+        ExamDayDocumentKind::DoorLists,
+        ExamDayDocumentKind::InvigilatorSignIn,
+        ExamDayDocumentKind::AttendanceSheets,
+        ExamDayDocumentKind::EnvelopeLabels,
+    ] {
+        let document = exam_schedule_service::render_exam_day_document(
+            &pool,
+            round_id,
+            kind,
+            Some(day_id),
+            None,
+        )
+        .await
+        .expect("exam-day document should render");
+        assert_eq!(
+            document.filename,
+            format!("exam-{}-9800-03-01.pdf", kind.as_str())
+        );
+        assert!(document.content.starts_with(b"%PDF-"));
+    }
+
+    let foreign_day = exam_schedule_service::render_exam_day_document(
+        &pool,
+        round_id,
+        ExamDayDocumentKind::DoorLists,
+        Some(Uuid::new_v4()),
+        None,
+    )
+    .await;
+    assert!(matches!(foreign_day, Err(AppError::NotFound(_))));
+}
+
 #[tokio::test]
 async fn concurrent_overlapping_placements_allow_only_one_commit() {
     let pool = migrated_pool().await;
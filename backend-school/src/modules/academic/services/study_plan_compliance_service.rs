@@ -0,0 +1,1117 @@
+use crate::error::AppError;
+use crate::modules::academic::models::study_plans::{
+    CurriculumRule, CurriculumRuleInput, CurriculumRuleKind, CurriculumRuleScope,
+    CurriculumRuleSet, CurriculumRuleUnit, RecordStudyPlanComplianceOverrideRequest,
+    SaveCurriculumRuleSetRequest, StudyPlanComplianceCheck, StudyPlanComplianceOverride,
+    StudyPlanComplianceReport,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, FromRow, PgConnection, PgPool};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+/// หน่วยกิต 1 หน่วยกิต = 40 ชั่วโมง, used when a subject has no hours set
+const HOURS_PER_CREDIT: f64 = 40.0;
+const DEFAULT_WEEKS_PER_TERM: i32 = 20;
+const DEFAULT_TERMS_PER_YEAR: i32 = 2;
+const LEVEL_TYPES: [&str; 3] = ["kindergarten", "primary", "secondary"];
+const SUBJECT_TYPES: [&str; 3] = ["BASIC", "ADDITIONAL", "ACTIVITY"];
+const ACTIVITY_TYPES: [&str; 5] = ["scout", "club", "guidance", "social", "other"];
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug, FromRow)]
+struct RuleSetRow {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    weeks_per_term: i32,
+    terms_per_year: i32,
+    is_default: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct RuleRow {
+    rule_set_id: Uuid,
+    #[sqlx(flatten)]
+    rule: CurriculumRule,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PlanGrade {
+    id: Uuid,
+    level_type: String,
+    year: i32,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PlanSubject {
+    grade_level_id: Uuid,
+    subject_group_id: Option<Uuid>,
+    subject_type: String,
+    credit: f64,
+    hours: Option<i32>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PlanActivity {
+    grade_level_id: Uuid,
+    term: Option<String>,
+    activity_type: String,
+    periods_per_week: i32,
+}
+
+#[derive(Debug, FromRow)]
+struct OverrideRow {
+    id: Uuid,
+    rule_set_id: Option<Uuid>,
+    reason: String,
+    waived_checks: Json<Vec<StudyPlanComplianceCheck>>,
+    created_by: Option<Uuid>,
+    created_by_name: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<OverrideRow> for StudyPlanComplianceOverride {
+    fn from(row: OverrideRow) -> Self {
+        Self {
+            id: row.id,
+            rule_set_id: row.rule_set_id,
+            reason: row.reason,
+            waived_checks: row.waived_checks.0,
+            created_by: row.created_by,
+            created_by_name: row.created_by_name,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TimeStructure {
+    weeks_per_term: i32,
+    terms_per_year: i32,
+}
+
+// ============================================
+// Rule Sets
+// ============================================
+
+pub async fn list_rule_sets(pool: &PgPool) -> Result<Vec<CurriculumRuleSet>, AppError> {
+    let rows = sqlx::query_as::<_, RuleSetRow>(
+        "SELECT id, name, description, weeks_per_term, terms_per_year, is_default,
+                created_at, updated_at
+         FROM curriculum_rule_sets
+         ORDER BY is_default DESC, name",
+    )
+    .fetch_all(pool)
+    .await?;
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut conn = pool.acquire().await?;
+    let mut rules = load_rules(&mut conn, &ids).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let set_rules = rules.remove(&row.id).unwrap_or_default();
+            rule_set_from_row(row, set_rules)
+        })
+        .collect())
+}
+
+pub async fn create_rule_set(
+    pool: &PgPool,
+    req: SaveCurriculumRuleSetRequest,
+    user_id: Uuid,
+) -> Result<CurriculumRuleSet, AppError> {
+    validate_rule_set(&req)?;
+    let mut tx = pool.begin().await?;
+    ensure_subject_groups_exist(&mut tx, &req.rules).await?;
+    if req.is_default.unwrap_or(false) {
+        clear_default_rule_set(&mut tx).await?;
+    }
+
+    let row = sqlx::query_as::<_, RuleSetRow>(
+        "INSERT INTO curriculum_rule_sets
+         (name, description, weeks_per_term, terms_per_year, is_default, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id, name, description, weeks_per_term, terms_per_year, is_default,
+                   created_at, updated_at",
+    )
+    .bind(req.name.trim())
+    .bind(&req.description)
+    .bind(req.weeks_per_term.unwrap_or(DEFAULT_WEEKS_PER_TERM))
+    .bind(req.terms_per_year.unwrap_or(DEFAULT_TERMS_PER_YEAR))
+    .bind(req.is_default.unwrap_or(false))
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    bulk_insert_curriculum_rules(&mut tx, row.id, &req.rules).await?;
+    let rules = load_rules(&mut tx, &[row.id])
+        .await?
+        .remove(&row.id)
+        .unwrap_or_default();
+    tx.commit().await?;
+    Ok(rule_set_from_row(row, rules))
+}
+
+/// Replaces the set and all of its rules. Rule ids change, so overrides
+/// recorded against the old rules no longer cover any failing check.
+pub async fn update_rule_set(
+    pool: &PgPool,
+    rule_set_id: Uuid,
+    req: SaveCurriculumRuleSetRequest,
+) -> Result<CurriculumRuleSet, AppError> {
+    validate_rule_set(&req)?;
+    let mut tx = pool.begin().await?;
+    ensure_subject_groups_exist(&mut tx, &req.rules).await?;
+    if req.is_default.unwrap_or(false) {
+        clear_default_rule_set(&mut tx).await?;
+    }
+
+    let row = sqlx::query_as::<_, RuleSetRow>(
+        "UPDATE curriculum_rule_sets SET
+            name = $1,
+            description = $2,
+            weeks_per_term = COALESCE($3, weeks_per_term),
+            terms_per_year = COALESCE($4, terms_per_year),
+            is_default = COALESCE($5, is_default)
+         WHERE id = $6
+         RETURNING id, name, description, weeks_per_term, terms_per_year, is_default,
+                   created_at, updated_at",
+    )
+    .bind(req.name.trim())
+    .bind(&req.description)
+    .bind(req.weeks_per_term)
+    .bind(req.terms_per_year)
+    .bind(req.is_default)
+    .bind(rule_set_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Curriculum rule set not found".to_string()))?;
+
+    sqlx::query("DELETE FROM curriculum_rules WHERE rule_set_id = $1")
+        .bind(rule_set_id)
+        .execute(&mut *tx)
+        .await?;
+    bulk_insert_curriculum_rules(&mut tx, rule_set_id, &req.rules).await?;
+    let rules = load_rules(&mut tx, &[rule_set_id])
+        .await?
+        .remove(&rule_set_id)
+        .unwrap_or_default();
+    tx.commit().await?;
+    Ok(rule_set_from_row(row, rules))
+}
+
+/// Versions pinned to the deleted set fall back to the school default.
+pub async fn delete_rule_set(pool: &PgPool, rule_set_id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM curriculum_rule_sets WHERE id = $1")
+        .bind(rule_set_id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            "Curriculum rule set not found".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn ensure_rule_set_exists(pool: &PgPool, rule_set_id: Uuid) -> Result<(), AppError> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM curriculum_rule_sets WHERE id = $1)")
+            .bind(rule_set_id)
+            .fetch_one(pool)
+            .await?;
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(
+            "Curriculum rule set not found".to_string(),
+        ))
+    }
+}
+
+fn rule_set_from_row(row: RuleSetRow, rules: Vec<CurriculumRule>) -> CurriculumRuleSet {
+    CurriculumRuleSet {
+        id: row.id,
+        name: row.name,
+        description: row.description,
+        weeks_per_term: row.weeks_per_term,
+        terms_per_year: row.terms_per_year,
+        is_default: row.is_default,
+        rules,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+async fn clear_default_rule_set(conn: &mut PgConnection) -> Result<(), AppError> {
+    sqlx::query("UPDATE curriculum_rule_sets SET is_default = false WHERE is_default")
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn ensure_subject_groups_exist(
+    conn: &mut PgConnection,
+    rules: &[CurriculumRuleInput],
+) -> Result<(), AppError> {
+    let group_ids: Vec<Uuid> = rules
+        .iter()
+        .filter_map(|rule| rule.subject_group_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if group_ids.is_empty() {
+        return Ok(());
+    }
+
+    let all_exist: bool = sqlx::query_scalar(
+        "SELECT NOT EXISTS (
+             SELECT requested.id
+             FROM UNNEST($1::uuid[]) AS requested(id)
+             LEFT JOIN subject_groups sg ON sg.id = requested.id
+             WHERE sg.id IS NULL
+         )",
+    )
+    .bind(&group_ids)
+    .fetch_one(conn)
+    .await?;
+    if all_exist {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("Subject group not found".to_string()))
+    }
+}
+
+async fn bulk_insert_curriculum_rules(
+    conn: &mut PgConnection,
+    rule_set_id: Uuid,
+    rules: &[CurriculumRuleInput],
+) -> Result<(), AppError> {
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let kinds: Vec<&str> = rules.iter().map(|rule| rule.rule_kind.as_str()).collect();
+    let labels: Vec<&str> = rules.iter().map(|rule| rule.label.trim()).collect();
+    let level_types: Vec<&str> = rules.iter().map(|rule| rule.level_type.as_str()).collect();
+    let years_from: Vec<i32> = rules.iter().map(|rule| rule.grade_year_from).collect();
+    let years_to: Vec<i32> = rules.iter().map(|rule| rule.grade_year_to).collect();
+    let scopes: Vec<&str> = rules.iter().map(|rule| rule.scope.as_str()).collect();
+    let group_ids: Vec<Option<Uuid>> = rules.iter().map(|rule| rule.subject_group_id).collect();
+    let subject_types: Vec<Option<&str>> = rules
+        .iter()
+        .map(|rule| rule.subject_type.as_deref())
+        .collect();
+    let activity_types: Vec<Option<&str>> = rules
+        .iter()
+        .map(|rule| rule.activity_type.as_deref())
+        .collect();
+    let min_values: Vec<Option<f64>> = rules.iter().map(|rule| rule.min_value).collect();
+    let max_values: Vec<Option<f64>> = rules.iter().map(|rule| rule.max_value).collect();
+    let display_orders: Vec<i32> = (1..=rules.len() as i32).collect();
+
+    sqlx::query(
+        "INSERT INTO curriculum_rules
+         (rule_set_id, rule_kind, label, level_type, grade_year_from, grade_year_to, scope,
+          subject_group_id, subject_type, activity_type, min_value, max_value, display_order)
+         SELECT $1, rule_kind, label, level_type, grade_year_from, grade_year_to, scope,
+                subject_group_id, subject_type, activity_type, min_value, max_value,
+                display_order
+         FROM UNNEST(
+             $2::text[], $3::text[], $4::text[], $5::int4[], $6::int4[], $7::text[],
+             $8::uuid[], $9::text[], $10::text[], $11::float8[], $12::float8[], $13::int4[]
+         ) AS rows(
+             rule_kind, label, level_type, grade_year_from, grade_year_to, scope,
+             subject_group_id, subject_type, activity_type, min_value, max_value,
+             display_order
+         )",
+    )
+    .bind(rule_set_id)
+    .bind(&kinds)
+    .bind(&labels)
+    .bind(&level_types)
+    .bind(&years_from)
+    .bind(&years_to)
+    .bind(&scopes)
+    .bind(&group_ids)
+    .bind(&subject_types)
+    .bind(&activity_types)
+    .bind(&min_values)
+    .bind(&max_values)
+    .bind(&display_orders)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn load_rules(
+    conn: &mut PgConnection,
+    rule_set_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<CurriculumRule>>, AppError> {
+    let rows = sqlx::query_as::<_, RuleRow>(
+        "SELECT cr.rule_set_id, cr.id, cr.rule_kind, cr.label, cr.level_type,
+                cr.grade_year_from, cr.grade_year_to, cr.scope, cr.subject_group_id,
+                sg.name_th AS subject_group_name, cr.subject_type, cr.activity_type,
+                cr.min_value, cr.max_value, cr.display_order
+         FROM curriculum_rules cr
+         LEFT JOIN subject_groups sg ON sg.id = cr.subject_group_id
+         WHERE cr.rule_set_id = ANY($1)
+         ORDER BY cr.display_order, cr.label",
+    )
+    .bind(rule_set_ids)
+    .fetch_all(conn)
+    .await?;
+
+    let mut rules: HashMap<Uuid, Vec<CurriculumRule>> = HashMap::new();
+    for row in rows {
+        rules.entry(row.rule_set_id).or_default().push(row.rule);
+    }
+    Ok(rules)
+}
+
+fn validate_rule_set(req: &SaveCurriculumRuleSetRequest) -> Result<(), AppError> {
+    if req.name.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Rule set name is required".to_string(),
+        ));
+    }
+    if req
+        .weeks_per_term
+        .is_some_and(|weeks| !(1..=30).contains(&weeks))
+    {
+        return Err(AppError::BadRequest(
+            "weeks_per_term must be between 1 and 30".to_string(),
+        ));
+    }
+    if req
+        .terms_per_year
+        .is_some_and(|terms| !(1..=4).contains(&terms))
+    {
+        return Err(AppError::BadRequest(
+            "terms_per_year must be between 1 and 4".to_string(),
+        ));
+    }
+    for (index, rule) in req.rules.iter().enumerate() {
+        validate_rule(rule)
+            .map_err(|message| AppError::BadRequest(format!("Rule {}: {message}", index + 1)))?;
+    }
+    Ok(())
+}
+
+fn validate_rule(rule: &CurriculumRuleInput) -> Result<(), String> {
+    if rule.label.trim().is_empty() {
+        return Err("label is required".to_string());
+    }
+    if !LEVEL_TYPES.contains(&rule.level_type.as_str()) {
+        return Err(format!("unknown level_type '{}'", rule.level_type));
+    }
+    if !(1..=6).contains(&rule.grade_year_from)
+        || !(rule.grade_year_from..=6).contains(&rule.grade_year_to)
+    {
+        return Err("grade years must satisfy 1 <= from <= to <= 6".to_string());
+    }
+    let needs_group = rule.rule_kind == CurriculumRuleKind::SubjectGroupHours;
+    if needs_group != rule.subject_group_id.is_some() {
+        return Err("subject_group_id is required for, and only for, subject_group_hours".into());
+    }
+    if let Some(subject_type) = rule.subject_type.as_deref() {
+        if rule.rule_kind == CurriculumRuleKind::ActivityHours {
+            return Err("subject_type does not apply to activity_hours".to_string());
+        }
+        if !SUBJECT_TYPES.contains(&subject_type) {
+            return Err(format!("unknown subject_type '{subject_type}'"));
+        }
+    }
+    if let Some(activity_type) = rule.activity_type.as_deref() {
+        if rule.rule_kind != CurriculumRuleKind::ActivityHours {
+            return Err("activity_type only applies to activity_hours".to_string());
+        }
+        if !ACTIVITY_TYPES.contains(&activity_type) {
+            return Err(format!("unknown activity_type '{activity_type}'"));
+        }
+    }
+    match (rule.min_value, rule.max_value) {
+        (None, None) => return Err("min_value or max_value is required".to_string()),
+        (Some(min), Some(max)) if min > max => {
+            return Err("min_value must not exceed max_value".to_string())
+        }
+        _ => {}
+    }
+    if [rule.min_value, rule.max_value]
+        .into_iter()
+        .flatten()
+        .any(|value| !value.is_finite() || value < 0.0)
+    {
+        return Err("bounds must be non-negative numbers".to_string());
+    }
+    Ok(())
+}
+
+// ============================================
+// Compliance Report
+// ============================================
+
+pub async fn get_compliance_report(
+    pool: &PgPool,
+    version_id: Uuid,
+) -> Result<StudyPlanComplianceReport, AppError> {
+    let mut conn = pool.acquire().await?;
+    load_report(&mut conn, version_id).await
+}
+
+/// Refuses activation and course generation while any failing check is not
+/// covered by the active override.
+pub async fn ensure_version_publishable(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+) -> Result<(), AppError> {
+    let report = load_report(conn, version_id).await?;
+    if !report.blocked {
+        return Ok(());
+    }
+    let unwaived = report
+        .checks
+        .iter()
+        .filter(|check| !check.passed && !check.waived)
+        .count();
+    Err(AppError::Conflict(format!(
+        "หลักสูตรฉบับนี้ไม่ผ่านเกณฑ์โครงสร้างเวลาเรียน {unwaived} รายการ ต้องแก้ไขหรือบันทึกการอนุมัติยกเว้นก่อน"
+    )))
+}
+
+/// Records an override waiving exactly the currently failing checks and
+/// replaces any earlier one.
+pub async fn record_override(
+    pool: &PgPool,
+    version_id: Uuid,
+    req: RecordStudyPlanComplianceOverrideRequest,
+    user_id: Uuid,
+) -> Result<StudyPlanComplianceReport, AppError> {
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest(
+            "Override reason is required".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT id FROM study_plan_versions WHERE id = $1 FOR UPDATE")
+        .bind(version_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Study-plan version not found".to_string()))?;
+    let report = load_report(&mut tx, version_id).await?;
+    let failing: Vec<StudyPlanComplianceCheck> = report
+        .checks
+        .into_iter()
+        .filter(|check| !check.passed)
+        .map(|check| StudyPlanComplianceCheck {
+            waived: true,
+            ..check
+        })
+        .collect();
+    if failing.is_empty() {
+        return Err(AppError::BadRequest(
+            "Study-plan version passes every check; no override is needed".to_string(),
+        ));
+    }
+
+    revoke_active_override(&mut tx, version_id, user_id).await?;
+    sqlx::query(
+        "INSERT INTO study_plan_compliance_overrides
+         (study_plan_version_id, rule_set_id, reason, waived_checks, created_by)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(version_id)
+    .bind(report.rule_set_id)
+    .bind(reason)
+    .bind(Json(&failing))
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let report = load_report(&mut tx, version_id).await?;
+    tx.commit().await?;
+    Ok(report)
+}
+
+pub async fn revoke_override(
+    pool: &PgPool,
+    version_id: Uuid,
+    user_id: Uuid,
+) -> Result<StudyPlanComplianceReport, AppError> {
+    let mut tx = pool.begin().await?;
+    if !revoke_active_override(&mut tx, version_id, user_id).await? {
+        return Err(AppError::NotFound(
+            "No active compliance override for this study-plan version".to_string(),
+        ));
+    }
+    let report = load_report(&mut tx, version_id).await?;
+    tx.commit().await?;
+    Ok(report)
+}
+
+async fn revoke_active_override(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "UPDATE study_plan_compliance_overrides
+         SET revoked_at = now(), revoked_by = $2
+         WHERE study_plan_version_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(version_id)
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+async fn load_report(
+    conn: &mut PgConnection,
+    version_id: Uuid,
+) -> Result<StudyPlanComplianceReport, AppError> {
+    let pinned_rule_set_id: Option<Uuid> = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT compliance_rule_set_id FROM study_plan_versions WHERE id = $1",
+    )
+    .bind(version_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Study-plan version not found".to_string()))?;
+
+    let rule_set = sqlx::query_as::<_, RuleSetRow>(
+        "SELECT id, name, description, weeks_per_term, terms_per_year, is_default,
+                created_at, updated_at
+         FROM curriculum_rule_sets
+         WHERE ($1::uuid IS NOT NULL AND id = $1)
+            OR ($1::uuid IS NULL AND is_default)",
+    )
+    .bind(pinned_rule_set_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let active_override: Option<StudyPlanComplianceOverride> = sqlx::query_as::<_, OverrideRow>(
+        "SELECT o.id, o.rule_set_id, o.reason, o.waived_checks, o.created_by,
+                    NULLIF(CONCAT_WS(' ', creator.first_name, creator.last_name), '')
+                        AS created_by_name,
+                    o.created_at
+             FROM study_plan_compliance_overrides o
+             LEFT JOIN users creator ON creator.id = o.created_by
+             WHERE o.study_plan_version_id = $1 AND o.revoked_at IS NULL",
+    )
+    .bind(version_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .map(StudyPlanComplianceOverride::from);
+
+    let Some(rule_set) = rule_set else {
+        return Ok(StudyPlanComplianceReport {
+            study_plan_version_id: version_id,
+            rule_set_id: None,
+            rule_set_name: None,
+            compliant: true,
+            blocked: false,
+            failed_count: 0,
+            checks: Vec::new(),
+            active_override,
+        });
+    };
+
+    let rules = load_rules(conn, &[rule_set.id])
+        .await?
+        .remove(&rule_set.id)
+        .unwrap_or_default();
+    let grades = sqlx::query_as::<_, PlanGrade>(
+        "WITH version_plan AS (
+             SELECT sp.grade_level_ids
+             FROM study_plan_versions spv
+             JOIN study_plans sp ON sp.id = spv.study_plan_id
+             WHERE spv.id = $1
+         ),
+         covered AS (
+             SELECT (jsonb_array_elements_text(
+                        CASE WHEN jsonb_typeof(grade_level_ids) = 'array'
+                             THEN grade_level_ids ELSE '[]'::jsonb END
+                    ))::uuid AS id
+             FROM version_plan
+             UNION
+             SELECT grade_level_id FROM study_plan_subjects WHERE study_plan_version_id = $1
+             UNION
+             SELECT grade_level_id FROM study_plan_version_activities
+             WHERE study_plan_version_id = $1
+         )
+         SELECT gl.id, gl.level_type, gl.year
+         FROM grade_levels gl
+         JOIN covered ON covered.id = gl.id",
+    )
+    .bind(version_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let subjects = sqlx::query_as::<_, PlanSubject>(
+        "SELECT sps.grade_level_id, s.group_id AS subject_group_id, s.type AS subject_type,
+                s.credit, s.hours_per_semester AS hours
+         FROM study_plan_subjects sps
+         JOIN subjects s ON s.id = sps.subject_id
+         WHERE sps.study_plan_version_id = $1",
+    )
+    .bind(version_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let activities = sqlx::query_as::<_, PlanActivity>(
+        "SELECT sva.grade_level_id, sva.term, ac.activity_type, ac.periods_per_week
+         FROM study_plan_version_activities sva
+         JOIN activity_catalog ac ON ac.id = sva.activity_catalog_id
+         WHERE sva.study_plan_version_id = $1",
+    )
+    .bind(version_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let structure = TimeStructure {
+        weeks_per_term: rule_set.weeks_per_term,
+        terms_per_year: rule_set.terms_per_year,
+    };
+    let mut checks = evaluate(&rules, structure, &grades, &subjects, &activities);
+    let waived_keys: HashSet<&str> = active_override
+        .iter()
+        .flat_map(|item| item.waived_checks.iter().map(|check| check.key.as_str()))
+        .collect();
+    mark_waived(&mut checks, &waived_keys);
+    let (compliant, blocked, failed_count) = summarize(&checks);
+
+    Ok(StudyPlanComplianceReport {
+        study_plan_version_id: version_id,
+        rule_set_id: Some(rule_set.id),
+        rule_set_name: Some(rule_set.name),
+        compliant,
+        blocked,
+        failed_count,
+        checks,
+        active_override,
+    })
+}
+
+fn evaluate(
+    rules: &[CurriculumRule],
+    structure: TimeStructure,
+    grades: &[PlanGrade],
+    subjects: &[PlanSubject],
+    activities: &[PlanActivity],
+) -> Vec<StudyPlanComplianceCheck> {
+    let mut grades = grades.to_vec();
+    grades.sort_by(|a, b| (&a.level_type, a.year).cmp(&(&b.level_type, b.year)));
+    let mut checks = Vec::new();
+
+    for rule in rules {
+        let (Some(kind), Some(scope)) = (
+            CurriculumRuleKind::from_code(&rule.rule_kind),
+            CurriculumRuleScope::from_code(&rule.scope),
+        ) else {
+            continue;
+        };
+        let in_band: Vec<&PlanGrade> = grades
+            .iter()
+            .filter(|grade| {
+                grade.level_type == rule.level_type
+                    && (rule.grade_year_from..=rule.grade_year_to).contains(&grade.year)
+            })
+            .collect();
+        if in_band.is_empty() {
+            continue;
+        }
+
+        let measure = |grade_ids: &[Uuid]| {
+            rule_actual(rule, kind, structure, grade_ids, subjects, activities)
+        };
+        match scope {
+            CurriculumRuleScope::PerGrade => {
+                for grade in in_band {
+                    checks.push(build_check(
+                        rule,
+                        kind,
+                        format!("{}:{}", rule.id, grade.id),
+                        Some(grade.id),
+                        grade_label(&grade.level_type, grade.year),
+                        measure(&[grade.id]),
+                    ));
+                }
+            }
+            CurriculumRuleScope::BandTotal => {
+                let grade_ids: Vec<Uuid> = in_band.iter().map(|grade| grade.id).collect();
+                let label = if rule.grade_year_from == rule.grade_year_to {
+                    grade_label(&rule.level_type, rule.grade_year_from)
+                } else {
+                    format!(
+                        "{}-{}",
+                        grade_label(&rule.level_type, rule.grade_year_from),
+                        grade_label(&rule.level_type, rule.grade_year_to)
+                    )
+                };
+                checks.push(build_check(
+                    rule,
+                    kind,
+                    rule.id.to_string(),
+                    None,
+                    label,
+                    measure(&grade_ids),
+                ));
+            }
+        }
+    }
+    checks
+}
+
+fn rule_actual(
+    rule: &CurriculumRule,
+    kind: CurriculumRuleKind,
+    structure: TimeStructure,
+    grade_ids: &[Uuid],
+    subjects: &[PlanSubject],
+    activities: &[PlanActivity],
+) -> f64 {
+    let matching_subjects = subjects.iter().filter(|subject| {
+        grade_ids.contains(&subject.grade_level_id)
+            && rule
+                .subject_type
+                .as_deref()
+                .is_none_or(|subject_type| subject.subject_type == subject_type)
+    });
+    match kind {
+        CurriculumRuleKind::SubjectGroupHours => matching_subjects
+            .filter(|subject| subject.subject_group_id == rule.subject_group_id)
+            .map(subject_hours)
+            .sum(),
+        CurriculumRuleKind::CreditRange => matching_subjects.map(|subject| subject.credit).sum(),
+        CurriculumRuleKind::ActivityHours => activities
+            .iter()
+            .filter(|activity| {
+                grade_ids.contains(&activity.grade_level_id)
+                    && rule
+                        .activity_type
+                        .as_deref()
+                        .is_none_or(|activity_type| activity.activity_type == activity_type)
+            })
+            .map(|activity| activity_hours(activity, structure))
+            .sum(),
+    }
+}
+
+fn subject_hours(subject: &PlanSubject) -> f64 {
+    subject
+        .hours
+        .map(f64::from)
+        .unwrap_or(subject.credit * HOURS_PER_CREDIT)
+}
+
+/// Activities pinned to a term run that term only; null term means every
+/// term of the year.
+fn activity_hours(activity: &PlanActivity, structure: TimeStructure) -> f64 {
+    let terms = if activity.term.is_some() {
+        1
+    } else {
+        structure.terms_per_year
+    };
+    f64::from(activity.periods_per_week * structure.weeks_per_term * terms)
+}
+
+fn build_check(
+    rule: &CurriculumRule,
+    kind: CurriculumRuleKind,
+    key: String,
+    grade_level_id: Option<Uuid>,
+    grade_label: String,
+    actual: f64,
+) -> StudyPlanComplianceCheck {
+    let passed = rule.min_value.is_none_or(|min| actual + EPSILON >= min)
+        && rule.max_value.is_none_or(|max| actual <= max + EPSILON);
+    StudyPlanComplianceCheck {
+        key,
+        rule_id: rule.id,
+        rule_kind: kind,
+        label: rule.label.clone(),
+        grade_level_id,
+        grade_label,
+        unit: match kind {
+            CurriculumRuleKind::CreditRange => CurriculumRuleUnit::Credits,
+            _ => CurriculumRuleUnit::Hours,
+        },
+        actual,
+        min_value: rule.min_value,
+        max_value: rule.max_value,
+        passed,
+        waived: false,
+    }
+}
+
+fn grade_label(level_type: &str, year: i32) -> String {
+    let prefix = match level_type {
+        "kindergarten" => "อ.",
+        "primary" => "ป.",
+        "secondary" => "ม.",
+        _ => "?.",
+    };
+    format!("{prefix}{year}")
+}
+
+fn mark_waived(checks: &mut [StudyPlanComplianceCheck], waived_keys: &HashSet<&str>) {
+    for check in checks.iter_mut() {
+        check.waived = !check.passed && waived_keys.contains(check.key.as_str());
+    }
+}
+
+/// Returns (compliant, blocked, failed_count).
+fn summarize(checks: &[StudyPlanComplianceCheck]) -> (bool, bool, i32) {
+    let failed_count = checks.iter().filter(|check| !check.passed).count() as i32;
+    let blocked = checks.iter().any(|check| !check.passed && !check.waived);
+    (failed_count == 0, blocked, failed_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRUCTURE: TimeStructure = TimeStructure {
+        weeks_per_term: 20,
+        terms_per_year: 2,
+    };
+
+    fn grade(level_type: &str, year: i32) -> PlanGrade {
+        PlanGrade {
+            id: Uuid::new_v4(),
+            level_type: level_type.to_string(),
+            year,
+        }
+    }
+
+    fn rule(
+        kind: CurriculumRuleKind,
+        scope: CurriculumRuleScope,
+        from: i32,
+        to: i32,
+    ) -> CurriculumRule {
+        CurriculumRule {
+            id: Uuid::new_v4(),
+            rule_kind: kind.as_str().to_string(),
+            label: "rule".to_string(),
+            level_type: "secondary".to_string(),
+            grade_year_from: from,
+            grade_year_to: to,
+            scope: scope.as_str().to_string(),
+            subject_group_id: None,
+            subject_group_name: None,
+            subject_type: None,
+            activity_type: None,
+            min_value: None,
+            max_value: None,
+            display_order: 1,
+        }
+    }
+
+    fn subject(grade_level_id: Uuid, group: Uuid, credit: f64, hours: Option<i32>) -> PlanSubject {
+        PlanSubject {
+            grade_level_id,
+            subject_group_id: Some(group),
+            subject_type: "BASIC".to_string(),
+            credit,
+            hours,
+        }
+    }
+
+    fn activity(grade_level_id: Uuid, activity_type: &str, term: Option<&str>) -> PlanActivity {
+        PlanActivity {
+            grade_level_id,
+            term: term.map(str::to_string),
+            activity_type: activity_type.to_string(),
+            periods_per_week: 1,
+        }
+    }
+
+    fn rule_input(kind: CurriculumRuleKind) -> CurriculumRuleInput {
+        CurriculumRuleInput {
+            rule_kind: kind,
+            label: "rule".to_string(),
+            level_type: "secondary".to_string(),
+            grade_year_from: 1,
+            grade_year_to: 3,
+            scope: CurriculumRuleScope::PerGrade,
+            subject_group_id: None,
+            subject_type: None,
+            activity_type: None,
+            min_value: Some(40.0),
+            max_value: None,
+        }
+    }
+
+    #[test]
+    fn per_grade_subject_hours_fall_back_to_credit_hours() {
+        let thai = Uuid::new_v4();
+        let m1 = grade("secondary", 1);
+        let m2 = grade("secondary", 2);
+        let mut thai_rule = rule(
+            CurriculumRuleKind::SubjectGroupHours,
+            CurriculumRuleScope::PerGrade,
+            1,
+            3,
+        );
+        thai_rule.subject_group_id = Some(thai);
+        thai_rule.min_value = Some(120.0);
+        let subjects = vec![
+            subject(m1.id, thai, 1.5, None),
+            subject(m1.id, thai, 1.5, Some(60)),
+            subject(m2.id, thai, 1.5, None),
+            subject(m2.id, Uuid::new_v4(), 3.0, None),
+        ];
+
+        let checks = evaluate(
+            &[thai_rule.clone()],
+            STRUCTURE,
+            &[m2.clone(), m1.clone()],
+            &subjects,
+            &[],
+        );
+
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].grade_label, "ม.1");
+        assert_eq!(checks[0].key, format!("{}:{}", thai_rule.id, m1.id));
+        assert_eq!(checks[0].actual, 120.0);
+        assert!(checks[0].passed);
+        assert_eq!(checks[1].actual, 60.0);
+        assert!(!checks[1].passed);
+    }
+
+    #[test]
+    fn band_total_credit_rule_sums_the_band_and_ignores_other_levels() {
+        let m4 = grade("secondary", 4);
+        let m5 = grade("secondary", 5);
+        let p4 = grade("primary", 4);
+        let mut credits = rule(
+            CurriculumRuleKind::CreditRange,
+            CurriculumRuleScope::BandTotal,
+            4,
+            6,
+        );
+        credits.min_value = Some(3.0);
+        credits.max_value = Some(4.0);
+        let group = Uuid::new_v4();
+        let subjects = vec![
+            subject(m4.id, group, 2.0, None),
+            subject(m5.id, group, 1.5, None),
+            subject(p4.id, group, 10.0, None),
+        ];
+
+        let checks = evaluate(&[credits.clone()], STRUCTURE, &[m4, m5, p4], &subjects, &[]);
+
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].key, credits.id.to_string());
+        assert_eq!(checks[0].grade_label, "ม.4-ม.6");
+        assert_eq!(checks[0].unit, CurriculumRuleUnit::Credits);
+        assert_eq!(checks[0].actual, 3.5);
+        assert!(checks[0].passed);
+    }
+
+    #[test]
+    fn activity_hours_count_every_term_when_term_is_unpinned() {
+        let m1 = grade("secondary", 1);
+        let mut guidance = rule(
+            CurriculumRuleKind::ActivityHours,
+            CurriculumRuleScope::PerGrade,
+            1,
+            3,
+        );
+        guidance.activity_type = Some("guidance".to_string());
+        guidance.min_value = Some(40.0);
+        let mut all_activities = rule(
+            CurriculumRuleKind::ActivityHours,
+            CurriculumRuleScope::PerGrade,
+            1,
+            3,
+        );
+        all_activities.min_value = Some(120.0);
+        let activities = vec![
+            activity(m1.id, "guidance", None),
+            activity(m1.id, "scout", Some("1")),
+        ];
+
+        let checks = evaluate(
+            &[guidance, all_activities],
+            STRUCTURE,
+            &[m1],
+            &[],
+            &activities,
+        );
+
+        assert_eq!(checks[0].actual, 40.0);
+        assert!(checks[0].passed);
+        assert_eq!(checks[1].actual, 60.0);
+        assert!(!checks[1].passed);
+    }
+
+    #[test]
+    fn rules_outside_the_plan_grades_produce_no_checks() {
+        let mut credits = rule(
+            CurriculumRuleKind::CreditRange,
+            CurriculumRuleScope::BandTotal,
+            4,
+            6,
+        );
+        credits.min_value = Some(77.0);
+
+        let checks = evaluate(&[credits], STRUCTURE, &[grade("secondary", 1)], &[], &[]);
+
+        assert!(checks.is_empty());
+    }
+
+    #[test]
+    fn override_only_unblocks_the_checks_it_waived() {
+        let m1 = grade("secondary", 1);
+        let mut first = rule(
+            CurriculumRuleKind::CreditRange,
+            CurriculumRuleScope::PerGrade,
+            1,
+            3,
+        );
+        first.min_value = Some(10.0);
+        let mut second = first.clone();
+        second.id = Uuid::new_v4();
+        let mut checks = evaluate(&[first, second], STRUCTURE, &[m1], &[], &[]);
+        let waived_key = checks[0].key.clone();
+
+        mark_waived(&mut checks, &HashSet::from([waived_key.as_str()]));
+        assert_eq!(summarize(&checks), (false, true, 2));
+        assert!(checks[0].waived);
+
+        let all_keys: HashSet<&str> = checks.iter().map(|check| check.key.as_str()).collect();
+        let mut checks = checks.clone();
+        mark_waived(&mut checks, &all_keys);
+        assert_eq!(summarize(&checks), (false, false, 2));
+    }
+
+    #[test]
+    fn rule_validation_rejects_inconsistent_rules() {
+        assert!(validate_rule(&rule_input(CurriculumRuleKind::CreditRange)).is_ok());
+
+        let missing_group = rule_input(CurriculumRuleKind::SubjectGroupHours);
+        assert!(validate_rule(&missing_group).is_err());
+
+        let mut activity_on_credit = rule_input(CurriculumRuleKind::CreditRange);
+        activity_on_credit.activity_type = Some("scout".to_string());
+        assert!(validate_rule(&activity_on_credit).is_err());
+
+        let mut reversed_years = rule_input(CurriculumRuleKind::CreditRange);
+        reversed_years.grade_year_from = 4;
+        assert!(validate_rule(&reversed_years).is_err());
+
+        let mut reversed_bounds = rule_input(CurriculumRuleKind::CreditRange);
+        reversed_bounds.max_value = Some(10.0);
+        assert!(validate_rule(&reversed_bounds).is_err());
+
+        let mut unbounded = rule_input(CurriculumRuleKind::ActivityHours);
+        unbounded.min_value = None;
+        assert!(validate_rule(&unbounded).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::modules::academic::models::activity::{
     SlotInstructorInfo,
 };
 use crate::modules::academic::models::study_plans::*;
-use crate::modules::academic::services::activity_service;
+use crate::modules::academic::services::{activity_service, study_plan_compliance_service};
 use crate::policies::resource_access_policy::UserResourceListAccess;
 use chrono::{DateTime, Utc};
 use sqlx::{types::Json, FromRow, PgPool};
@@ -524,16 +524,21 @@ pub async fn create_version(
     pool: &PgPool,
     req: CreateStudyPlanVersionRequest,
 ) -> Result<StudyPlanVersion, AppError> {
+    if let Some(rule_set_id) = req.compliance_rule_set_id {
+        study_plan_compliance_service::ensure_rule_set_exists(pool, rule_set_id).await?;
+    }
     sqlx::query_as::<_, StudyPlanVersion>(
         "INSERT INTO study_plan_versions
-         (study_plan_id, version_name, start_academic_year_id, end_academic_year_id, description)
-         VALUES ($1, $2, $3, $4, $5) RETURNING *",
+         (study_plan_id, version_name, start_academic_year_id, end_academic_year_id, description,
+          compliance_rule_set_id)
+         VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
     )
     .bind(req.study_plan_id)
     .bind(&req.version_name)
     .bind(req.start_academic_year_id)
     .bind(req.end_academic_year_id)
     .bind(&req.description)
+    .bind(req.compliance_rule_set_id)
     .fetch_one(pool)
     .await
     .map_err(|error| not_found_or(error, "Study-plan version not found"))
@@ -544,24 +549,38 @@ pub async fn update_version(
     version_id: Uuid,
     req: UpdateStudyPlanVersionRequest,
 ) -> Result<StudyPlanVersion, AppError> {
-    sqlx::query_as::<_, StudyPlanVersion>(
+    if let Some(rule_set_id) = req.compliance_rule_set_id {
+        study_plan_compliance_service::ensure_rule_set_exists(pool, rule_set_id).await?;
+    }
+    let mut tx = pool.begin().await?;
+    let version = sqlx::query_as::<_, StudyPlanVersion>(
         "UPDATE study_plan_versions SET
             version_name = COALESCE($1, version_name),
             start_academic_year_id = COALESCE($2, start_academic_year_id),
             end_academic_year_id = COALESCE($3, end_academic_year_id),
             description = COALESCE($4, description),
-            is_active = COALESCE($5, is_active)
-         WHERE id = $6 RETURNING *",
+            is_active = COALESCE($5, is_active),
+            compliance_rule_set_id = COALESCE($6, compliance_rule_set_id)
+         WHERE id = $7 RETURNING *",
     )
     .bind(&req.version_name)
     .bind(req.start_academic_year_id)
     .bind(req.end_academic_year_id)
     .bind(&req.description)
     .bind(req.is_active)
+    .bind(req.compliance_rule_set_id)
     .bind(version_id)
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await
-    .map_err(|error| not_found_or(error, "Study-plan version not found"))
+    .map_err(|error| not_found_or(error, "Study-plan version not found"))?;
+
+    // Checked after the update so a rule set switched in the same request is
+    // the one that applies.
+    if req.is_active == Some(true) {
+        study_plan_compliance_service::ensure_version_publishable(&mut tx, version_id).await?;
+    }
+    tx.commit().await?;
+    Ok(version)
 }
 
 pub async fn delete_version(pool: &PgPool, version_id: Uuid) -> Result<(), AppError> {
@@ -848,6 +867,7 @@ pub async fn generate_courses_from_plan(
         AppError::BadRequest("Classroom does not have a study plan assigned".to_string())
     })?;
     let grade_level_id = classroom.1;
+    study_plan_compliance_service::ensure_version_publishable(&mut tx, plan_version_id).await?;
 
     let (semester_term, target_academic_year_id): (String, Uuid) =
         sqlx::query_as("SELECT term, academic_year_id FROM academic_semesters WHERE id = $1")
@@ -1181,6 +1201,11 @@ pub async fn generate_activities_from_plan(
 ) -> Result<GenerateActivitiesFromPlanOutcome, AppError> {
     ensure_study_plan_version_exists(pool, req.study_plan_version_id).await?;
     ensure_academic_semester_exists(pool, req.semester_id).await?;
+    study_plan_compliance_service::ensure_version_publishable(
+        &mut *pool.acquire().await?,
+        req.study_plan_version_id,
+    )
+    .await?;
     let counts: (i64, i64) = sqlx::query_as(
         r#"
         WITH target_year AS (
@@ -34,6 +34,7 @@ fn empty_version_update() -> UpdateStudyPlanVersionRequest {
         end_academic_year_id: None,
         description: None,
         is_active: None,
+        compliance_rule_set_id: None,
     }
 }
 
@@ -25,6 +25,7 @@ pub fn admission_public_routes() -> Router<AppState> {
             "/portal/confirm",
             post(handlers::portal::confirm_enrollment),
         )
+        .route("/portal/decline", post(handlers::portal::decline_offer))
         .route(
             "/portal/form",
             post(handlers::portal::get_enrollment_form)
@@ -144,6 +145,24 @@ pub fn admission_staff_routes() -> Router<AppState> {
             "/rounds/{id}/scores/bulk",
             put(handlers::scores::bulk_update_scores),
         )
+        // === OMR Import (นำเข้าผลสแกนกระดาษคำตอบ) ===
+        .route(
+            "/subjects/{id}/answer-key",
+            get(handlers::score_imports::get_answer_key)
+                .put(handlers::score_imports::update_answer_key),
+        )
+        .route(
+            "/rounds/{id}/scores/omr-import",
+            post(handlers::score_imports::import_omr_scores),
+        )
+        .route(
+            "/rounds/{id}/scores/omr-imports",
+            get(handlers::score_imports::list_omr_imports),
+        )
+        .route(
+            "/omr-imports/{id}",
+            get(handlers::score_imports::get_omr_import),
+        )
         // === Selections (เรียงคะแนน + จัดห้อง) ===
         .route(
             "/rounds/{id}/ranking",
@@ -182,6 +201,27 @@ pub fn admission_staff_routes() -> Router<AppState> {
             "/rounds/{id}/enrollment",
             get(handlers::applications::list_enrollment_pending),
         )
+        // === Seat Offers & Waitlist (เสนอสิทธิ์ / ลำดับสำรอง) ===
+        .route(
+            "/rounds/{id}/offers",
+            get(handlers::offers::list_round_offers).post(handlers::offers::open_seat_offers),
+        )
+        .route(
+            "/rounds/{id}/offers/expire",
+            post(handlers::offers::process_overdue_offers),
+        )
+        .route(
+            "/rounds/{id}/offers/report",
+            get(handlers::offers::get_offer_report),
+        )
+        .route(
+            "/tracks/{id}/waitlist",
+            get(handlers::offers::get_track_waitlist),
+        )
+        .route(
+            "/applications/{id}/offers",
+            get(handlers::offers::list_application_offers),
+        )
         .route(
             "/applications/{id}/enroll",
             post(handlers::applications::complete_enrollment),
@@ -239,4 +279,13 @@ pub fn admission_staff_routes() -> Router<AppState> {
             "/applications/{id}/exam-seat",
             get(handlers::exam_rooms::get_application_exam_seat),
         )
+        // === Reports (ประกาศผล / สถิติ) ===
+        .route(
+            "/rounds/{id}/analytics",
+            get(handlers::reports::get_round_analytics),
+        )
+        .route(
+            "/rounds/{id}/announcement/{format}",
+            get(handlers::reports::download_result_announcement),
+        )
 }
@@ -1,6 +1,9 @@
 pub mod applications;
 pub mod exam_rooms;
+pub mod offers;
 pub mod portal;
+pub mod reports;
 pub mod rounds;
+pub mod score_imports;
 pub mod scores;
 pub mod selections;
@@ -244,12 +244,15 @@ pub async fn complete_enrollment(
     Json(payload): Json<CompleteEnrollmentRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let max_students = context.tenant.plan.max_students;
     let pool = context.tenant.pool;
     let actor = context.actor;
     actor.require_permission(codes::ADMISSION_ENROLL_ALL)?;
     let enroller_id = actor.user_id;
 
-    let result = application_service::complete_enrollment(&pool, id, payload, enroller_id).await?;
+    let result =
+        application_service::complete_enrollment(&pool, id, payload, enroller_id, max_students)
+            .await?;
     Ok(Json(ApiResponse::with_message(
         CompleteEnrollmentData {
             user_id: result.user_id,
@@ -385,6 +388,7 @@ pub async fn staff_upload_document(
                 purpose: FilePurpose::AdmissionApplicationDocument,
                 display_filename: original_filename,
                 bytes: file_data.into(),
+                max_storage_bytes: context.tenant.plan.max_storage_bytes,
             },
         )
         .await
@@ -0,0 +1,106 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::admission::models::offers::ListSeatOffersQuery;
+use crate::modules::admission::services::offer_service;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::permissions::registry::codes;
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::AppState;
+
+pub async fn open_seat_offers(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_ENROLL_ALL)?;
+    let result = offer_service::open_seat_offers(&pool, round_id, actor.user_id).await?;
+    let message = format!(
+        "เสนอสิทธิ์ {} คน ลำดับสำรอง {} คน",
+        result.offers_created, result.waitlisted
+    );
+    Ok(Json(ApiResponse::with_message(result, message)).into_response())
+}
+
+pub async fn list_round_offers(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+    Query(query): Query<ListSeatOffersQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let offers = offer_service::list_round_offers(&pool, round_id, query).await?;
+    Ok(Json(ApiResponse::ok(offers)).into_response())
+}
+
+/// Runs the expiry job for one round without waiting for the schedule.
+pub async fn process_overdue_offers(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_ENROLL_ALL)?;
+    let result = offer_service::process_overdue_offers(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+        Some(round_id),
+        Utc::now(),
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(result)).into_response())
+}
+
+pub async fn get_offer_report(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let report = offer_service::get_offer_report(&pool, round_id).await?;
+    Ok(Json(ApiResponse::ok(report)).into_response())
+}
+
+pub async fn get_track_waitlist(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(track_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let waitlist = offer_service::get_track_waitlist(&pool, track_id).await?;
+    Ok(Json(ApiResponse::ok(waitlist)).into_response())
+}
+
+pub async fn list_application_offers(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(application_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let offers = offer_service::list_application_offers(&pool, application_id).await?;
+    Ok(Json(ApiResponse::ok(offers)).into_response())
+}
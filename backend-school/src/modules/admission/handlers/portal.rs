@@ -11,7 +11,7 @@ use uuid::Uuid;
 use crate::api_response::{ApiErrorResponse, ApiResponse};
 use crate::error::AppError;
 use crate::modules::admission::models::applications::*;
-use crate::modules::admission::services::{application_service, portal_service};
+use crate::modules::admission::services::{application_service, offer_service, portal_service};
 use crate::modules::files::{
     consumer_service::{map_platform_error, request_deletions},
     models::FileDownloadGrantResponse,
@@ -75,6 +75,26 @@ pub async fn confirm_enrollment(
     .into_response())
 }
 
+pub async fn decline_offer(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<PortalCredentials>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context(&state, &headers).await?;
+    let release = portal_service::decline_offer(&tenant.pool, payload).await?;
+    if let Err(error) = offer_service::notify_seat_released(
+        &tenant.pool,
+        &state.notification_channel,
+        &tenant.subdomain,
+        &release,
+    )
+    .await
+    {
+        tracing::error!("Admission seat release notification failed: {}", error);
+    }
+    Ok(Json(ApiResponse::empty_with_message("สละสิทธิ์เข้าเรียนเรียบร้อยแล้ว")).into_response())
+}
+
 pub async fn get_enrollment_form(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -208,6 +228,7 @@ pub async fn portal_upload_document(
                 purpose: FilePurpose::AdmissionApplicationDocument,
                 display_filename: original_filename.clone(),
                 bytes: file_data.into(),
+                max_storage_bytes: tenant.plan.max_storage_bytes,
             },
         )
         .await
@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::admission::models::reports::{AnnouncementFormat, AnnouncementQuery};
+use crate::modules::admission::services::{analytics_service, announcement_service};
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::permissions::registry::codes;
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::AppState;
+
+pub async fn get_round_analytics(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let analytics = analytics_service::get_round_analytics(&pool, round_id).await?;
+    Ok(Json(ApiResponse::ok(analytics)).into_response())
+}
+
+/// Official list of passed applicants as PDF or CSV; national IDs are masked.
+pub async fn download_result_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path((round_id, format)): Path<(Uuid, AnnouncementFormat)>,
+    Query(query): Query<AnnouncementQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_READ_ALL)?;
+    let school_name = state
+        .admin_client
+        .get_school_name(&context.tenant.subdomain)
+        .await
+        .map_err(|_| AppError::ServiceUnavailable("school_name_lookup_failed".to_string()))?;
+
+    let file = announcement_service::render_result_announcement(
+        &pool,
+        round_id,
+        school_name,
+        format,
+        query.include_scores,
+    )
+    .await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file.filename),
+            ),
+        ],
+        file.content,
+    )
+        .into_response())
+}
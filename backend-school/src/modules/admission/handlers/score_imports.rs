@@ -0,0 +1,105 @@
+use axum::{
+    extract::{Extension, Path, State},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::admission::models::score_imports::{OmrImportRequest, UpdateAnswerKeyRequest};
+use crate::modules::admission::services::omr_import_service;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::permissions::registry::codes;
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::AppState;
+
+pub async fn get_answer_key(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(subject_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_SCORES_ALL)?;
+    let key = omr_import_service::get_answer_key(&pool, subject_id).await?;
+    Ok(Json(ApiResponse::ok(key)).into_response())
+}
+
+pub async fn update_answer_key(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(subject_id): Path<Uuid>,
+    Json(payload): Json<UpdateAnswerKeyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_SCORES_ALL)?;
+    let key = omr_import_service::upsert_answer_key(
+        &pool,
+        subject_id,
+        actor.user_id,
+        &payload.answer_key,
+    )
+    .await?;
+    Ok(Json(ApiResponse::with_message(key, "บันทึกเฉลยแล้ว")).into_response())
+}
+
+/// With `dryRun` the response is the marking and diff only; otherwise the
+/// new and changed scores are written.
+pub async fn import_omr_scores(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+    Json(payload): Json<OmrImportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_SCORES_ALL)?;
+    let result =
+        omr_import_service::import_omr_scores(&pool, round_id, actor.user_id, payload).await?;
+    let message = if result.dry_run {
+        format!(
+            "ตรวจได้ {} แผ่น ใหม่ {} เปลี่ยน {} พบปัญหา {} แผ่น",
+            result.scored_count,
+            result.new_count,
+            result.changed_count,
+            result.flagged.len()
+        )
+    } else {
+        format!(
+            "บันทึกคะแนน {} รายการ",
+            result.new_count + result.changed_count
+        )
+    };
+    Ok(Json(ApiResponse::with_message(result, message)).into_response())
+}
+
+pub async fn list_omr_imports(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(round_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_SCORES_ALL)?;
+    let imports = omr_import_service::list_imports(&pool, round_id).await?;
+    Ok(Json(ApiResponse::ok(imports)).into_response())
+}
+
+pub async fn get_omr_import(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(import_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = context.tenant.pool;
+    let actor = context.actor;
+    actor.require_permission(codes::ADMISSION_SCORES_ALL)?;
+    let detail = omr_import_service::get_import(&pool, import_id).await?;
+    Ok(Json(ApiResponse::ok(detail)).into_response())
+}
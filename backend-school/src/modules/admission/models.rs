@@ -1,2 +1,5 @@
 pub mod applications;
+pub mod offers;
+pub mod reports;
 pub mod rounds;
+pub mod score_imports;
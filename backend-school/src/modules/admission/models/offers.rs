@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// ==========================================
+// Seat Offer Models (เสนอสิทธิ์ / ลำดับสำรอง)
+// ==========================================
+
+#[derive(Debug, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatOffer {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_number: Option<String>,
+    pub applicant_name: String,
+    pub admission_track_id: Uuid,
+    pub track_name: String,
+    pub class_room_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_name: Option<String>,
+    /// "initial" (ได้รับสิทธิ์จากการจัดห้อง) หรือ "waitlist" (เลื่อนจากลำดับสำรอง)
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waitlist_position: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaces_offer_id: Option<Uuid>,
+    /// pending | accepted | declined | expired
+    pub status: String,
+    pub offered_at: DateTime<Utc>,
+    pub deadline_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responded_at: Option<DateTime<Utc>>,
+    /// NULL = เสนอสิทธิ์อัตโนมัติจากลำดับสำรอง
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offered_by: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistEntry {
+    pub position: i32,
+    pub application_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_number: Option<String>,
+    pub applicant_name: String,
+    pub application_status: String,
+    /// false เมื่อผู้สมัครถูกตัดสิทธิ์/ถอนตัวหลังจัดลำดับสำรอง
+    pub eligible: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSeatOffersQuery {
+    pub status: Option<String>,
+    pub track_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenSeatOffersResult {
+    pub offers_created: u64,
+    pub waitlisted: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessedSeatOffers {
+    pub expired: usize,
+    pub reoffered: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatOfferCounts {
+    pub offers_made: i64,
+    pub waitlist_offers: i64,
+    pub accepted: i64,
+    pub declined: i64,
+    pub expired: i64,
+    pub pending: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackSeatOfferSummary {
+    pub track_id: Uuid,
+    pub track_name: String,
+    pub confirmation_window_hours: i32,
+    pub waitlist_remaining: i64,
+    #[serde(flatten)]
+    pub counts: SeatOfferCounts,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatOfferReport {
+    pub round_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub tracks: Vec<TrackSeatOfferSummary>,
+    pub totals: SeatOfferCounts,
+}
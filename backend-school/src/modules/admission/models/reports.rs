@@ -0,0 +1,145 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ==========================================
+// Result Announcement (ประกาศรายชื่อผู้ผ่านการคัดเลือก)
+// ==========================================
+
+/// Announcement file formats, named by their URL segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementFormat {
+    Pdf,
+    /// UTF-8 CSV with a byte-order mark so spreadsheet programs read Thai
+    Csv,
+}
+
+impl AnnouncementFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Csv => "csv",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Pdf => "application/pdf",
+            Self::Csv => "text/csv; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementQuery {
+    /// Print each applicant's selection score
+    #[serde(default)]
+    pub include_scores: bool,
+}
+
+// ==========================================
+// Round Analytics
+// ==========================================
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyApplicationCount {
+    pub date: NaiveDate,
+    pub count: i64,
+    pub cumulative: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackApplicationsOverTime {
+    pub track_id: Uuid,
+    pub track_name: String,
+    /// Days with at least one application, in school-local dates
+    pub points: Vec<DailyApplicationCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeederSchoolSummary {
+    /// `None` when applicants left the previous school blank
+    pub school_name: Option<String>,
+    pub applicants: i64,
+    pub passed: i64,
+    pub enrolled: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubjectScoreDistribution {
+    pub subject_id: Uuid,
+    pub subject_name: String,
+    pub max_score: f64,
+    pub scored_count: i64,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub standard_deviation: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Ten equal-width bins from 0 to `max_score`; the last bin is closed
+    pub bins: Vec<ScoreBin>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomCutOff {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub passed_count: i64,
+    pub lowest_score: f64,
+}
+
+/// Selection-score cut-offs from the saved room assignments.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackCutOff {
+    pub track_id: Uuid,
+    pub track_name: String,
+    pub passed_count: i64,
+    pub lowest_passed_score: Option<f64>,
+    pub highest_passed_score: Option<f64>,
+    /// Best selection score among ranked applicants without a seat
+    pub highest_unplaced_score: Option<f64>,
+    pub rooms: Vec<RoomCutOff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackYield {
+    pub track_id: Uuid,
+    pub track_name: String,
+    pub applicants: i64,
+    /// Given a room or a seat offer at any point
+    pub passed: i64,
+    pub confirmed: i64,
+    pub declined: i64,
+    pub enrolled: i64,
+    /// enrolled / passed
+    pub yield_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdmissionRoundAnalytics {
+    pub round_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub applications_over_time: Vec<TrackApplicationsOverTime>,
+    pub feeder_schools: Vec<FeederSchoolSummary>,
+    pub score_distributions: Vec<SubjectScoreDistribution>,
+    pub cut_offs: Vec<TrackCutOff>,
+    pub yield_by_track: Vec<TrackYield>,
+}
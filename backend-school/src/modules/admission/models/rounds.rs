@@ -106,6 +106,26 @@ pub struct SelectionSettings {
     pub show_scores: Option<bool>,
 }
 
+impl SelectionSettings {
+    /// Comma-separated selection subjects for `get_track_ranking`; `None`
+    /// ranks the track on all of its subjects.
+    pub fn ranking_subject_ids(&self, track_id: Uuid) -> Option<String> {
+        self.subjects_by_track.get(&track_id).map(|ids| {
+            ids.iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    pub fn ranking_method(&self, track_id: Uuid) -> String {
+        self.method_by_track
+            .get(&track_id)
+            .cloned()
+            .unwrap_or_else(|| self.method.clone())
+    }
+}
+
 impl Default for SelectionSettings {
     fn default() -> Self {
         Self {
@@ -218,6 +238,8 @@ pub struct AdmissionTrack {
     pub scoring_subject_ids: Vec<Uuid>,
     pub tiebreak_method: String,
     pub display_order: i32,
+    /// ชั่วโมงที่ผู้ได้รับสิทธิ์ต้องยืนยันเข้าเรียน
+    pub confirmation_window_hours: i32,
     pub created_at: DateTime<Utc>,
 
     // Joined/computed fields
@@ -240,6 +262,7 @@ pub struct CreateAdmissionTrackRequest {
     pub scoring_subject_ids: Option<Vec<Uuid>>,
     pub tiebreak_method: Option<String>,
     pub display_order: Option<i32>,
+    pub confirmation_window_hours: Option<i32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -250,4 +273,5 @@ pub struct UpdateAdmissionTrackRequest {
     pub scoring_subject_ids: Option<Vec<Uuid>>,
     pub tiebreak_method: Option<String>,
     pub display_order: Option<i32>,
+    pub confirmation_window_hours: Option<i32>,
 }
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+// ==========================================
+// Answer Key & OMR Import Models (เฉลย / นำเข้าผลสแกนกระดาษคำตอบ)
+// ==========================================
+
+#[derive(Debug, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamAnswerKey {
+    pub exam_subject_id: Uuid,
+    /// หนึ่งอักขระต่อข้อ ใช้สัญลักษณ์เดียวกับที่เครื่องตรวจส่งออก; * = ยกเลิกข้อ
+    pub answer_key: String,
+    pub item_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAnswerKeyRequest {
+    /// Whitespace and commas are ignored, so keys can be typed in groups
+    pub answer_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrImportRequest {
+    pub exam_subject_id: Uuid,
+    pub file_name: Option<String>,
+    /// The machine's CSV export: seat number (exam ID) in the first column,
+    /// then either one answer-string column or one column per item
+    pub csv: String,
+    /// Whether the first row is a header; defaults to true
+    pub has_header: Option<bool>,
+    /// Report the marking and the diff against saved scores without writing
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OmrSheetIssue {
+    /// Seat number column is empty
+    MissingSeat,
+    /// Item count differs from the key or a mark is not a recognised symbol
+    Unreadable,
+    /// Every item is blank, usually an absent applicant's sheet
+    BlankSheet,
+    /// Same seat scanned again with identical answers; the first row is used
+    Duplicate,
+    /// Same seat scanned more than once with different answers; none is used
+    DuplicateConflict,
+    /// No exam seat in this round carries the seat number
+    UnknownSeat,
+    /// The seat belongs to a rejected or withdrawn application
+    InactiveApplication,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrFlaggedSheet {
+    /// 1-based line in the uploaded file
+    pub line: usize,
+    pub seat_number: String,
+    pub issue: OmrSheetIssue,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OmrScoreChangeKind {
+    New,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrScoreChange {
+    pub line: usize,
+    pub seat_number: String,
+    pub application_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_number: Option<String>,
+    pub applicant_name: String,
+    pub correct_count: usize,
+    /// Items marked with more than one answer, counted wrong
+    pub multiple_mark_count: usize,
+    pub previous_score: Option<f64>,
+    pub new_score: f64,
+    pub change: OmrScoreChangeKind,
+}
+
+/// A seated, active applicant with no sheet in the file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrMissingSheet {
+    pub seat_number: String,
+    pub application_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_number: Option<String>,
+    pub applicant_name: String,
+    pub existing_score: Option<f64>,
+}
+
+/// Classical item analysis over the sheets that were scored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrItemStatistic {
+    /// 1-based item number
+    pub item: usize,
+    pub key: String,
+    pub cancelled: bool,
+    /// Proportion answering correctly (p); none for cancelled items
+    pub difficulty: Option<f64>,
+    /// Upper-minus-lower 27% proportion correct (D)
+    pub discrimination: Option<f64>,
+    pub choice_counts: BTreeMap<String, usize>,
+    pub blank_count: usize,
+    pub multiple_mark_count: usize,
+    /// too_easy | too_hard | low_discrimination | negative_discrimination
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrImportResult {
+    pub dry_run: bool,
+    /// Set once the import has been written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_id: Option<Uuid>,
+    pub exam_subject_id: Uuid,
+    pub item_count: usize,
+    pub max_score: f64,
+    pub sheet_count: usize,
+    pub scored_count: usize,
+    pub new_count: usize,
+    pub changed_count: usize,
+    pub unchanged_count: usize,
+    pub changes: Vec<OmrScoreChange>,
+    pub flagged: Vec<OmrFlaggedSheet>,
+    pub missing: Vec<OmrMissingSheet>,
+    pub item_analysis: Vec<OmrItemStatistic>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrImportSummary {
+    pub id: Uuid,
+    pub exam_subject_id: Uuid,
+    pub subject_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    pub answer_key: String,
+    pub sheet_count: i32,
+    pub scored_count: i32,
+    pub flagged_count: i32,
+    pub missing_count: i32,
+    pub written_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_by: Option<Uuid>,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OmrImportDetail {
+    #[serde(flatten)]
+    pub summary: OmrImportSummary,
+    pub item_analysis: Vec<OmrItemStatistic>,
+}
@@ -1,7 +1,14 @@
+pub mod analytics_service;
+pub mod announcement_service;
 pub mod application_service;
 pub mod exam_room_service;
+pub mod offer_service;
+pub mod omr_import_service;
 pub mod pii;
 pub mod portal_service;
 pub mod round_service;
 pub mod score_service;
 pub mod selection_service;
+
+#[cfg(test)]
+mod application_service_tests;
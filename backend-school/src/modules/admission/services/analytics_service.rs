@@ -0,0 +1,543 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{types::Json, FromRow, PgPool};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::admission::models::reports::{
+    AdmissionRoundAnalytics, DailyApplicationCount, FeederSchoolSummary, RoomCutOff, ScoreBin,
+    SubjectScoreDistribution, TrackApplicationsOverTime, TrackCutOff, TrackYield,
+};
+use crate::modules::admission::models::rounds::SelectionSettings;
+use crate::modules::admission::services::selection_service::{self, TrackRankingResult};
+use crate::scheduling::SCHOOL_TIMEZONE_NAME;
+
+const SCORE_BIN_COUNT: usize = 10;
+
+#[derive(Debug, Clone, FromRow)]
+struct ApplicationFact {
+    track_id: Uuid,
+    previous_school: Option<String>,
+    status: String,
+    applied_on: NaiveDate,
+    passed: bool,
+    confirmed: bool,
+    declined: bool,
+}
+
+#[derive(Debug, FromRow)]
+struct TrackRow {
+    id: Uuid,
+    name: String,
+}
+
+#[derive(Debug, FromRow)]
+struct SubjectRow {
+    id: Uuid,
+    name: String,
+    max_score: f64,
+}
+
+fn read_error(e: sqlx::Error) -> AppError {
+    tracing::error!("Failed to load admission analytics: {}", e);
+    AppError::InternalServerError("ไม่สามารถโหลดสถิติการรับสมัครได้".to_string())
+}
+
+/// Applications over time, feeder schools, score distributions, cut-offs and
+/// yield for one round, computed on request.
+pub async fn get_round_analytics(
+    pool: &PgPool,
+    round_id: Uuid,
+) -> Result<AdmissionRoundAnalytics, AppError> {
+    let settings: Option<Json<SelectionSettings>> =
+        sqlx::query_scalar("SELECT selection_settings FROM admission_rounds WHERE id = $1")
+            .bind(round_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(read_error)?
+            .ok_or_else(|| AppError::NotFound("ไม่พบรอบรับสมัคร".to_string()))?;
+    let settings = settings.map(|Json(settings)| settings).unwrap_or_default();
+
+    let tracks: Vec<TrackRow> = sqlx::query_as(
+        "SELECT id, name FROM admission_tracks WHERE admission_round_id = $1 ORDER BY display_order ASC, created_at ASC",
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let facts: Vec<ApplicationFact> = sqlx::query_as(
+        r#"SELECT aa.admission_track_id AS track_id,
+                  aa.previous_school,
+                  aa.status,
+                  (aa.created_at AT TIME ZONE $2)::date AS applied_on,
+                  (ara.id IS NOT NULL OR EXISTS (
+                      SELECT 1 FROM admission_seat_offers so WHERE so.application_id = aa.id
+                  )) AS passed,
+                  COALESCE(ara.student_confirmed, false) AS confirmed,
+                  EXISTS (
+                      SELECT 1 FROM admission_seat_offers so
+                      WHERE so.application_id = aa.id AND so.status = 'declined'
+                  ) AS declined
+           FROM admission_applications aa
+           LEFT JOIN admission_room_assignments ara ON ara.application_id = aa.id
+           WHERE aa.admission_round_id = $1"#,
+    )
+    .bind(round_id)
+    .bind(SCHOOL_TIMEZONE_NAME)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let subjects: Vec<SubjectRow> = sqlx::query_as(
+        r#"SELECT id, name, max_score::float8 AS max_score
+           FROM admission_exam_subjects
+           WHERE admission_round_id = $1
+           ORDER BY display_order ASC, created_at ASC"#,
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let scores: Vec<(Uuid, f64)> = sqlx::query_as(
+        r#"SELECT es.exam_subject_id, es.score
+           FROM admission_exam_scores es
+           JOIN admission_applications aa ON aa.id = es.application_id
+           WHERE aa.admission_round_id = $1
+             AND es.score IS NOT NULL
+             AND aa.status NOT IN ('rejected', 'withdrawn', 'absent')"#,
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+    let mut scores_by_subject: HashMap<Uuid, Vec<f64>> = HashMap::new();
+    for (subject_id, score) in scores {
+        scores_by_subject.entry(subject_id).or_default().push(score);
+    }
+
+    let mut cut_offs = Vec::with_capacity(tracks.len());
+    for track in &tracks {
+        let ranking = selection_service::get_track_ranking(
+            pool,
+            track.id,
+            settings.ranking_subject_ids(track.id),
+            Some(settings.ranking_method(track.id)),
+        )
+        .await?;
+        cut_offs.push(track_cut_off(&ranking));
+    }
+
+    Ok(AdmissionRoundAnalytics {
+        round_id,
+        generated_at: Utc::now(),
+        applications_over_time: tracks
+            .iter()
+            .map(|track| TrackApplicationsOverTime {
+                track_id: track.id,
+                track_name: track.name.clone(),
+                points: applications_over_time(&facts, track.id),
+            })
+            .collect(),
+        feeder_schools: feeder_schools(&facts),
+        score_distributions: subjects
+            .into_iter()
+            .map(|subject| {
+                let scores = scores_by_subject.remove(&subject.id).unwrap_or_default();
+                score_distribution(subject.id, subject.name, subject.max_score, scores)
+            })
+            .collect(),
+        cut_offs,
+        yield_by_track: tracks
+            .iter()
+            .map(|track| track_yield(&facts, track.id, &track.name))
+            .collect(),
+    })
+}
+
+fn applications_over_time(facts: &[ApplicationFact], track_id: Uuid) -> Vec<DailyApplicationCount> {
+    let mut by_day: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for fact in facts.iter().filter(|fact| fact.track_id == track_id) {
+        *by_day.entry(fact.applied_on).or_default() += 1;
+    }
+    let mut cumulative = 0;
+    by_day
+        .into_iter()
+        .map(|(date, count)| {
+            cumulative += count;
+            DailyApplicationCount {
+                date,
+                count,
+                cumulative,
+            }
+        })
+        .collect()
+}
+
+/// Collapses runs of whitespace so "โรงเรียน  ก" and "โรงเรียน ก" count
+/// as one school.
+fn normalize_school_name(name: Option<&str>) -> Option<String> {
+    let normalized = name?.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+fn feeder_schools(facts: &[ApplicationFact]) -> Vec<FeederSchoolSummary> {
+    let mut by_school: HashMap<Option<String>, FeederSchoolSummary> = HashMap::new();
+    for fact in facts {
+        let school_name = normalize_school_name(fact.previous_school.as_deref());
+        let summary = by_school
+            .entry(school_name.clone())
+            .or_insert_with(|| FeederSchoolSummary {
+                school_name,
+                applicants: 0,
+                passed: 0,
+                enrolled: 0,
+            });
+        summary.applicants += 1;
+        summary.passed += i64::from(fact.passed);
+        summary.enrolled += i64::from(fact.status == "enrolled");
+    }
+    let mut schools: Vec<FeederSchoolSummary> = by_school.into_values().collect();
+    schools.sort_by(|a, b| {
+        b.applicants
+            .cmp(&a.applicants)
+            .then_with(|| a.school_name.is_none().cmp(&b.school_name.is_none()))
+            .then_with(|| a.school_name.cmp(&b.school_name))
+    });
+    schools
+}
+
+fn track_yield(facts: &[ApplicationFact], track_id: Uuid, track_name: &str) -> TrackYield {
+    let mut result = TrackYield {
+        track_id,
+        track_name: track_name.to_string(),
+        applicants: 0,
+        passed: 0,
+        confirmed: 0,
+        declined: 0,
+        enrolled: 0,
+        yield_rate: None,
+    };
+    for fact in facts.iter().filter(|fact| fact.track_id == track_id) {
+        result.applicants += 1;
+        result.passed += i64::from(fact.passed);
+        result.confirmed += i64::from(fact.confirmed);
+        result.declined += i64::from(fact.declined);
+        result.enrolled += i64::from(fact.status == "enrolled");
+    }
+    result.yield_rate = (result.passed > 0).then(|| result.enrolled as f64 / result.passed as f64);
+    result
+}
+
+fn score_distribution(
+    subject_id: Uuid,
+    subject_name: String,
+    max_score: f64,
+    mut scores: Vec<f64>,
+) -> SubjectScoreDistribution {
+    scores.sort_by(|a, b| a.total_cmp(b));
+    let count = scores.len();
+    let mean = (count > 0).then(|| scores.iter().sum::<f64>() / count as f64);
+    let median = match count {
+        0 => None,
+        n if n % 2 == 1 => Some(scores[n / 2]),
+        n => Some((scores[n / 2 - 1] + scores[n / 2]) / 2.0),
+    };
+    let standard_deviation = mean.map(|mean| {
+        let variance = scores
+            .iter()
+            .map(|score| (score - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        variance.sqrt()
+    });
+
+    let width = max_score / SCORE_BIN_COUNT as f64;
+    let mut bins: Vec<ScoreBin> = (0..SCORE_BIN_COUNT)
+        .map(|index| ScoreBin {
+            lower: width * index as f64,
+            upper: width * (index + 1) as f64,
+            count: 0,
+        })
+        .collect();
+    if width > 0.0 {
+        for score in &scores {
+            let index = ((score / width).floor().max(0.0) as usize).min(SCORE_BIN_COUNT - 1);
+            bins[index].count += 1;
+        }
+    }
+
+    SubjectScoreDistribution {
+        subject_id,
+        subject_name,
+        max_score,
+        scored_count: count as i64,
+        mean,
+        median,
+        standard_deviation,
+        min: scores.first().copied(),
+        max: scores.last().copied(),
+        bins,
+    }
+}
+
+fn track_cut_off(ranking: &TrackRankingResult) -> TrackCutOff {
+    let is_placed = |entry: &&selection_service::TrackRankingEntry| {
+        entry.room_saved && entry.assigned_room_id.is_some()
+    };
+    let placed: Vec<_> = ranking.applications.iter().filter(is_placed).collect();
+
+    let mut room_order: Vec<Uuid> = ranking
+        .rooms
+        .iter()
+        .filter_map(|room| room.room_id.parse().ok())
+        .collect();
+    for entry in &placed {
+        if let Some(room_id) = entry.assigned_room_id {
+            if !room_order.contains(&room_id) {
+                room_order.push(room_id);
+            }
+        }
+    }
+    let rooms = room_order
+        .into_iter()
+        .filter_map(|room_id| {
+            let in_room: Vec<_> = placed
+                .iter()
+                .filter(|entry| entry.assigned_room_id == Some(room_id))
+                .collect();
+            let lowest_score = in_room
+                .iter()
+                .map(|entry| entry.selection_score)
+                .reduce(f64::min)?;
+            Some(RoomCutOff {
+                room_id,
+                room_name: in_room[0].assigned_room.clone().unwrap_or_default(),
+                passed_count: in_room.len() as i64,
+                lowest_score,
+            })
+        })
+        .collect();
+
+    TrackCutOff {
+        track_id: ranking.track_id,
+        track_name: ranking.track_name.clone(),
+        passed_count: placed.len() as i64,
+        lowest_passed_score: placed
+            .iter()
+            .map(|entry| entry.selection_score)
+            .reduce(f64::min),
+        highest_passed_score: placed
+            .iter()
+            .map(|entry| entry.selection_score)
+            .reduce(f64::max),
+        highest_unplaced_score: ranking
+            .applications
+            .iter()
+            .filter(|entry| !is_placed(entry))
+            .map(|entry| entry.selection_score)
+            .reduce(f64::max),
+        rooms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::admission::services::selection_service::{
+        RankingRoomSummary, TrackRankingEntry,
+    };
+
+    fn fact(
+        track_id: Uuid,
+        day: u32,
+        school: Option<&str>,
+        status: &str,
+        passed: bool,
+    ) -> ApplicationFact {
+        ApplicationFact {
+            track_id,
+            previous_school: school.map(str::to_string),
+            status: status.to_string(),
+            applied_on: NaiveDate::from_ymd_opt(2027, 2, day).unwrap(),
+            passed,
+            confirmed: status == "enrolled",
+            declined: false,
+        }
+    }
+
+    #[test]
+    fn applications_over_time_accumulates_per_day() {
+        let track = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let facts = vec![
+            fact(track, 3, None, "submitted", false),
+            fact(track, 1, None, "submitted", false),
+            fact(track, 3, None, "submitted", false),
+            fact(other, 2, None, "submitted", false),
+        ];
+
+        let points = applications_over_time(&facts, track);
+
+        let summary: Vec<(u32, i64, i64)> = points
+            .iter()
+            .map(|point| {
+                (
+                    chrono::Datelike::day(&point.date),
+                    point.count,
+                    point.cumulative,
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![(1, 1, 1), (3, 2, 3)]);
+    }
+
+    #[test]
+    fn feeder_schools_merge_spacing_variants_and_sort_by_applicants() {
+        let track = Uuid::new_v4();
+        let facts = vec![
+            fact(track, 1, Some("โรงเรียน  ก"), "enrolled", true),
+            fact(track, 1, Some(" โรงเรียน ก "), "scored", false),
+            fact(track, 1, Some("โรงเรียน ข"), "accepted", true),
+            fact(track, 1, Some("   "), "submitted", false),
+            fact(track, 1, None, "submitted", false),
+        ];
+
+        let schools = feeder_schools(&facts);
+
+        assert_eq!(
+            schools,
+            vec![
+                FeederSchoolSummary {
+                    school_name: Some("โรงเรียน ก".to_string()),
+                    applicants: 2,
+                    passed: 1,
+                    enrolled: 1,
+                },
+                FeederSchoolSummary {
+                    school_name: None,
+                    applicants: 2,
+                    passed: 0,
+                    enrolled: 0,
+                },
+                FeederSchoolSummary {
+                    school_name: Some("โรงเรียน ข".to_string()),
+                    applicants: 1,
+                    passed: 1,
+                    enrolled: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn track_yield_divides_enrolled_by_passed() {
+        let track = Uuid::new_v4();
+        let mut declined = fact(track, 1, None, "accepted", true);
+        declined.declined = true;
+        let facts = vec![
+            fact(track, 1, None, "enrolled", true),
+            fact(track, 1, None, "accepted", true),
+            fact(track, 1, None, "scored", false),
+            declined,
+        ];
+
+        let result = track_yield(&facts, track, "ทั่วไป");
+
+        assert_eq!(result.applicants, 4);
+        assert_eq!(result.passed, 3);
+        assert_eq!(result.confirmed, 1);
+        assert_eq!(result.declined, 1);
+        assert_eq!(result.enrolled, 1);
+        assert_eq!(result.yield_rate, Some(1.0 / 3.0));
+        assert_eq!(track_yield(&[], track, "ทั่วไป").yield_rate, None);
+    }
+
+    #[test]
+    fn score_distribution_reports_summary_statistics_and_closed_last_bin() {
+        let distribution = score_distribution(
+            Uuid::new_v4(),
+            "คณิตศาสตร์".to_string(),
+            100.0,
+            vec![100.0, 40.0, 60.0, 0.0, 55.0],
+        );
+
+        assert_eq!(distribution.scored_count, 5);
+        assert_eq!(distribution.mean, Some(51.0));
+        assert_eq!(distribution.median, Some(55.0));
+        assert_eq!(distribution.min, Some(0.0));
+        assert_eq!(distribution.max, Some(100.0));
+        let sd = distribution.standard_deviation.unwrap();
+        assert!((sd - 1044.0_f64.sqrt()).abs() < 1e-9);
+        let counts: Vec<i64> = distribution.bins.iter().map(|bin| bin.count).collect();
+        assert_eq!(counts, vec![1, 0, 0, 0, 1, 1, 1, 0, 0, 1]);
+
+        let empty = score_distribution(Uuid::new_v4(), "ว่าง".to_string(), 50.0, Vec::new());
+        assert_eq!(empty.mean, None);
+        assert_eq!(empty.median, None);
+        assert_eq!(empty.bins.len(), SCORE_BIN_COUNT);
+    }
+
+    #[test]
+    fn track_cut_off_uses_saved_rooms_only() {
+        let room_a = Uuid::new_v4();
+        let room_b = Uuid::new_v4();
+        let entry = |score: f64, room: Option<(Uuid, &str)>, saved: bool| TrackRankingEntry {
+            application_id: Uuid::new_v4(),
+            application_number: None,
+            national_id: String::new(),
+            full_name: String::new(),
+            selection_score: score,
+            total_score: score,
+            selection_rank: 0,
+            final_rank: None,
+            assigned_room: room.map(|(_, name)| name.to_string()),
+            assigned_room_id: room.map(|(id, _)| id),
+            room_saved: saved,
+            is_overflow: false,
+            is_track_overridden: false,
+            original_track_name: None,
+            gender: None,
+        };
+        let room = |id: Uuid, name: &str| RankingRoomSummary {
+            room_id: id.to_string(),
+            room_name: name.to_string(),
+            capacity: 2,
+            student_count: 0,
+            male_count: 0,
+            female_count: 0,
+        };
+        let ranking = TrackRankingResult {
+            track_id: Uuid::new_v4(),
+            track_name: "วิทย์–คณิต".to_string(),
+            rooms: vec![room(room_a, "ม.4/1"), room(room_b, "ม.4/2")],
+            applications: vec![
+                entry(95.0, Some((room_a, "ม.4/1")), true),
+                entry(90.0, Some((room_a, "ม.4/1")), true),
+                entry(80.0, Some((room_b, "ม.4/2")), true),
+                entry(70.0, Some((room_b, "ม.4/2")), false),
+                entry(60.0, None, false),
+            ],
+        };
+
+        let cut_off = track_cut_off(&ranking);
+
+        assert_eq!(cut_off.passed_count, 3);
+        assert_eq!(cut_off.lowest_passed_score, Some(80.0));
+        assert_eq!(cut_off.highest_passed_score, Some(95.0));
+        assert_eq!(cut_off.highest_unplaced_score, Some(70.0));
+        let rooms: Vec<(String, i64, f64)> = cut_off
+            .rooms
+            .iter()
+            .map(|room| (room.room_name.clone(), room.passed_count, room.lowest_score))
+            .collect();
+        assert_eq!(
+            rooms,
+            vec![
+                ("ม.4/1".to_string(), 2, 90.0),
+                ("ม.4/2".to_string(), 1, 80.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::admission::models::reports::AnnouncementFormat;
+use crate::modules::admission::models::rounds::SelectionSettings;
+use crate::modules::admission::services::round_service::ROUND_GRADE_LEVEL_CASE;
+use crate::modules::admission::services::selection_service::{self, TrackRankingResult};
+use crate::scheduling::SCHOOL_TIMEZONE;
+use crate::utils::pdf::{
+    centered_line, draw_table, thai_long_date, PdfDocument, PdfFontWeight, PdfTextAlign,
+    TableColumn, A4_PORTRAIT, PAGE_MARGIN, TABLE_ROW_HEIGHT,
+};
+
+/// Digits of a 13-digit national ID left readable at the end.
+const VISIBLE_ID_DIGITS: usize = 4;
+/// Room heading plus a table header and one row
+const ROOM_BLOCK_MIN_HEIGHT: f32 = 30.0 + 2.0 * TABLE_ROW_HEIGHT;
+const SIGNATURE_BLOCK_HEIGHT: f32 = 110.0;
+
+pub struct AnnouncementFile {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AnnouncedApplicant {
+    order: usize,
+    application_number: Option<String>,
+    masked_national_id: String,
+    full_name: String,
+    selection_score: f64,
+    rank_in_track: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AnnouncedRoom {
+    room_name: String,
+    applicants: Vec<AnnouncedApplicant>,
+}
+
+#[derive(Debug, Clone)]
+struct AnnouncedTrack {
+    track_name: String,
+    rooms: Vec<AnnouncedRoom>,
+}
+
+#[derive(Debug, Clone)]
+struct ResultAnnouncement {
+    school_name: String,
+    round_name: String,
+    academic_year_name: String,
+    grade_level_name: String,
+    announced_on: NaiveDate,
+    tracks: Vec<AnnouncedTrack>,
+}
+
+/// Masks all but the last digits; 13-digit Thai IDs keep their 1-4-5-2-1
+/// grouping so readers can still recognise the shape.
+pub fn mask_national_id(national_id: &str) -> String {
+    let digits: Vec<char> = national_id.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() == 13 {
+        let visible_from = digits.len() - VISIBLE_ID_DIGITS;
+        let masked: String = digits
+            .iter()
+            .enumerate()
+            .map(|(index, digit)| if index < visible_from { 'X' } else { *digit })
+            .collect();
+        return format!(
+            "{}-{}-{}-{}-{}",
+            &masked[0..1],
+            &masked[1..5],
+            &masked[5..10],
+            &masked[10..12],
+            &masked[12..13]
+        );
+    }
+    let characters: Vec<char> = national_id.trim().chars().collect();
+    let visible = VISIBLE_ID_DIGITS.min(characters.len() / 2);
+    characters
+        .iter()
+        .enumerate()
+        .map(|(index, c)| {
+            if index < characters.len() - visible {
+                'X'
+            } else {
+                *c
+            }
+        })
+        .collect()
+}
+
+/// Applicants with a saved room, grouped by room in the ranking's room order
+/// and numbered from one within each room.
+fn announced_rooms(ranking: &TrackRankingResult) -> Vec<AnnouncedRoom> {
+    let mut room_ids: Vec<Uuid> = ranking
+        .rooms
+        .iter()
+        .filter_map(|room| room.room_id.parse().ok())
+        .collect();
+    for entry in &ranking.applications {
+        if let (true, Some(room_id)) = (entry.room_saved, entry.assigned_room_id) {
+            if !room_ids.contains(&room_id) {
+                room_ids.push(room_id);
+            }
+        }
+    }
+
+    room_ids
+        .into_iter()
+        .filter_map(|room_id| {
+            let entries: Vec<_> = ranking
+                .applications
+                .iter()
+                .filter(|entry| entry.room_saved && entry.assigned_room_id == Some(room_id))
+                .collect();
+            let room_name = entries.first()?.assigned_room.clone().unwrap_or_default();
+            let applicants = entries
+                .into_iter()
+                .enumerate()
+                .map(|(index, entry)| AnnouncedApplicant {
+                    order: index + 1,
+                    application_number: entry.application_number.clone(),
+                    masked_national_id: mask_national_id(&entry.national_id),
+                    full_name: entry.full_name.clone(),
+                    selection_score: entry.selection_score,
+                    rank_in_track: entry.final_rank,
+                })
+                .collect();
+            Some(AnnouncedRoom {
+                room_name,
+                applicants,
+            })
+        })
+        .collect()
+}
+
+async fn load_announcement(
+    pool: &PgPool,
+    round_id: Uuid,
+    school_name: String,
+) -> Result<ResultAnnouncement, AppError> {
+    let read_error = |e: sqlx::Error| {
+        tracing::error!("Failed to load admission announcement: {}", e);
+        AppError::InternalServerError("ไม่สามารถโหลดข้อมูลประกาศผลได้".to_string())
+    };
+    let (round_name, academic_year_name, grade_level_name, announce_date, settings): (
+        String,
+        String,
+        String,
+        Option<NaiveDate>,
+        Option<Json<SelectionSettings>>,
+    ) = sqlx::query_as(&format!(
+        r#"SELECT ar.name, ay.name, {grade_case}, ar.result_announce_date, ar.selection_settings
+           FROM admission_rounds ar
+           JOIN academic_years ay ON ar.academic_year_id = ay.id
+           JOIN grade_levels gl ON ar.grade_level_id = gl.id
+           WHERE ar.id = $1"#,
+        grade_case = ROUND_GRADE_LEVEL_CASE
+    ))
+    .bind(round_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound("ไม่พบรอบรับสมัคร".to_string()))?;
+    let settings = settings.map(|Json(settings)| settings).unwrap_or_default();
+
+    let track_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM admission_tracks WHERE admission_round_id = $1 ORDER BY display_order ASC, created_at ASC",
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut tracks = Vec::new();
+    for track_id in track_ids {
+        let ranking = selection_service::get_track_ranking(
+            pool,
+            track_id,
+            settings.ranking_subject_ids(track_id),
+            Some(settings.ranking_method(track_id)),
+        )
+        .await?;
+        let rooms = announced_rooms(&ranking);
+        if !rooms.is_empty() {
+            tracks.push(AnnouncedTrack {
+                track_name: ranking.track_name,
+                rooms,
+            });
+        }
+    }
+    if tracks.is_empty() {
+        return Err(AppError::BadRequest(
+            "ยังไม่มีผู้ผ่านการคัดเลือกที่บันทึกห้องเรียนแล้ว".to_string(),
+        ));
+    }
+
+    Ok(ResultAnnouncement {
+        school_name,
+        round_name,
+        academic_year_name,
+        grade_level_name,
+        announced_on: announce_date
+            .unwrap_or_else(|| Utc::now().with_timezone(&SCHOOL_TIMEZONE).date_naive()),
+        tracks,
+    })
+}
+
+/// The official list of passed applicants by track and room, with masked
+/// national IDs.
+pub async fn render_result_announcement(
+    pool: &PgPool,
+    round_id: Uuid,
+    school_name: String,
+    format: AnnouncementFormat,
+    include_scores: bool,
+) -> Result<AnnouncementFile, AppError> {
+    let announcement = load_announcement(pool, round_id, school_name).await?;
+    let content = match format {
+        AnnouncementFormat::Pdf => render_announcement_pdf(&announcement, include_scores).finish(),
+        AnnouncementFormat::Csv => render_announcement_csv(&announcement, include_scores),
+    };
+    Ok(AnnouncementFile {
+        filename: format!("admission-announcement-{}.{}", round_id, format.extension()),
+        content,
+    })
+}
+
+fn announcement_subject(announcement: &ResultAnnouncement) -> String {
+    format!(
+        "เรื่อง รายชื่อผู้ผ่านการคัดเลือกเข้าศึกษาต่อชั้น {} ปีการศึกษา {}",
+        announcement.grade_level_name, announcement.academic_year_name
+    )
+}
+
+fn format_score(score: f64) -> String {
+    format!("{:.2}", score)
+}
+
+fn render_announcement_pdf(announcement: &ResultAnnouncement, include_scores: bool) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let mut document = PdfDocument::new(format!("ประกาศผลการคัดเลือก {}", announcement.round_name));
+    let content_width = page.width - 2.0 * PAGE_MARGIN;
+    let score_width = if include_scores { 70.0 } else { 0.0 };
+    let mut columns = vec![
+        TableColumn::new("ลำดับ", 45.0, PdfTextAlign::Center),
+        TableColumn::new("เลขที่สมัคร", 90.0, PdfTextAlign::Center),
+        TableColumn::new("เลขประจำตัวประชาชน", 125.0, PdfTextAlign::Center),
+        TableColumn::new(
+            "ชื่อ–สกุล",
+            content_width - 260.0 - score_width,
+            PdfTextAlign::Left,
+        ),
+    ];
+    if include_scores {
+        columns.push(TableColumn::new("คะแนน", score_width, PdfTextAlign::Right));
+    }
+
+    let mut y = 0.0;
+    for track in &announcement.tracks {
+        document.add_page(page);
+        y = PAGE_MARGIN + 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            16.0,
+            PdfFontWeight::Bold,
+            &format!("ประกาศ{}", announcement.school_name),
+        );
+        y += 20.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            13.0,
+            PdfFontWeight::Regular,
+            &announcement_subject(announcement),
+        );
+        y += 18.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            12.0,
+            PdfFontWeight::Regular,
+            &announcement.round_name,
+        );
+        y += 24.0;
+        centered_line(
+            &mut document,
+            page,
+            y,
+            14.0,
+            PdfFontWeight::Bold,
+            &format!("สายการเรียน {}", track.track_name),
+        );
+        y += 10.0;
+
+        for room in &track.rooms {
+            if y + ROOM_BLOCK_MIN_HEIGHT > page.height - PAGE_MARGIN {
+                document.add_page(page);
+                y = PAGE_MARGIN;
+            }
+            y += 22.0;
+            document.text(
+                PAGE_MARGIN,
+                y,
+                13.0,
+                PdfFontWeight::Bold,
+                &format!("ห้อง {} ({} คน)", room.room_name, room.applicants.len()),
+            );
+            y += 8.0;
+            let rows: Vec<Vec<String>> = room
+                .applicants
+                .iter()
+                .map(|applicant| {
+                    let mut row = vec![
+                        applicant.order.to_string(),
+                        applicant.application_number.clone().unwrap_or_default(),
+                        applicant.masked_national_id.clone(),
+                        applicant.full_name.clone(),
+                    ];
+                    if include_scores {
+                        row.push(format_score(applicant.selection_score));
+                    }
+                    row
+                })
+                .collect();
+            let continued = format!(
+                "สายการเรียน {} · ห้อง {} (ต่อ)",
+                track.track_name, room.room_name
+            );
+            y = draw_table(&mut document, page, &columns, &rows, y, &continued);
+        }
+    }
+
+    if y + SIGNATURE_BLOCK_HEIGHT > page.height - PAGE_MARGIN {
+        document.add_page(page);
+        y = PAGE_MARGIN;
+    }
+    let signature_x = page.width * 0.65;
+    y += 36.0;
+    document.aligned_text(
+        signature_x,
+        y,
+        12.0,
+        PdfFontWeight::Regular,
+        PdfTextAlign::Center,
+        &format!("ประกาศ ณ วันที่ {}", thai_long_date(announcement.announced_on)),
+    );
+    y += 44.0;
+    document.aligned_text(
+        signature_x,
+        y,
+        12.0,
+        PdfFontWeight::Regular,
+        PdfTextAlign::Center,
+        "(......................................................)",
+    );
+    y += 18.0;
+    document.aligned_text(
+        signature_x,
+        y,
+        12.0,
+        PdfFontWeight::Regular,
+        PdfTextAlign::Center,
+        &format!("ผู้อำนวยการ{}", announcement.school_name),
+    );
+    document
+}
+
+/// Quotes a CSV field when needed and defuses leading formula characters.
+fn csv_cell(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn render_announcement_csv(announcement: &ResultAnnouncement, include_scores: bool) -> Vec<u8> {
+    let mut header = vec![
+        "สายการเรียน",
+        "ห้อง",
+        "ลำดับ",
+        "เลขที่สมัคร",
+        "เลขประจำตัวประชาชน",
+        "ชื่อ–สกุล",
+        "ลำดับในสายการเรียน",
+    ];
+    if include_scores {
+        header.push("คะแนน");
+    }
+    let mut output = String::from("\u{feff}");
+    output.push_str(&header.join(","));
+    output.push_str("\r\n");
+    for track in &announcement.tracks {
+        for room in &track.rooms {
+            for applicant in &room.applicants {
+                let mut row = vec![
+                    csv_cell(&track.track_name),
+                    csv_cell(&room.room_name),
+                    applicant.order.to_string(),
+                    csv_cell(applicant.application_number.as_deref().unwrap_or("")),
+                    csv_cell(&applicant.masked_national_id),
+                    csv_cell(&applicant.full_name),
+                    applicant
+                        .rank_in_track
+                        .map(|rank| rank.to_string())
+                        .unwrap_or_default(),
+                ];
+                if include_scores {
+                    row.push(format_score(applicant.selection_score));
+                }
+                output.push_str(&row.join(","));
+                output.push_str("\r\n");
+            }
+        }
+    }
+    output.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::admission::services::selection_service::{
+        RankingRoomSummary, TrackRankingEntry,
+    };
+
+    fn entry(name: &str, room: Option<(Uuid, &str)>, saved: bool, rank: i64) -> TrackRankingEntry {
+        TrackRankingEntry {
+            application_id: Uuid::new_v4(),
+            application_number: Some(format!("N{rank}")),
+            national_id: "1103700012345".to_string(),
+            full_name: name.to_string(),
+            selection_score: 100.0 - rank as f64,
+            total_score: 100.0 - rank as f64,
+            selection_rank: rank,
+            final_rank: Some(rank),
+            assigned_room: room.map(|(_, name)| name.to_string()),
+            assigned_room_id: room.map(|(id, _)| id),
+            room_saved: saved,
+            is_overflow: false,
+            is_track_overridden: false,
+            original_track_name: None,
+            gender: None,
+        }
+    }
+
+    fn room_summary(id: Uuid, name: &str) -> RankingRoomSummary {
+        RankingRoomSummary {
+            room_id: id.to_string(),
+            room_name: name.to_string(),
+            capacity: 40,
+            student_count: 0,
+            male_count: 0,
+            female_count: 0,
+        }
+    }
+
+    #[test]
+    fn mask_national_id_keeps_only_the_last_four_digits() {
+        assert_eq!(mask_national_id("1103700012345"), "X-XXXX-XXXX2-34-5");
+        assert_eq!(mask_national_id("1-1037-00012-34-5"), "X-XXXX-XXXX2-34-5");
+        assert_eq!(mask_national_id("AB123456"), "XXXX3456");
+        assert_eq!(mask_national_id("A1"), "X1");
+    }
+
+    #[test]
+    fn announced_rooms_lists_only_saved_seats_in_room_order() {
+        let room_a = Uuid::new_v4();
+        let room_b = Uuid::new_v4();
+        let ranking = TrackRankingResult {
+            track_id: Uuid::new_v4(),
+            track_name: "วิทย์–คณิต".to_string(),
+            rooms: vec![room_summary(room_a, "ม.4/1"), room_summary(room_b, "ม.4/2")],
+            applications: vec![
+                entry("ข", Some((room_b, "ม.4/2")), true, 1),
+                entry("ก", Some((room_a, "ม.4/1")), true, 2),
+                entry("ค", Some((room_a, "ม.4/1")), false, 3),
+                entry("ง", Some((room_a, "ม.4/1")), true, 4),
+                entry("จ", None, false, 5),
+            ],
+        };
+
+        let rooms = announced_rooms(&ranking);
+
+        let summary: Vec<(String, Vec<(usize, String)>)> = rooms
+            .iter()
+            .map(|room| {
+                (
+                    room.room_name.clone(),
+                    room.applicants
+                        .iter()
+                        .map(|applicant| (applicant.order, applicant.full_name.clone()))
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "ม.4/1".to_string(),
+                    vec![(1, "ก".to_string()), (2, "ง".to_string())]
+                ),
+                ("ม.4/2".to_string(), vec![(1, "ข".to_string())]),
+            ]
+        );
+        assert_eq!(
+            rooms[0].applicants[0].masked_national_id,
+            "X-XXXX-XXXX2-34-5"
+        );
+    }
+
+    #[test]
+    fn csv_cells_are_quoted_and_formula_safe() {
+        assert_eq!(csv_cell("ม.4/1"), "ม.4/1");
+        assert_eq!(csv_cell("a,b"), "\"a,b\"");
+        assert_eq!(csv_cell("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_cell("=1+1"), "'=1+1");
+    }
+
+    #[test]
+    fn announcement_files_render_every_track_and_room() {
+        let announcement = ResultAnnouncement {
+            school_name: "โรงเรียนทดสอบ".to_string(),
+            round_name: "รอบทั่วไป".to_string(),
+            academic_year_name: "2570".to_string(),
+            grade_level_name: "ม.4".to_string(),
+            announced_on: NaiveDate::from_ymd_opt(2027, 3, 15).unwrap(),
+            tracks: vec![AnnouncedTrack {
+                track_name: "วิทย์–คณิต".to_string(),
+                rooms: vec![AnnouncedRoom {
+                    room_name: "ม.4/1".to_string(),
+                    applicants: (1..=60)
+                        .map(|order| AnnouncedApplicant {
+                            order,
+                            application_number: Some(format!("A{order:03}")),
+                            masked_national_id: "X-XXXX-XXXX2-34-5".to_string(),
+                            full_name: format!("ผู้สมัคร {order}"),
+                            selection_score: 80.0,
+                            rank_in_track: Some(order as i64),
+                        })
+                        .collect(),
+                }],
+            }],
+        };
+
+        let pdf = render_announcement_pdf(&announcement, true);
+        assert!(pdf.page_count() >= 2);
+
+        let csv = String::from_utf8(render_announcement_csv(&announcement, false)).unwrap();
+        let lines: Vec<&str> = csv.trim_end().split("\r\n").collect();
+        assert!(lines[0].starts_with('\u{feff}'));
+        assert_eq!(lines.len(), 61);
+        assert_eq!(lines[1], "วิทย์–คณิต,ม.4/1,1,A001,X-XXXX-XXXX2-34-5,ผู้สมัคร 1,1");
+        assert_eq!(
+            thai_long_date(announcement.announced_on),
+            "15 มีนาคม พ.ศ. 2570"
+        );
+    }
+}
@@ -5,6 +5,7 @@ use crate::modules::files::{
     platform_types::{FileLifecycleStatus, FilePurpose},
     repository::PlatformFile,
 };
+use crate::modules::students::services::ensure_student_quota;
 use chrono::{Datelike, FixedOffset, Utc};
 use serde::Serialize;
 use sqlx::{types::Json, PgPool};
@@ -175,6 +176,8 @@ pub async fn submit_application(
         payload.guardian_national_id.as_deref(),
     )
     .map_err(|error| pii_error("encrypt submit application", error))?;
+    let national_id_hashes = pii::hash_candidates(&payload.national_id)
+        .map_err(|error| pii_error("hash submit application", error))?;
 
     let mut tx = pool
         .begin()
@@ -200,9 +203,9 @@ pub async fn submit_application(
         .map_err(|_| AppError::InternalServerError("Lock failed".to_string()))?;
 
     let already_applied: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM admission_applications WHERE national_id_hash = $1 AND admission_round_id = $2)",
+        "SELECT EXISTS(SELECT 1 FROM admission_applications WHERE national_id_hash = ANY($1) AND admission_round_id = $2)",
     )
-    .bind(&encrypted_pii.national_id_hash)
+    .bind(&national_id_hashes)
     .bind(round_id)
     .fetch_one(&mut *tx)
     .await
@@ -413,11 +416,12 @@ pub async fn list_applications(
     if let Some(ref search) = filter.search {
         if !search.is_empty() {
             let like_term = format!("%{}%", search);
-            let national_id_hash = pii::hash_required(search)
+            let national_id_hashes = pii::hash_candidates(search)
                 .map_err(|error| pii_error("hash application list search national_id", error))?;
             query.push(" AND (");
-            query.push("aa.national_id_hash = ");
-            query.push_bind(national_id_hash);
+            query.push("aa.national_id_hash = ANY(");
+            query.push_bind(national_id_hashes);
+            query.push(")");
             query.push(" OR ");
             query.push("aa.first_name ILIKE ");
             query.push_bind(like_term.clone());
@@ -789,11 +793,13 @@ pub struct EnrollmentResult {
     pub student_code: String,
 }
 
+/// `max_students` is the limit of the school's plan; `None` is unlimited.
 pub async fn complete_enrollment(
     pool: &PgPool,
     id: Uuid,
     payload: CompleteEnrollmentRequest,
     enroller_id: Uuid,
+    max_students: Option<i64>,
 ) -> Result<EnrollmentResult, AppError> {
     let mut tx = pool
         .begin()
@@ -827,6 +833,8 @@ pub async fn complete_enrollment(
     let class_room_id = class_room_id
         .ok_or_else(|| AppError::BadRequest("ไม่พบข้อมูลห้องเรียน กรุณาตรวจสอบการจัดห้อง".to_string()))?;
 
+    ensure_student_quota(&mut tx, max_students).await?;
+
     let student_code = if let Some(code) = payload.student_code.filter(|c| !c.is_empty()) {
         code
     } else if let Some(pre) = application
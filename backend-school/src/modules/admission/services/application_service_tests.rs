@@ -0,0 +1,189 @@
+use crate::error::AppError;
+use crate::modules::admission::models::applications::CompleteEnrollmentRequest;
+use crate::test_helpers::{create_named_test_pool, run_test_migrations};
+use crate::utils::quota::QuotaCode;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::application_service;
+
+pub(super) struct EnrollmentFixture {
+    pub(super) round_id: Uuid,
+    pub(super) track_id: Uuid,
+    pub(super) class_room_id: Uuid,
+    pub(super) enroller_id: Uuid,
+}
+
+/// Round, track, classroom and staff user for accepted-application scenarios
+pub(super) async fn insert_fixture(pool: &PgPool) -> EnrollmentFixture {
+    let grade_level_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM grade_levels ORDER BY created_at, id LIMIT 1")
+            .fetch_one(pool)
+            .await
+            .expect("baseline grade level should exist");
+    let year_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO academic_years (year, name, start_date, end_date)
+         VALUES (9801, 'Enrollment Quota', '9801-01-01', '9801-12-31')
+         RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .expect("academic year should insert");
+    let study_plan_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO study_plans (code, name_th)
+         VALUES ('ENROLL-QUOTA', 'Enrollment Quota')
+         RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .expect("study plan should insert");
+    let study_plan_version_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO study_plan_versions (study_plan_id, version_name, start_academic_year_id)
+         VALUES ($1, 'Test Version', $2)
+         RETURNING id",
+    )
+    .bind(study_plan_id)
+    .bind(year_id)
+    .fetch_one(pool)
+    .await
+    .expect("study-plan version should insert");
+    let class_room_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO class_rooms
+            (code, name, academic_year_id, grade_level_id, study_plan_version_id)
+         VALUES ('ENROLL-QUOTA', 'Enrollment Quota Room', $1, $2, $3)
+         RETURNING id",
+    )
+    .bind(year_id)
+    .bind(grade_level_id)
+    .bind(study_plan_version_id)
+    .fetch_one(pool)
+    .await
+    .expect("classroom should insert");
+    let round_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO admission_rounds (
+            academic_year_id, grade_level_id, name, apply_start_date, apply_end_date
+         ) VALUES ($1, $2, 'Enrollment Quota Round', CURRENT_DATE, CURRENT_DATE)
+         RETURNING id",
+    )
+    .bind(year_id)
+    .bind(grade_level_id)
+    .fetch_one(pool)
+    .await
+    .expect("admission round should insert");
+    let track_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO admission_tracks (admission_round_id, study_plan_id, name)
+         VALUES ($1, $2, 'Enrollment Quota Track')
+         RETURNING id",
+    )
+    .bind(round_id)
+    .bind(study_plan_id)
+    .fetch_one(pool)
+    .await
+    .expect("admission track should insert");
+    let enroller_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (password_hash, first_name, last_name, user_type, status)
+         VALUES ('test', 'Enroller', 'Staff', 'staff', 'active')
+         RETURNING id",
+    )
+    .fetch_one(pool)
+    .await
+    .expect("enroller should insert");
+
+    EnrollmentFixture {
+        round_id,
+        track_id,
+        class_room_id,
+        enroller_id,
+    }
+}
+
+/// An accepted application already assigned to the fixture's classroom
+pub(super) async fn insert_accepted_application(
+    pool: &PgPool,
+    fixture: &EnrollmentFixture,
+    hash_fill: char,
+) -> Uuid {
+    let application_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO admission_applications (
+            admission_round_id, admission_track_id, national_id,
+            national_id_hash, first_name, last_name, status
+         ) VALUES ($1, $2, 'encrypted-test-value', repeat($3, 64),
+                   'ผู้สมัคร', 'ทดสอบ', 'accepted')
+         RETURNING id",
+    )
+    .bind(fixture.round_id)
+    .bind(fixture.track_id)
+    .bind(hash_fill.to_string())
+    .fetch_one(pool)
+    .await
+    .expect("accepted application should insert");
+    sqlx::query(
+        "INSERT INTO admission_room_assignments (application_id, class_room_id)
+         VALUES ($1, $2)",
+    )
+    .bind(application_id)
+    .bind(fixture.class_room_id)
+    .execute(pool)
+    .await
+    .expect("room assignment should insert");
+    application_id
+}
+
+fn enrollment_request(student_code: &str) -> CompleteEnrollmentRequest {
+    CompleteEnrollmentRequest {
+        student_code: Some(student_code.to_string()),
+        form_data: None,
+    }
+}
+
+#[tokio::test]
+async fn complete_enrollment_stops_at_the_plan_student_limit() {
+    let pool = create_named_test_pool("admission_enrollment_student_quota").await;
+    run_test_migrations(&pool).await;
+    let fixture = insert_fixture(&pool).await;
+    let first = insert_accepted_application(&pool, &fixture, 'e').await;
+    let second = insert_accepted_application(&pool, &fixture, 'f').await;
+    let active_students: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM users WHERE user_type = 'student' AND status = 'active'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let max_students = Some(active_students + 1);
+
+    application_service::complete_enrollment(
+        &pool,
+        first,
+        enrollment_request("980001"),
+        fixture.enroller_id,
+        max_students,
+    )
+    .await
+    .expect("enrollment within the limit should succeed");
+    let result = application_service::complete_enrollment(
+        &pool,
+        second,
+        enrollment_request("980002"),
+        fixture.enroller_id,
+        max_students,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(AppError::QuotaExceeded(quota)) if quota.code == QuotaCode::StudentLimitReached
+    ));
+    let second_status: String =
+        sqlx::query_scalar("SELECT status FROM admission_applications WHERE id = $1")
+            .bind(second)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(second_status, "accepted");
+    let second_account: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE username = '980002'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(second_account, 0);
+}
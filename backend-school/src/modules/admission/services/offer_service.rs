@@ -0,0 +1,945 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool, Postgres, Transaction};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::db::{admin_client::AdminClient, pool_manager::PoolManager};
+use crate::error::AppError;
+use crate::modules::admission::models::offers::*;
+use crate::modules::admission::models::rounds::SelectionSettings;
+use crate::modules::admission::services::selection_service::{self, TrackRankingEntry};
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::permissions::registry::codes;
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+/// Applicants can only confirm while the round is enrolling, so deadlines
+/// must not start running earlier.
+const OFFER_ROUND_STATUS: &str = "enrolling";
+const SEAT_OFFER_STATUSES: &[&str] = &["pending", "accepted", "declined", "expired"];
+const OVERDUE_BATCH_LIMIT: i64 = 500;
+
+/// A waitlisted applicant can still be offered a seat: not excluded from the
+/// ranking and not already holding one.
+const WAITLIST_ELIGIBLE_PREDICATE: &str = r#"aa.status NOT IN ('rejected', 'withdrawn', 'absent', 'enrolled')
+  AND NOT EXISTS (
+      SELECT 1 FROM admission_room_assignments seat WHERE seat.application_id = aa.id
+  )"#;
+
+const SEAT_OFFER_SELECT: &str = r#"
+SELECT o.id, o.application_id, aa.application_number,
+       CONCAT(COALESCE(aa.title, ''), aa.first_name, ' ', aa.last_name) AS applicant_name,
+       o.admission_track_id, t.name AS track_name,
+       o.class_room_id, cr.name AS room_name,
+       o.source, o.waitlist_position, o.replaces_offer_id, o.status,
+       o.offered_at, o.deadline_at, o.responded_at, o.offered_by
+FROM admission_seat_offers o
+JOIN admission_applications aa ON aa.id = o.application_id
+JOIN admission_tracks t ON t.id = o.admission_track_id
+LEFT JOIN class_rooms cr ON cr.id = o.class_room_id
+"#;
+
+fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read admission seat offers: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลการเสนอสิทธิ์ได้".to_string())
+}
+
+fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write admission seat offers: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกการเสนอสิทธิ์ได้".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatReleaseReason {
+    Declined,
+    Expired,
+}
+
+impl SeatReleaseReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SeatReleaseReason::Declined => "declined",
+            SeatReleaseReason::Expired => "expired",
+        }
+    }
+}
+
+/// A seat given up by a declined or expired offer, and who it went to next.
+#[derive(Debug)]
+pub struct SeatRelease {
+    pub round_id: Uuid,
+    pub track_name: String,
+    pub reason: SeatReleaseReason,
+    pub released_application_number: Option<String>,
+    pub reoffered: bool,
+    pub next_application_number: Option<String>,
+}
+
+/// Waitlist positions follow the track ranking: applicants past the seat
+/// capacity in selection order, skipping anyone already offered a seat.
+pub fn waitlist_positions(
+    ranking: &[TrackRankingEntry],
+    already_offered: &HashSet<Uuid>,
+) -> Vec<(Uuid, i32)> {
+    let mut overflow: Vec<&TrackRankingEntry> = ranking
+        .iter()
+        .filter(|entry| entry.is_overflow && !already_offered.contains(&entry.application_id))
+        .collect();
+    overflow.sort_by_key(|entry| entry.selection_rank);
+    overflow
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| (entry.application_id, (index + 1) as i32))
+        .collect()
+}
+
+pub fn sum_offer_counts<'a>(
+    counts: impl IntoIterator<Item = &'a SeatOfferCounts>,
+) -> SeatOfferCounts {
+    counts
+        .into_iter()
+        .fold(SeatOfferCounts::default(), |total, row| SeatOfferCounts {
+            offers_made: total.offers_made + row.offers_made,
+            waitlist_offers: total.waitlist_offers + row.waitlist_offers,
+            accepted: total.accepted + row.accepted,
+            declined: total.declined + row.declined,
+            expired: total.expired + row.expired,
+            pending: total.pending + row.pending,
+        })
+}
+
+pub fn seat_release_notification_text(release: &SeatRelease) -> (String, String) {
+    let released = release
+        .released_application_number
+        .as_deref()
+        .unwrap_or("-");
+    let (title, action) = match release.reason {
+        SeatReleaseReason::Declined => ("ผู้สมัครสละสิทธิ์เข้าเรียน", "สละสิทธิ์"),
+        SeatReleaseReason::Expired => ("หมดเวลายืนยันสิทธิ์เข้าเรียน", "ไม่ยืนยันสิทธิ์ภายในกำหนด"),
+    };
+    let follow_up = if release.reoffered {
+        format!(
+            "เสนอสิทธิ์ให้ลำดับสำรองเลขที่ {} แล้ว",
+            release.next_application_number.as_deref().unwrap_or("-")
+        )
+    } else {
+        "ไม่มีผู้สมัครในลำดับสำรองที่จะเลื่อนขึ้น".to_string()
+    };
+    (
+        title.to_string(),
+        format!(
+            "ผู้สมัครเลขที่ {released} สาย{} {action} — {follow_up}",
+            release.track_name
+        ),
+    )
+}
+
+fn validate_offer_status_filter(status: Option<&str>) -> Result<(), AppError> {
+    match status {
+        Some(status) if !SEAT_OFFER_STATUSES.contains(&status) => Err(AppError::BadRequest(
+            format!("สถานะการเสนอสิทธิ์ '{}' ไม่ถูกต้อง", status),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Rebuilds each track's waitlist from the current ranking and offers every
+/// assigned seat that has not been offered yet. Safe to run again after
+/// staff re-assign rooms: applicants with an offer keep their history.
+pub async fn open_seat_offers(
+    pool: &PgPool,
+    round_id: Uuid,
+    offered_by: Uuid,
+) -> Result<OpenSeatOffersResult, AppError> {
+    let (round_status, selection_settings): (String, Option<Json<SelectionSettings>>) =
+        sqlx::query_as("SELECT status, selection_settings FROM admission_rounds WHERE id = $1")
+            .bind(round_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(read_error)?
+            .ok_or_else(|| AppError::NotFound("ไม่พบรอบรับสมัคร".to_string()))?;
+    if round_status != OFFER_ROUND_STATUS {
+        return Err(AppError::BadRequest(
+            "เสนอสิทธิ์ได้เมื่อรอบอยู่ในช่วงรายงานตัวเท่านั้น".to_string(),
+        ));
+    }
+
+    let has_assignments: bool = sqlx::query_scalar(
+        r#"SELECT EXISTS(
+               SELECT 1 FROM admission_room_assignments ara
+               JOIN admission_applications aa ON aa.id = ara.application_id
+               WHERE aa.admission_round_id = $1
+           )"#,
+    )
+    .bind(round_id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+    if !has_assignments {
+        return Err(AppError::BadRequest(
+            "ยังไม่ได้จัดห้องเรียน กรุณาจัดห้องก่อนเสนอสิทธิ์".to_string(),
+        ));
+    }
+
+    let settings = selection_settings
+        .map(|Json(settings)| settings)
+        .unwrap_or_default();
+    let track_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM admission_tracks WHERE admission_round_id = $1 ORDER BY display_order ASC, created_at ASC",
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+    let already_offered: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+        r#"SELECT DISTINCT o.application_id
+           FROM admission_seat_offers o
+           JOIN admission_applications aa ON aa.id = o.application_id
+           WHERE aa.admission_round_id = $1"#,
+    )
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?
+    .into_iter()
+    .collect();
+
+    let mut offers_created = 0;
+    let mut waitlisted = 0;
+    for track_id in track_ids {
+        let ranking = selection_service::get_track_ranking(
+            pool,
+            track_id,
+            settings.ranking_subject_ids(track_id),
+            Some(settings.ranking_method(track_id)),
+        )
+        .await?;
+        let (application_ids, positions): (Vec<Uuid>, Vec<i32>) =
+            waitlist_positions(&ranking.applications, &already_offered)
+                .into_iter()
+                .unzip();
+
+        let mut tx = pool.begin().await.map_err(write_error)?;
+        sqlx::query("DELETE FROM admission_waitlist_entries WHERE admission_track_id = $1")
+            .bind(track_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(write_error)?;
+        if !application_ids.is_empty() {
+            sqlx::query(
+                r#"INSERT INTO admission_waitlist_entries (application_id, admission_track_id, position)
+                   SELECT entry.application_id, $3, entry.position
+                   FROM UNNEST($1::uuid[], $2::int[]) AS entry(application_id, position)"#,
+            )
+            .bind(&application_ids)
+            .bind(&positions)
+            .bind(track_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(write_error)?;
+        }
+        let created = sqlx::query(
+            r#"INSERT INTO admission_seat_offers (
+                   application_id, admission_track_id, class_room_id, source, deadline_at, offered_by
+               )
+               SELECT aa.id, t.id, ara.class_room_id, 'initial',
+                      now() + make_interval(hours => t.confirmation_window_hours), $2
+               FROM admission_applications aa
+               JOIN admission_room_assignments ara ON ara.application_id = aa.id
+               JOIN admission_tracks t ON t.id = $1
+               WHERE COALESCE(aa.room_assignment_track_id, aa.admission_track_id) = $1
+                 AND aa.status = 'accepted'
+                 AND ara.student_confirmed = false
+                 AND NOT EXISTS (
+                     SELECT 1 FROM admission_seat_offers o WHERE o.application_id = aa.id
+                 )"#,
+        )
+        .bind(track_id)
+        .bind(offered_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(write_error)?
+        .rows_affected();
+        tx.commit().await.map_err(write_error)?;
+
+        offers_created += created;
+        waitlisted += application_ids.len();
+    }
+
+    Ok(OpenSeatOffersResult {
+        offers_created,
+        waitlisted,
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct LatestOffer {
+    id: Uuid,
+    status: String,
+    open: bool,
+}
+
+/// Accepts the applicant's latest offer inside the caller's confirmation
+/// transaction. The offer row is locked first, so an expiry or decline racing
+/// the confirmation either commits before it (and the confirmation is
+/// rejected) or finds the offer already accepted. Applicants without an offer
+/// (offers never opened for the round) confirm as before; an offer past its
+/// deadline can no longer be accepted even if the expiry job has not run yet.
+pub async fn accept_pending_offer(
+    tx: &mut Transaction<'_, Postgres>,
+    application_id: Uuid,
+) -> Result<(), AppError> {
+    let offer = sqlx::query_as::<_, LatestOffer>(
+        r#"SELECT id, status, deadline_at > now() AS open
+           FROM admission_seat_offers
+           WHERE application_id = $1
+           ORDER BY offered_at DESC
+           LIMIT 1
+           FOR UPDATE"#,
+    )
+    .bind(application_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(read_error)?;
+    let Some(offer) = offer else {
+        return Ok(());
+    };
+
+    match offer.status.as_str() {
+        "accepted" => Ok(()),
+        "pending" if offer.open => {
+            sqlx::query(
+                "UPDATE admission_seat_offers SET status = 'accepted', responded_at = now() WHERE id = $1",
+            )
+            .bind(offer.id)
+            .execute(&mut **tx)
+            .await
+            .map_err(write_error)?;
+            Ok(())
+        }
+        "pending" => Err(AppError::BadRequest(
+            "หมดเวลายืนยันสิทธิ์แล้ว สิทธิ์จะถูกเสนอให้ผู้สมัครลำดับสำรอง".to_string(),
+        )),
+        _ => Err(AppError::BadRequest(
+            "สิทธิ์เข้าเรียนนี้ถูกสละหรือหมดเวลายืนยันแล้ว ไม่สามารถยืนยันได้".to_string(),
+        )),
+    }
+}
+
+pub async fn decline_pending_offer(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<SeatRelease, AppError> {
+    let offer_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM admission_seat_offers WHERE application_id = $1 AND status = 'pending'",
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::BadRequest("ไม่มีสิทธิ์เข้าเรียนที่รอการยืนยัน".to_string()))?;
+
+    release_offer(pool, offer_id, SeatReleaseReason::Declined)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("สิทธิ์นี้ได้รับการดำเนินการไปแล้ว".to_string()))
+}
+
+/// Expires pending offers whose deadline has passed, optionally limited to
+/// one round, and re-offers each released seat.
+pub async fn expire_overdue_offers(
+    pool: &PgPool,
+    round_id: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<Vec<SeatRelease>, AppError> {
+    let due: Vec<Uuid> = sqlx::query_scalar(
+        r#"SELECT o.id
+           FROM admission_seat_offers o
+           JOIN admission_tracks t ON t.id = o.admission_track_id
+           WHERE o.status = 'pending'
+             AND o.deadline_at <= $1
+             AND ($2::uuid IS NULL OR t.admission_round_id = $2)
+           ORDER BY o.deadline_at ASC, o.offered_at ASC
+           LIMIT $3"#,
+    )
+    .bind(now)
+    .bind(round_id)
+    .bind(OVERDUE_BATCH_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut releases = Vec::with_capacity(due.len());
+    for offer_id in due {
+        if let Some(release) = release_offer(pool, offer_id, SeatReleaseReason::Expired).await? {
+            releases.push(release);
+        }
+    }
+    Ok(releases)
+}
+
+#[derive(sqlx::FromRow)]
+struct LockedOffer {
+    application_id: Uuid,
+    admission_track_id: Uuid,
+    class_room_id: Uuid,
+    application_number: Option<String>,
+    round_id: Uuid,
+    track_name: String,
+    confirmation_window_hours: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct WaitlistCandidate {
+    application_id: Uuid,
+    position: i32,
+    application_number: Option<String>,
+}
+
+/// Closes one pending offer and hands its seat (same room and rank) to the
+/// best eligible waitlisted applicant of the track, all in one transaction.
+/// Returns `None` when the offer was already closed by someone else.
+async fn release_offer(
+    pool: &PgPool,
+    offer_id: Uuid,
+    reason: SeatReleaseReason,
+) -> Result<Option<SeatRelease>, AppError> {
+    let mut tx = pool.begin().await.map_err(write_error)?;
+    let offer = sqlx::query_as::<_, LockedOffer>(
+        r#"SELECT o.application_id, o.admission_track_id, o.class_room_id,
+                  aa.application_number, t.admission_round_id AS round_id,
+                  t.name AS track_name, t.confirmation_window_hours
+           FROM admission_seat_offers o
+           JOIN admission_applications aa ON aa.id = o.application_id
+           JOIN admission_tracks t ON t.id = o.admission_track_id
+           WHERE o.id = $1 AND o.status = 'pending'
+           FOR UPDATE OF o"#,
+    )
+    .bind(offer_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(read_error)?;
+    let Some(offer) = offer else {
+        return Ok(None);
+    };
+
+    sqlx::query("UPDATE admission_seat_offers SET status = $2, responded_at = now() WHERE id = $1")
+        .bind(offer_id)
+        .bind(reason.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(write_error)?;
+    sqlx::query(
+        "UPDATE admission_applications SET status = 'withdrawn', updated_at = now() WHERE id = $1 AND status = 'accepted'",
+    )
+    .bind(offer.application_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(write_error)?;
+    let ranks: Option<(Option<i32>, Option<i32>)> = sqlx::query_as(
+        "DELETE FROM admission_room_assignments WHERE application_id = $1 RETURNING rank_in_track, rank_in_room",
+    )
+    .bind(offer.application_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(write_error)?;
+    let (rank_in_track, rank_in_room) = ranks.unwrap_or((None, None));
+
+    let candidate = sqlx::query_as::<_, WaitlistCandidate>(&format!(
+        r#"SELECT w.application_id, w.position, aa.application_number
+           FROM admission_waitlist_entries w
+           JOIN admission_applications aa ON aa.id = w.application_id
+           WHERE w.admission_track_id = $1
+             AND {WAITLIST_ELIGIBLE_PREDICATE}
+           ORDER BY w.position ASC
+           LIMIT 1
+           FOR UPDATE OF w SKIP LOCKED"#
+    ))
+    .bind(offer.admission_track_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(read_error)?;
+
+    if let Some(candidate) = &candidate {
+        sqlx::query(
+            r#"INSERT INTO admission_room_assignments (
+                   application_id, class_room_id, rank_in_track, rank_in_room,
+                   total_score, full_score, assigned_at
+               )
+               SELECT $1, $2, $3, $4, score.total, score.total, now()
+               FROM (
+                   SELECT COALESCE(SUM(score), 0)::double precision AS total
+                   FROM admission_exam_scores
+                   WHERE application_id = $1
+               ) score
+               ON CONFLICT (application_id) DO UPDATE SET
+                   class_room_id = EXCLUDED.class_room_id,
+                   rank_in_track = EXCLUDED.rank_in_track,
+                   rank_in_room = EXCLUDED.rank_in_room,
+                   total_score = EXCLUDED.total_score,
+                   full_score = EXCLUDED.full_score,
+                   assigned_by = NULL,
+                   assigned_at = EXCLUDED.assigned_at,
+                   student_confirmed = false,
+                   student_confirmed_at = NULL"#,
+        )
+        .bind(candidate.application_id)
+        .bind(offer.class_room_id)
+        .bind(rank_in_track)
+        .bind(rank_in_room)
+        .execute(&mut *tx)
+        .await
+        .map_err(write_error)?;
+        sqlx::query(
+            r#"UPDATE admission_applications
+               SET status = 'accepted',
+                   room_assignment_track_id = NULLIF($2, admission_track_id),
+                   updated_at = now()
+               WHERE id = $1"#,
+        )
+        .bind(candidate.application_id)
+        .bind(offer.admission_track_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(write_error)?;
+        sqlx::query(
+            r#"INSERT INTO admission_seat_offers (
+                   application_id, admission_track_id, class_room_id, source,
+                   waitlist_position, replaces_offer_id, deadline_at
+               )
+               VALUES ($1, $2, $3, 'waitlist', $4, $5, now() + make_interval(hours => $6))"#,
+        )
+        .bind(candidate.application_id)
+        .bind(offer.admission_track_id)
+        .bind(offer.class_room_id)
+        .bind(candidate.position)
+        .bind(offer_id)
+        .bind(offer.confirmation_window_hours)
+        .execute(&mut *tx)
+        .await
+        .map_err(write_error)?;
+        sqlx::query("DELETE FROM admission_waitlist_entries WHERE application_id = $1")
+            .bind(candidate.application_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(write_error)?;
+    }
+
+    tx.commit().await.map_err(write_error)?;
+
+    Ok(Some(SeatRelease {
+        round_id: offer.round_id,
+        track_name: offer.track_name,
+        reason,
+        released_application_number: offer.application_number,
+        reoffered: candidate.is_some(),
+        next_application_number: candidate.and_then(|candidate| candidate.application_number),
+    }))
+}
+
+pub async fn list_round_offers(
+    pool: &PgPool,
+    round_id: Uuid,
+    query: ListSeatOffersQuery,
+) -> Result<Vec<SeatOffer>, AppError> {
+    validate_offer_status_filter(query.status.as_deref())?;
+    sqlx::query_as::<_, SeatOffer>(&format!(
+        r#"{SEAT_OFFER_SELECT}
+           WHERE t.admission_round_id = $1
+             AND ($2::text IS NULL OR o.status = $2)
+             AND ($3::uuid IS NULL OR o.admission_track_id = $3)
+           ORDER BY t.display_order ASC, o.offered_at ASC"#
+    ))
+    .bind(round_id)
+    .bind(query.status)
+    .bind(query.track_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Every offer made to one application, oldest first.
+pub async fn list_application_offers(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<SeatOffer>, AppError> {
+    sqlx::query_as::<_, SeatOffer>(&format!(
+        "{SEAT_OFFER_SELECT} WHERE o.application_id = $1 ORDER BY o.offered_at ASC"
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+pub async fn get_track_waitlist(
+    pool: &PgPool,
+    track_id: Uuid,
+) -> Result<Vec<WaitlistEntry>, AppError> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM admission_tracks WHERE id = $1)")
+            .bind(track_id)
+            .fetch_one(pool)
+            .await
+            .map_err(read_error)?;
+    if !exists {
+        return Err(AppError::NotFound("ไม่พบสายการเรียน".to_string()));
+    }
+
+    sqlx::query_as::<_, WaitlistEntry>(&format!(
+        r#"SELECT w.position, w.application_id, aa.application_number,
+                  CONCAT(COALESCE(aa.title, ''), aa.first_name, ' ', aa.last_name) AS applicant_name,
+                  aa.status AS application_status,
+                  ({WAITLIST_ELIGIBLE_PREDICATE}) AS eligible
+           FROM admission_waitlist_entries w
+           JOIN admission_applications aa ON aa.id = w.application_id
+           WHERE w.admission_track_id = $1
+           ORDER BY w.position ASC"#
+    ))
+    .bind(track_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+#[derive(sqlx::FromRow)]
+struct TrackReportRow {
+    track_id: Uuid,
+    track_name: String,
+    confirmation_window_hours: i32,
+    waitlist_remaining: i64,
+    offers_made: i64,
+    waitlist_offers: i64,
+    accepted: i64,
+    declined: i64,
+    expired: i64,
+    pending: i64,
+}
+
+/// Offers made, accepted, declined and expired per track, with what is left
+/// on each waitlist.
+pub async fn get_offer_report(pool: &PgPool, round_id: Uuid) -> Result<SeatOfferReport, AppError> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM admission_rounds WHERE id = $1)")
+            .bind(round_id)
+            .fetch_one(pool)
+            .await
+            .map_err(read_error)?;
+    if !exists {
+        return Err(AppError::NotFound("ไม่พบรอบรับสมัคร".to_string()));
+    }
+
+    let rows = sqlx::query_as::<_, TrackReportRow>(&format!(
+        r#"SELECT t.id AS track_id, t.name AS track_name, t.confirmation_window_hours,
+                  (SELECT COUNT(*)
+                   FROM admission_waitlist_entries w
+                   JOIN admission_applications aa ON aa.id = w.application_id
+                   WHERE w.admission_track_id = t.id
+                     AND {WAITLIST_ELIGIBLE_PREDICATE}) AS waitlist_remaining,
+                  COUNT(o.id) AS offers_made,
+                  COUNT(o.id) FILTER (WHERE o.source = 'waitlist') AS waitlist_offers,
+                  COUNT(o.id) FILTER (WHERE o.status = 'accepted') AS accepted,
+                  COUNT(o.id) FILTER (WHERE o.status = 'declined') AS declined,
+                  COUNT(o.id) FILTER (WHERE o.status = 'expired') AS expired,
+                  COUNT(o.id) FILTER (WHERE o.status = 'pending') AS pending
+           FROM admission_tracks t
+           LEFT JOIN admission_seat_offers o ON o.admission_track_id = t.id
+           WHERE t.admission_round_id = $1
+           GROUP BY t.id
+           ORDER BY t.display_order ASC, t.created_at ASC"#
+    ))
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let tracks: Vec<TrackSeatOfferSummary> = rows
+        .into_iter()
+        .map(|row| TrackSeatOfferSummary {
+            track_id: row.track_id,
+            track_name: row.track_name,
+            confirmation_window_hours: row.confirmation_window_hours,
+            waitlist_remaining: row.waitlist_remaining,
+            counts: SeatOfferCounts {
+                offers_made: row.offers_made,
+                waitlist_offers: row.waitlist_offers,
+                accepted: row.accepted,
+                declined: row.declined,
+                expired: row.expired,
+                pending: row.pending,
+            },
+        })
+        .collect();
+    let totals = sum_offer_counts(tracks.iter().map(|track| &track.counts));
+
+    Ok(SeatOfferReport {
+        round_id,
+        generated_at: Utc::now(),
+        tracks,
+        totals,
+    })
+}
+
+/// Users holding the admission enrollment permission through a role or an
+/// organization grant.
+async fn enrollment_staff_ids(pool: &PgPool) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT holder.user_id
+        FROM (
+            SELECT ur.user_id
+            FROM user_roles ur
+            JOIN roles r ON r.id = ur.role_id AND r.is_active = true
+            JOIN role_permissions rp ON rp.role_id = r.id
+            JOIN permissions p ON p.id = rp.permission_id
+            WHERE p.code = $1
+              AND ur.ended_at IS NULL
+
+            UNION
+
+            SELECT om.user_id
+            FROM organization_members om
+            JOIN organization_units ou ON ou.id = om.organization_unit_id AND ou.is_active = true
+            JOIN organization_permission_grants opg ON opg.organization_unit_id = ou.id
+            JOIN permissions p ON p.id = opg.permission_id
+            WHERE p.code = $1
+              AND (om.ended_at IS NULL OR om.ended_at > CURRENT_DATE)
+              AND (opg.position_code IS NULL OR opg.position_code = om.position_code)
+        ) holder
+        JOIN users u ON u.id = holder.user_id AND u.status = 'active'
+        "#,
+    )
+    .bind(codes::ADMISSION_ENROLL_ALL)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Tells enrollment staff that a seat was given up and who it went to.
+/// Applicants have no accounts; they see their offer and its deadline on the
+/// portal. A failed send is logged and does not stop the rest.
+pub async fn notify_seat_released(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    release: &SeatRelease,
+) -> Result<usize, AppError> {
+    let (title, message) = seat_release_notification_text(release);
+    let link = format!("/staff/academic/admission/{}/enrollment", release.round_id);
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let mut sent = 0;
+    for user_id in enrollment_staff_ids(pool).await? {
+        match NotificationService::send(
+            pool,
+            &publisher,
+            user_id,
+            &title,
+            &message,
+            NotificationType::Warning,
+            Some(&link),
+        )
+        .await
+        {
+            Ok(_) => sent += 1,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to send admission seat release notification: {}",
+                    error
+                )
+            }
+        }
+    }
+    Ok(sent)
+}
+
+pub async fn process_overdue_offers(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    round_id: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<ProcessedSeatOffers, AppError> {
+    let releases = expire_overdue_offers(pool, round_id, now).await?;
+    for release in &releases {
+        if let Err(error) = notify_seat_released(pool, notification_channel, tenant, release).await
+        {
+            tracing::error!("Admission seat release notification failed: {}", error);
+        }
+    }
+    Ok(ProcessedSeatOffers {
+        expired: releases.len(),
+        reoffered: releases.iter().filter(|release| release.reoffered).count(),
+    })
+}
+
+pub async fn process_overdue_offers_for_all_tenants(
+    admin_client: Arc<AdminClient>,
+    pool_manager: Arc<PoolManager>,
+    notification_channel: broadcast::Sender<TenantNotificationEvent>,
+) {
+    let now = Utc::now();
+    let schools = match admin_client.list_active_schools().await {
+        Ok(schools) => schools,
+        Err(error) => {
+            tracing::error!("Failed to fetch schools for admission offers: {}", error);
+            return;
+        }
+    };
+
+    for school in schools {
+        if school.is_migrating() {
+            tracing::info!(
+                "Skipping admission offer expiry for {}: migration in progress",
+                school.subdomain
+            );
+            continue;
+        }
+        let Some(db_url) = school
+            .db_connection_string
+            .filter(|value| !value.is_empty())
+        else {
+            tracing::warn!(
+                "Skipping admission offer expiry for {}: no database URL",
+                school.subdomain
+            );
+            continue;
+        };
+
+        match pool_manager.get_pool(&db_url, &school.subdomain).await {
+            Ok(pool) => {
+                if let Err(error) = process_overdue_offers(
+                    &pool,
+                    &notification_channel,
+                    &school.subdomain,
+                    None,
+                    now,
+                )
+                .await
+                {
+                    tracing::error!(
+                        "Admission offer expiry failed for {}: {}",
+                        school.subdomain,
+                        error
+                    );
+                }
+            }
+            Err(error) => {
+                tracing::error!(
+                    "Failed to open tenant pool for admission offers {}: {}",
+                    school.subdomain,
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranking_entry(selection_rank: i64, is_overflow: bool) -> TrackRankingEntry {
+        TrackRankingEntry {
+            application_id: Uuid::new_v4(),
+            application_number: None,
+            national_id: String::new(),
+            full_name: String::new(),
+            selection_score: 0.0,
+            total_score: 0.0,
+            selection_rank,
+            final_rank: None,
+            assigned_room: None,
+            assigned_room_id: None,
+            room_saved: !is_overflow,
+            is_overflow,
+            is_track_overridden: false,
+            original_track_name: None,
+            gender: None,
+        }
+    }
+
+    fn release(reason: SeatReleaseReason, next: Option<&str>) -> SeatRelease {
+        SeatRelease {
+            round_id: Uuid::new_v4(),
+            track_name: "วิทย์-คณิต".to_string(),
+            reason,
+            released_application_number: Some("A001".to_string()),
+            reoffered: next.is_some(),
+            next_application_number: next.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn waitlist_positions_follow_selection_rank_and_skip_offered_applicants() {
+        let seated = ranking_entry(1, false);
+        let fourth = ranking_entry(4, true);
+        let second = ranking_entry(2, true);
+        let third = ranking_entry(3, true);
+        let expected = vec![(second.application_id, 1), (fourth.application_id, 2)];
+        let already_offered = HashSet::from([third.application_id]);
+
+        let positions = waitlist_positions(&[seated, fourth, second, third], &already_offered);
+
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn sum_offer_counts_adds_every_track() {
+        let first = SeatOfferCounts {
+            offers_made: 5,
+            waitlist_offers: 1,
+            accepted: 3,
+            declined: 1,
+            expired: 0,
+            pending: 1,
+        };
+        let second = SeatOfferCounts {
+            offers_made: 2,
+            waitlist_offers: 0,
+            accepted: 0,
+            declined: 0,
+            expired: 1,
+            pending: 1,
+        };
+
+        assert_eq!(
+            sum_offer_counts([&first, &second]),
+            SeatOfferCounts {
+                offers_made: 7,
+                waitlist_offers: 1,
+                accepted: 3,
+                declined: 1,
+                expired: 1,
+                pending: 2,
+            }
+        );
+        assert_eq!(sum_offer_counts([]), SeatOfferCounts::default());
+    }
+
+    #[test]
+    fn seat_release_notification_names_next_applicant_or_empty_waitlist() {
+        let (title, message) =
+            seat_release_notification_text(&release(SeatReleaseReason::Declined, Some("A042")));
+        assert_eq!(title, "ผู้สมัครสละสิทธิ์เข้าเรียน");
+        assert!(message.contains("A001"));
+        assert!(message.contains("ลำดับสำรองเลขที่ A042"));
+
+        let (title, message) =
+            seat_release_notification_text(&release(SeatReleaseReason::Expired, None));
+        assert_eq!(title, "หมดเวลายืนยันสิทธิ์เข้าเรียน");
+        assert!(message.contains("ไม่มีผู้สมัครในลำดับสำรอง"));
+    }
+
+    #[test]
+    fn offer_status_filter_accepts_known_statuses_only() {
+        assert!(validate_offer_status_filter(None).is_ok());
+        assert!(validate_offer_status_filter(Some("expired")).is_ok());
+        assert!(matches!(
+            validate_offer_status_filter(Some("cancelled")),
+            Err(AppError::BadRequest(message)) if message.contains("cancelled")
+        ));
+    }
+}
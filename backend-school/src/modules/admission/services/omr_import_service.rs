@@ -0,0 +1,949 @@
+use crate::error::AppError;
+use crate::modules::admission::models::applications::{BulkScoreEntry, UpdateScoreEntry};
+use crate::modules::admission::models::score_imports::*;
+use crate::modules::admission::services::score_service;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+pub const MAX_ANSWER_KEY_ITEMS: usize = 500;
+pub const MAX_OMR_SHEETS: usize = 5_000;
+const MAX_FILE_NAME_CHARS: usize = 255;
+
+/// In a key, an item every scored sheet is credited for.
+const CANCELLED_ITEM: char = '*';
+const INACTIVE_APPLICATION_STATUSES: [&str; 2] = ["rejected", "withdrawn"];
+
+/// Share of sheets in each of the upper and lower groups for discrimination.
+const DISCRIMINATION_GROUP_FRACTION: f64 = 0.27;
+const TOO_EASY_DIFFICULTY: f64 = 0.9;
+const TOO_HARD_DIFFICULTY: f64 = 0.2;
+const LOW_DISCRIMINATION: f64 = 0.2;
+
+fn read_error(context: &str, error: sqlx::Error) -> AppError {
+    tracing::error!("{}: {}", context, error);
+    AppError::InternalServerError("ไม่สามารถโหลดข้อมูลการนำเข้าคะแนนได้".to_string())
+}
+
+fn write_error(context: &str, error: sqlx::Error) -> AppError {
+    tracing::error!("{}: {}", context, error);
+    AppError::InternalServerError("ไม่สามารถบันทึกคะแนนที่นำเข้าได้".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SheetMark {
+    Choice(char),
+    Blank,
+    /// More than one bubble filled; counted wrong
+    Multiple,
+}
+
+/// Strips grouping whitespace and commas, upper-cases Latin letters and checks
+/// each item is a single answer symbol or `*`.
+pub fn normalize_answer_key(raw: &str) -> Result<String, AppError> {
+    let key: String = raw
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if key.is_empty() {
+        return Err(AppError::BadRequest("กรุณาระบุเฉลย".to_string()));
+    }
+    if key.chars().count() > MAX_ANSWER_KEY_ITEMS {
+        return Err(AppError::BadRequest(format!(
+            "เฉลยมีได้ไม่เกิน {} ข้อ",
+            MAX_ANSWER_KEY_ITEMS
+        )));
+    }
+    if let Some((index, symbol)) = key
+        .chars()
+        .enumerate()
+        .find(|(_, c)| *c != CANCELLED_ITEM && !c.is_alphanumeric())
+    {
+        return Err(AppError::BadRequest(format!(
+            "เฉลยข้อ {} ไม่ถูกต้อง: '{}'",
+            index + 1,
+            symbol
+        )));
+    }
+    if key.chars().all(|c| c == CANCELLED_ITEM) {
+        return Err(AppError::BadRequest(
+            "เฉลยต้องมีอย่างน้อยหนึ่งข้อที่ไม่ถูกยกเลิก".to_string(),
+        ));
+    }
+    Ok(key)
+}
+
+fn parse_mark(symbol: char) -> Option<SheetMark> {
+    match symbol {
+        ' ' | '-' | '.' | '_' => Some(SheetMark::Blank),
+        '*' | '?' | '#' => Some(SheetMark::Multiple),
+        c if c.is_alphanumeric() => Some(SheetMark::Choice(c.to_ascii_uppercase())),
+        _ => None,
+    }
+}
+
+/// One cell of the one-column-per-item layout; several symbols in a cell mean
+/// several filled bubbles.
+fn parse_item_cell(cell: &str) -> Option<SheetMark> {
+    let cell = cell.trim();
+    let mut symbols = cell.chars();
+    match (symbols.next(), symbols.next()) {
+        (None, _) => Some(SheetMark::Blank),
+        (Some(symbol), None) => parse_mark(symbol),
+        (Some(_), Some(_)) => cell
+            .chars()
+            .all(|c| matches!(parse_mark(c), Some(SheetMark::Choice(_))))
+            .then_some(SheetMark::Multiple),
+    }
+}
+
+/// Splits CSV text into non-empty records with the 1-based line each starts on.
+/// Handles quoted fields, doubled quotes, CRLF and a UTF-8 BOM.
+fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    fn push_record(records: &mut Vec<(usize, Vec<String>)>, line: usize, record: Vec<String>) {
+        if record.iter().any(|field| !field.trim().is_empty()) {
+            records.push((line, record));
+        }
+    }
+
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut record));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record_line, record);
+    }
+    records
+}
+
+#[derive(Debug, Clone)]
+struct RawSheet {
+    line: usize,
+    seat_number: String,
+    /// `None` where the symbol could not be read
+    marks: Vec<Option<SheetMark>>,
+}
+
+/// First column is the seat number; a single further column is an answer
+/// string (one symbol per item), more columns are one item each.
+fn sheets_from_records(records: Vec<(usize, Vec<String>)>, has_header: bool) -> Vec<RawSheet> {
+    records
+        .into_iter()
+        .skip(usize::from(has_header))
+        .map(|(line, fields)| {
+            let mut fields = fields.into_iter();
+            let seat_number = fields.next().unwrap_or_default().trim().to_string();
+            let answers: Vec<String> = fields.collect();
+            let marks = match answers.as_slice() {
+                [answer_string] => answer_string.chars().map(parse_mark).collect(),
+                cells => cells.iter().map(|cell| parse_item_cell(cell)).collect(),
+            };
+            RawSheet {
+                line,
+                seat_number,
+                marks,
+            }
+        })
+        .collect()
+}
+
+/// OMR machines often zero-pad numeric seat numbers, so leading zeros are
+/// ignored on both sides.
+fn normalize_seat_number(raw: &str) -> String {
+    let seat = raw.trim().to_uppercase();
+    if !seat.is_empty() && seat.chars().all(|c| c.is_ascii_digit()) {
+        let trimmed = seat.trim_start_matches('0');
+        return if trimmed.is_empty() {
+            "0".to_string()
+        } else {
+            trimmed.to_string()
+        };
+    }
+    seat
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct SeatRecord {
+    exam_id: String,
+    application_id: Uuid,
+    application_number: Option<String>,
+    applicant_name: String,
+    status: String,
+    score: Option<f64>,
+}
+
+fn is_inactive_status(status: &str) -> bool {
+    INACTIVE_APPLICATION_STATUSES.contains(&status)
+}
+
+fn count_correct(key: &[char], marks: &[SheetMark]) -> usize {
+    key.iter()
+        .zip(marks)
+        .filter(|(item, mark)| **item == CANCELLED_ITEM || **mark == SheetMark::Choice(**item))
+        .count()
+}
+
+fn marked_score(correct_count: usize, item_count: usize, max_score: f64) -> f64 {
+    if item_count == 0 {
+        return 0.0;
+    }
+    (correct_count as f64 * max_score / item_count as f64 * 100.0).round() / 100.0
+}
+
+fn classify_change(previous: Option<f64>, new_score: f64) -> OmrScoreChangeKind {
+    match previous {
+        None => OmrScoreChangeKind::New,
+        Some(score) if (score - new_score).abs() < 0.005 => OmrScoreChangeKind::Unchanged,
+        Some(_) => OmrScoreChangeKind::Changed,
+    }
+}
+
+#[derive(Debug)]
+struct ReadableSheet {
+    line: usize,
+    seat_number: String,
+    marks: Vec<SheetMark>,
+}
+
+#[derive(Debug, Default)]
+struct OmrEvaluation {
+    changes: Vec<OmrScoreChange>,
+    flagged: Vec<OmrFlaggedSheet>,
+    missing: Vec<OmrMissingSheet>,
+    /// Marks of every scored sheet, for item analysis
+    scored_marks: Vec<Vec<SheetMark>>,
+}
+
+impl OmrEvaluation {
+    fn flag(
+        &mut self,
+        line: usize,
+        seat_number: &str,
+        issue: OmrSheetIssue,
+        detail: String,
+        application_id: Option<Uuid>,
+    ) {
+        self.flagged.push(OmrFlaggedSheet {
+            line,
+            seat_number: seat_number.to_string(),
+            issue,
+            detail,
+            application_id,
+        });
+    }
+}
+
+fn evaluate_sheets(
+    key: &[char],
+    max_score: f64,
+    sheets: Vec<RawSheet>,
+    seats: &[SeatRecord],
+) -> OmrEvaluation {
+    let mut evaluation = OmrEvaluation::default();
+
+    let mut readable = Vec::with_capacity(sheets.len());
+    for sheet in sheets {
+        if sheet.seat_number.is_empty() {
+            evaluation.flag(
+                sheet.line,
+                "",
+                OmrSheetIssue::MissingSeat,
+                "ไม่มีเลขที่นั่งสอบ".to_string(),
+                None,
+            );
+            continue;
+        }
+        if sheet.marks.len() != key.len() {
+            evaluation.flag(
+                sheet.line,
+                &sheet.seat_number,
+                OmrSheetIssue::Unreadable,
+                format!("อ่านได้ {} ข้อ แต่เฉลยมี {} ข้อ", sheet.marks.len(), key.len()),
+                None,
+            );
+            continue;
+        }
+        if let Some(index) = sheet.marks.iter().position(Option::is_none) {
+            evaluation.flag(
+                sheet.line,
+                &sheet.seat_number,
+                OmrSheetIssue::Unreadable,
+                format!("ข้อ {} มีสัญลักษณ์ที่อ่านไม่ได้", index + 1),
+                None,
+            );
+            continue;
+        }
+        let marks: Vec<SheetMark> = sheet.marks.into_iter().flatten().collect();
+        if marks.iter().all(|mark| *mark == SheetMark::Blank) {
+            evaluation.flag(
+                sheet.line,
+                &sheet.seat_number,
+                OmrSheetIssue::BlankSheet,
+                "ไม่มีการฝนคำตอบ".to_string(),
+                None,
+            );
+            continue;
+        }
+        readable.push(ReadableSheet {
+            line: sheet.line,
+            seat_number: sheet.seat_number,
+            marks,
+        });
+    }
+
+    let mut seat_order: Vec<String> = Vec::new();
+    let mut sheets_by_seat: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, sheet) in readable.iter().enumerate() {
+        let seat = normalize_seat_number(&sheet.seat_number);
+        let group = sheets_by_seat.entry(seat.clone()).or_default();
+        if group.is_empty() {
+            seat_order.push(seat);
+        }
+        group.push(index);
+    }
+
+    let mut unique = Vec::with_capacity(seat_order.len());
+    for seat in &seat_order {
+        let group = &sheets_by_seat[seat];
+        let first = &readable[group[0]];
+        if group
+            .iter()
+            .all(|&index| readable[index].marks == first.marks)
+        {
+            for &index in &group[1..] {
+                let sheet = &readable[index];
+                evaluation.flag(
+                    sheet.line,
+                    &sheet.seat_number,
+                    OmrSheetIssue::Duplicate,
+                    format!("ซ้ำกับบรรทัด {} — ใช้บรรทัดแรก", first.line),
+                    None,
+                );
+            }
+            unique.push(group[0]);
+        } else {
+            for &index in group {
+                let sheet = &readable[index];
+                evaluation.flag(
+                    sheet.line,
+                    &sheet.seat_number,
+                    OmrSheetIssue::DuplicateConflict,
+                    format!("เลขที่นั่งนี้มี {} แผ่นที่คำตอบต่างกัน", group.len()),
+                    None,
+                );
+            }
+        }
+    }
+
+    let seat_index: HashMap<String, &SeatRecord> = seats
+        .iter()
+        .map(|seat| (normalize_seat_number(&seat.exam_id), seat))
+        .collect();
+    let mut matched = HashSet::new();
+    for index in unique {
+        let sheet = &readable[index];
+        let Some(seat) = seat_index.get(&normalize_seat_number(&sheet.seat_number)) else {
+            evaluation.flag(
+                sheet.line,
+                &sheet.seat_number,
+                OmrSheetIssue::UnknownSeat,
+                "ไม่พบเลขที่นั่งสอบนี้ในรอบรับสมัคร".to_string(),
+                None,
+            );
+            continue;
+        };
+        matched.insert(seat.application_id);
+        if is_inactive_status(&seat.status) {
+            evaluation.flag(
+                sheet.line,
+                &sheet.seat_number,
+                OmrSheetIssue::InactiveApplication,
+                format!("ใบสมัครอยู่ในสถานะ {}", seat.status),
+                Some(seat.application_id),
+            );
+            continue;
+        }
+        let correct_count = count_correct(key, &sheet.marks);
+        let new_score = marked_score(correct_count, key.len(), max_score);
+        evaluation.changes.push(OmrScoreChange {
+            line: sheet.line,
+            seat_number: sheet.seat_number.clone(),
+            application_id: seat.application_id,
+            application_number: seat.application_number.clone(),
+            applicant_name: seat.applicant_name.clone(),
+            correct_count,
+            multiple_mark_count: sheet
+                .marks
+                .iter()
+                .filter(|mark| **mark == SheetMark::Multiple)
+                .count(),
+            previous_score: seat.score,
+            new_score,
+            change: classify_change(seat.score, new_score),
+        });
+        evaluation.scored_marks.push(sheet.marks.clone());
+    }
+
+    evaluation.missing = seats
+        .iter()
+        .filter(|seat| !matched.contains(&seat.application_id) && !is_inactive_status(&seat.status))
+        .map(|seat| OmrMissingSheet {
+            seat_number: seat.exam_id.clone(),
+            application_id: seat.application_id,
+            application_number: seat.application_number.clone(),
+            applicant_name: seat.applicant_name.clone(),
+            existing_score: seat.score,
+        })
+        .collect();
+    evaluation.flagged.sort_by_key(|flag| flag.line);
+    evaluation
+}
+
+fn round_statistic(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+fn item_flags(difficulty: Option<f64>, discrimination: Option<f64>) -> Vec<String> {
+    let mut flags = Vec::new();
+    match difficulty {
+        Some(p) if p > TOO_EASY_DIFFICULTY => flags.push("too_easy".to_string()),
+        Some(p) if p < TOO_HARD_DIFFICULTY => flags.push("too_hard".to_string()),
+        _ => {}
+    }
+    match discrimination {
+        Some(d) if d < 0.0 => flags.push("negative_discrimination".to_string()),
+        Some(d) if d < LOW_DISCRIMINATION => flags.push("low_discrimination".to_string()),
+        _ => {}
+    }
+    flags
+}
+
+/// Difficulty (p) over all scored sheets and upper-minus-lower discrimination
+/// (D) over the top and bottom 27% by raw score.
+fn item_analysis(key: &[char], sheets: &[Vec<SheetMark>]) -> Vec<OmrItemStatistic> {
+    let sheet_count = sheets.len();
+    let totals: Vec<usize> = sheets
+        .iter()
+        .map(|marks| count_correct(key, marks))
+        .collect();
+    let mut ranked: Vec<usize> = (0..sheet_count).collect();
+    ranked.sort_by(|a, b| totals[*b].cmp(&totals[*a]));
+    let group_size = (sheet_count as f64 * DISCRIMINATION_GROUP_FRACTION).round() as usize;
+    let upper = &ranked[..group_size];
+    let lower = &ranked[sheet_count - group_size..];
+
+    key.iter()
+        .enumerate()
+        .map(|(index, &item)| {
+            let cancelled = item == CANCELLED_ITEM;
+            let mut choice_counts = BTreeMap::new();
+            let mut blank_count = 0;
+            let mut multiple_mark_count = 0;
+            for marks in sheets {
+                match marks[index] {
+                    SheetMark::Choice(choice) => {
+                        *choice_counts.entry(choice.to_string()).or_insert(0) += 1
+                    }
+                    SheetMark::Blank => blank_count += 1,
+                    SheetMark::Multiple => multiple_mark_count += 1,
+                }
+            }
+            let correct_in = |group: &[usize]| {
+                group
+                    .iter()
+                    .filter(|&&sheet| sheets[sheet][index] == SheetMark::Choice(item))
+                    .count()
+            };
+            let difficulty = (!cancelled && sheet_count > 0)
+                .then(|| round_statistic(correct_in(&ranked) as f64 / sheet_count as f64));
+            let discrimination = (!cancelled && group_size > 0).then(|| {
+                round_statistic(
+                    (correct_in(upper) as f64 - correct_in(lower) as f64) / group_size as f64,
+                )
+            });
+            OmrItemStatistic {
+                item: index + 1,
+                key: item.to_string(),
+                cancelled,
+                difficulty,
+                discrimination,
+                choice_counts,
+                blank_count,
+                multiple_mark_count,
+                flags: item_flags(difficulty, discrimination),
+            }
+        })
+        .collect()
+}
+
+// ==========================================
+// Answer keys
+// ==========================================
+
+const ANSWER_KEY_COLUMNS: &str =
+    "exam_subject_id, answer_key, char_length(answer_key) AS item_count, updated_by, updated_at";
+
+pub async fn get_answer_key(
+    pool: &PgPool,
+    subject_id: Uuid,
+) -> Result<Option<ExamAnswerKey>, AppError> {
+    sqlx::query_as::<_, ExamAnswerKey>(&format!(
+        "SELECT {} FROM admission_exam_answer_keys WHERE exam_subject_id = $1",
+        ANSWER_KEY_COLUMNS
+    ))
+    .bind(subject_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| read_error("Failed to fetch answer key", e))
+}
+
+pub async fn upsert_answer_key(
+    pool: &PgPool,
+    subject_id: Uuid,
+    user_id: Uuid,
+    raw_key: &str,
+) -> Result<ExamAnswerKey, AppError> {
+    let answer_key = normalize_answer_key(raw_key)?;
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM admission_exam_subjects WHERE id = $1)")
+            .bind(subject_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| read_error("Failed to check exam subject", e))?;
+    if !exists {
+        return Err(AppError::NotFound("ไม่พบวิชาสอบ".to_string()));
+    }
+
+    sqlx::query_as::<_, ExamAnswerKey>(&format!(
+        r#"INSERT INTO admission_exam_answer_keys (exam_subject_id, answer_key, updated_by, updated_at)
+           VALUES ($1, $2, $3, NOW())
+           ON CONFLICT (exam_subject_id)
+           DO UPDATE SET answer_key = EXCLUDED.answer_key,
+                         updated_by = EXCLUDED.updated_by,
+                         updated_at = NOW()
+           RETURNING {}"#,
+        ANSWER_KEY_COLUMNS
+    ))
+    .bind(subject_id)
+    .bind(&answer_key)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| write_error("Failed to save answer key", e))
+}
+
+// ==========================================
+// OMR import
+// ==========================================
+
+async fn load_round_seats(
+    pool: &PgPool,
+    round_id: Uuid,
+    subject_id: Uuid,
+) -> Result<Vec<SeatRecord>, AppError> {
+    sqlx::query_as::<_, SeatRecord>(
+        r#"SELECT esa.exam_id, aa.id AS application_id, aa.application_number,
+                  CONCAT(COALESCE(aa.title, ''), aa.first_name, ' ', aa.last_name) AS applicant_name,
+                  aa.status, esc.score
+           FROM admission_exam_seat_assignments esa
+           JOIN admission_applications aa ON aa.id = esa.application_id
+           LEFT JOIN admission_exam_scores esc
+                  ON esc.application_id = aa.id AND esc.exam_subject_id = $2
+           WHERE aa.admission_round_id = $1 AND esa.exam_id IS NOT NULL
+           ORDER BY esa.exam_id ASC"#,
+    )
+    .bind(round_id)
+    .bind(subject_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| read_error("Failed to fetch exam seats", e))
+}
+
+fn import_file_name(file_name: Option<String>) -> Option<String> {
+    file_name
+        .map(|name| {
+            name.trim()
+                .chars()
+                .take(MAX_FILE_NAME_CHARS)
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+}
+
+/// Marks an OMR export against the subject's answer key and diffs it with the
+/// saved scores. Unless `dry_run`, writes new and changed scores together with
+/// an import record holding the key and item analysis.
+pub async fn import_omr_scores(
+    pool: &PgPool,
+    round_id: Uuid,
+    user_id: Uuid,
+    request: OmrImportRequest,
+) -> Result<OmrImportResult, AppError> {
+    let subject_id = request.exam_subject_id;
+    let (subject_round_id, max_score): (Uuid, f64) = sqlx::query_as(
+        "SELECT admission_round_id, max_score::FLOAT8 FROM admission_exam_subjects WHERE id = $1",
+    )
+    .bind(subject_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| read_error("Failed to fetch exam subject", e))?
+    .ok_or_else(|| AppError::NotFound("ไม่พบวิชาสอบ".to_string()))?;
+    if subject_round_id != round_id {
+        return Err(AppError::BadRequest(
+            "วิชาสอบนี้ไม่ได้อยู่ในรอบรับสมัครนี้".to_string(),
+        ));
+    }
+    let answer_key = get_answer_key(pool, subject_id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("ยังไม่ได้กำหนดเฉลยของวิชานี้".to_string()))?
+        .answer_key;
+
+    let sheets = sheets_from_records(parse_csv(&request.csv), request.has_header.unwrap_or(true));
+    if sheets.is_empty() {
+        return Err(AppError::BadRequest("ไม่พบข้อมูลกระดาษคำตอบในไฟล์".to_string()));
+    }
+    if sheets.len() > MAX_OMR_SHEETS {
+        return Err(AppError::BadRequest(format!(
+            "นำเข้าได้ครั้งละไม่เกิน {} แผ่น",
+            MAX_OMR_SHEETS
+        )));
+    }
+
+    let seats = load_round_seats(pool, round_id, subject_id).await?;
+    let key: Vec<char> = answer_key.chars().collect();
+    let sheet_count = sheets.len();
+    let evaluation = evaluate_sheets(&key, max_score, sheets, &seats);
+    let count_changes = |kind: OmrScoreChangeKind| {
+        evaluation
+            .changes
+            .iter()
+            .filter(|change| change.change == kind)
+            .count()
+    };
+    let mut result = OmrImportResult {
+        dry_run: request.dry_run,
+        import_id: None,
+        exam_subject_id: subject_id,
+        item_count: key.len(),
+        max_score,
+        sheet_count,
+        scored_count: evaluation.changes.len(),
+        new_count: count_changes(OmrScoreChangeKind::New),
+        changed_count: count_changes(OmrScoreChangeKind::Changed),
+        unchanged_count: count_changes(OmrScoreChangeKind::Unchanged),
+        item_analysis: item_analysis(&key, &evaluation.scored_marks),
+        changes: evaluation.changes,
+        flagged: evaluation.flagged,
+        missing: evaluation.missing,
+    };
+    if request.dry_run {
+        return Ok(result);
+    }
+    if result.changes.is_empty() {
+        return Err(AppError::BadRequest("ไม่มีกระดาษคำตอบที่ให้คะแนนได้".to_string()));
+    }
+
+    let entries: Vec<BulkScoreEntry> = result
+        .changes
+        .iter()
+        .filter(|change| change.change != OmrScoreChangeKind::Unchanged)
+        .map(|change| BulkScoreEntry {
+            application_id: change.application_id,
+            scores: vec![UpdateScoreEntry {
+                exam_subject_id: subject_id,
+                score: Some(change.new_score),
+            }],
+        })
+        .collect();
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| write_error("Failed to begin OMR import", e))?;
+    let written =
+        score_service::bulk_update_scores_in_tx(&mut tx, round_id, user_id, &entries).await?;
+    let import_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO admission_omr_imports
+           (admission_round_id, exam_subject_id, file_name, answer_key, sheet_count,
+            scored_count, flagged_count, missing_count, written_count, item_analysis, imported_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+           RETURNING id"#,
+    )
+    .bind(round_id)
+    .bind(subject_id)
+    .bind(import_file_name(request.file_name))
+    .bind(&answer_key)
+    .bind(result.sheet_count as i32)
+    .bind(result.scored_count as i32)
+    .bind(result.flagged.len() as i32)
+    .bind(result.missing.len() as i32)
+    .bind(written as i32)
+    .bind(Json(&result.item_analysis))
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| write_error("Failed to record OMR import", e))?;
+    tx.commit()
+        .await
+        .map_err(|e| write_error("Failed to commit OMR import", e))?;
+
+    result.import_id = Some(import_id);
+    Ok(result)
+}
+
+const IMPORT_SUMMARY_SELECT: &str = r#"
+    SELECT i.id, i.exam_subject_id, s.name AS subject_name, i.file_name, i.answer_key,
+           i.sheet_count, i.scored_count, i.flagged_count, i.missing_count,
+           i.written_count, i.imported_by, i.imported_at
+    FROM admission_omr_imports i
+    JOIN admission_exam_subjects s ON s.id = i.exam_subject_id"#;
+
+pub async fn list_imports(
+    pool: &PgPool,
+    round_id: Uuid,
+) -> Result<Vec<OmrImportSummary>, AppError> {
+    sqlx::query_as::<_, OmrImportSummary>(&format!(
+        "{} WHERE i.admission_round_id = $1 ORDER BY i.imported_at DESC",
+        IMPORT_SUMMARY_SELECT
+    ))
+    .bind(round_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| read_error("Failed to list OMR imports", e))
+}
+
+pub async fn get_import(pool: &PgPool, import_id: Uuid) -> Result<OmrImportDetail, AppError> {
+    let summary = sqlx::query_as::<_, OmrImportSummary>(&format!(
+        "{} WHERE i.id = $1",
+        IMPORT_SUMMARY_SELECT
+    ))
+    .bind(import_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| read_error("Failed to fetch OMR import", e))?
+    .ok_or_else(|| AppError::NotFound("ไม่พบประวัติการนำเข้า".to_string()))?;
+    let Json(item_analysis) = sqlx::query_scalar::<_, Json<Vec<OmrItemStatistic>>>(
+        "SELECT item_analysis FROM admission_omr_imports WHERE id = $1",
+    )
+    .bind(import_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| read_error("Failed to fetch OMR item analysis", e))?;
+    Ok(OmrImportDetail {
+        summary,
+        item_analysis,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seat(exam_id: &str, status: &str, score: Option<f64>) -> SeatRecord {
+        SeatRecord {
+            exam_id: exam_id.to_string(),
+            application_id: Uuid::new_v4(),
+            application_number: Some(format!("A{}", exam_id)),
+            applicant_name: format!("ผู้สมัคร {}", exam_id),
+            status: status.to_string(),
+            score,
+        }
+    }
+
+    fn sheets(csv: &str) -> Vec<RawSheet> {
+        sheets_from_records(parse_csv(csv), true)
+    }
+
+    #[test]
+    fn parse_csv_handles_bom_quotes_crlf_and_blank_lines() {
+        let records =
+            parse_csv("\u{feff}seat,answers\r\n\"001\",\"A \"\"B\"\r\n\r\n002,\"AB\nC\"\n003,ABC");
+
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["seat".to_string(), "answers".to_string()]),
+                (2, vec!["001".to_string(), "A \"B".to_string()]),
+                (4, vec!["002".to_string(), "AB\nC".to_string()]),
+                (6, vec!["003".to_string(), "ABC".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_answer_key_strips_grouping_and_rejects_bad_symbols() {
+        assert_eq!(normalize_answer_key("abcda, bcd*a").unwrap(), "ABCDABCD*A");
+        assert!(normalize_answer_key("  ").is_err());
+        assert!(normalize_answer_key("AB-C").is_err());
+        assert!(normalize_answer_key("**").is_err());
+        assert!(normalize_answer_key(&"A".repeat(MAX_ANSWER_KEY_ITEMS + 1)).is_err());
+    }
+
+    #[test]
+    fn sheets_accept_answer_string_and_item_column_layouts() {
+        let string_layout = sheets("seat,answers\n1,A C*\n");
+        let column_layout = sheets("seat,q1,q2,q3,q4\n1,a,,c,AB\n");
+
+        let expected = vec![
+            Some(SheetMark::Choice('A')),
+            Some(SheetMark::Blank),
+            Some(SheetMark::Choice('C')),
+            Some(SheetMark::Multiple),
+        ];
+        assert_eq!(string_layout[0].marks, expected);
+        assert_eq!(column_layout[0].marks, expected);
+        assert_eq!(parse_item_cell("A/"), None);
+    }
+
+    #[test]
+    fn evaluate_sheets_scores_matches_and_flags_problem_sheets() {
+        let key: Vec<char> = "ABCD".chars().collect();
+        let seats = vec![
+            seat("10001", "verified", None),
+            seat("10002", "scored", Some(25.0)),
+            seat("10003", "verified", Some(50.0)),
+            seat("10004", "withdrawn", None),
+            seat("10005", "verified", None),
+            seat("10006", "verified", Some(10.0)),
+        ];
+        let csv = "seat,answers\n\
+                   010001,ABCD\n\
+                   10002,AB**\n\
+                   10003,AB--\n\
+                   10003,AB--\n\
+                   10004,ABCD\n\
+                   10005,ABCD\n\
+                   10005,DCBA\n\
+                   99999,ABCD\n\
+                   ,ABCD\n\
+                   10006,ABC\n\
+                   10006,----\n";
+
+        let evaluation = evaluate_sheets(&key, 100.0, sheets(csv), &seats);
+
+        let scored: Vec<(&str, f64, OmrScoreChangeKind)> = evaluation
+            .changes
+            .iter()
+            .map(|change| (change.seat_number.as_str(), change.new_score, change.change))
+            .collect();
+        assert_eq!(
+            scored,
+            vec![
+                ("010001", 100.0, OmrScoreChangeKind::New),
+                ("10002", 50.0, OmrScoreChangeKind::Changed),
+                ("10003", 50.0, OmrScoreChangeKind::Unchanged),
+            ]
+        );
+        assert_eq!(evaluation.changes[1].multiple_mark_count, 2);
+
+        let issues: Vec<(usize, OmrSheetIssue)> = evaluation
+            .flagged
+            .iter()
+            .map(|flag| (flag.line, flag.issue))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (5, OmrSheetIssue::Duplicate),
+                (6, OmrSheetIssue::InactiveApplication),
+                (7, OmrSheetIssue::DuplicateConflict),
+                (8, OmrSheetIssue::DuplicateConflict),
+                (9, OmrSheetIssue::UnknownSeat),
+                (10, OmrSheetIssue::MissingSeat),
+                (11, OmrSheetIssue::Unreadable),
+                (12, OmrSheetIssue::BlankSheet),
+            ]
+        );
+
+        let missing: Vec<&str> = evaluation
+            .missing
+            .iter()
+            .map(|sheet| sheet.seat_number.as_str())
+            .collect();
+        assert_eq!(missing, vec!["10005", "10006"]);
+        assert_eq!(evaluation.scored_marks.len(), 3);
+    }
+
+    #[test]
+    fn cancelled_items_credit_everyone_and_scores_round_to_two_places() {
+        let key: Vec<char> = "A*C".chars().collect();
+        let marks = vec![SheetMark::Blank, SheetMark::Blank, SheetMark::Choice('C')];
+
+        assert_eq!(count_correct(&key, &marks), 2);
+        assert_eq!(marked_score(2, 3, 10.0), 6.67);
+        assert_eq!(marked_score(0, 0, 10.0), 0.0);
+    }
+
+    #[test]
+    fn item_analysis_reports_difficulty_discrimination_and_distractors() {
+        let key: Vec<char> = "AB*".chars().collect();
+        let mark = |symbols: &str| -> Vec<SheetMark> {
+            symbols.chars().map(|c| parse_mark(c).unwrap()).collect()
+        };
+        // Four sheets: group size round(4 * 0.27) = 1 in each tail.
+        let sheets = vec![mark("ABA"), mark("ACA"), mark("BB-"), mark("C*-")];
+
+        let stats = item_analysis(&key, &sheets);
+
+        assert_eq!(stats[0].difficulty, Some(0.5));
+        assert_eq!(stats[0].discrimination, Some(1.0));
+        assert_eq!(
+            stats[0].choice_counts,
+            BTreeMap::from([
+                ("A".to_string(), 2),
+                ("B".to_string(), 1),
+                ("C".to_string(), 1)
+            ])
+        );
+        assert_eq!(stats[1].difficulty, Some(0.5));
+        assert_eq!(stats[1].multiple_mark_count, 1);
+        assert!(stats[2].cancelled);
+        assert_eq!(stats[2].difficulty, None);
+        assert_eq!(stats[2].blank_count, 2);
+        assert!(stats[2].flags.is_empty());
+        assert_eq!(
+            item_flags(Some(0.95), Some(-0.1)),
+            vec!["too_easy", "negative_discrimination"]
+        );
+        assert_eq!(
+            item_flags(Some(0.1), Some(0.1)),
+            vec!["too_hard", "low_discrimination"]
+        );
+    }
+}
@@ -26,6 +26,17 @@ pub fn hash_required(value: &str) -> Result<String, String> {
     field_encryption::hash_for_search(&normalized)
 }
 
+/// Every digest a stored blind index may hold for this national ID while keys are
+/// rotated. Lookups match any of them.
+pub fn hash_candidates(value: &str) -> Result<Vec<String>, String> {
+    let normalized = normalize_national_id(value);
+    if normalized.is_empty() {
+        return Err("national_id is required".to_string());
+    }
+
+    field_encryption::hash_for_search_candidates(&normalized)
+}
+
 pub fn hash_optional(value: Option<&str>) -> Result<Option<String>, String> {
     match value
         .map(normalize_national_id)
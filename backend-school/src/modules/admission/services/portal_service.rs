@@ -1,9 +1,10 @@
 use crate::error::AppError;
 use crate::modules::admission::models::applications::*;
 use crate::modules::admission::models::rounds::SelectionSettings;
+use crate::modules::admission::services::offer_service::{self, SeatRelease};
 use crate::modules::admission::services::pii;
 use serde_json::json;
-use sqlx::{types::Json, PgPool};
+use sqlx::{types::Json, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 fn pii_error(context: &str, error: String) -> AppError {
@@ -41,12 +42,12 @@ pub async fn verify_credentials(
     date_of_birth: &str,
 ) -> Result<Uuid, AppError> {
     let dob = parse_portal_birth_date(date_of_birth)?;
-    let national_id_hash =
-        pii::hash_required(national_id).map_err(|error| pii_error("hash credential", error))?;
+    let national_id_hashes =
+        pii::hash_candidates(national_id).map_err(|error| pii_error("hash credential", error))?;
     sqlx::query_scalar(
-        "SELECT id FROM admission_applications WHERE national_id_hash = $1 AND date_of_birth = $2 ORDER BY created_at DESC LIMIT 1"
+        "SELECT id FROM admission_applications WHERE national_id_hash = ANY($1) AND date_of_birth = $2 ORDER BY created_at DESC LIMIT 1"
     )
-    .bind(national_id_hash).bind(dob)
+    .bind(national_id_hashes).bind(dob)
     .fetch_optional(pool).await
     .map_err(|_| AppError::InternalServerError("Database error".to_string()))?
     .ok_or_else(|| AppError::AuthError("ไม่พบข้อมูลผู้สมัคร กรุณาตรวจสอบเลขบัตรประชาชนและวันเกิด".to_string()))
@@ -87,6 +88,18 @@ pub struct PortalAssignment {
     pub student_confirmed: bool,
 }
 
+/// The applicant's latest seat offer; the deadline is what the portal shows
+/// in place of a notification.
+#[derive(sqlx::FromRow, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortalSeatOffer {
+    pub status: String,
+    pub source: String,
+    pub offered_at: chrono::DateTime<chrono::Utc>,
+    pub deadline_at: chrono::DateTime<chrono::Utc>,
+    pub responded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PortalStatusResult {
@@ -94,6 +107,7 @@ pub struct PortalStatusResult {
     pub round_status: String,
     pub assignment_mode: String,
     pub assignment: Option<PortalAssignment>,
+    pub offer: Option<PortalSeatOffer>,
     pub scores: Option<Vec<ExamScore>>,
     pub enrollment_form: Option<EnrollmentForm>,
     pub documents: Vec<ApplicationDocument>,
@@ -173,6 +187,18 @@ pub async fn get_status(
     .await
     .unwrap_or(None);
 
+    let offer = sqlx::query_as::<_, PortalSeatOffer>(
+        r#"SELECT status, source, offered_at, deadline_at, responded_at
+           FROM admission_seat_offers
+           WHERE application_id = $1
+           ORDER BY offered_at DESC
+           LIMIT 1"#,
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await
+    .unwrap_or(None);
+
     let scores: Vec<ExamScore> = if show_scores {
         sqlx::query_as::<_, ExamScore>(
             r#"SELECT esc.id, esc.application_id, esc.exam_subject_id, esc.score,
@@ -220,6 +246,7 @@ pub async fn get_status(
         round_status,
         assignment_mode,
         assignment: if show_assignment { assignment } else { None },
+        offer: if show_assignment { offer } else { None },
         scores: if show_scores { Some(scores) } else { None },
         enrollment_form: if show_form { form } else { None },
         documents,
@@ -236,10 +263,29 @@ pub async fn confirm_enrollment(
     if round_status != "enrolling" {
         return Err(AppError::BadRequest("ยังไม่ถึงช่วงเวลารายงานตัว".to_string()));
     }
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| AppError::InternalServerError("Failed to confirm".to_string()))?;
+    confirm_seat(&mut tx, application_id).await?;
+    tx.commit()
+        .await
+        .map_err(|_| AppError::InternalServerError("Failed to confirm".to_string()))?;
+    Ok(())
+}
+
+/// Confirms the applicant's seat inside one transaction: the latest offer is
+/// locked and accepted before the application status is re-read, so a seat
+/// released concurrently by expiry or decline cannot be confirmed.
+async fn confirm_seat(
+    tx: &mut Transaction<'_, Postgres>,
+    application_id: Uuid,
+) -> Result<(), AppError> {
+    offer_service::accept_pending_offer(tx, application_id).await?;
     let status: String =
-        sqlx::query_scalar("SELECT status FROM admission_applications WHERE id = $1")
+        sqlx::query_scalar("SELECT status FROM admission_applications WHERE id = $1 FOR UPDATE")
             .bind(application_id)
-            .fetch_one(pool)
+            .fetch_one(&mut **tx)
             .await
             .map_err(|_| AppError::InternalServerError("Database error".to_string()))?;
     if status != "accepted" {
@@ -249,11 +295,28 @@ pub async fn confirm_enrollment(
         )));
     }
     sqlx::query("UPDATE admission_room_assignments SET student_confirmed = true, student_confirmed_at = NOW() WHERE application_id = $1")
-        .bind(application_id).execute(pool).await
+        .bind(application_id).execute(&mut **tx).await
         .map_err(|_| AppError::InternalServerError("Failed to confirm".to_string()))?;
     Ok(())
 }
 
+/// Gives up the applicant's pending seat offer; the seat moves to the next
+/// applicant on the track's waitlist.
+pub async fn decline_offer(
+    pool: &PgPool,
+    payload: PortalCredentials,
+) -> Result<SeatRelease, AppError> {
+    let application_id =
+        verify_credentials(pool, &payload.national_id, &payload.date_of_birth).await?;
+    let round_status = get_round_status(pool, application_id).await?;
+    if round_status != "enrolling" {
+        return Err(AppError::BadRequest(
+            "ไม่สามารถสละสิทธิ์ได้ในช่วงเวลานี้".to_string(),
+        ));
+    }
+    offer_service::decline_pending_offer(pool, application_id).await
+}
+
 pub async fn get_enrollment_form(
     pool: &PgPool,
     payload: PortalCredentials,
@@ -285,21 +348,11 @@ pub async fn submit_enrollment_form(
             "ยังไม่ถึงช่วงเวลารายงานตัว หรือหมดเขตแล้ว".to_string(),
         ));
     }
-    let status: String =
-        sqlx::query_scalar("SELECT status FROM admission_applications WHERE id = $1")
-            .bind(application_id)
-            .fetch_one(pool)
-            .await
-            .map_err(|_| AppError::InternalServerError("Database error".to_string()))?;
-    if status != "accepted" {
-        return Err(AppError::BadRequest(format!(
-            "ไม่สามารถยืนยันได้ (สถานะปัจจุบัน: {})",
-            status
-        )));
-    }
-    sqlx::query("UPDATE admission_room_assignments SET student_confirmed = true, student_confirmed_at = NOW() WHERE application_id = $1")
-        .bind(application_id).execute(pool).await
+    let mut tx = pool
+        .begin()
+        .await
         .map_err(|_| AppError::InternalServerError("Failed to confirm".to_string()))?;
+    confirm_seat(&mut tx, application_id).await?;
 
     let form_data = payload.form_data.unwrap_or(json!({}));
     sqlx::query(
@@ -309,12 +362,15 @@ pub async fn submit_enrollment_form(
     )
     .bind(application_id)
     .bind(form_data)
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .map_err(|e| {
         tracing::error!("Failed to submit enrollment form: {}", e);
         AppError::InternalServerError("ไม่สามารถบันทึกแบบฟอร์มได้".to_string())
     })?;
+    tx.commit()
+        .await
+        .map_err(|_| AppError::InternalServerError("ไม่สามารถบันทึกแบบฟอร์มได้".to_string()))?;
     Ok(())
 }
 
@@ -359,9 +415,11 @@ pub async fn update_application(
         .fetch_one(pool)
         .await
         .unwrap_or_default();
+        let national_id_hashes = pii::hash_candidates(&payload.data.national_id)
+            .map_err(|error| pii_error("hash update application", error))?;
         let already: bool = sqlx::query_scalar(
-            "SELECT EXISTS(SELECT 1 FROM admission_applications WHERE national_id_hash = $1 AND admission_round_id = $2 AND id != $3)"
-        ).bind(&encrypted_pii.national_id_hash).bind(round_id).bind(application_id)
+            "SELECT EXISTS(SELECT 1 FROM admission_applications WHERE national_id_hash = ANY($1) AND admission_round_id = $2 AND id != $3)"
+        ).bind(&national_id_hashes).bind(round_id).bind(application_id)
         .fetch_one(pool).await.unwrap_or(false);
         if already {
             return Err(AppError::BadRequest(
@@ -541,6 +599,114 @@ fn parse_portal_birth_date(date_of_birth: &str) -> Result<chrono::NaiveDate, App
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modules::admission::services::application_service_tests::{
+        insert_accepted_application, insert_fixture, EnrollmentFixture,
+    };
+    use crate::test_helpers::{create_named_test_pool, run_test_migrations};
+
+    async fn insert_offer(
+        pool: &PgPool,
+        fixture: &EnrollmentFixture,
+        application_id: Uuid,
+        status: &str,
+    ) -> Uuid {
+        sqlx::query_scalar(
+            r#"INSERT INTO admission_seat_offers (
+                   application_id, admission_track_id, class_room_id, source,
+                   status, offered_at, deadline_at, responded_at
+               )
+               VALUES ($1, $2, $3, 'initial', $4, now() - interval '2 days',
+                       CASE WHEN $4 = 'pending' THEN now() + interval '1 day'
+                            ELSE now() - interval '1 day' END,
+                       CASE WHEN $4 = 'pending' THEN NULL ELSE now() END)
+               RETURNING id"#,
+        )
+        .bind(application_id)
+        .bind(fixture.track_id)
+        .bind(fixture.class_room_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await
+        .expect("seat offer should insert")
+    }
+
+    async fn confirm(pool: &PgPool, application_id: Uuid) -> Result<(), AppError> {
+        let mut tx = pool.begin().await.unwrap();
+        confirm_seat(&mut tx, application_id).await?;
+        tx.commit().await.unwrap();
+        Ok(())
+    }
+
+    async fn seat_confirmed(pool: &PgPool, application_id: Uuid) -> bool {
+        sqlx::query_scalar(
+            "SELECT student_confirmed FROM admission_room_assignments WHERE application_id = $1",
+        )
+        .bind(application_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn confirm_seat_accepts_an_open_offer_and_rejects_released_ones() {
+        let pool = create_named_test_pool("admission_portal_confirm_seat").await;
+        run_test_migrations(&pool).await;
+        let fixture = insert_fixture(&pool).await;
+        let open = insert_accepted_application(&pool, &fixture, 'a').await;
+        let expired = insert_accepted_application(&pool, &fixture, 'b').await;
+        let declined = insert_accepted_application(&pool, &fixture, 'c').await;
+        let open_offer = insert_offer(&pool, &fixture, open, "pending").await;
+        insert_offer(&pool, &fixture, expired, "expired").await;
+        insert_offer(&pool, &fixture, declined, "declined").await;
+
+        confirm(&pool, open).await.unwrap();
+        let offer_status: String =
+            sqlx::query_scalar("SELECT status FROM admission_seat_offers WHERE id = $1")
+                .bind(open_offer)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(offer_status, "accepted");
+        assert!(seat_confirmed(&pool, open).await);
+        confirm(&pool, open)
+            .await
+            .expect("confirming an accepted offer again is a no-op");
+
+        for released in [expired, declined] {
+            assert!(matches!(
+                confirm(&pool, released).await,
+                Err(AppError::BadRequest(_))
+            ));
+            assert!(!seat_confirmed(&pool, released).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn confirm_seat_rechecks_the_application_status() {
+        let pool = create_named_test_pool("admission_portal_confirm_status").await;
+        run_test_migrations(&pool).await;
+        let fixture = insert_fixture(&pool).await;
+        let application = insert_accepted_application(&pool, &fixture, 'd').await;
+        let offer = insert_offer(&pool, &fixture, application, "pending").await;
+        sqlx::query("UPDATE admission_applications SET status = 'withdrawn' WHERE id = $1")
+            .bind(application)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            confirm(&pool, application).await,
+            Err(AppError::BadRequest(message)) if message.contains("withdrawn")
+        ));
+        let offer_status: String =
+            sqlx::query_scalar("SELECT status FROM admission_seat_offers WHERE id = $1")
+                .bind(offer)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(offer_status, "pending");
+        assert!(!seat_confirmed(&pool, application).await);
+    }
 
     #[test]
     fn parse_portal_birth_date_accepts_ddmmyyyy_buddhist_year() {
@@ -4,13 +4,16 @@ use chrono::{DateTime, Utc};
 use sqlx::{types::Json, FromRow, PgPool};
 use uuid::Uuid;
 
-const ROUND_GRADE_LEVEL_CASE: &str = r#"CASE gl.level_type
+pub const ROUND_GRADE_LEVEL_CASE: &str = r#"CASE gl.level_type
     WHEN 'kindergarten' THEN CONCAT('อ.', gl.year)
     WHEN 'primary'      THEN CONCAT('ป.', gl.year)
     WHEN 'secondary'    THEN CONCAT('ม.', gl.year)
     ELSE CONCAT('?.', gl.year)
 END"#;
 
+const DEFAULT_CONFIRMATION_WINDOW_HOURS: i32 = 72;
+const MAX_CONFIRMATION_WINDOW_HOURS: i32 = 720;
+
 #[derive(Debug, FromRow)]
 struct AdmissionTrackRow {
     id: Uuid,
@@ -21,6 +24,7 @@ struct AdmissionTrackRow {
     scoring_subject_ids: Json<Vec<Uuid>>,
     tiebreak_method: String,
     display_order: i32,
+    confirmation_window_hours: i32,
     created_at: DateTime<Utc>,
     study_plan_name: Option<String>,
     computed_capacity: Option<i64>,
@@ -39,6 +43,7 @@ impl From<AdmissionTrackRow> for AdmissionTrack {
             scoring_subject_ids: row.scoring_subject_ids.0,
             tiebreak_method: row.tiebreak_method,
             display_order: row.display_order,
+            confirmation_window_hours: row.confirmation_window_hours,
             created_at: row.created_at,
             study_plan_name: row.study_plan_name,
             computed_capacity: row.computed_capacity,
@@ -421,14 +426,15 @@ pub async fn create_track(
     round_id: Uuid,
     payload: CreateAdmissionTrackRequest,
 ) -> Result<AdmissionTrack, AppError> {
+    validate_confirmation_window_hours(payload.confirmation_window_hours)?;
     let scoring_ids = scoring_subject_ids_json(payload.scoring_subject_ids);
 
     sqlx::query_as::<_, AdmissionTrackRow>(
         r#"INSERT INTO admission_tracks (
                admission_round_id, study_plan_id, name, capacity_override,
-               scoring_subject_ids, tiebreak_method, display_order
+               scoring_subject_ids, tiebreak_method, display_order, confirmation_window_hours
            )
-           VALUES ($1, $2, $3, $4, $5, $6, $7)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
            RETURNING *,
                (SELECT name_th FROM study_plans WHERE id = $2) AS study_plan_name,
                0::bigint AS room_count,
@@ -442,6 +448,9 @@ pub async fn create_track(
     .bind(scoring_ids)
     .bind(track_tiebreak_method_or_default(payload.tiebreak_method))
     .bind(display_order_or_default(payload.display_order))
+    .bind(confirmation_window_hours_or_default(
+        payload.confirmation_window_hours,
+    ))
     .fetch_one(pool)
     .await
     .map_err(|e| {
@@ -456,6 +465,7 @@ pub async fn update_track(
     id: Uuid,
     payload: UpdateAdmissionTrackRequest,
 ) -> Result<AdmissionTrack, AppError> {
+    validate_confirmation_window_hours(payload.confirmation_window_hours)?;
     let scoring_ids = payload
         .scoring_subject_ids
         .map(|v| scoring_subject_ids_json(Some(v)));
@@ -466,7 +476,8 @@ pub async fn update_track(
                capacity_override = COALESCE($2, capacity_override),
                scoring_subject_ids = COALESCE($3, scoring_subject_ids),
                tiebreak_method = COALESCE($4, tiebreak_method),
-               display_order = COALESCE($5, display_order)
+               display_order = COALESCE($5, display_order),
+               confirmation_window_hours = COALESCE($7, confirmation_window_hours)
            WHERE id = $6
            RETURNING *,
                (SELECT name_th FROM study_plans WHERE id = study_plan_id) AS study_plan_name,
@@ -476,7 +487,7 @@ pub async fn update_track(
     )
     .bind(&payload.name).bind(payload.capacity_override).bind(scoring_ids)
     .bind(&payload.tiebreak_method).bind(payload.display_order)
-    .bind(id).fetch_one(pool).await
+    .bind(id).bind(payload.confirmation_window_hours).fetch_one(pool).await
     .map_err(|e| {
         tracing::error!("Failed to update track: {}", e);
         AppError::InternalServerError("Failed to update track".to_string())
@@ -569,6 +580,22 @@ fn track_tiebreak_method_or_default(tiebreak_method: Option<String>) -> String {
     tiebreak_method.unwrap_or_else(|| "applied_at".to_string())
 }
 
+fn confirmation_window_hours_or_default(hours: Option<i32>) -> i32 {
+    hours.unwrap_or(DEFAULT_CONFIRMATION_WINDOW_HOURS)
+}
+
+fn validate_confirmation_window_hours(hours: Option<i32>) -> Result<(), AppError> {
+    match hours {
+        Some(hours) if !(1..=MAX_CONFIRMATION_WINDOW_HOURS).contains(&hours) => {
+            Err(AppError::BadRequest(format!(
+                "ระยะเวลายืนยันสิทธิ์ต้องอยู่ระหว่าง 1 ถึง {} ชั่วโมง",
+                MAX_CONFIRMATION_WINDOW_HOURS
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 fn scoring_subject_ids_json(scoring_subject_ids: Option<Vec<Uuid>>) -> Json<Vec<Uuid>> {
     Json(scoring_subject_ids.unwrap_or_default())
 }
@@ -606,6 +633,7 @@ mod tests {
             "score"
         );
 
+        assert_eq!(confirmation_window_hours_or_default(None), 72);
         assert_eq!(scoring_subject_ids_json(None).0, Vec::<Uuid>::new());
         let subject_id = Uuid::new_v4();
         assert_eq!(
@@ -613,4 +641,16 @@ mod tests {
             vec![subject_id]
         );
     }
+
+    #[test]
+    fn confirmation_window_must_be_between_one_hour_and_thirty_days() {
+        assert!(validate_confirmation_window_hours(None).is_ok());
+        assert!(validate_confirmation_window_hours(Some(1)).is_ok());
+        assert!(validate_confirmation_window_hours(Some(720)).is_ok());
+        assert!(matches!(
+            validate_confirmation_window_hours(Some(0)),
+            Err(AppError::BadRequest(message)) if message.contains("720")
+        ));
+        assert!(validate_confirmation_window_hours(Some(721)).is_err());
+    }
 }
@@ -206,29 +206,29 @@ pub async fn bulk_update_scores(
     round_id: Uuid,
     user_id: Uuid,
     entries: &[BulkScoreEntry],
+) -> Result<usize, AppError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| AppError::InternalServerError("Transaction failed".to_string()))?;
+    let updated = bulk_update_scores_in_tx(&mut tx, round_id, user_id, entries).await?;
+    tx.commit()
+        .await
+        .map_err(|_| AppError::InternalServerError("Commit failed".to_string()))?;
+    Ok(updated)
+}
+
+/// Same as [`bulk_update_scores`] inside a caller's transaction, so imports can
+/// record their own audit row atomically with the scores.
+pub async fn bulk_update_scores_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    round_id: Uuid,
+    user_id: Uuid,
+    entries: &[BulkScoreEntry],
 ) -> Result<usize, AppError> {
     let updated = bulk_score_entry_count(entries);
     let rows = bulk_score_entries_to_rows(entries);
-
-    if !rows.is_empty() {
-        let app_ids: Vec<Uuid> = rows.iter().map(|row| row.application_id).collect();
-        let sub_ids: Vec<Uuid> = rows.iter().map(|row| row.exam_subject_id).collect();
-        let score_vals: Vec<Option<f64>> = rows.iter().map(|row| row.score).collect();
-
-        sqlx::query(
-            r#"INSERT INTO admission_exam_scores (application_id, exam_subject_id, score, entered_by, entered_at, updated_at)
-               SELECT a, s, sc, $4, NOW(), NOW()
-               FROM UNNEST($1::uuid[], $2::uuid[], $3::float8[]) AS t(a, s, sc)
-               ON CONFLICT (application_id, exam_subject_id)
-               DO UPDATE SET score = EXCLUDED.score, entered_by = EXCLUDED.entered_by, updated_at = NOW()"#
-        )
-        .bind(&app_ids).bind(&sub_ids).bind(&score_vals).bind(user_id)
-        .execute(pool).await
-        .map_err(|e| {
-            tracing::error!("Bulk score error: {}", e);
-            AppError::InternalServerError("Failed to bulk update scores".to_string())
-        })?;
-    }
+    upsert_application_scores(tx, user_id, &rows).await?;
 
     let app_id_set: Vec<Uuid> = entries.iter().map(|e| e.application_id).collect();
     sqlx::query(
@@ -244,9 +244,12 @@ pub async fn bulk_update_scores(
     )
     .bind(&app_id_set)
     .bind(round_id)
-    .execute(pool)
+    .execute(&mut **tx)
     .await
-    .ok();
+    .map_err(|e| {
+        tracing::error!("Failed to mark scored applications: {}", e);
+        AppError::InternalServerError("Failed to bulk update scores".to_string())
+    })?;
 
     Ok(updated)
 }
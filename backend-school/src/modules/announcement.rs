@@ -0,0 +1,14 @@
+pub mod handlers;
+pub mod models;
+pub mod services;
+
+use crate::AppState;
+use axum::Router;
+
+pub fn announcement_routes() -> Router<AppState> {
+    handlers::routes()
+}
+
+pub fn announcement_public_routes() -> Router<AppState> {
+    handlers::public_routes()
+}
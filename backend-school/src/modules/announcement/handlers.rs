@@ -0,0 +1,283 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::announcement::models::{
+    AnnouncementFilter, AnnouncementReceiptFilter, MyAnnouncementFilter, PublicAnnouncementQuery,
+    PublishAnnouncementRequest, UpsertAnnouncementRequest,
+};
+use crate::modules::announcement::services;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::files::{
+    consumer_service::{map_platform_error, request_deletions},
+    models::FileDownloadGrantResponse,
+    repository::SqlFileRepository,
+};
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::utils::tenant::tenant_context;
+use crate::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+/// GET /api/announcements - ประกาศทั้งหมดของโรงเรียน
+async fn list_announcements(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<AnnouncementFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_announcements(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// POST /api/announcements - สร้างประกาศฉบับร่าง
+async fn create_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<UpsertAnnouncementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::create_announcement(&context.tenant.pool, &context.actor, payload).await?;
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        outcome.detached_file_ids,
+    )
+    .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::ok(outcome.announcement)),
+    ))
+}
+
+async fn get_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let announcement = services::get_announcement(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(announcement)))
+}
+
+async fn update_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpsertAnnouncementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::update_announcement(&context.tenant.pool, &context.actor, id, payload).await?;
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        outcome.detached_file_ids,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(outcome.announcement)))
+}
+
+/// DELETE /api/announcements/:id - ลบประกาศฉบับร่าง
+async fn delete_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let detached_file_ids =
+        services::delete_announcement(&context.tenant.pool, &context.actor, id).await?;
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        detached_file_ids,
+    )
+    .await?;
+    Ok(Json(ApiResponse::empty()))
+}
+
+/// POST /api/announcements/:id/publish - เผยแพร่ทันทีหรือตั้งเวลาเผยแพร่
+async fn publish_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PublishAnnouncementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let announcement =
+        services::publish_announcement(&context.tenant.pool, &context.actor, id, payload).await?;
+
+    // Immediate publishing notifies the audience now instead of waiting for the
+    // next dispatch run; scheduled ones are left to the job.
+    if let Err(error) = services::dispatch_due_publications(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+    )
+    .await
+    {
+        tracing::error!(
+            announcement_id = %announcement.id,
+            error = %error,
+            "Failed to notify audience about published announcement"
+        );
+    }
+
+    Ok(Json(ApiResponse::ok(announcement)))
+}
+
+async fn archive_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let announcement =
+        services::archive_announcement(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(announcement)))
+}
+
+/// GET /api/announcements/:id/receipts - รายชื่อผู้อ่านและผู้กดรับทราบ
+async fn list_receipts(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Query(filter): Query<AnnouncementReceiptFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_receipts(&context.tenant.pool, &context.actor, id, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// POST /api/announcements/:id/reminders - เตือนผู้ที่ยังไม่กดรับทราบ
+async fn send_reminders(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let result = services::send_reminders_now(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+        &context.actor,
+        id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(result)))
+}
+
+/// GET /api/announcements/me - ประกาศที่ส่งถึงผู้ใช้
+async fn list_my_announcements(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<MyAnnouncementFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items =
+        services::list_my_announcements(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/announcements/me/:id - เปิดอ่านประกาศและบันทึกการอ่าน
+async fn get_my_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let item = services::get_my_announcement(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(item)))
+}
+
+async fn acknowledge_announcement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let item = services::acknowledge_announcement(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(item)))
+}
+
+/// GET /api/public/announcements - ประกาศสำหรับเว็บไซต์โรงเรียน
+async fn list_public_announcements(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<PublicAnnouncementQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context(&state, &headers).await?;
+    let items = services::list_public_announcements(&tenant.pool, query).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn get_public_announcement(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context(&state, &headers).await?;
+    let announcement = services::get_public_announcement(&tenant.pool, id).await?;
+    Ok(Json(ApiResponse::ok(announcement)))
+}
+
+/// POST /api/public/announcements/:id/files/:file_id/download - ลิงก์ดาวน์โหลดไฟล์แนบของประกาศสาธารณะ
+async fn download_public_announcement_file(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((id, file_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context(&state, &headers).await?;
+    services::ensure_public_announcement_file(&tenant.pool, id, file_id).await?;
+    let repository = SqlFileRepository::new(tenant.pool);
+    let grant = state
+        .file_platform
+        .private_download(&repository, file_id)
+        .await
+        .map_err(map_platform_error)?;
+    let response = FileDownloadGrantResponse::try_from(grant).map_err(|()| {
+        AppError::InternalServerError("file_stream_grant_not_supported".to_string())
+    })?;
+    Ok(Json(ApiResponse::ok(response)))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_announcements).post(create_announcement))
+        .route("/me", get(list_my_announcements))
+        .route("/me/{id}", get(get_my_announcement))
+        .route("/me/{id}/acknowledge", post(acknowledge_announcement))
+        .route(
+            "/{id}",
+            get(get_announcement)
+                .put(update_announcement)
+                .delete(delete_announcement),
+        )
+        .route("/{id}/publish", post(publish_announcement))
+        .route("/{id}/archive", post(archive_announcement))
+        .route("/{id}/receipts", get(list_receipts))
+        .route("/{id}/reminders", post(send_reminders))
+}
+
+pub fn public_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_public_announcements))
+        .route("/{id}", get(get_public_announcement))
+        .route(
+            "/{id}/files/{file_id}/download",
+            post(download_public_announcement_file),
+        )
+}
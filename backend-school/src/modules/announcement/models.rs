@@ -0,0 +1,249 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::modules::question_bank::models::RichContent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl AnnouncementStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Published => "published",
+            Self::Archived => "archived",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(Self::Draft),
+            "published" => Some(Self::Published),
+            "archived" => Some(Self::Archived),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementAudienceType {
+    AllStaff,
+    AllStudents,
+    AllGuardians,
+    Role,
+    GradeLevel,
+    Classroom,
+    OrganizationUnit,
+    ClassroomGuardians,
+}
+
+impl AnnouncementAudienceType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AllStaff => "all_staff",
+            Self::AllStudents => "all_students",
+            Self::AllGuardians => "all_guardians",
+            Self::Role => "role",
+            Self::GradeLevel => "grade_level",
+            Self::Classroom => "classroom",
+            Self::OrganizationUnit => "organization_unit",
+            Self::ClassroomGuardians => "classroom_guardians",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "all_staff" => Some(Self::AllStaff),
+            "all_students" => Some(Self::AllStudents),
+            "all_guardians" => Some(Self::AllGuardians),
+            "role" => Some(Self::Role),
+            "grade_level" => Some(Self::GradeLevel),
+            "classroom" => Some(Self::Classroom),
+            "organization_unit" => Some(Self::OrganizationUnit),
+            "classroom_guardians" => Some(Self::ClassroomGuardians),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementReceiptState {
+    Unread,
+    Read,
+    Acknowledged,
+    NotAcknowledged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementAudienceInput {
+    pub audience_type: AnnouncementAudienceType,
+    pub role_id: Option<Uuid>,
+    pub grade_level_id: Option<Uuid>,
+    pub class_room_id: Option<Uuid>,
+    pub organization_unit_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementAudience {
+    pub id: Uuid,
+    pub audience_type: AnnouncementAudienceType,
+    pub role_id: Option<Uuid>,
+    pub grade_level_id: Option<Uuid>,
+    pub class_room_id: Option<Uuid>,
+    pub organization_unit_id: Option<Uuid>,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementAttachment {
+    pub file_id: Uuid,
+    pub display_filename: String,
+    pub content_type: Option<String>,
+    pub byte_size: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementReceiptSummary {
+    pub audience_count: i64,
+    pub read_count: i64,
+    pub acknowledged_count: i64,
+}
+
+/// Management view with audiences and receipt totals.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Announcement {
+    pub id: Uuid,
+    pub title: String,
+    pub summary: Option<String>,
+    pub content: RichContent,
+    pub status: AnnouncementStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub is_pinned: bool,
+    pub is_public: bool,
+    pub requires_acknowledgement: bool,
+    pub acknowledge_by: Option<NaiveDate>,
+    pub audiences: Vec<AnnouncementAudience>,
+    pub attachments: Vec<AnnouncementAttachment>,
+    pub receipts: AnnouncementReceiptSummary,
+    pub published_notified_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_by_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What an audience member sees, with their own receipt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementFeedItem {
+    pub id: Uuid,
+    pub title: String,
+    pub summary: Option<String>,
+    pub content: RichContent,
+    pub publish_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub is_pinned: bool,
+    pub requires_acknowledgement: bool,
+    pub acknowledge_by: Option<NaiveDate>,
+    pub attachments: Vec<AnnouncementAttachment>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// School website view; audience, receipts and authorship stay internal.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicAnnouncement {
+    pub id: Uuid,
+    pub title: String,
+    pub summary: Option<String>,
+    pub content: RichContent,
+    pub publish_at: DateTime<Utc>,
+    pub is_pinned: bool,
+    pub attachments: Vec<AnnouncementAttachment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertAnnouncementRequest {
+    pub title: String,
+    pub summary: Option<String>,
+    pub content: RichContent,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub is_pinned: bool,
+    #[serde(default)]
+    pub is_public: bool,
+    #[serde(default)]
+    pub requires_acknowledgement: bool,
+    pub acknowledge_by: Option<NaiveDate>,
+    #[serde(default)]
+    pub audiences: Vec<AnnouncementAudienceInput>,
+    #[serde(default)]
+    pub attachment_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishAnnouncementRequest {
+    /// Leave empty to publish immediately.
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementFilter {
+    pub status: Option<AnnouncementStatus>,
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyAnnouncementFilter {
+    #[serde(default)]
+    pub unread_only: bool,
+    #[serde(default)]
+    pub pending_acknowledgement: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicAnnouncementQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementReceiptFilter {
+    pub state: Option<AnnouncementReceiptState>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementReceipt {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub user_type: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnouncementReminderResult {
+    pub recipient_count: usize,
+}
@@ -0,0 +1,44 @@
+mod audiences;
+mod feed;
+mod management;
+mod notifications;
+mod records;
+mod shared;
+
+#[cfg(test)]
+mod tests;
+
+pub use audiences::is_live_for_member;
+pub use feed::{
+    acknowledge_announcement, ensure_public_announcement_file, get_my_announcement,
+    get_public_announcement, list_my_announcements, list_public_announcements,
+};
+pub use management::{
+    archive_announcement, create_announcement, delete_announcement, get_announcement,
+    list_announcements, list_receipts, publish_announcement, update_announcement,
+};
+pub use notifications::{
+    dispatch_due_publications, process_due_announcements_for_all_tenants, send_reminders_now,
+};
+#[allow(unused_imports)]
+pub use shared::{
+    audience_label, can_edit, collect_file_ids, is_live, normalize_audiences,
+    notification_link_for_user_type, notification_text, public_limit, receipt_matches_state,
+    reminder_due, resolve_publish_at, validate_acknowledgement, validate_content, validate_expiry,
+    validate_publishable, AnnouncementNotificationKind,
+};
+
+#[cfg(test)]
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+#[cfg(test)]
+use uuid::Uuid;
+
+#[cfg(test)]
+use crate::error::AppError;
+#[cfg(test)]
+use crate::modules::announcement::models::{
+    AnnouncementAudienceInput, AnnouncementAudienceType, AnnouncementReceiptState,
+    AnnouncementStatus,
+};
+#[cfg(test)]
+use crate::modules::question_bank::models::RichContent;
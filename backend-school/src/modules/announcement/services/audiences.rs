@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::announcement::models::{
+    AnnouncementAudience, AnnouncementAudienceInput, AnnouncementAudienceType,
+    AnnouncementReceiptSummary,
+};
+
+use super::records::{read_error, write_error};
+use super::shared::{audience_label, parse_audience_type, LIVE_ANNOUNCEMENT_PREDICATE};
+
+/// Matches an `announcement_audiences audience` row against a `users member`
+/// row. Membership is resolved when the announcement is read, so students who
+/// change classroom see the announcements of their current classroom.
+pub(super) const AUDIENCE_MEMBER_PREDICATE: &str = r#"
+    (
+        (audience.audience_type = 'all_staff' AND member.user_type = 'staff')
+        OR (audience.audience_type = 'all_students' AND member.user_type = 'student')
+        OR (
+            audience.audience_type = 'all_guardians'
+            AND EXISTS (
+                SELECT 1 FROM student_parents link
+                WHERE link.parent_user_id = member.id
+            )
+        )
+        OR (
+            audience.audience_type = 'role'
+            AND EXISTS (
+                SELECT 1 FROM user_roles user_role
+                WHERE user_role.user_id = member.id
+                  AND user_role.role_id = audience.role_id
+                  AND user_role.ended_at IS NULL
+            )
+        )
+        OR (
+            audience.audience_type = 'grade_level'
+            AND EXISTS (
+                SELECT 1
+                FROM student_class_enrollments enrollment
+                JOIN class_rooms room ON room.id = enrollment.class_room_id
+                WHERE enrollment.student_id = member.id
+                  AND enrollment.status = 'active'
+                  AND room.grade_level_id = audience.grade_level_id
+            )
+        )
+        OR (
+            audience.audience_type = 'classroom'
+            AND EXISTS (
+                SELECT 1 FROM student_class_enrollments enrollment
+                WHERE enrollment.student_id = member.id
+                  AND enrollment.status = 'active'
+                  AND enrollment.class_room_id = audience.class_room_id
+            )
+        )
+        OR (
+            audience.audience_type = 'organization_unit'
+            AND EXISTS (
+                SELECT 1 FROM organization_members unit_member
+                WHERE unit_member.user_id = member.id
+                  AND unit_member.organization_unit_id = audience.organization_unit_id
+                  AND unit_member.ended_at IS NULL
+            )
+        )
+        OR (
+            audience.audience_type = 'classroom_guardians'
+            AND EXISTS (
+                SELECT 1
+                FROM student_parents link
+                JOIN student_class_enrollments enrollment
+                  ON enrollment.student_id = link.student_user_id
+                 AND enrollment.status = 'active'
+                WHERE link.parent_user_id = member.id
+                  AND enrollment.class_room_id = audience.class_room_id
+            )
+        )
+    )
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct AudienceRow {
+    id: Uuid,
+    announcement_id: Uuid,
+    audience_type: String,
+    role_id: Option<Uuid>,
+    grade_level_id: Option<Uuid>,
+    class_room_id: Option<Uuid>,
+    organization_unit_id: Option<Uuid>,
+    reference_name: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ReceiptSummaryRow {
+    announcement_id: Uuid,
+    audience_count: i64,
+    read_count: i64,
+    acknowledged_count: i64,
+}
+
+/// Checks that every referenced role, grade level, classroom and organization
+/// unit exists and is in use.
+pub(super) async fn validate_audience_references(
+    pool: &PgPool,
+    audiences: &[AnnouncementAudienceInput],
+) -> Result<(), AppError> {
+    for audience in audiences {
+        let exists = match audience.audience_type {
+            AnnouncementAudienceType::AllStaff
+            | AnnouncementAudienceType::AllStudents
+            | AnnouncementAudienceType::AllGuardians => true,
+            AnnouncementAudienceType::Role => {
+                reference_exists(
+                    pool,
+                    "SELECT EXISTS (SELECT 1 FROM roles WHERE id = $1 AND is_active = true)",
+                    audience.role_id,
+                )
+                .await?
+            }
+            AnnouncementAudienceType::GradeLevel => {
+                reference_exists(
+                    pool,
+                    "SELECT EXISTS (SELECT 1 FROM grade_levels WHERE id = $1)",
+                    audience.grade_level_id,
+                )
+                .await?
+            }
+            AnnouncementAudienceType::Classroom | AnnouncementAudienceType::ClassroomGuardians => {
+                reference_exists(
+                    pool,
+                    "SELECT EXISTS (SELECT 1 FROM class_rooms WHERE id = $1 AND is_active IS NOT FALSE)",
+                    audience.class_room_id,
+                )
+                .await?
+            }
+            AnnouncementAudienceType::OrganizationUnit => {
+                reference_exists(
+                    pool,
+                    "SELECT EXISTS (SELECT 1 FROM organization_units WHERE id = $1 AND is_active = true)",
+                    audience.organization_unit_id,
+                )
+                .await?
+            }
+        };
+        if !exists {
+            return Err(AppError::ValidationError(
+                "ไม่พบกลุ่มผู้รับประกาศที่เลือก".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn reference_exists(
+    pool: &PgPool,
+    query: &str,
+    reference_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(query)
+        .bind(reference_id)
+        .fetch_one(pool)
+        .await
+        .map_err(read_error)
+}
+
+pub(super) async fn replace_audiences(
+    transaction: &mut Transaction<'_, Postgres>,
+    announcement_id: Uuid,
+    audiences: &[AnnouncementAudienceInput],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM announcement_audiences WHERE announcement_id = $1")
+        .bind(announcement_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+    for audience in audiences {
+        sqlx::query(
+            r#"
+            INSERT INTO announcement_audiences (
+                announcement_id, audience_type, role_id, grade_level_id,
+                class_room_id, organization_unit_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(announcement_id)
+        .bind(audience.audience_type.as_str())
+        .bind(audience.role_id)
+        .bind(audience.grade_level_id)
+        .bind(audience.class_room_id)
+        .bind(audience.organization_unit_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn load_audiences(
+    pool: &PgPool,
+    announcement_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<AnnouncementAudience>>, AppError> {
+    let rows = sqlx::query_as::<_, AudienceRow>(
+        r#"
+        SELECT audience.id,
+               audience.announcement_id,
+               audience.audience_type,
+               audience.role_id,
+               audience.grade_level_id,
+               audience.class_room_id,
+               audience.organization_unit_id,
+               COALESCE(
+                   role.name,
+                   CASE
+                       WHEN grade_level.id IS NULL THEN NULL
+                       WHEN grade_level.level_type = 'kindergarten' THEN CONCAT('อ.', grade_level.year)
+                       WHEN grade_level.level_type = 'primary' THEN CONCAT('ป.', grade_level.year)
+                       WHEN grade_level.level_type = 'secondary' THEN CONCAT('ม.', grade_level.year)
+                       ELSE CONCAT('?.', grade_level.year)
+                   END,
+                   room.name,
+                   unit.name
+               ) AS reference_name
+        FROM announcement_audiences audience
+        LEFT JOIN roles role ON role.id = audience.role_id
+        LEFT JOIN grade_levels grade_level ON grade_level.id = audience.grade_level_id
+        LEFT JOIN class_rooms room ON room.id = audience.class_room_id
+        LEFT JOIN organization_units unit ON unit.id = audience.organization_unit_id
+        WHERE audience.announcement_id = ANY($1)
+        ORDER BY audience.announcement_id, audience.created_at, audience.id
+        "#,
+    )
+    .bind(announcement_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut audiences: HashMap<Uuid, Vec<AnnouncementAudience>> = HashMap::new();
+    for row in rows {
+        let audience_type = parse_audience_type(&row.audience_type)?;
+        audiences
+            .entry(row.announcement_id)
+            .or_default()
+            .push(AnnouncementAudience {
+                id: row.id,
+                audience_type,
+                role_id: row.role_id,
+                grade_level_id: row.grade_level_id,
+                class_room_id: row.class_room_id,
+                organization_unit_id: row.organization_unit_id,
+                label: audience_label(audience_type, row.reference_name.as_deref()),
+            });
+    }
+    Ok(audiences)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AudienceMember {
+    pub(super) user_id: Uuid,
+    pub(super) user_type: String,
+}
+
+/// Active users that currently fall inside any audience of the announcement,
+/// optionally only those who have not acknowledged it yet.
+pub(super) async fn audience_members(
+    pool: &PgPool,
+    announcement_id: Uuid,
+    pending_acknowledgement_only: bool,
+) -> Result<Vec<AudienceMember>, AppError> {
+    sqlx::query_as::<_, AudienceMember>(&format!(
+        r#"
+        SELECT member.id AS user_id, member.user_type
+        FROM users member
+        WHERE member.status = 'active'
+          AND EXISTS (
+              SELECT 1 FROM announcement_audiences audience
+              WHERE audience.announcement_id = $1
+                AND {AUDIENCE_MEMBER_PREDICATE}
+          )
+          AND (
+              NOT $2
+              OR NOT EXISTS (
+                  SELECT 1 FROM announcement_receipts receipt
+                  WHERE receipt.announcement_id = $1
+                    AND receipt.user_id = member.id
+                    AND receipt.acknowledged_at IS NOT NULL
+              )
+          )
+        "#
+    ))
+    .bind(announcement_id)
+    .bind(pending_acknowledgement_only)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Whether the user is in the audience of an announcement that is published,
+/// past its publish time and not yet expired.
+pub async fn is_live_for_member(
+    pool: &PgPool,
+    announcement_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(&format!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM announcements announcement
+            JOIN announcement_audiences audience ON audience.announcement_id = announcement.id
+            JOIN users member ON member.id = $2
+            WHERE announcement.id = $1
+              AND {LIVE_ANNOUNCEMENT_PREDICATE}
+              AND {AUDIENCE_MEMBER_PREDICATE}
+        )
+        "#
+    ))
+    .bind(announcement_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Audience size and receipt totals. Receipts from users who have since left
+/// the audience still count as reads so the totals never go down.
+pub(super) async fn load_receipt_summaries(
+    pool: &PgPool,
+    announcement_ids: &[Uuid],
+) -> Result<HashMap<Uuid, AnnouncementReceiptSummary>, AppError> {
+    let rows = sqlx::query_as::<_, ReceiptSummaryRow>(&format!(
+        r#"
+        SELECT announcement.id AS announcement_id,
+               (
+                   SELECT COUNT(*)
+                   FROM users member
+                   WHERE member.status = 'active'
+                     AND EXISTS (
+                         SELECT 1 FROM announcement_audiences audience
+                         WHERE audience.announcement_id = announcement.id
+                           AND {AUDIENCE_MEMBER_PREDICATE}
+                     )
+               ) AS audience_count,
+               (
+                   SELECT COUNT(*) FROM announcement_receipts receipt
+                   WHERE receipt.announcement_id = announcement.id
+               ) AS read_count,
+               (
+                   SELECT COUNT(*) FROM announcement_receipts receipt
+                   WHERE receipt.announcement_id = announcement.id
+                     AND receipt.acknowledged_at IS NOT NULL
+               ) AS acknowledged_count
+        FROM announcements announcement
+        WHERE announcement.id = ANY($1)
+        "#
+    ))
+    .bind(announcement_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.announcement_id,
+                AnnouncementReceiptSummary {
+                    audience_count: row.audience_count,
+                    read_count: row.read_count,
+                    acknowledged_count: row.acknowledged_count,
+                },
+            )
+        })
+        .collect())
+}
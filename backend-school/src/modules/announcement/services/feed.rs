@@ -0,0 +1,316 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::announcement::models::{
+    AnnouncementFeedItem, MyAnnouncementFilter, PublicAnnouncement, PublicAnnouncementQuery,
+};
+use crate::modules::question_bank::models::RichContent;
+
+use super::audiences::{is_live_for_member, AUDIENCE_MEMBER_PREDICATE};
+use super::records::{load_attachments, read_error, write_error};
+use super::shared::{public_limit, ANNOUNCEMENT_NOT_FOUND_MESSAGE, LIVE_ANNOUNCEMENT_PREDICATE};
+
+const MY_FEED_LIMIT: i64 = 200;
+
+#[derive(Debug, sqlx::FromRow)]
+struct FeedRow {
+    id: Uuid,
+    title: String,
+    summary: Option<String>,
+    content: Json<RichContent>,
+    publish_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    is_pinned: bool,
+    requires_acknowledgement: bool,
+    acknowledge_by: Option<NaiveDate>,
+    read_at: Option<DateTime<Utc>>,
+    acknowledged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PublicRow {
+    id: Uuid,
+    title: String,
+    summary: Option<String>,
+    content: Json<RichContent>,
+    publish_at: DateTime<Utc>,
+    is_pinned: bool,
+}
+
+fn feed_select(extra_conditions: &str) -> String {
+    format!(
+        r#"
+        SELECT announcement.id,
+               announcement.title,
+               announcement.summary,
+               announcement.content,
+               announcement.publish_at,
+               announcement.expires_at,
+               announcement.is_pinned,
+               announcement.requires_acknowledgement,
+               announcement.acknowledge_by,
+               receipt.read_at,
+               receipt.acknowledged_at
+        FROM announcements announcement
+        JOIN users member ON member.id = $1
+        LEFT JOIN announcement_receipts receipt
+          ON receipt.announcement_id = announcement.id AND receipt.user_id = member.id
+        WHERE {LIVE_ANNOUNCEMENT_PREDICATE}
+          AND EXISTS (
+              SELECT 1 FROM announcement_audiences audience
+              WHERE audience.announcement_id = announcement.id
+                AND {AUDIENCE_MEMBER_PREDICATE}
+          )
+          {extra_conditions}
+        "#
+    )
+}
+
+async fn feed_items(
+    pool: &PgPool,
+    rows: Vec<FeedRow>,
+) -> Result<Vec<AnnouncementFeedItem>, AppError> {
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut attachments = load_attachments(pool, &ids).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| AnnouncementFeedItem {
+            attachments: attachments.remove(&row.id).unwrap_or_default(),
+            id: row.id,
+            title: row.title,
+            summary: row.summary,
+            content: row.content.0,
+            publish_at: row.publish_at,
+            expires_at: row.expires_at,
+            is_pinned: row.is_pinned,
+            requires_acknowledgement: row.requires_acknowledgement,
+            acknowledge_by: row.acknowledge_by,
+            read_at: row.read_at,
+            acknowledged_at: row.acknowledged_at,
+        })
+        .collect())
+}
+
+async fn load_feed_item(
+    pool: &PgPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<AnnouncementFeedItem, AppError> {
+    let row = sqlx::query_as::<_, FeedRow>(&feed_select("AND announcement.id = $2"))
+        .bind(user_id)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))?;
+    feed_items(pool, vec![row])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))
+}
+
+/// Live announcements addressed to the actor, pinned and newest first.
+pub async fn list_my_announcements(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: MyAnnouncementFilter,
+) -> Result<Vec<AnnouncementFeedItem>, AppError> {
+    let rows = sqlx::query_as::<_, FeedRow>(&feed_select(
+        r#"
+          AND (NOT $2 OR receipt.user_id IS NULL)
+          AND (NOT $3 OR (announcement.requires_acknowledgement AND receipt.acknowledged_at IS NULL))
+        ORDER BY announcement.is_pinned DESC, announcement.publish_at DESC, announcement.id
+        LIMIT $4
+        "#,
+    ))
+    .bind(actor.user_id)
+    .bind(filter.unread_only)
+    .bind(filter.pending_acknowledgement)
+    .bind(MY_FEED_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    feed_items(pool, rows).await
+}
+
+/// Opens an announcement for an audience member and records the first read.
+pub async fn get_my_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<AnnouncementFeedItem, AppError> {
+    if !is_live_for_member(pool, id, actor.user_id).await? {
+        return Err(AppError::NotFound(
+            ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO announcement_receipts (announcement_id, user_id)
+        VALUES ($1, $2)
+        ON CONFLICT (announcement_id, user_id) DO NOTHING
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+
+    load_feed_item(pool, actor.user_id, id).await
+}
+
+/// Acknowledging also counts as reading; the first acknowledgement time is
+/// kept on repeat calls.
+pub async fn acknowledge_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<AnnouncementFeedItem, AppError> {
+    if !is_live_for_member(pool, id, actor.user_id).await? {
+        return Err(AppError::NotFound(
+            ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string(),
+        ));
+    }
+    let requires_acknowledgement = sqlx::query_scalar::<_, bool>(
+        "SELECT requires_acknowledgement FROM announcements WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+    if !requires_acknowledgement {
+        return Err(AppError::Conflict("ประกาศนี้ไม่ต้องกดรับทราบ".to_string()));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO announcement_receipts (announcement_id, user_id, acknowledged_at)
+        VALUES ($1, $2, NOW())
+        ON CONFLICT (announcement_id, user_id) DO UPDATE
+        SET acknowledged_at = COALESCE(announcement_receipts.acknowledged_at, NOW())
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+
+    load_feed_item(pool, actor.user_id, id).await
+}
+
+const PUBLIC_SELECT: &str = r#"
+    SELECT announcement.id,
+           announcement.title,
+           announcement.summary,
+           announcement.content,
+           announcement.publish_at,
+           announcement.is_pinned
+    FROM announcements announcement
+"#;
+
+async fn public_items(
+    pool: &PgPool,
+    rows: Vec<PublicRow>,
+) -> Result<Vec<PublicAnnouncement>, AppError> {
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut attachments = load_attachments(pool, &ids).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| PublicAnnouncement {
+            attachments: attachments.remove(&row.id).unwrap_or_default(),
+            id: row.id,
+            title: row.title,
+            summary: row.summary,
+            content: row.content.0,
+            publish_at: row.publish_at,
+            is_pinned: row.is_pinned,
+        })
+        .collect())
+}
+
+/// School website feed: live announcements marked public.
+pub async fn list_public_announcements(
+    pool: &PgPool,
+    query: PublicAnnouncementQuery,
+) -> Result<Vec<PublicAnnouncement>, AppError> {
+    let rows = sqlx::query_as::<_, PublicRow>(&format!(
+        r#"
+        {PUBLIC_SELECT}
+        WHERE announcement.is_public = true
+          AND {LIVE_ANNOUNCEMENT_PREDICATE}
+        ORDER BY announcement.is_pinned DESC, announcement.publish_at DESC, announcement.id
+        LIMIT $1
+        "#
+    ))
+    .bind(public_limit(query.limit))
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    public_items(pool, rows).await
+}
+
+pub async fn get_public_announcement(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<PublicAnnouncement, AppError> {
+    let row = sqlx::query_as::<_, PublicRow>(&format!(
+        r#"
+        {PUBLIC_SELECT}
+        WHERE announcement.id = $1
+          AND announcement.is_public = true
+          AND {LIVE_ANNOUNCEMENT_PREDICATE}
+        "#
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))?;
+
+    public_items(pool, vec![row])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))
+}
+
+/// Anonymous downloads are limited to images and attachments of a live public
+/// announcement.
+pub async fn ensure_public_announcement_file(
+    pool: &PgPool,
+    announcement_id: Uuid,
+    file_id: Uuid,
+) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar::<_, bool>(&format!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM announcements announcement
+            JOIN announcement_files attachment ON attachment.announcement_id = announcement.id
+            WHERE announcement.id = $1
+              AND attachment.file_id = $2
+              AND announcement.is_public = true
+              AND {LIVE_ANNOUNCEMENT_PREDICATE}
+        )
+        "#
+    ))
+    .bind(announcement_id)
+    .bind(file_id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("ไม่พบไฟล์ของประกาศ".to_string()))
+    }
+}
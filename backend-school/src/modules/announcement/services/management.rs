@@ -0,0 +1,418 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::announcement::models::{
+    Announcement, AnnouncementAudienceInput, AnnouncementFilter, AnnouncementReceipt,
+    AnnouncementReceiptFilter, AnnouncementStatus, PublishAnnouncementRequest,
+    UpsertAnnouncementRequest,
+};
+use crate::modules::question_bank::models::RichContent;
+use crate::policies::announcement_access_policy::{
+    require_announcement_manage, require_announcement_school_read,
+};
+
+use super::audiences::{
+    replace_audiences, validate_audience_references, AUDIENCE_MEMBER_PREDICATE,
+};
+use super::records::{
+    announcements_from_rows, attached_file_ids, load_announcement, load_row, read_error,
+    replace_files, validate_files, write_error, AnnouncementMutationOutcome, AnnouncementRow,
+    ANNOUNCEMENT_SELECT,
+};
+use super::shared::{
+    can_edit, collect_file_ids, normalize_audiences, normalize_summary, parse_status,
+    receipt_matches_state, required_title, resolve_publish_at, search_pattern,
+    validate_acknowledgement, validate_content, validate_expiry, validate_publishable,
+};
+
+struct PreparedAnnouncement {
+    title: String,
+    summary: Option<String>,
+    content: RichContent,
+    content_text: String,
+    expires_at: Option<DateTime<Utc>>,
+    is_pinned: bool,
+    is_public: bool,
+    requires_acknowledgement: bool,
+    acknowledge_by: Option<NaiveDate>,
+    audiences: Vec<AnnouncementAudienceInput>,
+    image_file_ids: Vec<Uuid>,
+    attachment_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ReceiptRow {
+    user_id: Uuid,
+    user_name: String,
+    user_type: String,
+    read_at: Option<DateTime<Utc>>,
+    acknowledged_at: Option<DateTime<Utc>>,
+}
+
+async fn prepare(
+    pool: &PgPool,
+    actor: &ActorContext,
+    announcement_id: Option<Uuid>,
+    payload: UpsertAnnouncementRequest,
+) -> Result<PreparedAnnouncement, AppError> {
+    let title = required_title(&payload.title)?;
+    let summary = normalize_summary(payload.summary)?;
+    validate_content(&payload.content)?;
+    validate_acknowledgement(payload.requires_acknowledgement, payload.acknowledge_by)?;
+    let audiences = normalize_audiences(payload.audiences)?;
+    validate_audience_references(pool, &audiences).await?;
+    let (image_file_ids, attachment_file_ids) =
+        collect_file_ids(&payload.content, &payload.attachment_file_ids)?;
+    let file_ids: Vec<Uuid> = image_file_ids
+        .iter()
+        .chain(&attachment_file_ids)
+        .copied()
+        .collect();
+    validate_files(pool, actor.user_id, announcement_id, &file_ids).await?;
+
+    Ok(PreparedAnnouncement {
+        title,
+        summary,
+        content_text: payload.content.search_text(),
+        content: payload.content,
+        expires_at: payload.expires_at,
+        is_pinned: payload.is_pinned,
+        is_public: payload.is_public,
+        requires_acknowledgement: payload.requires_acknowledgement,
+        acknowledge_by: payload.acknowledge_by,
+        audiences,
+        image_file_ids,
+        attachment_file_ids,
+    })
+}
+
+/// GET list for managers and school readers, pinned and newest first.
+pub async fn list_announcements(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: AnnouncementFilter,
+) -> Result<Vec<Announcement>, AppError> {
+    require_announcement_school_read(actor)?;
+    let search = filter
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(search_pattern);
+
+    let rows = sqlx::query_as::<_, AnnouncementRow>(&format!(
+        r#"
+        {ANNOUNCEMENT_SELECT}
+        WHERE ($1::text IS NULL OR announcement.status = $1)
+          AND (
+              $2::text IS NULL
+              OR announcement.title ILIKE $2
+              OR announcement.content_text ILIKE $2
+          )
+        ORDER BY announcement.is_pinned DESC,
+                 COALESCE(announcement.publish_at, announcement.created_at) DESC,
+                 announcement.id
+        "#
+    ))
+    .bind(filter.status.map(AnnouncementStatus::as_str))
+    .bind(search)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    announcements_from_rows(pool, rows).await
+}
+
+pub async fn get_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Announcement, AppError> {
+    require_announcement_school_read(actor)?;
+    load_announcement(pool, id).await
+}
+
+pub async fn create_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: UpsertAnnouncementRequest,
+) -> Result<AnnouncementMutationOutcome, AppError> {
+    require_announcement_manage(actor)?;
+    let prepared = prepare(pool, actor, None, payload).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO announcements (
+            title, summary, content, content_text, expires_at, is_pinned, is_public,
+            requires_acknowledgement, acknowledge_by, created_by, updated_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $10)
+        RETURNING id
+        "#,
+    )
+    .bind(&prepared.title)
+    .bind(&prepared.summary)
+    .bind(Json(&prepared.content))
+    .bind(&prepared.content_text)
+    .bind(prepared.expires_at)
+    .bind(prepared.is_pinned)
+    .bind(prepared.is_public)
+    .bind(prepared.requires_acknowledgement)
+    .bind(prepared.acknowledge_by)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    replace_audiences(&mut transaction, id, &prepared.audiences).await?;
+    let detached_file_ids = replace_files(
+        &mut transaction,
+        id,
+        &prepared.image_file_ids,
+        &prepared.attachment_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(AnnouncementMutationOutcome {
+        announcement: load_announcement(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+/// Drafts and published announcements can be corrected; a published one must
+/// keep a reachable audience.
+pub async fn update_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: UpsertAnnouncementRequest,
+) -> Result<AnnouncementMutationOutcome, AppError> {
+    require_announcement_manage(actor)?;
+    let current = load_row(pool, id).await?;
+    let status = parse_status(&current.status)?;
+    if !can_edit(status) {
+        return Err(AppError::Conflict("ประกาศที่เก็บถาวรแล้วแก้ไขไม่ได้".to_string()));
+    }
+    let prepared = prepare(pool, actor, Some(id), payload).await?;
+    validate_expiry(current.publish_at, prepared.expires_at)?;
+    if status == AnnouncementStatus::Published {
+        validate_publishable(
+            prepared.audiences.len(),
+            prepared.is_public,
+            prepared.requires_acknowledgement,
+        )?;
+    }
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    sqlx::query(
+        r#"
+        UPDATE announcements
+        SET title = $2,
+            summary = $3,
+            content = $4,
+            content_text = $5,
+            expires_at = $6,
+            is_pinned = $7,
+            is_public = $8,
+            requires_acknowledgement = $9,
+            acknowledge_by = $10,
+            updated_by = $11
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&prepared.title)
+    .bind(&prepared.summary)
+    .bind(Json(&prepared.content))
+    .bind(&prepared.content_text)
+    .bind(prepared.expires_at)
+    .bind(prepared.is_pinned)
+    .bind(prepared.is_public)
+    .bind(prepared.requires_acknowledgement)
+    .bind(prepared.acknowledge_by)
+    .bind(actor.user_id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    replace_audiences(&mut transaction, id, &prepared.audiences).await?;
+    let detached_file_ids = replace_files(
+        &mut transaction,
+        id,
+        &prepared.image_file_ids,
+        &prepared.attachment_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(AnnouncementMutationOutcome {
+        announcement: load_announcement(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+/// Publishes a draft now or at a scheduled time. Audience notifications go out
+/// from the dispatch job once the publish time has passed.
+pub async fn publish_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: PublishAnnouncementRequest,
+) -> Result<Announcement, AppError> {
+    require_announcement_manage(actor)?;
+    let current = load_announcement(pool, id).await?;
+    if current.status != AnnouncementStatus::Draft {
+        return Err(AppError::Conflict("เผยแพร่ได้เฉพาะประกาศฉบับร่าง".to_string()));
+    }
+    validate_publishable(
+        current.audiences.len(),
+        current.is_public,
+        current.requires_acknowledgement,
+    )?;
+    let publish_at = resolve_publish_at(payload.publish_at, current.expires_at, Utc::now())?;
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE announcements
+        SET status = 'published',
+            publish_at = $2,
+            published_by = $3,
+            updated_by = $3
+        WHERE id = $1 AND status = 'draft'
+        "#,
+    )
+    .bind(id)
+    .bind(publish_at)
+    .bind(actor.user_id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            "ประกาศถูกเผยแพร่หรือเปลี่ยนสถานะไปแล้ว".to_string(),
+        ));
+    }
+
+    load_announcement(pool, id).await
+}
+
+/// Takes an announcement off every feed; receipts are kept for reporting.
+pub async fn archive_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Announcement, AppError> {
+    require_announcement_manage(actor)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE announcements
+        SET status = 'archived', is_pinned = false, updated_by = $2
+        WHERE id = $1 AND status = 'published'
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        load_row(pool, id).await?;
+        return Err(AppError::Conflict(
+            "เก็บถาวรได้เฉพาะประกาศที่เผยแพร่แล้ว".to_string(),
+        ));
+    }
+
+    load_announcement(pool, id).await
+}
+
+/// Deletes a draft and returns its files for File Platform deletion.
+pub async fn delete_announcement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    require_announcement_manage(actor)?;
+    let current = load_row(pool, id).await?;
+    if parse_status(&current.status)? != AnnouncementStatus::Draft {
+        return Err(AppError::Conflict(
+            "ลบได้เฉพาะประกาศฉบับร่าง ประกาศที่เผยแพร่แล้วให้เก็บถาวรแทน".to_string(),
+        ));
+    }
+    let file_ids = attached_file_ids(pool, id).await?;
+
+    let deleted = sqlx::query("DELETE FROM announcements WHERE id = $1 AND status = 'draft'")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(write_error)?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            "ประกาศถูกเผยแพร่หรือเปลี่ยนสถานะไปแล้ว".to_string(),
+        ));
+    }
+
+    Ok(file_ids)
+}
+
+/// Current audience members with their receipts, plus anyone who read the
+/// announcement before leaving the audience.
+pub async fn list_receipts(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    filter: AnnouncementReceiptFilter,
+) -> Result<Vec<AnnouncementReceipt>, AppError> {
+    require_announcement_school_read(actor)?;
+    load_row(pool, id).await?;
+
+    let rows = sqlx::query_as::<_, ReceiptRow>(&format!(
+        r#"
+        SELECT member.id AS user_id,
+               CONCAT_WS(' ', member.first_name, member.last_name) AS user_name,
+               member.user_type,
+               receipt.read_at,
+               receipt.acknowledged_at
+        FROM users member
+        LEFT JOIN announcement_receipts receipt
+          ON receipt.announcement_id = $1 AND receipt.user_id = member.id
+        WHERE receipt.user_id IS NOT NULL
+           OR (
+               member.status = 'active'
+               AND EXISTS (
+                   SELECT 1 FROM announcement_audiences audience
+                   WHERE audience.announcement_id = $1
+                     AND {AUDIENCE_MEMBER_PREDICATE}
+               )
+           )
+        ORDER BY member.user_type, member.first_name, member.last_name, member.id
+        "#
+    ))
+    .bind(id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| {
+            filter.state.is_none_or(|state| {
+                receipt_matches_state(state, row.read_at.is_some(), row.acknowledged_at.is_some())
+            })
+        })
+        .map(|row| AnnouncementReceipt {
+            user_id: row.user_id,
+            user_name: row.user_name,
+            user_type: row.user_type,
+            read_at: row.read_at,
+            acknowledged_at: row.acknowledged_at,
+        })
+        .collect())
+}
@@ -0,0 +1,328 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::db::{admin_client::AdminClient, pool_manager::PoolManager};
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::announcement::models::AnnouncementReminderResult;
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::policies::announcement_access_policy::require_announcement_manage;
+use crate::scheduling::SCHOOL_TIMEZONE;
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+use super::audiences::{audience_members, AudienceMember};
+use super::records::{load_row, read_error, write_error};
+use super::shared::{
+    is_live, notification_link_for_user_type, notification_text, parse_status, reminder_due,
+    AnnouncementNotificationKind, LIVE_ANNOUNCEMENT_PREDICATE,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct NotificationCandidate {
+    id: Uuid,
+    title: String,
+    summary: Option<String>,
+    publish_at: DateTime<Utc>,
+    acknowledge_by: Option<NaiveDate>,
+    last_reminded_on: Option<NaiveDate>,
+}
+
+/// Sends one notification per member and returns how many went out. A failed
+/// send is logged and does not stop the rest of the audience.
+async fn notify_members(
+    pool: &PgPool,
+    publisher: &TenantNotificationPublisher<'_>,
+    announcement_id: Uuid,
+    members: &[AudienceMember],
+    title: &str,
+    message: &str,
+) -> usize {
+    let mut sent = 0;
+    for member in members {
+        match NotificationService::send(
+            pool,
+            publisher,
+            member.user_id,
+            title,
+            message,
+            NotificationType::Info,
+            notification_link_for_user_type(&member.user_type),
+        )
+        .await
+        {
+            Ok(_) => sent += 1,
+            Err(error) => tracing::error!(
+                announcement_id = %announcement_id,
+                user_id = %member.user_id,
+                error = %error,
+                "Failed to send announcement notification"
+            ),
+        }
+    }
+    sent
+}
+
+/// Claims announcements whose publish time has passed and notifies their
+/// audience once. Public-only announcements are claimed without sending.
+pub async fn dispatch_due_publications(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+) -> Result<usize, AppError> {
+    let claimed = sqlx::query_as::<_, NotificationCandidate>(&format!(
+        r#"
+        UPDATE announcements announcement
+        SET published_notified_at = NOW()
+        WHERE announcement.published_notified_at IS NULL
+          AND {LIVE_ANNOUNCEMENT_PREDICATE}
+        RETURNING announcement.id,
+                  announcement.title,
+                  announcement.summary,
+                  announcement.publish_at,
+                  announcement.acknowledge_by,
+                  announcement.last_reminded_on
+        "#
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(write_error)?;
+
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let mut sent = 0;
+    for announcement in claimed {
+        let members = audience_members(pool, announcement.id, false).await?;
+        let (title, message) = notification_text(
+            AnnouncementNotificationKind::Published,
+            &announcement.title,
+            announcement.summary.as_deref(),
+            announcement.acknowledge_by,
+        );
+        sent += notify_members(
+            pool,
+            &publisher,
+            announcement.id,
+            &members,
+            &title,
+            &message,
+        )
+        .await;
+    }
+    Ok(sent)
+}
+
+/// Sends the daily acknowledgement reminder to members who have not
+/// acknowledged yet. The day is claimed before sending so concurrent runs do
+/// not remind twice.
+pub async fn dispatch_due_reminders(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let local_now = now.with_timezone(&SCHOOL_TIMEZONE);
+    let today = local_now.date_naive();
+    let candidates = sqlx::query_as::<_, NotificationCandidate>(&format!(
+        r#"
+        SELECT announcement.id,
+               announcement.title,
+               announcement.summary,
+               announcement.publish_at,
+               announcement.acknowledge_by,
+               announcement.last_reminded_on
+        FROM announcements announcement
+        WHERE announcement.requires_acknowledgement = true
+          AND announcement.published_notified_at IS NOT NULL
+          AND {LIVE_ANNOUNCEMENT_PREDICATE}
+        "#
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let mut sent = 0;
+    for announcement in candidates {
+        let publish_date = announcement
+            .publish_at
+            .with_timezone(&SCHOOL_TIMEZONE)
+            .date_naive();
+        if !reminder_due(
+            today,
+            local_now.hour(),
+            publish_date,
+            announcement.acknowledge_by,
+            announcement.last_reminded_on,
+        ) {
+            continue;
+        }
+        if !claim_reminder_day(pool, announcement.id, today).await? {
+            continue;
+        }
+        sent += send_reminder(pool, &publisher, &announcement).await?;
+    }
+    Ok(sent)
+}
+
+async fn claim_reminder_day(
+    pool: &PgPool,
+    announcement_id: Uuid,
+    today: NaiveDate,
+) -> Result<bool, AppError> {
+    let claimed = sqlx::query(
+        r#"
+        UPDATE announcements
+        SET last_reminded_on = $2
+        WHERE id = $1
+          AND (last_reminded_on IS NULL OR last_reminded_on < $2)
+        "#,
+    )
+    .bind(announcement_id)
+    .bind(today)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+    Ok(claimed.rows_affected() > 0)
+}
+
+async fn send_reminder(
+    pool: &PgPool,
+    publisher: &TenantNotificationPublisher<'_>,
+    announcement: &NotificationCandidate,
+) -> Result<usize, AppError> {
+    let members = audience_members(pool, announcement.id, true).await?;
+    let (title, message) = notification_text(
+        AnnouncementNotificationKind::AcknowledgementReminder,
+        &announcement.title,
+        announcement.summary.as_deref(),
+        announcement.acknowledge_by,
+    );
+    Ok(notify_members(pool, publisher, announcement.id, &members, &title, &message).await)
+}
+
+/// Manual reminder from the receipts screen. It counts as the day's reminder
+/// so the scheduled one does not repeat it.
+pub async fn send_reminders_now(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<AnnouncementReminderResult, AppError> {
+    require_announcement_manage(actor)?;
+    let row = load_row(pool, id).await?;
+    let now = Utc::now();
+    if !row.requires_acknowledgement
+        || !is_live(
+            parse_status(&row.status)?,
+            row.publish_at,
+            row.expires_at,
+            now,
+        )
+    {
+        return Err(AppError::Conflict(
+            "ส่งการเตือนได้เฉพาะประกาศที่กำลังเผยแพร่และต้องกดรับทราบ".to_string(),
+        ));
+    }
+
+    let candidate = NotificationCandidate {
+        id: row.id,
+        title: row.title,
+        summary: row.summary,
+        publish_at: row.publish_at.unwrap_or(now),
+        acknowledge_by: row.acknowledge_by,
+        last_reminded_on: None,
+    };
+    let today = now.with_timezone(&SCHOOL_TIMEZONE).date_naive();
+    sqlx::query("UPDATE announcements SET last_reminded_on = $2 WHERE id = $1")
+        .bind(id)
+        .bind(today)
+        .execute(pool)
+        .await
+        .map_err(write_error)?;
+
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let recipient_count = send_reminder(pool, &publisher, &candidate).await?;
+    Ok(AnnouncementReminderResult { recipient_count })
+}
+
+pub async fn process_due_announcements(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let published = dispatch_due_publications(pool, notification_channel, tenant).await?;
+    let reminded = dispatch_due_reminders(pool, notification_channel, tenant, now).await?;
+    if published > 0 || reminded > 0 {
+        tracing::info!(
+            tenant,
+            published_notifications = published,
+            reminder_notifications = reminded,
+            "Dispatched announcement notifications"
+        );
+    }
+    Ok(())
+}
+
+pub async fn process_due_announcements_for_all_tenants(
+    admin_client: Arc<AdminClient>,
+    pool_manager: Arc<PoolManager>,
+    notification_channel: broadcast::Sender<TenantNotificationEvent>,
+) {
+    let now = Utc::now();
+    let schools = match admin_client.list_active_schools().await {
+        Ok(schools) => schools,
+        Err(error) => {
+            tracing::error!("Failed to fetch schools for announcements: {}", error);
+            return;
+        }
+    };
+
+    for school in schools {
+        if school.is_migrating() {
+            tracing::info!(
+                "Skipping announcement dispatch for {}: migration in progress",
+                school.subdomain
+            );
+            continue;
+        }
+        let Some(db_url) = school
+            .db_connection_string
+            .filter(|value| !value.is_empty())
+        else {
+            tracing::warn!(
+                "Skipping announcement dispatch for {}: no database URL",
+                school.subdomain
+            );
+            continue;
+        };
+
+        match pool_manager.get_pool(&db_url, &school.subdomain).await {
+            Ok(pool) => {
+                if let Err(error) =
+                    process_due_announcements(&pool, &notification_channel, &school.subdomain, now)
+                        .await
+                {
+                    tracing::error!(
+                        "Announcement dispatch failed for {}: {}",
+                        school.subdomain,
+                        error
+                    );
+                }
+            }
+            Err(error) => {
+                tracing::error!(
+                    "Failed to open tenant pool for announcements {}: {}",
+                    school.subdomain,
+                    error
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::announcement::models::{Announcement, AnnouncementAttachment};
+use crate::modules::question_bank::models::RichContent;
+
+use super::audiences::{load_audiences, load_receipt_summaries};
+use super::shared::{parse_status, ANNOUNCEMENT_NOT_FOUND_MESSAGE};
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AnnouncementRow {
+    pub(super) id: Uuid,
+    pub(super) title: String,
+    pub(super) summary: Option<String>,
+    pub(super) content: Json<RichContent>,
+    pub(super) status: String,
+    pub(super) publish_at: Option<DateTime<Utc>>,
+    pub(super) expires_at: Option<DateTime<Utc>>,
+    pub(super) is_pinned: bool,
+    pub(super) is_public: bool,
+    pub(super) requires_acknowledgement: bool,
+    pub(super) acknowledge_by: Option<NaiveDate>,
+    pub(super) published_notified_at: Option<DateTime<Utc>>,
+    pub(super) created_by: Option<Uuid>,
+    pub(super) created_by_name: Option<String>,
+    pub(super) created_at: DateTime<Utc>,
+    pub(super) updated_at: DateTime<Utc>,
+}
+
+pub(super) const ANNOUNCEMENT_SELECT: &str = r#"
+    SELECT announcement.id,
+           announcement.title,
+           announcement.summary,
+           announcement.content,
+           announcement.status,
+           announcement.publish_at,
+           announcement.expires_at,
+           announcement.is_pinned,
+           announcement.is_public,
+           announcement.requires_acknowledgement,
+           announcement.acknowledge_by,
+           announcement.published_notified_at,
+           announcement.created_by,
+           NULLIF(CONCAT_WS(' ', creator.first_name, creator.last_name), '') AS created_by_name,
+           announcement.created_at,
+           announcement.updated_at
+    FROM announcements announcement
+    LEFT JOIN users creator ON creator.id = announcement.created_by
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct AttachmentRow {
+    announcement_id: Uuid,
+    file_id: Uuid,
+    display_filename: String,
+    content_type: Option<String>,
+    byte_size: Option<i64>,
+}
+
+pub struct AnnouncementMutationOutcome {
+    pub announcement: Announcement,
+    /// Files removed from the announcement that the handler hands to File
+    /// Platform deletion.
+    pub detached_file_ids: Vec<Uuid>,
+}
+
+pub(super) async fn load_row(pool: &PgPool, id: Uuid) -> Result<AnnouncementRow, AppError> {
+    sqlx::query_as::<_, AnnouncementRow>(&format!(
+        "{ANNOUNCEMENT_SELECT} WHERE announcement.id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))
+}
+
+pub(super) async fn load_announcement(pool: &PgPool, id: Uuid) -> Result<Announcement, AppError> {
+    let row = load_row(pool, id).await?;
+    announcements_from_rows(pool, vec![row])
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(ANNOUNCEMENT_NOT_FOUND_MESSAGE.to_string()))
+}
+
+pub(super) async fn announcements_from_rows(
+    pool: &PgPool,
+    rows: Vec<AnnouncementRow>,
+) -> Result<Vec<Announcement>, AppError> {
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut audiences = load_audiences(pool, &ids).await?;
+    let mut attachments = load_attachments(pool, &ids).await?;
+    let mut receipts = load_receipt_summaries(pool, &ids).await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(Announcement {
+                id: row.id,
+                title: row.title,
+                summary: row.summary,
+                content: row.content.0,
+                status: parse_status(&row.status)?,
+                publish_at: row.publish_at,
+                expires_at: row.expires_at,
+                is_pinned: row.is_pinned,
+                is_public: row.is_public,
+                requires_acknowledgement: row.requires_acknowledgement,
+                acknowledge_by: row.acknowledge_by,
+                audiences: audiences.remove(&row.id).unwrap_or_default(),
+                attachments: attachments.remove(&row.id).unwrap_or_default(),
+                receipts: receipts.remove(&row.id).unwrap_or_default(),
+                published_notified_at: row.published_notified_at,
+                created_by: row.created_by,
+                created_by_name: row.created_by_name,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect()
+}
+
+/// Downloadable attachments in display order; inline content images are
+/// referenced from the content itself.
+pub(super) async fn load_attachments(
+    pool: &PgPool,
+    announcement_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<AnnouncementAttachment>>, AppError> {
+    let rows = sqlx::query_as::<_, AttachmentRow>(
+        r#"
+        SELECT attachment.announcement_id,
+               attachment.file_id,
+               file.display_filename,
+               version.detected_mime_type AS content_type,
+               version.byte_size
+        FROM announcement_files attachment
+        JOIN files file ON file.id = attachment.file_id
+        LEFT JOIN file_versions version
+          ON version.id = file.current_version_id AND version.file_id = file.id
+        WHERE attachment.announcement_id = ANY($1)
+          AND attachment.is_attachment = true
+          AND file.deleted_at IS NULL
+        ORDER BY attachment.announcement_id, attachment.sort_order, attachment.file_id
+        "#,
+    )
+    .bind(announcement_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut attachments: HashMap<Uuid, Vec<AnnouncementAttachment>> = HashMap::new();
+    for row in rows {
+        attachments
+            .entry(row.announcement_id)
+            .or_default()
+            .push(AnnouncementAttachment {
+                file_id: row.file_id,
+                display_filename: row.display_filename,
+                content_type: row.content_type,
+                byte_size: row.byte_size,
+            });
+    }
+    Ok(attachments)
+}
+
+/// Checks that every file is a ready announcement file that is either an
+/// unattached upload of the editor or already part of this announcement.
+pub(super) async fn validate_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    announcement_id: Option<Uuid>,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let usable = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        LEFT JOIN announcement_files attachment ON attachment.file_id = files.id
+        WHERE files.id = ANY($1)
+          AND files.purpose_code = 'announcement_file'
+          AND files.lifecycle_status = 'ready'
+          AND files.deleted_at IS NULL
+          AND (
+              (attachment.file_id IS NULL AND files.owner_user_id = $2)
+              OR attachment.announcement_id = $3
+          )
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .bind(announcement_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate announcement files: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบไฟล์แนบได้".to_string())
+    })?;
+
+    if usable == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "รูปหรือไฟล์แนบไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
+
+/// Attaches content images and attachments, keeping the attachment order, and
+/// returns the previously attached files that are no longer listed so the
+/// caller can hand them to File Platform deletion.
+pub(super) async fn replace_files(
+    transaction: &mut Transaction<'_, Postgres>,
+    announcement_id: Uuid,
+    image_file_ids: &[Uuid],
+    attachment_file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let file_ids: Vec<Uuid> = image_file_ids
+        .iter()
+        .chain(attachment_file_ids)
+        .copied()
+        .collect();
+    let detached_file_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        DELETE FROM announcement_files
+        WHERE announcement_id = $1
+          AND NOT (file_id = ANY($2))
+        RETURNING file_id
+        "#,
+    )
+    .bind(announcement_id)
+    .bind(&file_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if file_ids.is_empty() {
+        return Ok(detached_file_ids);
+    }
+
+    let is_attachment: Vec<bool> = image_file_ids
+        .iter()
+        .map(|_| false)
+        .chain(attachment_file_ids.iter().map(|_| true))
+        .collect();
+    let sort_orders: Vec<i32> = (0..image_file_ids.len() as i32)
+        .chain(0..attachment_file_ids.len() as i32)
+        .collect();
+    sqlx::query(
+        r#"
+        INSERT INTO announcement_files (
+            announcement_id, file_id, is_attachment, sort_order, attached_by
+        )
+        SELECT $1, item.file_id, item.is_attachment, item.sort_order, $5
+        FROM UNNEST($2::uuid[], $3::boolean[], $4::integer[])
+            AS item(file_id, is_attachment, sort_order)
+        ON CONFLICT (announcement_id, file_id) DO UPDATE
+        SET is_attachment = EXCLUDED.is_attachment,
+            sort_order = EXCLUDED.sort_order
+        "#,
+    )
+    .bind(announcement_id)
+    .bind(&file_ids)
+    .bind(&is_attachment)
+    .bind(&sort_orders)
+    .bind(attached_by)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    sqlx::query(
+        "UPDATE files SET retention_class = 'standard', expires_at = NULL, updated_at = NOW() WHERE id = ANY($1)",
+    )
+    .bind(&file_ids)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    Ok(detached_file_ids)
+}
+
+pub(super) async fn attached_file_ids(
+    pool: &PgPool,
+    announcement_id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT file_id FROM announcement_files WHERE announcement_id = $1 ORDER BY file_id",
+    )
+    .bind(announcement_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+pub(super) fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read announcement: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลประกาศได้".to_string())
+}
+
+pub(super) fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write announcement: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกประกาศได้".to_string())
+}
@@ -0,0 +1,357 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::announcement::models::{
+    AnnouncementAudienceInput, AnnouncementAudienceType, AnnouncementReceiptState,
+    AnnouncementStatus,
+};
+use crate::modules::question_bank::models::RichContent;
+
+pub(super) const ANNOUNCEMENT_NOT_FOUND_MESSAGE: &str = "ไม่พบประกาศ";
+pub(super) const MAX_AUDIENCES: usize = 50;
+pub(super) const MAX_FILES: usize = 20;
+pub(super) const MAX_TITLE_CHARS: usize = 200;
+pub(super) const MAX_SUMMARY_CHARS: usize = 500;
+/// Without an acknowledgement deadline, reminders stop this many days after
+/// publishing.
+pub(super) const DEFAULT_REMINDER_DAYS: i64 = 7;
+/// Reminders are not sent before this Bangkok hour.
+pub(super) const REMINDER_EARLIEST_HOUR: u32 = 7;
+/// Scheduled publishing may lag the requested time by one dispatch run, so a
+/// publish time slightly in the past is treated as "now".
+const PUBLISH_AT_GRACE_MINUTES: i64 = 5;
+const DEFAULT_PUBLIC_LIMIT: i64 = 20;
+const MAX_PUBLIC_LIMIT: i64 = 50;
+
+/// SQL counterpart of [`is_live`] for the `announcement` alias.
+pub(super) const LIVE_ANNOUNCEMENT_PREDICATE: &str = r#"
+    announcement.status = 'published'
+    AND announcement.publish_at <= NOW()
+    AND (announcement.expires_at IS NULL OR announcement.expires_at > NOW())
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnouncementNotificationKind {
+    Published,
+    AcknowledgementReminder,
+}
+
+/// Checks that each audience carries exactly the reference its type needs and
+/// drops duplicates while keeping the submitted order.
+pub fn normalize_audiences(
+    audiences: Vec<AnnouncementAudienceInput>,
+) -> Result<Vec<AnnouncementAudienceInput>, AppError> {
+    if audiences.len() > MAX_AUDIENCES {
+        return Err(AppError::ValidationError(format!(
+            "กำหนดกลุ่มผู้รับได้ไม่เกิน {MAX_AUDIENCES} กลุ่ม"
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    let mut normalized = Vec::with_capacity(audiences.len());
+    for audience in audiences {
+        let references = [
+            audience.role_id.is_some(),
+            audience.grade_level_id.is_some(),
+            audience.class_room_id.is_some(),
+            audience.organization_unit_id.is_some(),
+        ];
+        let expected = match audience.audience_type {
+            AnnouncementAudienceType::AllStaff
+            | AnnouncementAudienceType::AllStudents
+            | AnnouncementAudienceType::AllGuardians => [false, false, false, false],
+            AnnouncementAudienceType::Role => [true, false, false, false],
+            AnnouncementAudienceType::GradeLevel => [false, true, false, false],
+            AnnouncementAudienceType::Classroom | AnnouncementAudienceType::ClassroomGuardians => {
+                [false, false, true, false]
+            }
+            AnnouncementAudienceType::OrganizationUnit => [false, false, false, true],
+        };
+        if references != expected {
+            return Err(AppError::ValidationError(
+                "ข้อมูลกลุ่มผู้รับประกาศไม่ครบหรือไม่ตรงกับประเภท".to_string(),
+            ));
+        }
+        if seen.insert(audience_key(&audience)) {
+            normalized.push(audience);
+        }
+    }
+    Ok(normalized)
+}
+
+fn audience_key(audience: &AnnouncementAudienceInput) -> (AnnouncementAudienceType, Option<Uuid>) {
+    (
+        audience.audience_type,
+        audience
+            .role_id
+            .or(audience.grade_level_id)
+            .or(audience.class_room_id)
+            .or(audience.organization_unit_id),
+    )
+}
+
+pub fn validate_content(content: &RichContent) -> Result<(), AppError> {
+    content
+        .validate_shape()
+        .map_err(|message| AppError::ValidationError(message.to_string()))?;
+    if content.has_body() {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError("กรุณาระบุเนื้อหาประกาศ".to_string()))
+    }
+}
+
+pub fn validate_acknowledgement(
+    requires_acknowledgement: bool,
+    acknowledge_by: Option<NaiveDate>,
+) -> Result<(), AppError> {
+    if acknowledge_by.is_some() && !requires_acknowledgement {
+        return Err(AppError::ValidationError(
+            "กำหนดวันรับทราบได้เฉพาะประกาศที่ต้องกดรับทราบ".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Resolves the effective publish time and checks it against the expiry.
+pub fn resolve_publish_at(
+    requested: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, AppError> {
+    let publish_at = match requested {
+        Some(requested) if requested > now => requested,
+        Some(requested)
+            if now - requested > chrono::Duration::minutes(PUBLISH_AT_GRACE_MINUTES) =>
+        {
+            return Err(AppError::ValidationError(
+                "เวลาเผยแพร่ต้องไม่อยู่ในอดีต".to_string(),
+            ));
+        }
+        _ => now,
+    };
+    if expires_at.is_some_and(|expires_at| expires_at <= publish_at) {
+        return Err(AppError::ValidationError(
+            "เวลาหมดอายุต้องอยู่หลังเวลาเผยแพร่".to_string(),
+        ));
+    }
+    Ok(publish_at)
+}
+
+pub fn validate_expiry(
+    publish_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    match (publish_at, expires_at) {
+        (Some(publish_at), Some(expires_at)) if expires_at <= publish_at => Err(
+            AppError::ValidationError("เวลาหมดอายุต้องอยู่หลังเวลาเผยแพร่".to_string()),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// An announcement can go out when somebody can see it: an audience inside the
+/// school, the public website, or both. Acknowledgement needs an audience.
+pub fn validate_publishable(
+    audience_count: usize,
+    is_public: bool,
+    requires_acknowledgement: bool,
+) -> Result<(), AppError> {
+    if audience_count == 0 && requires_acknowledgement {
+        return Err(AppError::ValidationError(
+            "ประกาศที่ต้องกดรับทราบต้องกำหนดกลุ่มผู้รับ".to_string(),
+        ));
+    }
+    if audience_count == 0 && !is_public {
+        return Err(AppError::ValidationError(
+            "กรุณากำหนดกลุ่มผู้รับหรือเผยแพร่บนเว็บไซต์โรงเรียน".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn can_edit(status: AnnouncementStatus) -> bool {
+    status != AnnouncementStatus::Archived
+}
+
+pub fn is_live(
+    status: AnnouncementStatus,
+    publish_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    status == AnnouncementStatus::Published
+        && publish_at.is_some_and(|publish_at| publish_at <= now)
+        && expires_at.is_none_or(|expires_at| expires_at > now)
+}
+
+/// Daily reminders run from the day after publishing until the acknowledgement
+/// deadline, at most once a day and not before the morning.
+pub fn reminder_due(
+    today: NaiveDate,
+    local_hour: u32,
+    publish_date: NaiveDate,
+    acknowledge_by: Option<NaiveDate>,
+    last_reminded_on: Option<NaiveDate>,
+) -> bool {
+    let last_day =
+        acknowledge_by.unwrap_or(publish_date + chrono::Duration::days(DEFAULT_REMINDER_DAYS));
+    local_hour >= REMINDER_EARLIEST_HOUR
+        && today > publish_date
+        && today <= last_day
+        && last_reminded_on.is_none_or(|last_reminded_on| last_reminded_on < today)
+}
+
+pub fn receipt_matches_state(
+    state: AnnouncementReceiptState,
+    read: bool,
+    acknowledged: bool,
+) -> bool {
+    match state {
+        AnnouncementReceiptState::Unread => !read,
+        AnnouncementReceiptState::Read => read,
+        AnnouncementReceiptState::Acknowledged => acknowledged,
+        AnnouncementReceiptState::NotAcknowledged => !acknowledged,
+    }
+}
+
+pub fn audience_label(audience_type: AnnouncementAudienceType, name: Option<&str>) -> String {
+    let name = name.unwrap_or("-");
+    match audience_type {
+        AnnouncementAudienceType::AllStaff => "บุคลากรทั้งหมด".to_string(),
+        AnnouncementAudienceType::AllStudents => "นักเรียนทั้งหมด".to_string(),
+        AnnouncementAudienceType::AllGuardians => "ผู้ปกครองทั้งหมด".to_string(),
+        AnnouncementAudienceType::Role => format!("บทบาท {name}"),
+        AnnouncementAudienceType::GradeLevel => format!("นักเรียนชั้น {name}"),
+        AnnouncementAudienceType::Classroom => format!("นักเรียนห้อง {name}"),
+        AnnouncementAudienceType::OrganizationUnit => name.to_string(),
+        AnnouncementAudienceType::ClassroomGuardians => format!("ผู้ปกครองนักเรียนห้อง {name}"),
+    }
+}
+
+pub fn notification_text(
+    kind: AnnouncementNotificationKind,
+    title: &str,
+    summary: Option<&str>,
+    acknowledge_by: Option<NaiveDate>,
+) -> (String, String) {
+    match kind {
+        AnnouncementNotificationKind::Published => (
+            format!("ประกาศใหม่: {title}"),
+            summary
+                .map(str::to_string)
+                .unwrap_or_else(|| "มีประกาศใหม่จากโรงเรียน".to_string()),
+        ),
+        AnnouncementNotificationKind::AcknowledgementReminder => (
+            format!("กรุณารับทราบประกาศ: {title}"),
+            match acknowledge_by {
+                Some(date) => format!("กรุณาอ่านและกดรับทราบภายในวันที่ {}", date.format("%d/%m/%Y")),
+                None => "กรุณาอ่านและกดรับทราบประกาศนี้".to_string(),
+            },
+        ),
+    }
+}
+
+pub fn notification_link_for_user_type(user_type: &str) -> Option<&'static str> {
+    match user_type {
+        "staff" => Some("/staff/announcements"),
+        "student" => Some("/student/announcements"),
+        "parent" => Some("/parent/announcements"),
+        _ => None,
+    }
+}
+
+pub fn public_limit(requested: Option<i64>) -> i64 {
+    requested
+        .unwrap_or(DEFAULT_PUBLIC_LIMIT)
+        .clamp(1, MAX_PUBLIC_LIMIT)
+}
+
+/// Inline images in the content come first, then attachments in their
+/// submitted order. A file may appear only once.
+pub fn collect_file_ids(
+    content: &RichContent,
+    attachment_file_ids: &[Uuid],
+) -> Result<(Vec<Uuid>, Vec<Uuid>), AppError> {
+    let image_file_ids = dedupe_ids(content.image_file_ids().collect());
+    let attachment_file_ids = dedupe_ids(attachment_file_ids.to_vec());
+    if attachment_file_ids
+        .iter()
+        .any(|file_id| image_file_ids.contains(file_id))
+    {
+        return Err(AppError::ValidationError(
+            "ไฟล์เดียวกันใช้เป็นทั้งรูปในเนื้อหาและไฟล์แนบไม่ได้".to_string(),
+        ));
+    }
+    if image_file_ids.len() + attachment_file_ids.len() > MAX_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบรูปและไฟล์รวมกันได้ไม่เกิน {MAX_FILES} ไฟล์"
+        )));
+    }
+    Ok((image_file_ids, attachment_file_ids))
+}
+
+pub(super) fn dedupe_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut seen = HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+pub(super) fn required_title(value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::ValidationError("กรุณาระบุหัวข้อประกาศ".to_string()));
+    }
+    if value.chars().count() > MAX_TITLE_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "หัวข้อประกาศยาวได้ไม่เกิน {MAX_TITLE_CHARS} ตัวอักษร"
+        )));
+    }
+    Ok(value.to_string())
+}
+
+pub(super) fn normalize_summary(value: Option<String>) -> Result<Option<String>, AppError> {
+    let value = value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if value
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > MAX_SUMMARY_CHARS)
+    {
+        return Err(AppError::ValidationError(format!(
+            "เรื่องย่อยาวได้ไม่เกิน {MAX_SUMMARY_CHARS} ตัวอักษร"
+        )));
+    }
+    Ok(value)
+}
+
+pub(super) fn search_pattern(search: &str) -> String {
+    let mut pattern = String::with_capacity(search.len() + 2);
+    pattern.push('%');
+    for character in search.trim().chars() {
+        match character {
+            '\\' => pattern.push_str("\\\\"),
+            '%' => pattern.push_str("\\%"),
+            '_' => pattern.push_str("\\_"),
+            _ => pattern.push(character),
+        }
+    }
+    pattern.push('%');
+    pattern
+}
+
+pub(super) fn parse_status(value: &str) -> Result<AnnouncementStatus, AppError> {
+    AnnouncementStatus::from_code(value).ok_or_else(|| {
+        tracing::error!(status = value, "Unknown announcement status");
+        AppError::InternalServerError("สถานะประกาศไม่ถูกต้อง".to_string())
+    })
+}
+
+pub(super) fn parse_audience_type(value: &str) -> Result<AnnouncementAudienceType, AppError> {
+    AnnouncementAudienceType::from_code(value).ok_or_else(|| {
+        tracing::error!(audience_type = value, "Unknown announcement audience type");
+        AppError::InternalServerError("กลุ่มผู้รับประกาศไม่ถูกต้อง".to_string())
+    })
+}
@@ -0,0 +1,270 @@
+use super::*;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 6, 1, hour, 0, 0).unwrap()
+}
+
+fn audience(audience_type: AnnouncementAudienceType) -> AnnouncementAudienceInput {
+    AnnouncementAudienceInput {
+        audience_type,
+        role_id: None,
+        grade_level_id: None,
+        class_room_id: None,
+        organization_unit_id: None,
+    }
+}
+
+fn content_with_image(text: &str, image_file_id: Option<Uuid>) -> RichContent {
+    let mut blocks = vec![serde_json::json!({
+        "type": "paragraph",
+        "content": [{ "type": "text", "text": text }]
+    })];
+    if let Some(file_id) = image_file_id {
+        blocks.push(serde_json::json!({
+            "type": "image",
+            "attrs": {
+                "fileId": file_id,
+                "altText": null,
+                "caption": null,
+                "alignment": "center",
+                "widthPercent": 60
+            }
+        }));
+    }
+    serde_json::from_value(serde_json::json!({
+        "schemaVersion": 1,
+        "document": { "type": "doc", "content": blocks }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn audiences_need_exactly_the_reference_of_their_type() {
+    let classroom_id = Uuid::new_v4();
+    let classroom = AnnouncementAudienceInput {
+        class_room_id: Some(classroom_id),
+        ..audience(AnnouncementAudienceType::Classroom)
+    };
+    let guardians = AnnouncementAudienceInput {
+        class_room_id: Some(classroom_id),
+        ..audience(AnnouncementAudienceType::ClassroomGuardians)
+    };
+    let normalized = normalize_audiences(vec![
+        audience(AnnouncementAudienceType::AllStaff),
+        classroom.clone(),
+        guardians.clone(),
+        classroom.clone(),
+        audience(AnnouncementAudienceType::AllStaff),
+    ])
+    .unwrap();
+    assert_eq!(
+        normalized,
+        vec![
+            audience(AnnouncementAudienceType::AllStaff),
+            classroom,
+            guardians
+        ]
+    );
+
+    assert!(matches!(
+        normalize_audiences(vec![audience(AnnouncementAudienceType::Role)]),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        normalize_audiences(vec![AnnouncementAudienceInput {
+            role_id: Some(Uuid::new_v4()),
+            ..audience(AnnouncementAudienceType::AllStudents)
+        }]),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn announcement_needs_an_audience_or_the_website() {
+    assert!(validate_publishable(1, false, true).is_ok());
+    assert!(validate_publishable(0, true, false).is_ok());
+    assert!(matches!(
+        validate_publishable(0, false, false),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        validate_publishable(0, true, true),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        validate_acknowledgement(false, Some(date(2026, 6, 5))),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn publish_time_defaults_to_now_and_must_precede_expiry() {
+    let now = at(3);
+    assert_eq!(resolve_publish_at(None, None, now).unwrap(), now);
+    assert_eq!(
+        resolve_publish_at(Some(now - Duration::minutes(2)), None, now).unwrap(),
+        now
+    );
+    assert_eq!(
+        resolve_publish_at(Some(at(5)), Some(at(9)), now).unwrap(),
+        at(5)
+    );
+    assert!(matches!(
+        resolve_publish_at(Some(at(1)), None, now),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        resolve_publish_at(Some(at(5)), Some(at(5)), now),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(validate_expiry(None, Some(at(1))).is_ok());
+}
+
+#[test]
+fn only_published_announcements_inside_their_window_are_live() {
+    let now = at(6);
+    assert!(is_live(
+        AnnouncementStatus::Published,
+        Some(at(5)),
+        None,
+        now
+    ));
+    assert!(is_live(
+        AnnouncementStatus::Published,
+        Some(at(5)),
+        Some(at(7)),
+        now
+    ));
+    assert!(!is_live(
+        AnnouncementStatus::Published,
+        Some(at(7)),
+        None,
+        now
+    ));
+    assert!(!is_live(
+        AnnouncementStatus::Published,
+        Some(at(1)),
+        Some(at(6)),
+        now
+    ));
+    assert!(!is_live(AnnouncementStatus::Draft, Some(at(5)), None, now));
+    assert!(!is_live(
+        AnnouncementStatus::Archived,
+        Some(at(5)),
+        None,
+        now
+    ));
+    assert!(!can_edit(AnnouncementStatus::Archived));
+}
+
+#[test]
+fn reminders_run_daily_from_the_next_morning_until_the_deadline() {
+    let published = date(2026, 6, 1);
+    let deadline = Some(date(2026, 6, 3));
+    assert!(!reminder_due(published, 9, published, deadline, None));
+    assert!(!reminder_due(
+        date(2026, 6, 2),
+        6,
+        published,
+        deadline,
+        None
+    ));
+    assert!(reminder_due(date(2026, 6, 2), 7, published, deadline, None));
+    assert!(!reminder_due(
+        date(2026, 6, 2),
+        10,
+        published,
+        deadline,
+        Some(date(2026, 6, 2))
+    ));
+    assert!(reminder_due(
+        date(2026, 6, 3),
+        8,
+        published,
+        deadline,
+        Some(date(2026, 6, 2))
+    ));
+    assert!(!reminder_due(
+        date(2026, 6, 4),
+        8,
+        published,
+        deadline,
+        None
+    ));
+
+    assert!(reminder_due(date(2026, 6, 8), 8, published, None, None));
+    assert!(!reminder_due(date(2026, 6, 9), 8, published, None, None));
+}
+
+#[test]
+fn receipt_state_filter_matches_reads_and_acknowledgements() {
+    assert!(receipt_matches_state(
+        AnnouncementReceiptState::Unread,
+        false,
+        false
+    ));
+    assert!(receipt_matches_state(
+        AnnouncementReceiptState::Read,
+        true,
+        false
+    ));
+    assert!(receipt_matches_state(
+        AnnouncementReceiptState::NotAcknowledged,
+        true,
+        false
+    ));
+    assert!(!receipt_matches_state(
+        AnnouncementReceiptState::NotAcknowledged,
+        true,
+        true
+    ));
+    assert!(receipt_matches_state(
+        AnnouncementReceiptState::Acknowledged,
+        true,
+        true
+    ));
+}
+
+#[test]
+fn content_images_and_attachments_are_collected_separately() {
+    let image_id = Uuid::new_v4();
+    let attachment_id = Uuid::new_v4();
+    let content = content_with_image("กำหนดการสอบ", Some(image_id));
+    assert!(validate_content(&content).is_ok());
+    assert_eq!(
+        collect_file_ids(&content, &[attachment_id, attachment_id]).unwrap(),
+        (vec![image_id], vec![attachment_id])
+    );
+    assert!(matches!(
+        collect_file_ids(&content, &[image_id]),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn notifications_link_each_user_type_to_their_feed() {
+    assert_eq!(
+        notification_link_for_user_type("parent"),
+        Some("/parent/announcements")
+    );
+    assert_eq!(notification_link_for_user_type("unknown"), None);
+
+    let (title, message) = notification_text(
+        AnnouncementNotificationKind::AcknowledgementReminder,
+        "ประชุมผู้ปกครอง",
+        None,
+        Some(date(2026, 6, 5)),
+    );
+    assert_eq!(title, "กรุณารับทราบประกาศ: ประชุมผู้ปกครอง");
+    assert!(message.contains("05/06/2026"));
+    assert_eq!(
+        audience_label(AnnouncementAudienceType::ClassroomGuardians, Some("ม.1/2")),
+        "ผู้ปกครองนักเรียนห้อง ม.1/2"
+    );
+    assert_eq!(public_limit(Some(500)), 50);
+    assert_eq!(public_limit(None), 20);
+}
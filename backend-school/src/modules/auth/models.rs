@@ -164,6 +164,21 @@ pub struct CurrentUserResponse {
     #[schema(required = true)]
     pub profile_image_file_id: Option<Uuid>,
     pub permissions: Vec<String>,
+    /// Present only while platform support is signed in as this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_session: Option<SupportSessionBanner>,
+}
+
+/// Banner shown to everyone looking at a support session.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportSessionBanner {
+    pub support_access_id: Uuid,
+    pub ticket_reference: String,
+    pub admin_name: String,
+    /// `read_only` or `scoped`.
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
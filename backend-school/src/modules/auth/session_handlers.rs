@@ -12,6 +12,7 @@ use uuid::Uuid;
 use crate::{
     api_response::{ApiResponse, EmptyData},
     error::AppError,
+    modules::support_access::models::{SupportHandoffRequest, SupportSessionScope},
     utils::{
         client_address::client_address,
         tenant::{resolve_auth_tenant_context, TenantContext},
@@ -27,7 +28,7 @@ use super::{
     },
     models::{
         ChangePasswordRequest, CurrentUserResponse, LoginData, LoginRequest, SessionListData,
-        SessionResponse,
+        SessionResponse, SupportSessionBanner,
     },
     runtime::AuthRuntime,
     session_crypto::RawSessionToken,
@@ -104,6 +105,61 @@ pub(super) async fn login_with_tenant(
     Ok(response)
 }
 
+/// POST /api/auth/support-handoff - exchange a support handoff token from
+/// backend-admin for a time-limited session of the assisted user
+#[utoipa::path(
+    post,
+    path = "/api/auth/support-handoff",
+    operation_id = "supportHandoff",
+    tag = "auth",
+    request_body = SupportHandoffRequest,
+    responses(
+        (status = 200, description = "Support session for the assisted user", body = ApiResponse<LoginData>),
+        (status = 400, description = "Malformed request", body = crate::api_response::ApiErrorResponse),
+        (status = 401, description = "Handoff token invalid, used or expired", body = crate::api_response::ApiErrorResponse),
+        (status = 403, description = "Origin rejected", body = crate::api_response::ApiErrorResponse),
+        (status = 503, description = "Authentication service unavailable", body = crate::api_response::ApiErrorResponse)
+    )
+)]
+pub async fn support_handoff(
+    State(runtime): State<AuthRuntime>,
+    headers: HeaderMap,
+    payload_result: Result<Json<SupportHandoffRequest>, JsonRejection>,
+) -> Result<Response, AppError> {
+    let tenant = resolve_auth_tenant_context(&runtime, &headers, None)
+        .await
+        .map_err(audit_origin_rejection)?;
+    let payload = parse_json_payload(payload_result)?;
+    let handoff = RawSessionToken::parse(payload.token.trim())?;
+    let context = runtime.service_context(tenant);
+    let result = session_service::start_support_session(
+        &context,
+        handoff.token_hash(),
+        Utc::now(),
+        RawSessionToken::generate,
+    )
+    .await?;
+
+    let mut user = current_user_response(result.user);
+    user.support_session = result
+        .authenticated
+        .support
+        .as_ref()
+        .map(support_session_banner);
+    let encoded = result.credential.encoded();
+    let mut response = (StatusCode::OK, Json(ApiResponse::ok(LoginData { user }))).into_response();
+    append_response_cookie(
+        &mut response,
+        set_session_cookie(
+            encoded.expose_for_cookie(),
+            result.credential.cookie_max_age_seconds,
+        ),
+    );
+    append_response_cookie(&mut response, expire_legacy_cookie());
+    insert_csrf_header(&mut response, &result.csrf_token);
+    Ok(response)
+}
+
 #[utoipa::path(
     post,
     path = "/api/auth/logout",
@@ -176,11 +232,9 @@ pub async fn me(
 ) -> Result<Response, AppError> {
     let context = runtime.service_context(session.tenant.clone());
     let user = session_service::load_current_user(&context, &session).await?;
-    Ok((
-        StatusCode::OK,
-        Json(ApiResponse::ok(current_user_response(user))),
-    )
-        .into_response())
+    let mut body = current_user_response(user);
+    body.support_session = session.support.as_ref().map(support_session_banner);
+    Ok((StatusCode::OK, Json(ApiResponse::ok(body))).into_response())
 }
 
 #[utoipa::path(
@@ -331,6 +385,17 @@ fn current_user_response(user: LoginUserSnapshot) -> CurrentUserResponse {
         primary_role_name: user.primary_role_name,
         profile_image_file_id: user.profile_image_file_id,
         permissions: user.permissions,
+        support_session: None,
+    }
+}
+
+fn support_session_banner(support: &SupportSessionScope) -> SupportSessionBanner {
+    SupportSessionBanner {
+        support_access_id: support.support_access_id,
+        ticket_reference: support.ticket_reference.clone(),
+        admin_name: support.admin_name.clone(),
+        scope: support.scope.as_str().to_string(),
+        expires_at: support.expires_at,
     }
 }
 
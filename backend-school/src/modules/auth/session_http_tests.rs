@@ -15,7 +15,7 @@ use uuid::Uuid;
 
 use crate::{
     db::{
-        admin_client::{AdminClient, AdminClientConfig},
+        admin_client::{AdminClient, AdminClientConfig, TenantPlan},
         permission_cache::PermissionCache,
         pool_manager::PoolManager,
     },
@@ -58,6 +58,7 @@ impl HandlerFixture {
             tenant_id: Uuid::new_v4(),
             subdomain: format!("{test_name}-school"),
             pool: pool.clone(),
+            plan: TenantPlan::default(),
         };
         let (session_events, _) = broadcast::channel(32);
         let runtime = AuthRuntime {
@@ -136,6 +137,7 @@ impl HandlerFixture {
             user_id,
             username: username.to_string(),
             user_type: "staff".to_string(),
+            support: None,
         }
     }
 }
@@ -265,6 +267,7 @@ fn current_user_json_has_no_default_pii() {
         primary_role_name: Some("Teacher".to_string()),
         profile_image_file_id: None,
         permissions: vec!["academic.read".to_string()],
+        support_session: None,
     })
     .expect("current user response must serialize");
 
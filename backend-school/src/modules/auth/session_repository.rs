@@ -4,6 +4,9 @@ use subtle::ConstantTimeEq;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::modules::support_access::models::{
+    SupportAccessScope, SupportAccessStatus, SupportSessionScope,
+};
 
 use super::{
     session_crypto::{RawSessionToken, ThrottleBucketHash, TokenHash},
@@ -39,6 +42,7 @@ pub enum SessionRevocationReason {
     UserSelected,
     LogoutAll,
     PasswordChanged,
+    SupportEnded,
 }
 
 impl SessionRevocationReason {
@@ -48,6 +52,7 @@ impl SessionRevocationReason {
             Self::UserSelected => "user_selected",
             Self::LogoutAll => "logout_all",
             Self::PasswordChanged => "password_changed",
+            Self::SupportEnded => "support_ended",
         }
     }
 }
@@ -85,6 +90,7 @@ pub struct MaintainedSession {
     pub remember_me: bool,
     pub rotated_at: DateTime<Utc>,
     pub absolute_expires_at: DateTime<Utc>,
+    pub support: Option<SupportSessionScope>,
     pub replacement: Option<RawSessionToken>,
 }
 
@@ -121,15 +127,27 @@ struct AuthenticationRow {
     username: String,
     user_type: String,
     user_status: String,
+    support_access_id: Option<Uuid>,
+    support_ticket_reference: Option<String>,
+    support_admin_name: Option<String>,
+    support_scope: Option<String>,
+    support_permissions: Option<Vec<String>>,
+    support_status: Option<String>,
+    support_expires_at: Option<DateTime<Utc>>,
 }
 
 const AUTHENTICATION_COLUMNS: &str = r#"
     SELECT s.id, s.user_id, s.current_token_hash, s.previous_token_hash,
            s.previous_token_valid_until, s.remember_me, s.rotated_at, s.last_seen_at,
            s.idle_expires_at, s.absolute_expires_at, s.revoked_at,
-           u.username, u.user_type, u.status AS user_status
+           u.username, u.user_type, u.status AS user_status,
+           s.support_access_id, g.ticket_reference AS support_ticket_reference,
+           g.platform_admin_name AS support_admin_name, g.scope AS support_scope,
+           g.permissions AS support_permissions, g.status AS support_status,
+           g.expires_at AS support_expires_at
     FROM auth_sessions s
     JOIN users u ON u.id = s.user_id
+    LEFT JOIN support_access_grants g ON g.id = s.support_access_id
     WHERE s.current_token_hash = $1 OR s.previous_token_hash = $1
     LIMIT 2
 "#;
@@ -177,6 +195,68 @@ pub async fn create_login_session(
     transaction.commit().await.map_err(session_store_error)
 }
 
+/// Insert a support session inside the transaction that claimed its grant.
+pub async fn create_support_session(
+    transaction: &mut Transaction<'_, Postgres>,
+    session: &NewSession,
+    support_access_id: Uuid,
+) -> Result<(), AppError> {
+    lock_token_hash(transaction, session.current_token_hash).await?;
+    ensure_token_hash_available(transaction, session.current_token_hash).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO auth_sessions (
+            id, user_id, current_token_hash, remember_me, device_label,
+            created_at, last_seen_at, idle_expires_at, absolute_expires_at, rotated_at,
+            support_access_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        "#,
+    )
+    .bind(session.id)
+    .bind(session.user_id)
+    .bind(session.current_token_hash.as_bytes().as_slice())
+    .bind(session.remember_me)
+    .bind(&session.device_label)
+    .bind(session.times.created_at)
+    .bind(session.times.last_seen_at)
+    .bind(session.times.idle_expires_at)
+    .bind(session.times.absolute_expires_at)
+    .bind(session.times.rotated_at)
+    .bind(support_access_id)
+    .execute(&mut **transaction)
+    .await
+    .map_err(session_store_error)?;
+    Ok(())
+}
+
+/// Revoke every live session opened through a support grant. Returns the
+/// `(session_id, user_id)` pairs so the caller can notify open streams.
+pub async fn revoke_support_sessions(
+    pool: &PgPool,
+    support_access_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Vec<(Uuid, Uuid)>, AppError> {
+    sqlx::query_as(
+        r#"
+        UPDATE auth_sessions
+        SET revoked_at = $1, revocation_reason = $2
+        WHERE support_access_id = $3
+          AND revoked_at IS NULL
+          AND idle_expires_at > $1
+          AND absolute_expires_at > $1
+        RETURNING id, user_id
+        "#,
+    )
+    .bind(now)
+    .bind(SessionRevocationReason::SupportEnded.as_str())
+    .bind(support_access_id)
+    .fetch_all(pool)
+    .await
+    .map_err(session_store_error)
+}
+
 pub async fn authenticate_and_maintain<F>(
     pool: &PgPool,
     presented_hash: TokenHash,
@@ -709,6 +789,25 @@ fn base_session_is_valid(row: &AuthenticationRow, now: DateTime<Utc>) -> bool {
         && row.user_status == "active"
         && row.idle_expires_at > now
         && row.absolute_expires_at > now
+        && (row.support_access_id.is_none()
+            || support_scope(row).is_some_and(|support| support.expires_at > now))
+}
+
+/// Support sessions fail closed: a flagged session whose grant is not active
+/// or cannot be read back is not a session at all.
+fn support_scope(row: &AuthenticationRow) -> Option<SupportSessionScope> {
+    let support_access_id = row.support_access_id?;
+    if row.support_status.as_deref() != Some(SupportAccessStatus::Active.as_str()) {
+        return None;
+    }
+    Some(SupportSessionScope {
+        support_access_id,
+        ticket_reference: row.support_ticket_reference.clone()?,
+        admin_name: row.support_admin_name.clone()?,
+        scope: SupportAccessScope::from_code(row.support_scope.as_deref()?)?,
+        permissions: row.support_permissions.clone()?,
+        expires_at: row.support_expires_at?,
+    })
 }
 
 fn previous_token_is_valid(row: &AuthenticationRow, now: DateTime<Utc>) -> bool {
@@ -730,6 +829,7 @@ fn maintained(
     presented_as: PresentedTokenKind,
     replacement: Option<RawSessionToken>,
 ) -> MaintainedSession {
+    let support = support_scope(&row);
     MaintainedSession {
         session_id: row.id,
         user_id: row.user_id,
@@ -739,6 +839,7 @@ fn maintained(
         remember_me: row.remember_me,
         rotated_at: row.rotated_at,
         absolute_expires_at: row.absolute_expires_at,
+        support,
         replacement,
     }
 }
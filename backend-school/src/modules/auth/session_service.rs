@@ -7,8 +7,14 @@ use uuid::Uuid;
 use zeroize::Zeroizing;
 
 use crate::{
-    db::permission_cache::PermissionCache, error::AppError,
-    middleware::permission::get_cached_user_permissions, utils::tenant::TenantContext,
+    db::permission_cache::PermissionCache,
+    error::AppError,
+    middleware::permission::get_cached_user_permissions,
+    modules::support_access::{
+        models::SupportSessionScope,
+        services::{self as support_access, ClaimedSupportGrant},
+    },
+    utils::tenant::TenantContext,
 };
 
 use super::{
@@ -28,8 +34,9 @@ use super::{
         validate_login_input, validate_new_password, SessionLifetime, SessionTimes,
     },
     session_repository::{
-        apply_password_change, authenticate_and_maintain, cleanup_auth_state, list_user_sessions,
-        load_password_change_snapshot, lock_password_change, revalidate_session, revoke_sessions,
+        apply_password_change, authenticate_and_maintain, cleanup_auth_state,
+        create_support_session, list_user_sessions, load_password_change_snapshot,
+        lock_password_change, revalidate_session, revoke_sessions, revoke_support_sessions,
         NewSession, SessionMaintenanceMode, SessionRevocationReason, SessionRevocationTarget,
         SessionRow,
     },
@@ -81,6 +88,18 @@ pub struct AuthenticatedSession {
     pub user_id: Uuid,
     pub username: String,
     pub user_type: String,
+    /// Set when platform support opened this session through a support grant.
+    pub support: Option<SupportSessionScope>,
+}
+
+impl AuthenticatedSession {
+    /// The user's permissions as this session may use them.
+    pub fn effective_permissions(&self, permissions: Vec<String>) -> Vec<String> {
+        match &self.support {
+            Some(support) => support_access::narrow_permissions(support, permissions),
+            None => permissions,
+        }
+    }
 }
 
 impl fmt::Debug for AuthenticatedSession {
@@ -92,6 +111,13 @@ impl fmt::Debug for AuthenticatedSession {
             .field("user_id", &self.user_id)
             .field("username", &"[REDACTED]")
             .field("user_type", &self.user_type)
+            .field(
+                "support_access_id",
+                &self
+                    .support
+                    .as_ref()
+                    .map(|support| support.support_access_id),
+            )
             .finish()
     }
 }
@@ -371,6 +397,7 @@ where
         user_id: maintained.user_id,
         username: maintained.username,
         user_type: maintained.user_type,
+        support: maintained.support,
     };
     let csrf_token = session_csrf_token(
         context.config.hmac_key(),
@@ -402,7 +429,128 @@ pub async fn load_current_user(
         .await
         .map_err(|_| session_store_unavailable())?
         .ok_or_else(authentication_required)?;
-    load_active_shell_snapshot(context, user).await
+    let mut snapshot = load_active_shell_snapshot(context, user).await?;
+    snapshot.permissions = session.effective_permissions(snapshot.permissions);
+    Ok(snapshot)
+}
+
+/// Exchange a support handoff token for a session of the assisted user.
+/// Claiming the grant and creating the session happen in one transaction, so
+/// a token can never yield two sessions.
+pub async fn start_support_session<G>(
+    context: &SessionServiceContext,
+    handoff_hash: TokenHash,
+    now: DateTime<Utc>,
+    generate: G,
+) -> Result<LoginResult, AppError>
+where
+    G: FnOnce() -> Result<RawSessionToken, AppError>,
+{
+    let raw = generate()?;
+    let mut transaction = context
+        .tenant
+        .pool
+        .begin()
+        .await
+        .map_err(|_| session_store_unavailable())?;
+    let Some(ClaimedSupportGrant {
+        target_user_id,
+        scope,
+    }) = support_access::claim_handoff(&mut transaction, handoff_hash, now).await?
+    else {
+        transaction
+            .rollback()
+            .await
+            .map_err(|_| session_store_unavailable())?;
+        return Err(AppError::AuthError(
+            "ลิงก์เข้าช่วยเหลือหมดอายุหรือถูกใช้ไปแล้ว".to_string(),
+        ));
+    };
+    let user = find_active_user_shell_by_id(&context.tenant.pool, target_user_id)
+        .await
+        .map_err(|_| session_store_unavailable())?
+        .ok_or_else(authentication_required)?;
+
+    let session_id = Uuid::new_v4();
+    let lifetime = SessionLifetime::normal();
+    let new_session = NewSession {
+        id: session_id,
+        user_id: user.id,
+        current_token_hash: raw.token_hash(),
+        remember_me: false,
+        device_label: format!("ฝ่ายสนับสนุน · {}", scope.ticket_reference),
+        times: SessionTimes {
+            created_at: now,
+            last_seen_at: now,
+            idle_expires_at: (now + lifetime.idle).min(scope.expires_at),
+            absolute_expires_at: scope.expires_at,
+            rotated_at: now,
+        },
+    };
+    create_support_session(&mut transaction, &new_session, scope.support_access_id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(|_| session_store_unavailable())?;
+
+    if let Err(error) =
+        support_access::record_session_started(&context.tenant.pool, user.id, &scope).await
+    {
+        tracing::error!(
+            support_access_id = %scope.support_access_id,
+            error = %error,
+            "Failed to audit support session start"
+        );
+    }
+    audit::session_created(context.tenant.tenant_id, user.id, session_id);
+
+    let authenticated = AuthenticatedSession {
+        tenant: context.tenant.clone(),
+        session_id,
+        user_id: user.id,
+        username: user.username.clone(),
+        user_type: user.user_type.clone(),
+        support: Some(scope.clone()),
+    };
+    let mut snapshot = load_active_shell_snapshot(context, user).await?;
+    snapshot.permissions = support_access::narrow_permissions(&scope, snapshot.permissions);
+    let csrf_token = session_csrf_token(
+        context.config.hmac_key(),
+        context.tenant.tenant_id,
+        session_id,
+    );
+
+    Ok(LoginResult {
+        user: snapshot,
+        authenticated,
+        credential: credential(raw, false, scope.expires_at, now),
+        csrf_token,
+    })
+}
+
+/// Revoke the sessions of an ended support grant and close their open streams.
+pub async fn end_support_sessions(
+    pool: &sqlx::PgPool,
+    session_events: &broadcast::Sender<SessionRevocationEvent>,
+    tenant_id: Uuid,
+    subdomain: &str,
+    support_access_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<usize, AppError> {
+    let revoked = revoke_support_sessions(pool, support_access_id, now).await?;
+    for (session_id, user_id) in &revoked {
+        publish(
+            session_events,
+            SessionRevocationEvent::session(subdomain, *user_id, *session_id),
+        );
+        audit::session_revoked(
+            tenant_id,
+            *user_id,
+            *session_id,
+            SessionRevocationReason::SupportEnded,
+        );
+    }
+    Ok(revoked.len())
 }
 
 pub async fn list_sessions(
@@ -697,6 +845,7 @@ fn authenticated(
         user_id: user.id,
         username: user.username.clone(),
         user_type: user.user_type.clone(),
+        support: None,
     }
 }
 
@@ -16,7 +16,7 @@ use tracing_subscriber::fmt::MakeWriter;
 use uuid::Uuid;
 
 use crate::{
-    db::permission_cache::PermissionCache,
+    db::{admin_client::TenantPlan, permission_cache::PermissionCache},
     error::AppError,
     test_helpers::{create_named_test_pool, run_test_migrations},
     utils::tenant::TenantContext,
@@ -53,6 +53,7 @@ impl AuthServiceFixture {
             tenant_id: Uuid::new_v4(),
             subdomain: format!("{test_name}-school"),
             pool: pool.clone(),
+            plan: TenantPlan::default(),
         };
         let (events, _) = broadcast::channel(32);
         let context =
@@ -0,0 +1,10 @@
+pub mod handlers;
+pub mod models;
+pub mod services;
+
+use crate::AppState;
+use axum::Router;
+
+pub fn behavior_routes() -> Router<AppState> {
+    handlers::routes()
+}
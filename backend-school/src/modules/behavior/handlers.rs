@@ -0,0 +1,311 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, patch, post, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::behavior::models::{
+    BehaviorIncidentFilter, BehaviorInterventionFilter, BehaviorSemesterQuery,
+    CreateBehaviorIncidentRequest, CreateBehaviorInterventionRequest,
+    UpdateBehaviorInterventionRequest, UpsertBehaviorCategoryRequest,
+    UpsertBehaviorThresholdRequest, VoidBehaviorIncidentRequest,
+};
+use crate::modules::behavior::services;
+use crate::permissions::registry::codes;
+use crate::policies::behavior_access_policy;
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::AppState;
+
+const BEHAVIOR_ANY_ACCESS: [&str; 6] = [
+    codes::BEHAVIOR_READ_OWN,
+    codes::BEHAVIOR_READ_ASSIGNED,
+    codes::BEHAVIOR_READ_SCHOOL,
+    codes::BEHAVIOR_CREATE_SCHOOL,
+    codes::BEHAVIOR_MANAGE_ASSIGNED,
+    codes::BEHAVIOR_MANAGE_SCHOOL,
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorSettingsQuery {
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+async fn list_categories(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<BehaviorSettingsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context.actor.require_any_permission(&BEHAVIOR_ANY_ACCESS)?;
+    let include_inactive =
+        query.include_inactive && context.actor.has_permission(codes::BEHAVIOR_MANAGE_SCHOOL);
+    let items = services::list_categories(&context.tenant.pool, include_inactive).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_category(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<UpsertBehaviorCategoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    behavior_access_policy::require_behavior_manage_school(&context.actor)?;
+    let category =
+        services::create_category(&context.tenant.pool, payload, context.actor.user_id).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(category))))
+}
+
+async fn update_category(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpsertBehaviorCategoryRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    behavior_access_policy::require_behavior_manage_school(&context.actor)?;
+    let category = services::update_category(&context.tenant.pool, id, payload).await?;
+    Ok(Json(ApiResponse::ok(category)))
+}
+
+async fn list_thresholds(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<BehaviorSettingsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context.actor.require_any_permission(&BEHAVIOR_ANY_ACCESS)?;
+    let include_inactive =
+        query.include_inactive && context.actor.has_permission(codes::BEHAVIOR_MANAGE_SCHOOL);
+    let items = services::list_thresholds(&context.tenant.pool, include_inactive).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_threshold(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<UpsertBehaviorThresholdRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    behavior_access_policy::require_behavior_manage_school(&context.actor)?;
+    let threshold =
+        services::create_threshold(&context.tenant.pool, payload, context.actor.user_id).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(threshold))))
+}
+
+async fn update_threshold(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpsertBehaviorThresholdRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    behavior_access_policy::require_behavior_manage_school(&context.actor)?;
+    let threshold = services::update_threshold(&context.tenant.pool, id, payload).await?;
+    Ok(Json(ApiResponse::ok(threshold)))
+}
+
+async fn list_incidents(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<BehaviorIncidentFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_incidents(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn get_incident(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let incident = services::get_incident(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(incident)))
+}
+
+async fn create_incident(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<CreateBehaviorIncidentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let pool = &context.tenant.pool;
+    let outcome = services::create_incident(pool, &context.actor, payload).await?;
+    let incident = outcome.incident;
+
+    match services::record_threshold_crossings(
+        pool,
+        incident.student_id,
+        incident.academic_semester_id,
+        incident.id,
+        context.actor.user_id,
+    )
+    .await
+    {
+        Ok(created) if created > 0 => state.notify_work_items_changed(&context.tenant.subdomain),
+        Ok(_) => {}
+        Err(error) => tracing::error!(
+            incident_id = %incident.id,
+            error = %error,
+            "Failed to record behavior threshold follow-up"
+        ),
+    }
+
+    if outcome.notify_guardians {
+        if let Err(error) = services::notify_guardians(
+            pool,
+            &state.notification_channel,
+            &context.tenant.subdomain,
+            &incident,
+        )
+        .await
+        {
+            tracing::error!(
+                incident_id = %incident.id,
+                error = %error,
+                "Failed to notify guardians about behavior incident"
+            );
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(incident))))
+}
+
+async fn void_incident(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<VoidBehaviorIncidentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let incident =
+        services::void_incident(&context.tenant.pool, &context.actor, id, &payload.reason).await?;
+    Ok(Json(ApiResponse::ok(incident)))
+}
+
+async fn student_summary(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(student_id): Path<Uuid>,
+    Query(query): Query<BehaviorSemesterQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let summary = services::student_point_summary(
+        &context.tenant.pool,
+        &context.actor,
+        student_id,
+        query.academic_semester_id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(summary)))
+}
+
+async fn list_interventions(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<BehaviorInterventionFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_interventions(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_intervention(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<CreateBehaviorInterventionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let intervention =
+        services::create_intervention(&context.tenant.pool, &context.actor, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(intervention))))
+}
+
+async fn update_intervention(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateBehaviorInterventionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let intervention =
+        services::update_intervention(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok(Json(ApiResponse::ok(intervention)))
+}
+
+/// GET /api/behavior/guardian/students/:student_id/incidents - ผู้ปกครองดูพฤติกรรมบุตรหลาน
+async fn list_guardian_incidents(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(student_id): Path<Uuid>,
+    Query(query): Query<BehaviorSemesterQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_guardian_incidents(
+        &context.tenant.pool,
+        context.actor.user_id,
+        student_id,
+        query.academic_semester_id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/behavior/guardian/students/:student_id/summary - ผู้ปกครองดูคะแนนพฤติกรรมบุตรหลาน
+async fn guardian_summary(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(student_id): Path<Uuid>,
+    Query(query): Query<BehaviorSemesterQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let summary = services::get_guardian_point_summary(
+        &context.tenant.pool,
+        context.actor.user_id,
+        student_id,
+        query.academic_semester_id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(summary)))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/categories", get(list_categories).post(create_category))
+        .route("/categories/{id}", put(update_category))
+        .route("/thresholds", get(list_thresholds).post(create_threshold))
+        .route("/thresholds/{id}", put(update_threshold))
+        .route("/incidents", get(list_incidents).post(create_incident))
+        .route("/incidents/{id}", get(get_incident))
+        .route("/incidents/{id}/void", post(void_incident))
+        .route("/students/{student_id}/summary", get(student_summary))
+        .route(
+            "/interventions",
+            get(list_interventions).post(create_intervention),
+        )
+        .route("/interventions/{id}", patch(update_intervention))
+        .route(
+            "/guardian/students/{student_id}/incidents",
+            get(list_guardian_incidents),
+        )
+        .route(
+            "/guardian/students/{student_id}/summary",
+            get(guardian_summary),
+        )
+}
@@ -0,0 +1,301 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorCategoryKind {
+    Incident,
+    Merit,
+}
+
+impl BehaviorCategoryKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Incident => "incident",
+            Self::Merit => "merit",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "incident" => Some(Self::Incident),
+            "merit" => Some(Self::Merit),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorIncidentStatus {
+    Recorded,
+    Voided,
+}
+
+impl BehaviorIncidentStatus {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "recorded" => Some(Self::Recorded),
+            "voided" => Some(Self::Voided),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorInterventionActionType {
+    Counseling,
+    GuardianMeeting,
+    HomeVisit,
+    Referral,
+    Contract,
+    Other,
+}
+
+impl BehaviorInterventionActionType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Counseling => "counseling",
+            Self::GuardianMeeting => "guardian_meeting",
+            Self::HomeVisit => "home_visit",
+            Self::Referral => "referral",
+            Self::Contract => "contract",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "counseling" => Some(Self::Counseling),
+            "guardian_meeting" => Some(Self::GuardianMeeting),
+            "home_visit" => Some(Self::HomeVisit),
+            "referral" => Some(Self::Referral),
+            "contract" => Some(Self::Contract),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorInterventionStatus {
+    Planned,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl BehaviorInterventionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Planned => "planned",
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "planned" => Some(Self::Planned),
+            "in_progress" => Some(Self::InProgress),
+            "completed" => Some(Self::Completed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorCategory {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub kind: BehaviorCategoryKind,
+    pub points: i32,
+    pub description: Option<String>,
+    pub order_index: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertBehaviorCategoryRequest {
+    pub code: String,
+    pub name: String,
+    pub kind: BehaviorCategoryKind,
+    pub points: i32,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub order_index: i32,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorThreshold {
+    pub id: Uuid,
+    pub name: String,
+    pub points_at_or_below: i32,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertBehaviorThresholdRequest {
+    pub name: String,
+    pub points_at_or_below: i32,
+    pub description: Option<String>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorIncident {
+    pub id: Uuid,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub category_id: Uuid,
+    pub category_code: String,
+    pub category_name: String,
+    pub kind: BehaviorCategoryKind,
+    pub academic_semester_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub room_id: Option<Uuid>,
+    pub room_name: Option<String>,
+    pub location_note: Option<String>,
+    pub description: Option<String>,
+    pub points: i32,
+    pub is_confidential: bool,
+    pub status: BehaviorIncidentStatus,
+    pub reported_by: Option<Uuid>,
+    pub reported_by_name: Option<String>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+    pub guardian_notified_at: Option<DateTime<Utc>>,
+    pub evidence_file_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBehaviorIncidentRequest {
+    pub student_id: Uuid,
+    pub category_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+    pub room_id: Option<Uuid>,
+    pub location_note: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_confidential: bool,
+    #[serde(default)]
+    pub evidence_file_ids: Vec<Uuid>,
+    #[serde(default = "default_true")]
+    pub notify_guardians: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoidBehaviorIncidentRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorIncidentFilter {
+    pub student_id: Option<Uuid>,
+    pub academic_semester_id: Option<Uuid>,
+    pub class_room_id: Option<Uuid>,
+    pub kind: Option<BehaviorCategoryKind>,
+    #[serde(default)]
+    pub include_voided: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorSemesterQuery {
+    pub academic_semester_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorPointSummary {
+    pub student_id: Uuid,
+    pub academic_semester_id: Uuid,
+    pub merit_points: i64,
+    pub incident_points: i64,
+    pub total_points: i64,
+    pub merit_count: i64,
+    pub incident_count: i64,
+    pub reached_thresholds: Vec<BehaviorThreshold>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorIntervention {
+    pub id: Uuid,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub academic_semester_id: Uuid,
+    pub incident_id: Option<Uuid>,
+    pub action_type: BehaviorInterventionActionType,
+    pub title: String,
+    pub note: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub status: BehaviorInterventionStatus,
+    pub outcome: Option<String>,
+    pub assigned_to: Option<Uuid>,
+    pub assigned_to_name: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBehaviorInterventionRequest {
+    pub student_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub incident_id: Option<Uuid>,
+    pub action_type: BehaviorInterventionActionType,
+    pub title: String,
+    pub note: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub assigned_to: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBehaviorInterventionRequest {
+    pub status: Option<BehaviorInterventionStatus>,
+    pub note: Option<String>,
+    pub outcome: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub assigned_to: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BehaviorInterventionFilter {
+    pub student_id: Option<Uuid>,
+    pub academic_semester_id: Option<Uuid>,
+    pub status: Option<BehaviorInterventionStatus>,
+}
+
+fn default_true() -> bool {
+    true
+}
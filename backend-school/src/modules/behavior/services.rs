@@ -0,0 +1,42 @@
+mod categories;
+mod followups;
+mod incidents;
+mod interventions;
+mod notifications;
+mod shared;
+
+#[cfg(test)]
+mod tests;
+
+pub use categories::{
+    create_category, create_threshold, list_categories, list_thresholds, update_category,
+    update_threshold,
+};
+pub use followups::record_threshold_crossings;
+pub use incidents::{
+    create_incident, get_guardian_point_summary, get_incident, list_guardian_incidents,
+    list_incidents, student_point_summary, void_incident,
+};
+pub use interventions::{create_intervention, list_interventions, update_intervention};
+pub use notifications::notify_guardians;
+#[allow(unused_imports)]
+pub use shared::{
+    can_transition_intervention_status, guardian_notification_text, reached_thresholds,
+    validate_category_points, validate_threshold_points,
+};
+
+#[cfg(test)]
+use chrono::Utc;
+#[cfg(test)]
+use incidents::guardian_view;
+#[cfg(test)]
+use shared::{normalize_optional_text, required_text};
+#[cfg(test)]
+use uuid::Uuid;
+
+#[cfg(test)]
+use crate::error::AppError;
+#[cfg(test)]
+use crate::modules::behavior::models::{
+    BehaviorCategoryKind, BehaviorIncident, BehaviorIncidentStatus, BehaviorInterventionStatus,
+};
@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::behavior::models::{
+    BehaviorCategory, BehaviorThreshold, UpsertBehaviorCategoryRequest,
+    UpsertBehaviorThresholdRequest,
+};
+
+use super::shared::{
+    normalize_optional_text, parse_category_kind, required_text, validate_category_points,
+    validate_threshold_points,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BehaviorCategoryRow {
+    id: Uuid,
+    code: String,
+    name: String,
+    kind: String,
+    points: i32,
+    description: Option<String>,
+    order_index: i32,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BehaviorThresholdRow {
+    pub(super) id: Uuid,
+    pub(super) name: String,
+    pub(super) points_at_or_below: i32,
+    pub(super) description: Option<String>,
+    pub(super) is_active: bool,
+    pub(super) created_at: DateTime<Utc>,
+    pub(super) updated_at: DateTime<Utc>,
+}
+
+const CATEGORY_COLUMNS: &str =
+    "id, code, name, kind, points, description, order_index, is_active, created_at, updated_at";
+pub(super) const THRESHOLD_COLUMNS: &str =
+    "id, name, points_at_or_below, description, is_active, created_at, updated_at";
+
+pub async fn list_categories(
+    pool: &PgPool,
+    include_inactive: bool,
+) -> Result<Vec<BehaviorCategory>, AppError> {
+    let rows = sqlx::query_as::<_, BehaviorCategoryRow>(&format!(
+        r#"
+        SELECT {CATEGORY_COLUMNS}
+        FROM behavior_categories
+        WHERE ($1 OR is_active = true)
+        ORDER BY kind, order_index, name
+        "#
+    ))
+    .bind(include_inactive)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to list behavior categories: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงหมวดพฤติกรรมได้".to_string())
+    })?;
+
+    rows.into_iter().map(category_from_row).collect()
+}
+
+pub async fn create_category(
+    pool: &PgPool,
+    payload: UpsertBehaviorCategoryRequest,
+    created_by: Uuid,
+) -> Result<BehaviorCategory, AppError> {
+    let code = required_text(&payload.code, "กรุณาระบุรหัสหมวดพฤติกรรม")?;
+    let name = required_text(&payload.name, "กรุณาระบุชื่อหมวดพฤติกรรม")?;
+    validate_category_points(payload.kind, payload.points)?;
+
+    let row = sqlx::query_as::<_, BehaviorCategoryRow>(&format!(
+        r#"
+        INSERT INTO behavior_categories (
+            code, name, kind, points, description, order_index, is_active, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING {CATEGORY_COLUMNS}
+        "#
+    ))
+    .bind(code)
+    .bind(name)
+    .bind(payload.kind.as_str())
+    .bind(payload.points)
+    .bind(normalize_optional_text(payload.description))
+    .bind(payload.order_index)
+    .bind(payload.is_active)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .map_err(category_write_error)?;
+
+    category_from_row(row)
+}
+
+pub async fn update_category(
+    pool: &PgPool,
+    id: Uuid,
+    payload: UpsertBehaviorCategoryRequest,
+) -> Result<BehaviorCategory, AppError> {
+    let code = required_text(&payload.code, "กรุณาระบุรหัสหมวดพฤติกรรม")?;
+    let name = required_text(&payload.name, "กรุณาระบุชื่อหมวดพฤติกรรม")?;
+    validate_category_points(payload.kind, payload.points)?;
+
+    let row = sqlx::query_as::<_, BehaviorCategoryRow>(&format!(
+        r#"
+        UPDATE behavior_categories
+        SET code = $2,
+            name = $3,
+            kind = $4,
+            points = $5,
+            description = $6,
+            order_index = $7,
+            is_active = $8
+        WHERE id = $1
+        RETURNING {CATEGORY_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(code)
+    .bind(name)
+    .bind(payload.kind.as_str())
+    .bind(payload.points)
+    .bind(normalize_optional_text(payload.description))
+    .bind(payload.order_index)
+    .bind(payload.is_active)
+    .fetch_optional(pool)
+    .await
+    .map_err(category_write_error)?
+    .ok_or_else(|| AppError::NotFound("ไม่พบหมวดพฤติกรรม".to_string()))?;
+
+    category_from_row(row)
+}
+
+pub async fn list_thresholds(
+    pool: &PgPool,
+    include_inactive: bool,
+) -> Result<Vec<BehaviorThreshold>, AppError> {
+    let rows = sqlx::query_as::<_, BehaviorThresholdRow>(&format!(
+        r#"
+        SELECT {THRESHOLD_COLUMNS}
+        FROM behavior_thresholds
+        WHERE ($1 OR is_active = true)
+        ORDER BY points_at_or_below DESC
+        "#
+    ))
+    .bind(include_inactive)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to list behavior thresholds: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงเกณฑ์เฝ้าระวังได้".to_string())
+    })?;
+
+    Ok(rows.into_iter().map(threshold_from_row).collect())
+}
+
+pub async fn create_threshold(
+    pool: &PgPool,
+    payload: UpsertBehaviorThresholdRequest,
+    created_by: Uuid,
+) -> Result<BehaviorThreshold, AppError> {
+    let name = required_text(&payload.name, "กรุณาระบุชื่อเกณฑ์เฝ้าระวัง")?;
+    validate_threshold_points(payload.points_at_or_below)?;
+
+    let row = sqlx::query_as::<_, BehaviorThresholdRow>(&format!(
+        r#"
+        INSERT INTO behavior_thresholds (name, points_at_or_below, description, is_active, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {THRESHOLD_COLUMNS}
+        "#
+    ))
+    .bind(name)
+    .bind(payload.points_at_or_below)
+    .bind(normalize_optional_text(payload.description))
+    .bind(payload.is_active)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .map_err(threshold_write_error)?;
+
+    Ok(threshold_from_row(row))
+}
+
+pub async fn update_threshold(
+    pool: &PgPool,
+    id: Uuid,
+    payload: UpsertBehaviorThresholdRequest,
+) -> Result<BehaviorThreshold, AppError> {
+    let name = required_text(&payload.name, "กรุณาระบุชื่อเกณฑ์เฝ้าระวัง")?;
+    validate_threshold_points(payload.points_at_or_below)?;
+
+    let row = sqlx::query_as::<_, BehaviorThresholdRow>(&format!(
+        r#"
+        UPDATE behavior_thresholds
+        SET name = $2,
+            points_at_or_below = $3,
+            description = $4,
+            is_active = $5
+        WHERE id = $1
+        RETURNING {THRESHOLD_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(name)
+    .bind(payload.points_at_or_below)
+    .bind(normalize_optional_text(payload.description))
+    .bind(payload.is_active)
+    .fetch_optional(pool)
+    .await
+    .map_err(threshold_write_error)?
+    .ok_or_else(|| AppError::NotFound("ไม่พบเกณฑ์เฝ้าระวัง".to_string()))?;
+
+    Ok(threshold_from_row(row))
+}
+
+fn category_from_row(row: BehaviorCategoryRow) -> Result<BehaviorCategory, AppError> {
+    Ok(BehaviorCategory {
+        id: row.id,
+        code: row.code,
+        name: row.name,
+        kind: parse_category_kind(&row.kind)?,
+        points: row.points,
+        description: row.description,
+        order_index: row.order_index,
+        is_active: row.is_active,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+pub(super) fn threshold_from_row(row: BehaviorThresholdRow) -> BehaviorThreshold {
+    BehaviorThreshold {
+        id: row.id,
+        name: row.name,
+        points_at_or_below: row.points_at_or_below,
+        description: row.description,
+        is_active: row.is_active,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+fn category_write_error(error: sqlx::Error) -> AppError {
+    if is_unique_violation(&error) {
+        return AppError::Conflict("รหัสหมวดพฤติกรรมนี้ถูกใช้แล้ว".to_string());
+    }
+    tracing::error!("Failed to write behavior category: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกหมวดพฤติกรรมได้".to_string())
+}
+
+fn threshold_write_error(error: sqlx::Error) -> AppError {
+    if is_unique_violation(&error) {
+        return AppError::Conflict("มีเกณฑ์เฝ้าระวังที่คะแนนนี้แล้ว".to_string());
+    }
+    tracing::error!("Failed to write behavior threshold: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกเกณฑ์เฝ้าระวังได้".to_string())
+}
+
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Database(database_error) if database_error.code().as_deref() == Some("23505")
+    )
+}
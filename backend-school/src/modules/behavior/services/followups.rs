@@ -0,0 +1,274 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::work::services::{
+    self as work_service, CreateWorkItemInput, WorkItemAssigneeTargetInput, WorkItemAssigneeType,
+    WorkItemMetadata,
+};
+use crate::modules::workflow::models::WorkflowWindowMetadata;
+use crate::modules::workflow::services::{
+    self as workflow_service, CreateWorkflowWindowInput, WorkflowWindowSchedule,
+};
+use crate::permissions::registry::codes;
+
+use super::incidents::semester_total_points;
+use super::shared::reached_thresholds;
+
+const BEHAVIOR_MODULE_CODE: &str = "behavior";
+const FOLLOWUP_WORKFLOW_CODE: &str = "behavior_followup";
+
+#[derive(Debug, sqlx::FromRow)]
+struct ThresholdCandidateRow {
+    id: Uuid,
+    points_at_or_below: i32,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CrossingContextRow {
+    threshold_name: String,
+    points_at_or_below: i32,
+    student_name: String,
+}
+
+/// Records newly reached semester thresholds for a student and opens one advisor
+/// follow-up work item per crossing. Returns how many work items were created.
+pub async fn record_threshold_crossings(
+    pool: &PgPool,
+    student_id: Uuid,
+    academic_semester_id: Uuid,
+    incident_id: Uuid,
+    created_by: Uuid,
+) -> Result<usize, AppError> {
+    let total_points = semester_total_points(pool, student_id, academic_semester_id).await?;
+    let thresholds = sqlx::query_as::<_, ThresholdCandidateRow>(
+        "SELECT id, points_at_or_below FROM behavior_thresholds WHERE is_active = true",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(followup_error)?
+    .into_iter()
+    .map(|row| (row.id, row.points_at_or_below))
+    .collect::<Vec<_>>();
+
+    let mut created = 0;
+    for threshold_id in reached_thresholds(total_points, &thresholds) {
+        let crossing_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO behavior_threshold_crossings (
+                threshold_id, student_id, academic_semester_id, incident_id, points_total
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (threshold_id, student_id, academic_semester_id) DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(threshold_id)
+        .bind(student_id)
+        .bind(academic_semester_id)
+        .bind(incident_id)
+        .bind(total_points as i32)
+        .fetch_optional(pool)
+        .await
+        .map_err(followup_error)?;
+
+        let Some(crossing_id) = crossing_id else {
+            continue;
+        };
+        if create_followup_work_item(
+            pool,
+            crossing_id,
+            threshold_id,
+            student_id,
+            academic_semester_id,
+            total_points,
+            created_by,
+        )
+        .await?
+        {
+            created += 1;
+        }
+    }
+
+    Ok(created)
+}
+
+async fn create_followup_work_item(
+    pool: &PgPool,
+    crossing_id: Uuid,
+    threshold_id: Uuid,
+    student_id: Uuid,
+    academic_semester_id: Uuid,
+    total_points: i64,
+    created_by: Uuid,
+) -> Result<bool, AppError> {
+    let advisor_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT ca.user_id
+        FROM student_class_enrollments sce
+        JOIN classroom_advisors ca ON ca.classroom_id = sce.class_room_id
+        WHERE sce.student_id = $1
+          AND sce.status = 'active'
+        "#,
+    )
+    .bind(student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(followup_error)?;
+
+    if advisor_ids.is_empty() {
+        tracing::warn!(
+            student_id = %student_id,
+            threshold_id = %threshold_id,
+            "Behavior threshold crossed but student has no classroom advisor"
+        );
+        return Ok(false);
+    }
+
+    let context = sqlx::query_as::<_, CrossingContextRow>(
+        r#"
+        SELECT threshold.name AS threshold_name,
+               threshold.points_at_or_below,
+               CONCAT_WS(' ', student.first_name, student.last_name) AS student_name
+        FROM behavior_thresholds threshold
+        CROSS JOIN users student
+        WHERE threshold.id = $1
+          AND student.id = $2
+        "#,
+    )
+    .bind(threshold_id)
+    .bind(student_id)
+    .fetch_one(pool)
+    .await
+    .map_err(followup_error)?;
+
+    let workflow_window_id = ensure_followup_window(pool, academic_semester_id, created_by).await?;
+    let work_item_id = work_service::create_work_item(
+        pool,
+        CreateWorkItemInput {
+            workflow_window_id,
+            module_code: BEHAVIOR_MODULE_CODE.to_string(),
+            source_resource_type: "behavior_threshold_crossing".to_string(),
+            source_resource_id: Some(crossing_id),
+            title: format!(
+                "ติดตามพฤติกรรม: {} ({})",
+                context.student_name, context.threshold_name
+            ),
+            description: Some(format!(
+                "คะแนนพฤติกรรมภาคเรียนนี้ {total_points} คะแนน ถึงเกณฑ์ {} คะแนน",
+                context.points_at_or_below
+            )),
+            action_path: format!("/staff/behavior/students/{student_id}"),
+            required_permission: Some(codes::BEHAVIOR_MANAGE_ASSIGNED.to_string()),
+            metadata: WorkItemMetadata {
+                tags: vec!["behavior".to_string()],
+                source_label: Some("พฤติกรรมนักเรียน".to_string()),
+            },
+            assignees: advisor_ids
+                .into_iter()
+                .map(|user_id| WorkItemAssigneeTargetInput {
+                    assignee_type: WorkItemAssigneeType::User,
+                    user_id: Some(user_id),
+                    organization_unit_id: None,
+                    position_code: None,
+                })
+                .collect(),
+            created_by: Some(created_by),
+        },
+    )
+    .await?;
+
+    sqlx::query("UPDATE behavior_threshold_crossings SET work_item_id = $2 WHERE id = $1")
+        .bind(crossing_id)
+        .bind(work_item_id)
+        .execute(pool)
+        .await
+        .map_err(followup_error)?;
+
+    Ok(true)
+}
+
+async fn ensure_followup_window(
+    pool: &PgPool,
+    academic_semester_id: Uuid,
+    created_by: Uuid,
+) -> Result<Uuid, AppError> {
+    if let Some(id) = existing_followup_window(pool, academic_semester_id).await? {
+        return Ok(id);
+    }
+
+    let semester_name =
+        sqlx::query_scalar::<_, String>("SELECT name FROM academic_semesters WHERE id = $1")
+            .bind(academic_semester_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(followup_error)?
+            .ok_or_else(|| AppError::NotFound("ไม่พบภาคเรียน".to_string()))?;
+
+    let window = workflow_service::create_workflow_window(
+        pool,
+        CreateWorkflowWindowInput {
+            module_code: BEHAVIOR_MODULE_CODE.to_string(),
+            workflow_code: FOLLOWUP_WORKFLOW_CODE.to_string(),
+            title: format!("ติดตามพฤติกรรมนักเรียน {semester_name}"),
+            description: Some("งานติดตามนักเรียนที่คะแนนพฤติกรรมถึงเกณฑ์เฝ้าระวัง".to_string()),
+            organization_unit_id: None,
+            managed_by_permission: codes::BEHAVIOR_MANAGE_SCHOOL.to_string(),
+            schedule: WorkflowWindowSchedule {
+                opens_at: None,
+                due_at: None,
+                closes_at: None,
+            },
+            metadata: WorkflowWindowMetadata {
+                tags: vec!["behavior".to_string()],
+            },
+            created_by: Some(created_by),
+        },
+    )
+    .await?;
+    workflow_service::open_workflow_window(pool, window.id).await?;
+
+    let linked = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO behavior_followup_windows (academic_semester_id, workflow_window_id)
+        VALUES ($1, $2)
+        ON CONFLICT (academic_semester_id) DO NOTHING
+        RETURNING workflow_window_id
+        "#,
+    )
+    .bind(academic_semester_id)
+    .bind(window.id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)?;
+
+    if let Some(id) = linked {
+        return Ok(id);
+    }
+
+    // A concurrent incident linked its window first; retire ours and use theirs.
+    workflow_service::close_workflow_window(pool, window.id).await?;
+    existing_followup_window(pool, academic_semester_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::InternalServerError("ไม่สามารถสร้างรอบงานติดตามพฤติกรรมได้".to_string())
+        })
+}
+
+async fn existing_followup_window(
+    pool: &PgPool,
+    academic_semester_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT workflow_window_id FROM behavior_followup_windows WHERE academic_semester_id = $1",
+    )
+    .bind(academic_semester_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)
+}
+
+fn followup_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to record behavior follow-up: {}", error);
+    AppError::InternalServerError("ไม่สามารถสร้างงานติดตามพฤติกรรมได้".to_string())
+}
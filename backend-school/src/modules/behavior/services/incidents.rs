@@ -0,0 +1,601 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::behavior::models::{
+    BehaviorIncident, BehaviorIncidentFilter, BehaviorPointSummary, CreateBehaviorIncidentRequest,
+};
+use crate::modules::parents::services as parent_service;
+use crate::permissions::registry::codes;
+use crate::policies::behavior_access_policy;
+use crate::policies::resource_access_policy::UserResourceListAccess;
+
+use super::categories::{threshold_from_row, BehaviorThresholdRow, THRESHOLD_COLUMNS};
+use super::shared::{
+    ensure_student_user, normalize_optional_text, parse_category_kind, parse_incident_status,
+    required_text, resolve_semester_id, INCIDENT_NOT_FOUND_MESSAGE, MAX_EVIDENCE_FILES,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BehaviorIncidentRow {
+    id: Uuid,
+    student_id: Uuid,
+    student_name: String,
+    category_id: Uuid,
+    category_code: String,
+    category_name: String,
+    kind: String,
+    academic_semester_id: Uuid,
+    occurred_at: DateTime<Utc>,
+    room_id: Option<Uuid>,
+    room_name: Option<String>,
+    location_note: Option<String>,
+    description: Option<String>,
+    points: i32,
+    is_confidential: bool,
+    status: String,
+    reported_by: Option<Uuid>,
+    reported_by_name: Option<String>,
+    voided_at: Option<DateTime<Utc>>,
+    void_reason: Option<String>,
+    guardian_notified_at: Option<DateTime<Utc>>,
+    evidence_file_ids: Vec<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CategorySnapshotRow {
+    points: i32,
+    is_active: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PointTotalsRow {
+    merit_points: i64,
+    incident_points: i64,
+    merit_count: i64,
+    incident_count: i64,
+}
+
+pub struct BehaviorIncidentCreateOutcome {
+    pub incident: BehaviorIncident,
+    pub notify_guardians: bool,
+}
+
+const INCIDENT_SELECT: &str = r#"
+    SELECT i.id,
+           i.student_id,
+           CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+           i.category_id,
+           category.code AS category_code,
+           category.name AS category_name,
+           category.kind,
+           i.academic_semester_id,
+           i.occurred_at,
+           i.room_id,
+           room.name_th AS room_name,
+           i.location_note,
+           i.description,
+           i.points,
+           i.is_confidential,
+           i.status,
+           i.reported_by,
+           NULLIF(CONCAT_WS(' ', reporter.first_name, reporter.last_name), '') AS reported_by_name,
+           i.voided_at,
+           i.void_reason,
+           i.guardian_notified_at,
+           COALESCE(
+               (
+                   SELECT array_agg(evidence.file_id ORDER BY evidence.created_at, evidence.file_id)
+                   FROM behavior_incident_evidence evidence
+                   WHERE evidence.incident_id = i.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS evidence_file_ids,
+           i.created_at,
+           i.updated_at
+    FROM behavior_incidents i
+    JOIN users student ON student.id = i.student_id
+    JOIN behavior_categories category ON category.id = i.category_id
+    LEFT JOIN rooms room ON room.id = i.room_id
+    LEFT JOIN users reporter ON reporter.id = i.reported_by
+"#;
+
+pub async fn list_incidents(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: BehaviorIncidentFilter,
+) -> Result<Vec<BehaviorIncident>, AppError> {
+    let access = behavior_access_policy::resolve_behavior_list_access(actor).ok();
+    if access.is_none() {
+        behavior_access_policy::require_behavior_create(actor)?;
+    }
+
+    let mut builder = QueryBuilder::<Postgres>::new(INCIDENT_SELECT);
+    builder.push(" WHERE (");
+    match access {
+        Some(UserResourceListAccess::School) => {
+            builder.push("TRUE");
+        }
+        Some(UserResourceListAccess::Assigned(user_id)) => {
+            builder
+                .push(
+                    "i.student_id IN (
+                        SELECT sce.student_id
+                        FROM student_class_enrollments sce
+                        JOIN classroom_advisors ca ON ca.classroom_id = sce.class_room_id
+                        WHERE sce.status = 'active'
+                          AND ca.user_id = ",
+                )
+                .push_bind(user_id)
+                .push(")");
+        }
+        Some(
+            UserResourceListAccess::Own(user_id)
+            | UserResourceListAccess::OrganizationUnit(user_id)
+            | UserResourceListAccess::OrganizationTree(user_id),
+        ) => {
+            builder.push("i.student_id = ").push_bind(user_id);
+        }
+        None => {
+            builder.push("FALSE");
+        }
+    }
+    builder
+        .push(" OR i.reported_by = ")
+        .push_bind(actor.user_id)
+        .push(")");
+
+    if !actor.has_permission(codes::BEHAVIOR_CONFIDENTIAL_READ_SCHOOL) {
+        builder
+            .push(" AND (i.is_confidential = false OR i.reported_by = ")
+            .push_bind(actor.user_id)
+            .push(")");
+    }
+    if let Some(student_id) = filter.student_id {
+        builder.push(" AND i.student_id = ").push_bind(student_id);
+    }
+    if let Some(academic_semester_id) = filter.academic_semester_id {
+        builder
+            .push(" AND i.academic_semester_id = ")
+            .push_bind(academic_semester_id);
+    }
+    if let Some(class_room_id) = filter.class_room_id {
+        builder
+            .push(
+                " AND EXISTS (
+                    SELECT 1
+                    FROM student_class_enrollments sce
+                    WHERE sce.student_id = i.student_id
+                      AND sce.status = 'active'
+                      AND sce.class_room_id = ",
+            )
+            .push_bind(class_room_id)
+            .push(")");
+    }
+    if let Some(kind) = filter.kind {
+        builder
+            .push(" AND category.kind = ")
+            .push_bind(kind.as_str());
+    }
+    if !filter.include_voided {
+        builder.push(" AND i.status = 'recorded'");
+    }
+    builder.push(" ORDER BY i.occurred_at DESC, i.created_at DESC LIMIT 500");
+
+    let rows = builder
+        .build_query_as::<BehaviorIncidentRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("Failed to list behavior incidents: {}", error);
+            AppError::InternalServerError("ไม่สามารถดึงเหตุการณ์พฤติกรรมได้".to_string())
+        })?;
+
+    rows.into_iter().map(incident_from_row).collect()
+}
+
+pub async fn get_incident(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<BehaviorIncident, AppError> {
+    let incident = load_incident(pool, id).await?;
+    behavior_access_policy::require_incident_read(
+        pool,
+        actor,
+        incident.student_id,
+        incident.is_confidential,
+        incident.reported_by,
+    )
+    .await?;
+    Ok(incident)
+}
+
+pub async fn create_incident(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: CreateBehaviorIncidentRequest,
+) -> Result<BehaviorIncidentCreateOutcome, AppError> {
+    behavior_access_policy::require_behavior_create(actor)?;
+    ensure_student_user(pool, payload.student_id).await?;
+    let academic_semester_id = resolve_semester_id(pool, payload.academic_semester_id).await?;
+    let category = load_category_snapshot(pool, payload.category_id).await?;
+    let evidence_file_ids = dedupe_evidence_file_ids(payload.evidence_file_ids)?;
+    validate_evidence_files(pool, actor.user_id, &evidence_file_ids).await?;
+
+    let mut transaction = pool.begin().await.map_err(incident_write_error)?;
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO behavior_incidents (
+            student_id, category_id, academic_semester_id, occurred_at, room_id,
+            location_note, description, points, is_confidential, reported_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.student_id)
+    .bind(payload.category_id)
+    .bind(academic_semester_id)
+    .bind(payload.occurred_at)
+    .bind(payload.room_id)
+    .bind(normalize_optional_text(payload.location_note))
+    .bind(normalize_optional_text(payload.description))
+    .bind(category.points)
+    .bind(payload.is_confidential)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(incident_write_error)?;
+
+    if !evidence_file_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO behavior_incident_evidence (incident_id, file_id, attached_by)
+            SELECT $1, file_id, $3
+            FROM UNNEST($2::uuid[]) AS file_id
+            "#,
+        )
+        .bind(id)
+        .bind(&evidence_file_ids)
+        .bind(actor.user_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(incident_write_error)?;
+
+        sqlx::query(
+            "UPDATE files SET retention_class = 'standard', expires_at = NULL, updated_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(&evidence_file_ids)
+        .execute(&mut *transaction)
+        .await
+        .map_err(incident_write_error)?;
+    }
+
+    transaction.commit().await.map_err(incident_write_error)?;
+
+    let incident = load_incident(pool, id).await?;
+    let notify_guardians = payload.notify_guardians && !incident.is_confidential;
+    Ok(BehaviorIncidentCreateOutcome {
+        incident,
+        notify_guardians,
+    })
+}
+
+pub async fn void_incident(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    reason: &str,
+) -> Result<BehaviorIncident, AppError> {
+    behavior_access_policy::require_behavior_manage_school(actor)?;
+    let reason = required_text(reason, "กรุณาระบุเหตุผลการยกเลิก")?;
+    let incident = load_incident(pool, id).await?;
+    if !behavior_access_policy::can_view_incident(
+        actor,
+        incident.is_confidential,
+        incident.reported_by,
+    ) {
+        return Err(AppError::NotFound(INCIDENT_NOT_FOUND_MESSAGE.to_string()));
+    }
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE behavior_incidents
+        SET status = 'voided',
+            voided_at = NOW(),
+            voided_by = $2,
+            void_reason = $3
+        WHERE id = $1
+          AND status = 'recorded'
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .bind(reason)
+    .execute(pool)
+    .await
+    .map_err(incident_write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("เหตุการณ์นี้ถูกยกเลิกแล้ว".to_string()));
+    }
+
+    load_incident(pool, id).await
+}
+
+pub async fn student_point_summary(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+) -> Result<BehaviorPointSummary, AppError> {
+    behavior_access_policy::require_student_behavior_read(pool, actor, student_id).await?;
+    let academic_semester_id = resolve_semester_id(pool, academic_semester_id).await?;
+    load_point_summary(pool, student_id, academic_semester_id).await
+}
+
+pub async fn list_guardian_incidents(
+    pool: &PgPool,
+    parent_id: Uuid,
+    student_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+) -> Result<Vec<BehaviorIncident>, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, student_id).await?;
+    let academic_semester_id = resolve_semester_id(pool, academic_semester_id).await?;
+
+    let rows = sqlx::query_as::<_, BehaviorIncidentRow>(&format!(
+        r#"
+        {INCIDENT_SELECT}
+        WHERE i.student_id = $1
+          AND i.academic_semester_id = $2
+          AND i.status = 'recorded'
+          AND i.is_confidential = false
+        ORDER BY i.occurred_at DESC, i.created_at DESC
+        "#
+    ))
+    .bind(student_id)
+    .bind(academic_semester_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to list guardian behavior incidents: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงเหตุการณ์พฤติกรรมได้".to_string())
+    })?;
+
+    rows.into_iter()
+        .map(|row| incident_from_row(row).map(guardian_view))
+        .collect()
+}
+
+pub async fn get_guardian_point_summary(
+    pool: &PgPool,
+    parent_id: Uuid,
+    student_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+) -> Result<BehaviorPointSummary, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, student_id).await?;
+    let academic_semester_id = resolve_semester_id(pool, academic_semester_id).await?;
+    load_point_summary(pool, student_id, academic_semester_id).await
+}
+
+pub(super) async fn semester_total_points(
+    pool: &PgPool,
+    student_id: Uuid,
+    academic_semester_id: Uuid,
+) -> Result<i64, AppError> {
+    let totals = load_point_totals(pool, student_id, academic_semester_id).await?;
+    Ok(totals.merit_points + totals.incident_points)
+}
+
+pub(super) async fn load_incident(pool: &PgPool, id: Uuid) -> Result<BehaviorIncident, AppError> {
+    let row =
+        sqlx::query_as::<_, BehaviorIncidentRow>(&format!("{INCIDENT_SELECT} WHERE i.id = $1"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("Failed to load behavior incident: {}", error);
+                AppError::InternalServerError("ไม่สามารถดึงเหตุการณ์พฤติกรรมได้".to_string())
+            })?
+            .ok_or_else(|| AppError::NotFound(INCIDENT_NOT_FOUND_MESSAGE.to_string()))?;
+
+    incident_from_row(row)
+}
+
+async fn load_point_summary(
+    pool: &PgPool,
+    student_id: Uuid,
+    academic_semester_id: Uuid,
+) -> Result<BehaviorPointSummary, AppError> {
+    let totals = load_point_totals(pool, student_id, academic_semester_id).await?;
+    let thresholds = sqlx::query_as::<_, BehaviorThresholdRow>(&format!(
+        r#"
+        SELECT {THRESHOLD_COLUMNS}
+        FROM behavior_thresholds
+        WHERE id IN (
+            SELECT threshold_id
+            FROM behavior_threshold_crossings
+            WHERE student_id = $1
+              AND academic_semester_id = $2
+        )
+        ORDER BY points_at_or_below DESC
+        "#
+    ))
+    .bind(student_id)
+    .bind(academic_semester_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load behavior threshold crossings: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงคะแนนพฤติกรรมได้".to_string())
+    })?;
+
+    Ok(BehaviorPointSummary {
+        student_id,
+        academic_semester_id,
+        merit_points: totals.merit_points,
+        incident_points: totals.incident_points,
+        total_points: totals.merit_points + totals.incident_points,
+        merit_count: totals.merit_count,
+        incident_count: totals.incident_count,
+        reached_thresholds: thresholds.into_iter().map(threshold_from_row).collect(),
+    })
+}
+
+async fn load_point_totals(
+    pool: &PgPool,
+    student_id: Uuid,
+    academic_semester_id: Uuid,
+) -> Result<PointTotalsRow, AppError> {
+    sqlx::query_as::<_, PointTotalsRow>(
+        r#"
+        SELECT COALESCE(SUM(points) FILTER (WHERE points > 0), 0)::bigint AS merit_points,
+               COALESCE(SUM(points) FILTER (WHERE points < 0), 0)::bigint AS incident_points,
+               COUNT(*) FILTER (WHERE points > 0) AS merit_count,
+               COUNT(*) FILTER (WHERE points < 0) AS incident_count
+        FROM behavior_incidents
+        WHERE student_id = $1
+          AND academic_semester_id = $2
+          AND status = 'recorded'
+        "#,
+    )
+    .bind(student_id)
+    .bind(academic_semester_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load behavior point totals: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงคะแนนพฤติกรรมได้".to_string())
+    })
+}
+
+async fn load_category_snapshot(
+    pool: &PgPool,
+    category_id: Uuid,
+) -> Result<CategorySnapshotRow, AppError> {
+    let category = sqlx::query_as::<_, CategorySnapshotRow>(
+        "SELECT points, is_active FROM behavior_categories WHERE id = $1",
+    )
+    .bind(category_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load behavior category: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงหมวดพฤติกรรมได้".to_string())
+    })?
+    .ok_or_else(|| AppError::ValidationError("ไม่พบหมวดพฤติกรรม".to_string()))?;
+
+    if !category.is_active {
+        return Err(AppError::ValidationError(
+            "หมวดพฤติกรรมนี้ปิดใช้งานแล้ว".to_string(),
+        ));
+    }
+    Ok(category)
+}
+
+fn dedupe_evidence_file_ids(file_ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+    let mut unique = Vec::with_capacity(file_ids.len());
+    for file_id in file_ids {
+        if !unique.contains(&file_id) {
+            unique.push(file_id);
+        }
+    }
+    if unique.len() > MAX_EVIDENCE_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบหลักฐานได้ไม่เกิน {MAX_EVIDENCE_FILES} ไฟล์"
+        )));
+    }
+    Ok(unique)
+}
+
+async fn validate_evidence_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let ready_count = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        WHERE id = ANY($1)
+          AND owner_user_id = $2
+          AND purpose_code = 'behavior_evidence'
+          AND lifecycle_status = 'ready'
+          AND deleted_at IS NULL
+          AND NOT EXISTS (
+              SELECT 1
+              FROM behavior_incident_evidence evidence
+              WHERE evidence.file_id = files.id
+          )
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate behavior evidence files: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบไฟล์หลักฐานได้".to_string())
+    })?;
+
+    if ready_count == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "ไฟล์หลักฐานไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
+
+fn incident_from_row(row: BehaviorIncidentRow) -> Result<BehaviorIncident, AppError> {
+    Ok(BehaviorIncident {
+        id: row.id,
+        student_id: row.student_id,
+        student_name: row.student_name,
+        category_id: row.category_id,
+        category_code: row.category_code,
+        category_name: row.category_name,
+        kind: parse_category_kind(&row.kind)?,
+        academic_semester_id: row.academic_semester_id,
+        occurred_at: row.occurred_at,
+        room_id: row.room_id,
+        room_name: row.room_name,
+        location_note: row.location_note,
+        description: row.description,
+        points: row.points,
+        is_confidential: row.is_confidential,
+        status: parse_incident_status(&row.status)?,
+        reported_by: row.reported_by,
+        reported_by_name: row.reported_by_name,
+        voided_at: row.voided_at,
+        void_reason: row.void_reason,
+        guardian_notified_at: row.guardian_notified_at,
+        evidence_file_ids: row.evidence_file_ids,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+/// Guardians see what happened and the points, not who reported it or the
+/// evidence files, which stay behind the staff-side incident policy.
+pub(super) fn guardian_view(mut incident: BehaviorIncident) -> BehaviorIncident {
+    incident.reported_by = None;
+    incident.reported_by_name = None;
+    incident.evidence_file_ids = Vec::new();
+    incident
+}
+
+fn incident_write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write behavior incident: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกเหตุการณ์พฤติกรรมได้".to_string())
+}
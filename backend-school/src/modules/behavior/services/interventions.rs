@@ -0,0 +1,300 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::behavior::models::{
+    BehaviorIntervention, BehaviorInterventionFilter, BehaviorInterventionStatus,
+    CreateBehaviorInterventionRequest, UpdateBehaviorInterventionRequest,
+};
+use crate::permissions::registry::codes;
+use crate::policies::behavior_access_policy;
+use crate::policies::resource_access_policy::UserResourceListAccess;
+
+use super::shared::{
+    can_transition_intervention_status, ensure_student_user, normalize_optional_text,
+    parse_intervention_action_type, parse_intervention_status, required_text, resolve_semester_id,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BehaviorInterventionRow {
+    id: Uuid,
+    student_id: Uuid,
+    student_name: String,
+    academic_semester_id: Uuid,
+    incident_id: Option<Uuid>,
+    action_type: String,
+    title: String,
+    note: Option<String>,
+    due_date: Option<NaiveDate>,
+    status: String,
+    outcome: Option<String>,
+    assigned_to: Option<Uuid>,
+    assigned_to_name: Option<String>,
+    created_by: Option<Uuid>,
+    completed_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+const INTERVENTION_SELECT: &str = r#"
+    SELECT iv.id,
+           iv.student_id,
+           CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+           iv.academic_semester_id,
+           iv.incident_id,
+           iv.action_type,
+           iv.title,
+           iv.note,
+           iv.due_date,
+           iv.status,
+           iv.outcome,
+           iv.assigned_to,
+           NULLIF(CONCAT_WS(' ', assignee.first_name, assignee.last_name), '') AS assigned_to_name,
+           iv.created_by,
+           iv.completed_at,
+           iv.created_at,
+           iv.updated_at
+    FROM behavior_interventions iv
+    JOIN users student ON student.id = iv.student_id
+    LEFT JOIN users assignee ON assignee.id = iv.assigned_to
+    LEFT JOIN behavior_incidents incident ON incident.id = iv.incident_id
+"#;
+
+pub async fn list_interventions(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: BehaviorInterventionFilter,
+) -> Result<Vec<BehaviorIntervention>, AppError> {
+    let access = behavior_access_policy::resolve_behavior_list_access(actor)?;
+
+    let mut builder = QueryBuilder::<Postgres>::new(INTERVENTION_SELECT);
+    builder.push(" WHERE (");
+    match access {
+        UserResourceListAccess::School => {
+            builder.push("TRUE");
+        }
+        UserResourceListAccess::Assigned(user_id) => {
+            builder
+                .push(
+                    "iv.student_id IN (
+                        SELECT sce.student_id
+                        FROM student_class_enrollments sce
+                        JOIN classroom_advisors ca ON ca.classroom_id = sce.class_room_id
+                        WHERE sce.status = 'active'
+                          AND ca.user_id = ",
+                )
+                .push_bind(user_id)
+                .push(")");
+        }
+        UserResourceListAccess::Own(user_id)
+        | UserResourceListAccess::OrganizationUnit(user_id)
+        | UserResourceListAccess::OrganizationTree(user_id) => {
+            builder.push("iv.student_id = ").push_bind(user_id);
+        }
+    }
+    builder
+        .push(" OR iv.assigned_to = ")
+        .push_bind(actor.user_id)
+        .push(")");
+
+    if !actor.has_permission(codes::BEHAVIOR_CONFIDENTIAL_READ_SCHOOL) {
+        builder
+            .push(
+                " AND (incident.id IS NULL OR incident.is_confidential = false OR incident.reported_by = ",
+            )
+            .push_bind(actor.user_id)
+            .push(")");
+    }
+    if let Some(student_id) = filter.student_id {
+        builder.push(" AND iv.student_id = ").push_bind(student_id);
+    }
+    if let Some(academic_semester_id) = filter.academic_semester_id {
+        builder
+            .push(" AND iv.academic_semester_id = ")
+            .push_bind(academic_semester_id);
+    }
+    if let Some(status) = filter.status {
+        builder.push(" AND iv.status = ").push_bind(status.as_str());
+    }
+    builder.push(" ORDER BY iv.due_date ASC NULLS LAST, iv.created_at DESC LIMIT 500");
+
+    let rows = builder
+        .build_query_as::<BehaviorInterventionRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("Failed to list behavior interventions: {}", error);
+            AppError::InternalServerError("ไม่สามารถดึงการติดตามพฤติกรรมได้".to_string())
+        })?;
+
+    rows.into_iter().map(intervention_from_row).collect()
+}
+
+pub async fn create_intervention(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: CreateBehaviorInterventionRequest,
+) -> Result<BehaviorIntervention, AppError> {
+    ensure_student_user(pool, payload.student_id).await?;
+    behavior_access_policy::require_student_behavior_manage(pool, actor, payload.student_id)
+        .await?;
+    let title = required_text(&payload.title, "กรุณาระบุหัวข้อการติดตาม")?;
+    let academic_semester_id = resolve_semester_id(pool, payload.academic_semester_id).await?;
+    if let Some(incident_id) = payload.incident_id {
+        ensure_incident_belongs_to_student(pool, actor, incident_id, payload.student_id).await?;
+    }
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO behavior_interventions (
+            student_id, academic_semester_id, incident_id, action_type, title, note,
+            due_date, assigned_to, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.student_id)
+    .bind(academic_semester_id)
+    .bind(payload.incident_id)
+    .bind(payload.action_type.as_str())
+    .bind(title)
+    .bind(normalize_optional_text(payload.note))
+    .bind(payload.due_date)
+    .bind(payload.assigned_to.unwrap_or(actor.user_id))
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(intervention_write_error)?;
+
+    load_intervention(pool, id).await
+}
+
+pub async fn update_intervention(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: UpdateBehaviorInterventionRequest,
+) -> Result<BehaviorIntervention, AppError> {
+    let current = load_intervention(pool, id).await?;
+    if current.assigned_to != Some(actor.user_id) {
+        behavior_access_policy::require_student_behavior_manage(pool, actor, current.student_id)
+            .await?;
+    }
+
+    let next_status = payload.status.unwrap_or(current.status);
+    if !can_transition_intervention_status(current.status, next_status) {
+        return Err(AppError::Conflict(
+            "ไม่สามารถเปลี่ยนสถานะการติดตามนี้ได้".to_string(),
+        ));
+    }
+    let completed_now = next_status == BehaviorInterventionStatus::Completed
+        && current.status != BehaviorInterventionStatus::Completed;
+
+    sqlx::query(
+        r#"
+        UPDATE behavior_interventions
+        SET status = $2,
+            note = COALESCE($3, note),
+            outcome = COALESCE($4, outcome),
+            due_date = COALESCE($5, due_date),
+            assigned_to = COALESCE($6, assigned_to),
+            completed_at = CASE WHEN $7 THEN NOW() ELSE completed_at END
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(next_status.as_str())
+    .bind(normalize_optional_text(payload.note))
+    .bind(normalize_optional_text(payload.outcome))
+    .bind(payload.due_date)
+    .bind(payload.assigned_to)
+    .bind(completed_now)
+    .execute(pool)
+    .await
+    .map_err(intervention_write_error)?;
+
+    load_intervention(pool, id).await
+}
+
+async fn ensure_incident_belongs_to_student(
+    pool: &PgPool,
+    actor: &ActorContext,
+    incident_id: Uuid,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    let incident = sqlx::query_as::<_, (Uuid, bool, Option<Uuid>)>(
+        "SELECT student_id, is_confidential, reported_by FROM behavior_incidents WHERE id = $1",
+    )
+    .bind(incident_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(
+            "Failed to load behavior incident for intervention: {}",
+            error
+        );
+        AppError::InternalServerError("ไม่สามารถดึงเหตุการณ์พฤติกรรมได้".to_string())
+    })?;
+
+    match incident {
+        Some((incident_student_id, is_confidential, reported_by))
+            if incident_student_id == student_id
+                && behavior_access_policy::can_view_incident(
+                    actor,
+                    is_confidential,
+                    reported_by,
+                ) =>
+        {
+            Ok(())
+        }
+        _ => Err(AppError::ValidationError(
+            "เหตุการณ์ที่อ้างอิงไม่ใช่ของนักเรียนคนนี้".to_string(),
+        )),
+    }
+}
+
+async fn load_intervention(pool: &PgPool, id: Uuid) -> Result<BehaviorIntervention, AppError> {
+    let row = sqlx::query_as::<_, BehaviorInterventionRow>(&format!(
+        "{INTERVENTION_SELECT} WHERE iv.id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load behavior intervention: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงการติดตามพฤติกรรมได้".to_string())
+    })?
+    .ok_or_else(|| AppError::NotFound("ไม่พบรายการติดตามพฤติกรรม".to_string()))?;
+
+    intervention_from_row(row)
+}
+
+fn intervention_from_row(row: BehaviorInterventionRow) -> Result<BehaviorIntervention, AppError> {
+    Ok(BehaviorIntervention {
+        id: row.id,
+        student_id: row.student_id,
+        student_name: row.student_name,
+        academic_semester_id: row.academic_semester_id,
+        incident_id: row.incident_id,
+        action_type: parse_intervention_action_type(&row.action_type)?,
+        title: row.title,
+        note: row.note,
+        due_date: row.due_date,
+        status: parse_intervention_status(&row.status)?,
+        outcome: row.outcome,
+        assigned_to: row.assigned_to,
+        assigned_to_name: row.assigned_to_name,
+        created_by: row.created_by,
+        completed_at: row.completed_at,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+fn intervention_write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write behavior intervention: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกการติดตามพฤติกรรมได้".to_string())
+}
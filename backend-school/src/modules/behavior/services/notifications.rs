@@ -0,0 +1,86 @@
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::behavior::models::BehaviorIncident;
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+use super::shared::guardian_notification_text;
+
+const GUARDIAN_BEHAVIOR_LINK: &str = "/parent";
+
+/// Notifies linked guardians about a non-confidential incident and stamps
+/// `guardian_notified_at` once at least one guardian notification was stored.
+/// Confidential incidents are never sent, regardless of the caller's request.
+pub async fn notify_guardians(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    incident: &BehaviorIncident,
+) -> Result<usize, AppError> {
+    if incident.is_confidential {
+        return Ok(0);
+    }
+
+    let guardian_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT parent_users.id
+        FROM student_parents
+        JOIN users parent_users ON parent_users.id = student_parents.parent_user_id
+        WHERE student_parents.student_user_id = $1
+          AND parent_users.user_type = 'parent'
+          AND parent_users.status = 'active'
+        "#,
+    )
+    .bind(incident.student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load behavior guardian recipients: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงข้อมูลผู้ปกครองได้".to_string())
+    })?;
+
+    let (title, message) =
+        guardian_notification_text(incident.kind, &incident.category_name, incident.points);
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let mut successful_count = 0;
+    for guardian_id in guardian_ids {
+        if let Err(error) = NotificationService::send(
+            pool,
+            &publisher,
+            guardian_id,
+            &title,
+            &message,
+            NotificationType::Info,
+            Some(GUARDIAN_BEHAVIOR_LINK),
+        )
+        .await
+        {
+            tracing::error!(
+                incident_id = %incident.id,
+                recipient_user_id = %guardian_id,
+                error = %error,
+                "Behavior guardian notification failed for recipient"
+            );
+        } else {
+            successful_count += 1;
+        }
+    }
+
+    if successful_count > 0 {
+        sqlx::query("UPDATE behavior_incidents SET guardian_notified_at = NOW() WHERE id = $1")
+            .bind(incident.id)
+            .execute(pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("Failed to mark behavior guardian notification: {}", error);
+                AppError::InternalServerError("ไม่สามารถบันทึกสถานะการแจ้งผู้ปกครองได้".to_string())
+            })?;
+    }
+
+    Ok(successful_count)
+}
@@ -0,0 +1,172 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::behavior::models::{
+    BehaviorCategoryKind, BehaviorIncidentStatus, BehaviorInterventionActionType,
+    BehaviorInterventionStatus,
+};
+
+pub(super) const INCIDENT_NOT_FOUND_MESSAGE: &str = "ไม่พบเหตุการณ์พฤติกรรม";
+pub(super) const MAX_EVIDENCE_FILES: usize = 10;
+
+pub fn validate_category_points(kind: BehaviorCategoryKind, points: i32) -> Result<(), AppError> {
+    let valid = match kind {
+        BehaviorCategoryKind::Incident => points < 0,
+        BehaviorCategoryKind::Merit => points > 0,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(match kind {
+            BehaviorCategoryKind::Incident => "หมวดความผิดต้องมีคะแนนติดลบ".to_string(),
+            BehaviorCategoryKind::Merit => "หมวดความดีต้องมีคะแนนเป็นบวก".to_string(),
+        }))
+    }
+}
+
+pub fn validate_threshold_points(points_at_or_below: i32) -> Result<(), AppError> {
+    if points_at_or_below < 0 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "เกณฑ์เฝ้าระวังต้องเป็นคะแนนติดลบ".to_string(),
+        ))
+    }
+}
+
+/// Returns the thresholds a semester running total has reached, most severe last.
+/// Crossings are recorded once per threshold and semester, so callers may pass
+/// every active threshold after each incident without re-opening follow-ups.
+pub fn reached_thresholds(total_points: i64, thresholds: &[(Uuid, i32)]) -> Vec<Uuid> {
+    let mut reached = thresholds
+        .iter()
+        .filter(|(_, points_at_or_below)| total_points <= i64::from(*points_at_or_below))
+        .copied()
+        .collect::<Vec<_>>();
+    reached.sort_by_key(|(_, points_at_or_below)| std::cmp::Reverse(*points_at_or_below));
+    reached.into_iter().map(|(id, _)| id).collect()
+}
+
+pub fn can_transition_intervention_status(
+    from: BehaviorInterventionStatus,
+    to: BehaviorInterventionStatus,
+) -> bool {
+    use BehaviorInterventionStatus::{Cancelled, Completed, InProgress, Planned};
+
+    matches!(
+        (from, to),
+        (Planned, Planned)
+            | (Planned, InProgress)
+            | (Planned, Completed)
+            | (Planned, Cancelled)
+            | (InProgress, InProgress)
+            | (InProgress, Completed)
+            | (InProgress, Cancelled)
+            | (Completed, Completed)
+            | (Cancelled, Cancelled)
+    )
+}
+
+pub fn guardian_notification_text(
+    kind: BehaviorCategoryKind,
+    category_name: &str,
+    points: i32,
+) -> (String, String) {
+    match kind {
+        BehaviorCategoryKind::Incident => (
+            format!("แจ้งพฤติกรรม: {category_name}"),
+            format!("บุตรหลานของท่านถูกบันทึกพฤติกรรม {category_name} ({points} คะแนน)"),
+        ),
+        BehaviorCategoryKind::Merit => (
+            format!("ชื่นชมพฤติกรรม: {category_name}"),
+            format!("บุตรหลานของท่านได้รับการบันทึกความดี {category_name} (+{points} คะแนน)"),
+        ),
+    }
+}
+
+pub(super) fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn required_text(value: &str, message: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(AppError::ValidationError(message.to_string()))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+pub(super) async fn resolve_semester_id(
+    pool: &PgPool,
+    academic_semester_id: Option<Uuid>,
+) -> Result<Uuid, AppError> {
+    if let Some(id) = academic_semester_id {
+        return Ok(id);
+    }
+
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT id
+        FROM academic_semesters
+        WHERE is_active = true
+        ORDER BY start_date DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load active semester for behavior: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงภาคเรียนปัจจุบันได้".to_string())
+    })?
+    .ok_or_else(|| AppError::ValidationError("ไม่พบภาคเรียนที่เปิดใช้งาน".to_string()))
+}
+
+pub(super) async fn ensure_student_user(pool: &PgPool, student_id: Uuid) -> Result<(), AppError> {
+    let is_student: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND user_type = 'student')",
+    )
+    .bind(student_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check behavior student: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบข้อมูลนักเรียนได้".to_string())
+    })?;
+
+    if is_student {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("ไม่พบนักเรียน".to_string()))
+    }
+}
+
+pub(super) fn parse_category_kind(code: &str) -> Result<BehaviorCategoryKind, AppError> {
+    BehaviorCategoryKind::from_code(code).ok_or_else(|| {
+        AppError::InternalServerError("ประเภทหมวดพฤติกรรมในฐานข้อมูลไม่ถูกต้อง".to_string())
+    })
+}
+
+pub(super) fn parse_incident_status(code: &str) -> Result<BehaviorIncidentStatus, AppError> {
+    BehaviorIncidentStatus::from_code(code).ok_or_else(|| {
+        AppError::InternalServerError("สถานะเหตุการณ์พฤติกรรมในฐานข้อมูลไม่ถูกต้อง".to_string())
+    })
+}
+
+pub(super) fn parse_intervention_action_type(
+    code: &str,
+) -> Result<BehaviorInterventionActionType, AppError> {
+    BehaviorInterventionActionType::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("ประเภทการติดตามในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+pub(super) fn parse_intervention_status(
+    code: &str,
+) -> Result<BehaviorInterventionStatus, AppError> {
+    BehaviorInterventionStatus::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("สถานะการติดตามในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
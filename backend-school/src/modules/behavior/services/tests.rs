@@ -0,0 +1,127 @@
+use super::*;
+
+fn incident_fixture(kind: BehaviorCategoryKind, points: i32) -> BehaviorIncident {
+    let now = Utc::now();
+    BehaviorIncident {
+        id: Uuid::new_v4(),
+        student_id: Uuid::new_v4(),
+        student_name: "นักเรียน ทดสอบ".to_string(),
+        category_id: Uuid::new_v4(),
+        category_code: "LATE".to_string(),
+        category_name: "มาสาย".to_string(),
+        kind,
+        academic_semester_id: Uuid::new_v4(),
+        occurred_at: now,
+        room_id: None,
+        room_name: None,
+        location_note: Some("หน้าประตูโรงเรียน".to_string()),
+        description: None,
+        points,
+        is_confidential: false,
+        status: BehaviorIncidentStatus::Recorded,
+        reported_by: Some(Uuid::new_v4()),
+        reported_by_name: Some("ครู ทดสอบ".to_string()),
+        voided_at: None,
+        void_reason: None,
+        guardian_notified_at: None,
+        evidence_file_ids: vec![Uuid::new_v4()],
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[test]
+fn incident_categories_require_negative_points() {
+    assert!(validate_category_points(BehaviorCategoryKind::Incident, -5).is_ok());
+    assert!(validate_category_points(BehaviorCategoryKind::Incident, 0).is_err());
+    assert!(validate_category_points(BehaviorCategoryKind::Incident, 5).is_err());
+}
+
+#[test]
+fn merit_categories_require_positive_points() {
+    assert!(validate_category_points(BehaviorCategoryKind::Merit, 10).is_ok());
+    assert!(validate_category_points(BehaviorCategoryKind::Merit, 0).is_err());
+    assert!(matches!(
+        validate_category_points(BehaviorCategoryKind::Merit, -1),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn thresholds_must_be_negative() {
+    assert!(validate_threshold_points(-20).is_ok());
+    assert!(validate_threshold_points(0).is_err());
+    assert!(validate_threshold_points(15).is_err());
+}
+
+#[test]
+fn reached_thresholds_include_boundary_and_order_most_severe_last() {
+    let warning = Uuid::new_v4();
+    let probation = Uuid::new_v4();
+    let severe = Uuid::new_v4();
+    let thresholds = [(severe, -60), (warning, -20), (probation, -40)];
+
+    assert_eq!(
+        reached_thresholds(-40, &thresholds),
+        vec![warning, probation]
+    );
+    assert_eq!(
+        reached_thresholds(-75, &thresholds),
+        vec![warning, probation, severe]
+    );
+}
+
+#[test]
+fn reached_thresholds_is_empty_above_every_threshold() {
+    let thresholds = [(Uuid::new_v4(), -20)];
+
+    assert!(reached_thresholds(-19, &thresholds).is_empty());
+    assert!(reached_thresholds(10, &thresholds).is_empty());
+}
+
+#[test]
+fn intervention_status_cannot_reopen_finished_work() {
+    use BehaviorInterventionStatus::{Cancelled, Completed, InProgress, Planned};
+
+    assert!(can_transition_intervention_status(Planned, InProgress));
+    assert!(can_transition_intervention_status(InProgress, Completed));
+    assert!(can_transition_intervention_status(Planned, Cancelled));
+    assert!(!can_transition_intervention_status(Completed, InProgress));
+    assert!(!can_transition_intervention_status(Cancelled, Planned));
+    assert!(!can_transition_intervention_status(InProgress, Planned));
+}
+
+#[test]
+fn guardian_notification_text_distinguishes_incidents_and_merits() {
+    let (incident_title, incident_message) =
+        guardian_notification_text(BehaviorCategoryKind::Incident, "มาสาย", -5);
+    let (merit_title, merit_message) =
+        guardian_notification_text(BehaviorCategoryKind::Merit, "จิตอาสา", 10);
+
+    assert_eq!(incident_title, "แจ้งพฤติกรรม: มาสาย");
+    assert!(incident_message.contains("(-5 คะแนน)"));
+    assert_eq!(merit_title, "ชื่นชมพฤติกรรม: จิตอาสา");
+    assert!(merit_message.contains("(+10 คะแนน)"));
+}
+
+#[test]
+fn guardian_view_hides_reporter_and_evidence() {
+    let incident = guardian_view(incident_fixture(BehaviorCategoryKind::Incident, -5));
+
+    assert!(incident.reported_by.is_none());
+    assert!(incident.reported_by_name.is_none());
+    assert!(incident.evidence_file_ids.is_empty());
+    assert_eq!(incident.points, -5);
+    assert_eq!(incident.location_note.as_deref(), Some("หน้าประตูโรงเรียน"));
+}
+
+#[test]
+fn text_helpers_trim_and_require_values() {
+    assert_eq!(
+        normalize_optional_text(Some("  ห้อง 101  ".to_string())),
+        Some("ห้อง 101".to_string())
+    );
+    assert_eq!(normalize_optional_text(Some("   ".to_string())), None);
+    assert!(required_text("  ", "กรุณาระบุ").is_err());
+    assert_eq!(required_text(" ชื่อ ", "กรุณาระบุ").unwrap(), "ชื่อ");
+}
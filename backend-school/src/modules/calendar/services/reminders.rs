@@ -267,6 +267,13 @@ pub async fn process_due_calendar_reminders_for_all_tenants(
     };
 
     for school in schools {
+        if school.is_migrating() {
+            tracing::info!(
+                "Skipping calendar reminders for {}: migration in progress",
+                school.subdomain
+            );
+            continue;
+        }
         let Some(db_url) = school
             .db_connection_string
             .filter(|value| !value.is_empty())
@@ -1453,6 +1453,7 @@ pub async fn issue_certificates(
         &context.tenant.pool,
         &context.actor,
         school_name,
+        context.tenant.plan.max_certificates_per_year,
         request_id,
         payload,
     )
@@ -19,6 +19,7 @@ use crate::{
     permissions::registry::codes,
     policies::certificate_access_policy::{require_owner_action, CertificateAction},
     scheduling::SCHOOL_TIMEZONE,
+    utils::quota::{ensure_within_quota, QuotaCode},
 };
 
 use super::{
@@ -238,10 +239,13 @@ struct CertificateDetailRow {
     updated_at: DateTime<Utc>,
 }
 
+/// `max_certificates_per_year` is the limit of the school's plan per academic
+/// year; `None` is unlimited.
 pub async fn issue_request(
     pool: &PgPool,
     actor: &ActorContext,
     school_name: String,
+    max_certificates_per_year: Option<i64>,
     request_id: Uuid,
     request: IssueCertificateRequest,
 ) -> Result<IssueCertificateOutcome, AppError> {
@@ -353,6 +357,13 @@ pub async fn issue_request(
         return Ok(outcome);
     }
 
+    ensure_certificate_quota(
+        &mut tx,
+        campaign.academic_year_id,
+        max_certificates_per_year,
+        candidates.len(),
+    )
+    .await?;
     let activity_sequence = allocate_activity_sequence(&mut tx, &mut campaign).await?;
     let (first_sequence, last_sequence) =
         allocate_certificate_range(&mut tx, &mut campaign, candidates.len()).await?;
@@ -1074,6 +1085,38 @@ async fn lock_issue_command(
     Ok(())
 }
 
+/// Revoked certificates keep their numbers, so they still count toward the limit.
+async fn ensure_certificate_quota(
+    tx: &mut Transaction<'_, Postgres>,
+    academic_year_id: Uuid,
+    max_certificates_per_year: Option<i64>,
+    requested: usize,
+) -> Result<(), AppError> {
+    if max_certificates_per_year.is_none() {
+        return Ok(());
+    }
+
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::uuid::text, 847322))")
+        .bind(academic_year_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(issuance_db_error)?;
+    let issued = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM certificates WHERE academic_year_id = $1",
+    )
+    .bind(academic_year_id)
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(issuance_db_error)?;
+
+    ensure_within_quota(
+        QuotaCode::CertificateLimitReached,
+        max_certificates_per_year,
+        issued,
+        i64::try_from(requested).unwrap_or(i64::MAX),
+    )
+}
+
 async fn fetch_issue_run(
     tx: &mut Transaction<'_, Postgres>,
     request_id: Uuid,
@@ -6,6 +6,9 @@ use zeroize::Zeroizing;
 
 use crate::{error::AppError, utils::field_encryption};
 
+/// Blind-index domain of `certificates.qr_proof_hash`.
+pub const QR_PROOF_HASH_DOMAIN: &str = "certificate-qr-proof-v1";
+
 pub struct CertificateProof {
     encrypted: String,
     hash: String,
@@ -42,7 +45,7 @@ pub fn generate_certificate_proof() -> Result<CertificateProof, AppError> {
     rand::rng().fill_bytes(&mut *bytes);
     let plaintext = Zeroizing::new(URL_SAFE_NO_PAD.encode(&*bytes));
     let encrypted = field_encryption::encrypt(&plaintext).map_err(proof_crypto_error)?;
-    let hash = field_encryption::hash_for_search_with_domain("certificate-qr-proof-v1", &plaintext)
+    let hash = field_encryption::hash_for_search_with_domain(QR_PROOF_HASH_DOMAIN, &plaintext)
         .map_err(proof_crypto_error)?;
     Ok(CertificateProof {
         encrypted,
@@ -3,7 +3,7 @@ use std::{
     net::IpAddr,
 };
 
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{NaiveDate, Utc};
 use sqlx::{FromRow, PgPool, Postgres, Transaction};
 use uuid::Uuid;
 use zeroize::Zeroizing;
@@ -29,7 +29,7 @@ use crate::{
     },
     policies::certificate_access_policy::{require_owner_action, CertificateAction},
     scheduling::SCHOOL_TIMEZONE,
-    utils::field_encryption,
+    utils::{field_encryption, pdf::thai_date},
 };
 
 use super::{
@@ -984,29 +984,6 @@ fn file_grant(file_id: Uuid, grant: DownloadGrant) -> Result<CertificateRenderFi
     }
 }
 
-fn thai_date(date: NaiveDate) -> String {
-    const MONTHS: [&str; 12] = [
-        "มกราคม",
-        "กุมภาพันธ์",
-        "มีนาคม",
-        "เมษายน",
-        "พฤษภาคม",
-        "มิถุนายน",
-        "กรกฎาคม",
-        "สิงหาคม",
-        "กันยายน",
-        "ตุลาคม",
-        "พฤศจิกายน",
-        "ธันวาคม",
-    ];
-    format!(
-        "{} {} {}",
-        date.day(),
-        MONTHS[date.month0() as usize],
-        date.year() + 543
-    )
-}
-
 fn filename_part(value: &str) -> String {
     let value = value
         .chars()
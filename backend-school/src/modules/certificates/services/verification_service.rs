@@ -19,6 +19,7 @@ use crate::{
 };
 
 use super::import_validation::{normalize_display_text, normalize_name_for_match};
+use super::proof;
 
 const GENERIC_NOT_FOUND_MESSAGE: &str = "ไม่พบข้อมูลที่ตรงกัน";
 const RECEIPT_VERSION: u8 = 1;
@@ -118,12 +119,12 @@ pub async fn verify(
             if request.proof.is_empty() || request.proof.chars().count() > MAX_PROOF_LENGTH {
                 return Err(generic_not_found());
             }
-            let proof_hash = field_encryption::hash_for_search_with_domain(
-                "certificate-qr-proof-v1",
+            let proof_hashes = field_encryption::hash_for_search_with_domain_candidates(
+                proof::QR_PROOF_HASH_DOMAIN,
                 &request.proof,
             )
             .map_err(verification_crypto_error)?;
-            load_by_number_and_proof(pool, &certificate_number, &proof_hash)
+            load_by_number_and_proof(pool, &certificate_number, &proof_hashes)
                 .await?
                 .ok_or_else(generic_not_found)?
         }
@@ -161,9 +162,9 @@ async fn load_by_number(
 async fn load_by_number_and_proof(
     pool: &PgPool,
     certificate_number: &str,
-    proof_hash: &str,
+    proof_hashes: &[String],
 ) -> Result<Option<PublicVerificationRow>, AppError> {
-    verification_query(certificate_number, Some(proof_hash))
+    verification_query(certificate_number, Some(proof_hashes))
         .fetch_optional(pool)
         .await
         .map_err(AppError::from)
@@ -171,7 +172,7 @@ async fn load_by_number_and_proof(
 
 fn verification_query<'a>(
     certificate_number: &'a str,
-    proof_hash: Option<&'a str>,
+    proof_hashes: Option<&'a [String]>,
 ) -> sqlx::query::QueryAs<'a, sqlx::Postgres, PublicVerificationRow, sqlx::postgres::PgArguments> {
     let query = sqlx::query_as::<_, PublicVerificationRow>(
         "SELECT certificate.id, certificate.status, certificate.certificate_number,
@@ -187,10 +188,10 @@ fn verification_query<'a>(
            ON replacement.id = certificate.replaced_by_certificate_id
          WHERE certificate.certificate_number = $1
            AND campaign.status <> 'purging'
-           AND ($2::text IS NULL OR certificate.qr_proof_hash = $2)",
+           AND ($2::text[] IS NULL OR certificate.qr_proof_hash = ANY($2))",
     )
     .bind(certificate_number);
-    query.bind(proof_hash)
+    query.bind(proof_hashes)
 }
 
 fn names_match(
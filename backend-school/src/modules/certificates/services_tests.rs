@@ -4683,6 +4683,7 @@ async fn purging_campaign_hides_issued_surfaces_and_blocks_issue_and_revoke() {
         &pool,
         &actor,
         "โรงเรียนตัวอย่าง".to_string(),
+        None,
         pending_request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -6322,6 +6323,7 @@ async fn issue_public_verification_fixture(
         &pool,
         &issuer,
         "โรงเรียนตัวอย่าง".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -6786,6 +6788,7 @@ async fn issue_own_certificate_fixture(test_name: &str, year: i32) -> OwnCertifi
         &pool,
         &issuer,
         "โรงเรียนตัวอย่าง".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -7607,6 +7610,7 @@ async fn issue_locks_campaign_before_request() {
             &issue_pool,
             &issuer,
             "โรงเรียนตัวอย่าง".to_string(),
+            None,
             request_id,
             IssueCertificateRequest {
                 idempotency_key: Uuid::new_v4(),
@@ -7723,6 +7727,7 @@ async fn concurrent_first_issue_allocates_distinct_campaign_and_certificate_rang
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         reviewing_requests[0].id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -7732,6 +7737,7 @@ async fn concurrent_first_issue_allocates_distinct_campaign_and_certificate_rang
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         reviewing_requests[1].id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -7839,6 +7845,7 @@ async fn draft_numbering_is_lazy_shared_across_runs_and_idempotent() {
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         first_request.id,
         IssueCertificateRequest {
             idempotency_key: first_key,
@@ -7873,6 +7880,7 @@ async fn draft_numbering_is_lazy_shared_across_runs_and_idempotent() {
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         second_request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8054,6 +8062,7 @@ async fn issue_revalidation_returns_atomically_when_converted_external_gets_an_a
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest { idempotency_key },
     )
@@ -8080,6 +8089,7 @@ async fn issue_revalidation_returns_atomically_when_converted_external_gets_an_a
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest { idempotency_key },
     )
@@ -8208,6 +8218,7 @@ async fn issue_revalidation_returns_without_numbers_when_background_is_no_longer
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8368,6 +8379,7 @@ async fn issue_revalidation_returns_when_referenced_asset_metadata_changes() {
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8454,6 +8466,7 @@ async fn revoked_numbers_are_never_reused_and_replacement_links_only_after_issua
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8543,6 +8556,7 @@ async fn revoked_numbers_are_never_reused_and_replacement_links_only_after_issua
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         replacement_request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8628,6 +8642,7 @@ async fn issued_lists_and_details_enforce_exact_read_and_separate_download_scope
         &pool,
         &issuer,
         "โรงเรียนทดสอบ".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -8872,6 +8887,7 @@ async fn issued_manifest_uses_canonical_proof_url_and_refuses_revoked_certificat
         &pool,
         &issuer,
         "โรงเรียนตัวอย่าง".to_string(),
+        None,
         request.id,
         IssueCertificateRequest {
             idempotency_key: Uuid::new_v4(),
@@ -9129,6 +9145,7 @@ async fn issuance_rejects_activity_and_certificate_sequence_upper_bounds_without
             &pool,
             &issuer,
             "โรงเรียนทดสอบ".to_string(),
+            None,
             activity_request.id,
             IssueCertificateRequest {
                 idempotency_key: Uuid::new_v4(),
@@ -9196,6 +9213,7 @@ async fn issuance_rejects_activity_and_certificate_sequence_upper_bounds_without
             &pool,
             &issuer,
             "โรงเรียนทดสอบ".to_string(),
+            None,
             certificate_request.id,
             IssueCertificateRequest {
                 idempotency_key: Uuid::new_v4(),
@@ -4,6 +4,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::utils::quota::{QuotaCode, QuotaExceeded};
 
 use super::{
     platform_service::{FilePlatform, FilePlatformError},
@@ -69,6 +70,16 @@ pub fn map_platform_error(error: FilePlatformError) -> AppError {
         FilePlatformError::MalwareDetected => {
             AppError::BadRequest("ไฟล์ไม่ผ่านการตรวจสอบความปลอดภัย".to_string())
         }
+        FilePlatformError::StorageQuotaExceeded {
+            current,
+            requested,
+            limit,
+        } => AppError::QuotaExceeded(QuotaExceeded {
+            code: QuotaCode::StorageLimitReached,
+            current,
+            requested,
+            limit,
+        }),
         FilePlatformError::NotFound => AppError::NotFound("ไม่พบไฟล์".to_string()),
         FilePlatformError::NotReady => AppError::Conflict("ไฟล์ยังไม่พร้อมใช้งาน".to_string()),
         FilePlatformError::VisibilityMismatch => {
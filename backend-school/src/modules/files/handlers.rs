@@ -111,6 +111,7 @@ pub async fn upload_file(
                 purpose,
                 display_filename,
                 bytes,
+                max_storage_bytes: context.tenant.plan.max_storage_bytes,
             },
         )
         .await
@@ -30,6 +30,8 @@ pub struct UploadCommand {
     pub purpose: FilePurpose,
     pub display_filename: String,
     pub bytes: Bytes,
+    /// Storage limit of the school's plan; `None` is unlimited
+    pub max_storage_bytes: Option<i64>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,6 +48,11 @@ pub struct DeleteOutcome {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum FilePlatformError {
     InspectionRejected,
+    StorageQuotaExceeded {
+        current: i64,
+        requested: i64,
+        limit: i64,
+    },
     MalwareDetected,
     ScannerUnavailable,
     MetadataUnavailable,
@@ -60,6 +67,7 @@ impl FilePlatformError {
     pub const fn log_safe_code(self) -> &'static str {
         match self {
             Self::InspectionRejected => "file_inspection_rejected",
+            Self::StorageQuotaExceeded { .. } => "file_storage_quota_exceeded",
             Self::MalwareDetected => "file_malware_detected",
             Self::ScannerUnavailable => "file_scanner_unavailable",
             Self::MetadataUnavailable => "file_metadata_unavailable",
@@ -162,6 +170,19 @@ impl FilePlatform {
             .collect::<Vec<_>>();
         let byte_size = i64::try_from(command.bytes.len())
             .map_err(|_| FilePlatformError::InspectionRejected)?;
+        if let Some(limit) = command.max_storage_bytes {
+            let requested = derivatives.iter().fold(byte_size, |total, derivative| {
+                total.saturating_add(derivative.metadata.byte_size)
+            });
+            let current = repository.stored_bytes().await?;
+            if current.saturating_add(requested) > limit {
+                return Err(FilePlatformError::StorageQuotaExceeded {
+                    current,
+                    requested,
+                    limit,
+                });
+            }
+        }
         let display_filename = sanitized_display_filename(&command.display_filename);
         let upload = NewUpload {
             file_id,
@@ -649,6 +670,7 @@ mod tests {
         leased_operations: Mutex<Vec<LeasedOperation>>,
         lease_durations: Mutex<Vec<Duration>>,
         retry_delays: Mutex<Vec<Duration>>,
+        stored_bytes: Mutex<i64>,
     }
 
     #[async_trait]
@@ -780,6 +802,10 @@ mod tests {
             self.events.lock().unwrap().push("delete_retry_queued");
             Ok(())
         }
+
+        async fn stored_bytes(&self) -> Result<i64, RepositoryError> {
+            Ok(*self.stored_bytes.lock().unwrap())
+        }
     }
 
     fn platform(scan: ScanOutcome, provider: Arc<FakeProvider>) -> FilePlatform {
@@ -813,9 +839,35 @@ mod tests {
             purpose,
             display_filename: "../../unsafe ชื่อ.png".to_string(),
             bytes: png(),
+            max_storage_bytes: None,
         }
     }
 
+    #[tokio::test]
+    async fn upload_past_the_storage_limit_stores_nothing() {
+        let provider = Arc::new(FakeProvider::default());
+        let repository = FakeRepository::default();
+        *repository.stored_bytes.lock().unwrap() = 1_000;
+        let mut over_limit = command(FilePurpose::SchoolLogo);
+        over_limit.max_storage_bytes = Some(1_000);
+
+        let error = platform(ScanOutcome::Clean, Arc::clone(&provider))
+            .upload(&repository, over_limit)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            FilePlatformError::StorageQuotaExceeded {
+                current: 1_000,
+                limit: 1_000,
+                ..
+            }
+        ));
+        assert!(repository.events.lock().unwrap().is_empty());
+        assert!(provider.puts.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn successful_upload_transitions_processing_to_ready() {
         let provider = Arc::new(FakeProvider::default());
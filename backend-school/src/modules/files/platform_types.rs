@@ -22,10 +22,16 @@ pub enum FilePurpose {
     CertificateTemplateBackground,
     CertificateTemplateImage,
     CertificateTemplateFont,
+    BehaviorEvidence,
+    StudentLeaveDocument,
+    StaffLeaveDocument,
+    AnnouncementFile,
+    FeePaymentSlip,
+    ProfessionalDevelopmentEvidence,
 }
 
 impl FilePurpose {
-    pub const ALL: [Self; 15] = [
+    pub const ALL: [Self; 21] = [
         Self::SchoolLogo,
         Self::SchoolBanner,
         Self::ProfileImage,
@@ -41,6 +47,12 @@ impl FilePurpose {
         Self::CertificateTemplateBackground,
         Self::CertificateTemplateImage,
         Self::CertificateTemplateFont,
+        Self::BehaviorEvidence,
+        Self::StudentLeaveDocument,
+        Self::StaffLeaveDocument,
+        Self::AnnouncementFile,
+        Self::FeePaymentSlip,
+        Self::ProfessionalDevelopmentEvidence,
     ];
 
     pub const fn code(self) -> &'static str {
@@ -60,6 +72,12 @@ impl FilePurpose {
             Self::CertificateTemplateBackground => "certificate_template_background",
             Self::CertificateTemplateImage => "certificate_template_image",
             Self::CertificateTemplateFont => "certificate_template_font",
+            Self::BehaviorEvidence => "behavior_evidence",
+            Self::StudentLeaveDocument => "student_leave_document",
+            Self::StaffLeaveDocument => "staff_leave_document",
+            Self::AnnouncementFile => "announcement_file",
+            Self::FeePaymentSlip => "fee_payment_slip",
+            Self::ProfessionalDevelopmentEvidence => "professional_development_evidence",
         }
     }
 }
@@ -18,6 +18,12 @@ pub enum PolicyKey {
     CourseworkAttachment,
     ExplicitOwningResource,
     CertificateTemplate,
+    BehaviorEvidence,
+    StudentLeaveDocument,
+    StaffLeaveDocument,
+    Announcement,
+    FeePaymentSlip,
+    ProfessionalDevelopmentEvidence,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -48,6 +54,7 @@ pub enum PurposeRegistryError {
     InvalidVersion,
     DerivativeNotAllowed,
     InvalidPersistedObjectKey,
+    InvalidSnapshotEntry,
 }
 
 /// Immutable storage identity constructed only by the purpose registry.
@@ -73,6 +80,7 @@ impl fmt::Display for PurposeRegistryError {
             Self::InvalidVersion => "file version must be positive",
             Self::DerivativeNotAllowed => "derivative is not allowed for this purpose",
             Self::InvalidPersistedObjectKey => "persisted file object key is invalid",
+            Self::InvalidSnapshotEntry => "tenant snapshot archive entry is invalid",
         })
     }
 }
@@ -91,6 +99,37 @@ const ADMISSION_CONTENT: &[DetectedContent] = &[
     DetectedContent::Png,
     DetectedContent::Pdf,
 ];
+const BEHAVIOR_EVIDENCE_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Pdf,
+];
+const STUDENT_LEAVE_DOCUMENT_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Pdf,
+];
+const STAFF_LEAVE_DOCUMENT_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Pdf,
+];
+const ANNOUNCEMENT_FILE_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Webp,
+    DetectedContent::Pdf,
+];
+const FEE_PAYMENT_SLIP_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Pdf,
+];
+const PROFESSIONAL_DEVELOPMENT_EVIDENCE_CONTENT: &[DetectedContent] = &[
+    DetectedContent::Jpeg,
+    DetectedContent::Png,
+    DetectedContent::Pdf,
+];
 const THUMBNAIL_256: &[DerivativeRecipe] = &[DerivativeRecipe::Thumbnail256Webp];
 const THUMBNAIL_1024: &[DerivativeRecipe] = &[DerivativeRecipe::Thumbnail1024Webp];
 
@@ -300,6 +339,72 @@ pub fn purpose_definition(purpose: FilePurpose) -> Result<PurposeDefinition, Pur
             retention_class: RetentionClass::Temporary,
             policy_key: PolicyKey::CertificateTemplate,
         },
+        FilePurpose::BehaviorEvidence => PurposeDefinition {
+            domain_segment: "behavior",
+            purpose_segment: "evidence",
+            visibility: FileVisibility::Private,
+            allowed_content: BEHAVIOR_EVIDENCE_CONTENT,
+            limits: image_limits(10 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::BehaviorEvidence,
+        },
+        FilePurpose::StudentLeaveDocument => PurposeDefinition {
+            domain_segment: "student-leave",
+            purpose_segment: "documents",
+            visibility: FileVisibility::Private,
+            allowed_content: STUDENT_LEAVE_DOCUMENT_CONTENT,
+            limits: image_limits(10 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::StudentLeaveDocument,
+        },
+        FilePurpose::StaffLeaveDocument => PurposeDefinition {
+            domain_segment: "staff-leave",
+            purpose_segment: "documents",
+            visibility: FileVisibility::Private,
+            allowed_content: STAFF_LEAVE_DOCUMENT_CONTENT,
+            limits: image_limits(10 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::StaffLeaveDocument,
+        },
+        FilePurpose::AnnouncementFile => PurposeDefinition {
+            domain_segment: "announcements",
+            purpose_segment: "files",
+            visibility: FileVisibility::Private,
+            allowed_content: ANNOUNCEMENT_FILE_CONTENT,
+            limits: image_limits(20 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::Announcement,
+        },
+        FilePurpose::FeePaymentSlip => PurposeDefinition {
+            domain_segment: "finance",
+            purpose_segment: "payment-slips",
+            visibility: FileVisibility::Private,
+            allowed_content: FEE_PAYMENT_SLIP_CONTENT,
+            limits: image_limits(10 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::FeePaymentSlip,
+        },
+        FilePurpose::ProfessionalDevelopmentEvidence => PurposeDefinition {
+            domain_segment: "professional-development",
+            purpose_segment: "evidence",
+            visibility: FileVisibility::Private,
+            allowed_content: PROFESSIONAL_DEVELOPMENT_EVIDENCE_CONTENT,
+            limits: image_limits(20 * 1024 * 1024, 4096, 4096),
+            scan_requirement: ScanRequirement::RequiredClean,
+            derivatives: &[],
+            retention_class: RetentionClass::Temporary,
+            policy_key: PolicyKey::ProfessionalDevelopmentEvidence,
+        },
     };
 
     Ok(definition)
@@ -396,6 +501,44 @@ pub(crate) fn persisted_object_key(
     Ok(ObjectKey(value, storage_class))
 }
 
+/// Moves a persisted tenant key to another tenant while keeping the rest of the
+/// server-generated path. Used only when a tenant snapshot is restored elsewhere.
+pub(crate) fn retarget_object_key(
+    key: &ObjectKey,
+    tenant_id: Uuid,
+) -> Result<ObjectKey, PurposeRegistryError> {
+    let mut segments = key.as_str().split('/').collect::<Vec<_>>();
+    let tenant_segment = tenant_id.to_string();
+    segments[1] = &tenant_segment;
+    persisted_object_key(segments.join("/"), key.storage_class())
+}
+
+/// Archive entry of a tenant snapshot. Snapshots are always private and live
+/// outside the `tenants/` prefix so tenant file cleanup never touches them.
+pub(crate) fn tenant_snapshot_object_key(
+    tenant_id: Uuid,
+    snapshot_id: Uuid,
+    entry: &str,
+) -> Result<ObjectKey, PurposeRegistryError> {
+    if entry.is_empty()
+        || entry.len() > 512
+        || !entry.is_ascii()
+        || entry.bytes().any(|byte| {
+            !(byte.is_ascii_alphanumeric() || matches!(byte, b'/' | b'.' | b'-' | b'_'))
+        })
+        || entry
+            .split('/')
+            .any(|segment| segment.is_empty() || segment.starts_with('.'))
+    {
+        return Err(PurposeRegistryError::InvalidSnapshotEntry);
+    }
+
+    Ok(ObjectKey(
+        format!("snapshots/{tenant_id}/{snapshot_id}/{entry}"),
+        StorageClass::Private,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -696,7 +839,7 @@ mod tests {
             assert_eq!(definition.policy_key, PolicyKey::CertificateTemplate);
         }
 
-        assert_eq!(FilePurpose::ALL.len(), 15);
+        assert_eq!(FilePurpose::ALL.len(), 21);
     }
 
     #[test]
@@ -864,4 +1007,50 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn snapshot_keys_are_private_and_reject_traversal() {
+        let tenant = Uuid::parse_str("11111111-1111-1111-1111-111111111111").unwrap();
+        let snapshot = Uuid::parse_str("33333333-3333-3333-3333-333333333333").unwrap();
+
+        let key = tenant_snapshot_object_key(tenant, snapshot, "tables/users/000001.jsonl")
+            .expect("table part entry should be accepted");
+        assert_eq!(
+            key.as_str(),
+            "snapshots/11111111-1111-1111-1111-111111111111/33333333-3333-3333-3333-333333333333/tables/users/000001.jsonl"
+        );
+        assert_eq!(key.storage_class(), StorageClass::Private);
+
+        for invalid in [
+            "",
+            "../manifest.json",
+            "tables//users",
+            "tables/.hidden",
+            "ไฟล์",
+        ] {
+            assert_eq!(
+                tenant_snapshot_object_key(tenant, snapshot, invalid),
+                Err(PurposeRegistryError::InvalidSnapshotEntry),
+                "invalid snapshot entry should be rejected: {invalid}",
+            );
+        }
+    }
+
+    #[test]
+    fn retargeted_keys_only_change_the_tenant_segment() {
+        let source = persisted_object_key(
+            "tenants/11111111-1111-1111-1111-111111111111/identity/profile-image/22222222-2222-2222-2222-222222222222/v1/original.png".to_string(),
+            StorageClass::Public,
+        )
+        .unwrap();
+        let target = Uuid::parse_str("44444444-4444-4444-4444-444444444444").unwrap();
+
+        let moved = retarget_object_key(&source, target).unwrap();
+
+        assert_eq!(
+            moved.as_str(),
+            "tenants/44444444-4444-4444-4444-444444444444/identity/profile-image/22222222-2222-2222-2222-222222222222/v1/original.png"
+        );
+        assert_eq!(moved.storage_class(), StorageClass::Public);
+    }
 }
@@ -221,6 +221,8 @@ pub trait FileRepository: Send + Sync {
         error_code: &'static str,
         retry_delay: Duration,
     ) -> Result<(), RepositoryError>;
+    /// Bytes of all originals and derivatives not yet deleted from storage
+    async fn stored_bytes(&self) -> Result<i64, RepositoryError>;
 }
 
 #[derive(Clone)]
@@ -1168,6 +1170,20 @@ VALUES (
         .map_err(Self::database_error)?;
         Ok(())
     }
+
+    async fn stored_bytes(&self) -> Result<i64, RepositoryError> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+SELECT (
+    COALESCE((SELECT SUM(byte_size) FROM file_versions WHERE storage_status <> 'deleted'), 0)
+    + COALESCE((SELECT SUM(byte_size) FROM file_derivatives WHERE storage_status <> 'deleted'), 0)
+)::BIGINT
+"#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Self::database_error)
+    }
 }
 
 async fn mark_operation_succeeded(
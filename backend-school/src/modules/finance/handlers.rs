@@ -0,0 +1,338 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::finance::models::{
+    CreateFeeInvoiceBatchRequest, CreateStudentFeeDiscountRequest, FeeInvoiceBatchFilter,
+    FeeInvoiceFilter, FeeItemFilter, FeePayment, FeePaymentFilter, ReconciliationQuery,
+    RecordFeePaymentRequest, RejectFeePaymentRequest, ReplaceFeeItemRatesRequest,
+    StudentFeeDiscountFilter, SubmitFeePaymentRequest, UpdateFinanceSettingsRequest,
+    UpsertFeeItemRequest, VoidFeeInvoiceRequest,
+};
+use crate::modules::finance::services;
+use crate::utils::request_context::{actor_tenant_context_from_session, ActorTenantContext};
+use crate::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+/// Lets the guardian who reported a transfer know the outcome. A failed
+/// notification does not undo the review.
+async fn notify_reviewed(state: &AppState, context: &ActorTenantContext, payment: &FeePayment) {
+    if let Err(error) = services::notify_payment_reviewed(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+        payment,
+    )
+    .await
+    {
+        tracing::error!(
+            fee_payment_id = %payment.id,
+            error = %error,
+            "Failed to notify guardian about fee payment review"
+        );
+    }
+}
+
+async fn get_settings(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let settings = services::get_settings(&context.tenant.pool, &context.actor).await?;
+    Ok(Json(ApiResponse::ok(settings)))
+}
+
+async fn update_settings(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<UpdateFinanceSettingsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let settings = services::update_settings(&context.tenant.pool, &context.actor, payload).await?;
+    Ok(Json(ApiResponse::ok(settings)))
+}
+
+async fn list_fee_items(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<FeeItemFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_fee_items(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_fee_item(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<UpsertFeeItemRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let item = services::create_fee_item(&context.tenant.pool, &context.actor, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(item))))
+}
+
+async fn update_fee_item(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpsertFeeItemRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let item = services::update_fee_item(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok(Json(ApiResponse::ok(item)))
+}
+
+async fn delete_fee_item(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    services::delete_fee_item(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::empty()))
+}
+
+/// PUT /api/finance/fee-items/:id/rates - กำหนดอัตราค่าธรรมเนียมตามระดับชั้นของปีการศึกษา
+async fn replace_fee_item_rates(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReplaceFeeItemRatesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let item =
+        services::replace_fee_item_rates(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok(Json(ApiResponse::ok(item)))
+}
+
+async fn list_discounts(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<StudentFeeDiscountFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_discounts(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_discount(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<CreateStudentFeeDiscountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let discount = services::create_discount(&context.tenant.pool, &context.actor, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(discount))))
+}
+
+async fn delete_discount(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    services::delete_discount(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::empty()))
+}
+
+async fn list_batches(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<FeeInvoiceBatchFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_batches(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// POST /api/finance/batches - สร้างชุดใบแจ้งหนี้และออกใบแจ้งหนี้ให้นักเรียนในระดับชั้นที่เลือก
+async fn create_batch(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<CreateFeeInvoiceBatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let result = services::create_batch(&context.tenant.pool, &context.actor, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(result))))
+}
+
+async fn get_batch(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let batch = services::get_batch(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(batch)))
+}
+
+/// POST /api/finance/batches/:id/generate - ออกใบแจ้งหนี้ให้นักเรียนที่ยังไม่มีในชุดนี้
+async fn generate_batch_invoices(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let result =
+        services::generate_batch_invoices(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(result)))
+}
+
+async fn list_invoices(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<FeeInvoiceFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_invoices(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn get_invoice(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let invoice = services::get_invoice(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(invoice)))
+}
+
+async fn void_invoice(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<VoidFeeInvoiceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let invoice = services::void_invoice(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok(Json(ApiResponse::ok(invoice)))
+}
+
+/// POST /api/finance/invoices/:id/payments - รับชำระเงินที่ฝ่ายการเงินและออกใบเสร็จ
+async fn record_payment(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RecordFeePaymentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let payment =
+        services::record_payment(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(payment))))
+}
+
+async fn list_payments(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<FeePaymentFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_payments(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn confirm_payment(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let payment = services::confirm_payment(&context.tenant.pool, &context.actor, id).await?;
+    notify_reviewed(&state, &context, &payment).await;
+    Ok(Json(ApiResponse::ok(payment)))
+}
+
+async fn reject_payment(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RejectFeePaymentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let payment =
+        services::reject_payment(&context.tenant.pool, &context.actor, id, payload).await?;
+    notify_reviewed(&state, &context, &payment).await;
+    Ok(Json(ApiResponse::ok(payment)))
+}
+
+/// GET /api/finance/receipts/:id - ข้อมูลใบเสร็จสำหรับสร้าง PDF
+async fn get_receipt(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let receipt = services::get_receipt_document(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(receipt)))
+}
+
+/// GET /api/finance/reconciliation - สรุปใบเสร็จรายวันสำหรับกระทบยอด
+async fn get_reconciliation(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<ReconciliationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let reconciliation =
+        services::daily_reconciliation(&context.tenant.pool, &context.actor, query).await?;
+    Ok(Json(ApiResponse::ok(reconciliation)))
+}
+
+/// POST /api/finance/guardian/invoices/:id/payments - ผู้ปกครองแจ้งโอนเงินพร้อมสลิป
+async fn submit_guardian_payment(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SubmitFeePaymentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let payment =
+        services::submit_guardian_payment(&context.tenant.pool, context.actor.user_id, id, payload)
+            .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(payment))))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/settings", get(get_settings).put(update_settings))
+        .route("/fee-items", get(list_fee_items).post(create_fee_item))
+        .route(
+            "/fee-items/{id}",
+            put(update_fee_item).delete(delete_fee_item),
+        )
+        .route("/fee-items/{id}/rates", put(replace_fee_item_rates))
+        .route("/discounts", get(list_discounts).post(create_discount))
+        .route("/discounts/{id}", delete(delete_discount))
+        .route("/batches", get(list_batches).post(create_batch))
+        .route("/batches/{id}", get(get_batch))
+        .route("/batches/{id}/generate", post(generate_batch_invoices))
+        .route("/invoices", get(list_invoices))
+        .route("/invoices/{id}", get(get_invoice))
+        .route("/invoices/{id}/void", post(void_invoice))
+        .route("/invoices/{id}/payments", post(record_payment))
+        .route("/payments", get(list_payments))
+        .route("/payments/{id}/confirm", post(confirm_payment))
+        .route("/payments/{id}/reject", post(reject_payment))
+        .route("/receipts/{id}", get(get_receipt))
+        .route("/reconciliation", get(get_reconciliation))
+        .route(
+            "/guardian/invoices/{id}/payments",
+            post(submit_guardian_payment),
+        )
+}
@@ -0,0 +1,593 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptPayTargetType {
+    Phone,
+    TaxId,
+    Ewallet,
+}
+
+impl PromptPayTargetType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Phone => "phone",
+            Self::TaxId => "tax_id",
+            Self::Ewallet => "ewallet",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "phone" => Some(Self::Phone),
+            "tax_id" => Some(Self::TaxId),
+            "ewallet" => Some(Self::Ewallet),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeCategory {
+    Tuition,
+    Activity,
+    Exam,
+    Other,
+}
+
+impl FeeCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tuition => "tuition",
+            Self::Activity => "activity",
+            Self::Exam => "exam",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "tuition" => Some(Self::Tuition),
+            "activity" => Some(Self::Activity),
+            "exam" => Some(Self::Exam),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeDiscountKind {
+    Discount,
+    Scholarship,
+}
+
+impl FeeDiscountKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Discount => "discount",
+            Self::Scholarship => "scholarship",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "discount" => Some(Self::Discount),
+            "scholarship" => Some(Self::Scholarship),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeInvoiceStatus {
+    Open,
+    PartiallyPaid,
+    Paid,
+    Void,
+}
+
+impl FeeInvoiceStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::PartiallyPaid => "partially_paid",
+            Self::Paid => "paid",
+            Self::Void => "void",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "partially_paid" => Some(Self::PartiallyPaid),
+            "paid" => Some(Self::Paid),
+            "void" => Some(Self::Void),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeInvoiceLineKind {
+    Charge,
+    Discount,
+}
+
+impl FeeInvoiceLineKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Charge => "charge",
+            Self::Discount => "discount",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "charge" => Some(Self::Charge),
+            "discount" => Some(Self::Discount),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePaymentMethod {
+    Cash,
+    BankTransfer,
+    Promptpay,
+}
+
+impl FeePaymentMethod {
+    pub const ALL: [Self; 3] = [Self::Cash, Self::BankTransfer, Self::Promptpay];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cash => "cash",
+            Self::BankTransfer => "bank_transfer",
+            Self::Promptpay => "promptpay",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "cash" => Some(Self::Cash),
+            "bank_transfer" => Some(Self::BankTransfer),
+            "promptpay" => Some(Self::Promptpay),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePaymentStatus {
+    PendingReview,
+    Confirmed,
+    Rejected,
+}
+
+impl FeePaymentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PendingReview => "pending_review",
+            Self::Confirmed => "confirmed",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "pending_review" => Some(Self::PendingReview),
+            "confirmed" => Some(Self::Confirmed),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinanceSettings {
+    pub payee_name: String,
+    pub promptpay_target_type: Option<PromptPayTargetType>,
+    pub promptpay_target: Option<String>,
+    pub receipt_prefix: String,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFinanceSettingsRequest {
+    pub payee_name: String,
+    pub promptpay_target_type: Option<PromptPayTargetType>,
+    pub promptpay_target: Option<String>,
+    pub receipt_prefix: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeItemRate {
+    pub id: Uuid,
+    pub academic_year_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub academic_semester_name: Option<String>,
+    pub grade_level_id: Uuid,
+    pub grade_level_name: String,
+    pub amount_satang: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeItem {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub category: FeeCategory,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub sort_order: i32,
+    pub rates: Vec<FeeItemRate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertFeeItemRequest {
+    pub code: String,
+    pub name: String,
+    pub category: FeeCategory,
+    pub description: Option<String>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeItemFilter {
+    /// Limits the embedded rates to one academic year.
+    pub academic_year_id: Option<Uuid>,
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeItemRateInput {
+    pub grade_level_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub amount_satang: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceFeeItemRatesRequest {
+    pub academic_year_id: Uuid,
+    #[serde(default)]
+    pub rates: Vec<FeeItemRateInput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentFeeDiscount {
+    pub id: Uuid,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub academic_year_id: Uuid,
+    pub fee_item_id: Option<Uuid>,
+    pub fee_item_name: Option<String>,
+    pub discount_kind: FeeDiscountKind,
+    pub name: String,
+    pub percent_basis_points: Option<i32>,
+    pub amount_satang: Option<i64>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStudentFeeDiscountRequest {
+    pub student_id: Uuid,
+    pub academic_year_id: Uuid,
+    pub fee_item_id: Option<Uuid>,
+    pub discount_kind: FeeDiscountKind,
+    pub name: String,
+    pub percent_basis_points: Option<i32>,
+    pub amount_satang: Option<i64>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentFeeDiscountFilter {
+    pub student_id: Option<Uuid>,
+    pub academic_year_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceBatch {
+    pub id: Uuid,
+    pub academic_year_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub title: String,
+    pub due_date: NaiveDate,
+    pub grade_level_ids: Vec<Uuid>,
+    pub invoice_count: i64,
+    pub total_satang: i64,
+    pub paid_satang: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFeeInvoiceBatchRequest {
+    pub academic_year_id: Uuid,
+    pub academic_semester_id: Option<Uuid>,
+    pub title: String,
+    pub due_date: NaiveDate,
+    pub grade_level_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceBatchFilter {
+    pub academic_year_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceGenerationResult {
+    pub batch: FeeInvoiceBatch,
+    pub created_count: usize,
+    /// Students who already hold an invoice in this batch.
+    pub existing_count: usize,
+    /// Enrolled students whose grade level has no rate in the batch period.
+    pub without_charges_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceLine {
+    pub id: Uuid,
+    pub line_kind: FeeInvoiceLineKind,
+    #[schema(required = true)]
+    pub fee_item_id: Option<Uuid>,
+    pub description: String,
+    pub amount_satang: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceSummary {
+    pub id: Uuid,
+    pub invoice_number: String,
+    pub batch_id: Uuid,
+    pub batch_title: String,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub student_code: Option<String>,
+    pub class_room_id: Option<Uuid>,
+    pub class_room_name: Option<String>,
+    pub status: FeeInvoiceStatus,
+    pub total_satang: i64,
+    pub paid_satang: i64,
+    pub outstanding_satang: i64,
+    pub due_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoice {
+    #[serde(flatten)]
+    pub summary: FeeInvoiceSummary,
+    pub subtotal_satang: i64,
+    pub discount_satang: i64,
+    pub lines: Vec<FeeInvoiceLine>,
+    pub payments: Vec<FeePayment>,
+    /// EMVCo PromptPay payload for the outstanding amount; rendered as a QR
+    /// code by the client.
+    pub promptpay_payload: Option<String>,
+    pub void_reason: Option<String>,
+    pub voided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInvoiceFilter {
+    pub batch_id: Option<Uuid>,
+    pub class_room_id: Option<Uuid>,
+    pub student_id: Option<Uuid>,
+    pub status: Option<FeeInvoiceStatus>,
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoidFeeInvoiceRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePayment {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    pub method: FeePaymentMethod,
+    pub status: FeePaymentStatus,
+    pub amount_satang: i64,
+    pub paid_at: DateTime<Utc>,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    pub submitted_by: Option<Uuid>,
+    pub submitted_by_name: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub slip_file_ids: Vec<Uuid>,
+    pub receipt_id: Option<Uuid>,
+    pub receipt_number: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePaymentFilter {
+    pub status: Option<FeePaymentStatus>,
+    pub invoice_id: Option<Uuid>,
+}
+
+/// Payment taken at the finance desk; it is confirmed and receipted at once.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordFeePaymentRequest {
+    pub method: FeePaymentMethod,
+    pub amount_satang: i64,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub slip_file_ids: Vec<Uuid>,
+}
+
+/// Transfer reported by a guardian; it waits for cashier review.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitFeePaymentRequest {
+    pub method: FeePaymentMethod,
+    pub amount_satang: i64,
+    pub paid_at: DateTime<Utc>,
+    pub reference: Option<String>,
+    pub slip_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectFeePaymentRequest {
+    pub reason: String,
+}
+
+/// Everything the client needs to render a numbered PDF receipt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeReceiptDocument {
+    pub id: Uuid,
+    pub receipt_number: String,
+    pub issued_at: DateTime<Utc>,
+    pub payee_name: String,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub student_code: Option<String>,
+    pub class_room_name: Option<String>,
+    pub invoice_id: Uuid,
+    pub invoice_number: String,
+    pub batch_title: String,
+    pub method: FeePaymentMethod,
+    pub reference: Option<String>,
+    pub paid_at: DateTime<Utc>,
+    pub amount_satang: i64,
+    pub amount_text: String,
+    pub lines: Vec<FeeInvoiceLine>,
+    pub invoice_total_satang: i64,
+    pub outstanding_satang: i64,
+    pub issued_by_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationQuery {
+    /// School-local day; defaults to today.
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationEntry {
+    pub receipt_id: Uuid,
+    pub receipt_number: String,
+    pub issued_at: DateTime<Utc>,
+    pub invoice_number: String,
+    pub student_name: String,
+    pub student_code: Option<String>,
+    pub class_room_name: Option<String>,
+    pub method: FeePaymentMethod,
+    pub reference: Option<String>,
+    pub amount_satang: i64,
+    pub issued_by_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationMethodTotal {
+    pub method: FeePaymentMethod,
+    pub receipt_count: usize,
+    pub amount_satang: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyReconciliation {
+    pub date: NaiveDate,
+    pub entries: Vec<ReconciliationEntry>,
+    pub totals: Vec<ReconciliationMethodTotal>,
+    pub receipt_count: usize,
+    pub total_satang: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildFeePayment {
+    pub id: Uuid,
+    pub method: FeePaymentMethod,
+    pub status: FeePaymentStatus,
+    pub amount_satang: i64,
+    pub paid_at: DateTime<Utc>,
+    #[schema(required = true)]
+    pub rejection_reason: Option<String>,
+    #[schema(required = true)]
+    pub receipt_id: Option<Uuid>,
+    #[schema(required = true)]
+    pub receipt_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildFeeInvoice {
+    pub id: Uuid,
+    pub invoice_number: String,
+    pub title: String,
+    pub status: FeeInvoiceStatus,
+    pub due_date: NaiveDate,
+    pub total_satang: i64,
+    pub paid_satang: i64,
+    pub outstanding_satang: i64,
+    /// Pending guardian transfers not yet reviewed by the school.
+    pub pending_satang: i64,
+    #[schema(required = true)]
+    pub promptpay_payload: Option<String>,
+    pub lines: Vec<FeeInvoiceLine>,
+    pub payments: Vec<ChildFeePayment>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildFeeStatement {
+    pub student_id: Uuid,
+    pub outstanding_satang: i64,
+    pub overdue_satang: i64,
+    pub invoices: Vec<ChildFeeInvoice>,
+}
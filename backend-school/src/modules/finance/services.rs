@@ -0,0 +1,52 @@
+mod catalog;
+mod guardian;
+mod invoicing;
+mod notifications;
+mod payments;
+mod promptpay;
+mod reconciliation;
+mod records;
+mod shared;
+
+#[cfg(test)]
+mod tests;
+
+pub use catalog::{
+    create_discount, create_fee_item, delete_discount, delete_fee_item, get_settings,
+    list_discounts, list_fee_items, replace_fee_item_rates, update_fee_item, update_settings,
+};
+pub use guardian::child_fee_statement;
+pub use invoicing::{
+    create_batch, generate_batch_invoices, get_batch, get_invoice, list_batches, list_invoices,
+    void_invoice,
+};
+pub use notifications::notify_payment_reviewed;
+pub use payments::{
+    confirm_payment, get_receipt_document, list_payments, record_payment, reject_payment,
+    submit_guardian_payment,
+};
+#[allow(unused_imports)]
+pub use promptpay::{crc16_ccitt, format_promptpay_amount, promptpay_payload};
+pub use reconciliation::daily_reconciliation;
+#[allow(unused_imports)]
+pub use shared::{
+    buddhist_year, format_baht, format_document_number, is_overdue, normalize_fee_code,
+    normalize_promptpay_target, normalize_receipt_prefix, payment_review_notification_text,
+    reconciliation_totals, thai_baht_text, validate_discount_value,
+    validate_guardian_payment_method, validate_payment_amount, validate_rates,
+};
+
+#[cfg(test)]
+use chrono::NaiveDate;
+#[cfg(test)]
+use uuid::Uuid;
+
+#[cfg(test)]
+use crate::error::AppError;
+#[cfg(test)]
+use crate::modules::finance::models::{
+    FeeDiscountKind, FeeInvoiceLineKind, FeeItemRateInput, FeePaymentMethod, FeePaymentStatus,
+    PromptPayTargetType,
+};
+#[cfg(test)]
+use shared::{compute_invoice, FeeCharge, FeeDiscountRule};
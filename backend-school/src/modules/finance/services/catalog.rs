@@ -0,0 +1,577 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::finance::models::{
+    CreateStudentFeeDiscountRequest, FeeItem, FeeItemFilter, FeeItemRate, FinanceSettings,
+    ReplaceFeeItemRatesRequest, StudentFeeDiscount, StudentFeeDiscountFilter,
+    UpdateFinanceSettingsRequest, UpsertFeeItemRequest,
+};
+use crate::policies::finance_access_policy::{require_finance_manage, require_finance_read};
+
+use super::records::{load_settings, read_error, write_error};
+use super::shared::{
+    normalize_fee_code, normalize_promptpay_target, normalize_receipt_prefix, optional_text,
+    parse_category, parse_discount_kind, required_text, validate_discount_value, validate_rates,
+    FEE_ITEM_NOT_FOUND_MESSAGE,
+};
+
+const GRADE_LEVEL_LABEL: &str = r#"
+    CASE grade_level.level_type
+        WHEN 'kindergarten' THEN CONCAT('อ.', grade_level.year)
+        WHEN 'primary' THEN CONCAT('ป.', grade_level.year)
+        WHEN 'secondary' THEN CONCAT('ม.', grade_level.year)
+        ELSE CONCAT('?.', grade_level.year)
+    END
+"#;
+
+pub async fn get_settings(
+    pool: &PgPool,
+    actor: &ActorContext,
+) -> Result<FinanceSettings, AppError> {
+    require_finance_read(actor)?;
+    load_settings(pool).await
+}
+
+pub async fn update_settings(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: UpdateFinanceSettingsRequest,
+) -> Result<FinanceSettings, AppError> {
+    require_finance_manage(actor)?;
+    let payee_name = required_text(&payload.payee_name, "ชื่อผู้รับเงิน")?;
+    let receipt_prefix = normalize_receipt_prefix(&payload.receipt_prefix)?;
+    let promptpay_target = match (payload.promptpay_target_type, payload.promptpay_target) {
+        (Some(target_type), Some(target)) => {
+            Some(normalize_promptpay_target(target_type, &target)?)
+        }
+        (None, None) => None,
+        _ => {
+            return Err(AppError::ValidationError(
+                "กรุณาระบุประเภทและหมายเลขพร้อมเพย์ให้ครบ".to_string(),
+            ))
+        }
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO finance_settings (
+            id, payee_name, promptpay_target_type, promptpay_target, receipt_prefix, updated_by
+        )
+        VALUES (true, $1, $2, $3, $4, $5)
+        ON CONFLICT (id) DO UPDATE
+        SET payee_name = EXCLUDED.payee_name,
+            promptpay_target_type = EXCLUDED.promptpay_target_type,
+            promptpay_target = EXCLUDED.promptpay_target,
+            receipt_prefix = EXCLUDED.receipt_prefix,
+            updated_by = EXCLUDED.updated_by
+        "#,
+    )
+    .bind(&payee_name)
+    .bind(
+        payload
+            .promptpay_target_type
+            .map(|target_type| target_type.as_str()),
+    )
+    .bind(&promptpay_target)
+    .bind(&receipt_prefix)
+    .bind(actor.user_id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+
+    load_settings(pool).await
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct FeeItemRow {
+    id: Uuid,
+    code: String,
+    name: String,
+    category: String,
+    description: Option<String>,
+    is_active: bool,
+    sort_order: i32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RateRow {
+    id: Uuid,
+    fee_item_id: Uuid,
+    academic_year_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+    academic_semester_name: Option<String>,
+    grade_level_id: Uuid,
+    grade_level_name: String,
+    amount_satang: i64,
+}
+
+async fn load_rates(
+    pool: &PgPool,
+    fee_item_ids: &[Uuid],
+    academic_year_id: Option<Uuid>,
+) -> Result<HashMap<Uuid, Vec<FeeItemRate>>, AppError> {
+    let rows = sqlx::query_as::<_, RateRow>(&format!(
+        r#"
+        SELECT rate.id,
+               rate.fee_item_id,
+               rate.academic_year_id,
+               rate.academic_semester_id,
+               semester.name AS academic_semester_name,
+               rate.grade_level_id,
+               {GRADE_LEVEL_LABEL} AS grade_level_name,
+               rate.amount_satang
+        FROM fee_item_rates rate
+        JOIN grade_levels grade_level ON grade_level.id = rate.grade_level_id
+        LEFT JOIN academic_semesters semester ON semester.id = rate.academic_semester_id
+        WHERE rate.fee_item_id = ANY($1)
+          AND ($2::uuid IS NULL OR rate.academic_year_id = $2)
+        ORDER BY grade_level.level_type, grade_level.year, semester.start_date NULLS FIRST
+        "#
+    ))
+    .bind(fee_item_ids)
+    .bind(academic_year_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut rates: HashMap<Uuid, Vec<FeeItemRate>> = HashMap::new();
+    for row in rows {
+        rates.entry(row.fee_item_id).or_default().push(FeeItemRate {
+            id: row.id,
+            academic_year_id: row.academic_year_id,
+            academic_semester_id: row.academic_semester_id,
+            academic_semester_name: row.academic_semester_name,
+            grade_level_id: row.grade_level_id,
+            grade_level_name: row.grade_level_name,
+            amount_satang: row.amount_satang,
+        });
+    }
+    Ok(rates)
+}
+
+fn fee_item_from_row(row: FeeItemRow, rates: Vec<FeeItemRate>) -> Result<FeeItem, AppError> {
+    Ok(FeeItem {
+        id: row.id,
+        code: row.code,
+        name: row.name,
+        category: parse_category(&row.category)?,
+        description: row.description,
+        is_active: row.is_active,
+        sort_order: row.sort_order,
+        rates,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+const FEE_ITEM_SELECT: &str = r#"
+    SELECT id, code, name, category, description, is_active, sort_order, created_at, updated_at
+    FROM fee_items
+"#;
+
+async fn load_fee_item(
+    pool: &PgPool,
+    id: Uuid,
+    academic_year_id: Option<Uuid>,
+) -> Result<FeeItem, AppError> {
+    let row = sqlx::query_as::<_, FeeItemRow>(&format!("{FEE_ITEM_SELECT} WHERE id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .ok_or_else(|| AppError::NotFound(FEE_ITEM_NOT_FOUND_MESSAGE.to_string()))?;
+    let rates = load_rates(pool, &[id], academic_year_id)
+        .await?
+        .remove(&id)
+        .unwrap_or_default();
+    fee_item_from_row(row, rates)
+}
+
+pub async fn list_fee_items(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: FeeItemFilter,
+) -> Result<Vec<FeeItem>, AppError> {
+    require_finance_read(actor)?;
+    let rows = sqlx::query_as::<_, FeeItemRow>(&format!(
+        r#"
+        {FEE_ITEM_SELECT}
+        WHERE ($1 OR is_active = true)
+        ORDER BY sort_order, code
+        "#
+    ))
+    .bind(filter.include_inactive)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut rates = load_rates(pool, &ids, filter.academic_year_id).await?;
+    rows.into_iter()
+        .map(|row| {
+            let item_rates = rates.remove(&row.id).unwrap_or_default();
+            fee_item_from_row(row, item_rates)
+        })
+        .collect()
+}
+
+fn fee_item_write_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(database_error) = &error {
+        if database_error.constraint() == Some("fee_items_code_unique") {
+            return AppError::Conflict("รหัสค่าธรรมเนียมนี้มีอยู่แล้ว".to_string());
+        }
+    }
+    write_error(error)
+}
+
+pub async fn create_fee_item(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: UpsertFeeItemRequest,
+) -> Result<FeeItem, AppError> {
+    require_finance_manage(actor)?;
+    let code = normalize_fee_code(&payload.code)?;
+    let name = required_text(&payload.name, "ชื่อรายการค่าธรรมเนียม")?;
+    let description = optional_text(payload.description)?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO fee_items (
+            code, name, category, description, is_active, sort_order, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+    )
+    .bind(&code)
+    .bind(&name)
+    .bind(payload.category.as_str())
+    .bind(&description)
+    .bind(payload.is_active)
+    .bind(payload.sort_order)
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(fee_item_write_error)?;
+
+    load_fee_item(pool, id, None).await
+}
+
+pub async fn update_fee_item(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: UpsertFeeItemRequest,
+) -> Result<FeeItem, AppError> {
+    require_finance_manage(actor)?;
+    let code = normalize_fee_code(&payload.code)?;
+    let name = required_text(&payload.name, "ชื่อรายการค่าธรรมเนียม")?;
+    let description = optional_text(payload.description)?;
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE fee_items
+        SET code = $2,
+            name = $3,
+            category = $4,
+            description = $5,
+            is_active = $6,
+            sort_order = $7
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&code)
+    .bind(&name)
+    .bind(payload.category.as_str())
+    .bind(&description)
+    .bind(payload.is_active)
+    .bind(payload.sort_order)
+    .execute(pool)
+    .await
+    .map_err(fee_item_write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(FEE_ITEM_NOT_FOUND_MESSAGE.to_string()));
+    }
+
+    load_fee_item(pool, id, None).await
+}
+
+/// Items already billed stay for the invoice history; deactivate them instead.
+pub async fn delete_fee_item(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<(), AppError> {
+    require_finance_manage(actor)?;
+    let billed = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM fee_invoice_lines WHERE fee_item_id = $1)",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+    if billed {
+        return Err(AppError::Conflict(
+            "รายการนี้ถูกใช้ในใบแจ้งหนี้แล้ว ให้ปิดการใช้งานแทนการลบ".to_string(),
+        ));
+    }
+
+    let deleted = sqlx::query("DELETE FROM fee_items WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(write_error)?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound(FEE_ITEM_NOT_FOUND_MESSAGE.to_string()));
+    }
+    Ok(())
+}
+
+/// Replaces one academic year's rates for an item. Invoices already issued
+/// keep the amounts they were generated with.
+pub async fn replace_fee_item_rates(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: ReplaceFeeItemRatesRequest,
+) -> Result<FeeItem, AppError> {
+    require_finance_manage(actor)?;
+    validate_rates(&payload.rates)?;
+    load_fee_item(pool, id, None).await?;
+
+    let grade_level_ids: Vec<Uuid> = payload
+        .rates
+        .iter()
+        .map(|rate| rate.grade_level_id)
+        .collect();
+    let semester_ids: Vec<Option<Uuid>> = payload
+        .rates
+        .iter()
+        .map(|rate| rate.academic_semester_id)
+        .collect();
+    let amounts: Vec<i64> = payload
+        .rates
+        .iter()
+        .map(|rate| rate.amount_satang)
+        .collect();
+
+    let invalid_semesters = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM UNNEST($1::uuid[]) AS item(academic_semester_id)
+        LEFT JOIN academic_semesters semester
+          ON semester.id = item.academic_semester_id AND semester.academic_year_id = $2
+        WHERE item.academic_semester_id IS NOT NULL AND semester.id IS NULL
+        "#,
+    )
+    .bind(&semester_ids)
+    .bind(payload.academic_year_id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+    if invalid_semesters > 0 {
+        return Err(AppError::ValidationError(
+            "ภาคเรียนต้องอยู่ในปีการศึกษาที่เลือก".to_string(),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    sqlx::query("DELETE FROM fee_item_rates WHERE fee_item_id = $1 AND academic_year_id = $2")
+        .bind(id)
+        .bind(payload.academic_year_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(write_error)?;
+    sqlx::query(
+        r#"
+        INSERT INTO fee_item_rates (
+            fee_item_id, academic_year_id, grade_level_id, academic_semester_id, amount_satang
+        )
+        SELECT $1, $2, item.grade_level_id, item.academic_semester_id, item.amount_satang
+        FROM UNNEST($3::uuid[], $4::uuid[], $5::bigint[])
+            AS item(grade_level_id, academic_semester_id, amount_satang)
+        "#,
+    )
+    .bind(id)
+    .bind(payload.academic_year_id)
+    .bind(&grade_level_ids)
+    .bind(&semester_ids)
+    .bind(&amounts)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|error| {
+        if let sqlx::Error::Database(database_error) = &error {
+            if database_error.code().as_deref() == Some("23503") {
+                return AppError::ValidationError("ไม่พบระดับชั้นที่เลือก".to_string());
+            }
+        }
+        write_error(error)
+    })?;
+    transaction.commit().await.map_err(write_error)?;
+
+    load_fee_item(pool, id, Some(payload.academic_year_id)).await
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DiscountRow {
+    id: Uuid,
+    student_id: Uuid,
+    student_name: String,
+    academic_year_id: Uuid,
+    fee_item_id: Option<Uuid>,
+    fee_item_name: Option<String>,
+    discount_kind: String,
+    name: String,
+    percent_basis_points: Option<i32>,
+    amount_satang: Option<i64>,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+const DISCOUNT_SELECT: &str = r#"
+    SELECT discount.id,
+           discount.student_id,
+           CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+           discount.academic_year_id,
+           discount.fee_item_id,
+           fee_item.name AS fee_item_name,
+           discount.discount_kind,
+           discount.name,
+           discount.percent_basis_points,
+           discount.amount_satang,
+           discount.note,
+           discount.created_at
+    FROM student_fee_discounts discount
+    JOIN users student ON student.id = discount.student_id
+    LEFT JOIN fee_items fee_item ON fee_item.id = discount.fee_item_id
+"#;
+
+fn discount_from_row(row: DiscountRow) -> Result<StudentFeeDiscount, AppError> {
+    Ok(StudentFeeDiscount {
+        id: row.id,
+        student_id: row.student_id,
+        student_name: row.student_name,
+        academic_year_id: row.academic_year_id,
+        fee_item_id: row.fee_item_id,
+        fee_item_name: row.fee_item_name,
+        discount_kind: parse_discount_kind(&row.discount_kind)?,
+        name: row.name,
+        percent_basis_points: row.percent_basis_points,
+        amount_satang: row.amount_satang,
+        note: row.note,
+        created_at: row.created_at,
+    })
+}
+
+pub async fn list_discounts(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: StudentFeeDiscountFilter,
+) -> Result<Vec<StudentFeeDiscount>, AppError> {
+    require_finance_read(actor)?;
+    let rows = sqlx::query_as::<_, DiscountRow>(&format!(
+        r#"
+        {DISCOUNT_SELECT}
+        WHERE ($1::uuid IS NULL OR discount.student_id = $1)
+          AND ($2::uuid IS NULL OR discount.academic_year_id = $2)
+        ORDER BY student.first_name, student.last_name, discount.created_at
+        LIMIT 500
+        "#
+    ))
+    .bind(filter.student_id)
+    .bind(filter.academic_year_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    rows.into_iter().map(discount_from_row).collect()
+}
+
+/// Discounts are read when invoices are generated; invoices issued earlier are
+/// not repriced.
+pub async fn create_discount(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: CreateStudentFeeDiscountRequest,
+) -> Result<StudentFeeDiscount, AppError> {
+    require_finance_manage(actor)?;
+    validate_discount_value(payload.percent_basis_points, payload.amount_satang)?;
+    let name = required_text(&payload.name, "ชื่อส่วนลดหรือทุน")?;
+    let note = optional_text(payload.note)?;
+
+    let is_student = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND user_type = 'student')",
+    )
+    .bind(payload.student_id)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+    if !is_student {
+        return Err(AppError::ValidationError("ไม่พบนักเรียนที่เลือก".to_string()));
+    }
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO student_fee_discounts (
+            student_id, academic_year_id, fee_item_id, discount_kind, name,
+            percent_basis_points, amount_satang, note, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.student_id)
+    .bind(payload.academic_year_id)
+    .bind(payload.fee_item_id)
+    .bind(payload.discount_kind.as_str())
+    .bind(&name)
+    .bind(payload.percent_basis_points)
+    .bind(payload.amount_satang)
+    .bind(&note)
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        if let sqlx::Error::Database(database_error) = &error {
+            if database_error.code().as_deref() == Some("23503") {
+                return AppError::ValidationError(
+                    "ไม่พบปีการศึกษาหรือรายการค่าธรรมเนียมที่เลือก".to_string(),
+                );
+            }
+        }
+        write_error(error)
+    })?;
+
+    let row =
+        sqlx::query_as::<_, DiscountRow>(&format!("{DISCOUNT_SELECT} WHERE discount.id = $1"))
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(read_error)?;
+    discount_from_row(row)
+}
+
+pub async fn delete_discount(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<(), AppError> {
+    require_finance_manage(actor)?;
+    let deleted = sqlx::query("DELETE FROM student_fee_discounts WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(write_error)?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound("ไม่พบส่วนลดหรือทุนการศึกษา".to_string()));
+    }
+    Ok(())
+}
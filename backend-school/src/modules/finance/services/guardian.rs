@@ -0,0 +1,100 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::finance::models::{
+    ChildFeeInvoice, ChildFeePayment, ChildFeeStatement, FeePaymentStatus,
+};
+use crate::modules::parents::services as parent_service;
+
+use super::records::{
+    invoice_promptpay_payload, load_lines, load_payments, load_settings, read_error,
+    summary_from_row, InvoiceRow, INVOICE_SELECT,
+};
+use super::shared::{is_overdue, school_today};
+
+/// Outstanding balance and invoice history of one linked child. Void invoices
+/// are hidden from guardians.
+pub async fn child_fee_statement(
+    pool: &PgPool,
+    parent_id: Uuid,
+    student_id: Uuid,
+) -> Result<ChildFeeStatement, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, student_id).await?;
+
+    let rows = sqlx::query_as::<_, InvoiceRow>(&format!(
+        r#"
+        {INVOICE_SELECT}
+        WHERE invoice.student_id = $1
+          AND invoice.status <> 'void'
+        ORDER BY invoice.due_date DESC, invoice.invoice_number DESC
+        "#
+    ))
+    .bind(student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut lines = load_lines(pool, &ids).await?;
+    let mut payments = load_payments(pool, &ids).await?;
+    let settings = load_settings(pool).await?;
+    let today = school_today();
+
+    let mut outstanding_satang = 0;
+    let mut overdue_satang = 0;
+    let mut invoices = Vec::with_capacity(rows.len());
+    for row in rows {
+        let summary = summary_from_row(&row)?;
+        let invoice_payments = payments.remove(&row.id).unwrap_or_default();
+        let pending_satang = invoice_payments
+            .iter()
+            .filter(|payment| payment.status == FeePaymentStatus::PendingReview)
+            .map(|payment| payment.amount_satang)
+            .sum();
+
+        outstanding_satang += summary.outstanding_satang;
+        if is_overdue(summary.due_date, summary.outstanding_satang, today) {
+            overdue_satang += summary.outstanding_satang;
+        }
+        invoices.push(ChildFeeInvoice {
+            id: summary.id,
+            promptpay_payload: invoice_promptpay_payload(
+                &settings,
+                &summary.invoice_number,
+                summary.status,
+                summary.outstanding_satang,
+            ),
+            invoice_number: summary.invoice_number,
+            title: summary.batch_title,
+            status: summary.status,
+            due_date: summary.due_date,
+            total_satang: summary.total_satang,
+            paid_satang: summary.paid_satang,
+            outstanding_satang: summary.outstanding_satang,
+            pending_satang,
+            lines: lines.remove(&row.id).unwrap_or_default(),
+            payments: invoice_payments
+                .into_iter()
+                .map(|payment| ChildFeePayment {
+                    id: payment.id,
+                    method: payment.method,
+                    status: payment.status,
+                    amount_satang: payment.amount_satang,
+                    paid_at: payment.paid_at,
+                    rejection_reason: payment.rejection_reason,
+                    receipt_id: payment.receipt_id,
+                    receipt_number: payment.receipt_number,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(ChildFeeStatement {
+        student_id,
+        outstanding_satang,
+        overdue_satang,
+        invoices,
+    })
+}
@@ -0,0 +1,610 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::finance::models::{
+    CreateFeeInvoiceBatchRequest, FeeInvoice, FeeInvoiceBatch, FeeInvoiceBatchFilter,
+    FeeInvoiceFilter, FeeInvoiceGenerationResult, FeeInvoiceStatus, FeeInvoiceSummary,
+    VoidFeeInvoiceRequest,
+};
+use crate::policies::finance_access_policy::{require_finance_manage, require_finance_read};
+
+use super::records::{
+    allocate_invoice_number, invoice_detail, load_invoice_row, load_settings, read_error,
+    summary_from_row, write_error, InvoiceRow, INVOICE_SELECT,
+};
+use super::shared::{
+    compute_invoice, dedupe_ids, parse_discount_kind, required_text, school_today, search_pattern,
+    FeeCharge, FeeDiscountRule, BATCH_NOT_FOUND_MESSAGE, INVOICE_NOT_FOUND_MESSAGE,
+};
+
+const MAX_INVOICE_LIST: i64 = 500;
+
+#[derive(Debug, sqlx::FromRow)]
+struct BatchRow {
+    id: Uuid,
+    academic_year_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+    title: String,
+    due_date: NaiveDate,
+    grade_level_ids: Vec<Uuid>,
+    invoice_count: i64,
+    total_satang: i64,
+    paid_satang: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl From<BatchRow> for FeeInvoiceBatch {
+    fn from(row: BatchRow) -> Self {
+        Self {
+            id: row.id,
+            academic_year_id: row.academic_year_id,
+            academic_semester_id: row.academic_semester_id,
+            title: row.title,
+            due_date: row.due_date,
+            grade_level_ids: row.grade_level_ids,
+            invoice_count: row.invoice_count,
+            total_satang: row.total_satang,
+            paid_satang: row.paid_satang,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Void invoices are left out of the batch totals.
+const BATCH_SELECT: &str = r#"
+    SELECT batch.id,
+           batch.academic_year_id,
+           batch.academic_semester_id,
+           batch.title,
+           batch.due_date,
+           COALESCE(
+               (
+                   SELECT array_agg(grade.grade_level_id ORDER BY grade.grade_level_id)
+                   FROM fee_invoice_batch_grade_levels grade
+                   WHERE grade.batch_id = batch.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS grade_level_ids,
+           COUNT(invoice.id) FILTER (WHERE invoice.status <> 'void') AS invoice_count,
+           COALESCE(SUM(invoice.total_satang) FILTER (WHERE invoice.status <> 'void'), 0)::bigint
+               AS total_satang,
+           COALESCE(SUM(invoice.paid_satang) FILTER (WHERE invoice.status <> 'void'), 0)::bigint
+               AS paid_satang,
+           batch.created_at
+    FROM fee_invoice_batches batch
+    LEFT JOIN fee_invoices invoice ON invoice.batch_id = batch.id
+"#;
+
+async fn load_batch(pool: &PgPool, id: Uuid) -> Result<FeeInvoiceBatch, AppError> {
+    sqlx::query_as::<_, BatchRow>(&format!(
+        "{BATCH_SELECT} WHERE batch.id = $1 GROUP BY batch.id"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .map(FeeInvoiceBatch::from)
+    .ok_or_else(|| AppError::NotFound(BATCH_NOT_FOUND_MESSAGE.to_string()))
+}
+
+pub async fn list_batches(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: FeeInvoiceBatchFilter,
+) -> Result<Vec<FeeInvoiceBatch>, AppError> {
+    require_finance_read(actor)?;
+    let rows = sqlx::query_as::<_, BatchRow>(&format!(
+        r#"
+        {BATCH_SELECT}
+        WHERE ($1::uuid IS NULL OR batch.academic_year_id = $1)
+        GROUP BY batch.id
+        ORDER BY batch.due_date DESC, batch.created_at DESC
+        "#
+    ))
+    .bind(filter.academic_year_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows.into_iter().map(FeeInvoiceBatch::from).collect())
+}
+
+pub async fn get_batch(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<FeeInvoiceBatch, AppError> {
+    require_finance_read(actor)?;
+    load_batch(pool, id).await
+}
+
+pub async fn create_batch(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: CreateFeeInvoiceBatchRequest,
+) -> Result<FeeInvoiceGenerationResult, AppError> {
+    require_finance_manage(actor)?;
+    let title = required_text(&payload.title, "ชื่อชุดใบแจ้งหนี้")?;
+    let grade_level_ids = dedupe_ids(payload.grade_level_ids);
+    if grade_level_ids.is_empty() {
+        return Err(AppError::ValidationError(
+            "กรุณาเลือกระดับชั้นอย่างน้อย 1 ระดับ".to_string(),
+        ));
+    }
+    if let Some(semester_id) = payload.academic_semester_id {
+        let in_year = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM academic_semesters WHERE id = $1 AND academic_year_id = $2
+            )
+            "#,
+        )
+        .bind(semester_id)
+        .bind(payload.academic_year_id)
+        .fetch_one(pool)
+        .await
+        .map_err(read_error)?;
+        if !in_year {
+            return Err(AppError::ValidationError(
+                "ภาคเรียนต้องอยู่ในปีการศึกษาที่เลือก".to_string(),
+            ));
+        }
+    }
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let batch_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO fee_invoice_batches (
+            academic_year_id, academic_semester_id, title, due_date, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.academic_year_id)
+    .bind(payload.academic_semester_id)
+    .bind(&title)
+    .bind(payload.due_date)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(reference_error)?;
+    sqlx::query(
+        r#"
+        INSERT INTO fee_invoice_batch_grade_levels (batch_id, grade_level_id)
+        SELECT $1, grade_level_id
+        FROM UNNEST($2::uuid[]) AS item(grade_level_id)
+        "#,
+    )
+    .bind(batch_id)
+    .bind(&grade_level_ids)
+    .execute(&mut *transaction)
+    .await
+    .map_err(reference_error)?;
+
+    let counts = generate_in_transaction(&mut transaction, batch_id).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(FeeInvoiceGenerationResult {
+        batch: load_batch(pool, batch_id).await?,
+        created_count: counts.created,
+        existing_count: counts.existing,
+        without_charges_count: counts.without_charges,
+    })
+}
+
+/// Bills students who joined the selected grades after the batch was first
+/// generated. Students who already have an invoice in the batch are skipped.
+pub async fn generate_batch_invoices(
+    pool: &PgPool,
+    actor: &ActorContext,
+    batch_id: Uuid,
+) -> Result<FeeInvoiceGenerationResult, AppError> {
+    require_finance_manage(actor)?;
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let counts = generate_in_transaction(&mut transaction, batch_id).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(FeeInvoiceGenerationResult {
+        batch: load_batch(pool, batch_id).await?,
+        created_count: counts.created,
+        existing_count: counts.existing,
+        without_charges_count: counts.without_charges,
+    })
+}
+
+fn reference_error(error: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(database_error) = &error {
+        if database_error.code().as_deref() == Some("23503") {
+            return AppError::ValidationError("ไม่พบปีการศึกษาหรือระดับชั้นที่เลือก".to_string());
+        }
+    }
+    write_error(error)
+}
+
+#[derive(Debug, Default)]
+struct GenerationCounts {
+    created: usize,
+    existing: usize,
+    without_charges: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct LockedBatch {
+    academic_year_id: Uuid,
+    academic_semester_id: Option<Uuid>,
+    due_date: NaiveDate,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct EnrolledStudent {
+    student_id: Uuid,
+    class_room_id: Uuid,
+    grade_level_id: Uuid,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RateRow {
+    grade_level_id: Uuid,
+    fee_item_id: Uuid,
+    fee_item_name: String,
+    amount_satang: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DiscountRow {
+    id: Uuid,
+    student_id: Uuid,
+    fee_item_id: Option<Uuid>,
+    discount_kind: String,
+    name: String,
+    percent_basis_points: Option<i32>,
+    amount_satang: Option<i64>,
+}
+
+/// The batch row is locked for the whole run so two generations of the same
+/// batch cannot interleave and burn invoice numbers on conflicts.
+async fn generate_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    batch_id: Uuid,
+) -> Result<GenerationCounts, AppError> {
+    let batch = sqlx::query_as::<_, LockedBatch>(
+        r#"
+        SELECT academic_year_id, academic_semester_id, due_date
+        FROM fee_invoice_batches
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(batch_id)
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound(BATCH_NOT_FOUND_MESSAGE.to_string()))?;
+
+    let students = sqlx::query_as::<_, EnrolledStudent>(
+        r#"
+        SELECT DISTINCT ON (enrollment.student_id)
+               enrollment.student_id,
+               class_room.id AS class_room_id,
+               class_room.grade_level_id
+        FROM student_class_enrollments enrollment
+        JOIN class_rooms class_room ON class_room.id = enrollment.class_room_id
+        JOIN fee_invoice_batch_grade_levels batch_grade
+          ON batch_grade.grade_level_id = class_room.grade_level_id
+         AND batch_grade.batch_id = $1
+        JOIN users student ON student.id = enrollment.student_id
+        WHERE class_room.academic_year_id = $2
+          AND enrollment.status = 'active'
+          AND student.status = 'active'
+        ORDER BY enrollment.student_id, enrollment.enrollment_date DESC, enrollment.created_at DESC
+        "#,
+    )
+    .bind(batch_id)
+    .bind(batch.academic_year_id)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+
+    let invoiced: HashSet<Uuid> =
+        sqlx::query_scalar::<_, Uuid>("SELECT student_id FROM fee_invoices WHERE batch_id = $1")
+            .bind(batch_id)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(read_error)?
+            .into_iter()
+            .collect();
+
+    let rates = sqlx::query_as::<_, RateRow>(
+        r#"
+        SELECT rate.grade_level_id,
+               rate.fee_item_id,
+               fee_item.name AS fee_item_name,
+               rate.amount_satang
+        FROM fee_item_rates rate
+        JOIN fee_items fee_item ON fee_item.id = rate.fee_item_id
+        WHERE rate.academic_year_id = $1
+          AND rate.academic_semester_id IS NOT DISTINCT FROM $2
+          AND fee_item.is_active = true
+        ORDER BY fee_item.sort_order, fee_item.code
+        "#,
+    )
+    .bind(batch.academic_year_id)
+    .bind(batch.academic_semester_id)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+    let mut charges_by_grade: HashMap<Uuid, Vec<FeeCharge>> = HashMap::new();
+    for rate in rates {
+        charges_by_grade
+            .entry(rate.grade_level_id)
+            .or_default()
+            .push(FeeCharge {
+                fee_item_id: rate.fee_item_id,
+                description: rate.fee_item_name,
+                amount_satang: rate.amount_satang,
+            });
+    }
+
+    let pending: Vec<&EnrolledStudent> = students
+        .iter()
+        .filter(|student| !invoiced.contains(&student.student_id))
+        .collect();
+    let pending_ids: Vec<Uuid> = pending.iter().map(|student| student.student_id).collect();
+    let discount_rows = sqlx::query_as::<_, DiscountRow>(
+        r#"
+        SELECT id, student_id, fee_item_id, discount_kind, name, percent_basis_points, amount_satang
+        FROM student_fee_discounts
+        WHERE academic_year_id = $1
+          AND student_id = ANY($2)
+        ORDER BY created_at, id
+        "#,
+    )
+    .bind(batch.academic_year_id)
+    .bind(&pending_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+    let mut discounts_by_student: HashMap<Uuid, Vec<FeeDiscountRule>> = HashMap::new();
+    for row in discount_rows {
+        discounts_by_student
+            .entry(row.student_id)
+            .or_default()
+            .push(FeeDiscountRule {
+                id: row.id,
+                fee_item_id: row.fee_item_id,
+                discount_kind: parse_discount_kind(&row.discount_kind)?,
+                name: row.name,
+                percent_basis_points: row.percent_basis_points,
+                amount_satang: row.amount_satang,
+            });
+    }
+
+    let mut counts = GenerationCounts {
+        existing: students.len() - pending.len(),
+        ..GenerationCounts::default()
+    };
+    let issued_on = school_today();
+    for student in pending {
+        let Some(charges) = charges_by_grade.get(&student.grade_level_id) else {
+            counts.without_charges += 1;
+            continue;
+        };
+        let discounts = discounts_by_student
+            .get(&student.student_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let computation = compute_invoice(charges, discounts);
+        let total_satang = computation.total_satang();
+        let status = if total_satang == 0 {
+            FeeInvoiceStatus::Paid
+        } else {
+            FeeInvoiceStatus::Open
+        };
+
+        let invoice_number = allocate_invoice_number(transaction, issued_on).await?;
+        let invoice_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO fee_invoices (
+                invoice_number, batch_id, student_id, class_room_id, status,
+                subtotal_satang, discount_satang, total_satang, due_date
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&invoice_number)
+        .bind(batch_id)
+        .bind(student.student_id)
+        .bind(student.class_room_id)
+        .bind(status.as_str())
+        .bind(computation.subtotal_satang)
+        .bind(computation.discount_satang)
+        .bind(total_satang)
+        .bind(batch.due_date)
+        .fetch_one(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+        let line_kinds: Vec<&str> = computation
+            .lines
+            .iter()
+            .map(|line| line.line_kind.as_str())
+            .collect();
+        let fee_item_ids: Vec<Option<Uuid>> = computation
+            .lines
+            .iter()
+            .map(|line| line.fee_item_id)
+            .collect();
+        let discount_ids: Vec<Option<Uuid>> = computation
+            .lines
+            .iter()
+            .map(|line| line.discount_id)
+            .collect();
+        let descriptions: Vec<&str> = computation
+            .lines
+            .iter()
+            .map(|line| line.description.as_str())
+            .collect();
+        let amounts: Vec<i64> = computation
+            .lines
+            .iter()
+            .map(|line| line.amount_satang)
+            .collect();
+        sqlx::query(
+            r#"
+            INSERT INTO fee_invoice_lines (
+                invoice_id, line_kind, fee_item_id, discount_id, description, amount_satang,
+                sort_order
+            )
+            SELECT $1, item.line_kind, item.fee_item_id, item.discount_id, item.description,
+                   item.amount_satang, (item.ordinality - 1)::integer
+            FROM UNNEST($2::text[], $3::uuid[], $4::uuid[], $5::text[], $6::bigint[])
+                WITH ORDINALITY
+                AS item(line_kind, fee_item_id, discount_id, description, amount_satang, ordinality)
+            "#,
+        )
+        .bind(invoice_id)
+        .bind(&line_kinds)
+        .bind(&fee_item_ids)
+        .bind(&discount_ids)
+        .bind(&descriptions)
+        .bind(&amounts)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+        counts.created += 1;
+    }
+
+    Ok(counts)
+}
+
+pub async fn list_invoices(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: FeeInvoiceFilter,
+) -> Result<Vec<FeeInvoiceSummary>, AppError> {
+    require_finance_read(actor)?;
+    let search = filter
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|search| !search.is_empty())
+        .map(search_pattern);
+
+    let rows = sqlx::query_as::<_, InvoiceRow>(&format!(
+        r#"
+        {INVOICE_SELECT}
+        WHERE ($1::uuid IS NULL OR invoice.batch_id = $1)
+          AND ($2::uuid IS NULL OR invoice.class_room_id = $2)
+          AND ($3::uuid IS NULL OR invoice.student_id = $3)
+          AND ($4::text IS NULL OR invoice.status = $4)
+          AND (
+              $5::text IS NULL
+              OR invoice.invoice_number ILIKE $5
+              OR CONCAT_WS(' ', student.first_name, student.last_name) ILIKE $5
+              OR info.student_id ILIKE $5
+          )
+        ORDER BY class_room.name NULLS LAST, student.first_name, student.last_name, invoice.due_date
+        LIMIT $6
+        "#
+    ))
+    .bind(filter.batch_id)
+    .bind(filter.class_room_id)
+    .bind(filter.student_id)
+    .bind(filter.status.map(|status| status.as_str()))
+    .bind(search)
+    .bind(MAX_INVOICE_LIST)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    rows.iter().map(summary_from_row).collect()
+}
+
+pub async fn get_invoice(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<FeeInvoice, AppError> {
+    require_finance_read(actor)?;
+    let row = load_invoice_row(pool, id).await?;
+    let settings = load_settings(pool).await?;
+    invoice_detail(pool, row, &settings).await
+}
+
+/// Only invoices with no money against them can be voided; refunds are out of
+/// scope, so a paid invoice stays as issued.
+pub async fn void_invoice(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: VoidFeeInvoiceRequest,
+) -> Result<FeeInvoice, AppError> {
+    require_finance_manage(actor)?;
+    let reason = required_text(&payload.reason, "เหตุผลการยกเลิก")?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let locked = sqlx::query_as::<_, (String, i64)>(
+        "SELECT status, paid_satang FROM fee_invoices WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(read_error)?;
+    let Some((status, paid_satang)) = locked else {
+        return Err(AppError::NotFound(INVOICE_NOT_FOUND_MESSAGE.to_string()));
+    };
+    if status == FeeInvoiceStatus::Void.as_str() {
+        return Err(AppError::Conflict("ใบแจ้งหนี้นี้ถูกยกเลิกแล้ว".to_string()));
+    }
+    if paid_satang > 0 {
+        return Err(AppError::Conflict(
+            "ไม่สามารถยกเลิกใบแจ้งหนี้ที่มีการชำระเงินแล้ว".to_string(),
+        ));
+    }
+    let has_pending = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM fee_payments WHERE invoice_id = $1 AND status = 'pending_review'
+        )
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(read_error)?;
+    if has_pending {
+        return Err(AppError::Conflict(
+            "กรุณาตรวจสอบหลักฐานการชำระเงินที่รอดำเนินการก่อนยกเลิกใบแจ้งหนี้".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE fee_invoices
+        SET status = 'void',
+            voided_by = $2,
+            voided_at = NOW(),
+            void_reason = $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .bind(&reason)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let row = load_invoice_row(pool, id).await?;
+    let settings = load_settings(pool).await?;
+    invoice_detail(pool, row, &settings).await
+}
@@ -0,0 +1,65 @@
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+
+use crate::error::AppError;
+use crate::modules::finance::models::FeePayment;
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+use super::records::{read_error, write_error};
+use super::shared::payment_review_notification_text;
+
+const GUARDIAN_FEES_LINK: &str = "/parent";
+
+/// Tells the guardian who reported a transfer how the school reviewed it.
+/// Desk payments recorded by staff have no one to notify.
+pub async fn notify_payment_reviewed(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    payment: &FeePayment,
+) -> Result<bool, AppError> {
+    let Some(submitted_by) = payment.submitted_by else {
+        return Ok(false);
+    };
+    let invoice_number = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT invoice.invoice_number
+        FROM fee_invoices invoice
+        JOIN users submitter ON submitter.id = $2 AND submitter.user_type = 'parent'
+        WHERE invoice.id = $1
+        "#,
+    )
+    .bind(payment.invoice_id)
+    .bind(submitted_by)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?;
+    let Some(invoice_number) = invoice_number else {
+        return Ok(false);
+    };
+    let Some((title, message)) = payment_review_notification_text(
+        payment.status,
+        &invoice_number,
+        payment.amount_satang,
+        payment.rejection_reason.as_deref(),
+    ) else {
+        return Ok(false);
+    };
+
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    NotificationService::send(
+        pool,
+        &publisher,
+        submitted_by,
+        &title,
+        &message,
+        NotificationType::Info,
+        Some(GUARDIAN_FEES_LINK),
+    )
+    .await
+    .map_err(write_error)?;
+    Ok(true)
+}
@@ -0,0 +1,371 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::finance::models::{
+    FeePayment, FeePaymentFilter, FeePaymentStatus, FeeReceiptDocument, RecordFeePaymentRequest,
+    RejectFeePaymentRequest, SubmitFeePaymentRequest,
+};
+use crate::modules::parents::services as parent_service;
+use crate::policies::finance_access_policy::{
+    require_finance_issue, require_finance_read, require_student_finance_read,
+};
+
+use super::records::{
+    apply_confirmed_payment, attach_slips, issue_receipt, list_payment_rows, load_invoice_row,
+    load_lines, load_payment, load_settings, read_error, validate_slip_files, write_error,
+};
+use super::shared::{
+    dedupe_ids, optional_text, parse_payment_method, required_text, school_today, thai_baht_text,
+    validate_guardian_payment_method, validate_payment_amount, INVOICE_NOT_FOUND_MESSAGE,
+    PAYMENT_NOT_FOUND_MESSAGE, RECEIPT_NOT_FOUND_MESSAGE,
+};
+
+const MAX_PAYMENT_LIST: i64 = 500;
+
+async fn payment_or_not_found(pool: &PgPool, id: Uuid) -> Result<FeePayment, AppError> {
+    load_payment(pool, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(PAYMENT_NOT_FOUND_MESSAGE.to_string()))
+}
+
+/// Locks the invoice and returns what can still be paid on it. Transfers
+/// waiting for review are held back so a guardian cannot report the same
+/// balance twice.
+async fn lock_payable_amount(
+    transaction: &mut Transaction<'_, Postgres>,
+    invoice_id: Uuid,
+    hold_pending: bool,
+) -> Result<i64, AppError> {
+    let locked = sqlx::query_as::<_, (String, i64, i64)>(
+        "SELECT status, total_satang, paid_satang FROM fee_invoices WHERE id = $1 FOR UPDATE",
+    )
+    .bind(invoice_id)
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+    let Some((status, total_satang, paid_satang)) = locked else {
+        return Err(AppError::NotFound(INVOICE_NOT_FOUND_MESSAGE.to_string()));
+    };
+    if status == "void" {
+        return Err(AppError::Conflict("ใบแจ้งหนี้นี้ถูกยกเลิกแล้ว".to_string()));
+    }
+
+    let pending_satang = if hold_pending {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COALESCE(SUM(amount_satang), 0)::bigint
+            FROM fee_payments
+            WHERE invoice_id = $1 AND status = 'pending_review'
+            "#,
+        )
+        .bind(invoice_id)
+        .fetch_one(&mut **transaction)
+        .await
+        .map_err(read_error)?
+    } else {
+        0
+    };
+    Ok(total_satang - paid_satang - pending_satang)
+}
+
+/// Records money taken by the finance desk and issues the numbered receipt in
+/// the same transaction.
+pub async fn record_payment(
+    pool: &PgPool,
+    actor: &ActorContext,
+    invoice_id: Uuid,
+    payload: RecordFeePaymentRequest,
+) -> Result<FeePayment, AppError> {
+    require_finance_issue(actor)?;
+    let reference = optional_text(payload.reference)?;
+    let note = optional_text(payload.note)?;
+    let slip_file_ids = dedupe_ids(payload.slip_file_ids);
+    validate_slip_files(pool, actor.user_id, &slip_file_ids).await?;
+    let settings = load_settings(pool).await?;
+    let paid_at = payload.paid_at.unwrap_or_else(Utc::now);
+    reject_future_payment(paid_at)?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let payable = lock_payable_amount(&mut transaction, invoice_id, false).await?;
+    validate_payment_amount(payload.amount_satang, payable)?;
+
+    let payment_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO fee_payments (
+            invoice_id, method, status, amount_satang, paid_at, reference, note,
+            submitted_by, reviewed_by, reviewed_at
+        )
+        VALUES ($1, $2, 'confirmed', $3, $4, $5, $6, $7, $7, NOW())
+        RETURNING id
+        "#,
+    )
+    .bind(invoice_id)
+    .bind(payload.method.as_str())
+    .bind(payload.amount_satang)
+    .bind(paid_at)
+    .bind(&reference)
+    .bind(&note)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    attach_slips(&mut transaction, payment_id, &slip_file_ids, actor.user_id).await?;
+    apply_confirmed_payment(&mut transaction, invoice_id, payload.amount_satang).await?;
+    issue_receipt(
+        &mut transaction,
+        &settings.receipt_prefix,
+        payment_id,
+        invoice_id,
+        payload.amount_satang,
+        actor.user_id,
+        school_today(),
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    payment_or_not_found(pool, payment_id).await
+}
+
+fn reject_future_payment(paid_at: DateTime<Utc>) -> Result<(), AppError> {
+    if paid_at > Utc::now() {
+        return Err(AppError::ValidationError(
+            "วันเวลาที่ชำระต้องไม่อยู่ในอนาคต".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A guardian reports a transfer with its slip. Nothing is credited to the
+/// invoice until a cashier confirms it.
+pub async fn submit_guardian_payment(
+    pool: &PgPool,
+    parent_id: Uuid,
+    invoice_id: Uuid,
+    payload: SubmitFeePaymentRequest,
+) -> Result<FeePayment, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    let invoice = load_invoice_row(pool, invoice_id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, invoice.student_id).await?;
+    validate_guardian_payment_method(payload.method)?;
+    reject_future_payment(payload.paid_at)?;
+    let reference = optional_text(payload.reference)?;
+    let slip_file_ids = dedupe_ids(payload.slip_file_ids);
+    if slip_file_ids.is_empty() {
+        return Err(AppError::ValidationError(
+            "กรุณาแนบหลักฐานการโอนเงิน".to_string(),
+        ));
+    }
+    validate_slip_files(pool, parent_id, &slip_file_ids).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let payable = lock_payable_amount(&mut transaction, invoice_id, true).await?;
+    validate_payment_amount(payload.amount_satang, payable)?;
+
+    let payment_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO fee_payments (
+            invoice_id, method, status, amount_satang, paid_at, reference, submitted_by
+        )
+        VALUES ($1, $2, 'pending_review', $3, $4, $5, $6)
+        RETURNING id
+        "#,
+    )
+    .bind(invoice_id)
+    .bind(payload.method.as_str())
+    .bind(payload.amount_satang)
+    .bind(payload.paid_at)
+    .bind(&reference)
+    .bind(parent_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    attach_slips(&mut transaction, payment_id, &slip_file_ids, parent_id).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    payment_or_not_found(pool, payment_id).await
+}
+
+async fn lock_pending_payment(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<(Uuid, i64), AppError> {
+    let locked = sqlx::query_as::<_, (Uuid, String, i64)>(
+        "SELECT invoice_id, status, amount_satang FROM fee_payments WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+    let Some((invoice_id, status, amount_satang)) = locked else {
+        return Err(AppError::NotFound(PAYMENT_NOT_FOUND_MESSAGE.to_string()));
+    };
+    if status != FeePaymentStatus::PendingReview.as_str() {
+        return Err(AppError::Conflict(
+            "รายการชำระเงินนี้ได้รับการตรวจสอบแล้ว".to_string(),
+        ));
+    }
+    Ok((invoice_id, amount_satang))
+}
+
+pub async fn confirm_payment(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<FeePayment, AppError> {
+    require_finance_issue(actor)?;
+    let settings = load_settings(pool).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let (invoice_id, amount_satang) = lock_pending_payment(&mut transaction, id).await?;
+    sqlx::query(
+        r#"
+        UPDATE fee_payments
+        SET status = 'confirmed', reviewed_by = $2, reviewed_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    apply_confirmed_payment(&mut transaction, invoice_id, amount_satang).await?;
+    issue_receipt(
+        &mut transaction,
+        &settings.receipt_prefix,
+        id,
+        invoice_id,
+        amount_satang,
+        actor.user_id,
+        school_today(),
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    payment_or_not_found(pool, id).await
+}
+
+/// Rejected slips stay attached as the record of what was reviewed.
+pub async fn reject_payment(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: RejectFeePaymentRequest,
+) -> Result<FeePayment, AppError> {
+    require_finance_issue(actor)?;
+    let reason = required_text(&payload.reason, "เหตุผลที่ไม่รับรายการ")?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    lock_pending_payment(&mut transaction, id).await?;
+    sqlx::query(
+        r#"
+        UPDATE fee_payments
+        SET status = 'rejected', reviewed_by = $2, reviewed_at = NOW(), rejection_reason = $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(actor.user_id)
+    .bind(&reason)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    transaction.commit().await.map_err(write_error)?;
+
+    payment_or_not_found(pool, id).await
+}
+
+pub async fn list_payments(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: FeePaymentFilter,
+) -> Result<Vec<FeePayment>, AppError> {
+    require_finance_read(actor)?;
+    let invoice_ids = filter.invoice_id.map(|invoice_id| vec![invoice_id]);
+    list_payment_rows(
+        pool,
+        filter.status.map(|status| status.as_str()),
+        invoice_ids.as_deref(),
+        MAX_PAYMENT_LIST,
+    )
+    .await
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ReceiptRow {
+    id: Uuid,
+    receipt_number: String,
+    issued_at: DateTime<Utc>,
+    invoice_id: Uuid,
+    amount_satang: i64,
+    method: String,
+    reference: Option<String>,
+    paid_at: DateTime<Utc>,
+    issued_by_name: Option<String>,
+}
+
+/// Receipt data for the client-side PDF. Guardians may fetch receipts for
+/// their linked children.
+pub async fn get_receipt_document(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<FeeReceiptDocument, AppError> {
+    let receipt = sqlx::query_as::<_, ReceiptRow>(
+        r#"
+        SELECT receipt.id,
+               receipt.receipt_number,
+               receipt.issued_at,
+               receipt.invoice_id,
+               receipt.amount_satang,
+               payment.method,
+               payment.reference,
+               payment.paid_at,
+               NULLIF(CONCAT_WS(' ', issuer.first_name, issuer.last_name), '') AS issued_by_name
+        FROM fee_receipts receipt
+        JOIN fee_payments payment ON payment.id = receipt.payment_id
+        LEFT JOIN users issuer ON issuer.id = receipt.issued_by
+        WHERE receipt.id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound(RECEIPT_NOT_FOUND_MESSAGE.to_string()))?;
+
+    let invoice = load_invoice_row(pool, receipt.invoice_id).await?;
+    require_student_finance_read(pool, actor, invoice.student_id).await?;
+    let settings = load_settings(pool).await?;
+    let lines = load_lines(pool, &[invoice.id])
+        .await?
+        .remove(&invoice.id)
+        .unwrap_or_default();
+    let outstanding_satang = invoice.outstanding_satang();
+
+    Ok(FeeReceiptDocument {
+        id: receipt.id,
+        receipt_number: receipt.receipt_number,
+        issued_at: receipt.issued_at,
+        payee_name: settings.payee_name,
+        student_id: invoice.student_id,
+        student_name: invoice.student_name,
+        student_code: invoice.student_code,
+        class_room_name: invoice.class_room_name,
+        invoice_id: invoice.id,
+        invoice_number: invoice.invoice_number,
+        batch_title: invoice.batch_title,
+        method: parse_payment_method(&receipt.method)?,
+        reference: receipt.reference,
+        paid_at: receipt.paid_at,
+        amount_satang: receipt.amount_satang,
+        amount_text: thai_baht_text(receipt.amount_satang),
+        lines,
+        invoice_total_satang: invoice.total_satang,
+        outstanding_satang,
+        issued_by_name: receipt.issued_by_name,
+    })
+}
@@ -0,0 +1,79 @@
+use crate::modules::finance::models::PromptPayTargetType;
+
+/// Application ID for PromptPay merchant-presented credit transfers.
+const PROMPTPAY_AID: &str = "A000000677010111";
+const THAI_BAHT_NUMERIC_CODE: &str = "764";
+const COUNTRY_CODE: &str = "TH";
+/// EMVCo limits a bill number to 25 characters.
+const MAX_BILL_NUMBER_LENGTH: usize = 25;
+
+fn tlv(tag: &str, value: &str) -> String {
+    format!("{tag}{:02}{value}", value.len())
+}
+
+/// CRC-16/CCITT-FALSE as required by the EMVCo QR specification.
+pub fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// PromptPay encodes a mobile number with the 0066 country prefix in place of
+/// the leading zero; tax IDs and e-wallet IDs are used as stored.
+fn account_value(target_type: PromptPayTargetType, target: &str) -> (&'static str, String) {
+    match target_type {
+        PromptPayTargetType::Phone => (
+            "01",
+            format!("0066{}", target.strip_prefix('0').unwrap_or(target)),
+        ),
+        PromptPayTargetType::TaxId => ("02", target.to_string()),
+        PromptPayTargetType::Ewallet => ("03", target.to_string()),
+    }
+}
+
+pub fn format_promptpay_amount(amount_satang: i64) -> String {
+    format!("{}.{:02}", amount_satang / 100, amount_satang % 100)
+}
+
+/// Builds the EMVCo merchant-presented payload a banking app scans. With an
+/// amount the QR is single-use (dynamic); without one it is a static QR.
+pub fn promptpay_payload(
+    target_type: PromptPayTargetType,
+    target: &str,
+    amount_satang: Option<i64>,
+    bill_number: Option<&str>,
+) -> String {
+    let (account_tag, account) = account_value(target_type, target);
+    let mut payload = String::new();
+    payload.push_str(&tlv("00", "01"));
+    payload.push_str(&tlv(
+        "01",
+        if amount_satang.is_some() { "12" } else { "11" },
+    ));
+    payload.push_str(&tlv(
+        "29",
+        &format!("{}{}", tlv("00", PROMPTPAY_AID), tlv(account_tag, &account)),
+    ));
+    payload.push_str(&tlv("53", THAI_BAHT_NUMERIC_CODE));
+    if let Some(amount_satang) = amount_satang {
+        payload.push_str(&tlv("54", &format_promptpay_amount(amount_satang)));
+    }
+    payload.push_str(&tlv("58", COUNTRY_CODE));
+    if let Some(bill_number) = bill_number.filter(|value| !value.is_empty()) {
+        let bill_number: String = bill_number.chars().take(MAX_BILL_NUMBER_LENGTH).collect();
+        payload.push_str(&tlv("62", &tlv("01", &bill_number)));
+    }
+    payload.push_str("6304");
+    let crc = crc16_ccitt(payload.as_bytes());
+    payload.push_str(&format!("{crc:04X}"));
+    payload
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::finance::models::{
+    DailyReconciliation, ReconciliationEntry, ReconciliationQuery,
+};
+use crate::policies::finance_access_policy::require_finance_read;
+use crate::scheduling::SCHOOL_TIMEZONE_NAME;
+
+use super::records::read_error;
+use super::shared::{parse_payment_method, reconciliation_totals, school_today};
+
+#[derive(Debug, sqlx::FromRow)]
+struct EntryRow {
+    receipt_id: Uuid,
+    receipt_number: String,
+    issued_at: DateTime<Utc>,
+    invoice_number: String,
+    student_name: String,
+    student_code: Option<String>,
+    class_room_name: Option<String>,
+    method: String,
+    reference: Option<String>,
+    amount_satang: i64,
+    issued_by_name: Option<String>,
+}
+
+/// Receipts issued on one school-local day, for matching against the cash
+/// drawer and the bank statement. The client turns it into a spreadsheet.
+pub async fn daily_reconciliation(
+    pool: &PgPool,
+    actor: &ActorContext,
+    query: ReconciliationQuery,
+) -> Result<DailyReconciliation, AppError> {
+    require_finance_read(actor)?;
+    let date = query.date.unwrap_or_else(school_today);
+
+    let rows = sqlx::query_as::<_, EntryRow>(
+        r#"
+        SELECT receipt.id AS receipt_id,
+               receipt.receipt_number,
+               receipt.issued_at,
+               invoice.invoice_number,
+               CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+               info.student_id AS student_code,
+               class_room.name AS class_room_name,
+               payment.method,
+               payment.reference,
+               receipt.amount_satang,
+               NULLIF(CONCAT_WS(' ', issuer.first_name, issuer.last_name), '') AS issued_by_name
+        FROM fee_receipts receipt
+        JOIN fee_payments payment ON payment.id = receipt.payment_id
+        JOIN fee_invoices invoice ON invoice.id = receipt.invoice_id
+        JOIN users student ON student.id = invoice.student_id
+        LEFT JOIN student_info info ON info.user_id = invoice.student_id
+        LEFT JOIN class_rooms class_room ON class_room.id = invoice.class_room_id
+        LEFT JOIN users issuer ON issuer.id = receipt.issued_by
+        WHERE (receipt.issued_at AT TIME ZONE $1)::date = $2
+        ORDER BY receipt.receipt_number
+        "#,
+    )
+    .bind(SCHOOL_TIMEZONE_NAME)
+    .bind(date)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let entries = rows
+        .into_iter()
+        .map(|row| {
+            Ok(ReconciliationEntry {
+                receipt_id: row.receipt_id,
+                receipt_number: row.receipt_number,
+                issued_at: row.issued_at,
+                invoice_number: row.invoice_number,
+                student_name: row.student_name,
+                student_code: row.student_code,
+                class_room_name: row.class_room_name,
+                method: parse_payment_method(&row.method)?,
+                reference: row.reference,
+                amount_satang: row.amount_satang,
+                issued_by_name: row.issued_by_name,
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let methods: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.method, entry.amount_satang))
+        .collect();
+    Ok(DailyReconciliation {
+        date,
+        totals: reconciliation_totals(&methods),
+        receipt_count: entries.len(),
+        total_satang: entries.iter().map(|entry| entry.amount_satang).sum(),
+        entries,
+    })
+}
@@ -0,0 +1,564 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::finance::models::{
+    FeeInvoice, FeeInvoiceLine, FeeInvoiceStatus, FeeInvoiceSummary, FeePayment, FinanceSettings,
+};
+
+use super::promptpay::promptpay_payload;
+use super::shared::{
+    buddhist_year, format_document_number, parse_invoice_status, parse_line_kind,
+    parse_payment_method, parse_payment_status, parse_promptpay_target_type,
+    DEFAULT_RECEIPT_PREFIX, INVOICE_NOT_FOUND_MESSAGE, INVOICE_NUMBER_PREFIX, MAX_SLIP_FILES,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct SettingsRow {
+    payee_name: String,
+    promptpay_target_type: Option<String>,
+    promptpay_target: Option<String>,
+    receipt_prefix: String,
+    updated_at: DateTime<Utc>,
+}
+
+/// Settings fall back to defaults until finance staff save them the first time.
+pub(super) async fn load_settings(pool: &PgPool) -> Result<FinanceSettings, AppError> {
+    let row = sqlx::query_as::<_, SettingsRow>(
+        r#"
+        SELECT payee_name, promptpay_target_type, promptpay_target, receipt_prefix, updated_at
+        FROM finance_settings
+        WHERE id = true
+        "#,
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?;
+
+    let Some(row) = row else {
+        return Ok(FinanceSettings {
+            payee_name: String::new(),
+            promptpay_target_type: None,
+            promptpay_target: None,
+            receipt_prefix: DEFAULT_RECEIPT_PREFIX.to_string(),
+            updated_at: None,
+        });
+    };
+    Ok(FinanceSettings {
+        payee_name: row.payee_name,
+        promptpay_target_type: row
+            .promptpay_target_type
+            .as_deref()
+            .map(parse_promptpay_target_type)
+            .transpose()?,
+        promptpay_target: row.promptpay_target,
+        receipt_prefix: row.receipt_prefix,
+        updated_at: Some(row.updated_at),
+    })
+}
+
+/// QR payload for what is still owed; none once the invoice is settled or
+/// void, or while the school has no PromptPay ID.
+pub(super) fn invoice_promptpay_payload(
+    settings: &FinanceSettings,
+    invoice_number: &str,
+    status: FeeInvoiceStatus,
+    outstanding_satang: i64,
+) -> Option<String> {
+    if outstanding_satang <= 0 || status == FeeInvoiceStatus::Void {
+        return None;
+    }
+    let target_type = settings.promptpay_target_type?;
+    let target = settings.promptpay_target.as_deref()?;
+    Some(promptpay_payload(
+        target_type,
+        target,
+        Some(outstanding_satang),
+        Some(invoice_number),
+    ))
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct InvoiceRow {
+    pub(super) id: Uuid,
+    pub(super) invoice_number: String,
+    pub(super) batch_id: Uuid,
+    pub(super) batch_title: String,
+    pub(super) student_id: Uuid,
+    pub(super) student_name: String,
+    pub(super) student_code: Option<String>,
+    pub(super) class_room_id: Option<Uuid>,
+    pub(super) class_room_name: Option<String>,
+    pub(super) status: String,
+    pub(super) subtotal_satang: i64,
+    pub(super) discount_satang: i64,
+    pub(super) total_satang: i64,
+    pub(super) paid_satang: i64,
+    pub(super) due_date: NaiveDate,
+    pub(super) void_reason: Option<String>,
+    pub(super) voided_at: Option<DateTime<Utc>>,
+}
+
+pub(super) const INVOICE_SELECT: &str = r#"
+    SELECT invoice.id,
+           invoice.invoice_number,
+           invoice.batch_id,
+           batch.title AS batch_title,
+           invoice.student_id,
+           CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+           info.student_id AS student_code,
+           invoice.class_room_id,
+           class_room.name AS class_room_name,
+           invoice.status,
+           invoice.subtotal_satang,
+           invoice.discount_satang,
+           invoice.total_satang,
+           invoice.paid_satang,
+           invoice.due_date,
+           invoice.void_reason,
+           invoice.voided_at
+    FROM fee_invoices invoice
+    JOIN fee_invoice_batches batch ON batch.id = invoice.batch_id
+    JOIN users student ON student.id = invoice.student_id
+    LEFT JOIN student_info info ON info.user_id = invoice.student_id
+    LEFT JOIN class_rooms class_room ON class_room.id = invoice.class_room_id
+"#;
+
+impl InvoiceRow {
+    pub(super) fn outstanding_satang(&self) -> i64 {
+        if self.status == FeeInvoiceStatus::Void.as_str() {
+            0
+        } else {
+            self.total_satang - self.paid_satang
+        }
+    }
+}
+
+pub(super) fn summary_from_row(row: &InvoiceRow) -> Result<FeeInvoiceSummary, AppError> {
+    Ok(FeeInvoiceSummary {
+        id: row.id,
+        invoice_number: row.invoice_number.clone(),
+        batch_id: row.batch_id,
+        batch_title: row.batch_title.clone(),
+        student_id: row.student_id,
+        student_name: row.student_name.clone(),
+        student_code: row.student_code.clone(),
+        class_room_id: row.class_room_id,
+        class_room_name: row.class_room_name.clone(),
+        status: parse_invoice_status(&row.status)?,
+        total_satang: row.total_satang,
+        paid_satang: row.paid_satang,
+        outstanding_satang: row.outstanding_satang(),
+        due_date: row.due_date,
+    })
+}
+
+pub(super) async fn load_invoice_row(pool: &PgPool, id: Uuid) -> Result<InvoiceRow, AppError> {
+    sqlx::query_as::<_, InvoiceRow>(&format!("{INVOICE_SELECT} WHERE invoice.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .ok_or_else(|| AppError::NotFound(INVOICE_NOT_FOUND_MESSAGE.to_string()))
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct LineRow {
+    id: Uuid,
+    invoice_id: Uuid,
+    line_kind: String,
+    fee_item_id: Option<Uuid>,
+    description: String,
+    amount_satang: i64,
+}
+
+pub(super) async fn load_lines(
+    pool: &PgPool,
+    invoice_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<FeeInvoiceLine>>, AppError> {
+    let rows = sqlx::query_as::<_, LineRow>(
+        r#"
+        SELECT id, invoice_id, line_kind, fee_item_id, description, amount_satang
+        FROM fee_invoice_lines
+        WHERE invoice_id = ANY($1)
+        ORDER BY invoice_id, sort_order, id
+        "#,
+    )
+    .bind(invoice_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut lines: HashMap<Uuid, Vec<FeeInvoiceLine>> = HashMap::new();
+    for row in rows {
+        lines
+            .entry(row.invoice_id)
+            .or_default()
+            .push(FeeInvoiceLine {
+                id: row.id,
+                line_kind: parse_line_kind(&row.line_kind)?,
+                fee_item_id: row.fee_item_id,
+                description: row.description,
+                amount_satang: row.amount_satang,
+            });
+    }
+    Ok(lines)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PaymentRow {
+    id: Uuid,
+    invoice_id: Uuid,
+    method: String,
+    status: String,
+    amount_satang: i64,
+    paid_at: DateTime<Utc>,
+    reference: Option<String>,
+    note: Option<String>,
+    submitted_by: Option<Uuid>,
+    submitted_by_name: Option<String>,
+    reviewed_at: Option<DateTime<Utc>>,
+    rejection_reason: Option<String>,
+    slip_file_ids: Vec<Uuid>,
+    receipt_id: Option<Uuid>,
+    receipt_number: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+pub(super) const PAYMENT_SELECT: &str = r#"
+    SELECT payment.id,
+           payment.invoice_id,
+           payment.method,
+           payment.status,
+           payment.amount_satang,
+           payment.paid_at,
+           payment.reference,
+           payment.note,
+           payment.submitted_by,
+           NULLIF(CONCAT_WS(' ', submitter.first_name, submitter.last_name), '') AS submitted_by_name,
+           payment.reviewed_at,
+           payment.rejection_reason,
+           COALESCE(
+               (
+                   SELECT array_agg(slip.file_id ORDER BY slip.created_at, slip.file_id)
+                   FROM fee_payment_slips slip
+                   WHERE slip.payment_id = payment.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS slip_file_ids,
+           receipt.id AS receipt_id,
+           receipt.receipt_number,
+           payment.created_at
+    FROM fee_payments payment
+    LEFT JOIN users submitter ON submitter.id = payment.submitted_by
+    LEFT JOIN fee_receipts receipt ON receipt.payment_id = payment.id
+"#;
+
+fn payment_from_row(row: PaymentRow) -> Result<FeePayment, AppError> {
+    Ok(FeePayment {
+        id: row.id,
+        invoice_id: row.invoice_id,
+        method: parse_payment_method(&row.method)?,
+        status: parse_payment_status(&row.status)?,
+        amount_satang: row.amount_satang,
+        paid_at: row.paid_at,
+        reference: row.reference,
+        note: row.note,
+        submitted_by: row.submitted_by,
+        submitted_by_name: row.submitted_by_name,
+        reviewed_at: row.reviewed_at,
+        rejection_reason: row.rejection_reason,
+        slip_file_ids: row.slip_file_ids,
+        receipt_id: row.receipt_id,
+        receipt_number: row.receipt_number,
+        created_at: row.created_at,
+    })
+}
+
+pub(super) async fn load_payment(pool: &PgPool, id: Uuid) -> Result<Option<FeePayment>, AppError> {
+    sqlx::query_as::<_, PaymentRow>(&format!("{PAYMENT_SELECT} WHERE payment.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .map(payment_from_row)
+        .transpose()
+}
+
+pub(super) async fn list_payment_rows(
+    pool: &PgPool,
+    status: Option<&str>,
+    invoice_ids: Option<&[Uuid]>,
+    limit: i64,
+) -> Result<Vec<FeePayment>, AppError> {
+    let rows = sqlx::query_as::<_, PaymentRow>(&format!(
+        r#"
+        {PAYMENT_SELECT}
+        WHERE ($1::text IS NULL OR payment.status = $1)
+          AND ($2::uuid[] IS NULL OR payment.invoice_id = ANY($2))
+        ORDER BY payment.created_at DESC, payment.id
+        LIMIT $3
+        "#
+    ))
+    .bind(status)
+    .bind(invoice_ids)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    rows.into_iter().map(payment_from_row).collect()
+}
+
+pub(super) async fn load_payments(
+    pool: &PgPool,
+    invoice_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<FeePayment>>, AppError> {
+    let rows = sqlx::query_as::<_, PaymentRow>(&format!(
+        "{PAYMENT_SELECT} WHERE payment.invoice_id = ANY($1) ORDER BY payment.paid_at, payment.id"
+    ))
+    .bind(invoice_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut payments: HashMap<Uuid, Vec<FeePayment>> = HashMap::new();
+    for row in rows {
+        let payment = payment_from_row(row)?;
+        payments
+            .entry(payment.invoice_id)
+            .or_default()
+            .push(payment);
+    }
+    Ok(payments)
+}
+
+pub(super) async fn invoice_detail(
+    pool: &PgPool,
+    row: InvoiceRow,
+    settings: &FinanceSettings,
+) -> Result<FeeInvoice, AppError> {
+    let summary = summary_from_row(&row)?;
+    let lines = load_lines(pool, &[row.id])
+        .await?
+        .remove(&row.id)
+        .unwrap_or_default();
+    let payments = load_payments(pool, &[row.id])
+        .await?
+        .remove(&row.id)
+        .unwrap_or_default();
+    let promptpay_payload = invoice_promptpay_payload(
+        settings,
+        &row.invoice_number,
+        summary.status,
+        summary.outstanding_satang,
+    );
+    Ok(FeeInvoice {
+        summary,
+        subtotal_satang: row.subtotal_satang,
+        discount_satang: row.discount_satang,
+        lines,
+        payments,
+        promptpay_payload,
+        void_reason: row.void_reason,
+        voided_at: row.voided_at,
+    })
+}
+
+/// Reserves the next running number for the Buddhist year. The counter row is
+/// locked until the transaction ends, so numbers stay gap-free per commit
+/// order.
+pub(super) async fn allocate_document_number(
+    transaction: &mut Transaction<'_, Postgres>,
+    document_type: &str,
+    prefix: &str,
+    issued_on: NaiveDate,
+) -> Result<String, AppError> {
+    let year = buddhist_year(issued_on);
+    let sequence = sqlx::query_scalar::<_, i32>(
+        r#"
+        INSERT INTO finance_document_counters (document_type, buddhist_year, last_sequence)
+        VALUES ($1, $2, 1)
+        ON CONFLICT (document_type, buddhist_year) DO UPDATE
+        SET last_sequence = finance_document_counters.last_sequence + 1,
+            updated_at = NOW()
+        RETURNING last_sequence
+        "#,
+    )
+    .bind(document_type)
+    .bind(year)
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    Ok(format_document_number(prefix, year, sequence))
+}
+
+pub(super) async fn allocate_invoice_number(
+    transaction: &mut Transaction<'_, Postgres>,
+    issued_on: NaiveDate,
+) -> Result<String, AppError> {
+    allocate_document_number(transaction, "invoice", INVOICE_NUMBER_PREFIX, issued_on).await
+}
+
+/// Adds a confirmed payment to the invoice. The guard in the update keeps the
+/// paid amount within the total even when two cashiers confirm at once.
+pub(super) async fn apply_confirmed_payment(
+    transaction: &mut Transaction<'_, Postgres>,
+    invoice_id: Uuid,
+    amount_satang: i64,
+) -> Result<(), AppError> {
+    let applied = sqlx::query(
+        r#"
+        UPDATE fee_invoices
+        SET paid_satang = paid_satang + $2,
+            status = CASE
+                WHEN paid_satang + $2 >= total_satang THEN 'paid'
+                ELSE 'partially_paid'
+            END
+        WHERE id = $1
+          AND status IN ('open', 'partially_paid')
+          AND paid_satang + $2 <= total_satang
+        "#,
+    )
+    .bind(invoice_id)
+    .bind(amount_satang)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if applied.rows_affected() == 0 {
+        return Err(AppError::Conflict(
+            "ใบแจ้งหนี้ถูกยกเลิกหรือยอดชำระเกินยอดค้างชำระแล้ว".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub(super) async fn issue_receipt(
+    transaction: &mut Transaction<'_, Postgres>,
+    receipt_prefix: &str,
+    payment_id: Uuid,
+    invoice_id: Uuid,
+    amount_satang: i64,
+    issued_by: Uuid,
+    issued_on: NaiveDate,
+) -> Result<Uuid, AppError> {
+    let receipt_number =
+        allocate_document_number(transaction, "receipt", receipt_prefix, issued_on).await?;
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO fee_receipts (
+            receipt_number, payment_id, invoice_id, amount_satang, issued_by
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(&receipt_number)
+    .bind(payment_id)
+    .bind(invoice_id)
+    .bind(amount_satang)
+    .bind(issued_by)
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(write_error)
+}
+
+/// Slips must be ready, unattached and uploaded by the person recording the
+/// payment.
+pub(super) async fn validate_slip_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.len() > MAX_SLIP_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบหลักฐานการชำระเงินได้ไม่เกิน {MAX_SLIP_FILES} ไฟล์"
+        )));
+    }
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let usable = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        LEFT JOIN fee_payment_slips slip ON slip.file_id = files.id
+        WHERE files.id = ANY($1)
+          AND files.purpose_code = 'fee_payment_slip'
+          AND files.lifecycle_status = 'ready'
+          AND files.deleted_at IS NULL
+          AND slip.file_id IS NULL
+          AND files.owner_user_id = $2
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate fee payment slips: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบหลักฐานการชำระเงินได้".to_string())
+    })?;
+
+    if usable == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "หลักฐานการชำระเงินไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
+
+pub(super) async fn attach_slips(
+    transaction: &mut Transaction<'_, Postgres>,
+    payment_id: Uuid,
+    file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<(), AppError> {
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO fee_payment_slips (payment_id, file_id, attached_by)
+        SELECT $1, file_id, $3
+        FROM UNNEST($2::uuid[]) AS item(file_id)
+        "#,
+    )
+    .bind(payment_id)
+    .bind(file_ids)
+    .bind(attached_by)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    sqlx::query(
+        r#"
+        UPDATE files
+        SET retention_class = 'standard', expires_at = NULL, updated_at = NOW()
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(file_ids)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+    Ok(())
+}
+
+pub(super) fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read finance data: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลการเงินได้".to_string())
+}
+
+pub(super) fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write finance data: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกข้อมูลการเงินได้".to_string())
+}
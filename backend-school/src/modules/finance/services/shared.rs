@@ -0,0 +1,509 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::finance::models::{
+    FeeCategory, FeeDiscountKind, FeeInvoiceLineKind, FeeInvoiceStatus, FeeItemRateInput,
+    FeePaymentMethod, FeePaymentStatus, PromptPayTargetType, ReconciliationMethodTotal,
+};
+use crate::scheduling::SCHOOL_TIMEZONE;
+
+pub(super) const FEE_ITEM_NOT_FOUND_MESSAGE: &str = "ไม่พบรายการค่าธรรมเนียม";
+pub(super) const BATCH_NOT_FOUND_MESSAGE: &str = "ไม่พบชุดใบแจ้งหนี้";
+pub(super) const INVOICE_NOT_FOUND_MESSAGE: &str = "ไม่พบใบแจ้งหนี้";
+pub(super) const PAYMENT_NOT_FOUND_MESSAGE: &str = "ไม่พบรายการชำระเงิน";
+pub(super) const RECEIPT_NOT_FOUND_MESSAGE: &str = "ไม่พบใบเสร็จรับเงิน";
+pub(super) const INVOICE_NUMBER_PREFIX: &str = "INV";
+pub(super) const DEFAULT_RECEIPT_PREFIX: &str = "RC";
+pub(super) const MAX_SLIP_FILES: usize = 5;
+const MAX_NAME_CHARS: usize = 200;
+const MAX_NOTE_CHARS: usize = 1000;
+const MAX_FEE_CODE_CHARS: usize = 30;
+const MAX_RECEIPT_PREFIX_CHARS: usize = 10;
+const BUDDHIST_ERA_OFFSET: i32 = 543;
+const FULL_PERCENT_BASIS_POINTS: i64 = 10_000;
+
+/// One fee item priced for the student's grade level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FeeCharge {
+    pub(super) fee_item_id: Uuid,
+    pub(super) description: String,
+    pub(super) amount_satang: i64,
+}
+
+/// A student's discount or scholarship, applied to one fee item or, without
+/// one, to every charge on the invoice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FeeDiscountRule {
+    pub(super) id: Uuid,
+    pub(super) fee_item_id: Option<Uuid>,
+    pub(super) discount_kind: FeeDiscountKind,
+    pub(super) name: String,
+    pub(super) percent_basis_points: Option<i32>,
+    pub(super) amount_satang: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ComputedInvoiceLine {
+    pub(super) line_kind: FeeInvoiceLineKind,
+    pub(super) fee_item_id: Option<Uuid>,
+    pub(super) discount_id: Option<Uuid>,
+    pub(super) description: String,
+    pub(super) amount_satang: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct InvoiceComputation {
+    pub(super) lines: Vec<ComputedInvoiceLine>,
+    pub(super) subtotal_satang: i64,
+    pub(super) discount_satang: i64,
+}
+
+impl InvoiceComputation {
+    pub(super) fn total_satang(&self) -> i64 {
+        self.subtotal_satang - self.discount_satang
+    }
+}
+
+/// Prices an invoice. Discounts apply in the order given, each against what is
+/// still owed on the charges it targets, so stacked discounts never take a
+/// charge below zero. Percentages round down to the satang.
+pub(super) fn compute_invoice(
+    charges: &[FeeCharge],
+    discounts: &[FeeDiscountRule],
+) -> InvoiceComputation {
+    let mut lines: Vec<ComputedInvoiceLine> = charges
+        .iter()
+        .map(|charge| ComputedInvoiceLine {
+            line_kind: FeeInvoiceLineKind::Charge,
+            fee_item_id: Some(charge.fee_item_id),
+            discount_id: None,
+            description: charge.description.clone(),
+            amount_satang: charge.amount_satang,
+        })
+        .collect();
+    let subtotal_satang = charges.iter().map(|charge| charge.amount_satang).sum();
+    let mut remaining: Vec<i64> = charges.iter().map(|charge| charge.amount_satang).collect();
+    let mut discount_satang = 0;
+
+    for discount in discounts {
+        let targeted: Vec<usize> = charges
+            .iter()
+            .enumerate()
+            .filter(|(_, charge)| {
+                discount
+                    .fee_item_id
+                    .is_none_or(|fee_item_id| fee_item_id == charge.fee_item_id)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let base: i64 = targeted.iter().map(|index| remaining[*index]).sum();
+        let amount = match (discount.percent_basis_points, discount.amount_satang) {
+            (Some(basis_points), _) => base * i64::from(basis_points) / FULL_PERCENT_BASIS_POINTS,
+            (None, Some(amount_satang)) => amount_satang.min(base),
+            (None, None) => 0,
+        };
+        if amount <= 0 {
+            continue;
+        }
+
+        let mut left = amount;
+        for index in targeted {
+            let taken = left.min(remaining[index]);
+            remaining[index] -= taken;
+            left -= taken;
+        }
+        discount_satang += amount;
+        lines.push(ComputedInvoiceLine {
+            line_kind: FeeInvoiceLineKind::Discount,
+            fee_item_id: discount.fee_item_id,
+            discount_id: Some(discount.id),
+            description: discount_description(discount),
+            amount_satang: amount,
+        });
+    }
+
+    InvoiceComputation {
+        lines,
+        subtotal_satang,
+        discount_satang,
+    }
+}
+
+fn discount_description(discount: &FeeDiscountRule) -> String {
+    let label = match discount.discount_kind {
+        FeeDiscountKind::Discount => "ส่วนลด",
+        FeeDiscountKind::Scholarship => "ทุนการศึกษา",
+    };
+    match discount.percent_basis_points {
+        Some(basis_points) => format!(
+            "{label}: {} ({}%)",
+            discount.name,
+            f64::from(basis_points) / 100.0
+        ),
+        None => format!("{label}: {}", discount.name),
+    }
+}
+
+pub fn validate_payment_amount(
+    amount_satang: i64,
+    outstanding_satang: i64,
+) -> Result<(), AppError> {
+    if amount_satang <= 0 {
+        return Err(AppError::ValidationError(
+            "จำนวนเงินต้องมากกว่าศูนย์".to_string(),
+        ));
+    }
+    if amount_satang > outstanding_satang {
+        return Err(AppError::Conflict(
+            "จำนวนเงินเกินยอดค้างชำระของใบแจ้งหนี้".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Guardians can only report transfers; cash is taken at the finance desk.
+pub fn validate_guardian_payment_method(method: FeePaymentMethod) -> Result<(), AppError> {
+    match method {
+        FeePaymentMethod::BankTransfer | FeePaymentMethod::Promptpay => Ok(()),
+        FeePaymentMethod::Cash => Err(AppError::ValidationError(
+            "การชำระเงินสดต้องชำระที่ฝ่ายการเงินของโรงเรียน".to_string(),
+        )),
+    }
+}
+
+/// Strips separators and checks the PromptPay ID format. Tax IDs must belong
+/// to a juristic person (leading zero) so a personal national ID is never
+/// stored as the school's payment target.
+pub fn normalize_promptpay_target(
+    target_type: PromptPayTargetType,
+    value: &str,
+) -> Result<String, AppError> {
+    let digits: String = value
+        .chars()
+        .filter(|character| !matches!(character, ' ' | '-'))
+        .collect();
+    if !digits.chars().all(|character| character.is_ascii_digit()) {
+        return Err(AppError::ValidationError(
+            "หมายเลขพร้อมเพย์ต้องเป็นตัวเลขเท่านั้น".to_string(),
+        ));
+    }
+
+    let valid = match target_type {
+        PromptPayTargetType::Phone => digits.len() == 10 && digits.starts_with('0'),
+        PromptPayTargetType::TaxId => digits.len() == 13 && digits.starts_with('0'),
+        PromptPayTargetType::Ewallet => digits.len() == 15,
+    };
+    if valid {
+        return Ok(digits);
+    }
+    Err(AppError::ValidationError(
+        match target_type {
+            PromptPayTargetType::Phone => "เบอร์โทรศัพท์พร้อมเพย์ต้องเป็นตัวเลข 10 หลักขึ้นต้นด้วย 0",
+            PromptPayTargetType::TaxId => "ใช้ได้เฉพาะเลขประจำตัวผู้เสียภาษีของนิติบุคคล 13 หลักที่ขึ้นต้นด้วย 0",
+            PromptPayTargetType::Ewallet => "หมายเลข e-Wallet ต้องเป็นตัวเลข 15 หลัก",
+        }
+        .to_string(),
+    ))
+}
+
+pub fn normalize_receipt_prefix(value: &str) -> Result<String, AppError> {
+    let value = value.trim().to_ascii_uppercase();
+    if value.is_empty()
+        || value.len() > MAX_RECEIPT_PREFIX_CHARS
+        || !value
+            .chars()
+            .all(|character| character.is_ascii_uppercase())
+    {
+        return Err(AppError::ValidationError(format!(
+            "อักษรนำหน้าเลขที่ใบเสร็จต้องเป็นตัวอักษรภาษาอังกฤษ 1-{MAX_RECEIPT_PREFIX_CHARS} ตัว"
+        )));
+    }
+    Ok(value)
+}
+
+pub fn normalize_fee_code(value: &str) -> Result<String, AppError> {
+    let value = value.trim().to_ascii_uppercase();
+    if value.is_empty()
+        || value.len() > MAX_FEE_CODE_CHARS
+        || !value
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_'))
+    {
+        return Err(AppError::ValidationError(format!(
+            "รหัสค่าธรรมเนียมต้องเป็นตัวอักษรภาษาอังกฤษ ตัวเลข - หรือ _ ไม่เกิน {MAX_FEE_CODE_CHARS} ตัว"
+        )));
+    }
+    Ok(value)
+}
+
+/// Each grade level can have one rate per billing period (a semester, or the
+/// whole year when no semester is given).
+pub fn validate_rates(rates: &[FeeItemRateInput]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    for rate in rates {
+        if rate.amount_satang <= 0 {
+            return Err(AppError::ValidationError(
+                "อัตราค่าธรรมเนียมต้องมากกว่าศูนย์".to_string(),
+            ));
+        }
+        if !seen.insert((rate.grade_level_id, rate.academic_semester_id)) {
+            return Err(AppError::ValidationError(
+                "กำหนดอัตราซ้ำสำหรับระดับชั้นและภาคเรียนเดียวกัน".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_discount_value(
+    percent_basis_points: Option<i32>,
+    amount_satang: Option<i64>,
+) -> Result<(), AppError> {
+    match (percent_basis_points, amount_satang) {
+        (Some(basis_points), None) if (1..=10_000).contains(&basis_points) => Ok(()),
+        (None, Some(amount_satang)) if amount_satang > 0 => Ok(()),
+        (Some(_), Some(_)) | (None, None) => Err(AppError::ValidationError(
+            "กรุณาระบุส่วนลดเป็นร้อยละหรือจำนวนเงินอย่างใดอย่างหนึ่ง".to_string(),
+        )),
+        _ => Err(AppError::ValidationError(
+            "ส่วนลดต้องมากกว่าศูนย์และไม่เกินร้อยละ 100".to_string(),
+        )),
+    }
+}
+
+pub(super) fn school_today() -> NaiveDate {
+    Utc::now().with_timezone(&SCHOOL_TIMEZONE).date_naive()
+}
+
+pub fn buddhist_year(date: NaiveDate) -> i32 {
+    date.year() + BUDDHIST_ERA_OFFSET
+}
+
+pub fn format_document_number(prefix: &str, buddhist_year: i32, sequence: i32) -> String {
+    format!("{prefix}{buddhist_year}-{sequence:06}")
+}
+
+pub fn is_overdue(due_date: NaiveDate, outstanding_satang: i64, today: NaiveDate) -> bool {
+    outstanding_satang > 0 && due_date < today
+}
+
+/// Totals per payment method in a fixed order so every export has the same
+/// rows, including methods with no receipts that day.
+pub fn reconciliation_totals(
+    entries: &[(FeePaymentMethod, i64)],
+) -> Vec<ReconciliationMethodTotal> {
+    let mut totals: HashMap<FeePaymentMethod, (usize, i64)> = HashMap::new();
+    for (method, amount_satang) in entries {
+        let total = totals.entry(*method).or_default();
+        total.0 += 1;
+        total.1 += amount_satang;
+    }
+    FeePaymentMethod::ALL
+        .into_iter()
+        .map(|method| {
+            let (receipt_count, amount_satang) = totals.get(&method).copied().unwrap_or_default();
+            ReconciliationMethodTotal {
+                method,
+                receipt_count,
+                amount_satang,
+            }
+        })
+        .collect()
+}
+
+/// "1,500.00" style amount for notification text.
+pub fn format_baht(amount_satang: i64) -> String {
+    let baht = (amount_satang / 100).to_string();
+    let mut grouped = String::with_capacity(baht.len() + baht.len() / 3);
+    for (index, digit) in baht.chars().enumerate() {
+        if index > 0 && (baht.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{grouped}.{:02}", amount_satang % 100)
+}
+
+/// Message for the guardian who reported a transfer. Payments still waiting
+/// for review produce none.
+pub fn payment_review_notification_text(
+    status: FeePaymentStatus,
+    invoice_number: &str,
+    amount_satang: i64,
+    rejection_reason: Option<&str>,
+) -> Option<(String, String)> {
+    let amount = format_baht(amount_satang);
+    match status {
+        FeePaymentStatus::PendingReview => None,
+        FeePaymentStatus::Confirmed => Some((
+            "ยืนยันการชำระเงินแล้ว".to_string(),
+            format!("โรงเรียนยืนยันยอดชำระ {amount} บาท ของใบแจ้งหนี้ {invoice_number} และออกใบเสร็จแล้ว"),
+        )),
+        FeePaymentStatus::Rejected => Some((
+            "หลักฐานการชำระเงินไม่ผ่านการตรวจสอบ".to_string(),
+            format!(
+                "ยอด {amount} บาท ของใบแจ้งหนี้ {invoice_number} ไม่ผ่านการตรวจสอบ: {}",
+                rejection_reason.unwrap_or("-")
+            ),
+        )),
+    }
+}
+
+const THAI_DIGITS: [&str; 10] = [
+    "ศูนย์",
+    "หนึ่ง",
+    "สอง",
+    "สาม",
+    "สี่",
+    "ห้า",
+    "หก",
+    "เจ็ด",
+    "แปด",
+    "เก้า",
+];
+const THAI_PLACES: [&str; 6] = ["", "สิบ", "ร้อย", "พัน", "หมื่น", "แสน"];
+
+/// Reads a number below one million; `follows_higher` marks a group that comes
+/// after ล้าน, where a trailing one is still read เอ็ด.
+fn thai_group_words(number: u64, follows_higher: bool) -> String {
+    let digits: Vec<usize> = number
+        .to_string()
+        .bytes()
+        .map(|byte| usize::from(byte - b'0'))
+        .collect();
+    let mut words = String::new();
+    for (index, digit) in digits.iter().enumerate() {
+        let place = digits.len() - index - 1;
+        match (place, *digit) {
+            (_, 0) => {}
+            (1, 1) => words.push_str("สิบ"),
+            (1, 2) => words.push_str("ยี่สิบ"),
+            (0, 1) if number >= 10 || follows_higher => words.push_str("เอ็ด"),
+            (place, digit) => {
+                words.push_str(THAI_DIGITS[digit]);
+                words.push_str(THAI_PLACES[place]);
+            }
+        }
+    }
+    words
+}
+
+fn thai_number_words(number: u64) -> String {
+    if number == 0 {
+        return THAI_DIGITS[0].to_string();
+    }
+    let mut words = String::new();
+    let millions = number / 1_000_000;
+    let rest = number % 1_000_000;
+    if millions > 0 {
+        words.push_str(&thai_number_words(millions));
+        words.push_str("ล้าน");
+    }
+    if rest > 0 {
+        words.push_str(&thai_group_words(rest, millions > 0));
+    }
+    words
+}
+
+/// Amount in Thai words as printed on receipts, e.g. หนึ่งพันห้าร้อยบาทถ้วน.
+pub fn thai_baht_text(amount_satang: i64) -> String {
+    let amount_satang = amount_satang.unsigned_abs();
+    let baht = amount_satang / 100;
+    let satang = amount_satang % 100;
+    let mut text = String::new();
+    if baht > 0 || satang == 0 {
+        text.push_str(&thai_number_words(baht));
+        text.push_str("บาท");
+    }
+    if satang == 0 {
+        text.push_str("ถ้วน");
+    } else {
+        text.push_str(&thai_number_words(satang));
+        text.push_str("สตางค์");
+    }
+    text
+}
+
+pub(super) fn dedupe_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut seen = HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+pub(super) fn required_text(value: &str, field_label: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::ValidationError(format!("กรุณาระบุ{field_label}")));
+    }
+    if value.chars().count() > MAX_NAME_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "{field_label}ยาวได้ไม่เกิน {MAX_NAME_CHARS} ตัวอักษร"
+        )));
+    }
+    Ok(value.to_string())
+}
+
+pub(super) fn optional_text(value: Option<String>) -> Result<Option<String>, AppError> {
+    let value = value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if value
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(AppError::ValidationError(format!(
+            "ข้อความยาวได้ไม่เกิน {MAX_NOTE_CHARS} ตัวอักษร"
+        )));
+    }
+    Ok(value)
+}
+
+pub(super) fn search_pattern(search: &str) -> String {
+    let mut pattern = String::with_capacity(search.len() + 2);
+    pattern.push('%');
+    for character in search.trim().chars() {
+        match character {
+            '\\' => pattern.push_str("\\\\"),
+            '%' => pattern.push_str("\\%"),
+            '_' => pattern.push_str("\\_"),
+            _ => pattern.push(character),
+        }
+    }
+    pattern.push('%');
+    pattern
+}
+
+fn unknown_code(kind: &str, value: &str) -> AppError {
+    tracing::error!("Unknown finance {} code: {}", kind, value);
+    AppError::InternalServerError("ข้อมูลการเงินไม่ถูกต้อง".to_string())
+}
+
+pub(super) fn parse_category(value: &str) -> Result<FeeCategory, AppError> {
+    FeeCategory::from_code(value).ok_or_else(|| unknown_code("category", value))
+}
+
+pub(super) fn parse_discount_kind(value: &str) -> Result<FeeDiscountKind, AppError> {
+    FeeDiscountKind::from_code(value).ok_or_else(|| unknown_code("discount kind", value))
+}
+
+pub(super) fn parse_invoice_status(value: &str) -> Result<FeeInvoiceStatus, AppError> {
+    FeeInvoiceStatus::from_code(value).ok_or_else(|| unknown_code("invoice status", value))
+}
+
+pub(super) fn parse_line_kind(value: &str) -> Result<FeeInvoiceLineKind, AppError> {
+    FeeInvoiceLineKind::from_code(value).ok_or_else(|| unknown_code("line kind", value))
+}
+
+pub(super) fn parse_payment_method(value: &str) -> Result<FeePaymentMethod, AppError> {
+    FeePaymentMethod::from_code(value).ok_or_else(|| unknown_code("payment method", value))
+}
+
+pub(super) fn parse_payment_status(value: &str) -> Result<FeePaymentStatus, AppError> {
+    FeePaymentStatus::from_code(value).ok_or_else(|| unknown_code("payment status", value))
+}
+
+pub(super) fn parse_promptpay_target_type(value: &str) -> Result<PromptPayTargetType, AppError> {
+    PromptPayTargetType::from_code(value).ok_or_else(|| unknown_code("PromptPay target", value))
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn charge(fee_item_id: Uuid, amount_satang: i64) -> FeeCharge {
+    FeeCharge {
+        fee_item_id,
+        description: "ค่าบำรุงการศึกษา".to_string(),
+        amount_satang,
+    }
+}
+
+fn discount(
+    fee_item_id: Option<Uuid>,
+    percent_basis_points: Option<i32>,
+    amount_satang: Option<i64>,
+) -> FeeDiscountRule {
+    FeeDiscountRule {
+        id: Uuid::new_v4(),
+        fee_item_id,
+        discount_kind: FeeDiscountKind::Scholarship,
+        name: "ทุนเรียนดี".to_string(),
+        percent_basis_points,
+        amount_satang,
+    }
+}
+
+#[test]
+fn crc16_matches_the_ccitt_false_check_value() {
+    assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+}
+
+#[test]
+fn promptpay_payload_encodes_phone_amount_and_bill_number() {
+    assert_eq!(
+        promptpay_payload(
+            PromptPayTargetType::Phone,
+            "0812345678",
+            Some(150_000),
+            Some("INV2569-000001"),
+        ),
+        "00020101021229370016A00000067701011101130066812345678530376454071500.005802TH62180114INV2569-00000163041458"
+    );
+}
+
+#[test]
+fn promptpay_payload_without_amount_is_static() {
+    assert_eq!(
+        promptpay_payload(PromptPayTargetType::TaxId, "0105560000001", None, None),
+        "00020101021129370016A0000006770101110213010556000000153037645802TH63048494"
+    );
+    assert_eq!(format_promptpay_amount(5), "0.05");
+}
+
+#[test]
+fn invoice_discounts_apply_against_the_remaining_amount() {
+    let tuition = Uuid::new_v4();
+    let activity = Uuid::new_v4();
+    let charges = [charge(tuition, 1_000_000), charge(activity, 200_000)];
+    let discounts = [
+        discount(None, Some(5_000), None),
+        discount(None, None, Some(100_000)),
+    ];
+
+    let computation = compute_invoice(&charges, &discounts);
+
+    assert_eq!(computation.subtotal_satang, 1_200_000);
+    assert_eq!(computation.discount_satang, 700_000);
+    assert_eq!(computation.total_satang(), 500_000);
+    assert_eq!(computation.lines.len(), 4);
+    assert_eq!(computation.lines[2].line_kind, FeeInvoiceLineKind::Discount);
+    assert_eq!(computation.lines[2].amount_satang, 600_000);
+}
+
+#[test]
+fn fixed_discounts_are_capped_at_the_targeted_charge() {
+    let tuition = Uuid::new_v4();
+    let activity = Uuid::new_v4();
+    let charges = [charge(tuition, 1_000_000), charge(activity, 200_000)];
+    let discounts = [discount(Some(activity), None, Some(500_000))];
+
+    let computation = compute_invoice(&charges, &discounts);
+
+    assert_eq!(computation.discount_satang, 200_000);
+    assert_eq!(computation.total_satang(), 1_000_000);
+    assert_eq!(computation.lines[2].fee_item_id, Some(activity));
+}
+
+#[test]
+fn percent_discounts_round_down_to_the_satang() {
+    let charges = [charge(Uuid::new_v4(), 333)];
+    let computation = compute_invoice(&charges, &[discount(None, Some(3_333), None)]);
+    assert_eq!(computation.discount_satang, 110);
+}
+
+#[test]
+fn full_scholarship_leaves_nothing_to_pay() {
+    let charges = [charge(Uuid::new_v4(), 500_000)];
+    let computation = compute_invoice(
+        &charges,
+        &[
+            discount(None, Some(10_000), None),
+            discount(None, None, Some(1_000)),
+        ],
+    );
+    assert_eq!(computation.total_satang(), 0);
+    assert_eq!(computation.lines.len(), 2);
+}
+
+#[test]
+fn payment_amount_must_fit_the_outstanding_balance() {
+    assert!(validate_payment_amount(500, 500).is_ok());
+    assert!(matches!(
+        validate_payment_amount(0, 500),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        validate_payment_amount(501, 500),
+        Err(AppError::Conflict(_))
+    ));
+}
+
+#[test]
+fn guardians_cannot_report_cash_payments() {
+    assert!(validate_guardian_payment_method(FeePaymentMethod::Promptpay).is_ok());
+    assert!(validate_guardian_payment_method(FeePaymentMethod::BankTransfer).is_ok());
+    assert!(validate_guardian_payment_method(FeePaymentMethod::Cash).is_err());
+}
+
+#[test]
+fn promptpay_targets_are_normalized_and_checked() {
+    assert_eq!(
+        normalize_promptpay_target(PromptPayTargetType::Phone, "081-234-5678").unwrap(),
+        "0812345678"
+    );
+    assert_eq!(
+        normalize_promptpay_target(PromptPayTargetType::TaxId, "0 1055 60000 00 1").unwrap(),
+        "0105560000001"
+    );
+    assert!(normalize_promptpay_target(PromptPayTargetType::TaxId, "1103700012345").is_err());
+    assert!(normalize_promptpay_target(PromptPayTargetType::Phone, "08123456").is_err());
+    assert!(normalize_promptpay_target(PromptPayTargetType::Ewallet, "12345abc").is_err());
+}
+
+#[test]
+fn codes_and_prefixes_are_uppercased() {
+    assert_eq!(normalize_receipt_prefix(" rc ").unwrap(), "RC");
+    assert!(normalize_receipt_prefix("RC1").is_err());
+    assert_eq!(normalize_fee_code("tuition-m1").unwrap(), "TUITION-M1");
+    assert!(normalize_fee_code("ค่าเทอม").is_err());
+}
+
+#[test]
+fn rates_reject_duplicates_per_grade_and_semester() {
+    let grade_level_id = Uuid::new_v4();
+    let semester_id = Some(Uuid::new_v4());
+    let rate = |academic_semester_id, amount_satang| FeeItemRateInput {
+        grade_level_id,
+        academic_semester_id,
+        amount_satang,
+    };
+
+    assert!(validate_rates(&[rate(None, 100), rate(semester_id, 100)]).is_ok());
+    assert!(validate_rates(&[rate(semester_id, 100), rate(semester_id, 200)]).is_err());
+    assert!(validate_rates(&[rate(None, 0)]).is_err());
+}
+
+#[test]
+fn discounts_take_exactly_one_value() {
+    assert!(validate_discount_value(Some(10_000), None).is_ok());
+    assert!(validate_discount_value(None, Some(1)).is_ok());
+    assert!(validate_discount_value(Some(10_001), None).is_err());
+    assert!(validate_discount_value(Some(100), Some(100)).is_err());
+    assert!(validate_discount_value(None, None).is_err());
+}
+
+#[test]
+fn document_numbers_use_the_buddhist_year() {
+    assert_eq!(buddhist_year(date(2026, 5, 16)), 2569);
+    assert_eq!(format_document_number("INV", 2569, 1), "INV2569-000001");
+    assert_eq!(format_document_number("RC", 2569, 123_456), "RC2569-123456");
+}
+
+#[test]
+fn only_unpaid_invoices_past_due_are_overdue() {
+    let today = date(2026, 6, 15);
+    assert!(is_overdue(date(2026, 6, 14), 100, today));
+    assert!(!is_overdue(date(2026, 6, 15), 100, today));
+    assert!(!is_overdue(date(2026, 6, 1), 0, today));
+}
+
+#[test]
+fn reconciliation_totals_list_every_method() {
+    let totals = reconciliation_totals(&[
+        (FeePaymentMethod::Cash, 100),
+        (FeePaymentMethod::Promptpay, 250),
+        (FeePaymentMethod::Cash, 50),
+    ]);
+
+    assert_eq!(totals.len(), 3);
+    assert_eq!(totals[0].method, FeePaymentMethod::Cash);
+    assert_eq!((totals[0].receipt_count, totals[0].amount_satang), (2, 150));
+    assert_eq!((totals[1].receipt_count, totals[1].amount_satang), (0, 0));
+    assert_eq!((totals[2].receipt_count, totals[2].amount_satang), (1, 250));
+}
+
+#[test]
+fn baht_text_reads_like_a_thai_receipt() {
+    assert_eq!(thai_baht_text(150_000), "หนึ่งพันห้าร้อยบาทถ้วน");
+    assert_eq!(thai_baht_text(2_125), "ยี่สิบเอ็ดบาทยี่สิบห้าสตางค์");
+    assert_eq!(thai_baht_text(1_100_000), "หนึ่งหมื่นหนึ่งพันบาทถ้วน");
+    assert_eq!(format_baht(123_456_789), "1,234,567.89");
+}
+
+#[test]
+fn review_notifications_skip_pending_payments() {
+    assert!(
+        payment_review_notification_text(FeePaymentStatus::PendingReview, "INV", 100, None)
+            .is_none()
+    );
+    let (_, message) = payment_review_notification_text(
+        FeePaymentStatus::Rejected,
+        "INV2569-000001",
+        150_000,
+        Some("ยอดไม่ตรง"),
+    )
+    .unwrap();
+    assert!(message.contains("1,500.00"));
+    assert!(message.contains("ยอดไม่ตรง"));
+}
@@ -36,12 +36,13 @@ pub async fn get_user_menu(
     let context = actor_tenant_context_from_session(&state, &session)
         .await
         .map_err(|_| AppError::AuthError("ไม่สามารถดึงข้อมูล permissions ได้".to_string()))?;
+    let plan = context.tenant.plan;
     let pool = context.tenant.pool;
     let actor = context.actor;
     let user_type = public_menu_service::get_user_type(&pool, actor.user_id).await?;
 
     let rows = public_menu_service::fetch_menu_items(&pool, &user_type).await?;
-    let groups = public_menu_service::group_and_filter_menu(rows, &actor);
+    let groups = public_menu_service::group_and_filter_menu(rows, &actor, &plan);
 
     Ok((
         StatusCode::OK,
@@ -1,6 +1,8 @@
+use crate::db::admin_client::TenantPlan;
 use crate::error::AppError;
 use crate::middleware::permission::ActorContext;
 use crate::modules::menu::models::{MenuGroupResponse, MenuItemResponse};
+use crate::modules::system::services::feature_toggle_service;
 use sqlx::FromRow;
 use sqlx::PgPool;
 use std::collections::HashMap;
@@ -65,7 +67,13 @@ pub async fn fetch_menu_items(pool: &PgPool, user_type: &str) -> Result<Vec<Menu
     })
 }
 
-pub fn group_and_filter_menu(rows: Vec<MenuRow>, actor: &ActorContext) -> Vec<MenuGroupResponse> {
+/// Items of modules outside the school's plan are hidden even when the
+/// actor holds the permission.
+pub fn group_and_filter_menu(
+    rows: Vec<MenuRow>,
+    actor: &ActorContext,
+    plan: &TenantPlan,
+) -> Vec<MenuGroupResponse> {
     struct GroupWithOrder {
         order: i32,
         code: String,
@@ -92,7 +100,9 @@ pub fn group_and_filter_menu(rows: Vec<MenuRow>, actor: &ActorContext) -> Vec<Me
                 actor.has_module_permission(required_permission)
             };
 
-            if !can_open_menu {
+            if !can_open_menu
+                || !feature_toggle_service::is_permission_in_plan(plan, required_permission)
+            {
                 continue;
             }
         }
@@ -227,6 +237,7 @@ mod tests {
         let groups = group_and_filter_menu(
             rows,
             &actor(&[crate::permissions::registry::codes::ACADEMIC_CURRICULUM_READ_ALL]),
+            &TenantPlan::default(),
         );
 
         assert_eq!(groups.len(), 1);
@@ -248,6 +259,7 @@ mod tests {
         let groups = group_and_filter_menu(
             vec![menu_row("my-home", None, "daily", 10, "home", 10, 10)],
             &actor(&[]),
+            &TenantPlan::default(),
         );
 
         assert_eq!(groups.len(), 1);
@@ -273,6 +285,7 @@ mod tests {
                     10,
                 )],
                 &actor(&[permission]),
+                &TenantPlan::default(),
             );
 
             assert_eq!(groups.len(), 1, "expected menu for {permission}");
@@ -289,8 +302,39 @@ mod tests {
                 10,
             )],
             &actor(&[crate::permissions::registry::codes::CERTIFICATE_READ_OWN]),
+            &TenantPlan::default(),
         );
 
         assert!(own_only.is_empty());
     }
+
+    #[test]
+    fn hides_menu_items_of_modules_outside_the_plan() {
+        let plan = TenantPlan {
+            enabled_modules: Some(vec!["staff".to_string()]),
+            ..TenantPlan::default()
+        };
+        let groups = group_and_filter_menu(
+            vec![
+                menu_row("staff", Some("staff"), "personnel", 10, "personnel", 40, 10),
+                menu_row(
+                    "certificates",
+                    Some("certificate.read.school"),
+                    "academic-services",
+                    10,
+                    "academic",
+                    20,
+                    10,
+                ),
+            ],
+            &actor(&[
+                crate::permissions::registry::codes::STAFF_READ_ALL,
+                crate::permissions::registry::codes::CERTIFICATE_READ_SCHOOL,
+            ]),
+            &plan,
+        );
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].code, "staff");
+    }
 }
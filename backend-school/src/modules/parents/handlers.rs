@@ -191,3 +191,29 @@ pub async fn get_child_calendar_events(
 
     Ok((StatusCode::OK, Json(ApiResponse::ok(events))))
 }
+
+/// GET /api/parent/students/:student_id/fees - ยอดค้างชำระและใบแจ้งหนี้ของบุตรหลาน
+#[utoipa::path(
+    get,
+    path = "/api/parent/students/{student_id}/fees",
+    operation_id = "getParentChildFees",
+    tag = "parent",
+    params(("student_id" = Uuid, Path, description = "Linked student user ID")),
+    responses(
+        (status = 200, description = "Linked child's fee invoices and outstanding balance", body = ApiResponse<crate::modules::finance::models::ChildFeeStatement>),
+        (status = 401, description = "Authentication required", body = ApiErrorResponse),
+        (status = 403, description = "Parent-child access denied", body = ApiErrorResponse)
+    )
+)]
+pub async fn get_child_fees(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(student_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let statement =
+        parent_service::get_child_fees(&context.tenant.pool, context.actor.user_id, student_id)
+            .await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(statement))))
+}
@@ -7,6 +7,7 @@ use crate::modules::academic::models::timetable::TimetableEntry;
 use crate::modules::academic::services::exam_schedule_service;
 use crate::modules::academic::services::timetable_service::{self, TimetableFilter};
 use crate::modules::calendar::models::{CalendarEventQuery, CalendarViewerEvent};
+use crate::modules::finance::models::ChildFeeStatement;
 use crate::modules::students::models::{ParentDto, StudentDbRow, StudentProfile};
 use crate::utils::field_encryption;
 
@@ -147,7 +148,15 @@ pub async fn get_child_calendar_events(
     crate::modules::calendar::services::list_child_events(pool, parent_id, student_id, query).await
 }
 
-async fn ensure_parent_user(pool: &PgPool, parent_id: Uuid) -> Result<(), AppError> {
+pub async fn get_child_fees(
+    pool: &PgPool,
+    parent_id: Uuid,
+    student_id: Uuid,
+) -> Result<ChildFeeStatement, AppError> {
+    crate::modules::finance::services::child_fee_statement(pool, parent_id, student_id).await
+}
+
+pub(crate) async fn ensure_parent_user(pool: &PgPool, parent_id: Uuid) -> Result<(), AppError> {
     let user_type: Option<String> = sqlx::query_scalar("SELECT user_type FROM users WHERE id = $1")
         .bind(parent_id)
         .fetch_optional(pool)
@@ -160,7 +169,7 @@ async fn ensure_parent_user(pool: &PgPool, parent_id: Uuid) -> Result<(), AppErr
     parent_user_access(user_type.as_deref())
 }
 
-async fn ensure_parent_student_link(
+pub(crate) async fn ensure_parent_student_link(
     pool: &PgPool,
     parent_id: Uuid,
     student_id: Uuid,
@@ -0,0 +1,283 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::files::consumer_service::request_deletions;
+use crate::modules::professional_development::models::{
+    PdSummaryFilter, PdTrainingFilter, PlcSessionFilter, SavePdTrainingRecordRequest,
+    SavePlcSessionRequest, UpsertPdHourRequirementRequest,
+};
+use crate::modules::professional_development::services;
+use crate::utils::request_context::{actor_tenant_context_from_session, ActorTenantContext};
+use crate::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FiscalYearQuery {
+    fiscal_year: Option<i32>,
+}
+
+/// Hands evidence files a mutation dropped to File Platform deletion.
+async fn release_files(
+    state: &AppState,
+    context: &ActorTenantContext,
+    detached_file_ids: Vec<Uuid>,
+) -> Result<(), AppError> {
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        detached_file_ids,
+    )
+    .await
+}
+
+/// GET /api/professional-development/requirements - เกณฑ์ชั่วโมงรายปีงบประมาณ
+async fn list_requirements(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_requirements(&context.tenant.pool, &context.actor).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// PUT /api/professional-development/requirements/:fiscal_year - กำหนดเกณฑ์ชั่วโมง
+async fn upsert_requirement(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(fiscal_year): Path<i32>,
+    Json(payload): Json<UpsertPdHourRequirementRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let requirement =
+        services::upsert_requirement(&context.tenant.pool, &context.actor, fiscal_year, payload)
+            .await?;
+    Ok(Json(ApiResponse::ok(requirement)))
+}
+
+/// GET /api/professional-development/plc-sessions - บันทึก PLC
+async fn list_plc_sessions(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<PlcSessionFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_plc_sessions(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_plc_session(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<SavePlcSessionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::create_plc_session(&context.tenant.pool, &context.actor, payload).await?;
+    release_files(&state, &context, outcome.detached_file_ids).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(outcome.record))))
+}
+
+async fn get_plc_session(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let plc_session = services::get_plc_session(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(plc_session)))
+}
+
+async fn update_plc_session(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SavePlcSessionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::update_plc_session(&context.tenant.pool, &context.actor, id, payload).await?;
+    release_files(&state, &context, outcome.detached_file_ids).await?;
+    Ok(Json(ApiResponse::ok(outcome.record)))
+}
+
+async fn delete_plc_session(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let detached_file_ids =
+        services::delete_plc_session(&context.tenant.pool, &context.actor, id).await?;
+    release_files(&state, &context, detached_file_ids).await?;
+    Ok(Json(ApiResponse::empty()))
+}
+
+/// GET /api/professional-development/training - บันทึกการอบรมและพัฒนาตนเอง
+async fn list_training_records(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<PdTrainingFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items =
+        services::list_training_records(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_training_record(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<SavePdTrainingRecordRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::create_training_record(&context.tenant.pool, &context.actor, payload).await?;
+    release_files(&state, &context, outcome.detached_file_ids).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(outcome.record))))
+}
+
+async fn update_training_record(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SavePdTrainingRecordRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let outcome =
+        services::update_training_record(&context.tenant.pool, &context.actor, id, payload).await?;
+    release_files(&state, &context, outcome.detached_file_ids).await?;
+    Ok(Json(ApiResponse::ok(outcome.record)))
+}
+
+async fn delete_training_record(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let detached_file_ids =
+        services::delete_training_record(&context.tenant.pool, &context.actor, id).await?;
+    release_files(&state, &context, detached_file_ids).await?;
+    Ok(Json(ApiResponse::empty()))
+}
+
+/// GET /api/professional-development/me/summary - ชั่วโมงสะสมของตนเองเทียบเกณฑ์
+async fn my_summary(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<FiscalYearQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let summary =
+        services::my_summary(&context.tenant.pool, &context.actor, query.fiscal_year).await?;
+    Ok(Json(ApiResponse::ok(summary)))
+}
+
+/// GET /api/professional-development/summaries - ชั่วโมงสะสมของบุคลากรทั้งโรงเรียน
+async fn list_summaries(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<PdSummaryFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_summaries(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/professional-development/staff/:user_id/portfolio - แฟ้มสะสมผลงาน
+async fn get_portfolio(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FiscalYearQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let portfolio = services::get_portfolio(
+        &context.tenant.pool,
+        &context.actor,
+        user_id,
+        query.fiscal_year,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(portfolio)))
+}
+
+/// GET /api/professional-development/staff/:user_id/portfolio/pdf - พิมพ์แฟ้มสะสมผลงาน
+async fn download_portfolio(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FiscalYearQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let school_name = state
+        .admin_client
+        .get_school_name(&context.tenant.subdomain)
+        .await
+        .map_err(|_| AppError::ServiceUnavailable("school_name_lookup_failed".to_string()))?;
+
+    let file = services::render_portfolio_report(
+        &context.tenant.pool,
+        &context.actor,
+        user_id,
+        query.fiscal_year,
+        &school_name,
+    )
+    .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file.filename),
+            ),
+        ],
+        file.content,
+    )
+        .into_response())
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/requirements", get(list_requirements))
+        .route("/requirements/{fiscal_year}", put(upsert_requirement))
+        .route(
+            "/plc-sessions",
+            get(list_plc_sessions).post(create_plc_session),
+        )
+        .route(
+            "/plc-sessions/{id}",
+            get(get_plc_session)
+                .put(update_plc_session)
+                .delete(delete_plc_session),
+        )
+        .route(
+            "/training",
+            get(list_training_records).post(create_training_record),
+        )
+        .route(
+            "/training/{id}",
+            put(update_training_record).delete(delete_training_record),
+        )
+        .route("/me/summary", get(my_summary))
+        .route("/summaries", get(list_summaries))
+        .route("/staff/{user_id}/portfolio", get(get_portfolio))
+        .route("/staff/{user_id}/portfolio/pdf", get(download_portfolio))
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlcParticipantRole {
+    Facilitator,
+    #[default]
+    Member,
+}
+
+impl PlcParticipantRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Facilitator => "facilitator",
+            Self::Member => "member",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "facilitator" => Some(Self::Facilitator),
+            "member" => Some(Self::Member),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Facilitator => "ผู้นำกิจกรรม",
+            Self::Member => "สมาชิก",
+        }
+    }
+}
+
+/// Where a staff member stands against the fiscal year's hour requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PdRequirementStatus {
+    NotConfigured,
+    Met,
+    Below,
+}
+
+impl PdRequirementStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NotConfigured => "ยังไม่กำหนดเกณฑ์",
+            Self::Met => "ผ่านเกณฑ์",
+            Self::Below => "ยังไม่ครบเกณฑ์",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdHourRequirement {
+    pub fiscal_year: i32,
+    pub plc_hours_required: f64,
+    pub training_hours_required: f64,
+    pub note: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertPdHourRequirementRequest {
+    pub plc_hours_required: f64,
+    pub training_hours_required: f64,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlcParticipant {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub role: PlcParticipantRole,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlcSession {
+    pub id: Uuid,
+    pub fiscal_year: i32,
+    pub session_date: NaiveDate,
+    pub topic: String,
+    pub details: Option<String>,
+    pub hours: f64,
+    pub supervision_observation_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub participants: Vec<PlcParticipant>,
+    pub evidence_file_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePlcSessionRequest {
+    pub session_date: NaiveDate,
+    pub topic: String,
+    #[serde(default)]
+    pub details: Option<String>,
+    pub hours: f64,
+    #[serde(default)]
+    pub facilitator_user_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub member_user_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub supervision_observation_id: Option<Uuid>,
+    #[serde(default)]
+    pub evidence_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlcSessionFilter {
+    pub fiscal_year: Option<i32>,
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdTrainingRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub fiscal_year: i32,
+    pub title: String,
+    pub provider: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub hours: f64,
+    pub note: Option<String>,
+    pub certificate_file_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePdTrainingRecordRequest {
+    /// Defaults to the actor; recording for someone else needs the manage grant
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+    pub title: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub hours: f64,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub certificate_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdTrainingFilter {
+    pub fiscal_year: Option<i32>,
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdHourSummary {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub fiscal_year: i32,
+    pub plc_hours: f64,
+    pub plc_session_count: i64,
+    pub training_hours: f64,
+    pub training_count: i64,
+    pub plc_hours_required: Option<f64>,
+    pub training_hours_required: Option<f64>,
+    pub plc_hours_remaining: Option<f64>,
+    pub training_hours_remaining: Option<f64>,
+    pub status: PdRequirementStatus,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdSummaryFilter {
+    pub fiscal_year: Option<i32>,
+    pub status: Option<PdRequirementStatus>,
+}
+
+/// A released supervision observation shown as classroom evidence
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdPortfolioObservation {
+    pub id: Uuid,
+    pub cycle_title: String,
+    pub observed_at: DateTime<Utc>,
+    pub subject_name: Option<String>,
+    pub average_rating: Option<f64>,
+    pub linked_plc_session_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdPortfolio {
+    pub summary: PdHourSummary,
+    pub plc_sessions: Vec<PlcSession>,
+    pub training_records: Vec<PdTrainingRecord>,
+    pub observations: Vec<PdPortfolioObservation>,
+}
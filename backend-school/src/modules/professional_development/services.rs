@@ -0,0 +1,16 @@
+mod plc_sessions;
+mod portfolio_report;
+mod requirements;
+mod shared;
+mod summaries;
+mod training;
+
+pub use plc_sessions::{
+    create_plc_session, delete_plc_session, get_plc_session, list_plc_sessions, update_plc_session,
+};
+pub use portfolio_report::render_portfolio_report;
+pub use requirements::{list_requirements, upsert_requirement};
+pub use summaries::{get_portfolio, list_summaries, my_summary};
+pub use training::{
+    create_training_record, delete_training_record, list_training_records, update_training_record,
+};
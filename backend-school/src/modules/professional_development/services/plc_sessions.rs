@@ -0,0 +1,620 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::{
+    PlcParticipant, PlcParticipantRole, PlcSession, PlcSessionFilter, SavePlcSessionRequest,
+};
+use crate::modules::staff_leave::services::fiscal_year_for;
+use crate::policies::professional_development_access_policy;
+
+use super::shared::{
+    dedupe_ids, ensure_active_staff, ensure_not_future, hours_to_minutes, minutes_to_hours,
+    normalize_optional_text, read_error, required_text, resolve_fiscal_year, resolve_listed_user,
+    retain_evidence_files, validate_evidence_files, write_error,
+    ProfessionalDevelopmentMutationOutcome,
+};
+
+const SESSION_NOT_FOUND_MESSAGE: &str = "ไม่พบบันทึก PLC";
+const MAX_SESSION_MINUTES: i32 = 24 * 60;
+
+#[derive(Debug, sqlx::FromRow)]
+struct SessionRow {
+    id: Uuid,
+    fiscal_year: i32,
+    session_date: NaiveDate,
+    topic: String,
+    details: Option<String>,
+    duration_minutes: i32,
+    supervision_observation_id: Option<Uuid>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ParticipantRow {
+    session_id: Uuid,
+    user_id: Uuid,
+    user_name: String,
+    role: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct EvidenceRow {
+    session_id: Uuid,
+    file_id: Uuid,
+}
+
+struct ValidatedSession {
+    fiscal_year: i32,
+    topic: String,
+    details: Option<String>,
+    duration_minutes: i32,
+    participants: Vec<(Uuid, PlcParticipantRole)>,
+    evidence_file_ids: Vec<Uuid>,
+}
+
+pub async fn list_plc_sessions(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: PlcSessionFilter,
+) -> Result<Vec<PlcSession>, AppError> {
+    let user_id = resolve_listed_user(actor, filter.user_id)?;
+    let fiscal_year = resolve_fiscal_year(filter.fiscal_year)?;
+    load_sessions(pool, fiscal_year, user_id).await
+}
+
+pub async fn get_plc_session(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<PlcSession, AppError> {
+    let session = load_session(pool, id).await?;
+    professional_development_access_policy::require_plc_session_read(pool, actor, id).await?;
+    Ok(session)
+}
+
+pub async fn create_plc_session(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: SavePlcSessionRequest,
+) -> Result<ProfessionalDevelopmentMutationOutcome<PlcSession>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let input = validate_session_input(pool, actor, None, &payload).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO pd_plc_sessions (
+            fiscal_year, session_date, topic, details, duration_minutes,
+            supervision_observation_id, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+    )
+    .bind(input.fiscal_year)
+    .bind(payload.session_date)
+    .bind(&input.topic)
+    .bind(&input.details)
+    .bind(input.duration_minutes)
+    .bind(payload.supervision_observation_id)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    insert_plc_participants(&mut transaction, id, &input.participants).await?;
+    let detached_file_ids = replace_plc_evidence(
+        &mut transaction,
+        id,
+        &input.evidence_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(ProfessionalDevelopmentMutationOutcome {
+        record: load_session(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+pub async fn update_plc_session(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: SavePlcSessionRequest,
+) -> Result<ProfessionalDevelopmentMutationOutcome<PlcSession>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let current = load_session(pool, id).await?;
+    ensure_session_editor(actor, &current)?;
+    let input = validate_session_input(pool, actor, Some(id), &payload).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    sqlx::query(
+        r#"
+        UPDATE pd_plc_sessions
+        SET fiscal_year = $2,
+            session_date = $3,
+            topic = $4,
+            details = $5,
+            duration_minutes = $6,
+            supervision_observation_id = $7
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(input.fiscal_year)
+    .bind(payload.session_date)
+    .bind(&input.topic)
+    .bind(&input.details)
+    .bind(input.duration_minutes)
+    .bind(payload.supervision_observation_id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    sqlx::query("DELETE FROM pd_plc_participants WHERE session_id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(write_error)?;
+    insert_plc_participants(&mut transaction, id, &input.participants).await?;
+    let detached_file_ids = replace_plc_evidence(
+        &mut transaction,
+        id,
+        &input.evidence_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(ProfessionalDevelopmentMutationOutcome {
+        record: load_session(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+/// Deletes the session and returns its evidence files for File Platform
+/// deletion.
+pub async fn delete_plc_session(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let current = load_session(pool, id).await?;
+    ensure_session_editor(actor, &current)?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let detached_file_ids = replace_plc_evidence(&mut transaction, id, &[], actor.user_id).await?;
+    sqlx::query("DELETE FROM pd_plc_sessions WHERE id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(write_error)?;
+    transaction.commit().await.map_err(write_error)?;
+    Ok(detached_file_ids)
+}
+
+/// Sessions of a fiscal year, limited to those `user_id` took part in
+pub(super) async fn load_sessions(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Option<Uuid>,
+) -> Result<Vec<PlcSession>, AppError> {
+    let rows = sqlx::query_as::<_, SessionRow>(
+        r#"
+        SELECT s.id, s.fiscal_year, s.session_date, s.topic, s.details, s.duration_minutes,
+               s.supervision_observation_id, s.created_by, s.created_at, s.updated_at
+        FROM pd_plc_sessions s
+        WHERE s.fiscal_year = $1
+          AND (
+              $2::uuid IS NULL
+              OR EXISTS (
+                  SELECT 1
+                  FROM pd_plc_participants p
+                  WHERE p.session_id = s.id
+                    AND p.user_id = $2
+              )
+          )
+        ORDER BY s.session_date DESC, s.created_at DESC
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    assemble_sessions(pool, rows).await
+}
+
+async fn load_session(pool: &PgPool, id: Uuid) -> Result<PlcSession, AppError> {
+    let row = sqlx::query_as::<_, SessionRow>(
+        r#"
+        SELECT id, fiscal_year, session_date, topic, details, duration_minutes,
+               supervision_observation_id, created_by, created_at, updated_at
+        FROM pd_plc_sessions
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?
+    .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND_MESSAGE.to_string()))?;
+
+    assemble_sessions(pool, vec![row])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(SESSION_NOT_FOUND_MESSAGE.to_string()))
+}
+
+async fn assemble_sessions(
+    pool: &PgPool,
+    rows: Vec<SessionRow>,
+) -> Result<Vec<PlcSession>, AppError> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+
+    let participants = sqlx::query_as::<_, ParticipantRow>(
+        r#"
+        SELECT p.session_id,
+               p.user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               p.role
+        FROM pd_plc_participants p
+        JOIN users u ON u.id = p.user_id
+        WHERE p.session_id = ANY($1)
+        ORDER BY p.role = 'member', u.first_name, u.last_name
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let evidence = sqlx::query_as::<_, EvidenceRow>(
+        r#"
+        SELECT session_id, file_id
+        FROM pd_plc_evidence
+        WHERE session_id = ANY($1)
+        ORDER BY created_at, file_id
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    rows.into_iter()
+        .map(|row| {
+            let participants = participants
+                .iter()
+                .filter(|participant| participant.session_id == row.id)
+                .map(|participant| {
+                    Ok(PlcParticipant {
+                        user_id: participant.user_id,
+                        user_name: participant.user_name.clone(),
+                        role: PlcParticipantRole::from_code(&participant.role).ok_or_else(
+                            || {
+                                AppError::InternalServerError(
+                                    "บทบาทผู้เข้าร่วม PLC ในฐานข้อมูลไม่ถูกต้อง".to_string(),
+                                )
+                            },
+                        )?,
+                    })
+                })
+                .collect::<Result<Vec<_>, AppError>>()?;
+            Ok(PlcSession {
+                id: row.id,
+                fiscal_year: row.fiscal_year,
+                session_date: row.session_date,
+                topic: row.topic,
+                details: row.details,
+                hours: minutes_to_hours(i64::from(row.duration_minutes)),
+                supervision_observation_id: row.supervision_observation_id,
+                created_by: row.created_by,
+                participants,
+                evidence_file_ids: evidence
+                    .iter()
+                    .filter(|file| file.session_id == row.id)
+                    .map(|file| file.file_id)
+                    .collect(),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect()
+}
+
+async fn validate_session_input(
+    pool: &PgPool,
+    actor: &ActorContext,
+    session_id: Option<Uuid>,
+    payload: &SavePlcSessionRequest,
+) -> Result<ValidatedSession, AppError> {
+    let topic = required_text(&payload.topic, "กรุณาระบุหัวข้อ PLC")?;
+    ensure_not_future(payload.session_date, "วันที่จัดกิจกรรม PLC ต้องไม่เป็นวันในอนาคต")?;
+    let duration_minutes = hours_to_minutes(payload.hours, "จำนวนชั่วโมง PLC ต้องเป็นทวีคูณของ 15 นาที")?;
+    if !(1..=MAX_SESSION_MINUTES).contains(&duration_minutes) {
+        return Err(AppError::ValidationError(
+            "จำนวนชั่วโมง PLC ต้องมากกว่า 0 และไม่เกิน 24 ชั่วโมง".to_string(),
+        ));
+    }
+
+    let participants = plan_participants(
+        actor.user_id,
+        professional_development_access_policy::can_manage(actor),
+        &payload.facilitator_user_ids,
+        &payload.member_user_ids,
+    )?;
+    let participant_ids: Vec<Uuid> = participants.iter().map(|(user_id, _)| *user_id).collect();
+    ensure_active_staff(pool, &participant_ids).await?;
+
+    if let Some(observation_id) = payload.supervision_observation_id {
+        ensure_linkable_observation(pool, observation_id, &participant_ids).await?;
+    }
+
+    let evidence_file_ids = dedupe_ids(payload.evidence_file_ids.clone());
+    validate_evidence_files(pool, actor.user_id, session_id, &evidence_file_ids).await?;
+
+    Ok(ValidatedSession {
+        fiscal_year: fiscal_year_for(payload.session_date),
+        topic,
+        details: normalize_optional_text(payload.details.clone()),
+        duration_minutes,
+        participants,
+        evidence_file_ids,
+    })
+}
+
+/// Facilitators win over a duplicate member entry. Staff without the manage
+/// grant only record sessions they took part in, so they are added as a
+/// facilitator when they did not list themselves.
+pub(super) fn plan_participants(
+    actor_user_id: Uuid,
+    actor_can_manage: bool,
+    facilitator_user_ids: &[Uuid],
+    member_user_ids: &[Uuid],
+) -> Result<Vec<(Uuid, PlcParticipantRole)>, AppError> {
+    let mut participants: Vec<(Uuid, PlcParticipantRole)> = Vec::new();
+    for user_id in dedupe_ids(facilitator_user_ids.to_vec()) {
+        participants.push((user_id, PlcParticipantRole::Facilitator));
+    }
+    for user_id in dedupe_ids(member_user_ids.to_vec()) {
+        if !participants.iter().any(|(listed, _)| *listed == user_id) {
+            participants.push((user_id, PlcParticipantRole::Member));
+        }
+    }
+    if !actor_can_manage
+        && !participants
+            .iter()
+            .any(|(listed, _)| *listed == actor_user_id)
+    {
+        participants.insert(0, (actor_user_id, PlcParticipantRole::Facilitator));
+    }
+    if participants.is_empty() {
+        return Err(AppError::ValidationError(
+            "กรุณาระบุผู้เข้าร่วม PLC อย่างน้อย 1 คน".to_string(),
+        ));
+    }
+    Ok(participants)
+}
+
+/// The recorder, any facilitator and portfolio managers may change a session.
+pub(super) fn can_edit_session(actor: &ActorContext, session: &PlcSession) -> bool {
+    professional_development_access_policy::can_manage(actor)
+        || session.created_by == Some(actor.user_id)
+        || session.participants.iter().any(|participant| {
+            participant.user_id == actor.user_id
+                && participant.role == PlcParticipantRole::Facilitator
+        })
+}
+
+fn ensure_session_editor(actor: &ActorContext, session: &PlcSession) -> Result<(), AppError> {
+    if can_edit_session(actor, session) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "แก้ไขได้เฉพาะผู้บันทึกหรือผู้นำกิจกรรม PLC".to_string(),
+        ))
+    }
+}
+
+/// A session may reflect on a classroom observation of one of its
+/// participants that was not cancelled.
+async fn ensure_linkable_observation(
+    pool: &PgPool,
+    observation_id: Uuid,
+    participant_ids: &[Uuid],
+) -> Result<(), AppError> {
+    let linkable = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM supervision_observations
+            WHERE id = $1
+              AND status <> 'cancelled'
+              AND observed_user_id = ANY($2)
+        )
+        "#,
+    )
+    .bind(observation_id)
+    .bind(participant_ids)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+
+    if linkable {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "การนิเทศที่เชื่อมโยงต้องเป็นของผู้เข้าร่วม PLC และไม่ถูกยกเลิก".to_string(),
+        ))
+    }
+}
+
+async fn insert_plc_participants(
+    transaction: &mut Transaction<'_, Postgres>,
+    session_id: Uuid,
+    participants: &[(Uuid, PlcParticipantRole)],
+) -> Result<(), AppError> {
+    let user_ids: Vec<Uuid> = participants.iter().map(|(user_id, _)| *user_id).collect();
+    let roles: Vec<&str> = participants.iter().map(|(_, role)| role.as_str()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO pd_plc_participants (session_id, user_id, role)
+        SELECT $1, participant.user_id, participant.role
+        FROM UNNEST($2::uuid[], $3::text[]) AS participant(user_id, role)
+        "#,
+    )
+    .bind(session_id)
+    .bind(&user_ids)
+    .bind(&roles)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+    Ok(())
+}
+
+/// Attaches the given evidence and returns the previously attached files that
+/// are no longer listed.
+async fn replace_plc_evidence(
+    transaction: &mut Transaction<'_, Postgres>,
+    session_id: Uuid,
+    file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let detached_file_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        DELETE FROM pd_plc_evidence
+        WHERE session_id = $1
+          AND NOT (file_id = ANY($2))
+        RETURNING file_id
+        "#,
+    )
+    .bind(session_id)
+    .bind(file_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if !file_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO pd_plc_evidence (session_id, file_id, attached_by)
+            SELECT $1, file_id, $3
+            FROM UNNEST($2::uuid[]) AS file_id
+            ON CONFLICT (session_id, file_id) DO NOTHING
+            "#,
+        )
+        .bind(session_id)
+        .bind(file_ids)
+        .bind(attached_by)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+        retain_evidence_files(transaction, file_ids).await?;
+    }
+
+    Ok(detached_file_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permissions::registry::codes;
+
+    fn actor(user_id: Uuid, permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id,
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn recorder_joins_as_facilitator_unless_managing() {
+        let recorder = Uuid::new_v4();
+        let colleague = Uuid::new_v4();
+
+        let planned = plan_participants(recorder, false, &[], &[colleague, colleague]).unwrap();
+        assert_eq!(
+            planned,
+            vec![
+                (recorder, PlcParticipantRole::Facilitator),
+                (colleague, PlcParticipantRole::Member),
+            ]
+        );
+
+        let planned = plan_participants(recorder, false, &[colleague], &[recorder]).unwrap();
+        assert_eq!(
+            planned,
+            vec![
+                (colleague, PlcParticipantRole::Facilitator),
+                (recorder, PlcParticipantRole::Member),
+            ]
+        );
+
+        let planned = plan_participants(recorder, true, &[colleague], &[colleague]).unwrap();
+        assert_eq!(planned, vec![(colleague, PlcParticipantRole::Facilitator)]);
+        assert!(matches!(
+            plan_participants(recorder, true, &[], &[]),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn only_recorder_facilitators_and_managers_edit_sessions() {
+        let recorder = Uuid::new_v4();
+        let facilitator = Uuid::new_v4();
+        let member = Uuid::new_v4();
+        let now = Utc::now();
+        let participant = |user_id, role| PlcParticipant {
+            user_id,
+            user_name: "ครู".to_string(),
+            role,
+        };
+        let session = PlcSession {
+            id: Uuid::new_v4(),
+            fiscal_year: 2570,
+            session_date: now.date_naive(),
+            topic: "Lesson study".to_string(),
+            details: None,
+            hours: 2.0,
+            supervision_observation_id: None,
+            created_by: Some(recorder),
+            participants: vec![
+                participant(facilitator, PlcParticipantRole::Facilitator),
+                participant(member, PlcParticipantRole::Member),
+            ],
+            evidence_file_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        let submit = codes::PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN;
+
+        assert!(can_edit_session(&actor(recorder, &[submit]), &session));
+        assert!(can_edit_session(&actor(facilitator, &[submit]), &session));
+        assert!(!can_edit_session(&actor(member, &[submit]), &session));
+        assert!(can_edit_session(
+            &actor(
+                Uuid::new_v4(),
+                &[codes::PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL]
+            ),
+            &session
+        ));
+    }
+}
@@ -0,0 +1,447 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::{PdPortfolio, PlcParticipantRole};
+use crate::modules::staff_leave::services::fiscal_year_bounds;
+use crate::utils::pdf::{
+    centered_line, draw_wrapped_table, ensure_space, paragraph, school_date, thai_long_date,
+    thai_short_date, PdfDocument, PdfFontWeight, PdfPageSize, PdfTextAlign, TableColumn,
+    A4_PORTRAIT, PAGE_MARGIN, SECTION_MIN_HEIGHT,
+};
+
+use super::summaries::get_portfolio;
+
+const SIGNATURE_BLOCK_HEIGHT: f32 = 110.0;
+const ORDER_COLUMN_WIDTH: f32 = 28.0;
+const DATE_COLUMN_WIDTH: f32 = 72.0;
+const HOURS_COLUMN_WIDTH: f32 = 48.0;
+
+pub struct PortfolioReportFile {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+/// Renders the printable วPA portfolio of one staff member for a fiscal
+/// year; access follows [`get_portfolio`].
+pub async fn render_portfolio_report(
+    pool: &PgPool,
+    actor: &ActorContext,
+    user_id: Uuid,
+    fiscal_year: Option<i32>,
+    school_name: &str,
+) -> Result<PortfolioReportFile, AppError> {
+    let portfolio = get_portfolio(pool, actor, user_id, fiscal_year).await?;
+    Ok(PortfolioReportFile {
+        filename: format!(
+            "pd-portfolio-{}-{}.pdf",
+            portfolio.summary.fiscal_year, user_id
+        ),
+        content: render_portfolio_pdf(school_name, &portfolio).finish(),
+    })
+}
+
+fn render_portfolio_pdf(school_name: &str, portfolio: &PdPortfolio) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let summary = &portfolio.summary;
+    let content_width = page.width - 2.0 * PAGE_MARGIN;
+
+    let mut document = PdfDocument::new(format!(
+        "แฟ้มสะสมผลงานการพัฒนาวิชาชีพ {} ปีงบประมาณ {}",
+        summary.user_name, summary.fiscal_year
+    ));
+    document.add_page(page);
+    let mut y = PAGE_MARGIN + 16.0;
+    centered_line(
+        &mut document,
+        page,
+        y,
+        16.0,
+        PdfFontWeight::Bold,
+        "แฟ้มสะสมผลงานการพัฒนาวิชาชีพ",
+    );
+    y += 20.0;
+    centered_line(
+        &mut document,
+        page,
+        y,
+        13.0,
+        PdfFontWeight::Regular,
+        &format!("{} · ปีงบประมาณ {}", school_name, summary.fiscal_year),
+    );
+    y += 26.0;
+
+    let period = fiscal_year_bounds(summary.fiscal_year)
+        .map(|(starts_on, ends_on)| {
+            format!(
+                "{} ถึง {}",
+                thai_long_date(starts_on),
+                thai_long_date(ends_on)
+            )
+        })
+        .unwrap_or_else(|| "-".to_string());
+    for detail in [
+        format!("ชื่อ-สกุล: {}", summary.user_name),
+        format!("ช่วงเวลา: {}", period),
+    ] {
+        y = paragraph(&mut document, page, y, PdfFontWeight::Regular, &detail);
+    }
+
+    y = section_heading(&mut document, page, y, "สรุปชั่วโมงการพัฒนา");
+    let summary_columns = [
+        TableColumn::new("รายการ", content_width - 3.0 * 90.0, PdfTextAlign::Left),
+        TableColumn::new("ชั่วโมงที่ได้", 90.0, PdfTextAlign::Center),
+        TableColumn::new("เกณฑ์", 90.0, PdfTextAlign::Center),
+        TableColumn::new("ขาดอีก", 90.0, PdfTextAlign::Center),
+    ];
+    let summary_rows = vec![
+        vec![
+            format!(
+                "ชุมชนการเรียนรู้ทางวิชาชีพ (PLC) {} ครั้ง",
+                summary.plc_session_count
+            ),
+            format_hours(summary.plc_hours),
+            format_optional_hours(summary.plc_hours_required),
+            format_optional_hours(summary.plc_hours_remaining),
+        ],
+        vec![
+            format!("การอบรมและพัฒนาตนเอง {} รายการ", summary.training_count),
+            format_hours(summary.training_hours),
+            format_optional_hours(summary.training_hours_required),
+            format_optional_hours(summary.training_hours_remaining),
+        ],
+    ];
+    y = draw_wrapped_table(
+        &mut document,
+        page,
+        &summary_columns,
+        &summary_rows,
+        y + 6.0,
+        "สรุปชั่วโมงการพัฒนา (ต่อ)",
+    );
+    y = paragraph(
+        &mut document,
+        page,
+        y + 4.0,
+        PdfFontWeight::Bold,
+        &format!("ผลการประเมินตามเกณฑ์: {}", summary.status.label()),
+    );
+
+    let title = "1. กิจกรรมชุมชนการเรียนรู้ทางวิชาชีพ (PLC)";
+    y = section_heading(&mut document, page, y, title);
+    let role_width = 80.0;
+    let plc_columns = [
+        TableColumn::new("ที่", ORDER_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new("วันที่", DATE_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new(
+            "หัวข้อ",
+            content_width
+                - ORDER_COLUMN_WIDTH
+                - DATE_COLUMN_WIDTH
+                - role_width
+                - HOURS_COLUMN_WIDTH,
+            PdfTextAlign::Left,
+        ),
+        TableColumn::new("บทบาท", role_width, PdfTextAlign::Center),
+        TableColumn::new("ชั่วโมง", HOURS_COLUMN_WIDTH, PdfTextAlign::Center),
+    ];
+    let plc_rows: Vec<Vec<String>> = portfolio
+        .plc_sessions
+        .iter()
+        .enumerate()
+        .map(|(index, session)| {
+            let role = session
+                .participants
+                .iter()
+                .find(|participant| participant.user_id == summary.user_id)
+                .map(|participant| participant.role)
+                .unwrap_or(PlcParticipantRole::Member);
+            vec![
+                (index + 1).to_string(),
+                thai_short_date(session.session_date),
+                session.topic.clone(),
+                role.label().to_string(),
+                format_hours(session.hours),
+            ]
+        })
+        .collect();
+    y = table_or_dash(&mut document, page, y, &plc_columns, &plc_rows, title);
+
+    let title = "2. การอบรมและพัฒนาตนเอง";
+    y = section_heading(&mut document, page, y, title);
+    let provider_width = 110.0;
+    let certificate_width = 56.0;
+    let training_columns = [
+        TableColumn::new("ที่", ORDER_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new("วันที่", DATE_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new(
+            "หลักสูตร",
+            content_width
+                - ORDER_COLUMN_WIDTH
+                - DATE_COLUMN_WIDTH
+                - provider_width
+                - HOURS_COLUMN_WIDTH
+                - certificate_width,
+            PdfTextAlign::Left,
+        ),
+        TableColumn::new("ผู้จัด", provider_width, PdfTextAlign::Left),
+        TableColumn::new("ชั่วโมง", HOURS_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new("เกียรติบัตร", certificate_width, PdfTextAlign::Center),
+    ];
+    let training_rows: Vec<Vec<String>> = portfolio
+        .training_records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            vec![
+                (index + 1).to_string(),
+                thai_date_range(record.start_date, record.end_date),
+                record.title.clone(),
+                record.provider.clone().unwrap_or_else(|| "-".to_string()),
+                format_hours(record.hours),
+                if record.certificate_file_ids.is_empty() {
+                    "-".to_string()
+                } else {
+                    "มี".to_string()
+                },
+            ]
+        })
+        .collect();
+    y = table_or_dash(
+        &mut document,
+        page,
+        y,
+        &training_columns,
+        &training_rows,
+        title,
+    );
+
+    let title = "3. ผลการนิเทศการจัดการเรียนรู้";
+    y = section_heading(&mut document, page, y, title);
+    let subject_width = 120.0;
+    let score_width = 56.0;
+    let linked_width = 56.0;
+    let observation_columns = [
+        TableColumn::new("ที่", ORDER_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new("วันที่", DATE_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new(
+            "รอบการนิเทศ",
+            content_width
+                - ORDER_COLUMN_WIDTH
+                - DATE_COLUMN_WIDTH
+                - subject_width
+                - score_width
+                - linked_width,
+            PdfTextAlign::Left,
+        ),
+        TableColumn::new("รายวิชา", subject_width, PdfTextAlign::Left),
+        TableColumn::new("คะแนนเฉลี่ย", score_width, PdfTextAlign::Center),
+        TableColumn::new("PLC", linked_width, PdfTextAlign::Center),
+    ];
+    let observation_rows: Vec<Vec<String>> = portfolio
+        .observations
+        .iter()
+        .enumerate()
+        .map(|(index, observation)| {
+            vec![
+                (index + 1).to_string(),
+                thai_short_date(school_date(observation.observed_at)),
+                observation.cycle_title.clone(),
+                observation
+                    .subject_name
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                observation
+                    .average_rating
+                    .map(|score| format!("{:.2}", score))
+                    .unwrap_or_else(|| "-".to_string()),
+                observation.linked_plc_session_count.to_string(),
+            ]
+        })
+        .collect();
+    y = table_or_dash(
+        &mut document,
+        page,
+        y,
+        &observation_columns,
+        &observation_rows,
+        title,
+    );
+
+    y = ensure_space(&mut document, page, y, SIGNATURE_BLOCK_HEIGHT);
+    y += 36.0;
+    let slots = [
+        ("ผู้รายงาน", format!("({})", summary.user_name), "ครูผู้สอน"),
+        (
+            "ผู้รับรอง",
+            "(..........................................)".to_string(),
+            "ผู้บริหารสถานศึกษา",
+        ),
+    ];
+    let slot_width = content_width / slots.len() as f32;
+    for (index, (caption, name, role)) in slots.iter().enumerate() {
+        let x = PAGE_MARGIN + slot_width * (index as f32 + 0.5);
+        let lines = [
+            (0.0, PdfFontWeight::Bold, caption.to_string()),
+            (
+                30.0,
+                PdfFontWeight::Regular,
+                "ลงชื่อ ..............................".to_string(),
+            ),
+            (48.0, PdfFontWeight::Regular, name.clone()),
+            (66.0, PdfFontWeight::Regular, role.to_string()),
+        ];
+        for (offset, weight, text) in lines {
+            let text = document.fit_text(&text, 11.0, weight, slot_width - 8.0);
+            document.aligned_text(x, y + offset, 11.0, weight, PdfTextAlign::Center, &text);
+        }
+    }
+
+    document
+}
+
+fn section_heading(document: &mut PdfDocument, page: PdfPageSize, y: f32, title: &str) -> f32 {
+    let y = ensure_space(document, page, y, SECTION_MIN_HEIGHT) + 10.0;
+    paragraph(document, page, y, PdfFontWeight::Bold, title)
+}
+
+fn table_or_dash(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    y: f32,
+    columns: &[TableColumn],
+    rows: &[Vec<String>],
+    title: &str,
+) -> f32 {
+    if rows.is_empty() {
+        return paragraph(document, page, y, PdfFontWeight::Regular, "- ไม่มีรายการ -");
+    }
+    let continued = format!("{} (ต่อ)", title);
+    draw_wrapped_table(document, page, columns, rows, y + 6.0, &continued)
+}
+
+fn format_hours(hours: f64) -> String {
+    if hours.fract() == 0.0 {
+        format!("{:.0}", hours)
+    } else {
+        format!("{:.2}", hours)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+fn format_optional_hours(hours: Option<f64>) -> String {
+    hours.map(format_hours).unwrap_or_else(|| "-".to_string())
+}
+
+fn thai_date_range(start_date: NaiveDate, end_date: NaiveDate) -> String {
+    if start_date == end_date {
+        thai_short_date(start_date)
+    } else {
+        format!(
+            "{} - {}",
+            thai_short_date(start_date),
+            thai_short_date(end_date)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::professional_development::models::{
+        PdHourSummary, PdPortfolioObservation, PdRequirementStatus, PdTrainingRecord,
+        PlcParticipant, PlcSession,
+    };
+    use chrono::{TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn dates_and_hours_use_thai_print_formats() {
+        assert_eq!(thai_long_date(date(2025, 10, 1)), "1 ตุลาคม พ.ศ. 2568");
+        assert_eq!(thai_short_date(date(2026, 11, 2)), "2 พ.ย. 69");
+        assert_eq!(
+            thai_date_range(date(2026, 11, 2), date(2026, 11, 3)),
+            "2 พ.ย. 69 - 3 พ.ย. 69"
+        );
+        assert_eq!(format_hours(3.0), "3");
+        assert_eq!(format_hours(1.5), "1.5");
+        assert_eq!(format_hours(0.25), "0.25");
+        assert_eq!(format_optional_hours(None), "-");
+    }
+
+    #[test]
+    fn portfolio_spans_pages_with_every_section() {
+        let teacher = Uuid::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 11, 2, 3, 0, 0).unwrap();
+        let session = |index: usize| PlcSession {
+            id: Uuid::new_v4(),
+            fiscal_year: 2570,
+            session_date: date(2026, 11, 2),
+            topic: format!("ครั้งที่ {index} วิเคราะห์ผลการสังเกตชั้นเรียนและออกแบบกิจกรรมการเรียนรู้เชิงรุกร่วมกัน"),
+            details: None,
+            hours: 1.5,
+            supervision_observation_id: None,
+            created_by: Some(teacher),
+            participants: vec![PlcParticipant {
+                user_id: teacher,
+                user_name: "นางสาวครู ใจดี".to_string(),
+                role: PlcParticipantRole::Facilitator,
+            }],
+            evidence_file_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        let portfolio = PdPortfolio {
+            summary: PdHourSummary {
+                user_id: teacher,
+                user_name: "นางสาวครู ใจดี".to_string(),
+                fiscal_year: 2570,
+                plc_hours: 60.0,
+                plc_session_count: 40,
+                training_hours: 6.0,
+                training_count: 1,
+                plc_hours_required: Some(50.0),
+                training_hours_required: Some(12.0),
+                plc_hours_remaining: Some(0.0),
+                training_hours_remaining: Some(6.0),
+                status: PdRequirementStatus::Below,
+            },
+            plc_sessions: (1..=40).map(session).collect(),
+            training_records: vec![PdTrainingRecord {
+                id: Uuid::new_v4(),
+                user_id: teacher,
+                user_name: "นางสาวครู ใจดี".to_string(),
+                fiscal_year: 2570,
+                title: "การจัดการเรียนรู้แบบ Active Learning".to_string(),
+                provider: Some("สพม.กรุงเทพมหานคร เขต 1".to_string()),
+                start_date: date(2026, 11, 5),
+                end_date: date(2026, 11, 5),
+                hours: 6.0,
+                note: None,
+                certificate_file_ids: vec![Uuid::new_v4()],
+                created_at: now,
+                updated_at: now,
+            }],
+            observations: vec![PdPortfolioObservation {
+                id: Uuid::new_v4(),
+                cycle_title: "นิเทศภาคเรียนที่ 2".to_string(),
+                observed_at: now,
+                subject_name: Some("วิทยาศาสตร์".to_string()),
+                average_rating: Some(4.25),
+                linked_plc_session_count: 2,
+            }],
+        };
+
+        let document = render_portfolio_pdf("โรงเรียนตัวอย่าง", &portfolio);
+        assert!(document.page_count() >= 2);
+        let bytes = document.finish();
+        assert!(bytes.starts_with(b"%PDF-"));
+    }
+}
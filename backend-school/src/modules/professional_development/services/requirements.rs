@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::{
+    PdHourRequirement, UpsertPdHourRequirementRequest,
+};
+use crate::policies::professional_development_access_policy;
+
+use super::shared::{
+    hours_to_minutes, minutes_to_hours, normalize_optional_text, read_error, resolve_fiscal_year,
+    write_error, HourRequirement,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct RequirementRow {
+    fiscal_year: i32,
+    plc_minutes_required: i32,
+    training_minutes_required: i32,
+    note: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Every staff member can see the requirements they are measured against.
+pub async fn list_requirements(
+    pool: &PgPool,
+    actor: &ActorContext,
+) -> Result<Vec<PdHourRequirement>, AppError> {
+    if !professional_development_access_policy::can_read_school_professional_development(actor) {
+        professional_development_access_policy::require_professional_development_own_read(actor)?;
+    }
+
+    let rows = sqlx::query_as::<_, RequirementRow>(
+        r#"
+        SELECT fiscal_year, plc_minutes_required, training_minutes_required, note, updated_at
+        FROM pd_hour_requirements
+        ORDER BY fiscal_year DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows.into_iter().map(requirement_from_row).collect())
+}
+
+pub async fn upsert_requirement(
+    pool: &PgPool,
+    actor: &ActorContext,
+    fiscal_year: i32,
+    payload: UpsertPdHourRequirementRequest,
+) -> Result<PdHourRequirement, AppError> {
+    professional_development_access_policy::require_professional_development_manage(actor)?;
+    let fiscal_year = resolve_fiscal_year(Some(fiscal_year))?;
+    let message = "จำนวนชั่วโมงตามเกณฑ์ต้องไม่ติดลบและเป็นทวีคูณของ 15 นาที";
+    let plc_minutes = hours_to_minutes(payload.plc_hours_required, message)?;
+    let training_minutes = hours_to_minutes(payload.training_hours_required, message)?;
+
+    let row = sqlx::query_as::<_, RequirementRow>(
+        r#"
+        INSERT INTO pd_hour_requirements (
+            fiscal_year, plc_minutes_required, training_minutes_required, note, updated_by
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (fiscal_year) DO UPDATE
+        SET plc_minutes_required = EXCLUDED.plc_minutes_required,
+            training_minutes_required = EXCLUDED.training_minutes_required,
+            note = EXCLUDED.note,
+            updated_by = EXCLUDED.updated_by
+        RETURNING fiscal_year, plc_minutes_required, training_minutes_required, note, updated_at
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(plc_minutes)
+    .bind(training_minutes)
+    .bind(normalize_optional_text(payload.note))
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(write_error)?;
+
+    Ok(requirement_from_row(row))
+}
+
+pub(super) async fn load_requirement(
+    pool: &PgPool,
+    fiscal_year: i32,
+) -> Result<Option<HourRequirement>, AppError> {
+    let row = sqlx::query_as::<_, (i32, i32)>(
+        r#"
+        SELECT plc_minutes_required, training_minutes_required
+        FROM pd_hour_requirements
+        WHERE fiscal_year = $1
+        "#,
+    )
+    .bind(fiscal_year)
+    .fetch_optional(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(row.map(|(plc_minutes, training_minutes)| HourRequirement {
+        plc_minutes,
+        training_minutes,
+    }))
+}
+
+fn requirement_from_row(row: RequirementRow) -> PdHourRequirement {
+    PdHourRequirement {
+        fiscal_year: row.fiscal_year,
+        plc_hours_required: minutes_to_hours(i64::from(row.plc_minutes_required)),
+        training_hours_required: minutes_to_hours(i64::from(row.training_minutes_required)),
+        note: row.note,
+        updated_at: row.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_minutes_are_reported_as_hours() {
+        let requirement = requirement_from_row(RequirementRow {
+            fiscal_year: 2570,
+            plc_minutes_required: 50 * 60,
+            training_minutes_required: 20 * 60 + 45,
+            note: None,
+            updated_at: Utc::now(),
+        });
+
+        assert_eq!(requirement.fiscal_year, 2570);
+        assert_eq!(requirement.plc_hours_required, 50.0);
+        assert_eq!(requirement.training_hours_required, 20.75);
+    }
+}
@@ -0,0 +1,317 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::PdRequirementStatus;
+use crate::modules::staff_leave::services::{fiscal_year_bounds, fiscal_year_for};
+use crate::policies::professional_development_access_policy;
+use crate::scheduling::SCHOOL_TIMEZONE;
+
+pub(super) const MAX_EVIDENCE_FILES: usize = 10;
+const MINUTES_PER_QUARTER_HOUR: f64 = 15.0;
+const MAX_RECORDED_HOURS: f64 = 10_000.0;
+
+/// Changed record plus the files it no longer references, which the handler
+/// hands to File Platform deletion.
+pub struct ProfessionalDevelopmentMutationOutcome<T> {
+    pub record: T,
+    pub detached_file_ids: Vec<Uuid>,
+}
+
+/// The yearly minimums in minutes; a fiscal year without a row has none
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HourRequirement {
+    pub plc_minutes: i32,
+    pub training_minutes: i32,
+}
+
+pub(super) fn school_today() -> NaiveDate {
+    Utc::now().with_timezone(&SCHOOL_TIMEZONE).date_naive()
+}
+
+pub(super) fn resolve_fiscal_year(fiscal_year: Option<i32>) -> Result<i32, AppError> {
+    let fiscal_year = fiscal_year.unwrap_or_else(|| fiscal_year_for(school_today()));
+    if fiscal_year_bounds(fiscal_year).is_some() && (2500..=2700).contains(&fiscal_year) {
+        Ok(fiscal_year)
+    } else {
+        Err(AppError::ValidationError(
+            "ปีงบประมาณต้องเป็นปีพุทธศักราช".to_string(),
+        ))
+    }
+}
+
+/// Hours are entered in quarter-hour steps so they convert to whole minutes
+/// exactly; zero is allowed here and rejected by callers that need a duration.
+pub fn hours_to_minutes(hours: f64, message: &str) -> Result<i32, AppError> {
+    let quarters = hours * 4.0;
+    if !quarters.is_finite()
+        || quarters.fract() != 0.0
+        || !(0.0..=MAX_RECORDED_HOURS).contains(&hours)
+    {
+        return Err(AppError::ValidationError(message.to_string()));
+    }
+    Ok((quarters * MINUTES_PER_QUARTER_HOUR) as i32)
+}
+
+pub fn minutes_to_hours(minutes: i64) -> f64 {
+    minutes as f64 / 60.0
+}
+
+/// Both the PLC and the training minimum must be reached to meet the
+/// requirement.
+pub fn requirement_status(
+    plc_minutes: i64,
+    training_minutes: i64,
+    requirement: Option<HourRequirement>,
+) -> PdRequirementStatus {
+    match requirement {
+        None => PdRequirementStatus::NotConfigured,
+        Some(requirement)
+            if plc_minutes >= i64::from(requirement.plc_minutes)
+                && training_minutes >= i64::from(requirement.training_minutes) =>
+        {
+            PdRequirementStatus::Met
+        }
+        Some(_) => PdRequirementStatus::Below,
+    }
+}
+
+pub fn remaining_minutes(done_minutes: i64, required_minutes: i32) -> i64 {
+    (i64::from(required_minutes) - done_minutes).max(0)
+}
+
+/// Whose records a listing covers: the requested staff member when the actor
+/// may read them, otherwise everyone for school-wide readers and the actor
+/// themself for everybody else.
+pub(super) fn resolve_listed_user(
+    actor: &ActorContext,
+    requested_user_id: Option<Uuid>,
+) -> Result<Option<Uuid>, AppError> {
+    match requested_user_id {
+        Some(user_id) => {
+            professional_development_access_policy::require_portfolio_read(actor, user_id)?;
+            Ok(Some(user_id))
+        }
+        None
+            if professional_development_access_policy::can_read_school_professional_development(
+                actor,
+            ) =>
+        {
+            Ok(None)
+        }
+        None => {
+            professional_development_access_policy::require_professional_development_own_read(
+                actor,
+            )?;
+            Ok(Some(actor.user_id))
+        }
+    }
+}
+
+/// Recorded activities must have already taken place.
+pub(super) fn ensure_not_future(date: NaiveDate, message: &str) -> Result<(), AppError> {
+    if date > school_today() {
+        Err(AppError::ValidationError(message.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+pub(super) fn dedupe_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut unique = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    unique
+}
+
+pub(super) fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn required_text(value: &str, message: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(AppError::ValidationError(message.to_string()))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Evidence must be ready uploads of this purpose that are either still
+/// unattached and uploaded by the actor, or already attached to the PLC
+/// session or training record being edited.
+pub(super) async fn validate_evidence_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    record_id: Option<Uuid>,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.len() > MAX_EVIDENCE_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบหลักฐานได้ไม่เกิน {MAX_EVIDENCE_FILES} ไฟล์"
+        )));
+    }
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let usable = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        WHERE files.id = ANY($1)
+          AND files.purpose_code = 'professional_development_evidence'
+          AND files.lifecycle_status = 'ready'
+          AND files.deleted_at IS NULL
+          AND (
+              (
+                  files.owner_user_id = $2
+                  AND NOT EXISTS (SELECT 1 FROM pd_plc_evidence WHERE file_id = files.id)
+                  AND NOT EXISTS (SELECT 1 FROM pd_training_certificates WHERE file_id = files.id)
+              )
+              OR EXISTS (
+                  SELECT 1
+                  FROM pd_plc_evidence
+                  WHERE file_id = files.id
+                    AND session_id = $3
+              )
+              OR EXISTS (
+                  SELECT 1
+                  FROM pd_training_certificates
+                  WHERE file_id = files.id
+                    AND training_record_id = $3
+              )
+          )
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .bind(record_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(
+            "Failed to validate professional development evidence: {}",
+            error
+        );
+        AppError::InternalServerError("ไม่สามารถตรวจสอบหลักฐานแนบได้".to_string())
+    })?;
+
+    if usable == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "ไฟล์หลักฐานไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
+
+/// Newly attached evidence is kept for good instead of expiring as an
+/// unattached temporary upload.
+pub(super) async fn retain_evidence_files(
+    transaction: &mut Transaction<'_, Postgres>,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        "UPDATE files SET retention_class = 'standard', expires_at = NULL, updated_at = NOW() WHERE id = ANY($1)",
+    )
+    .bind(file_ids)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+    Ok(())
+}
+
+pub(super) async fn ensure_active_staff(pool: &PgPool, user_ids: &[Uuid]) -> Result<(), AppError> {
+    let active = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        WHERE id = ANY($1)
+          AND user_type = 'staff'
+          AND status = 'active'
+        "#,
+    )
+    .bind(user_ids)
+    .fetch_one(pool)
+    .await
+    .map_err(read_error)?;
+
+    if active == user_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "ผู้เข้าร่วมต้องเป็นบุคลากรที่ยังปฏิบัติงานอยู่".to_string(),
+        ))
+    }
+}
+
+pub(super) fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read professional development records: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลการพัฒนาวิชาชีพได้".to_string())
+}
+
+pub(super) fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!(
+        "Failed to write professional development records: {}",
+        error
+    );
+    AppError::InternalServerError("ไม่สามารถบันทึกข้อมูลการพัฒนาวิชาชีพได้".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hours_convert_in_quarter_hour_steps() {
+        assert_eq!(hours_to_minutes(1.5, "x").unwrap(), 90);
+        assert_eq!(hours_to_minutes(0.25, "x").unwrap(), 15);
+        assert_eq!(hours_to_minutes(0.0, "x").unwrap(), 0);
+        assert!(matches!(
+            hours_to_minutes(1.1, "x"),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            hours_to_minutes(-2.0, "x"),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            hours_to_minutes(f64::NAN, "x"),
+            Err(AppError::ValidationError(_))
+        ));
+        assert_eq!(minutes_to_hours(90), 1.5);
+    }
+
+    #[test]
+    fn requirement_needs_both_plc_and_training_minimums() {
+        let requirement = Some(HourRequirement {
+            plc_minutes: 50 * 60,
+            training_minutes: 20 * 60,
+        });
+        assert_eq!(
+            requirement_status(3000, 1200, requirement),
+            PdRequirementStatus::Met
+        );
+        assert_eq!(
+            requirement_status(3600, 600, requirement),
+            PdRequirementStatus::Below
+        );
+        assert_eq!(
+            requirement_status(0, 0, None),
+            PdRequirementStatus::NotConfigured
+        );
+        assert_eq!(remaining_minutes(2400, 3000), 600);
+        assert_eq!(remaining_minutes(3600, 3000), 0);
+    }
+}
@@ -0,0 +1,275 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::{
+    PdHourSummary, PdPortfolio, PdPortfolioObservation, PdSummaryFilter,
+};
+use crate::modules::staff_leave::services::fiscal_year_bounds;
+use crate::policies::professional_development_access_policy;
+use crate::scheduling::SCHOOL_TIMEZONE_NAME;
+
+use super::plc_sessions::load_sessions;
+use super::requirements::load_requirement;
+use super::shared::{
+    minutes_to_hours, read_error, remaining_minutes, requirement_status, resolve_fiscal_year,
+    HourRequirement,
+};
+use super::training::load_training_records;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(super) struct HourTotalsRow {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub plc_minutes: i64,
+    pub plc_session_count: i64,
+    pub training_minutes: i64,
+    pub training_count: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ObservationRow {
+    id: Uuid,
+    cycle_title: String,
+    observed_at: DateTime<Utc>,
+    subject_name: Option<String>,
+    average_rating: Option<f64>,
+    linked_plc_session_count: i64,
+}
+
+pub async fn my_summary(
+    pool: &PgPool,
+    actor: &ActorContext,
+    fiscal_year: Option<i32>,
+) -> Result<PdHourSummary, AppError> {
+    professional_development_access_policy::require_professional_development_own_read(actor)?;
+    let fiscal_year = resolve_fiscal_year(fiscal_year)?;
+    load_summary(pool, fiscal_year, actor.user_id).await
+}
+
+/// Annual totals of every active staff member against the fiscal year's
+/// requirement, optionally narrowed to one requirement status.
+pub async fn list_summaries(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: PdSummaryFilter,
+) -> Result<Vec<PdHourSummary>, AppError> {
+    professional_development_access_policy::require_professional_development_school_read(actor)?;
+    let fiscal_year = resolve_fiscal_year(filter.fiscal_year)?;
+    let requirement = load_requirement(pool, fiscal_year).await?;
+    let totals = load_hour_totals(pool, fiscal_year, None).await?;
+
+    Ok(totals
+        .into_iter()
+        .map(|row| build_summary(fiscal_year, row, requirement))
+        .filter(|summary| filter.status.is_none_or(|status| summary.status == status))
+        .collect())
+}
+
+/// One staff member's sessions, training and released classroom observations
+/// for a fiscal year.
+pub async fn get_portfolio(
+    pool: &PgPool,
+    actor: &ActorContext,
+    user_id: Uuid,
+    fiscal_year: Option<i32>,
+) -> Result<PdPortfolio, AppError> {
+    professional_development_access_policy::require_portfolio_read(actor, user_id)?;
+    let fiscal_year = resolve_fiscal_year(fiscal_year)?;
+
+    Ok(PdPortfolio {
+        summary: load_summary(pool, fiscal_year, user_id).await?,
+        plc_sessions: load_sessions(pool, fiscal_year, Some(user_id)).await?,
+        training_records: load_training_records(pool, fiscal_year, Some(user_id)).await?,
+        observations: load_released_observations(pool, fiscal_year, user_id).await?,
+    })
+}
+
+async fn load_summary(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Uuid,
+) -> Result<PdHourSummary, AppError> {
+    let requirement = load_requirement(pool, fiscal_year).await?;
+    let row = load_hour_totals(pool, fiscal_year, Some(user_id))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("ไม่พบบุคลากร".to_string()))?;
+    Ok(build_summary(fiscal_year, row, requirement))
+}
+
+/// Active staff for the school-wide list; a single requested staff member is
+/// returned even after leaving so their portfolio stays printable.
+async fn load_hour_totals(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Option<Uuid>,
+) -> Result<Vec<HourTotalsRow>, AppError> {
+    sqlx::query_as::<_, HourTotalsRow>(
+        r#"
+        SELECT u.id AS user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               COALESCE(plc.minutes, 0)::bigint AS plc_minutes,
+               COALESCE(plc.session_count, 0)::bigint AS plc_session_count,
+               COALESCE(training.minutes, 0)::bigint AS training_minutes,
+               COALESCE(training.record_count, 0)::bigint AS training_count
+        FROM users u
+        LEFT JOIN LATERAL (
+            SELECT SUM(s.duration_minutes) AS minutes, COUNT(*) AS session_count
+            FROM pd_plc_participants p
+            JOIN pd_plc_sessions s ON s.id = p.session_id
+            WHERE p.user_id = u.id
+              AND s.fiscal_year = $1
+        ) plc ON true
+        LEFT JOIN LATERAL (
+            SELECT SUM(t.duration_minutes) AS minutes, COUNT(*) AS record_count
+            FROM pd_training_records t
+            WHERE t.user_id = u.id
+              AND t.fiscal_year = $1
+        ) training ON true
+        WHERE u.user_type = 'staff'
+          AND (
+              ($2::uuid IS NULL AND u.status = 'active')
+              OR u.id = $2
+          )
+        ORDER BY u.first_name, u.last_name
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Observations whose results were released to the teacher, dated by the
+/// school calendar day the lesson was observed.
+async fn load_released_observations(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Uuid,
+) -> Result<Vec<PdPortfolioObservation>, AppError> {
+    let (starts_on, ends_on) = fiscal_year_bounds(fiscal_year)
+        .ok_or_else(|| AppError::ValidationError("ปีงบประมาณต้องเป็นปีพุทธศักราช".to_string()))?;
+
+    let rows = sqlx::query_as::<_, ObservationRow>(
+        r#"
+        SELECT o.id,
+               c.title AS cycle_title,
+               o.observed_at,
+               COALESCE(NULLIF(o.manual_subject_name, ''), NULLIF(o.lesson_snapshot->>'subjectName', ''))
+                   AS subject_name,
+               (
+                   SELECT AVG(r.rating_score)::double precision
+                   FROM supervision_evaluators e
+                   JOIN supervision_evaluator_responses r ON r.evaluator_id = e.id
+                   WHERE e.observation_id = o.id
+                     AND e.status = 'submitted'
+                     AND r.rating_score IS NOT NULL
+               ) AS average_rating,
+               (
+                   SELECT COUNT(*)
+                   FROM pd_plc_sessions s
+                   WHERE s.supervision_observation_id = o.id
+               ) AS linked_plc_session_count
+        FROM supervision_observations o
+        JOIN supervision_cycles c ON c.id = o.cycle_id
+        WHERE o.observed_user_id = $1
+          AND o.status IN ('published', 'acknowledged', 'completed')
+          AND (o.observed_at AT TIME ZONE $4)::date BETWEEN $2 AND $3
+        ORDER BY o.observed_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(starts_on)
+    .bind(ends_on)
+    .bind(SCHOOL_TIMEZONE_NAME)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PdPortfolioObservation {
+            id: row.id,
+            cycle_title: row.cycle_title,
+            observed_at: row.observed_at,
+            subject_name: row.subject_name,
+            average_rating: row.average_rating,
+            linked_plc_session_count: row.linked_plc_session_count,
+        })
+        .collect())
+}
+
+pub(super) fn build_summary(
+    fiscal_year: i32,
+    row: HourTotalsRow,
+    requirement: Option<HourRequirement>,
+) -> PdHourSummary {
+    PdHourSummary {
+        user_id: row.user_id,
+        user_name: row.user_name,
+        fiscal_year,
+        plc_hours: minutes_to_hours(row.plc_minutes),
+        plc_session_count: row.plc_session_count,
+        training_hours: minutes_to_hours(row.training_minutes),
+        training_count: row.training_count,
+        plc_hours_required: requirement
+            .map(|requirement| minutes_to_hours(i64::from(requirement.plc_minutes))),
+        training_hours_required: requirement
+            .map(|requirement| minutes_to_hours(i64::from(requirement.training_minutes))),
+        plc_hours_remaining: requirement.map(|requirement| {
+            minutes_to_hours(remaining_minutes(row.plc_minutes, requirement.plc_minutes))
+        }),
+        training_hours_remaining: requirement.map(|requirement| {
+            minutes_to_hours(remaining_minutes(
+                row.training_minutes,
+                requirement.training_minutes,
+            ))
+        }),
+        status: requirement_status(row.plc_minutes, row.training_minutes, requirement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::professional_development::models::PdRequirementStatus;
+
+    fn totals(plc_minutes: i64, training_minutes: i64) -> HourTotalsRow {
+        HourTotalsRow {
+            user_id: Uuid::new_v4(),
+            user_name: "ครู ใจดี".to_string(),
+            plc_minutes,
+            plc_session_count: 3,
+            training_minutes,
+            training_count: 1,
+        }
+    }
+
+    #[test]
+    fn summary_reports_hours_and_what_is_left() {
+        let requirement = Some(HourRequirement {
+            plc_minutes: 50 * 60,
+            training_minutes: 12 * 60,
+        });
+
+        let summary = build_summary(2570, totals(42 * 60 + 30, 18 * 60), requirement);
+        assert_eq!(summary.plc_hours, 42.5);
+        assert_eq!(summary.plc_hours_required, Some(50.0));
+        assert_eq!(summary.plc_hours_remaining, Some(7.5));
+        assert_eq!(summary.training_hours_remaining, Some(0.0));
+        assert_eq!(summary.status, PdRequirementStatus::Below);
+
+        let summary = build_summary(2570, totals(50 * 60, 12 * 60), requirement);
+        assert_eq!(summary.status, PdRequirementStatus::Met);
+
+        let summary = build_summary(2570, totals(0, 0), None);
+        assert_eq!(summary.plc_hours_required, None);
+        assert_eq!(summary.plc_hours_remaining, None);
+        assert_eq!(summary.status, PdRequirementStatus::NotConfigured);
+    }
+}
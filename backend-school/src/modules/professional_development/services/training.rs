@@ -0,0 +1,391 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::professional_development::models::{
+    PdTrainingFilter, PdTrainingRecord, SavePdTrainingRecordRequest,
+};
+use crate::modules::staff_leave::services::fiscal_year_for;
+use crate::policies::professional_development_access_policy;
+
+use super::shared::{
+    dedupe_ids, ensure_active_staff, ensure_not_future, hours_to_minutes, minutes_to_hours,
+    normalize_optional_text, read_error, required_text, resolve_fiscal_year, resolve_listed_user,
+    retain_evidence_files, validate_evidence_files, write_error,
+    ProfessionalDevelopmentMutationOutcome,
+};
+
+const TRAINING_NOT_FOUND_MESSAGE: &str = "ไม่พบบันทึกการอบรม";
+const MAX_TRAINING_MINUTES_PER_DAY: i64 = 24 * 60;
+
+#[derive(Debug, sqlx::FromRow)]
+struct TrainingRow {
+    id: Uuid,
+    user_id: Uuid,
+    user_name: String,
+    fiscal_year: i32,
+    title: String,
+    provider: Option<String>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    duration_minutes: i32,
+    note: Option<String>,
+    certificate_file_ids: Vec<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+struct ValidatedTraining {
+    user_id: Uuid,
+    fiscal_year: i32,
+    title: String,
+    provider: Option<String>,
+    duration_minutes: i32,
+    note: Option<String>,
+    certificate_file_ids: Vec<Uuid>,
+}
+
+pub async fn list_training_records(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: PdTrainingFilter,
+) -> Result<Vec<PdTrainingRecord>, AppError> {
+    let user_id = resolve_listed_user(actor, filter.user_id)?;
+    let fiscal_year = resolve_fiscal_year(filter.fiscal_year)?;
+    load_training_records(pool, fiscal_year, user_id).await
+}
+
+pub async fn create_training_record(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: SavePdTrainingRecordRequest,
+) -> Result<ProfessionalDevelopmentMutationOutcome<PdTrainingRecord>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let input = validate_training_input(pool, actor, None, &payload).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO pd_training_records (
+            user_id, fiscal_year, title, provider, start_date, end_date,
+            duration_minutes, note, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+    )
+    .bind(input.user_id)
+    .bind(input.fiscal_year)
+    .bind(&input.title)
+    .bind(&input.provider)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(input.duration_minutes)
+    .bind(&input.note)
+    .bind(actor.user_id)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    let detached_file_ids = replace_training_certificates(
+        &mut transaction,
+        id,
+        &input.certificate_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(ProfessionalDevelopmentMutationOutcome {
+        record: load_training_record(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+pub async fn update_training_record(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: SavePdTrainingRecordRequest,
+) -> Result<ProfessionalDevelopmentMutationOutcome<PdTrainingRecord>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let current = load_training_record(pool, id).await?;
+    ensure_training_editor(actor, current.user_id)?;
+    let payload = SavePdTrainingRecordRequest {
+        user_id: payload.user_id.or(Some(current.user_id)),
+        ..payload
+    };
+    let input = validate_training_input(pool, actor, Some(id), &payload).await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    sqlx::query(
+        r#"
+        UPDATE pd_training_records
+        SET user_id = $2,
+            fiscal_year = $3,
+            title = $4,
+            provider = $5,
+            start_date = $6,
+            end_date = $7,
+            duration_minutes = $8,
+            note = $9
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(input.user_id)
+    .bind(input.fiscal_year)
+    .bind(&input.title)
+    .bind(&input.provider)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(input.duration_minutes)
+    .bind(&input.note)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+
+    let detached_file_ids = replace_training_certificates(
+        &mut transaction,
+        id,
+        &input.certificate_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    Ok(ProfessionalDevelopmentMutationOutcome {
+        record: load_training_record(pool, id).await?,
+        detached_file_ids,
+    })
+}
+
+/// Deletes the record and returns its certificates for File Platform deletion.
+pub async fn delete_training_record(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    professional_development_access_policy::require_professional_development_submit(actor)?;
+    let current = load_training_record(pool, id).await?;
+    ensure_training_editor(actor, current.user_id)?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let detached_file_ids =
+        replace_training_certificates(&mut transaction, id, &[], actor.user_id).await?;
+    sqlx::query("DELETE FROM pd_training_records WHERE id = $1")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(write_error)?;
+    transaction.commit().await.map_err(write_error)?;
+    Ok(detached_file_ids)
+}
+
+pub(super) async fn load_training_records(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Option<Uuid>,
+) -> Result<Vec<PdTrainingRecord>, AppError> {
+    let rows = sqlx::query_as::<_, TrainingRow>(&training_select(
+        "t.fiscal_year = $1 AND ($2::uuid IS NULL OR t.user_id = $2)",
+    ))
+    .bind(fiscal_year)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    Ok(rows.into_iter().map(training_from_row).collect())
+}
+
+async fn load_training_record(pool: &PgPool, id: Uuid) -> Result<PdTrainingRecord, AppError> {
+    sqlx::query_as::<_, TrainingRow>(&training_select("t.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .map(training_from_row)
+        .ok_or_else(|| AppError::NotFound(TRAINING_NOT_FOUND_MESSAGE.to_string()))
+}
+
+fn training_select(condition: &str) -> String {
+    format!(
+        r#"
+        SELECT t.id,
+               t.user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               t.fiscal_year,
+               t.title,
+               t.provider,
+               t.start_date,
+               t.end_date,
+               t.duration_minutes,
+               t.note,
+               COALESCE(
+                   (
+                       SELECT array_agg(c.file_id ORDER BY c.created_at, c.file_id)
+                       FROM pd_training_certificates c
+                       WHERE c.training_record_id = t.id
+                   ),
+                   ARRAY[]::uuid[]
+               ) AS certificate_file_ids,
+               t.created_at,
+               t.updated_at
+        FROM pd_training_records t
+        JOIN users u ON u.id = t.user_id
+        WHERE {condition}
+        ORDER BY t.end_date DESC, t.created_at DESC
+        "#
+    )
+}
+
+fn training_from_row(row: TrainingRow) -> PdTrainingRecord {
+    PdTrainingRecord {
+        id: row.id,
+        user_id: row.user_id,
+        user_name: row.user_name,
+        fiscal_year: row.fiscal_year,
+        title: row.title,
+        provider: row.provider,
+        start_date: row.start_date,
+        end_date: row.end_date,
+        hours: minutes_to_hours(i64::from(row.duration_minutes)),
+        note: row.note,
+        certificate_file_ids: row.certificate_file_ids,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+async fn validate_training_input(
+    pool: &PgPool,
+    actor: &ActorContext,
+    training_record_id: Option<Uuid>,
+    payload: &SavePdTrainingRecordRequest,
+) -> Result<ValidatedTraining, AppError> {
+    let user_id = payload.user_id.unwrap_or(actor.user_id);
+    ensure_training_editor(actor, user_id)?;
+    ensure_active_staff(pool, &[user_id]).await?;
+
+    let title = required_text(&payload.title, "กรุณาระบุชื่อหลักสูตรหรือการอบรม")?;
+    if payload.end_date < payload.start_date {
+        return Err(AppError::ValidationError(
+            "วันสิ้นสุดการอบรมต้องไม่ก่อนวันเริ่มอบรม".to_string(),
+        ));
+    }
+    ensure_not_future(payload.end_date, "บันทึกได้เฉพาะการอบรมที่จบแล้ว")?;
+    let duration_minutes = hours_to_minutes(payload.hours, "จำนวนชั่วโมงอบรมต้องเป็นทวีคูณของ 15 นาที")?;
+    validate_training_duration(payload.start_date, payload.end_date, duration_minutes)?;
+
+    let certificate_file_ids = dedupe_ids(payload.certificate_file_ids.clone());
+    validate_evidence_files(
+        pool,
+        actor.user_id,
+        training_record_id,
+        &certificate_file_ids,
+    )
+    .await?;
+
+    Ok(ValidatedTraining {
+        user_id,
+        fiscal_year: fiscal_year_for(payload.end_date),
+        title,
+        provider: normalize_optional_text(payload.provider.clone()),
+        duration_minutes,
+        note: normalize_optional_text(payload.note.clone()),
+        certificate_file_ids,
+    })
+}
+
+/// Training hours must be positive and fit within the attended days.
+pub(super) fn validate_training_duration(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    duration_minutes: i32,
+) -> Result<(), AppError> {
+    let days = (end_date - start_date).num_days() + 1;
+    if duration_minutes <= 0 || i64::from(duration_minutes) > days * MAX_TRAINING_MINUTES_PER_DAY {
+        return Err(AppError::ValidationError(
+            "จำนวนชั่วโมงอบรมต้องมากกว่า 0 และไม่เกิน 24 ชั่วโมงต่อวันอบรม".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Staff record their own training; recording for others needs the manage grant.
+fn ensure_training_editor(actor: &ActorContext, owner_user_id: Uuid) -> Result<(), AppError> {
+    if owner_user_id == actor.user_id || professional_development_access_policy::can_manage(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "บันทึกหรือแก้ไขได้เฉพาะการอบรมของตนเอง".to_string(),
+        ))
+    }
+}
+
+async fn replace_training_certificates(
+    transaction: &mut Transaction<'_, Postgres>,
+    training_record_id: Uuid,
+    file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let detached_file_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        DELETE FROM pd_training_certificates
+        WHERE training_record_id = $1
+          AND NOT (file_id = ANY($2))
+        RETURNING file_id
+        "#,
+    )
+    .bind(training_record_id)
+    .bind(file_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if !file_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO pd_training_certificates (training_record_id, file_id, attached_by)
+            SELECT $1, file_id, $3
+            FROM UNNEST($2::uuid[]) AS file_id
+            ON CONFLICT (training_record_id, file_id) DO NOTHING
+            "#,
+        )
+        .bind(training_record_id)
+        .bind(file_ids)
+        .bind(attached_by)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+        retain_evidence_files(transaction, file_ids).await?;
+    }
+
+    Ok(detached_file_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn training_hours_fit_the_attended_days() {
+        assert!(validate_training_duration(date(2026, 11, 2), date(2026, 11, 3), 12 * 60).is_ok());
+        assert!(validate_training_duration(date(2026, 11, 2), date(2026, 11, 2), 24 * 60).is_ok());
+        assert!(matches!(
+            validate_training_duration(date(2026, 11, 2), date(2026, 11, 2), 25 * 60),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            validate_training_duration(date(2026, 11, 2), date(2026, 11, 4), 0),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+}
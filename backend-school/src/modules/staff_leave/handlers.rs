@@ -0,0 +1,247 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post, put},
+    Json, Router,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::files::consumer_service::request_deletions;
+use crate::modules::staff_leave::models::{
+    AdjustStaffLeaveBalanceRequest, CreateStaffLeaveTypeRequest, MyStaffLeaveFilter,
+    ReviewStaffLeaveRequest, StaffLeaveBalanceFilter, StaffLeaveCalendarQuery, StaffLeaveFilter,
+    StaffLeaveTypeFilter, SubmitStaffLeaveRequest, UpdateStaffLeaveTypeRequest,
+};
+use crate::modules::staff_leave::services::{self, StaffLeaveMutationOutcome};
+use crate::utils::request_context::{actor_tenant_context_from_session, ActorTenantContext};
+use crate::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FiscalYearQuery {
+    fiscal_year: Option<i32>,
+}
+
+/// Applies the side effects every leave mutation shares: File Platform deletion
+/// for dropped documents and the work inbox refresh signal.
+async fn finish_mutation(
+    state: &AppState,
+    context: &ActorTenantContext,
+    outcome: &mut StaffLeaveMutationOutcome,
+) -> Result<(), AppError> {
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        std::mem::take(&mut outcome.detached_file_ids),
+    )
+    .await?;
+    if outcome.work_items_changed {
+        state.notify_work_items_changed(&context.tenant.subdomain);
+    }
+    Ok(())
+}
+
+/// GET /api/staff-leave/types - ประเภทการลา
+async fn list_leave_types(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<StaffLeaveTypeFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_leave_types(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn create_leave_type(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<CreateStaffLeaveTypeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let leave_type =
+        services::create_leave_type(&context.tenant.pool, &context.actor, payload).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(leave_type))))
+}
+
+async fn update_leave_type(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateStaffLeaveTypeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let leave_type =
+        services::update_leave_type(&context.tenant.pool, &context.actor, id, payload).await?;
+    Ok(Json(ApiResponse::ok(leave_type)))
+}
+
+/// GET /api/staff-leave/me/requests - ใบลาของตนเอง
+async fn list_my_requests(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<MyStaffLeaveFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_my_requests(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/staff-leave/me/balances - วันลาคงเหลือของตนเองตามปีงบประมาณ
+async fn list_my_balances(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<FiscalYearQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items =
+        services::list_my_balances(&context.tenant.pool, &context.actor, query.fiscal_year).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/staff-leave/requests - ใบลาที่ผู้ใช้มีสิทธิ์พิจารณาหรือดูแล
+async fn list_requests(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<StaffLeaveFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_requests(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// POST /api/staff-leave/requests - ยื่นใบลา
+async fn submit_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<SubmitStaffLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::submit_request(&context.tenant.pool, &context.actor, payload).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(outcome.request))))
+}
+
+async fn get_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let request = services::get_request(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(request)))
+}
+
+/// PUT /api/staff-leave/requests/:id - แก้ไขและส่งใบลาที่ถูกส่งกลับอีกครั้ง
+async fn resubmit_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SubmitStaffLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::resubmit_request(&context.tenant.pool, &context.actor, id, payload).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+async fn cancel_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome = services::cancel_request(&context.tenant.pool, &context.actor, id).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+/// POST /api/staff-leave/requests/:id/review - พิจารณาใบลาในขั้นตอนปัจจุบัน
+async fn review_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReviewStaffLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::review_request(&context.tenant.pool, &context.actor, id, payload).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+
+    if let Err(error) = services::notify_requester_of_decision(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+        &outcome.request,
+    )
+    .await
+    {
+        tracing::error!(
+            leave_request_id = %outcome.request.id,
+            error = %error,
+            "Failed to notify requester about staff leave decision"
+        );
+    }
+
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+/// GET /api/staff-leave/balances - ยอดวันลาของบุคลากรทั้งโรงเรียน
+async fn list_balances(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<StaffLeaveBalanceFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_balances(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// PUT /api/staff-leave/balances/adjustment - ปรับยอดวันลาของบุคลากร
+async fn adjust_balance(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<AdjustStaffLeaveBalanceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let balance = services::adjust_balance(&context.tenant.pool, &context.actor, payload).await?;
+    Ok(Json(ApiResponse::ok(balance)))
+}
+
+/// GET /api/staff-leave/calendar - ปฏิทินการลาที่อนุมัติแล้ว
+async fn list_calendar(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(query): Query<StaffLeaveCalendarQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_calendar(&context.tenant.pool, &context.actor, query).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/types", get(list_leave_types).post(create_leave_type))
+        .route("/types/{id}", put(update_leave_type))
+        .route("/me/requests", get(list_my_requests))
+        .route("/me/balances", get(list_my_balances))
+        .route("/requests", get(list_requests).post(submit_request))
+        .route("/requests/{id}", get(get_request).put(resubmit_request))
+        .route("/requests/{id}/cancel", post(cancel_request))
+        .route("/requests/{id}/review", post(review_request))
+        .route("/balances", get(list_balances))
+        .route("/balances/adjustment", put(adjust_balance))
+        .route("/calendar", get(list_calendar))
+}
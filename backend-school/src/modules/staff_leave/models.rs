@@ -0,0 +1,368 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffLeaveDayPortion {
+    #[default]
+    Full,
+    Morning,
+    Afternoon,
+}
+
+impl StaffLeaveDayPortion {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Morning => "morning",
+            Self::Afternoon => "afternoon",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "full" => Some(Self::Full),
+            "morning" => Some(Self::Morning),
+            "afternoon" => Some(Self::Afternoon),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffLeaveStatus {
+    Pending,
+    Returned,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+impl StaffLeaveStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Returned => "returned",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "pending" => Some(Self::Pending),
+            "returned" => Some(Self::Returned),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffLeaveStepKind {
+    UnitLeader,
+    School,
+}
+
+impl StaffLeaveStepKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnitLeader => "unit_leader",
+            Self::School => "school",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "unit_leader" => Some(Self::UnitLeader),
+            "school" => Some(Self::School),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::UnitLeader => "หัวหน้าหน่วยงาน",
+            Self::School => "ผู้บริหารสถานศึกษา",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffLeaveStepStatus {
+    Waiting,
+    Pending,
+    Approved,
+    Returned,
+    Rejected,
+    Cancelled,
+}
+
+impl StaffLeaveStepStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Waiting => "waiting",
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Returned => "returned",
+            Self::Rejected => "rejected",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "waiting" => Some(Self::Waiting),
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "returned" => Some(Self::Returned),
+            "rejected" => Some(Self::Rejected),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffLeaveDecision {
+    Approve,
+    Return,
+    Reject,
+}
+
+/// Amounts are exposed in days (0.5 steps); storage keeps half-day units.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveType {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// `None` means the type is not limited by a yearly quota.
+    pub annual_entitlement_days: Option<f64>,
+    pub max_carry_over_days: f64,
+    pub max_accumulated_days: Option<f64>,
+    pub counts_weekends: bool,
+    pub requires_school_approval: bool,
+    pub document_required_from_days: Option<i32>,
+    pub is_active: bool,
+    pub sort_order: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStaffLeaveTypeRequest {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub annual_entitlement_days: Option<f64>,
+    #[serde(default)]
+    pub max_carry_over_days: f64,
+    pub max_accumulated_days: Option<f64>,
+    #[serde(default)]
+    pub counts_weekends: bool,
+    #[serde(default = "default_true")]
+    pub requires_school_approval: bool,
+    pub document_required_from_days: Option<i32>,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+/// Full replacement of the editable fields; the code is immutable because
+/// balances and reports refer to it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStaffLeaveTypeRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub annual_entitlement_days: Option<f64>,
+    #[serde(default)]
+    pub max_carry_over_days: f64,
+    pub max_accumulated_days: Option<f64>,
+    #[serde(default)]
+    pub counts_weekends: bool,
+    #[serde(default = "default_true")]
+    pub requires_school_approval: bool,
+    pub document_required_from_days: Option<i32>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveTypeFilter {
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveBalance {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub leave_type_id: Uuid,
+    pub leave_type_code: String,
+    pub leave_type_name: String,
+    pub fiscal_year: i32,
+    pub entitled_days: f64,
+    pub carried_over_days: f64,
+    pub adjustment_days: f64,
+    pub adjustment_note: Option<String>,
+    pub used_days: f64,
+    pub pending_days: f64,
+    /// `None` for leave types without a yearly quota.
+    pub remaining_days: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveBalanceFilter {
+    pub fiscal_year: Option<i32>,
+    pub user_id: Option<Uuid>,
+    pub leave_type_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdjustStaffLeaveBalanceRequest {
+    pub user_id: Uuid,
+    pub leave_type_id: Uuid,
+    pub fiscal_year: i32,
+    /// Replaces the current manual adjustment; may be negative.
+    pub adjustment_days: f64,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveApprovalStep {
+    pub id: Uuid,
+    pub step_order: i32,
+    pub step_kind: StaffLeaveStepKind,
+    pub organization_unit_id: Option<Uuid>,
+    pub organization_unit_name: Option<String>,
+    pub status: StaffLeaveStepStatus,
+    pub decided_by: Option<Uuid>,
+    pub decided_by_name: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub leave_type_id: Uuid,
+    pub leave_type_code: String,
+    pub leave_type_name: String,
+    pub fiscal_year: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub day_portion: StaffLeaveDayPortion,
+    pub requested_days: f64,
+    pub reason: String,
+    pub contact_during_leave: Option<String>,
+    pub status: StaffLeaveStatus,
+    pub decision_note: Option<String>,
+    pub decided_by: Option<Uuid>,
+    pub decided_by_name: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub steps: Vec<StaffLeaveApprovalStep>,
+    pub document_file_ids: Vec<Uuid>,
+    pub submitted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Used for both the first submission and a resubmission after return.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitStaffLeaveRequest {
+    pub leave_type_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default)]
+    pub day_portion: StaffLeaveDayPortion,
+    pub reason: String,
+    pub contact_during_leave: Option<String>,
+    #[serde(default)]
+    pub document_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewStaffLeaveRequest {
+    pub decision: StaffLeaveDecision,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveFilter {
+    pub user_id: Option<Uuid>,
+    pub leave_type_id: Option<Uuid>,
+    pub status: Option<StaffLeaveStatus>,
+    pub fiscal_year: Option<i32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Only requests whose current step the caller may decide.
+    #[serde(default)]
+    pub awaiting_my_review: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyStaffLeaveFilter {
+    pub fiscal_year: Option<i32>,
+    pub status: Option<StaffLeaveStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveCalendarQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub organization_unit_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveCalendarEntry {
+    pub leave_request_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub leave_type_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub day_portion: StaffLeaveDayPortion,
+}
+
+/// Approved leave covering a specific date, consumed by other modules'
+/// availability checks.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffLeaveAbsence {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub leave_request_id: Uuid,
+    pub leave_type_name: String,
+    pub day_portion: StaffLeaveDayPortion,
+}
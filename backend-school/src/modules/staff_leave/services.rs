@@ -0,0 +1,42 @@
+mod approvals;
+mod availability;
+mod balances;
+mod followups;
+mod notifications;
+mod records;
+mod requests;
+mod shared;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use approvals::{get_request, list_requests, review_request};
+pub use availability::{approved_absences_on, list_calendar};
+pub use balances::{adjust_balance, list_balances, list_my_balances};
+pub use notifications::notify_requester_of_decision;
+pub use records::StaffLeaveMutationOutcome;
+pub use requests::{cancel_request, list_my_requests, resubmit_request, submit_request};
+#[allow(unused_imports)]
+pub use shared::{
+    can_cancel, can_resubmit, carry_over_half_days, count_leave_half_days, days_to_half_days,
+    decision_notification_text, decision_requires_note, fiscal_year_bounds, fiscal_year_for,
+    format_leave_range, half_days_to_days, plan_approval_steps, portion_covers_window,
+    remaining_half_days, step_status_for, validate_leave_range, PlannedApprovalStep,
+};
+#[allow(unused_imports)]
+pub use types::is_valid_type_code;
+pub use types::{create_leave_type, list_leave_types, update_leave_type};
+
+#[cfg(test)]
+use chrono::{NaiveDate, NaiveTime};
+#[cfg(test)]
+use uuid::Uuid;
+
+#[cfg(test)]
+use crate::error::AppError;
+#[cfg(test)]
+use crate::modules::staff_leave::models::{
+    StaffLeaveDayPortion, StaffLeaveDecision, StaffLeaveStatus, StaffLeaveStepKind,
+    StaffLeaveStepStatus,
+};
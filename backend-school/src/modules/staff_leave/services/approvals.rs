@@ -0,0 +1,290 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff_leave::models::{
+    ReviewStaffLeaveRequest, StaffLeaveDecision, StaffLeaveFilter, StaffLeaveRequest,
+    StaffLeaveStatus, StaffLeaveStepKind, StaffLeaveStepStatus,
+};
+use crate::policies::staff_leave_access_policy;
+
+use super::balances::consume_balance;
+use super::followups::{close_step_work_item, open_step_work_item};
+use super::records::{
+    load_request, log_action, requests_from_rows, write_error, StaffLeaveMutationOutcome,
+    StaffLeaveRequestRow, REQUEST_SELECT,
+};
+use super::shared::{decision_requires_note, normalize_optional_text, step_status_for};
+
+const LEADER_POSITIONS_SQL: &str = "('director', 'deputy_director', 'head', 'deputy_head')";
+
+/// School-wide readers list every request; other staff see the requests routed
+/// through units they currently lead.
+pub async fn list_requests(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: StaffLeaveFilter,
+) -> Result<Vec<StaffLeaveRequest>, AppError> {
+    let school_wide = staff_leave_access_policy::can_read_school_staff_leave(actor);
+    if !school_wide {
+        staff_leave_access_policy::require_staff_leave_own_read(actor)?;
+    }
+    let can_approve_school = staff_leave_access_policy::can_approve_school_step(actor);
+
+    let mut builder = QueryBuilder::<Postgres>::new(REQUEST_SELECT);
+    builder.push(" WHERE ");
+    if school_wide {
+        builder.push("TRUE");
+    } else {
+        builder
+            .push(
+                "EXISTS (
+                    SELECT 1
+                    FROM staff_leave_approval_steps step
+                    JOIN organization_members leader
+                      ON leader.organization_unit_id = step.organization_unit_id
+                    WHERE step.leave_request_id = r.id
+                      AND step.step_kind = 'unit_leader'
+                      AND (leader.ended_at IS NULL OR leader.ended_at > CURRENT_DATE)
+                      AND leader.position_code IN ",
+            )
+            .push(LEADER_POSITIONS_SQL)
+            .push(" AND leader.user_id = ")
+            .push_bind(actor.user_id)
+            .push(")");
+    }
+
+    if filter.awaiting_my_review {
+        builder
+            .push(" AND r.user_id <> ")
+            .push_bind(actor.user_id)
+            .push(
+                " AND EXISTS (
+                    SELECT 1
+                    FROM staff_leave_approval_steps step
+                    WHERE step.leave_request_id = r.id
+                      AND step.status = 'pending'
+                      AND (",
+            )
+            .push_bind(can_approve_school)
+            .push(
+                " OR (
+                            step.step_kind = 'unit_leader'
+                            AND EXISTS (
+                                SELECT 1
+                                FROM organization_members leader
+                                WHERE leader.organization_unit_id = step.organization_unit_id
+                                  AND (leader.ended_at IS NULL OR leader.ended_at > CURRENT_DATE)
+                                  AND leader.position_code IN ",
+            )
+            .push(LEADER_POSITIONS_SQL)
+            .push(" AND leader.user_id = ")
+            .push_bind(actor.user_id)
+            .push(")))) ");
+    }
+    if let Some(user_id) = filter.user_id {
+        builder.push(" AND r.user_id = ").push_bind(user_id);
+    }
+    if let Some(leave_type_id) = filter.leave_type_id {
+        builder
+            .push(" AND r.leave_type_id = ")
+            .push_bind(leave_type_id);
+    }
+    if let Some(status) = filter.status {
+        builder.push(" AND r.status = ").push_bind(status.as_str());
+    }
+    if let Some(fiscal_year) = filter.fiscal_year {
+        builder.push(" AND r.fiscal_year = ").push_bind(fiscal_year);
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND r.end_date >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND r.start_date <= ").push_bind(to);
+    }
+    builder.push(" ORDER BY r.submitted_at DESC LIMIT 500");
+
+    let rows = builder
+        .build_query_as::<StaffLeaveRequestRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("Failed to list staff leave requests: {}", error);
+            AppError::InternalServerError("ไม่สามารถดึงรายการใบลาได้".to_string())
+        })?;
+
+    requests_from_rows(pool, rows).await
+}
+
+pub async fn get_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<StaffLeaveRequest, AppError> {
+    let request = load_request(pool, id).await?.request;
+    staff_leave_access_policy::require_staff_leave_request_read(pool, actor, id, request.user_id)
+        .await?;
+    Ok(request)
+}
+
+/// Decides the step currently awaiting a decision. Approving a unit step hands
+/// the request to the school step; approving the last step approves the leave
+/// and books the days against the requester's balance.
+pub async fn review_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: ReviewStaffLeaveRequest,
+) -> Result<StaffLeaveMutationOutcome, AppError> {
+    let current = load_request(pool, id).await?;
+    if current.request.status != StaffLeaveStatus::Pending {
+        return Err(AppError::Conflict("ใบลานี้ไม่ได้อยู่ระหว่างรอพิจารณา".to_string()));
+    }
+    if current.request.user_id == actor.user_id {
+        return Err(AppError::Forbidden(
+            "ไม่สามารถพิจารณาใบลาของตนเองได้".to_string(),
+        ));
+    }
+    let step = current
+        .current_step()
+        .map(|loaded| loaded.step.clone())
+        .ok_or_else(|| AppError::Conflict("ใบลานี้ไม่มีขั้นตอนที่รอพิจารณา".to_string()))?;
+    let allowed = match step.step_kind {
+        StaffLeaveStepKind::UnitLeader => {
+            staff_leave_access_policy::can_decide_unit_step(pool, actor, step.organization_unit_id)
+                .await?
+        }
+        StaffLeaveStepKind::School => staff_leave_access_policy::can_approve_school_step(actor),
+    };
+    if !allowed {
+        return Err(AppError::Forbidden("ไม่มีสิทธิ์พิจารณาใบลาในขั้นตอนนี้".to_string()));
+    }
+    let note = normalize_optional_text(payload.note);
+    if decision_requires_note(payload.decision) && note.is_none() {
+        return Err(AppError::ValidationError(
+            "กรุณาระบุเหตุผลเมื่อส่งกลับหรือไม่อนุมัติใบลา".to_string(),
+        ));
+    }
+    let next_step_id = current
+        .steps
+        .iter()
+        .find(|loaded| {
+            loaded.step.step_order > step.step_order
+                && loaded.step.status == StaffLeaveStepStatus::Waiting
+        })
+        .map(|loaded| loaded.step.id);
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE staff_leave_approval_steps
+        SET status = $2,
+            decided_by = $3,
+            decided_at = NOW(),
+            note = $4
+        WHERE id = $1
+          AND status = 'pending'
+        "#,
+    )
+    .bind(step.id)
+    .bind(step_status_for(payload.decision).as_str())
+    .bind(actor.user_id)
+    .bind(&note)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+
+    let advances_to_next_step = match (payload.decision, next_step_id) {
+        (StaffLeaveDecision::Approve, Some(next_step_id)) => {
+            sqlx::query("UPDATE staff_leave_approval_steps SET status = 'pending' WHERE id = $1")
+                .bind(next_step_id)
+                .execute(&mut *transaction)
+                .await
+                .map_err(write_error)?;
+            log_action(
+                &mut transaction,
+                id,
+                actor.user_id,
+                "step_approved",
+                Some(step.step_kind),
+                note.as_deref(),
+            )
+            .await?;
+            true
+        }
+        (decision, _) => {
+            let (status, action_kind) = match decision {
+                StaffLeaveDecision::Approve => (StaffLeaveStatus::Approved, "approved"),
+                StaffLeaveDecision::Return => (StaffLeaveStatus::Returned, "returned"),
+                StaffLeaveDecision::Reject => (StaffLeaveStatus::Rejected, "rejected"),
+            };
+            sqlx::query(
+                r#"
+                UPDATE staff_leave_requests
+                SET status = $2,
+                    decision_note = $3,
+                    decided_by = $4,
+                    decided_at = NOW()
+                WHERE id = $1
+                  AND status = 'pending'
+                "#,
+            )
+            .bind(id)
+            .bind(status.as_str())
+            .bind(&note)
+            .bind(actor.user_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(write_error)?;
+            sqlx::query(
+                r#"
+                UPDATE staff_leave_approval_steps
+                SET status = 'cancelled'
+                WHERE leave_request_id = $1
+                  AND status = 'waiting'
+                "#,
+            )
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(write_error)?;
+            if status == StaffLeaveStatus::Approved {
+                consume_balance(
+                    &mut transaction,
+                    current.request.user_id,
+                    current.request.leave_type_id,
+                    current.request.fiscal_year,
+                    current.requested_half_days,
+                    current.quota_limited,
+                )
+                .await?;
+            }
+            log_action(
+                &mut transaction,
+                id,
+                actor.user_id,
+                action_kind,
+                Some(step.step_kind),
+                note.as_deref(),
+            )
+            .await?;
+            false
+        }
+    };
+    transaction.commit().await.map_err(write_error)?;
+
+    let mut work_items_changed = close_step_work_item(pool, current.current_work_item_id()).await?;
+    let request = load_request(pool, id).await?.request;
+    if advances_to_next_step {
+        work_items_changed |= open_step_work_item(pool, &request).await?.is_some();
+    }
+    Ok(StaffLeaveMutationOutcome {
+        request,
+        work_items_changed,
+        detached_file_ids: Vec::new(),
+    })
+}
@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff::services::organization_delegation_service;
+use crate::modules::staff_leave::models::{
+    StaffLeaveAbsence, StaffLeaveCalendarEntry, StaffLeaveCalendarQuery,
+};
+use crate::policies::staff_leave_access_policy;
+
+use super::shared::{parse_day_portion, MAX_CALENDAR_DAYS};
+
+#[derive(Debug, sqlx::FromRow)]
+struct AbsenceRow {
+    user_id: Uuid,
+    user_name: String,
+    leave_request_id: Uuid,
+    leave_type_name: String,
+    day_portion: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CalendarRow {
+    leave_request_id: Uuid,
+    user_id: Uuid,
+    user_name: String,
+    leave_type_name: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    day_portion: String,
+}
+
+/// Approved leave of the given staff on one date. Exam invigilation and
+/// supervision use this to treat those staff as unavailable; callers decide
+/// with `portion_covers_window` whether half-day leave clashes with their time
+/// window.
+pub async fn approved_absences_on(
+    executor: impl PgExecutor<'_>,
+    user_ids: &[Uuid],
+    date: NaiveDate,
+) -> Result<Vec<StaffLeaveAbsence>, AppError> {
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query_as::<_, AbsenceRow>(
+        r#"
+        SELECT r.user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               r.id AS leave_request_id,
+               leave_type.name AS leave_type_name,
+               r.day_portion
+        FROM staff_leave_requests r
+        JOIN users u ON u.id = r.user_id
+        JOIN staff_leave_types leave_type ON leave_type.id = r.leave_type_id
+        WHERE r.user_id = ANY($1)
+          AND r.status = 'approved'
+          AND $2 BETWEEN r.start_date AND r.end_date
+        ORDER BY u.first_name, u.last_name
+        "#,
+    )
+    .bind(user_ids)
+    .bind(date)
+    .fetch_all(executor)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load approved staff leave: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบการลาของบุคลากรได้".to_string())
+    })?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(StaffLeaveAbsence {
+                user_id: row.user_id,
+                user_name: row.user_name,
+                leave_request_id: row.leave_request_id,
+                leave_type_name: row.leave_type_name,
+                day_portion: parse_day_portion(&row.day_portion)?,
+            })
+        })
+        .collect()
+}
+
+/// Approved leave in a date range. School-wide readers see everyone (optionally
+/// one unit's members); unit leaders see the members of units they lead, and
+/// other staff only their own leave.
+pub async fn list_calendar(
+    pool: &PgPool,
+    actor: &ActorContext,
+    query: StaffLeaveCalendarQuery,
+) -> Result<Vec<StaffLeaveCalendarEntry>, AppError> {
+    if query.to < query.from {
+        return Err(AppError::ValidationError(
+            "วันสิ้นสุดต้องไม่ก่อนวันเริ่มต้น".to_string(),
+        ));
+    }
+    if (query.to - query.from).num_days() + 1 > MAX_CALENDAR_DAYS {
+        return Err(AppError::ValidationError(format!(
+            "ดูปฏิทินการลาได้ครั้งละไม่เกิน {MAX_CALENDAR_DAYS} วัน"
+        )));
+    }
+
+    let school_wide = staff_leave_access_policy::can_read_school_staff_leave(actor);
+    if !school_wide {
+        staff_leave_access_policy::require_staff_leave_own_read(actor)?;
+        if let Some(organization_unit_id) = query.organization_unit_id {
+            if !organization_delegation_service::is_organization_unit_leader(
+                pool,
+                actor.user_id,
+                organization_unit_id,
+            )
+            .await?
+            {
+                return Err(AppError::Forbidden(
+                    "ดูปฏิทินการลาได้เฉพาะหน่วยงานที่ตนเป็นหัวหน้า".to_string(),
+                ));
+            }
+        }
+    }
+
+    let rows = sqlx::query_as::<_, CalendarRow>(
+        r#"
+        SELECT r.id AS leave_request_id,
+               r.user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               leave_type.name AS leave_type_name,
+               r.start_date,
+               r.end_date,
+               r.day_portion
+        FROM staff_leave_requests r
+        JOIN users u ON u.id = r.user_id
+        JOIN staff_leave_types leave_type ON leave_type.id = r.leave_type_id
+        WHERE r.status = 'approved'
+          AND r.start_date <= $2
+          AND r.end_date >= $1
+          AND (
+              $3::uuid IS NULL
+              OR r.user_id IN (
+                  SELECT member.user_id
+                  FROM organization_members member
+                  WHERE member.organization_unit_id = $3
+                    AND (member.ended_at IS NULL OR member.ended_at > CURRENT_DATE)
+              )
+          )
+          AND (
+              $4
+              OR r.user_id = $5
+              OR r.user_id IN (
+                  SELECT member.user_id
+                  FROM organization_members member
+                  JOIN organization_members leader
+                    ON leader.organization_unit_id = member.organization_unit_id
+                  WHERE leader.user_id = $5
+                    AND leader.position_code IN ('director', 'deputy_director', 'head', 'deputy_head')
+                    AND (leader.ended_at IS NULL OR leader.ended_at > CURRENT_DATE)
+                    AND (member.ended_at IS NULL OR member.ended_at > CURRENT_DATE)
+              )
+          )
+        ORDER BY r.start_date, u.first_name, u.last_name
+        "#,
+    )
+    .bind(query.from)
+    .bind(query.to)
+    .bind(query.organization_unit_id)
+    .bind(school_wide)
+    .bind(actor.user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load staff leave calendar: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงปฏิทินการลาได้".to_string())
+    })?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(StaffLeaveCalendarEntry {
+                leave_request_id: row.leave_request_id,
+                user_id: row.user_id,
+                user_name: row.user_name,
+                leave_type_name: row.leave_type_name,
+                start_date: row.start_date,
+                end_date: row.end_date,
+                day_portion: parse_day_portion(&row.day_portion)?,
+            })
+        })
+        .collect()
+}
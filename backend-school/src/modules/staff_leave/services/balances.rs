@@ -0,0 +1,493 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff_leave::models::{
+    AdjustStaffLeaveBalanceRequest, StaffLeaveBalance, StaffLeaveBalanceFilter,
+};
+use crate::policies::staff_leave_access_policy;
+use crate::scheduling::SCHOOL_TIMEZONE;
+
+use super::shared::{
+    carry_over_half_days, days_to_half_days, fiscal_year_bounds, fiscal_year_for,
+    half_days_to_days, remaining_half_days, required_text,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BalanceRow {
+    user_id: Uuid,
+    user_name: String,
+    leave_type_id: Uuid,
+    leave_type_code: String,
+    leave_type_name: String,
+    fiscal_year: i32,
+    entitled_half_days: i32,
+    carried_over_half_days: i32,
+    adjustment_half_days: i32,
+    adjustment_note: Option<String>,
+    used_half_days: i32,
+    pending_half_days: i32,
+    quota_limited: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct OpeningCandidate {
+    user_id: Uuid,
+    leave_type_id: Uuid,
+    annual_entitlement_half_days: Option<i32>,
+    max_carry_over_half_days: i32,
+    max_accumulated_half_days: Option<i32>,
+    prior_remaining_half_days: Option<i32>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct LockedBalance {
+    entitled_half_days: i32,
+    carried_over_half_days: i32,
+    adjustment_half_days: i32,
+    used_half_days: i32,
+}
+
+pub(super) fn school_today() -> NaiveDate {
+    Utc::now().with_timezone(&SCHOOL_TIMEZONE).date_naive()
+}
+
+pub async fn list_my_balances(
+    pool: &PgPool,
+    actor: &ActorContext,
+    fiscal_year: Option<i32>,
+) -> Result<Vec<StaffLeaveBalance>, AppError> {
+    staff_leave_access_policy::require_staff_leave_own_read(actor)?;
+    let fiscal_year = resolve_fiscal_year(fiscal_year)?;
+    let mut transaction = pool.begin().await.map_err(balance_write_error)?;
+    open_balances(&mut transaction, fiscal_year, Some(actor.user_id), None).await?;
+    transaction.commit().await.map_err(balance_write_error)?;
+
+    query_balances(pool, fiscal_year, Some(actor.user_id), None).await
+}
+
+pub async fn list_balances(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: StaffLeaveBalanceFilter,
+) -> Result<Vec<StaffLeaveBalance>, AppError> {
+    staff_leave_access_policy::require_staff_leave_school_read(actor)?;
+    let fiscal_year = resolve_fiscal_year(filter.fiscal_year)?;
+    let mut transaction = pool.begin().await.map_err(balance_write_error)?;
+    open_balances(
+        &mut transaction,
+        fiscal_year,
+        filter.user_id,
+        filter.leave_type_id,
+    )
+    .await?;
+    transaction.commit().await.map_err(balance_write_error)?;
+
+    query_balances(pool, fiscal_year, filter.user_id, filter.leave_type_id).await
+}
+
+/// Sets the manual adjustment for one balance, e.g. days transferred from
+/// another school or corrections to the opening figures.
+pub async fn adjust_balance(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: AdjustStaffLeaveBalanceRequest,
+) -> Result<StaffLeaveBalance, AppError> {
+    staff_leave_access_policy::require_staff_leave_manage(actor)?;
+    let fiscal_year = resolve_fiscal_year(Some(payload.fiscal_year))?;
+    let adjustment_half_days =
+        days_to_half_days(payload.adjustment_days, "จำนวนวันที่ปรับต้องเป็นจำนวนเต็มหรือครึ่งวัน")?;
+    let note = required_text(&payload.note, "กรุณาระบุเหตุผลการปรับยอดวันลา")?;
+
+    let mut transaction = pool.begin().await.map_err(balance_write_error)?;
+    open_balances(
+        &mut transaction,
+        fiscal_year,
+        Some(payload.user_id),
+        Some(payload.leave_type_id),
+    )
+    .await?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE staff_leave_balances
+        SET adjustment_half_days = $4,
+            adjustment_note = $5
+        WHERE user_id = $1
+          AND leave_type_id = $2
+          AND fiscal_year = $3
+        "#,
+    )
+    .bind(payload.user_id)
+    .bind(payload.leave_type_id)
+    .bind(fiscal_year)
+    .bind(adjustment_half_days)
+    .bind(&note)
+    .execute(&mut *transaction)
+    .await
+    .map_err(balance_write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(
+            "ไม่พบยอดวันลาของบุคลากรตามประเภทการลานี้".to_string(),
+        ));
+    }
+    transaction.commit().await.map_err(balance_write_error)?;
+
+    query_balances(
+        pool,
+        fiscal_year,
+        Some(payload.user_id),
+        Some(payload.leave_type_id),
+    )
+    .await?
+    .into_iter()
+    .next()
+    .ok_or_else(|| AppError::NotFound("ไม่พบยอดวันลา".to_string()))
+}
+
+/// Creates the missing balances of active staff for a fiscal year, carrying
+/// unused days over from the previous year's balance when one exists.
+pub(super) async fn open_balances(
+    transaction: &mut Transaction<'_, Postgres>,
+    fiscal_year: i32,
+    user_id: Option<Uuid>,
+    leave_type_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let candidates = sqlx::query_as::<_, OpeningCandidate>(
+        r#"
+        SELECT u.id AS user_id,
+               t.id AS leave_type_id,
+               t.annual_entitlement_half_days,
+               t.max_carry_over_half_days,
+               t.max_accumulated_half_days,
+               prior.entitled_half_days + prior.carried_over_half_days
+                   + prior.adjustment_half_days - prior.used_half_days
+                   AS prior_remaining_half_days
+        FROM users u
+        CROSS JOIN staff_leave_types t
+        LEFT JOIN staff_leave_balances prior
+          ON prior.user_id = u.id
+         AND prior.leave_type_id = t.id
+         AND prior.fiscal_year = $1 - 1
+        WHERE u.user_type = 'staff'
+          AND u.status = 'active'
+          AND t.is_active = true
+          AND ($2::uuid IS NULL OR u.id = $2)
+          AND ($3::uuid IS NULL OR t.id = $3)
+          AND NOT EXISTS (
+              SELECT 1
+              FROM staff_leave_balances current_balance
+              WHERE current_balance.user_id = u.id
+                AND current_balance.leave_type_id = t.id
+                AND current_balance.fiscal_year = $1
+          )
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(user_id)
+    .bind(leave_type_id)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(balance_read_error)?;
+
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    let mut user_ids = Vec::with_capacity(candidates.len());
+    let mut leave_type_ids = Vec::with_capacity(candidates.len());
+    let mut entitled = Vec::with_capacity(candidates.len());
+    let mut carried = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        user_ids.push(candidate.user_id);
+        leave_type_ids.push(candidate.leave_type_id);
+        entitled.push(candidate.annual_entitlement_half_days.unwrap_or(0));
+        carried.push(carry_over_half_days(
+            candidate.prior_remaining_half_days.unwrap_or(0),
+            candidate.max_carry_over_half_days,
+            candidate.annual_entitlement_half_days,
+            candidate.max_accumulated_half_days,
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO staff_leave_balances (
+            user_id, leave_type_id, fiscal_year, entitled_half_days, carried_over_half_days
+        )
+        SELECT user_id, leave_type_id, $5, entitled, carried
+        FROM UNNEST($1::uuid[], $2::uuid[], $3::int[], $4::int[])
+            AS opening(user_id, leave_type_id, entitled, carried)
+        ON CONFLICT (user_id, leave_type_id, fiscal_year) DO NOTHING
+        "#,
+    )
+    .bind(&user_ids)
+    .bind(&leave_type_ids)
+    .bind(&entitled)
+    .bind(&carried)
+    .bind(fiscal_year)
+    .execute(&mut **transaction)
+    .await
+    .map_err(balance_write_error)?;
+
+    Ok(())
+}
+
+/// Checks that a request fits the remaining quota after other pending requests
+/// have reserved their days. The balance row stays locked until the caller's
+/// transaction ends so concurrent submissions cannot overbook it.
+pub(super) async fn ensure_quota_available(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    leave_type_id: Uuid,
+    fiscal_year: i32,
+    requested_half_days: i32,
+    excluding_request_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let balance = lock_balance(transaction, user_id, leave_type_id, fiscal_year).await?;
+    let pending_half_days = sqlx::query_scalar::<_, i32>(
+        r#"
+        SELECT COALESCE(SUM(requested_half_days), 0)::int
+        FROM staff_leave_requests
+        WHERE user_id = $1
+          AND leave_type_id = $2
+          AND fiscal_year = $3
+          AND status = 'pending'
+          AND ($4::uuid IS NULL OR id <> $4)
+        "#,
+    )
+    .bind(user_id)
+    .bind(leave_type_id)
+    .bind(fiscal_year)
+    .bind(excluding_request_id)
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(balance_read_error)?;
+
+    let remaining = remaining_half_days(
+        balance.entitled_half_days,
+        balance.carried_over_half_days,
+        balance.adjustment_half_days,
+        balance.used_half_days,
+        pending_half_days,
+    );
+    if requested_half_days > remaining {
+        return Err(AppError::ValidationError(format!(
+            "วันลาคงเหลือไม่เพียงพอ (คงเหลือ {} วัน)",
+            half_days_to_days(remaining.max(0))
+        )));
+    }
+    Ok(())
+}
+
+/// Records approved days as used. Quota-limited types are re-checked because
+/// adjustments may have lowered the balance while the request was pending.
+pub(super) async fn consume_balance(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    leave_type_id: Uuid,
+    fiscal_year: i32,
+    requested_half_days: i32,
+    quota_limited: bool,
+) -> Result<(), AppError> {
+    let balance = lock_balance(transaction, user_id, leave_type_id, fiscal_year).await?;
+    if quota_limited {
+        let remaining = remaining_half_days(
+            balance.entitled_half_days,
+            balance.carried_over_half_days,
+            balance.adjustment_half_days,
+            balance.used_half_days,
+            0,
+        );
+        if requested_half_days > remaining {
+            return Err(AppError::Conflict(format!(
+                "วันลาคงเหลือของผู้ยื่นไม่เพียงพอ (คงเหลือ {} วัน)",
+                half_days_to_days(remaining.max(0))
+            )));
+        }
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE staff_leave_balances
+        SET used_half_days = used_half_days + $4
+        WHERE user_id = $1
+          AND leave_type_id = $2
+          AND fiscal_year = $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(leave_type_id)
+    .bind(fiscal_year)
+    .bind(requested_half_days)
+    .execute(&mut **transaction)
+    .await
+    .map_err(balance_write_error)?;
+
+    Ok(())
+}
+
+pub(super) async fn restore_balance(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    leave_type_id: Uuid,
+    fiscal_year: i32,
+    requested_half_days: i32,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE staff_leave_balances
+        SET used_half_days = GREATEST(used_half_days - $4, 0)
+        WHERE user_id = $1
+          AND leave_type_id = $2
+          AND fiscal_year = $3
+        "#,
+    )
+    .bind(user_id)
+    .bind(leave_type_id)
+    .bind(fiscal_year)
+    .bind(requested_half_days)
+    .execute(&mut **transaction)
+    .await
+    .map_err(balance_write_error)?;
+
+    Ok(())
+}
+
+/// Inactive types have no opening rule, so their balances are created empty
+/// when an old request still needs to be recorded against them.
+async fn lock_balance(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    leave_type_id: Uuid,
+    fiscal_year: i32,
+) -> Result<LockedBalance, AppError> {
+    open_balances(transaction, fiscal_year, Some(user_id), Some(leave_type_id)).await?;
+    sqlx::query(
+        r#"
+        INSERT INTO staff_leave_balances (user_id, leave_type_id, fiscal_year)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, leave_type_id, fiscal_year) DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .bind(leave_type_id)
+    .bind(fiscal_year)
+    .execute(&mut **transaction)
+    .await
+    .map_err(balance_write_error)?;
+
+    sqlx::query_as::<_, LockedBalance>(
+        r#"
+        SELECT entitled_half_days, carried_over_half_days, adjustment_half_days, used_half_days
+        FROM staff_leave_balances
+        WHERE user_id = $1
+          AND leave_type_id = $2
+          AND fiscal_year = $3
+        FOR UPDATE
+        "#,
+    )
+    .bind(user_id)
+    .bind(leave_type_id)
+    .bind(fiscal_year)
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(balance_read_error)
+}
+
+async fn query_balances(
+    pool: &PgPool,
+    fiscal_year: i32,
+    user_id: Option<Uuid>,
+    leave_type_id: Option<Uuid>,
+) -> Result<Vec<StaffLeaveBalance>, AppError> {
+    let rows = sqlx::query_as::<_, BalanceRow>(
+        r#"
+        SELECT b.user_id,
+               CONCAT_WS(' ', u.first_name, u.last_name) AS user_name,
+               b.leave_type_id,
+               t.code AS leave_type_code,
+               t.name AS leave_type_name,
+               b.fiscal_year,
+               b.entitled_half_days,
+               b.carried_over_half_days,
+               b.adjustment_half_days,
+               b.adjustment_note,
+               b.used_half_days,
+               COALESCE(pending.half_days, 0)::int AS pending_half_days,
+               t.annual_entitlement_half_days IS NOT NULL AS quota_limited
+        FROM staff_leave_balances b
+        JOIN users u ON u.id = b.user_id
+        JOIN staff_leave_types t ON t.id = b.leave_type_id
+        LEFT JOIN LATERAL (
+            SELECT SUM(r.requested_half_days) AS half_days
+            FROM staff_leave_requests r
+            WHERE r.user_id = b.user_id
+              AND r.leave_type_id = b.leave_type_id
+              AND r.fiscal_year = b.fiscal_year
+              AND r.status = 'pending'
+        ) pending ON true
+        WHERE b.fiscal_year = $1
+          AND ($2::uuid IS NULL OR b.user_id = $2)
+          AND ($3::uuid IS NULL OR b.leave_type_id = $3)
+        ORDER BY u.first_name, u.last_name, t.sort_order, t.name
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(user_id)
+    .bind(leave_type_id)
+    .fetch_all(pool)
+    .await
+    .map_err(balance_read_error)?;
+
+    Ok(rows.into_iter().map(balance_from_row).collect())
+}
+
+fn balance_from_row(row: BalanceRow) -> StaffLeaveBalance {
+    let remaining_days = row.quota_limited.then(|| {
+        half_days_to_days(remaining_half_days(
+            row.entitled_half_days,
+            row.carried_over_half_days,
+            row.adjustment_half_days,
+            row.used_half_days,
+            row.pending_half_days,
+        ))
+    });
+    StaffLeaveBalance {
+        user_id: row.user_id,
+        user_name: row.user_name,
+        leave_type_id: row.leave_type_id,
+        leave_type_code: row.leave_type_code,
+        leave_type_name: row.leave_type_name,
+        fiscal_year: row.fiscal_year,
+        entitled_days: half_days_to_days(row.entitled_half_days),
+        carried_over_days: half_days_to_days(row.carried_over_half_days),
+        adjustment_days: half_days_to_days(row.adjustment_half_days),
+        adjustment_note: row.adjustment_note,
+        used_days: half_days_to_days(row.used_half_days),
+        pending_days: half_days_to_days(row.pending_half_days),
+        remaining_days,
+    }
+}
+
+fn resolve_fiscal_year(fiscal_year: Option<i32>) -> Result<i32, AppError> {
+    let fiscal_year = fiscal_year.unwrap_or_else(|| fiscal_year_for(school_today()));
+    if fiscal_year_bounds(fiscal_year).is_some() && (2500..=2700).contains(&fiscal_year) {
+        Ok(fiscal_year)
+    } else {
+        Err(AppError::ValidationError(
+            "ปีงบประมาณต้องเป็นปีพุทธศักราช".to_string(),
+        ))
+    }
+}
+
+fn balance_read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read staff leave balances: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงยอดวันลาได้".to_string())
+}
+
+fn balance_write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write staff leave balances: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกยอดวันลาได้".to_string())
+}
@@ -0,0 +1,291 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::staff_leave::models::{
+    StaffLeaveRequest, StaffLeaveStepKind, StaffLeaveStepStatus,
+};
+use crate::modules::work::services::{
+    self as work_service, CreateWorkItemInput, WorkItemAssigneeTargetInput, WorkItemAssigneeType,
+    WorkItemMetadata,
+};
+use crate::modules::workflow::models::WorkflowWindowMetadata;
+use crate::modules::workflow::services::{
+    self as workflow_service, CreateWorkflowWindowInput, WorkflowWindowSchedule,
+};
+use crate::permissions::registry::codes;
+
+use super::shared::format_leave_range;
+
+const STAFF_LEAVE_MODULE_CODE: &str = "staff_leave";
+const REVIEW_WORKFLOW_CODE: &str = "staff_leave_review";
+
+/// Walks up from the requester's primary organization unit to the nearest unit
+/// with a current leader other than the requester, so a head of department's
+/// own leave goes to the leader of the parent unit.
+pub(super) async fn resolve_leader_unit(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        WITH RECURSIVE unit_chain AS (
+            SELECT unit.id, unit.parent_unit_id, 0 AS depth
+            FROM organization_members member
+            JOIN organization_units unit ON unit.id = member.organization_unit_id
+            WHERE member.user_id = $1
+              AND member.is_primary = true
+              AND (member.ended_at IS NULL OR member.ended_at > CURRENT_DATE)
+              AND unit.is_active = true
+
+            UNION ALL
+
+            SELECT parent.id, parent.parent_unit_id, unit_chain.depth + 1
+            FROM unit_chain
+            JOIN organization_units parent ON parent.id = unit_chain.parent_unit_id
+            WHERE unit_chain.depth < 10
+              AND parent.is_active = true
+        )
+        SELECT unit_chain.id
+        FROM unit_chain
+        WHERE EXISTS (
+            SELECT 1
+            FROM organization_members leader
+            WHERE leader.organization_unit_id = unit_chain.id
+              AND leader.user_id <> $1
+              AND leader.position_code IN ('director', 'deputy_director', 'head', 'deputy_head')
+              AND (leader.ended_at IS NULL OR leader.ended_at > CURRENT_DATE)
+        )
+        ORDER BY unit_chain.depth
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)
+}
+
+/// Creates the work item for the step now awaiting a decision. Returns `None`
+/// when nobody can be assigned; such requests still appear in the school-wide
+/// leave list for approvers.
+pub(super) async fn open_step_work_item(
+    pool: &PgPool,
+    request: &StaffLeaveRequest,
+) -> Result<Option<Uuid>, AppError> {
+    let Some(step) = request
+        .steps
+        .iter()
+        .find(|step| step.status == StaffLeaveStepStatus::Pending)
+    else {
+        return Ok(None);
+    };
+
+    let assignee_ids = match step.step_kind {
+        StaffLeaveStepKind::UnitLeader => match step.organization_unit_id {
+            Some(organization_unit_id) => {
+                unit_leader_ids(pool, organization_unit_id, request.user_id).await?
+            }
+            None => Vec::new(),
+        },
+        StaffLeaveStepKind::School => school_approver_ids(pool, request.user_id).await?,
+    };
+    if assignee_ids.is_empty() {
+        tracing::warn!(
+            leave_request_id = %request.id,
+            step_kind = step.step_kind.as_str(),
+            "Staff leave step has no assignable approver"
+        );
+        return Ok(None);
+    }
+
+    let workflow_window_id =
+        ensure_review_window(pool, request.fiscal_year, request.user_id).await?;
+    let work_item_id = work_service::create_work_item(
+        pool,
+        CreateWorkItemInput {
+            workflow_window_id,
+            module_code: STAFF_LEAVE_MODULE_CODE.to_string(),
+            source_resource_type: "staff_leave_request".to_string(),
+            source_resource_id: Some(request.id),
+            title: format!("พิจารณา{}: {}", request.leave_type_name, request.user_name),
+            description: Some(format!(
+                "วันที่ {} ({} วัน) - ขั้น{}",
+                format_leave_range(request.start_date, request.end_date),
+                request.requested_days,
+                step.step_kind.label()
+            )),
+            action_path: format!("/staff/leave/requests/{}", request.id),
+            required_permission: match step.step_kind {
+                StaffLeaveStepKind::UnitLeader => None,
+                StaffLeaveStepKind::School => Some(codes::STAFF_LEAVE_APPROVE_SCHOOL.to_string()),
+            },
+            metadata: WorkItemMetadata {
+                tags: vec!["staff_leave".to_string()],
+                source_label: Some("ใบลาบุคลากร".to_string()),
+            },
+            assignees: assignee_ids
+                .into_iter()
+                .map(|user_id| WorkItemAssigneeTargetInput {
+                    assignee_type: WorkItemAssigneeType::User,
+                    user_id: Some(user_id),
+                    organization_unit_id: None,
+                    position_code: None,
+                })
+                .collect(),
+            created_by: Some(request.user_id),
+        },
+    )
+    .await?;
+
+    sqlx::query("UPDATE staff_leave_approval_steps SET work_item_id = $2 WHERE id = $1")
+        .bind(step.id)
+        .bind(work_item_id)
+        .execute(pool)
+        .await
+        .map_err(followup_error)?;
+
+    Ok(Some(work_item_id))
+}
+
+pub(super) async fn close_step_work_item(
+    pool: &PgPool,
+    work_item_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    match work_item_id {
+        Some(work_item_id) => work_service::close_work_item(pool, work_item_id).await,
+        None => Ok(false),
+    }
+}
+
+async fn unit_leader_ids(
+    pool: &PgPool,
+    organization_unit_id: Uuid,
+    requester_id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT leader.user_id
+        FROM organization_members leader
+        JOIN users u ON u.id = leader.user_id AND u.status = 'active'
+        WHERE leader.organization_unit_id = $1
+          AND leader.user_id <> $2
+          AND leader.position_code IN ('director', 'deputy_director', 'head', 'deputy_head')
+          AND (leader.ended_at IS NULL OR leader.ended_at > CURRENT_DATE)
+        "#,
+    )
+    .bind(organization_unit_id)
+    .bind(requester_id)
+    .fetch_all(pool)
+    .await
+    .map_err(followup_error)
+}
+
+/// Users holding the school approval permission through a role or an
+/// organization grant.
+async fn school_approver_ids(pool: &PgPool, requester_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT approver.user_id
+        FROM (
+            SELECT ur.user_id
+            FROM user_roles ur
+            JOIN roles r ON r.id = ur.role_id AND r.is_active = true
+            JOIN role_permissions rp ON rp.role_id = r.id
+            JOIN permissions p ON p.id = rp.permission_id
+            WHERE p.code = $1
+              AND ur.ended_at IS NULL
+
+            UNION
+
+            SELECT om.user_id
+            FROM organization_members om
+            JOIN organization_units ou ON ou.id = om.organization_unit_id AND ou.is_active = true
+            JOIN organization_permission_grants opg ON opg.organization_unit_id = ou.id
+            JOIN permissions p ON p.id = opg.permission_id
+            WHERE p.code = $1
+              AND (om.ended_at IS NULL OR om.ended_at > CURRENT_DATE)
+              AND (opg.position_code IS NULL OR opg.position_code = om.position_code)
+        ) approver
+        JOIN users u ON u.id = approver.user_id AND u.status = 'active'
+        WHERE approver.user_id <> $2
+        "#,
+    )
+    .bind(codes::STAFF_LEAVE_APPROVE_SCHOOL)
+    .bind(requester_id)
+    .fetch_all(pool)
+    .await
+    .map_err(followup_error)
+}
+
+async fn ensure_review_window(
+    pool: &PgPool,
+    fiscal_year: i32,
+    created_by: Uuid,
+) -> Result<Uuid, AppError> {
+    if let Some(id) = existing_review_window(pool, fiscal_year).await? {
+        return Ok(id);
+    }
+
+    let window = workflow_service::create_workflow_window(
+        pool,
+        CreateWorkflowWindowInput {
+            module_code: STAFF_LEAVE_MODULE_CODE.to_string(),
+            workflow_code: REVIEW_WORKFLOW_CODE.to_string(),
+            title: format!("พิจารณาใบลาบุคลากร ปีงบประมาณ {fiscal_year}"),
+            description: Some("งานพิจารณาใบลาของบุคลากรโดยหัวหน้าหน่วยงานและผู้บริหาร".to_string()),
+            organization_unit_id: None,
+            managed_by_permission: codes::STAFF_LEAVE_MANAGE_SCHOOL.to_string(),
+            schedule: WorkflowWindowSchedule {
+                opens_at: None,
+                due_at: None,
+                closes_at: None,
+            },
+            metadata: WorkflowWindowMetadata {
+                tags: vec!["staff_leave".to_string()],
+            },
+            created_by: Some(created_by),
+        },
+    )
+    .await?;
+    workflow_service::open_workflow_window(pool, window.id).await?;
+
+    let linked = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO staff_leave_review_windows (fiscal_year, workflow_window_id)
+        VALUES ($1, $2)
+        ON CONFLICT (fiscal_year) DO NOTHING
+        RETURNING workflow_window_id
+        "#,
+    )
+    .bind(fiscal_year)
+    .bind(window.id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)?;
+
+    if let Some(id) = linked {
+        return Ok(id);
+    }
+
+    // Another submission linked its window first; retire ours and use theirs.
+    workflow_service::close_workflow_window(pool, window.id).await?;
+    existing_review_window(pool, fiscal_year)
+        .await?
+        .ok_or_else(|| AppError::InternalServerError("ไม่สามารถสร้างรอบงานพิจารณาใบลาได้".to_string()))
+}
+
+async fn existing_review_window(pool: &PgPool, fiscal_year: i32) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT workflow_window_id FROM staff_leave_review_windows WHERE fiscal_year = $1",
+    )
+    .bind(fiscal_year)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)
+}
+
+fn followup_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to route staff leave approval: {}", error);
+    AppError::InternalServerError("ไม่สามารถส่งใบลาถึงผู้พิจารณาได้".to_string())
+}
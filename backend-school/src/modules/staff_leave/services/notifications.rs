@@ -0,0 +1,52 @@
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+
+use crate::error::AppError;
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::modules::staff_leave::models::StaffLeaveRequest;
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+use super::shared::decision_notification_text;
+
+const REQUESTER_LEAVE_LINK: &str = "/staff/leave";
+
+/// Tells the requester about a final decision. Intermediate step approvals are
+/// visible on the request itself and do not notify. Returns whether a
+/// notification was sent.
+pub async fn notify_requester_of_decision(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    request: &StaffLeaveRequest,
+) -> Result<bool, AppError> {
+    let Some((title, message)) = decision_notification_text(
+        request.status,
+        &request.leave_type_name,
+        request.start_date,
+        request.end_date,
+    ) else {
+        return Ok(false);
+    };
+
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    NotificationService::send(
+        pool,
+        &publisher,
+        request.user_id,
+        &title,
+        &message,
+        NotificationType::Info,
+        Some(REQUESTER_LEAVE_LINK),
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(
+            "Failed to send staff leave decision notification: {}",
+            error
+        );
+        AppError::InternalServerError("ไม่สามารถแจ้งผลการพิจารณาใบลาได้".to_string())
+    })?;
+    Ok(true)
+}
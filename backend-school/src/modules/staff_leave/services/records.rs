@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::staff_leave::models::{
+    StaffLeaveApprovalStep, StaffLeaveRequest, StaffLeaveStepKind, StaffLeaveStepStatus,
+};
+
+use super::shared::{
+    half_days_to_days, parse_day_portion, parse_status, parse_step_kind, parse_step_status,
+    PlannedApprovalStep, REQUEST_NOT_FOUND_MESSAGE,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StaffLeaveRequestRow {
+    id: Uuid,
+    user_id: Uuid,
+    user_name: String,
+    leave_type_id: Uuid,
+    leave_type_code: String,
+    leave_type_name: String,
+    fiscal_year: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    day_portion: String,
+    requested_half_days: i32,
+    reason: String,
+    contact_during_leave: Option<String>,
+    status: String,
+    decision_note: Option<String>,
+    decided_by: Option<Uuid>,
+    decided_by_name: Option<String>,
+    decided_at: Option<DateTime<Utc>>,
+    document_file_ids: Vec<Uuid>,
+    quota_limited: bool,
+    submitted_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+pub(super) const REQUEST_SELECT: &str = r#"
+    SELECT r.id,
+           r.user_id,
+           CONCAT_WS(' ', requester.first_name, requester.last_name) AS user_name,
+           r.leave_type_id,
+           leave_type.code AS leave_type_code,
+           leave_type.name AS leave_type_name,
+           r.fiscal_year,
+           r.start_date,
+           r.end_date,
+           r.day_portion,
+           r.requested_half_days,
+           r.reason,
+           r.contact_during_leave,
+           r.status,
+           r.decision_note,
+           r.decided_by,
+           NULLIF(CONCAT_WS(' ', decider.first_name, decider.last_name), '') AS decided_by_name,
+           r.decided_at,
+           COALESCE(
+               (
+                   SELECT array_agg(document.file_id ORDER BY document.created_at, document.file_id)
+                   FROM staff_leave_request_documents document
+                   WHERE document.leave_request_id = r.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS document_file_ids,
+           leave_type.annual_entitlement_half_days IS NOT NULL AS quota_limited,
+           r.submitted_at,
+           r.created_at,
+           r.updated_at
+    FROM staff_leave_requests r
+    JOIN users requester ON requester.id = r.user_id
+    JOIN staff_leave_types leave_type ON leave_type.id = r.leave_type_id
+    LEFT JOIN users decider ON decider.id = r.decided_by
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct StaffLeaveStepRow {
+    id: Uuid,
+    leave_request_id: Uuid,
+    step_order: i32,
+    step_kind: String,
+    organization_unit_id: Option<Uuid>,
+    organization_unit_name: Option<String>,
+    status: String,
+    decided_by: Option<Uuid>,
+    decided_by_name: Option<String>,
+    decided_at: Option<DateTime<Utc>>,
+    note: Option<String>,
+    work_item_id: Option<Uuid>,
+}
+
+pub struct StaffLeaveMutationOutcome {
+    pub request: StaffLeaveRequest,
+    pub work_items_changed: bool,
+    /// Documents removed from the request that the handler hands to File
+    /// Platform deletion.
+    pub detached_file_ids: Vec<Uuid>,
+}
+
+/// Approval step plus its open work item, which is internal routing state.
+pub(super) struct LoadedApprovalStep {
+    pub(super) step: StaffLeaveApprovalStep,
+    pub(super) work_item_id: Option<Uuid>,
+}
+
+pub(super) struct LoadedStaffLeaveRequest {
+    pub(super) request: StaffLeaveRequest,
+    pub(super) requested_half_days: i32,
+    pub(super) quota_limited: bool,
+    pub(super) steps: Vec<LoadedApprovalStep>,
+}
+
+impl LoadedStaffLeaveRequest {
+    pub(super) fn current_step(&self) -> Option<&LoadedApprovalStep> {
+        self.steps
+            .iter()
+            .find(|loaded| loaded.step.status == StaffLeaveStepStatus::Pending)
+    }
+
+    pub(super) fn current_work_item_id(&self) -> Option<Uuid> {
+        self.current_step().and_then(|loaded| loaded.work_item_id)
+    }
+}
+
+pub(super) async fn load_request(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<LoadedStaffLeaveRequest, AppError> {
+    let row =
+        sqlx::query_as::<_, StaffLeaveRequestRow>(&format!("{REQUEST_SELECT} WHERE r.id = $1"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(read_error)?
+            .ok_or_else(|| AppError::NotFound(REQUEST_NOT_FOUND_MESSAGE.to_string()))?;
+
+    let mut steps = load_steps(pool, &[row.id]).await?;
+    loaded_from_row(row, steps.remove(&id).unwrap_or_default())
+}
+
+pub(super) async fn requests_from_rows(
+    pool: &PgPool,
+    rows: Vec<StaffLeaveRequestRow>,
+) -> Result<Vec<StaffLeaveRequest>, AppError> {
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut steps = load_steps(pool, &ids).await?;
+    rows.into_iter()
+        .map(|row| {
+            let request_steps = steps.remove(&row.id).unwrap_or_default();
+            loaded_from_row(row, request_steps).map(|loaded| loaded.request)
+        })
+        .collect()
+}
+
+async fn load_steps(
+    pool: &PgPool,
+    leave_request_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<LoadedApprovalStep>>, AppError> {
+    let rows = sqlx::query_as::<_, StaffLeaveStepRow>(
+        r#"
+        SELECT step.id,
+               step.leave_request_id,
+               step.step_order,
+               step.step_kind,
+               step.organization_unit_id,
+               unit.name AS organization_unit_name,
+               step.status,
+               step.decided_by,
+               NULLIF(CONCAT_WS(' ', decider.first_name, decider.last_name), '') AS decided_by_name,
+               step.decided_at,
+               step.note,
+               step.work_item_id
+        FROM staff_leave_approval_steps step
+        LEFT JOIN organization_units unit ON unit.id = step.organization_unit_id
+        LEFT JOIN users decider ON decider.id = step.decided_by
+        WHERE step.leave_request_id = ANY($1)
+        ORDER BY step.leave_request_id, step.step_order
+        "#,
+    )
+    .bind(leave_request_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)?;
+
+    let mut steps: HashMap<Uuid, Vec<LoadedApprovalStep>> = HashMap::new();
+    for row in rows {
+        steps
+            .entry(row.leave_request_id)
+            .or_default()
+            .push(LoadedApprovalStep {
+                step: StaffLeaveApprovalStep {
+                    id: row.id,
+                    step_order: row.step_order,
+                    step_kind: parse_step_kind(&row.step_kind)?,
+                    organization_unit_id: row.organization_unit_id,
+                    organization_unit_name: row.organization_unit_name,
+                    status: parse_step_status(&row.status)?,
+                    decided_by: row.decided_by,
+                    decided_by_name: row.decided_by_name,
+                    decided_at: row.decided_at,
+                    note: row.note,
+                },
+                work_item_id: row.work_item_id,
+            });
+    }
+    Ok(steps)
+}
+
+fn loaded_from_row(
+    row: StaffLeaveRequestRow,
+    steps: Vec<LoadedApprovalStep>,
+) -> Result<LoadedStaffLeaveRequest, AppError> {
+    Ok(LoadedStaffLeaveRequest {
+        requested_half_days: row.requested_half_days,
+        quota_limited: row.quota_limited,
+        request: StaffLeaveRequest {
+            id: row.id,
+            user_id: row.user_id,
+            user_name: row.user_name,
+            leave_type_id: row.leave_type_id,
+            leave_type_code: row.leave_type_code,
+            leave_type_name: row.leave_type_name,
+            fiscal_year: row.fiscal_year,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            day_portion: parse_day_portion(&row.day_portion)?,
+            requested_days: half_days_to_days(row.requested_half_days),
+            reason: row.reason,
+            contact_during_leave: row.contact_during_leave,
+            status: parse_status(&row.status)?,
+            decision_note: row.decision_note,
+            decided_by: row.decided_by,
+            decided_by_name: row.decided_by_name,
+            decided_at: row.decided_at,
+            steps: steps.iter().map(|loaded| loaded.step.clone()).collect(),
+            document_file_ids: row.document_file_ids,
+            submitted_at: row.submitted_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        },
+        steps,
+    })
+}
+
+/// Replaces the approval route; the first step becomes the one awaiting a
+/// decision and the rest wait for it.
+pub(super) async fn replace_steps(
+    transaction: &mut Transaction<'_, Postgres>,
+    leave_request_id: Uuid,
+    steps: &[PlannedApprovalStep],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM staff_leave_approval_steps WHERE leave_request_id = $1")
+        .bind(leave_request_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+    for (index, step) in steps.iter().enumerate() {
+        let status = if index == 0 {
+            StaffLeaveStepStatus::Pending
+        } else {
+            StaffLeaveStepStatus::Waiting
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO staff_leave_approval_steps (
+                leave_request_id, step_order, step_kind, organization_unit_id, status
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(leave_request_id)
+        .bind(index as i32 + 1)
+        .bind(step.step_kind.as_str())
+        .bind(step.organization_unit_id)
+        .bind(status.as_str())
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+/// Attaches the given documents and returns the previously attached files that
+/// are no longer listed, so the caller can hand them to File Platform deletion.
+pub(super) async fn replace_documents(
+    transaction: &mut Transaction<'_, Postgres>,
+    leave_request_id: Uuid,
+    file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let detached_file_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        DELETE FROM staff_leave_request_documents
+        WHERE leave_request_id = $1
+          AND NOT (file_id = ANY($2))
+        RETURNING file_id
+        "#,
+    )
+    .bind(leave_request_id)
+    .bind(file_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if !file_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO staff_leave_request_documents (leave_request_id, file_id, attached_by)
+            SELECT $1, file_id, $3
+            FROM UNNEST($2::uuid[]) AS file_id
+            ON CONFLICT (leave_request_id, file_id) DO NOTHING
+            "#,
+        )
+        .bind(leave_request_id)
+        .bind(file_ids)
+        .bind(attached_by)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+        sqlx::query(
+            "UPDATE files SET retention_class = 'standard', expires_at = NULL, updated_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(file_ids)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+    }
+
+    Ok(detached_file_ids)
+}
+
+pub(super) async fn log_action(
+    transaction: &mut Transaction<'_, Postgres>,
+    leave_request_id: Uuid,
+    actor_user_id: Uuid,
+    action_kind: &str,
+    step_kind: Option<StaffLeaveStepKind>,
+    note: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO staff_leave_request_actions (
+            leave_request_id, actor_user_id, action_kind, step_kind, note
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(leave_request_id)
+    .bind(actor_user_id)
+    .bind(action_kind)
+    .bind(step_kind.map(|kind| kind.as_str()))
+    .bind(note)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    Ok(())
+}
+
+pub(super) fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read staff leave request: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลใบลาได้".to_string())
+}
+
+pub(super) fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write staff leave request: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกใบลาได้".to_string())
+}
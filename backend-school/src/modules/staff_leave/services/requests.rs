@@ -0,0 +1,434 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff_leave::models::{
+    MyStaffLeaveFilter, StaffLeaveRequest, StaffLeaveStatus, SubmitStaffLeaveRequest,
+};
+use crate::policies::staff_leave_access_policy;
+
+use super::balances::{ensure_quota_available, restore_balance, school_today};
+use super::followups::{close_step_work_item, open_step_work_item, resolve_leader_unit};
+use super::records::{
+    load_request, log_action, replace_documents, replace_steps, requests_from_rows, write_error,
+    StaffLeaveMutationOutcome, StaffLeaveRequestRow, REQUEST_SELECT,
+};
+use super::shared::{
+    can_cancel, can_resubmit, count_leave_half_days, dedupe_ids, normalize_optional_text,
+    plan_approval_steps, required_text, validate_leave_range, MAX_DOCUMENT_FILES,
+};
+use super::types::{load_leave_type, StaffLeaveTypeRow};
+
+struct ValidatedLeaveInput {
+    leave_type: StaffLeaveTypeRow,
+    fiscal_year: i32,
+    requested_half_days: i32,
+    reason: String,
+    contact_during_leave: Option<String>,
+    document_file_ids: Vec<Uuid>,
+}
+
+pub async fn list_my_requests(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: MyStaffLeaveFilter,
+) -> Result<Vec<StaffLeaveRequest>, AppError> {
+    staff_leave_access_policy::require_staff_leave_own_read(actor)?;
+
+    let rows = sqlx::query_as::<_, StaffLeaveRequestRow>(&format!(
+        r#"
+        {REQUEST_SELECT}
+        WHERE r.user_id = $1
+          AND ($2::int IS NULL OR r.fiscal_year = $2)
+          AND ($3::text IS NULL OR r.status = $3)
+        ORDER BY r.start_date DESC, r.submitted_at DESC
+        LIMIT 200
+        "#
+    ))
+    .bind(actor.user_id)
+    .bind(filter.fiscal_year)
+    .bind(filter.status.map(|status| status.as_str()))
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to list own staff leave requests: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงรายการใบลาได้".to_string())
+    })?;
+
+    requests_from_rows(pool, rows).await
+}
+
+pub async fn submit_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: SubmitStaffLeaveRequest,
+) -> Result<StaffLeaveMutationOutcome, AppError> {
+    staff_leave_access_policy::require_staff_leave_request(actor)?;
+    let input = validate_leave_input(pool, actor.user_id, None, &payload).await?;
+    ensure_no_overlapping_request(pool, actor.user_id, None, &payload).await?;
+    let leader_unit_id = resolve_leader_unit(pool, actor.user_id).await?;
+    let steps = plan_approval_steps(leader_unit_id, input.leave_type.requires_school_approval);
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    if input.leave_type.annual_entitlement_half_days.is_some() {
+        ensure_quota_available(
+            &mut transaction,
+            actor.user_id,
+            input.leave_type.id,
+            input.fiscal_year,
+            input.requested_half_days,
+            None,
+        )
+        .await?;
+    }
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO staff_leave_requests (
+            user_id, leave_type_id, fiscal_year, start_date, end_date, day_portion,
+            requested_half_days, reason, contact_during_leave
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+    )
+    .bind(actor.user_id)
+    .bind(input.leave_type.id)
+    .bind(input.fiscal_year)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(payload.day_portion.as_str())
+    .bind(input.requested_half_days)
+    .bind(&input.reason)
+    .bind(&input.contact_during_leave)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    replace_steps(&mut transaction, id, &steps).await?;
+    replace_documents(
+        &mut transaction,
+        id,
+        &input.document_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    log_action(&mut transaction, id, actor.user_id, "submitted", None, None).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let request = load_request(pool, id).await?.request;
+    let work_item_id = open_step_work_item(pool, &request).await?;
+    Ok(StaffLeaveMutationOutcome {
+        request,
+        work_items_changed: work_item_id.is_some(),
+        detached_file_ids: Vec::new(),
+    })
+}
+
+/// Resubmission re-plans the approval route from the start because the
+/// requester's unit or the leave type may have changed since the first round.
+pub async fn resubmit_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: SubmitStaffLeaveRequest,
+) -> Result<StaffLeaveMutationOutcome, AppError> {
+    staff_leave_access_policy::require_staff_leave_request(actor)?;
+    let current = load_request(pool, id).await?.request;
+    ensure_requester(actor, &current)?;
+    if !can_resubmit(current.status) {
+        return Err(AppError::Conflict(
+            "แก้ไขได้เฉพาะใบลาที่ถูกส่งกลับให้แก้ไข".to_string(),
+        ));
+    }
+    let input = validate_leave_input(pool, actor.user_id, Some(id), &payload).await?;
+    ensure_no_overlapping_request(pool, actor.user_id, Some(id), &payload).await?;
+    let leader_unit_id = resolve_leader_unit(pool, actor.user_id).await?;
+    let steps = plan_approval_steps(leader_unit_id, input.leave_type.requires_school_approval);
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    if input.leave_type.annual_entitlement_half_days.is_some() {
+        ensure_quota_available(
+            &mut transaction,
+            actor.user_id,
+            input.leave_type.id,
+            input.fiscal_year,
+            input.requested_half_days,
+            Some(id),
+        )
+        .await?;
+    }
+    let updated = sqlx::query(
+        r#"
+        UPDATE staff_leave_requests
+        SET leave_type_id = $2,
+            fiscal_year = $3,
+            start_date = $4,
+            end_date = $5,
+            day_portion = $6,
+            requested_half_days = $7,
+            reason = $8,
+            contact_during_leave = $9,
+            status = 'pending',
+            decision_note = NULL,
+            decided_by = NULL,
+            decided_at = NULL,
+            submitted_at = NOW()
+        WHERE id = $1
+          AND status = 'returned'
+        "#,
+    )
+    .bind(id)
+    .bind(input.leave_type.id)
+    .bind(input.fiscal_year)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(payload.day_portion.as_str())
+    .bind(input.requested_half_days)
+    .bind(&input.reason)
+    .bind(&input.contact_during_leave)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+    replace_steps(&mut transaction, id, &steps).await?;
+    let detached_file_ids = replace_documents(
+        &mut transaction,
+        id,
+        &input.document_file_ids,
+        actor.user_id,
+    )
+    .await?;
+    log_action(
+        &mut transaction,
+        id,
+        actor.user_id,
+        "resubmitted",
+        None,
+        None,
+    )
+    .await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let request = load_request(pool, id).await?.request;
+    let work_item_id = open_step_work_item(pool, &request).await?;
+    Ok(StaffLeaveMutationOutcome {
+        request,
+        work_items_changed: work_item_id.is_some(),
+        detached_file_ids,
+    })
+}
+
+/// Cancelling approved leave before it starts gives the days back to the
+/// balance.
+pub async fn cancel_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<StaffLeaveMutationOutcome, AppError> {
+    staff_leave_access_policy::require_staff_leave_request(actor)?;
+    let current = load_request(pool, id).await?;
+    ensure_requester(actor, &current.request)?;
+    if !can_cancel(
+        current.request.status,
+        current.request.start_date,
+        school_today(),
+    ) {
+        return Err(AppError::Conflict(
+            "ยกเลิกได้เฉพาะใบลาที่ยังไม่สิ้นสุดการพิจารณา หรือใบลาที่อนุมัติแล้วแต่ยังไม่ถึงวันลา".to_string(),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE staff_leave_requests
+        SET status = 'cancelled'
+        WHERE id = $1
+          AND status = $2
+        "#,
+    )
+    .bind(id)
+    .bind(current.request.status.as_str())
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+    sqlx::query(
+        r#"
+        UPDATE staff_leave_approval_steps
+        SET status = 'cancelled'
+        WHERE leave_request_id = $1
+          AND status IN ('waiting', 'pending')
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if current.request.status == StaffLeaveStatus::Approved {
+        restore_balance(
+            &mut transaction,
+            current.request.user_id,
+            current.request.leave_type_id,
+            current.request.fiscal_year,
+            current.requested_half_days,
+        )
+        .await?;
+    }
+    log_action(&mut transaction, id, actor.user_id, "cancelled", None, None).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let work_items_changed = close_step_work_item(pool, current.current_work_item_id()).await?;
+    let request = load_request(pool, id).await?.request;
+    Ok(StaffLeaveMutationOutcome {
+        request,
+        work_items_changed,
+        detached_file_ids: Vec::new(),
+    })
+}
+
+fn ensure_requester(actor: &ActorContext, request: &StaffLeaveRequest) -> Result<(), AppError> {
+    if request.user_id == actor.user_id {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "แก้ไขหรือยกเลิกได้เฉพาะใบลาของตนเอง".to_string(),
+        ))
+    }
+}
+
+async fn validate_leave_input(
+    pool: &PgPool,
+    user_id: Uuid,
+    leave_request_id: Option<Uuid>,
+    payload: &SubmitStaffLeaveRequest,
+) -> Result<ValidatedLeaveInput, AppError> {
+    let reason = required_text(&payload.reason, "กรุณาระบุเหตุผลการลา")?;
+    let leave_type = load_leave_type(pool, payload.leave_type_id).await?;
+    if !leave_type.is_active {
+        return Err(AppError::ValidationError(
+            "ประเภทการลานี้ปิดใช้งานแล้ว".to_string(),
+        ));
+    }
+    let fiscal_year =
+        validate_leave_range(payload.start_date, payload.end_date, payload.day_portion)?;
+    let requested_half_days = count_leave_half_days(
+        payload.start_date,
+        payload.end_date,
+        payload.day_portion,
+        leave_type.counts_weekends,
+    )?;
+    let document_file_ids = dedupe_ids(payload.document_file_ids.clone());
+    if let Some(threshold_days) = leave_type.document_required_from_days {
+        if requested_half_days >= threshold_days * 2 && document_file_ids.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "{}ตั้งแต่ {threshold_days} วันขึ้นไปต้องแนบเอกสารประกอบ",
+                leave_type.name
+            )));
+        }
+    }
+    validate_document_files(pool, user_id, leave_request_id, &document_file_ids).await?;
+
+    Ok(ValidatedLeaveInput {
+        leave_type,
+        fiscal_year,
+        requested_half_days,
+        reason,
+        contact_during_leave: normalize_optional_text(payload.contact_during_leave.clone()),
+        document_file_ids,
+    })
+}
+
+/// Morning and afternoon leave on the same date do not overlap; any other
+/// combination of open or approved requests does.
+async fn ensure_no_overlapping_request(
+    pool: &PgPool,
+    user_id: Uuid,
+    excluding_id: Option<Uuid>,
+    payload: &SubmitStaffLeaveRequest,
+) -> Result<(), AppError> {
+    let overlaps = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM staff_leave_requests
+            WHERE user_id = $1
+              AND ($2::uuid IS NULL OR id <> $2)
+              AND status IN ('pending', 'returned', 'approved')
+              AND start_date <= $4
+              AND end_date >= $3
+              AND NOT (day_portion <> 'full' AND $5 <> 'full' AND day_portion <> $5)
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(excluding_id)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(payload.day_portion.as_str())
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check overlapping staff leave: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบใบลาเดิมได้".to_string())
+    })?;
+
+    if overlaps {
+        Err(AppError::Conflict("มีใบลาในช่วงวันที่นี้อยู่แล้ว".to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+async fn validate_document_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    leave_request_id: Option<Uuid>,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.len() > MAX_DOCUMENT_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบเอกสารได้ไม่เกิน {MAX_DOCUMENT_FILES} ไฟล์"
+        )));
+    }
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let usable = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        LEFT JOIN staff_leave_request_documents document ON document.file_id = files.id
+        WHERE files.id = ANY($1)
+          AND files.purpose_code = 'staff_leave_document'
+          AND files.lifecycle_status = 'ready'
+          AND files.deleted_at IS NULL
+          AND (
+              (document.file_id IS NULL AND files.owner_user_id = $2)
+              OR document.leave_request_id = $3
+          )
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .bind(leave_request_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate staff leave documents: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบเอกสารแนบได้".to_string())
+    })?;
+
+    if usable == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "เอกสารแนบไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
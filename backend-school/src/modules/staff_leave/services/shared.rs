@@ -0,0 +1,285 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::staff_leave::models::{
+    StaffLeaveDayPortion, StaffLeaveDecision, StaffLeaveStatus, StaffLeaveStepKind,
+    StaffLeaveStepStatus,
+};
+
+pub(super) const REQUEST_NOT_FOUND_MESSAGE: &str = "ไม่พบใบลา";
+pub(super) const LEAVE_TYPE_NOT_FOUND_MESSAGE: &str = "ไม่พบประเภทการลา";
+pub(super) const MAX_DOCUMENT_FILES: usize = 5;
+pub(super) const MAX_CALENDAR_DAYS: i64 = 62;
+const BUDDHIST_ERA_OFFSET: i32 = 543;
+const FISCAL_YEAR_START_MONTH: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedApprovalStep {
+    pub step_kind: StaffLeaveStepKind,
+    pub organization_unit_id: Option<Uuid>,
+}
+
+/// Thai government fiscal years run October to September and are numbered by
+/// the Buddhist Era year in which they end.
+pub fn fiscal_year_for(date: NaiveDate) -> i32 {
+    let closing_year = if date.month() >= FISCAL_YEAR_START_MONTH {
+        date.year() + 1
+    } else {
+        date.year()
+    };
+    closing_year + BUDDHIST_ERA_OFFSET
+}
+
+pub fn fiscal_year_bounds(fiscal_year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    let closing_year = fiscal_year - BUDDHIST_ERA_OFFSET;
+    Some((
+        NaiveDate::from_ymd_opt(closing_year - 1, FISCAL_YEAR_START_MONTH, 1)?,
+        NaiveDate::from_ymd_opt(closing_year, 9, 30)?,
+    ))
+}
+
+pub fn days_to_half_days(days: f64, message: &str) -> Result<i32, AppError> {
+    let half_days = days * 2.0;
+    if !half_days.is_finite() || half_days.fract() != 0.0 || half_days.abs() > 100_000.0 {
+        return Err(AppError::ValidationError(message.to_string()));
+    }
+    Ok(half_days as i32)
+}
+
+pub fn half_days_to_days(half_days: i32) -> f64 {
+    f64::from(half_days) / 2.0
+}
+
+/// Half-day portions are always a single date worth one unit; full-day leave
+/// counts two units per day, skipping weekends unless the type counts them
+/// (Thai regulations count calendar days for some long leave types).
+pub fn count_leave_half_days(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    day_portion: StaffLeaveDayPortion,
+    counts_weekends: bool,
+) -> Result<i32, AppError> {
+    if day_portion != StaffLeaveDayPortion::Full {
+        return if is_counted_day(start_date, counts_weekends) {
+            Ok(1)
+        } else {
+            Err(AppError::ValidationError(
+                "วันที่ลาครึ่งวันตรงกับวันหยุด".to_string(),
+            ))
+        };
+    }
+
+    let days = start_date
+        .iter_days()
+        .take_while(|date| *date <= end_date)
+        .filter(|date| is_counted_day(*date, counts_weekends))
+        .count() as i32;
+    if days == 0 {
+        return Err(AppError::ValidationError("ช่วงวันที่ลาไม่มีวันทำการ".to_string()));
+    }
+    Ok(days * 2)
+}
+
+fn is_counted_day(date: NaiveDate, counts_weekends: bool) -> bool {
+    counts_weekends || !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+pub fn validate_leave_range(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    day_portion: StaffLeaveDayPortion,
+) -> Result<i32, AppError> {
+    if end_date < start_date {
+        return Err(AppError::ValidationError(
+            "วันสิ้นสุดการลาต้องไม่ก่อนวันเริ่มลา".to_string(),
+        ));
+    }
+    if day_portion != StaffLeaveDayPortion::Full && start_date != end_date {
+        return Err(AppError::ValidationError(
+            "การลาครึ่งวันต้องเป็นวันเดียว".to_string(),
+        ));
+    }
+    let fiscal_year = fiscal_year_for(start_date);
+    if fiscal_year_for(end_date) != fiscal_year {
+        return Err(AppError::ValidationError(
+            "ช่วงวันที่ลาคร่อมปีงบประมาณ กรุณาแยกใบลาตามปีงบประมาณ".to_string(),
+        ));
+    }
+    Ok(fiscal_year)
+}
+
+/// Unused days move to the next fiscal year up to the type's carry-over limit,
+/// and never push the yearly total past the accumulation ceiling.
+pub fn carry_over_half_days(
+    prior_remaining_half_days: i32,
+    max_carry_over_half_days: i32,
+    annual_entitlement_half_days: Option<i32>,
+    max_accumulated_half_days: Option<i32>,
+) -> i32 {
+    let Some(entitlement) = annual_entitlement_half_days else {
+        return 0;
+    };
+    let carried = prior_remaining_half_days.clamp(0, max_carry_over_half_days.max(0));
+    match max_accumulated_half_days {
+        Some(ceiling) => carried.min((ceiling - entitlement).max(0)),
+        None => carried,
+    }
+}
+
+pub fn remaining_half_days(
+    entitled_half_days: i32,
+    carried_over_half_days: i32,
+    adjustment_half_days: i32,
+    used_half_days: i32,
+    pending_half_days: i32,
+) -> i32 {
+    entitled_half_days + carried_over_half_days + adjustment_half_days
+        - used_half_days
+        - pending_half_days
+}
+
+/// The requester's unit leader decides first when one exists; the school step
+/// follows when the leave type needs it, and is always present when no leader
+/// could be found so every request has someone to decide it.
+pub fn plan_approval_steps(
+    leader_unit_id: Option<Uuid>,
+    requires_school_approval: bool,
+) -> Vec<PlannedApprovalStep> {
+    let mut steps = Vec::with_capacity(2);
+    if let Some(organization_unit_id) = leader_unit_id {
+        steps.push(PlannedApprovalStep {
+            step_kind: StaffLeaveStepKind::UnitLeader,
+            organization_unit_id: Some(organization_unit_id),
+        });
+    }
+    if requires_school_approval || steps.is_empty() {
+        steps.push(PlannedApprovalStep {
+            step_kind: StaffLeaveStepKind::School,
+            organization_unit_id: None,
+        });
+    }
+    steps
+}
+
+pub fn can_resubmit(status: StaffLeaveStatus) -> bool {
+    status == StaffLeaveStatus::Returned
+}
+
+/// Open requests can always be withdrawn; approved leave only until it starts,
+/// after which the days have been taken.
+pub fn can_cancel(status: StaffLeaveStatus, start_date: NaiveDate, today: NaiveDate) -> bool {
+    match status {
+        StaffLeaveStatus::Pending | StaffLeaveStatus::Returned => true,
+        StaffLeaveStatus::Approved => start_date > today,
+        StaffLeaveStatus::Rejected | StaffLeaveStatus::Cancelled => false,
+    }
+}
+
+pub fn decision_requires_note(decision: StaffLeaveDecision) -> bool {
+    !matches!(decision, StaffLeaveDecision::Approve)
+}
+
+pub fn step_status_for(decision: StaffLeaveDecision) -> StaffLeaveStepStatus {
+    match decision {
+        StaffLeaveDecision::Approve => StaffLeaveStepStatus::Approved,
+        StaffLeaveDecision::Return => StaffLeaveStepStatus::Returned,
+        StaffLeaveDecision::Reject => StaffLeaveStepStatus::Rejected,
+    }
+}
+
+/// Half-day leave is split at noon: morning leave covers anything starting
+/// before noon, afternoon leave anything running past it.
+pub fn portion_covers_window(
+    day_portion: StaffLeaveDayPortion,
+    start_time: NaiveTime,
+    end_time: NaiveTime,
+) -> bool {
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap_or(NaiveTime::MIN);
+    match day_portion {
+        StaffLeaveDayPortion::Full => true,
+        StaffLeaveDayPortion::Morning => start_time < noon,
+        StaffLeaveDayPortion::Afternoon => end_time > noon || start_time >= noon,
+    }
+}
+
+pub fn format_leave_range(start_date: NaiveDate, end_date: NaiveDate) -> String {
+    if start_date == end_date {
+        start_date.format("%d/%m/%Y").to_string()
+    } else {
+        format!(
+            "{} - {}",
+            start_date.format("%d/%m/%Y"),
+            end_date.format("%d/%m/%Y")
+        )
+    }
+}
+
+pub fn decision_notification_text(
+    status: StaffLeaveStatus,
+    leave_type_name: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Option<(String, String)> {
+    let range = format_leave_range(start_date, end_date);
+    match status {
+        StaffLeaveStatus::Approved => Some((
+            "ใบลาได้รับการอนุมัติ".to_string(),
+            format!("{leave_type_name} วันที่ {range} ได้รับการอนุมัติแล้ว"),
+        )),
+        StaffLeaveStatus::Returned => Some((
+            "ใบลาถูกส่งกลับให้แก้ไข".to_string(),
+            format!("{leave_type_name} วันที่ {range} ถูกส่งกลับให้แก้ไข"),
+        )),
+        StaffLeaveStatus::Rejected => Some((
+            "ใบลาไม่ได้รับการอนุมัติ".to_string(),
+            format!("{leave_type_name} วันที่ {range} ไม่ได้รับการอนุมัติ"),
+        )),
+        StaffLeaveStatus::Pending | StaffLeaveStatus::Cancelled => None,
+    }
+}
+
+pub(super) fn dedupe_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut unique = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    unique
+}
+
+pub(super) fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn required_text(value: &str, message: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(AppError::ValidationError(message.to_string()))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+pub(super) fn parse_day_portion(code: &str) -> Result<StaffLeaveDayPortion, AppError> {
+    StaffLeaveDayPortion::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("ช่วงเวลาการลาในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+pub(super) fn parse_status(code: &str) -> Result<StaffLeaveStatus, AppError> {
+    StaffLeaveStatus::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("สถานะใบลาในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+pub(super) fn parse_step_kind(code: &str) -> Result<StaffLeaveStepKind, AppError> {
+    StaffLeaveStepKind::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("ขั้นตอนอนุมัติในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+pub(super) fn parse_step_status(code: &str) -> Result<StaffLeaveStepStatus, AppError> {
+    StaffLeaveStepStatus::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("สถานะขั้นตอนอนุมัติในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
@@ -0,0 +1,277 @@
+use super::*;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+#[test]
+fn fiscal_year_runs_october_to_september_in_buddhist_era() {
+    assert_eq!(fiscal_year_for(date(2025, 10, 1)), 2569);
+    assert_eq!(fiscal_year_for(date(2026, 9, 30)), 2569);
+    assert_eq!(fiscal_year_for(date(2026, 10, 1)), 2570);
+    assert_eq!(
+        fiscal_year_bounds(2569),
+        Some((date(2025, 10, 1), date(2026, 9, 30)))
+    );
+}
+
+#[test]
+fn leave_range_must_stay_inside_one_fiscal_year() {
+    assert_eq!(
+        validate_leave_range(
+            date(2026, 9, 28),
+            date(2026, 9, 30),
+            StaffLeaveDayPortion::Full
+        )
+        .unwrap(),
+        2569
+    );
+    assert!(matches!(
+        validate_leave_range(
+            date(2026, 9, 30),
+            date(2026, 10, 1),
+            StaffLeaveDayPortion::Full
+        ),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        validate_leave_range(
+            date(2026, 6, 2),
+            date(2026, 6, 1),
+            StaffLeaveDayPortion::Full
+        ),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn half_day_leave_is_a_single_date() {
+    assert!(validate_leave_range(
+        date(2026, 6, 1),
+        date(2026, 6, 1),
+        StaffLeaveDayPortion::Morning
+    )
+    .is_ok());
+    assert!(matches!(
+        validate_leave_range(
+            date(2026, 6, 1),
+            date(2026, 6, 2),
+            StaffLeaveDayPortion::Afternoon
+        ),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn leave_days_skip_weekends_unless_the_type_counts_them() {
+    // Friday 5 June to Monday 8 June 2026.
+    assert_eq!(
+        count_leave_half_days(
+            date(2026, 6, 5),
+            date(2026, 6, 8),
+            StaffLeaveDayPortion::Full,
+            false
+        )
+        .unwrap(),
+        4
+    );
+    assert_eq!(
+        count_leave_half_days(
+            date(2026, 6, 5),
+            date(2026, 6, 8),
+            StaffLeaveDayPortion::Full,
+            true
+        )
+        .unwrap(),
+        8
+    );
+    assert_eq!(
+        count_leave_half_days(
+            date(2026, 6, 8),
+            date(2026, 6, 8),
+            StaffLeaveDayPortion::Morning,
+            false
+        )
+        .unwrap(),
+        1
+    );
+    assert!(matches!(
+        count_leave_half_days(
+            date(2026, 6, 6),
+            date(2026, 6, 7),
+            StaffLeaveDayPortion::Full,
+            false
+        ),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn day_amounts_convert_in_half_day_steps() {
+    assert_eq!(days_to_half_days(1.5, "invalid").unwrap(), 3);
+    assert_eq!(days_to_half_days(-2.0, "invalid").unwrap(), -4);
+    assert!(days_to_half_days(1.25, "invalid").is_err());
+    assert!(days_to_half_days(f64::NAN, "invalid").is_err());
+    assert_eq!(half_days_to_days(21), 10.5);
+}
+
+#[test]
+fn carry_over_respects_limit_and_accumulation_ceiling() {
+    // Vacation: 10 days a year, up to 10 carried, at most 20 in total.
+    assert_eq!(carry_over_half_days(14, 20, Some(20), Some(40)), 14);
+    assert_eq!(carry_over_half_days(30, 20, Some(20), Some(40)), 20);
+    assert_eq!(carry_over_half_days(30, 20, Some(20), Some(30)), 10);
+    assert_eq!(carry_over_half_days(-4, 20, Some(20), Some(40)), 0);
+    assert_eq!(carry_over_half_days(30, 0, Some(120), None), 0);
+    assert_eq!(carry_over_half_days(30, 20, None, None), 0);
+}
+
+#[test]
+fn remaining_days_subtract_used_and_pending() {
+    assert_eq!(remaining_half_days(20, 10, 0, 6, 4), 20);
+    assert_eq!(remaining_half_days(20, 0, -4, 16, 2), -2);
+}
+
+#[test]
+fn approval_route_starts_with_the_unit_leader() {
+    let unit_id = Uuid::new_v4();
+
+    assert_eq!(
+        plan_approval_steps(Some(unit_id), true),
+        vec![
+            PlannedApprovalStep {
+                step_kind: StaffLeaveStepKind::UnitLeader,
+                organization_unit_id: Some(unit_id),
+            },
+            PlannedApprovalStep {
+                step_kind: StaffLeaveStepKind::School,
+                organization_unit_id: None,
+            },
+        ]
+    );
+    assert_eq!(plan_approval_steps(Some(unit_id), false).len(), 1);
+}
+
+#[test]
+fn approval_route_falls_back_to_the_school_without_a_leader() {
+    let steps = plan_approval_steps(None, false);
+
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].step_kind, StaffLeaveStepKind::School);
+}
+
+#[test]
+fn approved_leave_can_be_cancelled_only_before_it_starts() {
+    let today = date(2026, 6, 10);
+
+    assert!(can_cancel(
+        StaffLeaveStatus::Pending,
+        date(2026, 6, 1),
+        today
+    ));
+    assert!(can_cancel(
+        StaffLeaveStatus::Returned,
+        date(2026, 6, 1),
+        today
+    ));
+    assert!(can_cancel(
+        StaffLeaveStatus::Approved,
+        date(2026, 6, 11),
+        today
+    ));
+    assert!(!can_cancel(
+        StaffLeaveStatus::Approved,
+        date(2026, 6, 10),
+        today
+    ));
+    assert!(!can_cancel(
+        StaffLeaveStatus::Rejected,
+        date(2026, 6, 11),
+        today
+    ));
+    assert!(can_resubmit(StaffLeaveStatus::Returned));
+    assert!(!can_resubmit(StaffLeaveStatus::Pending));
+}
+
+#[test]
+fn return_and_reject_need_a_note() {
+    assert!(!decision_requires_note(StaffLeaveDecision::Approve));
+    assert!(decision_requires_note(StaffLeaveDecision::Return));
+    assert!(decision_requires_note(StaffLeaveDecision::Reject));
+    assert_eq!(
+        step_status_for(StaffLeaveDecision::Return),
+        StaffLeaveStepStatus::Returned
+    );
+}
+
+#[test]
+fn half_day_leave_covers_windows_on_its_side_of_noon() {
+    let morning_exam = (time(9, 0), time(11, 0));
+    let afternoon_exam = (time(13, 0), time(15, 0));
+
+    assert!(portion_covers_window(
+        StaffLeaveDayPortion::Full,
+        afternoon_exam.0,
+        afternoon_exam.1
+    ));
+    assert!(portion_covers_window(
+        StaffLeaveDayPortion::Morning,
+        morning_exam.0,
+        morning_exam.1
+    ));
+    assert!(!portion_covers_window(
+        StaffLeaveDayPortion::Morning,
+        afternoon_exam.0,
+        afternoon_exam.1
+    ));
+    assert!(portion_covers_window(
+        StaffLeaveDayPortion::Afternoon,
+        afternoon_exam.0,
+        afternoon_exam.1
+    ));
+    assert!(!portion_covers_window(
+        StaffLeaveDayPortion::Afternoon,
+        morning_exam.0,
+        morning_exam.1
+    ));
+    assert!(portion_covers_window(
+        StaffLeaveDayPortion::Afternoon,
+        time(11, 0),
+        time(12, 30)
+    ));
+}
+
+#[test]
+fn only_final_outcomes_notify_the_requester() {
+    assert!(decision_notification_text(
+        StaffLeaveStatus::Approved,
+        "ลาป่วย",
+        date(2026, 6, 1),
+        date(2026, 6, 2)
+    )
+    .is_some());
+    assert!(decision_notification_text(
+        StaffLeaveStatus::Pending,
+        "ลาป่วย",
+        date(2026, 6, 1),
+        date(2026, 6, 2)
+    )
+    .is_none());
+    assert_eq!(
+        format_leave_range(date(2026, 6, 1), date(2026, 6, 1)),
+        "01/06/2026"
+    );
+}
+
+#[test]
+fn leave_type_codes_are_lowercase_identifiers() {
+    assert!(is_valid_type_code("maternity"));
+    assert!(is_valid_type_code("ordination_1"));
+    assert!(!is_valid_type_code("1sick"));
+    assert!(!is_valid_type_code("Sick"));
+    assert!(!is_valid_type_code(""));
+}
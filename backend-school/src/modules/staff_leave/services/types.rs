@@ -0,0 +1,290 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff_leave::models::{
+    CreateStaffLeaveTypeRequest, StaffLeaveType, StaffLeaveTypeFilter, UpdateStaffLeaveTypeRequest,
+};
+use crate::policies::staff_leave_access_policy;
+
+use super::shared::{
+    days_to_half_days, half_days_to_days, normalize_optional_text, required_text,
+    LEAVE_TYPE_NOT_FOUND_MESSAGE,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StaffLeaveTypeRow {
+    pub(super) id: Uuid,
+    pub(super) code: String,
+    pub(super) name: String,
+    pub(super) description: Option<String>,
+    pub(super) annual_entitlement_half_days: Option<i32>,
+    pub(super) max_carry_over_half_days: i32,
+    pub(super) max_accumulated_half_days: Option<i32>,
+    pub(super) counts_weekends: bool,
+    pub(super) requires_school_approval: bool,
+    pub(super) document_required_from_days: Option<i32>,
+    pub(super) is_active: bool,
+    pub(super) sort_order: i32,
+    pub(super) created_at: DateTime<Utc>,
+    pub(super) updated_at: DateTime<Utc>,
+}
+
+const TYPE_SELECT: &str = r#"
+    SELECT id, code, name, description, annual_entitlement_half_days,
+           max_carry_over_half_days, max_accumulated_half_days, counts_weekends,
+           requires_school_approval, document_required_from_days, is_active,
+           sort_order, created_at, updated_at
+    FROM staff_leave_types
+"#;
+
+/// Validated leave type settings in storage units.
+struct LeaveTypeSettings {
+    name: String,
+    description: Option<String>,
+    annual_entitlement_half_days: Option<i32>,
+    max_carry_over_half_days: i32,
+    max_accumulated_half_days: Option<i32>,
+    document_required_from_days: Option<i32>,
+}
+
+/// Every staff member may see the active types so the request form can list
+/// them; inactive types are only shown to leave administrators.
+pub async fn list_leave_types(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: StaffLeaveTypeFilter,
+) -> Result<Vec<StaffLeaveType>, AppError> {
+    let include_inactive = filter.include_inactive;
+    if include_inactive {
+        staff_leave_access_policy::require_staff_leave_manage(actor)?;
+    } else {
+        staff_leave_access_policy::require_staff_leave_own_read(actor)
+            .or_else(|_| staff_leave_access_policy::require_staff_leave_school_read(actor))?;
+    }
+
+    let rows = sqlx::query_as::<_, StaffLeaveTypeRow>(&format!(
+        "{TYPE_SELECT} WHERE ($1 OR is_active = true) ORDER BY sort_order, name"
+    ))
+    .bind(include_inactive)
+    .fetch_all(pool)
+    .await
+    .map_err(type_read_error)?;
+
+    Ok(rows.into_iter().map(leave_type_from_row).collect())
+}
+
+pub async fn create_leave_type(
+    pool: &PgPool,
+    actor: &ActorContext,
+    payload: CreateStaffLeaveTypeRequest,
+) -> Result<StaffLeaveType, AppError> {
+    staff_leave_access_policy::require_staff_leave_manage(actor)?;
+    let code = payload.code.trim().to_lowercase();
+    if !is_valid_type_code(&code) {
+        return Err(AppError::ValidationError(
+            "รหัสประเภทการลาต้องเป็นภาษาอังกฤษตัวเล็ก ตัวเลข หรือ _ และขึ้นต้นด้วยตัวอักษร".to_string(),
+        ));
+    }
+    let settings = validate_settings(
+        &payload.name,
+        payload.description,
+        payload.annual_entitlement_days,
+        payload.max_carry_over_days,
+        payload.max_accumulated_days,
+        payload.document_required_from_days,
+    )?;
+
+    let row = sqlx::query_as::<_, StaffLeaveTypeRow>(
+        r#"
+        INSERT INTO staff_leave_types (
+            code, name, description, annual_entitlement_half_days,
+            max_carry_over_half_days, max_accumulated_half_days, counts_weekends,
+            requires_school_approval, document_required_from_days, sort_order
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (code) DO NOTHING
+        RETURNING id, code, name, description, annual_entitlement_half_days,
+                  max_carry_over_half_days, max_accumulated_half_days, counts_weekends,
+                  requires_school_approval, document_required_from_days, is_active,
+                  sort_order, created_at, updated_at
+        "#,
+    )
+    .bind(&code)
+    .bind(&settings.name)
+    .bind(&settings.description)
+    .bind(settings.annual_entitlement_half_days)
+    .bind(settings.max_carry_over_half_days)
+    .bind(settings.max_accumulated_half_days)
+    .bind(payload.counts_weekends)
+    .bind(payload.requires_school_approval)
+    .bind(settings.document_required_from_days)
+    .bind(payload.sort_order)
+    .fetch_optional(pool)
+    .await
+    .map_err(type_write_error)?
+    .ok_or_else(|| AppError::Conflict("รหัสประเภทการลานี้มีอยู่แล้ว".to_string()))?;
+
+    Ok(leave_type_from_row(row))
+}
+
+/// Changing the entitlement affects balances opened afterwards; balances that
+/// already exist keep their figures and are corrected through adjustments.
+pub async fn update_leave_type(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: UpdateStaffLeaveTypeRequest,
+) -> Result<StaffLeaveType, AppError> {
+    staff_leave_access_policy::require_staff_leave_manage(actor)?;
+    let settings = validate_settings(
+        &payload.name,
+        payload.description,
+        payload.annual_entitlement_days,
+        payload.max_carry_over_days,
+        payload.max_accumulated_days,
+        payload.document_required_from_days,
+    )?;
+
+    let row = sqlx::query_as::<_, StaffLeaveTypeRow>(
+        r#"
+        UPDATE staff_leave_types
+        SET name = $2,
+            description = $3,
+            annual_entitlement_half_days = $4,
+            max_carry_over_half_days = $5,
+            max_accumulated_half_days = $6,
+            counts_weekends = $7,
+            requires_school_approval = $8,
+            document_required_from_days = $9,
+            is_active = $10,
+            sort_order = $11
+        WHERE id = $1
+        RETURNING id, code, name, description, annual_entitlement_half_days,
+                  max_carry_over_half_days, max_accumulated_half_days, counts_weekends,
+                  requires_school_approval, document_required_from_days, is_active,
+                  sort_order, created_at, updated_at
+        "#,
+    )
+    .bind(id)
+    .bind(&settings.name)
+    .bind(&settings.description)
+    .bind(settings.annual_entitlement_half_days)
+    .bind(settings.max_carry_over_half_days)
+    .bind(settings.max_accumulated_half_days)
+    .bind(payload.counts_weekends)
+    .bind(payload.requires_school_approval)
+    .bind(settings.document_required_from_days)
+    .bind(payload.is_active)
+    .bind(payload.sort_order)
+    .fetch_optional(pool)
+    .await
+    .map_err(type_write_error)?
+    .ok_or_else(|| AppError::NotFound(LEAVE_TYPE_NOT_FOUND_MESSAGE.to_string()))?;
+
+    Ok(leave_type_from_row(row))
+}
+
+pub(super) async fn load_leave_type(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<StaffLeaveTypeRow, AppError> {
+    sqlx::query_as::<_, StaffLeaveTypeRow>(&format!("{TYPE_SELECT} WHERE id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(type_read_error)?
+        .ok_or_else(|| AppError::NotFound(LEAVE_TYPE_NOT_FOUND_MESSAGE.to_string()))
+}
+
+pub fn is_valid_type_code(code: &str) -> bool {
+    let mut chars = code.chars();
+    matches!(chars.next(), Some(first) if first.is_ascii_lowercase())
+        && code.len() <= 50
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn validate_settings(
+    name: &str,
+    description: Option<String>,
+    annual_entitlement_days: Option<f64>,
+    max_carry_over_days: f64,
+    max_accumulated_days: Option<f64>,
+    document_required_from_days: Option<i32>,
+) -> Result<LeaveTypeSettings, AppError> {
+    const AMOUNT_MESSAGE: &str = "จำนวนวันลาต้องเป็นจำนวนเต็มหรือครึ่งวัน";
+
+    let name = required_text(name, "กรุณาระบุชื่อประเภทการลา")?;
+    let annual_entitlement_half_days = annual_entitlement_days
+        .map(|days| days_to_half_days(days, AMOUNT_MESSAGE))
+        .transpose()?;
+    let max_carry_over_half_days = days_to_half_days(max_carry_over_days, AMOUNT_MESSAGE)?;
+    let max_accumulated_half_days = max_accumulated_days
+        .map(|days| days_to_half_days(days, AMOUNT_MESSAGE))
+        .transpose()?;
+
+    if annual_entitlement_half_days.is_some_and(|value| value < 0) || max_carry_over_half_days < 0 {
+        return Err(AppError::ValidationError("จำนวนวันลาต้องไม่ติดลบ".to_string()));
+    }
+    if annual_entitlement_half_days.is_none()
+        && (max_carry_over_half_days > 0 || max_accumulated_half_days.is_some())
+    {
+        return Err(AppError::ValidationError(
+            "ประเภทการลาที่ไม่จำกัดวันลาไม่สามารถสะสมวันลาได้".to_string(),
+        ));
+    }
+    if let (Some(entitlement), Some(ceiling)) =
+        (annual_entitlement_half_days, max_accumulated_half_days)
+    {
+        if ceiling < entitlement {
+            return Err(AppError::ValidationError(
+                "วันลาสะสมสูงสุดต้องไม่น้อยกว่าวันลาประจำปี".to_string(),
+            ));
+        }
+    }
+    if document_required_from_days.is_some_and(|days| days <= 0) {
+        return Err(AppError::ValidationError(
+            "จำนวนวันที่ต้องแนบเอกสารต้องมากกว่า 0".to_string(),
+        ));
+    }
+
+    Ok(LeaveTypeSettings {
+        name,
+        description: normalize_optional_text(description),
+        annual_entitlement_half_days,
+        max_carry_over_half_days,
+        max_accumulated_half_days,
+        document_required_from_days,
+    })
+}
+
+pub(super) fn leave_type_from_row(row: StaffLeaveTypeRow) -> StaffLeaveType {
+    StaffLeaveType {
+        id: row.id,
+        code: row.code,
+        name: row.name,
+        description: row.description,
+        annual_entitlement_days: row.annual_entitlement_half_days.map(half_days_to_days),
+        max_carry_over_days: half_days_to_days(row.max_carry_over_half_days),
+        max_accumulated_days: row.max_accumulated_half_days.map(half_days_to_days),
+        counts_weekends: row.counts_weekends,
+        requires_school_approval: row.requires_school_approval,
+        document_required_from_days: row.document_required_from_days,
+        is_active: row.is_active,
+        sort_order: row.sort_order,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+fn type_read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read staff leave types: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงประเภทการลาได้".to_string())
+}
+
+fn type_write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write staff leave type: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกประเภทการลาได้".to_string())
+}
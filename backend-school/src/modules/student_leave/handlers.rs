@@ -0,0 +1,193 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::AuthenticatedSession;
+use crate::modules::files::consumer_service::request_deletions;
+use crate::modules::student_leave::models::{
+    GuardianStudentLeaveFilter, ResubmitStudentLeaveRequest, ReviewStudentLeaveRequest,
+    StudentLeaveFilter, SubmitStudentLeaveRequest,
+};
+use crate::modules::student_leave::services::{self, StudentLeaveMutationOutcome};
+use crate::utils::request_context::{actor_tenant_context_from_session, ActorTenantContext};
+use crate::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemsData<T> {
+    items: Vec<T>,
+}
+
+/// Applies the side effects every leave mutation shares: File Platform deletion
+/// for dropped documents and the work inbox refresh signal.
+async fn finish_mutation(
+    state: &AppState,
+    context: &ActorTenantContext,
+    outcome: &mut StudentLeaveMutationOutcome,
+) -> Result<(), AppError> {
+    request_deletions(
+        state.file_platform.as_ref(),
+        &context.tenant.pool,
+        std::mem::take(&mut outcome.detached_file_ids),
+    )
+    .await?;
+    if outcome.work_items_changed {
+        state.notify_work_items_changed(&context.tenant.subdomain);
+    }
+    Ok(())
+}
+
+async fn list_requests(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<StudentLeaveFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items = services::list_requests(&context.tenant.pool, &context.actor, filter).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+async fn get_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let request = services::get_request(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(request)))
+}
+
+async fn review_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReviewStudentLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let decision = payload.decision;
+    let mut outcome =
+        services::review_request(&context.tenant.pool, &context.actor, id, payload).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+
+    if let Err(error) = services::notify_guardians_of_decision(
+        &context.tenant.pool,
+        &state.notification_channel,
+        &context.tenant.subdomain,
+        &outcome.request,
+        decision,
+    )
+    .await
+    {
+        tracing::error!(
+            leave_request_id = %outcome.request.id,
+            error = %error,
+            "Failed to notify guardians about student leave decision"
+        );
+    }
+
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+/// GET /api/student-leave/requests/:id/excused-occurrences - คาบที่ได้รับการยกเว้นจากใบลาที่อนุมัติ
+async fn list_excused_occurrences(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items =
+        services::list_excused_occurrences(&context.tenant.pool, &context.actor, id).await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// GET /api/student-leave/guardian/requests - ผู้ปกครองดูใบลาของบุตรหลาน
+async fn list_guardian_requests(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<GuardianStudentLeaveFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let items =
+        services::list_guardian_requests(&context.tenant.pool, context.actor.user_id, filter)
+            .await?;
+    Ok(Json(ApiResponse::ok(ItemsData { items })))
+}
+
+/// POST /api/student-leave/guardian/requests - ผู้ปกครองยื่นใบลาให้บุตรหลาน
+async fn submit_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Json(payload): Json<SubmitStudentLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::submit_request(&context.tenant.pool, context.actor.user_id, payload).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(outcome.request))))
+}
+
+async fn get_guardian_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let request =
+        services::get_guardian_request(&context.tenant.pool, context.actor.user_id, id).await?;
+    Ok(Json(ApiResponse::ok(request)))
+}
+
+/// PUT /api/student-leave/guardian/requests/:id - แก้ไขและส่งใบลาที่ถูกส่งกลับอีกครั้ง
+async fn resubmit_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ResubmitStudentLeaveRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::resubmit_request(&context.tenant.pool, context.actor.user_id, id, payload)
+            .await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+async fn cancel_request(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let mut outcome =
+        services::cancel_request(&context.tenant.pool, context.actor.user_id, id).await?;
+    finish_mutation(&state, &context, &mut outcome).await?;
+    Ok(Json(ApiResponse::ok(outcome.request)))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/requests", get(list_requests))
+        .route("/requests/{id}", get(get_request))
+        .route("/requests/{id}/review", post(review_request))
+        .route(
+            "/requests/{id}/excused-occurrences",
+            get(list_excused_occurrences),
+        )
+        .route(
+            "/guardian/requests",
+            get(list_guardian_requests).post(submit_request),
+        )
+        .route(
+            "/guardian/requests/{id}",
+            get(get_guardian_request).put(resubmit_request),
+        )
+        .route("/guardian/requests/{id}/cancel", post(cancel_request))
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudentLeaveType {
+    Sick,
+    Personal,
+    Other,
+}
+
+impl StudentLeaveType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sick => "sick",
+            Self::Personal => "personal",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "sick" => Some(Self::Sick),
+            "personal" => Some(Self::Personal),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sick => "ลาป่วย",
+            Self::Personal => "ลากิจ",
+            Self::Other => "ลาอื่น ๆ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudentLeaveStatus {
+    Pending,
+    Returned,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+impl StudentLeaveStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Returned => "returned",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "pending" => Some(Self::Pending),
+            "returned" => Some(Self::Returned),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudentLeaveDecision {
+    Approve,
+    Return,
+    Reject,
+}
+
+impl StudentLeaveDecision {
+    pub fn resulting_status(self) -> StudentLeaveStatus {
+        match self {
+            Self::Approve => StudentLeaveStatus::Approved,
+            Self::Return => StudentLeaveStatus::Returned,
+            Self::Reject => StudentLeaveStatus::Rejected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentLeaveRequest {
+    pub id: Uuid,
+    pub student_id: Uuid,
+    pub student_name: String,
+    pub requested_by: Uuid,
+    pub requested_by_name: String,
+    pub academic_semester_id: Uuid,
+    pub leave_type: StudentLeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub period_ids: Vec<Uuid>,
+    pub reason: String,
+    pub status: StudentLeaveStatus,
+    pub review_note: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_by_name: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub document_file_ids: Vec<Uuid>,
+    pub excused_occurrence_count: i64,
+    pub submitted_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitStudentLeaveRequest {
+    pub student_id: Uuid,
+    pub leave_type: StudentLeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default)]
+    pub period_ids: Vec<Uuid>,
+    pub reason: String,
+    #[serde(default)]
+    pub document_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResubmitStudentLeaveRequest {
+    pub leave_type: StudentLeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[serde(default)]
+    pub period_ids: Vec<Uuid>,
+    pub reason: String,
+    #[serde(default)]
+    pub document_file_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewStudentLeaveRequest {
+    pub decision: StudentLeaveDecision,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentLeaveFilter {
+    pub student_id: Option<Uuid>,
+    pub class_room_id: Option<Uuid>,
+    pub status: Option<StudentLeaveStatus>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuardianStudentLeaveFilter {
+    pub student_id: Option<Uuid>,
+    pub status: Option<StudentLeaveStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentLeaveExcusedOccurrence {
+    pub leave_date: NaiveDate,
+    pub timetable_entry_id: Uuid,
+    pub period_id: Uuid,
+    pub period_name: Option<String>,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub title: Option<String>,
+}
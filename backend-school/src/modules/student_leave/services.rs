@@ -0,0 +1,33 @@
+mod followups;
+mod guardian;
+mod notifications;
+mod records;
+mod reviews;
+mod shared;
+
+#[cfg(test)]
+mod tests;
+
+pub use guardian::{
+    cancel_request, get_guardian_request, list_guardian_requests, resubmit_request, submit_request,
+};
+pub use notifications::notify_guardians_of_decision;
+pub use records::StudentLeaveMutationOutcome;
+pub use reviews::{get_request, list_excused_occurrences, list_requests, review_request};
+#[allow(unused_imports)]
+pub use shared::{
+    can_guardian_cancel, can_guardian_resubmit, can_review, decision_notification_text, dedupe_ids,
+    format_leave_range, review_requires_note, validate_leave_range,
+};
+
+#[cfg(test)]
+use chrono::NaiveDate;
+#[cfg(test)]
+use uuid::Uuid;
+
+#[cfg(test)]
+use crate::error::AppError;
+#[cfg(test)]
+use crate::modules::student_leave::models::{
+    StudentLeaveDecision, StudentLeaveStatus, StudentLeaveType,
+};
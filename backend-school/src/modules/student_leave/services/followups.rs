@@ -0,0 +1,196 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::student_leave::models::StudentLeaveRequest;
+use crate::modules::work::services::{
+    self as work_service, CreateWorkItemInput, WorkItemAssigneeTargetInput, WorkItemAssigneeType,
+    WorkItemMetadata,
+};
+use crate::modules::workflow::models::WorkflowWindowMetadata;
+use crate::modules::workflow::services::{
+    self as workflow_service, CreateWorkflowWindowInput, WorkflowWindowSchedule,
+};
+use crate::permissions::registry::codes;
+
+use super::shared::format_leave_range;
+
+const STUDENT_LEAVE_MODULE_CODE: &str = "student_leave";
+const REVIEW_WORKFLOW_CODE: &str = "student_leave_review";
+
+/// Routes a pending request to the student's homeroom advisors. Returns the new
+/// work item, or `None` when the student has no advisor; school-wide reviewers
+/// still see such requests in the leave list.
+pub(super) async fn open_review_work_item(
+    pool: &PgPool,
+    request: &StudentLeaveRequest,
+) -> Result<Option<Uuid>, AppError> {
+    let advisor_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT ca.user_id
+        FROM student_class_enrollments sce
+        JOIN classroom_advisors ca ON ca.classroom_id = sce.class_room_id
+        WHERE sce.student_id = $1
+          AND sce.status = 'active'
+        "#,
+    )
+    .bind(request.student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(followup_error)?;
+
+    if advisor_ids.is_empty() {
+        tracing::warn!(
+            leave_request_id = %request.id,
+            student_id = %request.student_id,
+            "Student leave submitted but student has no classroom advisor"
+        );
+        return Ok(None);
+    }
+
+    let workflow_window_id =
+        ensure_review_window(pool, request.academic_semester_id, request.requested_by).await?;
+    let work_item_id = work_service::create_work_item(
+        pool,
+        CreateWorkItemInput {
+            workflow_window_id,
+            module_code: STUDENT_LEAVE_MODULE_CODE.to_string(),
+            source_resource_type: "student_leave_request".to_string(),
+            source_resource_id: Some(request.id),
+            title: format!(
+                "พิจารณา{}: {}",
+                request.leave_type.label(),
+                request.student_name
+            ),
+            description: Some(review_description(request)),
+            action_path: format!("/staff/student-leave/{}", request.id),
+            required_permission: Some(codes::STUDENT_LEAVE_APPROVE_ASSIGNED.to_string()),
+            metadata: WorkItemMetadata {
+                tags: vec!["student_leave".to_string()],
+                source_label: Some("ใบลานักเรียน".to_string()),
+            },
+            assignees: advisor_ids
+                .into_iter()
+                .map(|user_id| WorkItemAssigneeTargetInput {
+                    assignee_type: WorkItemAssigneeType::User,
+                    user_id: Some(user_id),
+                    organization_unit_id: None,
+                    position_code: None,
+                })
+                .collect(),
+            created_by: Some(request.requested_by),
+        },
+    )
+    .await?;
+
+    sqlx::query("UPDATE student_leave_requests SET work_item_id = $2 WHERE id = $1")
+        .bind(request.id)
+        .bind(work_item_id)
+        .execute(pool)
+        .await
+        .map_err(followup_error)?;
+
+    Ok(Some(work_item_id))
+}
+
+pub(super) async fn close_review_work_item(
+    pool: &PgPool,
+    work_item_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    match work_item_id {
+        Some(work_item_id) => work_service::close_work_item(pool, work_item_id).await,
+        None => Ok(false),
+    }
+}
+
+fn review_description(request: &StudentLeaveRequest) -> String {
+    let range = format_leave_range(request.start_date, request.end_date);
+    if request.period_ids.is_empty() {
+        format!("วันที่ {range} (ทั้งวัน)")
+    } else {
+        format!("วันที่ {range} ({} คาบ)", request.period_ids.len())
+    }
+}
+
+async fn ensure_review_window(
+    pool: &PgPool,
+    academic_semester_id: Uuid,
+    created_by: Uuid,
+) -> Result<Uuid, AppError> {
+    if let Some(id) = existing_review_window(pool, academic_semester_id).await? {
+        return Ok(id);
+    }
+
+    let semester_name =
+        sqlx::query_scalar::<_, String>("SELECT name FROM academic_semesters WHERE id = $1")
+            .bind(academic_semester_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(followup_error)?
+            .ok_or_else(|| AppError::NotFound("ไม่พบภาคเรียน".to_string()))?;
+
+    let window = workflow_service::create_workflow_window(
+        pool,
+        CreateWorkflowWindowInput {
+            module_code: STUDENT_LEAVE_MODULE_CODE.to_string(),
+            workflow_code: REVIEW_WORKFLOW_CODE.to_string(),
+            title: format!("พิจารณาใบลานักเรียน {semester_name}"),
+            description: Some("งานพิจารณาใบลาที่ผู้ปกครองส่งถึงครูที่ปรึกษา".to_string()),
+            organization_unit_id: None,
+            managed_by_permission: codes::STUDENT_LEAVE_APPROVE_SCHOOL.to_string(),
+            schedule: WorkflowWindowSchedule {
+                opens_at: None,
+                due_at: None,
+                closes_at: None,
+            },
+            metadata: WorkflowWindowMetadata {
+                tags: vec!["student_leave".to_string()],
+            },
+            created_by: Some(created_by),
+        },
+    )
+    .await?;
+    workflow_service::open_workflow_window(pool, window.id).await?;
+
+    let linked = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO student_leave_review_windows (academic_semester_id, workflow_window_id)
+        VALUES ($1, $2)
+        ON CONFLICT (academic_semester_id) DO NOTHING
+        RETURNING workflow_window_id
+        "#,
+    )
+    .bind(academic_semester_id)
+    .bind(window.id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)?;
+
+    if let Some(id) = linked {
+        return Ok(id);
+    }
+
+    // Another submission linked its window first; retire ours and use theirs.
+    workflow_service::close_workflow_window(pool, window.id).await?;
+    existing_review_window(pool, academic_semester_id)
+        .await?
+        .ok_or_else(|| AppError::InternalServerError("ไม่สามารถสร้างรอบงานพิจารณาใบลาได้".to_string()))
+}
+
+async fn existing_review_window(
+    pool: &PgPool,
+    academic_semester_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    sqlx::query_scalar::<_, Uuid>(
+        "SELECT workflow_window_id FROM student_leave_review_windows WHERE academic_semester_id = $1",
+    )
+    .bind(academic_semester_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(followup_error)
+}
+
+fn followup_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to route student leave review: {}", error);
+    AppError::InternalServerError("ไม่สามารถส่งใบลาถึงครูที่ปรึกษาได้".to_string())
+}
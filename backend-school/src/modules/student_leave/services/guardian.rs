@@ -0,0 +1,314 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::parents::services as parent_service;
+use crate::modules::student_leave::models::{
+    GuardianStudentLeaveFilter, ResubmitStudentLeaveRequest, StudentLeaveRequest,
+    SubmitStudentLeaveRequest,
+};
+
+use super::followups::{close_review_work_item, open_review_work_item};
+use super::records::{
+    load_request, replace_documents, replace_periods, request_from_row, write_error,
+    StudentLeaveMutationOutcome, StudentLeaveRequestRow, REQUEST_SELECT,
+};
+use super::shared::{
+    can_guardian_cancel, can_guardian_resubmit, dedupe_ids, required_text,
+    resolve_semester_for_range, validate_document_files, validate_leave_range, validate_period_ids,
+};
+
+struct ValidatedLeaveInput {
+    academic_semester_id: Uuid,
+    period_ids: Vec<Uuid>,
+    document_file_ids: Vec<Uuid>,
+    reason: String,
+}
+
+pub async fn list_guardian_requests(
+    pool: &PgPool,
+    parent_id: Uuid,
+    filter: GuardianStudentLeaveFilter,
+) -> Result<Vec<StudentLeaveRequest>, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    if let Some(student_id) = filter.student_id {
+        parent_service::ensure_parent_student_link(pool, parent_id, student_id).await?;
+    }
+
+    let rows = sqlx::query_as::<_, StudentLeaveRequestRow>(&format!(
+        r#"
+        {REQUEST_SELECT}
+        WHERE r.student_id IN (
+            SELECT student_user_id FROM student_parents WHERE parent_user_id = $1
+        )
+          AND ($2::uuid IS NULL OR r.student_id = $2)
+          AND ($3::text IS NULL OR r.status = $3)
+        ORDER BY r.start_date DESC, r.submitted_at DESC
+        LIMIT 200
+        "#
+    ))
+    .bind(parent_id)
+    .bind(filter.student_id)
+    .bind(filter.status.map(|status| status.as_str()))
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to list guardian student leave requests: {}", error);
+        AppError::InternalServerError("ไม่สามารถดึงรายการใบลาได้".to_string())
+    })?;
+
+    rows.into_iter().map(request_from_row).collect()
+}
+
+pub async fn get_guardian_request(
+    pool: &PgPool,
+    parent_id: Uuid,
+    id: Uuid,
+) -> Result<StudentLeaveRequest, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    let loaded = load_request(pool, id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, loaded.request.student_id).await?;
+    Ok(loaded.request)
+}
+
+pub async fn submit_request(
+    pool: &PgPool,
+    parent_id: Uuid,
+    payload: SubmitStudentLeaveRequest,
+) -> Result<StudentLeaveMutationOutcome, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, payload.student_id).await?;
+    let input = validate_leave_input(
+        pool,
+        parent_id,
+        None,
+        payload.start_date,
+        payload.end_date,
+        payload.period_ids,
+        &payload.reason,
+        payload.document_file_ids,
+    )
+    .await?;
+    ensure_no_overlapping_request(
+        pool,
+        payload.student_id,
+        None,
+        payload.start_date,
+        payload.end_date,
+    )
+    .await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO student_leave_requests (
+            student_id, requested_by, academic_semester_id, leave_type,
+            start_date, end_date, reason
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.student_id)
+    .bind(parent_id)
+    .bind(input.academic_semester_id)
+    .bind(payload.leave_type.as_str())
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(&input.reason)
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    replace_periods(&mut transaction, id, &input.period_ids).await?;
+    replace_documents(&mut transaction, id, &input.document_file_ids, parent_id).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let request = load_request(pool, id).await?.request;
+    let work_item_id = open_review_work_item(pool, &request).await?;
+    Ok(StudentLeaveMutationOutcome {
+        request,
+        work_items_changed: work_item_id.is_some(),
+        detached_file_ids: Vec::new(),
+    })
+}
+
+pub async fn resubmit_request(
+    pool: &PgPool,
+    parent_id: Uuid,
+    id: Uuid,
+    payload: ResubmitStudentLeaveRequest,
+) -> Result<StudentLeaveMutationOutcome, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    let current = load_request(pool, id).await?.request;
+    parent_service::ensure_parent_student_link(pool, parent_id, current.student_id).await?;
+    if !can_guardian_resubmit(current.status) {
+        return Err(AppError::Conflict(
+            "แก้ไขได้เฉพาะใบลาที่ถูกส่งกลับให้แก้ไข".to_string(),
+        ));
+    }
+    let input = validate_leave_input(
+        pool,
+        parent_id,
+        Some(id),
+        payload.start_date,
+        payload.end_date,
+        payload.period_ids,
+        &payload.reason,
+        payload.document_file_ids,
+    )
+    .await?;
+    ensure_no_overlapping_request(
+        pool,
+        current.student_id,
+        Some(id),
+        payload.start_date,
+        payload.end_date,
+    )
+    .await?;
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE student_leave_requests
+        SET academic_semester_id = $2,
+            leave_type = $3,
+            start_date = $4,
+            end_date = $5,
+            reason = $6,
+            status = 'pending',
+            work_item_id = NULL,
+            submitted_at = NOW()
+        WHERE id = $1
+          AND status = 'returned'
+        "#,
+    )
+    .bind(id)
+    .bind(input.academic_semester_id)
+    .bind(payload.leave_type.as_str())
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(&input.reason)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+    replace_periods(&mut transaction, id, &input.period_ids).await?;
+    let detached_file_ids =
+        replace_documents(&mut transaction, id, &input.document_file_ids, parent_id).await?;
+    transaction.commit().await.map_err(write_error)?;
+
+    let request = load_request(pool, id).await?.request;
+    let work_item_id = open_review_work_item(pool, &request).await?;
+    Ok(StudentLeaveMutationOutcome {
+        request,
+        work_items_changed: work_item_id.is_some(),
+        detached_file_ids,
+    })
+}
+
+pub async fn cancel_request(
+    pool: &PgPool,
+    parent_id: Uuid,
+    id: Uuid,
+) -> Result<StudentLeaveMutationOutcome, AppError> {
+    parent_service::ensure_parent_user(pool, parent_id).await?;
+    let current = load_request(pool, id).await?;
+    parent_service::ensure_parent_student_link(pool, parent_id, current.request.student_id).await?;
+    if !can_guardian_cancel(current.request.status) {
+        return Err(AppError::Conflict(
+            "ยกเลิกได้เฉพาะใบลาที่ยังไม่ได้พิจารณา".to_string(),
+        ));
+    }
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE student_leave_requests
+        SET status = 'cancelled'
+        WHERE id = $1
+          AND status IN ('pending', 'returned')
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+
+    let work_items_changed = close_review_work_item(pool, current.work_item_id).await?;
+    let request = load_request(pool, id).await?.request;
+    Ok(StudentLeaveMutationOutcome {
+        request,
+        work_items_changed,
+        detached_file_ids: Vec::new(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn validate_leave_input(
+    pool: &PgPool,
+    parent_id: Uuid,
+    leave_request_id: Option<Uuid>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    period_ids: Vec<Uuid>,
+    reason: &str,
+    document_file_ids: Vec<Uuid>,
+) -> Result<ValidatedLeaveInput, AppError> {
+    let reason = required_text(reason, "กรุณาระบุเหตุผลการลา")?;
+    let period_ids = dedupe_ids(period_ids);
+    validate_leave_range(start_date, end_date, &period_ids)?;
+    let academic_semester_id = resolve_semester_for_range(pool, start_date, end_date).await?;
+    validate_period_ids(pool, academic_semester_id, &period_ids).await?;
+    let document_file_ids = dedupe_ids(document_file_ids);
+    validate_document_files(pool, parent_id, leave_request_id, &document_file_ids).await?;
+
+    Ok(ValidatedLeaveInput {
+        academic_semester_id,
+        period_ids,
+        document_file_ids,
+        reason,
+    })
+}
+
+async fn ensure_no_overlapping_request(
+    pool: &PgPool,
+    student_id: Uuid,
+    excluding_id: Option<Uuid>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<(), AppError> {
+    let overlaps = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM student_leave_requests
+            WHERE student_id = $1
+              AND ($2::uuid IS NULL OR id <> $2)
+              AND status IN ('pending', 'returned', 'approved')
+              AND start_date <= $4
+              AND end_date >= $3
+        )
+        "#,
+    )
+    .bind(student_id)
+    .bind(excluding_id)
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check overlapping student leave: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบใบลาเดิมได้".to_string())
+    })?;
+
+    if overlaps {
+        Err(AppError::Conflict("มีใบลาในช่วงวันที่นี้อยู่แล้ว".to_string()))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::modules::student_leave::models::{StudentLeaveDecision, StudentLeaveRequest};
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+
+use super::shared::decision_notification_text;
+
+const GUARDIAN_LEAVE_LINK: &str = "/parent";
+
+/// Tells every active guardian linked to the student about the review outcome,
+/// not only the one who filed the request. Returns how many notifications were
+/// stored.
+pub async fn notify_guardians_of_decision(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    tenant: &str,
+    request: &StudentLeaveRequest,
+    decision: StudentLeaveDecision,
+) -> Result<usize, AppError> {
+    let guardian_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT parent_users.id
+        FROM student_parents
+        JOIN users parent_users ON parent_users.id = student_parents.parent_user_id
+        WHERE student_parents.student_user_id = $1
+          AND parent_users.user_type = 'parent'
+          AND parent_users.status = 'active'
+        "#,
+    )
+    .bind(request.student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(
+            "Failed to load student leave guardian recipients: {}",
+            error
+        );
+        AppError::InternalServerError("ไม่สามารถดึงข้อมูลผู้ปกครองได้".to_string())
+    })?;
+
+    let (title, message) = decision_notification_text(
+        decision,
+        &request.student_name,
+        request.leave_type,
+        request.start_date,
+        request.end_date,
+    );
+    let publisher = TenantNotificationPublisher::new(tenant, notification_channel);
+    let mut successful_count = 0;
+    for guardian_id in guardian_ids {
+        if let Err(error) = NotificationService::send(
+            pool,
+            &publisher,
+            guardian_id,
+            &title,
+            &message,
+            NotificationType::Info,
+            Some(GUARDIAN_LEAVE_LINK),
+        )
+        .await
+        {
+            tracing::error!(
+                leave_request_id = %request.id,
+                recipient_user_id = %guardian_id,
+                error = %error,
+                "Student leave guardian notification failed for recipient"
+            );
+        } else {
+            successful_count += 1;
+        }
+    }
+
+    Ok(successful_count)
+}
@@ -0,0 +1,233 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::student_leave::models::StudentLeaveRequest;
+
+use super::shared::{parse_leave_type, parse_status, REQUEST_NOT_FOUND_MESSAGE};
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StudentLeaveRequestRow {
+    id: Uuid,
+    student_id: Uuid,
+    student_name: String,
+    requested_by: Uuid,
+    requested_by_name: String,
+    academic_semester_id: Uuid,
+    leave_type: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    period_ids: Vec<Uuid>,
+    reason: String,
+    status: String,
+    review_note: Option<String>,
+    reviewed_by: Option<Uuid>,
+    reviewed_by_name: Option<String>,
+    reviewed_at: Option<DateTime<Utc>>,
+    work_item_id: Option<Uuid>,
+    document_file_ids: Vec<Uuid>,
+    excused_occurrence_count: i64,
+    submitted_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+pub(super) const REQUEST_SELECT: &str = r#"
+    SELECT r.id,
+           r.student_id,
+           CONCAT_WS(' ', student.first_name, student.last_name) AS student_name,
+           r.requested_by,
+           CONCAT_WS(' ', requester.first_name, requester.last_name) AS requested_by_name,
+           r.academic_semester_id,
+           r.leave_type,
+           r.start_date,
+           r.end_date,
+           COALESCE(
+               (
+                   SELECT array_agg(period.period_id ORDER BY period.period_id)
+                   FROM student_leave_request_periods period
+                   WHERE period.leave_request_id = r.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS period_ids,
+           r.reason,
+           r.status,
+           r.review_note,
+           r.reviewed_by,
+           NULLIF(CONCAT_WS(' ', reviewer.first_name, reviewer.last_name), '') AS reviewed_by_name,
+           r.reviewed_at,
+           r.work_item_id,
+           COALESCE(
+               (
+                   SELECT array_agg(document.file_id ORDER BY document.created_at, document.file_id)
+                   FROM student_leave_request_documents document
+                   WHERE document.leave_request_id = r.id
+               ),
+               ARRAY[]::uuid[]
+           ) AS document_file_ids,
+           (
+               SELECT COUNT(*)
+               FROM student_leave_excused_occurrences occurrence
+               WHERE occurrence.leave_request_id = r.id
+           ) AS excused_occurrence_count,
+           r.submitted_at,
+           r.created_at,
+           r.updated_at
+    FROM student_leave_requests r
+    JOIN users student ON student.id = r.student_id
+    JOIN users requester ON requester.id = r.requested_by
+    LEFT JOIN users reviewer ON reviewer.id = r.reviewed_by
+"#;
+
+pub struct StudentLeaveMutationOutcome {
+    pub request: StudentLeaveRequest,
+    pub work_items_changed: bool,
+    /// Documents removed from the request that the handler hands to File
+    /// Platform deletion.
+    pub detached_file_ids: Vec<Uuid>,
+}
+
+/// Loaded request plus the open review work item, which is internal routing
+/// state and never part of the API response.
+pub(super) struct LoadedLeaveRequest {
+    pub(super) request: StudentLeaveRequest,
+    pub(super) work_item_id: Option<Uuid>,
+}
+
+pub(super) async fn load_request(pool: &PgPool, id: Uuid) -> Result<LoadedLeaveRequest, AppError> {
+    let row =
+        sqlx::query_as::<_, StudentLeaveRequestRow>(&format!("{REQUEST_SELECT} WHERE r.id = $1"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(read_error)?
+            .ok_or_else(|| AppError::NotFound(REQUEST_NOT_FOUND_MESSAGE.to_string()))?;
+
+    loaded_from_row(row)
+}
+
+pub(super) fn loaded_from_row(row: StudentLeaveRequestRow) -> Result<LoadedLeaveRequest, AppError> {
+    let work_item_id = row.work_item_id;
+    Ok(LoadedLeaveRequest {
+        request: StudentLeaveRequest {
+            id: row.id,
+            student_id: row.student_id,
+            student_name: row.student_name,
+            requested_by: row.requested_by,
+            requested_by_name: row.requested_by_name,
+            academic_semester_id: row.academic_semester_id,
+            leave_type: parse_leave_type(&row.leave_type)?,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            period_ids: row.period_ids,
+            reason: row.reason,
+            status: parse_status(&row.status)?,
+            review_note: row.review_note,
+            reviewed_by: row.reviewed_by,
+            reviewed_by_name: row.reviewed_by_name,
+            reviewed_at: row.reviewed_at,
+            document_file_ids: row.document_file_ids,
+            excused_occurrence_count: row.excused_occurrence_count,
+            submitted_at: row.submitted_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        },
+        work_item_id,
+    })
+}
+
+pub(super) fn request_from_row(
+    row: StudentLeaveRequestRow,
+) -> Result<StudentLeaveRequest, AppError> {
+    loaded_from_row(row).map(|loaded| loaded.request)
+}
+
+pub(super) async fn replace_periods(
+    transaction: &mut Transaction<'_, Postgres>,
+    leave_request_id: Uuid,
+    period_ids: &[Uuid],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM student_leave_request_periods WHERE leave_request_id = $1")
+        .bind(leave_request_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+    if !period_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO student_leave_request_periods (leave_request_id, period_id)
+            SELECT $1, period_id
+            FROM UNNEST($2::uuid[]) AS period_id
+            "#,
+        )
+        .bind(leave_request_id)
+        .bind(period_ids)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+/// Attaches the given documents and returns the previously attached files that
+/// are no longer listed, so the caller can hand them to File Platform deletion.
+pub(super) async fn replace_documents(
+    transaction: &mut Transaction<'_, Postgres>,
+    leave_request_id: Uuid,
+    file_ids: &[Uuid],
+    attached_by: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let detached_file_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        DELETE FROM student_leave_request_documents
+        WHERE leave_request_id = $1
+          AND NOT (file_id = ANY($2))
+        RETURNING file_id
+        "#,
+    )
+    .bind(leave_request_id)
+    .bind(file_ids)
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    if !file_ids.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO student_leave_request_documents (leave_request_id, file_id, attached_by)
+            SELECT $1, file_id, $3
+            FROM UNNEST($2::uuid[]) AS file_id
+            ON CONFLICT (leave_request_id, file_id) DO NOTHING
+            "#,
+        )
+        .bind(leave_request_id)
+        .bind(file_ids)
+        .bind(attached_by)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+
+        sqlx::query(
+            "UPDATE files SET retention_class = 'standard', expires_at = NULL, updated_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(file_ids)
+        .execute(&mut **transaction)
+        .await
+        .map_err(write_error)?;
+    }
+
+    Ok(detached_file_ids)
+}
+
+pub(super) fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read student leave request: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลใบลาได้".to_string())
+}
+
+pub(super) fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write student leave request: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกใบลาได้".to_string())
+}
@@ -0,0 +1,238 @@
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::student_leave::models::{
+    ReviewStudentLeaveRequest, StudentLeaveDecision, StudentLeaveExcusedOccurrence,
+    StudentLeaveFilter, StudentLeaveRequest,
+};
+use crate::policies::resource_access_policy::UserResourceListAccess;
+use crate::policies::student_leave_access_policy;
+
+use super::followups::close_review_work_item;
+use super::records::{
+    load_request, read_error, request_from_row, write_error, StudentLeaveMutationOutcome,
+    StudentLeaveRequestRow, REQUEST_SELECT,
+};
+use super::shared::{can_review, normalize_optional_text, review_requires_note};
+
+pub async fn list_requests(
+    pool: &PgPool,
+    actor: &ActorContext,
+    filter: StudentLeaveFilter,
+) -> Result<Vec<StudentLeaveRequest>, AppError> {
+    let access = student_leave_access_policy::resolve_student_leave_list_access(actor)?;
+
+    let mut builder = QueryBuilder::<Postgres>::new(REQUEST_SELECT);
+    builder.push(" WHERE ");
+    match access {
+        UserResourceListAccess::School => {
+            builder.push("TRUE");
+        }
+        UserResourceListAccess::Assigned(user_id) => {
+            builder
+                .push(
+                    "r.student_id IN (
+                        SELECT sce.student_id
+                        FROM student_class_enrollments sce
+                        JOIN classroom_advisors ca ON ca.classroom_id = sce.class_room_id
+                        WHERE sce.status = 'active'
+                          AND ca.user_id = ",
+                )
+                .push_bind(user_id)
+                .push(")");
+        }
+        UserResourceListAccess::Own(user_id)
+        | UserResourceListAccess::OrganizationUnit(user_id)
+        | UserResourceListAccess::OrganizationTree(user_id) => {
+            builder.push("r.student_id = ").push_bind(user_id);
+        }
+    }
+
+    if let Some(student_id) = filter.student_id {
+        builder.push(" AND r.student_id = ").push_bind(student_id);
+    }
+    if let Some(class_room_id) = filter.class_room_id {
+        builder
+            .push(
+                " AND EXISTS (
+                    SELECT 1
+                    FROM student_class_enrollments sce
+                    WHERE sce.student_id = r.student_id
+                      AND sce.status = 'active'
+                      AND sce.class_room_id = ",
+            )
+            .push_bind(class_room_id)
+            .push(")");
+    }
+    if let Some(status) = filter.status {
+        builder.push(" AND r.status = ").push_bind(status.as_str());
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND r.end_date >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND r.start_date <= ").push_bind(to);
+    }
+    builder.push(" ORDER BY r.submitted_at DESC LIMIT 500");
+
+    let rows = builder
+        .build_query_as::<StudentLeaveRequestRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("Failed to list student leave requests: {}", error);
+            AppError::InternalServerError("ไม่สามารถดึงรายการใบลาได้".to_string())
+        })?;
+
+    rows.into_iter().map(request_from_row).collect()
+}
+
+pub async fn get_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<StudentLeaveRequest, AppError> {
+    let request = load_request(pool, id).await?.request;
+    student_leave_access_policy::require_student_leave_read(pool, actor, request.student_id)
+        .await?;
+    Ok(request)
+}
+
+pub async fn review_request(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+    payload: ReviewStudentLeaveRequest,
+) -> Result<StudentLeaveMutationOutcome, AppError> {
+    let current = load_request(pool, id).await?;
+    student_leave_access_policy::require_student_leave_review(
+        pool,
+        actor,
+        current.request.student_id,
+    )
+    .await?;
+    if !can_review(current.request.status) {
+        return Err(AppError::Conflict("ใบลานี้ไม่ได้อยู่ระหว่างรอพิจารณา".to_string()));
+    }
+    let note = normalize_optional_text(payload.note);
+    if review_requires_note(payload.decision) && note.is_none() {
+        return Err(AppError::ValidationError(
+            "กรุณาระบุเหตุผลเมื่อส่งกลับหรือไม่อนุมัติใบลา".to_string(),
+        ));
+    }
+
+    let mut transaction = pool.begin().await.map_err(write_error)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE student_leave_requests
+        SET status = $2,
+            review_note = $3,
+            reviewed_by = $4,
+            reviewed_at = NOW(),
+            work_item_id = NULL
+        WHERE id = $1
+          AND status = 'pending'
+        "#,
+    )
+    .bind(id)
+    .bind(payload.decision.resulting_status().as_str())
+    .bind(note)
+    .bind(actor.user_id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(write_error)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::Conflict("ใบลานี้ถูกเปลี่ยนสถานะแล้ว".to_string()));
+    }
+    if payload.decision == StudentLeaveDecision::Approve {
+        prefill_excused_occurrences(&mut transaction, &current.request).await?;
+    }
+    transaction.commit().await.map_err(write_error)?;
+
+    let work_items_changed = close_review_work_item(pool, current.work_item_id).await?;
+    let request = load_request(pool, id).await?.request;
+    Ok(StudentLeaveMutationOutcome {
+        request,
+        work_items_changed,
+        detached_file_ids: Vec::new(),
+    })
+}
+
+/// Shared by staff and guardians: a linked guardian sees the periods excused
+/// for their child, staff need leave read access to the student.
+pub async fn list_excused_occurrences(
+    pool: &PgPool,
+    actor: &ActorContext,
+    id: Uuid,
+) -> Result<Vec<StudentLeaveExcusedOccurrence>, AppError> {
+    let request = load_request(pool, id).await?.request;
+    student_leave_access_policy::require_leave_request_read(pool, actor, request.student_id)
+        .await?;
+
+    sqlx::query_as::<_, StudentLeaveExcusedOccurrence>(
+        r#"
+        SELECT occurrence.leave_date,
+               occurrence.timetable_entry_id,
+               occurrence.period_id,
+               period.name AS period_name,
+               period.start_time,
+               period.end_time,
+               COALESCE(subject.name_th, entry.title) AS title
+        FROM student_leave_excused_occurrences occurrence
+        JOIN academic_periods period ON period.id = occurrence.period_id
+        JOIN academic_timetable_entries entry ON entry.id = occurrence.timetable_entry_id
+        LEFT JOIN classroom_courses course ON course.id = entry.classroom_course_id
+        LEFT JOIN subjects subject ON subject.id = course.subject_id
+        WHERE occurrence.leave_request_id = $1
+        ORDER BY occurrence.leave_date, period.start_time
+        "#,
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await
+    .map_err(read_error)
+}
+
+/// Expands the approved range into the student's timetable occurrences so the
+/// attendance check sheet opens with those periods already marked as excused.
+/// Partial-day requests only cover the selected periods.
+async fn prefill_excused_occurrences(
+    transaction: &mut Transaction<'_, Postgres>,
+    request: &StudentLeaveRequest,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO student_leave_excused_occurrences (
+            leave_request_id, student_id, leave_date, timetable_entry_id, period_id
+        )
+        SELECT $1, $2, leave_day::date, entry.id, entry.period_id
+        FROM generate_series($3::date, $4::date, interval '1 day') AS leave_day
+        JOIN academic_timetable_entries entry
+          ON entry.academic_semester_id = $5
+         AND entry.is_active = true
+         AND entry.entry_type <> 'BREAK'
+         AND entry.day_of_week = to_char(leave_day, 'DY')
+        WHERE entry.classroom_id IN (
+                SELECT class_room_id
+                FROM student_class_enrollments
+                WHERE student_id = $2
+                  AND status = 'active'
+            )
+          AND (cardinality($6::uuid[]) = 0 OR entry.period_id = ANY($6))
+        ON CONFLICT (student_id, timetable_entry_id, leave_date) DO NOTHING
+        "#,
+    )
+    .bind(request.id)
+    .bind(request.student_id)
+    .bind(request.start_date)
+    .bind(request.end_date)
+    .bind(request.academic_semester_id)
+    .bind(&request.period_ids)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    Ok(())
+}
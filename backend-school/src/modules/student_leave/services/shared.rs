@@ -0,0 +1,251 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::student_leave::models::{
+    StudentLeaveDecision, StudentLeaveStatus, StudentLeaveType,
+};
+
+pub(super) const REQUEST_NOT_FOUND_MESSAGE: &str = "ไม่พบใบลา";
+pub(super) const MAX_LEAVE_DAYS: i64 = 30;
+pub(super) const MAX_DOCUMENT_FILES: usize = 5;
+
+/// Partial-day leave is expressed as a set of periods on a single date; multi-day
+/// requests always cover whole school days.
+pub fn validate_leave_range(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    period_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if end_date < start_date {
+        return Err(AppError::ValidationError(
+            "วันสิ้นสุดการลาต้องไม่ก่อนวันเริ่มลา".to_string(),
+        ));
+    }
+    if (end_date - start_date).num_days() + 1 > MAX_LEAVE_DAYS {
+        return Err(AppError::ValidationError(format!(
+            "ยื่นใบลาได้ครั้งละไม่เกิน {MAX_LEAVE_DAYS} วัน"
+        )));
+    }
+    if !period_ids.is_empty() && start_date != end_date {
+        return Err(AppError::ValidationError(
+            "การลาเป็นรายคาบต้องเป็นวันเดียว".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn dedupe_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut unique = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    unique
+}
+
+pub fn can_guardian_resubmit(status: StudentLeaveStatus) -> bool {
+    status == StudentLeaveStatus::Returned
+}
+
+pub fn can_guardian_cancel(status: StudentLeaveStatus) -> bool {
+    matches!(
+        status,
+        StudentLeaveStatus::Pending | StudentLeaveStatus::Returned
+    )
+}
+
+pub fn can_review(status: StudentLeaveStatus) -> bool {
+    status == StudentLeaveStatus::Pending
+}
+
+/// Guardians need to know what to fix or why the leave was refused, so only
+/// approval may be recorded without a note.
+pub fn review_requires_note(decision: StudentLeaveDecision) -> bool {
+    !matches!(decision, StudentLeaveDecision::Approve)
+}
+
+pub fn decision_notification_text(
+    decision: StudentLeaveDecision,
+    student_name: &str,
+    leave_type: StudentLeaveType,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> (String, String) {
+    let range = format_leave_range(start_date, end_date);
+    let label = leave_type.label();
+    match decision {
+        StudentLeaveDecision::Approve => (
+            "ใบลาได้รับการอนุมัติ".to_string(),
+            format!("{label}ของ {student_name} วันที่ {range} ได้รับการอนุมัติแล้ว"),
+        ),
+        StudentLeaveDecision::Return => (
+            "ใบลาถูกส่งกลับให้แก้ไข".to_string(),
+            format!("{label}ของ {student_name} วันที่ {range} ถูกส่งกลับให้แก้ไข"),
+        ),
+        StudentLeaveDecision::Reject => (
+            "ใบลาไม่ได้รับการอนุมัติ".to_string(),
+            format!("{label}ของ {student_name} วันที่ {range} ไม่ได้รับการอนุมัติ"),
+        ),
+    }
+}
+
+pub fn format_leave_range(start_date: NaiveDate, end_date: NaiveDate) -> String {
+    if start_date == end_date {
+        start_date.format("%d/%m/%Y").to_string()
+    } else {
+        format!(
+            "{} - {}",
+            start_date.format("%d/%m/%Y"),
+            end_date.format("%d/%m/%Y")
+        )
+    }
+}
+
+pub(super) fn normalize_optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn required_text(value: &str, message: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(AppError::ValidationError(message.to_string()))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+pub(super) fn parse_leave_type(code: &str) -> Result<StudentLeaveType, AppError> {
+    StudentLeaveType::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("ประเภทการลาในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+pub(super) fn parse_status(code: &str) -> Result<StudentLeaveStatus, AppError> {
+    StudentLeaveStatus::from_code(code)
+        .ok_or_else(|| AppError::InternalServerError("สถานะใบลาในฐานข้อมูลไม่ถูกต้อง".to_string()))
+}
+
+/// A request belongs to the semester containing its first day and must not run
+/// past that semester, so approvals only ever touch one semester's timetable.
+pub(super) async fn resolve_semester_for_range(
+    pool: &PgPool,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Uuid, AppError> {
+    let semester = sqlx::query_as::<_, (Uuid, NaiveDate)>(
+        r#"
+        SELECT id, end_date
+        FROM academic_semesters
+        WHERE $1 BETWEEN start_date AND end_date
+        ORDER BY is_active DESC, start_date DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(start_date)
+    .fetch_optional(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to resolve semester for student leave: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบภาคเรียนได้".to_string())
+    })?;
+
+    match semester {
+        Some((id, semester_end_date)) if end_date <= semester_end_date => Ok(id),
+        Some(_) => Err(AppError::ValidationError(
+            "ช่วงวันที่ลาคร่อมภาคเรียน กรุณาแยกใบลาตามภาคเรียน".to_string(),
+        )),
+        None => Err(AppError::ValidationError(
+            "วันที่ลาไม่อยู่ในภาคเรียนใด".to_string(),
+        )),
+    }
+}
+
+pub(super) async fn validate_period_ids(
+    pool: &PgPool,
+    academic_semester_id: Uuid,
+    period_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if period_ids.is_empty() {
+        return Ok(());
+    }
+
+    let matched = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM academic_periods period
+        JOIN academic_semesters semester ON semester.academic_year_id = period.academic_year_id
+        WHERE semester.id = $1
+          AND period.id = ANY($2)
+          AND period.is_active = true
+        "#,
+    )
+    .bind(academic_semester_id)
+    .bind(period_ids)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate student leave periods: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบคาบเรียนได้".to_string())
+    })?;
+
+    if matched == period_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "คาบเรียนที่เลือกไม่อยู่ในปีการศึกษานี้".to_string(),
+        ))
+    }
+}
+
+pub(super) async fn validate_document_files(
+    pool: &PgPool,
+    uploaded_by: Uuid,
+    leave_request_id: Option<Uuid>,
+    file_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if file_ids.len() > MAX_DOCUMENT_FILES {
+        return Err(AppError::ValidationError(format!(
+            "แนบเอกสารได้ไม่เกิน {MAX_DOCUMENT_FILES} ไฟล์"
+        )));
+    }
+    if file_ids.is_empty() {
+        return Ok(());
+    }
+
+    let usable = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM files
+        LEFT JOIN student_leave_request_documents document ON document.file_id = files.id
+        WHERE files.id = ANY($1)
+          AND files.purpose_code = 'student_leave_document'
+          AND files.lifecycle_status = 'ready'
+          AND files.deleted_at IS NULL
+          AND (
+              (document.file_id IS NULL AND files.owner_user_id = $2)
+              OR document.leave_request_id = $3
+          )
+        "#,
+    )
+    .bind(file_ids)
+    .bind(uploaded_by)
+    .bind(leave_request_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to validate student leave documents: {}", error);
+        AppError::InternalServerError("ไม่สามารถตรวจสอบเอกสารแนบได้".to_string())
+    })?;
+
+    if usable == file_ids.len() as i64 {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "เอกสารแนบไม่พร้อมใช้งาน".to_string(),
+        ))
+    }
+}
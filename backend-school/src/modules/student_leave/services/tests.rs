@@ -0,0 +1,116 @@
+use super::*;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn leave_range_accepts_single_and_multi_day_requests() {
+    assert!(validate_leave_range(date(2026, 6, 1), date(2026, 6, 1), &[]).is_ok());
+    assert!(validate_leave_range(date(2026, 6, 1), date(2026, 6, 5), &[]).is_ok());
+    assert!(validate_leave_range(date(2026, 6, 1), date(2026, 6, 1), &[Uuid::new_v4()]).is_ok());
+}
+
+#[test]
+fn leave_range_rejects_reversed_and_oversized_ranges() {
+    assert!(matches!(
+        validate_leave_range(date(2026, 6, 5), date(2026, 6, 1), &[]),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(validate_leave_range(date(2026, 6, 1), date(2026, 6, 30), &[]).is_ok());
+    assert!(matches!(
+        validate_leave_range(date(2026, 6, 1), date(2026, 7, 1), &[]),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn partial_day_leave_must_be_a_single_date() {
+    assert!(matches!(
+        validate_leave_range(date(2026, 6, 1), date(2026, 6, 2), &[Uuid::new_v4()]),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn dedupe_ids_keeps_first_occurrence_order() {
+    let first = Uuid::new_v4();
+    let second = Uuid::new_v4();
+
+    assert_eq!(
+        dedupe_ids(vec![first, second, first, second]),
+        vec![first, second]
+    );
+}
+
+#[test]
+fn guardians_edit_only_returned_and_cancel_only_open_requests() {
+    assert!(can_guardian_resubmit(StudentLeaveStatus::Returned));
+    assert!(!can_guardian_resubmit(StudentLeaveStatus::Pending));
+    assert!(!can_guardian_resubmit(StudentLeaveStatus::Approved));
+
+    assert!(can_guardian_cancel(StudentLeaveStatus::Pending));
+    assert!(can_guardian_cancel(StudentLeaveStatus::Returned));
+    assert!(!can_guardian_cancel(StudentLeaveStatus::Approved));
+    assert!(!can_guardian_cancel(StudentLeaveStatus::Rejected));
+    assert!(!can_guardian_cancel(StudentLeaveStatus::Cancelled));
+}
+
+#[test]
+fn only_pending_requests_can_be_reviewed() {
+    assert!(can_review(StudentLeaveStatus::Pending));
+    assert!(!can_review(StudentLeaveStatus::Returned));
+    assert!(!can_review(StudentLeaveStatus::Approved));
+    assert!(!can_review(StudentLeaveStatus::Cancelled));
+}
+
+#[test]
+fn return_and_reject_require_a_note() {
+    assert!(!review_requires_note(StudentLeaveDecision::Approve));
+    assert!(review_requires_note(StudentLeaveDecision::Return));
+    assert!(review_requires_note(StudentLeaveDecision::Reject));
+}
+
+#[test]
+fn decisions_map_to_request_statuses() {
+    assert_eq!(
+        StudentLeaveDecision::Approve.resulting_status(),
+        StudentLeaveStatus::Approved
+    );
+    assert_eq!(
+        StudentLeaveDecision::Return.resulting_status(),
+        StudentLeaveStatus::Returned
+    );
+    assert_eq!(
+        StudentLeaveDecision::Reject.resulting_status(),
+        StudentLeaveStatus::Rejected
+    );
+}
+
+#[test]
+fn leave_range_formats_single_day_without_separator() {
+    assert_eq!(
+        format_leave_range(date(2026, 6, 1), date(2026, 6, 1)),
+        "01/06/2026"
+    );
+    assert_eq!(
+        format_leave_range(date(2026, 6, 1), date(2026, 6, 3)),
+        "01/06/2026 - 03/06/2026"
+    );
+}
+
+#[test]
+fn decision_notification_mentions_student_and_range() {
+    let (title, message) = decision_notification_text(
+        StudentLeaveDecision::Approve,
+        "เด็กชาย ทดสอบ",
+        StudentLeaveType::Sick,
+        date(2026, 6, 1),
+        date(2026, 6, 2),
+    );
+
+    assert_eq!(title, "ใบลาได้รับการอนุมัติ");
+    assert!(message.starts_with("ลาป่วย"));
+    assert!(message.contains("เด็กชาย ทดสอบ"));
+    assert!(message.contains("01/06/2026 - 02/06/2026"));
+}
@@ -112,11 +112,12 @@ pub async fn create_student(
     Json(payload): Json<CreateStudentRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let max_students = context.tenant.plan.max_students;
     let pool = context.tenant.pool;
     let actor = context.actor;
     actor.require_permission(codes::STUDENT_CREATE_ALL)?;
 
-    let student = student_service::create_student(&pool, payload).await?;
+    let student = student_service::create_student(&pool, payload, max_students).await?;
 
     Ok((
         StatusCode::CREATED,
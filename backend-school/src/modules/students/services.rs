@@ -6,6 +6,7 @@ use uuid::Uuid;
 use crate::error::AppError;
 use crate::policies::resource_access_policy::UserResourceListAccess;
 use crate::utils::field_encryption;
+use crate::utils::quota::{ensure_within_quota, QuotaCode};
 
 use super::models::{
     CreateParentRequest, CreateStudentRequest, CreateStudentResponse, ListStudentsQuery, ParentDto,
@@ -239,9 +240,43 @@ fn bind_student_list_access_filter<'q>(
     }
 }
 
+/// Serializes student creation while a student limit applies, so concurrent
+/// requests cannot both take the last seat.
+const STUDENT_QUOTA_ADVISORY_LOCK_KEY: i64 = 0x5354_5544_5155_4f54;
+
+/// Checks the plan's student limit inside the caller's transaction; every
+/// path that activates a student account goes through here.
+pub(crate) async fn ensure_student_quota(
+    tx: &mut Transaction<'_, Postgres>,
+    max_students: Option<i64>,
+) -> Result<(), AppError> {
+    if max_students.is_none() {
+        return Ok(());
+    }
+
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(STUDENT_QUOTA_ADVISORY_LOCK_KEY)
+        .execute(&mut **tx)
+        .await?;
+    let active_students = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM users WHERE user_type = 'student' AND status = 'active'",
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    ensure_within_quota(
+        QuotaCode::StudentLimitReached,
+        max_students,
+        active_students,
+        1,
+    )
+}
+
+/// `max_students` is the limit of the school's plan; `None` is unlimited.
 pub async fn create_student(
     pool: &PgPool,
     payload: CreateStudentRequest,
+    max_students: Option<i64>,
 ) -> Result<CreateStudentResponse, AppError> {
     let password_hash = hash(&payload.password, DEFAULT_COST).map_err(|e| {
         tracing::error!("Student password hashing failed: {}", e);
@@ -266,6 +301,7 @@ pub async fn create_student(
         tracing::error!("Failed to start create student transaction: {}", e);
         AppError::InternalServerError("เกิดข้อผิดพลาดในการเริ่ม Transaction".to_string())
     })?;
+    ensure_student_quota(&mut tx, max_students).await?;
 
     let user_id = insert_student_user(
         &mut tx,
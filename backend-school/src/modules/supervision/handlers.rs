@@ -1,6 +1,6 @@
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::IntoResponse,
     routing::{get, patch, post, put},
     Json, Router,
@@ -24,7 +24,7 @@ use crate::modules::supervision::models::{
 };
 use crate::modules::supervision::services;
 use crate::policies::supervision_access_policy;
-use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::utils::request_context::{actor_tenant_context_from_session, ActorTenantContext};
 use crate::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -235,13 +235,12 @@ pub async fn get_observation(
     Ok(Json(ApiResponse::ok(observation)).into_response())
 }
 
-pub async fn get_observation_review(
-    State(state): State<AppState>,
-    Extension(session): Extension<AuthenticatedSession>,
-    Path(id): Path<Uuid>,
-) -> Result<impl IntoResponse, AppError> {
-    let context = actor_tenant_context_from_session(&state, &session).await?;
-    let observation = services::get_observation(&context.tenant.pool, id).await?;
+/// Results stay with approvers and managers until they are published; after
+/// that anyone who may read the observation may see them.
+async fn require_observation_results_access(
+    context: &ActorTenantContext,
+    observation: &SupervisionObservation,
+) -> Result<(), AppError> {
     let evaluator_user_ids = observation
         .evaluators
         .iter()
@@ -255,7 +254,7 @@ pub async fn get_observation_review(
             observation.observed_user_id,
             &evaluator_user_ids,
         )
-        .await?;
+        .await
     } else if !supervision_access_policy::can_approve_school(&context.actor)
         && !supervision_access_policy::can_manage_school(&context.actor)
     {
@@ -264,14 +263,55 @@ pub async fn get_observation_review(
             &context.actor,
             observation.observed_user_id,
         )
-        .await?;
+        .await
+    } else {
+        Ok(())
     }
+}
+
+pub async fn get_observation_review(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let observation = services::get_observation(&context.tenant.pool, id).await?;
+    require_observation_results_access(&context, &observation).await?;
 
     let review = services::get_observation_review(&context.tenant.pool, id).await?;
 
     Ok(Json(ApiResponse::ok(review)).into_response())
 }
 
+pub async fn download_observation_report(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let observation = services::get_observation(&context.tenant.pool, id).await?;
+    require_observation_results_access(&context, &observation).await?;
+    let school_name = state
+        .admin_client
+        .get_school_name(&context.tenant.subdomain)
+        .await
+        .map_err(|_| AppError::ServiceUnavailable("school_name_lookup_failed".to_string()))?;
+
+    let file = services::render_observation_report(&context.tenant.pool, id, &school_name).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file.filename),
+            ),
+        ],
+        file.content,
+    )
+        .into_response())
+}
+
 pub async fn evaluator_availability(
     State(state): State<AppState>,
     Extension(session): Extension<AuthenticatedSession>,
@@ -611,6 +651,34 @@ pub async fn teacher_status_overview(
     )
 }
 
+pub async fn cycle_analytics(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    let access = supervision_access_policy::resolve_observation_list_access(
+        &context.tenant.pool,
+        &context.actor,
+    )
+    .await?;
+    if !access.school && access.organization_unit_ids.is_empty() {
+        return Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์ดูรายงานวิเคราะห์ผลนิเทศ".to_string(),
+        ));
+    }
+
+    let analytics = services::cycle_analytics(
+        &context.tenant.pool,
+        access,
+        id,
+        !actor_can_view_unreleased_results(&context.actor),
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::ok(analytics)).into_response())
+}
+
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/cycles", get(list_cycles).post(create_cycle))
@@ -624,6 +692,10 @@ pub fn routes() -> Router<AppState> {
             get(get_observation).patch(update_observation),
         )
         .route("/observations/{id}/review", get(get_observation_review))
+        .route(
+            "/observations/{id}/report",
+            get(download_observation_report),
+        )
         .route(
             "/observations/{id}/evaluator-availability",
             get(evaluator_availability),
@@ -660,6 +732,7 @@ pub fn routes() -> Router<AppState> {
             post(acknowledge_observation),
         )
         .route("/reports/cycles/{id}/progress", get(cycle_progress))
+        .route("/reports/cycles/{id}/analytics", get(cycle_analytics))
         .route(
             "/reports/cycles/{id}/teacher-status",
             get(teacher_status_overview),
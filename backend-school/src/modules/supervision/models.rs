@@ -732,6 +732,67 @@ pub struct SupervisionTeacherStatusRow {
     pub next_step_label: String,
 }
 
+/// Mean of every submitted score for one rating item across the cycle
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisionItemAnalytics {
+    pub template_item_id: Uuid,
+    pub section_title: String,
+    pub label: String,
+    pub average_rating: Option<f64>,
+    pub response_count: i64,
+}
+
+/// Ratings of teachers belonging to one subject group or organization unit.
+/// A teacher in several groups counts toward each of them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisionGroupAnalytics {
+    pub id: Uuid,
+    pub name: String,
+    pub teacher_count: i64,
+    pub observation_count: i64,
+    pub average_rating: Option<f64>,
+}
+
+/// How closely evaluators scored the same items of one observation, over
+/// every pair of submitted evaluators
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisionObservationAgreement {
+    pub observation_id: Uuid,
+    pub observed_display_name: String,
+    pub evaluator_count: i64,
+    pub compared_item_count: i64,
+    pub pair_count: i64,
+    pub mean_absolute_difference: f64,
+    pub exact_agreement_rate: f64,
+    pub within_one_point_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisionAgreementSummary {
+    pub compared_observation_count: i64,
+    pub pair_count: i64,
+    pub mean_absolute_difference: Option<f64>,
+    pub exact_agreement_rate: Option<f64>,
+    pub within_one_point_rate: Option<f64>,
+    pub observations: Vec<SupervisionObservationAgreement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisionCycleAnalytics {
+    pub cycle_id: Uuid,
+    pub observation_count: i64,
+    pub average_rating: Option<f64>,
+    pub items: Vec<SupervisionItemAnalytics>,
+    pub subject_groups: Vec<SupervisionGroupAnalytics>,
+    pub organization_units: Vec<SupervisionGroupAnalytics>,
+    pub agreement: SupervisionAgreementSummary,
+}
+
 fn default_required_observations() -> i32 {
     1
 }
@@ -1,13 +1,17 @@
+mod cycle_analytics;
 mod cycles;
 mod evaluations;
+mod observation_report;
 mod observations;
 mod reviews_and_reports;
 mod shared;
 mod templates;
 
+pub use cycle_analytics::cycle_analytics;
 #[allow(unused_imports)]
 pub use cycles::{create_cycle, get_cycle, list_cycles, update_cycle};
 pub use evaluations::{replace_observation_evaluators, submit_my_evaluation};
+pub use observation_report::render_observation_report;
 pub use observations::{
     approve_observation_request, cancel_observation, cancel_requested_observation,
     evaluator_availability, get_observation, list_observations, observation_timetable_options,
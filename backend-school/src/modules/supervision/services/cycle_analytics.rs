@@ -0,0 +1,624 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::supervision::models::{
+    SupervisionAgreementSummary, SupervisionCycleAnalytics, SupervisionGroupAnalytics,
+    SupervisionItemAnalytics, SupervisionObservationAgreement,
+};
+
+use super::shared::SupervisionObservationListAccess;
+
+/// Scores closer than this count as the same rating
+const SCORE_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct AnalyticsScoreRow {
+    observation_id: Uuid,
+    observed_user_id: Uuid,
+    observed_display_name: String,
+    evaluator_id: Uuid,
+    template_item_id: Uuid,
+    section_title: String,
+    section_sort_order: i32,
+    item_label: String,
+    item_sort_order: i32,
+    rating_score: f64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct TeacherGroupRow {
+    user_id: Uuid,
+    organization_unit_id: Uuid,
+    organization_unit_name: String,
+    subject_group_id: Option<Uuid>,
+    subject_group_name: Option<String>,
+}
+
+/// Running totals over pairs of evaluator scores for the same item
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct PairTally {
+    pairs: i64,
+    total_difference: f64,
+    exact: i64,
+    within_one: i64,
+}
+
+impl PairTally {
+    fn add(&mut self, first: f64, second: f64) {
+        let difference = (first - second).abs();
+        self.pairs += 1;
+        self.total_difference += difference;
+        if difference < SCORE_TOLERANCE {
+            self.exact += 1;
+        }
+        if difference <= 1.0 + SCORE_TOLERANCE {
+            self.within_one += 1;
+        }
+    }
+
+    fn merge(&mut self, other: PairTally) {
+        self.pairs += other.pairs;
+        self.total_difference += other.total_difference;
+        self.exact += other.exact;
+        self.within_one += other.within_one;
+    }
+
+    /// Mean absolute difference, exact-agreement rate and within-one-point rate
+    fn rates(self) -> Option<(f64, f64, f64)> {
+        if self.pairs == 0 {
+            return None;
+        }
+        let pairs = self.pairs as f64;
+        Some((
+            self.total_difference / pairs,
+            self.exact as f64 / pairs,
+            self.within_one as f64 / pairs,
+        ))
+    }
+}
+
+/// Item, subject-group and organization-unit rating averages for a cycle plus
+/// inter-evaluator agreement. Only submitted evaluators' rating items count;
+/// with `released_only`, observations whose results are not yet published
+/// are left out.
+pub async fn cycle_analytics(
+    pool: &PgPool,
+    access: SupervisionObservationListAccess,
+    cycle_id: Uuid,
+    released_only: bool,
+) -> Result<SupervisionCycleAnalytics, AppError> {
+    if !access.school && access.organization_unit_ids.is_empty() {
+        return Ok(build_cycle_analytics(cycle_id, &[], &[]));
+    }
+
+    let scores = load_analytics_scores(pool, &access, cycle_id, released_only).await?;
+    let teacher_ids = scores
+        .iter()
+        .map(|row| row.observed_user_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let groups = load_teacher_groups(pool, &teacher_ids).await?;
+
+    let mut analytics = build_cycle_analytics(cycle_id, &scores, &groups);
+    if !access.school {
+        analytics
+            .organization_units
+            .retain(|unit| access.organization_unit_ids.contains(&unit.id));
+    }
+    Ok(analytics)
+}
+
+async fn load_analytics_scores(
+    pool: &PgPool,
+    access: &SupervisionObservationListAccess,
+    cycle_id: Uuid,
+    released_only: bool,
+) -> Result<Vec<AnalyticsScoreRow>, AppError> {
+    let mut builder = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT o.id AS observation_id,
+               o.observed_user_id,
+               COALESCE(NULLIF(TRIM(CONCAT(COALESCE(u.title, ''), u.first_name, ' ', u.last_name)), ''), u.username)
+                   AS observed_display_name,
+               e.id AS evaluator_id,
+               i.id AS template_item_id,
+               s.title AS section_title,
+               s.sort_order AS section_sort_order,
+               i.label AS item_label,
+               i.sort_order AS item_sort_order,
+               r.rating_score::double precision AS rating_score
+        FROM supervision_observations o
+        JOIN users u ON u.id = o.observed_user_id
+        JOIN supervision_evaluators e ON e.observation_id = o.id
+        JOIN supervision_evaluator_responses r ON r.evaluator_id = e.id
+        JOIN supervision_template_items i ON i.id = r.template_item_id
+        JOIN supervision_template_sections s ON s.id = i.section_id
+        WHERE o.cycle_id =
+        "#,
+    );
+    builder.push_bind(cycle_id);
+    builder.push(
+        r#"
+          AND o.status <> 'cancelled'
+          AND e.status = 'submitted'
+          AND i.item_type = 'rating'
+          AND r.rating_score IS NOT NULL
+        "#,
+    );
+
+    if released_only {
+        builder.push(" AND o.status IN ('published', 'acknowledged', 'completed')");
+    }
+
+    if !access.school {
+        builder.push(
+            r#"
+          AND EXISTS (
+              SELECT 1
+              FROM organization_members om_scope
+              WHERE om_scope.user_id = o.observed_user_id
+                AND om_scope.organization_unit_id = ANY(
+            "#,
+        );
+        builder.push_bind(access.organization_unit_ids.clone());
+        builder.push(
+            r#"
+                )
+                AND (om_scope.ended_at IS NULL OR om_scope.ended_at > CURRENT_DATE)
+          )
+            "#,
+        );
+    }
+
+    builder
+        .build_query_as::<AnalyticsScoreRow>()
+        .fetch_all(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                "Failed to load supervision cycle analytics scores: {}",
+                error
+            );
+            AppError::InternalServerError("ไม่สามารถดึงข้อมูลวิเคราะห์ผลนิเทศได้".to_string())
+        })
+}
+
+async fn load_teacher_groups(
+    pool: &PgPool,
+    teacher_ids: &[Uuid],
+) -> Result<Vec<TeacherGroupRow>, AppError> {
+    if teacher_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    sqlx::query_as::<_, TeacherGroupRow>(
+        r#"
+        SELECT DISTINCT om.user_id,
+               ou.id AS organization_unit_id,
+               ou.name AS organization_unit_name,
+               sg.id AS subject_group_id,
+               sg.name_th AS subject_group_name
+        FROM organization_members om
+        JOIN organization_units ou ON ou.id = om.organization_unit_id
+        LEFT JOIN subject_groups sg ON sg.id = ou.subject_group_id
+        WHERE om.user_id = ANY($1)
+          AND (om.ended_at IS NULL OR om.ended_at > CURRENT_DATE)
+        "#,
+    )
+    .bind(teacher_ids)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(
+            "Failed to load supervision analytics teacher groups: {}",
+            error
+        );
+        AppError::InternalServerError("ไม่สามารถดึงกลุ่มสาระของครูได้".to_string())
+    })
+}
+
+fn build_cycle_analytics(
+    cycle_id: Uuid,
+    scores: &[AnalyticsScoreRow],
+    groups: &[TeacherGroupRow],
+) -> SupervisionCycleAnalytics {
+    let mut scores_by_evaluator = HashMap::<Uuid, (Uuid, Vec<f64>)>::new();
+    let mut scores_by_item = HashMap::<Uuid, (&AnalyticsScoreRow, Vec<f64>)>::new();
+    let mut observation_teachers = HashMap::<Uuid, Uuid>::new();
+    for row in scores {
+        scores_by_evaluator
+            .entry(row.evaluator_id)
+            .or_insert_with(|| (row.observation_id, Vec::new()))
+            .1
+            .push(row.rating_score);
+        scores_by_item
+            .entry(row.template_item_id)
+            .or_insert_with(|| (row, Vec::new()))
+            .1
+            .push(row.rating_score);
+        observation_teachers.insert(row.observation_id, row.observed_user_id);
+    }
+
+    // Averages follow cycle progress: the mean of each evaluator's own mean
+    let mut evaluator_averages = HashMap::<Uuid, Vec<f64>>::new();
+    for (observation_id, evaluator_scores) in scores_by_evaluator.into_values() {
+        if let Some(average) = mean(&evaluator_scores) {
+            evaluator_averages
+                .entry(observation_id)
+                .or_default()
+                .push(average);
+        }
+    }
+    let average_rating = mean(
+        &evaluator_averages
+            .values()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>(),
+    );
+
+    let mut items = scores_by_item
+        .into_values()
+        .map(|(row, item_scores)| {
+            (
+                (row.section_sort_order, row.item_sort_order),
+                SupervisionItemAnalytics {
+                    template_item_id: row.template_item_id,
+                    section_title: row.section_title.clone(),
+                    label: row.item_label.clone(),
+                    average_rating: mean(&item_scores),
+                    response_count: item_scores.len() as i64,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|(left_order, left), (right_order, right)| {
+        left_order
+            .cmp(right_order)
+            .then_with(|| left.label.cmp(&right.label))
+    });
+
+    let subject_groups = group_analytics(
+        groups.iter().filter_map(|group| {
+            Some((
+                group.subject_group_id?,
+                group.subject_group_name.clone()?,
+                group.user_id,
+            ))
+        }),
+        &observation_teachers,
+        &evaluator_averages,
+    );
+    let organization_units = group_analytics(
+        groups.iter().map(|group| {
+            (
+                group.organization_unit_id,
+                group.organization_unit_name.clone(),
+                group.user_id,
+            )
+        }),
+        &observation_teachers,
+        &evaluator_averages,
+    );
+
+    SupervisionCycleAnalytics {
+        cycle_id,
+        observation_count: observation_teachers.len() as i64,
+        average_rating,
+        items: items.into_iter().map(|(_, item)| item).collect(),
+        subject_groups,
+        organization_units,
+        agreement: agreement_summary(scores),
+    }
+}
+
+/// Folds `(group id, group name, teacher id)` memberships into per-group
+/// averages over the evaluator averages of that group's observations
+fn group_analytics(
+    memberships: impl Iterator<Item = (Uuid, String, Uuid)>,
+    observation_teachers: &HashMap<Uuid, Uuid>,
+    evaluator_averages: &HashMap<Uuid, Vec<f64>>,
+) -> Vec<SupervisionGroupAnalytics> {
+    let mut groups = HashMap::<Uuid, (String, HashSet<Uuid>)>::new();
+    for (group_id, name, teacher_id) in memberships {
+        groups
+            .entry(group_id)
+            .or_insert_with(|| (name, HashSet::new()))
+            .1
+            .insert(teacher_id);
+    }
+
+    let mut analytics = groups
+        .into_iter()
+        .map(|(id, (name, teachers))| {
+            let observations = observation_teachers
+                .iter()
+                .filter(|(_, teacher_id)| teachers.contains(teacher_id))
+                .map(|(observation_id, _)| *observation_id)
+                .collect::<Vec<_>>();
+            let averages = observations
+                .iter()
+                .filter_map(|observation_id| evaluator_averages.get(observation_id))
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            SupervisionGroupAnalytics {
+                id,
+                name,
+                teacher_count: teachers.len() as i64,
+                observation_count: observations.len() as i64,
+                average_rating: mean(&averages),
+            }
+        })
+        .collect::<Vec<_>>();
+    analytics.sort_by(|left, right| left.name.cmp(&right.name));
+    analytics
+}
+
+/// Pairwise agreement per observation with two or more submitted evaluators,
+/// largest disagreement first, and pooled over every pair in the cycle
+fn agreement_summary(scores: &[AnalyticsScoreRow]) -> SupervisionAgreementSummary {
+    let mut by_observation = HashMap::<Uuid, Vec<&AnalyticsScoreRow>>::new();
+    for row in scores {
+        by_observation
+            .entry(row.observation_id)
+            .or_default()
+            .push(row);
+    }
+
+    let mut total = PairTally::default();
+    let mut observations = Vec::new();
+    for (observation_id, rows) in by_observation {
+        let evaluator_count = rows
+            .iter()
+            .map(|row| row.evaluator_id)
+            .collect::<HashSet<_>>()
+            .len();
+        if evaluator_count < 2 {
+            continue;
+        }
+        let mut scores_by_item = HashMap::<Uuid, Vec<f64>>::new();
+        for row in &rows {
+            scores_by_item
+                .entry(row.template_item_id)
+                .or_default()
+                .push(row.rating_score);
+        }
+        let (compared_item_count, tally) = pairwise_agreement(&scores_by_item);
+        let Some((mean_absolute_difference, exact_agreement_rate, within_one_point_rate)) =
+            tally.rates()
+        else {
+            continue;
+        };
+        total.merge(tally);
+        observations.push(SupervisionObservationAgreement {
+            observation_id,
+            observed_display_name: rows[0].observed_display_name.clone(),
+            evaluator_count: evaluator_count as i64,
+            compared_item_count,
+            pair_count: tally.pairs,
+            mean_absolute_difference,
+            exact_agreement_rate,
+            within_one_point_rate,
+        });
+    }
+    observations.sort_by(|left, right| {
+        right
+            .mean_absolute_difference
+            .total_cmp(&left.mean_absolute_difference)
+            .then_with(|| left.observed_display_name.cmp(&right.observed_display_name))
+    });
+
+    let rates = total.rates();
+    SupervisionAgreementSummary {
+        compared_observation_count: observations.len() as i64,
+        pair_count: total.pairs,
+        mean_absolute_difference: rates.map(|(difference, _, _)| difference),
+        exact_agreement_rate: rates.map(|(_, exact, _)| exact),
+        within_one_point_rate: rates.map(|(_, _, within_one)| within_one),
+        observations,
+    }
+}
+
+/// Compares every pair of evaluator scores on each item rated at least twice.
+/// Returns how many items were compared and the pair totals.
+fn pairwise_agreement(scores_by_item: &HashMap<Uuid, Vec<f64>>) -> (i64, PairTally) {
+    let mut tally = PairTally::default();
+    let mut compared_items = 0;
+    for item_scores in scores_by_item.values() {
+        if item_scores.len() < 2 {
+            continue;
+        }
+        compared_items += 1;
+        for (index, first) in item_scores.iter().enumerate() {
+            for second in &item_scores[index + 1..] {
+                tally.add(*first, *second);
+            }
+        }
+    }
+    (compared_items, tally)
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(
+        observation_id: Uuid,
+        teacher_id: Uuid,
+        evaluator_id: Uuid,
+        item_id: Uuid,
+        item_sort_order: i32,
+        rating_score: f64,
+    ) -> AnalyticsScoreRow {
+        AnalyticsScoreRow {
+            observation_id,
+            observed_user_id: teacher_id,
+            observed_display_name: format!("ครู {teacher_id}"),
+            evaluator_id,
+            template_item_id: item_id,
+            section_title: "การจัดการเรียนรู้".to_string(),
+            section_sort_order: 0,
+            item_label: format!("ข้อ {item_sort_order}"),
+            item_sort_order,
+            rating_score,
+        }
+    }
+
+    #[test]
+    fn pairwise_agreement_compares_every_evaluator_pair_per_item() {
+        let mut scores_by_item = HashMap::new();
+        scores_by_item.insert(Uuid::new_v4(), vec![4.0, 4.0, 2.0]);
+        scores_by_item.insert(Uuid::new_v4(), vec![5.0, 4.0]);
+        scores_by_item.insert(Uuid::new_v4(), vec![3.0]);
+
+        let (compared_items, tally) = pairwise_agreement(&scores_by_item);
+        assert_eq!(compared_items, 2);
+        assert_eq!(tally.pairs, 4);
+        let (difference, exact, within_one) = tally.rates().unwrap();
+        assert!((difference - 1.25).abs() < 1e-9);
+        assert!((exact - 0.25).abs() < 1e-9);
+        assert!((within_one - 0.5).abs() < 1e-9);
+        assert_eq!(PairTally::default().rates(), None);
+    }
+
+    #[test]
+    fn cycle_analytics_average_items_groups_and_agreement() {
+        let (first_teacher, second_teacher) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first_observation, second_observation) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first_item, second_item) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first_evaluator, second_evaluator, third_evaluator) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let scores = vec![
+            score(
+                first_observation,
+                first_teacher,
+                first_evaluator,
+                second_item,
+                2,
+                4.0,
+            ),
+            score(
+                first_observation,
+                first_teacher,
+                first_evaluator,
+                first_item,
+                1,
+                4.0,
+            ),
+            score(
+                first_observation,
+                first_teacher,
+                second_evaluator,
+                first_item,
+                1,
+                2.0,
+            ),
+            score(
+                first_observation,
+                first_teacher,
+                second_evaluator,
+                second_item,
+                2,
+                4.0,
+            ),
+            score(
+                second_observation,
+                second_teacher,
+                third_evaluator,
+                first_item,
+                1,
+                5.0,
+            ),
+        ];
+        let science = Uuid::new_v4();
+        let science_unit = Uuid::new_v4();
+        let homeroom_unit = Uuid::new_v4();
+        let groups = vec![
+            TeacherGroupRow {
+                user_id: first_teacher,
+                organization_unit_id: science_unit,
+                organization_unit_name: "กลุ่มสาระวิทยาศาสตร์".to_string(),
+                subject_group_id: Some(science),
+                subject_group_name: Some("วิทยาศาสตร์".to_string()),
+            },
+            TeacherGroupRow {
+                user_id: second_teacher,
+                organization_unit_id: science_unit,
+                organization_unit_name: "กลุ่มสาระวิทยาศาสตร์".to_string(),
+                subject_group_id: Some(science),
+                subject_group_name: Some("วิทยาศาสตร์".to_string()),
+            },
+            TeacherGroupRow {
+                user_id: second_teacher,
+                organization_unit_id: homeroom_unit,
+                organization_unit_name: "งานระบบดูแลช่วยเหลือนักเรียน".to_string(),
+                subject_group_id: None,
+                subject_group_name: None,
+            },
+        ];
+        let cycle_id = Uuid::new_v4();
+
+        let analytics = build_cycle_analytics(cycle_id, &scores, &groups);
+
+        assert_eq!(analytics.cycle_id, cycle_id);
+        assert_eq!(analytics.observation_count, 2);
+        // evaluator means 4.0, 3.0 and 5.0
+        assert_eq!(analytics.average_rating, Some(4.0));
+        assert_eq!(
+            analytics
+                .items
+                .iter()
+                .map(|item| (
+                    item.template_item_id,
+                    item.average_rating,
+                    item.response_count
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (first_item, Some(11.0 / 3.0), 3),
+                (second_item, Some(4.0), 2)
+            ]
+        );
+
+        assert_eq!(analytics.subject_groups.len(), 1);
+        assert_eq!(analytics.subject_groups[0].teacher_count, 2);
+        assert_eq!(analytics.subject_groups[0].observation_count, 2);
+        assert_eq!(analytics.subject_groups[0].average_rating, Some(4.0));
+        let homeroom = analytics
+            .organization_units
+            .iter()
+            .find(|unit| unit.id == homeroom_unit)
+            .unwrap();
+        assert_eq!(
+            (
+                homeroom.teacher_count,
+                homeroom.observation_count,
+                homeroom.average_rating
+            ),
+            (1, 1, Some(5.0))
+        );
+
+        let agreement = &analytics.agreement;
+        assert_eq!(agreement.compared_observation_count, 1);
+        assert_eq!(agreement.pair_count, 2);
+        assert_eq!(agreement.mean_absolute_difference, Some(1.0));
+        assert_eq!(agreement.exact_agreement_rate, Some(0.5));
+        assert_eq!(agreement.within_one_point_rate, Some(0.5));
+        assert_eq!(agreement.observations[0].observation_id, first_observation);
+        assert_eq!(agreement.observations[0].evaluator_count, 2);
+        assert_eq!(agreement.observations[0].compared_item_count, 2);
+    }
+}
@@ -5,11 +5,14 @@ use sqlx::{PgPool, Postgres, QueryBuilder, Row};
 use uuid::Uuid;
 
 use crate::error::AppError;
+use crate::modules::staff_leave::models::StaffLeaveAbsence;
+use crate::modules::staff_leave::services::{self as staff_leave_service, portion_covers_window};
 use crate::modules::supervision::models::{
     EvaluationResponseInput, ReplaceObservationEvaluatorsRequest, SaveEvaluationRequest,
     SupervisionEvaluatorAvailability, SupervisionEvaluatorConflict, SupervisionEvaluatorStatus,
     SupervisionObservation, SupervisionObservationStatus, SupervisionTemplateItemType,
 };
+use crate::scheduling::SCHOOL_TIMEZONE;
 
 use super::observations::{get_observation, insert_observation_action};
 use super::shared::{
@@ -652,9 +655,37 @@ pub(super) async fn validate_evaluator_availability_for_observation(
         )));
     }
 
+    if let Some(absence) = evaluators_on_leave(pool, evaluator_user_ids, observed_at)
+        .await?
+        .into_values()
+        .next()
+    {
+        return Err(AppError::ValidationError(format!(
+            "{} {}ในวันที่นิเทศ",
+            absence.user_name, absence.leave_type_name
+        )));
+    }
+
     Ok(())
 }
 
+/// Evaluators whose approved leave covers the observation time, keyed by user.
+pub(super) async fn evaluators_on_leave(
+    pool: &PgPool,
+    evaluator_user_ids: &[Uuid],
+    observed_at: DateTime<Utc>,
+) -> Result<HashMap<Uuid, StaffLeaveAbsence>, AppError> {
+    let local = observed_at.with_timezone(&SCHOOL_TIMEZONE);
+    let absences =
+        staff_leave_service::approved_absences_on(pool, evaluator_user_ids, local.date_naive())
+            .await?;
+    Ok(absences
+        .into_iter()
+        .filter(|absence| portion_covers_window(absence.day_portion, local.time(), local.time()))
+        .map(|absence| (absence.user_id, absence))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
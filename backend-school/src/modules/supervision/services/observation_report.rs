@@ -0,0 +1,575 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::supervision::models::{
+    SupervisionAction, SupervisionEvaluatorStatus, SupervisionObservationReview,
+    SupervisionReviewEvaluatorResult, SupervisionTemplateItem, SupervisionTemplateItemType,
+};
+use crate::utils::pdf::{
+    centered_line, draw_wrapped_table, ensure_space, paragraph, school_date, thai_long_date,
+    PdfDocument, PdfFontWeight, PdfTextAlign, TableColumn, A4_PORTRAIT, LINE_HEIGHT, PAGE_MARGIN,
+    SECTION_MIN_HEIGHT,
+};
+
+use super::cycles::get_cycle;
+use super::reviews_and_reports::get_observation_review;
+
+const SIGNATURE_BLOCK_HEIGHT: f32 = 130.0;
+const ORDER_COLUMN_WIDTH: f32 = 28.0;
+const SCORE_COLUMN_WIDTH: f32 = 46.0;
+const MIN_LABEL_COLUMN_WIDTH: f32 = 120.0;
+const DOTTED_NAME: &str = "(..........................................)";
+const DOTTED_DATE: &str = "วันที่ ..........................";
+
+pub struct ObservationReportFile {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+/// One sign-off under the report: who took the workflow step and when
+struct SignOff<'a> {
+    caption: &'static str,
+    role: &'static str,
+    action: Option<&'a SupervisionAction>,
+}
+
+/// Renders the printable result of one observation. Only submitted
+/// evaluators are listed; callers decide whether the actor may see results.
+pub async fn render_observation_report(
+    pool: &PgPool,
+    observation_id: Uuid,
+    school_name: &str,
+) -> Result<ObservationReportFile, AppError> {
+    let review = get_observation_review(pool, observation_id).await?;
+    let cycle = get_cycle(pool, review.observation.cycle_id).await?;
+    let cycle_label = format!(
+        "{} ภาคเรียนที่ {}/{}",
+        cycle.title, cycle.semester, cycle.academic_year
+    );
+
+    Ok(ObservationReportFile {
+        filename: format!("supervision-observation-{}.pdf", observation_id),
+        content: render_observation_report_pdf(school_name, &cycle_label, &review).finish(),
+    })
+}
+
+fn render_observation_report_pdf(
+    school_name: &str,
+    cycle_label: &str,
+    review: &SupervisionObservationReview,
+) -> PdfDocument {
+    let page = A4_PORTRAIT;
+    let observation = &review.observation;
+    let template = &review.template;
+    let evaluators: Vec<&SupervisionReviewEvaluatorResult> = review
+        .evaluator_results
+        .iter()
+        .filter(|evaluator| evaluator.status == SupervisionEvaluatorStatus::Submitted)
+        .collect();
+    let teacher_name = observation
+        .observed_display_name
+        .clone()
+        .unwrap_or_default();
+
+    let mut document = PdfDocument::new(format!("ผลการนิเทศการสอน {}", teacher_name));
+    document.add_page(page);
+    let mut y = PAGE_MARGIN + 16.0;
+    centered_line(
+        &mut document,
+        page,
+        y,
+        16.0,
+        PdfFontWeight::Bold,
+        "แบบรายงานผลการนิเทศการจัดการเรียนรู้",
+    );
+    y += 20.0;
+    centered_line(
+        &mut document,
+        page,
+        y,
+        13.0,
+        PdfFontWeight::Regular,
+        &format!("{} · {}", school_name, template.title),
+    );
+    y += 26.0;
+
+    let lesson = lesson_line(review);
+    let evaluator_names = evaluators
+        .iter()
+        .enumerate()
+        .map(|(index, evaluator)| {
+            let name = evaluator.evaluator_display_name.as_deref().unwrap_or("-");
+            match evaluator.role_label.as_deref() {
+                Some(role) if !role.trim().is_empty() => {
+                    format!("คนที่ {} {} ({})", index + 1, name, role)
+                }
+                _ => format!("คนที่ {} {}", index + 1, name),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let details = [
+        format!("ผู้รับการนิเทศ: {}", teacher_name),
+        format!("รอบการนิเทศ: {}", cycle_label),
+        format!("บทเรียน: {}", lesson),
+        format!(
+            "วันที่สังเกตการสอน: {}",
+            thai_long_date(school_date(observation.observed_at))
+        ),
+        format!(
+            "ผู้ประเมิน: {}",
+            if evaluator_names.is_empty() {
+                "-".to_string()
+            } else {
+                evaluator_names
+            }
+        ),
+    ];
+    for detail in &details {
+        y = paragraph(&mut document, page, y, PdfFontWeight::Regular, detail);
+    }
+
+    let label_width = page.width
+        - 2.0 * PAGE_MARGIN
+        - ORDER_COLUMN_WIDTH
+        - SCORE_COLUMN_WIDTH * (evaluators.len() + 1) as f32;
+    let label_width = label_width.max(MIN_LABEL_COLUMN_WIDTH);
+    let mut columns = vec![
+        TableColumn::new("ที่", ORDER_COLUMN_WIDTH, PdfTextAlign::Center),
+        TableColumn::new("รายการประเมิน", label_width, PdfTextAlign::Left),
+    ];
+    for index in 0..evaluators.len() {
+        columns.push(TableColumn::new(
+            format!("คนที่ {}", index + 1),
+            SCORE_COLUMN_WIDTH,
+            PdfTextAlign::Center,
+        ));
+    }
+    columns.push(TableColumn::new(
+        "เฉลี่ย",
+        SCORE_COLUMN_WIDTH,
+        PdfTextAlign::Center,
+    ));
+
+    let mut item_number = 0;
+    for section in &template.sections {
+        let (rating_items, text_items): (Vec<&SupervisionTemplateItem>, Vec<_>) = section
+            .items
+            .iter()
+            .partition(|item| item.item_type == SupervisionTemplateItemType::Rating);
+
+        y = ensure_space(&mut document, page, y, SECTION_MIN_HEIGHT) + 8.0;
+        y = paragraph(&mut document, page, y, PdfFontWeight::Bold, &section.title);
+
+        if !rating_items.is_empty() {
+            let rows: Vec<Vec<String>> = rating_items
+                .iter()
+                .map(|item| {
+                    item_number += 1;
+                    let scores: Vec<Option<f64>> = evaluators
+                        .iter()
+                        .map(|evaluator| rating_for(evaluator, item.id))
+                        .collect();
+                    let mut row = vec![item_number.to_string(), item.label.clone()];
+                    row.extend(scores.iter().map(|score| format_score(*score)));
+                    row.push(format_score(mean(scores.iter().flatten().copied())));
+                    row
+                })
+                .collect();
+            let continued = format!("{} (ต่อ)", section.title);
+            y = draw_wrapped_table(&mut document, page, &columns, &rows, y + 6.0, &continued);
+        }
+
+        for item in text_items {
+            y = ensure_space(&mut document, page, y, 2.0 * LINE_HEIGHT + 8.0);
+            y += 8.0;
+            y = paragraph(&mut document, page, y, PdfFontWeight::Bold, &item.label);
+            let mut answered = false;
+            for (index, evaluator) in evaluators.iter().enumerate() {
+                if let Some(text) = text_for(evaluator, item.id) {
+                    answered = true;
+                    y = paragraph(
+                        &mut document,
+                        page,
+                        y,
+                        PdfFontWeight::Regular,
+                        &format!("คนที่ {}: {}", index + 1, text),
+                    );
+                }
+            }
+            if !answered {
+                y = paragraph(&mut document, page, y, PdfFontWeight::Regular, "-");
+            }
+        }
+    }
+
+    let rating_max = template.rating_max;
+    y = ensure_space(&mut document, page, y, 2.0 * LINE_HEIGHT);
+    y += 12.0;
+    y = paragraph(
+        &mut document,
+        page,
+        y,
+        PdfFontWeight::Bold,
+        &format!(
+            "คะแนนเฉลี่ยรวม {} จากคะแนนเต็ม {}",
+            format_score(review.average_rating),
+            rating_max
+        ),
+    );
+
+    let comments: Vec<&SupervisionAction> = observation
+        .actions
+        .iter()
+        .filter(|action| {
+            action
+                .comment
+                .as_deref()
+                .is_some_and(|comment| !comment.trim().is_empty())
+        })
+        .collect();
+    if !comments.is_empty() {
+        y = ensure_space(&mut document, page, y, 2.0 * LINE_HEIGHT + 12.0);
+        y += 12.0;
+        y = paragraph(&mut document, page, y, PdfFontWeight::Bold, "ความคิดเห็น");
+        for action in comments {
+            y = paragraph(
+                &mut document,
+                page,
+                y,
+                PdfFontWeight::Regular,
+                &format!(
+                    "{} ({}, {}): {}",
+                    action.actor_display_name.as_deref().unwrap_or("-"),
+                    action_label(&action.action_kind),
+                    thai_long_date(school_date(action.created_at)),
+                    action.comment.as_deref().unwrap_or_default().trim()
+                ),
+            );
+        }
+    }
+
+    let sign_offs = sign_offs(&observation.actions);
+    y = ensure_space(&mut document, page, y, SIGNATURE_BLOCK_HEIGHT);
+    y += 36.0;
+    let slot_width = (page.width - 2.0 * PAGE_MARGIN) / sign_offs.len() as f32;
+    for (index, sign_off) in sign_offs.iter().enumerate() {
+        let x = PAGE_MARGIN + slot_width * (index as f32 + 0.5);
+        let name = sign_off
+            .action
+            .and_then(|action| action.actor_display_name.as_deref())
+            .map(|name| format!("({})", name))
+            .unwrap_or_else(|| DOTTED_NAME.to_string());
+        let date = sign_off
+            .action
+            .map(|action| format!("วันที่ {}", thai_long_date(school_date(action.created_at))))
+            .unwrap_or_else(|| DOTTED_DATE.to_string());
+        let lines = [
+            (0.0, PdfFontWeight::Bold, sign_off.caption.to_string()),
+            (
+                40.0,
+                PdfFontWeight::Regular,
+                "ลงชื่อ ..............................".to_string(),
+            ),
+            (58.0, PdfFontWeight::Regular, name),
+            (76.0, PdfFontWeight::Regular, sign_off.role.to_string()),
+            (94.0, PdfFontWeight::Regular, date),
+        ];
+        for (offset, weight, text) in lines {
+            let text = document.fit_text(&text, 11.0, weight, slot_width - 8.0);
+            document.aligned_text(x, y + offset, 11.0, weight, PdfTextAlign::Center, &text);
+        }
+    }
+
+    document
+}
+
+/// The certify, approve and acknowledge steps, each signed by whoever took
+/// the step most recently
+fn sign_offs(actions: &[SupervisionAction]) -> [SignOff<'_>; 3] {
+    let latest = |kinds: &[&str]| {
+        actions
+            .iter()
+            .filter(|action| kinds.contains(&action.action_kind.as_str()))
+            .max_by_key(|action| action.created_at)
+    };
+    [
+        SignOff {
+            caption: "รับรองผลการนิเทศ",
+            role: "หัวหน้ากลุ่มสาระการเรียนรู้",
+            action: latest(&["subject_group_certified"]),
+        },
+        SignOff {
+            caption: "อนุมัติผลการนิเทศ",
+            role: "ฝ่ายวิชาการ",
+            action: latest(&["academic_approved"]),
+        },
+        SignOff {
+            caption: "รับทราบผลการนิเทศ",
+            role: "ผู้รับการนิเทศ",
+            action: latest(&["acknowledged", "acknowledged_with_comment"]),
+        },
+    ]
+}
+
+fn action_label(action_kind: &str) -> &str {
+    match action_kind {
+        "subject_group_certified" => "รับรองผล",
+        "academic_approved" => "อนุมัติผล",
+        "acknowledged" | "acknowledged_with_comment" => "รับทราบผล",
+        "request_returned" => "ส่งคำขอกลับแก้ไข",
+        "request_cancelled" | "cancelled" => "ยกเลิก",
+        other => other,
+    }
+}
+
+fn lesson_line(review: &SupervisionObservationReview) -> String {
+    let observation = &review.observation;
+    let parts: Vec<Option<&str>> = match &observation.manual_lesson {
+        Some(lesson) => vec![
+            Some(lesson.subject_name.as_str()),
+            Some(lesson.classroom_label.as_str()),
+            Some(lesson.period_label.as_str()),
+            lesson.room_label.as_deref(),
+        ],
+        None => {
+            let snapshot = &observation.lesson_snapshot;
+            vec![
+                snapshot.subject_name.as_deref(),
+                snapshot.classroom_label.as_deref(),
+                snapshot.period_label.as_deref(),
+                snapshot.room_label.as_deref(),
+            ]
+        }
+    };
+    let parts: Vec<&str> = parts
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(" / ")
+    }
+}
+
+fn rating_for(evaluator: &SupervisionReviewEvaluatorResult, item_id: Uuid) -> Option<f64> {
+    evaluator
+        .responses
+        .iter()
+        .find(|response| response.template_item_id == item_id)
+        .and_then(|response| response.rating_score)
+}
+
+fn text_for(evaluator: &SupervisionReviewEvaluatorResult, item_id: Uuid) -> Option<&str> {
+    evaluator
+        .responses
+        .iter()
+        .find(|response| response.template_item_id == item_id)
+        .and_then(|response| response.text_response.as_deref())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+fn mean(scores: impl Iterator<Item = f64>) -> Option<f64> {
+    let (total, count) = scores.fold((0.0, 0), |(total, count), score| (total + score, count + 1));
+    (count > 0).then(|| total / count as f64)
+}
+
+fn format_score(score: Option<f64>) -> String {
+    match score {
+        Some(score) if score.fract() == 0.0 => format!("{:.0}", score),
+        Some(score) => format!("{:.2}", score),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::supervision::models::{
+        LessonSnapshot, SupervisionObservation, SupervisionObservationStatus,
+        SupervisionReviewResponse, SupervisionTemplate, SupervisionTemplateSection,
+        SupervisionTemplateStatus,
+    };
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn action(action_kind: &str, actor: &str, created_at: DateTime<Utc>) -> SupervisionAction {
+        SupervisionAction {
+            id: Uuid::new_v4(),
+            observation_id: Uuid::new_v4(),
+            actor_user_id: Some(Uuid::new_v4()),
+            actor_display_name: Some(actor.to_string()),
+            action_kind: action_kind.to_string(),
+            from_status: None,
+            to_status: None,
+            comment: None,
+            created_at,
+        }
+    }
+
+    fn item(label: &str, item_type: SupervisionTemplateItemType) -> SupervisionTemplateItem {
+        SupervisionTemplateItem {
+            id: Uuid::new_v4(),
+            section_id: Uuid::new_v4(),
+            label: label.to_string(),
+            description: None,
+            item_type,
+            required: true,
+            sort_order: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn evaluator(
+        name: &str,
+        status: SupervisionEvaluatorStatus,
+        responses: Vec<SupervisionReviewResponse>,
+    ) -> SupervisionReviewEvaluatorResult {
+        SupervisionReviewEvaluatorResult {
+            evaluator_id: Uuid::new_v4(),
+            evaluator_user_id: Uuid::new_v4(),
+            evaluator_display_name: Some(name.to_string()),
+            role_label: Some("ผู้นิเทศ".to_string()),
+            status,
+            submitted_at: None,
+            average_rating: None,
+            responses,
+        }
+    }
+
+    #[test]
+    fn sign_offs_take_the_latest_action_of_each_step() {
+        let day = |day| Utc.with_ymd_and_hms(2026, 11, day, 3, 0, 0).unwrap();
+        let actions = vec![
+            action("subject_group_certified", "หัวหน้าเดิม", day(2)),
+            action("subject_group_certified", "หัวหน้าใหม่", day(4)),
+            action("acknowledged_with_comment", "ครูผู้สอน", day(6)),
+        ];
+
+        let sign_offs = sign_offs(&actions);
+        assert_eq!(
+            sign_offs[0]
+                .action
+                .and_then(|action| action.actor_display_name.as_deref()),
+            Some("หัวหน้าใหม่")
+        );
+        assert!(sign_offs[1].action.is_none());
+        assert_eq!(
+            sign_offs[2].action.map(|action| action.created_at),
+            Some(day(6))
+        );
+        assert_eq!(thai_long_date(school_date(day(6))), "6 พฤศจิกายน พ.ศ. 2569");
+        assert_eq!(format_score(Some(4.0)), "4");
+        assert_eq!(format_score(Some(3.666)), "3.67");
+        assert_eq!(format_score(None), "-");
+    }
+
+    #[test]
+    fn report_lists_rating_tables_text_answers_and_signatures() {
+        let rating = item(
+            "ครูจัดกิจกรรมที่เน้นผู้เรียนเป็นสำคัญและใช้สื่อการเรียนรู้ที่หลากหลายเหมาะสมกับวัย",
+            SupervisionTemplateItemType::Rating,
+        );
+        let note = item("ข้อเสนอแนะ", SupervisionTemplateItemType::Text);
+        let response = |item: &SupervisionTemplateItem, score: Option<f64>, text: Option<&str>| {
+            SupervisionReviewResponse {
+                template_item_id: item.id,
+                rating_score: score,
+                text_response: text.map(str::to_string),
+            }
+        };
+        let observed_at = Utc.with_ymd_and_hms(2026, 11, 2, 2, 0, 0).unwrap();
+        let mut acknowledged = action("acknowledged_with_comment", "ครูผู้สอน", observed_at);
+        acknowledged.comment = Some("จะนำไปปรับปรุงในภาคเรียนถัดไป".to_string());
+        let review = SupervisionObservationReview {
+            observation: SupervisionObservation {
+                id: Uuid::new_v4(),
+                cycle_id: Uuid::new_v4(),
+                observed_user_id: Uuid::new_v4(),
+                observed_display_name: Some("นางสาวครู ใจดี".to_string()),
+                requested_by: None,
+                approved_by: None,
+                template_id: Uuid::new_v4(),
+                timetable_entry_id: None,
+                observed_at,
+                manual_lesson: None,
+                lesson_snapshot: LessonSnapshot {
+                    subject_name: Some("วิทยาศาสตร์".to_string()),
+                    classroom_label: Some("ม.2/1".to_string()),
+                    ..LessonSnapshot::default()
+                },
+                status: SupervisionObservationStatus::Completed,
+                requested_at: observed_at,
+                approved_at: None,
+                cancelled_at: None,
+                created_at: observed_at,
+                updated_at: observed_at,
+                evaluators: Vec::new(),
+                actions: vec![
+                    action("subject_group_certified", "หัวหน้ากลุ่มสาระ", observed_at),
+                    acknowledged,
+                ],
+                average_rating: Some(4.5),
+            },
+            template: SupervisionTemplate {
+                id: Uuid::new_v4(),
+                title: "แบบนิเทศชั้นเรียน".to_string(),
+                description: None,
+                status: SupervisionTemplateStatus::Active,
+                rating_min: 1,
+                rating_max: 5,
+                created_by: None,
+                created_at: observed_at,
+                updated_at: observed_at,
+                sections: vec![SupervisionTemplateSection {
+                    id: Uuid::new_v4(),
+                    template_id: Uuid::new_v4(),
+                    title: "การจัดการเรียนรู้".to_string(),
+                    description: None,
+                    sort_order: 0,
+                    created_at: observed_at,
+                    updated_at: observed_at,
+                    items: (0..40)
+                        .map(|_| rating.clone())
+                        .chain([note.clone()])
+                        .collect(),
+                }],
+                steps: Vec::new(),
+            },
+            evaluator_results: vec![
+                evaluator(
+                    "ผู้ประเมิน ก",
+                    SupervisionEvaluatorStatus::Submitted,
+                    vec![
+                        response(&rating, Some(5.0), None),
+                        response(&note, None, Some("ควรเพิ่มเวลาให้นักเรียนอภิปราย")),
+                    ],
+                ),
+                evaluator(
+                    "ผู้ประเมิน ข",
+                    SupervisionEvaluatorStatus::Submitted,
+                    vec![response(&rating, Some(4.0), None)],
+                ),
+                evaluator(
+                    "ผู้ประเมินที่ยังไม่ส่ง",
+                    SupervisionEvaluatorStatus::Draft,
+                    vec![response(&rating, Some(1.0), None)],
+                ),
+            ],
+            item_summaries: Vec::new(),
+            average_rating: Some(4.5),
+        };
+
+        let document =
+            render_observation_report_pdf("โรงเรียนทดสอบ", "รอบที่ 1 ภาคเรียนที่ 2/2569", &review);
+        assert!(document.page_count() >= 2);
+        assert_eq!(lesson_line(&review), "วิทยาศาสตร์ / ม.2/1");
+        let output = String::from_utf8_lossy(&document.finish()).into_owned();
+        assert!(output.starts_with("%PDF"));
+    }
+}
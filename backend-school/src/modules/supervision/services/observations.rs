@@ -17,7 +17,7 @@ use crate::modules::supervision::models::{
 
 use super::cycles::SupervisionCycleTargetRow;
 use super::evaluations::{
-    evaluator_availability_from_row, insert_supervision_evaluators,
+    evaluator_availability_from_row, evaluators_on_leave, insert_supervision_evaluators,
     validate_evaluator_availability_for_observation, EvaluatorAvailabilityRow,
 };
 use super::reviews_and_reports::fetch_observation_average_rating;
@@ -181,10 +181,22 @@ pub async fn evaluator_availability(
         AppError::InternalServerError("ไม่สามารถตรวจสอบผู้ประเมินที่ว่างได้".to_string())
     })?;
 
-    Ok(rows
+    let evaluator_ids = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+    let on_leave = evaluators_on_leave(pool, &evaluator_ids, observation.observed_at).await?;
+    let mut evaluators = rows
         .into_iter()
         .map(evaluator_availability_from_row)
-        .collect())
+        .collect::<Vec<_>>();
+    for evaluator in &mut evaluators {
+        if let Some(absence) = on_leave.get(&evaluator.id) {
+            evaluator.available = false;
+            evaluator
+                .conflict_reason
+                .get_or_insert_with(|| format!("{}ในวันดังกล่าว", absence.leave_type_name));
+        }
+    }
+    evaluators.sort_by_key(|evaluator| !evaluator.available);
+    Ok(evaluators)
 }
 
 pub async fn observation_timetable_options(
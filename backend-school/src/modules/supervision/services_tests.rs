@@ -494,4 +494,29 @@ async fn completed_evaluations_flow_through_certification_approval_acknowledgeme
         Some(SupervisionObservationStatus::Completed)
     );
     assert_eq!(teacher_row.average_rating, Some(3.0));
+
+    let analytics = services::cycle_analytics(
+        &pool,
+        services::SupervisionObservationListAccess::school(),
+        fixture.cycle.id,
+        true,
+    )
+    .await
+    .expect("cycle analytics should load");
+    assert_eq!(analytics.observation_count, 1);
+    assert_eq!(analytics.average_rating, Some(3.0));
+    assert!(!analytics.items.is_empty());
+    assert!(analytics
+        .items
+        .iter()
+        .all(|item| item.average_rating == Some(3.0) && item.response_count == 2));
+    assert_eq!(analytics.agreement.compared_observation_count, 1);
+    assert_eq!(analytics.agreement.mean_absolute_difference, Some(2.0));
+    assert_eq!(analytics.agreement.within_one_point_rate, Some(0.0));
+
+    let report = services::render_observation_report(&pool, requested.id, "โรงเรียนทดสอบ")
+        .await
+        .expect("observation report should render");
+    assert!(report.content.starts_with(b"%PDF"));
+    assert!(report.filename.ends_with(".pdf"));
 }
@@ -0,0 +1,177 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::auth::session_service::{self, AuthenticatedSession};
+use crate::modules::support_access::models::{
+    CreateSupportAccessRequest, DecideSupportAccessRequest, SupportAccessFilter,
+    SupportAccessLookupRequest,
+};
+use crate::modules::support_access::services;
+use crate::permissions::registry::codes;
+use crate::utils::request_context::actor_tenant_context_from_session;
+use crate::utils::tenant::tenant_context_by_subdomain;
+use crate::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_support_access))
+        .route("/{id}", get(get_support_access))
+        .route("/{id}/decision", post(decide_support_access))
+        .route("/{id}/end", post(end_support_access))
+}
+
+/// GET /api/support-access
+pub async fn list_support_access(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Query(filter): Query<SupportAccessFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context.actor.require_permission(codes::SETTINGS_READ_ALL)?;
+
+    let grants = services::list_grants(&context.tenant.pool, filter, Utc::now()).await?;
+    Ok(Json(ApiResponse::ok(grants)))
+}
+
+/// GET /api/support-access/{id}
+pub async fn get_support_access(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context.actor.require_permission(codes::SETTINGS_READ_ALL)?;
+
+    let grant = services::get_grant(&context.tenant.pool, id, Utc::now()).await?;
+    Ok(Json(ApiResponse::ok(grant)))
+}
+
+/// POST /api/support-access/{id}/decision
+pub async fn decide_support_access(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<DecideSupportAccessRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context
+        .actor
+        .require_permission(codes::SETTINGS_UPDATE_ALL)?;
+
+    let grant = services::decide_grant(
+        &context.tenant.pool,
+        context.actor.user_id,
+        id,
+        payload,
+        Utc::now(),
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(grant)))
+}
+
+/// POST /api/support-access/{id}/end — the school cuts a support session short.
+pub async fn end_support_access(
+    State(state): State<AppState>,
+    Extension(session): Extension<AuthenticatedSession>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let context = actor_tenant_context_from_session(&state, &session).await?;
+    context
+        .actor
+        .require_permission(codes::SETTINGS_UPDATE_ALL)?;
+
+    let now = Utc::now();
+    let pool = &context.tenant.pool;
+    let grant = services::end_grant(pool, id, "school", Some(context.actor.user_id), now).await?;
+    session_service::end_support_sessions(
+        pool,
+        &state.auth_runtime.session_events,
+        context.tenant.tenant_id,
+        &context.tenant.subdomain,
+        id,
+        now,
+    )
+    .await?;
+    Ok(Json(ApiResponse::ok(grant)))
+}
+
+// ===================================================================
+// Internal (backend-admin → backend-school)
+// ===================================================================
+
+/// Record a support request raised by platform staff.
+pub async fn create_support_access(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateSupportAccessRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context_by_subdomain(&state, &payload.subdomain).await?;
+    let grant = services::create_grant(
+        &tenant.pool,
+        &state.notification_channel,
+        payload,
+        Utc::now(),
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::ok(grant))))
+}
+
+/// Current state of a grant, so backend-admin can see the school's decision.
+pub async fn support_access_status(
+    State(state): State<AppState>,
+    Json(payload): Json<SupportAccessLookupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context_by_subdomain(&state, &payload.subdomain).await?;
+    let grant = services::get_grant(&tenant.pool, payload.support_access_id, Utc::now()).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(grant))))
+}
+
+/// Issue the single-use handoff token for an approved grant.
+pub async fn issue_support_handoff(
+    State(state): State<AppState>,
+    Json(payload): Json<SupportAccessLookupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context_by_subdomain(&state, &payload.subdomain).await?;
+    let handoff =
+        services::issue_handoff(&tenant.pool, payload.support_access_id, Utc::now()).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(handoff))))
+}
+
+/// End a grant from backend-admin and revoke its sessions.
+pub async fn end_support_access_internal(
+    State(state): State<AppState>,
+    Json(payload): Json<SupportAccessLookupRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tenant = tenant_context_by_subdomain(&state, &payload.subdomain).await?;
+    let now = Utc::now();
+    let grant = services::end_grant(
+        &tenant.pool,
+        payload.support_access_id,
+        "platform",
+        None,
+        now,
+    )
+    .await?;
+    session_service::end_support_sessions(
+        &tenant.pool,
+        &state.auth_runtime.session_events,
+        tenant.tenant_id,
+        &tenant.subdomain,
+        payload.support_access_id,
+        now,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(grant))))
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportAccessScope {
+    /// Every read permission the assisted user holds, and nothing else.
+    ReadOnly,
+    /// Only the permissions listed on the grant that the assisted user holds.
+    Scoped,
+}
+
+impl SupportAccessScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Scoped => "scoped",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "read_only" => Some(Self::ReadOnly),
+            "scoped" => Some(Self::Scoped),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportAccessStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Active,
+    Ended,
+}
+
+impl SupportAccessStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Active => "active",
+            Self::Ended => "ended",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            "active" => Some(Self::Active),
+            "ended" => Some(Self::Ended),
+            _ => None,
+        }
+    }
+}
+
+/// The support grant behind an authenticated session. Carried on
+/// `AuthenticatedSession` so permission loading and the session middleware
+/// can narrow and audit every request without another lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportSessionScope {
+    pub support_access_id: Uuid,
+    pub ticket_reference: String,
+    pub admin_name: String,
+    pub scope: SupportAccessScope,
+    pub permissions: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessGrant {
+    pub id: Uuid,
+    pub target_user_id: Uuid,
+    pub target_user_name: String,
+    pub platform_admin_name: String,
+    pub ticket_reference: String,
+    pub reason: Option<String>,
+    pub scope: String,
+    pub permissions: Vec<String>,
+    pub duration_minutes: i32,
+    pub requires_approval: bool,
+    pub status: String,
+    pub requested_at: DateTime<Utc>,
+    pub start_by: DateTime<Utc>,
+    pub decided_by_name: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decision_note: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub ended_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessFilter {
+    pub status: Option<SupportAccessStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportAccessDecision {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecideSupportAccessRequest {
+    pub decision: SupportAccessDecision,
+    pub note: Option<String>,
+}
+
+// ===================================================================
+// Internal (backend-admin → backend-school)
+// ===================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSupportAccessRequest {
+    pub subdomain: String,
+    pub support_access_id: Uuid,
+    pub platform_admin_id: Uuid,
+    pub platform_admin_name: String,
+    pub ticket_reference: String,
+    pub reason: Option<String>,
+    pub scope: SupportAccessScope,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    pub duration_minutes: i32,
+    pub requires_approval: bool,
+    /// Defaults to the longest-serving active school administrator.
+    pub target_username: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportAccessLookupRequest {
+    pub subdomain: String,
+    pub support_access_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportHandoffData {
+    pub grant: SupportAccessGrant,
+    /// Single-use token the school frontend exchanges for a session cookie.
+    pub token: String,
+    pub token_expires_at: DateTime<Utc>,
+}
+
+/// Body of `POST /api/auth/support-handoff`.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportHandoffRequest {
+    pub token: String,
+}
@@ -0,0 +1,817 @@
+use axum::http::Method;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::{PgPool, Postgres, Transaction};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::modules::auth::session_crypto::{RawSessionToken, TokenHash};
+use crate::modules::notification::events::TenantNotificationEvent;
+use crate::modules::support_access::models::{
+    CreateSupportAccessRequest, DecideSupportAccessRequest, SupportAccessDecision,
+    SupportAccessFilter, SupportAccessGrant, SupportAccessScope, SupportAccessStatus,
+    SupportHandoffData, SupportSessionScope,
+};
+use crate::permissions::registry::{codes, ALL_PERMISSIONS};
+use crate::services::notification::{
+    NotificationService, NotificationType, TenantNotificationPublisher,
+};
+use crate::utils::audit::AuditLogBuilder;
+
+pub const MIN_DURATION_MINUTES: i32 = 5;
+pub const MAX_DURATION_MINUTES: i32 = 240;
+const MAX_TICKET_REFERENCE_CHARS: usize = 100;
+/// How long a requested or approved grant may wait before support opens it.
+const START_WINDOW: Duration = Duration::hours(24);
+/// The handoff link is opened by the support agent immediately after it is issued.
+const HANDOFF_TTL: Duration = Duration::minutes(2);
+const SUPPORT_ACCESS_LINK: &str = "/staff/settings/support-access";
+const AUDIT_ENTITY_TYPE: &str = "support_access";
+const MAX_AUDIT_PATH_CHARS: usize = 500;
+
+/// Read-only support sessions may still use these POST routes because they
+/// only read data.
+const READ_ONLY_POST_ROUTES: &[&str] = &["/api/academic/planning/courses/instructors/batch"];
+
+const GRANT_COLUMNS: &str = r#"
+    SELECT g.id, g.target_user_id,
+           btrim(concat_ws(' ', target.first_name, target.last_name)) AS target_user_name,
+           g.platform_admin_name, g.ticket_reference, g.reason, g.scope, g.permissions,
+           g.duration_minutes, g.requires_approval, g.status, g.requested_at, g.start_by,
+           NULLIF(btrim(concat_ws(' ', decider.first_name, decider.last_name)), '')
+               AS decided_by_name,
+           g.decided_at, g.decision_note, g.started_at, g.expires_at, g.ended_at, g.ended_by
+    FROM support_access_grants g
+    JOIN users target ON target.id = g.target_user_id
+    LEFT JOIN users decider ON decider.id = g.decided_by
+"#;
+
+/// A grant whose handoff token was just exchanged for a session.
+pub struct ClaimedSupportGrant {
+    pub target_user_id: Uuid,
+    pub scope: SupportSessionScope,
+}
+
+#[derive(sqlx::FromRow)]
+struct ClaimRow {
+    id: Uuid,
+    target_user_id: Uuid,
+    platform_admin_name: String,
+    ticket_reference: String,
+    scope: String,
+    permissions: Vec<String>,
+    duration_minutes: i32,
+    status: String,
+    start_by: DateTime<Utc>,
+    handoff_expires_at: Option<DateTime<Utc>>,
+}
+
+// ===================================================================
+// Session narrowing
+// ===================================================================
+
+fn is_read_permission(code: &str) -> bool {
+    matches!(code.split('.').nth(1), Some("read" | "download"))
+}
+
+/// Effective permissions of a support session: the assisted user's own
+/// permissions cut down to the grant. A wildcard holder is expanded to the
+/// concrete registry codes so the wildcard itself never reaches the session.
+pub fn narrow_permissions(scope: &SupportSessionScope, permissions: Vec<String>) -> Vec<String> {
+    let has_wildcard = permissions
+        .iter()
+        .any(|permission| permission == codes::WILDCARD);
+
+    let mut narrowed: Vec<String> = match scope.scope {
+        SupportAccessScope::ReadOnly if has_wildcard => ALL_PERMISSIONS
+            .iter()
+            .map(|definition| definition.code)
+            .filter(|code| is_read_permission(code))
+            .map(str::to_string)
+            .collect(),
+        SupportAccessScope::ReadOnly => permissions
+            .into_iter()
+            .filter(|code| is_read_permission(code))
+            .collect(),
+        SupportAccessScope::Scoped => scope
+            .permissions
+            .iter()
+            .filter(|code| has_wildcard || permissions.contains(code))
+            .cloned()
+            .collect(),
+    };
+    narrowed.sort();
+    narrowed.dedup();
+    narrowed
+}
+
+fn is_read_only_post(path: &str) -> bool {
+    READ_ONLY_POST_ROUTES.contains(&path)
+        || path
+            .strip_prefix("/api/files/")
+            .and_then(|rest| rest.strip_suffix("/download"))
+            .and_then(|id| id.parse::<Uuid>().ok())
+            .is_some()
+}
+
+/// Routes a support session may never use whatever its scope: account
+/// security of the assisted user and the support access settings themselves.
+fn is_account_security_route(method: &Method, path: &str) -> bool {
+    path == "/api/support-access"
+        || path.starts_with("/api/support-access/")
+        || (method == Method::POST && path == "/api/auth/me/change-password")
+        || (method == Method::POST && path == "/api/auth/logout-all")
+        || (method == Method::PUT && path == "/api/auth/me/profile")
+        || (method == Method::DELETE && path.starts_with("/api/auth/sessions/"))
+}
+
+/// Whether a support session may make this request at all. Permission checks
+/// in the handlers still apply on top.
+pub fn request_allowed(scope: &SupportSessionScope, method: &Method, path: &str) -> bool {
+    if is_account_security_route(method, path) {
+        return false;
+    }
+    match scope.scope {
+        SupportAccessScope::Scoped => true,
+        SupportAccessScope::ReadOnly => {
+            matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+                || (method == Method::POST && is_read_only_post(path))
+        }
+    }
+}
+
+/// Writes one request made during a support session to the school's audit log.
+pub async fn record_support_request(
+    pool: &PgPool,
+    user_id: Uuid,
+    scope: &SupportSessionScope,
+    method: &Method,
+    path: &str,
+    status: u16,
+) -> Result<(), sqlx::Error> {
+    let request_path: String = path.chars().take(MAX_AUDIT_PATH_CHARS).collect();
+    AuditLogBuilder::new("support_request", AUDIT_ENTITY_TYPE)
+        .user(user_id, None, Some(support_actor_name(scope)))
+        .entity(
+            scope.support_access_id,
+            Some(scope.ticket_reference.clone()),
+        )
+        .request_context(None, None, Some(request_path), Some(method.to_string()))
+        .metadata(json!({
+            "ticketReference": scope.ticket_reference,
+            "scope": scope.scope.as_str(),
+            "status": status,
+        }))
+        .save(pool)
+        .await?;
+    Ok(())
+}
+
+/// Marks the moment a support agent entered the school as the assisted user.
+pub async fn record_session_started(
+    pool: &PgPool,
+    user_id: Uuid,
+    scope: &SupportSessionScope,
+) -> Result<(), sqlx::Error> {
+    AuditLogBuilder::new("support_session_started", AUDIT_ENTITY_TYPE)
+        .user(user_id, None, Some(support_actor_name(scope)))
+        .entity(
+            scope.support_access_id,
+            Some(scope.ticket_reference.clone()),
+        )
+        .metadata(json!({
+            "ticketReference": scope.ticket_reference,
+            "scope": scope.scope.as_str(),
+            "permissions": scope.permissions,
+            "expiresAt": scope.expires_at,
+        }))
+        .save(pool)
+        .await?;
+    Ok(())
+}
+
+fn support_actor_name(scope: &SupportSessionScope) -> String {
+    format!("{} (ฝ่ายสนับสนุน)", scope.admin_name)
+}
+
+// ===================================================================
+// Grants
+// ===================================================================
+
+fn validate_ticket_reference(value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::ValidationError(
+            "ต้องระบุเลขที่ตั๋วงานสนับสนุน".to_string(),
+        ));
+    }
+    if value.chars().count() > MAX_TICKET_REFERENCE_CHARS {
+        return Err(AppError::ValidationError(
+            "เลขที่ตั๋วงานสนับสนุนยาวเกินไป".to_string(),
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Scoped grants must name concrete registry permissions; read-only grants
+/// take none because they follow the assisted user's read permissions.
+fn validate_grant_permissions(
+    scope: SupportAccessScope,
+    permissions: &[String],
+) -> Result<Vec<String>, AppError> {
+    match scope {
+        SupportAccessScope::ReadOnly if permissions.is_empty() => Ok(Vec::new()),
+        SupportAccessScope::ReadOnly => Err(AppError::ValidationError(
+            "สิทธิ์แบบอ่านอย่างเดียวไม่ต้องระบุรายการสิทธิ์".to_string(),
+        )),
+        SupportAccessScope::Scoped => {
+            if permissions.is_empty() {
+                return Err(AppError::ValidationError(
+                    "ต้องระบุสิทธิ์อย่างน้อยหนึ่งรายการ".to_string(),
+                ));
+            }
+            let mut normalized = Vec::with_capacity(permissions.len());
+            for permission in permissions {
+                let permission = permission.trim();
+                if permission == codes::WILDCARD
+                    || !ALL_PERMISSIONS
+                        .iter()
+                        .any(|definition| definition.code == permission)
+                {
+                    return Err(AppError::ValidationError(format!("ไม่รู้จักสิทธิ์ {permission}")));
+                }
+                normalized.push(permission.to_string());
+            }
+            normalized.sort();
+            normalized.dedup();
+            Ok(normalized)
+        }
+    }
+}
+
+fn validate_duration(duration_minutes: i32) -> Result<(), AppError> {
+    if (MIN_DURATION_MINUTES..=MAX_DURATION_MINUTES).contains(&duration_minutes) {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(format!(
+            "ระยะเวลาต้องอยู่ระหว่าง {MIN_DURATION_MINUTES} ถึง {MAX_DURATION_MINUTES} นาที"
+        )))
+    }
+}
+
+fn initial_status(requires_approval: bool) -> SupportAccessStatus {
+    if requires_approval {
+        SupportAccessStatus::Pending
+    } else {
+        SupportAccessStatus::Approved
+    }
+}
+
+fn read_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to read support access grant: {}", error);
+    AppError::InternalServerError("ไม่สามารถดึงข้อมูลการเข้าช่วยเหลือได้".to_string())
+}
+
+fn write_error(error: sqlx::Error) -> AppError {
+    tracing::error!("Failed to write support access grant: {}", error);
+    AppError::InternalServerError("ไม่สามารถบันทึกข้อมูลการเข้าช่วยเหลือได้".to_string())
+}
+
+fn not_found() -> AppError {
+    AppError::NotFound("ไม่พบคำขอเข้าช่วยเหลือ".to_string())
+}
+
+/// Grants that were never opened in time, or whose session ran out, are
+/// closed lazily before anyone reads them.
+async fn close_expired_grants(pool: &PgPool, now: DateTime<Utc>) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE support_access_grants
+        SET status = 'ended', ended_at = $1, ended_by = 'expired',
+            handoff_token_hash = NULL, handoff_expires_at = NULL
+        WHERE (status IN ('pending', 'approved') AND start_by <= $1)
+           OR (status = 'active' AND expires_at <= $1)
+        "#,
+    )
+    .bind(now)
+    .execute(pool)
+    .await
+    .map_err(write_error)?;
+    Ok(())
+}
+
+async fn find_grant(pool: &PgPool, id: Uuid) -> Result<SupportAccessGrant, AppError> {
+    let query = format!("{GRANT_COLUMNS} WHERE g.id = $1");
+    sqlx::query_as::<_, SupportAccessGrant>(&query)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?
+        .ok_or_else(not_found)
+}
+
+async fn resolve_target_user(
+    pool: &PgPool,
+    target_username: Option<&str>,
+) -> Result<Uuid, AppError> {
+    let target = match target_username
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        Some(username) => sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT id FROM users
+            WHERE username = $1 AND status = 'active' AND user_type = 'staff'
+            "#,
+        )
+        .bind(username)
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?,
+        None => sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT u.id
+            FROM users u
+            JOIN user_roles ur ON ur.user_id = u.id AND ur.ended_at IS NULL
+            JOIN roles r ON r.id = ur.role_id AND r.code = 'ADMIN' AND r.is_active = true
+            WHERE u.status = 'active' AND u.user_type = 'staff'
+            ORDER BY ur.started_at, u.created_at, u.id
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(pool)
+        .await
+        .map_err(read_error)?,
+    };
+
+    target.ok_or_else(|| AppError::NotFound("ไม่พบบุคลากรที่จะเข้าช่วยเหลือ".to_string()))
+}
+
+/// Records a support request from backend-admin. The assisted user is told
+/// either way; with approval required nothing can start until a school
+/// administrator approves it.
+pub async fn create_grant(
+    pool: &PgPool,
+    notification_channel: &broadcast::Sender<TenantNotificationEvent>,
+    payload: CreateSupportAccessRequest,
+    now: DateTime<Utc>,
+) -> Result<SupportAccessGrant, AppError> {
+    let ticket_reference = validate_ticket_reference(&payload.ticket_reference)?;
+    let permissions = validate_grant_permissions(payload.scope, &payload.permissions)?;
+    validate_duration(payload.duration_minutes)?;
+    let admin_name = payload.platform_admin_name.trim();
+    if admin_name.is_empty() {
+        return Err(AppError::ValidationError(
+            "ต้องระบุชื่อเจ้าหน้าที่ฝ่ายสนับสนุน".to_string(),
+        ));
+    }
+    let target_user_id = resolve_target_user(pool, payload.target_username.as_deref()).await?;
+    let status = initial_status(payload.requires_approval);
+
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO support_access_grants (
+            id, target_user_id, platform_admin_id, platform_admin_name, ticket_reference,
+            reason, scope, permissions, duration_minutes, requires_approval, status,
+            requested_at, start_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(payload.support_access_id)
+    .bind(target_user_id)
+    .bind(payload.platform_admin_id)
+    .bind(admin_name)
+    .bind(&ticket_reference)
+    .bind(
+        payload
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty()),
+    )
+    .bind(payload.scope.as_str())
+    .bind(&permissions)
+    .bind(payload.duration_minutes)
+    .bind(payload.requires_approval)
+    .bind(status.as_str())
+    .bind(now)
+    .bind(now + START_WINDOW)
+    .execute(pool)
+    .await
+    .map_err(write_error)?
+    .rows_affected();
+    if inserted == 0 {
+        return Err(AppError::Conflict("มีคำขอเข้าช่วยเหลือนี้อยู่แล้ว".to_string()));
+    }
+
+    let grant = find_grant(pool, payload.support_access_id).await?;
+    let (title, message) = if payload.requires_approval {
+        (
+            "คำขอเข้าช่วยเหลือจากฝ่ายสนับสนุน",
+            format!("{admin_name} ขอเข้าใช้งานในนามของคุณ (ตั๋ว {ticket_reference}) กรุณาอนุมัติหรือปฏิเสธ"),
+        )
+    } else {
+        (
+            "ฝ่ายสนับสนุนจะเข้าช่วยเหลือ",
+            format!("{admin_name} จะเข้าใช้งานในนามของคุณชั่วคราว (ตั๋ว {ticket_reference})"),
+        )
+    };
+    let publisher = TenantNotificationPublisher::new(&payload.subdomain, notification_channel);
+    if let Err(error) = NotificationService::send(
+        pool,
+        &publisher,
+        target_user_id,
+        title,
+        &message,
+        NotificationType::Warning,
+        Some(SUPPORT_ACCESS_LINK),
+    )
+    .await
+    {
+        tracing::error!(
+            support_access_id = %grant.id,
+            error = %error,
+            "Failed to notify user about support access request"
+        );
+    }
+
+    Ok(grant)
+}
+
+pub async fn get_grant(
+    pool: &PgPool,
+    id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<SupportAccessGrant, AppError> {
+    close_expired_grants(pool, now).await?;
+    find_grant(pool, id).await
+}
+
+pub async fn list_grants(
+    pool: &PgPool,
+    filter: SupportAccessFilter,
+    now: DateTime<Utc>,
+) -> Result<Vec<SupportAccessGrant>, AppError> {
+    close_expired_grants(pool, now).await?;
+    let query = format!(
+        "{GRANT_COLUMNS} WHERE ($1::text IS NULL OR g.status = $1) \
+         ORDER BY g.requested_at DESC LIMIT 200"
+    );
+    sqlx::query_as::<_, SupportAccessGrant>(&query)
+        .bind(filter.status.map(SupportAccessStatus::as_str))
+        .fetch_all(pool)
+        .await
+        .map_err(read_error)
+}
+
+/// Approve or reject a pending grant on behalf of the school.
+pub async fn decide_grant(
+    pool: &PgPool,
+    actor_user_id: Uuid,
+    id: Uuid,
+    payload: DecideSupportAccessRequest,
+    now: DateTime<Utc>,
+) -> Result<SupportAccessGrant, AppError> {
+    close_expired_grants(pool, now).await?;
+    let status = match payload.decision {
+        SupportAccessDecision::Approve => SupportAccessStatus::Approved,
+        SupportAccessDecision::Reject => SupportAccessStatus::Rejected,
+    };
+    let note = payload
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE support_access_grants
+        SET status = $1, decided_by = $2, decided_at = $3, decision_note = $4
+        WHERE id = $5 AND status = 'pending'
+        "#,
+    )
+    .bind(status.as_str())
+    .bind(actor_user_id)
+    .bind(now)
+    .bind(note)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?
+    .rows_affected();
+    if updated == 0 {
+        find_grant(pool, id).await?;
+        return Err(AppError::Conflict("คำขอนี้ไม่ได้รออนุมัติแล้ว".to_string()));
+    }
+
+    let grant = find_grant(pool, id).await?;
+    if let Err(error) =
+        AuditLogBuilder::new(format!("support_{}", status.as_str()), AUDIT_ENTITY_TYPE)
+            .user(actor_user_id, None, None)
+            .entity(grant.id, Some(grant.ticket_reference.clone()))
+            .metadata(json!({ "note": note }))
+            .save(pool)
+            .await
+    {
+        tracing::error!(support_access_id = %grant.id, error = %error, "Failed to audit support access decision");
+    }
+    Ok(grant)
+}
+
+/// Issue the single-use token that turns an approved grant into a session.
+/// Only the SHA-256 of the token is stored.
+pub async fn issue_handoff(
+    pool: &PgPool,
+    id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<SupportHandoffData, AppError> {
+    close_expired_grants(pool, now).await?;
+    let grant = find_grant(pool, id).await?;
+    match SupportAccessStatus::from_code(&grant.status) {
+        Some(SupportAccessStatus::Approved) => {}
+        Some(SupportAccessStatus::Pending) => {
+            return Err(AppError::Conflict("คำขอยังรอการอนุมัติจากโรงเรียน".to_string()))
+        }
+        _ => return Err(AppError::Conflict("คำขอนี้ไม่สามารถเริ่มเซสชันได้แล้ว".to_string())),
+    }
+
+    let token = RawSessionToken::generate()?;
+    let token_expires_at = now + HANDOFF_TTL;
+    let updated = sqlx::query(
+        r#"
+        UPDATE support_access_grants
+        SET handoff_token_hash = $1, handoff_expires_at = $2
+        WHERE id = $3 AND status = 'approved'
+        "#,
+    )
+    .bind(token.token_hash().as_bytes().as_slice())
+    .bind(token_expires_at)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AppError::Conflict("คำขอนี้ไม่สามารถเริ่มเซสชันได้แล้ว".to_string()));
+    }
+
+    Ok(SupportHandoffData {
+        grant,
+        token: token.encode().expose_for_cookie().to_string(),
+        token_expires_at,
+    })
+}
+
+fn claimable(row: &ClaimRow, now: DateTime<Utc>) -> bool {
+    row.status == SupportAccessStatus::Approved.as_str()
+        && row.start_by > now
+        && row
+            .handoff_expires_at
+            .is_some_and(|expires_at| expires_at > now)
+}
+
+/// Consume a handoff token inside the caller's session transaction and mark
+/// the grant active. Returns `None` for unknown, used or expired tokens.
+pub async fn claim_handoff(
+    transaction: &mut Transaction<'_, Postgres>,
+    token_hash: TokenHash,
+    now: DateTime<Utc>,
+) -> Result<Option<ClaimedSupportGrant>, AppError> {
+    let row = sqlx::query_as::<_, ClaimRow>(
+        r#"
+        SELECT id, target_user_id, platform_admin_name, ticket_reference, scope, permissions,
+               duration_minutes, status, start_by, handoff_expires_at
+        FROM support_access_grants
+        WHERE handoff_token_hash = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(token_hash.as_bytes().as_slice())
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(read_error)?;
+    let Some(row) = row.filter(|row| claimable(row, now)) else {
+        return Ok(None);
+    };
+    let Some(scope) = SupportAccessScope::from_code(&row.scope) else {
+        return Ok(None);
+    };
+
+    let expires_at = now + Duration::minutes(i64::from(row.duration_minutes));
+    sqlx::query(
+        r#"
+        UPDATE support_access_grants
+        SET status = 'active', started_at = $1, expires_at = $2,
+            handoff_token_hash = NULL, handoff_expires_at = NULL
+        WHERE id = $3
+        "#,
+    )
+    .bind(now)
+    .bind(expires_at)
+    .bind(row.id)
+    .execute(&mut **transaction)
+    .await
+    .map_err(write_error)?;
+
+    Ok(Some(ClaimedSupportGrant {
+        target_user_id: row.target_user_id,
+        scope: SupportSessionScope {
+            support_access_id: row.id,
+            ticket_reference: row.ticket_reference,
+            admin_name: row.platform_admin_name,
+            scope,
+            permissions: row.permissions,
+            expires_at,
+        },
+    }))
+}
+
+/// End a grant from either side. Its sessions are revoked by the caller.
+pub async fn end_grant(
+    pool: &PgPool,
+    id: Uuid,
+    ended_by: &str,
+    actor_user_id: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<SupportAccessGrant, AppError> {
+    close_expired_grants(pool, now).await?;
+    let ended = sqlx::query(
+        r#"
+        UPDATE support_access_grants
+        SET status = 'ended', ended_at = $1, ended_by = $2,
+            handoff_token_hash = NULL, handoff_expires_at = NULL
+        WHERE id = $3 AND status IN ('pending', 'approved', 'active')
+        "#,
+    )
+    .bind(now)
+    .bind(ended_by)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(write_error)?
+    .rows_affected();
+
+    let grant = find_grant(pool, id).await?;
+    if ended > 0 {
+        let mut entry = AuditLogBuilder::new("support_ended", AUDIT_ENTITY_TYPE)
+            .entity(grant.id, Some(grant.ticket_reference.clone()))
+            .metadata(json!({ "endedBy": ended_by }));
+        if let Some(actor_user_id) = actor_user_id {
+            entry = entry.user(actor_user_id, None, None);
+        }
+        if let Err(error) = entry.save(pool).await {
+            tracing::error!(support_access_id = %grant.id, error = %error, "Failed to audit support access end");
+        }
+    }
+    Ok(grant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(kind: SupportAccessScope, permissions: &[&str]) -> SupportSessionScope {
+        SupportSessionScope {
+            support_access_id: Uuid::nil(),
+            ticket_reference: "SUP-1024".to_string(),
+            admin_name: "Platform Support".to_string(),
+            scope: kind,
+            permissions: permissions.iter().map(|code| code.to_string()).collect(),
+            expires_at: Utc::now() + Duration::minutes(30),
+        }
+    }
+
+    fn owned(codes: &[&str]) -> Vec<String> {
+        codes.iter().map(|code| code.to_string()).collect()
+    }
+
+    #[test]
+    fn read_only_keeps_only_read_permissions_of_the_user() {
+        let narrowed = narrow_permissions(
+            &scope(SupportAccessScope::ReadOnly, &[]),
+            owned(&[
+                codes::SETTINGS_UPDATE_ALL,
+                codes::SETTINGS_READ_ALL,
+                codes::ROLES_READ_ALL,
+            ]),
+        );
+
+        assert_eq!(
+            narrowed,
+            owned(&[codes::ROLES_READ_ALL, codes::SETTINGS_READ_ALL])
+        );
+    }
+
+    #[test]
+    fn read_only_expands_wildcard_without_keeping_it() {
+        let narrowed = narrow_permissions(
+            &scope(SupportAccessScope::ReadOnly, &[]),
+            owned(&[codes::WILDCARD]),
+        );
+
+        assert!(!narrowed.iter().any(|code| code == codes::WILDCARD));
+        assert!(narrowed.contains(&codes::SETTINGS_READ_ALL.to_string()));
+        assert!(!narrowed.contains(&codes::SETTINGS_UPDATE_ALL.to_string()));
+    }
+
+    #[test]
+    fn scoped_grant_never_exceeds_the_users_own_permissions() {
+        let grant = scope(
+            SupportAccessScope::Scoped,
+            &[codes::SETTINGS_UPDATE_ALL, codes::ROLES_UPDATE_ALL],
+        );
+
+        assert_eq!(
+            narrow_permissions(&grant, owned(&[codes::SETTINGS_UPDATE_ALL])),
+            owned(&[codes::SETTINGS_UPDATE_ALL])
+        );
+        assert_eq!(
+            narrow_permissions(&grant, owned(&[codes::WILDCARD])),
+            owned(&[codes::ROLES_UPDATE_ALL, codes::SETTINGS_UPDATE_ALL])
+        );
+    }
+
+    #[test]
+    fn read_only_sessions_cannot_write_except_read_style_posts() {
+        let grant = scope(SupportAccessScope::ReadOnly, &[]);
+        let file_download = format!("/api/files/{}/download", Uuid::nil());
+
+        assert!(request_allowed(&grant, &Method::GET, "/api/staff"));
+        assert!(!request_allowed(&grant, &Method::POST, "/api/staff"));
+        assert!(!request_allowed(&grant, &Method::DELETE, "/api/staff/1"));
+        assert!(request_allowed(&grant, &Method::POST, &file_download));
+        assert!(request_allowed(
+            &grant,
+            &Method::POST,
+            "/api/academic/planning/courses/instructors/batch"
+        ));
+    }
+
+    #[test]
+    fn support_sessions_cannot_touch_account_security_or_support_settings() {
+        let grant = scope(SupportAccessScope::Scoped, &[codes::SETTINGS_UPDATE_ALL]);
+
+        assert!(request_allowed(&grant, &Method::POST, "/api/staff"));
+        assert!(!request_allowed(
+            &grant,
+            &Method::POST,
+            "/api/auth/me/change-password"
+        ));
+        assert!(!request_allowed(
+            &grant,
+            &Method::POST,
+            "/api/auth/logout-all"
+        ));
+        assert!(!request_allowed(
+            &grant,
+            &Method::PUT,
+            "/api/auth/me/profile"
+        ));
+        assert!(!request_allowed(
+            &grant,
+            &Method::GET,
+            "/api/support-access"
+        ));
+        assert!(!request_allowed(
+            &grant,
+            &Method::POST,
+            "/api/support-access/00000000-0000-0000-0000-000000000000/end"
+        ));
+    }
+
+    #[test]
+    fn scoped_grants_require_known_concrete_permissions() {
+        assert!(validate_grant_permissions(SupportAccessScope::Scoped, &[]).is_err());
+        assert!(
+            validate_grant_permissions(SupportAccessScope::Scoped, &owned(&[codes::WILDCARD]))
+                .is_err()
+        );
+        assert!(validate_grant_permissions(
+            SupportAccessScope::ReadOnly,
+            &owned(&[codes::SETTINGS_READ_ALL])
+        )
+        .is_err());
+        assert_eq!(
+            validate_grant_permissions(
+                SupportAccessScope::Scoped,
+                &owned(&[codes::SETTINGS_READ_ALL, codes::SETTINGS_READ_ALL])
+            )
+            .unwrap(),
+            owned(&[codes::SETTINGS_READ_ALL])
+        );
+    }
+
+    #[test]
+    fn duration_and_ticket_are_bounded() {
+        assert!(validate_duration(MIN_DURATION_MINUTES).is_ok());
+        assert!(validate_duration(MAX_DURATION_MINUTES).is_ok());
+        assert!(validate_duration(MAX_DURATION_MINUTES + 1).is_err());
+        assert!(validate_ticket_reference("   ").is_err());
+        assert_eq!(validate_ticket_reference(" SUP-1 ").unwrap(), "SUP-1");
+        assert_eq!(initial_status(true), SupportAccessStatus::Pending);
+        assert_eq!(initial_status(false), SupportAccessStatus::Approved);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod feature_toggles;
 pub mod health;
+pub mod key_rotation;
+pub mod metrics;
 pub mod migration;
 pub mod provision;
 pub mod register_routes;
+pub mod snapshot;
+pub mod usage;
@@ -8,7 +8,9 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::api_response::ApiErrorResponse;
+use crate::db::admin_client::TenantPlan;
 use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
 use crate::modules::auth::session_service::AuthenticatedSession;
 use crate::modules::menu::models::FeatureToggle;
 use crate::modules::system::services::feature_toggle_service;
@@ -35,6 +37,22 @@ pub struct FeatureListResponse {
     pub data: Vec<FeatureToggle>,
 }
 
+fn ensure_feature_access(
+    actor: &ActorContext,
+    plan: &TenantPlan,
+    feature: &FeatureToggle,
+) -> Result<(), AppError> {
+    if let Some(ref module) = feature.module {
+        if !actor.has_module_permission(module) {
+            return Err(AppError::Forbidden(format!(
+                "No permission for module '{}'",
+                module
+            )));
+        }
+    }
+    feature_toggle_service::ensure_module_in_plan(plan, feature.module.as_deref())
+}
+
 #[utoipa::path(
     get,
     path = "/api/admin/features",
@@ -50,16 +68,14 @@ pub async fn list_features(
     Extension(session): Extension<AuthenticatedSession>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let plan = context.tenant.plan;
     let pool = context.tenant.pool;
     let actor = context.actor;
 
     let all_features = feature_toggle_service::list_features(&pool).await?;
     let features: Vec<FeatureToggle> = all_features
         .into_iter()
-        .filter(|f| match f.module {
-            Some(ref module) => actor.has_module_permission(module),
-            None => true,
-        })
+        .filter(|f| ensure_feature_access(&actor, &plan, f).is_ok())
         .collect();
 
     Ok((
@@ -90,18 +106,12 @@ pub async fn get_feature(
     Extension(session): Extension<AuthenticatedSession>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let plan = context.tenant.plan;
     let pool = context.tenant.pool;
     let actor = context.actor;
     let feature = feature_toggle_service::get_feature(&pool, id).await?;
 
-    if let Some(ref module) = feature.module {
-        if !actor.has_module_permission(module) {
-            return Err(AppError::Forbidden(format!(
-                "No permission for module '{}'",
-                module
-            )));
-        }
-    }
+    ensure_feature_access(&actor, &plan, &feature)?;
 
     Ok((
         StatusCode::OK,
@@ -120,18 +130,12 @@ pub async fn update_feature(
     JsonResponse(data): JsonResponse<UpdateFeatureRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let plan = context.tenant.plan;
     let pool = context.tenant.pool;
     let actor = context.actor;
     let existing = feature_toggle_service::get_feature(&pool, id).await?;
 
-    if let Some(ref module) = existing.module {
-        if !actor.has_module_permission(module) {
-            return Err(AppError::Forbidden(format!(
-                "No permission for module '{}'",
-                module
-            )));
-        }
-    }
+    ensure_feature_access(&actor, &plan, &existing)?;
 
     let feature = feature_toggle_service::update_feature(&pool, id, data.is_enabled).await?;
     Ok((
@@ -150,18 +154,12 @@ pub async fn toggle_feature(
     Extension(session): Extension<AuthenticatedSession>,
 ) -> Result<impl IntoResponse, AppError> {
     let context = actor_tenant_context_from_session(&state, &session).await?;
+    let plan = context.tenant.plan;
     let pool = context.tenant.pool;
     let actor = context.actor;
     let existing = feature_toggle_service::get_feature(&pool, id).await?;
 
-    if let Some(ref module) = existing.module {
-        if !actor.has_module_permission(module) {
-            return Err(AppError::Forbidden(format!(
-                "No permission for module '{}'",
-                module
-            )));
-        }
-    }
+    ensure_feature_access(&actor, &plan, &existing)?;
 
     let feature = feature_toggle_service::toggle_feature(&pool, id).await?;
     let feature_code = feature.code.clone();
@@ -0,0 +1,24 @@
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::system::models::KeyRotationRequest;
+use crate::modules::system::services::key_rotation_service;
+use axum::{http::StatusCode, response::IntoResponse, Json};
+
+/// Advance the re-encryption of one tenant towards the active keys. Called in a
+/// loop by backend-admin until the returned run is completed.
+pub async fn run_key_rotation(
+    Json(payload): Json<KeyRotationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let run = key_rotation_service::run_key_rotation(payload).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(run))))
+}
+
+/// Report how many encrypted values and blind indexes still use older keys.
+pub async fn key_rotation_status(
+    Json(payload): Json<KeyRotationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = key_rotation_service::key_rotation_status(payload).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(status))))
+}
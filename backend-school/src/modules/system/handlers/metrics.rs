@@ -0,0 +1,12 @@
+use crate::db::pool_metrics::PROMETHEUS_CONTENT_TYPE;
+use crate::AppState;
+use axum::{extract::State, http::header, response::IntoResponse};
+
+/// Tenant pool sizes, waits and evictions of this replica for Prometheus.
+/// Served on the internal router, so scrapes send the internal secret header.
+pub async fn tenant_pool_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        state.pool_manager.render_metrics().await,
+    )
+}
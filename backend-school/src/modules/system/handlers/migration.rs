@@ -1,4 +1,8 @@
+use crate::api_response::ApiResponse;
+use crate::db::migration::run_tenant_migrations;
 use crate::error::AppError;
+use crate::modules::system::models::TenantMigrationRequest;
+use crate::modules::system::services::migration_service;
 use crate::AppState;
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use serde::Serialize;
@@ -74,6 +78,17 @@ pub async fn migrate_all_schools(
 
     for school in schools {
         let subdomain = school.subdomain.clone();
+        if school.is_migrating() {
+            // A backend-admin campaign wave owns this tenant right now.
+            results.push(MigrationResult {
+                subdomain,
+                status: "skipped".to_string(),
+                version: school.migration_version.map(i64::from),
+                error: Some("Migration campaign in progress".to_string()),
+            });
+            continue;
+        }
+
         let db_url = match school.db_connection_string {
             Some(ref url) if !url.is_empty() => url.clone(),
             _ => {
@@ -195,6 +210,16 @@ pub async fn migration_status(
     ))
 }
 
+/// Migrate one tenant up to a campaign's target version. Called by
+/// backend-admin for each tenant of a wave; the summary reports failures.
+pub async fn migrate_tenant(
+    Json(payload): Json<TenantMigrationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = migration_service::migrate_tenant(payload).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(summary))))
+}
+
 /// Helper: Migrate a single school
 async fn migrate_single_school(
     state: &AppState,
@@ -221,6 +246,20 @@ async fn migrate_single_school(
         }
     };
 
+    if let Err(e) = run_tenant_migrations(&pool).await {
+        tracing::error!("❌ Migration failed for {}: {}", subdomain, e);
+        let _ = state
+            .admin_client
+            .update_migration_status(subdomain, 0, "failed", Some(&e))
+            .await;
+        return MigrationResult {
+            subdomain: subdomain.to_string(),
+            status: "failed".to_string(),
+            version: None,
+            error: Some(e),
+        };
+    }
+
     let current_version = match get_current_version(&pool).await {
         Ok(v) => v,
         Err(e) => {
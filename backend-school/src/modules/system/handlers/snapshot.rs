@@ -0,0 +1,32 @@
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::system::models::{SnapshotExportRequest, SnapshotRestoreRequest};
+use crate::modules::system::services::snapshot_service;
+use crate::AppState;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+/// Export a point-in-time snapshot of one tenant database and its File Platform
+/// objects. Called by backend-admin, which tracks the snapshot record.
+pub async fn export_tenant_snapshot(
+    State(state): State<AppState>,
+    Json(payload): Json<SnapshotExportRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = snapshot_service::export_tenant_snapshot(&state.file_platform, payload).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(summary))))
+}
+
+/// Restore a previously exported snapshot into a fresh or existing tenant.
+pub async fn restore_tenant_snapshot(
+    State(state): State<AppState>,
+    Json(payload): Json<SnapshotRestoreRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let subdomain = payload.target_subdomain.clone();
+    let summary = snapshot_service::restore_tenant_snapshot(&state.file_platform, payload).await?;
+
+    // Cached permissions describe the replaced rows.
+    state.permission_cache.invalidate_tenant(&subdomain);
+    state.notify_all_permissions_changed(&subdomain);
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(summary))))
+}
@@ -0,0 +1,15 @@
+use crate::api_response::ApiResponse;
+use crate::error::AppError;
+use crate::modules::system::models::TenantUsageRequest;
+use crate::modules::system::services::usage_service;
+use axum::{http::StatusCode, response::IntoResponse, Json};
+
+/// Report aggregate usage of one tenant. Called periodically by backend-admin,
+/// which stores the samples as a time series.
+pub async fn collect_tenant_usage(
+    Json(payload): Json<TenantUsageRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let metrics = usage_service::collect_tenant_usage(payload).await?;
+
+    Ok((StatusCode::OK, Json(ApiResponse::ok(metrics))))
+}
@@ -1,4 +1,7 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,3 +16,191 @@ pub struct ProvisionRequest {
     pub admin_first_name: String,
     pub admin_last_name: String,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotExportRequest {
+    pub snapshot_id: Uuid,
+    pub tenant_id: Uuid,
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRestoreRequest {
+    pub snapshot_id: Uuid,
+    pub source_tenant_id: Uuid,
+    /// SHA-256 of `manifest.json` recorded by the control plane at export time.
+    pub manifest_checksum: String,
+    pub target_tenant_id: Uuid,
+    pub target_subdomain: String,
+    pub db_connection_string: String,
+    /// Required when the target tenant already holds school data.
+    #[serde(default)]
+    pub replace_existing: bool,
+    /// Set for sandbox clones: personal data is replaced before the restore commits.
+    #[serde(default)]
+    pub anonymization: Option<SnapshotAnonymization>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotAnonymization {
+    /// Every account in the sandbox signs in with this password.
+    pub sandbox_password: String,
+}
+
+impl std::fmt::Debug for SnapshotAnonymization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotAnonymization")
+            .field("sandbox_password", &"[redacted]")
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotExportSummary {
+    pub snapshot_id: Uuid,
+    pub format_version: u32,
+    pub schema_version: i64,
+    pub captured_at: DateTime<Utc>,
+    pub manifest_checksum: String,
+    pub table_count: usize,
+    pub row_count: u64,
+    pub object_count: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRestoreSummary {
+    pub snapshot_id: Uuid,
+    pub snapshot_schema_version: i64,
+    pub schema_version: i64,
+    pub table_count: usize,
+    pub row_count: u64,
+    pub object_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anonymization: Option<AnonymizationSummary>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationSummary {
+    /// Values replaced with synthetic ones in classified columns
+    pub replaced_values: u64,
+    /// Values set to NULL, such as health notes and audit payloads
+    pub cleared_values: u64,
+    /// Free-text values in which a known identifier was rewritten
+    pub scrubbed_values: u64,
+    pub placeholder_objects: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMigrationRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+    pub target_version: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMigrationSummary {
+    pub subdomain: String,
+    /// `None` when the tenant database could not be reached or read.
+    pub from_version: Option<i64>,
+    pub applied_version: Option<i64>,
+    pub latest_version: i64,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantUsageRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantUsageMetrics {
+    pub subdomain: String,
+    pub collected_at: DateTime<Utc>,
+    /// Distinct users with a session seen in the last 7 and 30 days.
+    pub active_users_7d: i64,
+    pub active_users_30d: i64,
+    pub student_count: i64,
+    pub staff_count: i64,
+    /// Stored bytes of file versions and derivatives, keyed by `FilePurpose` code.
+    pub storage_bytes_by_purpose: BTreeMap<String, i64>,
+    pub storage_bytes: i64,
+    pub certificates_issued: i64,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub migration_version: Option<i64>,
+    pub database_bytes: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationRequest {
+    pub subdomain: String,
+    pub db_connection_string: String,
+}
+
+/// Progress of the re-encryption job towards one encryption and blind-index key pair.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationRun {
+    #[serde(skip)]
+    pub id: Uuid,
+    pub encryption_key_id: String,
+    pub blind_index_key_id: String,
+    pub status: String,
+    pub cursor_table: Option<String>,
+    #[serde(skip)]
+    pub cursor_id: Option<Uuid>,
+    pub rows_scanned: i64,
+    pub rows_rewritten: i64,
+    pub rows_failed: i64,
+    pub last_error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedColumnStatus {
+    pub table: String,
+    pub column: String,
+    /// Non-empty values per key id; unprefixed values count as `legacy`.
+    pub rows_by_key: BTreeMap<String, i64>,
+    pub rows_on_old_keys: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlindIndexStatus {
+    pub table: String,
+    pub column: String,
+    /// Rows whose digest has not been rebuilt under the active blind-index key.
+    pub rows_pending: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationStatus {
+    pub subdomain: String,
+    pub active_encryption_key_id: String,
+    pub active_blind_index_key_id: String,
+    pub rows_on_old_keys: i64,
+    pub blind_index_rows_pending: i64,
+    pub columns: Vec<EncryptedColumnStatus>,
+    pub blind_indexes: Vec<BlindIndexStatus>,
+    /// Run towards the active key pair, if one was started.
+    pub run: Option<KeyRotationRun>,
+}
@@ -1,3 +1,8 @@
+pub mod anonymization_service;
 pub mod feature_toggle_service;
+pub mod key_rotation_service;
+pub mod migration_service;
 pub mod provision_service;
 pub mod route_registration_service;
+pub mod snapshot_service;
+pub mod usage_service;
@@ -0,0 +1,1201 @@
+//! Replaces personal data in a restored tenant so it can serve as a sandbox
+//! for staging and training. Runs inside the restore transaction: nothing is
+//! committed unless the verification pass finds no original value left.
+
+use crate::error::AppError;
+use crate::modules::system::models::AnonymizationSummary;
+use crate::modules::system::services::snapshot_service::{list_tenant_tables, quote_identifier};
+use crate::utils::field_encryption;
+use crate::utils::file_hash::FileHasher;
+use chrono::{Datelike, NaiveDate};
+use futures::TryStreamExt;
+use serde_json::{Map, Value};
+use sqlx::{Postgres, Row, Transaction};
+use std::collections::{HashMap, HashSet};
+
+const PSEUDONYM_DOMAIN: &str = "sandbox-anonymization-v1";
+const UPDATE_BATCH_ROWS: usize = 500;
+const SANDBOX_PASSWORD_MIN_CHARS: usize = 12;
+const TEXT_TYPES: &[&str] = &["text", "character varying", "json", "jsonb"];
+
+const PLACEHOLDER_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00, 0x01, 0x00, 0x00,
+    0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae,
+    0x42, 0x60, 0x82,
+];
+const PLACEHOLDER_PDF: &[u8] = b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n\
+2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] >>\nendobj\n\
+xref\n0 4\n0000000000 65535 f \n0000000009 00000 n \n0000000058 00000 n \n0000000115 00000 n \n\
+trailer\n<< /Size 4 /Root 1 0 R >>\nstartxref\n186\n%%EOF\n";
+const PLACEHOLDER_TEXT: &[u8] = b"SchoolOrbit sandbox placeholder\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PiiKind {
+    /// `field_encryption` ciphertext whose blind index lives in `hash_column`
+    NationalId {
+        hash_column: &'static str,
+    },
+    FirstName,
+    LastName,
+    FullName,
+    Nickname,
+    Username,
+    Email,
+    Phone,
+    LineId,
+    Address,
+    FileName,
+    DateOfBirth,
+}
+
+impl PiiKind {
+    fn code(self) -> &'static str {
+        match self {
+            PiiKind::NationalId { .. } => "national_id",
+            PiiKind::FirstName => "first_name",
+            PiiKind::LastName => "last_name",
+            PiiKind::FullName => "full_name",
+            PiiKind::Nickname => "nickname",
+            PiiKind::Username => "username",
+            PiiKind::Email => "email",
+            PiiKind::Phone => "phone",
+            PiiKind::LineId => "line_id",
+            PiiKind::Address => "address",
+            PiiKind::FileName => "file_name",
+            PiiKind::DateOfBirth => "date_of_birth",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Treatment {
+    Replace(PiiKind),
+    /// Free text, health notes and network data with no useful synthetic form
+    Clear,
+}
+
+struct PiiColumn {
+    table: &'static str,
+    column: &'static str,
+    treatment: Treatment,
+}
+
+const fn replace(table: &'static str, column: &'static str, kind: PiiKind) -> PiiColumn {
+    PiiColumn {
+        table,
+        column,
+        treatment: Treatment::Replace(kind),
+    }
+}
+
+const fn clear(table: &'static str, column: &'static str) -> PiiColumn {
+    PiiColumn {
+        table,
+        column,
+        treatment: Treatment::Clear,
+    }
+}
+
+const USER_NATIONAL_ID: PiiKind = PiiKind::NationalId {
+    hash_column: "national_id_hash",
+};
+
+/// Columns holding personal data. National IDs follow the encrypted columns and
+/// blind indexes written by `admission::services::pii` and the user services.
+/// Entries whose table or column does not exist at the restored schema
+/// version are skipped.
+const PII_COLUMNS: &[PiiColumn] = &[
+    replace("users", "national_id", USER_NATIONAL_ID),
+    replace("users", "email", PiiKind::Email),
+    replace("users", "username", PiiKind::Username),
+    replace("users", "first_name", PiiKind::FirstName),
+    replace("users", "last_name", PiiKind::LastName),
+    replace("users", "nickname", PiiKind::Nickname),
+    replace("users", "phone", PiiKind::Phone),
+    replace("users", "emergency_contact", PiiKind::Phone),
+    replace("users", "line_id", PiiKind::LineId),
+    replace("users", "date_of_birth", PiiKind::DateOfBirth),
+    replace("users", "address", PiiKind::Address),
+    replace("admission_applications", "national_id", USER_NATIONAL_ID),
+    replace(
+        "admission_applications",
+        "father_national_id",
+        PiiKind::NationalId {
+            hash_column: "father_national_id_hash",
+        },
+    ),
+    replace(
+        "admission_applications",
+        "mother_national_id",
+        PiiKind::NationalId {
+            hash_column: "mother_national_id_hash",
+        },
+    ),
+    replace(
+        "admission_applications",
+        "guardian_national_id",
+        PiiKind::NationalId {
+            hash_column: "guardian_national_id_hash",
+        },
+    ),
+    replace("admission_applications", "first_name", PiiKind::FirstName),
+    replace("admission_applications", "last_name", PiiKind::LastName),
+    replace(
+        "admission_applications",
+        "date_of_birth",
+        PiiKind::DateOfBirth,
+    ),
+    replace("admission_applications", "phone", PiiKind::Phone),
+    replace("admission_applications", "email", PiiKind::Email),
+    replace("admission_applications", "address_line", PiiKind::Address),
+    replace("admission_applications", "home_house_no", PiiKind::Address),
+    replace("admission_applications", "home_moo", PiiKind::Address),
+    replace("admission_applications", "home_soi", PiiKind::Address),
+    replace("admission_applications", "home_road", PiiKind::Address),
+    replace("admission_applications", "home_phone", PiiKind::Phone),
+    replace(
+        "admission_applications",
+        "current_house_no",
+        PiiKind::Address,
+    ),
+    replace("admission_applications", "current_moo", PiiKind::Address),
+    replace("admission_applications", "current_soi", PiiKind::Address),
+    replace("admission_applications", "current_road", PiiKind::Address),
+    replace("admission_applications", "current_phone", PiiKind::Phone),
+    replace("admission_applications", "father_name", PiiKind::FullName),
+    replace("admission_applications", "father_phone", PiiKind::Phone),
+    replace("admission_applications", "mother_name", PiiKind::FullName),
+    replace("admission_applications", "mother_phone", PiiKind::Phone),
+    replace("admission_applications", "guardian_name", PiiKind::FullName),
+    replace("admission_applications", "guardian_phone", PiiKind::Phone),
+    replace("parent_info", "work_phone", PiiKind::Phone),
+    replace("consent_records", "parent_guardian_name", PiiKind::FullName),
+    clear("consent_records", "ip_address"),
+    clear("consent_records", "user_agent"),
+    clear("student_info", "allergies"),
+    clear("student_info", "medical_conditions"),
+    clear("staff_leave_requests", "contact_during_leave"),
+    replace(
+        "certificate_candidates",
+        "lookup_staff_username",
+        PiiKind::Username,
+    ),
+    replace(
+        "certificate_candidates",
+        "imported_first_name",
+        PiiKind::FirstName,
+    ),
+    replace(
+        "certificate_candidates",
+        "imported_last_name",
+        PiiKind::LastName,
+    ),
+    replace(
+        "certificate_candidates",
+        "account_first_name",
+        PiiKind::FirstName,
+    ),
+    replace(
+        "certificate_candidates",
+        "account_last_name",
+        PiiKind::LastName,
+    ),
+    replace("certificates", "first_name_snapshot", PiiKind::FirstName),
+    replace("certificates", "last_name_snapshot", PiiKind::LastName),
+    replace("files", "original_filename", PiiKind::FileName),
+    clear("finance_settings", "promptpay_target_type"),
+    clear("finance_settings", "promptpay_target"),
+    replace("audit_logs", "user_email", PiiKind::Email),
+    replace("audit_logs", "user_name", PiiKind::FullName),
+    clear("audit_logs", "entity_name"),
+    clear("audit_logs", "old_values"),
+    clear("audit_logs", "new_values"),
+    clear("audit_logs", "changes"),
+    clear("audit_logs", "description"),
+    clear("audit_logs", "ip_address"),
+    clear("audit_logs", "user_agent"),
+];
+
+/// Body written in place of an archived File Platform object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder {
+    pub body: &'static [u8],
+    pub content_type: &'static str,
+    pub extension: &'static str,
+}
+
+/// Images become a blank PNG and PDFs a blank page, so previews and
+/// certificate rendering keep working; everything else becomes plain text.
+pub fn placeholder_for(content_type: &str) -> Placeholder {
+    let content_type = content_type.to_ascii_lowercase();
+    if content_type.starts_with("image/") {
+        Placeholder {
+            body: PLACEHOLDER_PNG,
+            content_type: "image/png",
+            extension: "png",
+        }
+    } else if content_type == "application/pdf" {
+        Placeholder {
+            body: PLACEHOLDER_PDF,
+            content_type: "application/pdf",
+            extension: "pdf",
+        }
+    } else {
+        Placeholder {
+            body: PLACEHOLDER_TEXT,
+            content_type: "text/plain",
+            extension: "txt",
+        }
+    }
+}
+
+/// Hash of the password every sandbox account signs in with.
+pub async fn hash_sandbox_password(password: &str) -> Result<String, AppError> {
+    if password.chars().count() < SANDBOX_PASSWORD_MIN_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "Sandbox password must be at least {} characters",
+            SANDBOX_PASSWORD_MIN_CHARS
+        )));
+    }
+
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || bcrypt::hash(password, bcrypt::DEFAULT_COST))
+        .await
+        .map_err(|_| AppError::InternalServerError("Password worker failed".to_string()))?
+        .map_err(|_| AppError::InternalServerError("Failed to hash sandbox password".to_string()))
+}
+
+fn crypto_error(error: String) -> AppError {
+    tracing::error!(error = %error, "Sandbox anonymization crypto failure");
+    AppError::InternalServerError("Sandbox anonymization could not process encrypted data".into())
+}
+
+fn survived(table: &str, column: &str) -> AppError {
+    AppError::Conflict(format!(
+        "Sandbox anonymization left original personal data in {}.{}",
+        table, column
+    ))
+}
+
+fn digits(value: &str) -> String {
+    value.chars().filter(char::is_ascii_digit).collect()
+}
+
+/// Turns hex digest pairs into decimal digits.
+fn digest_digits(digest: &str, count: usize) -> String {
+    digest
+        .as_bytes()
+        .chunks(2)
+        .take(count)
+        .map(|pair| {
+            let byte =
+                u8::from_str_radix(std::str::from_utf8(pair).unwrap_or("0"), 16).unwrap_or(0);
+            char::from(b'0' + byte % 10)
+        })
+        .collect()
+}
+
+/// Check digit of a Thai national ID over its first twelve digits.
+fn thai_id_check_digit(first_twelve: &str) -> char {
+    let sum: u32 = first_twelve
+        .chars()
+        .filter_map(|digit| digit.to_digit(10))
+        .zip((2..=13).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    char::from(b'0' + ((11 - sum % 11) % 10) as u8)
+}
+
+/// A checksum-valid ID starting with 0, which citizen IDs never do.
+fn synthetic_national_id(digest: &str) -> String {
+    let first_twelve = format!("0{}", digest_digits(digest, 11));
+    let check = thai_id_check_digit(&first_twelve);
+    format!("{}{}", first_twelve, check)
+}
+
+/// Same length as the original, with the "00" prefix no Thai number uses.
+fn synthetic_phone(original: &str, digest: &str) -> String {
+    let length = digits(original).len().clamp(9, 10);
+    format!("00{}", digest_digits(digest, length - 2))
+}
+
+/// Keeps the birth year so grade levels and age checks still line up.
+fn synthetic_birth_date(original: &str, digest: &str) -> String {
+    let year = NaiveDate::parse_from_str(original, "%Y-%m-%d")
+        .map(|date| date.year())
+        .unwrap_or(2000);
+    let ordinal = u32::from_str_radix(&digest[..4], 16).unwrap_or(0) % 365 + 1;
+    match NaiveDate::from_yo_opt(year, ordinal) {
+        Some(date) => date.format("%Y-%m-%d").to_string(),
+        None => "2000-01-01".to_string(),
+    }
+}
+
+fn synthetic_file_name(original: &str, digest: &str) -> String {
+    match original.rsplit_once('.') {
+        Some((_, extension))
+            if !extension.is_empty()
+                && extension.len() <= 10
+                && extension.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            format!("file-{}.{}", &digest[..8], extension.to_ascii_lowercase())
+        }
+        _ => format!("file-{}", &digest[..8]),
+    }
+}
+
+/// Deterministic synthetic value for `original`; every form is recognisably fake
+/// so verification cannot mistake it for a real value.
+fn synthetic_value(kind: PiiKind, original: &str, digest: &str) -> String {
+    match kind {
+        PiiKind::NationalId { .. } => synthetic_national_id(digest),
+        PiiKind::FirstName => format!("ชื่อ{}", &digest[..6]),
+        PiiKind::LastName => format!("สกุล{}", &digest[..6]),
+        PiiKind::FullName => format!("ชื่อ{} สกุล{}", &digest[..6], &digest[6..12]),
+        PiiKind::Nickname => format!("เล่น{}", &digest[..4]),
+        PiiKind::Username => format!("user-{}", &digest[..12]),
+        PiiKind::Email => format!("user-{}@sandbox.invalid", &digest[..12]),
+        PiiKind::Phone => synthetic_phone(original, digest),
+        PiiKind::LineId => format!("line-{}", &digest[..8]),
+        PiiKind::Address => format!("ที่อยู่ {}", &digest[..6]),
+        PiiKind::FileName => synthetic_file_name(original, digest),
+        PiiKind::DateOfBirth => synthetic_birth_date(original, digest),
+    }
+}
+
+/// Form of an original that feeds the pseudonym, so the same person maps to the
+/// same synthetic value in every table.
+fn normalized(kind: PiiKind, original: &str) -> String {
+    match kind {
+        PiiKind::NationalId { .. } | PiiKind::Phone => {
+            let digits = digits(original);
+            if digits.is_empty() {
+                original.trim().to_string()
+            } else {
+                digits
+            }
+        }
+        PiiKind::Email | PiiKind::Username => original.trim().to_lowercase(),
+        _ => original.trim().to_string(),
+    }
+}
+
+/// Identifiers worth searching for in free text: full national IDs, phone
+/// numbers of at least nine digits and email addresses.
+fn identifier_key(kind: PiiKind, normalized: &str) -> Option<String> {
+    match kind {
+        PiiKind::NationalId { .. } if normalized.len() == 13 => Some(normalized.to_string()),
+        PiiKind::Phone
+            if normalized.len() >= 9 && normalized.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            Some(normalized.to_string())
+        }
+        PiiKind::Email if normalized.contains('@') => Some(normalized.to_string()),
+        _ => None,
+    }
+}
+
+fn is_email_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "._%+-@".contains(c)
+}
+
+/// Replaces known national IDs, phone numbers and email addresses inside free
+/// text. Digit groups joined by '-' count as one number, so "081-234-5678" is
+/// found as well. Returns `None` when nothing matched.
+fn scrub_identifiers(text: &str, identifiers: &HashMap<String, String>) -> Option<String> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut replacements: Vec<(usize, usize, &str)> = Vec::new();
+    let mut lengths: Vec<usize> = identifiers
+        .keys()
+        .filter(|key| !key.contains('@'))
+        .map(String::len)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    lengths.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut index = 0;
+    while index < chars.len() {
+        let (_, c) = chars[index];
+        if c.is_ascii_digit() {
+            // Collect one run of digits, allowing single '-' separators
+            let mut run: Vec<(usize, char)> = Vec::new();
+            let mut cursor = index;
+            while cursor < chars.len() {
+                let (position, c) = chars[cursor];
+                if c.is_ascii_digit() {
+                    run.push((position, c));
+                    cursor += 1;
+                } else if c == '-'
+                    && cursor + 1 < chars.len()
+                    && chars[cursor + 1].1.is_ascii_digit()
+                    && !run.is_empty()
+                {
+                    cursor += 1;
+                } else {
+                    break;
+                }
+            }
+
+            let mut start = 0;
+            while start < run.len() {
+                let matched = lengths.iter().find_map(|&length| {
+                    let window = run.get(start..start + length)?;
+                    let key: String = window.iter().map(|(_, c)| c).collect();
+                    identifiers
+                        .get(&key)
+                        .map(|replacement| (length, replacement.as_str()))
+                });
+                match matched {
+                    Some((length, replacement)) => {
+                        let (from, _) = run[start];
+                        let (last, last_char) = run[start + length - 1];
+                        replacements.push((from, last + last_char.len_utf8(), replacement));
+                        start += length;
+                    }
+                    None => start += 1,
+                }
+            }
+            index = cursor.max(index + 1);
+        } else if c == '@' {
+            let mut from = index;
+            while from > 0 && is_email_char(chars[from - 1].1) && chars[from - 1].1 != '@' {
+                from -= 1;
+            }
+            let mut to = index + 1;
+            while to < chars.len() && is_email_char(chars[to].1) && chars[to].1 != '@' {
+                to += 1;
+            }
+            while to > index + 1 && chars[to - 1].1 == '.' {
+                to -= 1;
+            }
+            let start = chars[from].0;
+            let end = chars.get(to).map_or(text.len(), |(position, _)| *position);
+            if let Some(replacement) = identifiers.get(&text[start..end].to_lowercase()) {
+                replacements.retain(|(existing, _, _)| *existing < start);
+                replacements.push((start, end, replacement));
+            }
+            index = to.max(index + 1);
+        } else {
+            index += 1;
+        }
+    }
+
+    if replacements.is_empty() {
+        return None;
+    }
+    replacements.sort_by_key(|(start, _, _)| *start);
+    let mut scrubbed = String::with_capacity(text.len());
+    let mut position = 0;
+    for (start, end, replacement) in replacements {
+        if start < position {
+            continue;
+        }
+        scrubbed.push_str(&text[position..start]);
+        scrubbed.push_str(replacement);
+        position = end;
+    }
+    scrubbed.push_str(&text[position..]);
+    Some(scrubbed)
+}
+
+/// Column name to base type (without length) for one table; empty when the
+/// table does not exist at this schema version.
+async fn table_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> Result<HashMap<String, String>, AppError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT attname::text AS name,
+               CASE WHEN atttypid = 'bpchar'::regtype THEN 'text'
+                    ELSE format_type(atttypid, NULL)
+               END AS data_type
+        FROM pg_attribute
+        WHERE attrelid = to_regclass($1)
+          AND attnum > 0
+          AND NOT attisdropped
+          AND attgenerated = ''
+        "#,
+    )
+    .bind(quote_identifier(table))
+    .fetch_all(&mut **tx)
+    .await?;
+
+    rows.iter()
+        .map(|row| Ok((row.try_get("name")?, row.try_get("data_type")?)))
+        .collect::<Result<_, sqlx::Error>>()
+        .map_err(AppError::from)
+}
+
+/// Writes changed values back by physical row address. Columns a row does not
+/// change are sent as NULL and keep their value.
+async fn apply_row_updates(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+    columns: &[(String, String)],
+    rows: Vec<Value>,
+) -> Result<(), AppError> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let assignments = columns
+        .iter()
+        .enumerate()
+        .map(|(index, (column, data_type))| {
+            format!(
+                "{column} = COALESCE(v.c{index}::{data_type}, target.{column})",
+                column = quote_identifier(column),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let record_columns = (0..columns.len())
+        .map(|index| format!("c{} text", index))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let update = format!(
+        "UPDATE {table} AS target SET {assignments} \
+         FROM json_to_recordset($1::json) AS v(row_ref text, {record_columns}) \
+         WHERE target.ctid = v.row_ref::tid",
+        table = quote_identifier(table),
+    );
+
+    for batch in rows.chunks(UPDATE_BATCH_ROWS) {
+        sqlx::query(&update)
+            .bind(Value::Array(batch.to_vec()).to_string())
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+fn select_columns(table: &str, columns: &[String]) -> String {
+    let selected = columns
+        .iter()
+        .map(|column| format!("{}::text", quote_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let filter = columns
+        .iter()
+        .map(|column| format!("{} IS NOT NULL", quote_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    format!(
+        "SELECT ctid::text, {} FROM {} WHERE {}",
+        selected,
+        quote_identifier(table),
+        filter
+    )
+}
+
+struct Anonymizer {
+    seed: String,
+    /// Normalised original identifier -> its synthetic replacement
+    identifiers: HashMap<String, String>,
+    /// Trimmed originals of replaced columns other than national IDs and birth dates
+    originals: HashSet<String>,
+    national_ids: HashSet<String>,
+    summary: AnonymizationSummary,
+}
+
+impl Anonymizer {
+    fn pseudonym(&mut self, kind: PiiKind, original: &str) -> Result<String, AppError> {
+        let normalized = normalized(kind, original);
+        if let Some(replacement) =
+            identifier_key(kind, &normalized).and_then(|key| self.identifiers.get(&key))
+        {
+            return Ok(replacement.clone());
+        }
+
+        let digest = field_encryption::hash_for_search_with_domain(
+            PSEUDONYM_DOMAIN,
+            &format!("{}\u{1f}{}\u{1f}{}", self.seed, kind.code(), normalized),
+        )
+        .map_err(crypto_error)?;
+        let replacement = synthetic_value(kind, original, &digest);
+
+        if let Some(key) = identifier_key(kind, &normalized) {
+            self.identifiers.insert(key, replacement.clone());
+        }
+        match kind {
+            PiiKind::NationalId { .. } => {
+                self.national_ids.insert(normalized);
+            }
+            PiiKind::DateOfBirth => {}
+            _ => {
+                self.originals.insert(original.trim().to_string());
+            }
+        }
+        Ok(replacement)
+    }
+
+    async fn anonymize_table(
+        &mut self,
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        entries: &[&PiiColumn],
+    ) -> Result<(), AppError> {
+        let columns = table_columns(tx, table).await?;
+        let present = entries
+            .iter()
+            .filter(|entry| match entry.treatment {
+                Treatment::Replace(PiiKind::NationalId { hash_column }) => {
+                    columns.contains_key(entry.column) && columns.contains_key(hash_column)
+                }
+                _ => columns.contains_key(entry.column),
+            })
+            .collect::<Vec<_>>();
+
+        let cleared = present
+            .iter()
+            .filter(|entry| entry.treatment == Treatment::Clear)
+            .map(|entry| entry.column.to_string())
+            .collect::<Vec<_>>();
+        if !cleared.is_empty() {
+            let assignments = cleared
+                .iter()
+                .map(|column| format!("{} = NULL", quote_identifier(column)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let filter = cleared
+                .iter()
+                .map(|column| format!("{} IS NOT NULL", quote_identifier(column)))
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            let result = sqlx::query(&format!(
+                "UPDATE {} SET {} WHERE {}",
+                quote_identifier(table),
+                assignments,
+                filter
+            ))
+            .execute(&mut **tx)
+            .await?;
+            self.summary.cleared_values += result.rows_affected();
+        }
+
+        let replaced = present
+            .iter()
+            .filter_map(|entry| match entry.treatment {
+                Treatment::Replace(kind) => Some((entry.column, kind)),
+                Treatment::Clear => None,
+            })
+            .collect::<Vec<_>>();
+        if replaced.is_empty() {
+            return Ok(());
+        }
+
+        // Targets in update order: every replaced column, then the blind index
+        // of each national ID column
+        let mut targets = replaced
+            .iter()
+            .map(|(column, _)| (column.to_string(), columns[*column].clone()))
+            .collect::<Vec<_>>();
+        let mut hash_targets = Vec::new();
+        for (index, (_, kind)) in replaced.iter().enumerate() {
+            if let PiiKind::NationalId { hash_column } = kind {
+                hash_targets.push((index, targets.len()));
+                targets.push((hash_column.to_string(), columns[*hash_column].clone()));
+            }
+        }
+
+        let names = replaced
+            .iter()
+            .map(|(column, _)| column.to_string())
+            .collect::<Vec<_>>();
+        let query = select_columns(table, &names);
+        let mut originals = Vec::new();
+        {
+            let mut rows = sqlx::query(&query).fetch(&mut **tx);
+            while let Some(row) = rows.try_next().await? {
+                let row_ref: String = row.try_get(0)?;
+                let values = (0..replaced.len())
+                    .map(|index| row.try_get::<Option<String>, _>(index + 1))
+                    .collect::<Result<Vec<_>, _>>()?;
+                originals.push((row_ref, values));
+            }
+        }
+
+        let mut updates = Vec::with_capacity(originals.len());
+        for (row_ref, values) in originals {
+            let mut record = Map::new();
+            record.insert("row_ref".to_string(), Value::String(row_ref));
+            for (index, value) in values.into_iter().enumerate() {
+                let Some(value) = value.filter(|value| !value.trim().is_empty()) else {
+                    continue;
+                };
+                let (_, kind) = replaced[index];
+                let replacement = match kind {
+                    PiiKind::NationalId { .. } => {
+                        // Legacy rows may still hold plaintext
+                        let plaintext = field_encryption::decrypt(&value).unwrap_or(value);
+                        let synthetic = self.pseudonym(kind, &plaintext)?;
+                        let hash_index = hash_targets
+                            .iter()
+                            .find(|(column_index, _)| *column_index == index)
+                            .map(|(_, hash_index)| *hash_index)
+                            .unwrap_or_default();
+                        record.insert(
+                            format!("c{}", hash_index),
+                            Value::String(
+                                field_encryption::hash_for_search(&synthetic)
+                                    .map_err(crypto_error)?,
+                            ),
+                        );
+                        field_encryption::encrypt(&synthetic).map_err(crypto_error)?
+                    }
+                    kind => self.pseudonym(kind, &value)?,
+                };
+                record.insert(format!("c{}", index), Value::String(replacement));
+                self.summary.replaced_values += 1;
+            }
+            if record.len() > 1 {
+                updates.push(Value::Object(record));
+            }
+        }
+
+        apply_row_updates(tx, table, &targets, updates).await
+    }
+
+    /// Free-text columns of every table, minus national ID ciphertexts and
+    /// blind indexes, which hold no plaintext.
+    async fn text_columns(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+    ) -> Result<Vec<(String, String)>, AppError> {
+        let opaque = PII_COLUMNS
+            .iter()
+            .filter(|entry| entry.table == table)
+            .filter_map(|entry| match entry.treatment {
+                Treatment::Replace(PiiKind::NationalId { hash_column }) => {
+                    Some([entry.column, hash_column])
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect::<HashSet<_>>();
+
+        let mut columns = table_columns(tx, table)
+            .await?
+            .into_iter()
+            .filter(|(column, data_type)| {
+                TEXT_TYPES.contains(&data_type.as_str()) && !opaque.contains(column.as_str())
+            })
+            .collect::<Vec<_>>();
+        columns.sort();
+        Ok(columns)
+    }
+
+    /// Rewrites identifiers left in free text, such as a parent's phone number in
+    /// a behaviour note or a national ID inside a JSON document.
+    async fn scrub_table(
+        &mut self,
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+    ) -> Result<(), AppError> {
+        let columns = self.text_columns(tx, table).await?;
+        if columns.is_empty() || self.identifiers.is_empty() {
+            return Ok(());
+        }
+
+        let names = columns
+            .iter()
+            .map(|(column, _)| column.clone())
+            .collect::<Vec<_>>();
+        let query = select_columns(table, &names);
+        let mut updates = Vec::new();
+        {
+            let mut rows = sqlx::query(&query).fetch(&mut **tx);
+            while let Some(row) = rows.try_next().await? {
+                let mut record = Map::new();
+                for index in 0..columns.len() {
+                    let Some(value) = row.try_get::<Option<String>, _>(index + 1)? else {
+                        continue;
+                    };
+                    if let Some(scrubbed) = scrub_identifiers(&value, &self.identifiers) {
+                        record.insert(format!("c{}", index), Value::String(scrubbed));
+                        self.summary.scrubbed_values += 1;
+                    }
+                }
+                if !record.is_empty() {
+                    record.insert("row_ref".to_string(), Value::String(row.try_get(0)?));
+                    updates.push(Value::Object(record));
+                }
+            }
+        }
+
+        apply_row_updates(tx, table, &columns, updates).await
+    }
+
+    async fn verify_table(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+        entries: &[&PiiColumn],
+    ) -> Result<(), AppError> {
+        let columns = table_columns(tx, table).await?;
+        for entry in entries
+            .iter()
+            .filter(|entry| columns.contains_key(entry.column))
+        {
+            let column = quote_identifier(entry.column);
+            let table_name = quote_identifier(table);
+            match entry.treatment {
+                Treatment::Clear => {
+                    let remaining = sqlx::query_scalar::<_, bool>(&format!(
+                        "SELECT EXISTS (SELECT 1 FROM {} WHERE {} IS NOT NULL)",
+                        table_name, column
+                    ))
+                    .fetch_one(&mut **tx)
+                    .await?;
+                    if remaining {
+                        return Err(survived(table, entry.column));
+                    }
+                }
+                Treatment::Replace(PiiKind::NationalId { hash_column }) => {
+                    if !columns.contains_key(hash_column) {
+                        continue;
+                    }
+                    let rows = sqlx::query_as::<_, (String, Option<String>)>(&format!(
+                        "SELECT {}::text, {}::text FROM {} WHERE {} IS NOT NULL AND {} <> ''",
+                        column,
+                        quote_identifier(hash_column),
+                        table_name,
+                        column,
+                        column
+                    ))
+                    .fetch_all(&mut **tx)
+                    .await?;
+                    for (ciphertext, hash) in rows {
+                        let plaintext =
+                            field_encryption::decrypt(&ciphertext).map_err(crypto_error)?;
+                        let expected =
+                            field_encryption::hash_for_search(&plaintext).map_err(crypto_error)?;
+                        if self.national_ids.contains(&digits(&plaintext))
+                            || hash.as_deref() != Some(expected.as_str())
+                        {
+                            return Err(survived(table, entry.column));
+                        }
+                    }
+                }
+                Treatment::Replace(PiiKind::DateOfBirth) => {}
+                Treatment::Replace(_) => {
+                    let sql = format!(
+                        "SELECT {}::text FROM {} WHERE {} IS NOT NULL",
+                        column, table_name, column
+                    );
+                    let mut rows = sqlx::query_scalar::<_, String>(&sql).fetch(&mut **tx);
+                    while let Some(value) = rows.try_next().await? {
+                        if self.originals.contains(value.trim()) {
+                            return Err(survived(table, entry.column));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn verify_free_text(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        table: &str,
+    ) -> Result<(), AppError> {
+        if self.identifiers.is_empty() {
+            return Ok(());
+        }
+        for (column, _) in self.text_columns(tx, table).await? {
+            let quoted = quote_identifier(&column);
+            let sql = format!(
+                "SELECT {}::text FROM {} WHERE {} IS NOT NULL",
+                quoted,
+                quote_identifier(table),
+                quoted
+            );
+            let mut rows = sqlx::query_scalar::<_, String>(&sql).fetch(&mut **tx);
+            while let Some(value) = rows.try_next().await? {
+                if scrub_identifiers(&value, &self.identifiers).is_some() {
+                    return Err(survived(table, &column));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn catalog_tables() -> Vec<(&'static str, Vec<&'static PiiColumn>)> {
+    let mut tables: Vec<(&'static str, Vec<&'static PiiColumn>)> = Vec::new();
+    for entry in PII_COLUMNS {
+        match tables.iter_mut().find(|(table, _)| *table == entry.table) {
+            Some((_, entries)) => entries.push(entry),
+            None => tables.push((entry.table, vec![entry])),
+        }
+    }
+    tables
+}
+
+/// Points File Platform rows at the placeholders written in place of their
+/// objects, keyed by the object keys recorded in the snapshot.
+pub async fn apply_object_placeholders(
+    tx: &mut Transaction<'_, Postgres>,
+    placeholders: &[(String, Placeholder)],
+) -> Result<(), AppError> {
+    let has_file_versions =
+        sqlx::query_scalar::<_, bool>("SELECT to_regclass('file_versions') IS NOT NULL")
+            .fetch_one(&mut **tx)
+            .await?;
+    if !has_file_versions || placeholders.is_empty() {
+        return Ok(());
+    }
+
+    let mut by_placeholder: Vec<(Placeholder, Vec<String>)> = Vec::new();
+    for (object_key, placeholder) in placeholders {
+        match by_placeholder
+            .iter_mut()
+            .find(|(existing, _)| existing == placeholder)
+        {
+            Some((_, keys)) => keys.push(object_key.clone()),
+            None => by_placeholder.push((*placeholder, vec![object_key.clone()])),
+        }
+    }
+
+    for (placeholder, keys) in by_placeholder {
+        let checksum = FileHasher::sha256(placeholder.body);
+        for table in ["file_versions", "file_derivatives"] {
+            sqlx::query(&format!(
+                "UPDATE {table} \
+                 SET checksum = $2, byte_size = $3, detected_mime_type = $4, canonical_extension = $5 \
+                 WHERE object_key = ANY($1)"
+            ))
+            .bind(&keys)
+            .bind(&checksum)
+            .bind(placeholder.body.len() as i64)
+            .bind(placeholder.content_type)
+            .bind(placeholder.extension)
+            .execute(&mut **tx)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE files f \
+             SET file_size = v.byte_size, mime_type = v.detected_mime_type, checksum = v.checksum \
+             FROM file_versions v \
+             WHERE v.id = f.current_version_id AND v.object_key = ANY($1)",
+        )
+        .bind(&keys)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Replaces personal data in the restored rows, resets every password to the
+/// sandbox password and verifies that no original value survives. `seed` makes
+/// the synthetic values stable across clones into the same sandbox.
+pub async fn anonymize_tenant(
+    tx: &mut Transaction<'_, Postgres>,
+    seed: &str,
+    sandbox_password_hash: &str,
+) -> Result<AnonymizationSummary, AppError> {
+    let mut anonymizer = Anonymizer {
+        seed: seed.to_string(),
+        identifiers: HashMap::new(),
+        originals: HashSet::new(),
+        national_ids: HashSet::new(),
+        summary: AnonymizationSummary::default(),
+    };
+    let catalog = catalog_tables();
+
+    for (table, entries) in &catalog {
+        anonymizer.anonymize_table(tx, table, entries).await?;
+    }
+
+    if !table_columns(tx, "users").await?.is_empty() {
+        sqlx::query("UPDATE users SET password_hash = $1")
+            .bind(sandbox_password_hash)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    let tables = list_tenant_tables(tx).await?;
+    for table in &tables {
+        anonymizer.scrub_table(tx, table).await?;
+    }
+
+    for (table, entries) in &catalog {
+        anonymizer.verify_table(tx, table, entries).await?;
+    }
+    for table in &tables {
+        anonymizer.verify_free_text(tx, table).await?;
+    }
+
+    Ok(anonymizer.summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "3fa91c07b2d45e68a1c0f9e27d3b5a4c6e8f0d1b2a3c4d5e6f708192a3b4c5d6";
+
+    fn is_valid_thai_id(value: &str) -> bool {
+        value.len() == 13
+            && value.chars().all(|c| c.is_ascii_digit())
+            && thai_id_check_digit(&value[..12]) == value.chars().last().unwrap()
+    }
+
+    #[test]
+    fn check_digit_matches_a_known_national_id() {
+        assert_eq!(thai_id_check_digit("110170020310"), '7');
+        assert!(is_valid_thai_id("1101700203107"));
+    }
+
+    #[test]
+    fn synthetic_values_are_recognisably_fake() {
+        let national_id = synthetic_value(
+            PiiKind::NationalId {
+                hash_column: "national_id_hash",
+            },
+            "1101700203107",
+            DIGEST,
+        );
+        assert!(is_valid_thai_id(&national_id));
+        assert!(national_id.starts_with('0'));
+
+        assert_eq!(
+            synthetic_value(PiiKind::Phone, "081-234-5678", DIGEST).len(),
+            10
+        );
+        assert_eq!(
+            synthetic_value(PiiKind::Phone, "02 123 4567", DIGEST).len(),
+            9
+        );
+        assert!(synthetic_value(PiiKind::Phone, "0812345678", DIGEST).starts_with("00"));
+        assert_eq!(
+            synthetic_value(PiiKind::Email, "Somchai@School.ac.th", DIGEST),
+            "user-3fa91c07b2d4@sandbox.invalid"
+        );
+        assert_eq!(
+            synthetic_value(PiiKind::FullName, "นายสมชาย ใจดี", DIGEST),
+            "ชื่อ3fa91c สกุล07b2d4"
+        );
+        assert_eq!(
+            synthetic_value(PiiKind::FileName, "บัตรประชาชน.PDF", DIGEST),
+            "file-3fa91c07.pdf"
+        );
+        assert!(
+            synthetic_value(PiiKind::Address, "99/1 หมู่ 4", DIGEST)
+                .chars()
+                .count()
+                <= 20
+        );
+    }
+
+    #[test]
+    fn birth_dates_keep_their_year() {
+        let date = synthetic_value(PiiKind::DateOfBirth, "2011-05-17", DIGEST);
+        assert!(date.starts_with("2011-"));
+        assert!(NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_ok());
+    }
+
+    #[test]
+    fn identical_originals_share_a_pseudonym_across_columns() {
+        let _guard = field_encryption::test_env_lock();
+        std::env::set_var("BLIND_INDEX_KEY", "anonymization-test-key");
+        let mut anonymizer = Anonymizer {
+            seed: "sandbox".to_string(),
+            identifiers: HashMap::new(),
+            originals: HashSet::new(),
+            national_ids: HashSet::new(),
+            summary: AnonymizationSummary::default(),
+        };
+
+        let first = anonymizer
+            .pseudonym(PiiKind::Phone, "081-234-5678")
+            .unwrap();
+        let second = anonymizer.pseudonym(PiiKind::Phone, "0812345678").unwrap();
+        let email = anonymizer
+            .pseudonym(PiiKind::Email, "Parent@Example.com")
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            anonymizer.identifiers.get("0812345678"),
+            Some(&first),
+            "free-text scrubbing reuses the column pseudonym"
+        );
+        assert_eq!(
+            anonymizer.identifiers.get("parent@example.com"),
+            Some(&email)
+        );
+
+        anonymizer.seed = "another-sandbox".to_string();
+        anonymizer.identifiers.clear();
+        assert_ne!(
+            anonymizer.pseudonym(PiiKind::Phone, "0812345678").unwrap(),
+            first
+        );
+    }
+
+    #[test]
+    fn free_text_identifiers_are_replaced() {
+        let identifiers = HashMap::from([
+            ("0812345678".to_string(), "0011112222".to_string()),
+            ("1101700203107".to_string(), "0123456789016".to_string()),
+            (
+                "parent@example.com".to_string(),
+                "user-abc@sandbox.invalid".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            scrub_identifiers(
+                "โทรหาผู้ปกครอง 081-234-5678 หรือ Parent@Example.com.",
+                &identifiers
+            )
+            .as_deref(),
+            Some("โทรหาผู้ปกครอง 0011112222 หรือ user-abc@sandbox.invalid.")
+        );
+        assert_eq!(
+            scrub_identifiers(r#"{"nationalId":"1-1017-00203-10-7"}"#, &identifiers).as_deref(),
+            Some(r#"{"nationalId":"0123456789016"}"#)
+        );
+        assert_eq!(scrub_identifiers("ห้อง 0812345", &identifiers), None);
+        assert_eq!(scrub_identifiers("other@example.com", &identifiers), None);
+    }
+
+    #[test]
+    fn placeholders_follow_the_content_family() {
+        assert_eq!(placeholder_for("image/jpeg").content_type, "image/png");
+        assert_eq!(placeholder_for("application/pdf").extension, "pdf");
+        assert_eq!(placeholder_for("text/csv").content_type, "text/plain");
+        assert!(placeholder_for("image/png").body.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn catalog_groups_columns_by_table_without_duplicates() {
+        let mut seen = HashSet::new();
+        for entry in PII_COLUMNS {
+            assert!(
+                seen.insert((entry.table, entry.column)),
+                "{}.{} is listed twice",
+                entry.table,
+                entry.column
+            );
+        }
+        let tables = catalog_tables();
+        assert_eq!(tables[0].0, "users");
+        assert_eq!(
+            tables
+                .iter()
+                .map(|(_, entries)| entries.len())
+                .sum::<usize>(),
+            PII_COLUMNS.len()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::db::admin_client::TenantPlan;
 use crate::error::AppError;
 use crate::modules::menu::models::FeatureToggle;
 use sqlx::PgPool;
@@ -79,6 +80,32 @@ pub async fn update_feature_enabled_by_code(
     .ok_or(AppError::NotFound("Feature toggle not found".to_string()))
 }
 
+/// Module of a permission code such as `attendance.read.all`
+pub fn permission_module(permission: &str) -> &str {
+    permission.split('.').next().unwrap_or(permission).trim()
+}
+
+/// Whether a `required_permission` value (a module, a permission, or a
+/// `|`-separated list of permissions) belongs to a module the school's plan includes
+pub fn is_permission_in_plan(plan: &TenantPlan, required_permission: &str) -> bool {
+    required_permission
+        .split('|')
+        .map(str::trim)
+        .filter(|permission| !permission.is_empty())
+        .any(|permission| plan.is_module_enabled(permission_module(permission)))
+}
+
+/// Features of modules outside the school's plan can be neither read nor toggled
+pub fn ensure_module_in_plan(plan: &TenantPlan, module: Option<&str>) -> Result<(), AppError> {
+    match module {
+        Some(module) if !plan.is_module_enabled(module) => Err(AppError::Forbidden(format!(
+            "โมดูล '{}' ไม่อยู่ในแพ็กเกจของโรงเรียน",
+            module
+        ))),
+        _ => Ok(()),
+    }
+}
+
 fn feature_update_set_clause(is_enabled: Option<bool>) -> String {
     let mut query = String::from("UPDATE feature_toggles SET updated_at = NOW()");
     if let Some(enabled) = is_enabled {
@@ -91,6 +118,13 @@ fn feature_update_set_clause(is_enabled: Option<bool>) -> String {
 mod tests {
     use super::*;
 
+    fn plan_with(modules: &[&str]) -> TenantPlan {
+        TenantPlan {
+            enabled_modules: Some(modules.iter().map(|module| module.to_string()).collect()),
+            ..TenantPlan::default()
+        }
+    }
+
     #[test]
     fn feature_update_set_clause_only_updates_timestamp_when_enabled_is_absent() {
         assert_eq!(
@@ -110,4 +144,43 @@ mod tests {
             "UPDATE feature_toggles SET updated_at = NOW(), is_enabled = false"
         );
     }
+
+    #[test]
+    fn permission_module_is_the_first_segment() {
+        assert_eq!(permission_module("attendance.read.all"), "attendance");
+        assert_eq!(permission_module("staff"), "staff");
+    }
+
+    #[test]
+    fn permissions_outside_the_plan_are_rejected() {
+        let plan = plan_with(&["staff"]);
+
+        assert!(is_permission_in_plan(&plan, "staff.read.all"));
+        assert!(!is_permission_in_plan(&plan, "finance.read.all"));
+        assert!(is_permission_in_plan(
+            &plan,
+            "finance.read.all | staff.read.all"
+        ));
+        assert!(!is_permission_in_plan(&plan, " | "));
+    }
+
+    #[test]
+    fn plan_without_module_list_allows_every_permission() {
+        assert!(is_permission_in_plan(
+            &TenantPlan::default(),
+            "finance.read.all"
+        ));
+    }
+
+    #[test]
+    fn module_gate_rejects_only_modules_outside_the_plan() {
+        let plan = plan_with(&["staff"]);
+
+        assert!(ensure_module_in_plan(&plan, Some("staff")).is_ok());
+        assert!(ensure_module_in_plan(&plan, None).is_ok());
+        assert!(matches!(
+            ensure_module_in_plan(&plan, Some("finance")),
+            Err(AppError::Forbidden(_))
+        ));
+    }
 }
@@ -0,0 +1,643 @@
+//! Background re-encryption of application-encrypted columns after a keyring
+//! rotation. backend-admin calls [`run_key_rotation`] repeatedly; each call
+//! processes batches for a bounded time and every batch commits its cursor, so
+//! an interrupted job resumes where it stopped.
+
+use super::migration_service::connect_tenant;
+use crate::error::AppError;
+use crate::modules::certificates::services::proof::QR_PROOF_HASH_DOMAIN;
+use crate::modules::system::models::{
+    BlindIndexStatus, EncryptedColumnStatus, KeyRotationRequest, KeyRotationRun, KeyRotationStatus,
+};
+use crate::utils::field_encryption::{self, LEGACY_KEY_ID, VERSIONED_PREFIX};
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+const BATCH_ROWS: i64 = 200;
+/// A request stops starting new batches after this long and reports progress.
+const RUN_TIME_BUDGET: Duration = Duration::from_secs(20);
+/// Transaction-level advisory lock; two runners never rewrite the same rows.
+const ROTATION_LOCK: &str = "field_key_rotation";
+
+struct BlindIndex {
+    column: &'static str,
+    /// `None` for the national-ID blind index, which predates domain separation.
+    domain: Option<&'static str>,
+}
+
+struct EncryptedField {
+    column: &'static str,
+    blind_index: Option<BlindIndex>,
+}
+
+struct EncryptedTable {
+    table: &'static str,
+    fields: &'static [EncryptedField],
+}
+
+const fn national_id(column: &'static str, hash_column: &'static str) -> EncryptedField {
+    EncryptedField {
+        column,
+        blind_index: Some(BlindIndex {
+            column: hash_column,
+            domain: None,
+        }),
+    }
+}
+
+/// Every column written through `field_encryption::encrypt`, in the order the job
+/// walks them. A new encrypted column must be listed here, or rotation leaves it
+/// on the old key without status reporting it.
+const ENCRYPTED_TABLES: &[EncryptedTable] = &[
+    EncryptedTable {
+        table: "users",
+        fields: &[national_id("national_id", "national_id_hash")],
+    },
+    EncryptedTable {
+        table: "admission_applications",
+        fields: &[
+            national_id("national_id", "national_id_hash"),
+            national_id("father_national_id", "father_national_id_hash"),
+            national_id("mother_national_id", "mother_national_id_hash"),
+            national_id("guardian_national_id", "guardian_national_id_hash"),
+        ],
+    },
+    EncryptedTable {
+        table: "certificates",
+        fields: &[EncryptedField {
+            column: "qr_proof_encrypted",
+            blind_index: Some(BlindIndex {
+                column: "qr_proof_hash",
+                domain: Some(QR_PROOF_HASH_DOMAIN),
+            }),
+        }],
+    },
+];
+
+struct ActiveKeys {
+    encryption: String,
+    blind_index: String,
+}
+
+fn keyring_error(error: String) -> AppError {
+    // Keyring errors name environment variables and key ids, never secrets.
+    tracing::error!("Field encryption keyring is misconfigured: {}", error);
+    AppError::ServiceUnavailable(format!(
+        "Field encryption keyring is misconfigured: {}",
+        error
+    ))
+}
+
+fn active_keys() -> Result<ActiveKeys, AppError> {
+    Ok(ActiveKeys {
+        encryption: field_encryption::active_encryption_key_id().map_err(keyring_error)?,
+        blind_index: field_encryption::active_blind_index_key_id().map_err(keyring_error)?,
+    })
+}
+
+/// SQL for the key id of a stored value, mirroring `field_encryption::ciphertext_key_id`.
+fn key_id_expression(column: &str) -> String {
+    format!(
+        "CASE WHEN {column} LIKE '{VERSIONED_PREFIX}:%' THEN split_part({column}, ':', 2) ELSE '{LEGACY_KEY_ID}' END"
+    )
+}
+
+fn select_batch_sql(table: &EncryptedTable) -> String {
+    let mut columns = vec!["id".to_string()];
+    for field in table.fields {
+        columns.push(format!("{}::text", field.column));
+        if let Some(blind_index) = &field.blind_index {
+            columns.push(format!("{}::text", blind_index.column));
+        }
+    }
+    format!(
+        "SELECT {} FROM {} WHERE ($1::uuid IS NULL OR id > $1) ORDER BY id LIMIT $2",
+        columns.join(", "),
+        table.table
+    )
+}
+
+fn update_row_sql(table: &str, columns: &[&str]) -> String {
+    let assignments: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| format!("{} = ${}", column, index + 2))
+        .collect();
+    format!(
+        "UPDATE {} SET {} WHERE id = $1",
+        table,
+        assignments.join(", ")
+    )
+}
+
+/// New ciphertext and blind index for one stored value, when they differ from
+/// what the active keys would produce.
+fn rotate_field(
+    field: &EncryptedField,
+    value: &str,
+    current_hash: Option<&str>,
+    keys: &ActiveKeys,
+) -> Result<Vec<(&'static str, String)>, String> {
+    let mut updates = Vec::new();
+    let plaintext = Zeroizing::new(field_encryption::decrypt(value)?);
+
+    if field_encryption::ciphertext_key_id(value)? != keys.encryption {
+        let ciphertext = field_encryption::encrypt(&plaintext)?;
+        if *Zeroizing::new(field_encryption::decrypt(&ciphertext)?) != *plaintext {
+            return Err("re-encrypted value did not round-trip".to_string());
+        }
+        updates.push((field.column, ciphertext));
+    }
+
+    if let Some(blind_index) = &field.blind_index {
+        let hash = match blind_index.domain {
+            Some(domain) => field_encryption::hash_for_search_with_domain(domain, &plaintext)?,
+            None => field_encryption::hash_for_search(&plaintext)?,
+        };
+        if current_hash != Some(hash.as_str()) {
+            updates.push((blind_index.column, hash));
+        }
+    }
+
+    Ok(updates)
+}
+
+#[derive(Default)]
+struct BatchOutcome {
+    rewritten: i64,
+    failed: i64,
+    last_error: Option<String>,
+}
+
+impl BatchOutcome {
+    /// Records where a row failed. The reason comes from field encryption or a
+    /// constraint name and never includes a value.
+    fn fail(&mut self, table: &str, column: &str, id: Uuid, reason: &str) {
+        tracing::warn!(table, column, %id, reason, "Key rotation skipped a row");
+        self.failed += 1;
+        self.last_error = Some(format!("{}.{} row {}: {}", table, column, id, reason));
+    }
+}
+
+async fn rotate_row(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &EncryptedTable,
+    row: &PgRow,
+    keys: &ActiveKeys,
+    outcome: &mut BatchOutcome,
+) -> Result<(), AppError> {
+    let id: Uuid = row.try_get(0)?;
+    let mut updates: Vec<(&'static str, String)> = Vec::new();
+    let mut index = 1;
+
+    for field in table.fields {
+        let value: Option<String> = row.try_get(index)?;
+        index += 1;
+        let mut current_hash: Option<String> = None;
+        if field.blind_index.is_some() {
+            current_hash = row.try_get(index)?;
+            index += 1;
+        }
+        let Some(value) = value.filter(|value| !value.is_empty()) else {
+            continue;
+        };
+        match rotate_field(field, &value, current_hash.as_deref(), keys) {
+            Ok(field_updates) => updates.extend(field_updates),
+            Err(reason) => {
+                outcome.fail(table.table, field.column, id, &reason);
+                return Ok(());
+            }
+        }
+    }
+
+    if updates.is_empty() {
+        return Ok(());
+    }
+
+    let columns: Vec<&str> = updates.iter().map(|(column, _)| *column).collect();
+    let sql = update_row_sql(table.table, &columns);
+    let mut query = sqlx::query(&sql).bind(id);
+    for (_, value) in &updates {
+        query = query.bind(value);
+    }
+
+    // A rebuilt blind index can collide with a duplicate written under the new key
+    // while the rotation was running; that row is reported and the batch goes on.
+    sqlx::query("SAVEPOINT key_rotation_row")
+        .execute(&mut **tx)
+        .await?;
+    match query.execute(&mut **tx).await {
+        Ok(_) => {
+            sqlx::query("RELEASE SAVEPOINT key_rotation_row")
+                .execute(&mut **tx)
+                .await?;
+            outcome.rewritten += 1;
+        }
+        Err(sqlx::Error::Database(error)) => {
+            sqlx::query("ROLLBACK TO SAVEPOINT key_rotation_row")
+                .execute(&mut **tx)
+                .await?;
+            let reason = match error.constraint() {
+                Some(constraint) => format!("update violates {}", constraint),
+                None => "update failed".to_string(),
+            };
+            outcome.fail(table.table, columns.join(",").as_str(), id, &reason);
+        }
+        Err(error) => return Err(error.into()),
+    }
+
+    Ok(())
+}
+
+async fn claim_run(pool: &PgPool, keys: &ActiveKeys) -> Result<KeyRotationRun, AppError> {
+    // A completed run towards the same keys starts over; rescanning is idempotent
+    // and picks up rows that failed last time.
+    let claimed = sqlx::query_as::<_, KeyRotationRun>(
+        "INSERT INTO field_key_rotation_runs (encryption_key_id, blind_index_key_id)
+         VALUES ($1, $2)
+         ON CONFLICT (encryption_key_id, blind_index_key_id) DO UPDATE
+         SET status = 'running', cursor_table = NULL, cursor_id = NULL,
+             rows_scanned = 0, rows_rewritten = 0, rows_failed = 0, last_error = NULL,
+             started_at = now(), updated_at = now(), completed_at = NULL
+         WHERE field_key_rotation_runs.status = 'completed'
+         RETURNING *",
+    )
+    .bind(&keys.encryption)
+    .bind(&keys.blind_index)
+    .fetch_optional(pool)
+    .await?;
+
+    match claimed {
+        Some(run) => Ok(run),
+        None => load_run(pool, keys)
+            .await?
+            .ok_or_else(|| AppError::InternalServerError("Key rotation run vanished".to_string())),
+    }
+}
+
+async fn load_run(pool: &PgPool, keys: &ActiveKeys) -> Result<Option<KeyRotationRun>, AppError> {
+    Ok(sqlx::query_as::<_, KeyRotationRun>(
+        "SELECT * FROM field_key_rotation_runs
+         WHERE encryption_key_id = $1 AND blind_index_key_id = $2",
+    )
+    .bind(&keys.encryption)
+    .bind(&keys.blind_index)
+    .fetch_optional(pool)
+    .await?)
+}
+
+fn cursor_table_index(run: &KeyRotationRun) -> Result<usize, AppError> {
+    match run.cursor_table.as_deref() {
+        None => Ok(0),
+        Some(name) => ENCRYPTED_TABLES
+            .iter()
+            .position(|table| table.table == name)
+            .ok_or_else(|| {
+                AppError::InternalServerError(format!(
+                    "Unknown key rotation cursor table '{}'",
+                    name
+                ))
+            }),
+    }
+}
+
+async fn process_batch(
+    pool: &PgPool,
+    run_id: Uuid,
+    keys: &ActiveKeys,
+) -> Result<KeyRotationRun, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock(hashtext($1))")
+        .bind(ROTATION_LOCK)
+        .fetch_one(&mut *tx)
+        .await?;
+    if !locked {
+        return Err(AppError::Conflict(
+            "Key rotation is already running for this tenant".to_string(),
+        ));
+    }
+    // Lets the certificate immutability trigger accept re-encrypted QR proofs.
+    sqlx::query("SET LOCAL app.field_key_rotation = 'on'")
+        .execute(&mut *tx)
+        .await?;
+
+    let run = sqlx::query_as::<_, KeyRotationRun>(
+        "SELECT * FROM field_key_rotation_runs WHERE id = $1 FOR UPDATE",
+    )
+    .bind(run_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if run.status == "completed" {
+        return Ok(run);
+    }
+
+    let table_index = cursor_table_index(&run)?;
+    let table = &ENCRYPTED_TABLES[table_index];
+    let rows = sqlx::query(&select_batch_sql(table))
+        .bind(run.cursor_id)
+        .bind(BATCH_ROWS)
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let run = match rows.last() {
+        None => match ENCRYPTED_TABLES.get(table_index + 1) {
+            Some(next) => {
+                sqlx::query_as::<_, KeyRotationRun>(
+                    "UPDATE field_key_rotation_runs
+                     SET cursor_table = $2, cursor_id = NULL, updated_at = now()
+                     WHERE id = $1
+                     RETURNING *",
+                )
+                .bind(run_id)
+                .bind(next.table)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+            None => {
+                sqlx::query_as::<_, KeyRotationRun>(
+                    "UPDATE field_key_rotation_runs
+                     SET status = 'completed', completed_at = now(), updated_at = now()
+                     WHERE id = $1
+                     RETURNING *",
+                )
+                .bind(run_id)
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        },
+        Some(last) => {
+            let last_id: Uuid = last.try_get(0)?;
+            let mut outcome = BatchOutcome::default();
+            for row in &rows {
+                rotate_row(&mut tx, table, row, keys, &mut outcome).await?;
+            }
+            sqlx::query_as::<_, KeyRotationRun>(
+                "UPDATE field_key_rotation_runs
+                 SET cursor_table = $2, cursor_id = $3,
+                     rows_scanned = rows_scanned + $4,
+                     rows_rewritten = rows_rewritten + $5,
+                     rows_failed = rows_failed + $6,
+                     last_error = COALESCE($7, last_error),
+                     updated_at = now()
+                 WHERE id = $1
+                 RETURNING *",
+            )
+            .bind(run_id)
+            .bind(table.table)
+            .bind(last_id)
+            .bind(rows.len() as i64)
+            .bind(outcome.rewritten)
+            .bind(outcome.failed)
+            .bind(outcome.last_error)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+    };
+
+    tx.commit().await?;
+    Ok(run)
+}
+
+/// Re-encrypt one tenant towards the active keys for up to [`RUN_TIME_BUDGET`].
+/// The returned run is `completed` once every catalogued column was walked.
+pub async fn run_key_rotation(request: KeyRotationRequest) -> Result<KeyRotationRun, AppError> {
+    let keys = active_keys()?;
+    let pool = connect_tenant(&request.db_connection_string)
+        .await
+        .map_err(AppError::InternalServerError)?;
+
+    let started = Instant::now();
+    let mut run = claim_run(&pool, &keys).await?;
+    while run.status != "completed" && started.elapsed() < RUN_TIME_BUDGET {
+        run = process_batch(&pool, run.id, &keys).await?;
+    }
+
+    tracing::info!(
+        subdomain = %request.subdomain,
+        encryption_key_id = %run.encryption_key_id,
+        blind_index_key_id = %run.blind_index_key_id,
+        status = %run.status,
+        rows_scanned = run.rows_scanned,
+        rows_rewritten = run.rows_rewritten,
+        rows_failed = run.rows_failed,
+        "Key rotation progressed"
+    );
+    pool.close().await;
+
+    Ok(run)
+}
+
+async fn column_key_counts(
+    pool: &PgPool,
+    table: &str,
+    column: &str,
+) -> Result<BTreeMap<String, i64>, AppError> {
+    let rows = sqlx::query_as::<_, (String, i64)>(&format!(
+        "SELECT {key_id}, COUNT(*) FROM {table}
+         WHERE {column} IS NOT NULL AND {column} <> ''
+         GROUP BY 1",
+        key_id = key_id_expression(column),
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Blind indexes are current once any run towards the active blind-index key
+/// finished; otherwise everything past the cursor of the open run is pending.
+async fn blind_index_pending(
+    pool: &PgPool,
+    keys: &ActiveKeys,
+    run: Option<&KeyRotationRun>,
+    table_index: usize,
+    column: &str,
+) -> Result<i64, AppError> {
+    let rebuilt: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+             SELECT 1 FROM field_key_rotation_runs
+             WHERE blind_index_key_id = $1 AND status = 'completed'
+         )",
+    )
+    .bind(&keys.blind_index)
+    .fetch_one(pool)
+    .await?;
+    if rebuilt {
+        return Ok(0);
+    }
+
+    let table = ENCRYPTED_TABLES[table_index].table;
+    let after_id = match run.filter(|run| run.status == "running") {
+        Some(run) => {
+            let cursor_index = cursor_table_index(run)?;
+            if cursor_index > table_index {
+                return Ok(0);
+            }
+            if cursor_index == table_index {
+                run.cursor_id
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
+    Ok(sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM {table}
+         WHERE {column} IS NOT NULL AND ($1::uuid IS NULL OR id > $1)"
+    ))
+    .bind(after_id)
+    .fetch_one(pool)
+    .await?)
+}
+
+/// Report how many stored values still use keys other than the active ones.
+pub async fn key_rotation_status(
+    request: KeyRotationRequest,
+) -> Result<KeyRotationStatus, AppError> {
+    let keys = active_keys()?;
+    let pool = connect_tenant(&request.db_connection_string)
+        .await
+        .map_err(AppError::InternalServerError)?;
+    let run = load_run(&pool, &keys).await?;
+
+    let mut columns = Vec::new();
+    let mut blind_indexes = Vec::new();
+    for (table_index, table) in ENCRYPTED_TABLES.iter().enumerate() {
+        for field in table.fields {
+            let rows_by_key = column_key_counts(&pool, table.table, field.column).await?;
+            let rows_on_old_keys = rows_by_key
+                .iter()
+                .filter(|(key_id, _)| **key_id != keys.encryption)
+                .map(|(_, count)| count)
+                .sum();
+            columns.push(EncryptedColumnStatus {
+                table: table.table.to_string(),
+                column: field.column.to_string(),
+                rows_by_key,
+                rows_on_old_keys,
+            });
+
+            if let Some(blind_index) = &field.blind_index {
+                let rows_pending = blind_index_pending(
+                    &pool,
+                    &keys,
+                    run.as_ref(),
+                    table_index,
+                    blind_index.column,
+                )
+                .await?;
+                blind_indexes.push(BlindIndexStatus {
+                    table: table.table.to_string(),
+                    column: blind_index.column.to_string(),
+                    rows_pending,
+                });
+            }
+        }
+    }
+    pool.close().await;
+
+    Ok(KeyRotationStatus {
+        subdomain: request.subdomain,
+        rows_on_old_keys: columns.iter().map(|column| column.rows_on_old_keys).sum(),
+        blind_index_rows_pending: blind_indexes.iter().map(|index| index.rows_pending).sum(),
+        active_encryption_key_id: keys.encryption,
+        active_blind_index_key_id: keys.blind_index,
+        columns,
+        blind_indexes,
+        run,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_keys() -> ActiveKeys {
+        ActiveKeys {
+            encryption: LEGACY_KEY_ID.to_string(),
+            blind_index: LEGACY_KEY_ID.to_string(),
+        }
+    }
+
+    #[test]
+    fn catalog_queries_read_each_value_with_its_blind_index() {
+        let sql = select_batch_sql(&ENCRYPTED_TABLES[2]);
+
+        assert_eq!(
+            sql,
+            "SELECT id, qr_proof_encrypted::text, qr_proof_hash::text FROM certificates \
+             WHERE ($1::uuid IS NULL OR id > $1) ORDER BY id LIMIT $2"
+        );
+        assert_eq!(
+            update_row_sql("users", &["national_id", "national_id_hash"]),
+            "UPDATE users SET national_id = $2, national_id_hash = $3 WHERE id = $1"
+        );
+    }
+
+    #[test]
+    fn current_values_need_no_rewrite() {
+        let _guard = field_encryption::test_env_lock();
+        std::env::set_var("ENCRYPTION_KEY", "key-rotation-test-key");
+        std::env::set_var("BLIND_INDEX_KEY", "key-rotation-blind-index-test-key");
+        let field = national_id("national_id", "national_id_hash");
+        let value = field_encryption::encrypt("1101700203107").unwrap();
+        let hash = field_encryption::hash_for_search("1101700203107").unwrap();
+
+        let updates = rotate_field(&field, &value, Some(&hash), &legacy_keys()).unwrap();
+
+        assert!(updates.is_empty());
+    }
+
+    #[test]
+    fn values_on_an_old_key_are_re_encrypted_and_reindexed() {
+        let _guard = field_encryption::test_env_lock();
+        std::env::set_var("ENCRYPTION_KEY", "key-rotation-test-key");
+        std::env::set_var("BLIND_INDEX_KEY", "key-rotation-blind-index-test-key");
+        let field = national_id("national_id", "national_id_hash");
+        let value = field_encryption::encrypt("1101700203107").unwrap();
+        let hash = field_encryption::hash_for_search("1101700203107").unwrap();
+
+        std::env::set_var("ENCRYPTION_KEYRING", "k2026:rotated-test-key");
+        std::env::set_var("ENCRYPTION_ACTIVE_KEY_ID", "k2026");
+        std::env::set_var("BLIND_INDEX_KEYRING", "b2026:rotated-blind-index-test-key");
+        std::env::set_var("BLIND_INDEX_ACTIVE_KEY_ID", "b2026");
+        let keys = active_keys();
+        let updates = keys
+            .as_ref()
+            .map_err(|_| "keyring")
+            .and_then(|keys| rotate_field(&field, &value, Some(&hash), keys).map_err(|_| "rotate"));
+        let new_hash = field_encryption::hash_for_search("1101700203107");
+        for name in [
+            "ENCRYPTION_KEYRING",
+            "ENCRYPTION_ACTIVE_KEY_ID",
+            "BLIND_INDEX_KEYRING",
+            "BLIND_INDEX_ACTIVE_KEY_ID",
+        ] {
+            std::env::remove_var(name);
+        }
+
+        let updates = updates.unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].0, "national_id");
+        assert!(updates[0].1.starts_with("v2:k2026:"));
+        assert_eq!(updates[1], ("national_id_hash", new_hash.unwrap()));
+        assert_ne!(updates[1].1, hash);
+    }
+
+    #[test]
+    fn values_from_an_unknown_key_fail_without_echoing_them() {
+        let _guard = field_encryption::test_env_lock();
+        std::env::set_var("ENCRYPTION_KEY", "key-rotation-test-key");
+        std::env::set_var("BLIND_INDEX_KEY", "key-rotation-blind-index-test-key");
+        let field = national_id("national_id", "national_id_hash");
+
+        let error =
+            rotate_field(&field, "v2:gone:AAAAAAAAAAAAAAAAAAAA", None, &legacy_keys()).unwrap_err();
+
+        assert_eq!(error, "Unknown encryption key id: gone");
+    }
+}
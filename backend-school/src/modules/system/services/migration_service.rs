@@ -0,0 +1,123 @@
+use crate::db::migration::{
+    applied_tenant_migration_version, latest_tenant_migration_version, run_tenant_migrations,
+    run_tenant_migrations_through,
+};
+use crate::error::AppError;
+use crate::modules::system::models::{TenantMigrationRequest, TenantMigrationSummary};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use std::time::Instant;
+
+fn validate_target_version(target_version: i64, latest_version: i64) -> Result<(), AppError> {
+    if target_version < 1 || target_version > latest_version {
+        return Err(AppError::ValidationError(format!(
+            "Target migration version must be between 1 and {}",
+            latest_version
+        )));
+    }
+    Ok(())
+}
+
+pub(super) async fn connect_tenant(db_connection_string: &str) -> Result<PgPool, String> {
+    PgPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(std::time::Duration::from_secs(30))
+        .connect(db_connection_string)
+        .await
+        .map_err(|error| format!("Database connection failed: {}", error))
+}
+
+async fn apply_through(
+    pool: &PgPool,
+    target_version: i64,
+    latest_version: i64,
+) -> Result<(), String> {
+    if target_version == latest_version {
+        // Also re-applies the permission contract for the current build.
+        run_tenant_migrations(pool).await
+    } else {
+        run_tenant_migrations_through(pool, target_version).await
+    }
+}
+
+/// Migrate one tenant up to `target_version` for a backend-admin campaign wave.
+///
+/// A failed migration is a tenant outcome rather than a request error: the
+/// summary carries the error and the version the tenant was left at, so the
+/// campaign can record it and pause. Tenants already past the target are left
+/// untouched; migrations never run backwards.
+pub async fn migrate_tenant(
+    payload: TenantMigrationRequest,
+) -> Result<TenantMigrationSummary, AppError> {
+    let latest_version = latest_tenant_migration_version();
+    validate_target_version(payload.target_version, latest_version)?;
+
+    tracing::info!(
+        subdomain = %payload.subdomain,
+        target_version = payload.target_version,
+        "Migrating tenant for campaign"
+    );
+    let started = Instant::now();
+
+    let pool = match connect_tenant(&payload.db_connection_string).await {
+        Ok(pool) => pool,
+        Err(error) => {
+            tracing::error!(subdomain = %payload.subdomain, "{}", error);
+            return Ok(TenantMigrationSummary {
+                subdomain: payload.subdomain,
+                from_version: None,
+                applied_version: None,
+                latest_version,
+                duration_ms: started.elapsed().as_millis() as u64,
+                error: Some(error),
+            });
+        }
+    };
+
+    let from_version = applied_tenant_migration_version(&pool).await.ok();
+    let error = match from_version {
+        Some(version) if version > payload.target_version => None,
+        _ => apply_through(&pool, payload.target_version, latest_version)
+            .await
+            .err(),
+    };
+    let applied_version = applied_tenant_migration_version(&pool).await.ok();
+    pool.close().await;
+
+    match &error {
+        Some(error) => tracing::error!(
+            subdomain = %payload.subdomain,
+            applied_version = ?applied_version,
+            "Tenant migration failed: {}",
+            error
+        ),
+        None => tracing::info!(
+            subdomain = %payload.subdomain,
+            from_version = ?from_version,
+            applied_version = ?applied_version,
+            "Tenant migrated"
+        ),
+    }
+
+    Ok(TenantMigrationSummary {
+        subdomain: payload.subdomain,
+        from_version,
+        applied_version,
+        latest_version,
+        duration_ms: started.elapsed().as_millis() as u64,
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_target_version;
+
+    #[test]
+    fn target_version_must_exist_in_this_build() {
+        assert!(validate_target_version(1, 3).is_ok());
+        assert!(validate_target_version(3, 3).is_ok());
+        assert!(validate_target_version(0, 3).is_err());
+        assert!(validate_target_version(4, 3).is_err());
+    }
+}
@@ -0,0 +1,1009 @@
+use crate::db::migration::{
+    run_tenant_migrations, run_tenant_migrations_through, tenant_migration_checksums,
+};
+use crate::error::AppError;
+use crate::modules::files::{
+    platform_service::FilePlatform,
+    platform_types::StorageClass,
+    purpose_registry::{persisted_object_key, retarget_object_key, tenant_snapshot_object_key},
+    storage_provider::{StorageError, StorageProvider, StoredObject},
+};
+use crate::modules::system::models::{
+    SnapshotExportRequest, SnapshotExportSummary, SnapshotRestoreRequest, SnapshotRestoreSummary,
+};
+use crate::modules::system::services::anonymization_service::{self, Placeholder};
+use crate::utils::file_hash::FileHasher;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgPoolOptions, PgPool, Postgres, Row, Transaction};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const MANIFEST_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const TABLE_PART_MAX_ROWS: usize = 1_000;
+const TABLE_PART_MAX_BYTES: usize = 32 * 1024 * 1024;
+/// A part closes after the row that crosses `TABLE_PART_MAX_BYTES`.
+const TABLE_PART_READ_MAX_BYTES: u64 = 2 * TABLE_PART_MAX_BYTES as u64;
+/// Upper bound for a single File Platform object copied into a snapshot.
+const SNAPSHOT_OBJECT_MAX_BYTES: u64 = 128 * 1024 * 1024;
+const RESTORE_BATCH_ROWS: usize = 500;
+const SNAPSHOT_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Tables whose rows never leave the tenant database. Auth sessions are live
+/// credentials; a restored tenant starts with everyone signed out. Support
+/// access grants belong to the original tenant's support history.
+const UNEXPORTED_TABLES: &[&str] = &["auth_sessions", "support_access_grants"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub snapshot_id: Uuid,
+    pub tenant_id: Uuid,
+    pub subdomain: String,
+    pub captured_at: DateTime<Utc>,
+    pub schema_version: i64,
+    pub migrations: Vec<SnapshotMigration>,
+    pub tables: Vec<SnapshotTable>,
+    pub sequences: Vec<SnapshotSequence>,
+    pub objects: Vec<SnapshotObject>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotMigration {
+    pub version: i64,
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotTable {
+    pub name: String,
+    pub row_count: u64,
+    pub parts: Vec<SnapshotPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotPart {
+    pub entry: String,
+    pub row_count: u64,
+    pub byte_size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSequence {
+    pub name: String,
+    pub last_value: Option<i64>,
+    pub start_value: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotObject {
+    pub object_key: String,
+    pub storage_class: String,
+    pub content_type: String,
+    pub byte_size: u64,
+    pub sha256: String,
+}
+
+impl SnapshotManifest {
+    fn row_count(&self) -> u64 {
+        self.tables.iter().map(|table| table.row_count).sum()
+    }
+
+    fn total_bytes(&self) -> u64 {
+        let table_bytes: u64 = self
+            .tables
+            .iter()
+            .flat_map(|table| table.parts.iter())
+            .map(|part| part.byte_size)
+            .sum();
+        let object_bytes: u64 = self.objects.iter().map(|object| object.byte_size).sum();
+        table_bytes + object_bytes
+    }
+}
+
+/// Buffers JSON lines for one table until a part is full.
+#[derive(Default)]
+struct TablePartBuffer {
+    body: Vec<u8>,
+    row_count: u64,
+}
+
+impl TablePartBuffer {
+    fn push(&mut self, row: &str) {
+        self.body.extend_from_slice(row.as_bytes());
+        self.body.push(b'\n');
+        self.row_count += 1;
+    }
+
+    fn is_full(&self) -> bool {
+        self.row_count as usize >= TABLE_PART_MAX_ROWS || self.body.len() >= TABLE_PART_MAX_BYTES
+    }
+
+    fn is_empty(&self) -> bool {
+        self.row_count == 0
+    }
+}
+
+pub(super) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn table_part_entry(table: &str, index: usize) -> String {
+    format!("tables/{table}/{index:06}.jsonl")
+}
+
+fn object_entry(sha256: &str) -> String {
+    format!("objects/{sha256}")
+}
+
+fn storage_class_code(storage_class: StorageClass) -> &'static str {
+    match storage_class {
+        StorageClass::Public => "public",
+        StorageClass::Private => "private",
+    }
+}
+
+fn parse_storage_class(value: &str) -> Result<StorageClass, AppError> {
+    match value {
+        "public" => Ok(StorageClass::Public),
+        "private" => Ok(StorageClass::Private),
+        _ => Err(AppError::InternalServerError(format!(
+            "Unknown storage class in snapshot: {}",
+            value
+        ))),
+    }
+}
+
+fn verify_checksum(body: &[u8], expected: &str, entry: &str) -> Result<(), AppError> {
+    if FileHasher::sha256(body) != expected.to_ascii_lowercase() {
+        return Err(AppError::Conflict(format!(
+            "Snapshot entry '{}' failed checksum verification",
+            entry
+        )));
+    }
+
+    Ok(())
+}
+
+/// Confirms that a snapshot can be replayed by this build: same archive format,
+/// a migration history that matches the compiled migrations, and a target that
+/// has not already moved past the snapshot schema.
+fn check_restore_compatibility(
+    manifest: &SnapshotManifest,
+    build_migrations: &[(i64, String)],
+    target_version: i64,
+) -> Result<(), AppError> {
+    if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(AppError::Conflict(format!(
+            "Snapshot format version {} is not supported (expected {})",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+
+    let latest_version = build_migrations
+        .iter()
+        .map(|(version, _)| *version)
+        .max()
+        .unwrap_or(0);
+    if manifest.schema_version > latest_version {
+        return Err(AppError::Conflict(format!(
+            "Snapshot schema version {} is newer than this build ({})",
+            manifest.schema_version, latest_version
+        )));
+    }
+
+    for migration in &manifest.migrations {
+        match build_migrations
+            .iter()
+            .find(|(version, _)| *version == migration.version)
+        {
+            Some((_, checksum)) if checksum.eq_ignore_ascii_case(&migration.checksum) => {}
+            Some(_) => {
+                return Err(AppError::Conflict(format!(
+                    "Snapshot migration {} was applied with a different checksum",
+                    migration.version
+                )));
+            }
+            None => {
+                return Err(AppError::Conflict(format!(
+                    "Snapshot migration {} is unknown to this build",
+                    migration.version
+                )));
+            }
+        }
+    }
+
+    if target_version > manifest.schema_version {
+        return Err(AppError::Conflict(format!(
+            "Target tenant is at schema version {} which is newer than the snapshot ({})",
+            target_version, manifest.schema_version
+        )));
+    }
+
+    Ok(())
+}
+
+/// Joins JSON lines into the JSON array consumed by `json_populate_recordset`.
+fn json_array_batches(body: &str, batch_rows: usize) -> Vec<String> {
+    let rows = body
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+
+    rows.chunks(batch_rows.max(1))
+        .map(|chunk| format!("[{}]", chunk.join(",")))
+        .collect()
+}
+
+async fn connect_tenant(db_connection_string: &str) -> Result<PgPool, AppError> {
+    PgPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(std::time::Duration::from_secs(30))
+        .connect(db_connection_string)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                "Failed to connect to tenant database for snapshot: {}",
+                error
+            );
+            AppError::InternalServerError(format!("Database connection failed: {}", error))
+        })
+}
+
+fn storage_error(entry: &str, error: StorageError) -> AppError {
+    tracing::error!(
+        entry,
+        error_code = error.log_safe_code(),
+        "Tenant snapshot storage operation failed"
+    );
+    AppError::ServiceUnavailable(format!(
+        "Snapshot storage operation failed for '{}': {}",
+        entry, error
+    ))
+}
+
+fn snapshot_object(
+    tenant_id: Uuid,
+    snapshot_id: Uuid,
+    entry: &str,
+) -> Result<StoredObject, AppError> {
+    let key = tenant_snapshot_object_key(tenant_id, snapshot_id, entry).map_err(|error| {
+        AppError::InternalServerError(format!("Invalid snapshot entry '{}': {}", entry, error))
+    })?;
+    Ok(StoredObject::new(key, SNAPSHOT_CONTENT_TYPE))
+}
+
+/// Snapshot archives are immutable; an entry that already exists was written by
+/// an earlier attempt of the same snapshot and carries the same checksum.
+async fn put_snapshot_entry(
+    provider: &dyn StorageProvider,
+    object: &StoredObject,
+    entry: &str,
+    body: Bytes,
+) -> Result<(), AppError> {
+    match provider.put(object, body).await {
+        Ok(()) | Err(StorageError::AlreadyExists) => Ok(()),
+        Err(error) => Err(storage_error(entry, error)),
+    }
+}
+
+/// Application tables of the tenant schema; SQLx migration history is owned by
+/// `run_tenant_migrations` and is never exported or truncated.
+pub(super) async fn list_tenant_tables(
+    connection: &mut sqlx::PgConnection,
+) -> Result<Vec<String>, AppError> {
+    Ok(sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT c.relname::text
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = current_schema()
+          AND c.relkind = 'r'
+          AND c.relname <> '_sqlx_migrations'
+        ORDER BY c.relname
+        "#,
+    )
+    .fetch_all(connection)
+    .await?)
+}
+
+async fn applied_schema_version(pool: &PgPool) -> Result<i64, AppError> {
+    let has_history =
+        sqlx::query_scalar::<_, bool>("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    if !has_history {
+        return Ok(0);
+    }
+
+    let version = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT MAX(version) FROM _sqlx_migrations WHERE success",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(version.unwrap_or(0))
+}
+
+async fn write_table_part(
+    provider: &dyn StorageProvider,
+    request: &SnapshotExportRequest,
+    table: &str,
+    index: usize,
+    buffer: TablePartBuffer,
+) -> Result<SnapshotPart, AppError> {
+    let entry = table_part_entry(table, index);
+    let sha256 = FileHasher::sha256(&buffer.body);
+    let byte_size = buffer.body.len() as u64;
+    let object = snapshot_object(request.tenant_id, request.snapshot_id, &entry)?;
+    put_snapshot_entry(provider, &object, &entry, Bytes::from(buffer.body)).await?;
+
+    Ok(SnapshotPart {
+        entry,
+        row_count: buffer.row_count,
+        byte_size,
+        sha256,
+    })
+}
+
+async fn export_table(
+    tx: &mut Transaction<'_, Postgres>,
+    provider: &dyn StorageProvider,
+    request: &SnapshotExportRequest,
+    table: &str,
+) -> Result<SnapshotTable, AppError> {
+    let mut parts = Vec::new();
+    let mut buffer = TablePartBuffer::default();
+    let query = format!(
+        "SELECT row_to_json(t)::text FROM {} t",
+        quote_identifier(table)
+    );
+    let mut rows = sqlx::query_scalar::<_, String>(&query).fetch(&mut **tx);
+
+    while let Some(row) = rows.try_next().await? {
+        buffer.push(&row);
+        if buffer.is_full() {
+            let full = std::mem::take(&mut buffer);
+            parts.push(write_table_part(provider, request, table, parts.len() + 1, full).await?);
+        }
+    }
+    if !buffer.is_empty() {
+        parts.push(write_table_part(provider, request, table, parts.len() + 1, buffer).await?);
+    }
+
+    Ok(SnapshotTable {
+        name: table.to_string(),
+        row_count: parts.iter().map(|part| part.row_count).sum(),
+        parts,
+    })
+}
+
+async fn copy_object_into_snapshot(
+    provider: &dyn StorageProvider,
+    request: &SnapshotExportRequest,
+    object_key: String,
+    storage_class: StorageClass,
+    content_type: String,
+    expected_checksum: &str,
+) -> Result<SnapshotObject, AppError> {
+    let key = persisted_object_key(object_key.clone(), storage_class).map_err(|error| {
+        AppError::InternalServerError(format!("Invalid persisted object key: {}", error))
+    })?;
+    let source = StoredObject::new(key, content_type.clone());
+    let body = provider
+        .get(&source, SNAPSHOT_OBJECT_MAX_BYTES)
+        .await
+        .map_err(|error| storage_error(&object_key, error))?;
+    let sha256 = FileHasher::sha256(&body);
+    if sha256 != expected_checksum {
+        return Err(AppError::Conflict(format!(
+            "Stored object '{}' does not match its recorded checksum",
+            object_key
+        )));
+    }
+
+    let entry = object_entry(&sha256);
+    let byte_size = body.len() as u64;
+    let target = snapshot_object(request.tenant_id, request.snapshot_id, &entry)?;
+    put_snapshot_entry(provider, &target, &entry, body).await?;
+
+    Ok(SnapshotObject {
+        object_key,
+        storage_class: storage_class_code(storage_class).to_string(),
+        content_type,
+        byte_size,
+        sha256,
+    })
+}
+
+/// Takes a point-in-time logical export of one tenant database plus the File
+/// Platform objects it references. All table data is read inside a single
+/// repeatable-read transaction so the archive reflects one instant.
+pub async fn export_tenant_snapshot(
+    platform: &FilePlatform,
+    request: SnapshotExportRequest,
+) -> Result<SnapshotExportSummary, AppError> {
+    tracing::info!(
+        snapshot_id = %request.snapshot_id,
+        tenant_id = %request.tenant_id,
+        subdomain = %request.subdomain,
+        "Exporting tenant snapshot"
+    );
+
+    let provider = platform.provider().as_ref();
+    let pool = connect_tenant(&request.db_connection_string).await?;
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    let captured_at = sqlx::query_scalar::<_, DateTime<Utc>>("SELECT now()")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let migrations = sqlx::query(
+        r#"
+        SELECT version, encode(checksum, 'hex') AS checksum
+        FROM _sqlx_migrations
+        WHERE success
+        ORDER BY version
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(SnapshotMigration {
+            version: row.try_get("version")?,
+            checksum: row.try_get("checksum")?,
+        })
+    })
+    .collect::<Result<Vec<_>, sqlx::Error>>()?;
+    let schema_version = migrations
+        .last()
+        .map(|migration| migration.version)
+        .ok_or_else(|| AppError::Conflict("Tenant has no applied migrations".to_string()))?;
+
+    let table_names = list_tenant_tables(&mut tx).await?;
+    let mut tables = Vec::with_capacity(table_names.len());
+    for table in table_names
+        .iter()
+        .filter(|table| !UNEXPORTED_TABLES.contains(&table.as_str()))
+    {
+        tables.push(export_table(&mut tx, provider, &request, table).await?);
+    }
+
+    let sequences = sqlx::query(
+        r#"
+        SELECT sequencename::text AS name, last_value, start_value
+        FROM pg_sequences
+        WHERE schemaname = current_schema()
+        ORDER BY sequencename
+        "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(SnapshotSequence {
+            name: row.try_get("name")?,
+            last_value: row.try_get("last_value")?,
+            start_value: row.try_get("start_value")?,
+        })
+    })
+    .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+    let stored_objects = if table_names.iter().any(|table| table == "file_versions") {
+        sqlx::query(
+            r#"
+            SELECT DISTINCT object_key, storage_class, detected_mime_type, checksum
+            FROM (
+                SELECT object_key, storage_class, detected_mime_type, checksum
+                FROM file_versions
+                WHERE storage_status = 'stored'
+                UNION ALL
+                SELECT object_key, storage_class, detected_mime_type, checksum
+                FROM file_derivatives
+                WHERE storage_status = 'stored'
+            ) stored
+            ORDER BY object_key
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    // Object bytes are immutable per key, so the copy can run after the read
+    // transaction has released its snapshot.
+    tx.commit().await?;
+    pool.close().await;
+
+    let mut objects = Vec::with_capacity(stored_objects.len());
+    for row in stored_objects {
+        let storage_class =
+            parse_storage_class(row.try_get::<String, _>("storage_class")?.as_str())?;
+        let checksum: String = row.try_get("checksum")?;
+        objects.push(
+            copy_object_into_snapshot(
+                provider,
+                &request,
+                row.try_get("object_key")?,
+                storage_class,
+                row.try_get("detected_mime_type")?,
+                &checksum,
+            )
+            .await?,
+        );
+    }
+
+    let manifest = SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        snapshot_id: request.snapshot_id,
+        tenant_id: request.tenant_id,
+        subdomain: request.subdomain.clone(),
+        captured_at,
+        schema_version,
+        migrations,
+        tables,
+        sequences,
+        objects,
+    };
+    let manifest_body = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| AppError::InternalServerError(error.to_string()))?;
+    let manifest_checksum = FileHasher::sha256(&manifest_body);
+    let manifest_object = snapshot_object(request.tenant_id, request.snapshot_id, MANIFEST_ENTRY)?;
+    put_snapshot_entry(
+        provider,
+        &manifest_object,
+        MANIFEST_ENTRY,
+        Bytes::from(manifest_body),
+    )
+    .await?;
+
+    tracing::info!(
+        snapshot_id = %request.snapshot_id,
+        schema_version,
+        tables = manifest.tables.len(),
+        objects = manifest.objects.len(),
+        "Tenant snapshot exported"
+    );
+
+    Ok(SnapshotExportSummary {
+        snapshot_id: manifest.snapshot_id,
+        format_version: manifest.format_version,
+        schema_version: manifest.schema_version,
+        captured_at: manifest.captured_at,
+        manifest_checksum,
+        table_count: manifest.tables.len(),
+        row_count: manifest.row_count(),
+        object_count: manifest.objects.len(),
+        total_bytes: manifest.total_bytes(),
+    })
+}
+
+async fn load_manifest(
+    provider: &dyn StorageProvider,
+    request: &SnapshotRestoreRequest,
+) -> Result<SnapshotManifest, AppError> {
+    let object = snapshot_object(
+        request.source_tenant_id,
+        request.snapshot_id,
+        MANIFEST_ENTRY,
+    )?;
+    let body = provider
+        .get(&object, MANIFEST_MAX_BYTES)
+        .await
+        .map_err(|error| storage_error(MANIFEST_ENTRY, error))?;
+    verify_checksum(&body, &request.manifest_checksum, MANIFEST_ENTRY)?;
+
+    let manifest: SnapshotManifest = serde_json::from_slice(&body).map_err(|error| {
+        AppError::Conflict(format!("Snapshot manifest cannot be read: {}", error))
+    })?;
+    if manifest.snapshot_id != request.snapshot_id || manifest.tenant_id != request.source_tenant_id
+    {
+        return Err(AppError::Conflict(
+            "Snapshot manifest does not belong to the requested snapshot".to_string(),
+        ));
+    }
+
+    Ok(manifest)
+}
+
+/// Copies archived objects under the target tenant. Sandbox clones never read
+/// the archived bytes; they write a placeholder, replacing whatever an earlier
+/// restore left at the key, and return the object keys it stands in for.
+async fn restore_objects(
+    provider: &dyn StorageProvider,
+    request: &SnapshotRestoreRequest,
+    manifest: &SnapshotManifest,
+) -> Result<Vec<(String, Placeholder)>, AppError> {
+    let mut placeholders = Vec::new();
+    for object in &manifest.objects {
+        let key = persisted_object_key(
+            object.object_key.clone(),
+            parse_storage_class(&object.storage_class)?,
+        )
+        .and_then(|key| retarget_object_key(&key, request.target_tenant_id))
+        .map_err(|error| {
+            AppError::Conflict(format!(
+                "Snapshot object key '{}' is invalid: {}",
+                object.object_key, error
+            ))
+        })?;
+
+        if request.anonymization.is_some() {
+            let placeholder = anonymization_service::placeholder_for(&object.content_type);
+            let target = StoredObject::new(key, placeholder.content_type);
+            provider
+                .delete(&target)
+                .await
+                .map_err(|error| storage_error(target.object_key.as_str(), error))?;
+            provider
+                .put(&target, Bytes::from_static(placeholder.body))
+                .await
+                .map_err(|error| storage_error(target.object_key.as_str(), error))?;
+            placeholders.push((object.object_key.clone(), placeholder));
+            continue;
+        }
+
+        let entry = object_entry(&object.sha256);
+        let archived = snapshot_object(request.source_tenant_id, request.snapshot_id, &entry)?;
+        let body = provider
+            .get(&archived, SNAPSHOT_OBJECT_MAX_BYTES)
+            .await
+            .map_err(|error| storage_error(&entry, error))?;
+        verify_checksum(&body, &object.sha256, &entry)?;
+
+        let target = StoredObject::new(key, object.content_type.clone());
+        match provider.put(&target, body).await {
+            Ok(()) | Err(StorageError::AlreadyExists) => {}
+            Err(error) => return Err(storage_error(target.object_key.as_str(), error)),
+        }
+    }
+
+    Ok(placeholders)
+}
+
+async fn target_has_school_data(pool: &PgPool) -> Result<bool, AppError> {
+    let has_users = sqlx::query_scalar::<_, bool>("SELECT to_regclass('users') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !has_users {
+        return Ok(false);
+    }
+
+    Ok(
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users)")
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+async fn restore_table(
+    tx: &mut Transaction<'_, Postgres>,
+    provider: &dyn StorageProvider,
+    request: &SnapshotRestoreRequest,
+    table: &SnapshotTable,
+) -> Result<(), AppError> {
+    let columns = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT attname::text
+        FROM pg_attribute
+        WHERE attrelid = $1::regclass
+          AND attnum > 0
+          AND NOT attisdropped
+          AND attgenerated = ''
+        ORDER BY attnum
+        "#,
+    )
+    .bind(quote_identifier(&table.name))
+    .fetch_all(&mut **tx)
+    .await?
+    .iter()
+    .map(|column| quote_identifier(column))
+    .collect::<Vec<_>>()
+    .join(", ");
+    let insert = format!(
+        "INSERT INTO {table} ({columns}) OVERRIDING SYSTEM VALUE \
+         SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json)",
+        table = quote_identifier(&table.name),
+    );
+
+    for part in &table.parts {
+        let object = snapshot_object(request.source_tenant_id, request.snapshot_id, &part.entry)?;
+        let body = provider
+            .get(&object, TABLE_PART_READ_MAX_BYTES)
+            .await
+            .map_err(|error| storage_error(&part.entry, error))?;
+        verify_checksum(&body, &part.sha256, &part.entry)?;
+        let body = std::str::from_utf8(&body).map_err(|_| {
+            AppError::Conflict(format!("Snapshot entry '{}' is not UTF-8", part.entry))
+        })?;
+
+        for batch in json_array_batches(body, RESTORE_BATCH_ROWS) {
+            sqlx::query(&insert).bind(batch).execute(&mut **tx).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores a snapshot into a fresh or existing tenant. The target is migrated to
+/// the snapshot schema version, its tables are replaced with the archived rows,
+/// and `run_tenant_migrations` then brings it forward to this build.
+pub async fn restore_tenant_snapshot(
+    platform: &FilePlatform,
+    request: SnapshotRestoreRequest,
+) -> Result<SnapshotRestoreSummary, AppError> {
+    tracing::info!(
+        snapshot_id = %request.snapshot_id,
+        source_tenant_id = %request.source_tenant_id,
+        target_tenant_id = %request.target_tenant_id,
+        target_subdomain = %request.target_subdomain,
+        "Restoring tenant snapshot"
+    );
+
+    let sandbox_password_hash = match &request.anonymization {
+        Some(anonymization) => {
+            if request.target_tenant_id == request.source_tenant_id {
+                return Err(AppError::Conflict(
+                    "A sandbox clone must restore into a different tenant".to_string(),
+                ));
+            }
+            Some(
+                anonymization_service::hash_sandbox_password(&anonymization.sandbox_password)
+                    .await?,
+            )
+        }
+        None => None,
+    };
+
+    let provider = platform.provider().as_ref();
+    let manifest = load_manifest(provider, &request).await?;
+    let pool = connect_tenant(&request.db_connection_string).await?;
+
+    let target_version = applied_schema_version(&pool).await?;
+    check_restore_compatibility(&manifest, &tenant_migration_checksums(), target_version)?;
+    if !request.replace_existing && target_has_school_data(&pool).await? {
+        return Err(AppError::Conflict(
+            "Target tenant already has school data; confirm replaceExisting to overwrite it"
+                .to_string(),
+        ));
+    }
+
+    let placeholders = restore_objects(provider, &request, &manifest).await?;
+
+    run_tenant_migrations_through(&pool, manifest.schema_version)
+        .await
+        .map_err(AppError::InternalServerError)?;
+
+    let mut tx = pool.begin().await?;
+    // Rows are replayed as archived, so FK checks and row triggers are paused
+    // for this transaction only. Circular references (files <-> file_versions)
+    // cannot be ordered otherwise.
+    sqlx::query("SET LOCAL session_replication_role = replica")
+        .execute(&mut *tx)
+        .await?;
+
+    let target_tables = list_tenant_tables(&mut tx).await?;
+    let known_tables = target_tables
+        .iter()
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+    if let Some(missing) = manifest
+        .tables
+        .iter()
+        .find(|table| !known_tables.contains(table.name.as_str()))
+    {
+        return Err(AppError::Conflict(format!(
+            "Snapshot table '{}' does not exist at schema version {}",
+            missing.name, manifest.schema_version
+        )));
+    }
+
+    let truncate_targets = target_tables
+        .iter()
+        .map(|table| quote_identifier(table))
+        .collect::<Vec<_>>();
+    sqlx::query(&format!(
+        "TRUNCATE {} RESTART IDENTITY CASCADE",
+        truncate_targets.join(", ")
+    ))
+    .execute(&mut *tx)
+    .await?;
+
+    for table in &manifest.tables {
+        restore_table(&mut tx, provider, &request, table).await?;
+    }
+
+    for sequence in &manifest.sequences {
+        sqlx::query("SELECT setval($1::regclass, $2, $3)")
+            .bind(quote_identifier(&sequence.name))
+            .bind(sequence.last_value.unwrap_or(sequence.start_value))
+            .bind(sequence.last_value.is_some())
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let anonymization = match &sandbox_password_hash {
+        Some(password_hash) => {
+            anonymization_service::apply_object_placeholders(&mut tx, &placeholders).await?;
+            let mut summary = anonymization_service::anonymize_tenant(
+                &mut tx,
+                &request.target_tenant_id.to_string(),
+                password_hash,
+            )
+            .await?;
+            summary.placeholder_objects = placeholders.len();
+            Some(summary)
+        }
+        None => None,
+    };
+
+    if request.target_tenant_id != request.source_tenant_id
+        && known_tables.contains("file_versions")
+    {
+        for table in ["file_versions", "file_derivatives"] {
+            sqlx::query(&format!(
+                "UPDATE {table} \
+                 SET object_key = 'tenants/' || $2 || substr(object_key, length('tenants/' || $1) + 1) \
+                 WHERE object_key LIKE 'tenants/' || $1 || '/%'"
+            ))
+            .bind(request.source_tenant_id.to_string())
+            .bind(request.target_tenant_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    run_tenant_migrations(&pool)
+        .await
+        .map_err(AppError::InternalServerError)?;
+    let schema_version = applied_schema_version(&pool).await?;
+    pool.close().await;
+
+    tracing::info!(
+        snapshot_id = %request.snapshot_id,
+        snapshot_schema_version = manifest.schema_version,
+        schema_version,
+        anonymized = anonymization.is_some(),
+        "Tenant snapshot restored"
+    );
+
+    Ok(SnapshotRestoreSummary {
+        snapshot_id: manifest.snapshot_id,
+        snapshot_schema_version: manifest.schema_version,
+        schema_version,
+        table_count: manifest.tables.len(),
+        row_count: manifest.row_count(),
+        object_count: manifest.objects.len(),
+        anonymization,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(schema_version: i64, migrations: &[(i64, &str)]) -> SnapshotManifest {
+        SnapshotManifest {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            snapshot_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            subdomain: "sandbox".to_string(),
+            captured_at: Utc::now(),
+            schema_version,
+            migrations: migrations
+                .iter()
+                .map(|(version, checksum)| SnapshotMigration {
+                    version: *version,
+                    checksum: checksum.to_string(),
+                })
+                .collect(),
+            tables: Vec::new(),
+            sequences: Vec::new(),
+            objects: Vec::new(),
+        }
+    }
+
+    fn build() -> Vec<(i64, String)> {
+        vec![
+            (1, "aa".to_string()),
+            (2, "bb".to_string()),
+            (3, "cc".to_string()),
+        ]
+    }
+
+    #[test]
+    fn compatible_snapshots_may_be_older_than_the_build() {
+        let snapshot = manifest(2, &[(1, "AA"), (2, "bb")]);
+
+        assert!(check_restore_compatibility(&snapshot, &build(), 0).is_ok());
+        assert!(check_restore_compatibility(&snapshot, &build(), 2).is_ok());
+    }
+
+    #[test]
+    fn restore_rejects_newer_or_diverged_history() {
+        let newer = manifest(4, &[(1, "aa"), (4, "dd")]);
+        let diverged = manifest(2, &[(1, "aa"), (2, "zz")]);
+        let unknown = manifest(2, &[(1, "aa"), (5, "ee")]);
+
+        for snapshot in [newer, diverged, unknown] {
+            assert!(matches!(
+                check_restore_compatibility(&snapshot, &build(), 0),
+                Err(AppError::Conflict(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn restore_rejects_targets_past_the_snapshot_and_unknown_formats() {
+        let snapshot = manifest(2, &[(1, "aa"), (2, "bb")]);
+        assert!(check_restore_compatibility(&snapshot, &build(), 3).is_err());
+
+        let mut future_format = manifest(2, &[(1, "aa"), (2, "bb")]);
+        future_format.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+        assert!(check_restore_compatibility(&future_format, &build(), 0).is_err());
+    }
+
+    #[test]
+    fn json_lines_are_batched_into_arrays() {
+        let body = "{\"id\":1}\n{\"id\":2}\n\n{\"id\":3}\n";
+
+        assert_eq!(
+            json_array_batches(body, 2),
+            vec![
+                "[{\"id\":1},{\"id\":2}]".to_string(),
+                "[{\"id\":3}]".to_string()
+            ]
+        );
+        assert!(json_array_batches("", 2).is_empty());
+    }
+
+    #[test]
+    fn table_parts_fill_by_row_count() {
+        let mut buffer = TablePartBuffer::default();
+        for _ in 0..TABLE_PART_MAX_ROWS - 1 {
+            buffer.push("{}");
+        }
+        assert!(!buffer.is_full());
+
+        buffer.push("{}");
+        assert!(buffer.is_full());
+        assert_eq!(buffer.body.len(), TABLE_PART_MAX_ROWS * 3);
+    }
+
+    #[test]
+    fn identifiers_and_entries_are_escaped_for_archive_paths() {
+        assert_eq!(quote_identifier("users"), "\"users\"");
+        assert_eq!(quote_identifier("odd\"name"), "\"odd\"\"name\"");
+        assert_eq!(table_part_entry("users", 12), "tables/users/000012.jsonl");
+        assert_eq!(object_entry("ab12"), "objects/ab12");
+    }
+
+    #[test]
+    fn checksum_verification_names_the_entry() {
+        let expected = FileHasher::sha256(b"rows");
+
+        assert!(verify_checksum(b"rows", &expected.to_ascii_uppercase(), "x").is_ok());
+        match verify_checksum(b"tampered", &expected, "tables/users/000001.jsonl") {
+            Err(AppError::Conflict(message)) => assert!(message.contains("tables/users")),
+            other => panic!("expected checksum conflict, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::migration_service::connect_tenant;
+use crate::db::migration::applied_tenant_migration_version;
+use crate::error::AppError;
+use crate::modules::files::platform_types::FilePurpose;
+use crate::modules::system::models::{TenantUsageMetrics, TenantUsageRequest};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+
+/// Files created before the File Platform have no purpose code.
+const UNCLASSIFIED_PURPOSE: &str = "unclassified";
+
+#[derive(sqlx::FromRow)]
+struct AccountCounts {
+    active_users_7d: i64,
+    active_users_30d: i64,
+    student_count: i64,
+    staff_count: i64,
+    last_login_at: Option<DateTime<Utc>>,
+}
+
+/// Every known purpose is reported, so an unused purpose reads as zero rather
+/// than missing from the time series.
+fn storage_by_purpose(rows: Vec<(Option<String>, i64)>) -> BTreeMap<String, i64> {
+    let mut storage: BTreeMap<String, i64> = FilePurpose::ALL
+        .iter()
+        .map(|purpose| (purpose.code().to_string(), 0))
+        .collect();
+
+    for (purpose_code, bytes) in rows {
+        let key = purpose_code.unwrap_or_else(|| UNCLASSIFIED_PURPOSE.to_string());
+        *storage.entry(key).or_insert(0) += bytes;
+    }
+
+    storage
+}
+
+async fn read_account_counts(pool: &PgPool) -> Result<AccountCounts, AppError> {
+    Ok(sqlx::query_as::<_, AccountCounts>(
+        "SELECT
+            (SELECT COUNT(DISTINCT user_id) FROM auth_sessions
+             WHERE last_seen_at >= NOW() - INTERVAL '7 days'
+               AND support_access_id IS NULL) AS active_users_7d,
+            (SELECT COUNT(DISTINCT user_id) FROM auth_sessions
+             WHERE last_seen_at >= NOW() - INTERVAL '30 days'
+               AND support_access_id IS NULL) AS active_users_30d,
+            (SELECT COUNT(*) FROM users
+             WHERE user_type = 'student' AND status = 'active') AS student_count,
+            (SELECT COUNT(*) FROM users
+             WHERE user_type = 'staff' AND status = 'active') AS staff_count,
+            (SELECT MAX(created_at) FROM auth_sessions
+             WHERE support_access_id IS NULL) AS last_login_at",
+    )
+    .fetch_one(pool)
+    .await?)
+}
+
+async fn read_storage(pool: &PgPool) -> Result<Vec<(Option<String>, i64)>, AppError> {
+    Ok(sqlx::query_as::<_, (Option<String>, i64)>(
+        "SELECT f.purpose_code, COALESCE(SUM(stored.byte_size), 0)::BIGINT
+         FROM files f
+         JOIN (
+             SELECT file_id, byte_size FROM file_versions WHERE storage_status <> 'deleted'
+             UNION ALL
+             SELECT file_id, byte_size FROM file_derivatives WHERE storage_status <> 'deleted'
+         ) stored ON stored.file_id = f.id
+         GROUP BY f.purpose_code",
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+async fn read_usage(pool: &PgPool, subdomain: String) -> Result<TenantUsageMetrics, AppError> {
+    let accounts = read_account_counts(pool).await?;
+    let storage_bytes_by_purpose = storage_by_purpose(read_storage(pool).await?);
+    let certificates_issued = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM certificates")
+        .fetch_one(pool)
+        .await?;
+    let database_bytes =
+        sqlx::query_scalar::<_, i64>("SELECT pg_database_size(current_database())")
+            .fetch_one(pool)
+            .await?;
+
+    Ok(TenantUsageMetrics {
+        subdomain,
+        collected_at: Utc::now(),
+        active_users_7d: accounts.active_users_7d,
+        active_users_30d: accounts.active_users_30d,
+        student_count: accounts.student_count,
+        staff_count: accounts.staff_count,
+        storage_bytes: storage_bytes_by_purpose.values().sum(),
+        storage_bytes_by_purpose,
+        certificates_issued,
+        last_login_at: accounts.last_login_at,
+        migration_version: applied_tenant_migration_version(pool).await.ok(),
+        database_bytes,
+    })
+}
+
+/// Read aggregate usage counters of one tenant for backend-admin analytics.
+///
+/// Only counts and sizes leave the tenant; no user rows are returned.
+pub async fn collect_tenant_usage(
+    payload: TenantUsageRequest,
+) -> Result<TenantUsageMetrics, AppError> {
+    let pool = connect_tenant(&payload.db_connection_string)
+        .await
+        .map_err(AppError::InternalServerError)?;
+    let usage = read_usage(&pool, payload.subdomain).await;
+    pool.close().await;
+
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{storage_by_purpose, UNCLASSIFIED_PURPOSE};
+    use crate::modules::files::platform_types::FilePurpose;
+
+    #[test]
+    fn storage_reports_every_purpose_and_unclassified_files() {
+        let storage =
+            storage_by_purpose(vec![(Some("certificate".to_string()), 2048), (None, 512)]);
+
+        assert_eq!(storage.len(), FilePurpose::ALL.len() + 1);
+        assert_eq!(storage["certificate"], 2048);
+        assert_eq!(storage["school_logo"], 0);
+        assert_eq!(storage[UNCLASSIFIED_PURPOSE], 512);
+    }
+}
@@ -416,6 +416,22 @@ pub async fn create_work_item(pool: &PgPool, input: CreateWorkItemInput) -> Resu
     Ok(item_id)
 }
 
+/// Closes an active work item once its source resource no longer needs action.
+/// Items that are already closed, cancelled or archived are left untouched.
+pub async fn close_work_item(pool: &PgPool, work_item_id: Uuid) -> Result<bool, AppError> {
+    let result =
+        sqlx::query("UPDATE work_items SET status = 'closed' WHERE id = $1 AND status = 'active'")
+            .bind(work_item_id)
+            .execute(pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("Failed to close work item: {}", error);
+                AppError::InternalServerError("ไม่สามารถปิดงานได้".to_string())
+            })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 pub async fn list_my_work_items(
     pool: &PgPool,
     user_id: Uuid,
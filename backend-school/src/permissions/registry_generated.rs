@@ -1,5 +1,5 @@
 // @generated by scripts/generate-permissions.mjs; DO NOT EDIT.
-// contract-sha256: 8a61f57e23c9ffbbbfb7cbb543e55990a86bee536c8f2ccef9a1df7e5bf9b9a8
+// contract-sha256: 503ea1a5f4f7ff78706fcbb2711c106bb971afad625762360f4b36a428c20d06
 
 pub mod codes {
     pub const WILDCARD: &str = "*";
@@ -15,6 +15,7 @@ pub mod codes {
     pub const ACADEMIC_CLASSROOM_UPDATE_ALL: &str = "academic_classroom.update.all";
     pub const ACADEMIC_COURSE_PLAN_MANAGE_ALL: &str = "academic_course_plan.manage.all";
     pub const ACADEMIC_COURSE_PLAN_READ_ALL: &str = "academic_course_plan.read.all";
+    pub const ACADEMIC_CURRICULUM_APPROVE_ALL: &str = "academic_curriculum.approve.all";
     pub const ACADEMIC_CURRICULUM_CREATE_ALL: &str = "academic_curriculum.create.all";
     pub const ACADEMIC_CURRICULUM_DELETE_ALL: &str = "academic_curriculum.delete.all";
     pub const ACADEMIC_CURRICULUM_MANAGE_ORGANIZATION_TREE: &str =
@@ -61,6 +62,15 @@ pub mod codes {
     pub const ADMISSION_READ_ALL: &str = "admission.read.all";
     pub const ADMISSION_SCORES_ALL: &str = "admission.scores.all";
     pub const ADMISSION_VERIFY_ALL: &str = "admission.verify.all";
+    pub const ANNOUNCEMENT_MANAGE_SCHOOL: &str = "announcement.manage.school";
+    pub const ANNOUNCEMENT_READ_SCHOOL: &str = "announcement.read.school";
+    pub const BEHAVIOR_CONFIDENTIAL_READ_SCHOOL: &str = "behavior_confidential.read.school";
+    pub const BEHAVIOR_CREATE_SCHOOL: &str = "behavior.create.school";
+    pub const BEHAVIOR_MANAGE_ASSIGNED: &str = "behavior.manage.assigned";
+    pub const BEHAVIOR_MANAGE_SCHOOL: &str = "behavior.manage.school";
+    pub const BEHAVIOR_READ_ASSIGNED: &str = "behavior.read.assigned";
+    pub const BEHAVIOR_READ_OWN: &str = "behavior.read.own";
+    pub const BEHAVIOR_READ_SCHOOL: &str = "behavior.read.school";
     pub const CALENDAR_MANAGE_SCHOOL: &str = "calendar.manage.school";
     pub const CALENDAR_READ_SCHOOL: &str = "calendar.read.school";
     pub const CERTIFICATE_CREATE_ORGANIZATION_UNIT: &str = "certificate.create.organization_unit";
@@ -86,6 +96,9 @@ pub mod codes {
     pub const FACILITY_UPDATE_ALL: &str = "facility.update.all";
     pub const FEATURES_READ_ALL: &str = "features.read.all";
     pub const FEATURES_UPDATE_ALL: &str = "features.update.all";
+    pub const FINANCE_ISSUE_SCHOOL: &str = "finance.issue.school";
+    pub const FINANCE_MANAGE_SCHOOL: &str = "finance.manage.school";
+    pub const FINANCE_READ_SCHOOL: &str = "finance.read.school";
     pub const MENU_CREATE_ALL: &str = "menu.create.all";
     pub const MENU_DELETE_ALL: &str = "menu.delete.all";
     pub const MENU_READ_ALL: &str = "menu.read.all";
@@ -97,6 +110,11 @@ pub mod codes {
         "organization_work.read.organization_unit";
     pub const ORGANIZATION_WORK_READ_OWN: &str = "organization_work.read.own";
     pub const ORGANIZATION_WORK_UPDATE_OWN: &str = "organization_work.update.own";
+    pub const PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL: &str =
+        "professional_development.manage.school";
+    pub const PROFESSIONAL_DEVELOPMENT_READ_OWN: &str = "professional_development.read.own";
+    pub const PROFESSIONAL_DEVELOPMENT_READ_SCHOOL: &str = "professional_development.read.school";
+    pub const PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN: &str = "professional_development.submit.own";
     pub const ROLES_ASSIGN_ALL: &str = "roles.assign.all";
     pub const ROLES_CREATE_ALL: &str = "roles.create.all";
     pub const ROLES_DELETE_ALL: &str = "roles.delete.all";
@@ -107,6 +125,11 @@ pub mod codes {
     pub const SETTINGS_UPDATE_ALL: &str = "settings.update.all";
     pub const STAFF_CREATE_ALL: &str = "staff.create.all";
     pub const STAFF_DELETE_ALL: &str = "staff.delete.all";
+    pub const STAFF_LEAVE_APPROVE_SCHOOL: &str = "staff_leave.approve.school";
+    pub const STAFF_LEAVE_MANAGE_SCHOOL: &str = "staff_leave.manage.school";
+    pub const STAFF_LEAVE_READ_OWN: &str = "staff_leave.read.own";
+    pub const STAFF_LEAVE_READ_SCHOOL: &str = "staff_leave.read.school";
+    pub const STAFF_LEAVE_REQUEST_OWN: &str = "staff_leave.request.own";
     pub const STAFF_PII_READ_OWN: &str = "staff_pii.read.own";
     pub const STAFF_PII_READ_SCHOOL: &str = "staff_pii.read.school";
     pub const STAFF_PROFILE_READ_ORGANIZATION_TREE: &str = "staff_profile.read.organization_tree";
@@ -117,6 +140,10 @@ pub mod codes {
     pub const STAFF_UPDATE_ALL: &str = "staff.update.all";
     pub const STUDENT_CREATE_ALL: &str = "student.create.all";
     pub const STUDENT_DELETE_ALL: &str = "student.delete.all";
+    pub const STUDENT_LEAVE_APPROVE_ASSIGNED: &str = "student_leave.approve.assigned";
+    pub const STUDENT_LEAVE_APPROVE_SCHOOL: &str = "student_leave.approve.school";
+    pub const STUDENT_LEAVE_READ_ASSIGNED: &str = "student_leave.read.assigned";
+    pub const STUDENT_LEAVE_READ_SCHOOL: &str = "student_leave.read.school";
     pub const STUDENT_PII_READ_ASSIGNED: &str = "student_pii.read.assigned";
     pub const STUDENT_PII_READ_OWN: &str = "student_pii.read.own";
     pub const STUDENT_PII_READ_SCHOOL: &str = "student_pii.read.school";
@@ -235,6 +262,14 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "all",
         description: "ดูวิชาที่เปิดสอนในแต่ละห้องเรียน",
     },
+    PermissionDef {
+        code: codes::ACADEMIC_CURRICULUM_APPROVE_ALL,
+        name: "อนุมัติยกเว้นเกณฑ์หลักสูตร",
+        module: "academic_curriculum",
+        action: "approve",
+        scope: "all",
+        description: "บันทึกการอนุมัติให้เปิดใช้หรือสร้างรายวิชาจากหลักสูตรที่ไม่ผ่านเกณฑ์โครงสร้างเวลาเรียน",
+    },
     PermissionDef {
         code: codes::ACADEMIC_CURRICULUM_CREATE_ALL,
         name: "สร้างรายวิชา",
@@ -555,6 +590,78 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "all",
         description: "ยืนยัน/ปฏิเสธใบสมัครของผู้สมัคร",
     },
+    PermissionDef {
+        code: codes::ANNOUNCEMENT_MANAGE_SCHOOL,
+        name: "จัดการประกาศของโรงเรียน",
+        module: "announcement",
+        action: "manage",
+        scope: "school",
+        description: "สร้าง แก้ไข เผยแพร่ และเก็บประกาศ พร้อมกำหนดกลุ่มผู้รับและส่งการแจ้งเตือน",
+    },
+    PermissionDef {
+        code: codes::ANNOUNCEMENT_READ_SCHOOL,
+        name: "ดูประกาศและการรับทราบทั้งโรงเรียน",
+        module: "announcement",
+        action: "read",
+        scope: "school",
+        description: "ดูประกาศทุกฉบับรวมถึงฉบับร่าง และรายงานการอ่านและการรับทราบ",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_CONFIDENTIAL_READ_SCHOOL,
+        name: "ดูเหตุการณ์พฤติกรรมที่เป็นความลับ",
+        module: "behavior_confidential",
+        action: "read",
+        scope: "school",
+        description: "ดูรายละเอียดและหลักฐานของเหตุการณ์พฤติกรรมที่ถูกระบุว่าเป็นความลับ",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_CREATE_SCHOOL,
+        name: "บันทึกพฤติกรรมนักเรียน",
+        module: "behavior",
+        action: "create",
+        scope: "school",
+        description: "บันทึกเหตุการณ์ความผิดหรือความดีของนักเรียนทุกคนในโรงเรียน",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_MANAGE_ASSIGNED,
+        name: "ติดตามพฤติกรรมนักเรียนในที่ปรึกษา",
+        module: "behavior",
+        action: "manage",
+        scope: "assigned",
+        description: "บันทึกและปรับสถานะการติดตามช่วยเหลือนักเรียนในห้องที่เป็นครูที่ปรึกษา",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_MANAGE_SCHOOL,
+        name: "จัดการระบบพฤติกรรมนักเรียน",
+        module: "behavior",
+        action: "manage",
+        scope: "school",
+        description: "จัดการหมวดคะแนน เกณฑ์เฝ้าระวัง ยกเลิกเหตุการณ์ และการติดตามช่วยเหลือทั้งโรงเรียน",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_READ_ASSIGNED,
+        name: "ดูพฤติกรรมนักเรียนในที่ปรึกษา",
+        module: "behavior",
+        action: "read",
+        scope: "assigned",
+        description: "ดูเหตุการณ์ คะแนน และการติดตามพฤติกรรมของนักเรียนในห้องที่เป็นครูที่ปรึกษา",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_READ_OWN,
+        name: "ดูพฤติกรรมของตนเอง",
+        module: "behavior",
+        action: "read",
+        scope: "own",
+        description: "นักเรียนดูคะแนนและเหตุการณ์พฤติกรรมที่ไม่เป็นความลับของตนเอง",
+    },
+    PermissionDef {
+        code: codes::BEHAVIOR_READ_SCHOOL,
+        name: "ดูพฤติกรรมนักเรียนทั้งโรงเรียน",
+        module: "behavior",
+        action: "read",
+        scope: "school",
+        description: "ดูเหตุการณ์ คะแนน และการติดตามพฤติกรรมของนักเรียนทั้งโรงเรียน",
+    },
     PermissionDef {
         code: codes::CALENDAR_MANAGE_SCHOOL,
         name: "จัดการปฏิทินโรงเรียน",
@@ -747,6 +854,30 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "all",
         description: "แก้ไขการตั้งค่าฟีเจอร์",
     },
+    PermissionDef {
+        code: codes::FINANCE_ISSUE_SCHOOL,
+        name: "รับชำระเงินและออกใบเสร็จ",
+        module: "finance",
+        action: "issue",
+        scope: "school",
+        description: "บันทึกการรับชำระเงิน ตรวจสอบสลิปโอนเงินจากผู้ปกครอง และออกใบเสร็จรับเงิน",
+    },
+    PermissionDef {
+        code: codes::FINANCE_MANAGE_SCHOOL,
+        name: "จัดการค่าธรรมเนียมและใบแจ้งหนี้",
+        module: "finance",
+        action: "manage",
+        scope: "school",
+        description: "กำหนดรายการค่าธรรมเนียม อัตราตามระดับชั้น ส่วนลดและทุนการศึกษา ออกใบแจ้งหนี้ และยกเลิกใบแจ้งหนี้",
+    },
+    PermissionDef {
+        code: codes::FINANCE_READ_SCHOOL,
+        name: "ดูข้อมูลการเงินค่าธรรมเนียม",
+        module: "finance",
+        action: "read",
+        scope: "school",
+        description: "ดูรายการค่าธรรมเนียม ใบแจ้งหนี้ การชำระเงิน ใบเสร็จ และรายงานกระทบยอดรายวันของโรงเรียน",
+    },
     PermissionDef {
         code: codes::MENU_CREATE_ALL,
         name: "สร้างเมนู",
@@ -819,6 +950,38 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "own",
         description: "แก้ไขรายละเอียดงานของตนเอง",
     },
+    PermissionDef {
+        code: codes::PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL,
+        name: "จัดการเกณฑ์และบันทึกการพัฒนาวิชาชีพ",
+        module: "professional_development",
+        action: "manage",
+        scope: "school",
+        description: "กำหนดเกณฑ์ชั่วโมงประจำปีและแก้ไขบันทึก PLC และการอบรมของบุคลากรทุกคน",
+    },
+    PermissionDef {
+        code: codes::PROFESSIONAL_DEVELOPMENT_READ_OWN,
+        name: "ดูแฟ้มพัฒนาวิชาชีพของตนเอง",
+        module: "professional_development",
+        action: "read",
+        scope: "own",
+        description: "ดูชั่วโมง PLC ชั่วโมงอบรม และพิมพ์แฟ้มสะสมผลงานของตนเอง",
+    },
+    PermissionDef {
+        code: codes::PROFESSIONAL_DEVELOPMENT_READ_SCHOOL,
+        name: "ดูการพัฒนาวิชาชีพของบุคลากรทั้งโรงเรียน",
+        module: "professional_development",
+        action: "read",
+        scope: "school",
+        description: "ดูสรุปชั่วโมงและแฟ้มสะสมผลงานการพัฒนาวิชาชีพของบุคลากรทุกคน",
+    },
+    PermissionDef {
+        code: codes::PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN,
+        name: "บันทึกการพัฒนาวิชาชีพของตนเอง",
+        module: "professional_development",
+        action: "submit",
+        scope: "own",
+        description: "บันทึกกิจกรรม PLC การอบรม และแนบหลักฐานของตนเอง",
+    },
     PermissionDef {
         code: codes::ROLES_ASSIGN_ALL,
         name: "มอบหมายบทบาท",
@@ -899,6 +1062,46 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "all",
         description: "ลบบุคลากร",
     },
+    PermissionDef {
+        code: codes::STAFF_LEAVE_APPROVE_SCHOOL,
+        name: "อนุมัติการลาของบุคลากร",
+        module: "staff_leave",
+        action: "approve",
+        scope: "school",
+        description: "พิจารณาใบลาขั้นสุดท้ายในนามโรงเรียน",
+    },
+    PermissionDef {
+        code: codes::STAFF_LEAVE_MANAGE_SCHOOL,
+        name: "จัดการประเภทการลาและวันลา",
+        module: "staff_leave",
+        action: "manage",
+        scope: "school",
+        description: "กำหนดประเภทการลา สิทธิ์วันลาประจำปี และปรับยอดวันลาของบุคลากร",
+    },
+    PermissionDef {
+        code: codes::STAFF_LEAVE_READ_OWN,
+        name: "ดูใบลาและวันลาคงเหลือของตนเอง",
+        module: "staff_leave",
+        action: "read",
+        scope: "own",
+        description: "ดูประวัติการลาและยอดวันลาคงเหลือของตนเอง",
+    },
+    PermissionDef {
+        code: codes::STAFF_LEAVE_READ_SCHOOL,
+        name: "ดูการลาของบุคลากรทั้งโรงเรียน",
+        module: "staff_leave",
+        action: "read",
+        scope: "school",
+        description: "ดูใบลา ยอดวันลา และปฏิทินการลาของบุคลากรทุกคน",
+    },
+    PermissionDef {
+        code: codes::STAFF_LEAVE_REQUEST_OWN,
+        name: "ยื่นใบลาของตนเอง",
+        module: "staff_leave",
+        action: "request",
+        scope: "own",
+        description: "ยื่น แก้ไข และยกเลิกใบลาของตนเอง",
+    },
     PermissionDef {
         code: codes::STAFF_PII_READ_OWN,
         name: "ดูข้อมูลอ่อนไหวบุคลากรของตนเอง",
@@ -979,6 +1182,38 @@ pub const ALL_PERMISSIONS: &[PermissionDef] = &[
         scope: "all",
         description: "ลบนักเรียน",
     },
+    PermissionDef {
+        code: codes::STUDENT_LEAVE_APPROVE_ASSIGNED,
+        name: "พิจารณาใบลานักเรียนในที่ปรึกษา",
+        module: "student_leave",
+        action: "approve",
+        scope: "assigned",
+        description: "อนุมัติ ส่งกลับแก้ไข หรือไม่อนุมัติคำขอลาของนักเรียนในห้องที่เป็นครูที่ปรึกษา",
+    },
+    PermissionDef {
+        code: codes::STUDENT_LEAVE_APPROVE_SCHOOL,
+        name: "พิจารณาใบลานักเรียนทั้งโรงเรียน",
+        module: "student_leave",
+        action: "approve",
+        scope: "school",
+        description: "อนุมัติ ส่งกลับแก้ไข หรือไม่อนุมัติคำขอลาของนักเรียนทุกคนในโรงเรียน",
+    },
+    PermissionDef {
+        code: codes::STUDENT_LEAVE_READ_ASSIGNED,
+        name: "ดูใบลานักเรียนในที่ปรึกษา",
+        module: "student_leave",
+        action: "read",
+        scope: "assigned",
+        description: "ดูคำขอลาที่ผู้ปกครองส่งสำหรับนักเรียนในห้องที่เป็นครูที่ปรึกษา",
+    },
+    PermissionDef {
+        code: codes::STUDENT_LEAVE_READ_SCHOOL,
+        name: "ดูใบลานักเรียนทั้งโรงเรียน",
+        module: "student_leave",
+        action: "read",
+        scope: "school",
+        description: "ดูคำขอลาของนักเรียนทุกคนในโรงเรียน",
+    },
     PermissionDef {
         code: codes::STUDENT_PII_READ_ASSIGNED,
         name: "ดูข้อมูลอ่อนไหวนักเรียนที่รับผิดชอบ",
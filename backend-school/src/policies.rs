@@ -1,12 +1,18 @@
 pub mod achievement_access_policy;
 pub mod activity_access_policy;
+pub mod announcement_access_policy;
+pub mod behavior_access_policy;
 pub mod certificate_access_policy;
 pub mod curriculum_access_policy;
 pub mod file_access_policy;
+pub mod finance_access_policy;
 pub mod organization_access_policy;
+pub mod professional_development_access_policy;
 pub mod question_bank_access_policy;
 pub mod resource_access_policy;
 pub mod staff_access_policy;
+pub mod staff_leave_access_policy;
 pub mod student_access_policy;
+pub mod student_leave_access_policy;
 pub mod supervision_access_policy;
 pub mod workflow_access_policy;
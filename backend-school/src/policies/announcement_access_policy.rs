@@ -0,0 +1,85 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::announcement::services as announcement_service;
+use crate::permissions::registry::codes;
+
+const ANNOUNCEMENT_SCHOOL_READ: [&str; 2] = [
+    codes::ANNOUNCEMENT_READ_SCHOOL,
+    codes::ANNOUNCEMENT_MANAGE_SCHOOL,
+];
+
+/// Announcement managers and readers see every announcement, including drafts
+/// and receipts.
+pub fn can_read_school_announcements(actor: &ActorContext) -> bool {
+    actor.has_any_permission(&ANNOUNCEMENT_SCHOOL_READ)
+}
+
+pub fn require_announcement_school_read(actor: &ActorContext) -> Result<(), AppError> {
+    if can_read_school_announcements(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ดูประกาศทั้งโรงเรียน".to_string()))
+    }
+}
+
+pub fn require_announcement_manage(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::ANNOUNCEMENT_MANAGE_SCHOOL) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์จัดการประกาศ".to_string()))
+    }
+}
+
+/// Audience members may read an announcement only while it is live; school
+/// readers may read it at any stage.
+pub async fn require_announcement_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    announcement_id: Uuid,
+) -> Result<(), AppError> {
+    if can_read_school_announcements(actor)
+        || announcement_service::is_live_for_member(pool, announcement_id, actor.user_id).await?
+    {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("ไม่พบประกาศ".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id: Uuid::new_v4(),
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn managers_and_readers_see_all_announcements() {
+        assert!(can_read_school_announcements(&actor(&[
+            codes::ANNOUNCEMENT_READ_SCHOOL
+        ])));
+        assert!(can_read_school_announcements(&actor(&[
+            codes::ANNOUNCEMENT_MANAGE_SCHOOL
+        ])));
+        assert!(!can_read_school_announcements(&actor(&[])));
+    }
+
+    #[test]
+    fn only_managers_edit_announcements() {
+        assert!(require_announcement_manage(&actor(&[codes::ANNOUNCEMENT_MANAGE_SCHOOL])).is_ok());
+        assert!(matches!(
+            require_announcement_manage(&actor(&[codes::ANNOUNCEMENT_READ_SCHOOL])),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+}
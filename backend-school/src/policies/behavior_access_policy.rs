@@ -0,0 +1,164 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::permissions::registry::codes;
+use crate::policies::resource_access_policy::{
+    self, ResourceAccessGrant, ResourceAccessPermissions, UserResourceListAccess,
+};
+use crate::policies::student_access_policy;
+
+const BEHAVIOR_READ_ACCESS: ResourceAccessPermissions = ResourceAccessPermissions {
+    own: Some(codes::BEHAVIOR_READ_OWN),
+    assigned: Some(codes::BEHAVIOR_READ_ASSIGNED),
+    organization_unit: None,
+    organization_tree: None,
+    school: Some(codes::BEHAVIOR_READ_SCHOOL),
+};
+
+const BEHAVIOR_MANAGE_ACCESS: ResourceAccessPermissions = ResourceAccessPermissions {
+    own: None,
+    assigned: Some(codes::BEHAVIOR_MANAGE_ASSIGNED),
+    organization_unit: None,
+    organization_tree: None,
+    school: Some(codes::BEHAVIOR_MANAGE_SCHOOL),
+};
+
+pub fn resolve_behavior_list_access(
+    actor: &ActorContext,
+) -> Result<UserResourceListAccess, AppError> {
+    resource_access_policy::resolve_user_resource_list_access(actor, BEHAVIOR_READ_ACCESS)
+        .ok_or_else(|| AppError::Forbidden("ไม่มีสิทธิ์ดูข้อมูลพฤติกรรมนักเรียน".to_string()))
+}
+
+pub fn require_behavior_create(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_any_permission(&[codes::BEHAVIOR_CREATE_SCHOOL, codes::BEHAVIOR_MANAGE_SCHOOL]) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์บันทึกพฤติกรรมนักเรียน".to_string()))
+    }
+}
+
+pub fn require_behavior_manage_school(actor: &ActorContext) -> Result<(), AppError> {
+    actor.require_permission(codes::BEHAVIOR_MANAGE_SCHOOL)
+}
+
+pub async fn require_student_behavior_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<ResourceAccessGrant, AppError> {
+    let target = student_access_policy::student_resource_target(pool, student_id).await?;
+    resource_access_policy::require_resource_access(
+        pool,
+        actor,
+        BEHAVIOR_READ_ACCESS,
+        &target,
+        "ไม่มีสิทธิ์ดูข้อมูลพฤติกรรมของนักเรียนนี้",
+    )
+    .await
+}
+
+pub async fn require_student_behavior_manage(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    let target = student_access_policy::student_resource_target(pool, student_id).await?;
+    resource_access_policy::require_resource_access(
+        pool,
+        actor,
+        BEHAVIOR_MANAGE_ACCESS,
+        &target,
+        "ไม่มีสิทธิ์ติดตามพฤติกรรมของนักเรียนนี้",
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Confidential incidents are visible only to holders of the dedicated confidential
+/// permission and to the staff member who reported them; student-scoped read grants
+/// (own, assigned, school) never reveal them on their own.
+pub fn can_view_confidential_incident(actor: &ActorContext, reported_by: Option<Uuid>) -> bool {
+    reported_by == Some(actor.user_id)
+        || actor.has_permission(codes::BEHAVIOR_CONFIDENTIAL_READ_SCHOOL)
+}
+
+pub fn can_view_incident(
+    actor: &ActorContext,
+    is_confidential: bool,
+    reported_by: Option<Uuid>,
+) -> bool {
+    !is_confidential || can_view_confidential_incident(actor, reported_by)
+}
+
+pub async fn require_incident_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+    is_confidential: bool,
+    reported_by: Option<Uuid>,
+) -> Result<(), AppError> {
+    if reported_by == Some(actor.user_id) {
+        return Ok(());
+    }
+    require_student_behavior_read(pool, actor, student_id).await?;
+    if can_view_incident(actor, is_confidential, reported_by) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound("ไม่พบเหตุการณ์พฤติกรรม".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(user_id: Uuid, permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id,
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn school_read_does_not_reveal_confidential_incidents() {
+        let actor = actor(Uuid::new_v4(), &[codes::BEHAVIOR_READ_SCHOOL]);
+
+        assert!(can_view_incident(&actor, false, Some(Uuid::new_v4())));
+        assert!(!can_view_incident(&actor, true, Some(Uuid::new_v4())));
+    }
+
+    #[test]
+    fn reporter_and_confidential_reader_can_view_confidential_incidents() {
+        let reporter_id = Uuid::new_v4();
+        let reporter = actor(reporter_id, &[codes::BEHAVIOR_CREATE_SCHOOL]);
+        let counselor = actor(Uuid::new_v4(), &[codes::BEHAVIOR_CONFIDENTIAL_READ_SCHOOL]);
+
+        assert!(can_view_incident(&reporter, true, Some(reporter_id)));
+        assert!(can_view_incident(&counselor, true, Some(reporter_id)));
+    }
+
+    #[test]
+    fn behavior_list_access_supports_assigned_advisors() {
+        let user_id = Uuid::new_v4();
+        let actor = actor(user_id, &[codes::BEHAVIOR_READ_ASSIGNED]);
+
+        assert_eq!(
+            resolve_behavior_list_access(&actor).expect("assigned access should resolve"),
+            UserResourceListAccess::Assigned(user_id)
+        );
+    }
+
+    #[test]
+    fn create_permission_alone_cannot_list_behavior() {
+        let actor = actor(Uuid::new_v4(), &[codes::BEHAVIOR_CREATE_SCHOOL]);
+
+        assert!(resolve_behavior_list_access(&actor).is_err());
+        assert!(require_behavior_create(&actor).is_ok());
+    }
+}
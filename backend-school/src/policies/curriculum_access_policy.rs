@@ -74,6 +74,27 @@ pub fn ensure_curriculum_delete(actor: &ActorContext) -> Result<(), AppError> {
     )
 }
 
+/// Rule sets apply to every study plan, so organization-scoped curriculum
+/// managers cannot edit them.
+pub fn ensure_curriculum_rule_set_manage(actor: &ActorContext) -> Result<(), AppError> {
+    ensure_school_permission(actor, codes::ACADEMIC_CURRICULUM_UPDATE_ALL)
+}
+
+pub fn ensure_curriculum_compliance_override(actor: &ActorContext) -> Result<(), AppError> {
+    ensure_school_permission(actor, codes::ACADEMIC_CURRICULUM_APPROVE_ALL)
+}
+
+fn ensure_school_permission(
+    actor: &ActorContext,
+    school_permission: &'static str,
+) -> Result<(), AppError> {
+    if actor.has_permission(school_permission) {
+        return Ok(());
+    }
+
+    Err(AppError::Forbidden(format!("ไม่มีสิทธิ์ {school_permission}")))
+}
+
 fn ensure_curriculum_access(
     actor: &ActorContext,
     permissions: ResourceAccessPermissions,
@@ -266,4 +287,25 @@ mod tests {
             Err(AppError::Forbidden(_))
         ));
     }
+
+    #[test]
+    fn curriculum_rule_set_and_override_helpers_require_school_permissions() {
+        let scoped_manager = actor(
+            Uuid::new_v4(),
+            &[codes::ACADEMIC_CURRICULUM_MANAGE_ORGANIZATION_TREE],
+        );
+        let school_manager = actor(Uuid::new_v4(), &[codes::ACADEMIC_CURRICULUM_UPDATE_ALL]);
+        let approver = actor(Uuid::new_v4(), &[codes::ACADEMIC_CURRICULUM_APPROVE_ALL]);
+
+        assert!(matches!(
+            ensure_curriculum_rule_set_manage(&scoped_manager),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(ensure_curriculum_rule_set_manage(&school_manager).is_ok());
+        assert!(matches!(
+            ensure_curriculum_compliance_override(&school_manager),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(ensure_curriculum_compliance_override(&approver).is_ok());
+    }
 }
@@ -13,9 +13,11 @@ use crate::{
     },
     permissions::registry::codes,
     policies::{
-        achievement_access_policy,
+        achievement_access_policy, announcement_access_policy, behavior_access_policy,
         certificate_access_policy::{self, CertificateAction},
-        question_bank_access_policy, staff_access_policy, student_access_policy,
+        finance_access_policy, professional_development_access_policy, question_bank_access_policy,
+        staff_access_policy, staff_leave_access_policy, student_access_policy,
+        student_leave_access_policy,
     },
 };
 
@@ -90,7 +92,13 @@ pub fn simple_file_access(
         | FilePurpose::GenericPrivateDocument
         | FilePurpose::CertificateTemplateBackground
         | FilePurpose::CertificateTemplateImage
-        | FilePurpose::CertificateTemplateFont => None,
+        | FilePurpose::CertificateTemplateFont
+        | FilePurpose::BehaviorEvidence
+        | FilePurpose::StudentLeaveDocument
+        | FilePurpose::StaffLeaveDocument
+        | FilePurpose::AnnouncementFile
+        | FilePurpose::FeePaymentSlip
+        | FilePurpose::ProfessionalDevelopmentEvidence => None,
     }
 }
 
@@ -163,6 +171,36 @@ pub async fn authorize_create(
             .await?;
             Ok(actor.user_id)
         }
+        FilePurpose::BehaviorEvidence => {
+            require_no_resource(resource_id)?;
+            behavior_access_policy::require_behavior_create(actor)?;
+            Ok(actor.user_id)
+        }
+        FilePurpose::StudentLeaveDocument => {
+            require_no_resource(resource_id)?;
+            student_leave_access_policy::require_active_guardian(pool, actor).await?;
+            Ok(actor.user_id)
+        }
+        FilePurpose::StaffLeaveDocument => {
+            require_no_resource(resource_id)?;
+            staff_leave_access_policy::require_staff_leave_request(actor)?;
+            Ok(actor.user_id)
+        }
+        FilePurpose::AnnouncementFile => {
+            require_no_resource(resource_id)?;
+            announcement_access_policy::require_announcement_manage(actor)?;
+            Ok(actor.user_id)
+        }
+        FilePurpose::FeePaymentSlip => {
+            require_no_resource(resource_id)?;
+            finance_access_policy::require_payment_slip_upload(pool, actor).await?;
+            Ok(actor.user_id)
+        }
+        FilePurpose::ProfessionalDevelopmentEvidence => {
+            require_no_resource(resource_id)?;
+            professional_development_access_policy::require_professional_development_submit(actor)?;
+            Ok(actor.user_id)
+        }
         FilePurpose::Transcript
         | FilePurpose::Certificate
         | FilePurpose::IdentityCard
@@ -303,6 +341,25 @@ pub async fn authorize_existing(
         | FilePurpose::CertificateTemplateFont => {
             authorize_certificate_template_file(pool, actor, file, action, resource_id).await
         }
+        FilePurpose::BehaviorEvidence => {
+            authorize_behavior_evidence_file(pool, actor, file, action, resource_id).await
+        }
+        FilePurpose::StudentLeaveDocument => {
+            authorize_student_leave_document_file(pool, actor, file, action, resource_id).await
+        }
+        FilePurpose::StaffLeaveDocument => {
+            authorize_staff_leave_document_file(pool, actor, file, action, resource_id).await
+        }
+        FilePurpose::AnnouncementFile => {
+            authorize_announcement_file(pool, actor, file, action, resource_id).await
+        }
+        FilePurpose::FeePaymentSlip => {
+            authorize_fee_payment_slip_file(pool, actor, file, action, resource_id).await
+        }
+        FilePurpose::ProfessionalDevelopmentEvidence => {
+            authorize_professional_development_evidence_file(pool, actor, file, action, resource_id)
+                .await
+        }
         FilePurpose::Transcript
         | FilePurpose::Certificate
         | FilePurpose::IdentityCard
@@ -518,6 +575,295 @@ async fn authorize_certificate_template_file(
     }
 }
 
+/// Evidence becomes part of an incident once attached; reads then follow the
+/// incident's confidentiality, and removal is no longer a plain file delete.
+async fn authorize_behavior_evidence_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let attachment = sqlx::query_as::<_, (Uuid, Uuid, bool, Option<Uuid>)>(
+        "SELECT incident.id, incident.student_id, incident.is_confidential, incident.reported_by
+         FROM behavior_incident_evidence AS evidence
+         JOIN behavior_incidents AS incident ON incident.id = evidence.incident_id
+         WHERE evidence.file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((incident_id, student_id, is_confidential, reported_by)) = attachment else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                behavior_access_policy::require_behavior_create(actor)
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != incident_id) {
+        return Err(unrelated_resource());
+    }
+    match action {
+        FilePolicyAction::Read => {
+            behavior_access_policy::require_incident_read(
+                pool,
+                actor,
+                student_id,
+                is_confidential,
+                reported_by,
+            )
+            .await
+        }
+        FilePolicyAction::Delete => Err(AppError::Conflict(
+            "ไฟล์นี้เป็นหลักฐานของเหตุการณ์พฤติกรรมแล้ว".to_string(),
+        )),
+        FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+    }
+}
+
+/// Leave documents are uploaded by a guardian before the request exists; once
+/// attached, linked guardians and leave reviewers of the student can read them.
+async fn authorize_student_leave_document_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let attachment = sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT request.id, request.student_id
+         FROM student_leave_request_documents AS document
+         JOIN student_leave_requests AS request ON request.id = document.leave_request_id
+         WHERE document.file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((leave_request_id, student_id)) = attachment else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                student_leave_access_policy::require_active_guardian(pool, actor).await
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != leave_request_id) {
+        return Err(unrelated_resource());
+    }
+    match action {
+        FilePolicyAction::Read => {
+            student_leave_access_policy::require_leave_request_read(pool, actor, student_id).await
+        }
+        FilePolicyAction::Delete => Err(AppError::Conflict("ไฟล์นี้แนบกับใบลาแล้ว".to_string())),
+        FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+    }
+}
+
+/// Staff attach their own leave documents; once attached, the request's
+/// readers (its requester, unit leaders on the route and school approvers) can
+/// read them.
+async fn authorize_staff_leave_document_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let attachment = sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT request.id, request.user_id
+         FROM staff_leave_request_documents AS document
+         JOIN staff_leave_requests AS request ON request.id = document.leave_request_id
+         WHERE document.file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((leave_request_id, requester_user_id)) = attachment else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                staff_leave_access_policy::require_staff_leave_request(actor)
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != leave_request_id) {
+        return Err(unrelated_resource());
+    }
+    match action {
+        FilePolicyAction::Read => {
+            staff_leave_access_policy::require_staff_leave_request_read(
+                pool,
+                actor,
+                leave_request_id,
+                requester_user_id,
+            )
+            .await
+        }
+        FilePolicyAction::Delete => Err(AppError::Conflict("ไฟล์นี้แนบกับใบลาแล้ว".to_string())),
+        FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+    }
+}
+
+/// Announcement managers upload images and attachments; once attached, anyone
+/// who may read the announcement can read its files.
+async fn authorize_announcement_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let announcement_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT announcement_id FROM announcement_files WHERE file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(announcement_id) = announcement_id else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                announcement_access_policy::require_announcement_manage(actor)
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != announcement_id) {
+        return Err(unrelated_resource());
+    }
+    match action {
+        FilePolicyAction::Read => {
+            announcement_access_policy::require_announcement_read(pool, actor, announcement_id)
+                .await
+        }
+        FilePolicyAction::Delete => Err(AppError::Conflict(
+            "ไฟล์นี้แนบกับประกาศแล้ว ให้นำออกจากประกาศแทน".to_string(),
+        )),
+        FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+    }
+}
+
+/// Slips are uploaded by a cashier or guardian before the payment is recorded;
+/// once attached, finance staff and the student's linked guardians can read
+/// them.
+async fn authorize_fee_payment_slip_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let attachment = sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT payment.id, invoice.student_id
+         FROM fee_payment_slips AS slip
+         JOIN fee_payments AS payment ON payment.id = slip.payment_id
+         JOIN fee_invoices AS invoice ON invoice.id = payment.invoice_id
+         WHERE slip.file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((payment_id, student_id)) = attachment else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                finance_access_policy::require_payment_slip_upload(pool, actor).await
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != payment_id) {
+        return Err(unrelated_resource());
+    }
+    match action {
+        FilePolicyAction::Read => {
+            finance_access_policy::require_student_finance_read(pool, actor, student_id).await
+        }
+        FilePolicyAction::Delete => {
+            Err(AppError::Conflict("ไฟล์นี้เป็นหลักฐานการชำระเงินแล้ว".to_string()))
+        }
+        FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+    }
+}
+
+/// Staff upload their own evidence and certificates; once attached, a PLC
+/// file is readable by the session's participants and a certificate by the
+/// training record's owner, plus school-wide portfolio readers.
+async fn authorize_professional_development_evidence_file(
+    pool: &PgPool,
+    actor: &ActorContext,
+    file: &PlatformFile,
+    action: FilePolicyAction,
+    resource_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let attachment = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+        "SELECT evidence.session_id, NULL::uuid
+         FROM pd_plc_evidence AS evidence
+         WHERE evidence.file_id = $1
+         UNION ALL
+         SELECT certificate.training_record_id, record.user_id
+         FROM pd_training_certificates AS certificate
+         JOIN pd_training_records AS record ON record.id = certificate.training_record_id
+         WHERE certificate.file_id = $1",
+    )
+    .bind(file.id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((owner_record_id, training_user_id)) = attachment else {
+        if resource_id.is_some() || file.owner_user_id != Some(actor.user_id) {
+            return Err(unrelated_resource());
+        }
+        return match action {
+            FilePolicyAction::Read | FilePolicyAction::Delete => {
+                professional_development_access_policy::require_professional_development_submit(
+                    actor,
+                )
+            }
+            FilePolicyAction::Create => Err(explicit_domain_policy_required()),
+        };
+    };
+    if resource_id.is_some_and(|resource_id| resource_id != owner_record_id) {
+        return Err(unrelated_resource());
+    }
+    match (action, training_user_id) {
+        (FilePolicyAction::Read, Some(user_id)) => {
+            professional_development_access_policy::require_portfolio_read(actor, user_id)
+        }
+        (FilePolicyAction::Read, None) => {
+            professional_development_access_policy::require_plc_session_read(
+                pool,
+                actor,
+                owner_record_id,
+            )
+            .await
+        }
+        (FilePolicyAction::Delete, _) => Err(AppError::Conflict(
+            "ไฟล์นี้แนบเป็นหลักฐานการพัฒนาวิชาชีพแล้ว".to_string(),
+        )),
+        (FilePolicyAction::Create, _) => Err(explicit_domain_policy_required()),
+    }
+}
+
 pub async fn authorize_portal_application(
     pool: &PgPool,
     authenticated_application_id: Uuid,
@@ -834,6 +1180,12 @@ mod tests {
             FilePurpose::CertificateTemplateBackground,
             FilePurpose::CertificateTemplateImage,
             FilePurpose::CertificateTemplateFont,
+            FilePurpose::BehaviorEvidence,
+            FilePurpose::StudentLeaveDocument,
+            FilePurpose::StaffLeaveDocument,
+            FilePurpose::AnnouncementFile,
+            FilePurpose::FeePaymentSlip,
+            FilePurpose::ProfessionalDevelopmentEvidence,
         ] {
             assert_eq!(
                 simple_file_access(
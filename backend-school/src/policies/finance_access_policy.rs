@@ -0,0 +1,110 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::parents::services as parent_service;
+use crate::permissions::registry::codes;
+
+const FINANCE_SCHOOL_READ: [&str; 3] = [
+    codes::FINANCE_READ_SCHOOL,
+    codes::FINANCE_MANAGE_SCHOOL,
+    codes::FINANCE_ISSUE_SCHOOL,
+];
+
+/// Managers and cashiers work from the same invoice and payment lists, so both
+/// grants imply read access.
+pub fn can_read_finance(actor: &ActorContext) -> bool {
+    actor.has_any_permission(&FINANCE_SCHOOL_READ)
+}
+
+pub fn require_finance_read(actor: &ActorContext) -> Result<(), AppError> {
+    if can_read_finance(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ดูข้อมูลการเงิน".to_string()))
+    }
+}
+
+pub fn require_finance_manage(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::FINANCE_MANAGE_SCHOOL) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์จัดการค่าธรรมเนียมและใบแจ้งหนี้".to_string(),
+        ))
+    }
+}
+
+pub fn require_finance_issue(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::FINANCE_ISSUE_SCHOOL) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์รับชำระเงินและออกใบเสร็จ".to_string(),
+        ))
+    }
+}
+
+/// Bank slips come from cashiers recording a transfer or from guardians paying
+/// online.
+pub async fn require_payment_slip_upload(
+    pool: &PgPool,
+    actor: &ActorContext,
+) -> Result<(), AppError> {
+    if actor.has_permission(codes::FINANCE_ISSUE_SCHOOL) {
+        return Ok(());
+    }
+    parent_service::ensure_parent_user(pool, actor.user_id).await
+}
+
+/// Finance staff read every student's invoices; guardians read only those of
+/// their linked children.
+pub async fn require_student_finance_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    if can_read_finance(actor) {
+        return Ok(());
+    }
+    parent_service::ensure_parent_user(pool, actor.user_id).await?;
+    parent_service::ensure_parent_student_link(pool, actor.user_id, student_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id: Uuid::new_v4(),
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn managers_and_cashiers_read_finance() {
+        assert!(can_read_finance(&actor(&[codes::FINANCE_READ_SCHOOL])));
+        assert!(can_read_finance(&actor(&[codes::FINANCE_MANAGE_SCHOOL])));
+        assert!(can_read_finance(&actor(&[codes::FINANCE_ISSUE_SCHOOL])));
+        assert!(!can_read_finance(&actor(&[])));
+    }
+
+    #[test]
+    fn issuing_receipts_and_managing_fees_are_separate_grants() {
+        let cashier = actor(&[codes::FINANCE_ISSUE_SCHOOL]);
+        assert!(require_finance_issue(&cashier).is_ok());
+        assert!(matches!(
+            require_finance_manage(&cashier),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            require_finance_issue(&actor(&[codes::FINANCE_MANAGE_SCHOOL])),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::permissions::registry::codes;
+
+const PROFESSIONAL_DEVELOPMENT_SCHOOL_READ: [&str; 2] = [
+    codes::PROFESSIONAL_DEVELOPMENT_READ_SCHOOL,
+    codes::PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL,
+];
+
+const PROFESSIONAL_DEVELOPMENT_OWN_ACCESS: [&str; 2] = [
+    codes::PROFESSIONAL_DEVELOPMENT_READ_OWN,
+    codes::PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN,
+];
+
+pub fn require_professional_development_submit(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN) || can_manage(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์บันทึกการพัฒนาวิชาชีพ".to_string()))
+    }
+}
+
+pub fn require_professional_development_own_read(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_any_permission(&PROFESSIONAL_DEVELOPMENT_OWN_ACCESS) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ดูข้อมูลการพัฒนาวิชาชีพ".to_string()))
+    }
+}
+
+/// Portfolio administrators always read every staff member's records.
+pub fn can_read_school_professional_development(actor: &ActorContext) -> bool {
+    actor.has_any_permission(&PROFESSIONAL_DEVELOPMENT_SCHOOL_READ)
+}
+
+pub fn require_professional_development_school_read(actor: &ActorContext) -> Result<(), AppError> {
+    if can_read_school_professional_development(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์ดูการพัฒนาวิชาชีพของบุคลากรทั้งโรงเรียน".to_string(),
+        ))
+    }
+}
+
+pub fn can_manage(actor: &ActorContext) -> bool {
+    actor.has_permission(codes::PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL)
+}
+
+pub fn require_professional_development_manage(actor: &ActorContext) -> Result<(), AppError> {
+    if can_manage(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์จัดการเกณฑ์และบันทึกการพัฒนาวิชาชีพ".to_string(),
+        ))
+    }
+}
+
+/// Staff read their own portfolio; school-wide readers read anyone's.
+pub fn require_portfolio_read(actor: &ActorContext, user_id: Uuid) -> Result<(), AppError> {
+    if user_id == actor.user_id && actor.has_any_permission(&PROFESSIONAL_DEVELOPMENT_OWN_ACCESS) {
+        return Ok(());
+    }
+    if can_read_school_professional_development(actor) {
+        return Ok(());
+    }
+    Err(AppError::Forbidden(
+        "ไม่มีสิทธิ์ดูแฟ้มพัฒนาวิชาชีพของบุคลากรคนนี้".to_string(),
+    ))
+}
+
+/// Every participant of a PLC session reads it together with its evidence.
+pub async fn require_plc_session_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    session_id: Uuid,
+) -> Result<(), AppError> {
+    if can_read_school_professional_development(actor) {
+        return Ok(());
+    }
+    require_professional_development_own_read(actor)?;
+
+    let participates = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM pd_plc_participants
+            WHERE session_id = $1
+              AND user_id = $2
+        )
+        "#,
+    )
+    .bind(session_id)
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check PLC session participation: {}", error);
+        AppError::InternalServerError("ตรวจสอบสิทธิ์ผิดพลาด".to_string())
+    })?;
+
+    if participates {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ดูบันทึก PLC นี้".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id: Uuid::new_v4(),
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn managers_submit_and_read_school_records() {
+        let manager = actor(&[codes::PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL]);
+        assert!(require_professional_development_submit(&manager).is_ok());
+        assert!(can_read_school_professional_development(&manager));
+        assert!(!can_read_school_professional_development(&actor(&[
+            codes::PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN,
+            codes::PROFESSIONAL_DEVELOPMENT_READ_OWN,
+        ])));
+    }
+
+    #[test]
+    fn portfolio_read_is_own_unless_school_reader() {
+        let teacher = actor(&[codes::PROFESSIONAL_DEVELOPMENT_READ_OWN]);
+        assert!(require_portfolio_read(&teacher, teacher.user_id).is_ok());
+        assert!(require_portfolio_read(&teacher, Uuid::new_v4()).is_err());
+
+        let reader = actor(&[codes::PROFESSIONAL_DEVELOPMENT_READ_SCHOOL]);
+        assert!(require_portfolio_read(&reader, Uuid::new_v4()).is_ok());
+        assert!(require_professional_development_submit(&reader).is_err());
+    }
+}
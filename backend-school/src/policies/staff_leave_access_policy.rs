@@ -0,0 +1,177 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::modules::staff::services::organization_delegation_service;
+use crate::permissions::registry::codes;
+
+const STAFF_LEAVE_SCHOOL_READ: [&str; 3] = [
+    codes::STAFF_LEAVE_READ_SCHOOL,
+    codes::STAFF_LEAVE_APPROVE_SCHOOL,
+    codes::STAFF_LEAVE_MANAGE_SCHOOL,
+];
+
+const STAFF_LEAVE_OWN_ACCESS: [&str; 2] =
+    [codes::STAFF_LEAVE_READ_OWN, codes::STAFF_LEAVE_REQUEST_OWN];
+
+pub fn require_staff_leave_request(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::STAFF_LEAVE_REQUEST_OWN) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ยื่นใบลา".to_string()))
+    }
+}
+
+pub fn require_staff_leave_own_read(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_any_permission(&STAFF_LEAVE_OWN_ACCESS) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("ไม่มีสิทธิ์ดูข้อมูลการลา".to_string()))
+    }
+}
+
+/// School-wide approvers and leave administrators always see every request.
+pub fn can_read_school_staff_leave(actor: &ActorContext) -> bool {
+    actor.has_any_permission(&STAFF_LEAVE_SCHOOL_READ)
+}
+
+pub fn require_staff_leave_school_read(actor: &ActorContext) -> Result<(), AppError> {
+    if can_read_school_staff_leave(actor) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์ดูการลาของบุคลากรทั้งโรงเรียน".to_string(),
+        ))
+    }
+}
+
+pub fn can_approve_school_step(actor: &ActorContext) -> bool {
+    actor.has_permission(codes::STAFF_LEAVE_APPROVE_SCHOOL)
+}
+
+pub fn require_staff_leave_manage(actor: &ActorContext) -> Result<(), AppError> {
+    if actor.has_permission(codes::STAFF_LEAVE_MANAGE_SCHOOL) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "ไม่มีสิทธิ์จัดการประเภทการลาและวันลา".to_string(),
+        ))
+    }
+}
+
+/// Unit leader steps belong to the leaders of that organization unit; school
+/// approvers may also decide them so a vacant leadership never blocks a request.
+pub async fn can_decide_unit_step(
+    pool: &PgPool,
+    actor: &ActorContext,
+    organization_unit_id: Option<Uuid>,
+) -> Result<bool, AppError> {
+    if can_approve_school_step(actor) {
+        return Ok(true);
+    }
+    match organization_unit_id {
+        Some(organization_unit_id) => {
+            organization_delegation_service::is_organization_unit_leader(
+                pool,
+                actor.user_id,
+                organization_unit_id,
+            )
+            .await
+        }
+        None => Ok(false),
+    }
+}
+
+/// Requesters read their own leave, school-wide readers read everything and a
+/// unit leader reads any request routed through a unit they lead.
+pub async fn require_staff_leave_request_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    leave_request_id: Uuid,
+    requester_user_id: Uuid,
+) -> Result<(), AppError> {
+    if requester_user_id == actor.user_id && actor.has_any_permission(&STAFF_LEAVE_OWN_ACCESS) {
+        return Ok(());
+    }
+    if can_read_school_staff_leave(actor) {
+        return Ok(());
+    }
+
+    let unit_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT DISTINCT organization_unit_id
+        FROM staff_leave_approval_steps
+        WHERE leave_request_id = $1
+          AND step_kind = 'unit_leader'
+          AND organization_unit_id IS NOT NULL
+        "#,
+    )
+    .bind(leave_request_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to load staff leave approval units: {}", error);
+        AppError::InternalServerError("ตรวจสอบสิทธิ์ผิดพลาด".to_string())
+    })?;
+
+    for organization_unit_id in unit_ids {
+        if organization_delegation_service::is_organization_unit_leader(
+            pool,
+            actor.user_id,
+            organization_unit_id,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+    }
+
+    Err(AppError::Forbidden("ไม่มีสิทธิ์ดูใบลานี้".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id: Uuid::new_v4(),
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn approvers_and_managers_read_school_leave() {
+        assert!(can_read_school_staff_leave(&actor(&[
+            codes::STAFF_LEAVE_APPROVE_SCHOOL
+        ])));
+        assert!(can_read_school_staff_leave(&actor(&[
+            codes::STAFF_LEAVE_MANAGE_SCHOOL
+        ])));
+        assert!(!can_read_school_staff_leave(&actor(&[
+            codes::STAFF_LEAVE_READ_OWN,
+            codes::STAFF_LEAVE_REQUEST_OWN,
+        ])));
+    }
+
+    #[test]
+    fn own_read_accepts_either_own_grant() {
+        assert!(require_staff_leave_own_read(&actor(&[codes::STAFF_LEAVE_REQUEST_OWN])).is_ok());
+        assert!(require_staff_leave_own_read(&actor(&[codes::STAFF_LEAVE_READ_OWN])).is_ok());
+        assert!(require_staff_leave_own_read(&actor(&[codes::STAFF_LEAVE_READ_SCHOOL])).is_err());
+    }
+
+    #[test]
+    fn only_school_approvers_decide_school_steps() {
+        assert!(can_approve_school_step(&actor(&[
+            codes::STAFF_LEAVE_APPROVE_SCHOOL
+        ])));
+        assert!(!can_approve_school_step(&actor(&[
+            codes::STAFF_LEAVE_MANAGE_SCHOOL
+        ])));
+    }
+}
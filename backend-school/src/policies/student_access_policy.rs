@@ -60,7 +60,7 @@ pub async fn can_read_student_pii(
     )
 }
 
-async fn student_resource_target(
+pub(crate) async fn student_resource_target(
     pool: &PgPool,
     target_user_id: Uuid,
 ) -> Result<ResourceAccessTarget, AppError> {
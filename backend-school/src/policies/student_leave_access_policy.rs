@@ -0,0 +1,205 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::middleware::permission::ActorContext;
+use crate::permissions::registry::codes;
+use crate::policies::resource_access_policy::{
+    self, ResourceAccessPermissions, UserResourceListAccess,
+};
+use crate::policies::student_access_policy;
+
+const STUDENT_LEAVE_READ_ACCESS: ResourceAccessPermissions = ResourceAccessPermissions {
+    own: None,
+    assigned: Some(codes::STUDENT_LEAVE_READ_ASSIGNED),
+    organization_unit: None,
+    organization_tree: None,
+    school: Some(codes::STUDENT_LEAVE_READ_SCHOOL),
+};
+
+const STUDENT_LEAVE_APPROVE_ACCESS: ResourceAccessPermissions = ResourceAccessPermissions {
+    own: None,
+    assigned: Some(codes::STUDENT_LEAVE_APPROVE_ASSIGNED),
+    organization_unit: None,
+    organization_tree: None,
+    school: Some(codes::STUDENT_LEAVE_APPROVE_SCHOOL),
+};
+
+/// Reviewers can always see what they are allowed to decide, so approve grants
+/// widen the list scope alongside the read grants.
+pub fn resolve_student_leave_list_access(
+    actor: &ActorContext,
+) -> Result<UserResourceListAccess, AppError> {
+    resource_access_policy::resolve_user_resource_list_access(actor, STUDENT_LEAVE_APPROVE_ACCESS)
+        .filter(|access| *access == UserResourceListAccess::School)
+        .or_else(|| {
+            resource_access_policy::resolve_user_resource_list_access(
+                actor,
+                STUDENT_LEAVE_READ_ACCESS,
+            )
+        })
+        .or_else(|| {
+            resource_access_policy::resolve_user_resource_list_access(
+                actor,
+                STUDENT_LEAVE_APPROVE_ACCESS,
+            )
+        })
+        .ok_or_else(|| AppError::Forbidden("ไม่มีสิทธิ์ดูใบลานักเรียน".to_string()))
+}
+
+pub async fn require_student_leave_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    let target = student_access_policy::student_resource_target(pool, student_id).await?;
+    if resource_access_policy::require_resource_access(
+        pool,
+        actor,
+        STUDENT_LEAVE_APPROVE_ACCESS,
+        &target,
+        "ไม่มีสิทธิ์ดูใบลาของนักเรียนนี้",
+    )
+    .await
+    .is_ok()
+    {
+        return Ok(());
+    }
+    resource_access_policy::require_resource_access(
+        pool,
+        actor,
+        STUDENT_LEAVE_READ_ACCESS,
+        &target,
+        "ไม่มีสิทธิ์ดูใบลาของนักเรียนนี้",
+    )
+    .await
+    .map(|_| ())
+}
+
+pub async fn require_student_leave_review(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    let target = student_access_policy::student_resource_target(pool, student_id).await?;
+    resource_access_policy::require_resource_access(
+        pool,
+        actor,
+        STUDENT_LEAVE_APPROVE_ACCESS,
+        &target,
+        "ไม่มีสิทธิ์พิจารณาใบลาของนักเรียนนี้",
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Guardians do not hold role permissions for leave; their access comes from an
+/// active `student_parents` link to the student the request is about.
+pub async fn is_linked_guardian(
+    pool: &PgPool,
+    parent_user_id: Uuid,
+    student_id: Uuid,
+) -> Result<bool, AppError> {
+    sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM student_parents
+            JOIN users parent_user ON parent_user.id = student_parents.parent_user_id
+            WHERE student_parents.parent_user_id = $1
+              AND student_parents.student_user_id = $2
+              AND parent_user.user_type = 'parent'
+              AND parent_user.status = 'active'
+        )
+        "#,
+    )
+    .bind(parent_user_id)
+    .bind(student_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check student leave guardian link: {}", error);
+        AppError::InternalServerError("ตรวจสอบสิทธิ์ผิดพลาด".to_string())
+    })
+}
+
+pub async fn require_active_guardian(pool: &PgPool, actor: &ActorContext) -> Result<(), AppError> {
+    let is_guardian = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND user_type = 'parent' AND status = 'active')",
+    )
+    .bind(actor.user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("Failed to check student leave guardian: {}", error);
+        AppError::InternalServerError("ตรวจสอบสิทธิ์ผิดพลาด".to_string())
+    })?;
+
+    if is_guardian {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "เฉพาะผู้ปกครองเท่านั้นที่ยื่นใบลาได้".to_string(),
+        ))
+    }
+}
+
+pub async fn require_leave_request_read(
+    pool: &PgPool,
+    actor: &ActorContext,
+    student_id: Uuid,
+) -> Result<(), AppError> {
+    if is_linked_guardian(pool, actor.user_id, student_id).await? {
+        return Ok(());
+    }
+    require_student_leave_read(pool, actor, student_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(user_id: Uuid, permissions: &[&str]) -> ActorContext {
+        ActorContext {
+            user_id,
+            permissions: permissions
+                .iter()
+                .map(|permission| permission.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn advisors_list_assigned_students() {
+        let user_id = Uuid::new_v4();
+        let actor = actor(user_id, &[codes::STUDENT_LEAVE_APPROVE_ASSIGNED]);
+
+        assert_eq!(
+            resolve_student_leave_list_access(&actor).expect("assigned access should resolve"),
+            UserResourceListAccess::Assigned(user_id)
+        );
+    }
+
+    #[test]
+    fn school_approvers_list_school_even_with_assigned_read() {
+        let actor = actor(
+            Uuid::new_v4(),
+            &[
+                codes::STUDENT_LEAVE_READ_ASSIGNED,
+                codes::STUDENT_LEAVE_APPROVE_SCHOOL,
+            ],
+        );
+
+        assert_eq!(
+            resolve_student_leave_list_access(&actor).expect("school access should resolve"),
+            UserResourceListAccess::School
+        );
+    }
+
+    #[test]
+    fn actors_without_leave_permissions_cannot_list() {
+        let actor = actor(Uuid::new_v4(), &[codes::BEHAVIOR_READ_SCHOOL]);
+
+        assert!(resolve_student_leave_list_access(&actor).is_err());
+    }
+}
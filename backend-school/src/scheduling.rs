@@ -8,6 +8,8 @@ pub const SCHOOL_TIMEZONE: Tz = chrono_tz::Asia::Bangkok;
 pub const SCHOOL_TIMEZONE_NAME: &str = "Asia/Bangkok";
 pub const FILE_PLATFORM_RECONCILIATION_CRON: &str = "0 0 * * * *";
 pub const CALENDAR_REMINDER_CRON: &str = "0 0 7 * * *";
+pub const ANNOUNCEMENT_DISPATCH_CRON: &str = "0 */10 * * * *";
+pub const ADMISSION_OFFER_EXPIRY_CRON: &str = "0 5,20,35,50 * * * *";
 
 #[derive(Clone, Copy, Debug)]
 pub struct ScheduledJobNextRun {
@@ -53,8 +55,8 @@ pub fn log_next_run(job_name: &str, schedule: &str, next_run: ScheduledJobNextRu
 #[cfg(test)]
 mod tests {
     use super::{
-        new_school_cron_job, next_run_for_job, ScheduledJobNextRun, CALENDAR_REMINDER_CRON,
-        FILE_PLATFORM_RECONCILIATION_CRON,
+        new_school_cron_job, next_run_for_job, ScheduledJobNextRun, ADMISSION_OFFER_EXPIRY_CRON,
+        ANNOUNCEMENT_DISPATCH_CRON, CALENDAR_REMINDER_CRON, FILE_PLATFORM_RECONCILIATION_CRON,
     };
     use chrono::Timelike;
     use tokio_cron_scheduler::JobScheduler;
@@ -81,4 +83,20 @@ mod tests {
 
         assert_eq!((next.bangkok.minute(), next.bangkok.second()), (0, 0));
     }
+
+    #[tokio::test]
+    async fn announcement_dispatch_runs_every_ten_minutes() {
+        let next = next_run(ANNOUNCEMENT_DISPATCH_CRON).await;
+
+        assert_eq!(next.bangkok.minute() % 10, 0);
+        assert_eq!(next.bangkok.second(), 0);
+    }
+
+    #[tokio::test]
+    async fn admission_offer_expiry_runs_every_quarter_hour_off_the_dispatch_minute() {
+        let next = next_run(ADMISSION_OFFER_EXPIRY_CRON).await;
+
+        assert_eq!(next.bangkok.minute() % 15, 5);
+        assert_eq!(next.bangkok.second(), 0);
+    }
 }
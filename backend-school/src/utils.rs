@@ -4,7 +4,9 @@ pub mod field_encryption; // Generic encryption for any field
 pub mod file_hash;
 pub mod file_processor;
 pub mod logging;
+pub mod pdf;
 pub mod permission_sync;
+pub mod quota;
 pub mod request_context;
 pub mod subdomain;
 pub mod tenant;
@@ -21,26 +21,184 @@ pub fn test_env_lock() -> std::sync::MutexGuard<'static, ()> {
         .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
-/// Get cipher from environment key
-fn get_cipher() -> Result<Aes256Gcm, String> {
-    let key_str = env::var("ENCRYPTION_KEY").map_err(|_| "ENCRYPTION_KEY not set".to_string())?;
+/// Key id under which the single-key `ENCRYPTION_KEY` and `BLIND_INDEX_KEY` are read.
+/// Ciphertexts written under it carry no key-id prefix, which keeps every value written
+/// before keyrings existed readable.
+pub const LEGACY_KEY_ID: &str = "legacy";
+
+/// Prefix of ciphertexts that name their key: `v2:{key_id}:{base64}`. Base64 never
+/// contains `:`, so legacy values cannot be mistaken for versioned ones.
+pub const VERSIONED_PREFIX: &str = "v2";
+const MAX_KEY_ID_LENGTH: usize = 32;
+
+/// Environment variables that describe one keyring.
+struct KeyringEnv {
+    legacy: &'static str,
+    keyring: &'static str,
+    active: &'static str,
+}
+
+const ENCRYPTION_ENV: KeyringEnv = KeyringEnv {
+    legacy: "ENCRYPTION_KEY",
+    keyring: "ENCRYPTION_KEYRING",
+    active: "ENCRYPTION_ACTIVE_KEY_ID",
+};
+
+const BLIND_INDEX_ENV: KeyringEnv = KeyringEnv {
+    legacy: "BLIND_INDEX_KEY",
+    keyring: "BLIND_INDEX_KEYRING",
+    active: "BLIND_INDEX_ACTIVE_KEY_ID",
+};
+
+/// One active key that new values are written with, plus decrypt-only keys that
+/// existing values may still use until they are re-encrypted.
+struct Keyring {
+    active_id: String,
+    keys: Vec<(String, String)>,
+}
+
+impl Keyring {
+    fn secret(&self, key_id: &str) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|(id, _)| id == key_id)
+            .map(|(_, secret)| secret.as_str())
+    }
+
+    fn active_secret(&self) -> &str {
+        self.secret(&self.active_id)
+            .expect("active key is validated when the keyring is loaded")
+    }
+
+    /// Active key first, so lookups hit the common case before older keys.
+    fn secrets_active_first(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.active_secret()).chain(
+            self.keys
+                .iter()
+                .filter(|(id, _)| *id != self.active_id)
+                .map(|(_, secret)| secret.as_str()),
+        )
+    }
+}
+
+fn valid_key_id(key_id: &str) -> bool {
+    !key_id.is_empty()
+        && key_id.len() <= MAX_KEY_ID_LENGTH
+        && key_id
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+}
+
+/// Parse a keyring such as `2026a:secret-one,2025b:secret-two`. Secrets may contain `:`
+/// but not `,`. Error messages name the key id only, never a secret.
+fn parse_keyring(spec: &str, variable: &str) -> Result<Vec<(String, String)>, String> {
+    let mut keys: Vec<(String, String)> = Vec::new();
+    for entry in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (key_id, secret) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("{variable} entries must be key_id:secret"))?;
+        let key_id = key_id.trim();
+        if !valid_key_id(key_id) || key_id == LEGACY_KEY_ID {
+            return Err(format!("{variable} has an invalid key id"));
+        }
+        if secret.trim().is_empty() {
+            return Err(format!("{variable} key {key_id} has an empty secret"));
+        }
+        if keys.iter().any(|(id, _)| id == key_id) {
+            return Err(format!("{variable} repeats key {key_id}"));
+        }
+        keys.push((key_id.to_string(), secret.to_string()));
+    }
+    Ok(keys)
+}
+
+fn non_empty_env(variable: &str) -> Option<String> {
+    env::var(variable)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Keyrings are read from the environment on every call, like the single keys were,
+/// so a restart with a new active key id takes effect without other changes.
+fn load_keyring(spec: &KeyringEnv) -> Result<Keyring, String> {
+    let mut keys = Vec::new();
+    if let Some(secret) = non_empty_env(spec.legacy) {
+        keys.push((LEGACY_KEY_ID.to_string(), secret));
+    }
+    let keyring = non_empty_env(spec.keyring);
+    if let Some(keyring) = &keyring {
+        keys.extend(parse_keyring(keyring, spec.keyring)?);
+    }
+    if keys.is_empty() {
+        return Err(format!("{} not set", spec.legacy));
+    }
 
+    let active_id = match non_empty_env(spec.active) {
+        Some(active_id) => active_id.trim().to_string(),
+        None if keyring.is_some() => return Err(format!("{} not set", spec.active)),
+        None => LEGACY_KEY_ID.to_string(),
+    };
+    if !keys.iter().any(|(id, _)| *id == active_id) {
+        return Err(format!("{} names an unknown key", spec.active));
+    }
+
+    Ok(Keyring { active_id, keys })
+}
+
+/// Derive the AES-256 key from a configured secret
+fn cipher_for(secret: &str) -> Aes256Gcm {
     // Derive 32-byte key using SHA-256
     let mut hasher = Sha256::new();
-    hasher.update(key_str.as_bytes());
+    hasher.update(secret.as_bytes());
     let key_bytes = hasher.finalize();
 
-    Ok(Aes256Gcm::new(&key_bytes))
+    Aes256Gcm::new(&key_bytes)
+}
+
+/// Split a stored value into its key id and base64 payload.
+fn split_ciphertext(value: &str) -> Result<(&str, &str), String> {
+    match value
+        .strip_prefix(VERSIONED_PREFIX)
+        .and_then(|rest| rest.strip_prefix(':'))
+    {
+        Some(rest) => rest
+            .split_once(':')
+            .filter(|(key_id, _)| valid_key_id(key_id))
+            .ok_or_else(|| "Invalid encrypted data: malformed key id".to_string()),
+        None => Ok((LEGACY_KEY_ID, value)),
+    }
+}
+
+/// Id of the key that encrypted a stored value. Unprefixed values belong to
+/// [`LEGACY_KEY_ID`].
+pub fn ciphertext_key_id(value: &str) -> Result<&str, String> {
+    split_ciphertext(value).map(|(key_id, _)| key_id)
+}
+
+/// Id of the key new ciphertexts are written with
+pub fn active_encryption_key_id() -> Result<String, String> {
+    Ok(load_keyring(&ENCRYPTION_ENV)?.active_id)
+}
+
+/// Id of the key new blind indexes are computed with
+pub fn active_blind_index_key_id() -> Result<String, String> {
+    Ok(load_keyring(&BLIND_INDEX_ENV)?.active_id)
 }
 
 /// Encrypt any string data
-/// Returns base64-encoded ciphertext with nonce prepended
+/// Returns base64-encoded ciphertext with nonce prepended, prefixed with the key id
+/// unless the active key is the legacy single key
 pub fn encrypt(plaintext: &str) -> Result<String, String> {
     if plaintext.is_empty() {
         return Ok(String::new());
     }
 
-    let cipher = get_cipher()?;
+    let keyring = load_keyring(&ENCRYPTION_ENV)?;
+    let cipher = cipher_for(keyring.active_secret());
 
     // Generate random 12-byte nonce
     let mut nonce_bytes = [0u8; 12];
@@ -57,18 +215,28 @@ pub fn encrypt(plaintext: &str) -> Result<String, String> {
     result.extend_from_slice(&ciphertext);
 
     // Encode as base64
-    Ok(general_purpose::STANDARD.encode(result))
+    let encoded = general_purpose::STANDARD.encode(result);
+    if keyring.active_id == LEGACY_KEY_ID {
+        Ok(encoded)
+    } else {
+        Ok(format!(
+            "{VERSIONED_PREFIX}:{}:{encoded}",
+            keyring.active_id
+        ))
+    }
 }
 
-/// Decrypt base64-encoded ciphertext
+/// Decrypt base64-encoded ciphertext with whichever keyring key wrote it
 pub fn decrypt(encrypted_base64: &str) -> Result<String, String> {
     if encrypted_base64.is_empty() {
         return Ok(String::new());
     }
 
+    let (key_id, payload) = split_ciphertext(encrypted_base64)?;
+
     // Decode from base64
     let encrypted = general_purpose::STANDARD
-        .decode(encrypted_base64)
+        .decode(payload)
         .map_err(|e| format!("Base64 decode failed: {}", e))?;
 
     // Extract nonce (first 12 bytes) and ciphertext
@@ -79,7 +247,11 @@ pub fn decrypt(encrypted_base64: &str) -> Result<String, String> {
     let (nonce_bytes, ciphertext) = encrypted.split_at(12);
     let nonce = Nonce::from_slice(nonce_bytes);
 
-    let cipher = get_cipher()?;
+    let keyring = load_keyring(&ENCRYPTION_ENV)?;
+    let secret = keyring
+        .secret(key_id)
+        .ok_or_else(|| format!("Unknown encryption key id: {}", key_id))?;
+    let cipher = cipher_for(secret);
 
     // Decrypt
     let plaintext = cipher
@@ -105,25 +277,49 @@ pub fn decrypt_optional(encrypted: Option<&str>) -> Result<Option<String>, Strin
     }
 }
 
-fn get_blind_index_key() -> Result<String, String> {
-    let key = env::var("BLIND_INDEX_KEY").map_err(|_| "BLIND_INDEX_KEY not set".to_string())?;
+fn blind_index_mac(secret: &str) -> Result<HmacSha256, String> {
+    <HmacSha256 as Mac>::new_from_slice(secret.as_bytes())
+        .map_err(|_| "Invalid BLIND_INDEX_KEY".to_string())
+}
 
-    if key.trim().is_empty() {
-        return Err("BLIND_INDEX_KEY not set".to_string());
-    }
+fn plain_hash(secret: &str, text: &str) -> Result<String, String> {
+    let mut mac = blind_index_mac(secret)?;
+    mac.update(text.as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
 
-    Ok(key)
+fn domain_hash(secret: &str, domain: &str, value: &str) -> Result<String, String> {
+    let mut mac = blind_index_mac(secret)?;
+    mac.update(b"schoolorbit-domain-separated-hmac-v1\0");
+    mac.update(&(domain.len() as u32).to_be_bytes());
+    mac.update(domain.as_bytes());
+    mac.update(value.as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+fn validate_domain(domain: &str) -> Result<(), String> {
+    if domain.is_empty() || domain.len() > 128 || !domain.is_ascii() {
+        return Err("Invalid blind-index domain".to_string());
+    }
+    Ok(())
 }
 
 /// Hash text for search/unique lookup without exposing plaintext.
-/// Uses HMAC-SHA256 keyed by BLIND_INDEX_KEY.
+/// Uses HMAC-SHA256 keyed by the active blind-index key.
 pub fn hash_for_search(text: &str) -> Result<String, String> {
-    let key = get_blind_index_key()?;
-    let mut mac = <HmacSha256 as Mac>::new_from_slice(key.as_bytes())
-        .map_err(|_| "Invalid BLIND_INDEX_KEY".to_string())?;
-    mac.update(text.as_bytes());
+    let keyring = load_keyring(&BLIND_INDEX_ENV)?;
+    plain_hash(keyring.active_secret(), text)
+}
 
-    Ok(hex::encode(mac.finalize().into_bytes()))
+/// Every digest a stored blind index for `text` may hold while a rotation is in
+/// progress, active key first. Lookups match any of them; writes use
+/// [`hash_for_search`].
+pub fn hash_for_search_candidates(text: &str) -> Result<Vec<String>, String> {
+    let keyring = load_keyring(&BLIND_INDEX_ENV)?;
+    keyring
+        .secrets_active_first()
+        .map(|secret| plain_hash(secret, text))
+        .collect()
 }
 
 /// Hashes a value for non-national-ID lookup with an explicit domain boundary.
@@ -132,17 +328,22 @@ pub fn hash_for_search(text: &str) -> Result<String, String> {
 /// their plaintext values happen to match. `hash_for_search` deliberately remains unchanged
 /// because it owns the existing national-ID blind-index contract.
 pub fn hash_for_search_with_domain(domain: &str, value: &str) -> Result<String, String> {
-    if domain.is_empty() || domain.len() > 128 || !domain.is_ascii() {
-        return Err("Invalid blind-index domain".to_string());
-    }
-    let key = get_blind_index_key()?;
-    let mut mac = <HmacSha256 as Mac>::new_from_slice(key.as_bytes())
-        .map_err(|_| "Invalid BLIND_INDEX_KEY".to_string())?;
-    mac.update(b"schoolorbit-domain-separated-hmac-v1\0");
-    mac.update(&(domain.len() as u32).to_be_bytes());
-    mac.update(domain.as_bytes());
-    mac.update(value.as_bytes());
-    Ok(hex::encode(mac.finalize().into_bytes()))
+    validate_domain(domain)?;
+    let keyring = load_keyring(&BLIND_INDEX_ENV)?;
+    domain_hash(keyring.active_secret(), domain, value)
+}
+
+/// Domain-separated counterpart of [`hash_for_search_candidates`].
+pub fn hash_for_search_with_domain_candidates(
+    domain: &str,
+    value: &str,
+) -> Result<Vec<String>, String> {
+    validate_domain(domain)?;
+    let keyring = load_keyring(&BLIND_INDEX_ENV)?;
+    keyring
+        .secrets_active_first()
+        .map(|secret| domain_hash(secret, domain, value))
+        .collect()
 }
 
 pub fn hash_optional_for_search(value: Option<&str>) -> Result<Option<String>, String> {
@@ -234,4 +435,132 @@ mod tests {
         );
         assert_eq!(certificate.len(), 64);
     }
+
+    /// Clears keyring variables when a test ends so single-key tests elsewhere
+    /// keep seeing the legacy configuration.
+    struct KeyringVars;
+
+    impl KeyringVars {
+        fn set(vars: &[(&str, &str)]) -> Self {
+            for (name, value) in vars {
+                env::set_var(name, value);
+            }
+            KeyringVars
+        }
+    }
+
+    impl Drop for KeyringVars {
+        fn drop(&mut self) {
+            for name in [
+                "ENCRYPTION_KEYRING",
+                "ENCRYPTION_ACTIVE_KEY_ID",
+                "BLIND_INDEX_KEYRING",
+                "BLIND_INDEX_ACTIVE_KEY_ID",
+            ] {
+                env::remove_var(name);
+            }
+        }
+    }
+
+    #[test]
+    fn single_key_mode_keeps_unprefixed_ciphertexts() {
+        let _guard = test_env_lock();
+        env::set_var("ENCRYPTION_KEY", "legacy-format-test-key");
+
+        let encrypted = encrypt("1234567890123").unwrap();
+
+        assert!(!encrypted.contains(':'));
+        assert_eq!(ciphertext_key_id(&encrypted).unwrap(), LEGACY_KEY_ID);
+        assert_eq!(active_encryption_key_id().unwrap(), LEGACY_KEY_ID);
+    }
+
+    #[test]
+    fn keyring_writes_with_active_key_and_reads_older_keys() {
+        let _guard = test_env_lock();
+        env::set_var("ENCRYPTION_KEY", "rotation-legacy-test-key");
+        let legacy = encrypt("1234567890123").unwrap();
+
+        let _vars = KeyringVars::set(&[
+            (
+                "ENCRYPTION_KEYRING",
+                "k2026:rotation-new-test-key:with-colon",
+            ),
+            ("ENCRYPTION_ACTIVE_KEY_ID", "k2026"),
+        ]);
+        let rotated = encrypt("1234567890123").unwrap();
+
+        assert!(rotated.starts_with("v2:k2026:"));
+        assert_eq!(ciphertext_key_id(&rotated).unwrap(), "k2026");
+        assert_eq!(decrypt(&rotated).unwrap(), "1234567890123");
+        assert_eq!(decrypt(&legacy).unwrap(), "1234567890123");
+    }
+
+    #[test]
+    fn ciphertext_from_a_removed_key_is_rejected_by_id() {
+        let _guard = test_env_lock();
+        env::set_var("ENCRYPTION_KEY", "rotation-legacy-test-key");
+        let rotated = {
+            let _vars = KeyringVars::set(&[
+                ("ENCRYPTION_KEYRING", "retired:retired-test-key"),
+                ("ENCRYPTION_ACTIVE_KEY_ID", "retired"),
+            ]);
+            encrypt("1234567890123").unwrap()
+        };
+
+        let error = decrypt(&rotated).unwrap_err();
+
+        assert_eq!(error, "Unknown encryption key id: retired");
+    }
+
+    #[test]
+    fn keyring_configuration_errors_do_not_echo_secrets() {
+        let _guard = test_env_lock();
+        env::set_var("ENCRYPTION_KEY", "rotation-legacy-test-key");
+
+        for (keyring, active) in [
+            ("missing-separator-secret", "k1"),
+            ("legacy:shadowing-secret", "legacy"),
+            ("k1:first-secret,k1:second-secret", "k1"),
+            ("k1:first-secret", "k9"),
+            ("Bad Id:spaced-secret", "k1"),
+        ] {
+            let _vars = KeyringVars::set(&[
+                ("ENCRYPTION_KEYRING", keyring),
+                ("ENCRYPTION_ACTIVE_KEY_ID", active),
+            ]);
+            let error = encrypt("1234567890123").unwrap_err();
+            assert!(!error.contains("-secret"), "{error}");
+        }
+
+        let _vars = KeyringVars::set(&[("ENCRYPTION_KEYRING", "k1:first-secret")]);
+        assert_eq!(
+            encrypt("1234567890123").unwrap_err(),
+            "ENCRYPTION_ACTIVE_KEY_ID not set"
+        );
+    }
+
+    #[test]
+    fn blind_index_candidates_cover_every_key_active_first() {
+        let _guard = test_env_lock();
+        env::set_var("BLIND_INDEX_KEY", "blind-index-test-key");
+        let legacy = hash_for_search("1234567890123").unwrap();
+        let legacy_domain =
+            hash_for_search_with_domain("certificate-qr-proof-v1", "proof-value").unwrap();
+
+        let _vars = KeyringVars::set(&[
+            ("BLIND_INDEX_KEYRING", "b2026:rotated-blind-index-test-key"),
+            ("BLIND_INDEX_ACTIVE_KEY_ID", "b2026"),
+        ]);
+        let candidates = hash_for_search_candidates("1234567890123").unwrap();
+        let domain_candidates =
+            hash_for_search_with_domain_candidates("certificate-qr-proof-v1", "proof-value")
+                .unwrap();
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0], hash_for_search("1234567890123").unwrap());
+        assert_eq!(candidates[1], legacy);
+        assert_ne!(candidates[0], legacy);
+        assert_eq!(domain_candidates[1], legacy_domain);
+        assert_eq!(active_blind_index_key_id().unwrap(), "b2026");
+    }
 }
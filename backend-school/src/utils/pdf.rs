@@ -0,0 +1,983 @@
+//! Small PDF writer for server-generated printouts (exam-day sheets, labels).
+//!
+//! Text is set in the Sarabun family that certificate layouts offer as their
+//! built-in font. Both weights are embedded as TrueType CID fonts with a
+//! ToUnicode map, so Thai text stays searchable and copyable. There is no
+//! OpenType shaping: Thai marks rely on the font's zero-width mark glyphs, with
+//! the low tone-mark and narrow variants picked by the small rule set in
+//! [`PdfFont::shape`].
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+
+use crate::scheduling::SCHOOL_TIMEZONE;
+
+const SARABUN_REGULAR: &[u8] = include_bytes!("../../assets/fonts/Sarabun-Regular.ttf");
+const SARABUN_BOLD: &[u8] = include_bytes!("../../assets/fonts/Sarabun-Bold.ttf");
+
+/// A4 portrait in points
+pub const A4_PORTRAIT: PdfPageSize = PdfPageSize {
+    width: 595.28,
+    height: 841.89,
+};
+/// A4 landscape in points
+pub const A4_LANDSCAPE: PdfPageSize = PdfPageSize {
+    width: 841.89,
+    height: 595.28,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfPageSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFontWeight {
+    Regular,
+    Bold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfTextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+struct PdfFont {
+    resource_name: &'static str,
+    base_font: &'static str,
+    data: &'static [u8],
+    face: ttf_parser::Face<'static>,
+    /// Glyphs drawn so far with the text they stand for, for widths and ToUnicode
+    used_glyphs: BTreeMap<u16, String>,
+}
+
+struct ShapedGlyph {
+    glyph_id: u16,
+    advance: u16,
+    text: String,
+}
+
+impl PdfFont {
+    fn new(resource_name: &'static str, base_font: &'static str, data: &'static [u8]) -> Self {
+        let face = ttf_parser::Face::parse(data, 0).expect("bundled Sarabun font should parse");
+        Self {
+            resource_name,
+            base_font,
+            data,
+            face,
+            used_glyphs: BTreeMap::new(),
+        }
+    }
+
+    fn units_per_em(&self) -> f32 {
+        f32::from(self.face.units_per_em())
+    }
+
+    fn glyph(&self, name: Option<String>, character: char) -> Option<ttf_parser::GlyphId> {
+        name.and_then(|name| self.face.glyph_index_by_name(&name))
+            .or_else(|| self.face.glyph_index(character))
+    }
+
+    /// Maps text to glyphs. Thai tone marks drop to their low `.small` form when
+    /// neither an upper vowel nor sara am shares the consonant, and upper marks
+    /// take their `.narrow` form after the tall consonants ป ฝ ฟ ฬ.
+    fn shape(&self, text: &str) -> Vec<ShapedGlyph> {
+        let characters: Vec<char> = text.chars().filter(|c| !c.is_control()).collect();
+        let mut shaped = Vec::with_capacity(characters.len());
+        let mut base: Option<char> = None;
+        let mut has_upper_vowel = false;
+        for (index, character) in characters.iter().copied().enumerate() {
+            let mut variant = None;
+            if is_thai_upper_mark(character) {
+                let name = format!("uni{:04X}", u32::from(character));
+                let before_sara_am = characters.get(index + 1) == Some(&'\u{0E33}');
+                if matches!(base, Some('ป' | 'ฝ' | 'ฟ' | 'ฬ')) {
+                    variant = Some(format!("{name}.narrow"));
+                } else if is_thai_tone_mark(character) && !has_upper_vowel && !before_sara_am {
+                    variant = Some(format!("{name}.small"));
+                }
+                if !is_thai_tone_mark(character) {
+                    has_upper_vowel = true;
+                }
+            } else if !is_thai_lower_mark(character) {
+                base = Some(character);
+                has_upper_vowel = false;
+            }
+
+            let glyph = self
+                .glyph(variant, character)
+                .or_else(|| self.face.glyph_index('?'))
+                .unwrap_or(ttf_parser::GlyphId(0));
+            shaped.push(ShapedGlyph {
+                glyph_id: glyph.0,
+                advance: self.face.glyph_hor_advance(glyph).unwrap_or(0),
+                text: character.to_string(),
+            });
+        }
+        shaped
+    }
+
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        let units: u32 = self
+            .shape(text)
+            .iter()
+            .map(|glyph| u32::from(glyph.advance))
+            .sum();
+        units as f32 * size / self.units_per_em()
+    }
+}
+
+fn is_thai_tone_mark(character: char) -> bool {
+    ('\u{0E48}'..='\u{0E4C}').contains(&character)
+}
+
+fn is_thai_upper_mark(character: char) -> bool {
+    matches!(
+        character,
+        '\u{0E31}' | '\u{0E34}'..='\u{0E37}' | '\u{0E47}'..='\u{0E4E}'
+    )
+}
+
+fn is_thai_lower_mark(character: char) -> bool {
+    ('\u{0E38}'..='\u{0E3A}').contains(&character)
+}
+
+/// Vowels written before the consonant they follow in speech
+fn is_thai_leading_vowel(character: char) -> bool {
+    ('\u{0E40}'..='\u{0E44}').contains(&character)
+}
+
+/// Signs that can never start a line: marks plus sara a, aa, am, lakkhangyao
+/// and mai yamok
+fn is_thai_trailing_sign(character: char) -> bool {
+    is_thai_upper_mark(character)
+        || is_thai_lower_mark(character)
+        || matches!(
+            character,
+            '\u{0E30}' | '\u{0E32}' | '\u{0E33}' | '\u{0E45}' | '\u{0E46}'
+        )
+}
+
+fn is_thai(character: char) -> bool {
+    ('\u{0E00}'..='\u{0E7F}').contains(&character)
+}
+
+/// Splits text into the pieces [`PdfDocument::wrap_text`] may break between:
+/// non-Thai words with their trailing spaces, and Thai clusters
+fn line_break_units(text: &str) -> Vec<String> {
+    let mut units: Vec<String> = Vec::new();
+    let mut after_leading_vowel = false;
+    for character in text.chars() {
+        let previous = units.last().and_then(|unit| unit.chars().last());
+        let joins_previous = after_leading_vowel
+            || character.is_whitespace()
+            || is_thai_trailing_sign(character)
+            || previous.is_some_and(|previous| {
+                !previous.is_whitespace() && !is_thai(previous) && !is_thai(character)
+            });
+        match units.last_mut() {
+            Some(unit) if joins_previous => unit.push(character),
+            _ => units.push(character.to_string()),
+        }
+        after_leading_vowel = is_thai_leading_vowel(character);
+    }
+    units
+}
+
+struct PdfPage {
+    size: PdfPageSize,
+    content: String,
+}
+
+/// Builds a document page by page. Coordinates are points from the top-left
+/// corner; text positions name the baseline.
+pub struct PdfDocument {
+    fonts: [PdfFont; 2],
+    pages: Vec<PdfPage>,
+    title: String,
+}
+
+impl PdfDocument {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            fonts: [
+                PdfFont::new("F1", "Sarabun-Regular", SARABUN_REGULAR),
+                PdfFont::new("F2", "Sarabun-Bold", SARABUN_BOLD),
+            ],
+            pages: Vec::new(),
+            title: title.into(),
+        }
+    }
+
+    pub fn add_page(&mut self, size: PdfPageSize) {
+        self.pages.push(PdfPage {
+            size,
+            content: String::new(),
+        });
+    }
+
+    #[cfg(test)]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    fn font(&self, weight: PdfFontWeight) -> &PdfFont {
+        match weight {
+            PdfFontWeight::Regular => &self.fonts[0],
+            PdfFontWeight::Bold => &self.fonts[1],
+        }
+    }
+
+    fn current_page(&mut self) -> &mut PdfPage {
+        if self.pages.is_empty() {
+            self.add_page(A4_PORTRAIT);
+        }
+        self.pages.last_mut().expect("a page was just added")
+    }
+
+    pub fn text_width(&self, text: &str, size: f32, weight: PdfFontWeight) -> f32 {
+        self.font(weight).text_width(text, size)
+    }
+
+    /// Shortens `text` with an ellipsis until it fits `max_width`
+    pub fn fit_text(&self, text: &str, size: f32, weight: PdfFontWeight, max_width: f32) -> String {
+        if self.text_width(text, size, weight) <= max_width {
+            return text.to_string();
+        }
+        let mut characters: Vec<char> = text.chars().collect();
+        while !characters.is_empty() {
+            characters.pop();
+            let candidate = format!("{}…", characters.iter().collect::<String>().trim_end());
+            if self.text_width(&candidate, size, weight) <= max_width {
+                return candidate;
+            }
+        }
+        String::new()
+    }
+
+    /// Breaks `text` into lines no wider than `max_width`. Lines end at
+    /// spaces where there are any; Thai runs may also break between
+    /// clusters, and only a word wider than the whole line is split mid-word.
+    /// Explicit newlines are kept.
+    pub fn wrap_text(
+        &self,
+        text: &str,
+        size: f32,
+        weight: PdfFontWeight,
+        max_width: f32,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut line = String::new();
+            for unit in line_break_units(paragraph) {
+                let candidate = format!("{line}{unit}");
+                if self.text_width(candidate.trim_end(), size, weight) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.trim().is_empty() {
+                    lines.push(line.trim_end().to_string());
+                }
+                line = String::new();
+                for character in unit.trim_start().chars() {
+                    let candidate = format!("{line}{character}");
+                    if !line.is_empty()
+                        && self.text_width(candidate.trim_end(), size, weight) > max_width
+                    {
+                        lines.push(line.trim_end().to_string());
+                        line = character.to_string();
+                    } else {
+                        line = candidate;
+                    }
+                }
+            }
+            lines.push(line.trim_end().to_string());
+        }
+        lines
+    }
+
+    pub fn text(&mut self, x: f32, y: f32, size: f32, weight: PdfFontWeight, text: &str) {
+        self.aligned_text(x, y, size, weight, PdfTextAlign::Left, text);
+    }
+
+    /// Draws one line of text; `x` is the left edge, centre or right edge
+    /// depending on `align`
+    pub fn aligned_text(
+        &mut self,
+        x: f32,
+        y: f32,
+        size: f32,
+        weight: PdfFontWeight,
+        align: PdfTextAlign,
+        text: &str,
+    ) {
+        if text.is_empty() {
+            return;
+        }
+        let font_index = match weight {
+            PdfFontWeight::Regular => 0,
+            PdfFontWeight::Bold => 1,
+        };
+        let font = &mut self.fonts[font_index];
+        let shaped = font.shape(text);
+        let width = shaped
+            .iter()
+            .map(|glyph| u32::from(glyph.advance))
+            .sum::<u32>() as f32
+            * size
+            / font.units_per_em();
+        let left = match align {
+            PdfTextAlign::Left => x,
+            PdfTextAlign::Center => x - width / 2.0,
+            PdfTextAlign::Right => x - width,
+        };
+        let mut hex = String::with_capacity(shaped.len() * 4);
+        for glyph in &shaped {
+            let _ = write!(hex, "{:04X}", glyph.glyph_id);
+            font.used_glyphs
+                .entry(glyph.glyph_id)
+                .or_insert_with(|| glyph.text.clone());
+        }
+        let resource_name = font.resource_name;
+
+        let page = self.current_page();
+        let baseline = page.size.height - y;
+        let _ = writeln!(
+            page.content,
+            "BT /{resource_name} {size:.2} Tf {left:.2} {baseline:.2} Td <{hex}> Tj ET"
+        );
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        let page = self.current_page();
+        let height = page.size.height;
+        let _ = writeln!(
+            page.content,
+            "{width:.2} w {x1:.2} {:.2} m {x2:.2} {:.2} l S",
+            height - y1,
+            height - y2
+        );
+    }
+
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, line_width: f32) {
+        let page = self.current_page();
+        let bottom = page.size.height - y - height;
+        let _ = writeln!(
+            page.content,
+            "{line_width:.2} w {x:.2} {bottom:.2} {width:.2} {height:.2} re S"
+        );
+    }
+
+    /// Fills a rectangle in grey, `gray` running from 0 (black) to 1 (white)
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        let page = self.current_page();
+        let bottom = page.size.height - y - height;
+        let _ = writeln!(
+            page.content,
+            "q {gray:.2} g {x:.2} {bottom:.2} {width:.2} {height:.2} re f Q"
+        );
+    }
+
+    /// Serialises the document; fonts no page used are left out
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.add_page(A4_PORTRAIT);
+        }
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let catalog_id = reserve_object(&mut objects);
+        let pages_id = reserve_object(&mut objects);
+        let info_id = reserve_object(&mut objects);
+
+        let mut font_resources = String::new();
+        for font in &self.fonts {
+            if font.used_glyphs.is_empty() {
+                continue;
+            }
+            let font_id = write_font(&mut objects, font);
+            let _ = write!(font_resources, "/{} {font_id} 0 R ", font.resource_name);
+        }
+
+        let mut page_ids = Vec::with_capacity(self.pages.len());
+        for page in &self.pages {
+            let content_id = reserve_object(&mut objects);
+            objects[content_id - 1] = stream_object("", page.content.as_bytes());
+            let page_id = reserve_object(&mut objects);
+            objects[page_id - 1] = format!(
+                "<< /Type /Page /Parent {pages_id} 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                 /Resources << /Font << {font_resources}>> >> /Contents {content_id} 0 R >>",
+                page.size.width, page.size.height
+            )
+            .into_bytes();
+            page_ids.push(page_id);
+        }
+
+        let kids = page_ids
+            .iter()
+            .map(|id| format!("{id} 0 R"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        objects[pages_id - 1] = format!(
+            "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+            page_ids.len()
+        )
+        .into_bytes();
+        objects[catalog_id - 1] =
+            format!("<< /Type /Catalog /Pages {pages_id} 0 R >>").into_bytes();
+        objects[info_id - 1] =
+            format!("<< /Title <{}> >>", utf16_hex(&self.title, true)).into_bytes();
+
+        let mut output = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, body) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            output.extend_from_slice(body);
+            output.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = output.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root {catalog_id} 0 R /Info {info_id} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        );
+        output.extend_from_slice(xref.as_bytes());
+        output
+    }
+}
+
+// ==========================================
+// Thai dates in the Buddhist era
+// ==========================================
+
+pub const THAI_MONTHS: [&str; 12] = [
+    "มกราคม",
+    "กุมภาพันธ์",
+    "มีนาคม",
+    "เมษายน",
+    "พฤษภาคม",
+    "มิถุนายน",
+    "กรกฎาคม",
+    "สิงหาคม",
+    "กันยายน",
+    "ตุลาคม",
+    "พฤศจิกายน",
+    "ธันวาคม",
+];
+pub const THAI_SHORT_MONTHS: [&str; 12] = [
+    "ม.ค.",
+    "ก.พ.",
+    "มี.ค.",
+    "เม.ย.",
+    "พ.ค.",
+    "มิ.ย.",
+    "ก.ค.",
+    "ส.ค.",
+    "ก.ย.",
+    "ต.ค.",
+    "พ.ย.",
+    "ธ.ค.",
+];
+
+/// Calendar day of an instant at the school
+pub fn school_date(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&SCHOOL_TIMEZONE).date_naive()
+}
+
+pub fn buddhist_year(date: NaiveDate) -> i32 {
+    date.year() + 543
+}
+
+/// "14 สิงหาคม 2569"
+pub fn thai_date(date: NaiveDate) -> String {
+    format!(
+        "{} {} {}",
+        date.day(),
+        THAI_MONTHS[date.month0() as usize],
+        buddhist_year(date)
+    )
+}
+
+/// "14 สิงหาคม พ.ศ. 2569", the form used in letters and announcements
+pub fn thai_long_date(date: NaiveDate) -> String {
+    format!(
+        "{} {} พ.ศ. {}",
+        date.day(),
+        THAI_MONTHS[date.month0() as usize],
+        buddhist_year(date)
+    )
+}
+
+/// "2 พ.ย. 69", for narrow table columns
+pub fn thai_short_date(date: NaiveDate) -> String {
+    format!(
+        "{} {} {}",
+        date.day(),
+        THAI_SHORT_MONTHS[date.month0() as usize],
+        buddhist_year(date) % 100
+    )
+}
+
+// ==========================================
+// Tables and centred lines, laid out against PAGE_MARGIN
+// ==========================================
+
+pub const PAGE_MARGIN: f32 = 36.0;
+pub const TABLE_ROW_HEIGHT: f32 = 20.0;
+pub const TABLE_FONT_SIZE: f32 = 11.0;
+pub const TABLE_CELL_PADDING: f32 = 4.0;
+/// Extra height per wrapped line in [`draw_wrapped_table`]
+const TABLE_LINE_HEIGHT: f32 = 14.0;
+
+/// One column of a ruled table drawn by [`draw_table`]
+pub struct TableColumn {
+    title: String,
+    width: f32,
+    align: PdfTextAlign,
+}
+
+impl TableColumn {
+    pub fn new(title: impl Into<String>, width: f32, align: PdfTextAlign) -> Self {
+        Self {
+            title: title.into(),
+            width,
+            align,
+        }
+    }
+
+    /// Where text in a cell starting at `x` is anchored for this alignment
+    fn anchor(&self, x: f32) -> f32 {
+        match self.align {
+            PdfTextAlign::Left => x + TABLE_CELL_PADDING,
+            PdfTextAlign::Center => x + self.width / 2.0,
+            PdfTextAlign::Right => x + self.width - TABLE_CELL_PADDING,
+        }
+    }
+}
+
+/// Draws a header row and `rows` from `top`, moving to a new page (headed by
+/// `continued`) when the page fills up. Returns where the table ends.
+pub fn draw_table(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    columns: &[TableColumn],
+    rows: &[Vec<String>],
+    top: f32,
+    continued: &str,
+) -> f32 {
+    let mut y = draw_table_header(document, columns, top);
+    for row in rows {
+        if y + TABLE_ROW_HEIGHT > page.height - PAGE_MARGIN {
+            document.add_page(page);
+            let heading_y = PAGE_MARGIN + 14.0;
+            document.text(PAGE_MARGIN, heading_y, 13.0, PdfFontWeight::Bold, continued);
+            y = draw_table_header(document, columns, heading_y + 10.0);
+        }
+        draw_table_row(document, columns, row, y, PdfFontWeight::Regular);
+        y += TABLE_ROW_HEIGHT;
+    }
+    y
+}
+
+/// Like [`draw_table`], but long cells wrap onto extra lines and the row
+/// grows to fit instead of shortening them
+pub fn draw_wrapped_table(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    columns: &[TableColumn],
+    rows: &[Vec<String>],
+    top: f32,
+    continued: &str,
+) -> f32 {
+    let mut y = draw_table_header(document, columns, top);
+    for row in rows {
+        let cells: Vec<Vec<String>> = columns
+            .iter()
+            .zip(row)
+            .map(|(column, cell)| {
+                document.wrap_text(
+                    cell,
+                    TABLE_FONT_SIZE,
+                    PdfFontWeight::Regular,
+                    column.width - 2.0 * TABLE_CELL_PADDING,
+                )
+            })
+            .collect();
+        let line_count = cells.iter().map(Vec::len).max().unwrap_or(1).max(1);
+        let height = TABLE_ROW_HEIGHT + (line_count - 1) as f32 * TABLE_LINE_HEIGHT;
+        if y + height > page.height - PAGE_MARGIN {
+            document.add_page(page);
+            let heading_y = PAGE_MARGIN + 14.0;
+            document.text(PAGE_MARGIN, heading_y, 13.0, PdfFontWeight::Bold, continued);
+            y = draw_table_header(document, columns, heading_y + 10.0);
+        }
+        let mut x = PAGE_MARGIN;
+        for (column, lines) in columns.iter().zip(&cells) {
+            document.rect(x, y, column.width, height, 0.5);
+            for (index, line) in lines.iter().enumerate() {
+                document.aligned_text(
+                    column.anchor(x),
+                    y + TABLE_ROW_HEIGHT - 6.0 + index as f32 * TABLE_LINE_HEIGHT,
+                    TABLE_FONT_SIZE,
+                    PdfFontWeight::Regular,
+                    column.align,
+                    line,
+                );
+            }
+            x += column.width;
+        }
+        y += height;
+    }
+    y
+}
+
+fn draw_table_header(document: &mut PdfDocument, columns: &[TableColumn], top: f32) -> f32 {
+    let width = columns.iter().map(|column| column.width).sum();
+    document.fill_rect(PAGE_MARGIN, top, width, TABLE_ROW_HEIGHT, 0.9);
+    let titles: Vec<String> = columns.iter().map(|column| column.title.clone()).collect();
+    draw_table_row(document, columns, &titles, top, PdfFontWeight::Bold);
+    top + TABLE_ROW_HEIGHT
+}
+
+fn draw_table_row(
+    document: &mut PdfDocument,
+    columns: &[TableColumn],
+    cells: &[String],
+    top: f32,
+    weight: PdfFontWeight,
+) {
+    let baseline = top + TABLE_ROW_HEIGHT - 6.0;
+    let mut x = PAGE_MARGIN;
+    for (column, cell) in columns.iter().zip(cells) {
+        document.rect(x, top, column.width, TABLE_ROW_HEIGHT, 0.5);
+        let text = document.fit_text(
+            cell,
+            TABLE_FONT_SIZE,
+            weight,
+            column.width - 2.0 * TABLE_CELL_PADDING,
+        );
+        document.aligned_text(
+            column.anchor(x),
+            baseline,
+            TABLE_FONT_SIZE,
+            weight,
+            column.align,
+            &text,
+        );
+        x += column.width;
+    }
+}
+
+/// One line of text centred between the page margins, shortened to fit
+pub fn centered_line(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    y: f32,
+    size: f32,
+    weight: PdfFontWeight,
+    text: &str,
+) {
+    let text = document.fit_text(text, size, weight, page.width - 2.0 * PAGE_MARGIN);
+    document.aligned_text(
+        page.width / 2.0,
+        y,
+        size,
+        weight,
+        PdfTextAlign::Center,
+        &text,
+    );
+}
+
+// ==========================================
+// Body text for multi-page reports
+// ==========================================
+
+pub const BODY_FONT_SIZE: f32 = 12.0;
+pub const LINE_HEIGHT: f32 = 16.0;
+/// Section heading plus a table header and one row
+pub const SECTION_MIN_HEIGHT: f32 = 24.0 + 2.0 * TABLE_ROW_HEIGHT;
+
+/// Starts a new page when fewer than `needed` points remain below `y`
+pub fn ensure_space(document: &mut PdfDocument, page: PdfPageSize, y: f32, needed: f32) -> f32 {
+    if y + needed > page.height - PAGE_MARGIN {
+        document.add_page(page);
+        PAGE_MARGIN
+    } else {
+        y
+    }
+}
+
+/// Writes wrapped body text from `y` and returns the baseline of its last line
+pub fn paragraph(
+    document: &mut PdfDocument,
+    page: PdfPageSize,
+    mut y: f32,
+    weight: PdfFontWeight,
+    text: &str,
+) -> f32 {
+    let lines = document.wrap_text(text, BODY_FONT_SIZE, weight, page.width - 2.0 * PAGE_MARGIN);
+    for line in lines {
+        y = ensure_space(document, page, y, LINE_HEIGHT) + LINE_HEIGHT;
+        document.text(PAGE_MARGIN, y, BODY_FONT_SIZE, weight, &line);
+    }
+    y
+}
+
+/// Appends an empty object slot and returns its object number
+fn reserve_object(objects: &mut Vec<Vec<u8>>) -> usize {
+    objects.push(Vec::new());
+    objects.len()
+}
+
+fn write_font(objects: &mut Vec<Vec<u8>>, font: &PdfFont) -> usize {
+    let scale = 1000.0 / font.units_per_em();
+    let scaled = |value: i16| (f32::from(value) * scale).round() as i32;
+    let face = &font.face;
+
+    let file_id = reserve_object(objects);
+    objects[file_id - 1] = stream_object(&format!("/Length1 {}", font.data.len()), font.data);
+
+    let bbox = face.global_bounding_box();
+    let descriptor_id = reserve_object(objects);
+    objects[descriptor_id - 1] = format!(
+        "<< /Type /FontDescriptor /FontName /{} /Flags 32 /FontBBox [{} {} {} {}] \
+         /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {file_id} 0 R >>",
+        font.base_font,
+        scaled(bbox.x_min),
+        scaled(bbox.y_min),
+        scaled(bbox.x_max),
+        scaled(bbox.y_max),
+        scaled(face.ascender()),
+        scaled(face.descender()),
+        scaled(face.capital_height().unwrap_or(face.ascender())),
+    )
+    .into_bytes();
+
+    let widths = font
+        .used_glyphs
+        .keys()
+        .map(|glyph_id| {
+            let advance = face
+                .glyph_hor_advance(ttf_parser::GlyphId(*glyph_id))
+                .unwrap_or(0);
+            format!(
+                "{glyph_id} [{}]",
+                (f32::from(advance) * scale).round() as i32
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let cid_font_id = reserve_object(objects);
+    objects[cid_font_id - 1] = format!(
+        "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} \
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+         /FontDescriptor {descriptor_id} 0 R /DW 0 /W [{widths}] /CIDToGIDMap /Identity >>",
+        font.base_font
+    )
+    .into_bytes();
+
+    let to_unicode_id = reserve_object(objects);
+    objects[to_unicode_id - 1] = stream_object("", to_unicode_cmap(&font.used_glyphs).as_bytes());
+
+    let font_id = reserve_object(objects);
+    objects[font_id - 1] = format!(
+        "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H \
+         /DescendantFonts [{cid_font_id} 0 R] /ToUnicode {to_unicode_id} 0 R >>",
+        font.base_font
+    )
+    .into_bytes();
+    font_id
+}
+
+fn stream_object(extra_entries: &str, data: &[u8]) -> Vec<u8> {
+    let mut object = format!("<< /Length {} {extra_entries} >>\nstream\n", data.len()).into_bytes();
+    object.extend_from_slice(data);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+fn to_unicode_cmap(used_glyphs: &BTreeMap<u16, String>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let entries: Vec<(&u16, &String)> = used_glyphs.iter().collect();
+    for chunk in entries.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", chunk.len());
+        for (glyph_id, text) in chunk {
+            let _ = writeln!(cmap, "<{glyph_id:04X}> <{}>", utf16_hex(text, false));
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend");
+    cmap
+}
+
+fn utf16_hex(text: &str, with_bom: bool) -> String {
+    let mut hex = String::from(if with_bom { "FEFF" } else { "" });
+    for unit in text.encode_utf16() {
+        let _ = write!(hex, "{unit:04X}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xref_offsets(output: &[u8]) -> Vec<usize> {
+        let text = String::from_utf8_lossy(output);
+        let xref_start = text.rfind("\nxref\n").expect("xref table should exist") + 1;
+        text[xref_start..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().expect("offset should be numeric"))
+            .collect()
+    }
+
+    #[test]
+    fn thai_tone_marks_take_low_form_without_upper_vowel() {
+        let document = PdfDocument::new("test");
+        let font = document.font(PdfFontWeight::Regular);
+        let low = font.face.glyph_index_by_name("uni0E48.small").unwrap().0;
+        let high = font.face.glyph_index('\u{0E48}').unwrap().0;
+
+        let plain = font.shape("ก่");
+        assert_eq!(plain[1].glyph_id, low);
+        assert_eq!(plain[1].advance, 0);
+        let stacked = font.shape("กี่");
+        assert_eq!(stacked[2].glyph_id, high);
+        assert_eq!(stacked[2].text, "\u{0E48}");
+    }
+
+    #[test]
+    fn thai_dates_use_the_buddhist_era() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert_eq!(buddhist_year(date), 2568);
+        assert_eq!(thai_date(date), "31 ธันวาคม 2568");
+        assert_eq!(
+            thai_long_date(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            "1 มกราคม พ.ศ. 2569"
+        );
+        assert_eq!(thai_short_date(date), "31 ธ.ค. 68");
+        let late_evening_utc = "2025-12-31T17:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            school_date(late_evening_utc),
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn paragraph_continues_on_a_new_page_at_the_bottom_margin() {
+        let mut document = PdfDocument::new("test");
+        document.add_page(A4_PORTRAIT);
+        let near_bottom = A4_PORTRAIT.height - PAGE_MARGIN - LINE_HEIGHT / 2.0;
+
+        let y = paragraph(
+            &mut document,
+            A4_PORTRAIT,
+            near_bottom,
+            PdfFontWeight::Regular,
+            "บันทึกการนิเทศ",
+        );
+
+        assert_eq!(document.page_count(), 2);
+        assert_eq!(y, PAGE_MARGIN + LINE_HEIGHT);
+        assert_eq!(ensure_space(&mut document, A4_PORTRAIT, y, LINE_HEIGHT), y);
+        assert_eq!(document.page_count(), 2);
+    }
+
+    #[test]
+    fn document_serialises_with_valid_cross_reference_offsets() {
+        let mut document = PdfDocument::new("ผังที่นั่งสอบ");
+        document.add_page(A4_PORTRAIT);
+        document.text(40.0, 60.0, 14.0, PdfFontWeight::Bold, "ห้องสอบ 101");
+        document.rect(40.0, 80.0, 100.0, 40.0, 0.5);
+        document.add_page(A4_LANDSCAPE);
+        document.text(40.0, 60.0, 12.0, PdfFontWeight::Regular, "นักเรียน");
+
+        let output = document.finish();
+        assert!(output.starts_with(b"%PDF-1.7"));
+        let offsets = xref_offsets(&output);
+        assert!(!offsets.is_empty());
+        for (index, offset) in offsets.iter().enumerate() {
+            let header = format!("{} 0 obj", index + 1);
+            assert!(output[*offset..].starts_with(header.as_bytes()));
+        }
+        let text = String::from_utf8_lossy(&output);
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/BaseFont /Sarabun-Bold"));
+        assert!(text.contains("/BaseFont /Sarabun-Regular"));
+    }
+
+    #[test]
+    fn unused_fonts_are_not_embedded_and_long_text_is_fitted() {
+        let mut document = PdfDocument::new("labels");
+        let fitted = document.fit_text(
+            "นายสมชายผู้มีนามสกุลยาวมากเป็นพิเศษ",
+            12.0,
+            PdfFontWeight::Regular,
+            60.0,
+        );
+        assert!(fitted.ends_with('…'));
+        assert!(document.text_width(&fitted, 12.0, PdfFontWeight::Regular) <= 60.0);
+        document.text(10.0, 10.0, 12.0, PdfFontWeight::Regular, &fitted);
+
+        let text = String::from_utf8_lossy(&document.finish()).into_owned();
+        assert!(!text.contains("Sarabun-Bold"));
+        assert_eq!(text.matches("/FontFile2").count(), 1);
+    }
+
+    #[test]
+    fn wrapped_lines_fit_and_never_strand_thai_marks() {
+        assert_eq!(
+            line_break_units("เก่งมาก ok go"),
+            ["เก่", "ง", "มา", "ก ", "ok ", "go"]
+        );
+
+        let document = PdfDocument::new("wrap");
+        let text = "ครูจัดกิจกรรมการเรียนรู้ที่เน้นผู้เรียนเป็นสำคัญ และใช้สื่อที่หลากหลาย\nข้อเสนอแนะ: ควรเพิ่มเวลาให้นักเรียนอภิปราย";
+        let lines = document.wrap_text(text, 12.0, PdfFontWeight::Regular, 120.0);
+        assert!(lines.len() > 3);
+        assert_eq!(
+            lines.concat().replace(' ', ""),
+            text.replace([' ', '\n'], "")
+        );
+        for line in &lines {
+            assert!(document.text_width(line, 12.0, PdfFontWeight::Regular) <= 120.0);
+            let first = line.chars().next().unwrap();
+            assert!(!is_thai_trailing_sign(first));
+            assert!(!is_thai_leading_vowel(line.chars().last().unwrap()));
+        }
+        assert!(lines.iter().any(|line| line.starts_with("ข้อเสนอแนะ")));
+
+        let mut document = document;
+        let columns = [
+            TableColumn::new("รายการ", 128.0, PdfTextAlign::Left),
+            TableColumn::new(format!("คนที่ {}", 1), 48.0, PdfTextAlign::Center),
+        ];
+        let bottom = draw_wrapped_table(
+            &mut document,
+            A4_PORTRAIT,
+            &columns,
+            &[vec![text.to_string(), "4".to_string()]],
+            PAGE_MARGIN,
+            "ต่อ",
+        );
+        assert_eq!(
+            bottom,
+            PAGE_MARGIN + 2.0 * TABLE_ROW_HEIGHT + (lines.len() - 1) as f32 * TABLE_LINE_HEIGHT
+        );
+    }
+}
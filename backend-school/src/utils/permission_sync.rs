@@ -1,10 +1,17 @@
 /// Permission sync utility - Auto-sync permission registry to database
 use crate::permissions::registry::ALL_PERMISSIONS;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 /// Sync all permissions from registry to database
 /// This is called after migrations complete to ensure DB is up-to-date
 pub async fn sync_permissions(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let mut connection = pool.acquire().await?;
+    sync_permissions_on(&mut connection).await
+}
+
+/// Same as `sync_permissions`, on a connection the caller holds, typically
+/// inside its transaction.
+pub async fn sync_permissions_on(connection: &mut PgConnection) -> Result<(), sqlx::Error> {
     // Step 1: Collect all permission codes from registry
     let registry_codes: Vec<&str> = ALL_PERMISSIONS.iter().map(|p| p.code).collect();
 
@@ -25,7 +32,7 @@ pub async fn sync_permissions(pool: &PgPool) -> Result<(), sqlx::Error> {
             query = query.bind(code);
         }
 
-        let result = query.execute(pool).await?;
+        let result = query.execute(&mut *connection).await?;
         if result.rows_affected() > 0 {
             tracing::info!(
                 "🗑️  Deleted {} old permissions not in registry",
@@ -55,7 +62,7 @@ pub async fn sync_permissions(pool: &PgPool) -> Result<(), sqlx::Error> {
         .bind(perm.action)
         .bind(perm.scope)
         .bind(perm.description)
-        .execute(pool)
+        .execute(&mut *connection)
         .await?;
     }
 
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+
+/// Which plan limit a request ran into. Variant names are the wire codes.
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaCode {
+    StudentLimitReached,
+    StorageLimitReached,
+    CertificateLimitReached,
+}
+
+/// Error data returned with a quota rejection so clients can show current usage
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaExceeded {
+    pub code: QuotaCode,
+    pub current: i64,
+    pub requested: i64,
+    pub limit: i64,
+}
+
+/// Reject `requested` more units when `current + requested` would pass `limit`.
+/// A missing limit is unlimited.
+pub fn ensure_within_quota(
+    code: QuotaCode,
+    limit: Option<i64>,
+    current: i64,
+    requested: i64,
+) -> Result<(), AppError> {
+    match limit {
+        Some(limit) if current.saturating_add(requested) > limit => {
+            Err(AppError::QuotaExceeded(QuotaExceeded {
+                code,
+                current,
+                requested,
+                limit,
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_up_to_the_limit_are_allowed() {
+        assert!(ensure_within_quota(QuotaCode::StudentLimitReached, Some(10), 9, 1).is_ok());
+        assert!(ensure_within_quota(QuotaCode::StudentLimitReached, None, i64::MAX, 1).is_ok());
+    }
+
+    #[test]
+    fn requests_past_the_limit_report_current_usage() {
+        let error =
+            ensure_within_quota(QuotaCode::CertificateLimitReached, Some(100), 95, 10).unwrap_err();
+
+        match error {
+            AppError::QuotaExceeded(quota) => assert_eq!(
+                quota,
+                QuotaExceeded {
+                    code: QuotaCode::CertificateLimitReached,
+                    current: 95,
+                    requested: 10,
+                    limit: 100,
+                }
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -21,13 +21,14 @@ pub async fn actor_tenant_context_from_session(
     session: &AuthenticatedSession,
 ) -> Result<ActorTenantContext, AppError> {
     let tenant = session.tenant.clone();
-    let actor = load_actor_context_for_session(
+    let mut actor = load_actor_context_for_session(
         session.user_id,
         &tenant.subdomain,
         &tenant.pool,
         &state.permission_cache,
     )
     .await?;
+    actor.permissions = session.effective_permissions(actor.permissions);
 
     Ok(ActorTenantContext { tenant, actor })
 }
@@ -44,6 +45,7 @@ pub fn current_user_tenant_context_from_session(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::admin_client::TenantPlan;
     use sqlx::postgres::PgPoolOptions;
 
     const USER_ID: &str = "8b391685-4a1c-4f25-a544-b1c5bd0d457e";
@@ -57,11 +59,13 @@ mod tests {
                 tenant_id: Uuid::parse_str("11111111-1111-1111-1111-111111111111").unwrap(),
                 subdomain: tenant.to_string(),
                 pool,
+                plan: TenantPlan::default(),
             },
             session_id: Uuid::parse_str("22222222-2222-2222-2222-222222222222").unwrap(),
             user_id: Uuid::parse_str(USER_ID).unwrap(),
             username: "teacher.one".to_string(),
             user_type: "staff".to_string(),
+            support: None,
         }
     }
 
@@ -2,6 +2,7 @@ use axum::http::HeaderMap;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::db::admin_client::{SchoolDatabaseInfo, TenantPlan};
 use crate::db::school_mapping::get_school_database_info;
 use crate::error::AppError;
 use crate::modules::auth::runtime::AuthRuntime;
@@ -14,6 +15,17 @@ pub struct TenantContext {
     pub tenant_id: Uuid,
     pub subdomain: String,
     pub pool: PgPool,
+    /// Limits and modules of the school's plan, refreshed on every lookup
+    pub plan: TenantPlan,
+}
+
+/// Tenant schemas are only changed by migration campaigns. While a wave is
+/// migrating this tenant its requests fail fast instead of racing the DDL.
+fn ensure_tenant_ready(school: &SchoolDatabaseInfo) -> Result<(), AppError> {
+    if school.migration_in_progress {
+        return Err(AppError::ServiceUnavailable("tenant_migrating".to_string()));
+    }
+    Ok(())
 }
 
 pub async fn resolve_tenant_context(
@@ -40,6 +52,7 @@ pub async fn resolve_tenant_context_by_subdomain(
             );
             AppError::NotFound("ไม่พบโรงเรียน".to_string())
         })?;
+    ensure_tenant_ready(&school)?;
 
     let pool = state
         .pool_manager
@@ -58,6 +71,7 @@ pub async fn resolve_tenant_context_by_subdomain(
         tenant_id: school.tenant_id,
         subdomain: subdomain.to_string(),
         pool,
+        plan: school.plan,
     })
 }
 
@@ -103,6 +117,7 @@ pub async fn resolve_auth_tenant_context(
                 AppError::ServiceUnavailable("tenant_directory".to_string())
             }
         })?;
+    ensure_tenant_ready(&school)?;
     let pool = runtime
         .pool_manager
         .get_pool(&school.database_url, &subdomain)
@@ -113,6 +128,7 @@ pub async fn resolve_auth_tenant_context(
         tenant_id: school.tenant_id,
         subdomain,
         pool,
+        plan: school.plan,
     })
 }
 
@@ -140,7 +156,8 @@ mod tests {
         },
     };
 
-    use super::resolve_auth_tenant_context;
+    use super::{ensure_tenant_ready, resolve_auth_tenant_context};
+    use crate::db::admin_client::{SchoolDatabaseInfo, TenantPlan};
 
     async fn directory_response(Path(subdomain): Path<String>) -> Response {
         match subdomain.as_str() {
@@ -191,6 +208,20 @@ mod tests {
         )])
     }
 
+    #[test]
+    fn migrating_tenants_are_not_ready() {
+        let school = |migration_in_progress| SchoolDatabaseInfo {
+            tenant_id: uuid::Uuid::nil(),
+            database_url: "postgres://tenant".to_string(),
+            migration_in_progress,
+            plan: TenantPlan::default(),
+        };
+
+        assert!(ensure_tenant_ready(&school(false)).is_ok());
+        let error = ensure_tenant_ready(&school(true)).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[tokio::test]
     async fn auth_tenant_resolver_distinguishes_unknown_school_from_directory_failure() {
         let (runtime, server) = runtime_with_directory().await;
@@ -120,13 +120,22 @@ fn remaining_vertical_handlers_use_typed_session_identity() {
     assert_typed_session_handlers(&[
         "src/modules/admission/handlers/applications.rs",
         "src/modules/admission/handlers/exam_rooms.rs",
+        "src/modules/admission/handlers/offers.rs",
+        "src/modules/admission/handlers/score_imports.rs",
+        "src/modules/admission/handlers/reports.rs",
         "src/modules/admission/handlers/rounds.rs",
         "src/modules/admission/handlers/scores.rs",
         "src/modules/admission/handlers/selections.rs",
+        "src/modules/announcement/handlers.rs",
         "src/modules/calendar/handlers.rs",
         "src/modules/facility/handlers.rs",
+        "src/modules/finance/handlers.rs",
+        "src/modules/professional_development/handlers.rs",
         "src/modules/question_bank/handlers.rs",
+        "src/modules/staff_leave/handlers.rs",
+        "src/modules/student_leave/handlers.rs",
         "src/modules/supervision/handlers.rs",
+        "src/modules/support_access/handlers.rs",
         "src/modules/work/handlers.rs",
         "src/modules/workflow/handlers.rs",
         "src/modules/lookup/handlers.rs",
@@ -232,8 +241,10 @@ fn supervision_service_facade_is_thin_and_preserves_public_surface() {
     let service_dir = manifest_dir().join("src/modules/supervision/services");
 
     for module in [
+        "cycle_analytics",
         "cycles",
         "evaluations",
+        "observation_report",
         "observations",
         "reviews_and_reports",
         "shared",
@@ -258,6 +269,7 @@ fn supervision_service_facade_is_thin_and_preserves_public_surface() {
         "certify_observation",
         "create_cycle",
         "create_template",
+        "cycle_analytics",
         "cycle_progress",
         "cycle_teacher_status",
         "evaluator_availability",
@@ -271,6 +283,7 @@ fn supervision_service_facade_is_thin_and_preserves_public_surface() {
         "list_templates",
         "manager_can_edit_observation",
         "observation_timetable_options",
+        "render_observation_report",
         "replace_observation_evaluators",
         "request_observation",
         "resolve_supervision_target_rule",
@@ -2267,9 +2280,13 @@ fn academic_exam_schedule_routes_are_registered_and_authorized() {
         "\"/exam-schedules/room-assignments/{assignment_id}/seats\"",
         "\"/exam-schedules/sessions\"",
         "\"/exam-schedules/sessions/{session_id}\"",
+        "\"/exam-schedules/{round_id}/auto-placement/preview\"",
+        "\"/exam-schedules/{round_id}/auto-placement/apply\"",
         "\"/exam-schedules/{round_id}/invigilators\"",
+        "\"/exam-schedules/{round_id}/invigilators/auto-assign\"",
         "\"/exam-schedules/{round_id}/invigilator-staff-options\"",
         "\"/exam-schedules/room-assignments/{assignment_id}/invigilators\"",
+        "\"/exam-schedules/{round_id}/documents/{kind}\"",
         "\"/exam-schedules/{round_id}/publish\"",
     ] {
         assert!(
@@ -2293,9 +2310,13 @@ fn academic_exam_schedule_routes_are_registered_and_authorized() {
         "post(handlers::exam_schedule::generate_seats)",
         "post(handlers::exam_schedule::place_session)",
         "delete(handlers::exam_schedule::delete_session)",
+        "post(handlers::exam_schedule::preview_auto_placement)",
+        "post(handlers::exam_schedule::apply_auto_placement)",
+        "post(handlers::exam_schedule::auto_assign_invigilators)",
         "get(handlers::exam_schedule::get_invigilator_workspace)",
         "get(handlers::exam_schedule::get_invigilator_staff_options)",
         "put(handlers::exam_schedule::update_assignment_invigilators)",
+        "get(handlers::exam_schedule::download_exam_day_document)",
         "post(handlers::exam_schedule::publish_round)",
     ] {
         assert!(
@@ -3647,8 +3668,8 @@ fn read_oriented_handlers_are_registered_in_the_openapi_document() {
 
     assert_eq!(
         read_oriented_handlers_from_routers(&main_router, &calendar_router).len(),
-        39,
-        "read-oriented router inventory must stay aligned with the 39-operation rollout"
+        40,
+        "read-oriented router inventory must stay aligned with the 40-operation rollout"
     );
     assert_eq!(
         read_oriented_handlers_missing_from_contract(&main_router, &calendar_router, &contract),
@@ -4277,7 +4298,7 @@ fn public_and_protected_routes_are_explicitly_partitioned() {
     assert_eq!(
         app.matches("DefaultBodyLimit::max(AUTH_JSON_BODY_LIMIT)")
             .count(),
-        2
+        3
     );
     assert!(app.contains("DefaultBodyLimit::max(APPLICATION_BODY_LIMIT)"));
 }
@@ -4704,6 +4725,11 @@ fn mutation_performance_foundation_services_use_bulk_helpers() {
             .as_slice(),
             ["for subject in &req.subjects", "for t in team"].as_slice(),
         ),
+        (
+            "src/modules/academic/services/study_plan_compliance_service.rs",
+            ["bulk_insert_curriculum_rules"].as_slice(),
+            ["for rule in &req.rules"].as_slice(),
+        ),
         (
             "src/modules/academic/services/academic_structure_service.rs",
             [
@@ -4746,6 +4772,19 @@ fn mutation_performance_foundation_services_use_bulk_helpers() {
             ]
             .as_slice(),
         ),
+        (
+            "src/modules/academic/services/activity_allocation_service.rs",
+            [
+                "bulk_insert_activity_preferences",
+                "bulk_insert_allocation_results",
+            ]
+            .as_slice(),
+            [
+                "for placement in &outcome.placements",
+                "for group_id in &group_ids",
+            ]
+            .as_slice(),
+        ),
         (
             "src/modules/admission/services/application_service.rs",
             [
@@ -4775,6 +4814,11 @@ fn mutation_performance_foundation_services_use_bulk_helpers() {
             ["Failed to insert supervision template steps"].as_slice(),
             ["Failed to insert supervision template step:"].as_slice(),
         ),
+        (
+            "src/modules/professional_development/services/plc_sessions.rs",
+            ["insert_plc_participants", "UNNEST($2::uuid[], $3::text[])"].as_slice(),
+            ["for (user_id, role) in"].as_slice(),
+        ),
         (
             "src/modules/staff/services/organization_permission_service.rs",
             ["bulk_insert_organization_permission_grants"].as_slice(),
@@ -5588,6 +5632,7 @@ fn file_platform_domain_relationships_own_attachment_and_lifecycle_deletion() {
         "src/modules/admission/handlers/applications.rs",
         "src/modules/admission/handlers/portal.rs",
         "src/modules/admission/handlers/rounds.rs",
+        "src/modules/professional_development/handlers.rs",
         "src/modules/question_bank/handlers.rs",
         "src/modules/auth/handlers.rs",
         "src/modules/staff/handlers/staff.rs",
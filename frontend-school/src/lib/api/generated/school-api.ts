@@ -165,6 +165,38 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/academic/activity-allocation-runs/{id}': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get: operations['getActivityAllocationRun'];
+		put?: never;
+		post?: never;
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
+	'/api/academic/activity-allocation-runs/{id}/publish': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put?: never;
+		post: operations['publishActivityAllocationRun'];
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/academic/activity-catalog': {
 		parameters: {
 			query?: never;
@@ -261,6 +293,22 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/academic/activity-slots/{id}/allocation-runs': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get: operations['listActivityAllocationRuns'];
+		put?: never;
+		post: operations['runActivityAllocation'];
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/academic/activity-slots/{id}/classroom-assignments': {
 		parameters: {
 			query?: never;
@@ -389,6 +437,22 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/academic/activity-slots/{id}/my-preferences': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get: operations['getMyActivityPreferences'];
+		put: operations['submitMyActivityPreferences'];
+		post?: never;
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/academic/activity-slots/{id}/timetable-entries': {
 		parameters: {
 			query?: never;
@@ -453,6 +517,38 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/academic/curriculum-rule-sets': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get: operations['listCurriculumRuleSets'];
+		put?: never;
+		post: operations['createCurriculumRuleSet'];
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
+	'/api/academic/curriculum-rule-sets/{id}': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put: operations['updateCurriculumRuleSet'];
+		post?: never;
+		delete: operations['deleteCurriculumRuleSet'];
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/academic/enrollments': {
 		parameters: {
 			query?: never;
@@ -837,6 +933,38 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/academic/study-plan-versions/{id}/compliance': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get: operations['getStudyPlanComplianceReport'];
+		put?: never;
+		post?: never;
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
+	'/api/academic/study-plan-versions/{id}/compliance-override': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put?: never;
+		post: operations['recordStudyPlanComplianceOverride'];
+		delete: operations['revokeStudyPlanComplianceOverride'];
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/academic/study-plan-versions/{id}/subjects': {
 		parameters: {
 			query?: never;
@@ -1496,6 +1624,22 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/auth/support-handoff': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		get?: never;
+		put?: never;
+		post: operations['supportHandoff'];
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/calendar/categories': {
 		parameters: {
 			query?: never;
@@ -2687,6 +2831,23 @@ export interface paths {
 		patch?: never;
 		trace?: never;
 	};
+	'/api/parent/students/{student_id}/fees': {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		/** GET /api/parent/students/:student_id/fees - ยอดค้างชำระและใบแจ้งหนี้ของบุตรหลาน */
+		get: operations['getParentChildFees'];
+		put?: never;
+		post?: never;
+		delete?: never;
+		options?: never;
+		head?: never;
+		patch?: never;
+		trace?: never;
+	};
 	'/api/parent/students/{student_id}/timetable': {
 		parameters: {
 			query?: never;
@@ -3190,6 +3351,78 @@ export interface components {
 		ActivityAddedCountData: {
 			added: number;
 		};
+		ActivityAllocationGroupFill: {
+			/** Format: uuid */
+			activityGroupId: string;
+			/** Format: int64 */
+			allocated: number;
+			/**
+			 * Format: int64
+			 * @description Members now; includes this run's placements once it is published
+			 */
+			currentMembers: number;
+			/**
+			 * Format: int64
+			 * @description Students who ranked the group first
+			 */
+			firstChoiceDemand: number;
+			groupName: string;
+			/** Format: int32 */
+			maxCapacity?: number | null;
+		};
+		/** @enum {string} */
+		ActivityAllocationMethod: 'random_serial_dictatorship' | 'grade_priority_stable';
+		ActivityAllocationPlacement: {
+			/** Format: uuid */
+			activityGroupId: string;
+			classroomName?: string | null;
+			groupName: string;
+			/** Format: int32 */
+			preferenceRank: number;
+			studentCode?: string | null;
+			/** Format: uuid */
+			studentId: string;
+			studentName?: string | null;
+		};
+		ActivityAllocationRun: {
+			/** Format: int32 */
+			alreadyEnrolledCount: number;
+			/** Format: date-time */
+			createdAt: string;
+			/** Format: uuid */
+			createdBy?: string | null;
+			/** Format: int32 */
+			firstChoiceCount: number;
+			/** Format: uuid */
+			id: string;
+			/** Format: int32 */
+			matchedCount: number;
+			method: components['schemas']['ActivityAllocationMethod'];
+			/** Format: date-time */
+			publishedAt?: string | null;
+			/** Format: uuid */
+			publishedBy?: string | null;
+			/** Format: int64 */
+			seed: number;
+			/** Format: uuid */
+			slotId: string;
+			/** @description draft | published | discarded */
+			status: string;
+			/**
+			 * Format: int32
+			 * @description Students taking part, excluding those already in a group of the slot
+			 */
+			studentCount: number;
+			/** Format: int32 */
+			unmatchedCount: number;
+		};
+		ActivityAllocationRunDetail: components['schemas']['ActivityAllocationRun'] & {
+			groups: components['schemas']['ActivityAllocationGroupFill'][];
+			/** @description Matched students per choice rank; index 0 is the first choice */
+			matchedByRank: number[];
+			placements: components['schemas']['ActivityAllocationPlacement'][];
+			unmatched: components['schemas']['ActivityUnmatchedStudent'][];
+		};
 		ActivityCatalog: {
 			activity_type: components['schemas']['ActivityCatalogType'];
 			/** Format: date-time */
@@ -3281,11 +3514,21 @@ export interface components {
 		};
 		/** @enum {string} */
 		ActivityMemberResult: 'pass' | 'fail';
+		ActivityPreferenceChoice: {
+			/** Format: uuid */
+			activityGroupId: string;
+			groupName: string;
+			/**
+			 * Format: int32
+			 * @description 1 = first choice
+			 */
+			preferenceRank: number;
+		};
 		ActivityProcessedCountData: {
 			count: number;
 		};
 		/** @enum {string} */
-		ActivityRegistrationType: 'self' | 'assigned';
+		ActivityRegistrationType: 'self' | 'assigned' | 'preference';
 		/** @enum {string} */
 		ActivitySchedulingMode: 'synchronized' | 'independent';
 		ActivitySlot: {
@@ -3308,6 +3551,11 @@ export interface components {
 			/** Format: uuid */
 			id: string;
 			is_active: boolean;
+			/**
+			 * Format: int32
+			 * @description Ranked choices allowed per student in preference registration
+			 */
+			max_preferences: number;
 			name?: string | null;
 			/** Format: int32 */
 			periods_per_week?: number | null;
@@ -3343,6 +3591,16 @@ export interface components {
 			};
 			slots: components['schemas']['ActivitySlot'][];
 		};
+		/** @enum {string} */
+		ActivityUnmatchedReason: 'no_preferences' | 'no_eligible_choice' | 'choices_full';
+		ActivityUnmatchedStudent: {
+			classroomName?: string | null;
+			reason: components['schemas']['ActivityUnmatchedReason'];
+			studentCode?: string | null;
+			/** Format: uuid */
+			studentId: string;
+			studentName?: string | null;
+		};
 		AddCatalogDefaultInstructorRequest: {
 			/** Format: uuid */
 			instructor_id: string;
@@ -3479,6 +3737,17 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_ActivityAllocationRunDetail: {
+			data: components['schemas']['ActivityAllocationRun'] & {
+				groups: components['schemas']['ActivityAllocationGroupFill'][];
+				/** @description Matched students per choice rank; index 0 is the first choice */
+				matchedByRank: number[];
+				placements: components['schemas']['ActivityAllocationPlacement'][];
+				unmatched: components['schemas']['ActivityUnmatchedStudent'][];
+			};
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_ActivityCatalog: {
 			data: {
 				activity_type: components['schemas']['ActivityCatalogType'];
@@ -3577,6 +3846,11 @@ export interface components {
 				/** Format: uuid */
 				id: string;
 				is_active: boolean;
+				/**
+				 * Format: int32
+				 * @description Ranked choices allowed per student in preference registration
+				 */
+				max_preferences: number;
 				name?: string | null;
 				/** Format: int32 */
 				periods_per_week?: number | null;
@@ -3871,6 +4145,19 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_ChildFeeStatement: {
+			data: {
+				invoices: components['schemas']['ChildFeeInvoice'][];
+				/** Format: int64 */
+				outstandingSatang: number;
+				/** Format: int64 */
+				overdueSatang: number;
+				/** Format: uuid */
+				studentId: string;
+			};
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_Classroom: {
 			data: {
 				/** Format: uuid */
@@ -3932,12 +4219,40 @@ export interface components {
 				/** Format: uuid */
 				profileImageFileId: string | null;
 				status: string;
+				/** @description Present only while platform support is signed in as this user. */
+				supportSession?: null | components['schemas']['SupportSessionBanner'];
 				username: string;
 				userType: string;
 			};
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_CurriculumRuleSet: {
+			data: {
+				/** Format: date-time */
+				created_at: string;
+				description?: string | null;
+				/** Format: uuid */
+				id: string;
+				is_default: boolean;
+				name: string;
+				rules: components['schemas']['CurriculumRule'][];
+				/**
+				 * Format: int32
+				 * @description Terms an activity pinned to "every term" runs in per grade level
+				 */
+				terms_per_year: number;
+				/** Format: date-time */
+				updated_at: string;
+				/**
+				 * Format: int32
+				 * @description Converts activity periods per week into hours
+				 */
+				weeks_per_term: number;
+			};
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_DailyTeachingOverview: {
 			data: {
 				/** Format: uuid */
@@ -4256,6 +4571,27 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_MyActivityPreferences: {
+			data: {
+				/**
+				 * Format: uuid
+				 * @description The group from the published allocation, if the student was placed
+				 */
+				allocatedGroupId?: string | null;
+				allocationPublished: boolean;
+				choices: components['schemas']['ActivityPreferenceChoice'][];
+				/** Format: int32 */
+				maxPreferences: number;
+				/** @description Whether choices can be submitted or changed right now */
+				registrationOpen: boolean;
+				/** Format: uuid */
+				slotId: string;
+				/** Format: date-time */
+				submittedAt?: string | null;
+			};
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_MyTimetableData: {
 			data: {
 				/** Format: int64 */
@@ -4588,8 +4924,38 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_StudyPlanComplianceReport: {
+			data: {
+				active_override?: null | components['schemas']['StudyPlanComplianceOverride'];
+				/**
+				 * @description Activation and course generation are refused: some failing check is
+				 *     not covered by the active override
+				 */
+				blocked: boolean;
+				checks: components['schemas']['StudyPlanComplianceCheck'][];
+				/** @description Every check passed */
+				compliant: boolean;
+				/** Format: int32 */
+				failed_count: number;
+				/**
+				 * Format: uuid
+				 * @description null when the version has no pinned set and no default set exists
+				 */
+				rule_set_id?: string | null;
+				rule_set_name?: string | null;
+				/** Format: uuid */
+				study_plan_version_id: string;
+			};
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_StudyPlanVersion: {
 			data: {
+				/**
+				 * Format: uuid
+				 * @description ชุดเกณฑ์ที่ใช้ตรวจหลักสูตรฉบับนี้ — null = ใช้ชุดเกณฑ์ตั้งต้นของโรงเรียน
+				 */
+				compliance_rule_set_id?: string | null;
 				/** Format: date-time */
 				created_at: string;
 				description?: string | null;
@@ -4740,6 +5106,42 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_Vec_ActivityAllocationRun: {
+			data: {
+				/** Format: int32 */
+				alreadyEnrolledCount: number;
+				/** Format: date-time */
+				createdAt: string;
+				/** Format: uuid */
+				createdBy?: string | null;
+				/** Format: int32 */
+				firstChoiceCount: number;
+				/** Format: uuid */
+				id: string;
+				/** Format: int32 */
+				matchedCount: number;
+				method: components['schemas']['ActivityAllocationMethod'];
+				/** Format: date-time */
+				publishedAt?: string | null;
+				/** Format: uuid */
+				publishedBy?: string | null;
+				/** Format: int64 */
+				seed: number;
+				/** Format: uuid */
+				slotId: string;
+				/** @description draft | published | discarded */
+				status: string;
+				/**
+				 * Format: int32
+				 * @description Students taking part, excluding those already in a group of the slot
+				 */
+				studentCount: number;
+				/** Format: int32 */
+				unmatchedCount: number;
+			}[];
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_Vec_ActivityCatalog: {
 			data: {
 				activity_type: components['schemas']['ActivityCatalogType'];
@@ -4840,6 +5242,11 @@ export interface components {
 				/** Format: uuid */
 				id: string;
 				is_active: boolean;
+				/**
+				 * Format: int32
+				 * @description Ranked choices allowed per student in preference registration
+				 */
+				max_preferences: number;
 				name?: string | null;
 				/** Format: int32 */
 				periods_per_week?: number | null;
@@ -5244,6 +5651,32 @@ export interface components {
 			message?: string;
 			success: boolean;
 		};
+		ApiResponse_Vec_CurriculumRuleSet: {
+			data: {
+				/** Format: date-time */
+				created_at: string;
+				description?: string | null;
+				/** Format: uuid */
+				id: string;
+				is_default: boolean;
+				name: string;
+				rules: components['schemas']['CurriculumRule'][];
+				/**
+				 * Format: int32
+				 * @description Terms an activity pinned to "every term" runs in per grade level
+				 */
+				terms_per_year: number;
+				/** Format: date-time */
+				updated_at: string;
+				/**
+				 * Format: int32
+				 * @description Converts activity periods per week into hours
+				 */
+				weeks_per_term: number;
+			}[];
+			message?: string;
+			success: boolean;
+		};
 		ApiResponse_Vec_DelegatablePermission: {
 			data: {
 				code: string;
@@ -6811,6 +7244,52 @@ export interface components {
 			relationship: string;
 			student_id: string | null;
 		};
+		ChildFeeInvoice: {
+			/** Format: date */
+			dueDate: string;
+			/** Format: uuid */
+			id: string;
+			invoiceNumber: string;
+			lines: components['schemas']['FeeInvoiceLine'][];
+			/** Format: int64 */
+			outstandingSatang: number;
+			/** Format: int64 */
+			paidSatang: number;
+			payments: components['schemas']['ChildFeePayment'][];
+			/**
+			 * Format: int64
+			 * @description Pending guardian transfers not yet reviewed by the school.
+			 */
+			pendingSatang: number;
+			promptpayPayload: string | null;
+			status: components['schemas']['FeeInvoiceStatus'];
+			title: string;
+			/** Format: int64 */
+			totalSatang: number;
+		};
+		ChildFeePayment: {
+			/** Format: int64 */
+			amountSatang: number;
+			/** Format: uuid */
+			id: string;
+			method: components['schemas']['FeePaymentMethod'];
+			/** Format: date-time */
+			paidAt: string;
+			/** Format: uuid */
+			receiptId: string | null;
+			receiptNumber: string | null;
+			rejectionReason: string | null;
+			status: components['schemas']['FeePaymentStatus'];
+		};
+		ChildFeeStatement: {
+			invoices: components['schemas']['ChildFeeInvoice'][];
+			/** Format: int64 */
+			outstandingSatang: number;
+			/** Format: int64 */
+			overdueSatang: number;
+			/** Format: uuid */
+			studentId: string;
+		};
 		Classroom: {
 			/** Format: uuid */
 			academic_year_id: string;
@@ -7187,6 +7666,8 @@ export interface components {
 			name_th: string;
 		};
 		CreateStudyPlanVersionRequest: {
+			/** Format: uuid */
+			compliance_rule_set_id?: string | null;
 			description?: string | null;
 			/** Format: uuid */
 			end_academic_year_id?: string | null;
@@ -7228,11 +7709,84 @@ export interface components {
 			/** Format: uuid */
 			profileImageFileId: string | null;
 			status: string;
+			/** @description Present only while platform support is signed in as this user. */
+			supportSession?: null | components['schemas']['SupportSessionBanner'];
 			username: string;
 			userType: string;
 		};
 		/** @enum {string} */
 		CurriculumInstructorRole: 'primary' | 'secondary';
+		CurriculumRule: {
+			activity_type?: null | components['schemas']['ActivityCatalogType'];
+			/** Format: int32 */
+			display_order: number;
+			/** Format: int32 */
+			grade_year_from: number;
+			/** Format: int32 */
+			grade_year_to: number;
+			/** Format: uuid */
+			id: string;
+			label: string;
+			/** @description kindergarten | primary | secondary */
+			level_type: string;
+			/** Format: double */
+			max_value?: number | null;
+			/** Format: double */
+			min_value?: number | null;
+			rule_kind: components['schemas']['CurriculumRuleKind'];
+			scope: components['schemas']['CurriculumRuleScope'];
+			/** Format: uuid */
+			subject_group_id?: string | null;
+			subject_group_name?: string | null;
+			subject_type?: null | components['schemas']['SubjectType'];
+		};
+		CurriculumRuleInput: {
+			activity_type?: null | components['schemas']['ActivityCatalogType'];
+			/** Format: int32 */
+			grade_year_from: number;
+			/** Format: int32 */
+			grade_year_to: number;
+			label: string;
+			/** @description kindergarten | primary | secondary */
+			level_type: string;
+			/** Format: double */
+			max_value?: number | null;
+			/** Format: double */
+			min_value?: number | null;
+			rule_kind: components['schemas']['CurriculumRuleKind'];
+			scope: components['schemas']['CurriculumRuleScope'];
+			/** Format: uuid */
+			subject_group_id?: string | null;
+			subject_type?: null | components['schemas']['SubjectType'];
+		};
+		/** @enum {string} */
+		CurriculumRuleKind: 'subject_group_hours' | 'activity_hours' | 'credit_range';
+		/** @enum {string} */
+		CurriculumRuleScope: 'per_grade' | 'band_total';
+		CurriculumRuleSet: {
+			/** Format: date-time */
+			created_at: string;
+			description?: string | null;
+			/** Format: uuid */
+			id: string;
+			is_default: boolean;
+			name: string;
+			rules: components['schemas']['CurriculumRule'][];
+			/**
+			 * Format: int32
+			 * @description Terms an activity pinned to "every term" runs in per grade level
+			 */
+			terms_per_year: number;
+			/** Format: date-time */
+			updated_at: string;
+			/**
+			 * Format: int32
+			 * @description Converts activity periods per week into hours
+			 */
+			weeks_per_term: number;
+		};
+		/** @enum {string} */
+		CurriculumRuleUnit: 'hours' | 'credits';
 		DailyTeachingEntry: {
 			activitySchedulingMode: null | components['schemas']['ActivitySchedulingMode'];
 			/** Format: uuid */
@@ -7373,6 +7927,24 @@ export interface components {
 			message: string | null;
 			success: boolean;
 		};
+		FeeInvoiceLine: {
+			/** Format: int64 */
+			amountSatang: number;
+			description: string;
+			/** Format: uuid */
+			feeItemId: string | null;
+			/** Format: uuid */
+			id: string;
+			lineKind: components['schemas']['FeeInvoiceLineKind'];
+		};
+		/** @enum {string} */
+		FeeInvoiceLineKind: 'charge' | 'discount';
+		/** @enum {string} */
+		FeeInvoiceStatus: 'open' | 'partially_paid' | 'paid' | 'void';
+		/** @enum {string} */
+		FeePaymentMethod: 'cash' | 'bank_transfer' | 'promptpay';
+		/** @enum {string} */
+		FeePaymentStatus: 'pending_review' | 'confirmed' | 'rejected';
 		FileDeleteResult: {
 			pendingRetry: boolean;
 		};
@@ -7419,7 +7991,13 @@ export interface components {
 			| 'generic_private_document'
 			| 'certificate_template_background'
 			| 'certificate_template_image'
-			| 'certificate_template_font';
+			| 'certificate_template_font'
+			| 'behavior_evidence'
+			| 'student_leave_document'
+			| 'staff_leave_document'
+			| 'announcement_file'
+			| 'fee_payment_slip'
+			| 'professional_development_evidence';
 		FileUploadMultipart: {
 			/** Format: binary */
 			file: string;
@@ -7736,6 +8314,23 @@ export interface components {
 			/** Format: uuid */
 			group_id: string;
 		};
+		MyActivityPreferences: {
+			/**
+			 * Format: uuid
+			 * @description The group from the published allocation, if the student was placed
+			 */
+			allocatedGroupId?: string | null;
+			allocationPublished: boolean;
+			choices: components['schemas']['ActivityPreferenceChoice'][];
+			/** Format: int32 */
+			maxPreferences: number;
+			/** @description Whether choices can be submitted or changed right now */
+			registrationOpen: boolean;
+			/** Format: uuid */
+			slotId: string;
+			/** Format: date-time */
+			submittedAt?: string | null;
+		};
 		MyTimetableData: {
 			/** Format: int64 */
 			current_seq: number;
@@ -8049,6 +8644,9 @@ export interface components {
 		};
 		/** @enum {string} */
 		RecipientType: 'student' | 'staff' | 'external';
+		RecordStudyPlanComplianceOverrideRequest: {
+			reason: string;
+		};
 		ReorderGroupsRequest: {
 			groups: components['schemas']['ReorderItem'][];
 		};
@@ -8136,6 +8734,27 @@ export interface components {
 			/** Format: date-time */
 			updated_at: string;
 		};
+		RunActivityAllocationRequest: {
+			method: components['schemas']['ActivityAllocationMethod'];
+			/**
+			 * Format: int64
+			 * @description Lottery seed; a random one is drawn when omitted. The same seed over
+			 *     the same preferences reproduces the run
+			 */
+			seed?: number | null;
+		};
+		SaveCurriculumRuleSetRequest: {
+			description?: string | null;
+			/** @description Makes this the set used by versions without a pinned rule set */
+			is_default?: boolean | null;
+			name: string;
+			/** @description Replaces every rule of the set, in display order */
+			rules: components['schemas']['CurriculumRuleInput'][];
+			/** Format: int32 */
+			terms_per_year?: number | null;
+			/** Format: int32 */
+			weeks_per_term?: number | null;
+		};
 		SchoolSettingsResponse: {
 			/** Format: uuid */
 			logoFileId: string | null;
@@ -8411,6 +9030,66 @@ export interface components {
 			/** Format: date-time */
 			updated_at: string;
 		};
+		StudyPlanComplianceCheck: {
+			/** Format: double */
+			actual: number;
+			/** @description e.g. "ม.1" or "ม.4-ม.6" */
+			grade_label: string;
+			/** Format: uuid */
+			grade_level_id?: string | null;
+			/**
+			 * @description Stable identity of the check: rule id, plus the grade level for
+			 *     per-grade rules
+			 */
+			key: string;
+			label: string;
+			/** Format: double */
+			max_value?: number | null;
+			/** Format: double */
+			min_value?: number | null;
+			passed: boolean;
+			/** Format: uuid */
+			rule_id: string;
+			rule_kind: components['schemas']['CurriculumRuleKind'];
+			unit: components['schemas']['CurriculumRuleUnit'];
+			/** @description Failed but covered by the active override */
+			waived: boolean;
+		};
+		StudyPlanComplianceOverride: {
+			/** Format: date-time */
+			created_at: string;
+			/** Format: uuid */
+			created_by?: string | null;
+			created_by_name?: string | null;
+			/** Format: uuid */
+			id: string;
+			reason: string;
+			/** Format: uuid */
+			rule_set_id?: string | null;
+			/** @description Failing checks as they stood when the override was recorded */
+			waived_checks: components['schemas']['StudyPlanComplianceCheck'][];
+		};
+		StudyPlanComplianceReport: {
+			active_override?: null | components['schemas']['StudyPlanComplianceOverride'];
+			/**
+			 * @description Activation and course generation are refused: some failing check is
+			 *     not covered by the active override
+			 */
+			blocked: boolean;
+			checks: components['schemas']['StudyPlanComplianceCheck'][];
+			/** @description Every check passed */
+			compliant: boolean;
+			/** Format: int32 */
+			failed_count: number;
+			/**
+			 * Format: uuid
+			 * @description null when the version has no pinned set and no default set exists
+			 */
+			rule_set_id?: string | null;
+			rule_set_name?: string | null;
+			/** Format: uuid */
+			study_plan_version_id: string;
+		};
 		StudyPlanSubject: {
 			/** Format: date-time */
 			created_at: string;
@@ -8439,6 +9118,11 @@ export interface components {
 			updated_at: string;
 		};
 		StudyPlanVersion: {
+			/**
+			 * Format: uuid
+			 * @description ชุดเกณฑ์ที่ใช้ตรวจหลักสูตรฉบับนี้ — null = ใช้ชุดเกณฑ์ตั้งต้นของโรงเรียน
+			 */
+			compliance_rule_set_id?: string | null;
 			/** Format: date-time */
 			created_at: string;
 			description?: string | null;
@@ -8552,9 +9236,28 @@ export interface components {
 		};
 		/** @enum {string} */
 		SubjectType: 'BASIC' | 'ADDITIONAL' | 'ACTIVITY';
+		SubmitActivityPreferencesRequest: {
+			/** @description Group IDs from first to last choice; an empty list withdraws */
+			groupIds: string[];
+		};
 		SubmitCertificateIssueRequest: {
 			candidateIds: string[];
 		};
+		/** @description Body of `POST /api/auth/support-handoff`. */
+		SupportHandoffRequest: {
+			token: string;
+		};
+		/** @description Banner shown to everyone looking at a support session. */
+		SupportSessionBanner: {
+			adminName: string;
+			/** Format: date-time */
+			expiresAt: string;
+			/** @description `read_only` or `scoped`. */
+			scope: string;
+			/** Format: uuid */
+			supportAccessId: string;
+			ticketReference: string;
+		};
 		/**
 		 * @description วิชาที่ครูสอน — ดึงจาก classroom_courses (+ classroom_course_instructors)
 		 *     Source of truth: ระบบ Course Planning ที่ assign วิชาให้ห้อง
@@ -8716,6 +9419,8 @@ export interface components {
 		 */
 		UpdateActivitySlotRequest: {
 			is_active?: boolean | null;
+			/** Format: int32 */
+			max_preferences?: number | null;
 			registration_type?: null | components['schemas']['ActivityRegistrationType'];
 			student_reg_end?: string | null;
 			student_reg_open?: boolean | null;
@@ -8950,9 +9655,15 @@ export interface components {
 			name_th?: string | null;
 		};
 		UpdateStudyPlanVersionRequest: {
+			/** Format: uuid */
+			compliance_rule_set_id?: string | null;
 			description?: string | null;
 			/** Format: uuid */
 			end_academic_year_id?: string | null;
+			/**
+			 * @description Activating a version requires it to pass its compliance checks or
+			 *     carry an active override
+			 */
 			is_active?: boolean | null;
 			/** Format: uuid */
 			start_academic_year_id?: string | null;
@@ -9827,6 +10538,15 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
+			/** @description Study-plan version fails its compliance checks without an override */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
 			/** @description Activities could not be generated */
 			500: {
 				headers: {
@@ -9948,25 +10668,25 @@ export interface operations {
 			};
 		};
 	};
-	listActivityCatalog: {
+	getActivityAllocationRun: {
 		parameters: {
-			query?: {
-				/** @description Return only the latest active version per name */
-				latest_only?: boolean;
-			};
+			query?: never;
 			header?: never;
-			path?: never;
+			path: {
+				/** @description Allocation run ID */
+				id: string;
+			};
 			cookie?: never;
 		};
 		requestBody?: never;
 		responses: {
-			/** @description Activity catalog */
+			/** @description Allocation run with placements, unmatched students and group fill */
 			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_Vec_ActivityCatalog'];
+					'application/json': components['schemas']['ApiResponse_ActivityAllocationRunDetail'];
 				};
 			};
 			/** @description Authentication required */
@@ -9978,7 +10698,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum read permission denied */
+			/** @description School-wide activity management permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -9987,7 +10707,16 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Activity catalog could not be loaded */
+			/** @description Allocation run not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Allocation run could not be loaded */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -9998,30 +10727,29 @@ export interface operations {
 			};
 		};
 	};
-	createActivityCatalog: {
+	publishActivityAllocationRun: {
 		parameters: {
 			query?: never;
 			header?: never;
-			path?: never;
-			cookie?: never;
-		};
-		requestBody: {
-			content: {
-				'application/json': components['schemas']['CreateCatalogRequest'];
+			path: {
+				/** @description Allocation run ID */
+				id: string;
 			};
+			cookie?: never;
 		};
+		requestBody?: never;
 		responses: {
-			/** @description Activity catalog version created */
-			201: {
+			/** @description Placements enrolled and student registration closed */
+			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_ActivityCatalog'];
+					'application/json': components['schemas']['ApiResponse_ActivityAllocationRunDetail'];
 				};
 			};
-			/** @description Catalog request is invalid */
-			400: {
+			/** @description Authentication required */
+			401: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -10029,8 +10757,8 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Authentication required */
-			401: {
+			/** @description School-wide activity management permission denied */
+			403: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -10038,8 +10766,8 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum create permission denied */
-			403: {
+			/** @description Allocation run not found */
+			404: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -10047,8 +10775,8 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Academic year, grade level, or instructor not found */
-			404: {
+			/** @description Run is not a draft, another run is published, or groups changed since the run */
+			409: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -10056,7 +10784,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Activity catalog could not be created */
+			/** @description Allocation could not be published */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -10067,29 +10795,25 @@ export interface operations {
 			};
 		};
 	};
-	updateActivityCatalog: {
+	listActivityCatalog: {
 		parameters: {
-			query?: never;
-			header?: never;
-			path: {
-				/** @description Activity catalog ID */
-				id: string;
+			query?: {
+				/** @description Return only the latest active version per name */
+				latest_only?: boolean;
 			};
+			header?: never;
+			path?: never;
 			cookie?: never;
 		};
-		requestBody: {
-			content: {
-				'application/json': components['schemas']['UpdateCatalogRequest'];
-			};
-		};
+		requestBody?: never;
 		responses: {
-			/** @description Activity catalog updated */
+			/** @description Activity catalog */
 			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_ActivityCatalog'];
+					'application/json': components['schemas']['ApiResponse_Vec_ActivityCatalog'];
 				};
 			};
 			/** @description Authentication required */
@@ -10101,7 +10825,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum update permission denied */
+			/** @description Curriculum read permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -10110,16 +10834,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Activity catalog or grade level not found */
-			404: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
-				};
-			};
-			/** @description Activity catalog could not be updated */
+			/** @description Activity catalog could not be loaded */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -10130,21 +10845,153 @@ export interface operations {
 			};
 		};
 	};
-	deleteActivityCatalog: {
+	createActivityCatalog: {
 		parameters: {
 			query?: never;
 			header?: never;
-			path: {
-				/** @description Activity catalog ID */
-				id: string;
-			};
+			path?: never;
 			cookie?: never;
 		};
-		requestBody?: never;
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['CreateCatalogRequest'];
+			};
+		};
 		responses: {
-			/** @description Activity catalog deleted */
-			200: {
-				headers: {
+			/** @description Activity catalog version created */
+			201: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_ActivityCatalog'];
+				};
+			};
+			/** @description Catalog request is invalid */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum create permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Academic year, grade level, or instructor not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity catalog could not be created */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	updateActivityCatalog: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity catalog ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['UpdateCatalogRequest'];
+			};
+		};
+		responses: {
+			/** @description Activity catalog updated */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_ActivityCatalog'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum update permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity catalog or grade level not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity catalog could not be updated */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	deleteActivityCatalog: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity catalog ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Activity catalog deleted */
+			200: {
+				headers: {
 					[name: string]: unknown;
 				};
 				content: {
@@ -10647,6 +11494,137 @@ export interface operations {
 			};
 		};
 	};
+	listActivityAllocationRuns: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity slot ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Allocation runs for the slot, newest first */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_Vec_ActivityAllocationRun'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description School-wide activity management permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Allocation runs could not be loaded */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	runActivityAllocation: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity slot ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['RunActivityAllocationRequest'];
+			};
+		};
+		responses: {
+			/** @description Draft allocation with placements and unmatched students */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_ActivityAllocationRunDetail'];
+				};
+			};
+			/** @description Activity slot is not in preference registration mode */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description School-wide activity management permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity slot not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description An allocation for the slot is already published */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Allocation could not be run */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
 	listActivitySlotClassroomAssignments: {
 		parameters: {
 			query?: never;
@@ -11253,7 +12231,7 @@ export interface operations {
 			};
 		};
 	};
-	deleteActivitySlotTimetableEntries: {
+	getMyActivityPreferences: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -11265,7 +12243,129 @@ export interface operations {
 		};
 		requestBody?: never;
 		responses: {
-			/** @description Slot timetable entries deleted */
+			/** @description Current student's ranked choices for the slot */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_MyActivityPreferences'];
+				};
+			};
+			/** @description Activity slot is not in preference registration mode */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity slot not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Preferences could not be loaded */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	submitMyActivityPreferences: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity slot ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['SubmitActivityPreferencesRequest'];
+			};
+		};
+		responses: {
+			/** @description Current student's ranked choices replaced */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_MyActivityPreferences'];
+				};
+			};
+			/** @description Registration is closed or a choice is invalid for the student */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Activity slot not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Preferences could not be saved */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	deleteActivitySlotTimetableEntries: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Activity slot ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Slot timetable entries deleted */
 			200: {
 				headers: {
 					[name: string]: unknown;
@@ -11511,7 +12611,263 @@ export interface operations {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Classroom update permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Classroom not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Classroom conflicts with an existing classroom */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Classroom could not be updated */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	listCurriculumRuleSets: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Curriculum rule sets with their rules */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_Vec_CurriculumRuleSet'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum read permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum rule sets could not be loaded */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	createCurriculumRuleSet: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['SaveCurriculumRuleSetRequest'];
+			};
+		};
+		responses: {
+			/** @description Curriculum rule set created */
+			201: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_CurriculumRuleSet'];
+				};
+			};
+			/** @description Invalid rule set or rule */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description School-wide curriculum update permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Referenced subject group not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum rule set could not be created */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	updateCurriculumRuleSet: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Curriculum rule set ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['SaveCurriculumRuleSetRequest'];
+			};
+		};
+		responses: {
+			/** @description Curriculum rule set and its rules replaced */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_CurriculumRuleSet'];
+				};
+			};
+			/** @description Invalid rule set or rule */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description School-wide curriculum update permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Rule set or referenced subject group not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum rule set could not be updated */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	deleteCurriculumRuleSet: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Curriculum rule set ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Curriculum rule set deleted */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_EmptyData'];
 				};
 			};
 			/** @description Authentication required */
@@ -11523,7 +12879,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Classroom update permission denied */
+			/** @description School-wide curriculum update permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -11532,7 +12888,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Classroom not found */
+			/** @description Curriculum rule set not found */
 			404: {
 				headers: {
 					[name: string]: unknown;
@@ -11541,16 +12897,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Classroom conflicts with an existing classroom */
-			409: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
-				};
-			};
-			/** @description Classroom could not be updated */
+			/** @description Curriculum rule set could not be deleted */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -12881,6 +14228,15 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
+			/** @description Study-plan version fails its compliance checks without an override */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
 			/** @description Courses could not be generated */
 			500: {
 				headers: {
@@ -13318,8 +14674,242 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan subject could not be deleted */
-			500: {
+			/** @description Study-plan subject could not be deleted */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	listStudyPlanVersions: {
+		parameters: {
+			query?: {
+				/** @description Return active versions only */
+				active_only?: boolean;
+				/** @description Filter by study plan */
+				study_plan_id?: string;
+			};
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Study-plan versions */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_Vec_StudyPlanVersion'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum read permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Study-plan versions could not be loaded */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	createStudyPlanVersion: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['CreateStudyPlanVersionRequest'];
+			};
+		};
+		responses: {
+			/** @description Study-plan version created */
+			201: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
+				};
+			};
+			/** @description Invalid study-plan version */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum create permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Referenced plan or academic year not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Study-plan version already exists */
+			409: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Study-plan version could not be created */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	getStudyPlanVersion: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Study-plan version ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Study-plan version */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Curriculum read permission denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Study-plan version not found */
+			404: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Study-plan version could not be loaded */
+			500: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
+	updateStudyPlanVersion: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Study-plan version ID */
+				id: string;
+			};
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['UpdateStudyPlanVersionRequest'];
+			};
+		};
+		responses: {
+			/** @description Study-plan version updated */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
+				};
+			};
+			/** @description Invalid study-plan version update */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -13327,33 +14917,17 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-		};
-	};
-	listStudyPlanVersions: {
-		parameters: {
-			query?: {
-				/** @description Return active versions only */
-				active_only?: boolean;
-				/** @description Filter by study plan */
-				study_plan_id?: string;
-			};
-			header?: never;
-			path?: never;
-			cookie?: never;
-		};
-		requestBody?: never;
-		responses: {
-			/** @description Study-plan versions */
-			200: {
+			/** @description Curriculum update permission denied */
+			403: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_Vec_StudyPlanVersion'];
+					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Authentication required */
-			401: {
+			/** @description Study-plan version not found */
+			404: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -13361,8 +14935,8 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum read permission denied */
-			403: {
+			/** @description Study-plan version conflicts with an existing version, or activation is blocked by failing compliance checks */
+			409: {
 				headers: {
 					[name: string]: unknown;
 				};
@@ -13370,7 +14944,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan versions could not be loaded */
+			/** @description Study-plan version could not be updated */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13381,35 +14955,25 @@ export interface operations {
 			};
 		};
 	};
-	createStudyPlanVersion: {
+	deleteStudyPlanVersion: {
 		parameters: {
 			query?: never;
 			header?: never;
-			path?: never;
-			cookie?: never;
-		};
-		requestBody: {
-			content: {
-				'application/json': components['schemas']['CreateStudyPlanVersionRequest'];
+			path: {
+				/** @description Study-plan version ID */
+				id: string;
 			};
+			cookie?: never;
 		};
+		requestBody?: never;
 		responses: {
-			/** @description Study-plan version created */
-			201: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
-				};
-			};
-			/** @description Invalid study-plan version */
-			400: {
+			/** @description Study-plan version deleted */
+			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
+					'application/json': components['schemas']['ApiResponse_EmptyData'];
 				};
 			};
 			/** @description Authentication required */
@@ -13421,7 +14985,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum create permission denied */
+			/** @description Curriculum delete permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -13430,7 +14994,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Referenced plan or academic year not found */
+			/** @description Study-plan version not found */
 			404: {
 				headers: {
 					[name: string]: unknown;
@@ -13439,16 +15003,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version already exists */
-			409: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
-				};
-			};
-			/** @description Study-plan version could not be created */
+			/** @description Study-plan version could not be deleted */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13459,7 +15014,7 @@ export interface operations {
 			};
 		};
 	};
-	getStudyPlanVersion: {
+	listStudyPlanActivities: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -13471,13 +15026,13 @@ export interface operations {
 		};
 		requestBody?: never;
 		responses: {
-			/** @description Study-plan version */
+			/** @description Activities in the study-plan version */
 			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
+					'application/json': components['schemas']['ApiResponse_Vec_StudyPlanVersionActivity'];
 				};
 			};
 			/** @description Authentication required */
@@ -13507,7 +15062,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version could not be loaded */
+			/** @description Plan activities could not be loaded */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13518,7 +15073,7 @@ export interface operations {
 			};
 		};
 	};
-	updateStudyPlanVersion: {
+	addStudyPlanActivity: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -13530,20 +15085,20 @@ export interface operations {
 		};
 		requestBody: {
 			content: {
-				'application/json': components['schemas']['UpdateStudyPlanVersionRequest'];
+				'application/json': components['schemas']['CreatePlanActivityRequest'];
 			};
 		};
 		responses: {
-			/** @description Study-plan version updated */
-			200: {
+			/** @description Activity added to the study-plan version */
+			201: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_StudyPlanVersion'];
+					'application/json': components['schemas']['ApiResponse_StudyPlanVersionActivity'];
 				};
 			};
-			/** @description Invalid study-plan version update */
+			/** @description Activity already exists in this plan scope or request is invalid */
 			400: {
 				headers: {
 					[name: string]: unknown;
@@ -13570,7 +15125,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version not found */
+			/** @description Study-plan version, catalog, or grade level not found */
 			404: {
 				headers: {
 					[name: string]: unknown;
@@ -13579,16 +15134,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version conflicts with an existing version */
-			409: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
-				};
-			};
-			/** @description Study-plan version could not be updated */
+			/** @description Plan activity could not be added */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13599,7 +15145,7 @@ export interface operations {
 			};
 		};
 	};
-	deleteStudyPlanVersion: {
+	getStudyPlanComplianceReport: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -13611,13 +15157,13 @@ export interface operations {
 		};
 		requestBody?: never;
 		responses: {
-			/** @description Study-plan version deleted */
+			/** @description Compliance report against the pinned or default rule set */
 			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_EmptyData'];
+					'application/json': components['schemas']['ApiResponse_StudyPlanComplianceReport'];
 				};
 			};
 			/** @description Authentication required */
@@ -13629,7 +15175,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum delete permission denied */
+			/** @description Curriculum read permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -13647,7 +15193,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version could not be deleted */
+			/** @description Compliance report could not be built */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13658,7 +15204,7 @@ export interface operations {
 			};
 		};
 	};
-	listStudyPlanActivities: {
+	recordStudyPlanComplianceOverride: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -13668,15 +15214,28 @@ export interface operations {
 			};
 			cookie?: never;
 		};
-		requestBody?: never;
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['RecordStudyPlanComplianceOverrideRequest'];
+			};
+		};
 		responses: {
-			/** @description Activities in the study-plan version */
-			200: {
+			/** @description Override recorded for the currently failing checks */
+			201: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiResponse_Vec_StudyPlanVersionActivity'];
+					'application/json': components['schemas']['ApiResponse_StudyPlanComplianceReport'];
+				};
+			};
+			/** @description Reason missing or nothing to override */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
 			/** @description Authentication required */
@@ -13688,7 +15247,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum read permission denied */
+			/** @description Curriculum approve permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -13706,7 +15265,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Plan activities could not be loaded */
+			/** @description Override could not be recorded */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -13717,7 +15276,7 @@ export interface operations {
 			};
 		};
 	};
-	addStudyPlanActivity: {
+	revokeStudyPlanComplianceOverride: {
 		parameters: {
 			query?: never;
 			header?: never;
@@ -13727,28 +15286,15 @@ export interface operations {
 			};
 			cookie?: never;
 		};
-		requestBody: {
-			content: {
-				'application/json': components['schemas']['CreatePlanActivityRequest'];
-			};
-		};
+		requestBody?: never;
 		responses: {
-			/** @description Activity added to the study-plan version */
-			201: {
-				headers: {
-					[name: string]: unknown;
-				};
-				content: {
-					'application/json': components['schemas']['ApiResponse_StudyPlanVersionActivity'];
-				};
-			};
-			/** @description Activity already exists in this plan scope or request is invalid */
-			400: {
+			/** @description Override revoked; the refreshed report is returned */
+			200: {
 				headers: {
 					[name: string]: unknown;
 				};
 				content: {
-					'application/json': components['schemas']['ApiErrorResponse'];
+					'application/json': components['schemas']['ApiResponse_StudyPlanComplianceReport'];
 				};
 			};
 			/** @description Authentication required */
@@ -13760,7 +15306,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Curriculum update permission denied */
+			/** @description Curriculum approve permission denied */
 			403: {
 				headers: {
 					[name: string]: unknown;
@@ -13769,7 +15315,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Study-plan version, catalog, or grade level not found */
+			/** @description Version not found or no active override */
 			404: {
 				headers: {
 					[name: string]: unknown;
@@ -13778,7 +15324,7 @@ export interface operations {
 					'application/json': components['schemas']['ApiErrorResponse'];
 				};
 			};
-			/** @description Plan activity could not be added */
+			/** @description Override could not be revoked */
 			500: {
 				headers: {
 					[name: string]: unknown;
@@ -16972,6 +18518,66 @@ export interface operations {
 			};
 		};
 	};
+	supportHandoff: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path?: never;
+			cookie?: never;
+		};
+		requestBody: {
+			content: {
+				'application/json': components['schemas']['SupportHandoffRequest'];
+			};
+		};
+		responses: {
+			/** @description Support session for the assisted user */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_LoginData'];
+				};
+			};
+			/** @description Malformed request */
+			400: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Handoff token invalid, used or expired */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Origin rejected */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Authentication service unavailable */
+			503: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
 	listCalendarCategories: {
 		parameters: {
 			query?: never;
@@ -21769,6 +23375,47 @@ export interface operations {
 			};
 		};
 	};
+	getParentChildFees: {
+		parameters: {
+			query?: never;
+			header?: never;
+			path: {
+				/** @description Linked student user ID */
+				student_id: string;
+			};
+			cookie?: never;
+		};
+		requestBody?: never;
+		responses: {
+			/** @description Linked child's fee invoices and outstanding balance */
+			200: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiResponse_ChildFeeStatement'];
+				};
+			};
+			/** @description Authentication required */
+			401: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+			/** @description Parent-child access denied */
+			403: {
+				headers: {
+					[name: string]: unknown;
+				};
+				content: {
+					'application/json': components['schemas']['ApiErrorResponse'];
+				};
+			};
+		};
+	};
 	getParentChildTimetable: {
 		parameters: {
 			query?: {
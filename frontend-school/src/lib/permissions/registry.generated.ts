@@ -1,5 +1,5 @@
 // @generated by scripts/generate-permissions.mjs; DO NOT EDIT.
-// contract-sha256: 8a61f57e23c9ffbbbfb7cbb543e55990a86bee536c8f2ccef9a1df7e5bf9b9a8
+// contract-sha256: 503ea1a5f4f7ff78706fcbb2711c106bb971afad625762360f4b36a428c20d06
 
 export const WILDCARD_PERMISSION = '*' as const;
 
@@ -17,19 +17,26 @@ export const PERMISSION_MODULES = {
 	ACHIEVEMENT: 'achievement',
 	ACTIVITY: 'activity',
 	ADMISSION: 'admission',
+	ANNOUNCEMENT: 'announcement',
+	BEHAVIOR: 'behavior',
+	BEHAVIOR_CONFIDENTIAL: 'behavior_confidential',
 	CALENDAR: 'calendar',
 	CERTIFICATE: 'certificate',
 	DASHBOARD: 'dashboard',
 	FACILITY: 'facility',
 	FEATURES: 'features',
+	FINANCE: 'finance',
 	MENU: 'menu',
 	ORGANIZATION_WORK: 'organization_work',
+	PROFESSIONAL_DEVELOPMENT: 'professional_development',
 	ROLES: 'roles',
 	SETTINGS: 'settings',
 	STAFF: 'staff',
+	STAFF_LEAVE: 'staff_leave',
 	STAFF_PII: 'staff_pii',
 	STAFF_PROFILE: 'staff_profile',
 	STUDENT: 'student',
+	STUDENT_LEAVE: 'student_leave',
 	STUDENT_PII: 'student_pii',
 	SUPERVISION: 'supervision',
 	SYSTEM: 'system'
@@ -47,6 +54,7 @@ export const PERMISSIONS = {
 	ACADEMIC_CLASSROOM_UPDATE_ALL: 'academic_classroom.update.all',
 	ACADEMIC_COURSE_PLAN_MANAGE_ALL: 'academic_course_plan.manage.all',
 	ACADEMIC_COURSE_PLAN_READ_ALL: 'academic_course_plan.read.all',
+	ACADEMIC_CURRICULUM_APPROVE_ALL: 'academic_curriculum.approve.all',
 	ACADEMIC_CURRICULUM_CREATE_ALL: 'academic_curriculum.create.all',
 	ACADEMIC_CURRICULUM_DELETE_ALL: 'academic_curriculum.delete.all',
 	ACADEMIC_CURRICULUM_MANAGE_ORGANIZATION_TREE: 'academic_curriculum.manage.organization_tree',
@@ -88,6 +96,15 @@ export const PERMISSIONS = {
 	ADMISSION_READ_ALL: 'admission.read.all',
 	ADMISSION_SCORES_ALL: 'admission.scores.all',
 	ADMISSION_VERIFY_ALL: 'admission.verify.all',
+	ANNOUNCEMENT_MANAGE_SCHOOL: 'announcement.manage.school',
+	ANNOUNCEMENT_READ_SCHOOL: 'announcement.read.school',
+	BEHAVIOR_CONFIDENTIAL_READ_SCHOOL: 'behavior_confidential.read.school',
+	BEHAVIOR_CREATE_SCHOOL: 'behavior.create.school',
+	BEHAVIOR_MANAGE_ASSIGNED: 'behavior.manage.assigned',
+	BEHAVIOR_MANAGE_SCHOOL: 'behavior.manage.school',
+	BEHAVIOR_READ_ASSIGNED: 'behavior.read.assigned',
+	BEHAVIOR_READ_OWN: 'behavior.read.own',
+	BEHAVIOR_READ_SCHOOL: 'behavior.read.school',
 	CALENDAR_MANAGE_SCHOOL: 'calendar.manage.school',
 	CALENDAR_READ_SCHOOL: 'calendar.read.school',
 	CERTIFICATE_CREATE_ORGANIZATION_UNIT: 'certificate.create.organization_unit',
@@ -112,6 +129,9 @@ export const PERMISSIONS = {
 	FACILITY_UPDATE_ALL: 'facility.update.all',
 	FEATURES_READ_ALL: 'features.read.all',
 	FEATURES_UPDATE_ALL: 'features.update.all',
+	FINANCE_ISSUE_SCHOOL: 'finance.issue.school',
+	FINANCE_MANAGE_SCHOOL: 'finance.manage.school',
+	FINANCE_READ_SCHOOL: 'finance.read.school',
 	MENU_CREATE_ALL: 'menu.create.all',
 	MENU_DELETE_ALL: 'menu.delete.all',
 	MENU_READ_ALL: 'menu.read.all',
@@ -121,6 +141,10 @@ export const PERMISSIONS = {
 	ORGANIZATION_WORK_READ_ORGANIZATION_UNIT: 'organization_work.read.organization_unit',
 	ORGANIZATION_WORK_READ_OWN: 'organization_work.read.own',
 	ORGANIZATION_WORK_UPDATE_OWN: 'organization_work.update.own',
+	PROFESSIONAL_DEVELOPMENT_MANAGE_SCHOOL: 'professional_development.manage.school',
+	PROFESSIONAL_DEVELOPMENT_READ_OWN: 'professional_development.read.own',
+	PROFESSIONAL_DEVELOPMENT_READ_SCHOOL: 'professional_development.read.school',
+	PROFESSIONAL_DEVELOPMENT_SUBMIT_OWN: 'professional_development.submit.own',
 	ROLES_ASSIGN_ALL: 'roles.assign.all',
 	ROLES_CREATE_ALL: 'roles.create.all',
 	ROLES_DELETE_ALL: 'roles.delete.all',
@@ -131,6 +155,11 @@ export const PERMISSIONS = {
 	SETTINGS_UPDATE_ALL: 'settings.update.all',
 	STAFF_CREATE_ALL: 'staff.create.all',
 	STAFF_DELETE_ALL: 'staff.delete.all',
+	STAFF_LEAVE_APPROVE_SCHOOL: 'staff_leave.approve.school',
+	STAFF_LEAVE_MANAGE_SCHOOL: 'staff_leave.manage.school',
+	STAFF_LEAVE_READ_OWN: 'staff_leave.read.own',
+	STAFF_LEAVE_READ_SCHOOL: 'staff_leave.read.school',
+	STAFF_LEAVE_REQUEST_OWN: 'staff_leave.request.own',
 	STAFF_PII_READ_OWN: 'staff_pii.read.own',
 	STAFF_PII_READ_SCHOOL: 'staff_pii.read.school',
 	STAFF_PROFILE_READ_ORGANIZATION_TREE: 'staff_profile.read.organization_tree',
@@ -141,6 +170,10 @@ export const PERMISSIONS = {
 	STAFF_UPDATE_ALL: 'staff.update.all',
 	STUDENT_CREATE_ALL: 'student.create.all',
 	STUDENT_DELETE_ALL: 'student.delete.all',
+	STUDENT_LEAVE_APPROVE_ASSIGNED: 'student_leave.approve.assigned',
+	STUDENT_LEAVE_APPROVE_SCHOOL: 'student_leave.approve.school',
+	STUDENT_LEAVE_READ_ASSIGNED: 'student_leave.read.assigned',
+	STUDENT_LEAVE_READ_SCHOOL: 'student_leave.read.school',
 	STUDENT_PII_READ_ASSIGNED: 'student_pii.read.assigned',
 	STUDENT_PII_READ_OWN: 'student_pii.read.own',
 	STUDENT_PII_READ_SCHOOL: 'student_pii.read.school',